@@ -1,20 +1,27 @@
-use std::{str::FromStr, sync::Arc};
+use std::{
+    fs::OpenOptions,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
 
-use browser_args::{BrowserArgs, Parser};
+use browser_args::{BrowserArgs, LogFormat, Parser};
 use browser_core::Browser;
 use browser_headless::HeadlessEngine;
 use browser_ui::Ui;
-use tracing::{error, info};
+use tracing::{Subscriber, error, info};
 use tracing_subscriber::{
-    EnvFilter,
+    EnvFilter, Layer,
     filter::Directive,
-    fmt::{self},
+    fmt::{self, MakeWriter},
     layer::SubscriberExt,
+    registry::LookupSpan,
     util::SubscriberInitExt,
 };
 
 /// The main entry point for the application
 fn main() {
+    let args = BrowserArgs::parse();
+
     let filter = EnvFilter::new("warn")
         .add_directive(Directive::from_str("browser=debug").unwrap())
         .add_directive(Directive::from_str("layout=debug").unwrap())
@@ -25,12 +32,24 @@ fn main() {
         .add_directive(Directive::from_str("http_fetch=debug").unwrap())
         .add_directive(Directive::from_str("http_cache=debug").unwrap());
 
+    let mut layers = vec![log_layer(args.logging.format, std::io::stderr, true)];
+    let mut log_file_error = None;
+    if let Some(path) = &args.logging.file {
+        match OpenOptions::new().create(true).append(true).open(path) {
+            Ok(file) => layers.push(log_layer(args.logging.format, Mutex::new(file), false)),
+            Err(error) => log_file_error = Some((path, error)),
+        }
+    }
+
     tracing_subscriber::registry()
         .with(filter)
-        .with(fmt::layer().with_file(false).with_line_number(false))
+        .with(layers)
         .init();
 
-    let args = BrowserArgs::parse();
+    if let Some((path, error)) = log_file_error {
+        error!(%error, "Failed to open the log file {}", path.display());
+    }
+
     let browser = Browser::new(&args);
 
     if args.headless.enabled {
@@ -41,15 +60,42 @@ fn main() {
             .build()
             .expect("Failed to create Tokio runtime");
 
-        return runtime.block_on(engine.run(args));
+        runtime.block_on(engine.run(args));
+        engine.browser.shutdown();
+        return;
     }
 
     let browser = Arc::new(browser);
-    let ui = Ui::run(browser, args);
+    let ui = Ui::run(Arc::clone(&browser), args);
 
     if let Err(error) = ui {
         error!(%error, "Application exited unsuccessfully.");
     } else {
+        browser.shutdown();
         info!("Application exited successfully.");
     }
 }
+
+/// Creates the layer writing log events to a writer in the given format. JSON events have their fields at the top
+/// level of the object next to `timestamp`, `level` and `target`, along with the span they were recorded in.
+fn log_layer<S, W>(format: LogFormat, writer: W, ansi: bool) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Text => fmt::layer()
+            .with_file(false)
+            .with_line_number(false)
+            .with_ansi(ansi)
+            .with_writer(writer)
+            .boxed(),
+        LogFormat::Json => fmt::layer()
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_span_list(false)
+            .with_writer(writer)
+            .boxed(),
+    }
+}
@@ -1,9 +1,12 @@
+use std::path::PathBuf;
+
 use clap::{ArgAction, Parser};
 use manifest::{APP_NAME, APP_VERSION};
 
-use crate::args::{headless::HeadlessArgs, preferences::PreferencesArgs};
+use crate::args::{headless::HeadlessArgs, logging::LoggingArgs, preferences::PreferencesArgs};
 
 pub mod headless;
+pub mod logging;
 pub mod preferences;
 
 #[derive(Parser, Debug, Clone)]
@@ -41,6 +44,29 @@ pub struct BrowserArgs {
     )]
     pub user_agent: Option<String>,
 
+    #[arg(
+        long = "dns-over-https",
+        value_name = "URL",
+        long_help = "Resolve hostnames with DNS-over-HTTPS using the given endpoint, e.g. https://1.1.1.1/dns-query, instead of the resolver of the operating system. This prevents hostname lookups from being visible to the local network."
+    )]
+    pub dns_over_https: Option<String>,
+
+    #[arg(
+        long = "data-saver",
+        long_help = "Reduce the data used to load pages, for metered or slow connections. Pages are told through the prefers-reduced-data media feature and the Save-Data request header, and lazily loaded images and prefetch or preload hints are skipped."
+    )]
+    pub data_saver: bool,
+
+    #[arg(
+        long = "load-extension",
+        value_name = "DIR",
+        long_help = "Load the unpacked extension in the given directory, which holds its manifest.json. Can be repeated to load several extensions."
+    )]
+    pub load_extensions: Vec<PathBuf>,
+
     #[command(flatten)]
     pub headless: HeadlessArgs,
+
+    #[command(flatten)]
+    pub logging: LoggingArgs,
 }
@@ -35,4 +35,29 @@ pub struct HeadlessArgs {
         conflicts_with = "input"
     )]
     pub commands: Vec<String>,
+
+    #[arg(
+        long,
+        help_heading = "Headless Mode",
+        help = "Print the metadata of every page navigated to as JSON.",
+        requires = "headless"
+    )]
+    pub extract_metadata: bool,
+
+    #[arg(
+        long,
+        help_heading = "Headless Mode",
+        help = "Print the DOM of every page navigated to as an indented tree.",
+        requires = "headless",
+        conflicts_with = "dump_dom_json"
+    )]
+    pub dump_dom: bool,
+
+    #[arg(
+        long,
+        help_heading = "Headless Mode",
+        help = "Print the DOM of every page navigated to as JSON.",
+        requires = "headless"
+    )]
+    pub dump_dom_json: bool,
 }
@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+
+/// The format log events are written in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines.
+    #[default]
+    Text,
+
+    /// One JSON object per line, with the fields of each event as keys.
+    Json,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct LoggingArgs {
+    #[arg(
+        long = "log-format",
+        value_enum,
+        default_value_t = LogFormat::Text,
+        help_heading = "Logging",
+        help = "The format logs are written in. With json, every event is written as a JSON object on its own line, so log streams can be processed with tools like jq."
+    )]
+    pub format: LogFormat,
+
+    #[arg(
+        long = "log-file",
+        value_name = "PATH",
+        help_heading = "Logging",
+        help = "Also write logs to a file, appending to it if it exists."
+    )]
+    pub file: Option<PathBuf>,
+}
@@ -18,4 +18,28 @@ pub struct PreferencesArgs {
         conflicts_with = "headless"
     )]
     pub force_dark: bool,
+
+    #[arg(
+        long = "emulate-mobile",
+        help_heading = "Preferences",
+        help = "Lay pages out at the width requested by their <meta name=\"viewport\"> tag, as on a mobile device.",
+        conflicts_with = "headless"
+    )]
+    pub emulate_mobile: bool,
+
+    #[arg(
+        long = "high-contrast",
+        help_heading = "Preferences",
+        help = "Tell websites that more contrast is preferred, matching their `prefers-contrast: more` styles.",
+        conflicts_with = "headless"
+    )]
+    pub high_contrast: bool,
+
+    #[arg(
+        long = "forced-colors",
+        help_heading = "Preferences",
+        help = "Replace the colors of websites with the system colors, matching their `forced-colors: active` styles.",
+        conflicts_with = "headless"
+    )]
+    pub forced_colors: bool,
 }
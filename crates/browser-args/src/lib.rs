@@ -1,4 +1,4 @@
 mod args;
 
-pub use args::BrowserArgs;
+pub use args::{BrowserArgs, logging::LogFormat};
 pub use clap::Parser;
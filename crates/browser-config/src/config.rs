@@ -1,22 +1,63 @@
 use browser_args::BrowserArgs;
-use http::HeaderMap;
+use http::{HeaderMap, HeaderName, HeaderValue};
+use tracing::warn;
+use url::Url;
 
 use crate::header::Headers;
 
 #[derive(Debug, Clone)]
 pub struct BrowserConfig {
     headers: HeaderMap,
+    dns_over_https: Option<Url>,
+    data_saver: bool,
 }
 
 impl BrowserConfig {
     pub fn new(args: &BrowserArgs) -> Self {
-        let headers = Headers::create_browser_headers(args.ua_compatibility, args.user_agent.clone());
+        let mut headers = Headers::create_browser_headers(args.ua_compatibility, args.user_agent.clone());
 
-        Self { headers }
+        if args.data_saver {
+            headers.insert(HeaderName::from_static("save-data"), HeaderValue::from_static("on"));
+        }
+
+        let dns_over_https = args
+            .dns_over_https
+            .as_deref()
+            .and_then(|endpoint| match Url::parse(endpoint) {
+                Ok(url) if url.scheme() == "https" => Some(url),
+                Ok(_) => {
+                    warn!("Ignoring DNS-over-HTTPS endpoint {}, it must use https", endpoint);
+                    None
+                }
+                Err(error) => {
+                    warn!(%error, "Ignoring invalid DNS-over-HTTPS endpoint {}", endpoint);
+                    None
+                }
+            });
+
+        Self {
+            headers,
+            dns_over_https,
+            data_saver: args.data_saver,
+        }
     }
 
     #[must_use]
     pub const fn headers(&self) -> &HeaderMap {
         &self.headers
     }
+
+    /// The DNS-over-HTTPS endpoint used to resolve hostnames, or `None` to use the resolver of the operating system.
+    #[must_use]
+    pub const fn dns_over_https(&self) -> Option<&Url> {
+        self.dns_over_https.as_ref()
+    }
+
+    /// Whether the user asked to reduce the data used to load pages, matched by the `prefers-reduced-data` media
+    /// feature. While set, requests carry `Save-Data: on`, lazily loaded images are not loaded, and prefetch and
+    /// preload hints are ignored.
+    #[must_use]
+    pub const fn data_saver(&self) -> bool {
+        self.data_saver
+    }
 }
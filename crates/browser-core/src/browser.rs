@@ -1,28 +1,111 @@
-use std::{collections::HashMap, vec};
+use std::{collections::HashMap, sync::Arc, time::Instant, vec};
 
-use crate::{Document, commands::parse_devtools_html, errors::CoreError, profile::Profile};
+use crate::{
+    Document, MemoryUsage,
+    commands::{FetchedImage, parse_devtools_html},
+    documents::DocumentStore,
+    download::DownloadManager,
+    errors::{CoreError, StorageError},
+    extension::ExtensionRegistry,
+    preconnect::PreconnectManager,
+    profile::Profile,
+    scheduler::ResourceScheduler,
+    session::{SessionEntry, SessionGuard},
+    spellcheck::{BackgroundSpellChecker, SpellChecker},
+};
 use async_trait::async_trait;
 use browser_args::BrowserArgs;
 use css_cssom::{CSSStyleSheet, StylesheetOrigin};
-use http_fetch::{client::HttpClient, clients::ReqwestClient};
+use http_fetch::{
+    client::HttpClient, clients::ReqwestClient, connections::ConnectionTracker, dns::DohResolver,
+    inflight::InFlightRequests, network_log::NetworkLog,
+};
 use io::{
     Readable, Writable,
     embedded::{DEFAULT_CSS, DEVTOOLS_CSS},
-    entries::PROFILE_CACHE_USER_AGENT,
+    entries::{GLOBAL_SPELLING_DICTIONARY, PROFILE_CACHE_USER_AGENT},
 };
+use manifest::APP_NAME;
 use postcard::{from_bytes, to_stdvec};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use tracing::{Instrument, instrument, trace, warn};
 
 use crate::{
-    events::{Commandable, EngineCommand, EngineResponse},
-    navigation::ScriptExecutor,
+    events::{Commandable, EngineCommand, EngineResponse, NavigationType},
+    script::{JavaScriptBridge, NullJavaScriptBridge},
 };
 
+/// Parses the embedded user agent stylesheet, the default styles of the HTML elements, which is cascaded below every
+/// author stylesheet.
+///
+/// # Panics
+/// If the embedded stylesheet is not valid UTF-8, which should never happen since it's embedded in the binary.
+#[must_use]
+pub fn load_ua_stylesheet() -> CSSStyleSheet {
+    let css_resource = DEFAULT_CSS.load();
+
+    CSSStyleSheet::from_css(
+        str::from_utf8(&css_resource).expect("Embedded default CSS should be valid UTF-8"),
+        StylesheetOrigin::UserAgent,
+        false,
+    )
+}
+
+/// The user agent stylesheet cached in the profile, keyed by the hash of the embedded CSS it was parsed from so that
+/// a cache written by another build is parsed again.
+#[derive(Serialize, Deserialize)]
+struct CachedUaStylesheet {
+    hash: [u8; 32],
+    stylesheet: CSSStyleSheet,
+}
+
+/// Hashes the embedded user agent stylesheet, the key of its cached parse.
+fn ua_stylesheet_hash() -> [u8; 32] {
+    Sha256::digest(&DEFAULT_CSS.load()).into()
+}
+
 #[derive(Debug)]
 pub struct Browser {
     profile: Profile,
     default_stylesheet: Option<CSSStyleSheet>,
     http_client: Box<dyn HttpClient>,
+
+    /// The requests sent by the HTTP client, shown in the network panel of the devtools.
+    network_log: NetworkLog,
+
+    /// The connections held open by the HTTP client, reported in the memory report.
+    connections: ConnectionTracker,
+
+    /// The responses being saved to disk instead of being shown.
+    pub(crate) downloads: DownloadManager,
+
+    /// Image loads in flight, shared by every caller loading the same image.
+    pub(crate) image_requests: InFlightRequests<Result<FetchedImage, CoreError>>,
+
+    /// Stylesheet loads in flight, shared by every page referencing the same stylesheet.
+    pub(crate) style_requests: Arc<InFlightRequests<Option<CSSStyleSheet>>>,
+
+    /// Orders the sub-resource loads of every page by priority.
+    pub(crate) resource_scheduler: ResourceScheduler,
+
+    /// Opens connections to the origins of preconnect hints ahead of their requests.
+    pub(crate) preconnects: PreconnectManager,
+
+    /// Evaluates the inline scripts of the pages, ignoring them unless a JavaScript runtime is plugged in.
+    pub(crate) script_bridge: Box<dyn JavaScriptBridge>,
+
+    /// The DOM of the loaded documents, which the event listeners of the pages run against.
+    pub(crate) documents: DocumentStore,
+
+    /// Finds the misspelled words of the text entered into editable controls.
+    spell_checker: Box<dyn SpellChecker>,
+
+    /// Marks the session as running until [`Browser::shutdown`], and saves its tabs for crash recovery.
+    session: SessionGuard,
+
+    /// The extensions loaded in this session.
+    extensions: ExtensionRegistry,
 }
 
 impl Browser {
@@ -30,62 +113,113 @@ impl Browser {
     const MAX_USER_AGENT_CSS_SIZE: Option<u64> = Some(50 * 1024);
 
     /// Creates a new instance of the `Browser` struct, initializing the HTTP client, cookie jar, and user agent stylesheet.
-    ///
-    /// # Panics
-    /// * This function will panic if the embedded user agent CSS is not valid UTF-8, which should never happen since it's embedded in the binary.
     pub fn new(args: &BrowserArgs) -> Self {
         let profile = Profile::new(args);
-        let http_client = Box::new(ReqwestClient::new());
-        let user_agent_css = DEFAULT_CSS.load();
+        let session = SessionGuard::acquire(profile.dirs().into());
+        let mut http_client = match profile.config().dns_over_https() {
+            Some(endpoint) => {
+                trace!("Resolving hostnames over DNS-over-HTTPS using {}", endpoint);
+                ReqwestClient::with_dns_resolver(DohResolver::new(endpoint.clone()))
+            }
+            None => ReqwestClient::new(),
+        };
+
+        // Headless mode is used to automate visits to many pages, so it respects the crawl delays of the sites.
+        if args.headless.enabled {
+            http_client = http_client.with_crawl_delays(APP_NAME);
+        }
+        let network_log = http_client.network_log();
+        let connections = http_client.connections();
 
         let stylesheet = if args.enable_ua_css {
-            match PROFILE_CACHE_USER_AGENT.read(&profile.dirs().into(), Self::MAX_USER_AGENT_CSS_SIZE) {
-                Ok(data) => {
-                    trace!("Loaded user agent stylesheet from cache");
-                    let data: &[u8] = &data;
-
-                    let out: CSSStyleSheet = from_bytes(data).unwrap_or_else(|_| {
-                        CSSStyleSheet::from_css(
-                            std::str::from_utf8(&user_agent_css).unwrap_or_default(),
-                            StylesheetOrigin::UserAgent,
-                            false,
-                        )
-                    });
-
-                    Some(out)
-                }
+            let hash = ua_stylesheet_hash();
+
+            let cached = match PROFILE_CACHE_USER_AGENT.read(&profile.dirs().into(), Self::MAX_USER_AGENT_CSS_SIZE) {
+                Ok(data) => match from_bytes::<CachedUaStylesheet>(&data) {
+                    Ok(cached) if cached.hash == hash => {
+                        trace!("Loaded user agent stylesheet from cache");
+                        Some(cached.stylesheet)
+                    }
+                    Ok(_) => {
+                        trace!("Cached user agent stylesheet is stale, parsing embedded CSS");
+                        None
+                    }
+                    Err(err) => {
+                        trace!("Failed to decode cached user agent stylesheet: {}, parsing embedded CSS", err);
+                        None
+                    }
+                },
                 Err(err) => {
                     trace!("Failed to load user agent stylesheet from cache: {}, parsing embedded CSS", err);
+                    None
+                }
+            };
 
-                    let parsed = CSSStyleSheet::from_css(
-                        std::str::from_utf8(&user_agent_css).unwrap_or_default(),
-                        StylesheetOrigin::UserAgent,
-                        false,
-                    );
-
-                    let serialized = to_stdvec(&parsed).unwrap();
+            Some(cached.unwrap_or_else(|| {
+                let cached = CachedUaStylesheet {
+                    hash,
+                    stylesheet: load_ua_stylesheet(),
+                };
 
-                    if PROFILE_CACHE_USER_AGENT
-                        .write(serialized.as_slice(), &profile.dirs().into())
-                        .is_err()
-                    {
-                        warn!("Failed to write user agent stylesheet to cache");
+                match to_stdvec(&cached) {
+                    Ok(serialized) => {
+                        if PROFILE_CACHE_USER_AGENT
+                            .write(serialized.as_slice(), &profile.dirs().into())
+                            .is_err()
+                        {
+                            warn!("Failed to write user agent stylesheet to cache");
+                        }
                     }
-
-                    Some(parsed)
+                    Err(err) => warn!("Failed to serialize user agent stylesheet: {}", err),
                 }
-            }
+
+                cached.stylesheet
+            }))
         } else {
             None
         };
 
+        let dictionary = GLOBAL_SPELLING_DICTIONARY.path(&profile.dirs().into());
+        let spell_checker = BackgroundSpellChecker::load(dictionary);
+
         Self {
             profile,
             default_stylesheet: stylesheet,
-            http_client,
+            http_client: Box::new(http_client),
+            network_log,
+            connections,
+            downloads: DownloadManager::new(),
+            image_requests: InFlightRequests::new(),
+            style_requests: Arc::new(InFlightRequests::new()),
+            resource_scheduler: ResourceScheduler::default(),
+            preconnects: PreconnectManager::default(),
+            script_bridge: Box::new(NullJavaScriptBridge),
+            documents: DocumentStore::default(),
+            spell_checker: Box::new(spell_checker),
+            session,
+            extensions: ExtensionRegistry::new(),
         }
     }
 
+    /// Replaces the bridge used to evaluate the scripts of the pages, e.g. with one backed by a JavaScript runtime.
+    #[must_use]
+    pub fn with_script_bridge(mut self, bridge: impl JavaScriptBridge + 'static) -> Self {
+        self.script_bridge = Box::new(bridge);
+        self
+    }
+
+    /// Replaces the checker used to find misspelled words in editable controls, e.g. with one backed by Hunspell.
+    #[must_use]
+    pub fn with_spell_checker(mut self, checker: impl SpellChecker + 'static) -> Self {
+        self.spell_checker = Box::new(checker);
+        self
+    }
+
+    /// The checker finding the misspelled words of the text entered into editable controls.
+    pub fn spell_checker(&self) -> &dyn SpellChecker {
+        &*self.spell_checker
+    }
+
     pub fn profile(&self) -> &Profile {
         &self.profile
     }
@@ -93,11 +227,27 @@ impl Browser {
     pub const fn http_client(&self) -> &dyn HttpClient {
         &*self.http_client
     }
-}
 
-impl ScriptExecutor for Browser {
-    fn execute_script(&self, _script: &str) {
-        //debug!("Executing script: {}", script);
+    /// The extensions loaded in this session.
+    pub const fn extensions(&self) -> &ExtensionRegistry {
+        &self.extensions
+    }
+
+    /// The tabs of the session that was open when the browser last crashed, offered for recovery until they are
+    /// restored with a `RestoreCrashSession` command. `None` if the previous run exited cleanly.
+    #[must_use]
+    pub fn crashed_session(&self) -> Option<Vec<SessionEntry>> {
+        self.session.crashed_session()
+    }
+
+    /// Marks the session as exited cleanly, to be called once the frontend stops, so that its tabs are not offered
+    /// for recovery by the next run. The persistent cookies are flushed to the profile so that they survive the
+    /// restart.
+    pub fn shutdown(&self) {
+        let saved = self.profile.cookie_jar().save();
+        trace!(saved, "Saved cookies");
+
+        self.session.release();
     }
 }
 
@@ -117,22 +267,19 @@ impl Commandable for Browser {
                     .as_ref()
                     .map_or_else(Vec::new, |default| vec![default.clone()]);
 
-                let (page, metadata) = self.navigate(&url, stylesheets).instrument(span).await?;
+                let user_activation = matches!(navigation_type, NavigationType::Normal);
+                let navigation = self
+                    .navigate(&url, None, user_activation, stylesheets)
+                    .instrument(span)
+                    .await?;
 
-                Ok(EngineResponse::NavigateSuccess(page, metadata, navigation_type))
+                Ok(navigation.into_response(navigation_type))
             }
             EngineCommand::GetDevtoolsPage { title, document } => {
                 let span = tracing::debug_span!("Browser::GetDevtoolsPage");
                 let _enter = span.enter();
 
-                let default_css = {
-                    let css_resource = DEFAULT_CSS.load();
-                    CSSStyleSheet::from_css(
-                        str::from_utf8(&css_resource).expect("Embedded default CSS should be valid UTF-8"),
-                        StylesheetOrigin::UserAgent,
-                        false,
-                    )
-                };
+                let default_css = load_ua_stylesheet();
                 let devtools_css = {
                     let css_resource = DEVTOOLS_CSS.load();
                     CSSStyleSheet::from_css(
@@ -143,8 +290,10 @@ impl Commandable for Browser {
                 };
 
                 let stylesheets = vec![default_css, devtools_css];
-                let dom =
-                    parse_devtools_html(&title, &document).map_err(|e| CoreError::DevtoolsGeneration(e.to_string()))?;
+                let cookies = self.profile().cookie_jar().all_cookies();
+                let requests = self.network_log.entries();
+                let dom = parse_devtools_html(&title, &document, &cookies, &requests)
+                    .map_err(|e| CoreError::DevtoolsGeneration(e.to_string()))?;
 
                 let devtools_page = Document::new(dom, HashMap::new(), stylesheets);
 
@@ -161,6 +310,220 @@ impl Commandable for Browser {
                     .instrument(span)
                     .await
             }
+            EngineCommand::FetchFavicon { page_url, link } => {
+                let span = tracing::debug_span!("Browser::FetchFavicon");
+
+                self.load_favicon(page_url, link).instrument(span).await
+            }
+            EngineCommand::FetchFont {
+                family,
+                request_url,
+                font_urls,
+            } => {
+                let span = tracing::debug_span!("Browser::FetchFont");
+
+                self.load_font(family, &request_url, &font_urls)
+                    .instrument(span)
+                    .await
+            }
+            EngineCommand::SubmitForm {
+                form_id,
+                document,
+                page_url,
+            } => {
+                let span = tracing::debug_span!("Browser::SubmitForm");
+
+                let stylesheets = self
+                    .default_stylesheet
+                    .as_ref()
+                    .map_or_else(Vec::new, |default| vec![default.clone()]);
+
+                self.submit_form(form_id, &document, &page_url, stylesheets)
+                    .instrument(span)
+                    .await
+            }
+            EngineCommand::OpenLink {
+                url,
+                page_url,
+                target,
+                noopener,
+                noreferrer,
+            } => {
+                let span = tracing::debug_span!("Browser::OpenLink", noopener, noreferrer);
+
+                let stylesheets = self
+                    .default_stylesheet
+                    .as_ref()
+                    .map_or_else(Vec::new, |default| vec![default.clone()]);
+
+                self.open_link(&url, &page_url, &target, noreferrer, stylesheets)
+                    .instrument(span)
+                    .await
+            }
+            EngineCommand::ExecuteCanvasCommands {
+                node_id,
+                width,
+                height,
+                commands,
+            } => {
+                let span = tracing::debug_span!("Browser::ExecuteCanvasCommands");
+                let _enter = span.enter();
+
+                Self::execute_canvas_commands(node_id, width, height, &commands)
+            }
+            EngineCommand::UpdateInputValue {
+                document_id,
+                node_id,
+                value,
+            } => {
+                let span = tracing::debug_span!("Browser::UpdateInputValue");
+                let _enter = span.enter();
+
+                self.documents.execute(document_id, |document| {
+                    Self::update_input_value(node_id, Self::control(document, node_id)?, &value)
+                })
+            }
+            EngineCommand::ResizeElement {
+                document_id,
+                node_id,
+                new_width,
+                new_height,
+            } => {
+                let span = tracing::debug_span!("Browser::ResizeElement");
+                let _enter = span.enter();
+
+                self.documents.execute(document_id, |document| {
+                    Ok(Self::resize_element(node_id, Self::control(document, node_id)?, new_width, new_height))
+                })
+            }
+            EngineCommand::DispatchEvent {
+                document_id,
+                node_id,
+                event_type,
+            } => {
+                let span = tracing::debug_span!("Browser::DispatchEvent");
+                let _enter = span.enter();
+
+                self.dispatch_event(document_id, node_id, event_type)
+            }
+            EngineCommand::ToggleCheckbox {
+                document_id,
+                node_id,
+            } => {
+                let span = tracing::debug_span!("Browser::ToggleCheckbox");
+                let _enter = span.enter();
+
+                self.documents
+                    .execute(document_id, |document| Self::toggle_checkbox(node_id, Self::control(document, node_id)?))
+            }
+            EngineCommand::SelectRadio {
+                document_id,
+                group_name,
+                node_id,
+            } => {
+                let span = tracing::debug_span!("Browser::SelectRadio");
+                let _enter = span.enter();
+
+                self.documents
+                    .execute(document_id, |document| Self::select_radio(&group_name, node_id, document))
+            }
+            EngineCommand::ToggleDetails {
+                document_id,
+                node_id,
+            } => {
+                let span = tracing::debug_span!("Browser::ToggleDetails");
+                let _enter = span.enter();
+
+                self.documents
+                    .execute(document_id, |document| Self::toggle_details(node_id, document))
+            }
+            EngineCommand::SelectOption {
+                document_id,
+                select_node_id,
+                option_index,
+            } => {
+                let span = tracing::debug_span!("Browser::SelectOption");
+                let _enter = span.enter();
+
+                self.documents
+                    .execute(document_id, |document| Self::select_option(select_node_id, option_index, document))
+            }
+            EngineCommand::WriteClipboard { mime_type, data } => {
+                let span = tracing::debug_span!("Browser::WriteClipboard");
+                let _enter = span.enter();
+
+                Self::write_clipboard(mime_type, data)
+            }
+            EngineCommand::ReadClipboard {
+                mime_type,
+                user_gesture,
+            } => {
+                let span = tracing::debug_span!("Browser::ReadClipboard");
+                let _enter = span.enter();
+
+                Self::read_clipboard(mime_type, user_gesture, Instant::now())
+            }
+            EngineCommand::ListCookies { filter } => {
+                let span = tracing::debug_span!("Browser::ListCookies");
+                let _enter = span.enter();
+
+                let cookies = self.profile().cookie_jar().cookies_matching(&filter);
+
+                Ok(EngineResponse::CookieList(cookies))
+            }
+            EngineCommand::DeleteCookies { filter } => {
+                let span = tracing::debug_span!("Browser::DeleteCookies");
+                let _enter = span.enter();
+
+                let deleted = self.profile().cookie_jar().delete_matching(&filter);
+                trace!(deleted, "Deleted cookies");
+
+                Ok(EngineResponse::CookiesDeleted(deleted))
+            }
+            EngineCommand::ClearSiteData { url, types } => {
+                let span = tracing::debug_span!("Browser::ClearSiteData");
+                let _enter = span.enter();
+
+                self.clear_origin_data(&url, &types)
+            }
+            EngineCommand::GetMemoryReport => {
+                let span = tracing::debug_span!("Browser::GetMemoryReport");
+                let _enter = span.enter();
+
+                let connections = self.connections.stats();
+                let usage = MemoryUsage {
+                    dom_bytes: self.documents.estimated_size_bytes(),
+                    network_log_bytes: self.network_log.estimated_size_bytes(),
+                    cookie_jar_bytes: self.profile().cookie_jar().estimated_size_bytes(),
+                    connections_active: connections.active,
+                    connections_idle: connections.idle,
+                    ..MemoryUsage::default()
+                }
+                .with_total();
+
+                Ok(EngineResponse::MemoryReport(usage))
+            }
+            EngineCommand::SaveAs { url, directory } => {
+                let span = tracing::debug_span!("Browser::SaveAs");
+
+                self.save_as(&url, &directory).instrument(span).await
+            }
+            EngineCommand::ListDownloads => Ok(EngineResponse::DownloadList(self.downloads.downloads())),
+            EngineCommand::SaveSession { tabs } => {
+                self.session.save(&tabs).map_err(StorageError::Session)?;
+
+                Ok(EngineResponse::SessionSaved)
+            }
+            EngineCommand::RestoreCrashSession => {
+                Ok(EngineResponse::CrashSessionRestored(self.session.take_crashed_session().unwrap_or_default()))
+            }
+            EngineCommand::LoadExtension { path } => {
+                let span = tracing::debug_span!("Browser::LoadExtension");
+
+                let extension = self.extensions.load(&path).instrument(span).await?;
+
+                Ok(EngineResponse::ExtensionLoaded(extension))
+            }
         }
     }
 }
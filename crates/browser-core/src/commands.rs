@@ -1,7 +1,24 @@
 //! Commands module, containing various command implementations for the browser core.
 
+mod canvas;
+mod clipboard;
+mod details;
+mod download;
+mod favicon;
+mod font;
+mod form;
+mod hint;
 mod html;
 mod image;
+mod input;
+mod link;
 mod navigate;
+mod resize;
+mod select;
+mod site_data;
+mod style;
 
 pub use html::parse_devtools_html;
+pub(crate) use image::FetchedImage;
+pub use navigate::Navigation;
+pub use site_data::StorageDataType;
@@ -0,0 +1,375 @@
+use html_dom::{CanvasColor, CanvasCommand, CanvasImage, NodeId};
+use tracing::warn;
+
+use crate::{
+    Browser, EngineResponse,
+    errors::{CanvasError, CoreError},
+};
+
+/// The largest canvas bitmap, in pixels, that will be rasterized.
+const MAX_CANVAS_PIXELS: u64 = 8192 * 8192;
+
+impl Browser {
+    /// Executes the command buffer of a `<canvas>` element, producing the RGBA pixels of its bitmap.
+    ///
+    /// # Arguments
+    /// * `node_id` - The `<canvas>` node the commands were recorded for.
+    /// * `width` - The width of the canvas bitmap in pixels.
+    /// * `height` - The height of the canvas bitmap in pixels.
+    /// * `commands` - The recorded drawing commands, executed in order.
+    ///
+    /// # Errors
+    /// If the bitmap is larger than [`MAX_CANVAS_PIXELS`].
+    pub fn execute_canvas_commands(
+        node_id: NodeId,
+        width: u32,
+        height: u32,
+        commands: &[CanvasCommand],
+    ) -> Result<EngineResponse, CoreError> {
+        if u64::from(width) * u64::from(height) > MAX_CANVAS_PIXELS {
+            return Err(CanvasError::TooLarge { width, height }.into());
+        }
+
+        let mut canvas = Rasterizer::new(width, height);
+        for command in commands {
+            canvas.execute(command);
+        }
+
+        Ok(EngineResponse::CanvasRendered {
+            node_id,
+            width,
+            height,
+            rgba: canvas.pixels,
+        })
+    }
+}
+
+/// A CPU rasterizer holding the bitmap and drawing state of a canvas.
+struct Rasterizer {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+    fill_style: CanvasColor,
+    stroke_style: CanvasColor,
+    line_width: f32,
+
+    /// The sub-paths of the current path, each a list of points.
+    path: Vec<Vec<(f32, f32)>>,
+}
+
+impl Rasterizer {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+            fill_style: [0, 0, 0, 255],
+            stroke_style: [0, 0, 0, 255],
+            line_width: 1.0,
+            path: Vec::new(),
+        }
+    }
+
+    fn execute(&mut self, command: &CanvasCommand) {
+        match command {
+            CanvasCommand::FillRect {
+                x,
+                y,
+                width,
+                height,
+            } => {
+                let rect = rect_points(*x, *y, *width, *height);
+                self.fill_polygon(&[rect], self.fill_style);
+            }
+            CanvasCommand::StrokeRect {
+                x,
+                y,
+                width,
+                height,
+            } => {
+                let mut rect = rect_points(*x, *y, *width, *height);
+                rect.push((*x, *y));
+                self.stroke_polyline(&rect);
+            }
+            CanvasCommand::FillText { text, .. } => {
+                // The engine has no glyph rasterizer of its own, the text is shaped by the UI. The rest of the canvas
+                // is still drawn.
+                warn!(?text, "Skipping canvas fillText, text is not supported yet");
+            }
+            CanvasCommand::DrawImage {
+                image,
+                x,
+                y,
+                width,
+                height,
+            } => self.draw_image(image, *x, *y, *width, *height),
+            CanvasCommand::BeginPath => self.path.clear(),
+            CanvasCommand::MoveTo { x, y } => self.path.push(vec![(*x, *y)]),
+            CanvasCommand::LineTo { x, y } => match self.path.last_mut() {
+                Some(sub_path) => sub_path.push((*x, *y)),
+                None => self.path.push(vec![(*x, *y)]),
+            },
+            CanvasCommand::ClosePath => {
+                if let Some(sub_path) = self.path.last_mut()
+                    && let Some(&first) = sub_path.first()
+                {
+                    sub_path.push(first);
+                    self.path.push(vec![first]);
+                }
+            }
+            CanvasCommand::Stroke => {
+                let path = std::mem::take(&mut self.path);
+                for sub_path in &path {
+                    self.stroke_polyline(sub_path);
+                }
+                self.path = path;
+            }
+            CanvasCommand::Fill => {
+                let path = std::mem::take(&mut self.path);
+                self.fill_polygon(&path, self.fill_style);
+                self.path = path;
+            }
+            CanvasCommand::SetFillStyle(color) => self.fill_style = *color,
+            CanvasCommand::SetStrokeStyle(color) => self.stroke_style = *color,
+            CanvasCommand::SetLineWidth(width) => {
+                if width.is_finite() && *width > 0.0 {
+                    self.line_width = *width;
+                }
+            }
+        }
+    }
+
+    /// Fills the polygons using the non-zero winding rule, each polygon is implicitly closed.
+    fn fill_polygon(&mut self, polygons: &[Vec<(f32, f32)>], color: CanvasColor) {
+        let edges: Vec<((f32, f32), (f32, f32))> = polygons
+            .iter()
+            .filter(|polygon| polygon.len() >= 3)
+            .flat_map(|polygon| {
+                polygon
+                    .iter()
+                    .zip(polygon.iter().cycle().skip(1))
+                    .map(|(a, b)| (*a, *b))
+            })
+            .filter(|((ax, ay), (bx, by))| ax.is_finite() && ay.is_finite() && bx.is_finite() && by.is_finite())
+            .filter(|((_, ay), (_, by))| ay != by)
+            .collect();
+
+        if edges.is_empty() {
+            return;
+        }
+
+        let min_y = edges
+            .iter()
+            .map(|((_, ay), (_, by))| ay.min(*by))
+            .fold(f32::INFINITY, f32::min)
+            .max(0.0) as u32;
+        let max_y = edges
+            .iter()
+            .map(|((_, ay), (_, by))| ay.max(*by))
+            .fold(f32::NEG_INFINITY, f32::max)
+            .ceil()
+            .min(self.height as f32) as u32;
+
+        let mut crossings: Vec<(f32, i32)> = Vec::new();
+        for row in min_y..max_y {
+            let sample_y = row as f32 + 0.5;
+
+            crossings.clear();
+            for &((ax, ay), (bx, by)) in &edges {
+                let (top, bottom, winding) = if ay < by {
+                    ((ax, ay), (bx, by), 1)
+                } else {
+                    ((bx, by), (ax, ay), -1)
+                };
+
+                if sample_y < top.1 || sample_y >= bottom.1 {
+                    continue;
+                }
+
+                let t = (sample_y - top.1) / (bottom.1 - top.1);
+                crossings.push((top.0 + t * (bottom.0 - top.0), winding));
+            }
+
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            for pair in crossings.windows(2) {
+                winding += pair[0].1;
+                if winding == 0 {
+                    continue;
+                }
+
+                let start = (pair[0].0 - 0.5).ceil().max(0.0) as u32;
+                let end = ((pair[1].0 - 0.5).ceil().min(self.width as f32)).max(0.0) as u32;
+                for column in start..end {
+                    self.blend(column, row, color);
+                }
+            }
+        }
+    }
+
+    /// Strokes a polyline with the current stroke style and line width.
+    fn stroke_polyline(&mut self, points: &[(f32, f32)]) {
+        let half = self.line_width / 2.0;
+
+        for segment in points.windows(2) {
+            let ((ax, ay), (bx, by)) = (segment[0], segment[1]);
+            let (dx, dy) = (bx - ax, by - ay);
+            let length = dx.hypot(dy);
+            if length == 0.0 || !length.is_finite() {
+                continue;
+            }
+
+            let (nx, ny) = (-dy / length * half, dx / length * half);
+            let quad = vec![
+                (ax + nx, ay + ny),
+                (bx + nx, by + ny),
+                (bx - nx, by - ny),
+                (ax - nx, ay - ny),
+            ];
+            self.fill_polygon(&[quad], self.stroke_style);
+        }
+    }
+
+    /// Draws an image scaled into the destination rectangle using nearest-neighbor sampling.
+    fn draw_image(&mut self, image: &CanvasImage, x: f32, y: f32, width: f32, height: f32) {
+        if image.width == 0
+            || image.height == 0
+            || image.rgba.len() < image.width as usize * image.height as usize * 4
+            || width <= 0.0
+            || height <= 0.0
+        {
+            return;
+        }
+
+        let start_x = x.max(0.0) as u32;
+        let start_y = y.max(0.0) as u32;
+        let end_x = (x + width).min(self.width as f32).max(0.0) as u32;
+        let end_y = (y + height).min(self.height as f32).max(0.0) as u32;
+
+        for row in start_y..end_y {
+            let src_y = (((row as f32 + 0.5 - y) / height) * image.height as f32) as u32;
+            for column in start_x..end_x {
+                let src_x = (((column as f32 + 0.5 - x) / width) * image.width as f32) as u32;
+                let index = (src_y.min(image.height - 1) * image.width + src_x.min(image.width - 1)) as usize * 4;
+
+                let color = [
+                    image.rgba[index],
+                    image.rgba[index + 1],
+                    image.rgba[index + 2],
+                    image.rgba[index + 3],
+                ];
+                self.blend(column, row, color);
+            }
+        }
+    }
+
+    /// Composites a color over the pixel at the given position (source-over).
+    fn blend(&mut self, x: u32, y: u32, color: CanvasColor) {
+        if x >= self.width || y >= self.height || color[3] == 0 {
+            return;
+        }
+
+        let index = (y as usize * self.width as usize + x as usize) * 4;
+        let pixel = &mut self.pixels[index..index + 4];
+
+        let src_a = u32::from(color[3]);
+        let dst_a = u32::from(pixel[3]) * (255 - src_a) / 255;
+        let out_a = src_a + dst_a;
+        if out_a == 0 {
+            return;
+        }
+
+        for (dst, src) in pixel.iter_mut().zip(color).take(3) {
+            *dst = ((u32::from(src) * src_a + u32::from(*dst) * dst_a) / out_a) as u8;
+        }
+        pixel[3] = out_a as u8;
+    }
+}
+
+/// The corners of a rectangle as a polygon.
+fn rect_points(x: f32, y: f32, width: f32, height: f32) -> Vec<(f32, f32)> {
+    vec![
+        (x, y),
+        (x + width, y),
+        (x + width, y + height),
+        (x, y + height),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(rasterizer: &Rasterizer, x: u32, y: u32) -> [u8; 4] {
+        let index = (y * rasterizer.width + x) as usize * 4;
+        rasterizer.pixels[index..index + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn test_fill_rect() {
+        let mut rasterizer = Rasterizer::new(10, 10);
+        rasterizer.execute(&CanvasCommand::SetFillStyle([255, 0, 0, 255]));
+        rasterizer.execute(&CanvasCommand::FillRect {
+            x: 2.0,
+            y: 2.0,
+            width: 4.0,
+            height: 4.0,
+        });
+
+        assert_eq!(pixel(&rasterizer, 2, 2), [255, 0, 0, 255]);
+        assert_eq!(pixel(&rasterizer, 5, 5), [255, 0, 0, 255]);
+        assert_eq!(pixel(&rasterizer, 6, 6), [0, 0, 0, 0]);
+        assert_eq!(pixel(&rasterizer, 1, 2), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_fill_path() {
+        let mut rasterizer = Rasterizer::new(10, 10);
+        for command in [
+            CanvasCommand::BeginPath,
+            CanvasCommand::MoveTo { x: 0.0, y: 0.0 },
+            CanvasCommand::LineTo { x: 10.0, y: 0.0 },
+            CanvasCommand::LineTo { x: 0.0, y: 10.0 },
+            CanvasCommand::Fill,
+        ] {
+            rasterizer.execute(&command);
+        }
+
+        assert_eq!(pixel(&rasterizer, 1, 1), [0, 0, 0, 255]);
+        assert_eq!(pixel(&rasterizer, 8, 8), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_canvas_too_large() {
+        let result = Browser::execute_canvas_commands(NodeId(0), u32::MAX, u32::MAX, &[]);
+        assert!(result.is_err());
+
+        let result = Browser::execute_canvas_commands(NodeId(0), 8193, 8192, &[]);
+        assert!(matches!(result, Err(CoreError::Canvas(CanvasError::TooLarge { .. }))));
+    }
+
+    #[test]
+    fn test_fill_text_is_skipped() {
+        let commands = [
+            CanvasCommand::FillText {
+                text: "Hello".to_string(),
+                x: 0.0,
+                y: 0.0,
+            },
+            CanvasCommand::FillRect {
+                x: 0.0,
+                y: 0.0,
+                width: 10.0,
+                height: 10.0,
+            },
+        ];
+
+        let Ok(EngineResponse::CanvasRendered { rgba, .. }) =
+            Browser::execute_canvas_commands(NodeId(0), 10, 10, &commands)
+        else {
+            panic!("the canvas should be rendered");
+        };
+        assert_eq!(rgba[..4], [0, 0, 0, 255]);
+    }
+}
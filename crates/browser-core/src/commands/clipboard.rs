@@ -0,0 +1,123 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    Browser, EngineResponse,
+    errors::{ClipboardError, CoreError},
+};
+
+/// The MIME types that can be exchanged with the system clipboard.
+const SUPPORTED_MIME_TYPES: [&str; 2] = ["text/plain", "text/html"];
+
+/// How long after a user gesture reading the clipboard is still allowed, the lifetime of a transient activation.
+///
+/// <https://html.spec.whatwg.org/multipage/interaction.html#transient-activation-duration>
+const TRANSIENT_ACTIVATION_DURATION: Duration = Duration::from_secs(5);
+
+impl Browser {
+    /// Validates data to be written to the system clipboard.
+    ///
+    /// # Arguments
+    /// * `mime_type` - The MIME type of the data, parameters such as `charset` are ignored.
+    /// * `data` - The data to write, which must be UTF-8 text.
+    ///
+    /// # Returns
+    /// The data for the UI to write to the clipboard, or an error if the MIME type is not supported or the data is
+    /// not valid UTF-8.
+    pub fn write_clipboard(mime_type: String, data: Vec<u8>) -> Result<EngineResponse, CoreError> {
+        let mime_type = Self::clipboard_mime_type(&mime_type)?;
+
+        if str::from_utf8(&data).is_err() {
+            return Err(ClipboardError::InvalidUtf8(mime_type).into());
+        }
+
+        Ok(EngineResponse::ClipboardWrite { mime_type, data })
+    }
+
+    /// Checks whether the system clipboard can be read, which is only allowed shortly after a user gesture so that
+    /// pages cannot read the clipboard behind the user's back.
+    ///
+    /// # Arguments
+    /// * `mime_type` - The MIME type to read, parameters such as `charset` are ignored.
+    /// * `user_gesture` - When the user gesture that triggered the read happened, if any.
+    /// * `now` - The current instant.
+    ///
+    /// # Returns
+    /// The permission for the UI to read the clipboard, or an error if the MIME type is not supported or there was no
+    /// recent user gesture.
+    pub fn read_clipboard(
+        mime_type: String,
+        user_gesture: Option<Instant>,
+        now: Instant,
+    ) -> Result<EngineResponse, CoreError> {
+        let mime_type = Self::clipboard_mime_type(&mime_type)?;
+
+        let Some(gesture) = user_gesture else {
+            return Err(ClipboardError::NoUserGesture.into());
+        };
+
+        if now.saturating_duration_since(gesture) > TRANSIENT_ACTIVATION_DURATION {
+            return Err(ClipboardError::GestureExpired.into());
+        }
+
+        Ok(EngineResponse::ClipboardReadAllowed { mime_type })
+    }
+
+    /// Normalizes a MIME type to its lowercase essence, checking that it can be exchanged with the clipboard.
+    fn clipboard_mime_type(mime_type: &str) -> Result<String, ClipboardError> {
+        let essence = mime_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+
+        if SUPPORTED_MIME_TYPES.contains(&essence.as_str()) {
+            Ok(essence)
+        } else {
+            Err(ClipboardError::UnsupportedMimeType(mime_type.to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_clipboard_normalizes_mime_type() {
+        let response = Browser::write_clipboard("Text/Plain; charset=utf-8".to_string(), b"hello".to_vec()).unwrap();
+
+        match response {
+            EngineResponse::ClipboardWrite { mime_type, data } => {
+                assert_eq!(mime_type, "text/plain");
+                assert_eq!(data, b"hello");
+            }
+            other => panic!("unexpected response: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_write_clipboard_rejects_unsupported_data() {
+        assert!(Browser::write_clipboard("image/png".to_string(), vec![0x89, 0x50]).is_err());
+        assert!(Browser::write_clipboard("text/html".to_string(), vec![0xff, 0xfe]).is_err());
+    }
+
+    #[test]
+    fn test_read_clipboard_requires_recent_gesture() {
+        let now = Instant::now();
+
+        assert!(Browser::read_clipboard("text/plain".to_string(), None, now).is_err());
+        assert!(matches!(
+            Browser::read_clipboard("text/plain".to_string(), Some(now), now + Duration::from_secs(1)),
+            Ok(EngineResponse::ClipboardReadAllowed { .. })
+        ));
+        assert!(
+            Browser::read_clipboard(
+                "text/plain".to_string(),
+                Some(now),
+                now + TRANSIENT_ACTIVATION_DURATION + Duration::from_secs(1)
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use html_dom::{DocumentRoot, HtmlTag, NodeId, Tag};
+
+use crate::{Browser, EngineResponse, errors::CoreError};
+
+impl Browser {
+    /// Opens or closes a `<details>` whose summary was clicked by the user. Opening a `<details>` with a `name`
+    /// closes the other `<details>` of the document with the same `name`, so that at most one of them is open, like an
+    /// accordion.
+    ///
+    /// # Arguments
+    /// * `node_id` - The node of the toggled `<details>`.
+    /// * `document` - The document the `<details>` belongs to, used to find the others with the same `name`.
+    ///
+    /// # Returns
+    /// The `<details>` elements to add the `open` attribute to and to remove it from, or an error if the node is not a
+    /// `<details>`.
+    pub fn toggle_details(node_id: NodeId, document: &DocumentRoot) -> Result<EngineResponse, CoreError> {
+        let element = document
+            .get_node(&node_id)
+            .and_then(|node| node.data.as_element())
+            .filter(|element| element.tag == Tag::Html(HtmlTag::Details))
+            .ok_or(CoreError::DetailsToggle(node_id))?;
+
+        if element.has_attribute("open") {
+            return Ok(EngineResponse::DetailsToggled {
+                opened: Vec::new(),
+                closed: vec![node_id],
+            });
+        }
+
+        let closed = match element
+            .get_attribute("name")
+            .filter(|name| !name.is_empty())
+        {
+            Some(name) => document
+                .nodes
+                .iter()
+                .filter(|node| {
+                    node.id != node_id
+                        && node.data.as_element().is_some_and(|other| {
+                            other.tag == Tag::Html(HtmlTag::Details)
+                                && other.has_attribute("open")
+                                && other.get_attribute("name") == Some(name)
+                        })
+                })
+                .map(|node| node.id)
+                .collect(),
+            None => Vec::new(),
+        };
+
+        Ok(EngineResponse::DetailsToggled {
+            opened: vec![node_id],
+            closed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use html_parser::{HtmlStreamParser, ParserState};
+
+    use super::*;
+
+    fn parse(html: &str) -> DocumentRoot {
+        let mut parser = HtmlStreamParser::simple(Cursor::new(html));
+
+        loop {
+            if let ParserState::Completed(result) = parser.step().unwrap() {
+                return result.dom_tree;
+            }
+        }
+    }
+
+    fn details(dom: &DocumentRoot) -> Vec<NodeId> {
+        dom.nodes
+            .iter()
+            .filter(|node| {
+                node.data
+                    .as_element()
+                    .is_some_and(|element| element.tag == Tag::Html(HtmlTag::Details))
+            })
+            .map(|node| node.id)
+            .collect()
+    }
+
+    #[test]
+    fn test_opening_named_details_closes_the_others() {
+        let dom = parse(
+            r#"<details name="faq" open><summary>A</summary></details>
+            <details name="faq"><summary>B</summary></details>
+            <details name="faq"><summary>C</summary></details>
+            <details name="other" open><summary>D</summary></details>"#,
+        );
+        let ids = details(&dom);
+
+        assert!(matches!(
+            Browser::toggle_details(ids[1], &dom),
+            Ok(EngineResponse::DetailsToggled { opened, closed }) if opened == [ids[1]] && closed == [ids[0]]
+        ));
+    }
+
+    #[test]
+    fn test_toggle_details_without_name() {
+        let dom = parse("<details open><summary>A</summary></details><details><summary>B</summary></details><p>");
+        let ids = details(&dom);
+
+        assert!(matches!(
+            Browser::toggle_details(ids[0], &dom),
+            Ok(EngineResponse::DetailsToggled { opened, closed }) if opened.is_empty() && closed == [ids[0]]
+        ));
+        assert!(matches!(
+            Browser::toggle_details(ids[1], &dom),
+            Ok(EngineResponse::DetailsToggled { opened, closed }) if opened == [ids[1]] && closed.is_empty()
+        ));
+
+        let paragraph = dom
+            .nodes
+            .iter()
+            .find(|node| {
+                node.data
+                    .as_element()
+                    .is_some_and(|element| element.tag == Tag::Html(HtmlTag::P))
+            })
+            .unwrap()
+            .id;
+        assert!(Browser::toggle_details(paragraph, &dom).is_err());
+    }
+}
@@ -0,0 +1,33 @@
+use std::path::Path;
+
+use tracing::debug;
+
+use crate::{
+    Browser, EngineResponse,
+    download::{create_download_file, download_filename},
+    errors::{CoreError, NavigationError},
+};
+
+impl Browser {
+    /// Saves the resource at a URL to a new file in a directory, whatever its `Content-Disposition`.
+    ///
+    /// # Arguments
+    /// * `url` - The URL of the resource, usually the page shown in the active tab.
+    /// * `directory` - The directory the file is created in, named after the last segment of the URL.
+    ///
+    /// # Returns
+    /// The download as it is when it starts, its body is written to disk in the background.
+    pub async fn save_as(&self, url: &str, directory: &Path) -> Result<EngineResponse, CoreError> {
+        let (request_url, response_handle) = self.fetch_document(url, None, true).await?;
+
+        let (save_path, file) = create_download_file(directory, &download_filename(&request_url, None))
+            .map_err(|error| NavigationError::Download(error.to_string()))?;
+
+        debug!(path = %save_path.display(), "Saving {} as", request_url);
+        let download = self
+            .downloads
+            .start(request_url, save_path, file, response_handle);
+
+        Ok(EngineResponse::DownloadStarted(download))
+    }
+}
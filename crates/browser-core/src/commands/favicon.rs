@@ -0,0 +1,93 @@
+use http_cache::block::MAX_BLOCK_SIZE;
+use http_fetch::{handles::LocalHandle, request::fetch};
+use http_types::{
+    properties::{Destination, RequestMode},
+    request::Request,
+};
+use io::{Readable, resource::Priority};
+use tracing::debug;
+use url::Url;
+
+use crate::{
+    Browser, EngineResponse,
+    context::page::{Favicon, FaviconLink},
+    errors::CoreError,
+};
+
+/// The largest favicon that is loaded, larger bodies are discarded.
+const MAX_FAVICON_SIZE: usize = 2 * 1024 * 1024;
+
+impl Browser {
+    /// Loads the favicon linked by a page, at the lowest priority since the page is displayed without it.
+    pub async fn load_favicon(&self, page_url: Url, link: FaviconLink) -> Result<EngineResponse, CoreError> {
+        let _permit = self.resource_scheduler.acquire(Priority::Low).await;
+        let client = self.http_client().box_clone();
+        let headers = self.profile().config().headers().clone();
+
+        let request = Request::builder_url(link.url.clone())
+            .destination(Destination::Image)
+            .request_mode(RequestMode::Cors)
+            .build();
+
+        let response_handle = if link.url.scheme() != "http" && link.url.scheme() != "https" {
+            match request.read(&self.profile().dirs().into(), Some(MAX_BLOCK_SIZE)) {
+                Ok(data) => LocalHandle::from(data).into(),
+                Err(error) => {
+                    debug!(%error, "Failed to load favicon {}", link.url);
+                    return Err(CoreError::Image(error.to_string()));
+                }
+            }
+        } else {
+            match fetch(
+                Some(&page_url),
+                request,
+                client.as_ref(),
+                &headers,
+                &self.profile().dirs().into(),
+                self.profile().cookie_jar(),
+                self.profile().http_cache(),
+            )
+            .await
+            {
+                Ok(handle) => handle,
+                Err(error) => {
+                    debug!(%error, "Failed to fetch favicon {}", link.url);
+                    return Err(CoreError::Image(error.to_string()));
+                }
+            }
+        };
+
+        if !response_handle.head().status_code.is_success() {
+            return Err(CoreError::Image(format!("Status Code: {}", response_handle.head().status_code.as_u16())));
+        }
+
+        let response = match response_handle.response().await {
+            Ok(resp) => resp,
+            Err(error) => {
+                debug!(%error, "Failed to read favicon response: {}", link.url);
+                return Err(CoreError::Image(format!("Failed to read favicon response: {}", error)));
+            }
+        };
+
+        let Some(body) = response.body.into_complete(MAX_FAVICON_SIZE).await else {
+            debug!("Favicon body is too large or failed to read: {}", link.url);
+            return Err(CoreError::Image("Favicon body is too large or failed to read".to_string()));
+        };
+
+        debug!(
+            resource.url = %link.url,
+            "resource.type" = "favicon",
+            resource.size = body.0.len(),
+            "Loaded favicon {}", link.url
+        );
+
+        Ok(EngineResponse::FaviconFetched {
+            page_url,
+            favicon: Favicon {
+                size: link.size,
+                content_type: link.content_type,
+                data: body.0.into(),
+            },
+        })
+    }
+}
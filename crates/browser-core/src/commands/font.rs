@@ -0,0 +1,134 @@
+use http_cache::block::MAX_BLOCK_SIZE;
+use http_fetch::{
+    errors::{FetchError, NetworkError},
+    handles::LocalHandle,
+    request::fetch,
+};
+use http_types::{
+    properties::{Destination, RequestMode},
+    request::Request,
+};
+use io::{Readable, resource::Priority};
+use tracing::debug;
+use url::Url;
+
+use crate::{
+    Browser, EngineResponse,
+    errors::{CoreError, NavigationError},
+};
+
+/// The signatures of the WOFF and WOFF2 formats, which the font database does not decode.
+const COMPRESSED_SIGNATURES: [&[u8; 4]; 2] = [b"wOFF", b"wOF2"];
+
+impl Browser {
+    /// Loads the font file of an `@font-face` rule using the browser's HTTP client, headers, and cookies. The sources
+    /// are tried in order until one is fetched in a format that can be read.
+    ///
+    /// # Arguments
+    /// * `family` - The family name of the rule, returned with the data.
+    /// * `request_url` - The URL of the page, which relative font URLs are resolved against.
+    /// * `font_urls` - The URLs of the `url()` sources of the rule, in order of preference.
+    ///
+    /// # Errors
+    /// Returns the error of the last source if none of them can be fetched and read.
+    pub async fn load_font(
+        &self,
+        family: String,
+        request_url: &Url,
+        font_urls: &[String],
+    ) -> Result<EngineResponse, CoreError> {
+        let mut last_error = CoreError::Font(format!("No font source for {family}"));
+
+        for font_url in font_urls {
+            match self.fetch_font(request_url, font_url).await {
+                Ok(data) => {
+                    return Ok(EngineResponse::FontFetched {
+                        family,
+                        url: font_url.clone(),
+                        data,
+                    });
+                }
+                Err(error) => {
+                    debug!(%error, "Trying the next source of font {family}");
+                    last_error = error;
+                }
+            }
+        }
+
+        Err(last_error)
+    }
+
+    /// Fetches a single font file, rejecting files in a compressed format.
+    async fn fetch_font(&self, request_url: &Url, font_url: &str) -> Result<Vec<u8>, CoreError> {
+        let absolute_url = request_url
+            .join(font_url)
+            .map_err(|error| NavigationError::Request {
+                source: FetchError::Network(NetworkError::InvalidUrl(error)),
+                url: font_url.to_string(),
+            })?;
+
+        // Fonts block the rendering of text, so they are fetched before the other subresources.
+        let _permit = self.resource_scheduler.acquire(Priority::High).await;
+        let client = self.http_client().box_clone();
+        let headers = self.profile().config().headers().clone();
+
+        let is_http = absolute_url.scheme() == "http" || absolute_url.scheme() == "https";
+
+        let font_request = Request::builder_url(absolute_url.clone())
+            .destination(Destination::Font)
+            .request_mode(RequestMode::Cors)
+            .build();
+
+        let response_handle = if is_http {
+            fetch(
+                Some(request_url),
+                font_request,
+                client.as_ref(),
+                &headers,
+                &self.profile().dirs().into(),
+                self.profile().cookie_jar(),
+                self.profile().http_cache(),
+            )
+            .await
+            .map_err(|error| CoreError::Font(error.to_string()))?
+        } else {
+            match font_request.read(&self.profile().dirs().into(), Some(MAX_BLOCK_SIZE)) {
+                Ok(data) => LocalHandle::from(data).into(),
+                Err(error) => {
+                    debug!(%error, "Failed to load font");
+                    return Err(CoreError::Font(error.to_string()));
+                }
+            }
+        };
+
+        if !response_handle.head().status_code.is_success() {
+            return Err(CoreError::Font(format!("Status Code: {}", response_handle.head().status_code.as_u16())));
+        }
+
+        let response = response_handle
+            .response()
+            .await
+            .map_err(|error| CoreError::Font(format!("Failed to read font response: {error}")))?;
+
+        let Some(body) = response.body.into_complete(MAX_BLOCK_SIZE as usize).await else {
+            debug!("Font body is too large or failed to read: {}", font_url);
+            return Err(CoreError::Font("Font body is too large or failed to read".to_string()));
+        };
+
+        if COMPRESSED_SIGNATURES
+            .iter()
+            .any(|signature| body.0.starts_with(*signature))
+        {
+            return Err(CoreError::Font(format!("{absolute_url} is a WOFF font, which is not supported")));
+        }
+
+        debug!(
+            resource.url = %absolute_url,
+            "resource.type" = "font",
+            resource.size = body.0.len(),
+            "Loaded font {}", absolute_url
+        );
+
+        Ok(body.0.into())
+    }
+}
@@ -0,0 +1,72 @@
+use css_cssom::CSSStyleSheet;
+use html_dom::{DocumentRoot, FormValidator, HtmlTag, NodeId, Tag, form_entries};
+use tracing::debug;
+use url::Url;
+
+use crate::{
+    Browser, EngineResponse, NavigationType,
+    errors::{CoreError, FormError},
+};
+
+impl Browser {
+    /// Validates the given form and, if all of its controls are valid, navigates to the form's action URL.
+    ///
+    /// Forms with the `novalidate` attribute skip constraint validation. Only the `GET` method is
+    /// currently supported, where the form entries are encoded into the query string of the action URL.
+    pub async fn submit_form(
+        &self,
+        form_id: NodeId,
+        document: &DocumentRoot,
+        page_url: &Url,
+        stylesheets: Vec<CSSStyleSheet>,
+    ) -> Result<EngineResponse, CoreError> {
+        let form_node = document
+            .get_node(&form_id)
+            .ok_or(FormError::NotFound(form_id))?;
+
+        let Some(form) = form_node
+            .data
+            .as_element()
+            .filter(|element| element.tag == Tag::Html(HtmlTag::Form))
+        else {
+            return Err(FormError::NotAForm(form_id).into());
+        };
+
+        let errors = FormValidator::validate_form(form_node, document);
+        if !errors.is_empty() {
+            debug!(count = errors.len(), "Form failed constraint validation");
+            return Ok(EngineResponse::FormValidationFailed(errors));
+        }
+
+        let method = form.get_attribute("method").unwrap_or("get").trim();
+        if !method.eq_ignore_ascii_case("get") {
+            return Err(FormError::UnsupportedMethod(method.to_string()).into());
+        }
+
+        let action = form
+            .get_attribute("action")
+            .map(str::trim)
+            .filter(|a| !a.is_empty());
+
+        let mut action_url = match action {
+            Some(action) => page_url
+                .join(action)
+                .map_err(|source| FormError::InvalidAction {
+                    action: action.to_string(),
+                    source,
+                })?,
+            None => page_url.clone(),
+        };
+
+        action_url
+            .query_pairs_mut()
+            .clear()
+            .extend_pairs(form_entries(form_node, document));
+
+        let navigation = self
+            .navigate(action_url.as_str(), None, true, stylesheets)
+            .await?;
+
+        Ok(navigation.into_response(NavigationType::Normal))
+    }
+}
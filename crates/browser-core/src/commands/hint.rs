@@ -0,0 +1,136 @@
+use std::sync::Arc;
+
+use html_parser::{ResourceHintKind, ResourceType};
+use http::HeaderMap;
+use http_cache::block::MAX_BLOCK_SIZE;
+use http_fetch::request::fetch;
+use http_types::{
+    properties::{Destination, RequestMode},
+    request::Request,
+};
+use io::{paths::AppPaths, resource::Priority};
+use tokio::net::lookup_host;
+use tracing::{Instrument, debug, trace};
+use url::Url;
+
+use crate::Browser;
+
+impl Browser {
+    /// Spawns a background task acting on a resource hint found while parsing a page.
+    ///
+    /// `dns-prefetch` resolves the host name of the hinted origin ahead of time and `preconnect` also opens a
+    /// connection to it, which the HTTP client keeps in its pool for the requests of the page, while `preload`,
+    /// `modulepreload` and `prefetch` fetch the resource in full so that it is stored in the HTTP cache and served
+    /// from there once the page (or a future navigation) actually requests it. The task yields before starting
+    /// so that it runs after the work that is currently blocking the page.
+    pub(crate) fn spawn_resource_hint(
+        &self,
+        kind: ResourceHintKind,
+        hint_url: Url,
+        as_type: Option<ResourceType>,
+        page_url: &Url,
+        headers: Arc<HeaderMap>,
+    ) {
+        let page_url = page_url.clone();
+        let client = self.http_client().box_clone();
+        let paths = AppPaths::from(self.profile().dirs());
+        let cache = self.profile().http_cache().clone();
+        let cookie_jar = self.profile().cookie_jar().clone();
+        let resource_scheduler = self.resource_scheduler.clone();
+        let preconnects = self.preconnects.clone();
+
+        tokio::spawn(
+            async move {
+                tokio::task::yield_now().await;
+
+                if hint_url.scheme() != "http" && hint_url.scheme() != "https" {
+                    trace!("Ignoring resource hint for non-HTTP URL {}", hint_url);
+                    return;
+                }
+
+                match kind {
+                    ResourceHintKind::Preconnect => preconnects.preconnect(&hint_url, client.as_ref()).await,
+                    ResourceHintKind::DnsPrefetch => {
+                        let (Some(host), Some(port)) = (hint_url.host_str(), hint_url.port_or_known_default()) else {
+                            return;
+                        };
+
+                        match lookup_host((host, port)).await {
+                            Ok(_) => trace!("Resolved {} ahead of time", host),
+                            Err(error) => debug!(%error, "Failed to resolve {} ahead of time", host),
+                        }
+                    }
+                    ResourceHintKind::Preload | ResourceHintKind::ModulePreload | ResourceHintKind::Prefetch => {
+                        let _permit = resource_scheduler
+                            .acquire(Self::hint_priority(kind, as_type))
+                            .await;
+                        let request = Request::builder_url(hint_url.clone())
+                            .destination(Self::hint_destination(as_type))
+                            .request_mode(RequestMode::Cors)
+                            .build();
+
+                        let response_handle = match fetch(
+                            Some(&page_url),
+                            request,
+                            client.as_ref(),
+                            &headers,
+                            &paths,
+                            &cookie_jar,
+                            &cache,
+                        )
+                        .await
+                        {
+                            Ok(response_handle) => response_handle,
+                            Err(error) => {
+                                debug!(%error, "Failed to fetch hinted resource {}", hint_url);
+                                return;
+                            }
+                        };
+
+                        if !response_handle.head().status_code.is_success() {
+                            return;
+                        }
+
+                        // Draining the body lets the cache handle store the response under the resource URL.
+                        match response_handle.response().await {
+                            Ok(response) => {
+                                if response
+                                    .body
+                                    .into_complete(MAX_BLOCK_SIZE as usize)
+                                    .await
+                                    .is_some()
+                                {
+                                    trace!("Cached hinted resource {}", hint_url);
+                                }
+                            }
+                            Err(error) => debug!(%error, "Failed to read hinted resource {}", hint_url),
+                        }
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+    }
+
+    /// The priority of the load of a hinted resource, prefetches are only needed by future navigations.
+    const fn hint_priority(kind: ResourceHintKind, as_type: Option<ResourceType>) -> Priority {
+        match (kind, as_type) {
+            (ResourceHintKind::Prefetch, _) => Priority::Idle,
+            (_, Some(ResourceType::Style | ResourceType::Script | ResourceType::Font)) => Priority::High,
+            (_, Some(ResourceType::Image | ResourceType::Favicon)) => Priority::Normal,
+            _ => Priority::Low,
+        }
+    }
+
+    /// Maps the `as` type of a resource hint to the request destination used to fetch it.
+    const fn hint_destination(as_type: Option<ResourceType>) -> Destination {
+        match as_type {
+            Some(ResourceType::Style) => Destination::Style,
+            Some(ResourceType::Script) => Destination::Script,
+            Some(ResourceType::Font) => Destination::Font,
+            Some(ResourceType::Image | ResourceType::Favicon) => Destination::Image,
+            Some(ResourceType::Document) => Destination::Document,
+            Some(ResourceType::Fetch) | None => Destination::Empty,
+        }
+    }
+}
@@ -1,10 +1,19 @@
 use std::io::{Cursor, Write};
 
+use cookies::Cookie;
 use html_dom::{DocumentRoot, DomNode, NodeData};
-use html_parser::{HtmlStreamParser, ParserState, errors::HtmlParsingError};
-
-/// Parses the HTML content of the active tab for devtools inspection. This function retrieves the HTML from the active tab's document.
-pub fn parse_devtools_html(title: &str, document: &DocumentRoot) -> Result<DocumentRoot, HtmlParsingError> {
+use html_escape::encode_text;
+use html_parser::{HtmlStreamParser, ParserState, errors::HtmlParseError};
+use http_fetch::network_log::NetworkEntry;
+
+/// Parses the HTML content of the active tab for devtools inspection. This function retrieves the HTML from the active tab's document,
+/// followed by a cookie inspector listing the provided cookies and a network panel listing the provided requests.
+pub fn parse_devtools_html(
+    title: &str,
+    document: &DocumentRoot,
+    cookies: &[Cookie],
+    requests: &[NetworkEntry],
+) -> Result<DocumentRoot, HtmlParseError> {
     fn node_to_html(mut html: &mut Vec<u8>, node: &DomNode, dom_tree: &DocumentRoot, depth: usize) {
         if node.data.as_text().is_some_and(|t| t.trim().is_empty()) {
             return;
@@ -75,6 +84,12 @@ pub fn parse_devtools_html(title: &str, document: &DocumentRoot) -> Result<Docum
     write!(&mut html, "<div data-active>").unwrap();
     write!(&mut html, "<p>DOM</p>").unwrap();
     write!(&mut html, "</div>").unwrap();
+    write!(&mut html, "<div>").unwrap();
+    write!(&mut html, "<p>Cookies</p>").unwrap();
+    write!(&mut html, "</div>").unwrap();
+    write!(&mut html, "<div>").unwrap();
+    write!(&mut html, "<p>Network</p>").unwrap();
+    write!(&mut html, "</div>").unwrap();
 
     write!(&mut html, "</nav>").unwrap();
     write!(&mut html, "</header>").unwrap();
@@ -84,6 +99,54 @@ pub fn parse_devtools_html(title: &str, document: &DocumentRoot) -> Result<Docum
         node_to_html(&mut html, &document[root_id], document, 0);
     }
     write!(&mut html, "</main>").unwrap();
+
+    write!(&mut html, "<section id=\"cookies\">").unwrap();
+    write!(
+        &mut html,
+        "<div class='cookie-row cookie-header'><span>Name</span><span>Value</span><span>Domain</span><span>Path</span><span>Expires</span><span>Secure</span><span>HttpOnly</span><span>SameSite</span></div>"
+    )
+    .unwrap();
+    for cookie in cookies {
+        let domain = cookie
+            .domain()
+            .as_ref()
+            .map_or_else(String::new, ToString::to_string);
+
+        write!(
+            &mut html,
+            "<div class='cookie-row'><span>{}</span><span>{}</span><span>{}</span><span>{}</span><span>{}</span><span>{}</span><span>{}</span><span>{}</span></div>",
+            encode_text(cookie.name()),
+            encode_text(cookie.value()),
+            encode_text(&domain),
+            encode_text(cookie.path()),
+            cookie.expires(),
+            cookie.secure(),
+            cookie.http_only(),
+            cookie.same_site(),
+        )
+        .unwrap();
+    }
+    write!(&mut html, "</section>").unwrap();
+
+    write!(&mut html, "<section id=\"network\">").unwrap();
+    write!(
+        &mut html,
+        "<div class='network-row network-header'><span>Method</span><span class='network-url'>URL</span><span>Status</span><span>Protocol</span></div>"
+    )
+    .unwrap();
+    for request in requests {
+        write!(
+            &mut html,
+            "<div class='network-row'><span>{}</span><span class='network-url'>{}</span><span>{}</span><span>{}</span></div>",
+            request.method,
+            encode_text(request.url.as_str()),
+            request.status_code.as_u16(),
+            request.protocol.as_ref(),
+        )
+        .unwrap();
+    }
+    write!(&mut html, "</section>").unwrap();
+
     write!(&mut html, "</body></html>").unwrap();
 
     let mut parser = HtmlStreamParser::simple(Cursor::new(html));
@@ -1,5 +1,4 @@
 use html_dom::NodeId;
-use html_escape::decode_html_entities;
 use http::header::CONTENT_TYPE;
 use http_cache::block::MAX_BLOCK_SIZE;
 use http_fetch::{
@@ -11,7 +10,7 @@ use http_types::{
     properties::{Destination, RequestMode},
     request::Request,
 };
-use io::Readable;
+use io::{Readable, resource::Priority};
 use tracing::debug;
 use url::Url;
 
@@ -20,6 +19,13 @@ use crate::{
     errors::{CoreError, NavigationError},
 };
 
+/// The response of an image request, shared between the callers loading the same image.
+#[derive(Debug, Clone)]
+pub(crate) struct FetchedImage {
+    content_type: String,
+    data: Vec<u8>,
+}
+
 impl Browser {
     /// Loads an image from the specified URL using the browser's HTTP client, headers, and cookies.
     pub async fn load_image(
@@ -28,29 +34,40 @@ impl Browser {
         request_url: Url,
         image_url: &str,
     ) -> Result<EngineResponse, CoreError> {
-        let client = self.http_client().box_clone();
-        let headers = self.profile().config().headers().clone();
-
-        let decoded_url = decode_html_entities(image_url);
-
         let absolute_url = request_url
-            .join(&decoded_url)
+            .join(image_url)
             .map_err(|error| NavigationError::Request {
                 source: FetchError::Network(NetworkError::InvalidUrl(error)),
                 url: image_url.to_string(),
             })?;
 
-        if std::path::Path::new(absolute_url.path())
-            .extension()
-            .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"))
-        {
-            debug!("SVG images are not supported, skipping: {}", absolute_url);
-            return Err(CoreError::Image("SVG images are not supported".to_string()));
-        }
+        let image = self
+            .image_requests
+            .load(&absolute_url, self.fetch_image(&request_url, absolute_url.clone(), image_url))
+            .await?;
+
+        Ok(EngineResponse::ImageFetched {
+            node_ids,
+            content_type: image.content_type,
+            url: image_url.to_string(),
+            data: image.data,
+        })
+    }
+
+    /// Fetches the body and content type of an image, concurrent loads of the same image share a single request.
+    async fn fetch_image(
+        &self,
+        request_url: &Url,
+        absolute_url: Url,
+        image_url: &str,
+    ) -> Result<FetchedImage, CoreError> {
+        let _permit = self.resource_scheduler.acquire(Priority::Normal).await;
+        let client = self.http_client().box_clone();
+        let headers = self.profile().config().headers().clone();
 
         let is_http = absolute_url.scheme() == "http" || absolute_url.scheme() == "https";
 
-        let image_request = Request::builder_url(absolute_url)
+        let image_request = Request::builder_url(absolute_url.clone())
             .destination(Destination::Image)
             .request_mode(RequestMode::Cors)
             .build();
@@ -65,7 +82,7 @@ impl Browser {
             }
         } else {
             match fetch(
-                Some(&request_url),
+                Some(request_url),
                 image_request,
                 client.as_ref(),
                 &headers,
@@ -99,6 +116,13 @@ impl Browser {
             return Err(CoreError::Image("Image body is too large or failed to read".to_string()));
         };
 
+        debug!(
+            resource.url = %absolute_url,
+            "resource.type" = "image",
+            resource.size = body.0.len(),
+            "Loaded image {}", absolute_url
+        );
+
         let content_type = response
             .head
             .headers
@@ -107,10 +131,8 @@ impl Browser {
             .unwrap_or("application/octet-stream")
             .to_string();
 
-        Ok(EngineResponse::ImageFetched {
-            node_ids,
+        Ok(FetchedImage {
             content_type,
-            url: image_url.to_string(),
             data: body.0.into(),
         })
     }
@@ -0,0 +1,288 @@
+use html_dom::{CheckableKind, DocumentRoot, Element, NodeId, RangeElement, TextControl, radio_group};
+use tracing::warn;
+
+use crate::{
+    Browser, EngineResponse,
+    context::page::DocumentId,
+    errors::{CoreError, InputError},
+    script::DomAccessor,
+};
+
+impl Browser {
+    /// Looks up the element of a form control in a document.
+    ///
+    /// # Errors
+    /// Returns `InputError::NotFound` if the node does not exist or is not an element.
+    pub(crate) fn control(document: &DocumentRoot, node_id: NodeId) -> Result<&Element, CoreError> {
+        document
+            .get_node(&node_id)
+            .and_then(|node| node.data.as_element())
+            .ok_or_else(|| InputError::NotFound(node_id).into())
+    }
+
+    /// Applies the constraints of an `<input>` or `<textarea>` to a value entered by the user, or of an
+    /// `<input type="range">` to a value picked by dragging its thumb.
+    ///
+    /// # Arguments
+    /// * `node_id` - The node of the edited control.
+    /// * `element` - The element of the edited control, used to resolve its constraints.
+    /// * `value` - The new value of the control.
+    ///
+    /// # Returns
+    /// The sanitized value to store in the DOM, or an error if the control is not an editable text control or an
+    /// enabled range input.
+    pub fn update_input_value(node_id: NodeId, element: &Element, value: &str) -> Result<EngineResponse, CoreError> {
+        if let Some(range) = RangeElement::from_element(element) {
+            if element.has_attribute("disabled") {
+                return Err(InputError::Disabled(node_id).into());
+            }
+
+            let value = value
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .ok_or_else(|| InputError::NotANumber(value.to_string()))?;
+
+            return Ok(EngineResponse::InputValueUpdated {
+                node_id,
+                value: range.sanitize(value).to_string(),
+            });
+        }
+
+        let control = TextControl::from_element(element).ok_or(InputError::NotATextControl(node_id))?;
+
+        if !control.editable {
+            return Err(InputError::ReadOnly(node_id).into());
+        }
+
+        Ok(EngineResponse::InputValueUpdated {
+            node_id,
+            value: control.sanitize(value),
+        })
+    }
+
+    /// Fires a DOM event at an element through the JavaScript bridge, e.g. `change` once the user settles the value
+    /// of a control. The listeners run against the engine's copy of the document.
+    ///
+    /// # Arguments
+    /// * `document_id` - The document of the node.
+    /// * `node_id` - The node the event is fired at.
+    /// * `event_type` - The type of the event.
+    ///
+    /// # Returns
+    /// The dispatched event with the nodes its listeners changed, or an error if the document is no longer loaded. A
+    /// listener throwing is logged like an uncaught exception, the changes made before it threw are still reported.
+    pub fn dispatch_event(
+        &self,
+        document_id: DocumentId,
+        node_id: NodeId,
+        event_type: String,
+    ) -> Result<EngineResponse, CoreError> {
+        let changes = self
+            .documents
+            .with_dom(document_id, |dom| {
+                let mut accessor = DomAccessor::new(dom);
+                if let Err(err) = self
+                    .script_bridge
+                    .dispatch_event(node_id, &event_type, &mut accessor)
+                {
+                    warn!(%node_id, event_type, "Uncaught exception in event listener: {}", err);
+                }
+
+                accessor.into_changes()
+            })
+            .ok_or(CoreError::DocumentNotFound(document_id))?;
+
+        Ok(EngineResponse::EventDispatched {
+            node_id,
+            event_type,
+            changes,
+        })
+    }
+
+    /// Toggles the checkedness of a checkbox clicked by the user.
+    ///
+    /// # Arguments
+    /// * `node_id` - The node of the clicked checkbox.
+    /// * `element` - The element of the clicked checkbox, whose `checked` attribute is its current state.
+    ///
+    /// # Returns
+    /// The checkbox to check or uncheck, or an error if the node is not an enabled checkbox.
+    pub fn toggle_checkbox(node_id: NodeId, element: &Element) -> Result<EngineResponse, CoreError> {
+        if CheckableKind::from_element(element) != Some(CheckableKind::Checkbox) {
+            return Err(InputError::NotACheckbox(node_id).into());
+        }
+
+        if element.has_attribute("disabled") {
+            return Err(InputError::Disabled(node_id).into());
+        }
+
+        Ok(if element.has_attribute("checked") {
+            EngineResponse::CheckedChanged {
+                checked: Vec::new(),
+                unchecked: vec![node_id],
+            }
+        } else {
+            EngineResponse::CheckedChanged {
+                checked: vec![node_id],
+                unchecked: Vec::new(),
+            }
+        })
+    }
+
+    /// Checks a radio button clicked by the user, unchecking the other radio buttons of its group.
+    ///
+    /// # Arguments
+    /// * `group_name` - The `name` of the clicked radio button.
+    /// * `node_id` - The node of the clicked radio button.
+    /// * `document` - The document the radio button belongs to, used to find the other radio buttons of its group.
+    ///
+    /// # Returns
+    /// The radio button to check and the other members of its group to uncheck, or an error if the node is not an
+    /// enabled radio button named `group_name`.
+    pub fn select_radio(
+        group_name: &str,
+        node_id: NodeId,
+        document: &DocumentRoot,
+    ) -> Result<EngineResponse, CoreError> {
+        let node = document
+            .get_node(&node_id)
+            .ok_or(InputError::NotFound(node_id))?;
+
+        let Some(element) = node
+            .data
+            .as_element()
+            .filter(|element| CheckableKind::from_element(element) == Some(CheckableKind::Radio))
+        else {
+            return Err(InputError::NotARadioButton(node_id).into());
+        };
+
+        if element.get_attribute("name").unwrap_or_default() != group_name {
+            return Err(InputError::NotInGroup {
+                node_id,
+                group: group_name.to_string(),
+            }
+            .into());
+        }
+
+        if element.has_attribute("disabled") {
+            return Err(InputError::Disabled(node_id).into());
+        }
+
+        Ok(EngineResponse::CheckedChanged {
+            checked: vec![node_id],
+            unchecked: radio_group(node, document)
+                .into_iter()
+                .filter(|id| *id != node_id)
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use html_dom::{HtmlTag, Tag};
+    use html_parser::{HtmlStreamParser, ParserState};
+
+    use super::*;
+
+    fn parse(html: &str) -> DocumentRoot {
+        let mut parser = HtmlStreamParser::simple(Cursor::new(html));
+
+        loop {
+            if let ParserState::Completed(result) = parser.step().unwrap() {
+                return result.dom_tree;
+            }
+        }
+    }
+
+    fn inputs(dom: &DocumentRoot) -> Vec<NodeId> {
+        dom.nodes
+            .iter()
+            .filter(|node| {
+                node.data
+                    .as_element()
+                    .is_some_and(|element| element.tag == Tag::Html(HtmlTag::Input))
+            })
+            .map(|node| node.id)
+            .collect()
+    }
+
+    #[test]
+    fn test_toggle_checkbox_flips_checked() {
+        let dom = parse(r#"<input type="checkbox" checked><input type="checkbox"><input type="checkbox" disabled>"#);
+        let ids = inputs(&dom);
+        let element = |index: usize| dom[ids[index]].data.as_element().unwrap();
+
+        assert!(matches!(
+            Browser::toggle_checkbox(ids[0], element(0)),
+            Ok(EngineResponse::CheckedChanged { checked, unchecked }) if checked.is_empty() && unchecked == [ids[0]]
+        ));
+        assert!(matches!(
+            Browser::toggle_checkbox(ids[1], element(1)),
+            Ok(EngineResponse::CheckedChanged { checked, unchecked }) if checked == [ids[1]] && unchecked.is_empty()
+        ));
+        assert!(Browser::toggle_checkbox(ids[2], element(2)).is_err());
+    }
+
+    #[test]
+    fn test_control_of_stale_node() {
+        let dom = parse("<p>text</p><input>");
+        let ids = inputs(&dom);
+        let text = dom
+            .nodes
+            .iter()
+            .find(|node| node.data.as_text().is_some())
+            .unwrap()
+            .id;
+
+        assert!(Browser::control(&dom, ids[0]).is_ok());
+        assert!(matches!(
+            Browser::control(&dom, text),
+            Err(CoreError::Input(InputError::NotFound(node_id))) if node_id == text
+        ));
+        assert!(Browser::control(&dom, NodeId(dom.nodes.len())).is_err());
+    }
+
+    #[test]
+    fn test_select_radio_unchecks_group_in_same_form() {
+        let dom = parse(
+            r#"<form><input type="radio" name="size" checked><input type="radio" name="size">
+            <input type="radio" name="color"></form><input type="radio" name="size">"#,
+        );
+        let ids = inputs(&dom);
+
+        assert!(matches!(
+            Browser::select_radio("size", ids[1], &dom),
+            Ok(EngineResponse::CheckedChanged { checked, unchecked }) if checked == [ids[1]] && unchecked == [ids[0]]
+        ));
+        assert!(Browser::select_radio("color", ids[1], &dom).is_err());
+    }
+
+    #[test]
+    fn test_range_value_snaps_to_step() {
+        let dom = parse(
+            r#"<input type="range"><input type="range" min="1" max="2" step="0.1">
+            <input type="range" max="10" step="4"><input type="range" disabled>"#,
+        );
+        let ids = inputs(&dom);
+        let update = |index: usize, value: &str| match Browser::update_input_value(
+            ids[index],
+            dom[ids[index]].data.as_element().unwrap(),
+            value,
+        ) {
+            Ok(EngineResponse::InputValueUpdated { value, .. }) => Some(value),
+            _ => None,
+        };
+
+        assert_eq!(update(0, "42.6").as_deref(), Some("43"));
+        assert_eq!(update(0, "250").as_deref(), Some("100"));
+        assert_eq!(update(1, "1.34").as_deref(), Some("1.3"));
+        assert_eq!(update(2, "10").as_deref(), Some("8"));
+        assert_eq!(update(0, "abc"), None);
+        assert_eq!(update(3, "10"), None);
+    }
+}
@@ -0,0 +1,45 @@
+use css_cssom::CSSStyleSheet;
+use tracing::debug;
+use url::Url;
+
+use crate::{
+    Browser, EngineResponse, NavigationType,
+    errors::{CoreError, NavigationError},
+    link::LinkTarget,
+};
+
+impl Browser {
+    /// Follows a link clicked on a page, in the browsing context chosen by the frontend for its target.
+    ///
+    /// A page opened in a new tab never gets a reference to the page it was opened from, whether or not the link has
+    /// `rel="noopener"`, so the two pages can not reach each other through the JavaScript bridge. With
+    /// `rel="noreferrer"`, the navigation request also leaves out the `Referer` header.
+    ///
+    /// # Arguments
+    /// * `href` - The `href` attribute of the link, resolved against `page_url`.
+    /// * `page_url` - The URL of the page the link is on.
+    /// * `target` - The browsing context the link is followed in.
+    /// * `noreferrer` - Whether the link has `rel="noreferrer"`.
+    /// * `stylesheets` - The stylesheets the page is styled with before its own.
+    pub async fn open_link(
+        &self,
+        href: &str,
+        page_url: &Url,
+        target: &LinkTarget,
+        noreferrer: bool,
+        stylesheets: Vec<CSSStyleSheet>,
+    ) -> Result<EngineResponse, CoreError> {
+        let url = page_url
+            .join(href.trim())
+            .map_err(|_| NavigationError::Forbidden(href.to_string()))?;
+
+        debug!(%url, ?target, noreferrer, "Following link");
+
+        let referrer = (!noreferrer).then_some(page_url);
+        let navigation = self
+            .navigate(url.as_str(), referrer, true, stylesheets)
+            .await?;
+
+        Ok(navigation.into_response(NavigationType::Normal))
+    }
+}
@@ -1,17 +1,22 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
 
-use http::HeaderMap;
+use http::{HeaderMap, header::CONTENT_TYPE};
 use tokio::task::JoinHandle;
-use tracing::{Instrument, debug, warn};
+use tracing::{Instrument, debug, info, trace, warn};
 use url::Url;
 
 use cookies::CookieJar;
-use css_cssom::{CSSStyleSheet, StylesheetOrigin};
-use html_parser::{BlockedReason, HtmlStreamParser, ParserState, ResourceType, Script};
+use css_cssom::CSSStyleSheet;
+use html_dom::extract_metadata;
+use html_parser::{
+    BlockedReason, HtmlStreamParser, ParserState, ResourceHintKind, ResourceType, Script, decode_document,
+};
 use http_cache::{block::MAX_BLOCK_SIZE, http::HttpCache};
 use http_fetch::{
     client::HttpClient,
+    disposition::ContentDisposition,
     errors::{FetchError, NetworkError},
+    handle::ResponseHandle,
     handles::LocalHandle,
     request::fetch,
 };
@@ -20,74 +25,103 @@ use http_types::{
     properties::{Destination, RequestMode},
     request::Request,
 };
-use io::{Readable, paths::AppPaths};
+use io::{
+    Readable,
+    paths::{AppPaths, create_paths, get_download_path},
+    resource::Priority,
+};
 
 use crate::{
-    Browser,
-    context::page::{Favicon, PageMetadata},
+    Browser, EngineResponse, NavigationType,
+    commands::style::StyleLoader,
+    context::{
+        page::{FaviconLink, PageMetadata},
+        timing::PageTimings,
+    },
+    download::{Download, create_download_file, download_filename},
     errors::NavigationError,
-    navigation::ScriptExecutor,
+    scheduler::ResourceScheduler,
 };
 
 use crate::context::{collector::TabCollector, page::Document};
 
+/// The outcome of a navigation.
+#[derive(Debug)]
+pub enum Navigation {
+    /// The response was parsed into a page to show.
+    Page(Document, PageMetadata),
+
+    /// The response is saved to disk instead of being shown, as asked by its `Content-Disposition` header.
+    Download(Download),
+}
+
+impl Navigation {
+    /// The response of the engine to the command that started the navigation.
+    #[must_use]
+    pub fn into_response(self, navigation_type: NavigationType) -> EngineResponse {
+        match self {
+            Self::Page(page, metadata) => EngineResponse::NavigateSuccess(page, metadata, navigation_type),
+            Self::Download(download) => EngineResponse::DownloadStarted(download),
+        }
+    }
+}
+
 impl Browser {
     /// Navigates the specified tab to the given URL, fetching and parsing the content.
-    /// Executes any scripts and processes stylesheets found during parsing.
+    /// Executes any scripts and processes stylesheets found during parsing. Responses with a
+    /// `Content-Disposition: attachment` header are saved to the download directory instead.
+    ///
+    /// The `Referer` header of the request is derived from `referrer`, the URL of the page the navigation started
+    /// from, following the referrer policy of the request. It is left out when there is no such page, such as for URLs
+    /// typed into the address bar.
+    ///
+    /// `user_activation` tells the server, through `Sec-Fetch-User`, that the user started the navigation, such as by
+    /// following a link. Reloads and history navigations are not user activated.
     pub async fn navigate(
         &self,
         url: &str,
+        referrer: Option<&Url>,
+        user_activation: bool,
         mut stylesheets: Vec<CSSStyleSheet>,
-    ) -> Result<(Document, PageMetadata), NavigationError> {
+    ) -> Result<Navigation, NavigationError> {
+        let mut timings = PageTimings::start();
         let client = self.http_client();
         let headers = Arc::new(self.profile().config().headers().clone());
         let cookie_jar = self.profile().cookie_jar();
 
-        let navigation_request = Request::builder(url)
-            .destination(Destination::Document)
-            .request_mode(RequestMode::Navigate)
-            .build();
+        let (request_url, response_handle) = self.fetch_document(url, referrer, user_activation).await?;
+        timings.first_byte = Some(Instant::now());
 
-        let request_url = navigation_request.context.url.clone();
+        info!(
+            navigation.url = %request_url,
+            navigation.status = response_handle.head().status_code.as_u16(),
+            navigation.protocol = response_handle.protocol().as_ref(),
+            "Received document {}", request_url
+        );
 
-        let response_handle = if navigation_request.context.url.scheme() != "http"
-            && navigation_request.context.url.scheme() != "https"
+        if let Some(disposition) = ContentDisposition::from_headers(&response_handle.head().headers)
+            && disposition.attachment
         {
-            match navigation_request.read(&self.profile().dirs().into(), Some(MAX_BLOCK_SIZE)) {
-                Ok(data) => LocalHandle::from(data).into(),
-                Err(error) => {
-                    return Err(NavigationError::Resource(error));
-                }
-            }
-        } else {
-            match fetch(
-                None,
-                navigation_request,
-                client,
-                &headers,
-                &self.profile().dirs().into(),
-                cookie_jar,
-                self.profile().http_cache(),
-            )
-            .await
-            {
-                Ok(response_handle) => response_handle,
-                Err(error) => {
-                    return Err(NavigationError::Request {
-                        source: error,
-                        url: url.to_string(),
-                    });
-                }
-            }
-        };
-
-        if !response_handle.head().status_code.is_success() {
-            return Err(NavigationError::Request {
-                source: FetchError::Network(NetworkError::HttpStatus(response_handle.head().status_code)),
-                url: url.to_string(),
-            });
+            let directory = get_download_path()
+                .ok_or_else(|| NavigationError::Download("no download directory available".to_string()))?;
+            create_paths(&directory).map_err(|error| NavigationError::Download(error.to_string()))?;
+
+            let filename = download_filename(&request_url, disposition.filename);
+            let (path, file) = create_download_file(&directory, &filename)
+                .map_err(|error| NavigationError::Download(error.to_string()))?;
+            return Ok(Navigation::Download(
+                self.downloads
+                    .start(request_url, path, file, response_handle),
+            ));
         }
 
+        let content_type = response_handle
+            .head()
+            .headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+
         let response = match response_handle.response().await {
             Ok(resp) => resp,
             Err(error) => {
@@ -106,13 +140,24 @@ impl Browser {
             });
         };
 
-        let mut favicon = Favicon::default();
+        // The resources blocking the first paint are scheduled per navigation, so that the images and fonts other
+        // pages are loading through the shared scheduler never hold them up.
+        let critical_scheduler = ResourceScheduler::default();
         let mut style_handles: Vec<JoinHandle<Option<CSSStyleSheet>>> = Vec::new();
-        let mut favicon_handle: Option<JoinHandle<Option<Vec<u8>>>> = None;
+        let mut favicon_link: Option<FaviconLink> = None;
+
+        let document = decode_document(&body.0, content_type.as_deref());
+        if let Some(error) = &document.error {
+            debug!(%error, "Decoded {} as {} with replacement characters", request_url, document.encoding);
+        }
 
-        let reader: &[u8] = &body.0;
-        let mut parser = HtmlStreamParser::new(reader).with_collector(TabCollector::default());
+        let reader: &[u8] = document.text.as_bytes();
+        let mut parser = HtmlStreamParser::new(reader).with_collector(TabCollector {
+            skip_lazy_images: self.profile().config().data_saver(),
+            ..TabCollector::default()
+        });
 
+        let parse_start = Instant::now();
         let result = loop {
             let state = parser.step().map_err(|e| NavigationError::Parsing {
                 url: url.to_string(),
@@ -131,7 +176,11 @@ impl Browser {
                                         source: e,
                                     })?;
 
-                                    self.execute_script(&script_content);
+                                    if let Some(dom) = parser.dom_mut()
+                                        && let Err(error) = self.script_bridge.evaluate(&script_content, dom)
+                                    {
+                                        debug!(%error, "Failed to evaluate inline script");
+                                    }
                                 }
                                 Script::External { .. } => {
                                     // TODO: external script and async/defer handling
@@ -147,11 +196,22 @@ impl Browser {
                                 source: e,
                             })?;
 
-                            let current_span = tracing::Span::current();
-                            let handle = tokio::task::spawn_blocking(move || {
-                                let _span = current_span.enter();
-                                Some(CSSStyleSheet::from_css(&css_content, StylesheetOrigin::Author, true))
-                            });
+                            let loader = StyleLoader::new(
+                                self.profile().dirs().into(),
+                                &request_url,
+                                self.profile().http_cache(),
+                                client.box_clone(),
+                                Arc::clone(&headers),
+                                cookie_jar,
+                            );
+                            let permit = critical_scheduler.acquire(Priority::Critical);
+                            let handle = tokio::spawn(
+                                async move {
+                                    let _permit = permit.await;
+                                    loader.load_inline(css_content).await
+                                }
+                                .in_current_span(),
+                            );
                             style_handles.push(handle);
                         }
                         BlockedReason::WaitingForResource(resource_type, href, metadata) => match resource_type {
@@ -164,15 +224,25 @@ impl Browser {
                                             url: href.clone(),
                                         })?;
 
-                                let handle = Self::spawn_style_fetch_and_parse(
+                                let style_load = Self::fetch_and_parse_style(
                                     self.profile().dirs().into(),
-                                    relative_url,
+                                    relative_url.clone(),
                                     &request_url,
                                     self.profile().http_cache(),
                                     client.box_clone(),
                                     Arc::clone(&headers),
                                     cookie_jar,
                                 );
+                                let style_requests = Arc::clone(&self.style_requests);
+                                let permit = critical_scheduler.acquire(Priority::Critical);
+
+                                let handle = tokio::spawn(
+                                    async move {
+                                        let _permit = permit.await;
+                                        style_requests.load(&relative_url, style_load).await
+                                    }
+                                    .in_current_span(),
+                                );
                                 style_handles.push(handle);
                             }
                             ResourceType::Favicon => {
@@ -184,91 +254,46 @@ impl Browser {
                                             url: href.clone(),
                                         })?;
 
-                                favicon.content_type = metadata.content_type;
-                                favicon.size = metadata.sizes;
-
-                                let page_url = request_url.clone();
-                                let client_clone = client.box_clone();
-                                let headers_clone = Arc::clone(&headers);
-                                let http_cache = self.profile().http_cache().clone();
-                                let dirs = AppPaths::from(self.profile().dirs()).clone();
-                                let cookie_jar = cookie_jar.clone();
-
-                                let handle = tokio::spawn(
-                                    async move {
-                                        let request = Request::builder(relative_url.as_str())
-                                            .destination(Destination::Image)
-                                            .request_mode(RequestMode::Cors)
-                                            .build();
-
-                                        let response_handle =
-                                            if relative_url.scheme() != "http" && relative_url.scheme() != "https" {
-                                                match request.read(&dirs, Some(MAX_BLOCK_SIZE)) {
-                                                    Ok(data) => LocalHandle::from(data).into(),
-                                                    Err(error) => {
-                                                        debug!(%error, "Failed to load favicon {}", relative_url);
-                                                        return None;
-                                                    }
-                                                }
-                                            } else {
-                                                match fetch(
-                                                    Some(&page_url),
-                                                    request,
-                                                    client_clone.as_ref(),
-                                                    &headers_clone,
-                                                    &dirs,
-                                                    &cookie_jar,
-                                                    &http_cache,
-                                                )
-                                                .await
-                                                {
-                                                    Ok(response_handle) => response_handle,
-                                                    Err(error) => {
-                                                        debug!(%error, "Failed to fetch favicon {}", relative_url);
-                                                        return None;
-                                                    }
-                                                }
-                                            };
-
-                                        if !response_handle.head().status_code.is_success() {
-                                            debug!(
-                                                "Failed to fetch favicon {}: status code {}",
-                                                relative_url,
-                                                response_handle.head().status_code
-                                            );
-                                            return None;
-                                        }
-
-                                        match response_handle.response().await {
-                                            Ok(response) => {
-                                                if let Some(complete_response) =
-                                                    response.into_complete(2 * 1024 * 1024).await
-                                                {
-                                                    Some(complete_response.body.0.into())
-                                                } else {
-                                                    debug!("Empty body for favicon {}", relative_url);
-                                                    None
-                                                }
-                                            }
-                                            Err(network_error) => {
-                                                debug!("Failed to fetch favicon {}: {}", relative_url, network_error);
-                                                None
-                                            }
-                                        }
-                                    }
-                                    .in_current_span(),
-                                );
-
-                                favicon_handle = Some(handle);
+                                favicon_link = Some(FaviconLink {
+                                    url: relative_url,
+                                    size: metadata.sizes,
+                                    content_type: metadata.content_type,
+                                });
+                            }
+                            other => {
+                                debug!("Ignoring blocking resource of type {:?}: {}", other, href);
+                            }
+                        },
+                        BlockedReason::ResourceHint {
+                            kind,
+                            href,
+                            as_type,
+                        } => match request_url.join(&href) {
+                            Ok(_)
+                                if self.profile().config().data_saver()
+                                    && matches!(
+                                        kind,
+                                        ResourceHintKind::Preload
+                                            | ResourceHintKind::ModulePreload
+                                            | ResourceHintKind::Prefetch
+                                    ) =>
+                            {
+                                debug!("Ignoring {:?} hint for {} with the data saver enabled", kind, href);
+                            }
+                            Ok(hint_url) => {
+                                self.spawn_resource_hint(kind, hint_url, as_type, &request_url, Arc::clone(&headers));
+                            }
+                            Err(error) => {
+                                debug!(%error, "Invalid resource hint URL {}", href);
                             }
                         },
                         BlockedReason::SVGContent { data } => {
-                            let _svg_content = data.map_err(|e| NavigationError::Parsing {
+                            // The parser keeps the markup as the text of the `<svg>` element, which is rasterized
+                            // once the page is displayed.
+                            data.map_err(|e| NavigationError::Parsing {
                                 url: url.to_string(),
                                 source: e,
                             })?;
-
-                            // TODO: Process SVG content
                         }
                         BlockedReason::MathML { data } => {
                             let _mathml_content = data.map_err(|e| NavigationError::Parsing {
@@ -281,11 +306,20 @@ impl Browser {
                     }
                 }
                 ParserState::Completed(build_result) => {
+                    timings.dom_content_loaded = Some(Instant::now());
+                    debug!(
+                        navigation.url = %request_url,
+                        parse.duration_ms = parse_start.elapsed().as_secs_f64() * 1000.0,
+                        "Parsed document {}", request_url
+                    );
                     break build_result;
                 }
             }
         };
 
+        critical_scheduler.first_paint_ready().await;
+        trace!("First paint ready for {}, the remaining resources load in the background", request_url);
+
         for handle in style_handles {
             match handle.await {
                 Ok(Some(stylesheet)) => {
@@ -299,33 +333,102 @@ impl Browser {
         }
 
         let result_metadata = result.metadata.unwrap();
+        let document = extract_metadata(&result.dom_tree, Some(&request_url));
         let mut page_metadata = PageMetadata {
             url: request_url,
             title: result_metadata
                 .title
                 .unwrap_or_else(|| "Untitled".to_string()),
+            favicon_link,
             favicon: None,
+            viewport: result_metadata.viewport,
+            document,
+            timings,
         };
 
-        if let Some(favicon_handle) = favicon_handle {
-            match favicon_handle.await {
-                Ok(Some(favicon_bytes)) => {
-                    favicon.data = favicon_bytes;
-                    page_metadata.favicon = Some(favicon);
+        page_metadata.timings.fully_loaded = Some(Instant::now());
+
+        let document = Document::new(result.dom_tree, result_metadata.images, stylesheets)
+            .with_lazy_images(result_metadata.lazy_images);
+        self.documents.insert(document.id(), document.dom().clone());
+
+        Ok(Navigation::Page(document, page_metadata))
+    }
+
+    /// Fetches the document at a URL for a navigation, reading URLs with schemes other than `http` and `https` from
+    /// disk.
+    ///
+    /// # Arguments
+    /// * `user_activation` - Whether the user started the navigation.
+    ///
+    /// # Returns
+    /// The parsed URL of the request and the response, whose body has not been read yet.
+    ///
+    /// # Errors
+    /// * If the request fails or the response status is not successful.
+    pub(crate) async fn fetch_document(
+        &self,
+        url: &str,
+        referrer: Option<&Url>,
+        user_activation: bool,
+    ) -> Result<(Url, Box<dyn ResponseHandle>), NavigationError> {
+        let client = self.http_client();
+        let headers = self.profile().config().headers();
+        let cookie_jar = self.profile().cookie_jar();
+
+        let navigation_request = Request::builder(url)
+            .destination(Destination::Document)
+            .request_mode(RequestMode::Navigate)
+            .user_activation(user_activation)
+            .build();
+
+        let request_url = navigation_request.context.url.clone();
+
+        let response_handle = if navigation_request.context.url.scheme() != "http"
+            && navigation_request.context.url.scheme() != "https"
+        {
+            match navigation_request.read(&self.profile().dirs().into(), Some(MAX_BLOCK_SIZE)) {
+                Ok(data) => LocalHandle::from(data).into(),
+                Err(error) => {
+                    return Err(NavigationError::Resource(error));
                 }
-                Ok(None) => {}
-                Err(e) => {
-                    warn!("Favicon fetch task panicked: {}", e);
+            }
+        } else {
+            match fetch(
+                referrer,
+                navigation_request,
+                client,
+                headers,
+                &self.profile().dirs().into(),
+                cookie_jar,
+                self.profile().http_cache(),
+            )
+            .await
+            {
+                Ok(response_handle) => response_handle,
+                Err(error) => {
+                    return Err(NavigationError::Request {
+                        source: error,
+                        url: url.to_string(),
+                    });
                 }
             }
+        };
+
+        if !response_handle.head().status_code.is_success() {
+            return Err(NavigationError::Request {
+                source: FetchError::Network(NetworkError::HttpStatus(response_handle.head().status_code)),
+                url: url.to_string(),
+            });
         }
 
-        Ok((Document::new(result.dom_tree, result_metadata.images, stylesheets), page_metadata))
+        Ok((request_url, response_handle))
     }
 
-    /// Spawns a task to fetch and parse a stylesheet from the given URL, returning a handle to the resulting stylesheet.
-    /// The task will handle cookies and headers appropriately, and will return `None` if fetching or parsing fails.
-    fn spawn_style_fetch_and_parse(
+    /// Creates a future that fetches and parses a stylesheet from the given URL along with the stylesheets it imports,
+    /// to be spawned by the caller. The future will handle cookies and headers appropriately, and will return `None` if
+    /// fetching or parsing fails.
+    fn fetch_and_parse_style(
         paths: AppPaths,
         style_url: Url,
         page_url: &Url,
@@ -333,79 +436,9 @@ impl Browser {
         client: Box<dyn HttpClient>,
         headers: Arc<HeaderMap>,
         cookie_jar: &CookieJar,
-    ) -> JoinHandle<Option<CSSStyleSheet>> {
-        let page_url = page_url.clone();
-        let cache = cache.clone();
-        let cookie_jar = cookie_jar.clone();
-
-        tokio::spawn(
-            async move {
-                let is_http = style_url.scheme() == "http" || style_url.scheme() == "https";
-
-                let request = Request::builder_url(style_url)
-                    .request_mode(RequestMode::Cors)
-                    .destination(Destination::Style)
-                    .build();
-
-                let response_handle = if !is_http {
-                    match request.read(&paths, Some(MAX_BLOCK_SIZE)) {
-                        Ok(data) => LocalHandle::from(data).into(),
-                        Err(error) => {
-                            debug!(%error, "Failed to load stylesheet locally");
-                            return None;
-                        }
-                    }
-                } else {
-                    match fetch(Some(&page_url), request, client.as_ref(), &headers, &paths, &cookie_jar, &cache).await
-                    {
-                        Ok(response) => response,
-                        Err(error) => {
-                            debug!(%error, "Failed to fetch stylesheet");
-                            return None;
-                        }
-                    }
-                };
+    ) -> impl Future<Output = Option<CSSStyleSheet>> + Send + 'static {
+        let loader = StyleLoader::new(paths, page_url, cache, client, headers, cookie_jar);
 
-                if !response_handle.head().status_code.is_success() {
-                    debug!("Failed to fetch stylesheet: status code {}", response_handle.head().status_code);
-                    return None;
-                }
-
-                let response = match response_handle.response().await {
-                    Ok(resp) => resp,
-                    Err(error) => {
-                        debug!(%error, "Failed to read body for stylesheet");
-                        return None;
-                    }
-                };
-
-                let body = match response.body.into_complete(MAX_BLOCK_SIZE as usize).await {
-                    Some(b) => b,
-                    None => {
-                        debug!("Empty body for stylesheet");
-                        return None;
-                    }
-                };
-
-                let body_bytes = body.0.to_vec();
-
-                let current_span = tracing::Span::current();
-                match tokio::task::spawn_blocking(move || {
-                    let _span = current_span.enter();
-
-                    let css_str = String::from_utf8_lossy(&body_bytes);
-                    CSSStyleSheet::from_css(&css_str, StylesheetOrigin::Author, true)
-                })
-                .await
-                {
-                    Ok(stylesheet) => Some(stylesheet),
-                    Err(error) => {
-                        warn!(%error, "CSS parse task panicked for");
-                        None
-                    }
-                }
-            }
-            .in_current_span(),
-        )
+        async move { loader.load(style_url).await }
     }
 }
@@ -0,0 +1,76 @@
+use html_dom::{Element, NodeId};
+
+use crate::{Browser, EngineResponse};
+
+impl Browser {
+    /// Resizes an element dragged by its resize handle, by setting its width and height in its `style` attribute.
+    ///
+    /// # Arguments
+    /// * `node_id` - The node of the resized element.
+    /// * `element` - The resized element, whose existing inline style is kept.
+    /// * `new_width` - The new width of the content box in pixels, or `None` if the width was not resized.
+    /// * `new_height` - The new height of the content box in pixels, or `None` if the height was not resized.
+    ///
+    /// # Returns
+    /// The new value of the `style` attribute of the element.
+    #[must_use]
+    pub fn resize_element(
+        node_id: NodeId,
+        element: &Element,
+        new_width: Option<f64>,
+        new_height: Option<f64>,
+    ) -> EngineResponse {
+        let style = resized_style(element.get_attribute("style").unwrap_or_default(), new_width, new_height);
+
+        EngineResponse::ElementResized { node_id, style }
+    }
+}
+
+/// Replaces the `width` and `height` declarations of an inline style with the resized dimensions, keeping the other
+/// declarations in their order.
+fn resized_style(style: &str, new_width: Option<f64>, new_height: Option<f64>) -> String {
+    let replaced = |declaration: &str| {
+        let property = declaration
+            .split_once(':')
+            .map_or("", |(property, _)| property.trim());
+
+        (new_width.is_some() && property.eq_ignore_ascii_case("width"))
+            || (new_height.is_some() && property.eq_ignore_ascii_case("height"))
+    };
+
+    let mut declarations: Vec<String> = style
+        .split(';')
+        .map(str::trim)
+        .filter(|declaration| !declaration.is_empty() && !replaced(declaration))
+        .map(String::from)
+        .collect();
+
+    if let Some(width) = new_width {
+        declarations.push(format!("width: {}px", width.max(0.0).round()));
+    }
+
+    if let Some(height) = new_height {
+        declarations.push(format!("height: {}px", height.max(0.0).round()));
+    }
+
+    declarations.join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resized_style_replaces_dimensions() {
+        assert_eq!(
+            resized_style("color: red; width: 10px;HEIGHT:5px", Some(120.4), Some(80.0)),
+            "color: red; width: 120px; height: 80px"
+        );
+    }
+
+    #[test]
+    fn test_resized_style_keeps_unresized_dimension() {
+        assert_eq!(resized_style("height: 5px", Some(40.0), None), "height: 5px; width: 40px");
+        assert_eq!(resized_style("", None, Some(30.0)), "height: 30px");
+    }
+}
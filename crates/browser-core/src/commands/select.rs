@@ -0,0 +1,153 @@
+use html_dom::{DocumentRoot, NodeId, SelectElement};
+
+use crate::{
+    Browser, EngineResponse,
+    errors::{CoreError, SelectError},
+};
+
+impl Browser {
+    /// Selects an option of a `<select>` dropdown chosen by the user.
+    ///
+    /// # Arguments
+    /// * `select_node_id` - The node of the `<select>` element.
+    /// * `option_index` - The index of the chosen option among the options of the select, in tree order.
+    /// * `document` - The document the select belongs to, used to look up its options.
+    ///
+    /// # Returns
+    /// The options of the select and the one to mark as `selected`, or an error if the node is not a select or the
+    /// option does not exist or is disabled.
+    pub fn select_option(
+        select_node_id: NodeId,
+        option_index: usize,
+        document: &DocumentRoot,
+    ) -> Result<EngineResponse, CoreError> {
+        let select = document
+            .get_node(&select_node_id)
+            .and_then(|node| SelectElement::from_node(node, document))
+            .ok_or(SelectError::NotASelect(select_node_id))?;
+
+        if select.disabled {
+            return Err(SelectError::Disabled(select_node_id).into());
+        }
+
+        let option = select
+            .options
+            .get(option_index)
+            .ok_or(SelectError::NoOption {
+                select: select_node_id,
+                index: option_index,
+            })?;
+
+        if option.disabled {
+            return Err(SelectError::OptionDisabled {
+                select: select_node_id,
+                index: option_index,
+            }
+            .into());
+        }
+
+        Ok(EngineResponse::OptionSelected {
+            select_node_id,
+            option_node_ids: select.options.iter().map(|option| option.node_id).collect(),
+            selected_node_id: option.node_id,
+            value: option.value.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use html_dom::{HtmlTag, Tag};
+    use html_parser::{HtmlStreamParser, ParserState};
+
+    use super::*;
+
+    fn parse(html: &str) -> DocumentRoot {
+        let mut parser = HtmlStreamParser::simple(Cursor::new(html));
+
+        loop {
+            if let ParserState::Completed(result) = parser.step().unwrap() {
+                return result.dom_tree;
+            }
+        }
+    }
+
+    fn select_id(dom: &DocumentRoot) -> NodeId {
+        dom.nodes
+            .iter()
+            .find(|node| {
+                node.data
+                    .as_element()
+                    .is_some_and(|element| element.tag == Tag::Html(HtmlTag::Select))
+            })
+            .map(|node| node.id)
+            .unwrap()
+    }
+
+    const HTML: &str = r#"<html><body><select name="fruit">
+        <option value="a">Apple
+        <optgroup label="Citrus" disabled><option>Lemon<option>Lime</optgroup>
+        <option selected>  Banana  split </select><p>After</p></body></html>"#;
+
+    #[test]
+    fn test_select_option_returns_chosen_option() {
+        let dom = parse(HTML);
+        let select_id = select_id(&dom);
+
+        let Ok(EngineResponse::OptionSelected {
+            select_node_id,
+            option_node_ids,
+            selected_node_id,
+            value,
+        }) = Browser::select_option(select_id, 3, &dom)
+        else {
+            panic!("expected the option to be selected");
+        };
+
+        assert_eq!(select_node_id, select_id);
+        assert_eq!(option_node_ids.len(), 4);
+        assert_eq!(selected_node_id, option_node_ids[3]);
+        assert_eq!(value, "Banana split");
+    }
+
+    #[test]
+    fn test_select_option_rejects_disabled_and_missing_options() {
+        let dom = parse(HTML);
+        let select_id = select_id(&dom);
+
+        assert!(matches!(
+            Browser::select_option(select_id, 1, &dom),
+            Err(CoreError::Select(SelectError::OptionDisabled { index: 1, .. }))
+        ));
+        assert!(matches!(
+            Browser::select_option(select_id, 4, &dom),
+            Err(CoreError::Select(SelectError::NoOption { index: 4, .. }))
+        ));
+    }
+
+    #[test]
+    fn test_select_closes_unclosed_options() {
+        let dom = parse(HTML);
+        let select_id = select_id(&dom);
+
+        let paragraph = dom
+            .nodes
+            .iter()
+            .find(|node| {
+                node.data
+                    .as_element()
+                    .is_some_and(|element| element.tag == Tag::Html(HtmlTag::P))
+            })
+            .unwrap();
+
+        assert_ne!(paragraph.parent, Some(select_id));
+        assert_eq!(
+            SelectElement::from_node(&dom[select_id], &dom)
+                .unwrap()
+                .value(),
+            "Banana split"
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use cookies::CookieFilter;
+use http_types::partition::StoragePartitionKey;
+use io::paths::AppPaths;
+use tracing::debug;
+use url::Url;
+
+use crate::{
+    Browser, EngineResponse,
+    errors::{CoreError, StorageError},
+};
+
+/// A kind of data the engine keeps per site, which can be cleared for an origin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageDataType {
+    /// The cookies set for the host of the origin or one of its subdomains.
+    Cookies,
+
+    /// The responses cached for pages of the origin, including those loaded from other origins.
+    Cache,
+
+    /// The pages of the origin in the browsing history. History is kept per tab by the frontend and never persisted,
+    /// so there is nothing for the engine to clear.
+    History,
+
+    /// The values entered into forms on pages of the origin. Form data is not stored by the engine, so there is
+    /// nothing to clear.
+    FormData,
+}
+
+impl Browser {
+    /// Clears the data stored for an origin, such as when the user chooses to forget a site.
+    ///
+    /// # Arguments
+    /// * `origin` - A URL of the origin whose data is cleared, only its scheme, host and port are used.
+    /// * `types` - The kinds of data to clear.
+    ///
+    /// # Returns
+    /// The number of cookies and cache entries that were removed.
+    ///
+    /// # Errors
+    /// * If the cached responses of the origin can not be removed from disk.
+    pub fn clear_origin_data(&self, origin: &Url, types: &[StorageDataType]) -> Result<EngineResponse, CoreError> {
+        let mut cookies = 0;
+        let mut cache_entries = 0;
+
+        if types.contains(&StorageDataType::Cookies)
+            && let Some(host) = origin.host_str()
+        {
+            cookies = self
+                .profile()
+                .cookie_jar()
+                .delete_matching(&CookieFilter::Domain(host.to_string()));
+        }
+
+        if types.contains(&StorageDataType::Cache) {
+            let paths = AppPaths::from(self.profile().dirs());
+
+            cache_entries = self
+                .profile()
+                .http_cache()
+                .clear_origin(&paths, &StoragePartitionKey::from_url(origin))
+                .map_err(StorageError::from)?;
+        }
+
+        debug!(%origin, cookies, cache_entries, "Cleared site data");
+
+        Ok(EngineResponse::SiteDataCleared {
+            cookies,
+            cache_entries,
+        })
+    }
+}
@@ -0,0 +1,458 @@
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use http::HeaderMap;
+use tokio::sync::OnceCell;
+use tracing::{debug, warn};
+use url::Url;
+
+use cookies::CookieJar;
+use css_cssom::{CSSAtRule, CSSStyleSheet, StylesheetOrigin};
+use http_cache::{block::MAX_BLOCK_SIZE, http::HttpCache};
+use http_fetch::{client::HttpClient, handles::LocalHandle, request::fetch};
+use http_types::{
+    properties::{Destination, RequestMode},
+    request::Request,
+};
+use io::{Readable, paths::AppPaths};
+
+/// The deepest a stylesheet can be nested in other stylesheets through `@import`, deeper imports are skipped.
+const MAX_IMPORT_DEPTH: usize = 16;
+
+/// The most `@import` rules followed while loading one stylesheet, so a stylesheet importing the same files many times
+/// over cannot make the loader fetch without end.
+const MAX_IMPORTS: usize = 256;
+
+/// Fetches and parses single stylesheets, without their imports.
+#[async_trait]
+trait StylesheetSource: Sync {
+    /// Fetches and parses the stylesheet at a URL.
+    ///
+    /// # Returns
+    /// The stylesheet, or `None` if fetching or parsing fails.
+    async fn fetch(&self, style_url: &Url) -> Option<CSSStyleSheet>;
+}
+
+/// Loads the stylesheets of a page together with the stylesheets they import with `@import`, using the HTTP client,
+/// headers, and cookies of the page.
+pub(super) struct StyleLoader {
+    paths: AppPaths,
+    page_url: Url,
+    cache: HttpCache,
+    client: Box<dyn HttpClient>,
+    headers: Arc<HeaderMap>,
+    cookie_jar: CookieJar,
+}
+
+impl StyleLoader {
+    pub(super) fn new(
+        paths: AppPaths,
+        page_url: &Url,
+        cache: &HttpCache,
+        client: Box<dyn HttpClient>,
+        headers: Arc<HeaderMap>,
+        cookie_jar: &CookieJar,
+    ) -> Self {
+        Self {
+            paths,
+            page_url: page_url.clone(),
+            cache: cache.clone(),
+            client,
+            headers,
+            cookie_jar: cookie_jar.clone(),
+        }
+    }
+
+    /// Fetches and parses the stylesheet at the given URL along with its imports.
+    ///
+    /// # Returns
+    /// The stylesheet with the rules of its imports before its own, or `None` if fetching or parsing fails.
+    pub(super) async fn load(&self, style_url: Url) -> Option<CSSStyleSheet> {
+        let mut stylesheet = self.fetch(&style_url).await?;
+
+        ImportLoad::new(self)
+            .load_imports(&mut stylesheet, &style_url, std::slice::from_ref(&style_url))
+            .await;
+
+        Some(stylesheet)
+    }
+
+    /// Parses the content of a `<style>` element along with its imports, which are relative to the page.
+    ///
+    /// # Returns
+    /// The stylesheet with the rules of its imports before its own, or `None` if parsing fails.
+    pub(super) async fn load_inline(&self, css: String) -> Option<CSSStyleSheet> {
+        let mut stylesheet = Self::parse(css).await?;
+
+        ImportLoad::new(self)
+            .load_imports(&mut stylesheet, &self.page_url, &[])
+            .await;
+
+        Some(stylesheet)
+    }
+
+    /// Parses a stylesheet on the blocking thread pool.
+    async fn parse(css: String) -> Option<CSSStyleSheet> {
+        let current_span = tracing::Span::current();
+        match tokio::task::spawn_blocking(move || {
+            let _span = current_span.enter();
+            CSSStyleSheet::from_css(&css, StylesheetOrigin::Author, true)
+        })
+        .await
+        {
+            Ok(stylesheet) => Some(stylesheet),
+            Err(error) => {
+                warn!(%error, "CSS parse task panicked for");
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StylesheetSource for StyleLoader {
+    async fn fetch(&self, style_url: &Url) -> Option<CSSStyleSheet> {
+        let is_http = style_url.scheme() == "http" || style_url.scheme() == "https";
+
+        let request = Request::builder_url(style_url.clone())
+            .request_mode(RequestMode::Cors)
+            .destination(Destination::Style)
+            .build();
+
+        let response_handle = if !is_http {
+            match request.read(&self.paths, Some(MAX_BLOCK_SIZE)) {
+                Ok(data) => LocalHandle::from(data).into(),
+                Err(error) => {
+                    debug!(%error, "Failed to load stylesheet locally");
+                    return None;
+                }
+            }
+        } else {
+            match fetch(
+                Some(&self.page_url),
+                request,
+                self.client.as_ref(),
+                &self.headers,
+                &self.paths,
+                &self.cookie_jar,
+                &self.cache,
+            )
+            .await
+            {
+                Ok(response) => response,
+                Err(error) => {
+                    debug!(%error, "Failed to fetch stylesheet");
+                    return None;
+                }
+            }
+        };
+
+        if !response_handle.head().status_code.is_success() {
+            debug!("Failed to fetch stylesheet: status code {}", response_handle.head().status_code);
+            return None;
+        }
+
+        let response = match response_handle.response().await {
+            Ok(resp) => resp,
+            Err(error) => {
+                debug!(%error, "Failed to read body for stylesheet");
+                return None;
+            }
+        };
+
+        let body = match response.body.into_complete(MAX_BLOCK_SIZE as usize).await {
+            Some(b) => b,
+            None => {
+                debug!("Empty body for stylesheet");
+                return None;
+            }
+        };
+
+        let body_bytes = body.0.to_vec();
+        debug!(
+            resource.url = %style_url,
+            "resource.type" = "style",
+            resource.size = body_bytes.len(),
+            "Loaded stylesheet {}", style_url
+        );
+
+        Self::parse(String::from_utf8_lossy(&body_bytes).into_owned()).await
+    }
+}
+
+/// The imports followed while loading one stylesheet, which share the stylesheets fetched so far and the number of
+/// imports that may still be followed.
+struct ImportLoad<'a> {
+    source: &'a dyn StylesheetSource,
+
+    /// The stylesheets fetched for the load, without their imports, so a stylesheet imported from several places is
+    /// only fetched once, even when the imports run at the same time.
+    fetched: Mutex<HashMap<Url, Arc<OnceCell<Option<CSSStyleSheet>>>>>,
+
+    /// How many more `@import` rules may be followed.
+    remaining: AtomicUsize,
+}
+
+impl<'a> ImportLoad<'a> {
+    fn new(source: &'a dyn StylesheetSource) -> Self {
+        Self {
+            source,
+            fetched: Mutex::new(HashMap::new()),
+            remaining: AtomicUsize::new(MAX_IMPORTS),
+        }
+    }
+
+    /// Fetches the stylesheets imported by a stylesheet, and their imports in turn, and inserts their rules before the
+    /// rules of the stylesheet, under the media query list and `supports()` condition of their `@import` rule. The
+    /// imports of a stylesheet are fetched at the same time, and inserted in the order of their rules.
+    ///
+    /// A stylesheet that imports one of the stylesheets it is itself imported by is skipped, so import cycles end,
+    /// while a stylesheet imported from several unrelated places is inserted for each of them. Imports nested deeper
+    /// than `MAX_IMPORT_DEPTH`, or after `MAX_IMPORTS` have been followed, are skipped.
+    ///
+    /// # Arguments
+    /// * `stylesheet` - The stylesheet whose imports are loaded.
+    /// * `base_url` - The URL the imports of the stylesheet are relative to.
+    /// * `ancestors` - The URLs of the stylesheet and of the stylesheets it is imported by.
+    fn load_imports<'b>(
+        &'b self,
+        stylesheet: &'b mut CSSStyleSheet,
+        base_url: &'b Url,
+        ancestors: &'b [Url],
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'b>> {
+        Box::pin(async move {
+            let imports: Vec<(Url, CSSAtRule)> = stylesheet
+                .import_rules()
+                .into_iter()
+                .filter_map(|import| {
+                    let href = import.import_url()?;
+                    match base_url.join(href) {
+                        Ok(url) => Some((url, import.clone())),
+                        Err(error) => {
+                            debug!(%error, href, "Invalid @import URL");
+                            None
+                        }
+                    }
+                })
+                .collect();
+
+            if imports.is_empty() {
+                return;
+            }
+
+            if ancestors.len() >= MAX_IMPORT_DEPTH {
+                debug!(%base_url, "Skipping imports nested too deeply");
+                return;
+            }
+
+            let loads = imports
+                .into_iter()
+                .filter(|(import_url, _)| {
+                    if ancestors.contains(import_url) {
+                        debug!(%import_url, "Skipping stylesheet that imports itself");
+                        return false;
+                    }
+
+                    if self
+                        .remaining
+                        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |remaining| remaining.checked_sub(1))
+                        .is_err()
+                    {
+                        debug!(%import_url, "Skipping import after too many imports");
+                        return false;
+                    }
+
+                    true
+                })
+                .map(|(import_url, import)| async move {
+                    let mut import_stylesheet = self.fetch(&import_url).await?;
+
+                    let mut import_ancestors = ancestors.to_vec();
+                    import_ancestors.push(import_url.clone());
+                    self.load_imports(&mut import_stylesheet, &import_url, &import_ancestors)
+                        .await;
+
+                    Some((import, import_stylesheet))
+                });
+
+            let imported = join_all(loads).await.into_iter().flatten().collect();
+            stylesheet.insert_imported(imported);
+        })
+    }
+
+    /// Fetches a stylesheet without its imports, or reuses it if it was already fetched for this load.
+    async fn fetch(&self, style_url: &Url) -> Option<CSSStyleSheet> {
+        let cell = Arc::clone(
+            self.fetched
+                .lock()
+                .unwrap()
+                .entry(style_url.clone())
+                .or_default(),
+        );
+
+        cell.get_or_init(|| self.source.fetch(style_url))
+            .await
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serves stylesheets from a map of URLs to CSS, counting the fetches of each URL.
+    struct MockSource {
+        stylesheets: HashMap<String, String>,
+        fetches: Mutex<HashMap<String, usize>>,
+    }
+
+    impl MockSource {
+        fn new(stylesheets: &[(&str, &str)]) -> Self {
+            Self {
+                stylesheets: stylesheets
+                    .iter()
+                    .map(|(url, css)| ((*url).to_string(), (*css).to_string()))
+                    .collect(),
+                fetches: Mutex::default(),
+            }
+        }
+
+        fn fetches(&self, url: &str) -> usize {
+            self.fetches
+                .lock()
+                .unwrap()
+                .get(url)
+                .copied()
+                .unwrap_or_default()
+        }
+
+        async fn load(&self, url: &str) -> CSSStyleSheet {
+            let url = Url::parse(url).unwrap();
+            let mut stylesheet = self.fetch(&url).await.unwrap();
+
+            ImportLoad::new(self)
+                .load_imports(&mut stylesheet, &url, std::slice::from_ref(&url))
+                .await;
+
+            stylesheet
+        }
+    }
+
+    #[async_trait]
+    impl StylesheetSource for MockSource {
+        async fn fetch(&self, style_url: &Url) -> Option<CSSStyleSheet> {
+            *self
+                .fetches
+                .lock()
+                .unwrap()
+                .entry(style_url.to_string())
+                .or_default() += 1;
+            tokio::task::yield_now().await;
+
+            let css = self.stylesheets.get(style_url.as_str())?;
+            Some(CSSStyleSheet::from_css(css, StylesheetOrigin::Author, false))
+        }
+    }
+
+    fn selectors(stylesheet: &CSSStyleSheet) -> Vec<String> {
+        stylesheet
+            .get_style_rules()
+            .iter()
+            .map(|rule| rule.selector_text().trim().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_imports_are_inserted_in_rule_order() {
+        let source = MockSource::new(&[
+            ("https://a.test/main.css", "@import 'b.css'; @import 'c.css'; .main {}"),
+            ("https://a.test/b.css", "@import 'd.css'; .b {}"),
+            ("https://a.test/c.css", ".c {}"),
+            ("https://a.test/d.css", ".d {}"),
+        ]);
+
+        let stylesheet = source.load("https://a.test/main.css").await;
+
+        assert_eq!(selectors(&stylesheet), [".d", ".b", ".c", ".main"]);
+    }
+
+    #[tokio::test]
+    async fn test_stylesheet_imported_twice_is_fetched_once() {
+        let source = MockSource::new(&[
+            ("https://a.test/main.css", "@import 'b.css'; @import 'c.css'; @import 'shared.css'; .main {}"),
+            ("https://a.test/b.css", "@import 'shared.css'; .b {}"),
+            ("https://a.test/c.css", "@import 'shared.css'; .c {}"),
+            ("https://a.test/shared.css", ".shared {}"),
+        ]);
+
+        let stylesheet = source.load("https://a.test/main.css").await;
+
+        assert_eq!(selectors(&stylesheet), [".shared", ".b", ".shared", ".c", ".shared", ".main"]);
+        assert_eq!(source.fetches("https://a.test/shared.css"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_import_cycles_end() {
+        let source = MockSource::new(&[
+            ("https://a.test/a.css", "@import 'b.css'; .a {}"),
+            ("https://a.test/b.css", "@import 'a.css'; .b {}"),
+        ]);
+
+        let stylesheet = source.load("https://a.test/a.css").await;
+
+        assert_eq!(selectors(&stylesheet), [".b", ".a"]);
+    }
+
+    #[tokio::test]
+    async fn test_import_depth_is_capped() {
+        let stylesheets: Vec<(String, String)> = (0..MAX_IMPORT_DEPTH + 4)
+            .map(|depth| {
+                (format!("https://a.test/{depth}.css"), format!("@import '{}.css'; .depth-{depth} {{}}", depth + 1))
+            })
+            .collect();
+        let stylesheets: Vec<(&str, &str)> = stylesheets
+            .iter()
+            .map(|(url, css)| (url.as_str(), css.as_str()))
+            .collect();
+        let source = MockSource::new(&stylesheets);
+
+        let stylesheet = source.load("https://a.test/0.css").await;
+
+        assert_eq!(selectors(&stylesheet).len(), MAX_IMPORT_DEPTH);
+        assert_eq!(source.fetches(&format!("https://a.test/{MAX_IMPORT_DEPTH}.css")), 0);
+    }
+
+    #[tokio::test]
+    async fn test_total_imports_are_capped() {
+        // Every stylesheet imports the next one twice, which doubles the imports at every level.
+        let stylesheets: Vec<(String, String)> = (0..MAX_IMPORT_DEPTH)
+            .map(|depth| {
+                let next = depth + 1;
+                (
+                    format!("https://a.test/{depth}.css"),
+                    format!("@import '{next}.css'; @import '{next}.css'; .depth-{depth} {{}}"),
+                )
+            })
+            .collect();
+        let stylesheets: Vec<(&str, &str)> = stylesheets
+            .iter()
+            .map(|(url, css)| (url.as_str(), css.as_str()))
+            .collect();
+        let source = MockSource::new(&stylesheets);
+
+        let stylesheet = source.load("https://a.test/0.css").await;
+
+        assert_eq!(selectors(&stylesheet).len(), MAX_IMPORTS + 1);
+        for depth in 1..MAX_IMPORT_DEPTH {
+            assert!(source.fetches(&format!("https://a.test/{depth}.css")) <= 1);
+        }
+    }
+}
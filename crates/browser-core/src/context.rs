@@ -1,3 +1,6 @@
 pub mod collector;
 pub mod history;
+pub mod lazy;
 pub mod page;
+pub mod timing;
+pub mod viewport;
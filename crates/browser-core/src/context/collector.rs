@@ -2,26 +2,74 @@ use std::collections::HashMap;
 
 use html_dom::{Collector, HtmlTag, NodeId, Tag, TagInfo};
 
+use crate::context::viewport::ViewportMeta;
+
 #[derive(Default)]
 pub struct TabCollector {
     /// The title of the tab, if available.
     pub title: Option<String>,
 
-    /// The URLs of images found in the document.
+    /// The URLs of images found in the document, including `<video>` posters, except the lazily loaded ones.
     pub images: HashMap<String, Vec<NodeId>>,
+
+    /// The viewport requested by the first `<meta name="viewport">` tag, if any.
+    pub viewport: Option<ViewportMeta>,
+
+    /// The URLs of images with `loading="lazy"`, which are only loaded once they are scrolled near the viewport.
+    pub lazy_images: HashMap<String, Vec<NodeId>>,
+
+    /// Whether images with `loading="lazy"` are left out entirely, so they are never loaded while the data saver is
+    /// enabled.
+    pub skip_lazy_images: bool,
 }
 
 impl Collector for TabCollector {
     fn collect(&mut self, tag: &TagInfo) {
         if *tag.tag == Tag::Html(HtmlTag::Img)
-            && let Some(src) = tag.attributes.as_ref().and_then(|attrs| attrs.get("src"))
+            && let Some(attributes) = tag.attributes.as_ref()
+            && let Some(src) = attributes.get("src")
+        {
+            let lazy = attributes
+                .get("loading")
+                .is_some_and(|loading| loading.trim().eq_ignore_ascii_case("lazy"));
+
+            if !lazy {
+                self.images
+                    .entry(src.clone())
+                    .or_default()
+                    .push(tag.node_id);
+            } else if !self.skip_lazy_images {
+                self.lazy_images
+                    .entry(src.clone())
+                    .or_default()
+                    .push(tag.node_id);
+            }
+        }
+
+        if *tag.tag == Tag::Html(HtmlTag::Video)
+            && let Some(poster) = tag
+                .attributes
+                .as_ref()
+                .and_then(|attrs| attrs.get("poster"))
+                .filter(|poster| !poster.trim().is_empty())
         {
             self.images
-                .entry(src.clone())
+                .entry(poster.clone())
                 .or_default()
                 .push(tag.node_id);
         }
 
+        if *tag.tag == Tag::Html(HtmlTag::Meta)
+            && self.viewport.is_none()
+            && let Some(attributes) = tag.attributes.as_ref()
+            && attributes
+                .get("name")
+                .is_some_and(|name| name.trim().eq_ignore_ascii_case("viewport"))
+            && let Some(content) = attributes.get("content")
+        {
+            self.viewport = Some(ViewportMeta::parse(content));
+        }
+
         if *tag.tag == Tag::Html(HtmlTag::Title)
             && self.title.is_none()
             && let Some(data) = tag.data
@@ -40,6 +40,16 @@ impl History {
         self.clear_forward_history();
     }
 
+    /// Takes back the page most recently added with [`History::add_back`], for a navigation that did not replace it,
+    /// such as one that turned out to be a download. The forward history cleared when the page was added stays
+    /// cleared.
+    pub fn take_back(&mut self) -> Option<(Document, PageMetadata)> {
+        let page = self.b_cache.pop()?;
+        let metadata = self.backward.pop()?;
+
+        Some((page, metadata))
+    }
+
     /// Adds a new page to the forward history, caching the page for quick retrieval when navigating forward.
     fn push_forward_entry(&mut self, page: Document, metadata: PageMetadata) {
         Self::push_cached_page(&mut self.f_cache, page);
@@ -122,15 +132,21 @@ impl Default for History {
 
 #[cfg(test)]
 mod tests {
+    use html_dom::DocumentMetadata;
     use url::Url;
 
     use super::*;
+    use crate::context::timing::PageTimings;
 
     fn page_metadata(index: usize) -> PageMetadata {
         PageMetadata {
             url: Url::parse(&format!("http://example.com/page{index}")).unwrap(),
             title: format!("Page {index}"),
+            favicon_link: None,
             favicon: None,
+            viewport: None,
+            document: DocumentMetadata::default(),
+            timings: PageTimings::start(),
         }
     }
 
@@ -163,6 +179,22 @@ mod tests {
         assert_eq!(forward_metadata.title, "Page 2");
     }
 
+    #[test]
+    fn test_take_back_restores_last_added_page() {
+        let mut history = History::new();
+
+        history.add_back(Document::blank(), page_metadata(1));
+        history.add_back(Document::blank(), page_metadata(2));
+
+        let (_, metadata) = history.take_back().unwrap();
+        assert_eq!(metadata.url.as_str(), "http://example.com/page2");
+        assert!(history.can_go_back());
+
+        let (_, metadata) = history.take_back().unwrap();
+        assert_eq!(metadata.url.as_str(), "http://example.com/page1");
+        assert!(history.take_back().is_none());
+    }
+
     #[test]
     fn test_new_navigation_clears_forward_history() {
         let mut history = History::new();
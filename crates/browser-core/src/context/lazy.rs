@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use html_dom::NodeId;
+
+/// How far below the bottom of the viewport a lazily loaded image starts loading, in CSS pixels, so that it is
+/// usually loaded by the time it is scrolled into view.
+pub const LAZY_LOAD_THRESHOLD: f64 = 1250.0;
+
+/// The `<img loading="lazy">` elements of a page whose images are not loaded yet, keyed by their `src` like the images
+/// of a [`crate::Document`].
+#[derive(Debug, Clone, Default)]
+pub struct LazyLoadQueue {
+    pending: HashMap<String, Vec<NodeId>>,
+}
+
+impl LazyLoadQueue {
+    #[must_use]
+    pub const fn new(pending: HashMap<String, Vec<NodeId>>) -> Self {
+        Self { pending }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Takes the images that have come within [`LAZY_LOAD_THRESHOLD`] of the visible part of the page. Elements
+    /// that are not laid out, e.g. because they are `display: none`, stay in the queue.
+    ///
+    /// # Arguments
+    /// * `scroll_y` - The vertical scroll offset of the page.
+    /// * `viewport_height` - The height of the viewport.
+    /// * `position` - The top of the border box of an element in page coordinates, or `None` if it is not laid out.
+    ///
+    /// # Returns
+    /// The `src` of every image to load now, along with the elements showing it.
+    pub fn take_within_threshold(
+        &mut self,
+        scroll_y: f64,
+        viewport_height: f64,
+        position: impl Fn(NodeId) -> Option<f64>,
+    ) -> HashMap<String, Vec<NodeId>> {
+        let load_until = scroll_y + viewport_height + LAZY_LOAD_THRESHOLD;
+        let mut ready: HashMap<String, Vec<NodeId>> = HashMap::new();
+
+        self.pending.retain(|src, node_ids| {
+            let (near, far): (Vec<NodeId>, Vec<NodeId>) = node_ids
+                .iter()
+                .partition(|node_id| position(**node_id).is_some_and(|top| top <= load_until));
+
+            if !near.is_empty() {
+                ready.insert(src.clone(), near);
+            }
+
+            *node_ids = far;
+            !node_ids.is_empty()
+        });
+
+        ready
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue() -> LazyLoadQueue {
+        LazyLoadQueue::new(HashMap::from([
+            ("hero.png".to_string(), vec![NodeId(1)]),
+            ("footer.png".to_string(), vec![NodeId(2), NodeId(3)]),
+            ("hidden.png".to_string(), vec![NodeId(4)]),
+        ]))
+    }
+
+    fn position(node_id: NodeId) -> Option<f64> {
+        match node_id.0 {
+            1 => Some(100.0),
+            2 => Some(3000.0),
+            3 => Some(6000.0),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_loads_images_within_threshold() {
+        let mut queue = queue();
+
+        let ready = queue.take_within_threshold(0.0, 800.0, position);
+        assert_eq!(ready, HashMap::from([("hero.png".to_string(), vec![NodeId(1)])]));
+
+        let ready = queue.take_within_threshold(1000.0, 800.0, position);
+        assert_eq!(ready, HashMap::from([("footer.png".to_string(), vec![NodeId(2)])]));
+
+        let ready = queue.take_within_threshold(5000.0, 800.0, position);
+        assert_eq!(ready, HashMap::from([("footer.png".to_string(), vec![NodeId(3)])]));
+        assert!(!queue.is_empty());
+    }
+
+    #[test]
+    fn test_unlaid_out_images_stay_queued() {
+        let mut queue = queue();
+
+        queue.take_within_threshold(100_000.0, 800.0, position);
+        assert_eq!(queue.pending, HashMap::from([("hidden.png".to_string(), vec![NodeId(4)])]));
+    }
+}
@@ -1,9 +1,16 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 use css_cssom::CSSStyleSheet;
-use html_dom::{DocumentRoot, NodeId};
+use css_selectors::{HoverSelectors, parse_selector_list};
+use html_dom::{DocumentMetadata, DocumentRoot, NodeId};
 use url::Url;
 
+use crate::context::{timing::PageTimings, viewport::ViewportMeta};
+
 /// Represents the favicon of a web page, including its size, content type, and binary data.
 #[derive(Debug, Clone, Default)]
 pub struct Favicon {
@@ -12,53 +19,221 @@ pub struct Favicon {
     pub data: Vec<u8>,
 }
 
+/// The `<link rel="icon">` of a web page, whose favicon is fetched once the page is displayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaviconLink {
+    pub url: Url,
+    pub size: Option<(u32, u32)>,
+    pub content_type: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct PageMetadata {
     pub url: Url,
     pub title: String,
+
+    /// The favicon linked by the page, if any.
+    pub favicon_link: Option<FaviconLink>,
+
+    /// The favicon of the page, once it has been fetched.
     pub favicon: Option<Favicon>,
+
+    /// The viewport requested with `<meta name="viewport">`, if any.
+    pub viewport: Option<ViewportMeta>,
+
+    /// The metadata of the document, like its description, canonical URL and Open Graph tags.
+    pub document: DocumentMetadata,
+
+    /// When the page reached each milestone of its load.
+    pub timings: PageTimings,
+}
+
+/// Identifies a document loaded by the engine, which keeps a copy of its DOM to run the event listeners of the page
+/// against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DocumentId(u64);
+
+impl DocumentId {
+    fn next() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl Display for DocumentId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
 }
 
 /// Represents a web page loaded in a tab.
 #[derive(Debug, Clone)]
 pub struct Document {
+    id: DocumentId,
     dom: DocumentRoot,
     images: HashMap<String, Vec<NodeId>>,
+    lazy_images: HashMap<String, Vec<NodeId>>,
     stylesheets: Vec<CSSStyleSheet>,
+
+    /// The selectors of the stylesheets that test `:hover`.
+    hover_selectors: HoverSelectors,
 }
 
 impl Document {
     #[must_use]
     pub fn new(dom: DocumentRoot, images: HashMap<String, Vec<NodeId>>, stylesheets: Vec<CSSStyleSheet>) -> Self {
+        let hover_selectors = Self::hover_selectors_of(&stylesheets);
+
         Self {
+            id: DocumentId::next(),
             dom,
             images,
+            lazy_images: HashMap::new(),
             stylesheets,
+            hover_selectors,
         }
     }
 
+    /// Sets the images of `<img loading="lazy">` elements, which are loaded once they are scrolled near the viewport
+    /// instead of with the page.
+    #[must_use]
+    pub fn with_lazy_images(mut self, lazy_images: HashMap<String, Vec<NodeId>>) -> Self {
+        self.lazy_images = lazy_images;
+        self
+    }
+
     /// Creates a new blank page with default settings.
     #[must_use]
     pub fn blank() -> Self {
         Self {
+            id: DocumentId::next(),
             dom: DocumentRoot::new(),
             images: HashMap::new(),
+            lazy_images: HashMap::new(),
             stylesheets: Vec::new(),
+            hover_selectors: HoverSelectors::default(),
         }
     }
 
+    /// The identifier of the document, shared by its clones.
+    #[must_use]
+    pub const fn id(&self) -> DocumentId {
+        self.id
+    }
+
     #[must_use]
     pub const fn dom(&self) -> &DocumentRoot {
         &self.dom
     }
 
+    pub const fn dom_mut(&mut self) -> &mut DocumentRoot {
+        &mut self.dom
+    }
+
     #[must_use]
     pub const fn stylesheets(&self) -> &Vec<CSSStyleSheet> {
         &self.stylesheets
     }
 
+    /// The selectors of the stylesheets that test `:hover`, which tell the elements that are restyled when they enter
+    /// or leave the hover state.
+    #[must_use]
+    pub const fn hover_selectors(&self) -> &HoverSelectors {
+        &self.hover_selectors
+    }
+
     #[must_use]
     pub const fn images(&self) -> &HashMap<String, Vec<NodeId>> {
         &self.images
     }
+
+    #[must_use]
+    pub const fn lazy_images(&self) -> &HashMap<String, Vec<NodeId>> {
+        &self.lazy_images
+    }
+
+    /// Collects the selectors of the style rules that test `:hover`. Only the rules mentioning `hover` are parsed.
+    fn hover_selectors_of(stylesheets: &[CSSStyleSheet]) -> HoverSelectors {
+        let mut hover_selectors = HoverSelectors::default();
+
+        for rule in stylesheets
+            .iter()
+            .flat_map(CSSStyleSheet::get_style_rules)
+            .filter(|rule| rule.selector_text().to_ascii_lowercase().contains("hover"))
+        {
+            for sequences in parse_selector_list(&rule.prelude).iter() {
+                hover_selectors.add(sequences);
+            }
+        }
+
+        hover_selectors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use css_cssom::StylesheetOrigin;
+    use html_dom::extract_metadata;
+    use html_parser::{HtmlStreamParser, ParserState};
+
+    use super::*;
+
+    fn parse(html: &str) -> DocumentRoot {
+        let mut parser = HtmlStreamParser::simple(Cursor::new(html));
+
+        loop {
+            if let ParserState::Completed(result) = parser.step().unwrap() {
+                return result.dom_tree;
+            }
+        }
+    }
+
+    #[test]
+    fn test_extract_metadata_from_fixture() {
+        let dom = parse(include_str!("../../tests/fixtures/metadata.html"));
+        let base_url = Url::parse("https://example.com/index.html").unwrap();
+
+        let metadata = extract_metadata(&dom, Some(&base_url));
+
+        assert_eq!(metadata.title.as_deref(), Some("Hello, metadata"));
+        assert_eq!(metadata.description.as_deref(), Some("A page describing itself."));
+        assert_eq!(metadata.author.as_deref(), Some("Ada Lovelace"));
+        assert_eq!(metadata.og_title.as_deref(), Some("Open Graph title"));
+        assert_eq!(metadata.og_description.as_deref(), Some("Open Graph description"));
+        assert_eq!(metadata.og_image.as_deref(), Some("https://example.com/cover.png"));
+        assert_eq!(metadata.published_date.as_deref(), Some("2024-05-01T08:00:00Z"));
+        assert_eq!(metadata.language.as_deref(), Some("sv-SE"));
+        assert_eq!(metadata.canonical_url, Some(Url::parse("https://example.com/articles/metadata").unwrap()));
+    }
+
+    #[test]
+    fn test_extract_metadata_without_metadata() {
+        let dom = parse("<html><body><p>No metadata</p></body></html>");
+
+        assert_eq!(extract_metadata(&dom, None), DocumentMetadata::default());
+    }
+
+    #[test]
+    fn test_hover_selectors_of_stylesheets() {
+        let stylesheet = |css| CSSStyleSheet::from_css(css, StylesheetOrigin::Author, false);
+        let document = |css| Document::new(DocumentRoot::new(), HashMap::new(), vec![stylesheet(css)]);
+
+        assert!(
+            document("p { color: red } a:focus { color: blue }")
+                .hover_selectors()
+                .is_empty()
+        );
+        assert!(
+            !document("p { color: red } nav a:hover { color: blue }")
+                .hover_selectors()
+                .is_empty()
+        );
+        assert!(
+            !document("@media screen { .menu:hover { color: blue } }")
+                .hover_selectors()
+                .is_empty()
+        );
+    }
 }
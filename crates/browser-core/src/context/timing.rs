@@ -0,0 +1,71 @@
+use std::time::{Duration, Instant};
+
+/// The points in time a page reached each milestone of its load, from the start of the navigation.
+///
+/// NOTE: The HTTP client does not report when the host name was resolved or the connection was established, so the
+/// network part of the load is only measured up to the first byte of the response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageTimings {
+    /// When the navigation was started.
+    pub navigation_start: Instant,
+
+    /// When the head of the response was received.
+    pub first_byte: Option<Instant>,
+
+    /// When the document was parsed, before waiting for stylesheets and other sub-resources.
+    pub dom_content_loaded: Option<Instant>,
+
+    /// When the page was first laid out for display.
+    pub first_paint: Option<Instant>,
+
+    /// When the stylesheets of the page were loaded.
+    pub fully_loaded: Option<Instant>,
+}
+
+impl PageTimings {
+    /// Starts the timings of a navigation at the current time.
+    #[must_use]
+    pub fn start() -> Self {
+        Self {
+            navigation_start: Instant::now(),
+            first_byte: None,
+            dom_content_loaded: None,
+            first_paint: None,
+            fully_loaded: None,
+        }
+    }
+
+    /// The milestones reached so far, in load order, as the time elapsed since the start of the navigation.
+    pub fn milestones(&self) -> impl Iterator<Item = (&'static str, Duration)> + '_ {
+        [
+            ("first_byte", self.first_byte),
+            ("dom_content_loaded", self.dom_content_loaded),
+            ("first_paint", self.first_paint),
+            ("fully_loaded", self.fully_loaded),
+        ]
+        .into_iter()
+        .filter_map(|(name, instant)| Some((name, instant?.saturating_duration_since(self.navigation_start))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_milestones_skip_unrecorded() {
+        let mut timings = PageTimings::start();
+        timings.first_byte = Some(timings.navigation_start + Duration::from_millis(40));
+        timings.fully_loaded = Some(timings.navigation_start + Duration::from_millis(120));
+
+        let milestones = timings.milestones().collect::<Vec<_>>();
+
+        assert_eq!(
+            milestones,
+            vec![
+                ("first_byte", Duration::from_millis(40)),
+                ("fully_loaded", Duration::from_millis(120))
+            ]
+        );
+    }
+}
@@ -0,0 +1,198 @@
+/// The smallest and largest width or height a viewport can be given, in CSS pixels.
+const DIMENSION_RANGE: (f32, f32) = (1.0, 10_000.0);
+
+/// The smallest and largest scale a viewport can be given.
+const SCALE_RANGE: (f32, f32) = (0.1, 10.0);
+
+/// The value of the `width` or `height` of a `<meta name="viewport">` tag.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ViewportDimension {
+    /// The width of the screen, `device-width`.
+    DeviceWidth,
+
+    /// The height of the screen, `device-height`.
+    DeviceHeight,
+
+    /// A fixed size in CSS pixels.
+    Length(f32),
+}
+
+impl ViewportDimension {
+    fn parse(value: &str) -> Option<Self> {
+        if value.eq_ignore_ascii_case("device-width") {
+            Some(Self::DeviceWidth)
+        } else if value.eq_ignore_ascii_case("device-height") {
+            Some(Self::DeviceHeight)
+        } else {
+            parse_number(value).map(|length| Self::Length(length.clamp(DIMENSION_RANGE.0, DIMENSION_RANGE.1)))
+        }
+    }
+
+    const fn resolve(self, device_width: f32, device_height: f32) -> f32 {
+        match self {
+            Self::DeviceWidth => device_width,
+            Self::DeviceHeight => device_height,
+            Self::Length(length) => length,
+        }
+    }
+}
+
+/// The viewport requested by a page with `<meta name="viewport" content="...">`.
+///
+/// <https://drafts.csswg.org/css-viewport/#viewport-meta>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewportMeta {
+    pub width: Option<ViewportDimension>,
+    pub height: Option<ViewportDimension>,
+    pub initial_scale: Option<f32>,
+    pub minimum_scale: Option<f32>,
+    pub maximum_scale: Option<f32>,
+    pub user_scalable: bool,
+}
+
+impl ViewportMeta {
+    /// Parses the `content` attribute of a `<meta name="viewport">` tag, a list of `key=value` pairs separated by
+    /// commas or semicolons. Unknown keys and invalid values are ignored.
+    #[must_use]
+    pub fn parse(content: &str) -> Self {
+        let mut viewport = Self {
+            width: None,
+            height: None,
+            initial_scale: None,
+            minimum_scale: None,
+            maximum_scale: None,
+            user_scalable: true,
+        };
+
+        for property in content.split([',', ';']) {
+            let Some((key, value)) = property.split_once('=') else {
+                continue;
+            };
+
+            let (key, value) = (key.trim(), value.trim());
+
+            match key.to_ascii_lowercase().as_str() {
+                "width" => viewport.width = ViewportDimension::parse(value),
+                "height" => viewport.height = ViewportDimension::parse(value),
+                "initial-scale" => viewport.initial_scale = parse_scale(value),
+                "minimum-scale" => viewport.minimum_scale = parse_scale(value),
+                "maximum-scale" => viewport.maximum_scale = parse_scale(value),
+                "user-scalable" => {
+                    viewport.user_scalable = !(value.eq_ignore_ascii_case("no") || parse_number(value) == Some(0.0));
+                }
+                _ => {}
+            }
+        }
+
+        viewport
+    }
+
+    /// The width the page is laid out at, in CSS pixels, which fills the screen when the page is shown at
+    /// [`ViewportMeta::scale`]. The two only make sense together, a page laid out at this width and drawn without
+    /// the scale overflows or underfills the screen.
+    ///
+    /// # Arguments
+    /// * `device_width` - The width of the screen, or the window the page is shown in.
+    /// * `device_height` - The height of the screen, or the window the page is shown in.
+    #[must_use]
+    pub fn layout_width(&self, device_width: f32, device_height: f32) -> f32 {
+        device_width / self.scale(device_width, device_height)
+    }
+
+    /// The zoom factor of the page, the number of screen pixels per CSS pixel.
+    ///
+    /// # Arguments
+    /// * `device_width` - The width of the screen, or the window the page is shown in.
+    /// * `device_height` - The height of the screen, or the window the page is shown in.
+    #[must_use]
+    pub fn scale(&self, device_width: f32, device_height: f32) -> f32 {
+        let scale = match (self.initial_scale, self.width) {
+            (Some(scale), _) => scale,
+            (None, Some(width)) => device_width / width.resolve(device_width, device_height),
+            (None, None) => 1.0,
+        };
+
+        let minimum = self.minimum_scale.unwrap_or(SCALE_RANGE.0);
+        let maximum = self.maximum_scale.unwrap_or(SCALE_RANGE.1).max(minimum);
+
+        scale.clamp(minimum, maximum)
+    }
+}
+
+/// Parses a scale, which may also be given as `yes` (1) or `no` (0.1).
+fn parse_scale(value: &str) -> Option<f32> {
+    if value.eq_ignore_ascii_case("yes") {
+        return Some(1.0);
+    }
+
+    if value.eq_ignore_ascii_case("no") {
+        return Some(SCALE_RANGE.0);
+    }
+
+    parse_number(value).map(|scale| scale.clamp(SCALE_RANGE.0, SCALE_RANGE.1))
+}
+
+/// Parses the leading number of a value, ignoring any trailing characters such as a `px` unit.
+fn parse_number(value: &str) -> Option<f32> {
+    let end = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-' || c == '+'))
+        .unwrap_or(value.len());
+
+    value[..end]
+        .parse()
+        .ok()
+        .filter(|number: &f32| number.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_device_width_initial_scale_one() {
+        let viewport = ViewportMeta::parse("width=device-width, initial-scale=1");
+
+        assert_eq!(viewport.width, Some(ViewportDimension::DeviceWidth));
+        assert_eq!(viewport.initial_scale, Some(1.0));
+        assert_eq!(viewport.layout_width(412.0, 915.0), 412.0);
+        assert_eq!(viewport.scale(412.0, 915.0), 1.0);
+    }
+
+    #[test]
+    fn test_fixed_width_scales_to_device() {
+        let viewport = ViewportMeta::parse("width=980");
+
+        assert_eq!(viewport.layout_width(490.0, 900.0), 980.0);
+        assert_eq!(viewport.scale(490.0, 900.0), 0.5);
+    }
+
+    #[test]
+    fn test_initial_scale_without_width() {
+        let viewport = ViewportMeta::parse("initial-scale=2.0; maximum-scale=1.5; user-scalable=no");
+
+        assert_eq!(viewport.scale(600.0, 800.0), 1.5);
+        assert_eq!(viewport.layout_width(600.0, 800.0), 400.0);
+        assert!(!viewport.user_scalable);
+    }
+
+    #[test]
+    fn test_invalid_values_are_ignored() {
+        let viewport = ViewportMeta::parse("width=wide, initial-scale=, foo=bar, height=20000px");
+
+        assert_eq!(viewport.width, None);
+        assert_eq!(viewport.initial_scale, None);
+        assert_eq!(viewport.height, Some(ViewportDimension::Length(10_000.0)));
+        assert_eq!(viewport.layout_width(800.0, 600.0), 800.0);
+    }
+
+    #[test]
+    fn test_layout_width_matches_scale() {
+        let viewport = ViewportMeta::parse("width=980, initial-scale=1");
+        assert_eq!(viewport.scale(490.0, 900.0), 1.0);
+        assert_eq!(viewport.layout_width(490.0, 900.0), 490.0);
+
+        let viewport = ViewportMeta::parse("width=5000, minimum-scale=0.5");
+        assert_eq!(viewport.scale(1000.0, 900.0), 0.5);
+        assert_eq!(viewport.layout_width(1000.0, 900.0), 2000.0);
+    }
+}
@@ -0,0 +1,248 @@
+//! The engine's copies of the DOM of the loaded documents, which the event listeners of a page run against. The
+//! changes they make are sent to the frontend, which holds its own copy of each document.
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use html_dom::{DocumentRoot, NodeId};
+
+use crate::{EngineResponse, context::page::DocumentId, errors::CoreError};
+
+/// The maximum number of documents kept, the least recently used one is dropped to make room for a new one. Covers
+/// the open tabs of a typical session along with the pages of their back-forward cache.
+const MAX_DOCUMENTS: usize = 32;
+
+/// The DOM of the documents loaded by the engine, shared between all clones.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DocumentStore {
+    /// The documents, from the least to the most recently used.
+    documents: Arc<Mutex<VecDeque<(DocumentId, DocumentRoot)>>>,
+}
+
+impl DocumentStore {
+    /// Keeps the DOM of a loaded document, dropping the least recently used document if the store is full.
+    ///
+    /// # Panics
+    /// * If the lock of the store is poisoned.
+    pub(crate) fn insert(&self, id: DocumentId, dom: DocumentRoot) {
+        let mut documents = self.documents.lock().unwrap();
+
+        documents.retain(|(document_id, _)| *document_id != id);
+        if documents.len() >= MAX_DOCUMENTS {
+            documents.pop_front();
+        }
+        documents.push_back((id, dom));
+    }
+
+    /// Runs a function with the DOM of a document, marking the document as the most recently used.
+    ///
+    /// # Returns
+    /// The result of the function, or `None` if the document is not kept.
+    ///
+    /// # Panics
+    /// * If the lock of the store is poisoned.
+    pub(crate) fn with_dom<T>(&self, id: DocumentId, f: impl FnOnce(&mut DocumentRoot) -> T) -> Option<T> {
+        let mut documents = self.documents.lock().unwrap();
+
+        let index = documents
+            .iter()
+            .position(|(document_id, _)| *document_id == id)?;
+        let document = documents.remove(index)?;
+        documents.push_back(document);
+
+        documents.back_mut().map(|(_, dom)| f(dom))
+    }
+
+    /// Runs a form control command against a document, then applies the attribute changes of its response to the
+    /// document like the frontend does to its copy.
+    ///
+    /// # Returns
+    /// The response of the command.
+    ///
+    /// # Errors
+    /// Returns `CoreError::DocumentNotFound` if the document is not kept, or the error of the command.
+    pub(crate) fn execute(
+        &self,
+        id: DocumentId,
+        command: impl FnOnce(&DocumentRoot) -> Result<EngineResponse, CoreError>,
+    ) -> Result<EngineResponse, CoreError> {
+        self.with_dom(id, |dom| {
+            let response = command(dom)?;
+            apply_response(dom, &response);
+            Ok(response)
+        })
+        .ok_or(CoreError::DocumentNotFound(id))?
+    }
+
+    /// A rough estimate of the memory used by the documents kept.
+    ///
+    /// # Panics
+    /// * If the lock of the store is poisoned.
+    pub(crate) fn estimated_size_bytes(&self) -> usize {
+        self.documents
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, dom)| dom.estimated_size_bytes())
+            .sum()
+    }
+}
+
+/// Applies the attribute changes of a response to a form control command to a document.
+fn apply_response(dom: &mut DocumentRoot, response: &EngineResponse) {
+    match response {
+        EngineResponse::InputValueUpdated { node_id, value } => set_attribute(dom, *node_id, "value", value),
+        EngineResponse::ElementResized { node_id, style } => set_attribute(dom, *node_id, "style", style),
+        EngineResponse::CheckedChanged { checked, unchecked } => {
+            toggle_attribute(dom, "checked", checked, unchecked);
+        }
+        EngineResponse::DetailsToggled { opened, closed } => toggle_attribute(dom, "open", opened, closed),
+        EngineResponse::OptionSelected {
+            select_node_id,
+            option_node_ids,
+            selected_node_id,
+            value,
+        } => {
+            let deselected: Vec<NodeId> = option_node_ids
+                .iter()
+                .filter(|id| *id != selected_node_id)
+                .copied()
+                .collect();
+            toggle_attribute(dom, "selected", &[*selected_node_id], &deselected);
+            set_attribute(dom, *select_node_id, "value", value);
+        }
+        _ => {}
+    }
+}
+
+fn set_attribute(dom: &mut DocumentRoot, node_id: NodeId, name: &str, value: &str) {
+    if let Some(element) = dom
+        .nodes
+        .get_mut(*node_id)
+        .and_then(|node| node.data.as_element_mut())
+    {
+        element.set_attribute(name, value);
+    }
+}
+
+fn toggle_attribute(dom: &mut DocumentRoot, name: &str, added: &[NodeId], removed: &[NodeId]) {
+    for node_id in removed {
+        if let Some(element) = dom
+            .nodes
+            .get_mut(**node_id)
+            .and_then(|node| node.data.as_element_mut())
+        {
+            element.remove_attribute(name);
+        }
+    }
+    for node_id in added {
+        set_attribute(dom, *node_id, name, "");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use html_dom::{Element, HtmlTag, NodeData, Tag};
+
+    use crate::{Browser, Document};
+
+    use super::*;
+
+    fn document() -> DocumentId {
+        Document::new(DocumentRoot::new(), HashMap::new(), Vec::new()).id()
+    }
+
+    #[test]
+    fn test_form_control_changes_are_applied() {
+        let store = DocumentStore::default();
+        let id = document();
+
+        let mut dom = DocumentRoot::new();
+        let input = |checked: bool| {
+            let mut element = Element::new(Tag::Html(HtmlTag::Input), HashSet::new(), HashMap::new());
+            if checked {
+                element.set_attribute("checked", "");
+            }
+            NodeData::Element(element)
+        };
+        let first = dom.push_node(&input(true), None);
+        let second = dom.push_node(&input(false), None);
+        store.insert(id, dom);
+
+        let response = store.execute(id, |_| {
+            Ok(EngineResponse::CheckedChanged {
+                checked: vec![second],
+                unchecked: vec![first],
+            })
+        });
+        assert!(response.is_ok());
+
+        let response =
+            store.execute(id, |document| Browser::update_input_value(first, Browser::control(document, first)?, "on"));
+        assert!(matches!(response, Ok(EngineResponse::InputValueUpdated { ref value, .. }) if value == "on"));
+
+        let attribute = |node_id: NodeId, name: &'static str| {
+            store
+                .with_dom(id, |dom| {
+                    dom[node_id]
+                        .data
+                        .as_element()
+                        .and_then(|element| element.get_attribute(name))
+                        .map(str::to_string)
+                })
+                .flatten()
+        };
+        assert_eq!(attribute(first, "checked"), None);
+        assert_eq!(attribute(second, "checked"), Some(String::new()));
+        assert_eq!(attribute(first, "value"), Some("on".to_string()));
+    }
+
+    #[test]
+    fn test_execute_reads_and_updates_the_document() {
+        let store = DocumentStore::default();
+        let id = document();
+
+        let mut dom = DocumentRoot::new();
+        let details = Element::new(Tag::Html(HtmlTag::Details), HashSet::new(), HashMap::new());
+        let node_id = dom.push_node(&NodeData::Element(details), None);
+        store.insert(id, dom);
+
+        let response = store.execute(id, |dom| Browser::toggle_details(node_id, dom));
+        assert!(matches!(response, Ok(EngineResponse::DetailsToggled { opened, .. }) if opened == [node_id]));
+
+        let response = store.execute(id, |dom| Browser::toggle_details(node_id, dom));
+        assert!(matches!(response, Ok(EngineResponse::DetailsToggled { closed, .. }) if closed == [node_id]));
+
+        let unknown = document();
+        assert!(matches!(
+            store.execute(unknown, |dom| Browser::toggle_details(node_id, dom)),
+            Err(CoreError::DocumentNotFound(document_id)) if document_id == unknown
+        ));
+    }
+
+    #[test]
+    fn test_unknown_document() {
+        let store = DocumentStore::default();
+
+        assert_eq!(store.with_dom(document(), |dom| dom.nodes.len()), None);
+    }
+
+    #[test]
+    fn test_least_recently_used_document_is_dropped() {
+        let store = DocumentStore::default();
+        let ids: Vec<DocumentId> = (0..MAX_DOCUMENTS).map(|_| document()).collect();
+        for id in &ids {
+            store.insert(*id, DocumentRoot::new());
+        }
+
+        assert_eq!(store.with_dom(ids[0], |_| ()), Some(()));
+        store.insert(document(), DocumentRoot::new());
+
+        assert_eq!(store.with_dom(ids[0], |_| ()), Some(()));
+        assert_eq!(store.with_dom(ids[1], |_| ()), None);
+    }
+}
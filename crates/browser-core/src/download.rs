@@ -0,0 +1,253 @@
+//! Responses saved to disk instead of being shown, either because the server asked for it with
+//! `Content-Disposition: attachment` or because the user chose to save a page.
+
+use std::{
+    fs::OpenOptions,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use futures::StreamExt;
+use http_fetch::handle::ResponseHandle;
+use tokio::{fs::File, io::AsyncWriteExt};
+use tracing::{Instrument, debug, warn};
+use url::Url;
+
+/// The name a download is saved under when neither the server nor the URL suggests one.
+const DEFAULT_FILENAME: &str = "download";
+
+/// The progress of a download.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DownloadState {
+    /// The body of the response is being written to disk.
+    InProgress,
+
+    /// The whole body has been written to disk.
+    Completed,
+
+    /// The download stopped because of the given error, the partially written file is left on disk.
+    Failed(String),
+}
+
+/// A file being downloaded, or downloaded earlier in this session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Download {
+    /// Identifies the download among the downloads of the session.
+    pub id: usize,
+
+    /// The URL the file is downloaded from.
+    pub url: Url,
+
+    /// The name of the file on disk.
+    pub filename: String,
+
+    /// Where the file is saved.
+    pub path: PathBuf,
+
+    /// The `Content-Type` of the response.
+    pub content_type: Option<String>,
+
+    /// The `Content-Length` of the response, `None` if the server did not send it.
+    pub content_length: Option<u64>,
+
+    /// The number of bytes written to disk so far.
+    pub received_bytes: u64,
+
+    pub state: DownloadState,
+}
+
+/// Writes the bodies of downloaded responses to disk in the background and keeps track of their progress, shared
+/// between all clones.
+#[derive(Debug, Clone, Default)]
+pub struct DownloadManager {
+    downloads: Arc<Mutex<Vec<Download>>>,
+    next_id: Arc<AtomicUsize>,
+}
+
+impl DownloadManager {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts writing the body of a response to a file, without waiting for it to be written.
+    ///
+    /// # Arguments
+    /// * `url` - The URL the response was fetched from.
+    /// * `path` - The file the body is written to.
+    /// * `file` - The file at `path`, created for the download by `create_download_file`.
+    /// * `response` - The response, whose head has been received but whose body has not been read yet.
+    ///
+    /// # Returns
+    /// The download as it is when it starts.
+    ///
+    /// # Panics
+    /// * If the lock of the download list is poisoned.
+    pub fn start(&self, url: Url, path: PathBuf, file: std::fs::File, response: Box<dyn ResponseHandle>) -> Download {
+        let headers = &response.head().headers;
+        let download = Download {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            filename: path
+                .file_name()
+                .map_or_else(|| DEFAULT_FILENAME.to_string(), |name| name.to_string_lossy().into_owned()),
+            url,
+            path,
+            content_type: headers
+                .get(http::header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string),
+            content_length: headers
+                .get(http::header::CONTENT_LENGTH)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok()),
+            received_bytes: 0,
+            state: DownloadState::InProgress,
+        };
+
+        self.downloads.lock().unwrap().push(download.clone());
+
+        let manager = self.clone();
+        let id = download.id;
+        let path = download.path.clone();
+        tokio::spawn(
+            async move {
+                let state = match manager.write(id, File::from_std(file), response).await {
+                    Ok(()) => DownloadState::Completed,
+                    Err(error) => {
+                        warn!(%error, "Download of {} failed", path.display());
+                        DownloadState::Failed(error)
+                    }
+                };
+
+                manager.update(id, |download| download.state = state);
+            }
+            .in_current_span(),
+        );
+
+        debug!(id = download.id, path = %download.path.display(), "Started download of {}", download.url);
+        download
+    }
+
+    /// Every download of the session, oldest first.
+    ///
+    /// # Panics
+    /// * If the lock of the download list is poisoned.
+    #[must_use]
+    pub fn downloads(&self) -> Vec<Download> {
+        self.downloads.lock().unwrap().clone()
+    }
+
+    async fn write(&self, id: usize, mut file: File, response: Box<dyn ResponseHandle>) -> Result<(), String> {
+        let response = response
+            .response()
+            .await
+            .map_err(|error| error.to_string())?;
+
+        let mut body = response.body.into_stream();
+        while let Some(chunk) = body.next().await {
+            let chunk = chunk?;
+            file.write_all(&chunk)
+                .await
+                .map_err(|error| error.to_string())?;
+
+            self.update(id, |download| download.received_bytes += chunk.len() as u64);
+        }
+
+        file.flush().await.map_err(|error| error.to_string())
+    }
+
+    fn update(&self, id: usize, f: impl FnOnce(&mut Download)) {
+        if let Some(download) = self
+            .downloads
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|download| download.id == id)
+        {
+            f(download);
+        }
+    }
+}
+
+/// The name to save a download under, the name suggested by the server, or the last segment of the URL path.
+///
+/// # Arguments
+/// * `url` - The URL the file is downloaded from.
+/// * `suggested` - The file name from the `Content-Disposition` header of the response, if any.
+#[must_use]
+pub fn download_filename(url: &Url, suggested: Option<String>) -> String {
+    suggested
+        .or_else(|| {
+            url.path_segments()
+                .and_then(|mut segments| segments.next_back())
+                .filter(|segment| !segment.is_empty())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| DEFAULT_FILENAME.to_string())
+}
+
+/// Creates a new file in `directory` named `filename` to save a download to, numbering the name like
+/// `report (1).pdf` when a file with the name already exists. Existing files are never opened, so a file created
+/// between choosing the name and creating it is not overwritten.
+///
+/// # Returns
+/// The path of the created file along with the file, opened for writing.
+///
+/// # Errors
+/// Returns the error of creating the file, other than the file already existing.
+pub fn create_download_file(directory: &Path, filename: &str) -> std::io::Result<(PathBuf, std::fs::File)> {
+    let (stem, extension) = match filename.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+        _ => (filename, None),
+    };
+
+    let mut n = 0;
+    loop {
+        let path = match (n, extension) {
+            (0, _) => directory.join(filename),
+            (n, Some(extension)) => directory.join(format!("{stem} ({n}).{extension}")),
+            (n, None) => directory.join(format!("{stem} ({n})")),
+        };
+
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(error) if error.kind() == ErrorKind::AlreadyExists => n += 1,
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_download_filename_fallbacks() {
+        let url = Url::parse("https://example.com/files/report.pdf?v=2").unwrap();
+        assert_eq!(download_filename(&url, Some("summary.pdf".to_string())), "summary.pdf");
+        assert_eq!(download_filename(&url, None), "report.pdf");
+
+        let root = Url::parse("https://example.com/").unwrap();
+        assert_eq!(download_filename(&root, None), DEFAULT_FILENAME);
+    }
+
+    #[test]
+    fn test_create_download_file_numbers_existing_files() {
+        let directory = std::env::temp_dir().join(format!("download-test-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let create = |filename: &str| create_download_file(&directory, filename).unwrap().0;
+
+        assert_eq!(create("report.pdf"), directory.join("report.pdf"));
+        assert_eq!(create("report.pdf"), directory.join("report (1).pdf"));
+
+        std::fs::write(directory.join("README"), b"kept").unwrap();
+        assert_eq!(create("README"), directory.join("README (1)"));
+        assert_eq!(std::fs::read(directory.join("README")).unwrap(), b"kept");
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -1,15 +1,22 @@
-use html_parser::errors::HtmlParsingError;
+use std::{path::PathBuf, sync::Arc};
+
+use extensions::errors::ManifestError;
+use html_dom::NodeId;
+use html_parser::errors::HtmlParseError;
+use http_cache::errors::CacheError;
 use http_fetch::errors::FetchError;
 use io::errors::{MiddlewareError, ResourceError};
 use thiserror::Error;
 
+use crate::context::page::DocumentId;
+
 #[derive(Error, Debug, Clone)]
 pub enum NavigationError {
     #[error("failed to parse HTML for {url}")]
     Parsing {
         url: String,
         #[source]
-        source: HtmlParsingError,
+        source: HtmlParseError,
     },
 
     #[error(transparent)]
@@ -30,6 +37,27 @@ pub enum NavigationError {
 
     #[error("cookie jar is locked")]
     CookieJarLocked,
+
+    #[error("failed to start download: {0}")]
+    Download(String),
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum JsError {
+    #[error("invalid selector: {0}")]
+    InvalidSelector(String),
+
+    #[error("node {0} is not an element")]
+    NotAnElement(NodeId),
+
+    #[error("node {child} cannot be inserted into node {parent}")]
+    HierarchyRequest { parent: NodeId, child: NodeId },
+
+    #[error("node {child} is not a child of node {parent}")]
+    NotFound { parent: NodeId, child: NodeId },
+
+    #[error("failed to evaluate script: {0}")]
+    Evaluation(String),
 }
 
 #[derive(Error, Debug, Clone)]
@@ -37,12 +65,176 @@ pub enum CoreError {
     #[error(transparent)]
     Navigation(#[from] NavigationError),
 
-    #[error("failed to initialize database: {0}")]
-    InitializeDatabase(String),
-
     #[error("failed to fetch an image: {0}")]
     Image(String),
 
+    #[error("failed to fetch a font: {0}")]
+    Font(String),
+
     #[error("failed to generate devtools HTML: {0}")]
     DevtoolsGeneration(String),
+
+    #[error("failed to submit form")]
+    Form(#[from] FormError),
+
+    #[error("failed to render canvas")]
+    Canvas(#[from] CanvasError),
+
+    #[error("failed to update input value")]
+    Input(#[from] InputError),
+
+    #[error("failed to select option")]
+    Select(#[from] SelectError),
+
+    #[error("failed to toggle details: node {0} is not a details element")]
+    DetailsToggle(NodeId),
+
+    #[error("failed to access clipboard")]
+    Clipboard(#[from] ClipboardError),
+
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    #[error("failed to load extension")]
+    Extension(#[from] ExtensionError),
+
+    #[error("failed to dispatch event: {0}")]
+    Script(#[from] JsError),
+
+    #[error("document {0} is no longer loaded")]
+    DocumentNotFound(DocumentId),
+}
+
+/// An error reading or writing the data the browser stores in its profile.
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum StorageError {
+    #[error("failed to open the {name} database: {message}")]
+    OpenDatabase { name: &'static str, message: String },
+
+    #[error("failed to save session")]
+    Session(#[source] ResourceError),
+
+    #[error("failed to clear the HTTP cache")]
+    Cache(#[source] Arc<CacheError>),
+}
+
+impl From<CacheError> for StorageError {
+    fn from(error: CacheError) -> Self {
+        Self::Cache(Arc::new(error))
+    }
+}
+
+/// An error submitting a `<form>`.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FormError {
+    #[error("form node {0} not found")]
+    NotFound(NodeId),
+
+    #[error("node {0} is not a form")]
+    NotAForm(NodeId),
+
+    #[error("unsupported form method: {0}")]
+    UnsupportedMethod(String),
+
+    #[error("invalid form action {action:?}")]
+    InvalidAction {
+        action: String,
+        #[source]
+        source: url::ParseError,
+    },
+}
+
+/// An error executing the command buffer of a `<canvas>`.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CanvasError {
+    #[error("canvas of {width}x{height} pixels is too large")]
+    TooLarge { width: u32, height: u32 },
+}
+
+/// An error changing the value or checkedness of a form control.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InputError {
+    #[error("node {0} not found")]
+    NotFound(NodeId),
+
+    #[error("node {0} is disabled")]
+    Disabled(NodeId),
+
+    #[error("node {0} is read-only")]
+    ReadOnly(NodeId),
+
+    #[error("{0:?} is not a number")]
+    NotANumber(String),
+
+    #[error("node {0} is not a text control")]
+    NotATextControl(NodeId),
+
+    #[error("node {0} is not a checkbox")]
+    NotACheckbox(NodeId),
+
+    #[error("node {0} is not a radio button")]
+    NotARadioButton(NodeId),
+
+    #[error("radio button {node_id} is not in group {group:?}")]
+    NotInGroup { node_id: NodeId, group: String },
+}
+
+/// An error selecting an option of a `<select>`.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SelectError {
+    #[error("node {0} is not a select")]
+    NotASelect(NodeId),
+
+    #[error("select {0} is disabled")]
+    Disabled(NodeId),
+
+    #[error("select {select} has no option {index}")]
+    NoOption { select: NodeId, index: usize },
+
+    #[error("option {index} of select {select} is disabled")]
+    OptionDisabled { select: NodeId, index: usize },
+}
+
+/// An error exchanging data with the system clipboard.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ClipboardError {
+    #[error("unsupported MIME type {0}")]
+    UnsupportedMimeType(String),
+
+    #[error("{0} data is not valid UTF-8")]
+    InvalidUtf8(String),
+
+    #[error("reading requires a user gesture")]
+    NoUserGesture,
+
+    #[error("the user gesture has expired")]
+    GestureExpired,
+}
+
+/// An error loading an unpacked extension.
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum ExtensionError {
+    #[error("failed to read {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: Arc<std::io::Error>,
+    },
+
+    #[error("{} is too large", .0.display())]
+    TooLarge(PathBuf),
+
+    #[error("invalid manifest {}", path.display())]
+    Manifest {
+        path: PathBuf,
+        #[source]
+        source: ManifestError,
+    },
 }
@@ -1,10 +1,19 @@
+use std::{path::PathBuf, time::Instant};
+
 use async_trait::async_trait;
 
 use crate::{
-    context::page::PageMetadata,
+    commands::StorageDataType,
+    context::page::{DocumentId, Favicon, FaviconLink, PageMetadata},
+    download::Download,
     errors::{CoreError, NavigationError},
+    extension::Extension,
+    link::LinkTarget,
+    memory::MemoryUsage,
+    session::SessionEntry,
 };
-use html_dom::{DocumentRoot, NodeId};
+use cookies::{Cookie, CookieFilter};
+use html_dom::{CanvasCommand, DocumentRoot, DomNode, NodeId, ValidationError};
 use url::Url;
 
 use crate::context::page::Document;
@@ -42,6 +51,101 @@ pub enum EngineResponse {
         data: Vec<u8>,
     },
 
+    /// The favicon of a page was successfully fetched from the network.
+    FaviconFetched { page_url: Url, favicon: Favicon },
+
+    /// The font file of an `@font-face` rule was successfully fetched from the network.
+    FontFetched {
+        family: String,
+        url: String,
+        data: Vec<u8>,
+    },
+
+    /// The command buffer of a `<canvas>` element was executed, producing its RGBA bitmap.
+    CanvasRendered {
+        node_id: NodeId,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    },
+
+    /// The value of an `<input>` or `<textarea>` was accepted, after applying the constraints of the control.
+    InputValueUpdated { node_id: NodeId, value: String },
+
+    /// A DOM event was fired at an element through the JavaScript bridge, giving the nodes its listeners changed or
+    /// created, to be applied to the document with `DocumentRoot::apply_changes`.
+    EventDispatched {
+        node_id: NodeId,
+        event_type: String,
+        changes: Vec<DomNode>,
+    },
+
+    /// An element was resized by the user, giving the new value of its `style` attribute.
+    ElementResized { node_id: NodeId, style: String },
+
+    /// A checkbox or radio button was clicked, giving the controls to add the `checked` attribute to and to remove it
+    /// from.
+    CheckedChanged {
+        checked: Vec<NodeId>,
+        unchecked: Vec<NodeId>,
+    },
+
+    /// The summary of a `<details>` was clicked, giving the `<details>` elements to add the `open` attribute to and to
+    /// remove it from.
+    DetailsToggled {
+        opened: Vec<NodeId>,
+        closed: Vec<NodeId>,
+    },
+
+    /// An option of a `<select>` was chosen, giving all of its options so that the others can be deselected and the
+    /// new value of the select.
+    OptionSelected {
+        select_node_id: NodeId,
+        option_node_ids: Vec<NodeId>,
+        selected_node_id: NodeId,
+        value: String,
+    },
+
+    /// A form submission was blocked because one or more of its controls failed constraint validation.
+    FormValidationFailed(Vec<ValidationError>),
+
+    /// The cookies matching a `ListCookies` filter.
+    CookieList(Vec<Cookie>),
+
+    /// The number of cookies removed by a `DeleteCookies` command.
+    CookiesDeleted(usize),
+
+    /// The data of an origin was removed by a `ClearSiteData` command.
+    SiteDataCleared {
+        cookies: usize,
+        cache_entries: usize,
+    },
+
+    /// The response of a navigation or a save-as command is being saved to disk instead of being shown.
+    DownloadStarted(Download),
+
+    /// Every download of the session, in reply to a `ListDownloads` command.
+    DownloadList(Vec<Download>),
+
+    /// The open tabs were saved by a `SaveSession` command.
+    SessionSaved,
+
+    /// The tabs of the session that was open when the browser crashed, to be reopened by the UI. Empty if there is no
+    /// such session or it was already restored.
+    CrashSessionRestored(Vec<SessionEntry>),
+
+    /// The memory used by the subsystems owned by the engine, in reply to a `GetMemoryReport` command.
+    MemoryReport(MemoryUsage),
+
+    /// Data accepted for the system clipboard, to be written by the UI.
+    ClipboardWrite { mime_type: String, data: Vec<u8> },
+
+    /// Reading the system clipboard was allowed, the UI can read its content of the given MIME type.
+    ClipboardReadAllowed { mime_type: String },
+
+    /// An extension was loaded and registered by a `LoadExtension` command.
+    ExtensionLoaded(Extension),
+
     /// A general browser error occurred (for errors that don't fit other categories).
     Error(CoreError),
 }
@@ -67,4 +171,132 @@ pub enum EngineCommand {
         request_url: Url,
         image_url: String,
     },
+
+    /// Command to fetch the favicon linked by a page using the browser's HTTP client, headers, and cookies.
+    FetchFavicon { page_url: Url, link: FaviconLink },
+
+    /// Command to fetch the font file of an `@font-face` rule using the browser's HTTP client, headers, and cookies,
+    /// trying its sources in order.
+    FetchFont {
+        family: String,
+        request_url: Url,
+        font_urls: Vec<String>,
+    },
+
+    /// Command to validate and submit a `<form>`, navigating to its action URL if all controls are valid.
+    SubmitForm {
+        form_id: NodeId,
+        document: DocumentRoot,
+        page_url: Url,
+    },
+
+    /// Command to follow a link clicked on a page, in the tab the frontend picked for its target. A link opened in a
+    /// new tab is always isolated from the page it was opened from, `noopener` only records that the link asked for
+    /// it, while `noreferrer` leaves out the `Referer` header of the navigation request.
+    OpenLink {
+        url: String,
+        page_url: Url,
+        target: LinkTarget,
+        noopener: bool,
+        noreferrer: bool,
+    },
+
+    /// Execute the recorded drawing commands of a `<canvas>` element against a bitmap of the given size.
+    ExecuteCanvasCommands {
+        node_id: NodeId,
+        width: u32,
+        height: u32,
+        commands: Vec<CanvasCommand>,
+    },
+
+    /// Update the value of an `<input>` or `<textarea>` edited by the user.
+    UpdateInputValue {
+        document_id: DocumentId,
+        node_id: NodeId,
+        value: String,
+    },
+
+    /// Resize an element dragged by its resize handle to the given content box size, `None` for a dimension that was
+    /// not resized.
+    ResizeElement {
+        document_id: DocumentId,
+        node_id: NodeId,
+        new_width: Option<f64>,
+        new_height: Option<f64>,
+    },
+
+    /// Fire a DOM event, such as `change`, at an element of a loaded document.
+    DispatchEvent {
+        document_id: DocumentId,
+        node_id: NodeId,
+        event_type: String,
+    },
+
+    /// Toggle the checkedness of an `<input type="checkbox">` clicked by the user.
+    ToggleCheckbox {
+        document_id: DocumentId,
+        node_id: NodeId,
+    },
+
+    /// Check an `<input type="radio">` clicked by the user, unchecking the other radio buttons with the same `name` in
+    /// its form.
+    SelectRadio {
+        document_id: DocumentId,
+        group_name: String,
+        node_id: NodeId,
+    },
+
+    /// Open or close a `<details>` whose summary was clicked by the user, closing the other `<details>` with the same
+    /// `name` in the document when it opens.
+    ToggleDetails {
+        document_id: DocumentId,
+        node_id: NodeId,
+    },
+
+    /// Select the option at the given index, in tree order, of a `<select>` dropdown chosen by the user.
+    SelectOption {
+        document_id: DocumentId,
+        select_node_id: NodeId,
+        option_index: usize,
+    },
+
+    /// List the cookies in the profile's cookie jar that match the filter.
+    ListCookies { filter: CookieFilter },
+
+    /// Delete the cookies in the profile's cookie jar that match the filter.
+    DeleteCookies { filter: CookieFilter },
+
+    /// Clear the given kinds of data stored for the origin of a URL.
+    ClearSiteData {
+        url: Url,
+        types: Vec<StorageDataType>,
+    },
+
+    /// Estimate the memory used by the subsystems owned by the engine.
+    GetMemoryReport,
+
+    /// Save the resource at a URL to a new file in the given directory, whatever its `Content-Disposition`.
+    SaveAs { url: String, directory: PathBuf },
+
+    /// List the downloads of the session along with their progress.
+    ListDownloads,
+
+    /// Save the open tabs, so that they can be restored if the browser crashes before the next save.
+    SaveSession { tabs: Vec<SessionEntry> },
+
+    /// Take the tabs of the session that was open when the browser last crashed, which are only restored once.
+    RestoreCrashSession,
+
+    /// Write data of the given MIME type to the system clipboard.
+    WriteClipboard { mime_type: String, data: Vec<u8> },
+
+    /// Read the content of the given MIME type from the system clipboard, which requires a recent user gesture such as
+    /// a paste shortcut.
+    ReadClipboard {
+        mime_type: String,
+        user_gesture: Option<Instant>,
+    },
+
+    /// Read the `manifest.json` of the extension in a directory and register the extension.
+    LoadExtension { path: PathBuf },
 }
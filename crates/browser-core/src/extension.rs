@@ -0,0 +1,202 @@
+//! The extensions loaded into the browser, registered from their `manifest.json` by a `LoadExtension` command.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+use extensions::{ExtensionManifest, MANIFEST_FILE_NAME};
+use tracing::info;
+
+use crate::errors::{CoreError, ExtensionError};
+
+/// The maximum size of a `manifest.json`, set to 1 MiB.
+const MAX_MANIFEST_SIZE: u64 = 1024 * 1024;
+
+/// An extension loaded from a directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extension {
+    /// The directory of the extension, which the paths in its manifest are relative to.
+    pub path: PathBuf,
+
+    pub manifest: ExtensionManifest,
+}
+
+/// The extensions loaded in this session, along with the match patterns of their content scripts for deciding which
+/// pages they are injected into.
+#[derive(Debug, Clone, Default)]
+pub struct ExtensionRegistry {
+    extensions: Arc<RwLock<Vec<Extension>>>,
+}
+
+impl ExtensionRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads and registers the extension in a directory, replacing an extension loaded earlier from the same
+    /// directory. Paths are canonicalized first, so the same directory reached through another path, such as a
+    /// symbolic link, is still recognized.
+    ///
+    /// # Arguments
+    /// * `path` - The directory of the extension, or its `manifest.json`.
+    ///
+    /// # Errors
+    /// * If the manifest can not be read or is not a valid extension manifest.
+    ///
+    /// # Panics
+    /// * If the lock of the extension list is poisoned.
+    pub async fn load(&self, path: &Path) -> Result<Extension, CoreError> {
+        let read_error = |path: &Path| {
+            let path = path.to_path_buf();
+            move |source| ExtensionError::Read {
+                path,
+                source: Arc::new(source),
+            }
+        };
+
+        let path = tokio::fs::canonicalize(path)
+            .await
+            .map_err(read_error(path))?;
+        let is_dir = tokio::fs::metadata(&path)
+            .await
+            .map_err(read_error(&path))?
+            .is_dir();
+
+        let (directory, manifest_path) = if is_dir {
+            (path.clone(), path.join(MANIFEST_FILE_NAME))
+        } else {
+            (path.parent().map(Path::to_path_buf).unwrap_or_default(), path)
+        };
+
+        let metadata = tokio::fs::metadata(&manifest_path)
+            .await
+            .map_err(read_error(&manifest_path))?;
+        if metadata.len() > MAX_MANIFEST_SIZE {
+            return Err(ExtensionError::TooLarge(manifest_path).into());
+        }
+
+        let json = tokio::fs::read_to_string(&manifest_path)
+            .await
+            .map_err(read_error(&manifest_path))?;
+        let manifest = ExtensionManifest::parse(&json).map_err(|source| ExtensionError::Manifest {
+            path: manifest_path,
+            source,
+        })?;
+
+        let extension = Extension {
+            path: directory,
+            manifest,
+        };
+        info!(
+            name = extension.manifest.name,
+            version = extension.manifest.version,
+            path = %extension.path.display(),
+            "Loaded extension"
+        );
+
+        let mut extensions = self.extensions.write().unwrap();
+        extensions.retain(|loaded| loaded.path != extension.path);
+        extensions.push(extension.clone());
+
+        Ok(extension)
+    }
+
+    /// The loaded extensions, in the order they were loaded.
+    ///
+    /// # Panics
+    /// * If the lock of the extension list is poisoned.
+    #[must_use]
+    pub fn extensions(&self) -> Vec<Extension> {
+        self.extensions.read().unwrap().clone()
+    }
+
+    /// The match patterns of the content scripts of every loaded extension, along with the name of the extension.
+    ///
+    /// # Panics
+    /// * If the lock of the extension list is poisoned.
+    #[must_use]
+    pub fn content_script_matches(&self) -> Vec<(String, String)> {
+        self.extensions
+            .read()
+            .unwrap()
+            .iter()
+            .flat_map(|extension| {
+                extension
+                    .manifest
+                    .content_script_matches()
+                    .map(|pattern| (extension.manifest.name.clone(), pattern.to_string()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extension_dir(name: &str, manifest: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("extension-test-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join(MANIFEST_FILE_NAME), manifest).unwrap();
+        directory
+    }
+
+    #[tokio::test]
+    async fn test_load_registers_content_script_matches() {
+        let directory = extension_dir(
+            "load",
+            r#"{
+                "manifest_version": 3,
+                "name": "Highlighter",
+                "version": "1.0",
+                "content_scripts": [{ "matches": ["https://*.example.com/*"], "js": ["content.js"] }]
+            }"#,
+        );
+        let registry = ExtensionRegistry::new();
+
+        let extension = registry.load(&directory).await.unwrap();
+        assert_eq!(extension.path, directory.canonicalize().unwrap());
+        registry
+            .load(&directory.join(MANIFEST_FILE_NAME))
+            .await
+            .unwrap();
+        registry.load(&directory.join(".")).await.unwrap();
+
+        assert_eq!(registry.extensions().len(), 1);
+        assert_eq!(
+            registry.content_script_matches(),
+            [("Highlighter".to_string(), "https://*.example.com/*".to_string())]
+        );
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_missing_directory_is_not_registered() {
+        let directory = std::env::temp_dir().join(format!("extension-test-missing-{}", std::process::id()));
+        let registry = ExtensionRegistry::new();
+
+        assert!(matches!(
+            registry.load(&directory).await,
+            Err(CoreError::Extension(ExtensionError::Read { path, .. })) if path == directory
+        ));
+        assert!(registry.extensions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_manifest_is_not_registered() {
+        let directory = extension_dir("invalid", r#"{ "manifest_version": 4, "name": "Future", "version": "1" }"#);
+        let registry = ExtensionRegistry::new();
+
+        assert!(matches!(
+            registry.load(&directory).await,
+            Err(CoreError::Extension(ExtensionError::Manifest { .. }))
+        ));
+        assert!(registry.extensions().is_empty());
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
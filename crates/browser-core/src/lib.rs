@@ -1,13 +1,33 @@
 mod browser;
 mod commands;
 mod context;
+mod documents;
+mod download;
 pub mod errors;
 mod events;
-mod navigation;
+mod extension;
+mod link;
+mod memory;
+mod preconnect;
 mod profile;
+mod scheduler;
+mod script;
+mod session;
+mod spellcheck;
 
-pub use browser::Browser;
+pub use browser::{Browser, load_ua_stylesheet};
+pub use commands::{Navigation, StorageDataType};
 pub use context::collector::TabCollector;
 pub use context::history::History;
-pub use context::page::{Document, PageMetadata};
+pub use context::lazy::{LAZY_LOAD_THRESHOLD, LazyLoadQueue};
+pub use context::page::{Document, DocumentId, Favicon, FaviconLink, PageMetadata};
+pub use context::timing::PageTimings;
+pub use context::viewport::{ViewportDimension, ViewportMeta};
+pub use download::{Download, DownloadState, create_download_file, download_filename};
 pub use events::{Commandable, EngineCommand, EngineResponse, NavigationType};
+pub use extension::{Extension, ExtensionRegistry};
+pub use link::{LinkRel, LinkTarget};
+pub use memory::MemoryUsage;
+pub use script::{DomAccessor, JavaScriptBridge, JsValue, NullJavaScriptBridge};
+pub use session::SessionEntry;
+pub use spellcheck::{BackgroundSpellChecker, NullSpellChecker, SpellChecker, SpellError, SymSpellChecker};
@@ -0,0 +1,113 @@
+//! The browsing context a link is followed in, from its `target` attribute, and the keywords of its `rel` attribute
+//! that restrict what the linked page learns about the page it was opened from.
+//!
+//! <https://html.spec.whatwg.org/multipage/links.html#following-hyperlinks>
+
+/// The browsing context a link navigates, from the `target` attribute of an `<a>` element.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum LinkTarget {
+    /// `_self`, or no target, navigates the tab the link is in.
+    #[default]
+    Current,
+
+    /// `_blank` opens the link in a new tab.
+    Blank,
+
+    /// `_parent` navigates the parent of the frame the link is in, the tab itself when the link is not in a frame.
+    Parent,
+
+    /// `_top` navigates the top-level browsing context of the link, its tab.
+    Top,
+
+    /// Any other name navigates the tab with that name, or opens a new tab with the name if there is none.
+    Named(String),
+}
+
+impl LinkTarget {
+    /// Parses the `target` attribute of a link, matching its keywords ASCII case-insensitively.
+    ///
+    /// # Arguments
+    /// * `target` - The value of the attribute, `None` if it is not set.
+    #[must_use]
+    pub fn from_attribute(target: Option<&str>) -> Self {
+        let Some(target) = target.map(str::trim).filter(|target| !target.is_empty()) else {
+            return Self::Current;
+        };
+
+        match target.to_ascii_lowercase().as_str() {
+            "_self" => Self::Current,
+            "_blank" => Self::Blank,
+            "_parent" => Self::Parent,
+            "_top" => Self::Top,
+            _ => Self::Named(target.to_string()),
+        }
+    }
+}
+
+/// The security keywords of the `rel` attribute of a link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LinkRel {
+    /// The opened page gets no reference to the page that opened it.
+    pub noopener: bool,
+
+    /// The navigation request sends no `Referer` header, which also implies `noopener`.
+    pub noreferrer: bool,
+}
+
+impl LinkRel {
+    /// Parses the space-separated keywords of the `rel` attribute of a link, ASCII case-insensitively.
+    ///
+    /// # Arguments
+    /// * `rel` - The value of the attribute, `None` if it is not set.
+    #[must_use]
+    pub fn from_attribute(rel: Option<&str>) -> Self {
+        let has = |keyword: &str| {
+            rel.is_some_and(|rel| {
+                rel.split_ascii_whitespace()
+                    .any(|token| token.eq_ignore_ascii_case(keyword))
+            })
+        };
+
+        let noreferrer = has("noreferrer");
+
+        Self {
+            noopener: noreferrer || has("noopener"),
+            noreferrer,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_keywords() {
+        assert_eq!(LinkTarget::from_attribute(None), LinkTarget::Current);
+        assert_eq!(LinkTarget::from_attribute(Some("  ")), LinkTarget::Current);
+        assert_eq!(LinkTarget::from_attribute(Some("_self")), LinkTarget::Current);
+        assert_eq!(LinkTarget::from_attribute(Some("_BLANK")), LinkTarget::Blank);
+        assert_eq!(LinkTarget::from_attribute(Some("_parent")), LinkTarget::Parent);
+        assert_eq!(LinkTarget::from_attribute(Some("_top")), LinkTarget::Top);
+        assert_eq!(LinkTarget::from_attribute(Some("docs")), LinkTarget::Named("docs".to_string()));
+    }
+
+    #[test]
+    fn test_rel_keywords() {
+        assert_eq!(LinkRel::from_attribute(None), LinkRel::default());
+        assert_eq!(
+            LinkRel::from_attribute(Some("external NoOpener")),
+            LinkRel {
+                noopener: true,
+                noreferrer: false,
+            }
+        );
+        assert_eq!(
+            LinkRel::from_attribute(Some("noreferrer")),
+            LinkRel {
+                noopener: true,
+                noreferrer: true,
+            }
+        );
+    }
+}
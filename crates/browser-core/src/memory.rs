@@ -0,0 +1,72 @@
+/// An estimate of the memory used by each subsystem of the browser, in bytes, along with the connections held open by
+/// the HTTP client.
+///
+/// The engine only knows the size of the state it owns, its copies of the documents, the network log and the cookie
+/// jar, so the documents shown, layout trees and GPU caches are added by the frontend that holds them before calling
+/// [`MemoryUsage::with_total`]. The HTTP cache is stored on disk and is not counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// The DOM trees of every open document.
+    pub dom_bytes: usize,
+
+    /// The layout trees of every open document.
+    pub layout_bytes: usize,
+
+    /// The decoded images uploaded to the GPU.
+    pub image_cache_bytes: usize,
+
+    /// The glyph atlas texture and its lookup table.
+    pub glyph_atlas_bytes: usize,
+
+    /// The requests recorded by the network log.
+    pub network_log_bytes: usize,
+
+    /// The cookies held in memory.
+    pub cookie_jar_bytes: usize,
+
+    /// The connections of the HTTP client carrying a request.
+    pub connections_active: usize,
+
+    /// The keep-alive connections of the HTTP client waiting to be reused.
+    pub connections_idle: usize,
+
+    /// The sum of every other field.
+    pub total_bytes: usize,
+}
+
+impl MemoryUsage {
+    /// Recomputes `total_bytes` from the byte counts of the subsystems.
+    #[must_use]
+    pub const fn with_total(mut self) -> Self {
+        self.total_bytes = self.dom_bytes
+            + self.layout_bytes
+            + self.image_cache_bytes
+            + self.glyph_atlas_bytes
+            + self.network_log_bytes
+            + self.cookie_jar_bytes;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_total_sums_every_subsystem() {
+        let usage = MemoryUsage {
+            dom_bytes: 1,
+            layout_bytes: 2,
+            image_cache_bytes: 4,
+            glyph_atlas_bytes: 8,
+            network_log_bytes: 16,
+            cookie_jar_bytes: 32,
+            connections_active: 2,
+            connections_idle: 3,
+            total_bytes: 0,
+        }
+        .with_total();
+
+        assert_eq!(usage.total_bytes, 63);
+    }
+}
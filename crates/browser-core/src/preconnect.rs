@@ -0,0 +1,151 @@
+//! Connections opened ahead of time for `<link rel="preconnect">` hints, so that the first request to a hinted origin
+//! does not wait for the DNS lookup and the TCP and TLS handshakes.
+
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use http::Method;
+use http_fetch::client::HttpClient;
+use http_types::{
+    properties::{Credentials, Destination, RequestMode},
+    request::Request,
+};
+use tokio::sync::Semaphore;
+use tracing::{debug, trace};
+use url::Url;
+
+/// The maximum number of connections being opened for preconnect hints at the same time.
+const MAX_CONCURRENT_PRECONNECTS: usize = 6;
+
+/// How long a connection opened for a hint is assumed to stay in the pool of the HTTP client, an origin is not
+/// preconnected to again within this time. Shorter than the idle timeout of the pool.
+const PRECONNECT_LIFETIME: Duration = Duration::from_secs(60);
+
+/// Opens connections to the origins of preconnect hints, shared between all clones.
+///
+/// Each origin is preconnected to once, however many pages hint it, until the connection may have been closed by the
+/// pool. The connection is opened by a `HEAD` request to the root of the origin, without cookies, and is kept alive
+/// by the pool of the HTTP client for the requests of the page.
+#[derive(Debug, Clone)]
+pub(crate) struct PreconnectManager {
+    /// The origins preconnected to, with the time of their preconnect.
+    origins: Arc<Mutex<HashMap<String, Instant>>>,
+    permits: Arc<Semaphore>,
+}
+
+impl Default for PreconnectManager {
+    fn default() -> Self {
+        Self {
+            origins: Arc::default(),
+            permits: Arc::new(Semaphore::new(MAX_CONCURRENT_PRECONNECTS)),
+        }
+    }
+}
+
+impl PreconnectManager {
+    /// Opens a connection to the origin of a URL, unless one was opened recently. Waits while the maximum number of
+    /// preconnects are running.
+    ///
+    /// # Arguments
+    /// * `url` - The URL of the hint, only its origin is used.
+    /// * `client` - The HTTP client whose pool keeps the connection.
+    pub(crate) async fn preconnect(&self, url: &Url, client: &dyn HttpClient) {
+        let Some(origin_url) = Self::origin_url(url) else {
+            return;
+        };
+
+        if !self.claim(&origin_url, Instant::now()) {
+            trace!("Already preconnected to {}", origin_url);
+            return;
+        }
+
+        let Ok(_permit) = self.permits.acquire().await else {
+            return;
+        };
+
+        let request = Request::builder_url(origin_url.clone())
+            .method(Method::HEAD)
+            .credentials(Credentials::Omit)
+            .destination(Destination::Empty)
+            .request_mode(RequestMode::Cors)
+            .build();
+
+        match client.send(Arc::new(request.context), request.body).await {
+            Ok(_) => trace!("Preconnected to {}", origin_url),
+            Err(error) => {
+                debug!(%error, "Failed to preconnect to {}", origin_url);
+                self.release(&origin_url);
+            }
+        }
+    }
+
+    /// The root URL of the origin of a URL, `None` for opaque origins.
+    fn origin_url(url: &Url) -> Option<Url> {
+        let origin = url.origin();
+        if !origin.is_tuple() {
+            return None;
+        }
+
+        Url::parse(&origin.ascii_serialization()).ok()
+    }
+
+    /// Marks an origin as preconnected to at `now`.
+    ///
+    /// # Returns
+    /// Whether the origin was not preconnected to within the lifetime of a connection, so it should be now.
+    fn claim(&self, origin_url: &Url, now: Instant) -> bool {
+        let Ok(mut origins) = self.origins.lock() else {
+            return false;
+        };
+
+        origins.retain(|_, preconnected| now.duration_since(*preconnected) < PRECONNECT_LIFETIME);
+
+        match origins.entry(origin_url.to_string()) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert(now);
+                true
+            }
+        }
+    }
+
+    /// Forgets a failed preconnect, so that a later hint for the origin tries again.
+    fn release(&self, origin_url: &Url) {
+        if let Ok(mut origins) = self.origins.lock() {
+            origins.remove(origin_url.as_str());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_origin_url() {
+        let url = Url::parse("https://fonts.googleapis.com/css2?family=Inter").unwrap();
+        assert_eq!(PreconnectManager::origin_url(&url).unwrap().as_str(), "https://fonts.googleapis.com/");
+
+        let opaque = Url::parse("data:text/plain,hello").unwrap();
+        assert_eq!(PreconnectManager::origin_url(&opaque), None);
+    }
+
+    #[test]
+    fn test_claim_deduplicates_origins() {
+        let manager = PreconnectManager::default();
+        let origin = Url::parse("https://cdn.example.com/").unwrap();
+        let other = Url::parse("https://fonts.example.com/").unwrap();
+        let now = Instant::now();
+
+        assert!(manager.claim(&origin, now));
+        assert!(!manager.claim(&origin, now));
+        assert!(manager.claim(&other, now));
+        assert!(manager.claim(&origin, now + PRECONNECT_LIFETIME));
+
+        manager.release(&other);
+        assert!(manager.claim(&other, now + PRECONNECT_LIFETIME));
+    }
+}
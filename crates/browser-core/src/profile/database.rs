@@ -1,8 +1,12 @@
 use cookies::{CookieDatabase, CookieJar};
 use database::Database;
 use http_cache::{http::HttpCache, index::IndexDatabase};
+use tracing::{debug, warn};
 
-use crate::{errors::CoreError, profile::paths::ProfilePaths};
+use crate::{
+    errors::{CoreError, StorageError},
+    profile::paths::ProfilePaths,
+};
 
 #[derive(Debug)]
 pub(crate) struct Databases {
@@ -12,14 +16,24 @@ pub(crate) struct Databases {
 
 impl Databases {
     pub fn init(dirs: &ProfilePaths) -> Result<Self, CoreError> {
-        let cookie_database =
-            CookieDatabase::open(dirs.into()).map_err(|e| CoreError::InitializeDatabase(e.to_string()))?;
+        let cookie_database = CookieDatabase::open(dirs.into()).map_err(|e| StorageError::OpenDatabase {
+            name: "cookie",
+            message: e.to_string(),
+        })?;
         let cookie_jar = CookieJar::load(cookie_database);
 
-        let index_database =
-            IndexDatabase::open(dirs.into()).map_err(|e| CoreError::InitializeDatabase(e.to_string()))?;
+        let index_database = IndexDatabase::open(dirs.into()).map_err(|e| StorageError::OpenDatabase {
+            name: "cache index",
+            message: e.to_string(),
+        })?;
         let http_cache = HttpCache::new(index_database);
 
+        match http_cache.purge_unpartitioned(&dirs.into()) {
+            Ok(0) => {}
+            Ok(purged) => debug!(purged, "Removed cache entries stored before the cache was partitioned"),
+            Err(error) => warn!(%error, "Failed to remove cache entries stored before the cache was partitioned"),
+        }
+
         Ok(Self {
             cookie_jar,
             http_cache,
@@ -0,0 +1,156 @@
+use std::sync::{Arc, Mutex};
+
+use io::resource::{Priority, ResourceManager, ResourceRequest};
+use tokio::sync::{
+    Notify,
+    oneshot::{self, Sender},
+};
+
+/// Schedules sub-resource loads through a [`ResourceManager`].
+///
+/// A load waits for a [`ResourcePermit`] of its priority before touching the network, and frees its slot by dropping
+/// the permit once it finishes, which starts the next queued load. The browser shares one scheduler between the
+/// images, fonts and hints of every page, while each navigation schedules the resources that block its first paint
+/// with its own, so that the loads of other pages never delay it.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ResourceScheduler {
+    manager: Arc<Mutex<ResourceManager<Sender<ResourcePermit>>>>,
+
+    /// Notified whenever the last queued critical load finishes.
+    critical_drained: Arc<Notify>,
+}
+
+impl ResourceScheduler {
+    /// Queues a load of the given priority, which counts as pending from this call on.
+    ///
+    /// # Returns
+    /// A future resolving to the permit once the load may start.
+    ///
+    /// # Panics
+    /// * If the lock of the manager is poisoned.
+    pub(crate) fn acquire(&self, priority: Priority) -> impl Future<Output = ResourcePermit> + Send + 'static {
+        let (sender, receiver) = oneshot::channel();
+
+        self.manager.lock().unwrap().push(ResourceRequest {
+            priority,
+            payload: sender,
+        });
+        self.dispatch();
+
+        async move {
+            receiver
+                .await
+                .expect("Queued resource requests are always granted a permit")
+        }
+    }
+
+    /// Whether every critical resource queued so far has been loaded.
+    ///
+    /// # Panics
+    /// * If the lock of the manager is poisoned.
+    pub(crate) fn is_first_paint_ready(&self) -> bool {
+        self.manager.lock().unwrap().is_first_paint_ready()
+    }
+
+    /// Waits until every critical resource queued so far has been loaded, after which the page can be painted while
+    /// less urgent resources are still loading.
+    pub(crate) async fn first_paint_ready(&self) {
+        loop {
+            let drained = self.critical_drained.notified();
+            tokio::pin!(drained);
+            drained.as_mut().enable();
+
+            if self.is_first_paint_ready() {
+                return;
+            }
+
+            drained.await;
+        }
+    }
+
+    /// Grants a permit to every queued load that has a free slot.
+    fn dispatch(&self) {
+        let mut unclaimed = Vec::new();
+
+        {
+            let Ok(mut manager) = self.manager.lock() else {
+                return;
+            };
+
+            while let Some(request) = manager.pop() {
+                let permit = ResourcePermit {
+                    scheduler: self.clone(),
+                    priority: request.priority,
+                };
+
+                // The load was cancelled while queued, its permit is released once the lock is dropped.
+                if let Err(permit) = request.payload.send(permit) {
+                    unclaimed.push(permit);
+                }
+            }
+        }
+
+        drop(unclaimed);
+    }
+}
+
+/// A slot of a priority tier held by a running load, freed when dropped.
+#[derive(Debug)]
+pub(crate) struct ResourcePermit {
+    scheduler: ResourceScheduler,
+    priority: Priority,
+}
+
+impl Drop for ResourcePermit {
+    fn drop(&mut self) {
+        let drained = self.scheduler.manager.lock().is_ok_and(|mut manager| {
+            manager.complete(self.priority);
+            manager.is_first_paint_ready()
+        });
+
+        self.scheduler.dispatch();
+
+        if drained && self.priority == Priority::Critical {
+            self.scheduler.critical_drained.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::timeout;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_first_paint_waits_for_critical_loads() {
+        let scheduler = ResourceScheduler::default();
+        let permit = scheduler.acquire(Priority::Critical);
+        assert!(!scheduler.is_first_paint_ready());
+
+        let waiting = scheduler.clone();
+        let first_paint = tokio::spawn(async move { waiting.first_paint_ready().await });
+
+        let permit = permit.await;
+        tokio::task::yield_now().await;
+        assert!(!first_paint.is_finished());
+
+        drop(permit);
+        timeout(Duration::from_secs(1), first_paint)
+            .await
+            .expect("first paint is signalled once the critical loads finish")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_first_paint_ignores_lower_priorities() {
+        let scheduler = ResourceScheduler::default();
+        let _image = scheduler.acquire(Priority::Normal).await;
+
+        timeout(Duration::from_secs(1), scheduler.first_paint_ready())
+            .await
+            .expect("loads below the critical tier never delay the first paint");
+    }
+}
@@ -0,0 +1,353 @@
+use std::{collections::HashSet, fmt::Debug};
+
+use css_cssom::ComponentValue;
+use css_selectors::{matches_selector_list, parse_selector_list};
+use css_tokenizer::CssTokenizer;
+use html_dom::{DocumentRoot, DomNode, Element, NodeData, NodeId, Tag};
+
+use crate::errors::JsError;
+
+/// A value returned from evaluating a script.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum JsValue {
+    #[default]
+    Undefined,
+    Null,
+    Boolean(bool),
+    Number(f64),
+    String(String),
+
+    /// A reference to a node of the document the script was evaluated against.
+    Node(NodeId),
+}
+
+/// The bridge between the engine and a JavaScript runtime, which evaluates the scripts of a page against its DOM.
+///
+/// The engine does not contain a JavaScript engine itself, a runtime is plugged in by implementing this trait and
+/// exposing the methods of `DomAccessor` to the scripts it runs.
+pub trait JavaScriptBridge: Debug + Send + Sync {
+    /// Evaluates a script against a document.
+    ///
+    /// # Arguments
+    /// * `script` - The source of the script.
+    /// * `dom` - The document the script runs in, which it may modify.
+    ///
+    /// # Returns
+    /// The completion value of the script.
+    ///
+    /// # Errors
+    /// Returns a `JsError` if the script throws or fails to evaluate.
+    fn evaluate(&self, script: &str, dom: &mut DocumentRoot) -> Result<JsValue, JsError>;
+
+    /// Fires a DOM event at a node, running the listeners the scripts of the page added for it. Runtimes that do
+    /// not track listeners can leave the default, which ignores the event.
+    ///
+    /// # Arguments
+    /// * `target` - The node the event is fired at.
+    /// * `event_type` - The type of the event, e.g. `change`.
+    /// * `dom` - The document the target belongs to, which the listeners may modify through the accessor so that
+    ///   their changes are sent back to the user interface.
+    ///
+    /// # Errors
+    /// Returns a `JsError` if a listener throws.
+    fn dispatch_event(&self, target: NodeId, event_type: &str, dom: &mut DomAccessor<'_>) -> Result<(), JsError> {
+        let _ = (target, event_type, dom);
+        Ok(())
+    }
+}
+
+/// A `JavaScriptBridge` that ignores every script, used when no JavaScript runtime is available.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NullJavaScriptBridge;
+
+impl JavaScriptBridge for NullJavaScriptBridge {
+    fn evaluate(&self, _script: &str, _dom: &mut DocumentRoot) -> Result<JsValue, JsError> {
+        Ok(JsValue::Undefined)
+    }
+}
+
+/// The DOM operations a `JavaScriptBridge` exposes to scripts, like `document.querySelector` and
+/// `Element.setAttribute`.
+pub struct DomAccessor<'dom> {
+    dom: &'dom mut DocumentRoot,
+
+    /// The nodes modified or created through the accessor.
+    changed: HashSet<NodeId>,
+}
+
+impl<'dom> DomAccessor<'dom> {
+    /// Creates an accessor for the given document.
+    pub fn new(dom: &'dom mut DocumentRoot) -> Self {
+        Self {
+            dom,
+            changed: HashSet::new(),
+        }
+    }
+
+    /// The nodes modified or created through the accessor, as they are now, to be applied to the other copies of the
+    /// document with `DocumentRoot::apply_changes`.
+    #[must_use]
+    pub fn into_changes(self) -> Vec<DomNode> {
+        self.changed
+            .into_iter()
+            .filter_map(|node_id| self.dom.get_node(&node_id).cloned())
+            .collect()
+    }
+
+    /// Finds the first element in document order matching a selector, like `document.querySelector`.
+    ///
+    /// # Arguments
+    /// * `selector` - A CSS selector list.
+    ///
+    /// # Returns
+    /// The first matching element attached to the document, or `None` if no element matches.
+    ///
+    /// # Errors
+    /// Returns `JsError::InvalidSelector` if the selector cannot be parsed.
+    pub fn query_selector(&self, selector: &str) -> Result<Option<NodeId>, JsError> {
+        let component_values: Vec<ComponentValue> = CssTokenizer::new(selector, false)
+            .map(ComponentValue::Token)
+            .collect();
+        let selector_list = parse_selector_list(&component_values);
+
+        if selector_list.is_empty() {
+            return Err(JsError::InvalidSelector(selector.to_string()));
+        }
+
+        let mut stack: Vec<NodeId> = self.dom.root_nodes.iter().rev().copied().collect();
+        while let Some(node_id) = stack.pop() {
+            let node = &self.dom[node_id];
+
+            if let NodeData::Element(element) = &node.data
+                && matches_selector_list(&selector_list, self.dom, node, element.class_set.as_ref())
+            {
+                return Ok(Some(node_id));
+            }
+
+            stack.extend(node.children.iter().rev());
+        }
+
+        Ok(None)
+    }
+
+    /// Reads an attribute of an element, like `Element.getAttribute`.
+    ///
+    /// # Errors
+    /// Returns `JsError::NotAnElement` if the node does not exist or is not an element.
+    pub fn get_attribute(&self, node_id: NodeId, name: &str) -> Result<Option<String>, JsError> {
+        Ok(self
+            .element(node_id)?
+            .get_attribute(name)
+            .map(str::to_string))
+    }
+
+    /// Sets an attribute of an element, like `Element.setAttribute`. Setting `class` also updates the classes the
+    /// element is matched with.
+    ///
+    /// # Errors
+    /// Returns `JsError::NotAnElement` if the node does not exist or is not an element.
+    pub fn set_attribute(&mut self, node_id: NodeId, name: &str, value: &str) -> Result<(), JsError> {
+        let element = self.element_mut(node_id)?;
+        element.set_attribute(name, value);
+
+        if name.eq_ignore_ascii_case("class") {
+            element.class_set = Some(value.split_whitespace().map(str::to_string).collect());
+        }
+        self.changed.insert(node_id);
+
+        Ok(())
+    }
+
+    /// Creates an element that is not attached to the document yet, like `document.createElement`.
+    ///
+    /// # Arguments
+    /// * `tag_name` - The tag name of the element.
+    pub fn create_element(&mut self, tag_name: &str) -> NodeId {
+        let node_id = self.dom.create_node(NodeData::Element(Element {
+            tag: Tag::from_str_insensitive(tag_name),
+            ..Element::default()
+        }));
+        self.changed.insert(node_id);
+
+        node_id
+    }
+
+    /// Appends a node as the last child of an element, moving it if it already has a parent, like
+    /// `Node.appendChild`.
+    ///
+    /// # Errors
+    /// Returns `JsError::HierarchyRequest` if the parent is not an element or the child contains the parent.
+    pub fn append_child(&mut self, parent: NodeId, child: NodeId) -> Result<(), JsError> {
+        let previous_parent = self.dom.get_node(&child).and_then(|node| node.parent);
+
+        if self.dom.append_child(parent, child) {
+            self.changed
+                .extend([parent, child].into_iter().chain(previous_parent));
+            Ok(())
+        } else {
+            Err(JsError::HierarchyRequest { parent, child })
+        }
+    }
+
+    /// Removes a child from an element, like `Node.removeChild`.
+    ///
+    /// # Returns
+    /// The removed node, which stays valid and can be inserted again.
+    ///
+    /// # Errors
+    /// Returns `JsError::NotFound` if `child` is not a child of `parent`.
+    pub fn remove_child(&mut self, parent: NodeId, child: NodeId) -> Result<NodeId, JsError> {
+        if self.dom.remove_child(parent, child) {
+            self.changed.extend([parent, child]);
+            Ok(child)
+        } else {
+            Err(JsError::NotFound { parent, child })
+        }
+    }
+
+    fn element(&self, node_id: NodeId) -> Result<&Element, JsError> {
+        self.dom
+            .get_node(&node_id)
+            .and_then(|node| node.data.as_element())
+            .ok_or(JsError::NotAnElement(node_id))
+    }
+
+    fn element_mut(&mut self, node_id: NodeId) -> Result<&mut Element, JsError> {
+        self.dom
+            .nodes
+            .get_mut(*node_id)
+            .and_then(|node| node.data.as_element_mut())
+            .ok_or(JsError::NotAnElement(node_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use html_dom::HtmlTag;
+
+    use super::*;
+
+    fn element(tag: HtmlTag, attributes: &[(&str, &str)]) -> NodeData {
+        let attributes: HashMap<String, String> = attributes
+            .iter()
+            .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+            .collect();
+
+        NodeData::Element(Element {
+            class_set: attributes
+                .get("class")
+                .map(|classes| classes.split_whitespace().map(str::to_string).collect()),
+            attributes: Some(attributes),
+            tag: Tag::Html(tag),
+        })
+    }
+
+    /// `<html><body><div id="main"><p class="intro">Hello</p></div></body></html>`
+    fn document() -> (DocumentRoot, NodeId, NodeId) {
+        let mut dom = DocumentRoot::new();
+        let html = dom.push_node(&element(HtmlTag::Html, &[]), None);
+        let body = dom.push_node(&element(HtmlTag::Body, &[]), Some(html));
+        let main = dom.push_node(&element(HtmlTag::Div, &[("id", "main")]), Some(body));
+        let intro = dom.push_node(&element(HtmlTag::P, &[("class", "intro")]), Some(main));
+        dom.push_node(&NodeData::Text("Hello".to_string()), Some(intro));
+
+        (dom, main, intro)
+    }
+
+    #[test]
+    fn test_null_bridge_ignores_scripts() {
+        let (mut dom, _, _) = document();
+        let nodes = dom.nodes.len();
+
+        let result = NullJavaScriptBridge.evaluate("document.body.remove()", &mut dom);
+
+        assert_eq!(result, Ok(JsValue::Undefined));
+        assert_eq!(dom.nodes.len(), nodes);
+    }
+
+    #[test]
+    fn test_query_selector() {
+        let (mut dom, main, intro) = document();
+        let accessor = DomAccessor::new(&mut dom);
+
+        assert_eq!(accessor.query_selector("#main"), Ok(Some(main)));
+        assert_eq!(accessor.query_selector("div .intro"), Ok(Some(intro)));
+        assert_eq!(accessor.query_selector("span, p"), Ok(Some(intro)));
+        assert_eq!(accessor.query_selector("span"), Ok(None));
+    }
+
+    #[test]
+    fn test_attributes() {
+        let (mut dom, main, intro) = document();
+        let mut accessor = DomAccessor::new(&mut dom);
+
+        assert_eq!(accessor.get_attribute(main, "id"), Ok(Some("main".to_string())));
+        assert_eq!(accessor.get_attribute(main, "title"), Ok(None));
+
+        accessor.set_attribute(intro, "class", "lead").unwrap();
+        assert_eq!(accessor.query_selector(".intro"), Ok(None));
+        assert_eq!(accessor.query_selector(".lead"), Ok(Some(intro)));
+
+        let text = dom[intro].children[0];
+        let accessor = DomAccessor::new(&mut dom);
+        assert_eq!(accessor.get_attribute(text, "id"), Err(JsError::NotAnElement(text)));
+    }
+
+    #[test]
+    fn test_create_append_and_remove() {
+        let (mut dom, main, intro) = document();
+        let mut accessor = DomAccessor::new(&mut dom);
+
+        let span = accessor.create_element("SPAN");
+        assert_eq!(accessor.query_selector("span"), Ok(None));
+
+        accessor.append_child(main, span).unwrap();
+        assert_eq!(accessor.query_selector("#main span"), Ok(Some(span)));
+
+        assert_eq!(accessor.remove_child(main, intro), Ok(intro));
+        assert_eq!(accessor.query_selector("p"), Ok(None));
+        assert_eq!(
+            accessor.remove_child(main, intro),
+            Err(JsError::NotFound {
+                parent: main,
+                child: intro
+            })
+        );
+
+        assert_eq!(
+            accessor.append_child(span, main),
+            Err(JsError::HierarchyRequest {
+                parent: span,
+                child: main
+            })
+        );
+
+        assert_eq!(dom[main].children, vec![span]);
+        assert_eq!(dom[span].parent, Some(main));
+        assert_eq!(dom[intro].parent, None);
+    }
+
+    #[test]
+    fn test_changes_are_applied_to_another_copy() {
+        let (mut dom, main, intro) = document();
+        let mut copy = dom.clone();
+
+        let mut accessor = DomAccessor::new(&mut dom);
+        accessor.set_attribute(main, "title", "Main").unwrap();
+        let span = accessor.create_element("span");
+        accessor.append_child(intro, span).unwrap();
+
+        let changes = accessor.into_changes();
+        let mut changed: Vec<NodeId> = changes.iter().map(|node| node.id).collect();
+        changed.sort_by_key(|node_id| node_id.0);
+        assert_eq!(changed, vec![main, intro, span]);
+
+        assert!(copy.apply_changes(changes));
+        assert_eq!(copy.nodes.len(), dom.nodes.len());
+        assert_eq!(copy[intro].children, dom[intro].children);
+        assert_eq!(copy[span].parent, Some(intro));
+        assert_eq!(copy[main].data.as_element().unwrap().get_attribute("title"), Some("Main"));
+    }
+}
@@ -0,0 +1,274 @@
+//! The tabs of the running session, saved to the profile so that they can be reopened after the browser crashes.
+//!
+//! The browser holds an OS lock on a lock file while it runs, and writes its process ID to it. A clean exit empties the
+//! file. Finding a process ID in the file after acquiring its lock means the previous run never exited cleanly, so the
+//! tabs it last saved are offered for recovery. A run that can not acquire the lock shares the profile with a running
+//! browser, and leaves its session alone.
+
+use std::{
+    fs::{File, OpenOptions, TryLockError},
+    io::{Read, Seek, SeekFrom, Write},
+    path::Path,
+    sync::Mutex,
+};
+
+use io::{
+    Readable, Writable,
+    entries::{PROFILE_SESSION, PROFILE_SESSION_LOCK},
+    errors::ResourceError,
+    paths::AppPaths,
+};
+use postcard::{from_bytes, to_stdvec};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+/// The maximum size of the saved session file, set to 1 MiB.
+const MAX_SESSION_SIZE: Option<u64> = Some(1024 * 1024);
+
+/// A tab of a saved session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionEntry {
+    /// The URL of the page shown in the tab.
+    pub url: String,
+
+    /// The title of the page, empty if it has none.
+    pub title: String,
+}
+
+/// Marks the session as running until a clean exit, and keeps the tabs of the previous session if it crashed.
+#[derive(Debug)]
+pub(crate) struct SessionGuard {
+    paths: AppPaths,
+
+    /// The locked lock file, `None` if another run of the browser holds the lock or it could not be created.
+    lock: Mutex<Option<File>>,
+
+    /// The tabs saved by the previous run if it did not exit cleanly, until they are restored.
+    crashed_session: Mutex<Option<Vec<SessionEntry>>>,
+}
+
+impl SessionGuard {
+    /// Checks whether the previous run exited cleanly and marks this one as running. The lock file is written and
+    /// synced synchronously, so it is on disk before the first navigation starts.
+    ///
+    /// # Arguments
+    /// * `paths` - The directories of the profile the session belongs to.
+    pub(crate) fn acquire(paths: AppPaths) -> Self {
+        let (lock, crashed) = match Self::lock(&PROFILE_SESSION_LOCK.path(&paths)) {
+            Ok(Some((file, crashed))) => (Some(file), crashed),
+            Ok(None) => {
+                info!("The profile is in use by another running browser, its session is not recovered or saved");
+                (None, false)
+            }
+            Err(error) => {
+                warn!(%error, "Failed to mark the session as running, it will not be recovered after a crash");
+                (None, false)
+            }
+        };
+
+        let crashed_session = if crashed {
+            match PROFILE_SESSION.read(&paths, MAX_SESSION_SIZE) {
+                Ok(data) => from_bytes::<Vec<SessionEntry>>(&data)
+                    .inspect_err(|error| warn!(%error, "Failed to parse the session of the previous run"))
+                    .ok()
+                    .filter(|tabs| !tabs.is_empty()),
+                Err(error) => {
+                    debug!(%error, "No session saved by the previous run");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        if let Some(tabs) = &crashed_session {
+            info!("The previous run did not exit cleanly, {} tabs can be restored", tabs.len());
+        }
+
+        Self {
+            paths,
+            lock: Mutex::new(lock),
+            crashed_session: Mutex::new(crashed_session),
+        }
+    }
+
+    /// Locks the lock file and writes the ID of this process to it.
+    ///
+    /// # Returns
+    /// The locked file and whether it held the process ID of a run that did not exit cleanly, or `None` if another
+    /// running browser holds the lock.
+    fn lock(path: &Path) -> std::io::Result<Option<(File, bool)>> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => return Ok(None),
+            Err(TryLockError::Error(error)) => return Err(error),
+        }
+
+        let mut previous = String::new();
+        file.read_to_string(&mut previous)?;
+        let crashed = !previous.trim().is_empty();
+        if crashed {
+            debug!(pid = previous.trim(), "Found the lock file of a previous run");
+        }
+
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(std::process::id().to_string().as_bytes())?;
+        file.sync_all()?;
+
+        Ok(Some((file, crashed)))
+    }
+
+    /// The tabs of the previous run if it crashed and they have not been restored yet.
+    ///
+    /// # Panics
+    /// * If the lock of the crashed session is poisoned.
+    pub(crate) fn crashed_session(&self) -> Option<Vec<SessionEntry>> {
+        self.crashed_session.lock().unwrap().clone()
+    }
+
+    /// Takes the tabs of the previous run for restoring, so that they are only offered once.
+    ///
+    /// # Panics
+    /// * If the lock of the crashed session is poisoned.
+    pub(crate) fn take_crashed_session(&self) -> Option<Vec<SessionEntry>> {
+        self.crashed_session.lock().unwrap().take()
+    }
+
+    /// Saves the tabs currently open, replacing the previously saved ones. Nothing is saved when another running
+    /// browser holds the lock, as the saved session is the one of that browser.
+    ///
+    /// # Errors
+    /// * If the session can not be serialized or written to the profile.
+    ///
+    /// # Panics
+    /// * If the lock of the lock file is poisoned.
+    pub(crate) fn save(&self, tabs: &[SessionEntry]) -> Result<(), ResourceError> {
+        if self.lock.lock().unwrap().is_none() {
+            return Ok(());
+        }
+
+        let data = to_stdvec(tabs).map_err(|error| ResourceError::Io(error.to_string()))?;
+
+        PROFILE_SESSION.write(data, &self.paths)
+    }
+
+    /// Marks the session as exited cleanly by emptying the lock file and releasing its lock, so that the next run does
+    /// not offer to recover it.
+    ///
+    /// # Panics
+    /// * If the lock of the lock file is poisoned.
+    pub(crate) fn release(&self) {
+        let Some(file) = self.lock.lock().unwrap().take() else {
+            return;
+        };
+
+        match file.set_len(0).and_then(|()| file.sync_all()) {
+            Ok(()) => debug!("Session exited cleanly"),
+            Err(error) => warn!(%error, "Failed to mark the session as exited cleanly"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    fn paths(name: &str) -> AppPaths {
+        let directory = Arc::new(std::env::temp_dir().join(format!("session-test-{name}-{}", std::process::id())));
+        let _ = std::fs::remove_dir_all(directory.as_path());
+
+        AppPaths {
+            profile_cache: Arc::clone(&directory),
+            profile_config: Arc::clone(&directory),
+            profile_data: Arc::clone(&directory),
+            global_cache: Arc::clone(&directory),
+            global_config: Arc::clone(&directory),
+            global_data: Arc::clone(&directory),
+            temp: directory,
+        }
+    }
+
+    fn tabs() -> Vec<SessionEntry> {
+        vec![
+            SessionEntry {
+                url: "https://example.com/".to_string(),
+                title: "Example Domain".to_string(),
+            },
+            SessionEntry {
+                url: "https://example.org/docs".to_string(),
+                title: String::new(),
+            },
+        ]
+    }
+
+    fn cleanup(paths: &AppPaths) {
+        std::fs::remove_dir_all(paths.profile_data.as_path()).unwrap();
+    }
+
+    #[test]
+    fn test_crash_offers_saved_session() {
+        let paths = paths("crash");
+
+        let guard = SessionGuard::acquire(paths.clone());
+        assert_eq!(guard.crashed_session(), None);
+        guard.save(&tabs()).unwrap();
+        drop(guard);
+
+        let guard = SessionGuard::acquire(paths.clone());
+        assert_eq!(guard.crashed_session(), Some(tabs()));
+        assert_eq!(guard.take_crashed_session(), Some(tabs()));
+        assert_eq!(guard.crashed_session(), None);
+
+        cleanup(&paths);
+    }
+
+    #[test]
+    fn test_second_run_leaves_running_session_alone() {
+        let paths = paths("shared");
+
+        let guard = SessionGuard::acquire(paths.clone());
+        guard.save(&tabs()).unwrap();
+
+        let other = SessionGuard::acquire(paths.clone());
+        assert_eq!(other.crashed_session(), None);
+        other.save(&[]).unwrap();
+        other.release();
+        drop(other);
+        drop(guard);
+
+        assert_eq!(std::fs::read_to_string(PROFILE_SESSION_LOCK.path(&paths)).unwrap(), std::process::id().to_string());
+
+        let guard = SessionGuard::acquire(paths.clone());
+        assert_eq!(guard.crashed_session(), Some(tabs()));
+
+        cleanup(&paths);
+    }
+
+    #[test]
+    fn test_clean_exit_is_not_recovered() {
+        let paths = paths("clean");
+
+        let guard = SessionGuard::acquire(paths.clone());
+        guard.save(&tabs()).unwrap();
+        guard.release();
+
+        let guard = SessionGuard::acquire(paths.clone());
+        assert_eq!(guard.crashed_session(), None);
+
+        cleanup(&paths);
+    }
+}
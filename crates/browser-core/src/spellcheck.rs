@@ -0,0 +1,437 @@
+//! Spell checking of the text entered into editable controls, which the UI underlines where words are misspelled.
+//!
+//! The checker is a trait so that another backend, such as Hunspell, can be plugged in with
+//! [`Browser::with_spell_checker`](crate::Browser::with_spell_checker). The default backend looks words up in a
+//! frequency dictionary with the `SymSpell` algorithm, or in the word list of the system when the profile has no
+//! dictionary.
+
+use std::{
+    fmt::{Debug, Formatter},
+    fs,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
+    thread,
+};
+
+use symspell::{SymSpell, UnicodeStringStrategy, Verbosity};
+use tracing::{trace, warn};
+
+/// The largest number of edits between a misspelled word and the suggestions offered for it.
+const MAX_EDIT_DISTANCE: i64 = 2;
+
+/// The largest number of suggestions offered for a misspelled word.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// The word lists installed with the system, one word per line, used when the profile has no dictionary.
+#[cfg(unix)]
+const SYSTEM_WORD_LISTS: &[&str] = &["/usr/share/dict/words", "/usr/dict/words"];
+#[cfg(not(unix))]
+const SYSTEM_WORD_LISTS: &[&str] = &[];
+
+/// A misspelled word found by a `SpellChecker`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpellError {
+    /// The byte range of the word in the checked text.
+    pub range: Range<usize>,
+
+    /// The words it may have been meant as, most likely first.
+    pub suggestions: Vec<String>,
+}
+
+/// Finds the misspelled words of a text.
+pub trait SpellChecker: Debug + Send + Sync {
+    /// Checks the spelling of a text.
+    ///
+    /// # Arguments
+    /// * `text` - The text to check, such as the value of a `<textarea>`.
+    ///
+    /// # Returns
+    /// The misspelled words of the text, in the order they appear.
+    fn check(&self, text: &str) -> Vec<SpellError>;
+
+    /// Updates the misspelled words of a text after it was edited, checking only the words touched by the edit and
+    /// moving the misspelled words after it.
+    ///
+    /// # Arguments
+    /// * `errors` - The misspelled words of the text before the edit.
+    /// * `previous` - The text before the edit.
+    /// * `text` - The text after the edit.
+    ///
+    /// # Returns
+    /// The misspelled words of the edited text, in the order they appear.
+    fn check_edit(&self, errors: &[SpellError], previous: &str, text: &str) -> Vec<SpellError> {
+        let edited = edited_words(previous, text);
+        let previous_end = edited.end + previous.len() - text.len();
+
+        let before = errors
+            .iter()
+            .filter(|error| error.range.end < edited.start)
+            .cloned();
+        let rechecked = self
+            .check(&text[edited.clone()])
+            .into_iter()
+            .map(|error| SpellError {
+                range: error.range.start + edited.start..error.range.end + edited.start,
+                ..error
+            });
+        let after = errors
+            .iter()
+            .filter(|error| error.range.start > previous_end)
+            .map(|error| SpellError {
+                range: error.range.start + text.len() - previous.len()..error.range.end + text.len() - previous.len(),
+                ..error.clone()
+            });
+
+        before.chain(rechecked).chain(after).collect()
+    }
+}
+
+/// A `SpellChecker` that accepts every word, used when no dictionary is available.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NullSpellChecker;
+
+impl SpellChecker for NullSpellChecker {
+    fn check(&self, _text: &str) -> Vec<SpellError> {
+        Vec::new()
+    }
+}
+
+/// A `SpellChecker` backed by a frequency dictionary, suggesting the most frequent words within two edits of a
+/// misspelled word.
+pub struct SymSpellChecker {
+    symspell: SymSpell<UnicodeStringStrategy>,
+}
+
+impl SymSpellChecker {
+    /// Loads a frequency dictionary, a text file with a word and its count separated by a space on each line. A word
+    /// list without counts, like the ones installed with the system, is loaded with every word equally frequent.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the dictionary.
+    ///
+    /// # Returns
+    /// The checker, or `None` if the dictionary can not be read or has no words.
+    #[must_use]
+    pub fn from_dictionary(path: &Path) -> Option<Self> {
+        let dictionary = fs::read_to_string(path).ok()?;
+        let mut symspell = SymSpell::default();
+        let mut loaded = false;
+
+        for line in dictionary
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+        {
+            loaded |= if line.contains(' ') {
+                symspell.load_dictionary_line(line, 0, 1, " ")
+            } else {
+                symspell.load_dictionary_line(&format!("{} 1", line.to_lowercase()), 0, 1, " ")
+            };
+        }
+
+        loaded.then_some(Self { symspell })
+    }
+}
+
+impl Debug for SymSpellChecker {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SymSpellChecker").finish_non_exhaustive()
+    }
+}
+
+impl SpellChecker for SymSpellChecker {
+    fn check(&self, text: &str) -> Vec<SpellError> {
+        words(text)
+            .filter_map(|range| {
+                let word = text[range.clone()].to_lowercase();
+                let suggestions = self
+                    .symspell
+                    .lookup(&word, Verbosity::Closest, MAX_EDIT_DISTANCE);
+
+                if suggestions
+                    .first()
+                    .is_some_and(|suggestion| suggestion.distance == 0)
+                {
+                    return None;
+                }
+
+                Some(SpellError {
+                    range,
+                    suggestions: suggestions
+                        .into_iter()
+                        .take(MAX_SUGGESTIONS)
+                        .map(|suggestion| suggestion.term)
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// A `SymSpellChecker` whose dictionary is loaded on a background thread, so that starting the browser does not wait
+/// for it. Every word is accepted until the dictionary is loaded, or if none of the dictionaries can be read.
+#[derive(Debug, Clone, Default)]
+pub struct BackgroundSpellChecker {
+    checker: Arc<OnceLock<SymSpellChecker>>,
+}
+
+impl BackgroundSpellChecker {
+    /// Starts loading the first dictionary that can be read out of the one in the profile and the word lists of the
+    /// system.
+    ///
+    /// # Arguments
+    /// * `profile_dictionary` - The path of the frequency dictionary of the profile, tried first.
+    #[must_use]
+    pub fn load(profile_dictionary: PathBuf) -> Self {
+        let paths: Vec<PathBuf> = std::iter::once(profile_dictionary)
+            .chain(SYSTEM_WORD_LISTS.iter().map(PathBuf::from))
+            .collect();
+
+        Self::load_first(paths)
+    }
+
+    fn load_first(paths: Vec<PathBuf>) -> Self {
+        let background = Self::default();
+        let checker = Arc::clone(&background.checker);
+
+        let spawned = thread::Builder::new()
+            .name("spellcheck-dictionary".to_string())
+            .spawn(move || {
+                let Some((path, loaded)) = paths
+                    .iter()
+                    .find_map(|path| Some((path, SymSpellChecker::from_dictionary(path)?)))
+                else {
+                    trace!("No spelling dictionary found, spell checking is disabled");
+                    return;
+                };
+
+                trace!("Loaded spelling dictionary from {}", path.display());
+                let _ = checker.set(loaded);
+            });
+
+        if let Err(err) = spawned {
+            warn!("Failed to start loading the spelling dictionary: {}", err);
+        }
+
+        background
+    }
+
+    /// Whether the dictionary has been loaded.
+    #[must_use]
+    pub fn is_loaded(&self) -> bool {
+        self.checker.get().is_some()
+    }
+}
+
+impl SpellChecker for BackgroundSpellChecker {
+    fn check(&self, text: &str) -> Vec<SpellError> {
+        self.checker
+            .get()
+            .map_or_else(Vec::new, |checker| checker.check(text))
+    }
+}
+
+/// The byte range of the words of a text touched by an edit, found by comparing it to the text before the edit.
+fn edited_words(previous: &str, text: &str) -> Range<usize> {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '\'';
+
+    let prefix = previous
+        .char_indices()
+        .zip(text.chars())
+        .find(|((_, a), b)| a != b)
+        .map_or_else(|| previous.len().min(text.len()), |((index, _), _)| index);
+    let suffix = previous[prefix..]
+        .chars()
+        .rev()
+        .zip(text[prefix..].chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(c, _)| c.len_utf8())
+        .sum::<usize>();
+
+    let start = text[..prefix]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_word_char(*c))
+        .last()
+        .map_or(prefix, |(index, _)| index);
+    let end = text[text.len() - suffix..]
+        .char_indices()
+        .find(|(_, c)| !is_word_char(*c))
+        .map_or(text.len(), |(index, _)| text.len() - suffix + index);
+
+    start..end
+}
+
+/// Splits a text into the byte ranges of the words worth checking. A word is a run of letters, digits and inner
+/// apostrophes, and words with digits, single letters and words in all capitals, which are usually acronyms, are
+/// skipped.
+fn words(text: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut start = None;
+    let mut ranges = Vec::new();
+
+    for (index, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        let in_word = c.is_alphanumeric() || (c == '\'' && start.is_some());
+
+        match (in_word, start) {
+            (true, None) => start = Some(index),
+            (false, Some(word_start)) => {
+                ranges.push(word_start..index);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    ranges.into_iter().filter_map(move |range| {
+        let word = text[range.clone()].trim_end_matches('\'');
+        let range = range.start..range.start + word.len();
+
+        let skipped =
+            word.chars().count() < 2 || word.chars().any(|c| c.is_numeric()) || word.chars().all(|c| !c.is_lowercase());
+
+        (!skipped).then_some(range)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_words_skips_numbers_and_acronyms() {
+        let text = "Teh quick brown NASA fox's 42nd jump, a test'";
+        let words: Vec<&str> = words(text).map(|range| &text[range]).collect();
+
+        assert_eq!(words, ["Teh", "quick", "brown", "fox's", "jump", "test"]);
+    }
+
+    #[test]
+    fn test_symspell_checker_suggests_corrections() {
+        let path = std::env::temp_dir().join(format!("spellcheck-test-{}.txt", std::process::id()));
+        std::fs::write(&path, "the 1000\nquick 500\nbrown 400\nfox 300\n").unwrap();
+
+        let checker = SymSpellChecker::from_dictionary(&path).unwrap();
+        let errors = checker.check("Teh quick browm fox");
+
+        assert_eq!(
+            errors,
+            [
+                SpellError {
+                    range: 0..3,
+                    suggestions: vec!["the".to_string()],
+                },
+                SpellError {
+                    range: 10..15,
+                    suggestions: vec!["brown".to_string()],
+                },
+            ]
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    /// Flags the words `teh` and `browm`, recording every text it checks.
+    #[derive(Debug, Default)]
+    struct RecordingChecker {
+        checked: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl SpellChecker for RecordingChecker {
+        fn check(&self, text: &str) -> Vec<SpellError> {
+            self.checked.lock().unwrap().push(text.to_string());
+
+            words(text)
+                .filter(|range| matches!(&text[range.clone()], "teh" | "browm"))
+                .map(|range| SpellError {
+                    range,
+                    suggestions: Vec::new(),
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_check_edit_rechecks_only_the_edited_word() {
+        let checker = RecordingChecker::default();
+        let previous = "teh quick brwn fox browm";
+        let errors = checker.check(previous);
+
+        let text = "teh quick browm fox browm";
+        let errors = checker.check_edit(&errors, previous, text);
+
+        assert_eq!(checker.checked.lock().unwrap().last().map(String::as_str), Some("browm"));
+        let misspelled: Vec<&str> = errors
+            .iter()
+            .map(|error| &text[error.range.clone()])
+            .collect();
+        assert_eq!(misspelled, ["teh", "browm", "browm"]);
+
+        let previous = text;
+        let text = "teh quick browm browm";
+        let errors = checker.check_edit(&errors, previous, text);
+
+        assert_eq!(checker.checked.lock().unwrap().last().map(String::as_str), Some("browm"));
+        let misspelled: Vec<&str> = errors
+            .iter()
+            .map(|error| &text[error.range.clone()])
+            .collect();
+        assert_eq!(misspelled, ["teh", "browm", "browm"]);
+    }
+
+    #[test]
+    fn test_edited_words() {
+        assert_eq!(edited_words("the quick", "the quiick"), 4..10);
+        assert_eq!(edited_words("the quick", "thequick"), 0..8);
+        assert_eq!(edited_words("the quick", "the quick "), 4..10);
+        assert_eq!(edited_words("", "a"), 0..1);
+    }
+
+    #[test]
+    fn test_word_list_without_counts() {
+        let path = std::env::temp_dir().join(format!("spellcheck-test-words-{}.txt", std::process::id()));
+        std::fs::write(&path, "The\nquick\nbrown\nfox\n").unwrap();
+
+        let checker = SymSpellChecker::from_dictionary(&path).unwrap();
+        let errors = checker.check("The quick browm fox");
+
+        assert_eq!(
+            errors,
+            [SpellError {
+                range: 10..15,
+                suggestions: vec!["brown".to_string()],
+            }]
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_background_checker_loads_first_readable_dictionary() {
+        let path = std::env::temp_dir().join(format!("spellcheck-test-background-{}.txt", std::process::id()));
+        std::fs::write(&path, "quick 500\nbrown 400\n").unwrap();
+
+        let missing = path.with_extension("missing");
+        let checker = BackgroundSpellChecker::load_first(vec![missing, path.clone()]);
+
+        for _ in 0..500 {
+            if checker.is_loaded() {
+                break;
+            }
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        assert!(checker.is_loaded());
+        assert_eq!(checker.check("quick browm").len(), 1);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_null_spell_checker_accepts_everything() {
+        assert!(NullSpellChecker.check("Teh quikc browm fox").is_empty());
+    }
+}
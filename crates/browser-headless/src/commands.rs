@@ -1,7 +1,10 @@
 use clap::{Parser, Subcommand};
 
+use crate::errors::HeadlessError;
+
 pub mod content;
 pub mod dom;
+pub mod dump;
 pub mod layout;
 pub mod navigation;
 pub mod node;
@@ -89,6 +92,12 @@ pub enum HeadlessCommand {
 
     /// Print information about the current page (title, URL, document size)
     Info,
+
+    /// Print an estimate of the memory used by the page and the engine
+    Memory,
+
+    /// Print the load timings of the current page
+    Timings,
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -128,6 +137,12 @@ pub enum NodeCommand {
         id: usize,
     },
 
+    /// Print a node's border box, client size and scroll size
+    Metrics {
+        /// `NodeId` from DOM/layout output
+        id: usize,
+    },
+
     /// Print child nodes (direct children by default)
     Children {
         /// `NodeId` from DOM/layout output
@@ -142,23 +157,22 @@ impl HeadlessCommand {
     /// Parse a command string into a `HeadlessCommand`
     ///
     /// # Errors
-    /// * If the input is empty or only whitespace, returns an empty error string (indicating no command)
-    /// * If the input cannot be parsed as a valid command, returns an error message describing the parse failure
-    pub fn parse(input: &str) -> Result<Self, String> {
+    /// * [`HeadlessError::Empty`] - If the input is empty or only whitespace, indicating no command.
+    /// * [`HeadlessError::Parse`] - If the input cannot be split into words, e.g. because of an unclosed quote.
+    /// * [`HeadlessError::Command`] - If the words are not a valid command.
+    pub fn parse(input: &str) -> Result<Self, HeadlessError> {
         let input = input.trim();
         if input.is_empty() {
-            return Err(String::new());
+            return Err(HeadlessError::Empty);
         }
 
-        let args = shell_words::split(input).map_err(|e| format!("Parse error: {e}"))?;
+        let args = shell_words::split(input)?;
 
         if args.is_empty() {
-            return Err(String::new());
+            return Err(HeadlessError::Empty);
         }
 
-        HeadlessArgs::try_parse_from(&args)
-            .map(|parsed| parsed.command)
-            .map_err(|e| e.to_string())
+        Ok(HeadlessArgs::try_parse_from(&args)?.command)
     }
 
     /// Generate help text for all commands
@@ -182,6 +196,8 @@ impl HeadlessCommand {
         help.push_str("  body                  Print HTML document\n");
         help.push_str("  cookies <domain>      Print cookies\n");
         help.push_str("  info                  Print page summary\n");
+        help.push_str("  memory                Print estimated memory usage\n");
+        help.push_str("  timings               Print page load timings\n");
         help.push('\n');
         help.push_str("Layout & DOM:\n");
         help.push_str("  dom <selector>        Query DOM with a CSS selector\n");
@@ -190,6 +206,7 @@ impl HeadlessCommand {
         help.push_str("  node dom <id>         Print node DOM subtree\n");
         help.push_str("  node style <id>       Print computed style for node\n");
         help.push_str("  node layout <id>      Print layout subtree for node\n");
+        help.push_str("  node metrics <id>     Print client and scroll size of node\n");
         help.push_str("  node children <id>    Print child nodes (use --recursive)\n");
         help.push_str("  layout                Print layout tree\n");
         help.push_str("  resize <w> <h>        Set viewport size\n");
@@ -223,6 +240,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_memory() {
+        let cmd = HeadlessCommand::parse("memory").unwrap();
+        assert!(matches!(cmd, HeadlessCommand::Memory));
+    }
+
+    #[test]
+    fn test_parse_timings() {
+        let cmd = HeadlessCommand::parse("timings").unwrap();
+        assert!(matches!(cmd, HeadlessCommand::Timings));
+    }
+
     #[test]
     fn test_parse_cookies() {
         let cmd = HeadlessCommand::parse("cookies example.com").unwrap();
@@ -323,6 +352,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_node_metrics() {
+        let cmd = HeadlessCommand::parse("node metrics 14").unwrap();
+        match cmd {
+            HeadlessCommand::Node {
+                command: NodeCommand::Metrics { id },
+            } => assert_eq!(id, 14),
+            _ => panic!("Expected node metrics command"),
+        }
+    }
+
     #[test]
     fn test_parse_node_children_recursive() {
         let cmd = HeadlessCommand::parse("node children 14 --recursive").unwrap();
@@ -346,19 +386,19 @@ mod tests {
     #[test]
     fn test_parse_empty() {
         let result = HeadlessCommand::parse("");
-        assert!(result.is_err());
+        assert!(matches!(result, Err(HeadlessError::Empty)));
     }
 
     #[test]
     fn test_parse_whitespace() {
         let result = HeadlessCommand::parse("   ");
-        assert!(result.is_err());
+        assert!(matches!(result, Err(HeadlessError::Empty)));
     }
 
     #[test]
     fn test_parse_unknown() {
         let result = HeadlessCommand::parse("unknown_command");
-        assert!(result.is_err());
+        assert!(matches!(result, Err(HeadlessError::Command(_))));
     }
 
     #[test]
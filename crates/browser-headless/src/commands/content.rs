@@ -1,4 +1,25 @@
-use crate::HeadlessEngine;
+use browser_core::{Commandable, EngineCommand, EngineResponse, MemoryUsage};
+use html_dom::DocumentMetadata;
+use layout::LayoutTree;
+use serde_json::json;
+
+use crate::{HeadlessEngine, errors::HeadlessError};
+
+/// Formats the metadata of a document as a JSON object, with missing values as `null`.
+pub fn metadata_json(metadata: &DocumentMetadata) -> String {
+    json!({
+        "title": metadata.title,
+        "description": metadata.description,
+        "og_title": metadata.og_title,
+        "og_image": metadata.og_image,
+        "og_description": metadata.og_description,
+        "canonical_url": metadata.canonical_url.as_ref().map(ToString::to_string),
+        "author": metadata.author,
+        "published_date": metadata.published_date,
+        "language": metadata.language,
+    })
+    .to_string()
+}
 
 pub fn cmd_title(engine: &HeadlessEngine) {
     println!(
@@ -59,3 +80,47 @@ pub fn cmd_info(engine: &HeadlessEngine) {
         println!("Layout: not computed");
     }
 }
+
+pub fn cmd_timings(engine: &HeadlessEngine) {
+    let Some(metadata) = &engine.metadata else {
+        println!("No page loaded");
+        return;
+    };
+
+    for (milestone, elapsed) in metadata.timings.milestones() {
+        println!("{milestone:<20} {:>10.2} ms", elapsed.as_secs_f64() * 1000.0);
+    }
+}
+
+pub async fn cmd_memory(engine: &HeadlessEngine) -> Result<(), HeadlessError> {
+    let usage = match engine
+        .browser
+        .execute(EngineCommand::GetMemoryReport)
+        .await?
+    {
+        EngineResponse::MemoryReport(usage) => usage,
+        _ => return Err(HeadlessError::UnexpectedResponse("memory report")),
+    };
+
+    let usage = MemoryUsage {
+        dom_bytes: engine
+            .page
+            .as_ref()
+            .map_or(0, |page| page.dom().estimated_size_bytes()),
+        layout_bytes: engine
+            .layout_tree
+            .as_ref()
+            .map_or(0, LayoutTree::estimated_size_bytes),
+        ..usage
+    }
+    .with_total();
+
+    println!("DOM: {} bytes", usage.dom_bytes);
+    println!("Layout: {} bytes", usage.layout_bytes);
+    println!("Network log: {} bytes", usage.network_log_bytes);
+    println!("Cookies: {} bytes", usage.cookie_jar_bytes);
+    println!("Connections: {} active, {} idle", usage.connections_active, usage.connections_idle);
+    println!("Total: {} bytes", usage.total_bytes);
+
+    Ok(())
+}
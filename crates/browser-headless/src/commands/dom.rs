@@ -1,13 +1,13 @@
 use css_cssom::ComponentValue;
-use css_selectors::{generate_selector_list, matches_compound};
+use css_selectors::{matches_selector_list, parse_selector_list};
 use css_tokenizer::CssTokenizer;
 use html_dom::{NodeData, NodeId};
 
-use crate::HeadlessEngine;
+use crate::{HeadlessEngine, errors::HeadlessError};
 
-pub fn cmd_dom(engine: &HeadlessEngine, selector: &str) -> Result<(), String> {
+pub fn cmd_dom(engine: &HeadlessEngine, selector: &str) -> Result<(), HeadlessError> {
     let Some(page) = &engine.page else {
-        return Err("No page loaded. Please navigate to a URL first.".to_string());
+        return Err(HeadlessError::NoPage);
     };
 
     let document = page.dom();
@@ -15,23 +15,19 @@ pub fn cmd_dom(engine: &HeadlessEngine, selector: &str) -> Result<(), String> {
     let component_values: Vec<ComponentValue> = CssTokenizer::new(selector, false)
         .map(ComponentValue::Token)
         .collect();
-    let selector_lists = generate_selector_list(&component_values);
+    let selector_list = parse_selector_list(&component_values);
 
-    if selector_lists.is_empty() {
-        return Err(format!("Invalid selector: {selector}"));
+    if selector_list.is_empty() {
+        return Err(HeadlessError::InvalidSelector(selector.to_string()));
     }
 
     let mut matches_found: Vec<(NodeId, String)> = Vec::new();
 
     for node in &document.nodes {
-        if let NodeData::Element(element) = &node.data {
-            for selector_sequence in &selector_lists {
-                if matches_compound(selector_sequence, document, node, element.class_set.as_ref()) {
-                    let desc = describe_element(element, node.id);
-                    matches_found.push((node.id, desc));
-                    break;
-                }
-            }
+        if let NodeData::Element(element) = &node.data
+            && matches_selector_list(&selector_list, document, node, element.class_set.as_ref())
+        {
+            matches_found.push((node.id, describe_element(element, node.id)));
         }
     }
 
@@ -0,0 +1,181 @@
+use std::{collections::HashMap, fmt::Write as _};
+
+use html_dom::{DocumentRoot, NodeData, NodeId};
+use serde_json::{Map, Value, json};
+
+/// The number of characters of a text node shown in the text dump of a DOM, longer texts are cut off with an ellipsis.
+const TEXT_PREVIEW_LENGTH: usize = 80;
+
+/// How the DOM of every page navigated to is printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DomDump {
+    /// An indented tree, see [`serialize_dom_to_text`].
+    Text,
+
+    /// A JSON document, see [`serialize_dom_to_json`].
+    Json,
+}
+
+impl DomDump {
+    /// Serializes a DOM in this format.
+    #[must_use]
+    pub fn serialize(self, root: &DocumentRoot) -> String {
+        match self {
+            Self::Text => serialize_dom_to_text(root),
+            Self::Json => serialize_dom_to_json(root).to_string(),
+        }
+    }
+}
+
+/// Serializes a DOM to an indented tree with one node per line. Elements are shown with their tag name and attributes
+/// sorted by name, text nodes as a quoted preview of their whitespace-collapsed content, and whitespace-only text is
+/// left out.
+#[must_use]
+pub fn serialize_dom_to_text(root: &DocumentRoot) -> String {
+    let mut output = String::new();
+
+    for node_id in &root.root_nodes {
+        write_text_node(root, *node_id, 0, &mut output);
+    }
+
+    output
+}
+
+/// Serializes a DOM to JSON, as an array of its root nodes. An element is an object like
+/// `{ "tag": "div", "attrs": { "id": "main" }, "children": [...] }` and a text node is `{ "text": "..." }`, with
+/// whitespace-only text left out.
+#[must_use]
+pub fn serialize_dom_to_json(root: &DocumentRoot) -> Value {
+    Value::Array(
+        root.root_nodes
+            .iter()
+            .filter_map(|node_id| json_node(root, *node_id))
+            .collect(),
+    )
+}
+
+fn write_text_node(root: &DocumentRoot, node_id: NodeId, depth: usize, output: &mut String) {
+    let Some(node) = root.get_node(&node_id) else {
+        return;
+    };
+
+    let indent = "  ".repeat(depth);
+
+    match &node.data {
+        NodeData::Element(element) => {
+            let _ = write!(output, "{indent}{}", element.tag_name());
+            for (name, value) in sorted_attributes(element.attributes.as_ref()) {
+                let _ = write!(output, " {name}={value:?}");
+            }
+            output.push('\n');
+
+            for child_id in &node.children {
+                write_text_node(root, *child_id, depth + 1, output);
+            }
+        }
+        NodeData::Text(text) => {
+            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            if text.is_empty() {
+                return;
+            }
+
+            let preview = if text.chars().count() > TEXT_PREVIEW_LENGTH {
+                format!("{}\u{2026}", text.chars().take(TEXT_PREVIEW_LENGTH).collect::<String>())
+            } else {
+                text
+            };
+
+            let _ = writeln!(output, "{indent}{preview:?}");
+        }
+    }
+}
+
+fn json_node(root: &DocumentRoot, node_id: NodeId) -> Option<Value> {
+    let node = root.get_node(&node_id)?;
+
+    match &node.data {
+        NodeData::Element(element) => {
+            let attrs: Map<String, Value> = sorted_attributes(element.attributes.as_ref())
+                .into_iter()
+                .map(|(name, value)| (name.clone(), Value::String(value.clone())))
+                .collect();
+            let children: Vec<Value> = node
+                .children
+                .iter()
+                .filter_map(|child_id| json_node(root, *child_id))
+                .collect();
+
+            Some(json!({
+                "tag": element.tag_name(),
+                "attrs": attrs,
+                "children": children,
+            }))
+        }
+        NodeData::Text(text) => (!text.trim().is_empty()).then(|| json!({ "text": text })),
+    }
+}
+
+fn sorted_attributes(attributes: Option<&HashMap<String, String>>) -> Vec<(&String, &String)> {
+    let mut attributes: Vec<_> = attributes
+        .into_iter()
+        .flatten()
+        .filter(|(name, _)| !name.trim().is_empty())
+        .collect();
+    attributes.sort_by(|a, b| a.0.cmp(b.0));
+    attributes
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use html_dom::{Element, HtmlTag, Tag};
+
+    use super::*;
+
+    fn document() -> DocumentRoot {
+        let mut dom = DocumentRoot::new();
+        let body = dom.push_node(
+            &NodeData::Element(Element::new(Tag::Html(HtmlTag::Body), HashSet::new(), HashMap::new())),
+            None,
+        );
+        let div = dom.push_node(
+            &NodeData::Element(Element::new(
+                Tag::Html(HtmlTag::Div),
+                HashSet::new(),
+                HashMap::from([
+                    ("id".to_string(), "main".to_string()),
+                    ("class".to_string(), "content".to_string()),
+                ]),
+            )),
+            Some(body),
+        );
+        dom.push_node(&NodeData::Text("\n  ".to_string()), Some(div));
+        dom.push_node(&NodeData::Text("Hello,\n   world".to_string()), Some(div));
+        dom
+    }
+
+    #[test]
+    fn test_serialize_dom_to_text() {
+        assert_eq!(
+            serialize_dom_to_text(&document()),
+            "body\n  div class=\"content\" id=\"main\"\n    \"Hello, world\"\n"
+        );
+    }
+
+    #[test]
+    fn test_serialize_dom_to_json() {
+        assert_eq!(
+            serialize_dom_to_json(&document()),
+            json!([{
+                "tag": "body",
+                "attrs": {},
+                "children": [{
+                    "tag": "div",
+                    "attrs": { "class": "content", "id": "main" },
+                    "children": [{ "text": "Hello,\n   world" }],
+                }],
+            }])
+        );
+    }
+}
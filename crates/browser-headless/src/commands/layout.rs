@@ -1,14 +1,14 @@
 use css_display::LayoutNodeId;
-use layout::LayoutTree;
+use layout::{LayoutTree, errors::LayoutError};
 use tracing::info;
 
-use crate::HeadlessEngine;
+use crate::{HeadlessEngine, errors::HeadlessError};
 
-pub fn cmd_node(engine: &mut HeadlessEngine, x: f64, y: f64) -> Result<(), String> {
+pub fn cmd_node(engine: &mut HeadlessEngine, x: f64, y: f64) -> Result<(), HeadlessError> {
     engine.ensure_layout()?;
 
     let Some(ref layout) = engine.layout_tree else {
-        return Err("Layout not available".to_string());
+        return Err(LayoutError::NoDocument.into());
     };
 
     let nodes = layout.resolve(x, y);
@@ -25,11 +25,11 @@ pub fn cmd_node(engine: &mut HeadlessEngine, x: f64, y: f64) -> Result<(), Strin
     Ok(())
 }
 
-pub fn cmd_layout(engine: &mut HeadlessEngine) -> Result<(), String> {
+pub fn cmd_layout(engine: &mut HeadlessEngine) -> Result<(), HeadlessError> {
     engine.ensure_layout()?;
 
     let Some(ref layout) = engine.layout_tree else {
-        return Err("Layout not available".to_string());
+        return Err(LayoutError::NoDocument.into());
     };
 
     println!("Layout Tree ({}x{}):", layout.content_width, layout.content_height);
@@ -41,9 +41,9 @@ pub fn cmd_layout(engine: &mut HeadlessEngine) -> Result<(), String> {
     Ok(())
 }
 
-pub fn cmd_resize(engine: &mut HeadlessEngine, width: f64, height: f64) -> Result<(), String> {
+pub fn cmd_resize(engine: &mut HeadlessEngine, width: f64, height: f64) -> Result<(), HeadlessError> {
     if width <= 0.0 || height <= 0.0 {
-        return Err("Viewport dimensions must be positive".to_string());
+        return Err(HeadlessError::InvalidViewport { width, height });
     }
 
     engine.viewport_width = width;
@@ -3,13 +3,13 @@ use std::mem::take;
 use browser_core::{Commandable, EngineCommand, EngineResponse, NavigationType};
 use tracing::info;
 
-use crate::HeadlessEngine;
+use crate::{HeadlessEngine, commands::content::metadata_json, errors::HeadlessError};
 
 pub async fn cmd_navigate(
     engine: &mut HeadlessEngine,
     url: &str,
     navigation_type: NavigationType,
-) -> Result<(), String> {
+) -> Result<(), HeadlessError> {
     if let Some(page) = std::mem::take(&mut engine.page)
         && let Some(metadata) = std::mem::take(&mut engine.metadata)
     {
@@ -24,34 +24,48 @@ pub async fn cmd_navigate(
         })
         .await;
 
-    match result {
-        Ok(res) => match res {
-            EngineResponse::NavigateSuccess(page, metadata, _navigation_type) => {
-                // TODO: Store in permanent history.
-                //if matches!(navigation_type, NavigationType::Normal) {
-                //
-                //}
+    match result? {
+        EngineResponse::NavigateSuccess(page, metadata, _navigation_type) => {
+            // TODO: Store in permanent history.
+            //if matches!(navigation_type, NavigationType::Normal) {
+            //
+            //}
 
+            if engine.extract_metadata {
+                println!("{}", metadata_json(&metadata.document));
+            }
+
+            if let Some(dump) = engine.dump_dom {
+                print!("{}", dump.serialize(page.dom()));
+            }
+
+            engine.page = Some(page);
+            engine.metadata = Some(metadata);
+            engine.recompute_layout();
+            info!("Navigated to: {}", url);
+            Ok(())
+        }
+        EngineResponse::DownloadStarted(download) => {
+            if let Some((page, metadata)) = engine.history.take_back() {
                 engine.page = Some(page);
                 engine.metadata = Some(metadata);
-                engine.recompute_layout();
-                info!("Navigated to: {}", url);
-                Ok(())
             }
-            EngineResponse::NavigateError(err) => Err(format!("Navigation error: {err}")),
-            _ => Err("Unexpected response from navigation command".to_string()),
-        },
-        Err(e) => Err(format!("Navigation error: {e}")),
+
+            println!("Downloading {} to {}", download.url, download.path.display());
+            Ok(())
+        }
+        EngineResponse::NavigateError(err) => Err(err.into()),
+        _ => Err(HeadlessError::UnexpectedResponse("navigation")),
     }
 }
 
-pub async fn cmd_back(engine: &mut HeadlessEngine) -> Result<(), String> {
+pub async fn cmd_back(engine: &mut HeadlessEngine) -> Result<(), HeadlessError> {
     let Some(page) = take(&mut engine.page) else {
-        return Err("No current page to navigate back from".to_string());
+        return Err(HeadlessError::NoPage);
     };
 
     let Some(metadata) = take(&mut engine.metadata) else {
-        return Err("No current page metadata to navigate back from".to_string());
+        return Err(HeadlessError::NoPage);
     };
 
     match engine.history.go_back(page, metadata) {
@@ -66,13 +80,13 @@ pub async fn cmd_back(engine: &mut HeadlessEngine) -> Result<(), String> {
     }
 }
 
-pub async fn cmd_forward(engine: &mut HeadlessEngine) -> Result<(), String> {
+pub async fn cmd_forward(engine: &mut HeadlessEngine) -> Result<(), HeadlessError> {
     let Some(page) = std::mem::take(&mut engine.page) else {
-        return Err("No current page to navigate forward from".to_string());
+        return Err(HeadlessError::NoPage);
     };
 
     let Some(metadata) = std::mem::take(&mut engine.metadata) else {
-        return Err("No current page metadata to navigate forward from".to_string());
+        return Err(HeadlessError::NoPage);
     };
 
     match engine.history.go_forward(page, metadata) {
@@ -87,13 +101,12 @@ pub async fn cmd_forward(engine: &mut HeadlessEngine) -> Result<(), String> {
     }
 }
 
-pub async fn cmd_reload(engine: &mut HeadlessEngine) -> Result<(), String> {
+pub async fn cmd_reload(engine: &mut HeadlessEngine) -> Result<(), HeadlessError> {
     let url = engine
         .metadata
         .as_ref()
         .map(|m| m.url.clone())
-        .ok_or_else(|| "No page to reload".to_string())?
-        .clone();
+        .ok_or(HeadlessError::NoPage)?;
 
     cmd_navigate(engine, url.as_str(), NavigationType::Reload).await
 }
@@ -2,18 +2,19 @@ use std::fmt::Write as _;
 
 use css_display::LayoutNodeId;
 use html_dom::{DocumentRoot, DomNode, NodeData, NodeId};
+use layout::errors::LayoutError;
 
-use crate::{HeadlessEngine, commands::layout::print_layout_node};
+use crate::{HeadlessEngine, commands::layout::print_layout_node, errors::HeadlessError};
 
-pub fn cmd_node_id(engine: &HeadlessEngine, id: usize) -> Result<(), String> {
+pub fn cmd_node_id(engine: &HeadlessEngine, id: usize) -> Result<(), HeadlessError> {
     let Some(page) = &engine.page else {
-        return Err("No page loaded. Please navigate to a URL first.".to_string());
+        return Err(HeadlessError::NoPage);
     };
 
     let document = page.dom();
     let node = document
         .get_node(&id.into())
-        .ok_or_else(|| format!("Node {id} not found in DOM"))?;
+        .ok_or(HeadlessError::NodeNotFound(id.into()))?;
 
     println!("Node {id}");
     println!("Type: {}", describe_node_type(node));
@@ -38,18 +39,18 @@ pub fn cmd_node_id(engine: &HeadlessEngine, id: usize) -> Result<(), String> {
     Ok(())
 }
 
-pub fn cmd_node_dom(engine: &HeadlessEngine, id: usize, max_depth: Option<usize>) -> Result<(), String> {
+pub fn cmd_node_dom(engine: &HeadlessEngine, id: usize, max_depth: Option<usize>) -> Result<(), HeadlessError> {
     let Some(page) = &engine.page else {
-        return Err("No page loaded. Please navigate to a URL first.".to_string());
+        return Err(HeadlessError::NoPage);
     };
 
     let document = page.dom();
     document
         .get_node(&id.into())
-        .ok_or_else(|| format!("Node {id} not found in DOM"))?;
+        .ok_or(HeadlessError::NodeNotFound(id.into()))?;
 
     let mut output = String::new();
-    write_dom_subtree(document, id.into(), 0, max_depth, &mut output).map_err(|e| e.to_string())?;
+    write_dom_subtree(document, id.into(), 0, max_depth, &mut output)?;
 
     if output.trim().is_empty() {
         println!("Node {id} has no printable DOM output");
@@ -60,16 +61,16 @@ pub fn cmd_node_dom(engine: &HeadlessEngine, id: usize, max_depth: Option<usize>
     Ok(())
 }
 
-pub fn cmd_node_style(engine: &mut HeadlessEngine, id: usize) -> Result<(), String> {
+pub fn cmd_node_style(engine: &mut HeadlessEngine, id: usize) -> Result<(), HeadlessError> {
     engine.ensure_layout()?;
 
     let Some(style_tree) = engine.style_tree.as_ref() else {
-        return Err("Style tree not available".to_string());
+        return Err(LayoutError::NoDocument.into());
     };
 
     let styled_node = style_tree
         .get(id.into())
-        .ok_or_else(|| format!("Node {id} not found in style tree"))?;
+        .ok_or(HeadlessError::NotStyled(id.into()))?;
 
     println!("Computed style for node {id}:");
     println!("{:#?}", styled_node);
@@ -77,19 +78,17 @@ pub fn cmd_node_style(engine: &mut HeadlessEngine, id: usize) -> Result<(), Stri
     Ok(())
 }
 
-pub fn cmd_node_layout(engine: &mut HeadlessEngine, id: usize) -> Result<(), String> {
+pub fn cmd_node_layout(engine: &mut HeadlessEngine, id: usize) -> Result<(), HeadlessError> {
     engine.ensure_layout()?;
 
     let Some(layout) = engine.layout_tree.as_ref() else {
-        return Err("Layout not available".to_string());
+        return Err(LayoutError::NoDocument.into());
     };
 
-    let path = layout
-        .find_path(LayoutNodeId::new(id))
-        .ok_or_else(|| format!("Node {id} is not present in the layout tree (it may not render, e.g. display:none)"))?;
     let node = layout
-        .node_at(&path)
-        .ok_or_else(|| format!("Layout node {id} could not be resolved by path"))?;
+        .find_path(LayoutNodeId::new(id))
+        .and_then(|path| layout.node_at(&path))
+        .ok_or(LayoutError::NotRendered(id.into()))?;
 
     println!("Layout subtree for node {id}:");
     print_layout_node(layout, node, 0);
@@ -97,15 +96,36 @@ pub fn cmd_node_layout(engine: &mut HeadlessEngine, id: usize) -> Result<(), Str
     Ok(())
 }
 
-pub fn cmd_node_children(engine: &HeadlessEngine, id: usize, recursive: bool) -> Result<(), String> {
+pub fn cmd_node_metrics(engine: &mut HeadlessEngine, id: usize) -> Result<(), HeadlessError> {
+    engine.ensure_layout()?;
+
+    let Some(layout) = engine.layout_tree.as_ref() else {
+        return Err(LayoutError::NoDocument.into());
+    };
+
+    let node = layout
+        .find_path(LayoutNodeId::new(id))
+        .and_then(|path| layout.node_at(&path))
+        .ok_or(LayoutError::NotRendered(id.into()))?;
+
+    let rect = node.border_box();
+    println!("Metrics for node {id}:");
+    println!("  rect:   x={:.2} y={:.2} w={:.2} h={:.2}", rect.x, rect.y, rect.width, rect.height);
+    println!("  client: w={:.2} h={:.2}", node.client_width(), node.client_height());
+    println!("  scroll: w={:.2} h={:.2}", node.scroll_width(), node.scroll_height());
+
+    Ok(())
+}
+
+pub fn cmd_node_children(engine: &HeadlessEngine, id: usize, recursive: bool) -> Result<(), HeadlessError> {
     let Some(page) = &engine.page else {
-        return Err("No page loaded. Please navigate to a URL first.".to_string());
+        return Err(HeadlessError::NoPage);
     };
 
     let document = page.dom();
     let node = document
         .get_node(&id.into())
-        .ok_or_else(|| format!("Node {id} not found in DOM"))?;
+        .ok_or(HeadlessError::NodeNotFound(id.into()))?;
 
     if node.children.is_empty() {
         println!("Node {id} has no children");
@@ -122,7 +142,10 @@ pub fn cmd_node_children(engine: &HeadlessEngine, id: usize, recursive: bool) ->
         for child_id in &node.children {
             let child = document
                 .get_node(child_id)
-                .ok_or_else(|| format!("Node {id} references missing child {}", child_id.0))?;
+                .ok_or(HeadlessError::MissingChild {
+                    parent: id.into(),
+                    child: *child_id,
+                })?;
             println!("  [{}] {}", child_id.0, describe_node_type(child));
         }
     }
@@ -130,7 +153,7 @@ pub fn cmd_node_children(engine: &HeadlessEngine, id: usize, recursive: bool) ->
     Ok(())
 }
 
-fn print_descendants(document: &DocumentRoot, node_id: NodeId, depth: usize) -> Result<(), String> {
+fn print_descendants(document: &DocumentRoot, node_id: NodeId, depth: usize) -> Result<(), HeadlessError> {
     let node = &document[node_id];
 
     let indent = "  ".repeat(depth);
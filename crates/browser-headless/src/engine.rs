@@ -1,26 +1,28 @@
 use std::{
     io::{self, Write},
     net::Ipv4Addr,
+    time::Instant,
 };
 
 use css_display::BoxTree;
-use tracing::{error, info};
+use tracing::{debug, error, info};
 use url::Url;
 
 use browser_args::BrowserArgs;
 use browser_core::{Browser, Document, History, NavigationType, PageMetadata};
-use browser_preferences::theme::ThemeCategory;
-use css_style::{AbsoluteContext, StyleTree};
-use layout::{ImageContext, LayoutInput, LayoutTree, Rect, TextContext};
+use css_style::{AbsoluteContext, FontFace, StyleTree, SystemPreferences};
+use layout::{ImageContext, LayoutInput, LayoutTree, Rect, TextContext, errors::LayoutError};
 
 use crate::commands::{
     HeadlessCommand, NodeCommand,
-    content::{cmd_body, cmd_cookies, cmd_headers, cmd_info, cmd_title, cmd_url},
+    content::{cmd_body, cmd_cookies, cmd_headers, cmd_info, cmd_memory, cmd_timings, cmd_title, cmd_url},
     dom::cmd_dom,
+    dump::DomDump,
     layout::{cmd_layout, cmd_node, cmd_resize},
     navigation::{cmd_back, cmd_forward, cmd_navigate, cmd_reload},
-    node::{cmd_node_children, cmd_node_dom, cmd_node_id, cmd_node_layout, cmd_node_style},
+    node::{cmd_node_children, cmd_node_dom, cmd_node_id, cmd_node_layout, cmd_node_metrics, cmd_node_style},
 };
+use crate::errors::HeadlessError;
 
 const DEFAULT_VIEWPORT_WIDTH: f64 = 1280.0;
 const DEFAULT_VIEWPORT_HEIGHT: f64 = 800.0;
@@ -35,6 +37,12 @@ pub struct HeadlessEngine {
     pub layout_tree: Option<LayoutTree>,
     pub style_tree: Option<StyleTree>,
     pub text_ctx: TextContext,
+
+    /// Whether the metadata of every page navigated to is printed as JSON.
+    pub extract_metadata: bool,
+
+    /// The format the DOM of every page navigated to is printed in, `None` to not print it.
+    pub dump_dom: Option<DomDump>,
 }
 
 impl HeadlessEngine {
@@ -50,14 +58,16 @@ impl HeadlessEngine {
             layout_tree: None,
             style_tree: None,
             text_ctx: TextContext::default(),
+            extract_metadata: false,
+            dump_dom: None,
         }
     }
 
     /// Handle a single command input
-    async fn handle_command(&mut self, input: &str) -> Result<(), String> {
+    async fn handle_command(&mut self, input: &str) -> Result<(), HeadlessError> {
         let command = match HeadlessCommand::parse(input) {
             Ok(cmd) => cmd,
-            Err(e) if e.is_empty() => return Ok(()),
+            Err(HeadlessError::Empty) => return Ok(()),
             Err(e) => return Err(e),
         };
 
@@ -101,6 +111,7 @@ impl HeadlessEngine {
                 NodeCommand::Dom { id, max_depth } => cmd_node_dom(self, id, max_depth),
                 NodeCommand::Style { id } => cmd_node_style(self, id),
                 NodeCommand::Layout { id } => cmd_node_layout(self, id),
+                NodeCommand::Metrics { id } => cmd_node_metrics(self, id),
                 NodeCommand::Children { id, recursive } => cmd_node_children(self, id, recursive),
             },
             HeadlessCommand::Resize { width, height } => cmd_resize(self, width, height),
@@ -109,16 +120,21 @@ impl HeadlessEngine {
                 cmd_info(self);
                 Ok(())
             }
+            HeadlessCommand::Memory => cmd_memory(self).await,
+            HeadlessCommand::Timings => {
+                cmd_timings(self);
+                Ok(())
+            }
         }
     }
 
-    pub(crate) fn ensure_layout(&mut self) -> Result<(), String> {
+    pub(crate) fn ensure_layout(&mut self) -> Result<(), LayoutError> {
         if self.layout_tree.is_some() {
             return Ok(());
         }
         self.recompute_layout();
         if self.layout_tree.is_none() {
-            return Err("Could not compute layout - no active page".to_string());
+            return Err(LayoutError::NoDocument);
         }
         Ok(())
     }
@@ -139,15 +155,20 @@ impl HeadlessEngine {
             viewport_width: self.viewport_width,
             viewport_height: self.viewport_height,
             root_color: css_values::color::Color::BLACK,
-            theme_category: ThemeCategory::Light,
+            system_preferences: SystemPreferences {
+                prefers_reduced_data: self.browser.profile().config().data_saver(),
+                ..SystemPreferences::default()
+            },
             document_url: self.metadata.as_ref().map_or(&localhost, |m| &m.url),
         };
 
+        let layout_start = Instant::now();
         let style_tree = StyleTree::build(None, &ctx, document, stylesheets);
         let box_tree = BoxTree::new(document, &style_tree);
 
         let viewport = Rect::new(0.0, 0.0, self.viewport_width, self.viewport_height);
         let image_ctx = ImageContext::new();
+        self.text_ctx.set_font_faces(FontFace::collect(stylesheets));
         let layout_tree = LayoutTree::compute_layout(
             &mut LayoutInput {
                 dom: document,
@@ -158,8 +179,21 @@ impl HeadlessEngine {
             viewport,
         );
 
+        debug!(
+            navigation.url = %ctx.document_url,
+            layout.duration_ms = layout_start.elapsed().as_secs_f64() * 1000.0,
+            "Laid out {}", ctx.document_url
+        );
+
         self.style_tree = Some(style_tree);
         self.layout_tree = Some(layout_tree);
+
+        if let Some(metadata) = self.metadata.as_mut() {
+            metadata
+                .timings
+                .first_paint
+                .get_or_insert_with(Instant::now);
+        }
     }
 
     /// Main loop to process commands
@@ -168,6 +202,15 @@ impl HeadlessEngine {
     /// * If the input file cannot be read
     /// * If io operations fail when reading from stdin or writing to stdout
     pub async fn run(&mut self, args: BrowserArgs) {
+        self.extract_metadata = args.headless.extract_metadata;
+        self.dump_dom = if args.headless.dump_dom_json {
+            Some(DomDump::Json)
+        } else if args.headless.dump_dom {
+            Some(DomDump::Text)
+        } else {
+            None
+        };
+
         if let Some(ref url) = args.url
             && let Err(e) = cmd_navigate(self, url, NavigationType::Normal).await
         {
@@ -0,0 +1,51 @@
+use browser_core::errors::{CoreError, NavigationError};
+use html_dom::NodeId;
+use layout::errors::LayoutError;
+use thiserror::Error;
+
+/// An error running a command of the headless browser.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum HeadlessError {
+    #[error("no command given")]
+    Empty,
+
+    #[error("Parse error: {0}")]
+    Parse(#[from] shell_words::ParseError),
+
+    #[error(transparent)]
+    Command(#[from] clap::Error),
+
+    #[error("No page loaded. Please navigate to a URL first.")]
+    NoPage,
+
+    #[error("Navigation error: {0}")]
+    Navigation(#[from] NavigationError),
+
+    #[error("Engine error: {0}")]
+    Engine(#[from] CoreError),
+
+    #[error("Unexpected response from {0} command")]
+    UnexpectedResponse(&'static str),
+
+    #[error(transparent)]
+    Layout(#[from] LayoutError),
+
+    #[error("Node {0} not found in DOM")]
+    NodeNotFound(NodeId),
+
+    #[error("Node {parent} references missing child {child}")]
+    MissingChild { parent: NodeId, child: NodeId },
+
+    #[error("Node {0} not found in style tree")]
+    NotStyled(NodeId),
+
+    #[error("Invalid selector: {0}")]
+    InvalidSelector(String),
+
+    #[error("Viewport dimensions must be positive, got {width}x{height}")]
+    InvalidViewport { width: f64, height: f64 },
+
+    #[error(transparent)]
+    Format(#[from] std::fmt::Error),
+}
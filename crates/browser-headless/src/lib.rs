@@ -1,5 +1,6 @@
 mod commands;
 mod engine;
+pub mod errors;
 
 pub use commands::HeadlessCommand;
 pub use engine::HeadlessEngine;
@@ -12,6 +12,9 @@ use tracing::warn;
 use crate::theme::Theme;
 
 pub mod theme;
+mod watcher;
+
+pub use watcher::PreferencesWatcher;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct BrowserPreferences {
@@ -29,6 +32,20 @@ pub struct BrowserPreferences {
     /// This applies a heuristic to text & background colors in HTML and inverts them.
     #[serde(default)]
     force_dark: bool,
+
+    /// Whether to lay pages out at the width requested by their `<meta name="viewport">` tag, as a mobile browser
+    /// does. Pages are drawn without zoom, so desktop windows otherwise ignore the requested width.
+    #[serde(default)]
+    emulate_mobile: bool,
+
+    /// Whether pages are told that the user prefers more contrast, matched by `prefers-contrast: more`.
+    #[serde(default)]
+    high_contrast: bool,
+
+    /// Whether the colors of pages are replaced by the system colors of the active theme, matched by
+    /// `forced-colors: active`. Elements can opt out with `forced-color-adjust: none`.
+    #[serde(default)]
+    forced_colors: bool,
 }
 
 impl BrowserPreferences {
@@ -50,33 +67,53 @@ impl BrowserPreferences {
             ]),
             theme: active_theme,
             force_dark,
+            emulate_mobile: false,
+            high_contrast: false,
+            forced_colors: false,
         }
     }
 
     pub fn load(args: &BrowserArgs, paths: AppPaths) -> Self {
-        let is_incognito = args.incognito;
+        let config = Self::read_file(args, &paths).unwrap_or_else(|error| {
+            warn!(%error, "Failed to parse preferences file, using default settings.");
+            Self::new("light".to_string(), args.preferences.force_dark)
+        });
+
+        Self::resolve(config, args, &paths)
+    }
 
-        let mut config = match PROFILE_PREFERENCES.read(&paths, Self::MAX_PREFERENCES_FILE_SIZE) {
+    /// Loads the preferences again after the preferences file changed.
+    ///
+    /// # Errors
+    /// If the preferences file is not valid UTF-8 or TOML, such as a file saved halfway through an edit, in which case
+    /// the current preferences should be kept.
+    fn reload(args: &BrowserArgs, paths: &AppPaths) -> Result<Self, String> {
+        let config = Self::read_file(args, paths)?;
+        Ok(Self::resolve(config, args, paths))
+    }
+
+    /// Reads the preferences file, a missing or unreadable file giving the default preferences.
+    ///
+    /// # Errors
+    /// If the preferences file is not valid UTF-8 or TOML.
+    fn read_file(args: &BrowserArgs, paths: &AppPaths) -> Result<Self, String> {
+        match PROFILE_PREFERENCES.read(paths, Self::MAX_PREFERENCES_FILE_SIZE) {
             Ok(data) => {
-                let Ok(data) = std::str::from_utf8(&data) else {
-                    warn!("Failed to parse preferences file as UTF-8, using default settings.");
-                    return Self::new("light".to_string(), args.preferences.force_dark);
-                };
-
-                match toml::from_str::<Self>(data) {
-                    Ok(conf) => conf,
-                    Err(_) => Self::new("light".to_string(), args.preferences.force_dark),
-                }
+                let data = std::str::from_utf8(&data).map_err(|error| error.to_string())?;
+                toml::from_str::<Self>(data).map_err(|error| error.to_string())
             }
             Err(error) => {
-                if !is_incognito {
+                if !args.incognito {
                     warn!(%error, "Failed to load preferences, using default settings.");
                 }
-                Self::new("light".to_string(), args.preferences.force_dark)
+                Ok(Self::new("light".to_string(), args.preferences.force_dark))
             }
-        };
+        }
+    }
 
-        config.themes = Self::load_themes(&paths, is_incognito);
+    /// Loads the themes of the preferences and applies the overrides of the command line arguments.
+    fn resolve(mut config: Self, args: &BrowserArgs, paths: &AppPaths) -> Self {
+        config.themes = Self::load_themes(paths, args.incognito);
 
         if config.theme.is_empty() || !config.themes.contains_key(&config.theme) {
             warn!(
@@ -91,6 +128,18 @@ impl BrowserPreferences {
             config.force_dark = true;
         }
 
+        if args.preferences.emulate_mobile {
+            config.emulate_mobile = true;
+        }
+
+        if args.preferences.high_contrast {
+            config.high_contrast = true;
+        }
+
+        if args.preferences.forced_colors {
+            config.forced_colors = true;
+        }
+
         if let Some(override_theme) = &args.preferences.theme {
             if !config.themes.contains_key(override_theme) {
                 warn!(
@@ -126,6 +175,21 @@ impl BrowserPreferences {
         self.force_dark
     }
 
+    #[must_use]
+    pub const fn emulate_mobile(&self) -> bool {
+        self.emulate_mobile
+    }
+
+    #[must_use]
+    pub const fn high_contrast(&self) -> bool {
+        self.high_contrast
+    }
+
+    #[must_use]
+    pub const fn forced_colors(&self) -> bool {
+        self.forced_colors
+    }
+
     fn load_themes(paths: &AppPaths, is_incognito: bool) -> HashMap<String, Theme> {
         let mut themes = HashMap::from([
             ("light".to_string(), Theme::light()),
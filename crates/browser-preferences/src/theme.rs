@@ -12,8 +12,8 @@ mod typography;
 
 /// The theme category, affects how colors are adjusted.
 ///
-/// Represents the category of a theme, either Light or Dark, this will impact the CSS functions that need the colors to be
-/// adjusted for light or dark themes, e.g. using `light-dark()` to adjust the colors for light and dark themes.
+/// Represents the category of a theme, either Light or Dark. Pages resolve `light-dark()` and `prefers-color-scheme`
+/// against the color scheme of the system instead, so that the two always agree.
 #[derive(Debug, Clone, Default, Copy, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ThemeCategory {
@@ -0,0 +1,183 @@
+use std::{
+    ffi::OsString,
+    sync::mpsc::{self, RecvTimeoutError},
+    time::Duration,
+};
+
+use browser_args::BrowserArgs;
+use io::{entries::PROFILE_PREFERENCES, paths::AppPaths};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use tracing::warn;
+
+use crate::BrowserPreferences;
+
+/// Watches the preferences file for changes, stopping when dropped.
+pub struct PreferencesWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+impl BrowserPreferences {
+    /// How long the preferences file must be left untouched before it is read again, editors often write a file in
+    /// several steps.
+    const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
+    /// Watches the preferences file, calling `callback` with the reloaded preferences every time the file is written.
+    /// A file that can't be parsed, such as one saved halfway through an edit, is logged and skipped so that the
+    /// current preferences are kept.
+    ///
+    /// The directory of the file is watched rather than the file itself, so that editors replacing the file on save
+    /// and a file created after startup are picked up as well.
+    ///
+    /// # Arguments
+    /// * `args` - The command line arguments, whose overrides are applied to the reloaded preferences.
+    /// * `paths` - The directories of the application.
+    /// * `callback` - Called on a background thread with the reloaded preferences.
+    ///
+    /// # Errors
+    /// If the directory of the preferences file does not exist or cannot be watched.
+    pub fn watch<F>(args: BrowserArgs, paths: AppPaths, callback: F) -> notify::Result<PreferencesWatcher>
+    where
+        F: Fn(Self) + Send + 'static,
+    {
+        let path = PROFILE_PREFERENCES.path(&paths);
+        let file_name = path.file_name().map(OsString::from);
+        let Some(directory) = path.parent() else {
+            return Err(notify::Error::path_not_found().add_path(path));
+        };
+
+        let (sender, receiver) = mpsc::channel();
+
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            let Ok(event) = event else {
+                return;
+            };
+
+            let is_write = event.kind.is_create() || event.kind.is_modify();
+            let is_preferences = event
+                .paths
+                .iter()
+                .any(|path| path.file_name() == file_name.as_deref());
+
+            if is_write && is_preferences {
+                let _ = sender.send(());
+            }
+        })?;
+
+        watcher.watch(directory, RecursiveMode::NonRecursive)?;
+
+        std::thread::spawn(move || {
+            while receiver.recv().is_ok() {
+                loop {
+                    match receiver.recv_timeout(Self::WATCH_DEBOUNCE) {
+                        Ok(()) => {}
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+
+                match Self::reload(&args, &paths) {
+                    Ok(preferences) => callback(preferences),
+                    Err(error) => warn!(%error, "Failed to parse preferences file, keeping the current settings."),
+                }
+            }
+        });
+
+        Ok(PreferencesWatcher { _watcher: watcher })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, sync::Arc};
+
+    use clap::Parser;
+
+    use super::*;
+
+    /// How long a test waits for the watcher to report a change.
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn paths(name: &str) -> AppPaths {
+        let directory = Arc::new(std::env::temp_dir().join(format!("preferences-test-{name}-{}", std::process::id())));
+        let _ = std::fs::remove_dir_all(directory.as_path());
+        std::fs::create_dir_all(directory.as_path()).unwrap();
+
+        AppPaths {
+            profile_cache: Arc::clone(&directory),
+            profile_config: Arc::clone(&directory),
+            profile_data: Arc::clone(&directory),
+            global_cache: Arc::clone(&directory),
+            global_config: Arc::clone(&directory),
+            global_data: Arc::clone(&directory),
+            temp: directory,
+        }
+    }
+
+    fn write(path: &Path, contents: &str) {
+        std::fs::write(path, contents).unwrap();
+    }
+
+    fn watch(name: &str) -> (AppPaths, PreferencesWatcher, mpsc::Receiver<BrowserPreferences>) {
+        let paths = paths(name);
+        let (sender, receiver) = mpsc::channel();
+        let watcher =
+            BrowserPreferences::watch(BrowserArgs::parse_from(["browser"]), paths.clone(), move |preferences| {
+                let _ = sender.send(preferences);
+            })
+            .unwrap();
+
+        (paths, watcher, receiver)
+    }
+
+    #[test]
+    fn test_reloads_written_preferences() {
+        let (paths, watcher, receiver) = watch("reload");
+
+        write(&PROFILE_PREFERENCES.path(&paths), "theme = \"dark\"\nforce_dark = true\n");
+
+        let preferences = receiver.recv_timeout(TIMEOUT).unwrap();
+        assert_eq!(preferences.theme_name(), "dark");
+        assert!(preferences.force_dark());
+
+        drop(watcher);
+        std::fs::remove_dir_all(paths.profile_config.as_path()).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_preferences_keep_current() {
+        let (paths, watcher, receiver) = watch("invalid");
+
+        write(&PROFILE_PREFERENCES.path(&paths), "theme = \"dark");
+        assert!(
+            receiver
+                .recv_timeout(BrowserPreferences::WATCH_DEBOUNCE * 5)
+                .is_err()
+        );
+
+        write(&PROFILE_PREFERENCES.path(&paths), "theme = \"dark\"\n");
+        assert_eq!(receiver.recv_timeout(TIMEOUT).unwrap().theme_name(), "dark");
+
+        drop(watcher);
+        std::fs::remove_dir_all(paths.profile_config.as_path()).unwrap();
+    }
+
+    #[test]
+    fn test_load_falls_back_to_defaults() {
+        let paths = paths("load");
+        let args = BrowserArgs::parse_from(["browser"]);
+
+        write(&PROFILE_PREFERENCES.path(&paths), "theme = ");
+        assert!(BrowserPreferences::reload(&args, &paths).is_err());
+        assert_eq!(BrowserPreferences::load(&args, paths.clone()).theme_name(), "light");
+
+        std::fs::remove_file(PROFILE_PREFERENCES.path(&paths)).unwrap();
+        assert_eq!(
+            BrowserPreferences::reload(&args, &paths)
+                .unwrap()
+                .theme_name(),
+            "light"
+        );
+
+        std::fs::remove_dir_all(paths.profile_config.as_path()).unwrap();
+    }
+}
@@ -3,6 +3,12 @@
 /// Main application and event definitions.
 mod app;
 
+/// Editing state of the focused text control.
+mod input;
+
+/// Text selection of the page.
+mod selection;
+
 /// Tab representation and related types.
 mod tabs;
 
@@ -11,6 +17,8 @@ mod window;
 
 mod handler;
 
-pub use app::Application;
-pub use tabs::{Devtools, DevtoolsContext, DevtoolsPage, Page, Tab, TabId, manager::TabManager};
+pub use app::{Application, apply_accessibility_preferences};
+pub use input::{TextInputAction, TextInputController};
+pub use selection::SelectionState;
+pub use tabs::{Devtools, DevtoolsContext, DevtoolsPage, LoadedPage, Page, Tab, TabId, manager::TabManager};
 pub use window::{ApplicationWindow, ScrollOffset, WindowController, WindowType};
@@ -1,22 +1,29 @@
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 use std::sync::Arc;
 
 use browser_args::BrowserArgs;
-use browser_core::Browser;
+use browser_core::{Browser, Download, MemoryUsage, SessionEntry};
 use browser_preferences::BrowserPreferences;
+use css_style::{InputDevice, SystemPreferences};
+use css_values::media::{ColorScheme, ContrastPreference, ForcedColorsState};
+use iced::futures::Stream;
 use iced::keyboard::key;
-use iced::theme::{Custom, Palette};
+use iced::theme::{Custom, Mode, Palette};
 use iced::widget::text;
 use iced::window::Id;
-use iced::{Color, Subscription, event, keyboard};
+use iced::{Color, Subscription, event, keyboard, mouse, system};
 use iced::{Renderer, Task, Theme, window};
+use io::paths::AppPaths;
 use manifest::APP_NAME;
+use tracing::warn;
 
 use crate::core::WindowType;
 
 use crate::core::WindowController;
-use crate::events::{BrowserEvent, Event, EventHandler, WindowEvent};
+use crate::events::{BrowserEvent, EngineRequest, Event, EventHandler, WindowEvent};
+use crate::renderer::pipeline::GpuMemory;
 use crate::windows::browser::window::BrowserContext;
 
 /// Represents the main application state, including the current window, tabs, and client.
@@ -27,11 +34,34 @@ pub struct Application {
     /// The browser preferences, loaded from the configuration.
     pub preferences: Arc<BrowserPreferences>,
 
+    /// The preferences of the operating system exposed to pages, such as its color scheme.
+    pub system_preferences: SystemPreferences,
+
     /// The browser contexts for each open window, keyed by window ID.
     pub browser_windows: HashMap<Id, BrowserContext>,
 
     /// The window controller managing multiple windows.
     pub window_controller: WindowController,
+
+    /// The downloads of the session, as last reported by the browser, shown in the footer of the browser windows.
+    pub downloads: Vec<Download>,
+
+    /// Whether a refresh of the progress of the downloads is scheduled, a single one is scheduled at a time however
+    /// many downloads are in progress.
+    pub polling_downloads: bool,
+
+    /// The tabs of the session that was open when the browser last crashed, offered for restoring in the footer of
+    /// the browser windows until they are restored or the offer is dismissed.
+    pub crash_recovery: Option<Vec<SessionEntry>>,
+
+    /// The GPU memory used by the caches of the page renderer, for the memory report.
+    pub gpu_memory: Arc<GpuMemory>,
+
+    /// The memory used by the browser as of the last memory report, requested when the devtools window opens.
+    pub memory_usage: Option<MemoryUsage>,
+
+    /// What the preferences are reloaded from when the preferences file changes.
+    preferences_source: PreferencesSource,
 }
 
 impl Application {
@@ -44,13 +74,47 @@ impl Application {
         let mut window_controller = WindowController::new();
         let (main_window_id, browser_task) = window_controller.new_window(None, WindowType::Browser);
 
-        let tasks = vec![browser_task.discard()];
+        let mut tasks = vec![
+            // The scale factor is only reported when it changes, so that of the window is read once it is open.
+            browser_task
+                .then(window::scale_factor)
+                .map(|scale_factor| Event::Browser(BrowserEvent::ScaleFactorChanged(f64::from(scale_factor)))),
+            system::theme().map(|mode| Event::Browser(BrowserEvent::SystemThemeChanged(color_scheme(mode)))),
+        ];
+
+        if let Some(tabs) = browser.crashed_session() {
+            tasks.push(Task::done(Event::Browser(BrowserEvent::CrashRecoveryAvailable(tabs))));
+        }
+
+        tasks.extend(
+            args.load_extensions.iter().map(|path| {
+                Task::done(Event::EngineRequest(EngineRequest::LoadExtension(main_window_id, path.clone())))
+            }),
+        );
+
+        let preferences_source = PreferencesSource {
+            args: args.clone(),
+            paths: browser.profile().dirs().into(),
+        };
+
+        let mut system_preferences = SystemPreferences {
+            prefers_reduced_data: browser.profile().config().data_saver(),
+            ..SystemPreferences::default()
+        };
+        apply_accessibility_preferences(&mut system_preferences, &preferences);
 
         let app = Self {
             browser,
             preferences,
+            system_preferences,
             browser_windows: HashMap::from([(main_window_id, BrowserContext::new(Some(args)))]),
             window_controller,
+            downloads: Vec::new(),
+            polling_downloads: false,
+            crash_recovery: None,
+            gpu_memory: Arc::default(),
+            memory_usage: None,
+            preferences_source,
         };
 
         (app, Task::batch(tasks))
@@ -80,6 +144,8 @@ impl Application {
     pub fn subscriptions(&self) -> iced::Subscription<Event> {
         Subscription::batch([
             window::close_events().map(|window_id| Event::Window(WindowEvent::CloseWindow(window_id))),
+            system::theme_changes().map(|mode| Event::Browser(BrowserEvent::SystemThemeChanged(color_scheme(mode)))),
+            Subscription::run_with(self.preferences_source.clone(), watch_preferences),
             event::listen_with(|event, _status, window_id| match event {
                 iced::Event::Keyboard(keyboard::Event::KeyPressed {
                     key: keyboard::Key::Named(key::Named::F5),
@@ -90,6 +156,13 @@ impl Application {
                     modifiers,
                     ..
                 }) if modifiers.control() => Some(Event::Browser(BrowserEvent::Refresh(window_id))),
+                iced::Event::Window(window::Event::Rescaled(scale_factor)) => {
+                    Some(Event::Browser(BrowserEvent::ScaleFactorChanged(f64::from(scale_factor))))
+                }
+                iced::Event::Touch(_) => Some(Event::Browser(BrowserEvent::InputDeviceChanged(InputDevice::Touch))),
+                iced::Event::Mouse(mouse::Event::ButtonPressed(_) | mouse::Event::WheelScrolled { .. }) => {
+                    Some(Event::Browser(BrowserEvent::InputDeviceChanged(InputDevice::Mouse)))
+                }
                 _ => None,
             }),
             self.window_controller.subscriptions(),
@@ -134,3 +207,76 @@ impl Application {
         Theme::Custom(Arc::new(custom))
     }
 }
+
+/// The command line arguments and directories the preferences are loaded with, identifying the subscription watching
+/// the preferences file.
+#[derive(Debug, Clone)]
+struct PreferencesSource {
+    args: Arc<BrowserArgs>,
+    paths: AppPaths,
+}
+
+impl Hash for PreferencesSource {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.paths.profile_config.hash(state);
+    }
+}
+
+/// Watches the preferences file, producing an event with the reloaded preferences every time it is written.
+fn watch_preferences(source: &PreferencesSource) -> impl Stream<Item = Event> + use<> {
+    let source = source.clone();
+
+    iced::stream::channel(4, async move |output| {
+        let watcher = BrowserPreferences::watch((*source.args).clone(), source.paths, move |preferences| {
+            let _ = output
+                .clone()
+                .try_send(Event::Browser(BrowserEvent::PreferencesChanged(Box::new(preferences))));
+        });
+
+        let _watcher = match watcher {
+            Ok(watcher) => watcher,
+            Err(error) => {
+                warn!(%error, "Failed to watch the preferences file, changes will apply after a restart.");
+                return;
+            }
+        };
+
+        std::future::pending::<()>().await;
+    })
+}
+
+/// Maps the theme mode reported by the operating system to the color scheme exposed to pages, systems without a
+/// preference get the light scheme.
+const fn color_scheme(mode: Mode) -> ColorScheme {
+    match mode {
+        Mode::Dark => ColorScheme::Dark,
+        Mode::Light | Mode::None => ColorScheme::Light,
+    }
+}
+
+/// Sets the contrast and forced colors exposed to pages from the preferences of the browser.
+///
+/// # Returns
+/// Whether either of them changed, in which case the loaded pages need to be styled again.
+pub(crate) fn apply_accessibility_preferences(
+    system_preferences: &mut SystemPreferences,
+    preferences: &BrowserPreferences,
+) -> bool {
+    let prefers_contrast = if preferences.high_contrast() {
+        ContrastPreference::More
+    } else {
+        ContrastPreference::NoPreference
+    };
+    let forced_colors = if preferences.forced_colors() {
+        ForcedColorsState::Active
+    } else {
+        ForcedColorsState::None
+    };
+
+    let changed =
+        system_preferences.prefers_contrast != prefers_contrast || system_preferences.forced_colors != forced_colors;
+    system_preferences.prefers_contrast = prefers_contrast;
+    system_preferences.forced_colors = forced_colors;
+
+    changed
+}
@@ -1,12 +1,12 @@
 use std::sync::Arc;
 
-use browser_core::{Commandable, EngineCommand};
+use browser_core::{Commandable, EngineCommand, EngineResponse, SessionEntry};
 use iced::{Task, window, window::Id};
 use tracing::debug;
 
 use crate::{
     core::{Application, WindowType},
-    events::Event,
+    events::{BrowserEvent, Event},
     windows::browser::window::BrowserContext,
 };
 
@@ -33,27 +33,38 @@ impl Application {
 
                 let document = page.document.dom().clone();
                 let title = page.metadata.title.clone();
-
-                Task::perform(
-                    async move {
-                        browser
-                            .execute(EngineCommand::GetDevtoolsPage { title, document })
-                            .await
-                    },
-                    move |result| match result {
-                        Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
-                        Err(e) => {
-                            panic!("Failed to get devtools page: {e:?}");
-                        }
-                    },
-                )
+                let memory_browser = Arc::clone(&browser);
+
+                Task::batch([
+                    Task::perform(
+                        async move {
+                            browser
+                                .execute(EngineCommand::GetDevtoolsPage { title, document })
+                                .await
+                        },
+                        move |result| match result {
+                            Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                            Err(e) => {
+                                panic!("Failed to get devtools page: {e:?}");
+                            }
+                        },
+                    ),
+                    Task::perform(
+                        async move { memory_browser.execute(EngineCommand::GetMemoryReport).await },
+                        move |result| match result {
+                            Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                            Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+                        },
+                    ),
+                ])
             }
             WindowType::Browser => {
                 let (id, task) = self.window_controller.new_window(None, window_type);
 
                 self.browser_windows.insert(id, BrowserContext::new(None));
 
-                task.discard()
+                task.then(window::scale_factor)
+                    .map(|scale_factor| Event::Browser(BrowserEvent::ScaleFactorChanged(f64::from(scale_factor))))
             }
         }
     }
@@ -97,6 +108,43 @@ impl Application {
             close_tasks.push(self.window_controller.close_all_windows());
         }
 
-        Task::batch(close_tasks).discard()
+        let save_session = self
+            .browser_windows
+            .keys()
+            .next()
+            .map_or_else(Task::none, |window_id| self.save_session(*window_id));
+
+        Task::batch([Task::batch(close_tasks).discard(), save_session])
+    }
+
+    /// Saves the tabs open in every browser window with a `SaveSession` command, so that they can be restored if the
+    /// browser crashes. Tabs that have not loaded a page yet are left out.
+    pub fn save_session(&self, window_id: Id) -> Task<Event> {
+        let Some(tab_id) = self
+            .browser_windows
+            .get(&window_id)
+            .map(|ctx| ctx.tab_manager.active_tab_id())
+        else {
+            return Task::none();
+        };
+
+        let tabs = self
+            .browser_windows
+            .values()
+            .flat_map(|ctx| ctx.tab_manager.tabs())
+            .filter_map(|tab| tab.page.as_ref())
+            .map(|page| SessionEntry {
+                url: page.metadata.url.to_string(),
+                title: page.metadata.title.trim().to_string(),
+            })
+            .collect();
+
+        let browser = Arc::clone(&self.browser);
+        Task::perform(async move { browser.execute(EngineCommand::SaveSession { tabs }).await }, move |result| {
+            match result {
+                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+            }
+        })
     }
 }
@@ -0,0 +1,541 @@
+use browser_core::{SpellChecker, SpellError};
+use html_dom::{DocumentRoot, NodeId, TextControl, TextControlKind, control_value};
+
+/// An editing operation on the focused text control, produced from keyboard input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextInputAction {
+    /// Insert text at the cursor, replacing the selection.
+    Insert(String),
+
+    /// Delete the selection, or the character before the cursor.
+    Backspace,
+
+    /// Delete the selection, or the character after the cursor.
+    Delete,
+
+    MoveLeft {
+        select: bool,
+    },
+    MoveRight {
+        select: bool,
+    },
+
+    /// Move to the start of the current line.
+    MoveHome {
+        select: bool,
+    },
+
+    /// Move to the end of the current line.
+    MoveEnd {
+        select: bool,
+    },
+
+    SelectAll,
+
+    /// Replace the misspelled word at the cursor with one of its suggestions.
+    Correct(String),
+}
+
+/// Maintains the value, cursor and selection of the focused `<input>` or `<textarea>`.
+///
+/// Positions are character indices into the value, not byte offsets.
+#[derive(Debug, Clone)]
+pub struct TextInputController {
+    node_id: NodeId,
+    control: TextControl,
+    text: String,
+    cursor: usize,
+
+    /// The position the selection was started from, the selection spans from here to the cursor.
+    anchor: Option<usize>,
+
+    /// The misspelled words of the value, as of the last call to [`TextInputController::check_spelling`] or
+    /// [`TextInputController::recheck_spelling`].
+    misspellings: Vec<SpellError>,
+}
+
+impl TextInputController {
+    /// Creates a controller for the given node, with the cursor placed at the end of its current value.
+    ///
+    /// # Returns
+    /// The controller, or `None` if the node is not a text control.
+    pub fn focus(node_id: NodeId, dom: &DocumentRoot) -> Option<Self> {
+        let node = dom.get_node(&node_id)?;
+        let control = TextControl::from_element(node.data.as_element()?)?;
+        let text = control_value(node, dom);
+        let cursor = text.chars().count();
+
+        Some(Self {
+            node_id,
+            control,
+            text,
+            cursor,
+            anchor: None,
+            misspellings: Vec::new(),
+        })
+    }
+
+    pub const fn node_id(&self) -> NodeId {
+        self.node_id
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The value as shown in the control, with passwords masked.
+    pub fn display_text(&self) -> String {
+        self.control.display_text(&self.text)
+    }
+
+    /// The selected range as ordered character indices, or `None` if nothing is selected.
+    pub fn selection_range(&self) -> Option<(usize, usize)> {
+        let anchor = self.anchor?;
+        (anchor != self.cursor).then(|| (anchor.min(self.cursor), anchor.max(self.cursor)))
+    }
+
+    /// The selected text, or `None` if nothing is selected or the control is a password, whose value is never copied.
+    pub fn selected_text(&self) -> Option<String> {
+        if self.control.kind == TextControlKind::Password {
+            return None;
+        }
+
+        let (start, end) = self.selection_range()?;
+        Some(self.text.chars().skip(start).take(end - start).collect())
+    }
+
+    /// The line and column of the cursor.
+    pub fn cursor_line_column(&self) -> (usize, usize) {
+        self.line_column(self.cursor)
+    }
+
+    /// Checks the spelling of the value, unless the control has spell checking turned off.
+    pub fn check_spelling(&mut self, checker: &dyn SpellChecker) {
+        self.misspellings = if self.control.spellcheck {
+            checker.check(&self.text)
+        } else {
+            Vec::new()
+        };
+    }
+
+    /// Updates the misspelled words of the value after an edit, checking only the words the edit touched.
+    ///
+    /// # Arguments
+    /// * `checker` - The spell checker.
+    /// * `previous` - The value before the edit.
+    pub fn recheck_spelling(&mut self, checker: &dyn SpellChecker, previous: &str) {
+        if self.control.spellcheck {
+            self.misspellings = checker.check_edit(&self.misspellings, previous, &self.text);
+        }
+    }
+
+    /// The misspelled word the cursor is in or just after, whose suggestions are offered to replace it.
+    pub fn misspelling_at_cursor(&self) -> Option<&SpellError> {
+        let cursor = self.byte_index(self.cursor);
+
+        self.misspellings
+            .iter()
+            .find(|error| error.range.start <= cursor && cursor <= error.range.end)
+    }
+
+    /// The misspelled words of the value as `(line, start column, end column)` spans, one per line of each word.
+    pub fn misspelled_lines(&self) -> Vec<(usize, usize, usize)> {
+        self.misspellings
+            .iter()
+            .filter_map(|error| {
+                let start = self.text.get(..error.range.start)?.chars().count();
+                let end = start + self.text.get(error.range.clone())?.chars().count();
+
+                Some(self.selection_lines(start, end))
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Splits a range of characters into `(line, start column, end column)` spans, one per line.
+    pub fn selection_lines(&self, start: usize, end: usize) -> Vec<(usize, usize, usize)> {
+        let (start_line, start_column) = self.line_column(start);
+        let (end_line, end_column) = self.line_column(end);
+
+        (start_line..=end_line)
+            .map(|line| {
+                let from = if line == start_line { start_column } else { 0 };
+                let to = if line == end_line {
+                    end_column
+                } else {
+                    self.text
+                        .split('\n')
+                        .nth(line)
+                        .map_or(0, |l| l.chars().count())
+                };
+
+                (line, from, to)
+            })
+            .collect()
+    }
+
+    /// Applies an editing action.
+    ///
+    /// # Returns
+    /// `true` if the value of the control changed.
+    pub fn apply(&mut self, action: TextInputAction) -> bool {
+        let len = self.text.chars().count();
+
+        match action {
+            TextInputAction::Insert(text) => return self.insert(&text),
+            TextInputAction::Backspace => {
+                if self.delete_selection() {
+                    return true;
+                }
+
+                if !self.control.editable || self.cursor == 0 {
+                    return false;
+                }
+
+                self.remove_range(self.cursor - 1, self.cursor);
+                self.cursor -= 1;
+                return true;
+            }
+            TextInputAction::Delete => {
+                if self.delete_selection() {
+                    return true;
+                }
+
+                if !self.control.editable || self.cursor >= len {
+                    return false;
+                }
+
+                self.remove_range(self.cursor, self.cursor + 1);
+                return true;
+            }
+            TextInputAction::MoveLeft { select } => {
+                let target = match (select, self.selection_range()) {
+                    (false, Some((start, _))) => start,
+                    _ => self.cursor.saturating_sub(1),
+                };
+                self.move_to(target, select);
+            }
+            TextInputAction::MoveRight { select } => {
+                let target = match (select, self.selection_range()) {
+                    (false, Some((_, end))) => end,
+                    _ => (self.cursor + 1).min(len),
+                };
+                self.move_to(target, select);
+            }
+            TextInputAction::MoveHome { select } => {
+                let (_, column) = self.cursor_line_column();
+                self.move_to(self.cursor - column, select);
+            }
+            TextInputAction::MoveEnd { select } => {
+                let line_end = self
+                    .text
+                    .chars()
+                    .skip(self.cursor)
+                    .position(|c| c == '\n')
+                    .map_or(len, |offset| self.cursor + offset);
+                self.move_to(line_end, select);
+            }
+            TextInputAction::SelectAll => {
+                self.anchor = Some(0);
+                self.cursor = len;
+            }
+            TextInputAction::Correct(word) => return self.correct(&word),
+        }
+
+        false
+    }
+
+    /// Inserts text at the cursor, dropping characters the control does not accept and anything past `maxlength`.
+    fn insert(&mut self, text: &str) -> bool {
+        if !self.control.editable {
+            return false;
+        }
+
+        let deleted = self.delete_selection();
+
+        let current = self.text.chars().count();
+        let room = self
+            .control
+            .max_length
+            .map_or(usize::MAX, |max| max.saturating_sub(current));
+
+        let inserted: String = text
+            .chars()
+            .filter(|c| self.control.accepts_char(*c))
+            .take(room)
+            .collect();
+
+        if inserted.is_empty() {
+            return deleted;
+        }
+
+        let byte_index = self.byte_index(self.cursor);
+        self.text.insert_str(byte_index, &inserted);
+        self.cursor += inserted.chars().count();
+
+        true
+    }
+
+    /// Replaces the misspelled word at the cursor, placing the cursor after the replacement.
+    fn correct(&mut self, word: &str) -> bool {
+        if !self.control.editable {
+            return false;
+        }
+
+        let Some(range) = self
+            .misspelling_at_cursor()
+            .map(|error| error.range.clone())
+        else {
+            return false;
+        };
+
+        let len = self.text.chars().count() - self.text[range.clone()].chars().count() + word.chars().count();
+        if self.control.max_length.is_some_and(|max| len > max) {
+            return false;
+        }
+
+        self.cursor = self.text[..range.start].chars().count() + word.chars().count();
+        self.anchor = None;
+        self.text.replace_range(range, word);
+
+        true
+    }
+
+    /// Removes the selected text, returning `true` if anything was removed.
+    fn delete_selection(&mut self) -> bool {
+        let Some((start, end)) = self.selection_range() else {
+            self.anchor = None;
+            return false;
+        };
+
+        if !self.control.editable {
+            return false;
+        }
+
+        self.remove_range(start, end);
+        self.cursor = start;
+        self.anchor = None;
+
+        true
+    }
+
+    fn remove_range(&mut self, start: usize, end: usize) {
+        let range = self.byte_index(start)..self.byte_index(end);
+        self.text.replace_range(range, "");
+    }
+
+    fn move_to(&mut self, position: usize, select: bool) {
+        if select {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+
+        self.cursor = position;
+    }
+
+    fn byte_index(&self, position: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(position)
+            .map_or(self.text.len(), |(index, _)| index)
+    }
+
+    fn line_column(&self, position: usize) -> (usize, usize) {
+        let before = self.text.chars().take(position);
+        let mut line = 0;
+        let mut column = 0;
+
+        for c in before {
+            if c == '\n' {
+                line += 1;
+                column = 0;
+            } else {
+                column += 1;
+            }
+        }
+
+        (line, column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use html_dom::{Element, HtmlTag, NodeData, Tag};
+
+    use super::*;
+
+    fn controller(tag: HtmlTag, attributes: &[(&str, &str)]) -> TextInputController {
+        let mut element = Element::new(Tag::Html(tag), HashSet::new(), HashMap::new());
+        for (name, value) in attributes {
+            element.set_attribute(name, value);
+        }
+
+        let mut dom = DocumentRoot::new();
+        let node_id = dom.push_node(&NodeData::Element(element), None);
+
+        TextInputController::focus(node_id, &dom).unwrap()
+    }
+
+    fn input(value: &str) -> TextInputController {
+        controller(HtmlTag::Input, &[("value", value)])
+    }
+
+    #[test]
+    fn test_focus_places_cursor_at_end() {
+        let input = input("héllo");
+
+        assert_eq!(input.cursor_line_column(), (0, 5));
+        assert_eq!(input.selection_range(), None);
+    }
+
+    #[test]
+    fn test_not_a_text_control() {
+        let mut dom = DocumentRoot::new();
+        let element = Element::new(Tag::Html(HtmlTag::Div), HashSet::new(), HashMap::new());
+        let node_id = dom.push_node(&NodeData::Element(element), None);
+
+        assert!(TextInputController::focus(node_id, &dom).is_none());
+    }
+
+    #[test]
+    fn test_insert_and_delete_at_cursor() {
+        let mut input = input("abc");
+
+        input.apply(TextInputAction::MoveLeft { select: false });
+        input.apply(TextInputAction::MoveLeft { select: false });
+        assert!(input.apply(TextInputAction::Insert("X".to_string())));
+        assert_eq!(input.text(), "aXbc");
+        assert_eq!(input.cursor_line_column(), (0, 2));
+
+        assert!(input.apply(TextInputAction::Backspace));
+        assert_eq!(input.text(), "abc");
+        assert_eq!(input.cursor_line_column(), (0, 1));
+
+        assert!(input.apply(TextInputAction::Delete));
+        assert_eq!(input.text(), "ac");
+        assert_eq!(input.cursor_line_column(), (0, 1));
+    }
+
+    #[test]
+    fn test_edits_at_the_ends_of_the_value() {
+        let mut input = input("añ");
+
+        assert!(!input.apply(TextInputAction::Delete));
+        assert!(input.apply(TextInputAction::Backspace));
+        assert_eq!(input.text(), "a");
+
+        input.apply(TextInputAction::MoveHome { select: false });
+        assert!(!input.apply(TextInputAction::Backspace));
+        assert_eq!(input.text(), "a");
+    }
+
+    #[test]
+    fn test_selection_is_replaced_by_insert() {
+        let mut input = input("hello world");
+
+        input.apply(TextInputAction::MoveHome { select: false });
+        for _ in 0..5 {
+            input.apply(TextInputAction::MoveRight { select: true });
+        }
+        assert_eq!(input.selection_range(), Some((0, 5)));
+        assert_eq!(input.selected_text().as_deref(), Some("hello"));
+
+        assert!(input.apply(TextInputAction::Insert("bye".to_string())));
+        assert_eq!(input.text(), "bye world");
+        assert_eq!(input.selection_range(), None);
+        assert_eq!(input.cursor_line_column(), (0, 3));
+    }
+
+    #[test]
+    fn test_moving_without_select_collapses_the_selection() {
+        let mut input = input("hello");
+
+        input.apply(TextInputAction::SelectAll);
+        assert_eq!(input.selection_range(), Some((0, 5)));
+
+        input.apply(TextInputAction::MoveLeft { select: false });
+        assert_eq!(input.selection_range(), None);
+        assert_eq!(input.cursor_line_column(), (0, 0));
+
+        input.apply(TextInputAction::SelectAll);
+        input.apply(TextInputAction::MoveRight { select: false });
+        assert_eq!(input.selection_range(), None);
+        assert_eq!(input.cursor_line_column(), (0, 5));
+    }
+
+    #[test]
+    fn test_backspace_deletes_the_selection() {
+        let mut input = input("hello");
+
+        input.apply(TextInputAction::MoveLeft { select: true });
+        input.apply(TextInputAction::MoveLeft { select: true });
+        assert_eq!(input.selection_range(), Some((3, 5)));
+
+        assert!(input.apply(TextInputAction::Backspace));
+        assert_eq!(input.text(), "hel");
+        assert_eq!(input.selection_range(), None);
+    }
+
+    #[test]
+    fn test_insert_stops_at_max_length() {
+        let mut input = controller(HtmlTag::Input, &[("value", "ab"), ("maxlength", "3")]);
+
+        assert!(input.apply(TextInputAction::Insert("xyz".to_string())));
+        assert_eq!(input.text(), "abx");
+
+        assert!(!input.apply(TextInputAction::Insert("q".to_string())));
+        assert_eq!(input.text(), "abx");
+    }
+
+    #[test]
+    fn test_readonly_control_is_not_edited() {
+        let mut input = controller(HtmlTag::Input, &[("value", "abc"), ("readonly", "")]);
+
+        assert!(!input.apply(TextInputAction::Insert("x".to_string())));
+        assert!(!input.apply(TextInputAction::Backspace));
+
+        input.apply(TextInputAction::SelectAll);
+        assert!(!input.apply(TextInputAction::Delete));
+        assert_eq!(input.text(), "abc");
+        assert_eq!(input.selected_text().as_deref(), Some("abc"));
+    }
+
+    #[test]
+    fn test_password_selection_is_never_copied() {
+        let mut input = controller(HtmlTag::Input, &[("value", "secret"), ("type", "password")]);
+
+        input.apply(TextInputAction::SelectAll);
+
+        assert_eq!(input.selection_range(), Some((0, 6)));
+        assert_eq!(input.selected_text(), None);
+        assert_ne!(input.display_text(), "secret");
+    }
+
+    #[test]
+    fn test_home_and_end_move_within_the_line() {
+        let mut textarea = controller(HtmlTag::Textarea, &[("value", "one\ntwo")]);
+        assert_eq!(textarea.cursor_line_column(), (1, 3));
+
+        textarea.apply(TextInputAction::MoveHome { select: false });
+        assert_eq!(textarea.cursor_line_column(), (1, 0));
+
+        textarea.apply(TextInputAction::MoveLeft { select: false });
+        assert_eq!(textarea.cursor_line_column(), (0, 3));
+
+        textarea.apply(TextInputAction::MoveHome { select: true });
+        assert_eq!(textarea.selected_text().as_deref(), Some("one"));
+
+        textarea.apply(TextInputAction::MoveEnd { select: false });
+        assert_eq!(textarea.cursor_line_column(), (0, 3));
+    }
+
+    #[test]
+    fn test_selection_lines_span_every_line() {
+        let textarea = controller(HtmlTag::Textarea, &[("value", "ab\ncde\nf")]);
+
+        assert_eq!(textarea.selection_lines(1, 8), vec![(0, 1, 2), (1, 0, 3), (2, 0, 1)]);
+    }
+}
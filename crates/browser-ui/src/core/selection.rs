@@ -0,0 +1,39 @@
+use html_dom::NodeId;
+
+/// The text selected on the page by dragging the pointer over it.
+///
+/// Positions are a text node and a byte offset into the text laid out for it, see [`layout::LayoutTree::glyphs_in_range`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SelectionState {
+    /// The position the selection was started from.
+    pub anchor: Option<(NodeId, usize)>,
+
+    /// The position the selection extends to, which may come before the anchor.
+    pub focus: Option<(NodeId, usize)>,
+}
+
+impl SelectionState {
+    /// A selection collapsed at a position, as when the pointer is pressed over text.
+    pub const fn collapsed(position: (NodeId, usize)) -> Self {
+        Self {
+            anchor: Some(position),
+            focus: Some(position),
+        }
+    }
+
+    /// The selection from the anchor to another position.
+    #[must_use]
+    pub const fn extended(self, focus: (NodeId, usize)) -> Self {
+        Self {
+            anchor: self.anchor,
+            focus: Some(focus),
+        }
+    }
+
+    /// The anchor and focus of the selection, or `None` if nothing is selected.
+    pub fn range(&self) -> Option<((NodeId, usize), (NodeId, usize))> {
+        let anchor = self.anchor?;
+        let focus = self.focus?;
+        (anchor != focus).then_some((anchor, focus))
+    }
+}
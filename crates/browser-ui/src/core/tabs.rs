@@ -6,4 +6,4 @@ pub mod tab;
 
 pub use devtools::{Devtools, DevtoolsContext, DevtoolsPage};
 pub use page::Page;
-pub use tab::{Tab, TabId};
+pub use tab::{LoadedPage, Tab, TabId};
@@ -1,24 +1,37 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
 use browser_core::{
-    Commandable, Document, EngineCommand, EngineResponse, NavigationType, PageMetadata, errors::CoreError,
+    Browser, Commandable, Document, Download, EngineCommand, EngineResponse, Favicon, LazyLoadQueue, LinkRel,
+    LinkTarget, NavigationType, PageMetadata, SessionEntry,
+    errors::{CoreError, NavigationError},
 };
+use browser_preferences::BrowserPreferences;
 use css_display::BoxTree;
+use css_style::FontFace;
+use html_dom::{DomNode, HoverChange, SelectElement, ValidationError, inline_svgs, svg_document};
 use iced::Task;
 use image::ImageFormat;
-use layout::{LayoutImage, LayoutInput, LayoutTree, NodeId, Rect};
+use io::paths::{create_paths, get_download_path};
+use layout::{LayoutImage, LayoutInput, LayoutTree, NodeId, Rect, TextContext, errors::LayoutError};
 use regex::Regex;
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 use url::Url;
 
 use crate::{
-    core::{Application, Tab, TabId},
+    core::{Application, LoadedPage, SelectionState, Tab, TabId, TextInputAction, TextInputController},
     errors::{BrowserError, TabError},
     events::{BrowserEvent, Event},
-    util::image::decode_image_bytes,
-    windows::browser::window::BrowserContext,
+    util::image::{decode_image_bytes, decode_svg_bytes, is_svg},
+    windows::browser::window::{BrowserContext, BrowserWindow},
 };
 
+/// The interval between the frames of the loading spinner shown in the title of a loading tab.
+const LOADING_FRAME_INTERVAL: Duration = Duration::from_millis(120);
+
 impl Tab {
     /// Handles the creation of a new tab when a `NewTab` event is received from the UI.
     pub fn create_new_tab(application: &mut Application, window_id: iced::window::Id) -> Task<Event> {
@@ -63,7 +76,7 @@ impl Tab {
             }
         }
 
-        Task::none()
+        application.save_session(window_id)
     }
 
     /// Handles the switching of the active tab when a `ChangeActiveTab` event is received from the UI.
@@ -116,49 +129,1202 @@ impl Tab {
                 let local_regex =
                     Regex::new(r"^(localhost|127\.0\.0\.1|192\.168\.|10\.|172\.(1[6-9]|2[0-9]|3[0-1])\.)").unwrap();
 
-                if new_url.starts_with("http://") || new_url.starts_with("https://") {
-                    new_url
-                } else if local_regex.is_match(new_url.as_str()) {
-                    format!("http://{new_url}")
-                } else {
-                    format!("https://{new_url}")
-                }
-            },
-            |rel_url| {
-                if rel_url.contains("://") || rel_url.starts_with("about:") {
-                    rel_url
-                } else {
-                    format!("http://{rel_url}")
-                }
-            },
+                if new_url.starts_with("http://") || new_url.starts_with("https://") {
+                    new_url
+                } else if local_regex.is_match(new_url.as_str()) {
+                    format!("http://{new_url}")
+                } else {
+                    format!("https://{new_url}")
+                }
+            },
+            |rel_url| {
+                if rel_url.contains("://") || rel_url.starts_with("about:") {
+                    rel_url
+                } else {
+                    format!("http://{rel_url}")
+                }
+            },
+        );
+
+        let tab = ctx
+            .tab_manager
+            .active_tab_mut()
+            .expect("There should always be an active tab in the browser");
+        let tab_id = tab.id;
+        let loading = Self::start_loading(tab, window_id);
+        ctx.current_url.clone_from(&url);
+
+        let navigation = Task::perform(
+            async move {
+                browser
+                    .execute(EngineCommand::Navigate {
+                        url,
+                        navigation_type: NavigationType::Normal,
+                    })
+                    .await
+            },
+            move |result| match result {
+                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                Err(err) => match err {
+                    CoreError::Navigation(nav_err) => {
+                        Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::NavigateError(nav_err)))
+                    }
+                    _ => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+                },
+            },
+        );
+
+        Task::batch([loading, navigation])
+    }
+
+    /// Follows a link clicked on the page of the active tab. Links targeting `_blank`, or a name no tab has, open in a
+    /// new tab that has no reference to the tab it was opened from. Every other target navigates the active tab, as
+    /// pages are never shown in frames.
+    pub fn open_link(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        href: String,
+        target: LinkTarget,
+        rel: LinkRel,
+    ) -> Task<Event> {
+        let ctx = application
+            .browser_windows
+            .get_mut(&window_id)
+            .expect("No browser context found for window ID");
+
+        let Some(page_url) = ctx
+            .tab_manager
+            .active_tab()
+            .and_then(|tab| tab.page.as_ref())
+            .map(|page_ctx| page_ctx.metadata.url.clone())
+        else {
+            return Self::navigate_to_url(application, window_id, href);
+        };
+
+        let named_tab = match &target {
+            LinkTarget::Named(name) => ctx
+                .tab_manager
+                .tabs()
+                .iter()
+                .find(|tab| tab.name.as_deref() == Some(name.as_str()))
+                .map(|tab| tab.id),
+            _ => None,
+        };
+
+        let tab_id = match (&target, named_tab) {
+            (_, Some(tab_id)) => tab_id,
+            (LinkTarget::Blank | LinkTarget::Named(_), None) => {
+                let mut tab = Tab::new(TabId::new(ctx.tab_manager.next_tab_id()));
+                if let LinkTarget::Named(name) = &target {
+                    tab.name = Some(name.clone());
+                }
+
+                let tab_id = tab.id;
+                ctx.tab_manager.add_tab(tab);
+                tab_id
+            }
+            (LinkTarget::Current | LinkTarget::Parent | LinkTarget::Top, None) => ctx.tab_manager.active_tab_id(),
+        };
+
+        if ctx.tab_manager.change_active_tab(tab_id).is_err() {
+            return Task::none();
+        }
+
+        let Some(tab) = ctx.tab_manager.get_tab_mut(tab_id) else {
+            return Task::none();
+        };
+
+        if let Some(page_ctx) = std::mem::take(&mut tab.page) {
+            tab.history.add_back(page_ctx.document, page_ctx.metadata);
+        }
+
+        let loading = Self::start_loading(tab, window_id);
+        ctx.current_url = page_url
+            .join(href.trim())
+            .map_or_else(|_| href.clone(), |url| url.to_string());
+
+        let browser = Arc::clone(&application.browser);
+        let navigation = Task::perform(
+            async move {
+                browser
+                    .execute(EngineCommand::OpenLink {
+                        url: href,
+                        page_url,
+                        target,
+                        noopener: rel.noopener,
+                        noreferrer: rel.noreferrer,
+                    })
+                    .await
+            },
+            move |result| match result {
+                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                Err(err) => match err {
+                    CoreError::Navigation(nav_err) => {
+                        Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::NavigateError(nav_err)))
+                    }
+                    _ => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+                },
+            },
+        );
+
+        Task::batch([loading, navigation])
+    }
+
+    /// Handles a form submission request by sending a `SubmitForm` command to the browser. The browser validates
+    /// the form first and either navigates to the form's action or responds with the validation errors.
+    pub fn submit_form(application: &Application, window_id: iced::window::Id, form_id: NodeId) -> Task<Event> {
+        let tab = application
+            .browser_windows
+            .get(&window_id)
+            .expect("No browser context found for window ID")
+            .tab_manager
+            .active_tab()
+            .expect("There should always be an active tab in the browser");
+
+        let Some(page_ctx) = &tab.page else {
+            return Task::none();
+        };
+
+        let tab_id = tab.id;
+        let document = page_ctx.document.dom().clone();
+        let page_url = page_ctx.metadata.url.clone();
+        let browser = Arc::clone(&application.browser);
+
+        Task::perform(
+            async move {
+                browser
+                    .execute(EngineCommand::SubmitForm {
+                        form_id,
+                        document,
+                        page_url,
+                    })
+                    .await
+            },
+            move |result| match result {
+                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                Err(err) => match err {
+                    CoreError::Navigation(nav_err) => {
+                        Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::NavigateError(nav_err)))
+                    }
+                    _ => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+                },
+            },
+        )
+    }
+
+    /// Handles a blocked form submission by storing the validation errors on the tab, so that the
+    /// browser can render validation tooltips next to the invalid fields.
+    pub fn on_form_validation_failed(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+        errors: Vec<ValidationError>,
+    ) -> Task<Event> {
+        let Some(ctx) = application.browser_windows.get_mut(&window_id) else {
+            error!("Browser context not found for window ID: {}", window_id);
+            return Task::none();
+        };
+
+        if let Some(tab) = ctx.tab_manager.get_tab_mut(tab_id) {
+            tab.validation_errors = errors;
+        }
+
+        Task::none()
+    }
+
+    /// Moves keyboard focus to the given text control of the active tab, or removes focus when `node_id` is `None`
+    /// or the node is not a text control. The styles of the controls losing and gaining focus are recomputed, since
+    /// they may match `:focus` rules.
+    pub fn focus_input(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        node_id: Option<NodeId>,
+    ) -> Task<Event> {
+        let Some(ctx) = application.browser_windows.get_mut(&window_id) else {
+            error!("Browser context not found for window ID: {}", window_id);
+            return Task::none();
+        };
+
+        let Some(tab) = ctx.tab_manager.active_tab_mut() else {
+            return Task::none();
+        };
+
+        tab.focused_input = node_id.and_then(|node_id| {
+            let page_ctx = tab.page.as_ref()?;
+            let mut input = TextInputController::focus(node_id, page_ctx.document.dom())?;
+            input.check_spelling(application.browser.spell_checker());
+            Some(input)
+        });
+        if tab.focused_input.is_some() {
+            tab.selection = SelectionState::default();
+        }
+
+        let focused = tab.focused_input;
+        let Some(page_ctx) = tab.page.as_mut() else {
+            return Task::none();
+        };
+
+        page_ctx.set_focused(focused);
+        let style_invalidations = page_ctx.take_style_invalidations();
+        if style_invalidations.is_empty() {
+            return Task::none();
+        }
+
+        let layout_invalidations = tab.recompute_styles(
+            ctx.viewport,
+            &application.preferences,
+            application.system_preferences,
+            &style_invalidations,
+        );
+        tab.repaint(&layout_invalidations);
+
+        let node_ids: Vec<NodeId> = layout_invalidations.layout_nodes().collect();
+        if node_ids.is_empty() {
+            return Task::none();
+        }
+
+        Self::relayout_nodes(ctx.viewport, &ctx.text_context, &application.preferences, tab, window_id, node_ids)
+    }
+
+    /// Replaces the text selection of the active tab, which is repainted with the next frame. Selecting text on the page
+    /// moves focus away from the focused text control.
+    pub fn select_text(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        selection: SelectionState,
+    ) -> Task<Event> {
+        let Some(tab) = application
+            .browser_windows
+            .get_mut(&window_id)
+            .and_then(|ctx| ctx.tab_manager.active_tab_mut())
+        else {
+            return Task::none();
+        };
+
+        tab.selection = selection;
+
+        if tab.focused_input.is_some() {
+            return Self::focus_input(application, window_id, None);
+        }
+
+        Task::none()
+    }
+
+    /// Applies a change of the hover state of the active tab, recomputing the styles of the nodes entering and leaving
+    /// it so that `:hover` rules are applied and removed. Paint-only changes are repainted in place, and only the nodes
+    /// whose geometry changed are laid out again.
+    pub fn on_hover_state_changed(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        added: Vec<NodeId>,
+        removed: Vec<NodeId>,
+    ) -> Task<Event> {
+        let Some(ctx) = application.browser_windows.get_mut(&window_id) else {
+            error!("Browser context not found for window ID: {}", window_id);
+            return Task::none();
+        };
+
+        let Some(tab) = ctx.tab_manager.active_tab_mut() else {
+            return Task::none();
+        };
+
+        let Some(page_ctx) = tab.page.as_mut() else {
+            return Task::none();
+        };
+
+        let change = HoverChange { added, removed };
+        page_ctx.apply_hover_change(&change);
+
+        let style_invalidations = page_ctx.take_style_invalidations();
+        if style_invalidations.is_empty() {
+            return Task::none();
+        }
+
+        let layout_invalidations = tab.recompute_styles(
+            ctx.viewport,
+            &application.preferences,
+            application.system_preferences,
+            &style_invalidations,
+        );
+        tab.repaint(&layout_invalidations);
+
+        let node_ids: Vec<NodeId> = layout_invalidations.layout_nodes().collect();
+        if node_ids.is_empty() {
+            return Task::none();
+        }
+
+        Self::relayout_nodes(ctx.viewport, &ctx.text_context, &application.preferences, tab, window_id, node_ids)
+    }
+
+    /// Sends a click on a checkbox of the active tab to the browser with a `ToggleCheckbox` command.
+    pub fn toggle_checkbox(application: &Application, window_id: iced::window::Id, node_id: NodeId) -> Task<Event> {
+        let Some(tab) = application
+            .browser_windows
+            .get(&window_id)
+            .and_then(|ctx| ctx.tab_manager.active_tab())
+        else {
+            return Task::none();
+        };
+
+        let Some(page_ctx) = &tab.page else {
+            return Task::none();
+        };
+
+        let tab_id = tab.id;
+        let document_id = page_ctx.document.id();
+        let browser = Arc::clone(&application.browser);
+
+        Task::perform(
+            async move {
+                browser
+                    .execute(EngineCommand::ToggleCheckbox {
+                        document_id,
+                        node_id,
+                    })
+                    .await
+            },
+            move |result| match result {
+                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+            },
+        )
+    }
+
+    /// Sends a click on a radio button of the active tab to the browser with a `SelectRadio` command.
+    pub fn select_radio(
+        application: &Application,
+        window_id: iced::window::Id,
+        group_name: String,
+        node_id: NodeId,
+    ) -> Task<Event> {
+        let Some(tab) = application
+            .browser_windows
+            .get(&window_id)
+            .and_then(|ctx| ctx.tab_manager.active_tab())
+        else {
+            return Task::none();
+        };
+
+        let Some(page_ctx) = &tab.page else {
+            return Task::none();
+        };
+
+        let tab_id = tab.id;
+        let document_id = page_ctx.document.id();
+        let browser = Arc::clone(&application.browser);
+
+        Task::perform(
+            async move {
+                browser
+                    .execute(EngineCommand::SelectRadio {
+                        document_id,
+                        group_name,
+                        node_id,
+                    })
+                    .await
+            },
+            move |result| match result {
+                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+            },
+        )
+    }
+
+    /// Handles a clicked checkbox or radio button by updating the `checked` attributes of the affected controls,
+    /// recomputing the styles matching `:checked`, and relaying out the elements whose style changed.
+    pub fn on_checked_changed(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+        checked: &[NodeId],
+        unchecked: &[NodeId],
+    ) -> Task<Event> {
+        Self::toggle_boolean_attribute(application, window_id, tab_id, "checked", checked, unchecked)
+    }
+
+    /// Sends a click on the summary of a `<details>` of the active tab to the browser with a `ToggleDetails` command.
+    pub fn toggle_details(application: &Application, window_id: iced::window::Id, node_id: NodeId) -> Task<Event> {
+        let Some(tab) = application
+            .browser_windows
+            .get(&window_id)
+            .and_then(|ctx| ctx.tab_manager.active_tab())
+        else {
+            return Task::none();
+        };
+
+        let Some(page_ctx) = &tab.page else {
+            return Task::none();
+        };
+
+        let tab_id = tab.id;
+        let document_id = page_ctx.document.id();
+        let browser = Arc::clone(&application.browser);
+
+        Task::perform(
+            async move {
+                browser
+                    .execute(EngineCommand::ToggleDetails {
+                        document_id,
+                        node_id,
+                    })
+                    .await
+            },
+            move |result| match result {
+                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+            },
+        )
+    }
+
+    /// Handles a toggled `<details>` by updating the `open` attributes of it and of the `<details>` it closed, and
+    /// relaying out all of them in a single pass.
+    pub fn on_details_toggled(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+        opened: &[NodeId],
+        closed: &[NodeId],
+    ) -> Task<Event> {
+        Self::toggle_boolean_attribute(application, window_id, tab_id, "open", opened, closed)
+    }
+
+    /// Adds a boolean attribute to some elements of a tab and removes it from others, then recomputes the styles
+    /// depending on it and relays out the elements whose style changed.
+    fn toggle_boolean_attribute(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+        attribute: &str,
+        added: &[NodeId],
+        removed: &[NodeId],
+    ) -> Task<Event> {
+        let Some(ctx) = application.browser_windows.get_mut(&window_id) else {
+            error!("Browser context not found for window ID: {}", window_id);
+            return Task::none();
+        };
+
+        let Some(tab) = ctx.tab_manager.get_tab_mut(tab_id) else {
+            return Task::none();
+        };
+
+        let Some(page_ctx) = tab.page.as_mut() else {
+            return Task::none();
+        };
+
+        for node_id in removed {
+            page_ctx.remove_attribute(*node_id, attribute);
+        }
+        for node_id in added {
+            page_ctx.set_attribute(*node_id, attribute, "");
+        }
+
+        let style_invalidations = page_ctx.take_style_invalidations();
+        let layout_invalidations = tab.recompute_styles(
+            ctx.viewport,
+            &application.preferences,
+            application.system_preferences,
+            &style_invalidations,
+        );
+        tab.repaint(&layout_invalidations);
+
+        let node_ids = layout_invalidations.layout_nodes().collect();
+
+        Self::relayout_nodes(ctx.viewport, &ctx.text_context, &application.preferences, tab, window_id, node_ids)
+    }
+
+    /// Opens the dropdown menu of the given `<select>` of the active tab, or closes the open menu when `node_id` is
+    /// `None`, the node is not a dropdown, or the dropdown is disabled. Opening a menu removes focus from the focused
+    /// text control.
+    pub fn toggle_dropdown(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        node_id: Option<NodeId>,
+    ) -> Task<Event> {
+        let Some(ctx) = application.browser_windows.get_mut(&window_id) else {
+            error!("Browser context not found for window ID: {}", window_id);
+            return Task::none();
+        };
+
+        let Some(tab) = ctx.tab_manager.active_tab_mut() else {
+            return Task::none();
+        };
+
+        tab.open_dropdown = node_id.filter(|node_id| {
+            tab.page.as_ref().is_some_and(|page_ctx| {
+                let dom = page_ctx.document.dom();
+                dom.get_node(node_id)
+                    .and_then(|node| SelectElement::from_node(node, dom))
+                    .is_some_and(|select| !select.multiple && !select.disabled)
+            })
+        });
+
+        if tab.open_dropdown.is_some() {
+            return Task::done(Event::Browser(BrowserEvent::FocusInput(window_id, None)));
+        }
+
+        Task::none()
+    }
+
+    /// Sends the option chosen in the open dropdown menu of the active tab to the browser with a `SelectOption`
+    /// command, closing the menu.
+    pub fn select_option(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        select_node_id: NodeId,
+        option_index: usize,
+    ) -> Task<Event> {
+        let Some(ctx) = application.browser_windows.get_mut(&window_id) else {
+            error!("Browser context not found for window ID: {}", window_id);
+            return Task::none();
+        };
+
+        let Some(tab) = ctx.tab_manager.active_tab_mut() else {
+            return Task::none();
+        };
+
+        tab.open_dropdown = None;
+
+        let Some(page_ctx) = &tab.page else {
+            return Task::none();
+        };
+
+        let tab_id = tab.id;
+        let document_id = page_ctx.document.id();
+        let browser = Arc::clone(&application.browser);
+
+        Task::perform(
+            async move {
+                browser
+                    .execute(EngineCommand::SelectOption {
+                        document_id,
+                        select_node_id,
+                        option_index,
+                    })
+                    .await
+            },
+            move |result| match result {
+                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+            },
+        )
+    }
+
+    /// Handles a chosen option by moving the `selected` attribute to it from the other options of the `<select>`,
+    /// storing the new `value` of the select, and relaying out the select to show the label of the option.
+    pub fn on_option_selected(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+        select_node_id: NodeId,
+        option_node_ids: &[NodeId],
+        selected_node_id: NodeId,
+        value: String,
+    ) -> Task<Event> {
+        let Some(ctx) = application.browser_windows.get_mut(&window_id) else {
+            error!("Browser context not found for window ID: {}", window_id);
+            return Task::none();
+        };
+
+        let Some(tab) = ctx.tab_manager.get_tab_mut(tab_id) else {
+            return Task::none();
+        };
+
+        let Some(page_ctx) = tab.page.as_mut() else {
+            return Task::none();
+        };
+
+        for option_id in option_node_ids.iter().filter(|id| **id != selected_node_id) {
+            page_ctx.remove_attribute(*option_id, "selected");
+        }
+
+        if !page_ctx.set_attribute(selected_node_id, "selected", "")
+            || !page_ctx.set_attribute(select_node_id, "value", value)
+        {
+            return Task::none();
+        }
+
+        let style_invalidations = page_ctx.take_style_invalidations();
+        let layout_invalidations = tab.recompute_styles(
+            ctx.viewport,
+            &application.preferences,
+            application.system_preferences,
+            &style_invalidations,
+        );
+        tab.repaint(&layout_invalidations);
+
+        // The label shown by the select changed, so it is laid out again even if its style did not.
+        let mut node_ids: Vec<NodeId> = layout_invalidations
+            .layout_nodes()
+            .filter(|id| *id != select_node_id)
+            .collect();
+        node_ids.push(select_node_id);
+
+        Self::relayout_nodes(ctx.viewport, &ctx.text_context, &application.preferences, tab, window_id, node_ids)
+    }
+
+    /// Applies a keyboard edit to the focused text control of the active tab. If the value changed, the new value
+    /// is sent to the browser with an `UpdateInputValue` command.
+    pub fn edit_input(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        action: TextInputAction,
+    ) -> Task<Event> {
+        let Some(ctx) = application.browser_windows.get_mut(&window_id) else {
+            error!("Browser context not found for window ID: {}", window_id);
+            return Task::none();
+        };
+
+        let Some(tab) = ctx.tab_manager.active_tab_mut() else {
+            return Task::none();
+        };
+
+        let tab_id = tab.id;
+        let (Some(page_ctx), Some(input)) = (&tab.page, &mut tab.focused_input) else {
+            return Task::none();
+        };
+
+        let previous = input.text().to_string();
+        if !input.apply(action) {
+            return Task::none();
+        }
+
+        input.recheck_spelling(application.browser.spell_checker(), &previous);
+
+        let node_id = input.node_id();
+        let value = input.text().to_string();
+        let document_id = page_ctx.document.id();
+        let browser = Arc::clone(&application.browser);
+
+        Task::perform(
+            async move {
+                browser
+                    .execute(EngineCommand::UpdateInputValue {
+                        document_id,
+                        node_id,
+                        value,
+                    })
+                    .await
+            },
+            move |result| match result {
+                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+            },
+        )
+    }
+
+    /// Saves the page shown in the active tab to the download directory with a `SaveAs` command, under the last
+    /// segment of its URL.
+    pub fn save_page(application: &Application, window_id: iced::window::Id) -> Task<Event> {
+        let Some(tab) = application
+            .browser_windows
+            .get(&window_id)
+            .and_then(|ctx| ctx.tab_manager.active_tab())
+        else {
+            return Task::none();
+        };
+
+        let Some(page_ctx) = &tab.page else {
+            return Task::none();
+        };
+
+        let Some(directory) = get_download_path().filter(|directory| create_paths(directory).is_ok()) else {
+            error!("No download directory available to save the page to");
+            return Task::none();
+        };
+
+        let url = page_ctx.metadata.url.clone();
+        let tab_id = tab.id;
+        let browser = Arc::clone(&application.browser);
+
+        Task::perform(
+            async move {
+                browser
+                    .execute(EngineCommand::SaveAs {
+                        url: url.to_string(),
+                        directory,
+                    })
+                    .await
+            },
+            move |result| match result {
+                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+            },
+        )
+    }
+
+    /// Handles a navigation whose response is saved to disk instead of being shown. The tab goes back to showing the
+    /// page it showed before the navigation, and the download is listed in the footer until the session ends.
+    pub fn on_download_started(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+        download: Download,
+    ) -> Task<Event> {
+        debug!(url = %download.url, path = %download.path.display(), "Download started");
+        application.downloads.push(download);
+        let poll = BrowserWindow::poll_downloads_later(application, window_id);
+
+        let Some(tab) = application
+            .browser_windows
+            .get_mut(&window_id)
+            .and_then(|ctx| ctx.tab_manager.get_tab_mut(tab_id))
+        else {
+            return poll;
+        };
+
+        tab.loading = None;
+        let previous = if tab.page.is_none() {
+            tab.history.take_back()
+        } else {
+            None
+        };
+
+        match previous {
+            Some((page, metadata)) => Task::batch([
+                poll,
+                Self::on_navigation_success(application, window_id, tab_id, page, metadata, NavigationType::Reload),
+            ]),
+            None => poll,
+        }
+    }
+
+    /// Asks the browser for the tabs of the session that was open when it last crashed with a `RestoreCrashSession`
+    /// command, withdrawing the offer to restore them.
+    pub fn restore_crash_session(application: &mut Application, window_id: iced::window::Id) -> Task<Event> {
+        application.crash_recovery = None;
+
+        let Some(tab_id) = application
+            .browser_windows
+            .get(&window_id)
+            .map(|ctx| ctx.tab_manager.active_tab_id())
+        else {
+            return Task::none();
+        };
+
+        let browser = Arc::clone(&application.browser);
+        Task::perform(async move { browser.execute(EngineCommand::RestoreCrashSession).await }, move |result| {
+            match result {
+                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+            }
+        })
+    }
+
+    /// Reopens each tab of the crashed session in a new tab of the window, navigating it to the page it showed.
+    pub fn on_crash_session_restored(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tabs: Vec<SessionEntry>,
+    ) -> Task<Event> {
+        let mut tasks = Vec::with_capacity(tabs.len());
+
+        for entry in tabs {
+            let Some(ctx) = application.browser_windows.get_mut(&window_id) else {
+                break;
+            };
+
+            let tab_id = TabId::new(ctx.tab_manager.next_tab_id());
+            ctx.tab_manager.add_tab(Tab::new(tab_id));
+            if ctx.tab_manager.change_active_tab(tab_id).is_err() {
+                continue;
+            }
+
+            debug!(url = %entry.url, "Restoring tab of the crashed session");
+            tasks.push(Self::navigate_to_url(application, window_id, entry.url));
+        }
+
+        Task::batch(tasks)
+    }
+
+    /// Sends the selected text of the focused text control, or the text selected on the page, to the browser with a
+    /// `WriteClipboard` command.
+    pub fn copy_selection(application: &Application, window_id: iced::window::Id) -> Task<Event> {
+        let Some(tab) = application
+            .browser_windows
+            .get(&window_id)
+            .and_then(|ctx| ctx.tab_manager.active_tab())
+        else {
+            return Task::none();
+        };
+
+        let text = match &tab.focused_input {
+            Some(input) => input.selected_text(),
+            None => tab
+                .selection
+                .range()
+                .zip(tab.layout_tree.as_ref())
+                .map(|((anchor, focus), layout_tree)| layout_tree.text_in_range(anchor, focus)),
+        };
+        let Some(text) = text.filter(|text| !text.is_empty()) else {
+            return Task::none();
+        };
+
+        let tab_id = tab.id;
+        let browser = Arc::clone(&application.browser);
+
+        Task::perform(
+            async move {
+                browser
+                    .execute(EngineCommand::WriteClipboard {
+                        mime_type: String::from("text/plain"),
+                        data: text.into_bytes(),
+                    })
+                    .await
+            },
+            move |result| match result {
+                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+            },
+        )
+    }
+
+    /// Asks the browser for permission to read the system clipboard with a `ReadClipboard` command, carrying the
+    /// instant of the paste shortcut as the user gesture.
+    pub fn paste_clipboard(
+        application: &Application,
+        window_id: iced::window::Id,
+        user_gesture: Instant,
+    ) -> Task<Event> {
+        let Some(tab) = application
+            .browser_windows
+            .get(&window_id)
+            .and_then(|ctx| ctx.tab_manager.active_tab())
+        else {
+            return Task::none();
+        };
+
+        if tab.focused_input.is_none() {
+            return Task::none();
+        }
+
+        let tab_id = tab.id;
+        let browser = Arc::clone(&application.browser);
+
+        Task::perform(
+            async move {
+                browser
+                    .execute(EngineCommand::ReadClipboard {
+                        mime_type: String::from("text/plain"),
+                        user_gesture: Some(user_gesture),
+                    })
+                    .await
+            },
+            move |result| match result {
+                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+            },
+        )
+    }
+
+    /// Handles data accepted by the browser for the clipboard by writing it to the system clipboard.
+    ///
+    /// The system clipboard only holds text, so `text/html` is written as its markup.
+    pub fn on_clipboard_write(mime_type: &str, data: Vec<u8>) -> Task<Event> {
+        match String::from_utf8(data) {
+            Ok(text) => iced::clipboard::write(text),
+            Err(err) => {
+                error!(mime_type, %err, "Clipboard data is not valid UTF-8");
+                Task::none()
+            }
+        }
+    }
+
+    /// Handles a permitted clipboard read by reading the system clipboard and emitting its content as a
+    /// `ClipboardContent` event.
+    pub fn on_clipboard_read_allowed(window_id: iced::window::Id, mime_type: String) -> Task<Event> {
+        iced::clipboard::read().map(move |content| {
+            Event::Browser(BrowserEvent::ClipboardContent {
+                window_id,
+                mime_type: mime_type.clone(),
+                data: content.unwrap_or_default().into_bytes(),
+            })
+        })
+    }
+
+    /// Handles the content read from the clipboard by inserting it into the focused text control.
+    pub fn on_clipboard_content(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        mime_type: &str,
+        data: &[u8],
+    ) -> Task<Event> {
+        if data.is_empty() {
+            return Task::none();
+        }
+
+        let Ok(text) = str::from_utf8(data) else {
+            error!(mime_type, "Clipboard content is not valid UTF-8");
+            return Task::none();
+        };
+
+        Self::edit_input(application, window_id, TextInputAction::Insert(text.to_string()))
+    }
+
+    /// Handles an accepted input value by storing it in the `value` attribute of the control, recomputing the styles
+    /// the change invalidated, and relaying out the control to show the new text.
+    pub fn on_input_value_updated(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+        node_id: NodeId,
+        value: String,
+    ) -> Task<Event> {
+        let Some(ctx) = application.browser_windows.get_mut(&window_id) else {
+            error!("Browser context not found for window ID: {}", window_id);
+            return Task::none();
+        };
+
+        let Some(tab) = ctx.tab_manager.get_tab_mut(tab_id) else {
+            return Task::none();
+        };
+
+        let Some(page_ctx) = tab.page.as_mut() else {
+            return Task::none();
+        };
+
+        if !page_ctx.set_attribute(node_id, "value", value) {
+            return Task::none();
+        }
+
+        let style_invalidations = page_ctx.take_style_invalidations();
+        let layout_invalidations = tab.recompute_styles(
+            ctx.viewport,
+            &application.preferences,
+            application.system_preferences,
+            &style_invalidations,
+        );
+        tab.repaint(&layout_invalidations);
+
+        // The content of the control changed, so it is laid out again even if its style did not.
+        let mut node_ids: Vec<NodeId> = layout_invalidations
+            .layout_nodes()
+            .filter(|id| *id != node_id)
+            .collect();
+        node_ids.push(node_id);
+
+        Self::relayout_nodes(ctx.viewport, &ctx.text_context, &application.preferences, tab, window_id, node_ids)
+    }
+
+    /// Sends the size of an element being dragged by its resize handle to the browser with a `ResizeElement`
+    /// command.
+    pub fn resize_element(
+        application: &Application,
+        window_id: iced::window::Id,
+        node_id: NodeId,
+        new_width: Option<f64>,
+        new_height: Option<f64>,
+    ) -> Task<Event> {
+        let Some(tab) = application
+            .browser_windows
+            .get(&window_id)
+            .and_then(|ctx| ctx.tab_manager.active_tab())
+        else {
+            return Task::none();
+        };
+
+        let Some(page_ctx) = &tab.page else {
+            return Task::none();
+        };
+
+        let tab_id = tab.id;
+        let document_id = page_ctx.document.id();
+        let browser = Arc::clone(&application.browser);
+
+        Task::perform(
+            async move {
+                browser
+                    .execute(EngineCommand::ResizeElement {
+                        document_id,
+                        node_id,
+                        new_width,
+                        new_height,
+                    })
+                    .await
+            },
+            move |result| match result {
+                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+            },
+        )
+    }
+
+    /// Sends a value picked for a control of the active tab without typing, like dragging the thumb of a range slider,
+    /// to the browser with an `UpdateInputValue` command.
+    pub fn set_input_value(
+        application: &Application,
+        window_id: iced::window::Id,
+        node_id: NodeId,
+        value: String,
+    ) -> Task<Event> {
+        let Some(tab) = application
+            .browser_windows
+            .get(&window_id)
+            .and_then(|ctx| ctx.tab_manager.active_tab())
+        else {
+            return Task::none();
+        };
+
+        let Some(page_ctx) = &tab.page else {
+            return Task::none();
+        };
+
+        let tab_id = tab.id;
+        let document_id = page_ctx.document.id();
+        let browser = Arc::clone(&application.browser);
+
+        Task::perform(
+            async move {
+                browser
+                    .execute(EngineCommand::UpdateInputValue {
+                        document_id,
+                        node_id,
+                        value,
+                    })
+                    .await
+            },
+            move |result| match result {
+                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+            },
+        )
+    }
+
+    /// Fires a DOM event at an element of the active tab through the JavaScript bridge with a `DispatchEvent`
+    /// command.
+    pub fn dispatch_event(
+        application: &Application,
+        window_id: iced::window::Id,
+        node_id: NodeId,
+        event_type: String,
+    ) -> Task<Event> {
+        let Some(tab) = application
+            .browser_windows
+            .get(&window_id)
+            .and_then(|ctx| ctx.tab_manager.active_tab())
+        else {
+            return Task::none();
+        };
+
+        let Some(page_ctx) = &tab.page else {
+            return Task::none();
+        };
+
+        let tab_id = tab.id;
+        let document_id = page_ctx.document.id();
+        let browser = Arc::clone(&application.browser);
+
+        Task::perform(
+            async move {
+                browser
+                    .execute(EngineCommand::DispatchEvent {
+                        document_id,
+                        node_id,
+                        event_type,
+                    })
+                    .await
+            },
+            move |result| match result {
+                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+            },
+        )
+    }
+
+    /// Handles a dispatched event by applying the changes its listeners made to the document, recomputing the styles
+    /// of the changed elements, and laying out the whole page again if nodes were added, moved, or removed.
+    pub fn on_event_dispatched(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+        changes: Vec<DomNode>,
+    ) -> Task<Event> {
+        if changes.is_empty() {
+            return Task::none();
+        }
+
+        let Some(ctx) = application.browser_windows.get_mut(&window_id) else {
+            error!("Browser context not found for window ID: {}", window_id);
+            return Task::none();
+        };
+
+        let Some(tab) = ctx.tab_manager.get_tab_mut(tab_id) else {
+            return Task::none();
+        };
+
+        let Some(page_ctx) = tab.page.as_mut() else {
+            return Task::none();
+        };
+
+        let Some(structure_changed) = page_ctx.apply_changes(changes) else {
+            warn!("Discarding DOM changes for tab {}, its document is out of sync with the engine", tab_id);
+            return Task::none();
+        };
+
+        let style_invalidations = page_ctx.take_style_invalidations();
+
+        if structure_changed {
+            let mut text_context = ctx.text_context.lock().unwrap();
+            tab.resize_current_page(
+                ctx.viewport,
+                &mut text_context,
+                &application.preferences,
+                application.system_preferences,
+            );
+
+            return Task::none();
+        }
+
+        let layout_invalidations = tab.recompute_styles(
+            ctx.viewport,
+            &application.preferences,
+            application.system_preferences,
+            &style_invalidations,
+        );
+        tab.repaint(&layout_invalidations);
+
+        let node_ids = layout_invalidations.layout_nodes().collect();
+
+        Self::relayout_nodes(ctx.viewport, &ctx.text_context, &application.preferences, tab, window_id, node_ids)
+    }
+
+    /// Handles a resized element by storing its new `style` attribute, recomputing the styles the change invalidated,
+    /// and relaying out the element at its new size.
+    pub fn on_element_resized(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+        node_id: NodeId,
+        style: String,
+    ) -> Task<Event> {
+        let Some(ctx) = application.browser_windows.get_mut(&window_id) else {
+            error!("Browser context not found for window ID: {}", window_id);
+            return Task::none();
+        };
+
+        let Some(tab) = ctx.tab_manager.get_tab_mut(tab_id) else {
+            return Task::none();
+        };
+
+        let Some(page_ctx) = tab.page.as_mut() else {
+            return Task::none();
+        };
+
+        if !page_ctx.set_attribute(node_id, "style", style) {
+            return Task::none();
+        }
+
+        let style_invalidations = page_ctx.take_style_invalidations();
+        let layout_invalidations = tab.recompute_styles(
+            ctx.viewport,
+            &application.preferences,
+            application.system_preferences,
+            &style_invalidations,
         );
+        tab.repaint(&layout_invalidations);
 
-        let tab_id = ctx
-            .tab_manager
-            .active_tab()
-            .expect("There should always be an active tab in the browser")
-            .id;
-        ctx.current_url.clone_from(&url);
+        let node_ids = layout_invalidations.layout_nodes().collect();
 
-        Task::perform(
-            async move {
-                browser
-                    .execute(EngineCommand::Navigate {
-                        url,
-                        navigation_type: NavigationType::Normal,
-                    })
-                    .await
-            },
-            move |result| match result {
-                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
-                Err(err) => match err {
-                    CoreError::Navigation(nav_err) => {
-                        Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::NavigateError(nav_err)))
-                    }
-                    _ => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
-                },
-            },
-        )
+        Self::relayout_nodes(ctx.viewport, &ctx.text_context, &application.preferences, tab, window_id, node_ids)
     }
 
     /// Handles navigation back in the tab's history by sending a `NavigateBack` command to the browser and processing the result,
@@ -189,8 +1355,9 @@ impl Tab {
                     let tab_id = tab.id;
                     let url = metadata.url.to_string();
                     let browser = Arc::clone(&application.browser);
+                    let loading = Self::start_loading(tab, window_id);
 
-                    Task::perform(
+                    let navigation = Task::perform(
                         async move {
                             browser
                                 .execute(EngineCommand::Navigate {
@@ -210,7 +1377,9 @@ impl Tab {
                                 _ => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
                             },
                         },
-                    )
+                    );
+
+                    Task::batch([loading, navigation])
                 }
             }
         } else {
@@ -246,8 +1415,9 @@ impl Tab {
                     let tab_id = tab.id;
                     let url = metadata.url.to_string();
                     let browser = Arc::clone(&application.browser);
+                    let loading = Self::start_loading(tab, window_id);
 
-                    Task::perform(
+                    let navigation = Task::perform(
                         async move {
                             browser
                                 .execute(EngineCommand::Navigate {
@@ -267,7 +1437,9 @@ impl Tab {
                                 _ => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
                             },
                         },
-                    )
+                    );
+
+                    Task::batch([loading, navigation])
                 }
             }
         } else {
@@ -278,13 +1450,13 @@ impl Tab {
     /// Handles refreshing the current page by re-navigating to the current URL. It retrieves the current URL from the active tab's page
     /// information and sends a `Navigate` command to the browser with that URL. If the current URL is empty
     /// (e.g., if the tab has no page loaded), it simply returns without performing any action.
-    pub fn refresh_page(application: &Application, window_id: iced::window::Id) -> Task<Event> {
+    pub fn refresh_page(application: &mut Application, window_id: iced::window::Id) -> Task<Event> {
         let tab = application
             .browser_windows
-            .get(&window_id)
+            .get_mut(&window_id)
             .expect("No browser context found for window ID")
             .tab_manager
-            .active_tab()
+            .active_tab_mut()
             .expect("There should always be an active tab in the browser");
 
         let Some(page_ctx) = &tab.page else {
@@ -294,8 +1466,9 @@ impl Tab {
         let tab_id = tab.id;
         let url = page_ctx.metadata.url.to_string();
         let browser = Arc::clone(&application.browser);
+        let loading = Self::start_loading(tab, window_id);
 
-        Task::perform(
+        let navigation = Task::perform(
             async move {
                 browser
                     .execute(EngineCommand::Navigate {
@@ -313,11 +1486,86 @@ impl Tab {
                     _ => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
                 },
             },
-        )
+        );
+
+        Task::batch([loading, navigation])
+    }
+
+    /// Starts the loading spinner of a tab whose navigation is starting, unless it is already spinning.
+    fn start_loading(tab: &mut Tab, window_id: iced::window::Id) -> Task<Event> {
+        if tab.loading.is_some() {
+            return Task::none();
+        }
+
+        tab.loading = Some(0);
+        Self::loading_frame_task(window_id, tab.id)
+    }
+
+    /// Waits for the next frame of the loading spinner of a tab.
+    fn loading_frame_task(window_id: iced::window::Id, tab_id: TabId) -> Task<Event> {
+        Task::perform(tokio::time::sleep(LOADING_FRAME_INTERVAL), move |()| {
+            Event::Browser(BrowserEvent::LoadingFrameElapsed(window_id, tab_id))
+        })
+    }
+
+    /// Advances the loading spinner of a tab to its next frame, as long as the tab is still loading.
+    pub fn on_loading_frame_elapsed(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+    ) -> Task<Event> {
+        let Some(frame) = application
+            .browser_windows
+            .get_mut(&window_id)
+            .and_then(|ctx| ctx.tab_manager.get_tab_mut(tab_id))
+            .and_then(|tab| tab.loading.as_mut())
+        else {
+            return Task::none();
+        };
+
+        *frame = frame.wrapping_add(1);
+        Self::loading_frame_task(window_id, tab_id)
+    }
+
+    /// Handles a failed navigation by stopping the loading spinner of the tab.
+    pub fn on_navigation_error(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+        error: &NavigationError,
+    ) -> Task<Event> {
+        error!(%error, "Navigation failed");
+        Self::stop_loading(application, window_id, tab_id);
+
+        Task::none()
+    }
+
+    /// Handles a failed command by stopping the loading spinner of the tab, as a navigation can fail with errors other
+    /// than a `NavigationError`, like a failed form submission.
+    pub fn on_engine_error(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+        error: &CoreError,
+    ) -> Task<Event> {
+        error!(%error, "Engine command failed");
+        Self::stop_loading(application, window_id, tab_id);
+
+        Task::none()
+    }
+
+    fn stop_loading(application: &mut Application, window_id: iced::window::Id, tab_id: TabId) {
+        if let Some(tab) = application
+            .browser_windows
+            .get_mut(&window_id)
+            .and_then(|ctx| ctx.tab_manager.get_tab_mut(tab_id))
+        {
+            tab.loading = None;
+        }
     }
 
     /// Handles successful navigation by updating the tab's document, stylesheets, layout tree, and initiating image
-    /// fetches for any images found on the page.
+    /// fetches for any images found on the page. Inline `<svg>` elements are rasterized like fetched images.
     pub fn on_navigation_success(
         application: &mut Application,
         window_id: iced::window::Id,
@@ -330,6 +1578,7 @@ impl Tab {
             && let Some(tab) = ctx.tab_manager.get_tab_mut(tab_id)
         {
             tab.prepare_for_navigation();
+            tab.loading = None;
 
             // TODO: Store in permanent history.
             //if matches!(navigation_type, NavigationType::Normal) {
@@ -337,42 +1586,133 @@ impl Tab {
             //}
 
             let viewport = ctx.viewport;
-            ctx.current_url = metadata.url.to_string();
 
             let mut text_context = ctx.text_context.lock().unwrap();
-            tab.resolve_page(viewport, &mut text_context, page, metadata, &application.preferences, None);
+            let web_fonts = Self::start_web_fonts(&mut text_context, FontFace::collect(page.stylesheets()));
+            let font_deadline = text_context.font_loader().next_deadline();
+            tab.resolve_page(
+                viewport,
+                &mut text_context,
+                LoadedPage {
+                    document: page,
+                    metadata,
+                    scroll_offset: None,
+                },
+                &application.preferences,
+                application.system_preferences,
+            );
             drop(text_context);
 
             let page_ctx = tab.page.as_ref().unwrap();
 
-            let tasks: Vec<Task<Event>> = page_ctx
+            debug!(
+                timings = ?page_ctx.metadata.timings.milestones().collect::<Vec<_>>(),
+                "Loaded {}", page_ctx.metadata.url
+            );
+
+            let navigation_state = Task::done(Event::Browser(BrowserEvent::NavigationStateChanged {
+                window_id,
+                tab_id,
+                can_go_back: tab.history.can_go_back(),
+                can_go_forward: tab.history.can_go_forward(),
+                current_url: page_ctx.metadata.url.clone(),
+                current_title: Some(page_ctx.metadata.title.trim().to_string()).filter(|title| !title.is_empty()),
+            }));
+
+            let mut tasks: Vec<Task<Event>> = page_ctx
                 .document
                 .images()
                 .iter()
                 .map(|(src, ids)| {
-                    let node_ids = ids.clone();
-                    let browser = application.browser.clone();
-                    let src = src.clone();
-                    let request_url = page_ctx.metadata.url.clone();
-
-                    Task::perform(
-                        async move {
-                            browser
-                                .execute(EngineCommand::FetchImage {
-                                    node_ids,
-                                    request_url,
-                                    image_url: src,
-                                })
-                                .await
-                        },
-                        move |result| match result {
-                            Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
-                            Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
-                        },
+                    Self::fetch_image(
+                        &application.browser,
+                        window_id,
+                        tab_id,
+                        page_ctx.metadata.url.clone(),
+                        src.clone(),
+                        ids.clone(),
                     )
                 })
                 .collect();
 
+            tasks.extend(web_fonts.into_iter().map(|(family, font_urls)| {
+                let browser = application.browser.clone();
+                let request_url = page_ctx.metadata.url.clone();
+                let failed_family = family.clone();
+
+                Task::perform(
+                    async move {
+                        browser
+                            .execute(EngineCommand::FetchFont {
+                                family,
+                                request_url,
+                                font_urls,
+                            })
+                            .await
+                    },
+                    move |result| match result {
+                        Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                        Err(err) => {
+                            debug!(%err, "Failed to load web font {failed_family}");
+                            Event::Browser(BrowserEvent::FontFailed(window_id, tab_id, failed_family))
+                        }
+                    },
+                )
+            }));
+            if let Some(link) = page_ctx.metadata.favicon_link.clone() {
+                let browser = application.browser.clone();
+                let page_url = page_ctx.metadata.url.clone();
+
+                tasks.push(Task::perform(
+                    async move {
+                        browser
+                            .execute(EngineCommand::FetchFavicon { page_url, link })
+                            .await
+                    },
+                    move |result| match result {
+                        Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                        Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+                    },
+                ));
+            }
+            tasks.push(Self::font_timeline_task(window_id, tab_id, font_deadline));
+            tasks.push(navigation_state);
+
+            let dom = page_ctx.document.dom();
+            let style_tree = tab.style_tree.as_ref();
+            tasks.extend(inline_svgs(dom).into_iter().filter_map(|node_id| {
+                // The shapes of the SVG document inherit the `paint-order` the page's stylesheets gave the element.
+                let paint_order = style_tree
+                    .and_then(|style_tree| style_tree.get(node_id))
+                    .filter(|style| !style.paint_order.is_normal())
+                    .map(|style| format!("paint-order: {}", style.paint_order));
+                let markup = svg_document(dom, node_id, paint_order.as_deref())?;
+                let url = format!("inline svg #{node_id}");
+
+                Some(Task::perform(
+                    async move { decode_svg_bytes(&url, markup.as_bytes()).map(|decoded| (url, decoded)) },
+                    move |result| match result {
+                        Ok((url, decoded)) => Event::Browser(BrowserEvent::ImageDecoded {
+                            window_id,
+                            tab_id,
+                            node_ids: vec![node_id],
+                            url,
+                            image_data: decoded,
+                        }),
+                        Err(err) => Event::Browser(BrowserEvent::Error(err.into())),
+                    },
+                ))
+            }));
+
+            tab.lazy_images = LazyLoadQueue::new(page_ctx.document.lazy_images().clone());
+            tasks.push(Self::load_lazy_images(
+                &application.browser,
+                viewport,
+                &application.preferences,
+                tab,
+                window_id,
+            ));
+
             if !tasks.is_empty() {
                 return Task::batch(tasks);
             }
@@ -381,6 +1721,155 @@ impl Tab {
         Task::none()
     }
 
+    /// Declares the web fonts of a document to the font loader and starts their `font-display` timeline, so that the
+    /// first layout of the document already hides or replaces the text using them. The fonts of the previous document
+    /// are forgotten.
+    ///
+    /// # Returns
+    /// The family and the URLs of the font files of every `@font-face` rule with a `url()` source in a supported
+    /// format.
+    fn start_web_fonts(text_context: &mut TextContext, font_faces: Vec<FontFace>) -> Vec<(String, Vec<String>)> {
+        let now = Instant::now();
+        let web_fonts = font_faces
+            .iter()
+            .map(|face| (face.family.clone(), face.urls().map(str::to_string).collect::<Vec<_>>()))
+            .filter(|(_, font_urls)| !font_urls.is_empty())
+            .collect::<Vec<_>>();
+
+        text_context.font_loader_mut().clear();
+        text_context.set_font_faces(font_faces);
+        for (family, _) in &web_fonts {
+            text_context.font_loader_mut().start(family, now);
+        }
+
+        web_fonts
+    }
+
+    /// Waits until the next web font reaches the end of its block or swap period, `None` when no font is waiting.
+    fn font_timeline_task(window_id: iced::window::Id, tab_id: TabId, deadline: Option<Instant>) -> Task<Event> {
+        deadline.map_or_else(Task::none, |deadline| {
+            Task::perform(tokio::time::sleep_until(deadline.into()), move |()| {
+                Event::Browser(BrowserEvent::FontTimelineElapsed(window_id, tab_id))
+            })
+        })
+    }
+
+    /// Shows the favicon fetched for a page, unless the tab has navigated away from it in the meantime.
+    pub fn on_favicon_loaded(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+        page_url: &Url,
+        favicon: Favicon,
+    ) -> Task<Event> {
+        let Some(ctx) = application.browser_windows.get_mut(&window_id) else {
+            error!("Browser context not found for window ID: {}", window_id);
+            return Task::none();
+        };
+
+        if let Some(page) = ctx
+            .tab_manager
+            .get_tab_mut(tab_id)
+            .and_then(|tab| tab.page.as_mut())
+            .filter(|page| page.metadata.url == *page_url)
+        {
+            page.metadata.favicon = Some(favicon);
+        }
+
+        Task::none()
+    }
+
+    /// Handles a fetched web font by registering it with the text context and laying the page out again with it, if
+    /// it arrived before the end of its swap period, or with the fallback font if its data can not be read.
+    pub fn on_font_loaded(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+        family: &str,
+        url: &str,
+        data: Vec<u8>,
+    ) -> Task<Event> {
+        let Some(ctx) = application.browser_windows.get_mut(&window_id) else {
+            error!("Browser context not found for window ID: {}", window_id);
+            return Task::none();
+        };
+
+        let mut text_context = ctx.text_context.lock().unwrap();
+        if !text_context.load_web_font(family, data, Instant::now()) {
+            debug!("Not using web font {family} from {url}");
+            return Task::none();
+        }
+
+        if let Some(tab) = ctx.tab_manager.get_tab_mut(tab_id) {
+            tab.resize_current_page(
+                ctx.viewport,
+                &mut text_context,
+                &application.preferences,
+                application.system_preferences,
+            );
+        }
+
+        Task::none()
+    }
+
+    /// Handles a web font whose sources all failed to load by ending its block period, laying the page out again with
+    /// its text shown in the fallback font.
+    pub fn on_font_failed(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+        family: &str,
+    ) -> Task<Event> {
+        let Some(ctx) = application.browser_windows.get_mut(&window_id) else {
+            error!("Browser context not found for window ID: {}", window_id);
+            return Task::none();
+        };
+
+        let mut text_context = ctx.text_context.lock().unwrap();
+        if text_context.font_loader_mut().fail(family)
+            && let Some(tab) = ctx.tab_manager.get_tab_mut(tab_id)
+        {
+            tab.resize_current_page(
+                ctx.viewport,
+                &mut text_context,
+                &application.preferences,
+                application.system_preferences,
+            );
+        }
+
+        Task::none()
+    }
+
+    /// Handles the end of the block or swap period of a web font by laying the page out again, showing its text in the
+    /// fallback font or giving up on the font, and waits for the next period to end.
+    pub fn on_font_timeline_elapsed(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+    ) -> Task<Event> {
+        let Some(ctx) = application.browser_windows.get_mut(&window_id) else {
+            error!("Browser context not found for window ID: {}", window_id);
+            return Task::none();
+        };
+
+        let mut text_context = ctx.text_context.lock().unwrap();
+        if text_context.font_loader_mut().advance(Instant::now())
+            && let Some(tab) = ctx.tab_manager.get_tab_mut(tab_id)
+        {
+            tab.resize_current_page(
+                ctx.viewport,
+                &mut text_context,
+                &application.preferences,
+                application.system_preferences,
+            );
+        }
+
+        let deadline = text_context.font_loader().next_deadline();
+        drop(text_context);
+
+        Self::font_timeline_task(window_id, tab_id, deadline)
+    }
+
     /// Handles the completion of a relayout operation, updating the tab's layout tree if the
     /// generation matches.
     pub fn on_relayout(
@@ -396,6 +1885,8 @@ impl Tab {
         };
 
         if let Some(tab) = ctx.tab_manager.get_tab_mut(tab_id) {
+            tab.revealing.clear();
+
             if tab.layout_generation == generation {
                 tab.layout_tree = Some(layout_tree);
             } else {
@@ -416,7 +1907,7 @@ impl Tab {
         window_id: iced::window::Id,
         tab_id: TabId,
         node_ids: Vec<NodeId>,
-        url: String,
+        url: &str,
         image_data: LayoutImage,
     ) -> Task<Event> {
         let Some(ctx) = application.browser_windows.get_mut(&window_id) else {
@@ -432,6 +1923,8 @@ impl Tab {
             return Task::none();
         };
 
+        debug!("Decoded image {url} ({}x{})", image_data.width, image_data.height);
+
         {
             let image_ctx = page_ctx.image_context();
             let mut image_ctx = image_ctx.lock().unwrap();
@@ -441,17 +1934,127 @@ impl Tab {
             }
         }
 
-        let viewport = ctx.viewport;
+        Self::relayout_nodes(ctx.viewport, &ctx.text_context, &application.preferences, tab, window_id, node_ids)
+    }
+
+    /// Lays out the contents of the `content-visibility: auto` elements of a tab that were skipped and that have been
+    /// scrolled into view, in the background like [`Tab::relayout_nodes`]. Nothing is laid out while every such
+    /// element is already being revealed by a relayout that has not completed yet.
+    pub fn reveal_skipped_content(
+        viewport: iced::Size,
+        text_context: &Arc<Mutex<TextContext>>,
+        preferences: &BrowserPreferences,
+        tab: &mut Tab,
+        window_id: iced::window::Id,
+    ) -> Task<Event> {
+        let visible_area = tab.visible_area(viewport, preferences);
+        let Some(layout_tree) = &tab.layout_tree else {
+            return Task::none();
+        };
+
+        let mut revealed = false;
+        for node_id in layout_tree.skipped_nodes(visible_area) {
+            revealed |= tab.revealing.insert(node_id);
+        }
+
+        if !revealed {
+            return Task::none();
+        }
+
+        Self::relayout_nodes(viewport, text_context, preferences, tab, window_id, Vec::new())
+    }
+
+    /// Starts loading the images of the `<img loading="lazy">` elements of a tab that have been scrolled within
+    /// [`browser_core::LAZY_LOAD_THRESHOLD`] of the viewport. Elements are positioned by the current layout, where
+    /// images that are not loaded yet only take up the size given by their attributes or styles.
+    pub fn load_lazy_images(
+        browser: &Arc<Browser>,
+        viewport: iced::Size,
+        preferences: &BrowserPreferences,
+        tab: &mut Tab,
+        window_id: iced::window::Id,
+    ) -> Task<Event> {
+        if tab.lazy_images.is_empty() {
+            return Task::none();
+        }
+
+        let visible_area = tab.visible_area(viewport, preferences);
+        let (Some(page_ctx), Some(layout_tree)) = (tab.page.as_ref(), tab.layout_tree.as_ref()) else {
+            return Task::none();
+        };
+
+        let mut positions: HashMap<NodeId, f64> = HashMap::new();
+        for node in layout_tree.nodes.iter().flatten() {
+            if let Some(node_id) = node.node_id {
+                positions.entry(node_id).or_insert(node.dimensions.y);
+            }
+        }
+
+        let ready = tab
+            .lazy_images
+            .take_within_threshold(visible_area.y, visible_area.height, |node_id| positions.get(&node_id).copied());
+
+        Task::batch(ready.into_iter().map(|(src, node_ids)| {
+            debug!("Loading lazy image {} as it comes into view", src);
+            Self::fetch_image(browser, window_id, tab.id, page_ctx.metadata.url.clone(), src, node_ids)
+        }))
+    }
+
+    /// Fetches an image of a page, delivering it as an `ImageFetched` engine response.
+    fn fetch_image(
+        browser: &Arc<Browser>,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+        request_url: Url,
+        image_url: String,
+        node_ids: Vec<NodeId>,
+    ) -> Task<Event> {
+        let browser = browser.clone();
+
+        Task::perform(
+            async move {
+                browser
+                    .execute(EngineCommand::FetchImage {
+                        node_ids,
+                        request_url,
+                        image_url,
+                    })
+                    .await
+            },
+            move |result| match result {
+                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+            },
+        )
+    }
 
+    /// Relayouts the given DOM nodes of a tab in the background, e.g. after their content has changed, along with the
+    /// skipped contents of `content-visibility: auto` elements that are in view. The resulting layout tree is
+    /// delivered as a `RelayoutComplete` event, or as a [`LayoutError::NotLaidOut`] error if the tab has no layout yet.
+    fn relayout_nodes(
+        viewport: iced::Size,
+        text_context: &Arc<Mutex<TextContext>>,
+        preferences: &BrowserPreferences,
+        tab: &Tab,
+        window_id: iced::window::Id,
+        node_ids: Vec<NodeId>,
+    ) -> Task<Event> {
         let Some(page_ctx) = tab.page.clone() else {
             return Task::none();
         };
 
+        let visible_area = tab.visible_area(viewport, preferences);
+        let viewport = Tab::layout_viewport(viewport, &page_ctx.metadata, preferences);
+        let viewport = Rect::new(0.0, 0.0, f64::from(viewport.width), f64::from(viewport.height));
         let image_ctx = page_ctx.image_context();
         let style_tree = tab.style_tree.clone();
         let layout_tree = tab.layout_tree.clone();
-        let text_ctx = ctx.text_context.clone();
+        let text_ctx = Arc::clone(text_context);
         let generation = tab.layout_generation;
+        let tab_id = tab.id;
+        let error = LayoutError::NotLaidOut {
+            nodes: node_ids.clone(),
+        };
 
         Task::perform(
             async move {
@@ -459,24 +2062,23 @@ impl Tab {
                     let dom_tree = page_ctx.document.dom();
                     let style_tree = style_tree?;
                     let mut text_ctx = text_ctx.lock().unwrap();
+                    text_ctx.set_font_faces(FontFace::collect(page_ctx.document.stylesheets()));
                     let image_ctx = image_ctx.lock().unwrap();
                     let mut layout_tree = layout_tree?;
                     let box_tree = BoxTree::new(dom_tree, &style_tree);
+                    let mut input = LayoutInput {
+                        dom: dom_tree,
+                        box_tree: &box_tree,
+                        text: &mut text_ctx,
+                        image: &image_ctx,
+                    };
 
                     for node_id in node_ids {
-                        LayoutTree::relayout_node(
-                            &node_id,
-                            Rect::new(0.0, 0.0, f64::from(viewport.width), f64::from(viewport.height)),
-                            &mut layout_tree,
-                            &mut LayoutInput {
-                                dom: dom_tree,
-                                box_tree: &box_tree,
-                                text: &mut text_ctx,
-                                image: &image_ctx,
-                            },
-                        );
+                        LayoutTree::relayout_node(&node_id, viewport, &mut layout_tree, &mut input);
                     }
 
+                    LayoutTree::reveal_skipped(visible_area, viewport, &mut layout_tree, &mut input);
+
                     Some(layout_tree)
                 })
                 .await
@@ -484,7 +2086,7 @@ impl Tab {
             },
             move |layout_tree| {
                 layout_tree.map_or_else(
-                    || Event::Browser(BrowserEvent::Error(BrowserError::ImageLoad(url))),
+                    || Event::Browser(BrowserEvent::Error(error.into())),
                     |layout_tree| {
                         Event::Browser(BrowserEvent::RelayoutComplete(window_id, tab_id, generation, layout_tree))
                     },
@@ -493,6 +2095,27 @@ impl Tab {
         )
     }
 
+    /// Handles a rendered `<canvas>` bitmap by storing it in the image context of the tab, like a decoded image,
+    /// and relaying out the canvas element.
+    pub fn on_canvas_rendered(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+        node_id: NodeId,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Task<Event> {
+        let image_data = LayoutImage {
+            width,
+            height,
+            rgba,
+            svg_source: None,
+        };
+
+        Self::on_image_decoded(application, window_id, tab_id, vec![node_id], &format!("canvas #{node_id}"), image_data)
+    }
+
     /// Handles successful image loads by decoding the image bytes, storing it in the cache, and updating the
     /// corresponding image elements in the tab's layout tree. If the image fails to decode, it marks the cache
     /// entry as failed and triggers a UI update to reflect the failed image load
@@ -508,16 +2131,17 @@ impl Tab {
     ) -> Task<Event> {
         Task::perform(
             async move {
+                if is_svg(&content_type, &url) {
+                    return decode_svg_bytes(&url, bytes.as_slice()).map(|decoded| (url, decoded));
+                }
+
                 let format = if let Some(from_mime) = ImageFormat::from_mime_type(content_type) {
                     Some(from_mime)
                 } else {
                     ImageFormat::from_extension(url.rsplit('.').next().unwrap_or_default())
                 };
 
-                match decode_image_bytes(&url, bytes.as_slice(), format) {
-                    Ok(decoded) => Ok((url, decoded)),
-                    Err(err) => Err(err),
-                }
+                decode_image_bytes(&url, bytes.as_slice(), format).map(|decoded| (url, decoded))
             },
             move |result| match result {
                 Ok((url, decoded)) => Event::Browser(BrowserEvent::ImageDecoded {
@@ -527,7 +2151,7 @@ impl Tab {
                     url,
                     image_data: decoded,
                 }),
-                Err(err) => Event::Browser(BrowserEvent::Error(BrowserError::ImageLoad(err))),
+                Err(err) => Event::Browser(BrowserEvent::Error(err.into())),
             },
         )
     }
@@ -1,6 +1,8 @@
 use std::sync::{Arc, Mutex};
 
 use browser_core::{Document, PageMetadata};
+use css_style::StyleInvalidationSet;
+use html_dom::{DomNode, HoverChange, NodeId};
 use layout::ImageContext;
 
 #[derive(Debug, Clone)]
@@ -9,6 +11,9 @@ pub struct Page {
     pub metadata: PageMetadata,
 
     image_ctx: Arc<Mutex<ImageContext>>,
+
+    /// The nodes mutated since the styles of the page were last recomputed.
+    style_invalidations: StyleInvalidationSet,
 }
 
 impl Page {
@@ -17,10 +22,111 @@ impl Page {
             document,
             metadata,
             image_ctx: Arc::new(Mutex::new(image_ctx)),
+            style_invalidations: StyleInvalidationSet::new(),
         }
     }
 
     pub fn image_context(&self) -> Arc<Mutex<ImageContext>> {
         Arc::clone(&self.image_ctx)
     }
+
+    /// Sets an attribute of an element in the document, invalidating the style of the element.
+    ///
+    /// # Returns
+    /// `false` if the node does not exist or is not an element.
+    pub fn set_attribute(&mut self, node_id: NodeId, name: &str, value: impl Into<String>) -> bool {
+        let Some(element) = self
+            .document
+            .dom_mut()
+            .nodes
+            .get_mut(*node_id)
+            .and_then(|node| node.data.as_element_mut())
+        else {
+            return false;
+        };
+
+        element.set_attribute(name, value);
+        self.style_invalidations.insert(node_id);
+
+        true
+    }
+
+    /// Removes an attribute of an element and records the element for style invalidation.
+    ///
+    /// # Returns
+    /// Whether the attribute was present and removed.
+    pub fn remove_attribute(&mut self, node_id: NodeId, name: &str) -> bool {
+        let removed = self
+            .document
+            .dom_mut()
+            .nodes
+            .get_mut(*node_id)
+            .and_then(|node| node.data.as_element_mut())
+            .is_some_and(|element| element.remove_attribute(name));
+
+        if removed {
+            self.style_invalidations.insert(node_id);
+        }
+
+        removed
+    }
+
+    /// Applies the changes the engine made to its copy of the document, such as by running event listeners,
+    /// invalidating the style of the changed nodes.
+    ///
+    /// # Returns
+    /// Whether nodes were added, moved, or removed, or `None` if the changes could not be applied because the copies
+    /// of the document are out of sync.
+    pub fn apply_changes(&mut self, changes: Vec<DomNode>) -> Option<bool> {
+        let dom = self.document.dom();
+        let structure_changed = changes.iter().any(|node| {
+            dom.get_node(&node.id)
+                .is_none_or(|existing| existing.parent != node.parent || existing.children != node.children)
+        });
+        let node_ids: Vec<NodeId> = changes.iter().map(|node| node.id).collect();
+
+        if !self.document.dom_mut().apply_changes(changes) {
+            return None;
+        }
+
+        for node_id in node_ids {
+            self.style_invalidations.insert(node_id);
+        }
+
+        Some(structure_changed)
+    }
+
+    /// Applies a change of the hover state of the document, invalidating the style of the nodes entering and leaving
+    /// it that a `:hover` selector of the document may match.
+    pub fn apply_hover_change(&mut self, change: &HoverChange) {
+        self.document.dom_mut().apply_hover_change(change);
+
+        let dom = self.document.dom();
+        let hover_selectors = self.document.hover_selectors();
+        if hover_selectors.is_empty() {
+            return;
+        }
+
+        for node_id in change.nodes() {
+            if dom
+                .get_node(&node_id)
+                .and_then(|node| node.data.as_element())
+                .is_some_and(|element| hover_selectors.may_match(element))
+            {
+                self.style_invalidations.insert(node_id);
+            }
+        }
+    }
+
+    /// Moves the keyboard focus of the document, invalidating the style of the nodes losing and gaining it.
+    pub fn set_focused(&mut self, node_id: Option<NodeId>) {
+        for node_id in self.document.dom_mut().set_focused(node_id) {
+            self.style_invalidations.insert(node_id);
+        }
+    }
+
+    /// Takes the nodes mutated since the last call, leaving the set empty.
+    pub fn take_style_invalidations(&mut self) -> StyleInvalidationSet {
+        std::mem::take(&mut self.style_invalidations)
+    }
 }
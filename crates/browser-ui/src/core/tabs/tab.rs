@@ -1,14 +1,24 @@
-use std::{fmt::Display, ops::Deref, sync::MutexGuard};
+use std::{collections::HashSet, fmt::Display, ops::Deref, sync::MutexGuard, time::Instant};
 
-use browser_core::{Document, History, PageMetadata};
+use browser_core::{Document, History, LazyLoadQueue, PageMetadata};
 use browser_preferences::BrowserPreferences;
 use css_display::BoxTree;
-use css_style::{AbsoluteContext, StyleTree};
+use css_style::{AbsoluteContext, FontFace, LayoutInvalidationSet, StyleInvalidationSet, StyleTree, SystemPreferences};
 use css_values::color::Color;
+use html_dom::{NodeId, ValidationError};
 use iced::Size;
 use layout::{ImageContext, LayoutInput, LayoutTree, Rect, TextContext};
 
-use crate::core::{Devtools, Page, ScrollOffset};
+use crate::core::{Devtools, Page, ScrollOffset, SelectionState, TextInputController};
+
+/// A document loaded by a navigation, to be styled and laid out in a tab by [`Tab::resolve_page`].
+pub struct LoadedPage {
+    pub document: Document,
+    pub metadata: PageMetadata,
+
+    /// The scroll offset to restore, or `None` to show the page from the top.
+    pub scroll_offset: Option<ScrollOffset>,
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct TabId(usize);
@@ -38,6 +48,9 @@ impl Display for TabId {
 pub struct Tab {
     pub id: TabId,
 
+    /// The name of the browsing context of the tab, set when it was opened by a link targeting that name.
+    pub name: Option<String>,
+
     pub page: Option<Page>,
     pub devtools: Option<Devtools>,
 
@@ -48,12 +61,36 @@ pub struct Tab {
     pub scroll_offset: ScrollOffset,
 
     pub history: History,
+
+    /// Constraint validation errors from the last blocked form submission, shown as tooltips.
+    pub validation_errors: Vec<ValidationError>,
+
+    /// The `<input>` or `<textarea>` currently receiving keyboard input.
+    pub focused_input: Option<TextInputController>,
+
+    /// The `<select>` whose dropdown menu is open.
+    pub open_dropdown: Option<NodeId>,
+
+    /// The text selected on the page.
+    pub selection: SelectionState,
+
+    /// The frame of the spinner shown in the title of the tab while a navigation is in progress, `None` when the tab
+    /// is not loading.
+    pub loading: Option<usize>,
+
+    /// The `<img loading="lazy">` elements of the page whose images are loaded once they are scrolled into view.
+    pub lazy_images: LazyLoadQueue,
+
+    /// The `content-visibility: auto` elements whose skipped contents are being laid out by a background relayout,
+    /// so that scrolling does not start another one for them before it completes.
+    pub revealing: HashSet<NodeId>,
 }
 
 impl Tab {
     pub fn new(id: TabId) -> Self {
         Self {
             id,
+            name: None,
             page: None,
             devtools: None,
             style_tree: None,
@@ -61,14 +98,104 @@ impl Tab {
             layout_generation: 0,
             scroll_offset: ScrollOffset::default(),
             history: History::new(),
+            validation_errors: Vec::new(),
+            focused_input: None,
+            open_dropdown: None,
+            selection: SelectionState::default(),
+            loading: None,
+            lazy_images: LazyLoadQueue::default(),
+            revealing: HashSet::new(),
         }
     }
 
+    /// The viewport the page is laid out in, the window size, with the width requested by its
+    /// `<meta name="viewport">` while a mobile device is emulated. Pages are drawn without zoom, so the requested width
+    /// is not applied otherwise, as it only fills the window together with its scale.
+    pub fn layout_viewport(viewport: Size, metadata: &PageMetadata, preferences: &BrowserPreferences) -> Size {
+        match metadata.viewport {
+            Some(meta) if preferences.emulate_mobile() => {
+                Size::new(meta.layout_width(viewport.width, viewport.height), viewport.height)
+            }
+            _ => viewport,
+        }
+    }
+
+    /// Styles and lays out a document. When its stylesheets contain `@container` rules, the document is styled again
+    /// against the containers measured by the first layout and laid out once more. The second layout is not measured
+    /// again, so styles selected by a query can not make the layout oscillate.
+    ///
+    /// The sizes remembered by the `previous` layout of the document are kept, so that `content-visibility: auto`
+    /// elements that were shown keep their size while their contents are skipped.
+    fn style_and_layout(
+        preferences: &BrowserPreferences,
+        absolute_ctx: &AbsoluteContext,
+        document: &Document,
+        text_context: &mut TextContext,
+        image_ctx: &ImageContext,
+        viewport: Rect,
+        previous: Option<&LayoutTree>,
+    ) -> (StyleTree, LayoutTree) {
+        let layout = |style_tree: &StyleTree, text_context: &mut TextContext| {
+            let box_tree = BoxTree::new(document.dom(), style_tree);
+            LayoutTree::compute_layout_with_remembered_sizes(
+                &mut LayoutInput {
+                    dom: document.dom(),
+                    box_tree: &box_tree,
+                    text: text_context,
+                    image: image_ctx,
+                },
+                viewport,
+                previous
+                    .map(|layout_tree| layout_tree.remembered_sizes.clone())
+                    .unwrap_or_default(),
+            )
+        };
+
+        let style_tree = StyleTree::build(Some(preferences), absolute_ctx, document.dom(), document.stylesheets());
+        let layout_tree = layout(&style_tree, text_context);
+
+        if !style_tree.has_container_queries() {
+            return (style_tree, layout_tree);
+        }
+
+        let containers = layout_tree.container_sizes(&style_tree);
+        if containers.is_empty() {
+            return (style_tree, layout_tree);
+        }
+
+        let style_tree = StyleTree::build_with_containers(
+            Some(preferences),
+            absolute_ctx,
+            document.dom(),
+            document.stylesheets(),
+            containers,
+        );
+        let layout_tree = layout(&style_tree, text_context);
+
+        (style_tree, layout_tree)
+    }
+
+    /// The area of the page shown in a window of the given size, the layout viewport moved by the scroll offset.
+    pub fn visible_area(&self, viewport: Size, preferences: &BrowserPreferences) -> Rect {
+        let viewport = self
+            .page
+            .as_ref()
+            .map_or(viewport, |page_ctx| Self::layout_viewport(viewport, &page_ctx.metadata, preferences));
+
+        Rect::new(
+            f64::from(self.scroll_offset.x),
+            f64::from(self.scroll_offset.y),
+            f64::from(viewport.width),
+            f64::from(viewport.height),
+        )
+    }
+
     pub fn resize_current_page(
         &mut self,
         viewport: Size,
         text_context: &mut MutexGuard<'_, TextContext>,
         preferences: &BrowserPreferences,
+        system_preferences: SystemPreferences,
     ) {
         let Some(page_ctx) = self.page.as_ref() else {
             return;
@@ -80,74 +207,131 @@ impl Tab {
             return;
         };
 
+        let viewport = Self::layout_viewport(viewport, metadata, preferences);
         let absolute_ctx = AbsoluteContext {
             root_font_size: 16.0,
             viewport_width: f64::from(viewport.width),
             viewport_height: f64::from(viewport.height),
-            theme_category: preferences.theme().category,
+            system_preferences,
             document_url: &metadata.url,
             root_line_height_multiplier: 1.2,
             root_color: Color::BLACK,
         };
 
-        let style_tree = StyleTree::build(Some(preferences), &absolute_ctx, page.dom(), page.stylesheets());
-        let box_tree = BoxTree::new(page.dom(), &style_tree);
-        let layout_tree = {
+        text_context.set_font_faces(FontFace::collect(page.stylesheets()));
+        let (style_tree, layout_tree) = {
             let image_ctx = page_ctx.image_context();
             let image_ctx = image_ctx.lock().unwrap();
-            LayoutTree::compute_layout(
-                &mut LayoutInput {
-                    dom: page.dom(),
-                    box_tree: &box_tree,
-                    text: text_context,
-                    image: &image_ctx,
-                },
+            Self::style_and_layout(
+                preferences,
+                &absolute_ctx,
+                page,
+                text_context,
+                &image_ctx,
                 Rect::new(0.0, 0.0, f64::from(viewport.width), f64::from(viewport.height)),
+                self.layout_tree.as_ref(),
             )
         };
 
         self.style_tree = Some(style_tree);
         self.layout_tree = Some(layout_tree);
+        self.revealing.clear();
     }
 
     pub fn resolve_page(
         &mut self,
         viewport: Size,
         text_context: &mut MutexGuard<'_, TextContext>,
-        document: Document,
-        metadata: PageMetadata,
+        loaded: LoadedPage,
         preferences: &BrowserPreferences,
-        scroll_offset: Option<ScrollOffset>,
+        system_preferences: SystemPreferences,
     ) {
+        let LoadedPage {
+            document,
+            mut metadata,
+            scroll_offset,
+        } = loaded;
+        let viewport = Self::layout_viewport(viewport, &metadata, preferences);
         let absolute_ctx = AbsoluteContext {
             root_font_size: 16.0,
             viewport_width: f64::from(viewport.width),
             viewport_height: f64::from(viewport.height) - 87.0 - 60.0,
-            theme_category: preferences.theme().category,
+            system_preferences,
             document_url: &metadata.url,
             root_line_height_multiplier: 1.2,
             root_color: Color::BLACK,
         };
 
-        let style_tree = StyleTree::build(Some(preferences), &absolute_ctx, document.dom(), document.stylesheets());
-        let box_tree = BoxTree::new(document.dom(), &style_tree);
         let image_ctx = ImageContext::new();
-        let layout_tree = LayoutTree::compute_layout(
-            &mut LayoutInput {
-                dom: document.dom(),
-                box_tree: &box_tree,
-                text: text_context,
-                image: &image_ctx,
-            },
+        text_context.set_font_faces(FontFace::collect(document.stylesheets()));
+        let (style_tree, layout_tree) = Self::style_and_layout(
+            preferences,
+            &absolute_ctx,
+            &document,
+            text_context,
+            &image_ctx,
             Rect::new(0.0, 0.0, f64::from(viewport.width), f64::from(viewport.height) - 87.0 - 60.0),
+            None,
         );
 
+        metadata
+            .timings
+            .first_paint
+            .get_or_insert_with(Instant::now);
+
         self.style_tree = Some(style_tree);
         self.layout_tree = Some(layout_tree);
         self.page = Some(Page::new(document, metadata, image_ctx));
         self.scroll_offset = scroll_offset.unwrap_or_default();
     }
 
+    /// Recomputes the styles of the mutated nodes and their descendants, keeping the rest of the style tree.
+    ///
+    /// # Returns
+    /// The nodes whose computed style changed, split by whether they need layout or only repaint.
+    pub fn recompute_styles(
+        &mut self,
+        viewport: Size,
+        preferences: &BrowserPreferences,
+        system_preferences: SystemPreferences,
+        dirty: &StyleInvalidationSet,
+    ) -> LayoutInvalidationSet {
+        let (Some(page_ctx), Some(style_tree)) = (self.page.as_ref(), self.style_tree.as_mut()) else {
+            return LayoutInvalidationSet::new();
+        };
+
+        let viewport = Self::layout_viewport(viewport, &page_ctx.metadata, preferences);
+        let absolute_ctx = AbsoluteContext {
+            root_font_size: 16.0,
+            viewport_width: f64::from(viewport.width),
+            viewport_height: f64::from(viewport.height),
+            system_preferences,
+            document_url: &page_ctx.metadata.url,
+            root_line_height_multiplier: 1.2,
+            root_color: Color::BLACK,
+        };
+
+        style_tree.recompute(
+            Some(preferences),
+            &absolute_ctx,
+            page_ctx.document.dom(),
+            page_ctx.document.stylesheets(),
+            dirty,
+        )
+    }
+
+    /// Applies the paint-only changes of a style recomputation to the layout tree, the nodes needing layout are
+    /// left to a relayout.
+    pub fn repaint(&mut self, dirty: &LayoutInvalidationSet) {
+        let (Some(page_ctx), Some(style_tree), Some(layout_tree)) =
+            (self.page.as_ref(), self.style_tree.as_ref(), self.layout_tree.as_mut())
+        else {
+            return;
+        };
+
+        layout_tree.repaint(page_ctx.document.dom(), style_tree, dirty);
+    }
+
     /// Prepare the tab for a brand-new navigation.  Clears stale image
     /// metadata and pending state, and increments the layout generation so
     /// that any in-flight background relayout from the previous page is
@@ -159,6 +343,11 @@ impl Tab {
             image_ctx.clear();
         }
 
+        self.validation_errors.clear();
+        self.focused_input = None;
+        self.open_dropdown = None;
+        self.selection = SelectionState::default();
+        self.revealing.clear();
         self.layout_generation += 1;
     }
 }
@@ -1,3 +1,12 @@
+use std::sync::Arc;
+
+use browser_core::errors::StorageError;
+use cookies::errors::CookieError;
+use css_parser::errors::CssParseError;
+use html_parser::errors::HtmlParseError;
+use http_fetch::errors::NetworkError;
+use layout::errors::LayoutError;
+use renderer::errors::RenderError;
 use thiserror::Error;
 
 /// Subsystem errors sits at the boundary of a subsystem and the engine.
@@ -7,13 +16,38 @@ pub enum UiError {
     Runtime(#[from] iced::Error),
 }
 
+/// The errors of the subsystems surfaced to the browser UI.
 #[derive(Error, Debug, Clone)]
 pub enum BrowserError {
     #[error(transparent)]
     Tab(#[from] TabError),
 
-    #[error("unable to load image: {0}")]
-    ImageLoad(String),
+    #[error(transparent)]
+    HtmlParse(#[from] HtmlParseError),
+
+    #[error(transparent)]
+    CssParse(#[from] CssParseError),
+
+    #[error(transparent)]
+    Layout(#[from] LayoutError),
+
+    #[error(transparent)]
+    Render(#[from] RenderError),
+
+    #[error(transparent)]
+    Network(#[from] NetworkError),
+
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    #[error(transparent)]
+    Cookie(Arc<CookieError>),
+}
+
+impl From<CookieError> for BrowserError {
+    fn from(error: CookieError) -> Self {
+        Self::Cookie(Arc::new(error))
+    }
 }
 
 #[derive(Error, Debug, Clone)]
@@ -1,10 +1,15 @@
+use browser_core::SessionEntry;
+use browser_preferences::BrowserPreferences;
+use css_style::InputDevice;
+use css_values::media::ColorScheme;
 use html_dom::NodeId;
 use iced::{Size, Task, window::Id};
 use layout::{LayoutImage, LayoutTree};
 use tracing::error;
+use url::Url;
 
 use crate::{
-    core::{Application, Tab, TabId},
+    core::{Application, SelectionState, Tab, TabId, TextInputAction},
     errors::BrowserError,
     events::{Event, EventHandler},
     windows::browser::window::BrowserWindow,
@@ -53,12 +58,79 @@ pub enum BrowserEvent {
         image_data: LayoutImage,
     },
 
+    /// A page finished loading in a tab, carrying the state of its history and the page now shown, so that the
+    /// address bar and navigation buttons can follow it.
+    NavigationStateChanged {
+        window_id: Id,
+        tab_id: TabId,
+        can_go_back: bool,
+        can_go_forward: bool,
+        current_url: Url,
+        current_title: Option<String>,
+    },
+
+    /// A download is in progress and its progress is due to be refreshed.
+    PollDownloads(Id),
+
+    /// The browser crashed the last time it ran, carrying the tabs that were open, which are offered for restoring.
+    CrashRecoveryAvailable(Vec<SessionEntry>),
+
+    /// The user declined to restore the tabs of the crashed session.
+    DismissCrashRecovery,
+
+    /// The loading spinner of a tab is due to show its next frame.
+    LoadingFrameElapsed(Id, TabId),
+
+    /// A web font of a tab reached the end of its `font-display` block or swap period.
+    FontTimelineElapsed(Id, TabId),
+
+    /// No source of a web font of a tab could be loaded, carrying its family name.
+    FontFailed(Id, TabId, String),
+
     /// A background relayout has completed.  Carries the tab id, the layout
     /// generation the work was started with, and the resulting layout tree.
     /// If the generation no longer matches the tab's current generation the
     /// result is stale (e.g. the user navigated away) and should be discarded.
     RelayoutComplete(Id, TabId, u64, LayoutTree),
 
+    /// Move keyboard focus to a text control, or remove focus when `None`.
+    FocusInput(Id, Option<NodeId>),
+
+    /// Apply a keyboard edit to the focused text control.
+    EditInput(Id, TextInputAction),
+
+    /// The pointer moved onto other elements, the `added` nodes now match `:hover` and the `removed` ones no longer do.
+    HoverStateChanged {
+        window_id: Id,
+        added: Vec<NodeId>,
+        removed: Vec<NodeId>,
+    },
+
+    /// The text selected on the page changed, by dragging the pointer over it or selecting all.
+    SelectText(Id, SelectionState),
+
+    /// The content of the system clipboard was read for a paste, to be inserted into the focused text control.
+    ClipboardContent {
+        window_id: Id,
+        mime_type: String,
+        data: Vec<u8>,
+    },
+
+    /// Open the dropdown menu of a `<select>`, or close the open one when `None`.
+    ToggleDropdown(Id, Option<NodeId>),
+
+    /// The color scheme of the operating system changed, or was detected at startup.
+    SystemThemeChanged(ColorScheme),
+
+    /// The user interacted with a different kind of input device, such as touching the screen after using the mouse.
+    InputDeviceChanged(InputDevice),
+
+    /// The number of device pixels per CSS pixel of a window changed, such as when it was moved to another display.
+    ScaleFactorChanged(f64),
+
+    /// The preferences file was written, carrying the reloaded preferences.
+    PreferencesChanged(Box<BrowserPreferences>),
+
     /// An error occurred during a browser operation, with the provided error message.
     Error(BrowserError),
 }
@@ -84,11 +156,65 @@ impl EventHandler<BrowserEvent> for Application {
                 node_ids,
                 url,
                 image_data,
-            } => Tab::on_image_decoded(self, window_id, tab_id, node_ids, url, image_data),
+            } => Tab::on_image_decoded(self, window_id, tab_id, node_ids, &url, image_data),
+
+            BrowserEvent::NavigationStateChanged {
+                window_id,
+                tab_id,
+                can_go_back,
+                can_go_forward,
+                current_url,
+                current_title,
+            } => BrowserWindow::on_navigation_state_changed(
+                self,
+                window_id,
+                tab_id,
+                can_go_back,
+                can_go_forward,
+                &current_url,
+                current_title.as_deref(),
+            ),
+            BrowserEvent::PollDownloads(window_id) => BrowserWindow::poll_downloads(self, window_id),
+            BrowserEvent::CrashRecoveryAvailable(tabs) => BrowserWindow::on_crash_recovery_available(self, tabs),
+            BrowserEvent::DismissCrashRecovery => BrowserWindow::dismiss_crash_recovery(self),
+            BrowserEvent::LoadingFrameElapsed(window_id, tab_id) => {
+                Tab::on_loading_frame_elapsed(self, window_id, tab_id)
+            }
+            BrowserEvent::FontTimelineElapsed(window_id, tab_id) => {
+                Tab::on_font_timeline_elapsed(self, window_id, tab_id)
+            }
+            BrowserEvent::FontFailed(window_id, tab_id, family) => {
+                Tab::on_font_failed(self, window_id, tab_id, &family)
+            }
+
+            BrowserEvent::FocusInput(window_id, node_id) => Tab::focus_input(self, window_id, node_id),
+            BrowserEvent::EditInput(window_id, action) => Tab::edit_input(self, window_id, action),
+            BrowserEvent::HoverStateChanged {
+                window_id,
+                added,
+                removed,
+            } => Tab::on_hover_state_changed(self, window_id, added, removed),
+            BrowserEvent::SelectText(window_id, selection) => Tab::select_text(self, window_id, selection),
+            BrowserEvent::ClipboardContent {
+                window_id,
+                mime_type,
+                data,
+            } => Tab::on_clipboard_content(self, window_id, &mime_type, &data),
+            BrowserEvent::ToggleDropdown(window_id, node_id) => Tab::toggle_dropdown(self, window_id, node_id),
 
             BrowserEvent::RelayoutComplete(window_id, tab_id, generation, layout_tree) => {
                 Tab::on_relayout(self, window_id, tab_id, generation, layout_tree)
             }
+            BrowserEvent::SystemThemeChanged(color_scheme) => {
+                BrowserWindow::on_system_theme_changed(self, color_scheme)
+            }
+            BrowserEvent::InputDeviceChanged(input_device) => {
+                BrowserWindow::on_input_device_changed(self, input_device)
+            }
+            BrowserEvent::ScaleFactorChanged(scale_factor) => {
+                BrowserWindow::on_scale_factor_changed(self, scale_factor)
+            }
+            BrowserEvent::PreferencesChanged(preferences) => BrowserWindow::on_preferences_changed(self, *preferences),
             BrowserEvent::Error(error) => {
                 error!(%error, "Browser error occurred");
                 Task::none()
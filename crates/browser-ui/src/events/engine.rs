@@ -1,11 +1,15 @@
-use browser_core::EngineResponse;
+use std::{path::PathBuf, time::Instant};
+
+use browser_core::{EngineResponse, LinkRel, LinkTarget, MemoryUsage};
+use html_dom::NodeId;
 use iced::{Task, window::Id};
-use tracing::error;
+use layout::LayoutTree;
+use tracing::{debug, info};
 
 use crate::{
     core::{Application, Tab, TabId},
     events::{Event, EventHandler},
-    windows::devtools::window::DevtoolsWindow,
+    windows::{browser::window::BrowserWindow, devtools::window::DevtoolsWindow},
 };
 
 /// Represents requests that can be sent to the browser kernel from the UI.
@@ -13,12 +17,84 @@ use crate::{
 pub enum EngineRequest {
     /// Navigate to the specified URL.
     NavigateTo(Id, String),
+
+    /// Follow a link clicked on the page of the active tab, with its `href`, `target` and `rel` attributes.
+    OpenLink(Id, String, LinkTarget, LinkRel),
+
+    /// Validate and submit the form with the specified DOM node ID in the active tab.
+    SubmitForm(Id, NodeId),
+
+    /// Resize the element with the specified DOM node ID in the active tab to a new width and height, `None` for a
+    /// dimension that is not resized.
+    ResizeElement(Id, NodeId, Option<f64>, Option<f64>),
+
+    /// Set the value of the control with the specified DOM node ID in the active tab, e.g. while the thumb of a range
+    /// slider is dragged.
+    SetInputValue(Id, NodeId, String),
+
+    /// Fire the DOM event of the specified type at the element with the specified DOM node ID in the active tab.
+    DispatchEvent(Id, NodeId, String),
+
+    /// Toggle the checkbox with the specified DOM node ID in the active tab.
+    ToggleCheckbox(Id, NodeId),
+
+    /// Check the radio button with the specified group name and DOM node ID in the active tab.
+    SelectRadio(Id, String, NodeId),
+
+    /// Open or close the `<details>` with the specified DOM node ID in the active tab, whose summary was clicked.
+    ToggleDetails(Id, NodeId),
+
+    /// Select the option at the specified index of the `<select>` with the specified DOM node ID in the active tab.
+    SelectOption(Id, NodeId, usize),
+
+    /// Save the page shown in the active tab to the download directory.
+    SavePage(Id),
+
+    /// Reopen the tabs of the session that was open when the browser last crashed, in new tabs of the window.
+    RestoreCrashSession(Id),
+
+    /// Copy the selected text of the focused text control in the active tab, or the text selected on the page, to the
+    /// system clipboard.
+    CopySelection(Id),
+
+    /// Paste the system clipboard into the focused text control in the active tab, triggered by a user gesture at the
+    /// specified instant.
+    PasteClipboard(Id, Instant),
+
+    /// Load the unpacked extension in the specified directory.
+    LoadExtension(Id, PathBuf),
 }
 
 impl EventHandler<EngineRequest> for Application {
     fn handle(&mut self, event: EngineRequest) -> Task<Event> {
         match event {
             EngineRequest::NavigateTo(window_id, url) => Tab::navigate_to_url(self, window_id, url),
+            EngineRequest::OpenLink(window_id, href, target, rel) => Tab::open_link(self, window_id, href, target, rel),
+            EngineRequest::SubmitForm(window_id, form_id) => Tab::submit_form(self, window_id, form_id),
+            EngineRequest::ResizeElement(window_id, node_id, new_width, new_height) => {
+                Tab::resize_element(self, window_id, node_id, new_width, new_height)
+            }
+            EngineRequest::SetInputValue(window_id, node_id, value) => {
+                Tab::set_input_value(self, window_id, node_id, value)
+            }
+            EngineRequest::DispatchEvent(window_id, node_id, event_type) => {
+                Tab::dispatch_event(self, window_id, node_id, event_type)
+            }
+            EngineRequest::ToggleCheckbox(window_id, node_id) => Tab::toggle_checkbox(self, window_id, node_id),
+            EngineRequest::SelectRadio(window_id, group_name, node_id) => {
+                Tab::select_radio(self, window_id, group_name, node_id)
+            }
+            EngineRequest::ToggleDetails(window_id, node_id) => Tab::toggle_details(self, window_id, node_id),
+            EngineRequest::SelectOption(window_id, select_node_id, option_index) => {
+                Tab::select_option(self, window_id, select_node_id, option_index)
+            }
+            EngineRequest::SavePage(window_id) => Tab::save_page(self, window_id),
+            EngineRequest::RestoreCrashSession(window_id) => Tab::restore_crash_session(self, window_id),
+            EngineRequest::CopySelection(window_id) => Tab::copy_selection(self, window_id),
+            EngineRequest::PasteClipboard(window_id, user_gesture) => {
+                Tab::paste_clipboard(self, window_id, user_gesture)
+            }
+            EngineRequest::LoadExtension(window_id, path) => BrowserWindow::load_extension(self, window_id, path),
         }
     }
 }
@@ -35,10 +111,7 @@ impl EventHandler<(Id, TabId, Box<EngineResponse>)> for Application {
             EngineResponse::NavigateSuccess(page, metadata, navigation_type) => {
                 Tab::on_navigation_success(self, window_id, tab_id, page, metadata, navigation_type)
             }
-            EngineResponse::NavigateError(error) => {
-                error!(%error, "Navigation failed");
-                Task::none()
-            }
+            EngineResponse::NavigateError(error) => Tab::on_navigation_error(self, window_id, tab_id, &error),
 
             EngineResponse::ImageFetched {
                 node_ids,
@@ -47,10 +120,121 @@ impl EventHandler<(Id, TabId, Box<EngineResponse>)> for Application {
                 data,
             } => Tab::on_image_loaded(self, window_id, tab_id, node_ids, content_type, url, data),
 
-            EngineResponse::Error(error) => {
-                error!(%error, "Engine command failed");
+            EngineResponse::FaviconFetched { page_url, favicon } => {
+                Tab::on_favicon_loaded(self, window_id, tab_id, &page_url, favicon)
+            }
+
+            EngineResponse::FontFetched { family, url, data } => {
+                Tab::on_font_loaded(self, window_id, tab_id, &family, &url, data)
+            }
+
+            EngineResponse::CanvasRendered {
+                node_id,
+                width,
+                height,
+                rgba,
+            } => Tab::on_canvas_rendered(self, window_id, tab_id, node_id, width, height, rgba),
+
+            EngineResponse::InputValueUpdated { node_id, value } => {
+                Tab::on_input_value_updated(self, window_id, tab_id, node_id, value)
+            }
+
+            EngineResponse::EventDispatched {
+                node_id,
+                event_type,
+                changes,
+            } => {
+                debug!(%node_id, event_type, changes = changes.len(), "Event dispatched");
+                Tab::on_event_dispatched(self, window_id, tab_id, changes)
+            }
+
+            EngineResponse::ElementResized { node_id, style } => {
+                Tab::on_element_resized(self, window_id, tab_id, node_id, style)
+            }
+
+            EngineResponse::CheckedChanged { checked, unchecked } => {
+                Tab::on_checked_changed(self, window_id, tab_id, &checked, &unchecked)
+            }
+
+            EngineResponse::DetailsToggled { opened, closed } => {
+                Tab::on_details_toggled(self, window_id, tab_id, &opened, &closed)
+            }
+
+            EngineResponse::OptionSelected {
+                select_node_id,
+                option_node_ids,
+                selected_node_id,
+                value,
+            } => Tab::on_option_selected(
+                self,
+                window_id,
+                tab_id,
+                select_node_id,
+                &option_node_ids,
+                selected_node_id,
+                value,
+            ),
+
+            EngineResponse::FormValidationFailed(errors) => {
+                Tab::on_form_validation_failed(self, window_id, tab_id, errors)
+            }
+
+            EngineResponse::ClipboardWrite { mime_type, data } => Tab::on_clipboard_write(&mime_type, data),
+            EngineResponse::ClipboardReadAllowed { mime_type } => Tab::on_clipboard_read_allowed(window_id, mime_type),
+
+            EngineResponse::CookieList(cookies) => {
+                debug!(count = cookies.len(), "Received cookie list");
+                Task::none()
+            }
+            EngineResponse::CookiesDeleted(count) => {
+                debug!(count, "Deleted cookies");
                 Task::none()
             }
+            EngineResponse::SiteDataCleared {
+                cookies,
+                cache_entries,
+            } => {
+                debug!(cookies, cache_entries, "Cleared site data");
+                Task::none()
+            }
+            EngineResponse::DownloadStarted(download) => Tab::on_download_started(self, window_id, tab_id, download),
+            EngineResponse::DownloadList(downloads) => BrowserWindow::on_download_list(self, window_id, downloads),
+            EngineResponse::SessionSaved => Task::none(),
+            EngineResponse::CrashSessionRestored(tabs) => Tab::on_crash_session_restored(self, window_id, tabs),
+            EngineResponse::MemoryReport(usage) => {
+                // The engine only measures its own state, the documents shown, layout trees and GPU caches are held here.
+                let tabs = || {
+                    self.browser_windows
+                        .values()
+                        .flat_map(|ctx| ctx.tab_manager.tabs())
+                };
+
+                let usage = MemoryUsage {
+                    dom_bytes: tabs()
+                        .filter_map(|tab| tab.page.as_ref())
+                        .map(|page| page.document.dom().estimated_size_bytes())
+                        .sum::<usize>()
+                        + usage.dom_bytes,
+                    layout_bytes: tabs()
+                        .filter_map(|tab| tab.layout_tree.as_ref())
+                        .map(LayoutTree::estimated_size_bytes)
+                        .sum(),
+                    image_cache_bytes: self.gpu_memory.image_cache_bytes(),
+                    glyph_atlas_bytes: self.gpu_memory.glyph_atlas_bytes(),
+                    ..usage
+                }
+                .with_total();
+
+                debug!(?usage, "Received memory report");
+                self.memory_usage = Some(usage);
+                Task::none()
+            }
+            EngineResponse::ExtensionLoaded(extension) => {
+                info!(name = extension.manifest.name, version = extension.manifest.version, "Extension loaded");
+                Task::none()
+            }
+
+            EngineResponse::Error(error) => Tab::on_engine_error(self, window_id, tab_id, &error),
         }
     }
 }
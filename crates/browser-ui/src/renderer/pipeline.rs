@@ -1,3 +1,5 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use iced::{
     advanced::graphics::text::cosmic_text::FontSystem,
     wgpu::{self},
@@ -17,6 +19,38 @@ pub struct HtmlPipeline {
     pub gpu_image_cache: GpuImageCache,
 }
 
+/// The GPU memory used by the caches of the [`HtmlPipeline`], as of the last frame it prepared.
+///
+/// The pipeline lives with the renderer of iced, so its primitives report the sizes here for the memory report of the
+/// application.
+#[derive(Debug, Default)]
+pub struct GpuMemory {
+    image_cache_bytes: AtomicUsize,
+    glyph_atlas_bytes: AtomicUsize,
+}
+
+impl GpuMemory {
+    /// Records the current size of the caches of the pipeline.
+    pub fn record(&self, pipeline: &HtmlPipeline) {
+        self.image_cache_bytes
+            .store(pipeline.gpu_image_cache.estimated_size_bytes(), Ordering::Relaxed);
+        self.glyph_atlas_bytes
+            .store(pipeline.glyph_atlas.estimated_size_bytes(), Ordering::Relaxed);
+    }
+
+    /// The decoded images uploaded to the GPU.
+    #[must_use]
+    pub fn image_cache_bytes(&self) -> usize {
+        self.image_cache_bytes.load(Ordering::Relaxed)
+    }
+
+    /// The glyph atlas texture and its lookup table.
+    #[must_use]
+    pub fn glyph_atlas_bytes(&self) -> usize {
+        self.glyph_atlas_bytes.load(Ordering::Relaxed)
+    }
+}
+
 impl Pipeline for HtmlPipeline {
     fn new(device: &wgpu::Device, _queue: &wgpu::Queue, format: wgpu::TextureFormat) -> Self
     where
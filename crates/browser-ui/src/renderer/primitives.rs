@@ -1,44 +1,42 @@
+use std::sync::Arc;
+
 use iced::{
     Rectangle,
     wgpu::{self, RenderPass},
     widget::shader::{Primitive, Viewport},
 };
 use layout::{Color4f, Rect};
-use renderer::{ImageRenderInfo, RenderRect, RenderTri, TextBlockInfo};
+use renderer::{ImageRenderInfo, RenderShape, TextBlockInfo};
 
-use crate::{core::ScrollOffset, renderer::pipeline::HtmlPipeline};
+use crate::{
+    core::ScrollOffset,
+    renderer::pipeline::{GpuMemory, HtmlPipeline},
+};
 
 /// The primitive that carries render data from `draw()` to `prepare()`/`render()`
 #[derive(Debug, Clone)]
 pub struct HtmlPrimitive {
-    pub rects: Vec<RenderRect>,
-    pub tris: Vec<RenderTri>,
+    /// The rectangles and triangles of the frame in paint order, drawn in that order by the rect pipeline.
+    pub shapes: Vec<RenderShape>,
     pub text_blocks: Vec<TextBlockInfo>,
     pub images: Vec<ImageRenderInfo>,
     pub scroll_offset: ScrollOffset,
+
+    /// Where the sizes of the GPU caches are reported once the frame is prepared.
+    pub gpu_memory: Option<Arc<GpuMemory>>,
 }
 
 impl HtmlPrimitive {
     pub const fn new(scroll_offset: ScrollOffset) -> Self {
         Self {
-            rects: Vec::new(),
-            tris: Vec::new(),
+            shapes: Vec::new(),
             text_blocks: Vec::new(),
             images: Vec::new(),
             scroll_offset,
+            gpu_memory: None,
         }
     }
 
-    /// Add a rectangle to be rendered
-    pub fn push_rect(&mut self, rect: Rect, background: Color4f) {
-        self.rects.push(RenderRect { rect, background });
-    }
-
-    /// Add a triangle to be rendered
-    pub fn push_triangle(&mut self, p0: [f32; 2], p1: [f32; 2], p2: [f32; 2], color: Color4f) {
-        self.tris.push(RenderTri { p0, p1, p2, color });
-    }
-
     /// Add a text block to be rendered
     pub fn push_text_block(&mut self, text_block: TextBlockInfo) {
         self.text_blocks.push(text_block);
@@ -76,32 +74,32 @@ impl Primitive for HtmlPrimitive {
         pipeline.image_pipeline.clear();
         pipeline.gpu_image_cache.clear();
 
-        for render_rect in &self.rects {
-            let offset_rect = Rect::new(
-                render_rect.rect.x as f32 - self.scroll_offset.x,
-                render_rect.rect.y as f32 - self.scroll_offset.y,
-                render_rect.rect.width as f32,
-                render_rect.rect.height as f32,
-            );
-            pipeline
-                .rect_pipeline
-                .push_quad(offset_rect, render_rect.background);
-        }
-
-        for tri in &self.tris {
-            let p0 = [
-                tri.p0[0] - self.scroll_offset.x,
-                tri.p0[1] - self.scroll_offset.y,
-            ];
-            let p1 = [
-                tri.p1[0] - self.scroll_offset.x,
-                tri.p1[1] - self.scroll_offset.y,
-            ];
-            let p2 = [
-                tri.p2[0] - self.scroll_offset.x,
-                tri.p2[1] - self.scroll_offset.y,
-            ];
-            pipeline.rect_pipeline.push_triangle(p0, p1, p2, tri.color);
+        let offset = |point: [f32; 2]| {
+            [
+                point[0] - self.scroll_offset.x,
+                point[1] - self.scroll_offset.y,
+            ]
+        };
+
+        for shape in &self.shapes {
+            match shape {
+                RenderShape::Rect(render_rect) => {
+                    let offset_rect = Rect::new(
+                        render_rect.rect.x as f32 - self.scroll_offset.x,
+                        render_rect.rect.y as f32 - self.scroll_offset.y,
+                        render_rect.rect.width as f32,
+                        render_rect.rect.height as f32,
+                    );
+                    pipeline
+                        .rect_pipeline
+                        .push_rounded_quad(offset_rect, render_rect.radii, render_rect.background);
+                }
+                RenderShape::Tri(tri) => {
+                    pipeline
+                        .rect_pipeline
+                        .push_triangle(offset(tri.p0), offset(tri.p1), offset(tri.p2), tri.color);
+                }
+            }
         }
 
         let (atlas_width, atlas_height) = pipeline.glyph_atlas.size();
@@ -134,26 +132,37 @@ impl Primitive for HtmlPrimitive {
         }
 
         for image_info in &self.images {
-            pipeline
-                .gpu_image_cache
-                .ensure_uploaded(device, queue, &image_info.node_id, &image_info.data);
+            let object_rect = image_info.object_rect();
+            pipeline.gpu_image_cache.ensure_uploaded(
+                device,
+                queue,
+                image_info,
+                (object_rect.width.ceil() as u32, object_rect.height.ceil() as u32),
+            );
 
+            // `object-fit` crops the image by drawing only part of its texture, and letterboxes it by drawing it in
+            // a smaller rectangle.
+            let (dest, uv) = image_info.placement();
             let screen_rect = Rect::new(
-                image_info.screen_rect.x as f32 - self.scroll_offset.x,
-                image_info.screen_rect.y as f32 - self.scroll_offset.y,
-                image_info.screen_rect.width as f32,
-                image_info.screen_rect.height as f32,
+                dest.x as f32 - self.scroll_offset.x,
+                dest.y as f32 - self.scroll_offset.y,
+                dest.width as f32,
+                dest.height as f32,
             );
-            let full_uv = Rect::new(0.0, 0.0, 1.0, 1.0);
+            let uv_rect = Rect::new(uv.x as f32, uv.y as f32, uv.width as f32, uv.height as f32);
 
             pipeline
                 .image_pipeline
-                .push_quad(screen_rect, full_uv, Color4f::WHITE);
+                .push_quad(screen_rect, uv_rect, Color4f::WHITE);
         }
 
         pipeline.rect_pipeline.flush(queue);
         pipeline.text_pipeline.flush(queue);
         pipeline.image_pipeline.flush(queue);
+
+        if let Some(gpu_memory) = &self.gpu_memory {
+            gpu_memory.record(pipeline);
+        }
     }
 
     fn draw(&self, pipeline: &Self::Pipeline, render_pass: &mut RenderPass<'_>) -> bool {
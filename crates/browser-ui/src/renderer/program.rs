@@ -1,25 +1,121 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use browser_core::{LinkRel, LinkTarget};
 use css_values::cursor::Cursor as CssCursor;
-use html_dom::{DocumentRoot, HtmlTag, Tag};
+use html_dom::{CheckableKind, DocumentRoot, DomNode, Element, HtmlTag, NodeId, RangeElement, Tag, TextControl};
 use iced::{
-    Rectangle,
-    keyboard::{Key, key::Named},
+    Point, Rectangle,
+    keyboard::{Key, Modifiers, key::Named},
     mouse::{self, Cursor, Interaction},
     widget::{Action, shader::Program},
     window::Id,
 };
-use layout::LayoutTree;
-use renderer::{ImageRenderInfo, RenderRect, RenderTri, TextBlockInfo};
+use layout::{Color4f, LayoutNode, LayoutTree, Rect, ReplacedKind, Resize};
+use renderer::{ImageRenderInfo, RenderRect, RenderShape, RenderTri, TextBlockInfo};
 
 use crate::{
-    core::{ScrollOffset, WindowType},
+    core::{ScrollOffset, SelectionState, TextInputAction, WindowType},
     events::{BrowserEvent, DevtoolEvent, EngineRequest, Event},
-    renderer::primitives::HtmlPrimitive,
+    renderer::{
+        pipeline::GpuMemory,
+        primitives::HtmlPrimitive,
+        viewport::{range_position_at, resize_handle},
+    },
 };
 
+/// The shortest time between two sizes sent to the browser while an element is resized, each of which lays out the
+/// page again.
+const RESIZE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// An element being resized by dragging its resize handle.
+#[derive(Debug, Clone, Copy)]
+pub struct ResizeDrag {
+    node_id: NodeId,
+    resize: Resize,
+
+    /// The cursor position the drag started at.
+    origin: Point,
+
+    /// The size of the element when the drag started.
+    width: f64,
+    height: f64,
+
+    /// The size with the cursor at its latest position.
+    size: (Option<f64>, Option<f64>),
+
+    /// The size last sent to the browser, and when it was sent.
+    sent: (Option<f64>, Option<f64>),
+    sent_at: Option<Instant>,
+}
+
+impl ResizeDrag {
+    /// The size of the element with the cursor at the given position, `None` for a dimension that is not resized.
+    fn size_at(&self, position: Point) -> (Option<f64>, Option<f64>) {
+        let width = (self.width + f64::from(position.x - self.origin.x)).max(0.0);
+        let height = (self.height + f64::from(position.y - self.origin.y)).max(0.0);
+
+        (self.resize.resizes_width().then_some(width), self.resize.resizes_height().then_some(height))
+    }
+
+    /// Takes the request resizing the element to its latest size, unless that size was already sent, or, when
+    /// `throttle` is set, another size was sent less than `RESIZE_INTERVAL` ago.
+    fn take_request(&mut self, window_id: iced::window::Id, throttle: bool) -> Option<EngineRequest> {
+        if self.size == self.sent
+            || (throttle
+                && self
+                    .sent_at
+                    .is_some_and(|at| at.elapsed() < RESIZE_INTERVAL))
+        {
+            return None;
+        }
+
+        self.sent = self.size;
+        self.sent_at = Some(Instant::now());
+
+        let (width, height) = self.size;
+        Some(EngineRequest::ResizeElement(window_id, self.node_id, width, height))
+    }
+}
+
+/// An `<input type="range">` whose thumb is being dragged.
+#[derive(Debug, Clone, Copy)]
+pub struct RangeDrag {
+    node_id: NodeId,
+
+    /// The content box of the slider in page coordinates.
+    dimensions: Rect,
+    range: RangeElement,
+
+    /// The value of the slider when the drag started.
+    initial_value: f64,
+
+    /// The value last sent to the browser.
+    value: f64,
+}
+
+impl RangeDrag {
+    /// The value of the slider with its thumb dragged to the given horizontal position in page coordinates.
+    fn value_at(&self, x: f64) -> f64 {
+        self.range.value_at(range_position_at(self.dimensions, x))
+    }
+}
+
 /// State for the shader widget
 #[derive(Debug, Default)]
 pub struct HtmlState {
     pub holding_shift: bool,
+
+    /// The element being resized, while its resize handle is dragged.
+    pub resizing: Option<ResizeDrag>,
+
+    /// The range slider whose thumb is being dragged.
+    pub sliding: Option<RangeDrag>,
+
+    /// Whether text is being selected, from the pointer being pressed over text until it is released.
+    pub selecting: bool,
 }
 
 /// HTML/CSS renderer using wgpu
@@ -28,11 +124,8 @@ pub struct HtmlRenderer<'html> {
     /// The ID of the window being rendered (used for event routing)
     pub window_id: Id,
 
-    /// Rectangles to render (populated by layout engine)
-    pub rects: Vec<RenderRect>,
-
-    /// Triangles to render (populated by layout engine)
-    pub tris: Vec<RenderTri>,
+    /// Rectangles and triangles to render in paint order (populated by layout engine)
+    pub shapes: Vec<RenderShape>,
 
     /// Text blocks to render (populated by layout engine)
     pub text_blocks: Vec<TextBlockInfo>,
@@ -51,9 +144,24 @@ pub struct HtmlRenderer<'html> {
 
     /// Where wheel scroll events should be routed.
     window_type: WindowType,
+
+    /// The text control receiving keyboard input, if any.
+    focused_input: Option<NodeId>,
+
+    /// The text selected on the page.
+    pub selection: SelectionState,
+
+    /// The color of checked checkboxes and radio buttons, taken from the browser theme.
+    pub accent_color: Color4f,
+
+    /// Where the sizes of the GPU caches are reported for the memory report.
+    gpu_memory: Option<Arc<GpuMemory>>,
 }
 
 impl<'html> HtmlRenderer<'html> {
+    /// The color of checked checkboxes and radio buttons when the theme does not provide one.
+    pub const DEFAULT_ACCENT_COLOR: Color4f = Color4f::rgba(0.1, 0.45, 0.91, 1.0);
+
     pub fn new(
         window_id: Id,
         dom_tree: &'html DocumentRoot,
@@ -63,19 +171,121 @@ impl<'html> HtmlRenderer<'html> {
     ) -> Self {
         Self {
             window_id,
-            rects: Vec::with_capacity(1000),
-            tris: Vec::with_capacity(1000),
+            shapes: Vec::with_capacity(2000),
             text_blocks: Vec::with_capacity(1000),
             images: Vec::with_capacity(100),
             dom_tree,
             layout_tree,
             scroll_offset,
             window_type,
+            focused_input: None,
+            selection: SelectionState::default(),
+            accent_color: Self::DEFAULT_ACCENT_COLOR,
+            gpu_memory: None,
+        }
+    }
+
+    /// Sets the color of checked checkboxes and radio buttons.
+    #[must_use]
+    pub const fn with_accent_color(mut self, accent_color: Color4f) -> Self {
+        self.accent_color = accent_color;
+        self
+    }
+
+    /// Sets where the sizes of the GPU caches are reported.
+    #[must_use]
+    pub fn with_gpu_memory(mut self, gpu_memory: Arc<GpuMemory>) -> Self {
+        self.gpu_memory = Some(gpu_memory);
+        self
+    }
+
+    /// Paints a rectangle over the shapes painted so far.
+    pub fn push_rect(&mut self, rect: RenderRect) {
+        self.shapes.push(RenderShape::Rect(rect));
+    }
+
+    /// Paints a triangle over the shapes painted so far.
+    pub fn push_tri(&mut self, tri: RenderTri) {
+        self.shapes.push(RenderShape::Tri(tri));
+    }
+
+    /// Sets the text control that keyboard input should be routed to.
+    #[must_use]
+    pub const fn with_focused_input(mut self, focused_input: Option<NodeId>) -> Self {
+        self.focused_input = focused_input;
+        self
+    }
+
+    /// Sets the text selected on the page.
+    #[must_use]
+    pub const fn with_selection(mut self, selection: SelectionState) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Determine the position in the text of the page nearest to the cursor, which may be outside of the bounds while
+    /// a selection is dragged.
+    fn text_position(&self, cursor: iced::advanced::mouse::Cursor, bounds: Rectangle) -> Option<(NodeId, usize)> {
+        let cursor = cursor.position()?;
+
+        let x = cursor.x + self.scroll_offset.x - bounds.x;
+        let y = cursor.y + self.scroll_offset.y - bounds.y;
+
+        self.layout_tree
+            .text_position_at(f64::from(x), f64::from(y))
+    }
+
+    /// Translates a shortcut pressed while no text control is focused into a change or copy of the page selection, or a
+    /// save of the page.
+    fn selection_shortcut_event(&self, key: &Key, modifiers: Modifiers) -> Option<Event> {
+        let Key::Character(c) = key else {
+            return None;
+        };
+
+        if !modifiers.command() {
+            return None;
+        }
+
+        if c.eq_ignore_ascii_case("a") {
+            let block = self.selection.anchor.map(|(node_id, _)| node_id);
+            let (anchor, focus) = self.layout_tree.block_text_range(block)?;
+
+            return Some(Event::Browser(BrowserEvent::SelectText(
+                self.window_id,
+                SelectionState::collapsed(anchor).extended(focus),
+            )));
+        }
+
+        if c.eq_ignore_ascii_case("c") && self.selection.range().is_some() {
+            return Some(Event::EngineRequest(EngineRequest::CopySelection(self.window_id)));
+        }
+
+        if c.eq_ignore_ascii_case("s") {
+            return Some(Event::EngineRequest(EngineRequest::SavePage(self.window_id)));
+        }
+
+        None
+    }
+
+    /// Determine the innermost DOM node under the cursor, the target of `:hover`.
+    fn get_hovered_node(&self, cursor: iced::advanced::mouse::Cursor, bounds: Rectangle) -> Option<NodeId> {
+        let cursor = cursor.position()?;
+
+        if !bounds.contains(cursor) {
+            return None;
         }
+
+        let x = cursor.x + self.scroll_offset.x - bounds.x;
+        let y = cursor.y + self.scroll_offset.y - bounds.y;
+
+        self.layout_tree
+            .resolve(f64::from(x), f64::from(y))
+            .into_iter()
+            .find_map(|node| node.node_id)
     }
 
-    /// Determine if the cursor is hovering over a link and return its href if so.
-    fn get_hovered_href(&self, cursor: iced::advanced::mouse::Cursor, bounds: Rectangle) -> Option<String> {
+    /// Determine if the cursor is hovering over a link and return its `<a>` element if so.
+    fn get_hovered_link(&self, cursor: iced::advanced::mouse::Cursor, bounds: Rectangle) -> Option<&Element> {
         let cursor = cursor.position()?;
 
         if !bounds.contains(cursor) {
@@ -97,26 +307,282 @@ impl<'html> HtmlRenderer<'html> {
             if let Some(n) = dom_node.data.as_element()
                 && n.tag == Tag::Html(HtmlTag::A)
             {
-                return Some(
-                    n.attributes
-                        .as_ref()
-                        .and_then(|attrs| attrs.get("href").cloned())
-                        .unwrap_or_default(),
-                );
+                return Some(n);
             }
 
             for ancestor in self.dom_tree.ancestors(dom_node) {
                 if let Some(n) = ancestor.data.as_element()
                     && n.tag == Tag::Html(HtmlTag::A)
-                    && let Some(href) = n.attributes.as_ref().and_then(|attrs| attrs.get("href"))
+                    && n.get_attribute("href").is_some()
                 {
-                    return Some(href.clone());
+                    return Some(n);
                 }
             }
         }
         None
     }
 
+    /// Determine if the cursor is hovering over a submit button and return the ID of its form if so.
+    fn get_hovered_submit_form(&self, cursor: iced::advanced::mouse::Cursor, bounds: Rectangle) -> Option<NodeId> {
+        let cursor = cursor.position()?;
+
+        if !bounds.contains(cursor) {
+            return None;
+        }
+
+        let x = cursor.x + self.scroll_offset.x - bounds.x;
+        let y = cursor.y + self.scroll_offset.y - bounds.y;
+
+        let nodes = self.layout_tree.resolve(f64::from(x), f64::from(y));
+
+        for node in nodes {
+            let Some(node_id) = node.node_id else {
+                continue;
+            };
+
+            let dom_node = &self.dom_tree[node_id];
+
+            let submit_button = std::iter::once(dom_node)
+                .chain(self.dom_tree.ancestors(dom_node))
+                .find(|n| Self::is_submit_button(n));
+
+            if let Some(button) = submit_button {
+                return self
+                    .dom_tree
+                    .ancestors(button)
+                    .into_iter()
+                    .find(|n| {
+                        n.data
+                            .as_element()
+                            .is_some_and(|e| e.tag == Tag::Html(HtmlTag::Form))
+                    })
+                    .map(|form| form.id);
+            }
+        }
+
+        None
+    }
+
+    /// Determine if the cursor is hovering over an editable text control and return its node ID if so.
+    fn get_hovered_text_control(&self, cursor: iced::advanced::mouse::Cursor, bounds: Rectangle) -> Option<NodeId> {
+        let cursor = cursor.position()?;
+
+        if !bounds.contains(cursor) {
+            return None;
+        }
+
+        let x = cursor.x + self.scroll_offset.x - bounds.x;
+        let y = cursor.y + self.scroll_offset.y - bounds.y;
+
+        self.layout_tree
+            .resolve(f64::from(x), f64::from(y))
+            .into_iter()
+            .filter_map(|node| node.node_id)
+            .find(|node_id| {
+                self.dom_tree[*node_id]
+                    .data
+                    .as_element()
+                    .and_then(TextControl::from_element)
+                    .is_some()
+            })
+    }
+
+    /// Determine if the cursor is hovering over a `<select>` dropdown that is not disabled and return its node ID if so.
+    fn get_hovered_select(&self, cursor: iced::advanced::mouse::Cursor, bounds: Rectangle) -> Option<NodeId> {
+        let cursor = cursor.position()?;
+
+        if !bounds.contains(cursor) {
+            return None;
+        }
+
+        let x = cursor.x + self.scroll_offset.x - bounds.x;
+        let y = cursor.y + self.scroll_offset.y - bounds.y;
+
+        self.layout_tree
+            .resolve(f64::from(x), f64::from(y))
+            .into_iter()
+            .filter(|node| {
+                node.image_data
+                    .as_ref()
+                    .is_some_and(|data| data.kind == ReplacedKind::Select)
+            })
+            .filter_map(|node| node.node_id)
+            .find(|node_id| {
+                self.dom_tree[*node_id]
+                    .data
+                    .as_element()
+                    .is_some_and(|element| !element.has_attribute("disabled"))
+            })
+    }
+
+    /// Determine if the cursor is hovering over a checkbox or radio button that is not disabled and return its node ID
+    /// and kind if so.
+    fn get_hovered_checkable(
+        &self,
+        cursor: iced::advanced::mouse::Cursor,
+        bounds: Rectangle,
+    ) -> Option<(NodeId, CheckableKind)> {
+        let cursor = cursor.position()?;
+
+        if !bounds.contains(cursor) {
+            return None;
+        }
+
+        let x = cursor.x + self.scroll_offset.x - bounds.x;
+        let y = cursor.y + self.scroll_offset.y - bounds.y;
+
+        self.layout_tree
+            .resolve(f64::from(x), f64::from(y))
+            .into_iter()
+            .filter_map(|node| node.node_id)
+            .find_map(|node_id| {
+                let element = self.dom_tree[node_id].data.as_element()?;
+                if element.has_attribute("disabled") {
+                    return None;
+                }
+
+                CheckableKind::from_element(element).map(|kind| (node_id, kind))
+            })
+    }
+
+    /// Determine if the cursor is over a range slider that is not disabled and return its node ID, content box and
+    /// parsed attributes if so.
+    fn get_hovered_range(
+        &self,
+        cursor: iced::advanced::mouse::Cursor,
+        bounds: Rectangle,
+    ) -> Option<(NodeId, Rect, RangeElement)> {
+        let cursor = cursor.position()?;
+
+        if !bounds.contains(cursor) {
+            return None;
+        }
+
+        let x = cursor.x + self.scroll_offset.x - bounds.x;
+        let y = cursor.y + self.scroll_offset.y - bounds.y;
+
+        self.layout_tree
+            .resolve(f64::from(x), f64::from(y))
+            .into_iter()
+            .find_map(|node| {
+                if node.image_data.as_ref()?.kind != ReplacedKind::Range {
+                    return None;
+                }
+
+                let node_id = node.node_id?;
+                let element = self.dom_tree[node_id].data.as_element()?;
+                if element.has_attribute("disabled") {
+                    return None;
+                }
+
+                RangeElement::from_element(element).map(|range| (node_id, node.dimensions, range))
+            })
+    }
+
+    /// Determine if the cursor is over the `<summary>` of a `<details>` and return the `<details>` if so.
+    fn get_hovered_details_summary(&self, cursor: iced::advanced::mouse::Cursor, bounds: Rectangle) -> Option<NodeId> {
+        let cursor = cursor.position()?;
+
+        if !bounds.contains(cursor) {
+            return None;
+        }
+
+        let x = cursor.x + self.scroll_offset.x - bounds.x;
+        let y = cursor.y + self.scroll_offset.y - bounds.y;
+
+        self.layout_tree
+            .resolve(f64::from(x), f64::from(y))
+            .into_iter()
+            .filter_map(|node| node.node_id)
+            .find_map(|node_id| {
+                let node = &self.dom_tree[node_id];
+                if node.data.as_element()?.tag != Tag::Html(HtmlTag::Summary) {
+                    return None;
+                }
+
+                let parent_id = node.parent?;
+                let parent = self.dom_tree[parent_id].data.as_element()?;
+
+                (parent.tag == Tag::Html(HtmlTag::Details)).then_some(parent_id)
+            })
+    }
+
+    /// Determine if the cursor is over the resize handle of a resizable element and return the element if so.
+    fn get_hovered_resize_handle(
+        &self,
+        cursor: iced::advanced::mouse::Cursor,
+        bounds: Rectangle,
+    ) -> Option<&LayoutNode> {
+        let cursor = cursor.position()?;
+
+        if !bounds.contains(cursor) {
+            return None;
+        }
+
+        let x = f64::from(cursor.x + self.scroll_offset.x - bounds.x);
+        let y = f64::from(cursor.y + self.scroll_offset.y - bounds.y);
+
+        // The handle lies in the padding box, which the hit testing of the layout tree does not cover.
+        self.layout_tree.nodes.iter().flatten().rev().find(|node| {
+            node.node_id.is_some() && resize_handle(node).is_some_and(|handle| handle.contains_point(x, y))
+        })
+    }
+
+    /// The cursor shown over the resize handle of an element.
+    const fn resize_interaction(resize: Resize) -> Interaction {
+        match resize {
+            Resize::Horizontal | Resize::Inline => Interaction::ResizingHorizontally,
+            Resize::Vertical | Resize::Block => Interaction::ResizingVertically,
+            Resize::Both | Resize::None => Interaction::ResizingDiagonallyDown,
+        }
+    }
+
+    /// Translates a key press into an edit of the focused text control.
+    fn text_input_event(&self, key: &Key, modifiers: Modifiers, text: Option<&str>) -> Option<Event> {
+        let select = modifiers.shift();
+
+        let action = match key {
+            Key::Named(Named::Escape | Named::Tab) => {
+                return Some(Event::Browser(BrowserEvent::FocusInput(self.window_id, None)));
+            }
+            Key::Named(Named::Backspace) => TextInputAction::Backspace,
+            Key::Named(Named::Delete) => TextInputAction::Delete,
+            Key::Named(Named::ArrowLeft) => TextInputAction::MoveLeft { select },
+            Key::Named(Named::ArrowRight) => TextInputAction::MoveRight { select },
+            Key::Named(Named::Home | Named::ArrowUp) => TextInputAction::MoveHome { select },
+            Key::Named(Named::End | Named::ArrowDown) => TextInputAction::MoveEnd { select },
+            Key::Named(Named::Enter) => TextInputAction::Insert(String::from("\n")),
+            Key::Character(c) if modifiers.command() && c.eq_ignore_ascii_case("a") => TextInputAction::SelectAll,
+            Key::Character(c) if modifiers.command() && c.eq_ignore_ascii_case("c") => {
+                return Some(Event::EngineRequest(EngineRequest::CopySelection(self.window_id)));
+            }
+            Key::Character(c) if modifiers.command() && c.eq_ignore_ascii_case("v") => {
+                return Some(Event::EngineRequest(EngineRequest::PasteClipboard(self.window_id, Instant::now())));
+            }
+            _ if modifiers.command() => return None,
+            _ => TextInputAction::Insert(text?.to_string()),
+        };
+
+        Some(Event::Browser(BrowserEvent::EditInput(self.window_id, action)))
+    }
+
+    /// Whether the DOM node is a `<button>` or `<input>` that submits its form when activated.
+    fn is_submit_button(node: &DomNode) -> bool {
+        let Some(element) = node.data.as_element() else {
+            return false;
+        };
+
+        let button_type = element.get_attribute("type").map(str::trim);
+
+        match element.tag {
+            Tag::Html(HtmlTag::Button) => button_type.is_none_or(|t| t.eq_ignore_ascii_case("submit")),
+            Tag::Html(HtmlTag::Input) => {
+                button_type.is_some_and(|t| t.eq_ignore_ascii_case("submit") || t.eq_ignore_ascii_case("image"))
+            }
+            _ => false,
+        }
+    }
+
     /// Determine the mouse cursor interaction based on the layout nodes under the cursor position.
     fn hovered_cursor(&self, cursor: iced::advanced::mouse::Cursor, bounds: Rectangle) -> Option<Interaction> {
         let cursor = cursor.position()?;
@@ -221,14 +687,9 @@ impl Program<Event> for HtmlRenderer<'_> {
 
     fn draw(&self, _state: &Self::State, _cursor: Cursor, _bounds: Rectangle) -> Self::Primitive {
         let mut primitive = HtmlPrimitive::new(self.scroll_offset);
+        primitive.gpu_memory = self.gpu_memory.clone();
 
-        for tri in &self.tris {
-            primitive.push_triangle(tri.p0, tri.p1, tri.p2, tri.color);
-        }
-
-        for render_rect in &self.rects {
-            primitive.push_rect(render_rect.rect, render_rect.background);
-        }
+        primitive.shapes.clone_from(&self.shapes);
 
         for text_block in &self.text_blocks {
             primitive.push_text_block(text_block.clone());
@@ -260,6 +721,212 @@ impl Program<Event> for HtmlRenderer<'_> {
             }
         }
 
+        if matches!(self.window_type, WindowType::Browser)
+            && let Some(mut drag) = state.resizing
+        {
+            // The size is sent at most every `RESIZE_INTERVAL` while dragging, and the final size once released.
+            let request = match event {
+                iced::Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                    drag.size = drag.size_at(*position);
+                    let request = drag.take_request(self.window_id, true);
+                    state.resizing = Some(drag);
+                    Some(request)
+                }
+                iced::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                    state.resizing = None;
+                    Some(drag.take_request(self.window_id, false))
+                }
+                _ => None,
+            };
+
+            match request {
+                Some(Some(request)) => return Some(Action::publish(Event::EngineRequest(request)).and_capture()),
+                Some(None) => return Some(Action::capture()),
+                None => {}
+            }
+        }
+
+        if matches!(self.window_type, WindowType::Browser)
+            && let Some(mut drag) = state.sliding
+        {
+            match event {
+                iced::Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                    let value = drag.value_at(f64::from(position.x + self.scroll_offset.x - bounds.x));
+                    if (value - drag.value).abs() <= f64::EPSILON {
+                        return Some(Action::capture());
+                    }
+
+                    drag.value = value;
+                    state.sliding = Some(drag);
+
+                    let request = EngineRequest::SetInputValue(self.window_id, drag.node_id, value.to_string());
+                    return Some(Action::publish(Event::EngineRequest(request)).and_capture());
+                }
+                iced::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                    state.sliding = None;
+
+                    // The value settles once the thumb is released, which is when `change` fires.
+                    if (drag.value - drag.initial_value).abs() <= f64::EPSILON {
+                        return Some(Action::capture());
+                    }
+
+                    let request = EngineRequest::DispatchEvent(self.window_id, drag.node_id, "change".to_string());
+                    return Some(Action::publish(Event::EngineRequest(request)).and_capture());
+                }
+                _ => {}
+            }
+        }
+
+        if matches!(self.window_type, WindowType::Browser) && state.selecting {
+            match event {
+                iced::Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                    if let Some(focus) = self.text_position(cursor, bounds)
+                        && self.selection.focus != Some(focus)
+                    {
+                        let selection = self.selection.extended(focus);
+                        return Some(
+                            Action::publish(Event::Browser(BrowserEvent::SelectText(self.window_id, selection)))
+                                .and_capture(),
+                        );
+                    }
+                }
+                iced::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                    state.selecting = false;
+
+                    // A drag that selected text does not also click the link it ended on.
+                    if self.selection.range().is_some() {
+                        return Some(Action::capture());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if matches!(self.window_type, WindowType::Browser)
+            && matches!(event, iced::Event::Mouse(mouse::Event::CursorMoved { .. } | mouse::Event::CursorLeft))
+        {
+            let change = self
+                .dom_tree
+                .hover_change(self.get_hovered_node(cursor, bounds));
+
+            if !change.is_empty() {
+                return Some(Action::publish(Event::Browser(BrowserEvent::HoverStateChanged {
+                    window_id: self.window_id,
+                    added: change.added,
+                    removed: change.removed,
+                })));
+            }
+        }
+
+        if matches!(self.window_type, WindowType::Browser)
+            && matches!(event, iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)))
+            && let Some(node) = self.get_hovered_resize_handle(cursor, bounds)
+            && let (Some(node_id), Some(origin)) = (node.node_id, cursor.position())
+        {
+            state.resizing = Some(ResizeDrag {
+                node_id,
+                resize: node.resize,
+                origin,
+                width: node.dimensions.width,
+                height: node.dimensions.height,
+                size: (None, None),
+                sent: (None, None),
+                sent_at: None,
+            });
+
+            return Some(Action::capture());
+        }
+
+        if matches!(self.window_type, WindowType::Browser)
+            && self.focused_input.is_some()
+            && let iced::Event::Keyboard(iced::keyboard::Event::KeyPressed {
+                key,
+                modifiers,
+                text,
+                ..
+            }) = event
+            && let Some(event) = self.text_input_event(key, *modifiers, text.as_deref())
+        {
+            return Some(Action::publish(event).and_capture());
+        }
+
+        if matches!(self.window_type, WindowType::Browser)
+            && self.focused_input.is_none()
+            && let iced::Event::Keyboard(iced::keyboard::Event::KeyPressed { key, modifiers, .. }) = event
+            && let Some(event) = self.selection_shortcut_event(key, *modifiers)
+        {
+            return Some(Action::publish(event).and_capture());
+        }
+
+        if matches!(self.window_type, WindowType::Browser)
+            && matches!(event, iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)))
+            && let Some(node_id) = self.get_hovered_text_control(cursor, bounds)
+        {
+            return Some(Action::publish(Event::Browser(BrowserEvent::FocusInput(self.window_id, Some(node_id)))));
+        }
+
+        if matches!(self.window_type, WindowType::Browser)
+            && matches!(event, iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)))
+            && let Some((node_id, kind)) = self.get_hovered_checkable(cursor, bounds)
+        {
+            let request = match kind {
+                CheckableKind::Checkbox => EngineRequest::ToggleCheckbox(self.window_id, node_id),
+                CheckableKind::Radio => {
+                    let group_name = self.dom_tree[node_id]
+                        .data
+                        .as_element()
+                        .and_then(|element| element.get_attribute("name"))
+                        .unwrap_or_default()
+                        .to_string();
+
+                    EngineRequest::SelectRadio(self.window_id, group_name, node_id)
+                }
+            };
+
+            return Some(Action::publish(Event::EngineRequest(request)));
+        }
+
+        if matches!(self.window_type, WindowType::Browser)
+            && matches!(event, iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)))
+            && let Some((node_id, dimensions, range)) = self.get_hovered_range(cursor, bounds)
+            && let Some(position) = cursor.position()
+        {
+            let mut drag = RangeDrag {
+                node_id,
+                dimensions,
+                range,
+                initial_value: range.value,
+                value: range.value,
+            };
+
+            // Pressing the track moves the thumb under the cursor before it is dragged from there.
+            let value = drag.value_at(f64::from(position.x + self.scroll_offset.x - bounds.x));
+            let changed = (value - drag.value).abs() > f64::EPSILON;
+            drag.value = value;
+            state.sliding = Some(drag);
+
+            if changed {
+                let request = EngineRequest::SetInputValue(self.window_id, node_id, value.to_string());
+                return Some(Action::publish(Event::EngineRequest(request)).and_capture());
+            }
+
+            return Some(Action::capture());
+        }
+
+        if matches!(self.window_type, WindowType::Browser)
+            && matches!(event, iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)))
+            && let Some(node_id) = self.get_hovered_details_summary(cursor, bounds)
+        {
+            return Some(Action::publish(Event::EngineRequest(EngineRequest::ToggleDetails(self.window_id, node_id))));
+        }
+
+        if matches!(self.window_type, WindowType::Browser)
+            && matches!(event, iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)))
+            && let Some(node_id) = self.get_hovered_select(cursor, bounds)
+        {
+            return Some(Action::publish(Event::Browser(BrowserEvent::ToggleDropdown(self.window_id, Some(node_id)))));
+        }
+
         if let iced::Event::Mouse(e) = event
             && let mouse::Event::WheelScrolled { delta } = e
         {
@@ -311,9 +978,43 @@ impl Program<Event> for HtmlRenderer<'_> {
         if matches!(self.window_type, WindowType::Browser)
             && matches!(event, iced::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)))
             // TODO: Pre calculate all anchor tags and store in State to avoid doing this hit testing on every click
-            && let Some(href) = self.get_hovered_href(cursor, bounds)
+            && let Some(link) = self.get_hovered_link(cursor, bounds)
         {
-            return Some(Action::publish(Event::EngineRequest(EngineRequest::NavigateTo(self.window_id, href))));
+            return Some(Action::publish(Event::EngineRequest(EngineRequest::OpenLink(
+                self.window_id,
+                link.get_attribute("href").unwrap_or_default().to_string(),
+                LinkTarget::from_attribute(link.get_attribute("target")),
+                LinkRel::from_attribute(link.get_attribute("rel")),
+            ))));
+        }
+
+        if matches!(self.window_type, WindowType::Browser)
+            && matches!(event, iced::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)))
+            && let Some(form_id) = self.get_hovered_submit_form(cursor, bounds)
+        {
+            return Some(Action::publish(Event::EngineRequest(EngineRequest::SubmitForm(self.window_id, form_id))));
+        }
+
+        if matches!(self.window_type, WindowType::Browser)
+            && matches!(event, iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)))
+            && cursor.is_over(bounds)
+        {
+            let position = self.text_position(cursor, bounds);
+            state.selecting = position.is_some();
+
+            // Pressing over text starts a new selection and pressing anywhere else clears the current one, either of
+            // which also moves focus away from the focused text control.
+            if position.is_some() || self.selection.anchor.is_some() {
+                let selection = position.map(SelectionState::collapsed).unwrap_or_default();
+                return Some(Action::publish(Event::Browser(BrowserEvent::SelectText(self.window_id, selection))));
+            }
+        }
+
+        if self.focused_input.is_some()
+            && matches!(event, iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)))
+            && cursor.is_over(bounds)
+        {
+            return Some(Action::publish(Event::Browser(BrowserEvent::FocusInput(self.window_id, None))));
         }
 
         None
@@ -321,13 +1022,21 @@ impl Program<Event> for HtmlRenderer<'_> {
 
     fn mouse_interaction(
         &self,
-        _state: &Self::State,
+        state: &Self::State,
         bounds: Rectangle,
         cursor: iced::advanced::mouse::Cursor,
     ) -> iced::advanced::mouse::Interaction {
         if !matches!(self.window_type, WindowType::Browser) {
             return Interaction::default();
         }
+
+        if let Some(drag) = state.resizing {
+            return Self::resize_interaction(drag.resize);
+        }
+
+        if let Some(node) = self.get_hovered_resize_handle(cursor, bounds) {
+            return Self::resize_interaction(node.resize);
+        }
         // TODO: Pre calculate all cursor styles and store in State to avoid doing this hit testing on every mouse move
         self.hovered_cursor(cursor, bounds).unwrap_or_default()
     }
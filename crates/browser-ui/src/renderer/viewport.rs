@@ -1,10 +1,49 @@
+use std::borrow::Cow;
+
 use css_display::LayoutNodeId;
-use layout::{Color4f, ImageContext, LayoutNode, LayoutTree, Rect};
-use renderer::{ImageRenderInfo, RenderRect, RenderTri, TextBlockInfo};
+use css_style::ComputedBoxShadow;
+use html_dom::{MeterElement, MeterRegion, ProgressElement, RangeElement};
+use layout::{
+    Color4f, CornerRadii, ImageContext, Isolation, LayoutColors, LayoutNode, LayoutTree, PartColors, Rect,
+    ReplacedKind, Resize,
+};
+use renderer::{
+    BlendMode, CORNER_SEGMENTS, ImageRenderInfo, RenderRect, RenderTri, TextBlockInfo, rounded_rect_outline,
+    rounded_rect_outline_with_segments,
+};
 
-use crate::{core::ScrollOffset, renderer::program::HtmlRenderer};
+use crate::{
+    core::{ScrollOffset, TextInputController},
+    renderer::program::HtmlRenderer,
+};
 
 const IMAGE_PLACEHOLDER_COLOR: Color4f = Color4f::rgba(0.8, 0.8, 0.8, 1.0);
+const MEDIA_ICON_COLOR: Color4f = Color4f::rgba(1.0, 1.0, 1.0, 0.9);
+const AUDIO_BACKGROUND_COLOR: Color4f = Color4f::rgba(0.94, 0.94, 0.94, 1.0);
+const AUDIO_CONTROL_COLOR: Color4f = Color4f::rgba(0.2, 0.2, 0.2, 1.0);
+const AUDIO_TRACK_COLOR: Color4f = Color4f::rgba(0.75, 0.75, 0.75, 1.0);
+const TEXT_CONTROL_BACKGROUND_COLOR: Color4f = Color4f::rgba(1.0, 1.0, 1.0, 1.0);
+const TEXT_CONTROL_BORDER_COLOR: Color4f = Color4f::rgba(0.46, 0.46, 0.46, 1.0);
+const TEXT_CONTROL_PADDING: f64 = 4.0;
+const CHECKABLE_SEGMENTS: usize = 24;
+const CHECKMARK_COLOR: Color4f = Color4f::rgba(1.0, 1.0, 1.0, 1.0);
+const SELECT_ARROW_WIDTH: f64 = 16.0;
+const SELECT_ARROW_SIZE: f32 = 8.0;
+const TEXT_SELECTION_COLOR: Color4f = Color4f::rgba(0.2, 0.45, 0.9, 0.35);
+const BOX_SHADOW_BLUR_STEPS: usize = 8;
+const BOX_SHADOW_BLUR_CORNER_SEGMENTS: usize = 3;
+const SPELLING_ERROR_COLOR: Color4f = Color4f::rgba(0.9, 0.1, 0.1, 1.0);
+const SPELLING_WAVE_LENGTH: f32 = 4.0;
+const SPELLING_WAVE_HEIGHT: f32 = 2.0;
+const RESIZE_HANDLE_COLOR: Color4f = Color4f::rgba(0.46, 0.46, 0.46, 0.8);
+const RESIZE_HANDLE_SIZE: f64 = 12.0;
+const GAUGE_TRACK_COLOR: Color4f = Color4f::rgba(0.88, 0.88, 0.88, 1.0);
+const METER_OPTIMUM_COLOR: Color4f = Color4f::rgba(0.42, 0.75, 0.27, 1.0);
+const METER_SUBOPTIMUM_COLOR: Color4f = Color4f::rgba(0.96, 0.78, 0.18, 1.0);
+const METER_EVEN_LESS_GOOD_COLOR: Color4f = Color4f::rgba(0.86, 0.24, 0.2, 1.0);
+const RANGE_TRACK_HEIGHT: f64 = 4.0;
+const RANGE_THUMB_WIDTH: f64 = 8.0;
+const RANGE_THUMB_COLOR: Color4f = Color4f::rgba(0.96, 0.96, 0.96, 1.0);
 
 /// Helper function to determine if a layout node is within the visible viewport based on its dimensions and the current scroll offset.
 fn is_visible_node(node_dimensions: Rect, initial_bounds: Rect, scroll_offset: ScrollOffset) -> bool {
@@ -22,11 +61,224 @@ fn is_visible_node(node_dimensions: Rect, initial_bounds: Rect, scroll_offset: S
         && node_dimensions.x <= viewport_right
 }
 
+/// The blend group a node is painted in, an element with a `mix-blend-mode` and its descendants are blended with the
+/// color painted behind the element.
+///
+/// The backdrop is approximated by the background colors of the ancestors of the element, since the page is not
+/// rendered into offscreen layers, so colors and images are blended with a solid color.
+#[derive(Debug, Clone, Copy)]
+struct BlendLayer {
+    mode: BlendMode,
+    backdrop: Color4f,
+}
+
+impl BlendLayer {
+    const NORMAL: Self = Self {
+        mode: BlendMode::Normal,
+        backdrop: Color4f::TRANSPARENT,
+    };
+
+    fn blend(self, color: Color4f) -> Color4f {
+        if color.a <= 0.0 {
+            return color;
+        }
+
+        self.mode.composite(self.backdrop, color)
+    }
+
+    /// The colors of a node as seen through the blend group.
+    fn apply(self, colors: &LayoutColors) -> Cow<'_, LayoutColors> {
+        if self.mode == BlendMode::Normal {
+            return Cow::Borrowed(colors);
+        }
+
+        let mut blended = colors.clone();
+        blended.background_color = self.blend(colors.background_color);
+        blended.color = self.blend(colors.color);
+        blended.border_color.top = self.blend(colors.border_color.top);
+        blended.border_color.right = self.blend(colors.border_color.right);
+        blended.border_color.bottom = self.blend(colors.border_color.bottom);
+        blended.border_color.left = self.blend(colors.border_color.left);
+        Cow::Owned(blended)
+    }
+
+    /// The blend mode and backdrop an image painted in the blend group is blended with.
+    fn image_blend(self) -> Option<(BlendMode, Color4f)> {
+        (self.mode != BlendMode::Normal).then_some((self.mode, self.backdrop))
+    }
+}
+
+/// Draws the outer shadows of `box-shadow` behind the border box of a node, the last listed at the bottom.
+///
+/// NOTE: Inset shadows are not drawn.
+fn render_box_shadows(node: &LayoutNode, renderer: &mut HtmlRenderer) {
+    for shadow in node.box_shadow.iter().rev().filter(|shadow| !shadow.inset) {
+        render_outer_shadow(node, shadow, renderer);
+    }
+}
+
+/// Draws the shadows of the `drop-shadow()` functions of the `filter` of a node, below everything the node paints.
+/// A drop shadow follows the alpha of the element, which is approximated by its border box, so the shadows are only
+/// drawn when an opaque background or an image fills the box. The blur radius of a drop shadow is a standard
+/// deviation, twice that is the blur radius of the equivalent box shadow.
+///
+/// NOTE: The shadows of text, and of an element with a transparent or translucent background, are not drawn.
+fn render_drop_shadows(node: &LayoutNode, renderer: &mut HtmlRenderer) {
+    if node.colors.background_color.a < 1.0 && node.image_data.is_none() {
+        return;
+    }
+
+    for shadow in node.drop_shadow.iter().rev() {
+        let box_shadow = ComputedBoxShadow {
+            inset: false,
+            offset_x: shadow.offset_x,
+            offset_y: shadow.offset_y,
+            blur_radius: shadow.blur_radius * 2.0,
+            spread_radius: 0.0,
+            color: shadow.color,
+        };
+        render_outer_shadow(node, &box_shadow, renderer);
+    }
+}
+
+/// Draws one outer shadow behind the border box of a node. A blurred shadow is approximated by stacking translucent
+/// copies that shrink from half the blur radius outside the shadow to half inside it, so its edge fades out over the
+/// blur radius.
+///
+/// The shadow is only drawn outside the border box, so it does not show through a transparent background. With square
+/// corners the copies are cut into the rectangles around the border box, and with rounded corners they are drawn as a
+/// ring between their outline and the outline of the border box. The copies of a blurred shadow are only faint steps
+/// of its edge, so their corners are drawn with fewer segments to keep the rings within the vertex budget of the rect
+/// pipeline.
+///
+/// NOTE: A copy with rounded corners that does not enclose the border box, such as one offset further than its
+/// spread, is only drawn when the background hides it.
+fn render_outer_shadow(node: &LayoutNode, shadow: &ComputedBoxShadow, renderer: &mut HtmlRenderer) {
+    if shadow.color.a <= 0.0 {
+        return;
+    }
+
+    let (steps, corner_segments) = if shadow.blur_radius > 0.0 {
+        (BOX_SHADOW_BLUR_STEPS, BOX_SHADOW_BLUR_CORNER_SEGMENTS)
+    } else {
+        (1, CORNER_SEGMENTS)
+    };
+
+    let border_box = node.border_box();
+    let to_f32 = |rect: Rect| Rect::new(rect.x as f32, rect.y as f32, rect.width as f32, rect.height as f32);
+    let border_outline = (!node.border_radius.is_zero())
+        .then(|| rounded_rect_outline_with_segments(to_f32(border_box), node.border_radius, corner_segments));
+    let color = Color4f {
+        a: 1.0 - (1.0 - shadow.color.a).powf(1.0 / steps as f64),
+        ..shadow.color
+    };
+
+    for step in 0..steps {
+        let blur_offset = if steps > 1 {
+            shadow.blur_radius * (0.5 - step as f64 / (steps - 1) as f64)
+        } else {
+            0.0
+        };
+        let outset = shadow.spread_radius + blur_offset;
+        let width = border_box.width + 2.0 * outset;
+        let height = border_box.height + 2.0 * outset;
+        if width <= 0.0 || height <= 0.0 {
+            continue;
+        }
+
+        let shadow_box =
+            Rect::new(border_box.x + shadow.offset_x - outset, border_box.y + shadow.offset_y - outset, width, height);
+        let radii = node.border_radius.outset(outset);
+
+        let Some(border_outline) = &border_outline else {
+            for rect in rect_difference(shadow_box, border_box) {
+                renderer.push_rect(RenderRect {
+                    rect,
+                    background: color,
+                    radii: CornerRadii::default(),
+                });
+            }
+            continue;
+        };
+
+        if encloses(shadow_box, border_box) {
+            let outline = rounded_rect_outline_with_segments(to_f32(shadow_box), radii, corner_segments);
+            for index in 0..outline.len() {
+                let next = (index + 1) % outline.len();
+                renderer.push_tri(RenderTri {
+                    p0: outline[index],
+                    p1: outline[next],
+                    p2: border_outline[next],
+                    color,
+                });
+                renderer.push_tri(RenderTri {
+                    p0: outline[index],
+                    p1: border_outline[next],
+                    p2: border_outline[index],
+                    color,
+                });
+            }
+        } else if node.colors.background_color.a >= 1.0 {
+            renderer.push_rect(RenderRect {
+                rect: shadow_box,
+                background: color,
+                radii,
+            });
+        }
+    }
+}
+
+/// Whether a rectangle lies entirely within another.
+fn encloses(outer: Rect, inner: Rect) -> bool {
+    inner.x >= outer.x
+        && inner.y >= outer.y
+        && inner.x + inner.width <= outer.x + outer.width
+        && inner.y + inner.height <= outer.y + outer.height
+}
+
+/// Cuts a hole out of a rectangle, returning the rectangles above, below, left and right of the hole that cover the
+/// rest of it. The whole rectangle is returned when the hole does not overlap it.
+fn rect_difference(rect: Rect, hole: Rect) -> Vec<Rect> {
+    let right = rect.x + rect.width;
+    let bottom = rect.y + rect.height;
+    let hole_right = hole.x + hole.width;
+    let hole_bottom = hole.y + hole.height;
+
+    let mut parts = Vec::with_capacity(4);
+    let mut push = |x: f64, y: f64, part_right: f64, part_bottom: f64| {
+        if part_right > x && part_bottom > y {
+            parts.push(Rect::new(x, y, part_right - x, part_bottom - y));
+        }
+    };
+
+    let middle_top = rect.y.max(hole.y);
+    let middle_bottom = bottom.min(hole_bottom);
+
+    if middle_bottom <= middle_top || hole_right <= rect.x || hole.x >= right {
+        push(rect.x, rect.y, right, bottom);
+        return parts;
+    }
+
+    push(rect.x, rect.y, right, middle_top);
+    push(rect.x, middle_bottom, right, bottom);
+    push(rect.x, middle_top, right.min(hole.x), middle_bottom);
+    push(rect.x.max(hole_right), middle_top, right, middle_bottom);
+
+    parts
+}
+
 /// Helper function to render a single layout node into the HtmlRenderer, including its background, borders, text, and images.
-fn render_node(node: &LayoutNode, image_ctx: &ImageContext, renderer: &mut HtmlRenderer) {
+fn render_node(node: &LayoutNode, layer: BlendLayer, image_ctx: &ImageContext, renderer: &mut HtmlRenderer) {
+    render_drop_shadows(node, renderer);
+    render_box_shadows(node, renderer);
+
+    let colors = layer.apply(&node.colors);
     let border = node.border;
-    let border_color = &node.colors.border_color;
-    if border.top > 0.0 || border.right > 0.0 || border.bottom > 0.0 || border.left > 0.0 {
+    let border_color = &colors.border_color;
+    let has_border = border.top > 0.0 || border.right > 0.0 || border.bottom > 0.0 || border.left > 0.0;
+    if has_border && !node.border_radius.is_zero() {
+        render_rounded_border(node, &colors, renderer);
+    } else if has_border {
         let x = node.dimensions.x as f32;
         let y = node.dimensions.y as f32;
         let h = (node.dimensions.height + node.padding.vertical()) as f32;
@@ -43,13 +295,13 @@ fn render_node(node: &LayoutNode, image_ctx: &ImageContext, renderer: &mut HtmlR
         let outer_bottom = y + h;
 
         if border.top > 0.0 && border_color.top.a > 0.0 {
-            renderer.tris.push(RenderTri {
+            renderer.push_tri(RenderTri {
                 p0: [x, y],
                 p1: [outer_right, y],
                 p2: [inner_right, inner_y],
                 color: border_color.top,
             });
-            renderer.tris.push(RenderTri {
+            renderer.push_tri(RenderTri {
                 p0: [x, y],
                 p1: [inner_right, inner_y],
                 p2: [inner_x, inner_y],
@@ -58,13 +310,13 @@ fn render_node(node: &LayoutNode, image_ctx: &ImageContext, renderer: &mut HtmlR
         }
 
         if border.right > 0.0 && border_color.right.a > 0.0 {
-            renderer.tris.push(RenderTri {
+            renderer.push_tri(RenderTri {
                 p0: [outer_right, y],
                 p1: [outer_right, outer_bottom],
                 p2: [inner_right, inner_bottom],
                 color: border_color.right,
             });
-            renderer.tris.push(RenderTri {
+            renderer.push_tri(RenderTri {
                 p0: [outer_right, y],
                 p1: [inner_right, inner_bottom],
                 p2: [inner_right, inner_y],
@@ -73,13 +325,13 @@ fn render_node(node: &LayoutNode, image_ctx: &ImageContext, renderer: &mut HtmlR
         }
 
         if border.bottom > 0.0 && border_color.bottom.a > 0.0 {
-            renderer.tris.push(RenderTri {
+            renderer.push_tri(RenderTri {
                 p0: [outer_right, outer_bottom],
                 p1: [x, outer_bottom],
                 p2: [inner_x, inner_bottom],
                 color: border_color.bottom,
             });
-            renderer.tris.push(RenderTri {
+            renderer.push_tri(RenderTri {
                 p0: [outer_right, outer_bottom],
                 p1: [inner_x, inner_bottom],
                 p2: [inner_right, inner_bottom],
@@ -88,13 +340,13 @@ fn render_node(node: &LayoutNode, image_ctx: &ImageContext, renderer: &mut HtmlR
         }
 
         if border.left > 0.0 && border_color.left.a > 0.0 {
-            renderer.tris.push(RenderTri {
+            renderer.push_tri(RenderTri {
                 p0: [x, outer_bottom],
                 p1: [x, y],
                 p2: [inner_x, inner_y],
                 color: border_color.left,
             });
-            renderer.tris.push(RenderTri {
+            renderer.push_tri(RenderTri {
                 p0: [x, outer_bottom],
                 p1: [inner_x, inner_y],
                 p2: [inner_x, inner_bottom],
@@ -103,16 +355,17 @@ fn render_node(node: &LayoutNode, image_ctx: &ImageContext, renderer: &mut HtmlR
         }
     }
 
-    if node.colors.background_color.a > 0.0 {
+    if colors.background_color.a > 0.0 {
         let border = node.border;
         let padding = node.padding;
         let inner_x = node.dimensions.x + border.left;
         let inner_y = node.dimensions.y + border.top;
         let inner_width = (node.dimensions.width + padding.horizontal() - border.horizontal()).max(0.0);
         let inner_height = (node.dimensions.height + padding.vertical() - border.vertical()).max(0.0);
-        renderer.rects.push(RenderRect {
+        renderer.push_rect(RenderRect {
             rect: Rect::new(inner_x, inner_y, inner_width, inner_height),
-            background: node.colors.background_color,
+            background: colors.background_color,
+            radii: node.border_radius.inset(border),
         });
     }
 
@@ -122,7 +375,9 @@ fn render_node(node: &LayoutNode, image_ctx: &ImageContext, renderer: &mut HtmlR
                 text,
                 (node.dimensions.x + fragment.size.x) as f32,
                 (node.dimensions.y + fragment.size.y) as f32,
-                node.colors.color,
+                colors.color,
+                fragment.spacing.letter as f32,
+                fragment.spacing.word as f32,
             );
             if !text_block.glyphs.is_empty() {
                 renderer.text_blocks.push(text_block);
@@ -131,11 +386,86 @@ fn render_node(node: &LayoutNode, image_ctx: &ImageContext, renderer: &mut HtmlR
     }
 
     if let Some(image_data) = &node.image_data {
+        match image_data.kind {
+            ReplacedKind::Audio => {
+                render_audio_controls(node.dimensions, renderer);
+                return;
+            }
+            ReplacedKind::TextInput | ReplacedKind::TextArea => {
+                render_text_control_frame(node, renderer);
+                return;
+            }
+            ReplacedKind::Select => {
+                render_text_control_frame(node, renderer);
+                render_select_arrow(node, colors.color, renderer);
+                return;
+            }
+            ReplacedKind::Checkbox | ReplacedKind::Radio => {
+                let checked = renderer.dom_tree[image_data.node_id]
+                    .data
+                    .as_element()
+                    .is_some_and(|element| element.has_attribute("checked"));
+
+                if image_data.kind == ReplacedKind::Checkbox {
+                    render_checkbox(node.dimensions, checked, renderer);
+                } else {
+                    render_radio(node.dimensions, checked, renderer);
+                }
+                return;
+            }
+            ReplacedKind::Progress | ReplacedKind::Meter => {
+                let element = renderer.dom_tree[image_data.node_id].data.as_element();
+                let (fraction, indicator_color) = match image_data.kind {
+                    ReplacedKind::Meter => element
+                        .and_then(MeterElement::from_element)
+                        .map_or((0.0, None), |meter| {
+                            let color = match meter.region() {
+                                MeterRegion::Optimum => METER_OPTIMUM_COLOR,
+                                MeterRegion::Suboptimum => METER_SUBOPTIMUM_COLOR,
+                                MeterRegion::EvenLessGood => METER_EVEN_LESS_GOOD_COLOR,
+                            };
+                            (meter.position(), Some(color))
+                        }),
+                    _ => (
+                        element
+                            .and_then(ProgressElement::from_element)
+                            .and_then(|progress| progress.position())
+                            .unwrap_or(0.0),
+                        None,
+                    ),
+                };
+
+                render_gauge(node.dimensions, fraction, indicator_color, image_data.part_colors, renderer);
+                return;
+            }
+            ReplacedKind::Range => {
+                let position = renderer.dom_tree[image_data.node_id]
+                    .data
+                    .as_element()
+                    .and_then(RangeElement::from_element)
+                    .map_or(0.0, |range| range.position());
+
+                render_range(node.dimensions, position, image_data.part_colors, renderer);
+                return;
+            }
+            _ => {}
+        }
+
         let Some(image) = image_ctx.get(&image_data.node_id) else {
-            renderer.rects.push(RenderRect {
+            // A canvas without a bitmap is fully transparent.
+            if image_data.kind == ReplacedKind::Canvas {
+                return;
+            }
+
+            renderer.push_rect(RenderRect {
                 rect: node.dimensions,
                 background: IMAGE_PLACEHOLDER_COLOR,
+                radii: CornerRadii::default(),
             });
+
+            if image_data.kind == ReplacedKind::Video {
+                render_play_icon(node.dimensions, renderer);
+            }
             return;
         };
 
@@ -143,10 +473,569 @@ fn render_node(node: &LayoutNode, image_ctx: &ImageContext, renderer: &mut HtmlR
             node_id: image_data.node_id,
             screen_rect: node.dimensions,
             data: image,
+            image_rendering: image_data.image_rendering,
+            object_fit: image_data.object_fit,
+            blend: layer.image_blend(),
+        });
+    }
+}
+
+/// Draws the border of a node with rounded corners as a ring between the outlines of its border box and its padding box.
+/// The first half of a corner takes the color of the side it starts on and the second half the color of the side it
+/// ends on.
+fn render_rounded_border(node: &LayoutNode, colors: &LayoutColors, renderer: &mut HtmlRenderer) {
+    let to_f32 = |rect: Rect| Rect::new(rect.x as f32, rect.y as f32, rect.width as f32, rect.height as f32);
+    let outer = rounded_rect_outline(to_f32(node.border_box()), node.border_radius);
+    let inner = rounded_rect_outline(to_f32(node.padding_box()), node.border_radius.inset(node.border));
+
+    let border_color = &colors.border_color;
+    let sides = [
+        (node.border.top, border_color.top),
+        (node.border.right, border_color.right),
+        (node.border.bottom, border_color.bottom),
+        (node.border.left, border_color.left),
+    ];
+
+    for index in 0..outer.len() {
+        let next = (index + 1) % outer.len();
+        let corner = index / (CORNER_SEGMENTS + 1);
+
+        // The corners go clockwise from the top left, which starts on the left side and ends on the top side.
+        let side = if index % (CORNER_SEGMENTS + 1) < CORNER_SEGMENTS / 2 {
+            (corner + 3) % 4
+        } else {
+            corner
+        };
+
+        let (width, color) = sides[side];
+        if width <= 0.0 || color.a <= 0.0 {
+            continue;
+        }
+
+        renderer.push_tri(RenderTri {
+            p0: outer[index],
+            p1: outer[next],
+            p2: inner[next],
+            color,
+        });
+        renderer.push_tri(RenderTri {
+            p0: outer[index],
+            p1: inner[next],
+            p2: inner[index],
+            color,
+        });
+    }
+}
+
+/// Helper function to draw a centered play (▶) icon over a video placeholder.
+fn render_play_icon(dimensions: Rect, renderer: &mut HtmlRenderer) {
+    let size = (dimensions.width.min(dimensions.height) * 0.25).min(48.0) as f32;
+    if size <= 0.0 {
+        return;
+    }
+
+    let cx = (dimensions.x + dimensions.width / 2.0) as f32;
+    let cy = (dimensions.y + dimensions.height / 2.0) as f32;
+
+    renderer.push_tri(RenderTri {
+        p0: [cx - size * 0.4, cy - size / 2.0],
+        p1: [cx + size * 0.6, cy],
+        p2: [cx - size * 0.4, cy + size / 2.0],
+        color: MEDIA_ICON_COLOR,
+    });
+}
+
+/// Helper function to draw the placeholder of an `<audio controls>` player, consisting of a play button,
+/// a timeline and a volume icon.
+fn render_audio_controls(dimensions: Rect, renderer: &mut HtmlRenderer) {
+    renderer.push_rect(RenderRect {
+        rect: dimensions,
+        background: AUDIO_BACKGROUND_COLOR,
+        radii: CornerRadii::default(),
+    });
+
+    let x = dimensions.x as f32;
+    let y = dimensions.y as f32;
+    let w = dimensions.width as f32;
+    let cy = y + dimensions.height as f32 / 2.0;
+    let icon = (dimensions.height as f32 * 0.3).min(16.0);
+
+    if w < icon * 6.0 {
+        return;
+    }
+
+    let play_x = x + icon;
+    renderer.push_tri(RenderTri {
+        p0: [play_x, cy - icon / 2.0],
+        p1: [play_x + icon, cy],
+        p2: [play_x, cy + icon / 2.0],
+        color: AUDIO_CONTROL_COLOR,
+    });
+
+    let volume_x = x + w - icon * 2.5;
+    renderer.push_rect(RenderRect {
+        rect: Rect::new(f64::from(volume_x), f64::from(cy - icon / 4.0), f64::from(icon / 3.0), f64::from(icon / 2.0)),
+        background: AUDIO_CONTROL_COLOR,
+        radii: CornerRadii::default(),
+    });
+    renderer.push_tri(RenderTri {
+        p0: [volume_x, cy],
+        p1: [volume_x + icon, cy - icon / 2.0],
+        p2: [volume_x + icon, cy + icon / 2.0],
+        color: AUDIO_CONTROL_COLOR,
+    });
+
+    let track_x = play_x + icon * 2.0;
+    let track_w = volume_x - icon - track_x;
+    if track_w > 0.0 {
+        renderer.push_rect(RenderRect {
+            rect: Rect::new(f64::from(track_x), f64::from(cy - 2.0), f64::from(track_w), 4.0),
+            background: AUDIO_TRACK_COLOR,
+            radii: CornerRadii::default(),
+        });
+    }
+}
+
+/// The square in the bottom-right corner of the padding box of a resizable element that the user drags to resize it.
+pub fn resize_handle(node: &LayoutNode) -> Option<Rect> {
+    if node.resize == Resize::None {
+        return None;
+    }
+
+    let right = node.dimensions.x + node.dimensions.width + node.padding.horizontal() - node.border.right;
+    let bottom = node.dimensions.y + node.dimensions.height + node.padding.vertical() - node.border.bottom;
+    let size = RESIZE_HANDLE_SIZE
+        .min(node.dimensions.width)
+        .min(node.dimensions.height);
+
+    (size > 0.0).then_some(Rect::new(right - size, bottom - size, size, size))
+}
+
+/// Helper function to draw the triangular resize handle of a resizable element.
+///
+/// The triangle is drawn as rows of rectangles, since triangles are drawn before the backgrounds they would be
+/// hidden behind.
+fn render_resize_handle(handle: Rect, renderer: &mut HtmlRenderer) {
+    let rows = handle.height.ceil() as usize;
+
+    for row in 0..rows {
+        let row_y = handle.y + row as f64;
+        let row_width = (row as f64 + 1.0).min(handle.width);
+
+        renderer.push_rect(RenderRect {
+            rect: Rect::new(handle.x + handle.width - row_width, row_y, row_width, 1.0),
+            background: RESIZE_HANDLE_COLOR,
+            radii: CornerRadii::default(),
+        });
+    }
+}
+
+/// Helper function to draw the field of an `<input>` or `<textarea>` whose author styles do not provide one.
+fn render_text_control_frame(node: &LayoutNode, renderer: &mut HtmlRenderer) {
+    if node.colors.background_color.a <= 0.0 {
+        renderer.push_rect(RenderRect {
+            rect: node.dimensions,
+            background: TEXT_CONTROL_BACKGROUND_COLOR,
+            radii: CornerRadii::default(),
+        });
+    }
+
+    let border = node.border;
+    if border.top > 0.0 || border.right > 0.0 || border.bottom > 0.0 || border.left > 0.0 {
+        return;
+    }
+
+    render_control_outline(node.dimensions, renderer);
+}
+
+/// Helper function to draw the one pixel outline of a form control.
+fn render_control_outline(dimensions: Rect, renderer: &mut HtmlRenderer) {
+    let Rect {
+        x,
+        y,
+        width,
+        height,
+    } = dimensions;
+    for edge in [
+        Rect::new(x, y, width, 1.0),
+        Rect::new(x, y + height - 1.0, width, 1.0),
+        Rect::new(x, y, 1.0, height),
+        Rect::new(x + width - 1.0, y, 1.0, height),
+    ] {
+        renderer.push_rect(RenderRect {
+            rect: edge,
+            background: TEXT_CONTROL_BORDER_COLOR,
+            radii: CornerRadii::default(),
+        });
+    }
+}
+
+/// Helper function to draw the bar of a `<progress>` or `<meter>`, a track filled from its start up to `fraction` of
+/// its width. The indicator is drawn in the accent color unless a color is given, and the colors of the parts styled by
+/// the page take precedence over both.
+fn render_gauge(
+    dimensions: Rect,
+    fraction: f64,
+    indicator_color: Option<Color4f>,
+    part_colors: PartColors,
+    renderer: &mut HtmlRenderer,
+) {
+    renderer.push_rect(RenderRect {
+        rect: dimensions,
+        background: part_colors.track.unwrap_or(GAUGE_TRACK_COLOR),
+        radii: CornerRadii::default(),
+    });
+
+    let width = dimensions.width * fraction.clamp(0.0, 1.0);
+    if width <= 0.0 {
+        return;
+    }
+
+    renderer.push_rect(RenderRect {
+        rect: Rect::new(dimensions.x, dimensions.y, width, dimensions.height),
+        background: part_colors
+            .indicator
+            .or(indicator_color)
+            .unwrap_or(renderer.accent_color),
+        radii: CornerRadii::default(),
+    });
+}
+
+/// The thumb of an `<input type="range">` slider, which moves across the slider as the value goes from the minimum to
+/// the maximum.
+///
+/// # Arguments
+/// * `dimensions` - The content box of the slider.
+/// * `position` - The fraction of the range from the minimum to the maximum covered by the value.
+pub fn range_thumb(dimensions: Rect, position: f64) -> Rect {
+    let width = RANGE_THUMB_WIDTH.min(dimensions.width);
+    let x = dimensions.x + (dimensions.width - width) * position.clamp(0.0, 1.0);
+
+    Rect::new(x, dimensions.y, width, dimensions.height)
+}
+
+/// The fraction of the range of an `<input type="range">` slider picked by dragging its thumb to the given position,
+/// the inverse of [`range_thumb`] for the center of the thumb.
+///
+/// # Arguments
+/// * `dimensions` - The content box of the slider.
+/// * `x` - The horizontal position in page coordinates.
+pub fn range_position_at(dimensions: Rect, x: f64) -> f64 {
+    let width = RANGE_THUMB_WIDTH.min(dimensions.width);
+    let travel = dimensions.width - width;
+    if travel <= 0.0 {
+        return 0.0;
+    }
+
+    ((x - dimensions.x - width / 2.0) / travel).clamp(0.0, 1.0)
+}
+
+/// Helper function to draw an `<input type="range">` slider, a thin track filled up to the thumb with the accent color
+/// and the thumb on top. The colors of the parts styled by the page take precedence over those of the browser.
+fn render_range(dimensions: Rect, position: f64, part_colors: PartColors, renderer: &mut HtmlRenderer) {
+    let thumb = range_thumb(dimensions, position);
+    let track_height = RANGE_TRACK_HEIGHT.min(dimensions.height);
+    let track = Rect::new(
+        dimensions.x,
+        dimensions.y + (dimensions.height - track_height) / 2.0,
+        dimensions.width,
+        track_height,
+    );
+
+    renderer.push_rect(RenderRect {
+        rect: track,
+        background: part_colors.track.unwrap_or(GAUGE_TRACK_COLOR),
+        radii: CornerRadii::default(),
+    });
+
+    let filled = thumb.x + thumb.width / 2.0 - dimensions.x;
+    if filled > 0.0 {
+        renderer.push_rect(RenderRect {
+            rect: Rect::new(track.x, track.y, filled, track.height),
+            background: part_colors.indicator.unwrap_or(renderer.accent_color),
+            radii: CornerRadii::default(),
+        });
+    }
+
+    renderer.push_rect(RenderRect {
+        rect: thumb,
+        background: part_colors.thumb.unwrap_or(RANGE_THUMB_COLOR),
+        radii: CornerRadii::default(),
+    });
+    render_control_outline(thumb, renderer);
+}
+
+/// Helper function to draw a checkbox, an outlined box that is filled with the accent color and shows a checkmark
+/// while checked.
+fn render_checkbox(dimensions: Rect, checked: bool, renderer: &mut HtmlRenderer) {
+    let Rect {
+        x,
+        y,
+        width,
+        height,
+    } = dimensions;
+
+    if !checked {
+        renderer.push_rect(RenderRect {
+            rect: dimensions,
+            background: TEXT_CONTROL_BACKGROUND_COLOR,
+            radii: CornerRadii::default(),
+        });
+        render_control_outline(dimensions, renderer);
+        return;
+    }
+
+    renderer.push_rect(RenderRect {
+        rect: dimensions,
+        background: renderer.accent_color,
+        radii: CornerRadii::default(),
+    });
+
+    // The checkmark is two strokes, from the left edge down to the bottom and from there up to the right edge.
+    let point = |fx: f64, fy: f64| [(x + width * fx) as f32, (y + height * fy) as f32];
+    let stroke = (width.min(height) * 0.12) as f32;
+    for (from, to) in [
+        (point(0.2, 0.5), point(0.42, 0.72)),
+        (point(0.42, 0.72), point(0.8, 0.3)),
+    ] {
+        render_stroke(from, to, stroke, CHECKMARK_COLOR, renderer);
+    }
+}
+
+/// Helper function to draw a radio button, an outlined circle with a dot in the accent color while checked.
+fn render_radio(dimensions: Rect, checked: bool, renderer: &mut HtmlRenderer) {
+    let center = [
+        (dimensions.x + dimensions.width / 2.0) as f32,
+        (dimensions.y + dimensions.height / 2.0) as f32,
+    ];
+    let radius = (dimensions.width.min(dimensions.height) / 2.0) as f32;
+    if radius <= 1.0 {
+        return;
+    }
+
+    let ring = if checked {
+        renderer.accent_color
+    } else {
+        TEXT_CONTROL_BORDER_COLOR
+    };
+
+    render_circle(center, radius, ring, renderer);
+    render_circle(center, radius - 1.0, TEXT_CONTROL_BACKGROUND_COLOR, renderer);
+
+    if checked {
+        render_circle(center, radius * 0.5, renderer.accent_color, renderer);
+    }
+}
+
+/// Helper function to draw a filled circle as a fan of triangles.
+fn render_circle(center: [f32; 2], radius: f32, color: Color4f, renderer: &mut HtmlRenderer) {
+    let point = |index: usize| {
+        let angle = index as f32 / CHECKABLE_SEGMENTS as f32 * std::f32::consts::TAU;
+        [
+            center[0] + radius * angle.cos(),
+            center[1] + radius * angle.sin(),
+        ]
+    };
+
+    for index in 0..CHECKABLE_SEGMENTS {
+        renderer.push_tri(RenderTri {
+            p0: center,
+            p1: point(index),
+            p2: point(index + 1),
+            color,
         });
     }
 }
 
+/// Helper function to draw a straight line of the given thickness as two triangles.
+fn render_stroke(from: [f32; 2], to: [f32; 2], thickness: f32, color: Color4f, renderer: &mut HtmlRenderer) {
+    let (dx, dy) = (to[0] - from[0], to[1] - from[1]);
+    let length = dx.hypot(dy);
+    if length <= 0.0 {
+        return;
+    }
+
+    let (nx, ny) = (-dy / length * thickness / 2.0, dx / length * thickness / 2.0);
+    let corners = [
+        [from[0] + nx, from[1] + ny],
+        [to[0] + nx, to[1] + ny],
+        [to[0] - nx, to[1] - ny],
+        [from[0] - nx, from[1] - ny],
+    ];
+
+    renderer.push_tri(RenderTri {
+        p0: corners[0],
+        p1: corners[1],
+        p2: corners[2],
+        color,
+    });
+    renderer.push_tri(RenderTri {
+        p0: corners[0],
+        p1: corners[2],
+        p2: corners[3],
+        color,
+    });
+}
+
+/// Helper function to draw the downward arrow at the end of a `<select>` dropdown, in its text color.
+fn render_select_arrow(node: &LayoutNode, color: Color4f, renderer: &mut HtmlRenderer) {
+    let dimensions = node.dimensions;
+    if dimensions.width < SELECT_ARROW_WIDTH {
+        return;
+    }
+
+    let cx = (dimensions.x + dimensions.width - SELECT_ARROW_WIDTH / 2.0) as f32;
+    let cy = (dimensions.y + dimensions.height / 2.0) as f32;
+
+    renderer.push_tri(RenderTri {
+        p0: [cx - SELECT_ARROW_SIZE / 2.0, cy - SELECT_ARROW_SIZE / 4.0],
+        p1: [cx + SELECT_ARROW_SIZE / 2.0, cy - SELECT_ARROW_SIZE / 4.0],
+        p2: [cx, cy + SELECT_ARROW_SIZE / 4.0],
+        color,
+    });
+}
+
+/// Helper function to draw a red wavy line under a misspelled word, from `x0` to `x1` with its crests at `y`.
+fn render_spelling_wave(x0: f32, x1: f32, y: f32, renderer: &mut HtmlRenderer) {
+    let mut x = x0;
+    let mut up = false;
+
+    while x < x1 {
+        let next = (x + SPELLING_WAVE_LENGTH / 2.0).min(x1);
+        let (from_y, to_y) = if up {
+            (y + SPELLING_WAVE_HEIGHT, y)
+        } else {
+            (y, y + SPELLING_WAVE_HEIGHT)
+        };
+
+        render_stroke([x, from_y], [next, to_y], 1.0, SPELLING_ERROR_COLOR, renderer);
+
+        x = next;
+        up = !up;
+    }
+}
+
+/// Helper function to draw the caret, selection and spelling errors of the focused text control.
+pub fn collect_text_input_overlay(renderer: &mut HtmlRenderer, layout_tree: &LayoutTree, input: &TextInputController) {
+    let Some(node) = layout_tree
+        .nodes
+        .iter()
+        .flatten()
+        .find(|node| node.node_id == Some(input.node_id()) && node.image_data.is_some())
+    else {
+        return;
+    };
+
+    let display = input.display_text();
+
+    if let Some((start, end)) = input.selection_range() {
+        for (line, from, to) in input.selection_lines(start, end) {
+            let Some((x0, y, height)) = caret_position(node, &display, line, from) else {
+                continue;
+            };
+            let Some((x1, _, _)) = caret_position(node, &display, line, to) else {
+                continue;
+            };
+
+            renderer.push_rect(RenderRect {
+                rect: Rect::new(f64::from(x0), f64::from(y), f64::from((x1 - x0).max(2.0)), f64::from(height)),
+                background: TEXT_SELECTION_COLOR,
+                radii: CornerRadii::default(),
+            });
+        }
+    }
+
+    for (line, from, to) in input.misspelled_lines() {
+        let (Some((x0, y, height)), Some((x1, _, _))) =
+            (caret_position(node, &display, line, from), caret_position(node, &display, line, to))
+        else {
+            continue;
+        };
+
+        render_spelling_wave(x0, x1, y + height - SPELLING_WAVE_HEIGHT, renderer);
+    }
+
+    let (line, column) = input.cursor_line_column();
+    if let Some((x, y, height)) = caret_position(node, &display, line, column) {
+        renderer.push_rect(RenderRect {
+            rect: Rect::new(f64::from(x), f64::from(y), 1.0, f64::from(height)),
+            background: node.colors.color,
+            radii: CornerRadii::default(),
+        });
+    }
+}
+
+/// Helper function to draw the highlight of the text selected on the page, one rectangle per run of selected glyphs on
+/// a line.
+pub fn collect_text_selection_overlay(renderer: &mut HtmlRenderer, layout_tree: &LayoutTree) {
+    let Some(((start_node, start_offset), (end_node, end_offset))) = renderer.selection.range() else {
+        return;
+    };
+
+    let mut highlight: Option<Rect> = None;
+    for (rect, _) in layout_tree.glyphs_in_range(start_node, start_offset, end_node, end_offset) {
+        match &mut highlight {
+            Some(line) if line.y == rect.y && line.height == rect.height => {
+                line.width = (rect.x + rect.width).max(line.x + line.width) - line.x;
+            }
+            _ => {
+                if let Some(line) = highlight.replace(rect) {
+                    renderer.push_rect(RenderRect {
+                        rect: line,
+                        background: TEXT_SELECTION_COLOR,
+                        radii: CornerRadii::default(),
+                    });
+                }
+            }
+        }
+    }
+
+    if let Some(line) = highlight {
+        renderer.push_rect(RenderRect {
+            rect: line,
+            background: TEXT_SELECTION_COLOR,
+            radii: CornerRadii::default(),
+        });
+    }
+}
+
+/// Resolves the screen position and height of a caret placed before the character at `column` on `line`.
+fn caret_position(node: &LayoutNode, display: &str, line: usize, column: usize) -> Option<(f32, f32, f32)> {
+    let Some(fragment) = node.text_fragments.get(line) else {
+        // An empty control without a placeholder has no text, so the caret sits at the start of the field.
+        return (line == 0).then(|| {
+            (
+                (node.dimensions.x + TEXT_CONTROL_PADDING) as f32,
+                (node.dimensions.y + TEXT_CONTROL_PADDING) as f32,
+                (node.dimensions.height - TEXT_CONTROL_PADDING * 2.0).max(1.0) as f32,
+            )
+        });
+    };
+    let line_text = display.split('\n').nth(line).unwrap_or_default();
+    let byte_index = line_text
+        .char_indices()
+        .nth(column)
+        .map_or(line_text.len(), |(index, _)| index);
+
+    let offset = fragment
+        .buffers
+        .first()
+        .and_then(|buffer| {
+            buffer.layout_runs().next().map(|run| {
+                run.glyphs
+                    .iter()
+                    .find(|glyph| glyph.start >= byte_index)
+                    .map_or_else(|| run.line_w, |glyph| glyph.x)
+            })
+        })
+        .unwrap_or(0.0);
+
+    let x = (node.dimensions.x + fragment.size.x) as f32 + offset;
+    let y = (node.dimensions.y + fragment.size.y) as f32;
+
+    Some((x, y, fragment.size.height as f32))
+}
+
 /// Helper function to collect all render data from a layout tree with viewport culling
 pub fn collect_render_data_from_layout<'html>(
     image_ctx: &ImageContext,
@@ -155,30 +1044,78 @@ pub fn collect_render_data_from_layout<'html>(
     initial_bounds: Rect,
     scroll_offset: ScrollOffset,
 ) {
-    fn collect_node(
-        node_id: &LayoutNodeId,
-        image_ctx: &ImageContext,
-        renderer: &mut HtmlRenderer,
+    /// What every node of the tree is collected with.
+    struct Collection<'a> {
+        image_ctx: &'a ImageContext,
         initial_bounds: Rect,
         scroll_offset: ScrollOffset,
-        parent_visible: bool,
-    ) {
+    }
+
+    /// What a node takes over from its parent when it is collected.
+    #[derive(Clone, Copy)]
+    struct Parent {
+        visible: bool,
+
+        /// The blend group the parent is painted in.
+        layer: BlendLayer,
+
+        /// The color painted behind the children of the parent in its blend group.
+        behind: Color4f,
+    }
+
+    fn collect_node(node_id: &LayoutNodeId, collection: &Collection, parent: Parent, renderer: &mut HtmlRenderer) {
         let Some(Some(node)) = &renderer.layout_tree.nodes.get(node_id.index()) else {
             return;
         };
 
-        let self_visible = is_visible_node(node.dimensions, initial_bounds, scroll_offset);
+        let layer = if node.mix_blend_mode == layout::BlendMode::Normal {
+            parent.layer
+        } else {
+            BlendLayer {
+                mode: node.mix_blend_mode.into(),
+                backdrop: parent.behind,
+            }
+        };
 
-        if self_visible || parent_visible {
-            render_node(node, image_ctx, renderer);
+        // An isolated group, and the group of a blended element, start out empty, so its descendants only blend with
+        // the backgrounds painted inside of it.
+        let group_start = if node.isolation == Isolation::Isolate || node.mix_blend_mode != layout::BlendMode::Normal {
+            Color4f::TRANSPARENT
+        } else {
+            parent.behind
+        };
+
+        let visible = is_visible_node(node.dimensions, collection.initial_bounds, collection.scroll_offset);
+
+        if visible || parent.visible {
+            render_node(node, layer, collection.image_ctx, renderer);
+
+            if let Some(handle) = resize_handle(node) {
+                render_resize_handle(handle, renderer);
+            }
         }
 
+        let parent = Parent {
+            visible,
+            layer,
+            behind: BlendMode::Normal.composite(group_start, node.colors.background_color),
+        };
         for child in &node.children {
-            collect_node(child, image_ctx, renderer, initial_bounds, scroll_offset, self_visible);
+            collect_node(child, collection, parent, renderer);
         }
     }
 
-    for root in &layout_tree.root_nodes {
-        collect_node(root, image_ctx, renderer, initial_bounds, scroll_offset, false);
+    let collection = Collection {
+        image_ctx,
+        initial_bounds,
+        scroll_offset,
+    };
+    let root = Parent {
+        visible: false,
+        layer: BlendLayer::NORMAL,
+        behind: Color4f::TRANSPARENT,
+    };
+    for root_id in &layout_tree.root_nodes {
+        collect_node(root_id, &collection, root, renderer);
     }
 }
@@ -1,6 +1,7 @@
 use iced::window::{Icon, icon::from_rgba};
 use image::{GenericImageView, ImageFormat};
 use layout::LayoutImage;
+use renderer::{SvgRenderer, errors::RenderError};
 use tracing::error;
 
 /// Loads an icon from a byte vector and converts it to an Iced window icon.
@@ -25,12 +26,18 @@ pub fn load_icon(data: &[u8]) -> Icon {
 }
 
 /// Decode raw image bytes into RGBA pixel data.
-pub fn decode_image_bytes(url: &str, bytes: &[u8], format: Option<ImageFormat>) -> Result<LayoutImage, String> {
+///
+/// # Errors
+/// * [`RenderError::ImageDecode`] - If the bytes are not an image of the given format, or of any known format.
+pub fn decode_image_bytes(url: &str, bytes: &[u8], format: Option<ImageFormat>) -> Result<LayoutImage, RenderError> {
     let img = match format {
-        Some(fmt) => image::load_from_memory_with_format(bytes, fmt)
-            .map_err(|e| format!("Failed to decode image {url} with format {fmt:?}: {e}"))?,
-        None => image::load_from_memory(bytes).map_err(|e| format!("Failed to decode image {url}: {e}"))?,
-    };
+        Some(fmt) => image::load_from_memory_with_format(bytes, fmt),
+        None => image::load_from_memory(bytes),
+    }
+    .map_err(|e| RenderError::ImageDecode {
+        url: url.to_string(),
+        message: e.to_string(),
+    })?;
 
     let (width, height) = img.dimensions();
     let rgba = img.to_rgba8().into_raw();
@@ -39,5 +46,28 @@ pub fn decode_image_bytes(url: &str, bytes: &[u8], format: Option<ImageFormat>)
         rgba,
         width,
         height,
+        svg_source: None,
     })
 }
+
+/// Decode an SVG document into RGBA pixel data at its intrinsic size. The returned image keeps the SVG source, so
+/// it is rasterized again at the size it is drawn at.
+///
+/// # Errors
+/// * [`RenderError::Svg`] - If the document cannot be parsed or has no size to be rasterized at.
+pub fn decode_svg_bytes(url: &str, bytes: &[u8]) -> Result<LayoutImage, RenderError> {
+    SvgRenderer::parse(bytes)
+        .and_then(|svg| svg.render_intrinsic())
+        .map_err(|source| RenderError::Svg {
+            url: url.to_string(),
+            source,
+        })
+}
+
+/// Whether a resource with the given content type and URL is an SVG document.
+pub fn is_svg(content_type: &str, url: &str) -> bool {
+    content_type.contains("image/svg+xml")
+        || std::path::Path::new(url.split(['?', '#']).next().unwrap_or_default())
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"))
+}
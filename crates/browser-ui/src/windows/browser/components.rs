@@ -1,5 +1,7 @@
 //! Components for the browser view.
 
+pub mod dropdown;
 pub mod navigation;
 pub mod search;
+pub mod spelling;
 pub mod tab;
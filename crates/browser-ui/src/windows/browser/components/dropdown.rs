@@ -0,0 +1,96 @@
+use html_dom::{NodeId, SelectElement};
+use iced::{
+    Background, Border, Color, Element, Length, Padding,
+    widget::{Column, Stack, button, container, mouse_area, scrollable, text},
+    window::Id,
+};
+
+use crate::events::{BrowserEvent, EngineRequest, Event};
+
+/// The tallest a dropdown menu grows before its options scroll.
+const MAX_MENU_HEIGHT: f32 = 300.0;
+
+/// The popup menu of an open `<select>` dropdown, listing its options below the select.
+pub struct DropdownMenu;
+
+impl DropdownMenu {
+    /// Renders the menu at the given position over the page, with a transparent backdrop that closes the menu when
+    /// clicked outside of it.
+    ///
+    /// # Arguments
+    /// * `window_id` - The window the select belongs to.
+    /// * `select_node_id` - The node of the `<select>` element.
+    /// * `select` - The options of the select.
+    /// * `x`, `y` - The top left corner of the menu, relative to the page viewport.
+    /// * `width` - The width of the menu, at least the width of the select.
+    pub fn render<'a>(
+        window_id: Id,
+        select_node_id: NodeId,
+        select: &SelectElement,
+        (x, y): (f32, f32),
+        width: f32,
+    ) -> Element<'a, Event> {
+        let selected_index = select.selected_index();
+
+        let options = select.options.iter().enumerate().map(|(index, option)| {
+            let is_selected = selected_index == Some(index);
+            let disabled = option.disabled;
+
+            button(text(option.label.clone()).size(14))
+                .width(Length::Fill)
+                .padding(Padding::from([4, 8]))
+                .style(move |_, status| {
+                    let background = match status {
+                        _ if disabled => Color::TRANSPARENT,
+                        button::Status::Hovered | button::Status::Pressed => Color::from_rgb8(232, 240, 254),
+                        _ if is_selected => Color::from_rgb8(241, 243, 244),
+                        _ => Color::TRANSPARENT,
+                    };
+
+                    button::Style {
+                        background: Some(Background::Color(background)),
+                        text_color: if disabled {
+                            Color::from_rgb8(154, 160, 166)
+                        } else {
+                            Color::from_rgb8(32, 33, 36)
+                        },
+                        ..Default::default()
+                    }
+                })
+                .on_press_maybe((!disabled).then_some(Event::EngineRequest(EngineRequest::SelectOption(
+                    window_id,
+                    select_node_id,
+                    index,
+                ))))
+                .into()
+        });
+
+        let menu = container(scrollable(Column::with_children(options)))
+            .width(Length::Fixed(width))
+            .max_height(MAX_MENU_HEIGHT)
+            .padding(Padding::from([4, 0]))
+            .style(|_| container::Style {
+                background: Some(Background::Color(Color::from_rgb8(255, 255, 255))),
+                border: Border {
+                    color: Color::from_rgb8(218, 220, 224),
+                    width: 1.0,
+                    radius: 4.0.into(),
+                },
+                ..Default::default()
+            });
+
+        let backdrop = mouse_area(container("").width(Length::Fill).height(Length::Fill))
+            .on_press(Event::Browser(BrowserEvent::ToggleDropdown(window_id, None)));
+
+        Stack::new()
+            .push(backdrop)
+            .push(container(menu).padding(Padding {
+                top: y.max(0.0),
+                left: x.max(0.0),
+                ..Padding::ZERO
+            }))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+}
@@ -0,0 +1,61 @@
+use iced::{
+    Background, Border, Color, Element, Padding,
+    widget::{Row, button, container, text},
+    window::Id,
+};
+
+use crate::{
+    core::TextInputAction,
+    events::{BrowserEvent, Event},
+};
+
+/// The suggestions offered for the misspelled word at the cursor of the focused text control, shown below the control.
+pub struct SpellingSuggestions;
+
+impl SpellingSuggestions {
+    /// Renders the suggestions at the given position over the page, each replacing the misspelled word when clicked.
+    ///
+    /// # Arguments
+    /// * `window_id` - The window the text control belongs to.
+    /// * `suggestions` - The suggestions for the misspelled word, most likely first.
+    /// * `x`, `y` - The top left corner of the suggestions, relative to the page viewport.
+    pub fn render<'a>(window_id: Id, suggestions: &[String], (x, y): (f32, f32)) -> Element<'a, Event> {
+        let buttons = suggestions.iter().map(|suggestion| {
+            button(text(suggestion.clone()).size(13))
+                .padding(Padding::from([4, 8]))
+                .style(|_, status| button::Style {
+                    background: Some(Background::Color(match status {
+                        button::Status::Hovered | button::Status::Pressed => Color::from_rgb8(232, 240, 254),
+                        _ => Color::TRANSPARENT,
+                    })),
+                    text_color: Color::from_rgb8(32, 33, 36),
+                    ..Default::default()
+                })
+                .on_press(Event::Browser(BrowserEvent::EditInput(
+                    window_id,
+                    TextInputAction::Correct(suggestion.clone()),
+                )))
+                .into()
+        });
+
+        let suggestions = container(Row::with_children(buttons))
+            .padding(Padding::from([2, 4]))
+            .style(|_| container::Style {
+                background: Some(Background::Color(Color::from_rgb8(255, 255, 255))),
+                border: Border {
+                    color: Color::from_rgb8(218, 220, 224),
+                    width: 1.0,
+                    radius: 4.0.into(),
+                },
+                ..Default::default()
+            });
+
+        container(suggestions)
+            .padding(Padding {
+                top: y.max(0.0),
+                left: x.max(0.0),
+                ..Padding::ZERO
+            })
+            .into()
+    }
+}
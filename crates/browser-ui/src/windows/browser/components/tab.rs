@@ -15,6 +15,9 @@ use crate::{
     events::{BrowserEvent, Event},
 };
 
+/// The frames of the spinner shown in front of the title of a loading tab.
+const LOADING_SPINNER_FRAMES: [&str; 4] = ["|", "/", "-", "\\"];
+
 pub struct TabButton;
 
 impl TabButton {
@@ -24,17 +27,28 @@ impl TabButton {
         tab: &'app Tab,
         active_tab_id: TabId,
     ) -> MouseArea<'app, Event> {
-        let tab_title = text(
-            tab.page
-                .as_ref()
-                .map_or("about:blank", |ctx| ctx.metadata.title.trim()),
-        )
+        let tab_title = text(tab.page.as_ref().map_or_else(
+            || {
+                if tab.loading.is_some() {
+                    "Loading..."
+                } else {
+                    "about:blank"
+                }
+            },
+            |ctx| ctx.metadata.title.trim(),
+        ))
         .width(Length::Shrink)
         .height(Length::Shrink);
 
         let mut tab_title_row = Row::new();
 
-        if let Some(favicon) = &tab
+        if let Some(frame) = tab.loading {
+            tab_title_row = tab_title_row.push(
+                text(LOADING_SPINNER_FRAMES[frame % LOADING_SPINNER_FRAMES.len()])
+                    .width(Length::Fixed(16.0))
+                    .center(),
+            );
+        } else if let Some(favicon) = &tab
             .page
             .as_ref()
             .and_then(|ctx| ctx.metadata.favicon.as_ref())
@@ -1,8 +1,101 @@
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use browser_core::{Commandable, Download, DownloadState, EngineCommand, EngineResponse, SessionEntry};
+use browser_preferences::BrowserPreferences;
+use css_style::InputDevice;
+use css_values::media::ColorScheme;
 use iced::Task;
+use tracing::debug;
+use url::Url;
+
+use crate::{
+    core::{Application, Tab, TabId, apply_accessibility_preferences},
+    events::{BrowserEvent, Event},
+    windows::browser::window::BrowserWindow,
+};
 
-use crate::{core::Application, events::Event, windows::browser::window::BrowserWindow};
+/// The interval at which the progress of the downloads is refreshed while any is in progress.
+const DOWNLOAD_POLL_INTERVAL: Duration = Duration::from_millis(500);
 
 impl BrowserWindow {
+    /// Schedules a refresh of the progress of the downloads, unless one is already scheduled.
+    pub fn poll_downloads_later(application: &mut Application, window_id: iced::window::Id) -> Task<Event> {
+        if application.polling_downloads {
+            return Task::none();
+        }
+
+        application.polling_downloads = true;
+        Self::schedule_download_poll(window_id)
+    }
+
+    /// Asks the browser for the progress of the downloads with a `ListDownloads` command.
+    pub fn poll_downloads(application: &mut Application, window_id: iced::window::Id) -> Task<Event> {
+        let Some(tab_id) = application
+            .browser_windows
+            .get(&window_id)
+            .map(|ctx| ctx.tab_manager.active_tab_id())
+        else {
+            application.polling_downloads = false;
+            return Task::none();
+        };
+
+        let browser = Arc::clone(&application.browser);
+        Task::perform(async move { browser.execute(EngineCommand::ListDownloads).await }, move |result| match result {
+            Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+            Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+        })
+    }
+
+    /// Loads the unpacked extension in a directory with a `LoadExtension` command.
+    pub fn load_extension(application: &Application, window_id: iced::window::Id, path: PathBuf) -> Task<Event> {
+        let Some(tab_id) = application
+            .browser_windows
+            .get(&window_id)
+            .map(|ctx| ctx.tab_manager.active_tab_id())
+        else {
+            return Task::none();
+        };
+
+        let browser = Arc::clone(&application.browser);
+        Task::perform(async move { browser.execute(EngineCommand::LoadExtension { path }).await }, move |result| {
+            match result {
+                Ok(event) => Event::EngineResponse(window_id, tab_id, Box::new(event)),
+                Err(err) => Event::EngineResponse(window_id, tab_id, Box::new(EngineResponse::Error(err))),
+            }
+        })
+    }
+
+    /// Shows the reported progress of the downloads, polling again while any of them is still in progress.
+    pub fn on_download_list(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        downloads: Vec<Download>,
+    ) -> Task<Event> {
+        // A download that started after the list was taken is not in it yet, and is still in progress.
+        let in_progress = downloads
+            .iter()
+            .any(|download| download.state == DownloadState::InProgress)
+            || application
+                .downloads
+                .iter()
+                .any(|known| downloads.iter().all(|download| download.id != known.id));
+        application.downloads = downloads;
+
+        if in_progress {
+            application.polling_downloads = true;
+            Self::schedule_download_poll(window_id)
+        } else {
+            application.polling_downloads = false;
+            Task::none()
+        }
+    }
+
+    fn schedule_download_poll(window_id: iced::window::Id) -> Task<Event> {
+        Task::perform(tokio::time::sleep(DOWNLOAD_POLL_INTERVAL), move |()| {
+            Event::Browser(BrowserEvent::PollDownloads(window_id))
+        })
+    }
+
     /// Handles the change of the current URL when a `UrlChanged` event is received from the UI.
     pub fn on_url_change(application: &mut Application, window_id: iced::window::Id, url: String) -> Task<Event> {
         if let Some(ctx) = application.browser_windows.get_mut(&window_id) {
@@ -12,14 +105,59 @@ impl BrowserWindow {
         Task::none()
     }
 
+    /// Handles a page that finished loading in a tab by showing its URL in the address bar, when the tab is the active
+    /// tab of the window. The navigation buttons follow the history of the active tab on their own.
+    pub fn on_navigation_state_changed(
+        application: &mut Application,
+        window_id: iced::window::Id,
+        tab_id: TabId,
+        can_go_back: bool,
+        can_go_forward: bool,
+        current_url: &Url,
+        current_title: Option<&str>,
+    ) -> Task<Event> {
+        debug!(%current_url, ?current_title, can_go_back, can_go_forward, "Navigation state changed");
+
+        if let Some(ctx) = application.browser_windows.get_mut(&window_id)
+            && ctx
+                .tab_manager
+                .active_tab()
+                .is_some_and(|tab| tab.id == tab_id)
+        {
+            ctx.current_url = current_url.to_string();
+        }
+
+        application.save_session(window_id)
+    }
+
+    /// Offers to restore the tabs of the session that was open when the browser crashed.
+    pub fn on_crash_recovery_available(application: &mut Application, tabs: Vec<SessionEntry>) -> Task<Event> {
+        application.crash_recovery = Some(tabs);
+
+        Task::none()
+    }
+
+    /// Withdraws the offer to restore the tabs of the crashed session.
+    pub fn dismiss_crash_recovery(application: &mut Application) -> Task<Event> {
+        application.crash_recovery = None;
+
+        Task::none()
+    }
+
     /// Handles the scrolling of content when a `ContentScrolled` event is received from the UI,
-    /// updating the scroll offset of the active tab.
+    /// updating the scroll offset of the active tab and laying out the `content-visibility: auto` content that
+    /// comes into view.
     pub fn on_scrolled(application: &mut Application, window_id: iced::window::Id, x: f32, y: f32) -> Task<Event> {
         if let Some(ctx) = application.browser_windows.get_mut(&window_id)
             && let Some(tab) = ctx.tab_manager.active_tab_mut()
         {
             tab.scroll_offset.x = x;
             tab.scroll_offset.y = y;
+
+            return Task::batch([
+                Tab::reveal_skipped_content(ctx.viewport, &ctx.text_context, &application.preferences, tab, window_id),
+                Tab::load_lazy_images(&application.browser, ctx.viewport, &application.preferences, tab, window_id),
+            ]);
         }
 
         Task::none()
@@ -43,9 +181,85 @@ impl BrowserWindow {
 
             let mut tc = ctx.text_context.lock().unwrap();
 
-            tab.resize_current_page(new_viewport, &mut tc, &application.preferences);
+            tab.resize_current_page(new_viewport, &mut tc, &application.preferences, application.system_preferences);
+        }
+
+        Task::none()
+    }
+
+    /// Handles a change of the color scheme of the operating system, recomputing the styles and layout of every loaded
+    /// page so that `prefers-color-scheme` media queries are evaluated against the new scheme.
+    pub fn on_system_theme_changed(application: &mut Application, color_scheme: ColorScheme) -> Task<Event> {
+        if application.system_preferences.prefers_color_scheme == color_scheme {
+            return Task::none();
+        }
+
+        application.system_preferences.prefers_color_scheme = color_scheme;
+        Self::restyle_pages(application);
+
+        Task::none()
+    }
+
+    /// Handles the user switching between a mouse and a touchscreen, recomputing the styles and layout of every loaded
+    /// page so that `pointer` and `hover` media queries are evaluated against the new device.
+    pub fn on_input_device_changed(application: &mut Application, input_device: InputDevice) -> Task<Event> {
+        if application.system_preferences.input_device == input_device {
+            return Task::none();
+        }
+
+        application.system_preferences.input_device = input_device;
+        Self::restyle_pages(application);
+
+        Task::none()
+    }
+
+    /// Handles a change of the scale factor of a window, recomputing the styles and layout of every loaded page so that
+    /// the images of `image-set()` are picked for the new device pixel ratio.
+    pub fn on_scale_factor_changed(application: &mut Application, scale_factor: f64) -> Task<Event> {
+        if (application.system_preferences.device_pixel_ratio - scale_factor).abs() < f64::EPSILON {
+            return Task::none();
+        }
+
+        application.system_preferences.device_pixel_ratio = scale_factor;
+        Self::restyle_pages(application);
+
+        Task::none()
+    }
+
+    /// Handles the preferences file being changed on disk. The new theme of the browser UI applies on the next frame,
+    /// while the loaded pages are only styled again when forced dark mode, high contrast, or forced colors were turned
+    /// on or off, or the theme changed between a light and a dark one.
+    pub fn on_preferences_changed(application: &mut Application, preferences: BrowserPreferences) -> Task<Event> {
+        let force_dark_changed = application.preferences.force_dark() != preferences.force_dark();
+        let category_changed = application.preferences.theme().category != preferences.theme().category;
+        let accessibility_changed = apply_accessibility_preferences(&mut application.system_preferences, &preferences);
+
+        application.preferences = Arc::new(preferences);
+
+        if force_dark_changed || category_changed || accessibility_changed {
+            Self::restyle_pages(application);
         }
 
         Task::none()
     }
+
+    /// Recomputes the styles and layout of the loaded page of every tab.
+    fn restyle_pages(application: &mut Application) {
+        for ctx in application.browser_windows.values_mut() {
+            let mut tc = ctx.text_context.lock().unwrap();
+
+            for tab in ctx.tab_manager.tabs_mut() {
+                if tab.page.is_none() {
+                    continue;
+                }
+
+                tab.resize_current_page(
+                    ctx.viewport,
+                    &mut tc,
+                    &application.preferences,
+                    application.system_preferences,
+                );
+            }
+        }
+    }
 }
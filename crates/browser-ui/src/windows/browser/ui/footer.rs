@@ -1,15 +1,17 @@
 use std::str::FromStr;
 
+use browser_core::{Download, DownloadState};
 use iced::{
-    Background, Border, Color, Length,
+    Background, Border, Color, Element, Length,
+    alignment::Vertical,
     border::Radius,
-    widget::{button, container},
+    widget::{button, container, row, text},
     window::Id,
 };
 
 use crate::{
     core::{Application, WindowType},
-    events::{Event, WindowEvent},
+    events::{BrowserEvent, EngineRequest, Event, WindowEvent},
 };
 
 pub struct BrowserFooter;
@@ -17,7 +19,8 @@ pub struct BrowserFooter;
 impl BrowserFooter {
     /// Renders the footer of the browser window.
     ///
-    /// Contains a button to open the devtools!
+    /// Offers to restore the tabs of a crashed session, lists the downloads of the session with their progress, and
+    /// contains a button to open the devtools!
     pub fn render(app: &Application, window_id: Id) -> container::Container<'_, Event> {
         let theme = app.preferences.theme();
 
@@ -36,27 +39,71 @@ impl BrowserFooter {
             |devtools| Event::Window(WindowEvent::CloseWindow(devtools.window_id)),
         );
 
-        container(
-            button("Open DevTools")
-                .style(|_, status| button::Style {
-                    background: match status {
-                        button::Status::Hovered => {
-                            Some(Background::Color(Color::from_str(&theme.colors.primary).unwrap()).scale_alpha(0.8))
-                        }
-                        _ => Some(Background::Color(Color::from_str(&theme.colors.primary).unwrap())),
-                    },
-                    border: Border {
-                        radius: Radius::new(theme.style.border_radius),
-                        ..Default::default()
-                    },
+        let crash_recovery = app.crash_recovery.as_ref().map(|tabs| {
+            row![
+                text(format!("The browser did not shut down correctly, restore {} tabs?", tabs.len())),
+                button("Restore")
+                    .on_press(Event::EngineRequest(EngineRequest::RestoreCrashSession(window_id)))
+                    .padding(10),
+                button("Dismiss")
+                    .style(button::secondary)
+                    .on_press(Event::Browser(BrowserEvent::DismissCrashRecovery))
+                    .padding(10),
+            ]
+            .align_y(Vertical::Center)
+            .spacing(10.0)
+        });
+
+        let downloads = row(app
+            .downloads
+            .iter()
+            .map(|download| text(download_label(download)).into())
+            .collect::<Vec<_>>())
+        .spacing(20.0)
+        .width(Length::Fill);
+
+        let devtools_button = button("Open DevTools")
+            .style(|_, status| button::Style {
+                background: match status {
+                    button::Status::Hovered => {
+                        Some(Background::Color(Color::from_str(&theme.colors.primary).unwrap()).scale_alpha(0.8))
+                    }
+                    _ => Some(Background::Color(Color::from_str(&theme.colors.primary).unwrap())),
+                },
+                border: Border {
+                    radius: Radius::new(theme.style.border_radius),
                     ..Default::default()
-                })
-                .on_press(toggle_devtools_event)
-                .padding(10),
-        )
-        .style(|_| container::background(Background::Color(Color::from_str(theme.colors.foreground.as_str()).unwrap())))
-        .padding(10.0)
-        .width(Length::Fill)
-        .height(Length::Shrink)
+                },
+                ..Default::default()
+            })
+            .on_press(toggle_devtools_event)
+            .padding(10);
+
+        let mut children: Vec<Element<'_, Event>> = Vec::with_capacity(3);
+        children.extend(crash_recovery.map(Element::from));
+        children.push(downloads.into());
+        children.push(devtools_button.into());
+
+        container(row(children).align_y(Vertical::Center).spacing(10.0))
+            .style(|_| {
+                container::background(Background::Color(Color::from_str(theme.colors.foreground.as_str()).unwrap()))
+            })
+            .padding(10.0)
+            .width(Length::Fill)
+            .height(Length::Shrink)
     }
 }
+
+/// The name of a downloaded file followed by its progress, as a percentage when its size is known.
+fn download_label(download: &Download) -> String {
+    let progress = match &download.state {
+        DownloadState::InProgress => match download.content_length.filter(|length| *length > 0) {
+            Some(length) => format!("{}%", download.received_bytes.saturating_mul(100) / length),
+            None => format!("{} KiB", download.received_bytes / 1024),
+        },
+        DownloadState::Completed => "Done".to_string(),
+        DownloadState::Failed(_) => "Failed".to_string(),
+    };
+
+    format!("{} \u{2014} {progress}", download.filename)
+}
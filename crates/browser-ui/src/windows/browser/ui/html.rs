@@ -1,15 +1,20 @@
 use std::str::FromStr;
 
+use html_dom::{NodeId, SelectElement, ValidationError};
 use iced::{
-    Background, Color, Length,
-    widget::{Shader, container, shader},
+    Background, Border, Color, Element, Length, Padding,
+    widget::{Shader, Stack, container, shader, text},
 };
 use layout::{LayoutTree, Rect};
 
 use crate::{
-    core::{Application, Page, ScrollOffset},
+    core::{Application, Page, ScrollOffset, TextInputController},
     events::Event,
-    renderer::{program::HtmlRenderer, viewport::collect_render_data_from_layout},
+    renderer::{
+        program::HtmlRenderer,
+        viewport::{collect_render_data_from_layout, collect_text_input_overlay, collect_text_selection_overlay},
+    },
+    windows::browser::components::{dropdown::DropdownMenu, spelling::SpellingSuggestions},
 };
 
 pub struct BrowserHtml<'renderer> {
@@ -17,6 +22,9 @@ pub struct BrowserHtml<'renderer> {
     layout_tree: &'renderer LayoutTree,
     initial_bounds: Rect,
     scroll_offset: ScrollOffset,
+    validation_errors: &'renderer [ValidationError],
+    focused_input: Option<&'renderer TextInputController>,
+    open_dropdown: Option<NodeId>,
 }
 
 impl<'renderer> BrowserHtml<'renderer> {
@@ -25,15 +33,117 @@ impl<'renderer> BrowserHtml<'renderer> {
         layout_tree: &'renderer LayoutTree,
         initial_bounds: Rect,
         scroll_offset: ScrollOffset,
+        validation_errors: &'renderer [ValidationError],
+        focused_input: Option<&'renderer TextInputController>,
     ) -> Self {
         Self {
             renderer,
             layout_tree,
             initial_bounds,
             scroll_offset,
+            validation_errors,
+            focused_input,
+            open_dropdown: None,
         }
     }
 
+    /// Sets the `<select>` whose dropdown menu is shown over the page.
+    #[must_use]
+    pub const fn with_open_dropdown(mut self, open_dropdown: Option<NodeId>) -> Self {
+        self.open_dropdown = open_dropdown;
+        self
+    }
+
+    /// Builds the menu of the open `<select>` dropdown, positioned just below the select.
+    fn dropdown_menu(&self) -> Option<Element<'renderer, Event>> {
+        let select_node_id = self.open_dropdown?;
+        let dom = self.renderer.dom_tree;
+        let select = SelectElement::from_node(dom.get_node(&select_node_id)?, dom)?;
+
+        let node = self
+            .layout_tree
+            .nodes
+            .iter()
+            .flatten()
+            .find(|node| node.node_id == Some(select_node_id) && node.image_data.is_some())?;
+
+        let x = node.dimensions.x as f32 - self.scroll_offset.x;
+        let y = (node.dimensions.y + node.dimensions.height) as f32 - self.scroll_offset.y;
+
+        Some(DropdownMenu::render(
+            self.renderer.window_id,
+            select_node_id,
+            &select,
+            (x, y),
+            node.dimensions.width as f32,
+        ))
+    }
+
+    /// Builds the suggestions for the misspelled word at the cursor of the focused text control, positioned just below
+    /// the control.
+    fn spelling_suggestions(&self) -> Option<Element<'renderer, Event>> {
+        let input = self.focused_input?;
+        let misspelling = input
+            .misspelling_at_cursor()
+            .filter(|misspelling| !misspelling.suggestions.is_empty())?;
+
+        let node = self
+            .layout_tree
+            .nodes
+            .iter()
+            .flatten()
+            .find(|node| node.node_id == Some(input.node_id()) && node.image_data.is_some())?;
+
+        let x = node.dimensions.x as f32 - self.scroll_offset.x;
+        let y =
+            (node.dimensions.y + node.dimensions.height + node.padding.vertical()) as f32 - self.scroll_offset.y + 4.0;
+
+        Some(SpellingSuggestions::render(self.renderer.window_id, &misspelling.suggestions, (x, y)))
+    }
+
+    /// Builds a browser-native tooltip for each form validation error, positioned just below the invalid field.
+    fn validation_tooltips(&self) -> Vec<Element<'renderer, Event>> {
+        self.validation_errors
+            .iter()
+            .filter_map(|error| {
+                let node = self
+                    .layout_tree
+                    .nodes
+                    .iter()
+                    .flatten()
+                    .find(|node| node.node_id == Some(error.node_id))?;
+
+                let x = node.dimensions.x as f32 - self.scroll_offset.x;
+                let y = (node.dimensions.y + node.dimensions.height + node.padding.vertical()) as f32
+                    - self.scroll_offset.y
+                    + 4.0;
+
+                let tooltip = container(text(error.message.clone()).size(13))
+                    .padding(Padding::from([4, 8]))
+                    .style(|_| container::Style {
+                        background: Some(Background::Color(Color::from_rgb8(255, 255, 255))),
+                        text_color: Some(Color::from_rgb8(32, 33, 36)),
+                        border: Border {
+                            color: Color::from_rgb8(218, 220, 224),
+                            width: 1.0,
+                            radius: 4.0.into(),
+                        },
+                        ..Default::default()
+                    });
+
+                Some(
+                    container(tooltip)
+                        .padding(Padding {
+                            top: y.max(0.0),
+                            left: x.max(0.0),
+                            ..Padding::ZERO
+                        })
+                        .into(),
+                )
+            })
+            .collect()
+    }
+
     pub fn render(mut self, app: &'renderer Application, page_ctx: &Page) -> container::Container<'renderer, Event> {
         let image_ctx = page_ctx.image_context();
         let image_ctx = image_ctx.lock().unwrap();
@@ -46,11 +156,26 @@ impl<'renderer> BrowserHtml<'renderer> {
             self.scroll_offset,
         );
 
+        collect_text_selection_overlay(&mut self.renderer, self.layout_tree);
+
+        if let Some(focused_input) = self.focused_input {
+            collect_text_input_overlay(&mut self.renderer, self.layout_tree, focused_input);
+        }
+
+        let mut overlays = self.validation_tooltips();
+        overlays.extend(self.spelling_suggestions());
+        overlays.extend(self.dropdown_menu());
+
         let shader: Shader<Event, HtmlRenderer> = shader(self.renderer)
             .width(Length::Fill)
             .height(Length::Fill);
 
-        container(shader)
+        let content = Stack::with_children(overlays)
+            .push_under(shader)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        container(content)
             .width(Length::Fill)
             .height(Length::Fill)
             .style(move |_| {
@@ -1,4 +1,7 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
 
 use browser_args::BrowserArgs;
 use iced::{
@@ -9,11 +12,11 @@ use iced::{
     window::{self, Id, Position, Settings, settings::PlatformSpecific},
 };
 use io::embedded::WINDOW_ICON;
-use layout::{Rect, TextContext};
+use layout::{Color4f, Rect, TextContext};
 use manifest::{APP_ID, APP_NAME};
 
 use crate::{
-    core::{Application, ApplicationWindow, TabManager, WindowType},
+    core::{Application, ApplicationWindow, TabManager, TextInputController, WindowType},
     events::{BrowserEvent, Event},
     load_fallback_fonts,
     renderer::program::HtmlRenderer,
@@ -65,6 +68,14 @@ impl BrowserWindow {
     pub const DEFAULT_VIEWPORT_SIZE: Size = Size::new(1920.0, 1080.0);
 }
 
+/// Converts a theme color to the color of checked checkboxes and radio buttons, keeping the default of the renderer
+/// when the theme color is not valid.
+fn accent_color(theme_color: &str) -> Color4f {
+    iced::Color::from_str(theme_color).map_or(HtmlRenderer::DEFAULT_ACCENT_COLOR, |color| {
+        Color4f::rgba(f64::from(color.r), f64::from(color.g), f64::from(color.b), f64::from(color.a))
+    })
+}
+
 impl ApplicationWindow for BrowserWindow {
     fn new(_parent_id: Option<Id>, id: Id) -> Self
     where
@@ -104,13 +115,25 @@ impl ApplicationWindow for BrowserWindow {
                 layout_tree,
                 active_tab.scroll_offset,
                 WindowType::Browser,
-            );
+            )
+            .with_focused_input(
+                active_tab
+                    .focused_input
+                    .as_ref()
+                    .map(TextInputController::node_id),
+            )
+            .with_selection(active_tab.selection)
+            .with_accent_color(accent_color(&app.preferences.theme().colors.primary))
+            .with_gpu_memory(Arc::clone(&app.gpu_memory));
             let html = BrowserHtml::new(
                 renderer,
                 layout_tree,
                 Rect::new(0.0, 87.0, f64::from(viewport.width), f64::from(content_viewport_height) - 87.0 - 60.0),
                 active_tab.scroll_offset,
-            );
+                &active_tab.validation_errors,
+                active_tab.focused_input.as_ref(),
+            )
+            .with_open_dropdown(active_tab.open_dropdown);
             let html_content = html.render(app, page_ctx);
             column = column.push(html_content);
         } else {
@@ -67,7 +67,7 @@ impl DevtoolsWindow {
                     root_font_size: 16.0,
                     viewport_width: f64::from(new_viewport.width),
                     viewport_height: f64::from(new_viewport.height),
-                    theme_category: application.preferences.theme().category,
+                    system_preferences: application.system_preferences,
                     document_url: &localhost,
                     root_color: Color::BLACK,
                     root_line_height_multiplier: 1.2,
@@ -111,7 +111,7 @@ impl DevtoolsWindow {
                 root_font_size: 16.0,
                 viewport_width: f64::from(devtools_ctx.viewport.width),
                 viewport_height: f64::from(devtools_ctx.viewport.height),
-                theme_category: application.preferences.theme().category,
+                system_preferences: application.system_preferences,
                 document_url: &localhost,
                 root_line_height_multiplier: 1.2,
                 root_color: Color::BLACK,
@@ -1,6 +1,9 @@
+use std::sync::Arc;
+
+use browser_core::MemoryUsage;
 use iced::{
     Length, Renderer, Size, Theme,
-    widget::{container, text},
+    widget::{column, container, text},
     window::{self, Id, Position, Settings, settings::PlatformSpecific},
 };
 use io::embedded::DEVTOOLS_ICON;
@@ -24,6 +27,23 @@ pub struct DevtoolsWindow {
 
 impl DevtoolsWindow {
     pub(crate) const DEFAULT_VIEWPORT_SIZE: Size = Size::new(800.0, 600.0);
+
+    /// A one-line summary of the memory report, with the sizes in KiB.
+    fn memory_summary(usage: &MemoryUsage) -> String {
+        format!(
+            "Memory: {} KiB (DOM {} KiB, layout {} KiB, images {} KiB, glyphs {} KiB, network log {} KiB, cookies {} \
+             KiB), connections: {} active, {} idle",
+            usage.total_bytes / 1024,
+            usage.dom_bytes / 1024,
+            usage.layout_bytes / 1024,
+            usage.image_cache_bytes / 1024,
+            usage.glyph_atlas_bytes / 1024,
+            usage.network_log_bytes / 1024,
+            usage.cookie_jar_bytes / 1024,
+            usage.connections_active,
+            usage.connections_idle,
+        )
+    }
 }
 
 impl ApplicationWindow for DevtoolsWindow {
@@ -81,7 +101,8 @@ impl ApplicationWindow for DevtoolsWindow {
             devtools_page.layout_tree(),
             devtools_page.scroll_offset,
             WindowType::Devtools,
-        );
+        )
+        .with_gpu_memory(Arc::clone(&application.gpu_memory));
 
         let html = DevtoolsHtml::new(
             renderer,
@@ -90,10 +111,18 @@ impl ApplicationWindow for DevtoolsWindow {
             devtools_page.scroll_offset,
         );
 
-        html.render(application, &devtools.context)
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .into()
+        let memory = application
+            .memory_usage
+            .as_ref()
+            .map_or_else(|| "Memory: measuring…".to_string(), Self::memory_summary);
+
+        column![
+            container(text(memory).size(12)).padding(4),
+            html.render(application, &devtools.context)
+                .width(Length::Fill)
+                .height(Length::Fill),
+        ]
+        .into()
     }
 
     fn settings() -> iced::window::Settings {
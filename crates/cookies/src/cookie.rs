@@ -1,6 +1,6 @@
 use std::{fmt::Display, net::Ipv4Addr};
 
-use crate::errors::ParsingError;
+use crate::errors::CookieError;
 use time::{Date, Duration, OffsetDateTime, Time, UtcDateTime, UtcOffset, macros::format_description};
 use tracing::{debug, instrument};
 use url::{Host, Url};
@@ -21,14 +21,55 @@ impl Display for Expiration {
     }
 }
 
+/// The `SameSite` attribute of a cookie, which limits the cross-site requests it is sent with.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/HTTP/Reference/Headers/Set-Cookie#samesitesamesite-value>
 #[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub enum SameSite {
+    /// Only sent with same-site requests.
     Strict,
+
+    /// Also sent with cross-site top-level navigations with a safe method, like following a link.
     #[default]
     Lax,
+
+    /// Sent with every request, the cookie must have the `Secure` attribute.
     None,
 }
 
+impl SameSite {
+    /// Whether a cookie with this attribute is sent with a request.
+    ///
+    /// # Arguments
+    /// * `site` - How the request relates to the site of the page that made it.
+    #[must_use]
+    pub const fn allows(&self, site: RequestSite) -> bool {
+        match site {
+            RequestSite::SameSite => true,
+            RequestSite::CrossSite {
+                top_level_navigation,
+            } => match self {
+                Self::Strict => false,
+                Self::Lax => top_level_navigation,
+                Self::None => true,
+            },
+        }
+    }
+}
+
+/// How a request relates to the page that made it, which decides the cookies it is sent with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestSite {
+    /// A request to the site of the page, or one not made by a page, like a URL typed into the address bar.
+    SameSite,
+
+    /// A request to another site than the one of the page.
+    CrossSite {
+        /// Whether the request navigates the top-level browsing context with a safe method such as `GET`.
+        top_level_navigation: bool,
+    },
+}
+
 impl From<&str> for SameSite {
     fn from(value: &str) -> Self {
         if value.eq_ignore_ascii_case("strict") {
@@ -82,6 +123,30 @@ impl Cookie {
         CookieBuilder::default()
     }
 
+    /// A rough estimate of the memory used by the cookie, including its strings.
+    #[must_use]
+    pub fn estimated_size_bytes(&self) -> usize {
+        size_of::<Self>()
+            + self.name.len()
+            + self.value.len()
+            + self.path.len()
+            + self
+                .domain
+                .as_ref()
+                .map_or(0, |domain| size_of::<Host>() + domain.to_string().len())
+    }
+
+    /// Replaces the `Max-Age` of the cookie by the date it expires at when set at `now`, so that its lifetime is
+    /// counted from when it was set rather than from whenever it is next stored.
+    #[must_use]
+    pub fn with_expiry_from(mut self, now: OffsetDateTime) -> Self {
+        if let Some(max_age) = self.max_age.take() {
+            self.expires = Expiration::Date(now.saturating_add(max_age));
+        }
+
+        self
+    }
+
     /// Parses a cookie string into a Cookie struct. The `request_url` is used to determine the default path if the
     /// Path attribute is not specified in the cookie string.
     ///
@@ -91,11 +156,11 @@ impl Cookie {
     ///                   if not specified in the cookie string.
     ///
     /// # Errors
-    /// * `CookieParsingError::InvalidCookie` - If the cookie string is not in a valid format (e.g. missing name or value).
-    /// * `CookieParsingError::DateError` - If the Expires attribute is present but cannot be parsed into a valid date.
-    /// * `CookieParsingError::TimeError` - If the Expires attribute is present but the time portion cannot be parsed into a valid time.
+    /// * `CookieError::InvalidCookie` - If the cookie string is not in a valid format (e.g. missing name or value).
+    /// * `CookieError::Date` - If the Expires attribute is present but cannot be parsed into a valid date.
+    /// * `CookieError::Time` - If the Expires attribute is present but the time portion cannot be parsed into a valid time.
     #[instrument(skip(request_url), level = "trace", fields(cookie_str = %cookie_str))]
-    pub fn parse(cookie_str: &str, request_url: &Url) -> Result<Self, ParsingError> {
+    pub fn parse(cookie_str: &str, request_url: &Url) -> Result<Self, CookieError> {
         let parts = cookie_str.split(';');
         let mut cookie = Self::default();
 
@@ -108,7 +173,7 @@ impl Cookie {
 
             if cookie.name.is_empty() {
                 let Some(pair) = part.split_once('=') else {
-                    return Err(ParsingError::InvalidCookie);
+                    return Err(CookieError::InvalidCookie);
                 };
                 cookie.name = pair.0.trim().into();
                 cookie.value = pair.1.trim().into();
@@ -147,75 +212,79 @@ impl Cookie {
         Ok(cookie)
     }
 
-    pub(crate) fn validate_cookie_prefix(cookie: &Self) -> Result<(), ParsingError> {
+    pub(crate) fn validate_cookie_prefix(cookie: &Self) -> Result<(), CookieError> {
         if cookie.name().starts_with("__Host-Http-") {
             if !cookie.secure() {
-                return Err(ParsingError::PrefixMismatch {
+                return Err(CookieError::PrefixMismatch {
                     prefix: String::from("__Host-Http-"),
                     message: String::from("have the Secure attribute"),
                 });
             }
             if !cookie.http_only() {
-                return Err(ParsingError::PrefixMismatch {
+                return Err(CookieError::PrefixMismatch {
                     prefix: String::from("__Host-Http-"),
                     message: String::from("have the HttpOnly attribute"),
                 });
             }
             if cookie.domain().is_some() {
-                return Err(ParsingError::PrefixMismatch {
+                return Err(CookieError::PrefixMismatch {
                     prefix: String::from("__Host-Http-"),
                     message: String::from("not have a Domain attribute"),
                 });
             }
             if cookie.path() != "/" {
-                return Err(ParsingError::PrefixMismatch {
+                return Err(CookieError::PrefixMismatch {
                     prefix: String::from("__Host-Http-"),
                     message: String::from("have Path set to /"),
                 });
             }
         } else if cookie.name().starts_with("__Host-") {
             if !cookie.secure() {
-                return Err(ParsingError::PrefixMismatch {
+                return Err(CookieError::PrefixMismatch {
                     prefix: String::from("__Host-"),
                     message: String::from("have the Secure attribute"),
                 });
             }
             if cookie.domain().is_some() {
-                return Err(ParsingError::PrefixMismatch {
+                return Err(CookieError::PrefixMismatch {
                     prefix: String::from("__Host-"),
                     message: String::from("not have a Domain attribute"),
                 });
             }
             if cookie.path() != "/" {
-                return Err(ParsingError::PrefixMismatch {
+                return Err(CookieError::PrefixMismatch {
                     prefix: String::from("__Host-"),
                     message: String::from("have Path set to /"),
                 });
             }
         } else if cookie.name().starts_with("__Http-") {
             if !cookie.secure() {
-                return Err(ParsingError::PrefixMismatch {
+                return Err(CookieError::PrefixMismatch {
                     prefix: String::from("__Http-"),
                     message: String::from("have the Secure attribute"),
                 });
             }
             if !cookie.http_only() {
-                return Err(ParsingError::PrefixMismatch {
+                return Err(CookieError::PrefixMismatch {
                     prefix: String::from("__Http-"),
                     message: String::from("have the HttpOnly attribute"),
                 });
             }
         } else if cookie.name().starts_with("__Secure-") && !cookie.secure() {
-            return Err(ParsingError::PrefixMismatch {
+            return Err(CookieError::PrefixMismatch {
                 prefix: String::from("__Secure-"),
                 message: String::from("have the Secure attribute"),
             });
         }
 
+        if *cookie.same_site() == SameSite::None && !cookie.secure() {
+            return Err(CookieError::InsecureSameSiteNone);
+        }
+
         Ok(())
     }
 
-    fn parse_expires(cookie: &mut Self, value: Option<&str>) -> Result<(), ParsingError> {
+    fn parse_expires(cookie: &mut Self, value: Option<&str>) -> Result<(), CookieError> {
         if let Some(expires) = value {
             let date_parts: Vec<&str> = expires.split_ascii_whitespace().collect();
 
@@ -225,13 +294,13 @@ impl Cookie {
                 let full_date = [date_parts[1], date_parts[2], date_parts[3]].join("-");
 
                 let date = match Date::parse(full_date.as_str(), date_format) {
-                    Err(e) => return Err(ParsingError::Date(e.to_string())),
+                    Err(e) => return Err(CookieError::Date(e.to_string())),
                     Ok(date) => date,
                 };
 
                 let time_format = format_description!("[hour]:[minute]:[second]");
                 let time = match Time::parse(date_parts[4], time_format) {
-                    Err(e) => return Err(ParsingError::Time(e.to_string())),
+                    Err(e) => return Err(CookieError::Time(e.to_string())),
                     Ok(parsed) => parsed,
                 };
 
@@ -242,13 +311,13 @@ impl Cookie {
                 let full_date = [date_parts[1], date_parts[2], date_parts[4]].join("-");
 
                 let date = match Date::parse(full_date.as_str(), date_format) {
-                    Err(e) => return Err(ParsingError::Date(e.to_string())),
+                    Err(e) => return Err(CookieError::Date(e.to_string())),
                     Ok(date) => date,
                 };
 
                 let time_format = format_description!("[hour]:[minute]:[second]");
                 let time = match Time::parse(date_parts[3], time_format) {
-                    Err(e) => return Err(ParsingError::Time(e.to_string())),
+                    Err(e) => return Err(CookieError::Time(e.to_string())),
                     Ok(parsed) => parsed,
                 };
 
@@ -272,13 +341,13 @@ impl Cookie {
                 let date_format = format_description!("[day]-[month repr:short]-[year]");
 
                 let date = match Date::parse(correct_date.trim(), date_format) {
-                    Err(e) => return Err(ParsingError::Date(e.to_string())),
+                    Err(e) => return Err(CookieError::Date(e.to_string())),
                     Ok(date) => date,
                 };
 
                 let time_format = format_description!("[hour]:[minute]:[second]");
                 let time = match Time::parse(date_parts[2], time_format) {
-                    Err(e) => return Err(ParsingError::Time(e.to_string())),
+                    Err(e) => return Err(CookieError::Time(e.to_string())),
                     Ok(parsed) => parsed,
                 };
 
@@ -289,7 +358,7 @@ impl Cookie {
         Ok(())
     }
 
-    fn parse_max_age(cookie: &mut Self, value: Option<&str>) -> Result<(), ParsingError> {
+    fn parse_max_age(cookie: &mut Self, value: Option<&str>) -> Result<(), CookieError> {
         if let Some(max_age) = value {
             let value = if max_age.starts_with('-') {
                 "0"
@@ -299,7 +368,7 @@ impl Cookie {
 
             let val = match value.parse::<i64>() {
                 Err(e) => {
-                    return Err(ParsingError::Parsing(String::from("i16"), e.to_string()));
+                    return Err(CookieError::Parsing(String::from("i16"), e.to_string()));
                 }
                 Ok(val) => val,
             };
@@ -312,7 +381,7 @@ impl Cookie {
         Ok(())
     }
 
-    fn parse_domain(cookie: &mut Self, value: Option<&str>) -> Result<(), ParsingError> {
+    fn parse_domain(cookie: &mut Self, value: Option<&str>) -> Result<(), CookieError> {
         if let Some(domain) = value {
             let mut domain_mut = domain;
 
@@ -322,7 +391,7 @@ impl Cookie {
 
             let domain = match Host::parse(domain_mut) {
                 Err(e) => {
-                    return Err(ParsingError::Parsing(String::from("host"), e.to_string()));
+                    return Err(CookieError::Parsing(String::from("host"), e.to_string()));
                 }
                 Ok(host) => host,
             };
@@ -482,7 +551,7 @@ impl CookieBuilder {
         self
     }
 
-    pub fn build(self) -> Result<Cookie, ParsingError> {
+    pub fn build(self) -> Result<Cookie, CookieError> {
         let cookie = Cookie {
             name: self.name,
             value: self.value,
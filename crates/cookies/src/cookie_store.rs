@@ -1,17 +1,24 @@
 use std::{
     collections::HashMap,
     fmt::{Display, Formatter},
-    sync::{Arc, Mutex, RwLock},
+    sync::{
+        Arc, Mutex, RwLock,
+        mpsc::{self, Receiver, Sender},
+    },
 };
 
 use database::{Database, Table};
 use io::paths::AppPaths;
 use rusqlite::{Connection, Result};
-use time::UtcDateTime;
+use time::{OffsetDateTime, UtcDateTime};
 use tracing::debug;
 use url::Host;
 
-use crate::{Expiration, cookie::Cookie, table::CookieTable};
+use crate::{
+    CookieEvent, CookieFilter, Expiration, RequestSite,
+    cookie::Cookie,
+    table::{CookieRecord, CookieTable},
+};
 
 #[cfg(not(test))]
 const COOKIE_DATABASE: &str = "cookies.db";
@@ -40,6 +47,19 @@ impl Database for CookieDatabase {
     }
 }
 
+#[cfg(test)]
+impl CookieDatabase {
+    /// Opens a database kept in memory, for the tests of the jar.
+    pub(crate) fn in_memory() -> Self {
+        let conn = Connection::open_in_memory().unwrap();
+        CookieTable::create_table(&conn).unwrap();
+
+        Self {
+            connection: Mutex::new(conn),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CookieJar {
     inner: Arc<CookieJarInner>,
@@ -52,6 +72,9 @@ pub struct CookieJarInner {
     //  second level: attributes
     cookies: RwLock<HashMap<Host, Vec<Cookie>>>,
     database: CookieDatabase,
+
+    /// The senders of the subscribers notified of every change, dropped once their receiver is gone.
+    subscribers: Mutex<Vec<Sender<CookieEvent>>>,
 }
 
 impl CookieJar {
@@ -68,10 +91,26 @@ impl CookieJar {
             inner: Arc::new(CookieJarInner {
                 cookies: RwLock::new(cookies),
                 database,
+                subscribers: Mutex::new(Vec::new()),
             }),
         }
     }
 
+    /// Subscribes to the changes made to the jar, such as the cookies set by responses or deleted by the user.
+    ///
+    /// # Returns
+    /// A receiver of every change made after subscribing, the subscription ends when the receiver is dropped.
+    #[must_use]
+    pub fn subscribe(&self) -> Receiver<CookieEvent> {
+        let (sender, receiver) = mpsc::channel();
+
+        if let Ok(mut subscribers) = self.inner.subscribers.lock() {
+            subscribers.push(sender);
+        }
+
+        receiver
+    }
+
     #[must_use]
     pub fn get_cookies_for_domain(&self, domain: &str) -> Vec<Cookie> {
         let Ok(host) = Host::parse(domain) else {
@@ -102,16 +141,19 @@ impl CookieJar {
     /// * `domain` - The domain to match against.
     /// * `path` - The path to match against.
     /// * `secure` - Whether the request is made over a secure connection.
+    /// * `site` - How the request relates to the page that made it, cross-site requests leave out cookies whose
+    ///   `SameSite` attribute does not allow them.
     ///
     /// # Returns
     /// A vector to the matching stored cookies.
     #[must_use]
-    pub fn get_cookies(&self, domain: &Host<&str>, path: &str, secure: bool) -> Vec<Cookie> {
+    pub fn get_cookies(&self, domain: &Host<&str>, path: &str, secure: bool, site: RequestSite) -> Vec<Cookie> {
         let cookies = self.get_cookies_for_domain(&domain.to_string());
 
         cookies
             .into_iter()
             .filter(|cookie| Self::validate_cookie(domain, path, secure, cookie))
+            .filter(|cookie| cookie.same_site().allows(site))
             .collect()
     }
 
@@ -122,8 +164,11 @@ impl CookieJar {
     /// * `request_domain` - The domain of the request setting the cookie.
     ///
     /// # Notes
-    /// This function currently does not handle cookie expiration or maximum cookie limits.
+    /// A `Max-Age` is converted to the date the cookie expires at, so that storing the cookie again does not extend its
+    /// lifetime. This function currently does not handle maximum cookie limits.
     pub fn add_cookie(&self, cookie: Cookie, request_domain: Host<String>) {
+        let cookie = cookie.with_expiry_from(OffsetDateTime::now_utc());
+
         if let Some(domain) = cookie.domain()
             && !request_domain
                 .to_string()
@@ -139,7 +184,9 @@ impl CookieJar {
             return;
         };
 
-        if cookie.max_age().is_none() && *cookie.expires() == Expiration::Session {
+        if Self::is_session_cookie(&cookie) {
+            let event = CookieEvent::Set(cookie.clone());
+
             match writer.get_mut(&request_domain) {
                 Some(domain_cookies) => domain_cookies.push(cookie),
                 None => {
@@ -150,26 +197,312 @@ impl CookieJar {
                 }
             }
 
+            drop(writer);
+            self.emit(event);
+
             return;
         }
 
+        let record = CookieRecord {
+            host: request_domain.clone(),
+            cookie: cookie.clone(),
+        };
         if let Ok(connection) = self.inner.database.connection.lock()
-            && let Err(e) = CookieTable::insert(&connection, &cookie)
+            && let Err(e) = CookieTable::insert(&connection, &record)
         {
             debug!("Failed to insert cookie into database: {}", e);
         }
 
-        if let Some(domain_cookies) = writer.get_mut(&request_domain) {
-            domain_cookies.retain(|c| !(c.name() == cookie.name() && c.domain() == cookie.domain()));
+        let event = if let Some(domain_cookies) = writer.get_mut(&request_domain) {
+            let before = domain_cookies.len();
+            domain_cookies
+                .retain(|c| !(c.name() == cookie.name() && c.domain() == cookie.domain() && c.path() == cookie.path()));
+
+            let event = if domain_cookies.len() < before {
+                CookieEvent::Updated(cookie.clone())
+            } else {
+                CookieEvent::Set(cookie.clone())
+            };
+
             domain_cookies.push(cookie);
+            event
         } else {
+            let event = CookieEvent::Set(cookie.clone());
             let mut domain_cookies = Vec::with_capacity(16);
             domain_cookies.push(cookie);
 
             writer.insert(request_domain, domain_cookies);
+            event
+        };
+
+        drop(writer);
+        self.emit(event);
+    }
+
+    /// A rough estimate of the memory used by the cookies held in memory, the database is not included.
+    #[must_use]
+    pub fn estimated_size_bytes(&self) -> usize {
+        let Ok(reader) = self.inner.cookies.read() else {
+            return 0;
+        };
+
+        reader
+            .iter()
+            .map(|(host, cookies)| {
+                size_of::<Host>()
+                    + host.to_string().len()
+                    + (cookies.capacity() - cookies.len()) * size_of::<Cookie>()
+                    + cookies
+                        .iter()
+                        .map(Cookie::estimated_size_bytes)
+                        .sum::<usize>()
+            })
+            .sum()
+    }
+
+    /// Returns a snapshot of every cookie in the jar.
+    #[must_use]
+    pub fn all_cookies(&self) -> Vec<Cookie> {
+        self.cookies_matching(&CookieFilter::All)
+    }
+
+    /// Returns all cookies belonging to the domain or any of its subdomains.
+    ///
+    /// # Arguments
+    /// * `domain` - The domain to search for, e.g. `example.com`.
+    #[must_use]
+    pub fn cookies_by_domain(&self, domain: &str) -> Vec<Cookie> {
+        self.cookies_matching(&CookieFilter::Domain(domain.to_string()))
+    }
+
+    /// Returns all cookies with a name matching the pattern, where `*` matches any sequence of characters.
+    ///
+    /// # Arguments
+    /// * `name` - The exact name or wildcard pattern to search for.
+    #[must_use]
+    pub fn cookies_by_name(&self, name: &str) -> Vec<Cookie> {
+        self.cookies_matching(&CookieFilter::Name(name.to_string()))
+    }
+
+    /// Returns all cookies selected by the filter.
+    ///
+    /// # Arguments
+    /// * `filter` - The filter selecting which cookies to return.
+    ///
+    /// # Returns
+    /// A vector of the matching cookies, cloned out of the jar.
+    #[must_use]
+    pub fn cookies_matching(&self, filter: &CookieFilter) -> Vec<Cookie> {
+        let Ok(read) = self.inner.cookies.read() else {
+            debug!("Unable to get read lock");
+            return Vec::new();
+        };
+
+        read.iter()
+            .flat_map(|(host, cookies)| {
+                cookies
+                    .iter()
+                    .filter(move |cookie| filter.matches(host, cookie))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Deletes all cookies belonging to the domain or any of its subdomains.
+    ///
+    /// # Arguments
+    /// * `domain` - The domain to delete cookies for.
+    ///
+    /// # Returns
+    /// The number of deleted cookies.
+    pub fn delete_by_domain(&self, domain: &str) -> usize {
+        self.delete_matching(&CookieFilter::Domain(domain.to_string()))
+    }
+
+    /// Deletes all cookies selected by the filter, both from memory and from the database.
+    ///
+    /// # Arguments
+    /// * `filter` - The filter selecting which cookies to delete.
+    ///
+    /// # Returns
+    /// The number of deleted cookies.
+    pub fn delete_matching(&self, filter: &CookieFilter) -> usize {
+        let Ok(mut writer) = self.inner.cookies.write() else {
+            debug!("Unable to get write lock");
+            return 0;
+        };
+
+        let mut removed = Vec::new();
+
+        for (host, cookies) in writer.iter_mut() {
+            cookies.retain(|cookie| {
+                if filter.matches(host, cookie) {
+                    removed.push((host.clone(), cookie.clone()));
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+
+        writer.retain(|_, cookies| !cookies.is_empty());
+        drop(writer);
+
+        if let Ok(connection) = self.inner.database.connection.lock() {
+            for (host, cookie) in &removed {
+                if let Err(e) =
+                    CookieTable::delete(&connection, cookie.name(), &Self::stored_domain(host, cookie), cookie.path())
+                {
+                    debug!("Failed to delete cookie from database: {}", e);
+                }
+            }
+        }
+
+        self.emit_deleted(&removed);
+
+        removed.len()
+    }
+
+    /// Writes every cookie of the jar that outlives the session to the database in a single transaction, so that the
+    /// cookies restored by [`CookieJar::load`] on the next start match the jar. Session cookies are never stored.
+    ///
+    /// # Returns
+    /// The number of stored cookies, zero if the database could not be written.
+    pub fn save(&self) -> usize {
+        let Ok(reader) = self.inner.cookies.read() else {
+            debug!("Unable to get read lock");
+            return 0;
+        };
+
+        let Ok(mut connection) = self.inner.database.connection.lock() else {
+            debug!("Unable to lock the cookie database");
+            return 0;
+        };
+
+        let save = |transaction: &Connection| -> Result<usize> {
+            let mut saved = 0;
+
+            for (host, cookies) in reader.iter() {
+                for cookie in cookies {
+                    if Self::is_session_cookie(cookie) {
+                        continue;
+                    }
+
+                    let record = CookieRecord {
+                        host: host.clone(),
+                        cookie: cookie.clone(),
+                    };
+                    CookieTable::insert(transaction, &record)?;
+                    saved += 1;
+                }
+            }
+
+            Ok(saved)
+        };
+
+        let result = connection.transaction().and_then(|transaction| {
+            let saved = save(&transaction)?;
+            transaction.commit()?;
+            Ok(saved)
+        });
+
+        match result {
+            Ok(saved) => saved,
+            Err(e) => {
+                debug!("Failed to save cookies to database: {}", e);
+                0
+            }
+        }
+    }
+
+    /// Deletes every cookie, both from memory and from the database.
+    pub fn clear(&self) {
+        let Ok(mut writer) = self.inner.cookies.write() else {
+            debug!("Unable to get write lock");
+            return;
+        };
+
+        writer.clear();
+        drop(writer);
+
+        if let Ok(connection) = self.inner.database.connection.lock()
+            && let Err(e) = CookieTable::delete_all(&connection)
+        {
+            debug!("Failed to delete cookies from database: {}", e);
+        }
+
+        self.emit(CookieEvent::Cleared);
+    }
+
+    /// Deletes all cookies whose expiration date is at or before `now`. Session cookies are kept.
+    ///
+    /// # Arguments
+    /// * `now` - The point in time to compare expiration dates against.
+    ///
+    /// # Returns
+    /// The number of deleted cookies.
+    pub fn delete_expired(&self, now: &OffsetDateTime) -> usize {
+        let Ok(mut writer) = self.inner.cookies.write() else {
+            debug!("Unable to get write lock");
+            return 0;
+        };
+
+        let mut removed = Vec::new();
+
+        for (host, cookies) in writer.iter_mut() {
+            cookies.retain(|cookie| {
+                let expired = matches!(cookie.expires(), Expiration::Date(date) if date <= now);
+                if expired {
+                    removed.push((host.clone(), cookie.clone()));
+                }
+
+                !expired
+            });
+        }
+
+        writer.retain(|_, cookies| !cookies.is_empty());
+        drop(writer);
+
+        if let Ok(connection) = self.inner.database.connection.lock()
+            && let Err(e) = CookieTable::delete_expired(&connection, now.unix_timestamp())
+        {
+            debug!("Failed to delete expired cookies from database: {}", e);
+        }
+
+        self.emit_deleted(&removed);
+
+        removed.len()
+    }
+
+    /// Sends an event to every subscriber, forgetting the subscribers whose receiver was dropped.
+    fn emit(&self, event: CookieEvent) {
+        if let Ok(mut subscribers) = self.inner.subscribers.lock() {
+            subscribers.retain(|sender| sender.send(event.clone()).is_ok());
+        }
+    }
+
+    fn emit_deleted(&self, removed: &[(Host, Cookie)]) {
+        for (host, cookie) in removed {
+            self.emit(CookieEvent::Deleted {
+                name: cookie.name().to_string(),
+                domain: Self::stored_domain(host, cookie),
+            });
         }
     }
 
+    /// Whether a cookie only lasts for the session, which keeps it out of the database.
+    fn is_session_cookie(cookie: &Cookie) -> bool {
+        cookie.max_age().is_none() && *cookie.expires() == Expiration::Session
+    }
+
+    /// The domain a cookie is stored under in the database, the host that set it for a cookie without a domain.
+    fn stored_domain(host: &Host, cookie: &Cookie) -> String {
+        cookie
+            .domain()
+            .as_ref()
+            .map_or_else(|| host.to_string(), ToString::to_string)
+    }
+
     fn validate_cookie(domain: &Host<&str>, path: &str, secure: bool, cookie: &Cookie) -> bool {
         if let Some(cookie_domain) = cookie.domain()
             && **cookie_domain != *domain
@@ -185,12 +518,11 @@ impl CookieJar {
             return false;
         }
 
-        if let Some(max_age) = cookie.max_age() {
-            let current_time = UtcDateTime::now().unix_timestamp();
-            if max_age.whole_seconds() <= current_time {
-                // TODO: Mark as stale/remove them from the database
-                return false;
-            }
+        if let Expiration::Date(expires) = cookie.expires()
+            && expires.unix_timestamp() <= UtcDateTime::now().unix_timestamp()
+        {
+            // TODO: Mark as stale/remove them from the database
+            return false;
         }
 
         true
@@ -214,3 +546,148 @@ impl Display for CookieJar {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use time::Duration;
+    use url::Url;
+
+    use super::*;
+
+    fn localhost() -> Url {
+        Url::parse("http://localhost").unwrap()
+    }
+
+    fn jar() -> CookieJar {
+        CookieJar::load(CookieDatabase::in_memory())
+    }
+
+    fn set(jar: &CookieJar, cookie: &str) {
+        jar.add_cookie(Cookie::parse(cookie, &localhost()).unwrap(), Host::Domain("localhost".to_string()));
+    }
+
+    #[test]
+    fn test_max_age_is_stored_as_expiry() {
+        let jar = jar();
+        let before = OffsetDateTime::now_utc();
+        set(&jar, "ID=HelloWorld; Max-Age=3600");
+
+        let cookies = jar.get_cookies_for_domain("localhost");
+        assert_eq!(cookies.len(), 1);
+        assert!(cookies[0].max_age().is_none());
+
+        let Expiration::Date(expires) = *cookies[0].expires() else {
+            panic!("expected the Max-Age to be converted to an expiry date");
+        };
+        assert!(expires >= before + Duration::hours(1));
+        assert!(expires < before + Duration::hours(1) + Duration::minutes(1));
+
+        jar.save();
+        jar.save();
+
+        let stored = CookieTable::get_all(&jar.inner.database.connection.lock().unwrap());
+        let stored = &stored[&Host::Domain("localhost".to_string())];
+        assert_eq!(stored.len(), 1);
+        assert_eq!(*stored[0].expires(), Expiration::Date(expires.replace_nanosecond(0).unwrap()));
+    }
+
+    #[test]
+    fn test_cookies_with_different_paths_are_kept_apart() {
+        let jar = jar();
+        set(&jar, "ID=root; Path=/; Max-Age=3600");
+        set(&jar, "ID=docs; Path=/docs; Max-Age=3600");
+        set(&jar, "ID=updated; Path=/docs; Max-Age=3600");
+
+        let mut values: Vec<String> = jar
+            .get_cookies_for_domain("localhost")
+            .iter()
+            .map(|cookie| cookie.value().to_string())
+            .collect();
+        values.sort_unstable();
+        assert_eq!(values, ["root", "updated"]);
+    }
+
+    #[test]
+    fn test_subscribe_set_and_update() {
+        let jar = jar();
+        let events = jar.subscribe();
+
+        set(&jar, "session=1");
+        set(&jar, "ID=first; Max-Age=3600");
+        set(&jar, "ID=second; Max-Age=3600");
+
+        let events: Vec<CookieEvent> = events.try_iter().collect();
+        assert_eq!(events.len(), 3);
+        assert!(matches!(&events[0], CookieEvent::Set(cookie) if cookie.name() == "session"));
+        assert!(matches!(&events[1], CookieEvent::Set(cookie) if cookie.value() == "first"));
+        assert!(matches!(&events[2], CookieEvent::Updated(cookie) if cookie.value() == "second"));
+    }
+
+    #[test]
+    fn test_subscribe_expired_and_cleared() {
+        let jar = jar();
+        set(&jar, "session=1");
+        set(&jar, "ID=HelloWorld; Max-Age=3600");
+
+        let events = jar.subscribe();
+        assert_eq!(jar.delete_expired(&(OffsetDateTime::now_utc() + Duration::hours(2))), 1);
+        jar.clear();
+
+        assert_eq!(
+            events.try_iter().collect::<Vec<_>>(),
+            [
+                CookieEvent::Deleted {
+                    name: "ID".to_string(),
+                    domain: "localhost".to_string(),
+                },
+                CookieEvent::Cleared,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_subscribe_only_receives_later_events() {
+        let jar = jar();
+        set(&jar, "before=1");
+
+        let events = jar.subscribe();
+        set(&jar, "after=1");
+
+        let events: Vec<CookieEvent> = events.try_iter().collect();
+        assert!(matches!(events.as_slice(), [CookieEvent::Set(cookie)] if cookie.name() == "after"));
+    }
+
+    #[test]
+    fn test_dropped_subscribers_are_pruned() {
+        let jar = jar();
+        let kept = jar.subscribe();
+        drop(jar.subscribe());
+        assert_eq!(jar.inner.subscribers.lock().unwrap().len(), 2);
+
+        set(&jar, "ID=1");
+        assert_eq!(jar.inner.subscribers.lock().unwrap().len(), 1);
+        assert_eq!(kept.try_iter().count(), 1);
+    }
+
+    #[test]
+    fn test_estimated_size_counts_cookies() {
+        let jar = jar();
+        assert_eq!(jar.estimated_size_bytes(), 0);
+
+        set(&jar, "ID=1");
+
+        let cookies = jar.get_cookies_for_domain("localhost");
+        let cookie = &cookies[0];
+        assert_eq!(cookie.estimated_size_bytes(), size_of::<Cookie>() + "ID".len() + "1".len() + cookie.path().len());
+
+        let spare = {
+            let reader = jar.inner.cookies.read().unwrap();
+            let stored = &reader[&Host::Domain("localhost".to_string())];
+            stored.capacity() - stored.len()
+        };
+        assert_eq!(
+            jar.estimated_size_bytes(),
+            size_of::<Host>() + "localhost".len() + spare * size_of::<Cookie>() + cookie.estimated_size_bytes()
+        );
+    }
+}
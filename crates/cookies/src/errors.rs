@@ -2,7 +2,8 @@ use rusqlite::Error;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
-pub enum ParsingError {
+#[non_exhaustive]
+pub enum CookieError {
     #[error("must have at least a name=value pair")]
     InvalidCookie,
 
@@ -18,6 +19,9 @@ pub enum ParsingError {
     #[error("{prefix} prefixed cookies must {message}")]
     PrefixMismatch { prefix: String, message: String },
 
+    #[error("SameSite=None cookies must have the Secure attribute")]
+    InsecureSameSiteNone,
+
     #[error(transparent)]
     Database(#[from] Error),
 }
@@ -0,0 +1,17 @@
+use crate::Cookie;
+
+/// A change to the contents of a [`CookieJar`](crate::CookieJar), sent to every subscriber of the jar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CookieEvent {
+    /// A cookie was added to the jar.
+    Set(Cookie),
+
+    /// A cookie replaced a stored cookie with the same name and domain.
+    Updated(Cookie),
+
+    /// A cookie was deleted from the jar, either explicitly or because it expired.
+    Deleted { name: String, domain: String },
+
+    /// Every cookie was deleted from the jar.
+    Cleared,
+}
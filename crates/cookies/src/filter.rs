@@ -0,0 +1,79 @@
+use url::Host;
+
+use crate::Cookie;
+
+/// Selects a subset of the cookies stored in a `CookieJar`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum CookieFilter {
+    /// Matches every cookie.
+    #[default]
+    All,
+
+    /// Matches cookies belonging to the domain or any of its subdomains.
+    Domain(String),
+
+    /// Matches cookies whose name matches the pattern, where `*` matches any sequence of characters.
+    Name(String),
+}
+
+impl CookieFilter {
+    /// Checks whether a cookie stored under the given host is selected by this filter.
+    ///
+    /// # Arguments
+    /// * `host` - The host the cookie is stored under in the jar.
+    /// * `cookie` - The cookie to check.
+    #[must_use]
+    pub fn matches(&self, host: &Host, cookie: &Cookie) -> bool {
+        match self {
+            Self::All => true,
+            Self::Domain(domain) => {
+                let domain = domain.trim_start_matches('.');
+
+                domain_matches(&host.to_string(), domain)
+                    || cookie
+                        .domain()
+                        .as_ref()
+                        .is_some_and(|d| domain_matches(&d.to_string(), domain))
+            }
+            Self::Name(pattern) => wildcard_matches(pattern, cookie.name()),
+        }
+    }
+}
+
+/// Whether `host` is `domain` or one of its subdomains.
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host.eq_ignore_ascii_case(domain)
+        || host
+            .len()
+            .checked_sub(domain.len() + 1)
+            .is_some_and(|split| host.as_bytes()[split] == b'.' && host[split + 1..].eq_ignore_ascii_case(domain))
+}
+
+/// Matches `value` against a pattern where `*` matches any (possibly empty) sequence of characters.
+fn wildcard_matches(pattern: &str, value: &str) -> bool {
+    let mut parts = pattern.split('*');
+
+    let Some(first) = parts.next() else {
+        return value.is_empty();
+    };
+
+    if !pattern.contains('*') {
+        return pattern == value;
+    }
+
+    let Some(mut rest) = value.strip_prefix(first) else {
+        return false;
+    };
+
+    let mut parts: Vec<&str> = parts.collect();
+    let last = parts.pop().unwrap_or_default();
+
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.len() >= last.len() && rest.ends_with(last)
+}
@@ -3,17 +3,28 @@
 mod cookie;
 mod cookie_store;
 pub mod errors;
+mod event;
+mod filter;
 mod table;
 
-pub use cookie::{Cookie, Expiration};
+pub use cookie::{Cookie, Expiration, RequestSite, SameSite};
 pub use cookie_store::{CookieDatabase, CookieJar};
+pub use event::CookieEvent;
+pub use filter::CookieFilter;
 
 #[cfg(test)]
 mod tests {
-    use time::Duration;
+    use database::Table;
+    use rusqlite::Connection;
+    use time::{Duration, OffsetDateTime};
     use url::{Host, Url};
 
-    use crate::{cookie::Cookie, cookie::Expiration};
+    use crate::{
+        CookieFilter, RequestSite, SameSite,
+        cookie::Cookie,
+        cookie::Expiration,
+        table::{CookieRecord, CookieTable},
+    };
 
     fn localhost() -> Url {
         Url::parse("http://localhost").unwrap()
@@ -294,4 +305,118 @@ mod tests {
         assert_eq!(c3.value(), "AB.CD=TEST");
         assert!(c3.secure());
     }
+
+    #[test]
+    fn test_stored_cookies_keep_their_host() {
+        let conn = Connection::open_in_memory().unwrap();
+        CookieTable::create_table(&conn).unwrap();
+
+        let expires = OffsetDateTime::now_utc() + Duration::days(1);
+        let host_only = |host: &str| CookieRecord {
+            host: Host::parse(host).unwrap(),
+            cookie: Cookie::builder()
+                .name(String::from("ID"))
+                .value(host.to_string())
+                .expires(Expiration::Date(expires))
+                .http_only(true)
+                .build_unchecked(),
+        };
+        let shared = CookieRecord {
+            host: Host::parse("accounts.example.com").unwrap(),
+            cookie: Cookie::builder()
+                .name(String::from("SID"))
+                .value(String::from("shared"))
+                .expires(Expiration::Date(expires))
+                .domain(Host::parse("example.com").unwrap())
+                .build_unchecked(),
+        };
+
+        CookieTable::insert(&conn, &host_only("a.test")).unwrap();
+        CookieTable::insert(&conn, &host_only("b.test")).unwrap();
+        CookieTable::insert(&conn, &shared).unwrap();
+
+        let cookies = CookieTable::get_all(&conn);
+        let a = &cookies[&Host::parse("a.test").unwrap()];
+        assert_eq!(a.len(), 1);
+        assert_eq!(a[0].value(), "a.test");
+        assert!(a[0].domain().is_none());
+        assert!(a[0].http_only());
+
+        let shared_cookies = CookieTable::get_cookies_by_domain(&conn, "accounts.example.com");
+        assert_eq!(shared_cookies.len(), 1);
+        assert_eq!(*shared_cookies[0].domain().as_ref().unwrap(), Host::Domain("example.com".to_string()).into());
+        assert!(!shared_cookies[0].http_only());
+    }
+
+    #[test]
+    fn test_cookie_table_adds_missing_columns() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE cookies (
+                id INTEGER PRIMARY KEY, name TEXT, value TEXT, expiration INTEGER, domain TEXT, path TEXT,
+                secure BOOLEAN, http_only BOOLEAN, same_site TEXT, UNIQUE (name, domain)
+            );
+            INSERT INTO cookies (name, value, expiration, domain, path, secure, http_only, same_site)
+            VALUES ('ID', 'old', 4102444800, 'example.com', '/', 1, 0, 'Lax');",
+        )
+        .unwrap();
+
+        CookieTable::create_table(&conn).unwrap();
+
+        let cookies = CookieTable::get_cookies_by_domain(&conn, "example.com");
+        assert_eq!(cookies.len(), 1);
+        assert_eq!(cookies[0].value(), "old");
+        assert!(cookies[0].secure());
+    }
+
+    #[test]
+    fn test_filter_by_domain() {
+        let cookie = Cookie::parse("ID=HelloWorld", &localhost()).unwrap();
+        let host = Host::parse("accounts.google.com").unwrap();
+
+        assert!(CookieFilter::All.matches(&host, &cookie));
+        assert!(CookieFilter::Domain("google.com".to_string()).matches(&host, &cookie));
+        assert!(CookieFilter::Domain(".google.com".to_string()).matches(&host, &cookie));
+        assert!(!CookieFilter::Domain("gle.com".to_string()).matches(&host, &cookie));
+        assert!(!CookieFilter::Domain("example.com".to_string()).matches(&host, &cookie));
+    }
+
+    #[test]
+    fn test_filter_by_name() {
+        let cookie = Cookie::parse("__Secure-ENID=TEST; Secure", &Url::parse("https://localhost").unwrap()).unwrap();
+        let host = Host::parse("localhost").unwrap();
+
+        assert!(CookieFilter::Name("__Secure-ENID".to_string()).matches(&host, &cookie));
+        assert!(CookieFilter::Name("__Secure-*".to_string()).matches(&host, &cookie));
+        assert!(CookieFilter::Name("*ENID".to_string()).matches(&host, &cookie));
+        assert!(CookieFilter::Name("*cure*".to_string()).matches(&host, &cookie));
+        assert!(!CookieFilter::Name("ENID".to_string()).matches(&host, &cookie));
+        assert!(!CookieFilter::Name("__Host-*".to_string()).matches(&host, &cookie));
+    }
+
+    #[test]
+    fn test_same_site_none_requires_secure() {
+        let url = Url::parse("https://localhost").unwrap();
+
+        let cookie = Cookie::parse("ID=HelloWorld; SameSite=None; Secure", &url).unwrap();
+        assert_eq!(*cookie.same_site(), SameSite::None);
+        assert!(Cookie::parse("ID=HelloWorld; SameSite=None", &url).is_err());
+        assert!(Cookie::parse("ID=HelloWorld; SameSite=Strict", &url).is_ok());
+    }
+
+    #[test]
+    fn test_same_site_enforcement() {
+        let subresource = RequestSite::CrossSite {
+            top_level_navigation: false,
+        };
+        let navigation = RequestSite::CrossSite {
+            top_level_navigation: true,
+        };
+
+        assert!(SameSite::Strict.allows(RequestSite::SameSite));
+        assert!(!SameSite::Strict.allows(navigation));
+        assert!(SameSite::Lax.allows(navigation));
+        assert!(!SameSite::Lax.allows(subresource));
+        assert!(SameSite::None.allows(subresource));
+    }
 }
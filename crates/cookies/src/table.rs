@@ -1,196 +1,312 @@
-use std::{collections::HashMap, panic};
+use std::collections::HashMap;
 
 use database::Table;
-use rusqlite::{Connection, Error, Result, params};
-use time::{Duration, OffsetDateTime, UtcDateTime};
+use rusqlite::{Connection, Error, Result, Row, params};
+use time::{OffsetDateTime, UtcDateTime};
 use tracing::debug;
 use url::Host;
 
 use crate::{Cookie, Expiration, cookie::SameSite};
 
-pub struct CookieTable;
-
-impl CookieTable {
-    /// Retrieves all cookies
-    pub fn get_all(conn: &Connection) -> HashMap<Host, Vec<Cookie>> {
-        let mut cookies = HashMap::with_capacity(32);
+/// The columns of a cookie row, in the order read by `CookieRecord::from_row`.
+const COLUMNS: &str = "name, value, expiration, domain, path, secure, http_only, same_site, host, host_only";
+
+/// The columns of the cookies table along with its key. A cookie is identified by its name, domain, and path, so that
+/// cookies of the same name set for different paths are kept apart.
+const TABLE_DEFINITION: &str = "
+    id INTEGER PRIMARY KEY,
+    name TEXT,
+    value TEXT,
+    expiration INTEGER,
+    domain TEXT,
+    path TEXT,
+    secure BOOLEAN,
+    http_only BOOLEAN,
+    same_site TEXT,
+    host TEXT,
+    host_only BOOLEAN NOT NULL DEFAULT 0,
+    UNIQUE (name, domain, path)";
+
+/// A stored cookie along with the host it was set by, which is the key of the cookie in the jar. A cookie without a
+/// `Domain` attribute is only sent back to that host.
+#[derive(Debug, Clone)]
+pub struct CookieRecord {
+    pub host: Host,
+    pub cookie: Cookie,
+}
 
-        let stmt =
-            conn.prepare("SELECT name, value, expiration, domain, path, secure, http_only, same_site FROM cookies");
+impl CookieRecord {
+    /// Reads a record from a row selected with `COLUMNS`. Rows written before the `host` column existed are keyed by
+    /// their domain.
+    fn from_row(row: &Row) -> Result<Self> {
+        let Ok(expiry) = OffsetDateTime::from_unix_timestamp(row.get(2)?) else {
+            return Err(Error::InvalidQuery);
+        };
 
-        if stmt.is_err() {
-            return cookies;
-        }
+        let domain_value = row.get::<usize, String>(3)?;
+        let host_value = row.get::<usize, Option<String>>(8)?;
+        let Ok(host) = Host::parse(host_value.as_deref().unwrap_or(&domain_value)) else {
+            return Err(Error::InvalidQuery);
+        };
 
-        let mut binding = stmt.unwrap();
-        let rows_result = binding.query_map([], |row| {
-            let expiry_value = row.get::<usize, i64>(2)?;
-            let Ok(expiry) = OffsetDateTime::from_unix_timestamp(expiry_value) else {
+        let mut builder = Cookie::builder()
+            .name(row.get(0)?)
+            .value(row.get(1)?)
+            .expires(Expiration::Date(expiry))
+            .path(row.get(4)?)
+            .secure(row.get(5)?)
+            .http_only(row.get(6)?)
+            .same_site(SameSite::from(row.get::<usize, String>(7)?));
+
+        if !row.get::<usize, bool>(9)? {
+            let Ok(domain) = Host::parse(&domain_value) else {
                 return Err(Error::InvalidQuery);
             };
+            builder = builder.domain(domain);
+        }
 
-            let domain_value = row.get::<usize, String>(3)?;
-            let Ok(domain) = Host::parse(domain_value.as_str()) else {
-                return Err(Error::InvalidQuery);
-            };
+        Ok(Self {
+            host,
+            cookie: builder.build_unchecked(),
+        })
+    }
+}
 
-            let cookie = Cookie::builder()
-                .name(row.get(0)?)
-                .value(row.get(1)?)
-                .expires(Expiration::Date(expiry))
-                .domain(domain.clone())
-                .path(row.get(4)?)
-                .secure(row.get(5)?)
-                .http_only(row.get(6)?)
-                .same_site(SameSite::from(row.get::<usize, String>(7)?))
-                .build_unchecked();
-
-            Ok((domain, cookie))
-        });
-
-        match rows_result {
-            Ok(rows) => {
-                for row in rows {
-                    match row {
-                        Ok((domain, cookie)) => {
-                            let curr = cookies.get_mut(&domain);
-
-                            match curr {
-                                Some(domain_cookies) => domain_cookies.push(cookie),
-                                None => {
-                                    let mut domain_cookies = Vec::with_capacity(16);
-                                    domain_cookies.push(cookie);
-
-                                    cookies.insert(domain, domain_cookies);
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            debug!(%e);
-                        }
-                    }
-                }
+pub struct CookieTable;
 
-                cookies
-            }
+impl CookieTable {
+    /// Retrieves all cookies, keyed by the host that set them
+    pub fn get_all(conn: &Connection) -> HashMap<Host, Vec<Cookie>> {
+        let mut cookies: HashMap<Host, Vec<Cookie>> = HashMap::with_capacity(32);
+
+        let Ok(mut stmt) = conn.prepare(&format!("SELECT {COLUMNS} FROM cookies")) else {
+            return cookies;
+        };
+
+        let rows = match stmt.query_map([], CookieRecord::from_row) {
+            Ok(rows) => rows,
             Err(err) => {
                 debug!(%err);
-                cookies
+                return cookies;
+            }
+        };
+
+        for row in rows {
+            match row {
+                Ok(record) => cookies
+                    .entry(record.host)
+                    .or_insert_with(|| Vec::with_capacity(16))
+                    .push(record.cookie),
+                Err(e) => {
+                    debug!(%e);
+                }
             }
         }
+
+        cookies
     }
 
-    /// Retrieve all cookies from a specific domain
+    /// Retrieve all cookies set by a specific host
     pub fn get_cookies_by_domain<D: AsRef<str>>(conn: &Connection, domain: D) -> Vec<Cookie> {
-        let mut cookies = Vec::new();
+        let Ok(mut stmt) =
+            conn.prepare(&format!("SELECT {COLUMNS} FROM cookies WHERE host=?1 OR (host IS NULL AND domain=?1)"))
+        else {
+            return Vec::new();
+        };
 
-        let stmt = conn.prepare(
-            "SELECT name, value, expiration, domain, path, secure, http_only, same_site FROM cookies WHERE domain=?1",
-        );
+        let Ok(rows) = stmt.query_map([domain.as_ref()], CookieRecord::from_row) else {
+            return Vec::new();
+        };
 
-        if stmt.is_err() {
-            return cookies;
-        }
+        rows.filter_map(|row| row.ok().map(|record| record.cookie))
+            .collect()
+    }
+
+    /// Deletes a single cookie identified by its name, domain, and path
+    pub fn delete(conn: &Connection, name: &str, domain: &str, path: &str) -> Result<usize> {
+        conn.execute("DELETE FROM cookies WHERE name=?1 AND domain=?2 AND path=?3", params![name, domain, path])
+    }
 
-        let mut binding = stmt.unwrap();
-        let cookies_iter = binding.query_map([domain.as_ref()], |row| {
-            let val = row.get::<usize, i64>(2)?;
+    /// Deletes every stored cookie, returning the number of removed rows
+    pub fn delete_all(conn: &Connection) -> Result<usize> {
+        conn.execute("DELETE FROM cookies", [])
+    }
 
-            let Ok(expiry) = OffsetDateTime::from_unix_timestamp(val) else {
-                panic!();
-            };
+    /// Deletes all cookies that expired at or before the given unix timestamp, returning the number of removed rows
+    pub fn delete_expired(conn: &Connection, now: i64) -> Result<usize> {
+        conn.execute("DELETE FROM cookies WHERE expiration <= ?1", [now])
+    }
 
-            let dom_host = row.get::<usize, String>(3)?;
-            let Ok(domain) = Host::parse(dom_host.as_str()) else {
-                panic!();
-            };
+    /// Adds the columns introduced after the table was first created to a table created by an older version.
+    fn add_missing_columns(conn: &Connection) -> Result<()> {
+        let mut stmt = conn.prepare("PRAGMA table_info(cookies)")?;
+        let columns = stmt
+            .query_map([], |row| row.get::<usize, String>(1))?
+            .collect::<Result<Vec<_>>>()?;
 
-            let cookie = Cookie::builder()
-                .name(row.get(0)?)
-                .value(row.get(1)?)
-                .expires(Expiration::Date(expiry))
-                .max_age(Duration::seconds(val))
-                .domain(domain)
-                .path(row.get(4)?)
-                .secure(row.get(5)?)
-                .secure(row.get(6)?)
-                .same_site(SameSite::from(row.get::<usize, String>(7)?))
-                .build_unchecked();
-
-            Ok(cookie)
-        });
-
-        if cookies_iter.is_err() {
-            return cookies;
+        if !columns.iter().any(|column| column == "host") {
+            conn.execute("ALTER TABLE cookies ADD COLUMN host TEXT", [])?;
         }
 
-        for cookie in cookies_iter.unwrap() {
-            if cookie.is_err() {
-                continue;
-            }
+        if !columns.iter().any(|column| column == "host_only") {
+            conn.execute("ALTER TABLE cookies ADD COLUMN host_only BOOLEAN NOT NULL DEFAULT 0", [])?;
+        }
+
+        Ok(())
+    }
+
+    /// Rebuilds a table created by an older version, whose key left out the path of the cookies, with the path in its
+    /// key. SQLite cannot change the constraints of a table, so the rows are copied to a new table that replaces it.
+    fn add_path_to_key(conn: &Connection) -> Result<()> {
+        let sql: String =
+            conn.query_row("SELECT sql FROM sqlite_master WHERE type='table' AND name='cookies'", [], |row| {
+                row.get(0)
+            })?;
 
-            cookies.push(cookie.unwrap());
+        if !sql.contains("UNIQUE (name, domain)") {
+            return Ok(());
         }
 
-        cookies
+        conn.execute_batch(&format!(
+            "BEGIN;
+            CREATE TABLE cookies_by_path ({TABLE_DEFINITION});
+            INSERT INTO cookies_by_path (id, {COLUMNS}) SELECT id, {COLUMNS} FROM cookies;
+            DROP TABLE cookies;
+            ALTER TABLE cookies_by_path RENAME TO cookies;
+            COMMIT;"
+        ))
     }
 }
 
 impl Table for CookieTable {
-    type Record = Cookie;
+    type Record = CookieRecord;
 
     fn create_table(conn: &Connection) -> Result<()> {
+        conn.execute(&format!("CREATE TABLE IF NOT EXISTS cookies ({TABLE_DEFINITION})"), [])?;
+
+        Self::add_missing_columns(conn)?;
+        Self::add_path_to_key(conn)?;
+
+        // TODO: index on expiration/max-age
         conn.execute_batch(
-            "BEGIN;
-            CREATE TABLE IF NOT EXISTS cookies (
-                id INTEGER PRIMARY KEY,
-                name TEXT,
-                value TEXT,
-                expiration INTEGER,
-                domain TEXT,
-                path TEXT,
-                secure BOOLEAN,
-                http_only BOOLEAN,
-                same_site TEXT,
-                UNIQUE (name, domain)
-            );
-            CREATE INDEX IF NOT EXISTS domain_idx ON cookies (domain);
-            COMMIT;",
-        )?; // TODO: index on expiration/max-age
+            "CREATE INDEX IF NOT EXISTS domain_idx ON cookies (domain);
+            CREATE INDEX IF NOT EXISTS host_idx ON cookies (host);",
+        )?;
+
         Ok(())
     }
 
     fn insert(conn: &Connection, data: &Self::Record) -> Result<()> {
-        let expiry = match data.expires() {
+        let cookie = &data.cookie;
+
+        let expiry = match cookie.expires() {
             Expiration::Session => None,
             Expiration::Date(offset) => Some(offset.unix_timestamp()),
         };
 
-        let max_age = data.max_age().as_ref().map_or_else(
+        let max_age = cookie.max_age().as_ref().map_or_else(
             || expiry.unwrap_or_default(),
             |val| UtcDateTime::now().unix_timestamp() + val.whole_seconds(),
         );
 
-        let same_site = data.same_site().to_string();
+        let same_site = cookie.same_site().to_string();
+
+        // A host-only cookie is stored under its host, so that the cookies of the same name set by different hosts
+        // do not replace each other.
+        let domain = cookie
+            .domain()
+            .as_ref()
+            .map_or_else(|| data.host.to_string(), ToString::to_string);
 
         conn.execute(
             "INSERT OR REPLACE INTO cookies
-            (name, value, expiration, domain, path, secure, http_only, same_site)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            (name, value, expiration, domain, path, secure, http_only, same_site, host, host_only)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
             params![
-                data.name(),
-                data.value(),
+                cookie.name(),
+                cookie.value(),
                 max_age,
-                data.domain()
-                    .as_ref()
-                    .map_or_else(|| "127.0.0.1".to_string(), ToString::to_string),
-                data.path(),
-                data.secure(),
-                data.http_only(),
+                domain,
+                cookie.path(),
+                cookie.secure(),
+                cookie.http_only(),
                 same_site,
+                data.host.to_string(),
+                cookie.domain().is_none(),
             ],
         )?;
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::*;
+
+    fn localhost() -> Host {
+        Host::Domain("localhost".to_string())
+    }
+
+    fn record(cookie: &str) -> CookieRecord {
+        CookieRecord {
+            host: localhost(),
+            cookie: Cookie::parse(cookie, &Url::parse("http://localhost").unwrap()).unwrap(),
+        }
+    }
+
+    fn values(conn: &Connection) -> Vec<String> {
+        let mut values: Vec<String> = CookieTable::get_all(conn)[&localhost()]
+            .iter()
+            .map(|cookie| cookie.value().to_string())
+            .collect();
+        values.sort_unstable();
+        values
+    }
+
+    #[test]
+    fn test_cookies_with_different_paths_are_kept_apart() {
+        let conn = Connection::open_in_memory().unwrap();
+        CookieTable::create_table(&conn).unwrap();
+
+        CookieTable::insert(&conn, &record("ID=root; Path=/; Max-Age=3600")).unwrap();
+        CookieTable::insert(&conn, &record("ID=docs; Path=/docs; Max-Age=3600")).unwrap();
+        CookieTable::insert(&conn, &record("ID=updated; Path=/docs; Max-Age=3600")).unwrap();
+        assert_eq!(values(&conn), ["root", "updated"]);
+
+        CookieTable::delete(&conn, "ID", "localhost", "/docs").unwrap();
+        assert_eq!(values(&conn), ["root"]);
+    }
+
+    #[test]
+    fn test_table_keyed_without_path_is_migrated() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE cookies (
+                id INTEGER PRIMARY KEY,
+                name TEXT,
+                value TEXT,
+                expiration INTEGER,
+                domain TEXT,
+                path TEXT,
+                secure BOOLEAN,
+                http_only BOOLEAN,
+                same_site TEXT,
+                UNIQUE (name, domain)
+            );
+            INSERT INTO cookies (name, value, expiration, domain, path, secure, http_only, same_site)
+            VALUES ('ID', 'old', 4102444800, 'localhost', '/', 0, 0, 'Lax');",
+        )
+        .unwrap();
+
+        CookieTable::create_table(&conn).unwrap();
+        CookieTable::insert(&conn, &record("ID=docs; Path=/docs; Max-Age=3600")).unwrap();
+        assert_eq!(values(&conn), ["docs", "old"]);
+
+        // The migrated table is left as it is when it is opened again.
+        CookieTable::create_table(&conn).unwrap();
+        assert_eq!(values(&conn), ["docs", "old"]);
+    }
+}
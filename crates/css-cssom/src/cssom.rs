@@ -8,7 +8,7 @@ use css_parser::{CssParser, DeclarationOrAtRule, Stylesheet};
 use serde::{Deserialize, Serialize};
 
 use crate::declaration::CSSDeclaration;
-use crate::rules::{css::CSSRule, style::CSSStyleRule};
+use crate::rules::{at::CSSAtRule, css::CSSRule, style::CSSStyleRule};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum StylesheetOrigin {
@@ -111,6 +111,57 @@ impl CSSStyleSheet {
         Ok(())
     }
 
+    /// Get the `@import` rules of the stylesheet that have a URL, in order.
+    ///
+    /// Only the `@import` rules before any other rule except `@charset` and `@layer` are valid, later ones are ignored.
+    #[must_use]
+    pub fn import_rules(&self) -> Vec<&CSSAtRule> {
+        self.rules
+            .iter()
+            .map_while(|rule| match rule {
+                CSSRule::AtRule(at_rule)
+                    if at_rule.name().eq_ignore_ascii_case("charset")
+                        || (at_rule.name().eq_ignore_ascii_case("layer") && !at_rule.has_block()) =>
+                {
+                    Some(None)
+                }
+                CSSRule::AtRule(at_rule) if at_rule.name().eq_ignore_ascii_case("import") => {
+                    Some(at_rule.import_url().map(|_| at_rule))
+                }
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Insert the rules of the stylesheets imported by this one before its own rules, so that they come first in the
+    /// cascade. The stylesheets are given in the order of their `@import` rules, each with the rule that imported it.
+    ///
+    /// The rules of a stylesheet imported with a media query list or a `supports()` condition are nested in the
+    /// equivalent `@media` and `@supports` rules, so that they only apply while the conditions hold.
+    pub fn insert_imported(&mut self, imported: Vec<(CSSAtRule, Self)>) {
+        let rules: Vec<CSSRule> = imported
+            .into_iter()
+            .flat_map(|(import, stylesheet)| {
+                let mut rules = stylesheet.rules;
+                if !import.import_media().is_empty() {
+                    rules = vec![CSSRule::AtRule(CSSAtRule::with_rules(
+                        "media",
+                        import.import_media().to_vec(),
+                        rules,
+                    ))];
+                }
+                if let Some(supports) = import.import_supports() {
+                    rules = vec![CSSRule::AtRule(CSSAtRule::with_rules(
+                        "supports", supports, rules,
+                    ))];
+                }
+                rules
+            })
+            .collect();
+        self.rules.splice(0..0, rules);
+    }
+
     /// Serialize the stylesheet back to CSS text
     fn to_css_string(&self) -> String {
         let mut result = String::new();
@@ -230,9 +281,68 @@ mod tests {
         if let CSSRule::AtRule(at_rule) = &stylesheet.css_rules()[0] {
             assert_eq!(at_rule.name(), "import");
             assert!(!at_rule.has_block());
+            assert_eq!(at_rule.import_url(), Some("styles.css"));
         }
     }
 
+    #[test]
+    fn test_import_urls() {
+        let mut stylesheet = CSSStyleSheet::from_css(
+            "@charset \"utf-8\"; @import \"a.css\"; @import url(b.css) screen; p { color: red; } @import \"late.css\";",
+            StylesheetOrigin::Author,
+            false,
+        );
+        let imports: Vec<CSSAtRule> = stylesheet.import_rules().into_iter().cloned().collect();
+        let urls: Vec<_> = imports.iter().filter_map(CSSAtRule::import_url).collect();
+        assert_eq!(urls, ["a.css", "b.css"]);
+
+        let imported = CSSStyleSheet::from_css("div { color: blue; }", StylesheetOrigin::Author, false);
+        stylesheet.insert_imported(vec![(imports[0].clone(), imported)]);
+
+        let CSSRule::Style(style) = &stylesheet.css_rules()[0] else {
+            panic!("expected the imported style rule first");
+        };
+        assert_eq!(style.selector_text(), "div");
+    }
+
+    #[test]
+    fn test_conditional_imports() {
+        let mut stylesheet = CSSStyleSheet::from_css(
+            "@import url(a.css) layer(base) supports(display: grid) screen and (min-width: 600px); \
+             @import \"b.css\" print; @import \"c.css\";",
+            StylesheetOrigin::Author,
+            false,
+        );
+        let imports: Vec<CSSAtRule> = stylesheet.import_rules().into_iter().cloned().collect();
+        assert!(imports[0].import_supports().is_some());
+        assert!(imports[1].import_supports().is_none());
+        assert!(imports[2].import_media().is_empty());
+
+        let imported = |css| CSSStyleSheet::from_css(css, StylesheetOrigin::Author, false);
+        stylesheet.insert_imported(vec![
+            (imports[0].clone(), imported("div { color: blue; }")),
+            (imports[1].clone(), imported("p { color: red; }")),
+            (imports[2].clone(), imported("a { color: green; }")),
+        ]);
+
+        let CSSRule::AtRule(supports) = &stylesheet.css_rules()[0] else {
+            panic!("expected the supports() condition first");
+        };
+        assert_eq!(supports.name(), "supports");
+        assert_eq!(supports.prelude(), "(display: grid)");
+        let CSSRule::AtRule(media) = &supports.rules[0] else {
+            panic!("expected the media query list inside the supports() condition");
+        };
+        assert_eq!(media.name(), "media");
+        assert_eq!(media.prelude(), "screen and (min-width: 600px)");
+
+        let CSSRule::AtRule(print) = &stylesheet.css_rules()[1] else {
+            panic!("expected the media query list of the second import");
+        };
+        assert_eq!(print.prelude(), "print");
+        assert!(stylesheet.css_rules()[2].is_style_rule());
+    }
+
     #[test]
     fn test_parse_media_rule() {
         let mut parser = CssParser::default();
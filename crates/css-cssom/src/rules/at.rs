@@ -1,6 +1,8 @@
 use std::fmt::Display;
 
-use css_parser::{AssociatedToken, AtRule, ComponentValue, CssTokenKind, Property, QualifiedRule, SimpleBlock};
+use css_parser::{
+    AssociatedToken, AtRule, ComponentValue, CssTokenKind, Function, Property, QualifiedRule, SimpleBlock,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -31,6 +33,10 @@ pub struct CSSAtRule {
 
     /// Whether this at-rule has a block (vs ending with semicolon)
     has_block: bool,
+
+    /// The unresolved URL of the stylesheet imported by an `@import` rule
+    #[serde(default)]
+    import_url: Option<String>,
 }
 
 impl CSSAtRule {
@@ -44,6 +50,21 @@ impl CSSAtRule {
             rules: Vec::new(),
             declarations: Vec::new(),
             has_block: false,
+            import_url: None,
+        }
+    }
+
+    /// Create an at-rule with a block of nested rules, like the `@media` rule an `@import` rule with a media query
+    /// list is equivalent to
+    pub(crate) fn with_rules(name: &str, prelude_values: Vec<ComponentValue>, rules: Vec<CSSRule>) -> Self {
+        Self {
+            name: name.to_string(),
+            prelude: prelude_to_string(&prelude_values),
+            prelude_values,
+            rules,
+            declarations: Vec::new(),
+            has_block: true,
+            import_url: None,
         }
     }
 
@@ -62,6 +83,7 @@ impl CSSAtRule {
     pub(crate) fn from_parsed(ar: AtRule, collect_positions: bool) -> Self {
         let prelude = prelude_to_string(&ar.prelude);
         let has_block = ar.block.is_some();
+        let import_url = ar.import_url().map(str::to_string);
 
         let mut css_at_rule = Self {
             name: ar.name.clone(),
@@ -70,6 +92,7 @@ impl CSSAtRule {
             rules: Vec::new(),
             declarations: Vec::new(),
             has_block,
+            import_url,
         };
 
         if let Some(block) = ar.block {
@@ -251,6 +274,79 @@ impl CSSAtRule {
         self.has_block
     }
 
+    /// Get the URL of the stylesheet imported by an `@import` rule, not yet resolved against the URL of the stylesheet
+    #[must_use]
+    pub fn import_url(&self) -> Option<&str> {
+        self.import_url.as_deref()
+    }
+
+    /// Get the `supports()` condition of an `@import` rule as the prelude of the equivalent `@supports` rule, such as
+    /// `(display: grid)` for `supports(display: grid)`
+    #[must_use]
+    pub fn import_supports(&self) -> Option<Vec<ComponentValue>> {
+        let function = self.import_conditions().0?;
+        let is_declaration = function
+            .value
+            .iter()
+            .any(|cv| matches!(cv, ComponentValue::Token(token) if token.kind == CssTokenKind::Colon));
+
+        Some(if is_declaration {
+            vec![ComponentValue::SimpleBlock(SimpleBlock {
+                associated_token: AssociatedToken::Parenthesis,
+                value: function.value.clone(),
+            })]
+        } else {
+            function.value.clone()
+        })
+    }
+
+    /// Get the media query list of an `@import` rule, the component values after its URL, layer and `supports()`
+    /// condition, which is empty when the import is unconditional
+    #[must_use]
+    pub fn import_media(&self) -> &[ComponentValue] {
+        self.import_conditions().1
+    }
+
+    /// Split the prelude of an `@import` rule after its URL and optional layer into the `supports()` condition and
+    /// the media query list
+    fn import_conditions(&self) -> (Option<&Function>, &[ComponentValue]) {
+        fn trim_start(values: &[ComponentValue]) -> &[ComponentValue] {
+            let start = values
+                .iter()
+                .position(|cv| !cv.is_whitespace())
+                .unwrap_or(values.len());
+            &values[start..]
+        }
+
+        if self.import_url.is_none() {
+            return (None, &[]);
+        }
+
+        let mut rest = trim_start(
+            trim_start(&self.prelude_values)
+                .get(1..)
+                .unwrap_or_default(),
+        );
+
+        let is_layer = match rest.first() {
+            Some(ComponentValue::Token(token)) => {
+                matches!(&token.kind, CssTokenKind::Ident(ident) if ident.eq_ignore_ascii_case("layer"))
+            }
+            Some(ComponentValue::Function(function)) => function.name.eq_ignore_ascii_case("layer"),
+            _ => false,
+        };
+        if is_layer {
+            rest = trim_start(&rest[1..]);
+        }
+
+        match rest.split_first() {
+            Some((ComponentValue::Function(function), tail)) if function.name.eq_ignore_ascii_case("supports") => {
+                (Some(function), trim_start(tail))
+            }
+            _ => (None, rest),
+        }
+    }
+
     /// Serialize this at-rule to CSS text
     fn to_css_string(&self) -> String {
         let mut result = format!("@{}", self.name);
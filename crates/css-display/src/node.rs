@@ -1,6 +1,6 @@
 use std::{fmt::Debug, ops::Deref};
 
-use css_style::ComputedStyle;
+use css_style::{ComputedStyle, GeneratedContent, PartStyles};
 use css_values::display::OutsideDisplay;
 use html_dom::NodeId;
 
@@ -55,6 +55,14 @@ pub struct BoxNode<'a> {
     pub node_id: Option<NodeId>,
     pub style: CopiedStyle<'a>,
     pub children: Vec<LayoutNodeId>,
+
+    /// The content of a `::before` or `::after` pseudo-element, which is laid out as a run of text and has no DOM node
+    /// of its own.
+    pub generated_content: Option<&'a GeneratedContent>,
+
+    /// The styles of the `::part()` pseudo-elements of a `<progress>` or `<meter>`, which are painted as part of the
+    /// box of the element.
+    pub parts: Option<&'a PartStyles>,
 }
 
 impl<'a> BoxNode<'a> {
@@ -71,6 +79,24 @@ impl<'a> BoxNode<'a> {
             node_id: Some(*node_id),
             style: CopiedStyle::Defined(style),
             children,
+            generated_content: None,
+            parts: None,
+        }
+    }
+
+    pub const fn new_generated_node(
+        parent_id: Option<LayoutNodeId>,
+        layout_id: LayoutNodeId,
+        content: &'a GeneratedContent,
+    ) -> Self {
+        Self {
+            parent_id,
+            layout_id,
+            node_id: None,
+            style: CopiedStyle::Defined(&content.style),
+            children: Vec::new(),
+            generated_content: Some(content),
+            parts: None,
         }
     }
 
@@ -92,6 +118,8 @@ impl<'a> BoxNode<'a> {
             node_id: None,
             style: CopiedStyle::Anonymous(Box::new(inherited)),
             children,
+            generated_content: None,
+            parts: None,
         }
     }
 }
@@ -104,6 +132,7 @@ impl<'a> Debug for BoxNode<'a> {
             .field("node_id", &self.node_id)
             .field("is_anonymous", &matches!(self.style, CopiedStyle::Anonymous(_)))
             .field("children", &self.children)
+            .field("generated_content", &self.generated_content.map(|content| &content.text))
             .finish()
     }
 }
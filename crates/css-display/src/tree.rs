@@ -1,6 +1,6 @@
 use std::{fmt::Debug, ops::Index};
 
-use css_style::{ComputedStyle, StyleTree};
+use css_style::{ComputedStyle, GeneratedContent, PseudoElement, StyleTree};
 use css_values::text::Whitespace;
 use html_dom::{DocumentRoot, NodeId};
 
@@ -12,6 +12,13 @@ pub enum ChildFormattingContext {
     Block,
 }
 
+/// What a box is generated from, a DOM node or the content of a `::before` or `::after` pseudo-element.
+#[derive(Debug, Clone, Copy)]
+enum BoxSource<'node> {
+    Node(&'node NodeId),
+    Generated(&'node GeneratedContent),
+}
+
 /// <https://www.w3.org/TR/CSS2/visuren.html#box-gen>
 #[derive(Debug, Clone)]
 pub struct BoxTree<'node> {
@@ -109,7 +116,9 @@ impl<'node> BoxTree<'node> {
             (parent_id.unwrap(), false)
         } else {
             let id = LayoutNodeId::new(nodes.len());
-            nodes.push(BoxNode::new(parent_id, id, node_id, style, Vec::new()));
+            let mut node = BoxNode::new(parent_id, id, node_id, style, Vec::new());
+            node.parts = style_tree.parts(*node_id);
+            nodes.push(node);
             dom_to_layout[node_id.index()] = Some(id);
             (id, true)
         };
@@ -117,19 +126,41 @@ impl<'node> BoxTree<'node> {
         let mut layout_children: Vec<LayoutNodeId> = Vec::new();
         let mut anon_children: Vec<LayoutNodeId> = Vec::new();
         let mut current_anon_id: Option<LayoutNodeId> = None;
-        for child_id in &dom[node_id].children {
-            let child_dom_node = &dom[child_id];
-            let child_style = &style_tree[child_id];
-
-            if child_style.display.is_none() || Self::is_suppressable_whitespace(child_id, style, dom) {
-                continue;
-            }
 
-            let needs_anonymous = match cfc {
-                ChildFormattingContext::Block => child_style.display.is_inline(),
-                ChildFormattingContext::Inline {
-                    has_inline_element_siblings: text_needs_wrapping,
-                } => text_needs_wrapping && child_dom_node.data.as_text().is_some(),
+        let children = style_tree
+            .pseudo_element(*node_id, PseudoElement::Before)
+            .map(BoxSource::Generated)
+            .into_iter()
+            .chain(dom[node_id].children.iter().map(BoxSource::Node))
+            .chain(
+                style_tree
+                    .pseudo_element(*node_id, PseudoElement::After)
+                    .map(BoxSource::Generated),
+            );
+
+        for child in children {
+            let needs_anonymous = match child {
+                BoxSource::Node(child_id) => {
+                    let child_style = &style_tree[child_id];
+
+                    if child_style.display.is_none() || Self::is_suppressable_whitespace(child_id, style, dom) {
+                        continue;
+                    }
+
+                    match cfc {
+                        ChildFormattingContext::Block => child_style.display.is_inline(),
+                        ChildFormattingContext::Inline {
+                            has_inline_element_siblings: text_needs_wrapping,
+                        } => text_needs_wrapping && dom[child_id].data.as_text().is_some(),
+                    }
+                }
+                // Generated content is laid out as text, so it is wrapped like the text children of the element.
+                BoxSource::Generated(_) => match cfc {
+                    ChildFormattingContext::Block => true,
+                    ChildFormattingContext::Inline {
+                        has_inline_element_siblings,
+                    } => has_inline_element_siblings,
+                },
             };
 
             if needs_anonymous {
@@ -141,22 +172,15 @@ impl<'node> BoxTree<'node> {
                 }
 
                 let anon_id = current_anon_id.unwrap();
-                anon_children.extend(Self::build_box_node(
-                    Some(anon_id),
-                    child_id,
-                    dom,
-                    style_tree,
-                    nodes,
-                    dom_to_layout,
-                ));
+                anon_children.extend(Self::build_child(Some(anon_id), child, dom, style_tree, nodes, dom_to_layout));
             } else {
                 if let Some(anon_id) = current_anon_id.take() {
                     nodes[anon_id.index()].children = std::mem::take(&mut anon_children);
                 }
 
-                layout_children.extend(Self::build_box_node(
+                layout_children.extend(Self::build_child(
                     Some(layout_id),
-                    child_id,
+                    child,
                     dom,
                     style_tree,
                     nodes,
@@ -178,6 +202,24 @@ impl<'node> BoxTree<'node> {
         }
     }
 
+    fn build_child(
+        parent_id: Option<LayoutNodeId>,
+        child: BoxSource<'node>,
+        dom: &'node DocumentRoot,
+        style_tree: &'node StyleTree,
+        nodes: &mut Vec<BoxNode<'node>>,
+        dom_to_layout: &mut [Option<LayoutNodeId>],
+    ) -> Vec<LayoutNodeId> {
+        match child {
+            BoxSource::Node(node_id) => Self::build_box_node(parent_id, node_id, dom, style_tree, nodes, dom_to_layout),
+            BoxSource::Generated(content) => {
+                let id = LayoutNodeId::new(nodes.len());
+                nodes.push(BoxNode::new_generated_node(parent_id, id, content));
+                vec![id]
+            }
+        }
+    }
+
     #[cfg(debug_assertions)]
     fn assert_continuous_tree(root_nodes: &[LayoutNodeId], nodes: &[BoxNode]) {
         for node_id in root_nodes {
@@ -251,10 +293,12 @@ impl<'node> Index<LayoutNodeId> for BoxTree<'node> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use css_style::{ComputedStyle, StyleTree};
+    use css_cssom::{CSSStyleSheet, CssParser};
+    use css_style::{AbsoluteContext, ComputedStyle, StyleTree};
     use css_values::display::OutsideDisplay;
-    use html_dom::{DocumentRoot, DomNode, Element, NodeData, NodeId, Tag};
+    use html_dom::{DocumentRoot, DomNode, Element, HtmlTag, NodeData, NodeId, Tag};
     use std::collections::{HashMap, HashSet};
+    use url::Url;
 
     fn element_node(id: usize, parent: Option<usize>, children: Vec<usize>) -> DomNode {
         DomNode {
@@ -273,6 +317,7 @@ mod tests {
                 element_node(2, Some(0), vec![]),
             ],
             root_nodes: vec![NodeId(0)],
+            ..Default::default()
         }
     }
 
@@ -328,4 +373,41 @@ mod tests {
         assert!(tree[anonymous_id].style.display.is_block());
         assert_eq!(tree[anonymous_id].children, vec![LayoutNodeId::new(2)]);
     }
+
+    #[test]
+    fn pseudo_elements_wrap_the_children() {
+        let mut dom = DocumentRoot::new();
+        let div = dom
+            .push_node(&NodeData::Element(Element::new(Tag::Html(HtmlTag::Div), HashSet::new(), HashMap::new())), None);
+        let text = dom.push_node(&NodeData::Text("Hello".to_string()), Some(div));
+
+        let css = "div { display: block } div::before { content: \"> \" } div::after { content: \"!\" }";
+        let stylesheets = [CSSStyleSheet::from(
+            CssParser::default().parse_css(css, false),
+        )];
+        let url = Url::parse("http://localhost").unwrap();
+        let styles = StyleTree::build(None, &AbsoluteContext::default_url(&url), &dom, &stylesheets);
+        let tree = BoxTree::new(&dom, &styles);
+
+        let children = &tree[tree.root_nodes[0]].children;
+        assert_eq!(children.len(), 3);
+
+        let before = &tree[children[0]];
+        assert_eq!(before.node_id, None);
+        assert_eq!(
+            before
+                .generated_content
+                .map(|content| content.text.as_str()),
+            Some("> ")
+        );
+        assert_eq!(before.parent_id, Some(tree.root_nodes[0]));
+
+        assert_eq!(tree[children[1]].node_id, Some(text));
+        assert_eq!(
+            tree[children[2]]
+                .generated_content
+                .map(|content| content.text.as_str()),
+            Some("!")
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use crate::errors::CssParsingError;
+use crate::errors::CssParseError;
 use css_tokenizer::CssTokenKind;
 
 use crate::{AssociatedToken, CssParser, SimpleBlock, consumers::component::consume_component_value};
@@ -24,7 +24,7 @@ pub fn consume_simple_block(css_parser: &mut CssParser) -> SimpleBlock {
         if let Some(token) = css_parser.peek() {
             if token.kind == CssTokenKind::Eof {
                 let pos = current.position.unwrap_or_default();
-                css_parser.record_error(CssParsingError::EofInSimpleBlock(pos));
+                css_parser.record_error(CssParseError::EofInSimpleBlock(pos));
                 break;
             }
 
@@ -37,7 +37,7 @@ pub fn consume_simple_block(css_parser: &mut CssParser) -> SimpleBlock {
             block.value.push(component_value);
         } else {
             let pos = current.position.unwrap_or_default();
-            css_parser.record_error(CssParsingError::IncompleteSimpleBlock(pos));
+            css_parser.record_error(CssParseError::IncompleteSimpleBlock(pos));
             break;
         }
     }
@@ -1,4 +1,4 @@
-use crate::{errors::CssParsingError, property::Property};
+use crate::{errors::CssParseError, property::Property};
 use css_tokenizer::{CssToken, CssTokenKind, SourcePosition};
 
 use crate::{
@@ -39,7 +39,7 @@ pub fn consume_list_of_declarations(css_parser: &mut CssParser) -> Vec<Declarati
             }
             _ => {
                 let pos = token.position.unwrap_or_default();
-                css_parser.record_error(CssParsingError::InvalidDeclarationStart(pos));
+                css_parser.record_error(CssParseError::InvalidDeclarationStart(pos));
 
                 while let Some(token) = css_parser.peek() {
                     if matches!(token.kind, CssTokenKind::Eof | CssTokenKind::Semicolon) {
@@ -66,11 +66,11 @@ fn consume_declaration_from_tokens(tokens: &[CssToken]) -> Option<Declaration> {
         if let CssTokenKind::Ident(ref ident) = token.kind {
             ident.clone()
         } else {
-            sub_parser.record_error(CssParsingError::InvalidDeclarationName(token.position.unwrap_or_default()));
+            sub_parser.record_error(CssParseError::InvalidDeclarationName(token.position.unwrap_or_default()));
             return None;
         }
     } else {
-        sub_parser.record_error(CssParsingError::EofInDeclaration(SourcePosition::default()));
+        sub_parser.record_error(CssParseError::EofInDeclaration(SourcePosition::default()));
         return None;
     };
 
@@ -80,7 +80,7 @@ fn consume_declaration_from_tokens(tokens: &[CssToken]) -> Option<Declaration> {
     sub_parser.skip_whitespace();
 
     if !matches!(sub_parser.peek().map(|t| &t.kind), Some(CssTokenKind::Colon)) {
-        sub_parser.record_error(CssParsingError::MissingColonInDeclaration(
+        sub_parser.record_error(CssParseError::MissingColonInDeclaration(
             sub_parser
                 .peek()
                 .and_then(|t| t.position)
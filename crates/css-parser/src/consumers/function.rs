@@ -1,4 +1,4 @@
-use crate::errors::CssParsingError;
+use crate::errors::CssParseError;
 use css_tokenizer::CssTokenKind;
 
 use crate::{CssParser, Function, consumers::component::consume_component_value};
@@ -25,7 +25,7 @@ pub fn consume_function(css_parser: &mut CssParser) -> Function {
         if let Some(token) = css_parser.peek() {
             match &token.kind {
                 CssTokenKind::Eof => {
-                    css_parser.record_error(CssParsingError::EofInFunction(pos));
+                    css_parser.record_error(CssParseError::EofInFunction(pos));
                     break;
                 }
                 CssTokenKind::CloseParen => {
@@ -33,7 +33,7 @@ pub fn consume_function(css_parser: &mut CssParser) -> Function {
                     break;
                 }
                 CssTokenKind::CloseCurly | CssTokenKind::CloseSquare => {
-                    css_parser.record_error(CssParsingError::IncompleteFunction(pos));
+                    css_parser.record_error(CssParseError::IncompleteFunction(pos));
                     break;
                 }
                 _ => {
@@ -41,7 +41,7 @@ pub fn consume_function(css_parser: &mut CssParser) -> Function {
                 }
             }
         } else {
-            css_parser.record_error(CssParsingError::IncompleteFunction(pos));
+            css_parser.record_error(CssParseError::IncompleteFunction(pos));
             break;
         }
     }
@@ -1,4 +1,4 @@
-use crate::errors::CssParsingError;
+use crate::errors::CssParseError;
 use css_tokenizer::{CssTokenKind, SourcePosition};
 
 use crate::{
@@ -64,7 +64,7 @@ pub fn consume_at_rule(css_parser: &mut CssParser) -> AtRule {
         if let Some(token) = css_parser.peek() {
             match &token.kind {
                 CssTokenKind::Eof => {
-                    css_parser.record_error(CssParsingError::EofInAtRule(pos));
+                    css_parser.record_error(CssParseError::EofInAtRule(pos));
                     break;
                 }
                 CssTokenKind::Semicolon => {
@@ -80,7 +80,7 @@ pub fn consume_at_rule(css_parser: &mut CssParser) -> AtRule {
                 }
             }
         } else {
-            css_parser.record_error(CssParsingError::IncompleteAtRule(pos));
+            css_parser.record_error(CssParseError::IncompleteAtRule(pos));
             break;
         }
     }
@@ -99,7 +99,7 @@ fn consume_qualified_rule(css_parser: &mut CssParser) -> Option<QualifiedRule> {
             match &token.kind {
                 CssTokenKind::Eof => {
                     let pos = token.position.unwrap_or_default();
-                    css_parser.record_error(CssParsingError::EofInQualifiedRule(pos));
+                    css_parser.record_error(CssParseError::EofInQualifiedRule(pos));
                     return None;
                 }
                 CssTokenKind::OpenCurly => {
@@ -112,7 +112,7 @@ fn consume_qualified_rule(css_parser: &mut CssParser) -> Option<QualifiedRule> {
             }
         } else {
             // Parse error, return nothing
-            css_parser.record_error(CssParsingError::IncompleteQualifiedRule(SourcePosition::default()));
+            css_parser.record_error(CssParseError::IncompleteQualifiedRule(SourcePosition::default()));
             return None;
         }
     }
@@ -2,7 +2,8 @@ use css_tokenizer::SourcePosition;
 use thiserror::Error;
 
 #[derive(Error, Debug, Clone, PartialEq, Eq, PartialOrd)]
-pub enum CssParsingError {
+#[non_exhaustive]
+pub enum CssParseError {
     #[error("incomplete simple block at {0}")]
     IncompleteSimpleBlock(SourcePosition),
 
@@ -2,7 +2,7 @@
 //!
 //! <https://www.w3.org/TR/css-syntax-3/#parsing>
 
-use crate::errors::CssParsingError;
+use crate::errors::CssParseError;
 use css_tokenizer::CssToken;
 use css_tokenizer::CssTokenKind;
 use css_tokenizer::CssTokenizer;
@@ -26,7 +26,7 @@ pub struct CssParser {
     pos: usize,
 
     /// Collected parsing errors
-    errors: Vec<CssParsingError>,
+    errors: Vec<CssParseError>,
 }
 
 impl CssParser {
@@ -58,24 +58,24 @@ impl CssParser {
         Stylesheet { rules }
     }
 
-    const fn get_error_pos(error: &CssParsingError) -> SourcePosition {
+    const fn get_error_pos(error: &CssParseError) -> SourcePosition {
         match error {
-            CssParsingError::EofInAtRule(pos)
-            | CssParsingError::EofInFunction(pos)
-            | CssParsingError::IncompleteAtRule(pos)
-            | CssParsingError::IncompleteFunction(pos)
-            | CssParsingError::InvalidDeclarationStart(pos)
-            | CssParsingError::IncompleteSimpleBlock(pos)
-            | CssParsingError::IncompleteQualifiedRule(pos)
-            | CssParsingError::EofInSimpleBlock(pos)
-            | CssParsingError::EofInQualifiedRule(pos)
-            | CssParsingError::EofInDeclaration(pos)
-            | CssParsingError::InvalidDeclarationName(pos)
-            | CssParsingError::MissingColonInDeclaration(pos) => *pos,
+            CssParseError::EofInAtRule(pos)
+            | CssParseError::EofInFunction(pos)
+            | CssParseError::IncompleteAtRule(pos)
+            | CssParseError::IncompleteFunction(pos)
+            | CssParseError::InvalidDeclarationStart(pos)
+            | CssParseError::IncompleteSimpleBlock(pos)
+            | CssParseError::IncompleteQualifiedRule(pos)
+            | CssParseError::EofInSimpleBlock(pos)
+            | CssParseError::EofInQualifiedRule(pos)
+            | CssParseError::EofInDeclaration(pos)
+            | CssParseError::InvalidDeclarationName(pos)
+            | CssParseError::MissingColonInDeclaration(pos) => *pos,
         }
     }
 
-    pub fn record_error(&mut self, error: CssParsingError) {
+    pub fn record_error(&mut self, error: CssParseError) {
         self.errors.push(error);
     }
 
@@ -144,4 +144,34 @@ impl AtRule {
             block: None,
         }
     }
+
+    /// The URL of the stylesheet an `@import` rule imports, as written in the rule and not yet resolved against the
+    /// URL of the importing stylesheet.
+    ///
+    /// # Returns
+    /// The URL of a `@import "a.css"`, `@import url(a.css)` or `@import url("a.css")` rule, `None` for any other
+    /// rule.
+    #[must_use]
+    pub fn import_url(&self) -> Option<&str> {
+        if !self.name.eq_ignore_ascii_case("import") {
+            return None;
+        }
+
+        let url = |cv: &ComponentValue| match cv {
+            ComponentValue::Token(token) => match &token.kind {
+                CssTokenKind::String(url) | CssTokenKind::Url(url) => Some(url.as_str()),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        match self.prelude.iter().find(|cv| !cv.is_whitespace())? {
+            ComponentValue::Function(function) if function.name.eq_ignore_ascii_case("url") => function
+                .value
+                .iter()
+                .find(|cv| !cv.is_whitespace())
+                .and_then(url),
+            cv => url(cv),
+        }
+    }
 }
@@ -0,0 +1,130 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use css_cssom::{CssTokenKind, HashType};
+use html_dom::Element;
+
+use crate::selector::CompoundSelectorSequence;
+
+/// The number of 64-bit words in a filter, the false positive rate drops as the filter grows.
+const FILTER_WORDS: usize = 4;
+
+/// The number of bits in a filter.
+const FILTER_BITS: u64 = (FILTER_WORDS * 64) as u64;
+
+/// The kind of a hashed feature, so that an ID, a class and a tag with the same name set different bits.
+#[derive(Hash)]
+enum Feature<'a> {
+    Id(&'a str),
+    Class(&'a str),
+    Tag(&'a str),
+}
+
+/// A Bloom filter over the IDs, class names and tag names of a selector or an element.
+///
+/// A selector filter holds the features its subject (rightmost) compound selector requires, while an element filter
+/// holds the features the element has. If a bit of the selector filter is missing from the element filter, the
+/// element definitely lacks a required feature and the selector cannot match, which lets the full match be skipped.
+/// The filter may report a possible match for an element that does not match, but never the other way around.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SelectorBloomFilter {
+    bits: [u64; FILTER_WORDS],
+}
+
+impl SelectorBloomFilter {
+    /// Builds the filter of the features required by the subject of a selector.
+    ///
+    /// Features after a pseudo-class are not required, since matching stops at the first pseudo-class of a compound
+    /// selector. Attribute selectors and `:is()` arguments are ignored.
+    ///
+    /// # Arguments
+    /// * `sequences` - The compound selector sequences of the selector
+    ///
+    /// # Returns
+    /// The filter of the required features, which is empty for a selector without any required ID, class or tag.
+    #[must_use]
+    pub fn from_selector(sequences: &[CompoundSelectorSequence]) -> Self {
+        let mut filter = Self::default();
+
+        let Some(subject) = sequences.last() else {
+            return filter;
+        };
+
+        for compound in &subject.compound_selectors {
+            let tokens = &compound.tokens;
+
+            for (i, token) in tokens.iter().enumerate() {
+                let prev = i.checked_sub(1).map(|i| &tokens[i].kind);
+
+                match &token.kind {
+                    CssTokenKind::Colon => break,
+                    CssTokenKind::Hash { value, type_flag } if *type_flag == HashType::Id => {
+                        filter.insert(&Feature::Id(&value.to_ascii_lowercase()));
+                    }
+                    CssTokenKind::Ident(name) if matches!(prev, Some(CssTokenKind::Delim('.'))) => {
+                        filter.insert(&Feature::Class(name));
+                    }
+                    CssTokenKind::Ident(name) if prev.is_none() || matches!(prev, Some(CssTokenKind::Whitespace)) => {
+                        let next = tokens.get(i + 1).map(|t| &t.kind);
+                        if name != "*" && matches!(next, None | Some(CssTokenKind::Delim(_) | CssTokenKind::Whitespace))
+                        {
+                            filter.insert(&Feature::Tag(&name.to_ascii_lowercase()));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        filter
+    }
+
+    /// Builds the filter of the features of an element, its ID, its classes and its tag name.
+    #[must_use]
+    pub fn from_element(element: &Element) -> Self {
+        let mut filter = Self::default();
+
+        filter.insert(&Feature::Tag(&element.tag_name().to_ascii_lowercase()));
+
+        if let Some(id) = element.id() {
+            filter.insert(&Feature::Id(&id.to_ascii_lowercase()));
+        }
+
+        for class in element.class_set.iter().flatten() {
+            filter.insert(&Feature::Class(class));
+        }
+
+        filter
+    }
+
+    /// Whether an element may match the selector of this filter.
+    ///
+    /// # Arguments
+    /// * `element` - The filter of the element, built with [`SelectorBloomFilter::from_element`]
+    ///
+    /// # Returns
+    /// `false` if the element definitely lacks a feature required by the selector, `true` otherwise.
+    #[must_use]
+    pub fn might_match(&self, element: &Self) -> bool {
+        self.bits
+            .iter()
+            .zip(element.bits.iter())
+            .all(|(required, present)| required & !present == 0)
+    }
+
+    /// Whether the filter has no bit set, in which case it matches every element.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|word| *word == 0)
+    }
+
+    /// Sets the two bits of a feature, both derived from a single 64-bit hash.
+    fn insert(&mut self, feature: &Feature) {
+        let mut hasher = DefaultHasher::new();
+        feature.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        for bit in [hash % FILTER_BITS, (hash >> 32) % FILTER_BITS] {
+            self.bits[(bit / 64) as usize] |= 1 << (bit % 64);
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SelectorError {
+    #[error("selector is empty")]
+    Empty,
+
+    #[error("invalid selector: {0}")]
+    InvalidSelector(String),
+
+    #[error("expected a single selector, found a list of {0}")]
+    SelectorList(usize),
+
+    #[error("invalid specificity: {0}")]
+    InvalidSpecificity(String),
+}
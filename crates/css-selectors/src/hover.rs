@@ -0,0 +1,74 @@
+use css_cssom::CssToken;
+use html_dom::Element;
+
+use crate::{
+    matching::matches_simple_selectors,
+    selector::{CompoundSelectorSequence, PseudoClass, SelectorList},
+};
+
+/// The compound selectors of the rules of a document that test `:hover`, which tell the elements whose style can
+/// depend on their hover state. The other elements entering and leaving the hover state do not need to be restyled.
+#[derive(Debug, Clone, Default)]
+pub struct HoverSelectors {
+    /// The type, class, and ID selectors of every compound selector with a `:hover`.
+    compounds: Vec<Vec<CssToken>>,
+
+    /// Whether a `:hover` is nested in `:is()`, `:not()`, or `:where()`, which may depend on the hover state of any
+    /// element.
+    nested: bool,
+}
+
+impl HoverSelectors {
+    /// Adds the compound selectors of a selector that test `:hover`.
+    ///
+    /// # Arguments
+    /// * `sequences` - The compound selector sequences of the selector.
+    pub fn add(&mut self, sequences: &[CompoundSelectorSequence]) {
+        for compound_selector in sequences
+            .iter()
+            .flat_map(|sequence| &sequence.compound_selectors)
+        {
+            for pseudo_class in &compound_selector.pseudo_classes {
+                match pseudo_class {
+                    PseudoClass::Hover => self.compounds.push(compound_selector.tokens.clone()),
+                    PseudoClass::Not(list) | PseudoClass::Is(list) | PseudoClass::Where(list)
+                        if Self::contains_hover(list) =>
+                    {
+                        self.nested = true;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Whether no selector tests `:hover`.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.compounds.is_empty() && !self.nested
+    }
+
+    /// Whether the style of an element can depend on its hover state, which is the case when its type, classes, and
+    /// ID match a compound selector with a `:hover`.
+    #[must_use]
+    pub fn may_match(&self, element: &Element) -> bool {
+        self.nested
+            || self
+                .compounds
+                .iter()
+                .any(|tokens| matches_simple_selectors(tokens, element, element.class_set.as_ref()))
+    }
+
+    fn contains_hover(selector_list: &SelectorList) -> bool {
+        selector_list
+            .iter()
+            .flatten()
+            .flat_map(|sequence| &sequence.compound_selectors)
+            .flat_map(|compound_selector| &compound_selector.pseudo_classes)
+            .any(|pseudo_class| match pseudo_class {
+                PseudoClass::Hover => true,
+                PseudoClass::Not(list) | PseudoClass::Is(list) | PseudoClass::Where(list) => Self::contains_hover(list),
+                _ => false,
+            })
+    }
+}
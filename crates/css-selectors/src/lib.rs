@@ -2,6 +2,15 @@
 //!
 //! This module provides functionality for parsing, matching, and calculating specificity of CSS selectors.
 
+/// A module for the Bloom filters used to skip selectors that cannot match an element
+mod bloom;
+
+/// A module for the errors of parsing selectors and specificities
+pub mod errors;
+
+/// A module for finding the elements whose style can depend on their hover state
+mod hover;
+
 /// A module for matching CSS selectors against DOM nodes
 mod matching;
 
@@ -14,9 +23,14 @@ mod selector;
 /// A module for calculating the specificity of CSS selectors
 mod specificity;
 
-pub use matching::{AttributeOperator, Combinator, matches_compound};
+pub use bloom::SelectorBloomFilter;
+pub use hover::HoverSelectors;
+pub use matching::{AttributeOperator, Combinator, matches_compound, matches_selector_list};
 pub use parser::CaseSensitivity;
-pub use selector::{AttributeSelector, CompoundSelector, CompoundSelectorSequence, generate_selector_list};
+pub use selector::{
+    AttributeSelector, CompoundSelector, CompoundSelectorSequence, Nth, PseudoClass, PseudoElement, PseudoElementKind,
+    SelectorList, parse_selector_list, selector_pseudo_element,
+};
 pub use specificity::{SelectorSpecificity, SpecificityCalculable};
 
 #[cfg(test)]
@@ -26,7 +40,9 @@ mod tests {
 
     use css_cssom::{AssociatedToken, ComponentValue, CssToken, CssTokenKind, HashType, SimpleBlock};
 
-    use crate::{SelectorSpecificity, SpecificityCalculable};
+    use crate::{
+        HoverSelectors, SelectorBloomFilter, SelectorSpecificity, SpecificityCalculable, errors::SelectorError,
+    };
     use crate::{matching::matches_compound, selector::generate_compound_sequences};
     use html_dom::{DocumentRoot, DomNode, Element, HtmlTag, NodeData, NodeId, Tag};
 
@@ -1319,6 +1335,42 @@ mod tests {
         assert!(matches_compound(&sequences, &tree, &node, Some(&hash_set)));
     }
 
+    #[test]
+    fn match_selector_list() {
+        let components = generate_compound_token!(
+            CssTokenKind::Ident("div".to_string()),
+            CssTokenKind::Comma,
+            CssTokenKind::Whitespace,
+            CssTokenKind::Comma,
+            CssTokenKind::Ident("span".to_string()),
+            CssTokenKind::Delim('.'),
+            CssTokenKind::Ident("note".to_string()),
+        );
+
+        let selector_list = crate::parse_selector_list(&components);
+        assert_eq!(selector_list.len(), 2);
+
+        let tree = DocumentRoot::new();
+        let node = |tag| DomNode {
+            id: NodeId(0),
+            parent: None,
+            children: Vec::new(),
+            data: NodeData::Element(Element::new(Tag::Html(tag), HashSet::new(), HashMap::default())),
+        };
+        let note = HashSet::from(["note".to_string()]);
+
+        assert!(crate::matches_selector_list(&selector_list, &tree, &node(HtmlTag::Div), Some(&HashSet::new())));
+        assert!(crate::matches_selector_list(&selector_list, &tree, &node(HtmlTag::Span), Some(&note)));
+        assert!(!crate::matches_selector_list(&selector_list, &tree, &node(HtmlTag::Span), Some(&HashSet::new())));
+        assert!(!crate::matches_selector_list(&selector_list, &tree, &node(HtmlTag::P), Some(&note)));
+        assert!(!crate::matches_selector_list(
+            &crate::SelectorList::default(),
+            &tree,
+            &node(HtmlTag::Div),
+            Some(&HashSet::new())
+        ));
+    }
+
     #[test]
     fn empty_is_argument_does_not_generate_matchable_selector() {
         let components = vec![
@@ -1409,4 +1461,424 @@ mod tests {
 
         assert!(!matches_compound(&sequences, &tree, &node, Some(&hash_set)));
     }
+
+    // === Bloom Filter Tests ===
+
+    #[test]
+    fn bloom_filter_might_match_element_with_required_features() {
+        let components = generate_compound_token!(
+            CssTokenKind::Ident("a".to_string()),
+            CssTokenKind::Delim('.'),
+            CssTokenKind::Ident("external-link".to_string()),
+            CssTokenKind::Hash {
+                value: "Main-Link".to_string(),
+                type_flag: HashType::Id,
+            },
+        );
+
+        let sequences = generate_compound_sequences(&components);
+        let filter = SelectorBloomFilter::from_selector(&sequences);
+        assert!(!filter.is_empty());
+
+        let mut hash_set = HashSet::new();
+        hash_set.insert("external-link".to_string());
+        hash_set.insert("other".to_string());
+
+        let mut attributes = HashMap::new();
+        attributes.insert("class".to_string(), "external-link other".to_string());
+        attributes.insert("id".to_string(), "main-link".to_string());
+
+        let NodeData::Element(element) = generate_node_data!(HtmlTag::A, hash_set, attributes) else {
+            unreachable!();
+        };
+
+        assert!(filter.might_match(&SelectorBloomFilter::from_element(&element)));
+    }
+
+    #[test]
+    fn bloom_filter_rejects_element_missing_a_class() {
+        let components = generate_compound_token!(
+            CssTokenKind::Ident("div".to_string()),
+            CssTokenKind::Delim('.'),
+            CssTokenKind::Ident("highlight".to_string()),
+        );
+
+        let sequences = generate_compound_sequences(&components);
+        let filter = SelectorBloomFilter::from_selector(&sequences);
+
+        let NodeData::Element(element) = generate_node_data!(HtmlTag::Div, HashSet::new(), HashMap::default()) else {
+            unreachable!();
+        };
+
+        assert!(!filter.might_match(&SelectorBloomFilter::from_element(&element)));
+    }
+
+    #[test]
+    fn bloom_filter_only_requires_subject_features() {
+        let components = generate_compound_token!(
+            CssTokenKind::Delim('.'),
+            CssTokenKind::Ident("container".to_string()),
+            CssTokenKind::Whitespace,
+            CssTokenKind::Ident("p".to_string()),
+            CssTokenKind::Colon,
+            CssTokenKind::Ident("link".to_string()),
+        );
+
+        let sequences = generate_compound_sequences(&components);
+        let filter = SelectorBloomFilter::from_selector(&sequences);
+
+        let NodeData::Element(element) = generate_node_data!(HtmlTag::P, HashSet::new(), HashMap::default()) else {
+            unreachable!();
+        };
+
+        assert!(filter.might_match(&SelectorBloomFilter::from_element(&element)));
+    }
+
+    #[test]
+    fn specificity_display_round_trip() {
+        let specificity = SelectorSpecificity::new(1, 2, 3);
+
+        assert_eq!(specificity.to_string(), "(1, 2, 3)");
+        assert_eq!("(1, 2, 3)".parse(), Ok(specificity));
+        assert_eq!(" ( 0,1 , 0 ) ".parse(), Ok(SelectorSpecificity::new(0, 1, 0)));
+    }
+
+    #[test]
+    fn specificity_from_str_rejects_invalid_notation() {
+        assert!("1, 2, 3".parse::<SelectorSpecificity>().is_err());
+        assert!("(1, 2)".parse::<SelectorSpecificity>().is_err());
+        assert!("(1, 2, 3, 4)".parse::<SelectorSpecificity>().is_err());
+        assert!("(1, -2, 3)".parse::<SelectorSpecificity>().is_err());
+    }
+
+    #[test]
+    fn specificity_from_selector_string() {
+        let (sequences, specificity) = SelectorSpecificity::from_selector_string("#main ul > li.active").unwrap();
+
+        assert_eq!(sequences.len(), 3);
+        assert_eq!(specificity, SelectorSpecificity::new(1, 1, 2));
+    }
+
+    #[test]
+    fn specificity_from_selector_string_rejects_lists() {
+        assert_eq!(SelectorSpecificity::from_selector_string("h1, h2").unwrap_err(), SelectorError::SelectorList(2));
+        assert_eq!(SelectorSpecificity::from_selector_string("  ").unwrap_err(), SelectorError::Empty);
+    }
+
+    #[test]
+    fn pseudo_element_from_selector_string() {
+        let (sequences, specificity) = SelectorSpecificity::from_selector_string("li.item::before").unwrap();
+
+        assert_eq!(crate::selector_pseudo_element(&sequences), Some(crate::PseudoElement::Before));
+        assert_eq!(specificity, SelectorSpecificity::new(0, 1, 2));
+        assert!(matches_compound(
+            &sequences,
+            &DocumentRoot::new(),
+            &list_item(),
+            Some(&HashSet::from(["item".to_string()]))
+        ));
+    }
+
+    #[test]
+    fn legacy_single_colon_pseudo_element() {
+        let (sequences, _) = SelectorSpecificity::from_selector_string("li:after").unwrap();
+
+        assert_eq!(crate::selector_pseudo_element(&sequences), Some(crate::PseudoElement::After));
+        assert!(matches_compound(&sequences, &DocumentRoot::new(), &list_item(), Some(&HashSet::new())));
+
+        let (sequences, _) = SelectorSpecificity::from_selector_string("p:first-line").unwrap();
+        assert_eq!(crate::selector_pseudo_element(&sequences), Some(crate::PseudoElement::FirstLine));
+
+        let (sequences, _) = SelectorSpecificity::from_selector_string("input:placeholder").unwrap();
+        assert_eq!(crate::selector_pseudo_element(&sequences), None);
+
+        let (sequences, _) = SelectorSpecificity::from_selector_string("li::marker").unwrap();
+        assert_eq!(crate::selector_pseudo_element(&sequences), None);
+    }
+
+    #[test]
+    fn typographic_and_highlight_pseudo_elements() {
+        let pseudo_element = |selector| {
+            let (sequences, specificity) = SelectorSpecificity::from_selector_string(selector).unwrap();
+            (crate::selector_pseudo_element(&sequences), specificity)
+        };
+
+        assert_eq!(
+            pseudo_element("p::first-letter"),
+            (Some(crate::PseudoElement::FirstLetter), SelectorSpecificity::new(0, 0, 2))
+        );
+        assert_eq!(
+            pseudo_element("input.search::placeholder"),
+            (Some(crate::PseudoElement::Placeholder), SelectorSpecificity::new(0, 1, 2))
+        );
+        assert_eq!(
+            pseudo_element("::selection"),
+            (Some(crate::PseudoElement::Selection), SelectorSpecificity::new(0, 0, 1))
+        );
+
+        assert_eq!(crate::PseudoElement::FirstLine.kind(), crate::PseudoElementKind::Typographic);
+        assert_eq!(crate::PseudoElement::Selection.kind(), crate::PseudoElementKind::Highlight);
+        assert_eq!(crate::PseudoElement::After.kind(), crate::PseudoElementKind::Generated);
+    }
+
+    #[test]
+    fn part_pseudo_elements() {
+        let (sequences, _) = SelectorSpecificity::from_selector_string("progress::part(track)").unwrap();
+        assert_eq!(crate::selector_pseudo_element(&sequences), Some(crate::PseudoElement::Track));
+
+        let (sequences, _) = SelectorSpecificity::from_selector_string("meter::part(indicator)").unwrap();
+        assert_eq!(crate::selector_pseudo_element(&sequences), Some(crate::PseudoElement::Indicator));
+
+        let (sequences, _) = SelectorSpecificity::from_selector_string("input::part(thumb)").unwrap();
+        assert_eq!(crate::selector_pseudo_element(&sequences), Some(crate::PseudoElement::Thumb));
+
+        let (sequences, _) = SelectorSpecificity::from_selector_string("meter::part(label)").unwrap();
+        assert_eq!(crate::selector_pseudo_element(&sequences), None);
+    }
+
+    #[test]
+    fn checked_pseudo_class_reads_checked_attribute() {
+        let (sequences, _) = SelectorSpecificity::from_selector_string("input:checked").unwrap();
+
+        let input = |attributes: &[(&str, &str)]| DomNode {
+            id: NodeId(0),
+            parent: None,
+            children: Vec::new(),
+            data: NodeData::Element(Element::new(
+                Tag::Html(HtmlTag::Input),
+                HashSet::new(),
+                attributes
+                    .iter()
+                    .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+                    .collect(),
+            )),
+        };
+
+        let checked = input(&[("type", "checkbox"), ("checked", "")]);
+        let unchecked = input(&[("type", "radio")]);
+        let text = input(&[("type", "text"), ("checked", "")]);
+
+        let document = DocumentRoot::new();
+        assert!(matches_compound(&sequences, &document, &checked, Some(&HashSet::new())));
+        assert!(!matches_compound(&sequences, &document, &unchecked, Some(&HashSet::new())));
+        assert!(!matches_compound(&sequences, &document, &text, Some(&HashSet::new())));
+    }
+
+    #[test]
+    fn hover_and_focus_pseudo_classes_read_element_state() {
+        let element = |tag| NodeData::Element(Element::new(Tag::Html(tag), HashSet::new(), HashMap::default()));
+
+        let mut document = DocumentRoot::new();
+        let div = document.push_node(&element(HtmlTag::Div), None);
+        let input = document.push_node(&element(HtmlTag::Input), Some(div));
+
+        let (hover, _) = SelectorSpecificity::from_selector_string("div:hover").unwrap();
+        let (focus, _) = SelectorSpecificity::from_selector_string("input:focus").unwrap();
+        assert!(!matches_compound(&hover, &document, &document[div], Some(&HashSet::new())));
+
+        let change = document.hover_change(Some(input));
+        assert_eq!(change.added, vec![div, input]);
+        document.apply_hover_change(&change);
+        assert!(matches_compound(&hover, &document, &document[div], Some(&HashSet::new())));
+
+        let change = document.hover_change(None);
+        assert_eq!(change.removed, vec![div, input]);
+        document.apply_hover_change(&change);
+        assert!(!matches_compound(&hover, &document, &document[div], Some(&HashSet::new())));
+
+        assert_eq!(document.set_focused(Some(input)), vec![input]);
+        assert!(matches_compound(&focus, &document, &document[input], Some(&HashSet::new())));
+        assert_eq!(document.set_focused(None), vec![input]);
+        assert!(!matches_compound(&focus, &document, &document[input], Some(&HashSet::new())));
+    }
+
+    #[test]
+    fn structural_pseudo_classes_count_element_siblings() {
+        let element = |tag| NodeData::Element(Element::new(Tag::Html(tag), HashSet::new(), HashMap::default()));
+
+        let mut document = DocumentRoot::new();
+        let list = document.push_node(&element(HtmlTag::Ul), None);
+        let items: Vec<_> = (0..5)
+            .map(|_| {
+                document.push_node(&NodeData::Text(" ".to_string()), Some(list));
+                document.push_node(&element(HtmlTag::Li), Some(list))
+            })
+            .collect();
+        let paragraph = document.push_node(&element(HtmlTag::P), Some(list));
+
+        let matching = |selector: &str| {
+            let (sequences, _) = SelectorSpecificity::from_selector_string(selector).unwrap();
+            items
+                .iter()
+                .chain([&paragraph])
+                .map(|id| matches_compound(&sequences, &document, &document[id], Some(&HashSet::new())))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(matching("li:nth-child(odd)"), [true, false, true, false, true, false]);
+        assert_eq!(matching(":nth-child(2n+1)"), matching("li:nth-child(odd)"));
+        assert_eq!(matching(":nth-child(-n + 3)"), [true, true, true, false, false, false]);
+        assert_eq!(matching(":nth-child(3)"), [false, false, true, false, false, false]);
+        assert_eq!(matching(":nth-last-child(2n)"), [true, false, true, false, true, false]);
+        assert_eq!(matching(":first-child"), [true, false, false, false, false, false]);
+        assert_eq!(matching(":last-child"), [false, false, false, false, false, true]);
+        assert_eq!(matching("li:last-of-type"), [false, false, false, false, true, false]);
+        assert_eq!(matching(":only-of-type"), [false, false, false, false, false, true]);
+        assert_eq!(matching(":nth-child(foo)"), [false; 6]);
+    }
+
+    #[test]
+    fn not_is_and_where_pseudo_classes() {
+        let classes = HashSet::from(["active".to_string()]);
+        let attributes = HashMap::from([("class".to_string(), "active".to_string())]);
+        let node = DomNode {
+            id: NodeId(0),
+            parent: None,
+            children: Vec::new(),
+            data: NodeData::Element(Element::new(Tag::Html(HtmlTag::Li), classes.clone(), attributes)),
+        };
+        let document = DocumentRoot::new();
+
+        let matches = |selector: &str| {
+            let (sequences, _) = SelectorSpecificity::from_selector_string(selector).unwrap();
+            matches_compound(&sequences, &document, &node, Some(&classes))
+        };
+
+        assert!(matches("li:not(.hidden)"));
+        assert!(!matches("li:not(p, .active)"));
+        assert!(matches(":is(p, li.active)"));
+        assert!(matches("li:where(.active)"));
+        assert!(!matches("li:where(p)"));
+        assert!(!matches("li:unknown"));
+    }
+
+    #[test]
+    fn pseudo_class_specificity() {
+        let specificity = |selector| {
+            SelectorSpecificity::from_selector_string(selector)
+                .unwrap()
+                .1
+        };
+
+        assert_eq!(specificity("a:hover"), SelectorSpecificity::new(0, 1, 1));
+        assert_eq!(specificity("li:nth-child(2n + 1)"), SelectorSpecificity::new(0, 1, 1));
+        assert_eq!(specificity(":is(#main, .nav) a"), SelectorSpecificity::new(1, 0, 1));
+        assert_eq!(specificity(":not(.a, p)"), SelectorSpecificity::new(0, 1, 0));
+        assert_eq!(specificity(":where(#main, .nav) a"), SelectorSpecificity::new(0, 0, 1));
+    }
+
+    #[test]
+    fn parse_an_plus_b_expressions() {
+        let nth = |argument: &str| {
+            let (sequences, _) = SelectorSpecificity::from_selector_string(&format!(":nth-child({argument})")).unwrap();
+            match sequences[0].compound_selectors[0].pseudo_classes.as_slice() {
+                [crate::PseudoClass::NthChild(nth)] => Some((nth.a, nth.b)),
+                _ => None,
+            }
+        };
+
+        assert_eq!(nth("even"), Some((2, 0)));
+        assert_eq!(nth("5"), Some((0, 5)));
+        assert_eq!(nth("n"), Some((1, 0)));
+        assert_eq!(nth("+n+2"), Some((1, 2)));
+        assert_eq!(nth("-2n-1"), Some((-2, -1)));
+        assert_eq!(nth("3n - 2"), Some((3, -2)));
+        assert_eq!(nth("-n- 4"), Some((-1, -4)));
+        assert_eq!(nth("2n+"), None);
+        assert_eq!(nth("1.5"), None);
+    }
+
+    #[test]
+    fn hover_selectors_match_elements_by_their_hover_compound() {
+        let element =
+            |tag, class: &str| Element::new(Tag::Html(tag), HashSet::from([class.to_string()]), HashMap::default());
+        let div = element(HtmlTag::Div, "card");
+        let link = element(HtmlTag::A, "nav");
+        let option = element(HtmlTag::Option, "");
+
+        let mut hover_selectors = HoverSelectors::default();
+        assert!(hover_selectors.is_empty());
+        assert!(!hover_selectors.may_match(&div));
+
+        for selector in ["select option:enabled:hover", ".card:hover > p", "p:focus"] {
+            let (sequences, _) = SelectorSpecificity::from_selector_string(selector).unwrap();
+            hover_selectors.add(&sequences);
+        }
+        assert!(!hover_selectors.is_empty());
+        assert!(hover_selectors.may_match(&option));
+        assert!(hover_selectors.may_match(&div));
+        assert!(!hover_selectors.may_match(&link));
+
+        let (sequences, _) = SelectorSpecificity::from_selector_string("p:not(:hover)").unwrap();
+        hover_selectors.add(&sequences);
+        assert!(hover_selectors.may_match(&link));
+    }
+
+    fn list_item() -> DomNode {
+        DomNode {
+            id: NodeId(0),
+            parent: None,
+            children: Vec::new(),
+            data: NodeData::Element(Element::new(Tag::Html(HtmlTag::Li), HashSet::new(), HashMap::default())),
+        }
+    }
+
+    fn matches_element(components: &[ComponentValue], tag: Tag) -> bool {
+        let sequences = generate_compound_sequences(components);
+        let tree = DocumentRoot::new();
+        let node = DomNode {
+            id: NodeId(0),
+            parent: None,
+            children: Vec::new(),
+            data: NodeData::Element(Element::new(tag, HashSet::new(), HashMap::default())),
+        };
+
+        matches_compound(&sequences, &tree, &node, Some(&HashSet::new()))
+    }
+
+    #[test]
+    fn match_namespaced_type_selector() {
+        let components = generate_compound_token!(
+            CssTokenKind::Ident("svg".to_string()),
+            CssTokenKind::Delim('|'),
+            CssTokenKind::Ident("circle".to_string()),
+        );
+
+        let sequences = generate_compound_sequences(&components);
+        assert_eq!(sequences[0].compound_selectors[0].namespace.as_deref(), Some("svg"));
+        assert_eq!(sequences[0].specificity(), SelectorSpecificity::new(0, 0, 1));
+
+        assert!(matches_element(&components, Tag::from_str_insensitive("circle")));
+        assert!(!matches_element(&components, Tag::Html(HtmlTag::Div)));
+    }
+
+    #[test]
+    fn no_match_namespace_mismatch() {
+        let components = generate_compound_token!(
+            CssTokenKind::Ident("html".to_string()),
+            CssTokenKind::Delim('|'),
+            CssTokenKind::Ident("circle".to_string()),
+        );
+
+        assert!(!matches_element(&components, Tag::from_str_insensitive("circle")));
+    }
+
+    #[test]
+    fn match_any_namespace_selector() {
+        let components = generate_compound_token!(
+            CssTokenKind::Delim('*'),
+            CssTokenKind::Delim('|'),
+            CssTokenKind::Ident("circle".to_string()),
+        );
+
+        assert_eq!(generate_compound_sequences(&components)[0].compound_selectors[0].namespace, None);
+        assert!(matches_element(&components, Tag::from_str_insensitive("circle")));
+    }
+
+    #[test]
+    fn no_match_without_namespace_selector() {
+        let components = generate_compound_token!(CssTokenKind::Delim('|'), CssTokenKind::Ident("circle".to_string()),);
+
+        assert!(!matches_element(&components, Tag::from_str_insensitive("circle")));
+    }
 }
@@ -1,11 +1,14 @@
 use std::{collections::HashSet, hash::BuildHasher};
 
 use css_cssom::{CssToken, CssTokenKind, HashType};
-use html_dom::{DocumentRoot, DomNode, Element, HtmlTag, Tag};
+use html_dom::{
+    CheckableKind, DocumentRoot, DomNode, Element, HTML_NAMESPACE, HtmlTag, MATHML_NAMESPACE, NodeData, SVG_NAMESPACE,
+    Tag,
+};
 
 use crate::{
     parser::CaseSensitivity,
-    selector::{CompoundSelector, CompoundSelectorSequence},
+    selector::{CompoundSelector, CompoundSelectorSequence, PseudoClass, SelectorList},
 };
 
 /// The operators used in attribute selectors
@@ -68,18 +71,22 @@ fn matches_compound_selectors<H: BuildHasher>(
     node: &DomNode,
 ) -> bool {
     for compound_selector in compound_selectors {
-        if !matches_simple_selectors(&compound_selector.tokens, element, class_set) {
+        if let Some(prefix) = &compound_selector.namespace
+            && !matches_namespace(prefix, &element.tag)
+        {
             return false;
         }
 
-        for is_selector_list in &compound_selector.is_selector_lists {
-            let any_match = is_selector_list
-                .iter()
-                .any(|sequence| matches_compound(sequence, tree, node, class_set));
+        if !matches_simple_selectors(&compound_selector.tokens, element, class_set) {
+            return false;
+        }
 
-            if !any_match {
-                return false;
-            }
+        if !compound_selector
+            .pseudo_classes
+            .iter()
+            .all(|pseudo_class| matches_pseudo_class(pseudo_class, element, class_set, tree, node))
+        {
+            return false;
         }
 
         for attribute_selector in &compound_selector.attribute_selectors {
@@ -230,6 +237,28 @@ fn matches_compound_selectors<H: BuildHasher>(
     true
 }
 
+/// Check if an element is in the namespace of a namespace prefix
+///
+/// Stylesheets cannot declare namespaces yet, so the prefixes are the default ones of the HTML, SVG and MathML
+/// namespaces. Every element of an HTML document has a namespace, so the empty prefix never matches.
+///
+/// # Arguments
+/// * `prefix` - The namespace prefix of the selector, e.g. `svg` in `svg|circle`
+/// * `tag` - The tag of the element to check
+///
+/// # Returns
+/// * `bool` - True if the element is in the namespace, false otherwise
+fn matches_namespace(prefix: &str, tag: &Tag) -> bool {
+    let namespace = match prefix.to_ascii_lowercase().as_str() {
+        "html" | "xhtml" => HTML_NAMESPACE,
+        "svg" => SVG_NAMESPACE,
+        "math" | "mathml" => MATHML_NAMESPACE,
+        _ => return false,
+    };
+
+    tag.namespace() == namespace
+}
+
 /// Check if an element matches a list of simple selectors
 ///
 /// # Arguments
@@ -238,7 +267,7 @@ fn matches_compound_selectors<H: BuildHasher>(
 ///
 /// # Returns
 /// * `bool` - True if the element matches the simple selectors, false otherwise
-fn matches_simple_selectors<H: BuildHasher>(
+pub(crate) fn matches_simple_selectors<H: BuildHasher>(
     simple_selectors: &[CssToken],
     element: &Element,
     class_set: Option<&HashSet<String, H>>,
@@ -256,7 +285,7 @@ fn matches_simple_selectors<H: BuildHasher>(
                 if prev.is_none() || matches!(prev, Some(CssTokenKind::Whitespace)) {
                     match next {
                         None | Some(CssTokenKind::Delim(_) | CssTokenKind::Whitespace)
-                            if Tag::from_str_insensitive(ident) != element.tag && ident != "*" =>
+                            if !element.tag.as_str().eq_ignore_ascii_case(ident) && ident != "*" =>
                         {
                             return false;
                         }
@@ -270,8 +299,6 @@ fn matches_simple_selectors<H: BuildHasher>(
                     if *delim == '.' && !class_set.contains(ident) {
                         return false;
                     }
-                } else if matches!(prev, Some(CssTokenKind::Colon)) {
-                    return false;
                 }
             }
             CssTokenKind::Hash { value, type_flag } => {
@@ -287,27 +314,120 @@ fn matches_simple_selectors<H: BuildHasher>(
                     return false;
                 }
             }
-            CssTokenKind::Colon => {
-                let _prev = previous_token.map(|t| &t.kind);
-                let next = next_token.map(|t| &t.kind);
-
-                if let Some(CssTokenKind::Ident(ident)) = next {
-                    if ident.eq_ignore_ascii_case("root") {
-                        return element.tag == Tag::Html(HtmlTag::Html);
-                    } else if ident.eq_ignore_ascii_case("link") {
-                        return element.tag == Tag::Html(HtmlTag::A) && element.has_attribute("href");
-                    }
-                }
+            // Pseudo-classes are parsed into the compound selector, so a colon left among the tokens is invalid
+            CssTokenKind::Colon => return false,
+            _ => {} // TODO: Handle other simple selectors
+        }
+    }
 
-                // TODO: Handle more pseudo-classes and pseudo-elements
+    true
+}
 
-                return false;
+/// Check if an element matches a pseudo-class
+///
+/// # Arguments
+/// * `pseudo_class` - The pseudo-class to check
+/// * `element` - The DOM element to check for a match
+/// * `tree` - The `DocumentRoot` holding the element, its siblings and its hover and focus state
+/// * `node` - The `DomNode` of the element
+///
+/// # Returns
+/// * `bool` - True if the element matches the pseudo-class, false otherwise
+fn matches_pseudo_class<H: BuildHasher>(
+    pseudo_class: &PseudoClass,
+    element: &Element,
+    class_set: Option<&HashSet<String, H>>,
+    tree: &DocumentRoot,
+    node: &DomNode,
+) -> bool {
+    match pseudo_class {
+        PseudoClass::Root => element.tag == Tag::Html(HtmlTag::Html),
+        PseudoClass::Link => element.tag == Tag::Html(HtmlTag::A) && element.has_attribute("href"),
+        PseudoClass::Checked => match element.tag {
+            Tag::Html(HtmlTag::Input) => {
+                CheckableKind::from_element(element).is_some() && element.has_attribute("checked")
             }
-            _ => {} // TODO: Handle other simple selectors
+            Tag::Html(HtmlTag::Option) => element.has_attribute("selected"),
+            _ => false,
+        },
+        PseudoClass::Hover => tree.is_hovered(node.id),
+        PseudoClass::Focus => tree.is_focused(node.id),
+        PseudoClass::Empty => node.children.iter().all(|child| match &tree[child].data {
+            NodeData::Element(_) => false,
+            NodeData::Text(text) => text.is_empty(),
+        }),
+        PseudoClass::FirstChild
+        | PseudoClass::LastChild
+        | PseudoClass::OnlyChild
+        | PseudoClass::NthChild(_)
+        | PseudoClass::NthLastChild(_) => matches_position(pseudo_class, sibling_position(tree, node, element, false)),
+        PseudoClass::FirstOfType
+        | PseudoClass::LastOfType
+        | PseudoClass::OnlyOfType
+        | PseudoClass::NthOfType(_)
+        | PseudoClass::NthLastOfType(_) => matches_position(pseudo_class, sibling_position(tree, node, element, true)),
+        PseudoClass::Not(selector_list) => !matches_selector_list(selector_list, tree, node, class_set),
+        PseudoClass::Is(selector_list) | PseudoClass::Where(selector_list) => {
+            matches_selector_list(selector_list, tree, node, class_set)
         }
+        PseudoClass::Unsupported(_) => false,
     }
+}
 
-    true
+/// Check if a position among siblings matches a structural pseudo-class
+///
+/// # Arguments
+/// * `pseudo_class` - The structural pseudo-class, such as `:first-child` or `:nth-of-type()`
+/// * `(position, count)` - The position of the element among its siblings, counting from 1, and the number of siblings
+///
+/// # Returns
+/// * `bool` - True if the position matches the pseudo-class, false otherwise
+fn matches_position(pseudo_class: &PseudoClass, (position, count): (usize, usize)) -> bool {
+    let from_start = i32::try_from(position).unwrap_or(i32::MAX);
+    let from_end = i32::try_from(count + 1 - position).unwrap_or(i32::MAX);
+
+    match pseudo_class {
+        PseudoClass::FirstChild | PseudoClass::FirstOfType => position == 1,
+        PseudoClass::LastChild | PseudoClass::LastOfType => position == count,
+        PseudoClass::OnlyChild | PseudoClass::OnlyOfType => count == 1,
+        PseudoClass::NthChild(nth) | PseudoClass::NthOfType(nth) => nth.matches(from_start),
+        PseudoClass::NthLastChild(nth) | PseudoClass::NthLastOfType(nth) => nth.matches(from_end),
+        _ => false,
+    }
+}
+
+/// Find the position of an element among the element children of its parent, or among the root nodes if it has none
+///
+/// # Arguments
+/// * `tree` - The `DocumentRoot` holding the element and its siblings
+/// * `node` - The `DomNode` of the element
+/// * `element` - The element itself
+/// * `of_type` - Whether to only count the siblings with the same tag as the element
+///
+/// # Returns
+/// * `(usize, usize)` - The position of the element, counting from 1, and the number of siblings including the element
+fn sibling_position(tree: &DocumentRoot, node: &DomNode, element: &Element, of_type: bool) -> (usize, usize) {
+    let siblings = node
+        .parent
+        .map_or(tree.root_nodes.as_slice(), |parent| tree[parent].children.as_slice());
+
+    let mut position = 1;
+    let mut count = 0;
+    for sibling in siblings {
+        let NodeData::Element(sibling_element) = &tree[sibling].data else {
+            continue;
+        };
+        if of_type && sibling_element.tag != element.tag {
+            continue;
+        }
+
+        count += 1;
+        if *sibling == node.id {
+            position = count;
+        }
+    }
+
+    (position, count.max(position))
 }
 
 /// Check if a DOM node matches a sequence of compound selectors
@@ -429,3 +549,25 @@ pub fn matches_compound<H: BuildHasher>(
 
     matches_from_index(sequence, sequence.len() - 1, tree, node, class_set)
 }
+
+/// Check if a DOM node matches any selector of a selector list
+///
+/// # Arguments
+/// * `selector_list` - The selector list, such as `div, span`
+/// * `tree` - The `DocumentRoot` representing the DOM tree
+/// * `node` - The `DomNode` to check for a match
+/// * `class_set` - The classes of the node, if it is an element
+///
+/// # Returns
+/// * `bool` - True if the node matches at least one selector of the list, false otherwise
+#[must_use]
+pub fn matches_selector_list<H: BuildHasher>(
+    selector_list: &SelectorList,
+    tree: &DocumentRoot,
+    node: &DomNode,
+    class_set: Option<&HashSet<String, H>>,
+) -> bool {
+    selector_list
+        .iter()
+        .any(|sequence| matches_compound(sequence, tree, node, class_set))
+}
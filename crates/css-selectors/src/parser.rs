@@ -1,6 +1,9 @@
-use css_cssom::{ComponentValue, CssToken, CssTokenKind};
+use css_cssom::{ComponentValue, CssToken, CssTokenKind, NumericValue};
 
-use crate::{matching::AttributeOperator, selector::AttributeSelector};
+use crate::{
+    matching::AttributeOperator,
+    selector::{AttributeSelector, Nth},
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum CaseSensitivity {
@@ -26,6 +29,68 @@ pub fn parse_attribute_selectors_components(components: &[ComponentValue]) -> Op
     parse_attribute_selector(tokens.as_slice())
 }
 
+/// Parse the `An+B` argument of the `:nth-child()` family of pseudo-classes, such as `odd`, `3` or `-n + 3`
+///
+/// # Arguments
+/// * `components` - The component values of the argument of the pseudo-class
+///
+/// # Returns
+/// * `Option<Nth>` - The expression, or `None` if it is invalid or has an `of S` selector list, which is not supported
+pub fn parse_an_plus_b(components: &[ComponentValue]) -> Option<Nth> {
+    let mut tokens = Vec::with_capacity(components.len());
+    for cv in components {
+        let token = cv.as_token()?;
+        if !matches!(token.kind, CssTokenKind::Whitespace) {
+            tokens.push(&token.kind);
+        }
+    }
+
+    let integer = |value: &NumericValue| value.to_i64().and_then(|value| i32::try_from(value).ok());
+
+    // The coefficient of `n`, the rest of the token starting at the `n`, such as the `n-1` of `2n-1`, and the tokens
+    // after it
+    let (a, unit, b_tokens) = match tokens.as_slice() {
+        [CssTokenKind::Ident(ident)] if ident.eq_ignore_ascii_case("odd") => return Some(Nth::new(2, 1)),
+        [CssTokenKind::Ident(ident)] if ident.eq_ignore_ascii_case("even") => return Some(Nth::new(2, 0)),
+        [CssTokenKind::Number(value)] => return Some(Nth::new(0, integer(value)?)),
+        [CssTokenKind::Dimension { value, unit }, rest @ ..] => (integer(value)?, unit.to_ascii_lowercase(), rest),
+        [
+            CssTokenKind::Delim('+'),
+            CssTokenKind::Ident(ident),
+            rest @ ..,
+        ] => (1, ident.to_ascii_lowercase(), rest),
+        [CssTokenKind::Ident(ident), rest @ ..] => {
+            let ident = ident.to_ascii_lowercase();
+            match ident.strip_prefix('-') {
+                Some(unit) => (-1, unit.to_string(), rest),
+                None => (1, ident, rest),
+            }
+        }
+        _ => return None,
+    };
+
+    let b = match (unit.strip_prefix('n')?, b_tokens) {
+        ("", []) => 0,
+        ("", [CssTokenKind::Number(value)] | [CssTokenKind::Delim('+'), CssTokenKind::Number(value)]) => {
+            integer(value)?
+        }
+        ("", [CssTokenKind::Delim('-'), CssTokenKind::Number(value)]) | ("-", [CssTokenKind::Number(value)]) => {
+            -integer(value)?
+        }
+        (rest, []) => {
+            let digits = rest.strip_prefix('-')?;
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+
+            -digits.parse::<i32>().ok()?
+        }
+        _ => return None,
+    };
+
+    Some(Nth::new(a, b))
+}
+
 /// Parse an attribute selector from a list of CSS tokens
 ///
 /// # Arguments
@@ -1,8 +1,8 @@
-use css_cssom::{AssociatedToken, ComponentValue, CssToken, CssTokenKind};
+use css_cssom::{AssociatedToken, ComponentValue, CssToken, CssTokenKind, Function};
 
 use crate::{
     matching::{AttributeOperator, Combinator},
-    parser::{CaseSensitivity, parse_attribute_selectors_components},
+    parser::{CaseSensitivity, parse_an_plus_b, parse_attribute_selectors_components},
 };
 
 /// A CSS attribute selector
@@ -21,6 +21,247 @@ pub struct AttributeSelector {
     pub case: Option<CaseSensitivity>,
 }
 
+/// How a pseudo-element is represented once styled, which decides where its style is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PseudoElementKind {
+    /// A box of its own inserted into the element, the `::before` and `::after` pseudo-elements.
+    Generated,
+
+    /// A portion of the text of the element, the `::first-line` and `::first-letter` pseudo-elements.
+    Typographic,
+
+    /// The selected text of the element, the `::selection` pseudo-element.
+    Highlight,
+
+    /// A part of an element the browser draws by itself, such as the placeholder of an `<input>` or the thumb of a
+    /// range slider.
+    Part,
+}
+
+/// A pseudo-element, which styles something that is not an element of the document, such as `::before`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PseudoElement {
+    /// `::before`, generated content placed before the children of the element.
+    Before,
+
+    /// `::after`, generated content placed after the children of the element.
+    After,
+
+    /// `::first-line`, the first formatted line of a block container.
+    FirstLine,
+
+    /// `::first-letter`, the first letter of the first line of a block container, along with its punctuation.
+    FirstLetter,
+
+    /// `::placeholder`, the placeholder text of an empty `<input>` or `<textarea>`.
+    Placeholder,
+
+    /// `::selection`, the part of the element selected by the user.
+    Selection,
+
+    /// `::part(track)`, the groove of a `<progress>` or `<meter>` bar, or of an `<input type="range">` slider.
+    Track,
+
+    /// `::part(indicator)`, the filled portion of a `<progress>` or `<meter>` bar.
+    Indicator,
+
+    /// `::part(thumb)`, the handle dragged along the track of an `<input type="range">`.
+    Thumb,
+}
+
+impl PseudoElement {
+    /// Looks up a pseudo-element by its name, without the leading colons.
+    ///
+    /// # Returns
+    /// The pseudo-element, or `None` if the name is not a supported pseudo-element.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "before" => Some(Self::Before),
+            "after" => Some(Self::After),
+            "first-line" => Some(Self::FirstLine),
+            "first-letter" => Some(Self::FirstLetter),
+            "placeholder" => Some(Self::Placeholder),
+            "selection" => Some(Self::Selection),
+            _ => None,
+        }
+    }
+
+    /// How the pseudo-element is represented once styled.
+    #[must_use]
+    pub const fn kind(self) -> PseudoElementKind {
+        match self {
+            Self::Before | Self::After => PseudoElementKind::Generated,
+            Self::FirstLine | Self::FirstLetter => PseudoElementKind::Typographic,
+            Self::Selection => PseudoElementKind::Highlight,
+            Self::Placeholder | Self::Track | Self::Indicator | Self::Thumb => PseudoElementKind::Part,
+        }
+    }
+
+    /// Whether the pseudo-element can also be written with a single colon, as in CSS 2, which only holds for the
+    /// pseudo-elements that existed then.
+    #[must_use]
+    pub const fn allows_single_colon(self) -> bool {
+        matches!(self, Self::Before | Self::After | Self::FirstLine | Self::FirstLetter)
+    }
+
+    /// Looks up the pseudo-element of a `::part()` function by the name of the part.
+    ///
+    /// # Returns
+    /// The pseudo-element, or `None` if no built-in element exposes a part of that name.
+    #[must_use]
+    pub fn from_part_name(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("track") {
+            Some(Self::Track)
+        } else if name.eq_ignore_ascii_case("indicator") {
+            Some(Self::Indicator)
+        } else if name.eq_ignore_ascii_case("thumb") {
+            Some(Self::Thumb)
+        } else {
+            None
+        }
+    }
+}
+
+/// An `An+B` expression of the `:nth-child()` family, which matches the elements at the positions `a * n + b` for
+/// any `n` of zero or more, counting from 1.
+///
+/// <https://drafts.csswg.org/css-syntax-3/#anb-microsyntax>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Nth {
+    pub a: i32,
+    pub b: i32,
+}
+
+impl Nth {
+    #[must_use]
+    pub const fn new(a: i32, b: i32) -> Self {
+        Self { a, b }
+    }
+
+    /// Whether the expression matches the element at a position, counting from 1.
+    #[must_use]
+    pub fn matches(self, position: i32) -> bool {
+        let a = i64::from(self.a);
+        let offset = i64::from(position) - i64::from(self.b);
+
+        if a == 0 {
+            offset == 0
+        } else {
+            offset % a == 0 && offset / a >= 0
+        }
+    }
+}
+
+/// A pseudo-class, which selects elements by their state or their position in the document rather than by their
+/// name or attributes, such as `:hover` or `:first-child`.
+#[derive(Debug)]
+pub enum PseudoClass {
+    /// `:root`, the root element of the document.
+    Root,
+
+    /// `:link`, a link that has an `href`.
+    Link,
+
+    /// `:checked`, a checked checkbox or radio button, or a selected option.
+    Checked,
+
+    /// `:hover`, an element under the pointer, or one of its ancestors.
+    Hover,
+
+    /// `:focus`, the element with the keyboard focus.
+    Focus,
+
+    /// `:empty`, an element without child elements or text.
+    Empty,
+
+    FirstChild,
+    LastChild,
+    OnlyChild,
+    FirstOfType,
+    LastOfType,
+    OnlyOfType,
+    NthChild(Nth),
+    NthLastChild(Nth),
+    NthOfType(Nth),
+    NthLastOfType(Nth),
+
+    /// `:not()`, an element matching none of the selectors of the list.
+    Not(SelectorList),
+
+    /// `:is()`, an element matching any selector of the list, as specific as its most specific selector.
+    Is(SelectorList),
+
+    /// `:where()`, like `:is()` but without any specificity.
+    Where(SelectorList),
+
+    /// A pseudo-class that is not supported, or has invalid arguments, which never matches.
+    Unsupported(String),
+}
+
+impl PseudoClass {
+    /// Looks up a pseudo-class without arguments by its name, without the leading colon.
+    #[must_use]
+    pub fn from_name(name: &str) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "root" => Self::Root,
+            "link" => Self::Link,
+            "checked" => Self::Checked,
+            "hover" => Self::Hover,
+            "focus" => Self::Focus,
+            "empty" => Self::Empty,
+            "first-child" => Self::FirstChild,
+            "last-child" => Self::LastChild,
+            "only-child" => Self::OnlyChild,
+            "first-of-type" => Self::FirstOfType,
+            "last-of-type" => Self::LastOfType,
+            "only-of-type" => Self::OnlyOfType,
+            _ => Self::Unsupported(name.to_string()),
+        }
+    }
+
+    /// Parses a functional pseudo-class, such as `:nth-child(2n + 1)` or `:not(.hidden)`.
+    ///
+    /// # Returns
+    /// The pseudo-class, or `None` for an `:is()` or `:where()` without any valid selector, which are left out of the
+    /// selector.
+    #[must_use]
+    pub fn from_function(function: &Function) -> Option<Self> {
+        let name = function.name.to_ascii_lowercase();
+        let nth = || parse_an_plus_b(&function.value);
+
+        let pseudo_class = match name.as_str() {
+            "is" | "where" => {
+                let selector_list = parse_selector_list(&function.value);
+                if selector_list.is_empty() {
+                    return None;
+                }
+
+                if name == "is" {
+                    Self::Is(selector_list)
+                } else {
+                    Self::Where(selector_list)
+                }
+            }
+            "not" => {
+                let selector_list = parse_selector_list(&function.value);
+                if selector_list.is_empty() {
+                    Self::Unsupported(name)
+                } else {
+                    Self::Not(selector_list)
+                }
+            }
+            "nth-child" => nth().map_or(Self::Unsupported(name), Self::NthChild),
+            "nth-last-child" => nth().map_or(Self::Unsupported(name), Self::NthLastChild),
+            "nth-of-type" => nth().map_or(Self::Unsupported(name), Self::NthOfType),
+            "nth-last-of-type" => nth().map_or(Self::Unsupported(name), Self::NthLastOfType),
+            _ => Self::Unsupported(name),
+        };
+
+        Some(pseudo_class)
+    }
+}
+
 /// A compound selector consisting of simple selectors and attribute selectors
 #[derive(Debug)]
 pub struct CompoundSelector {
@@ -30,10 +271,19 @@ pub struct CompoundSelector {
     /// A list of attribute selectors
     pub attribute_selectors: Vec<AttributeSelector>,
 
-    /// Selector lists from `:is()` pseudo-class arguments.
-    /// Each inner `Vec<CompoundSelectorSequence>` is one selector in the comma-separated list.
-    /// The element must match at least one of these selector lists for the `:is()` to match.
-    pub is_selector_lists: Vec<Vec<Vec<CompoundSelectorSequence>>>,
+    /// The namespace prefix of the type selector, e.g. `svg` in `svg|circle`.
+    ///
+    /// `None` matches elements in any namespace, which is also the case for `*|circle`, while an empty prefix, as in
+    /// `|circle`, only matches elements without a namespace.
+    pub namespace: Option<String>,
+
+    /// The pseudo-classes of the selector, all of which the element must match.
+    pub pseudo_classes: Vec<PseudoClass>,
+
+    /// The pseudo-element the selector ends with, e.g. `::before` in `li::before`.
+    ///
+    /// The selector then styles the pseudo-element of the matched element rather than the element itself.
+    pub pseudo_element: Option<PseudoElement>,
 }
 
 /// A sequence of compound selectors with an optional combinator
@@ -46,13 +296,64 @@ pub struct CompoundSelectorSequence {
     pub combinator: Option<Combinator>,
 }
 
-/// Generate a list of selector sequences from a list of component values
-pub fn generate_selector_list(components: &[ComponentValue]) -> Vec<Vec<CompoundSelectorSequence>> {
-    components
-        .split(|cv| matches!(cv, ComponentValue::Token(t) if matches!(t.kind, CssTokenKind::Comma)))
-        .map(generate_compound_sequences)
-        .filter(|sequences| !sequences.is_empty())
-        .collect()
+/// A comma separated list of selectors, such as `h1, h2.title`, which matches an element matching any of its
+/// selectors. Each selector is the compound selector sequences of one group of the list.
+#[derive(Debug, Default)]
+pub struct SelectorList(pub Vec<Vec<CompoundSelectorSequence>>);
+
+impl SelectorList {
+    /// The number of selectors in the list.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the list has no selector, which matches no element.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The selectors of the list, in the order they are written.
+    pub fn iter(&self) -> std::slice::Iter<'_, Vec<CompoundSelectorSequence>> {
+        self.0.iter()
+    }
+}
+
+impl IntoIterator for SelectorList {
+    type Item = Vec<CompoundSelectorSequence>;
+    type IntoIter = std::vec::IntoIter<Vec<CompoundSelectorSequence>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a SelectorList {
+    type Item = &'a Vec<CompoundSelectorSequence>;
+    type IntoIter = std::slice::Iter<'a, Vec<CompoundSelectorSequence>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Parse a selector list, splitting the component values on their commas
+///
+/// # Arguments
+/// * `components` - The component values of the selector list, such as the prelude of a style rule
+///
+/// # Returns
+/// * `SelectorList` - The selectors of the list, leaving out the empty ones
+#[must_use]
+pub fn parse_selector_list(components: &[ComponentValue]) -> SelectorList {
+    SelectorList(
+        components
+            .split(|cv| matches!(cv, ComponentValue::Token(t) if matches!(t.kind, CssTokenKind::Comma)))
+            .map(generate_compound_sequences)
+            .filter(|sequences| !sequences.is_empty())
+            .collect(),
+    )
 }
 
 /// Generate compound selector sequences from a list of component values
@@ -78,8 +379,14 @@ pub fn generate_compound_sequences(components: &[ComponentValue]) -> Vec<Compoun
         .rposition(|cv| !cv.is_whitespace())
         .map_or(0, |idx| idx + 1);
     let trimmed_components = &components[start..end];
+    let mut skip = 0;
 
     for (idx, component) in trimmed_components.iter().enumerate() {
+        if skip > 0 {
+            skip -= 1;
+            continue;
+        }
+
         match component {
             ComponentValue::SimpleBlock(block) => {
                 if block.associated_token == AssociatedToken::SquareBracket {
@@ -93,7 +400,9 @@ pub fn generate_compound_sequences(components: &[ComponentValue]) -> Vec<Compoun
                             let new_compound_selector = CompoundSelector {
                                 attribute_selectors: vec![attr_selector],
                                 tokens: Vec::new(),
-                                is_selector_lists: Vec::new(),
+                                namespace: None,
+                                pseudo_classes: Vec::new(),
+                                pseudo_element: None,
                             };
 
                             current_sequence
@@ -134,16 +443,73 @@ pub fn generate_compound_sequences(components: &[ComponentValue]) -> Vec<Compoun
                     flush_sequence(&mut current_sequence, &mut sequences);
                 }
 
+                CssTokenKind::Delim('|') => {
+                    // A namespace prefix is only valid at the start of a compound selector, before the element name.
+                    match current_sequence.compound_selectors.last_mut() {
+                        Some(cs) if cs.tokens.len() == 1 && cs.attribute_selectors.is_empty() => {
+                            match &cs.tokens[0].kind {
+                                CssTokenKind::Ident(prefix) => cs.namespace = Some(prefix.clone()),
+                                CssTokenKind::Delim('*') => cs.namespace = None,
+                                _ => continue,
+                            }
+
+                            cs.tokens.clear();
+                        }
+                        Some(_) => {}
+                        None => current_sequence.compound_selectors.push(CompoundSelector {
+                            tokens: Vec::new(),
+                            attribute_selectors: Vec::new(),
+                            namespace: Some(String::new()),
+                            pseudo_classes: Vec::new(),
+                            pseudo_element: None,
+                        }),
+                    }
+                }
                 CssTokenKind::Ident(_)
                 | CssTokenKind::Hash { .. }
                 | CssTokenKind::Delim('.' | '*')
                 | CssTokenKind::Colon => {
                     if matches!(&token.kind, CssTokenKind::Colon)
-                        && let Some(ComponentValue::Function(f)) = trimmed_components.get(idx + 1)
-                        && f.name.eq_ignore_ascii_case("is")
+                        && let Some((pseudo_element, length)) = parse_pseudo_element(&trimmed_components[idx + 1..])
                     {
+                        skip = length;
+
+                        if let Some(cs) = current_sequence.compound_selectors.last_mut() {
+                            cs.pseudo_element = Some(pseudo_element);
+                        } else {
+                            current_sequence.compound_selectors.push(CompoundSelector {
+                                tokens: Vec::new(),
+                                attribute_selectors: Vec::new(),
+                                namespace: None,
+                                pseudo_classes: Vec::new(),
+                                pseudo_element: Some(pseudo_element),
+                            });
+                        }
+
                         continue;
                     }
+
+                    if matches!(&token.kind, CssTokenKind::Colon) {
+                        match trimmed_components.get(idx + 1) {
+                            Some(ComponentValue::Token(CssToken {
+                                kind: CssTokenKind::Ident(name),
+                                ..
+                            })) => {
+                                skip = 1;
+                                push_pseudo_class(&mut current_sequence, PseudoClass::from_name(name));
+                                continue;
+                            }
+                            Some(ComponentValue::Function(function)) => {
+                                skip = 1;
+                                if let Some(pseudo_class) = PseudoClass::from_function(function) {
+                                    push_pseudo_class(&mut current_sequence, pseudo_class);
+                                }
+                                continue;
+                            }
+                            _ => {}
+                        }
+                    }
+
                     let compound_selector = current_sequence.compound_selectors.last_mut();
 
                     if let Some(cs) = compound_selector {
@@ -152,7 +518,9 @@ pub fn generate_compound_sequences(components: &[ComponentValue]) -> Vec<Compoun
                         let new_compound_selector = CompoundSelector {
                             attribute_selectors: Vec::new(),
                             tokens: vec![token.clone()],
-                            is_selector_lists: Vec::new(),
+                            namespace: None,
+                            pseudo_classes: Vec::new(),
+                            pseudo_element: None,
                         };
 
                         current_sequence
@@ -162,29 +530,8 @@ pub fn generate_compound_sequences(components: &[ComponentValue]) -> Vec<Compoun
                 }
                 _ => {}
             },
-            ComponentValue::Function(function) => {
-                if function.name.eq_ignore_ascii_case("is") {
-                    let selector_lists = generate_selector_list(&function.value);
-                    if selector_lists.is_empty() {
-                        continue;
-                    }
-
-                    let compound_selector = current_sequence.compound_selectors.last_mut();
-                    if let Some(cs) = compound_selector {
-                        cs.is_selector_lists.push(selector_lists);
-                    } else {
-                        let new_compound_selector = CompoundSelector {
-                            tokens: Vec::new(),
-                            attribute_selectors: Vec::new(),
-                            is_selector_lists: vec![selector_lists],
-                        };
-
-                        current_sequence
-                            .compound_selectors
-                            .push(new_compound_selector);
-                    }
-                }
-            }
+            // Functions are only valid as the arguments of pseudo-classes, which are read along with their colon.
+            ComponentValue::Function(_) => {}
         }
     }
 
@@ -195,6 +542,74 @@ pub fn generate_compound_sequences(components: &[ComponentValue]) -> Vec<Compoun
     sequences
 }
 
+/// Adds a pseudo-class to the last compound selector of a sequence, or to a new one if the sequence is empty.
+fn push_pseudo_class(current_sequence: &mut CompoundSelectorSequence, pseudo_class: PseudoClass) {
+    if let Some(cs) = current_sequence.compound_selectors.last_mut() {
+        cs.pseudo_classes.push(pseudo_class);
+    } else {
+        current_sequence.compound_selectors.push(CompoundSelector {
+            tokens: Vec::new(),
+            attribute_selectors: Vec::new(),
+            namespace: None,
+            pseudo_classes: vec![pseudo_class],
+            pseudo_element: None,
+        });
+    }
+}
+
+/// The pseudo-element a selector styles, which is written at the end of its last compound selector.
+///
+/// # Arguments
+/// * `sequences` - The compound selector sequences of a single selector.
+///
+/// # Returns
+/// The pseudo-element, or `None` if the selector styles the matched element itself.
+#[must_use]
+pub fn selector_pseudo_element(sequences: &[CompoundSelectorSequence]) -> Option<PseudoElement> {
+    sequences
+        .last()
+        .and_then(|sequence| sequence.compound_selectors.last())
+        .and_then(|compound_selector| compound_selector.pseudo_element)
+}
+
+/// Reads a `::before`, `::after` or `::part()` pseudo-element, or the legacy single colon `:before` and `:after` forms,
+/// from the components following a colon.
+///
+/// # Returns
+/// The pseudo-element and the number of components it spans after the colon.
+fn parse_pseudo_element(components: &[ComponentValue]) -> Option<(PseudoElement, usize)> {
+    let ident = |component: Option<&ComponentValue>| match component {
+        Some(ComponentValue::Token(CssToken {
+            kind: CssTokenKind::Ident(name),
+            ..
+        })) => PseudoElement::from_name(name),
+        _ => None,
+    };
+
+    match components.first() {
+        Some(ComponentValue::Token(CssToken {
+            kind: CssTokenKind::Colon,
+            ..
+        })) => match components.get(1) {
+            Some(ComponentValue::Function(function)) if function.name.eq_ignore_ascii_case("part") => function
+                .value
+                .iter()
+                .find_map(|argument| match argument {
+                    ComponentValue::Token(CssToken {
+                        kind: CssTokenKind::Ident(name),
+                        ..
+                    }) => PseudoElement::from_part_name(name),
+                    _ => None,
+                })
+                .map(|pseudo_element| (pseudo_element, 2)),
+            second => ident(second).map(|pseudo_element| (pseudo_element, 2)),
+        },
+        first => ident(first)
+            .filter(|pseudo_element| pseudo_element.allows_single_colon())
+            .map(|pseudo_element| (pseudo_element, 1)),
+    }
+}
+
 fn flush_sequence(current_sequence: &mut CompoundSelectorSequence, sequences: &mut Vec<CompoundSelectorSequence>) {
     if !current_sequence.compound_selectors.is_empty() {
         sequences.push(std::mem::take(current_sequence));
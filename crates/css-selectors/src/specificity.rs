@@ -1,25 +1,30 @@
 use std::{
     cmp::Ordering,
+    fmt::{Display, Formatter},
     ops::{Add, AddAssign},
+    str::FromStr,
 };
 
-use css_cssom::{CssTokenKind, HashType};
+use css_cssom::{CSSRule, CSSStyleSheet, CssParser, CssTokenKind, HashType};
 
-use crate::selector::{CompoundSelector, CompoundSelectorSequence};
+use crate::{
+    errors::SelectorError,
+    selector::{CompoundSelector, CompoundSelectorSequence, PseudoClass, parse_selector_list},
+};
 
 /// A CSS specificity value
 ///
 /// (a, b, c) where:
 /// - a: Number of ID selectors
-/// - b: Number of class selectors, attributes selectors, and (TODO: pseudo-classes)
-/// - c: Number of element selectors and (TODO: pseudo-elements)
+/// - b: Number of class selectors, attributes selectors, and pseudo-classes
+/// - c: Number of element selectors and pseudo-elements
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SelectorSpecificity(
     /// IDs
     pub u32,
-    /// Classes, attributes, and (TODO: pseudo-classes)
+    /// Classes, attributes, and pseudo-classes
     pub u32,
-    /// Element selectors and (TODO: pseudo-elements)
+    /// Element selectors and pseudo-elements
     pub u32,
 );
 
@@ -28,6 +33,78 @@ impl SelectorSpecificity {
     pub const fn new(a: u32, b: u32, c: u32) -> Self {
         Self(a, b, c)
     }
+
+    /// Parses a single selector, such as `ul > li.active`, and calculates its specificity.
+    ///
+    /// The selector goes through the same tokenizer and parser as the selectors of a stylesheet, so the result is
+    /// identical to the one of a style rule with that selector.
+    ///
+    /// # Arguments
+    /// * `selector` - The selector text, without a declaration block.
+    ///
+    /// # Returns
+    /// The compound selector sequences of the selector and its specificity.
+    ///
+    /// # Errors
+    /// * If the selector is empty, cannot be parsed, or is a comma separated list of selectors.
+    pub fn from_selector_string(selector: &str) -> Result<(Vec<CompoundSelectorSequence>, Self), SelectorError> {
+        if selector.trim().is_empty() {
+            return Err(SelectorError::Empty);
+        }
+
+        if selector.contains(['{', '}']) {
+            return Err(SelectorError::InvalidSelector(selector.to_string()));
+        }
+
+        let stylesheet = CSSStyleSheet::from(CssParser::default().parse_css(&format!("{selector} {{}}"), false));
+        let Some(CSSRule::Style(rule)) = stylesheet.css_rules().first() else {
+            return Err(SelectorError::InvalidSelector(selector.to_string()));
+        };
+
+        let mut selector_list = parse_selector_list(&rule.prelude).0;
+        let sequences = match selector_list.len() {
+            0 => return Err(SelectorError::InvalidSelector(selector.to_string())),
+            1 => selector_list.remove(0),
+            n => return Err(SelectorError::SelectorList(n)),
+        };
+
+        let specificity = sequences
+            .iter()
+            .map(SpecificityCalculable::specificity)
+            .fold(Self::default(), |acc, sp| acc + sp);
+
+        Ok((sequences, specificity))
+    }
+}
+
+impl Display for SelectorSpecificity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.0, self.1, self.2)
+    }
+}
+
+impl FromStr for SelectorSpecificity {
+    type Err = SelectorError;
+
+    /// Parses the `(a, b, c)` notation produced by [`Display`], whitespace around the components is ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || SelectorError::InvalidSpecificity(s.to_string());
+
+        let inner = s
+            .trim()
+            .strip_prefix('(')
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(invalid)?;
+
+        let mut components = inner
+            .split(',')
+            .map(|component| component.trim().parse::<u32>());
+
+        match (components.next(), components.next(), components.next(), components.next()) {
+            (Some(Ok(a)), Some(Ok(b)), Some(Ok(c)), None) => Ok(Self(a, b, c)),
+            _ => Err(invalid()),
+        }
+    }
 }
 
 impl From<(u32, u32, u32)> for SelectorSpecificity {
@@ -82,6 +159,10 @@ impl SpecificityCalculable for CompoundSelector {
 
         specificity.1 += u32::try_from(self.attribute_selectors.len()).unwrap_or(u32::MAX);
 
+        if self.pseudo_element.is_some() {
+            specificity.2 += 1;
+        }
+
         for (i, token) in self.tokens.iter().enumerate() {
             match &token.kind {
                 CssTokenKind::Hash { value, type_flag } if *type_flag == HashType::Id && !value.is_empty() => {
@@ -117,19 +198,24 @@ impl SpecificityCalculable for CompoundSelector {
             }
         }
 
-        for is_selector_list in &self.is_selector_lists {
-            let max_specificity = is_selector_list
-                .iter()
-                .map(|selector_sequence| {
-                    selector_sequence
+        for pseudo_class in &self.pseudo_classes {
+            match pseudo_class {
+                // `:is()` and `:not()` are as specific as the most specific selector of their argument
+                PseudoClass::Is(selector_list) | PseudoClass::Not(selector_list) => {
+                    specificity += selector_list
                         .iter()
-                        .map(SpecificityCalculable::specificity)
-                        .fold(SelectorSpecificity::default(), |acc, sp| acc + sp)
-                })
-                .max()
-                .unwrap_or_default();
-
-            specificity += max_specificity;
+                        .map(|selector_sequence| {
+                            selector_sequence
+                                .iter()
+                                .map(SpecificityCalculable::specificity)
+                                .fold(SelectorSpecificity::default(), |acc, sp| acc + sp)
+                        })
+                        .max()
+                        .unwrap_or_default();
+                }
+                PseudoClass::Where(_) => {}
+                _ => specificity.1 += 1,
+            }
         }
 
         specificity
@@ -1,11 +1,16 @@
 use std::collections::{HashMap, HashSet};
 
-use css_cssom::{CSSDeclaration, ComponentValue, CssTokenKind, HashType, Property, StylesheetOrigin};
-use css_selectors::{CompoundSelectorSequence, SelectorSpecificity, matches_compound};
-
+use css_cssom::{
+    CSSDeclaration, ComponentValue, ComponentValueStream, CssTokenKind, HashType, Property, StylesheetOrigin,
+};
+use css_selectors::{
+    CompoundSelectorSequence, PseudoElement, SelectorBloomFilter, SelectorSpecificity, matches_compound,
+};
+
+use css_values::{CSSParsable, global::Global};
 use html_dom::{DocumentRoot, DomNode, Element};
 
-use crate::rules::GeneratedRule;
+use crate::{container::ContainerSizes, rules::GeneratedRule};
 
 /// The key extracted from a rule's rightmost (subject) compound selector,
 /// used for fast pre-filtering of rules that cannot possibly match an element.
@@ -69,7 +74,7 @@ fn extract_key_selector(sequences: &[CompoundSelectorSequence]) -> SelectorKey {
 }
 
 /// An index that groups rules by their rightmost selector key for fast lookup.
-#[derive(Debug)]
+#[derive(Debug, Clone, Default)]
 pub struct RuleIndex {
     by_id: HashMap<String, Vec<usize>>,
     by_class: HashMap<String, Vec<usize>>,
@@ -189,84 +194,109 @@ pub struct CascadedDeclaration<'rules> {
     pub origin: StylesheetOrigin,
 }
 
-impl CascadedDeclaration<'_> {
+/// The declarations that apply to a node, collected rule by rule. The `!important` declarations are kept in a bucket of
+/// their own that is cascaded before the normal declarations, so that they win over normal declarations whatever their
+/// specificity, inline declarations included.
+#[derive(Debug, Clone, Default)]
+pub struct CascadedStyle<'rules> {
+    /// The declarations without `!important`.
+    normal: Vec<CascadedDeclaration<'rules>>,
+
+    /// The declarations with `!important`.
+    important: Vec<CascadedDeclaration<'rules>>,
+
+    /// The declarations of custom properties, important or not.
+    variables: Vec<CascadedDeclaration<'rules>>,
+
+    /// The source order given to the next declaration.
+    source_order: usize,
+}
+
+impl<'rules> CascadedStyle<'rules> {
     /// Collect all declarations that apply to the given DOM node from the provided stylesheets, including inline styles.
-    pub fn collect<'css>(
+    ///
+    /// With a `pseudo_element` only the rules styling that pseudo-element of the node are collected, and otherwise only
+    /// the rules styling the node itself. Rules nested in `@container` rules are only collected when their query matches
+    /// one of the `containers`.
+    pub fn collect(
         node: &DomNode,
+        pseudo_element: Option<PseudoElement>,
         dom: &DocumentRoot,
-        rules: &'css [GeneratedRule],
+        rules: &'rules [GeneratedRule],
         rule_index: &RuleIndex,
-        inline_declarations: &'css [CSSDeclaration],
-    ) -> (Vec<CascadedDeclaration<'css>>, Vec<CascadedDeclaration<'css>>) {
-        let mut declarations = Vec::new();
-        let mut variables = Vec::new();
-        let mut source_order: usize = 0;
+        containers: &ContainerSizes,
+        inline_declarations: &'rules [CSSDeclaration],
+    ) -> Self {
+        let mut style = Self::default();
 
         let Some(element) = node.data.as_element() else {
-            return (vec![], vec![]);
+            return style;
         };
 
         let class_set = element.class_set.as_ref();
         let candidates = rule_index.candidates(element);
+        let element_filter = SelectorBloomFilter::from_element(element);
 
         for &idx in &candidates {
             let rule = &rules[idx];
-            if matches_compound(&rule.selector_sequences, dom, node, class_set) {
-                for decl in rule.declarations {
-                    if decl.property.is_custom() {
-                        variables.push(CascadedDeclaration {
-                            property: &decl.property,
-                            values: &decl.original_values,
-                            important: decl.important,
-                            specificity: CascadeSpecificity::from(rule.specificity),
-                            source_order,
-                            origin: rule.origin,
-                        });
-                        source_order += 1;
-                        continue;
-                    }
+            if rule.pseudo_element != pseudo_element || !rule.bloom_filter.might_match(&element_filter) {
+                continue;
+            }
 
-                    declarations.push(CascadedDeclaration {
-                        property: &decl.property,
-                        values: &decl.original_values,
-                        important: decl.important,
-                        specificity: CascadeSpecificity::from(rule.specificity),
-                        source_order,
-                        origin: rule.origin,
-                    });
-                    source_order += 1;
-                }
+            if matches_compound(&rule.selector_sequences, dom, node, class_set)
+                && rule
+                    .container_query
+                    .as_ref()
+                    .is_none_or(|query| containers.matches(query, dom, node))
+            {
+                style.apply_rule(&rule.declarations, CascadeSpecificity::from(rule.specificity), rule.origin);
             }
         }
 
-        for decl in inline_declarations {
-            if decl.property.is_custom() {
-                variables.push(CascadedDeclaration {
-                    property: &decl.property,
-                    values: &decl.original_values,
-                    important: decl.important,
-                    specificity: CascadeSpecificity::inline(),
-                    source_order,
-                    origin: StylesheetOrigin::Author,
-                });
-                source_order += 1;
-                continue;
-            }
+        style.apply_rule(inline_declarations, CascadeSpecificity::inline(), StylesheetOrigin::Author);
 
-            declarations.push(CascadedDeclaration {
+        style
+    }
+
+    /// Adds the declarations of a rule, after those of the rules added before it in source order.
+    ///
+    /// # Arguments
+    /// * `declarations` - The declarations of the rule, or those of a `style` attribute.
+    /// * `specificity` - The specificity of the selector of the rule, `CascadeSpecificity::inline()` for a `style`
+    ///   attribute.
+    /// * `origin` - The origin of the stylesheet of the rule.
+    pub fn apply_rule(
+        &mut self,
+        declarations: &'rules [CSSDeclaration],
+        specificity: CascadeSpecificity,
+        origin: StylesheetOrigin,
+    ) {
+        for decl in declarations {
+            self.push(CascadedDeclaration {
                 property: &decl.property,
                 values: &decl.original_values,
                 important: decl.important,
-                specificity: CascadeSpecificity::inline(),
-                source_order,
-                origin: StylesheetOrigin::Author,
+                specificity,
+                source_order: self.source_order,
+                origin,
             });
-            source_order += 1;
+            self.source_order += 1;
         }
+    }
 
-        (declarations, variables)
+    /// Adds a single declaration to the bucket it belongs to.
+    pub fn push(&mut self, declaration: CascadedDeclaration<'rules>) {
+        if declaration.property.is_custom() {
+            self.variables.push(declaration);
+        } else if declaration.important {
+            self.important.push(declaration);
+        } else {
+            self.normal.push(declaration);
+        }
     }
+}
 
+impl CascadedDeclaration<'_> {
     const fn origin_priority(origin: StylesheetOrigin, important: bool) -> u8 {
         match (origin, important) {
             (StylesheetOrigin::UserAgent, true) => 1,
@@ -289,19 +319,53 @@ impl CascadedDeclaration<'_> {
     }
 }
 
+/// The level of an origin in the cascade, ignoring importance, a `revert` keyword rolls the cascade back to the
+/// declarations of lower levels.
+const fn origin_level(origin: StylesheetOrigin) -> u8 {
+    match origin {
+        StylesheetOrigin::UserAgent => 0,
+        StylesheetOrigin::User => 1,
+        StylesheetOrigin::Author => 2,
+    }
+}
+
+/// Whether the value of a declaration is the `revert` or `revert-layer` keyword.
+fn is_revert(values: &[ComponentValue]) -> bool {
+    let mut stream = ComponentValueStream::new(values);
+
+    matches!(Global::parse(&mut stream), Ok(Global::Revert | Global::RevertLayer)) && !stream.has_remaining_tokens()
+}
+
 /// Perform the cascade and return the final set of properties and their values after applying all cascading rules, including inline styles and !important declarations.
-pub fn cascade<'decl>(
-    declarations: &'decl mut [CascadedDeclaration],
-) -> Vec<(&'decl Property, &'decl Vec<ComponentValue>)> {
-    CascadedDeclaration::sort_declarations(declarations);
+///
+/// The bucket of `!important` declarations is cascaded first, so that a property set by one of them is never taken
+/// from a normal declaration.
+pub fn cascade<'rules>(style: &mut CascadedStyle<'rules>) -> Vec<(&'rules Property, &'rules Vec<ComponentValue>)> {
+    CascadedDeclaration::sort_declarations(&mut style.important);
+    CascadedDeclaration::sort_declarations(&mut style.normal);
 
     let mut cascaded_styles: Vec<(&Property, &Vec<ComponentValue>)> = Vec::with_capacity(32);
-    let mut seen = HashSet::with_capacity(declarations.len());
+    let mut seen = HashSet::with_capacity(style.important.len() + style.normal.len());
+    let mut reverted: HashMap<&Property, StylesheetOrigin> = HashMap::new();
 
-    for decl in declarations.iter() {
-        if seen.insert(decl.property) {
-            cascaded_styles.push((decl.property, decl.values));
+    for decl in style.important.iter().chain(&style.normal) {
+        if seen.contains(decl.property) {
+            continue;
         }
+
+        if let Some(origin) = reverted.get(decl.property)
+            && origin_level(decl.origin) >= origin_level(*origin)
+        {
+            continue;
+        }
+
+        if is_revert(decl.values) && decl.origin != StylesheetOrigin::UserAgent {
+            reverted.insert(decl.property, decl.origin);
+            continue;
+        }
+
+        seen.insert(decl.property);
+        cascaded_styles.push((decl.property, decl.values));
     }
 
     cascaded_styles.reverse();
@@ -309,14 +373,14 @@ pub fn cascade<'decl>(
 }
 
 /// Perform the cascade for custom properties (CSS variables) and return the final set of variables and their values after applying all cascading rules, including inline styles and !important declarations.
-pub fn cascade_variables<'decl>(
-    declarations: &'decl mut [CascadedDeclaration],
-) -> HashMap<&'decl Property, &'decl Vec<ComponentValue>> {
-    CascadedDeclaration::sort_declarations(declarations);
+pub fn cascade_variables<'rules>(
+    style: &mut CascadedStyle<'rules>,
+) -> HashMap<&'rules Property, &'rules Vec<ComponentValue>> {
+    CascadedDeclaration::sort_declarations(&mut style.variables);
 
     let mut cascaded_variables: HashMap<&Property, &Vec<ComponentValue>> = HashMap::with_capacity(32);
 
-    for decl in declarations.iter() {
+    for decl in &style.variables {
         cascaded_variables
             .entry(decl.property)
             .or_insert(decl.values);
@@ -324,3 +388,140 @@ pub fn cascade_variables<'decl>(
 
     cascaded_variables
 }
+
+#[cfg(test)]
+mod tests {
+    use css_cssom::CSSStyleSheet;
+
+    use super::*;
+
+    fn declaration(
+        decl: &CSSDeclaration,
+        specificity: CascadeSpecificity,
+        source_order: usize,
+        origin: StylesheetOrigin,
+    ) -> CascadedDeclaration<'_> {
+        CascadedDeclaration {
+            property: &decl.property,
+            values: &decl.original_values,
+            important: decl.important,
+            specificity,
+            source_order,
+            origin,
+        }
+    }
+
+    fn cascaded(declarations: Vec<CascadedDeclaration<'_>>) -> CascadedStyle<'_> {
+        let mut style = CascadedStyle::default();
+        for declaration in declarations {
+            style.push(declaration);
+        }
+        style
+    }
+
+    fn winner(declarations: Vec<CascadedDeclaration<'_>>) -> &Vec<ComponentValue> {
+        let cascaded = cascade(&mut cascaded(declarations));
+        assert_eq!(cascaded.len(), 1);
+        cascaded[0].1
+    }
+
+    #[test]
+    fn test_specificity_wins_over_source_order() {
+        let class = CSSStyleSheet::from_inline("color: red;");
+        let tag = CSSStyleSheet::from_inline("color: blue;");
+
+        let declarations = vec![
+            declaration(&class[0], CascadeSpecificity(0, 0, 1, 0), 0, StylesheetOrigin::Author),
+            declaration(&tag[0], CascadeSpecificity(0, 0, 0, 1), 1, StylesheetOrigin::Author),
+        ];
+
+        assert_eq!(winner(declarations), &class[0].original_values);
+    }
+
+    #[test]
+    fn test_source_order_breaks_specificity_ties() {
+        let first = CSSStyleSheet::from_inline("color: red;");
+        let second = CSSStyleSheet::from_inline("color: blue;");
+
+        let declarations = vec![
+            declaration(&second[0], CascadeSpecificity(0, 0, 1, 0), 1, StylesheetOrigin::Author),
+            declaration(&first[0], CascadeSpecificity(0, 0, 1, 0), 0, StylesheetOrigin::Author),
+        ];
+
+        assert_eq!(winner(declarations), &second[0].original_values);
+    }
+
+    #[test]
+    fn test_important_wins_over_inline() {
+        let important = CSSStyleSheet::from_inline("color: red !important;");
+        let inline = CSSStyleSheet::from_inline("color: blue;");
+
+        let declarations = vec![
+            declaration(&important[0], CascadeSpecificity(0, 0, 0, 1), 0, StylesheetOrigin::Author),
+            declaration(&inline[0], CascadeSpecificity::inline(), 1, StylesheetOrigin::Author),
+        ];
+
+        assert_eq!(winner(declarations), &important[0].original_values);
+    }
+
+    #[test]
+    fn test_apply_rule_keeps_important_declarations_apart() {
+        let author = CSSStyleSheet::from_inline("color: red !important; margin: 0;");
+        let inline = CSSStyleSheet::from_inline("color: blue; margin: 1px;");
+
+        let mut style = CascadedStyle::default();
+        style.apply_rule(&author, CascadeSpecificity(0, 0, 0, 1), StylesheetOrigin::Author);
+        style.apply_rule(&inline, CascadeSpecificity::inline(), StylesheetOrigin::Author);
+
+        assert_eq!(style.important.len(), 1);
+        assert_eq!(style.normal.len(), 3);
+        assert_eq!(style.important[0].values, &author[0].original_values);
+
+        let cascaded = cascade(&mut style);
+        assert_eq!(cascaded.len(), 2);
+        assert!(cascaded.contains(&(&author[0].property, &author[0].original_values)));
+        assert!(cascaded.contains(&(&inline[1].property, &inline[1].original_values)));
+    }
+
+    #[test]
+    fn test_important_user_agent_wins_over_important_author() {
+        let user_agent = CSSStyleSheet::from_inline("color: red !important;");
+        let author = CSSStyleSheet::from_inline("color: blue !important;");
+
+        let declarations = vec![
+            declaration(&user_agent[0], CascadeSpecificity(0, 0, 0, 1), 0, StylesheetOrigin::UserAgent),
+            declaration(&author[0], CascadeSpecificity(0, 1, 0, 0), 1, StylesheetOrigin::Author),
+        ];
+
+        assert_eq!(winner(declarations), &user_agent[0].original_values);
+    }
+
+    #[test]
+    fn test_revert_rolls_back_to_user_agent() {
+        let user_agent = CSSStyleSheet::from_inline("color: red;");
+        let author = CSSStyleSheet::from_inline("color: blue;");
+        let revert = CSSStyleSheet::from_inline("color: revert;");
+
+        let declarations = vec![
+            declaration(&user_agent[0], CascadeSpecificity(0, 0, 0, 1), 0, StylesheetOrigin::UserAgent),
+            declaration(&author[0], CascadeSpecificity(0, 0, 0, 1), 1, StylesheetOrigin::Author),
+            declaration(&revert[0], CascadeSpecificity(0, 0, 1, 0), 2, StylesheetOrigin::Author),
+        ];
+
+        assert_eq!(winner(declarations), &user_agent[0].original_values);
+    }
+
+    #[test]
+    fn test_revert_without_lower_origin_is_dropped() {
+        let revert = CSSStyleSheet::from_inline("color: revert;");
+
+        let declarations = vec![declaration(
+            &revert[0],
+            CascadeSpecificity(0, 0, 1, 0),
+            0,
+            StylesheetOrigin::Author,
+        )];
+
+        assert!(cascade(&mut cascaded(declarations)).is_empty());
+    }
+}
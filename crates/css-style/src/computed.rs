@@ -1,25 +1,39 @@
 use std::sync::Arc;
 
-use browser_preferences::BrowserPreferences;
+use css_selectors::PseudoElement;
 use css_values::{
     AlignContent, AlignItems, AlignSelf, FlexDirection, FlexWrap, JustifyContent, JustifyItems, JustifySelf,
-    OverflowAnchor, OverflowBlock, OverflowWrap,
+    OverflowAnchor, OverflowBlock, OverflowWrap, Resize,
+    background::{BlendMode, Isolation, MaskMode},
     border::{BorderStyle, BorderWidth},
-    color::{Color, base::ColorBase, named::NamedColor},
+    color::{Color, ForcedColorAdjust, base::ColorBase, named::NamedColor, system::SystemColor},
+    container::{ContainerName, ContainerType},
+    content::{Content, CounterIncrement, CounterReset, QuoteList},
     cursor::Cursor,
-    display::{Clear, Float},
-    text::{FontSize, LineHeight, TextAlign, Whitespace, WritingMode},
+    display::{Clear, ContentVisibility, Float, WillChange},
+    image::{ImageRendering, ObjectFit},
+    media::ForcedColorsState,
+    quantity::Length,
+    svg::PaintOrder,
+    text::{
+        FontFeatureSettings, FontKerning, FontSize, FontSizeAdjust, FontVariantCaps, FontVariantLigatures,
+        FontVariantNumeric, HyphenateCharacter, Hyphens, Spacing, TabSize, TextAlign, Whitespace, WordBreak,
+        WritingMode,
+    },
 };
-use html_dom::{DocumentRoot, NodeId};
+use html_dom::NodeId;
 
 use crate::{
-    AbsoluteContext, Color4f, ComputedMaxSize, ComputedSize, Display, FontFamily, Position, RelativeType, StyleContext,
-    clone_compute, compute, compute_px,
+    AbsoluteContext, Color4f, ComputedIntrinsicLength, ComputedMaxSize, ComputedSize, Display, FontFamily, Position,
+    RelativeType, StyleContext, clone_compute, compute, compute_px,
     computed::{
+        filter::ComputedFilter,
         image::ComputedBackgroundImage,
         layout::{ComputedFlexBasis, ComputedGap, compute_overflow},
-        offset::{ComputedMargin, ComputedOffset},
-        position::ComputedBackgroundSize,
+        offset::{ComputedMargin, ComputedOffset, ComputedTextIndent},
+        position::{ComputedBackgroundSize, ComputedLengthPercentage},
+        shadow::ComputedBoxShadowList,
+        text::ComputedLineHeight,
     },
     functions::variables::ScopedVariables,
     into_compute,
@@ -27,21 +41,24 @@ use crate::{
         CSSProperty, PixelRepr,
         background::{
             BackgroundAttachment, BackgroundBlendMode, BackgroundClip, BackgroundOrigin, BackgroundPositionX,
-            BackgroundPositionY, BackgroundRepeat,
+            BackgroundPositionY, BackgroundRepeat, MaskOrigin,
         },
     },
-    rules::Rules,
+    rules::CascadeContext,
     specified::SpecifiedStyle,
     tree::PropertyRegistry,
 };
 
 pub mod color;
 pub mod dimension;
+pub mod filter;
 mod handler;
 pub mod image;
 pub mod layout;
 pub mod offset;
 pub mod position;
+pub mod shadow;
+pub mod text;
 
 /// The final style resolution for a DOM node.
 ///
@@ -53,6 +70,7 @@ pub struct ComputedStyle {
     pub align_content: AlignContent,
     pub align_items: AlignItems,
     pub align_self: AlignSelf,
+    pub backdrop_filter: ComputedFilter,
     pub background_attachment: BackgroundAttachment,
     pub background_blend_mode: BackgroundBlendMode,
     pub background_clip: BackgroundClip,
@@ -69,6 +87,10 @@ pub struct ComputedStyle {
     pub border_left_color: Color4f,
     pub border_left_style: BorderStyle,
     pub border_left_width: f64,
+    pub border_radius_bottom_left: ComputedLengthPercentage,
+    pub border_radius_bottom_right: ComputedLengthPercentage,
+    pub border_radius_top_left: ComputedLengthPercentage,
+    pub border_radius_top_right: ComputedLengthPercentage,
     pub border_right_color: Color4f,
     pub border_right_style: BorderStyle,
     pub border_right_width: f64,
@@ -76,11 +98,21 @@ pub struct ComputedStyle {
     pub border_top_style: BorderStyle,
     pub border_top_width: f64,
     pub bottom: ComputedMargin,
+    pub box_shadow: ComputedBoxShadowList,
     pub clear: Clear,
     pub color: Color4f,
     pub column_gap: ComputedGap,
+    pub contain_intrinsic_height: ComputedIntrinsicLength,
+    pub contain_intrinsic_width: ComputedIntrinsicLength,
+    pub container_name: ContainerName,
+    pub container_type: ContainerType,
+    pub content: Content,
+    pub content_visibility: ContentVisibility,
+    pub counter_increment: CounterIncrement,
+    pub counter_reset: CounterReset,
     pub cursor: Cursor,
     pub display: Display,
+    pub filter: ComputedFilter,
     pub flex_basis: ComputedFlexBasis,
     pub flex_direction: FlexDirection,
     pub flex_grow: f64,
@@ -88,20 +120,41 @@ pub struct ComputedStyle {
     pub flex_wrap: FlexWrap,
     pub float: Float,
     pub font_family: Arc<FontFamily>,
+    pub font_kerning: FontKerning,
     pub font_size: f64,
-    pub font_weight: u16,
+    pub font_size_adjust: FontSizeAdjust,
+    pub font_variant_ligatures: FontVariantLigatures,
+    pub font_variant_numeric: FontVariantNumeric,
+    pub font_variant_caps: FontVariantCaps,
+    pub font_feature_settings: FontFeatureSettings,
+    pub font_weight: f32,
+    pub forced_color_adjust: ForcedColorAdjust,
     pub height: ComputedSize,
+    pub hyphenate_character: HyphenateCharacter,
+    pub hyphens: Hyphens,
+    pub image_rendering: ImageRendering,
+    pub isolation: Isolation,
     pub justify_content: JustifyContent,
     pub justify_items: JustifyItems,
     pub justify_self: JustifySelf,
     pub left: ComputedMargin,
-    pub line_height: f64,
+    pub letter_spacing: f64,
+    pub line_height: ComputedLineHeight,
     pub margin_bottom: ComputedMargin,
     pub margin_left: ComputedMargin,
     pub margin_right: ComputedMargin,
     pub margin_top: ComputedMargin,
+    pub mask_image: ComputedBackgroundImage,
+    pub mask_mode: MaskMode,
+    pub mask_origin: MaskOrigin,
+    pub mask_position_x: BackgroundPositionX,
+    pub mask_position_y: BackgroundPositionY,
+    pub mask_repeat: BackgroundRepeat,
+    pub mask_size: ComputedBackgroundSize,
     pub max_height: ComputedMaxSize,
     pub max_width: ComputedMaxSize,
+    pub mix_blend_mode: BlendMode,
+    pub object_fit: ObjectFit,
     pub order: i64,
     pub overflow_anchor: OverflowAnchor,
     pub overflow_wrap: OverflowWrap,
@@ -111,13 +164,21 @@ pub struct ComputedStyle {
     pub padding_left: ComputedOffset,
     pub padding_right: ComputedOffset,
     pub padding_top: ComputedOffset,
+    pub paint_order: PaintOrder,
     pub position: Position,
+    pub quotes: QuoteList,
+    pub resize: Resize,
     pub right: ComputedMargin,
     pub row_gap: ComputedGap,
+    pub tab_size: TabSize,
     pub text_align: TextAlign,
+    pub text_indent: ComputedTextIndent,
     pub top: ComputedMargin,
     pub whitespace: Whitespace,
     pub width: ComputedSize,
+    pub will_change: WillChange,
+    pub word_break: WordBreak,
+    pub word_spacing: f64,
     pub writing_mode: WritingMode,
 
     pub variables: Option<Arc<ScopedVariables>>,
@@ -125,16 +186,29 @@ pub struct ComputedStyle {
 
 impl ComputedStyle {
     /// Computes the `ComputedStyle` for a given node in the DOM.
+    ///
+    /// With a `pseudo_element` the style of that pseudo-element of the node is computed instead, which inherits from
+    /// the node itself, so the style of the node must already be in `styles`.
     pub fn from_node(
-        preferences: Option<&BrowserPreferences>,
-        absolute_ctx: &AbsoluteContext,
+        ctx: &CascadeContext,
         node_id: NodeId,
-        dom: &DocumentRoot,
-        rules: &Rules,
+        pseudo_element: Option<PseudoElement>,
         property_registry: &mut PropertyRegistry,
         styles: &[ComputedStyle],
     ) -> Self {
-        let parent = match dom[&node_id].parent.and_then(|pid| styles.get(*pid)) {
+        let CascadeContext {
+            preferences,
+            absolute_ctx,
+            dom,
+            ..
+        } = *ctx;
+
+        let parent_id = match pseudo_element {
+            Some(_) => Some(node_id),
+            None => dom[&node_id].parent,
+        };
+
+        let parent = match parent_id.and_then(|pid| styles.get(*pid)) {
             Some(parent) => parent,
             None => &ComputedStyle::default(),
         };
@@ -142,7 +216,7 @@ impl ComputedStyle {
         let mut style_ctx = StyleContext::new(parent);
 
         let specified_style =
-            SpecifiedStyle::from_node(absolute_ctx, &style_ctx, parent, node_id, dom, rules, property_registry);
+            SpecifiedStyle::from_node(ctx, &style_ctx, parent, node_id, pseudo_element, property_registry);
 
         let margin_top = into_compute!(specified_style, parent, margin_top);
         let margin_right = into_compute!(specified_style, parent, margin_right);
@@ -191,6 +265,14 @@ impl ComputedStyle {
             align_content: compute!(specified_style, parent, align_content),
             align_items: compute!(specified_style, parent, align_items),
             align_self: compute!(specified_style, parent, align_self),
+            backdrop_filter: ComputedFilter::resolve(
+                &specified_style
+                    .backdrop_filter
+                    .compute(parent.backdrop_filter.clone().into()),
+                &specified_style.color,
+                &style_ctx,
+                absolute_ctx,
+            ),
             background_attachment: clone_compute!(specified_style, parent, background_attachment),
             background_blend_mode: clone_compute!(specified_style, parent, background_blend_mode),
             background_clip: clone_compute!(specified_style, parent, background_clip),
@@ -270,8 +352,44 @@ impl ComputedStyle {
             border_left_width: compute_px!(specified_style, parent, border_left_width, BorderWidth)
                 .to_px(None, Some(&style_ctx), absolute_ctx)
                 .unwrap_or(0.0),
+            border_radius_bottom_left: ComputedLengthPercentage::resolve(
+                into_compute!(specified_style, parent, border_radius_bottom_left),
+                None,
+                &style_ctx,
+                absolute_ctx,
+            )
+            .unwrap_or_default(),
+            border_radius_bottom_right: ComputedLengthPercentage::resolve(
+                into_compute!(specified_style, parent, border_radius_bottom_right),
+                None,
+                &style_ctx,
+                absolute_ctx,
+            )
+            .unwrap_or_default(),
+            border_radius_top_left: ComputedLengthPercentage::resolve(
+                into_compute!(specified_style, parent, border_radius_top_left),
+                None,
+                &style_ctx,
+                absolute_ctx,
+            )
+            .unwrap_or_default(),
+            border_radius_top_right: ComputedLengthPercentage::resolve(
+                into_compute!(specified_style, parent, border_radius_top_right),
+                None,
+                &style_ctx,
+                absolute_ctx,
+            )
+            .unwrap_or_default(),
             bottom: ComputedMargin::resolve(bottom, Some(RelativeType::ParentHeight), &style_ctx, absolute_ctx)
                 .unwrap_or_default(),
+            box_shadow: ComputedBoxShadowList::resolve(
+                &specified_style
+                    .box_shadow
+                    .compute(parent.box_shadow.clone().into()),
+                &specified_style.color,
+                &style_ctx,
+                absolute_ctx,
+            ),
             clear: compute!(specified_style, parent, clear),
             color: Color4f::from_css_color_property(
                 &specified_style.color,
@@ -288,8 +406,32 @@ impl ComputedStyle {
                 absolute_ctx,
             )
             .unwrap_or_default(),
+            contain_intrinsic_height: ComputedIntrinsicLength::resolve(
+                into_compute!(specified_style, parent, contain_intrinsic_height),
+                &style_ctx,
+                absolute_ctx,
+            )
+            .unwrap_or_default(),
+            contain_intrinsic_width: ComputedIntrinsicLength::resolve(
+                into_compute!(specified_style, parent, contain_intrinsic_width),
+                &style_ctx,
+                absolute_ctx,
+            )
+            .unwrap_or_default(),
+            container_name: clone_compute!(specified_style, parent, container_name),
+            container_type: compute!(specified_style, parent, container_type),
+            content: clone_compute!(specified_style, parent, content),
+            content_visibility: compute!(specified_style, parent, content_visibility),
+            counter_increment: clone_compute!(specified_style, parent, counter_increment),
+            counter_reset: clone_compute!(specified_style, parent, counter_reset),
             cursor: compute!(specified_style, parent, cursor),
             display: compute!(specified_style, parent, display).adjust_float(float),
+            filter: ComputedFilter::resolve(
+                &specified_style.filter.compute(parent.filter.clone().into()),
+                &specified_style.color,
+                &style_ctx,
+                absolute_ctx,
+            ),
             flex_basis: ComputedFlexBasis::resolve(
                 specified_style.flex_basis.compute(parent.flex_basis.into()),
                 RelativeType::BackgroundArea, // TODO: flex container's inner main size
@@ -319,12 +461,24 @@ impl ComputedStyle {
                     .font_family
                     .compute((*parent.font_family).clone()),
             ),
+            font_kerning: compute!(specified_style, parent, font_kerning),
             font_size,
+            font_size_adjust: compute!(specified_style, parent, font_size_adjust),
+            font_variant_ligatures: compute!(specified_style, parent, font_variant_ligatures),
+            font_variant_numeric: compute!(specified_style, parent, font_variant_numeric),
+            font_variant_caps: compute!(specified_style, parent, font_variant_caps),
+            font_feature_settings: clone_compute!(specified_style, parent, font_feature_settings),
             font_weight: specified_style
                 .font_weight
-                .compute(parent.font_weight.into()) as u16,
+                .compute(parent.font_weight.into())
+                .resolve(parent.font_weight),
+            forced_color_adjust: compute!(specified_style, parent, forced_color_adjust),
             height: ComputedSize::resolve(height, RelativeType::ParentHeight, &style_ctx, absolute_ctx)
                 .unwrap_or_default(),
+            hyphenate_character: clone_compute!(specified_style, parent, hyphenate_character),
+            hyphens: compute!(specified_style, parent, hyphens),
+            image_rendering: compute!(specified_style, parent, image_rendering),
+            isolation: compute!(specified_style, parent, isolation),
             justify_content: compute!(specified_style, parent, justify_content),
             justify_items: compute!(specified_style, parent, justify_items),
             justify_self: compute!(specified_style, parent, justify_self),
@@ -332,11 +486,40 @@ impl ComputedStyle {
                 .unwrap_or(ComputedMargin::Auto),
             max_height: ComputedMaxSize::resolve(max_height, RelativeType::ParentHeight, &style_ctx, absolute_ctx)
                 .unwrap_or_default(),
-            line_height: compute_px!(specified_style, parent, line_height, LineHeight)
+            letter_spacing: compute_px!(specified_style, parent, letter_spacing, Spacing)
                 .to_px(None, Some(&style_ctx), absolute_ctx)
-                .unwrap(),
+                .unwrap_or_default(),
+            line_height: ComputedLineHeight::resolve(
+                specified_style
+                    .line_height
+                    .compute(parent.line_height.into()),
+                &style_ctx,
+                absolute_ctx,
+            )
+            .unwrap_or(parent.line_height),
             margin_top: ComputedMargin::resolve(margin_top, Some(RelativeType::ParentWidth), &style_ctx, absolute_ctx)
                 .unwrap_or_default(),
+            mask_image: ComputedBackgroundImage::resolve(
+                specified_style
+                    .mask_image
+                    .compute(parent.mask_image.clone().into())
+                    .0,
+                absolute_ctx,
+            )
+            .unwrap_or_default(),
+            mask_mode: compute!(specified_style, parent, mask_mode),
+            mask_origin: clone_compute!(specified_style, parent, mask_origin),
+            mask_position_x: clone_compute!(specified_style, parent, mask_position_x),
+            mask_position_y: clone_compute!(specified_style, parent, mask_position_y),
+            mask_repeat: clone_compute!(specified_style, parent, mask_repeat),
+            mask_size: ComputedBackgroundSize::resolve(
+                specified_style
+                    .mask_size
+                    .compute(parent.mask_size.clone().into()),
+                Some(RelativeType::BackgroundArea),
+                &style_ctx,
+                absolute_ctx,
+            ),
             margin_right: ComputedMargin::resolve(
                 margin_right,
                 Some(RelativeType::ParentWidth),
@@ -358,6 +541,8 @@ impl ComputedStyle {
                 absolute_ctx,
             )
             .unwrap_or_default(),
+            mix_blend_mode: compute!(specified_style, parent, mix_blend_mode),
+            object_fit: compute!(specified_style, parent, object_fit),
             order,
             overflow_anchor: compute!(specified_style, parent, overflow_anchor),
             overflow_wrap: compute!(specified_style, parent, overflow_wrap),
@@ -391,7 +576,10 @@ impl ComputedStyle {
                 absolute_ctx,
             )
             .unwrap_or_default(),
+            paint_order: clone_compute!(specified_style, parent, paint_order),
             position: compute!(specified_style, parent, position),
+            quotes: clone_compute!(specified_style, parent, quotes),
+            resize: compute!(specified_style, parent, resize),
             right: ComputedMargin::resolve(right, Some(RelativeType::ParentWidth), &style_ctx, absolute_ctx)
                 .unwrap_or(ComputedMargin::Auto),
             row_gap: ComputedGap::resolve(
@@ -401,12 +589,32 @@ impl ComputedStyle {
                 absolute_ctx,
             )
             .unwrap_or_default(),
+            tab_size: match compute!(specified_style, parent, tab_size) {
+                TabSize::Length(length) => TabSize::Length(Length::px(
+                    length
+                        .to_px(None, Some(&style_ctx), absolute_ctx)
+                        .unwrap_or_default(),
+                )),
+                tab_size => tab_size,
+            },
             text_align: compute!(specified_style, parent, text_align),
+            text_indent: ComputedTextIndent::resolve(
+                specified_style
+                    .text_indent
+                    .compute(parent.text_indent.into()),
+                &style_ctx,
+                absolute_ctx,
+            ),
             top: ComputedMargin::resolve(top, Some(RelativeType::ParentHeight), &style_ctx, absolute_ctx)
                 .unwrap_or(ComputedMargin::Auto),
             whitespace: compute!(specified_style, parent, whitespace),
             width: ComputedSize::resolve(width, RelativeType::ParentWidth, &style_ctx, absolute_ctx)
                 .unwrap_or_default(),
+            will_change: clone_compute!(specified_style, parent, will_change),
+            word_break: compute!(specified_style, parent, word_break),
+            word_spacing: compute_px!(specified_style, parent, word_spacing, Spacing)
+                .to_px(None, Some(&style_ctx), absolute_ctx)
+                .unwrap_or_default(),
             max_width: ComputedMaxSize::resolve(max_width, RelativeType::ParentWidth, &style_ctx, absolute_ctx)
                 .unwrap_or_default(),
             writing_mode: compute!(specified_style, parent, writing_mode),
@@ -417,6 +625,12 @@ impl ComputedStyle {
             computed.apply_dark_heuristic();
         }
 
+        if absolute_ctx.system_preferences.forced_colors == ForcedColorsState::Active
+            && computed.forced_color_adjust == ForcedColorAdjust::Auto
+        {
+            computed.apply_forced_colors(absolute_ctx);
+        }
+
         computed
     }
 
@@ -430,21 +644,96 @@ impl ComputedStyle {
         }
     }
 
+    /// The `color` of the element as sRGB gamma-encoded red, green, blue and alpha, as the shaders of the renderer
+    /// expect it. Colors of other color spaces, such as `color(display-p3 1 0 0)`, were already converted to sRGB
+    /// when the style was computed.
+    #[must_use]
+    pub fn color_as_srgb(&self) -> [f32; 4] {
+        Color4f::<f32>::from(self.color).into()
+    }
+
+    /// Replaces the colors chosen by the page with the system colors of the forced colors palette. The background
+    /// keeps its transparency, so that content behind a translucent or transparent background stays visible.
+    ///
+    /// <https://drafts.csswg.org/css-color-adjust/#forced-colors-properties>
+    pub fn apply_forced_colors(&mut self, absolute_ctx: &AbsoluteContext) {
+        let canvas = Color4f::system(SystemColor::Canvas, absolute_ctx);
+        let canvas_text = Color4f::system(SystemColor::CanvasText, absolute_ctx);
+
+        self.background_color = Color4f {
+            a: self.background_color.a,
+            ..canvas
+        };
+        self.border_bottom_color = canvas_text;
+        self.border_left_color = canvas_text;
+        self.border_right_color = canvas_text;
+        self.border_top_color = canvas_text;
+        self.color = canvas_text;
+    }
+
     pub fn inherited_subset(&self) -> Self {
         Self {
             color: self.color,
             cursor: self.cursor,
             font_family: Arc::clone(&self.font_family),
+            font_kerning: self.font_kerning,
             font_size: self.font_size,
+            font_size_adjust: self.font_size_adjust,
+            font_variant_ligatures: self.font_variant_ligatures,
+            font_variant_numeric: self.font_variant_numeric,
+            font_variant_caps: self.font_variant_caps,
+            font_feature_settings: self.font_feature_settings.clone(),
             font_weight: self.font_weight,
+            forced_color_adjust: self.forced_color_adjust,
+            hyphenate_character: self.hyphenate_character.clone(),
+            hyphens: self.hyphens,
+            image_rendering: self.image_rendering,
+            letter_spacing: self.letter_spacing,
             line_height: self.line_height,
+            overflow_wrap: self.overflow_wrap,
+            paint_order: self.paint_order.clone(),
+            quotes: self.quotes.clone(),
+            tab_size: self.tab_size,
             text_align: self.text_align,
+            text_indent: self.text_indent,
             whitespace: self.whitespace,
+            word_break: self.word_break,
+            word_spacing: self.word_spacing,
             writing_mode: self.writing_mode,
 
             ..Default::default()
         }
     }
+
+    /// Whether changing from this style to another affects the geometry of the node, rather than only how it is painted.
+    ///
+    /// Colors and the cursor are paint properties, any other difference requires the node to be laid out again.
+    #[must_use]
+    pub fn requires_layout(&self, other: &Self) -> bool {
+        self.without_paint_properties() != other.without_paint_properties()
+    }
+
+    fn without_paint_properties(&self) -> Self {
+        Self {
+            backdrop_filter: ComputedFilter::default(),
+            background_color: Color4f::TRANSPARENT,
+            border_bottom_color: Color4f::TRANSPARENT,
+            border_left_color: Color4f::TRANSPARENT,
+            border_radius_bottom_left: ComputedLengthPercentage::default(),
+            border_radius_bottom_right: ComputedLengthPercentage::default(),
+            border_radius_top_left: ComputedLengthPercentage::default(),
+            border_radius_top_right: ComputedLengthPercentage::default(),
+            border_right_color: Color4f::TRANSPARENT,
+            border_top_color: Color4f::TRANSPARENT,
+            box_shadow: ComputedBoxShadowList::default(),
+            color: Color4f::TRANSPARENT,
+            cursor: Cursor::default(),
+            filter: ComputedFilter::default(),
+            forced_color_adjust: ForcedColorAdjust::Auto,
+
+            ..self.clone()
+        }
+    }
 }
 
 impl Default for ComputedStyle {
@@ -453,6 +742,7 @@ impl Default for ComputedStyle {
             align_content: AlignContent::default(),
             align_items: AlignItems::default(),
             align_self: AlignSelf::default(),
+            backdrop_filter: ComputedFilter::default(),
             background_attachment: BackgroundAttachment::default(),
             background_blend_mode: BackgroundBlendMode::default(),
             background_clip: BackgroundClip::default(),
@@ -469,6 +759,10 @@ impl Default for ComputedStyle {
             border_left_color: Color4f::BLACK,
             border_left_style: BorderStyle::None,
             border_left_width: 0.0,
+            border_radius_bottom_left: ComputedLengthPercentage::default(),
+            border_radius_bottom_right: ComputedLengthPercentage::default(),
+            border_radius_top_left: ComputedLengthPercentage::default(),
+            border_radius_top_right: ComputedLengthPercentage::default(),
             border_right_color: Color4f::BLACK,
             border_right_style: BorderStyle::None,
             border_right_width: 0.0,
@@ -476,11 +770,21 @@ impl Default for ComputedStyle {
             border_top_style: BorderStyle::None,
             border_top_width: 0.0,
             bottom: ComputedMargin::Auto,
+            box_shadow: ComputedBoxShadowList::default(),
             clear: Clear::default(),
             color: Color4f::BLACK,
             column_gap: ComputedGap::default(),
+            contain_intrinsic_height: ComputedIntrinsicLength::None,
+            contain_intrinsic_width: ComputedIntrinsicLength::None,
+            container_name: ContainerName::default(),
+            container_type: ContainerType::Normal,
+            content: Content::default(),
+            content_visibility: ContentVisibility::Visible,
+            counter_increment: CounterIncrement::default(),
+            counter_reset: CounterReset::default(),
             cursor: Cursor::default(),
             display: Display::default(),
+            filter: ComputedFilter::default(),
             flex_basis: ComputedFlexBasis::default(),
             flex_direction: FlexDirection::default(),
             flex_grow: 0.0,
@@ -488,20 +792,41 @@ impl Default for ComputedStyle {
             flex_wrap: FlexWrap::default(),
             float: Float::default(),
             font_family: Arc::new(FontFamily::default()),
+            font_kerning: FontKerning::Auto,
             font_size: 16.0,
-            font_weight: 500,
+            font_size_adjust: FontSizeAdjust::None,
+            font_variant_ligatures: FontVariantLigatures::default(),
+            font_variant_numeric: FontVariantNumeric::default(),
+            font_variant_caps: FontVariantCaps::Normal,
+            font_feature_settings: FontFeatureSettings::default(),
+            font_weight: 500.0,
+            forced_color_adjust: ForcedColorAdjust::Auto,
             height: ComputedSize::Auto,
+            hyphenate_character: HyphenateCharacter::Auto,
+            hyphens: Hyphens::Manual,
+            image_rendering: ImageRendering::Auto,
+            isolation: Isolation::Auto,
             justify_content: JustifyContent::default(),
             justify_items: JustifyItems::default(),
             justify_self: JustifySelf::default(),
             left: ComputedMargin::Auto,
-            line_height: 1.2,
+            letter_spacing: 0.0,
+            line_height: ComputedLineHeight::Factor(1.2),
             margin_bottom: 0.0.into(),
             margin_left: 0.0.into(),
             margin_right: 0.0.into(),
             margin_top: 0.0.into(),
+            mask_image: ComputedBackgroundImage::none(),
+            mask_mode: MaskMode::MatchSource,
+            mask_origin: MaskOrigin::default(),
+            mask_position_x: BackgroundPositionX::default(),
+            mask_position_y: BackgroundPositionY::default(),
+            mask_repeat: BackgroundRepeat::default(),
+            mask_size: ComputedBackgroundSize::default(),
             max_height: ComputedMaxSize::None,
             max_width: ComputedMaxSize::None,
+            mix_blend_mode: BlendMode::Normal,
+            object_fit: ObjectFit::Fill,
             order: 0,
             overflow_anchor: OverflowAnchor::default(),
             overflow_wrap: OverflowWrap::default(),
@@ -511,13 +836,21 @@ impl Default for ComputedStyle {
             padding_left: 0.0.into(),
             padding_right: 0.0.into(),
             padding_top: 0.0.into(),
+            paint_order: PaintOrder::default(),
             position: Position::Static,
+            quotes: QuoteList::default(),
+            resize: Resize::None,
             right: ComputedMargin::Auto,
             row_gap: ComputedGap::default(),
+            tab_size: TabSize::default(),
             text_align: TextAlign::Start,
+            text_indent: ComputedTextIndent::default(),
             top: ComputedMargin::Auto,
             whitespace: Whitespace::Normal,
             width: ComputedSize::Auto,
+            will_change: WillChange::Auto,
+            word_break: WordBreak::Normal,
+            word_spacing: 0.0,
             writing_mode: WritingMode::HorizontalTb,
 
             variables: None,
@@ -1,14 +1,131 @@
-use browser_preferences::theme::ThemeCategory;
-use css_values::color::{
-    Color, ColorValue, Fraction, Hue,
-    base::{ColorBase, HexColor},
-    function::ColorFunction,
-    named::NamedColor,
-    system::SystemColor,
+use css_values::{
+    color::{
+        Color, ColorValue, Fraction, Hue,
+        base::{ColorBase, HexColor},
+        function::{ColorFunction, ColorSpace},
+        named::NamedColor,
+        system::SystemColor,
+    },
+    media::{ColorScheme, ForcedColorsState},
 };
 
 use crate::{AbsoluteContext, StyleContext, properties::CSSProperty};
 
+/// A 3x3 matrix converting linear-light components between color spaces.
+type Matrix3 = [[f64; 3]; 3];
+
+/// From linear-light Display P3 to CIE XYZ with a D65 white point.
+const DISPLAY_P3_TO_XYZ_D65: Matrix3 = [
+    [
+        0.486_570_948_648_216_2,
+        0.265_667_693_169_093_06,
+        0.198_217_285_234_362_5,
+    ],
+    [
+        0.228_974_564_069_748_8,
+        0.691_738_521_836_506_4,
+        0.079_286_914_093_745,
+    ],
+    [0.0, 0.045_113_381_858_902_64, 1.043_944_368_900_976],
+];
+
+/// From linear-light A98 RGB to CIE XYZ with a D65 white point.
+const A98_RGB_TO_XYZ_D65: Matrix3 = [
+    [
+        0.576_669_042_910_130_5,
+        0.185_558_237_906_546_3,
+        0.188_228_646_234_994_7,
+    ],
+    [
+        0.297_344_975_250_536_05,
+        0.627_363_566_255_466_1,
+        0.075_291_458_493_997_88,
+    ],
+    [
+        0.027_031_361_386_412_34,
+        0.070_688_852_535_827_23,
+        0.991_337_536_837_638_8,
+    ],
+];
+
+/// From linear-light ProPhoto RGB to CIE XYZ with a D50 white point.
+const PROPHOTO_RGB_TO_XYZ_D50: Matrix3 = [
+    [
+        0.797_766_644_900_642_3,
+        0.135_181_297_400_533_08,
+        0.031_347_734_128_392_2,
+    ],
+    [
+        0.288_074_828_819_401_3,
+        0.711_835_234_241_873,
+        0.000_089_936_938_725_64,
+    ],
+    [0.0, 0.0, 0.825_104_602_510_460_2],
+];
+
+/// From linear-light Rec. 2020 to CIE XYZ with a D65 white point.
+const REC2020_TO_XYZ_D65: Matrix3 = [
+    [
+        0.636_958_048_301_291_4,
+        0.144_616_903_586_208_32,
+        0.168_880_975_164_172_1,
+    ],
+    [
+        0.262_700_212_011_267_1,
+        0.677_998_071_518_870_8,
+        0.059_301_716_469_861_96,
+    ],
+    [0.0, 0.028_072_693_049_087_428, 1.060_985_057_710_791],
+];
+
+/// The Bradford chromatic adaptation from a D50 to a D65 white point.
+const XYZ_D50_TO_D65: Matrix3 = [
+    [
+        0.955_473_421_488_075,
+        -0.023_098_454_948_764_71,
+        0.063_259_243_200_570_72,
+    ],
+    [
+        -0.028_369_709_333_863_7,
+        1.009_995_398_081_304_1,
+        0.021_041_441_191_917_323,
+    ],
+    [
+        0.012_314_014_864_481_998,
+        -0.020_507_649_298_898_964,
+        1.330_365_926_242_124,
+    ],
+];
+
+/// From CIE XYZ with a D65 white point to linear-light sRGB.
+const XYZ_D65_TO_LINEAR_SRGB: Matrix3 = [
+    [
+        3.240_969_941_904_522_6,
+        -1.537_383_177_570_094,
+        -0.498_610_760_293_003_4,
+    ],
+    [
+        -0.969_243_636_280_879_6,
+        1.875_967_501_507_720_2,
+        0.041_555_057_407_175_59,
+    ],
+    [
+        0.055_630_079_696_993_66,
+        -0.203_976_958_888_976_52,
+        1.056_971_514_242_878_6,
+    ],
+];
+
+/// Multiplies a matrix with a column vector of three components.
+fn multiply(matrix: &Matrix3, [x, y, z]: [f64; 3]) -> [f64; 3] {
+    matrix.map(|[a, b, c]| a.mul_add(x, b.mul_add(y, c * z)))
+}
+
+/// Applies a transfer function to the magnitude of a component, keeping its sign for components outside the gamut.
+fn signed(c: f64, transfer: impl Fn(f64) -> f64) -> f64 {
+    transfer(c.abs()).copysign(c)
+}
+
 /// RGBA color representation for rendering (values 0.0-1.0, sRGB gamma-encoded)
 ///
 /// All conversion paths produce **sRGB gamma-encoded** values so that colours
@@ -74,9 +191,9 @@ impl Color4f {
             match resolved {
                 Color::Current => return None,
                 Color::LightDark(light, dark) => {
-                    resolved = match absolute_ctx.theme_category {
-                        ThemeCategory::Light => light.as_ref(),
-                        ThemeCategory::Dark => dark.as_ref(),
+                    resolved = match absolute_ctx.system_preferences.prefers_color_scheme {
+                        ColorScheme::Light => light.as_ref(),
+                        ColorScheme::Dark => dark.as_ref(),
                     };
                 }
                 _ => return Some(resolved),
@@ -85,7 +202,7 @@ impl Color4f {
     }
 
     /// Converts a CSS Color to Color4f
-    fn from_css_color(
+    pub(crate) fn from_css_color(
         color: &Color,
         text_color: &CSSProperty<Color>,
         style_ctx: &StyleContext,
@@ -100,11 +217,11 @@ impl Color4f {
                 || style_ctx.parent_style.color,
                 |resolved| Self::from_css_color(resolved, text_color, style_ctx, absolute_ctx),
             ),
-            Color::System(system) => Self::from(*system),
+            Color::System(system) => Self::system(*system, absolute_ctx),
             Color::LightDark(light, dark) => {
-                let branch = match absolute_ctx.theme_category {
-                    ThemeCategory::Light => light.as_ref(),
-                    ThemeCategory::Dark => dark.as_ref(),
+                let branch = match absolute_ctx.system_preferences.prefers_color_scheme {
+                    ColorScheme::Light => light.as_ref(),
+                    ColorScheme::Dark => dark.as_ref(),
                 };
                 Self::from_css_color(branch, text_color, style_ctx, absolute_ctx)
             }
@@ -129,6 +246,20 @@ impl Color4f {
         Self::from_css_color(resolved_color, text_color, style_ctx, absolute_ctx)
     }
 
+    /// Resolves a system color from the palette of the system, which is the high contrast palette matching the color
+    /// scheme of the system while forced colors are active.
+    pub(crate) fn system(system: SystemColor, absolute_ctx: &AbsoluteContext) -> Self {
+        let preferences = absolute_ctx.system_preferences;
+        if preferences.forced_colors != ForcedColorsState::Active {
+            return Self::from(system);
+        }
+
+        system
+            .to_forced_hex(preferences.prefers_color_scheme)
+            .and_then(Self::hex_to_rgb_tuple)
+            .map_or(Self::BLACK, Self::from)
+    }
+
     /// Parses a hex color string (e.g. "#RRGGBB") into an (r, g, b) tuple.
     fn hex_to_rgb_tuple(hex: &str) -> Option<(u8, u8, u8)> {
         let hex = hex.trim_start_matches('#');
@@ -176,6 +307,70 @@ impl Color4f {
 }
 
 impl Color4f<f64> {
+    /// Converts a color of the `color()` function from its color space to sRGB, through CIE XYZ with a D65 white
+    /// point. Colors outside the sRGB gamut are clipped to it.
+    ///
+    /// # Arguments
+    /// * `space` - The color space of the components.
+    /// * `components` - The components, from 0.0 to 1.0 within the gamut of the space.
+    /// * `alpha` - The opacity, from 0.0 to 1.0.
+    #[must_use]
+    pub fn from_color_space(space: ColorSpace, components: [f64; 3], alpha: f64) -> Self {
+        let linear = match space {
+            ColorSpace::Srgb => components.map(|c| signed(c, Self::srgb_to_linear)),
+            ColorSpace::SrgbLinear => components,
+            ColorSpace::DisplayP3 => Self::xyz_d65_to_linear_srgb(multiply(
+                &DISPLAY_P3_TO_XYZ_D65,
+                components.map(|c| signed(c, Self::srgb_to_linear)),
+            )),
+            ColorSpace::A98Rgb => Self::xyz_d65_to_linear_srgb(multiply(
+                &A98_RGB_TO_XYZ_D65,
+                components.map(|c| signed(c, |c| c.powf(563.0 / 256.0))),
+            )),
+            ColorSpace::ProphotoRgb => {
+                let xyz_d50 = multiply(
+                    &PROPHOTO_RGB_TO_XYZ_D50,
+                    components.map(|c| {
+                        signed(c, |c| {
+                            if c <= 16.0 / 512.0 {
+                                c / 16.0
+                            } else {
+                                c.powf(1.8)
+                            }
+                        })
+                    }),
+                );
+                Self::xyz_d65_to_linear_srgb(multiply(&XYZ_D50_TO_D65, xyz_d50))
+            }
+            ColorSpace::Rec2020 => {
+                const ALPHA: f64 = 1.099_296_826_809_44;
+                const BETA: f64 = 0.018_053_968_510_807;
+
+                Self::xyz_d65_to_linear_srgb(multiply(
+                    &REC2020_TO_XYZ_D65,
+                    components.map(|c| {
+                        signed(c, |c| {
+                            if c < BETA * 4.5 {
+                                c / 4.5
+                            } else {
+                                ((c + ALPHA - 1.0) / ALPHA).powf(1.0 / 0.45)
+                            }
+                        })
+                    }),
+                ))
+            }
+            ColorSpace::XyzD50 => Self::xyz_d65_to_linear_srgb(multiply(&XYZ_D50_TO_D65, components)),
+            ColorSpace::XyzD65 => Self::xyz_d65_to_linear_srgb(components),
+        };
+
+        let [r, g, b] = linear.map(|c| Self::linear_to_srgb(c).clamp(0.0, 1.0));
+        Self::rgba(r, g, b, alpha)
+    }
+
+    fn xyz_d65_to_linear_srgb(xyz: [f64; 3]) -> [f64; 3] {
+        multiply(&XYZ_D65_TO_LINEAR_SRGB, xyz)
+    }
+
     /// Converts a color from HSL space to sRGB space, given the HSL components (hue in degrees, saturation and lightness as fractions) and alpha.
     #[must_use]
     pub fn from_hsl(h_deg: f64, s_frac: f64, l_frac: f64, alpha: f64) -> Self {
@@ -441,6 +636,14 @@ impl From<ColorFunction> for Color4f {
                 let b = colorfulness.value(0.0..=0.4, Fraction::Unsigned) * h_rad.sin();
                 Self::from_oklab(lightness.value(0.0..=1.0, Fraction::Unsigned), a, b, alpha.value())
             }
+            ColorFunction::Color(space, c1, c2, c3, alpha) => {
+                // Unlike the other functions, numbers outside the gamut are kept until the color is converted.
+                let component = |value: ColorValue| match value {
+                    ColorValue::Number(number) => number,
+                    value => value.value(0.0..=1.0, Fraction::Unsigned),
+                };
+                Self::from_color_space(space, [component(c1), component(c2), component(c3)], alpha.value())
+            }
         }
     }
 }
@@ -460,7 +663,8 @@ impl From<Color4f<f64>> for Color4f<f32> {
 mod tests {
     use std::net::Ipv4Addr;
 
-    use css_values::{color::Alpha, numeric::Percentage};
+    use css_cssom::{CSSStyleSheet, ComponentValueStream};
+    use css_values::{CSSParsable, color::Alpha, numeric::Percentage};
     use url::Url;
 
     use super::*;
@@ -498,14 +702,12 @@ mod tests {
     }
 
     #[test]
-    fn light_dark_current_in_light_theme_falls_back_to_parent_color() {
+    fn light_dark_current_in_light_scheme_falls_back_to_parent_color() {
         let url = Box::leak(Box::new(Url::parse(&format!("http://{}", Ipv4Addr::LOCALHOST)).unwrap()));
         let parent_color = [0.1, 0.2, 0.3, 1.0].into();
         let style_ctx = style_ctx_with_parent_color(parent_color);
-        let absolute_ctx = AbsoluteContext {
-            theme_category: ThemeCategory::Light,
-            ..AbsoluteContext::default_url(url)
-        };
+        let mut absolute_ctx = AbsoluteContext::default_url(url);
+        absolute_ctx.system_preferences.prefers_color_scheme = ColorScheme::Light;
 
         let text_color = CSSProperty::Value(Color::LightDark(
             Box::new(Color::Current),
@@ -525,13 +727,11 @@ mod tests {
     }
 
     #[test]
-    fn light_dark_current_in_dark_theme_uses_dark_branch() {
+    fn light_dark_current_in_dark_scheme_uses_dark_branch() {
         let url = Box::leak(Box::new(Url::parse(&format!("http://{}", Ipv4Addr::LOCALHOST)).unwrap()));
         let style_ctx = style_ctx_with_parent_color([0.1, 0.2, 0.3, 1.0].into());
-        let absolute_ctx = AbsoluteContext {
-            theme_category: ThemeCategory::Dark,
-            ..AbsoluteContext::default_url(url)
-        };
+        let mut absolute_ctx = AbsoluteContext::default_url(url);
+        absolute_ctx.system_preferences.prefers_color_scheme = ColorScheme::Dark;
 
         let text_color = CSSProperty::Value(Color::LightDark(
             Box::new(Color::Current),
@@ -659,4 +859,58 @@ mod tests {
         let rgb = Color4f::from(oklch);
         assert!(rgb.compare_with_tolerance(&Color4f::rgba_u8(69, 169, 74, 255), 0.01));
     }
+
+    #[test]
+    fn convert_color_function_to_rgb() {
+        let parse = |value: &str| {
+            let decls = CSSStyleSheet::from_inline(&format!("color: {value};"));
+            match Color::parse(&mut ComponentValueStream::new(&decls[0].original_values)).unwrap() {
+                Color::Base(ColorBase::Function(function)) => Color4f::from(function),
+                Color::Base(ColorBase::Hex(hex)) => Color4f::from(hex),
+                color => panic!("unexpected color {color:?}"),
+            }
+        };
+
+        let red = parse("#ff0000");
+        assert!(parse("color(srgb 1 0 0)").compare_with_tolerance(&red, 1e-9));
+        assert!(parse("color(srgb 100% 0% 0%)").compare_with_tolerance(&red, 1e-9));
+        assert!(parse("color(srgb-linear 1 0 0)").compare_with_tolerance(&red, 1e-9));
+        assert!(parse("color(xyz-d65 0.4124 0.2126 0.0193)").compare_with_tolerance(&red, 0.01));
+
+        // The white points of the color spaces all map to white.
+        for space in ["display-p3", "a98-rgb", "prophoto-rgb", "rec2020"] {
+            let white = parse(&format!("color({space} 1 1 1 / 0.5)"));
+            assert!(white.compare_with_tolerance(&Color4f::rgba(1.0, 1.0, 1.0, 0.5), 0.001), "{space}: {white:?}");
+        }
+        assert!(parse("color(xyz-d50 0.9642 1 0.8251)").compare_with_tolerance(&Color4f::WHITE, 0.001));
+
+        // The red of Display P3 is outside of sRGB, so it is clipped to its red.
+        assert!(parse("color(display-p3 1 0 0)").compare_with_tolerance(&red, 1e-9));
+        let p3_green = parse("color(display-p3 0 0.5 0)");
+        assert!(p3_green.r < 0.01 && p3_green.g > 0.45 && p3_green.b < 0.01, "{p3_green:?}");
+    }
+
+    #[test]
+    fn system_colors_use_forced_palette_while_forced_colors_are_active() {
+        let url = Box::leak(Box::new(Url::parse(&format!("http://{}", Ipv4Addr::LOCALHOST)).unwrap()));
+        let mut absolute_ctx = AbsoluteContext::default_url(url);
+        absolute_ctx.system_preferences.prefers_color_scheme = ColorScheme::Dark;
+
+        assert_eq!(Color4f::system(SystemColor::Canvas, &absolute_ctx), Color4f::WHITE);
+
+        absolute_ctx.system_preferences.forced_colors = ForcedColorsState::Active;
+        assert_eq!(Color4f::system(SystemColor::Canvas, &absolute_ctx), Color4f::BLACK);
+        assert_eq!(Color4f::system(SystemColor::CanvasText, &absolute_ctx), Color4f::WHITE);
+
+        let mut style = ComputedStyle {
+            background_color: Color4f::rgba(1.0, 0.0, 0.0, 0.5),
+            color: Color4f::rgba(0.0, 0.0, 1.0, 1.0),
+            ..Default::default()
+        };
+        style.apply_forced_colors(&absolute_ctx);
+
+        assert_eq!(style.background_color, Color4f::rgba(0.0, 0.0, 0.0, 0.5));
+        assert_eq!(style.color, Color4f::WHITE);
+        assert_eq!(style.border_top_color, Color4f::WHITE);
+    }
 }
@@ -1,11 +1,11 @@
 use css_values::{
     calc::CalcKind,
-    dimension::{MaxSize, Size},
+    dimension::{ContainIntrinsicLength, MaxSize, Size},
     numeric::Percentage,
     quantity::Length,
 };
 
-use crate::{AbsoluteContext, RelativeType, StyleContext, properties::PixelRepr};
+use crate::{AbsoluteContext, RelativeType, StyleContext, errors::ResolveError, properties::PixelRepr};
 
 #[derive(Debug, Clone, Default, Copy, PartialEq)]
 pub enum ComputedSize {
@@ -25,7 +25,7 @@ impl ComputedSize {
         relative_type: RelativeType,
         style_ctx: &StyleContext,
         absolute_ctx: &AbsoluteContext,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, ResolveError> {
         Ok(match size {
             Size::Auto => Self::Auto,
             Size::Length(length) => {
@@ -113,7 +113,7 @@ impl ComputedMaxSize {
         relative_type: RelativeType,
         style_ctx: &StyleContext,
         absolute_ctx: &AbsoluteContext,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, ResolveError> {
         Ok(match max_size {
             MaxSize::None => Self::None,
             MaxSize::Length(length) => {
@@ -163,3 +163,62 @@ impl From<f64> for ComputedMaxSize {
         Self::Px(value)
     }
 }
+
+/// A computed `contain-intrinsic-width` or `contain-intrinsic-height`.
+#[derive(Debug, Clone, Default, Copy, PartialEq)]
+pub enum ComputedIntrinsicLength {
+    #[default]
+    None,
+    Px(f64),
+    AutoPx(f64),
+}
+
+impl ComputedIntrinsicLength {
+    pub fn resolve(
+        length: ContainIntrinsicLength,
+        style_ctx: &StyleContext,
+        absolute_ctx: &AbsoluteContext,
+    ) -> Result<Self, ResolveError> {
+        Ok(match length {
+            ContainIntrinsicLength::None => Self::None,
+            ContainIntrinsicLength::Length(length) => {
+                Self::Px(length.to_px(Some(RelativeType::FontSize), Some(style_ctx), absolute_ctx)?)
+            }
+            ContainIntrinsicLength::AutoLength(length) => {
+                Self::AutoPx(length.to_px(Some(RelativeType::FontSize), Some(style_ctx), absolute_ctx)?)
+            }
+        })
+    }
+
+    /// The size of an element whose contents are skipped, `None` if the element is sized as if it was empty.
+    #[must_use]
+    pub const fn to_px(self) -> Option<f64> {
+        match self {
+            Self::None => None,
+            Self::Px(px) | Self::AutoPx(px) => Some(px),
+        }
+    }
+
+    /// The size of an element whose contents are skipped like [`Self::to_px`], using the last remembered size of its
+    /// contents instead of the length for `auto <length>`.
+    ///
+    /// # Arguments
+    /// * `remembered` - The size the contents of the element had when they were last laid out, if they were.
+    #[must_use]
+    pub const fn to_px_with_remembered(self, remembered: Option<f64>) -> Option<f64> {
+        match (self, remembered) {
+            (Self::AutoPx(_), Some(remembered)) => Some(remembered),
+            _ => self.to_px(),
+        }
+    }
+}
+
+impl From<ComputedIntrinsicLength> for ContainIntrinsicLength {
+    fn from(value: ComputedIntrinsicLength) -> Self {
+        match value {
+            ComputedIntrinsicLength::None => Self::None,
+            ComputedIntrinsicLength::Px(px) => Self::Length(Length::px(px)),
+            ComputedIntrinsicLength::AutoPx(px) => Self::AutoLength(Length::px(px)),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use css_values::{
+    color::Color,
+    filter::{DropShadow, Filter, FilterFunction},
+    quantity::Length,
+};
+
+use crate::{
+    AbsoluteContext, Color4f, RelativeType, StyleContext,
+    properties::{CSSProperty, PixelRepr},
+};
+
+/// A `drop-shadow()` filter function with its lengths in pixels and its color resolved.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComputedDropShadow {
+    pub color: Color4f,
+    pub offset_x: f64,
+    pub offset_y: f64,
+    pub blur_radius: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ComputedFilterFunction {
+    /// A `blur()` with its radius, the standard deviation of the Gaussian, in pixels.
+    Blur(f64),
+    DropShadow(ComputedDropShadow),
+}
+
+/// The computed `filter` or `backdrop-filter` property, empty for `none`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ComputedFilter(pub Vec<ComputedFilterFunction>);
+
+impl From<ComputedFilter> for Filter {
+    fn from(computed: ComputedFilter) -> Self {
+        Self(
+            computed
+                .0
+                .into_iter()
+                .map(|function| match function {
+                    ComputedFilterFunction::Blur(radius) => FilterFunction::Blur(Length::px(radius)),
+                    ComputedFilterFunction::DropShadow(shadow) => FilterFunction::DropShadow(DropShadow {
+                        color: Some(shadow.color.into()),
+                        offset_x: Length::px(shadow.offset_x),
+                        offset_y: Length::px(shadow.offset_y),
+                        blur_radius: Length::px(shadow.blur_radius),
+                    }),
+                })
+                .collect(),
+        )
+    }
+}
+
+impl ComputedFilter {
+    /// Whether the value is `none`.
+    #[must_use]
+    pub fn is_none(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The drop shadows of the filter, in the order they are applied.
+    pub fn drop_shadows(&self) -> impl Iterator<Item = &ComputedDropShadow> {
+        self.0.iter().filter_map(|function| match function {
+            ComputedFilterFunction::DropShadow(shadow) => Some(shadow),
+            ComputedFilterFunction::Blur(_) => None,
+        })
+    }
+
+    /// Resolves the lengths of the filter functions to pixels and their colors, a shadow without a color taking the
+    /// `color` of the element.
+    pub(crate) fn resolve(
+        filter: &Filter,
+        text_color: &CSSProperty<Color>,
+        style_ctx: &StyleContext,
+        absolute_ctx: &AbsoluteContext,
+    ) -> Self {
+        let px = |length: Length| {
+            length
+                .to_px(Some(RelativeType::FontSize), Some(style_ctx), absolute_ctx)
+                .unwrap_or(0.0)
+        };
+
+        Self(
+            filter
+                .0
+                .iter()
+                .map(|function| match function {
+                    FilterFunction::Blur(radius) => ComputedFilterFunction::Blur(px(*radius).max(0.0)),
+                    FilterFunction::DropShadow(shadow) => ComputedFilterFunction::DropShadow(ComputedDropShadow {
+                        color: Color4f::from_css_color(
+                            shadow.color.as_ref().unwrap_or(&Color::Current),
+                            text_color,
+                            style_ctx,
+                            absolute_ctx,
+                        ),
+                        offset_x: px(shadow.offset_x),
+                        offset_y: px(shadow.offset_y),
+                        blur_radius: px(shadow.blur_radius).max(0.0),
+                    }),
+                })
+                .collect(),
+        )
+    }
+}
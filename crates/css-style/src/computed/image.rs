@@ -1,12 +1,21 @@
-use css_values::image::{Gradient, Image};
+use css_values::{
+    image::{Gradient, Image, ImageSet, ImageSetCandidate},
+    quantity::Resolution,
+};
 use url::Url;
 
-use crate::{AbsoluteContext, properties::background::BackgroundImage};
+use crate::{AbsoluteContext, errors::ResolveError, properties::background::BackgroundImage};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ComputedImage {
     None,
-    Url(Url),
+
+    /// An image file, with its resolution in image pixels per CSS pixel, which is `1.0` unless it was picked from an
+    /// `image-set()`.
+    Url {
+        url: Url,
+        resolution: f64,
+    },
     Gradient(Box<Gradient>),
 }
 
@@ -14,25 +23,55 @@ impl From<ComputedImage> for Image {
     fn from(computed: ComputedImage) -> Self {
         match computed {
             ComputedImage::None => Self::None,
-            ComputedImage::Url(url) => Self::Url(url.to_string()),
+            ComputedImage::Url { url, resolution } if resolution == 1.0 => Self::Url(url.to_string()),
+            ComputedImage::Url { url, resolution } => Self::ImageSet(ImageSet {
+                candidates: vec![ImageSetCandidate {
+                    url: url.to_string(),
+                    resolution: Resolution::dppx(resolution),
+                }],
+            }),
             ComputedImage::Gradient(gradient) => Self::Gradient(gradient),
         }
     }
 }
 
 impl ComputedImage {
-    pub fn resolve(image: Image, absolute_ctx: &AbsoluteContext) -> Result<Self, String> {
+    pub fn resolve(image: Image, absolute_ctx: &AbsoluteContext) -> Result<Self, ResolveError> {
         match image {
-            Image::Url(url) => Ok(Self::Url(
-                absolute_ctx
-                    .document_url
-                    .join(&url)
-                    .map_err(|e| format!("Failed to resolve URL: {e}"))?,
-            )),
+            Image::Url(url) => Ok(Self::Url {
+                url: absolute_ctx.document_url.join(&url)?,
+                resolution: 1.0,
+            }),
             Image::Gradient(gradient) => Ok(Self::Gradient(gradient)),
+            Image::ImageSet(set) => set
+                .select(absolute_ctx.system_preferences.device_pixel_ratio)
+                .map_or(Ok(Self::None), |candidate| {
+                    Ok(Self::Url {
+                        url: absolute_ctx.document_url.join(&candidate.url)?,
+                        resolution: candidate.resolution.to_dppx(),
+                    })
+                }),
             Image::None => Ok(Self::None),
         }
     }
+
+    /// The natural size of the image in CSS pixels, its size in image pixels divided by its resolution, so that a
+    /// `2x` candidate of an `image-set()` is drawn at half the size of its file. `None` for images without a natural
+    /// size.
+    ///
+    /// # Arguments
+    /// * `width` - The width of the decoded image in image pixels.
+    /// * `height` - The height of the decoded image in image pixels.
+    ///
+    /// <https://drafts.csswg.org/css-images-4/#image-set-notation>
+    #[must_use]
+    pub fn natural_size(&self, width: f64, height: f64) -> Option<(f64, f64)> {
+        match self {
+            Self::Url { resolution, .. } if *resolution > 0.0 => Some((width / resolution, height / resolution)),
+            Self::Url { .. } => Some((width, height)),
+            Self::None | Self::Gradient(_) => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, PartialEq)]
@@ -49,7 +88,7 @@ impl ComputedBackgroundImage {
         Self(vec![])
     }
 
-    pub fn resolve(images: Vec<Image>, absolute_ctx: &AbsoluteContext) -> Result<Self, String> {
+    pub fn resolve(images: Vec<Image>, absolute_ctx: &AbsoluteContext) -> Result<Self, ResolveError> {
         images
             .into_iter()
             .map(|image| ComputedImage::resolve(image, absolute_ctx))
@@ -57,3 +96,44 @@ impl ComputedBackgroundImage {
             .map(ComputedBackgroundImage)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use css_cssom::{CSSStyleSheet, ComponentValueStream};
+    use css_values::CSSParsable;
+
+    use super::*;
+
+    #[test]
+    fn test_image_set_resolves_to_candidate_of_device_pixel_ratio() {
+        let url = Url::parse("http://localhost/style/").unwrap();
+        let mut absolute_ctx = AbsoluteContext::default_url(&url);
+        absolute_ctx.system_preferences.device_pixel_ratio = 2.0;
+
+        let decls = CSSStyleSheet::from_inline(r#"background-image: image-set("img.png" 1x, "img@2x.png" 2x);"#);
+        let image = Image::parse(&mut ComponentValueStream::new(&decls[0].original_values)).unwrap();
+
+        let computed = ComputedImage::resolve(image, &absolute_ctx).unwrap();
+        assert_eq!(
+            computed,
+            ComputedImage::Url {
+                url: Url::parse("http://localhost/style/img@2x.png").unwrap(),
+                resolution: 2.0,
+            }
+        );
+        assert_eq!(computed.natural_size(200.0, 100.0), Some((100.0, 50.0)));
+
+        let inherited = ComputedImage::resolve(Image::from(computed.clone()), &absolute_ctx);
+        assert_eq!(inherited, Ok(computed));
+    }
+
+    #[test]
+    fn test_url_keeps_natural_size() {
+        let url = Url::parse("http://localhost/").unwrap();
+        let absolute_ctx = AbsoluteContext::default_url(&url);
+
+        let computed = ComputedImage::resolve(Image::Url("img.png".to_string()), &absolute_ctx).unwrap();
+        assert_eq!(computed.natural_size(200.0, 100.0), Some((200.0, 100.0)));
+        assert_eq!(Image::from(computed), Image::Url("http://localhost/img.png".to_string()));
+    }
+}
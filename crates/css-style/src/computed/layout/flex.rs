@@ -1,6 +1,6 @@
 use css_values::FlexBasis;
 
-use crate::{AbsoluteContext, ComputedSize, RelativeType, StyleContext};
+use crate::{AbsoluteContext, ComputedSize, RelativeType, StyleContext, errors::ResolveError};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ComputedFlexBasis {
@@ -14,7 +14,7 @@ impl ComputedFlexBasis {
         relative_type: RelativeType,
         style_ctx: &StyleContext,
         absolute_ctx: &AbsoluteContext,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, ResolveError> {
         Ok(match flex_basis {
             FlexBasis::Content => Self::Content,
             FlexBasis::Size(size) => Self::Size(ComputedSize::resolve(size, relative_type, style_ctx, absolute_ctx)?),
@@ -1,6 +1,6 @@
 use css_values::{Gap, calc::CalcKind, numeric::Percentage, quantity::Length};
 
-use crate::{AbsoluteContext, RelativeType, StyleContext, properties::PixelRepr};
+use crate::{AbsoluteContext, RelativeType, StyleContext, errors::ResolveError, properties::PixelRepr};
 
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum ComputedGap {
@@ -16,7 +16,7 @@ impl ComputedGap {
         relative_type: RelativeType,
         style_ctx: &StyleContext,
         absolute_ctx: &AbsoluteContext,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, ResolveError> {
         Ok(match gap {
             Gap::Normal => Self::Normal,
             Gap::Length(length) => Self::Length(length.to_px(Some(relative_type), Some(style_ctx), absolute_ctx)?),
@@ -2,9 +2,10 @@ use css_values::{
     calc::CalcKind,
     dimension::{MarginValue, OffsetValue},
     numeric::Percentage,
+    text::TextIndent,
 };
 
-use crate::{AbsoluteContext, RelativeType, StyleContext, properties::PixelRepr};
+use crate::{AbsoluteContext, RelativeType, StyleContext, errors::ResolveError, properties::PixelRepr};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ComputedOffset {
@@ -18,7 +19,7 @@ impl ComputedOffset {
         relative_type: Option<RelativeType>,
         style_ctx: &StyleContext,
         absolute_ctx: &AbsoluteContext,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, ResolveError> {
         Ok(match offset_value {
             OffsetValue::Length(len) => Self::Px(len.to_px(relative_type, Some(style_ctx), absolute_ctx)?),
             OffsetValue::Percentage(pct) => Self::Percentage(pct.as_fraction()),
@@ -83,7 +84,7 @@ impl ComputedMargin {
         relative_type: Option<RelativeType>,
         style_ctx: &StyleContext,
         absolute_ctx: &AbsoluteContext,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, ResolveError> {
         match offset_value {
             MarginValue::Auto => Ok(Self::Auto),
             MarginValue::Length(len) => Ok(Self::Px(len.to_px(relative_type, Some(style_ctx), absolute_ctx)?)),
@@ -134,3 +135,53 @@ impl From<f64> for ComputedMargin {
         Self::Px(value)
     }
 }
+
+/// The computed `text-indent`, with a length resolved to pixels and a percentage kept as a fraction of the width of the
+/// containing block.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ComputedTextIndent {
+    pub offset: ComputedOffset,
+    pub hanging: bool,
+    pub each_line: bool,
+}
+
+impl ComputedTextIndent {
+    pub fn resolve(text_indent: TextIndent, style_ctx: &StyleContext, absolute_ctx: &AbsoluteContext) -> Self {
+        Self {
+            offset: ComputedOffset::resolve(
+                text_indent.length,
+                Some(RelativeType::ParentWidth),
+                style_ctx,
+                absolute_ctx,
+            )
+            .unwrap_or_default(),
+            hanging: text_indent.hanging,
+            each_line: text_indent.each_line,
+        }
+    }
+
+    /// The indentation of a line of the block container.
+    ///
+    /// # Arguments
+    /// * `containing_width` - The width of the containing block, for a percentage indentation.
+    /// * `first_line` - Whether the line is the first line of the block container, or with `each-line` the first line
+    ///   after a forced line break.
+    #[must_use]
+    pub fn line_offset(&self, containing_width: f64, first_line: bool) -> f64 {
+        if first_line != self.hanging {
+            self.offset.to_px(containing_width)
+        } else {
+            0.0
+        }
+    }
+}
+
+impl From<ComputedTextIndent> for TextIndent {
+    fn from(value: ComputedTextIndent) -> Self {
+        Self {
+            length: value.offset.into(),
+            hanging: value.hanging,
+            each_line: value.each_line,
+        }
+    }
+}
@@ -8,6 +8,7 @@ use css_values::{
 
 use crate::{
     AbsoluteContext, RelativeType, StyleContext,
+    errors::ResolveError,
     properties::{PixelRepr, background::BackgroundSize},
 };
 
@@ -23,7 +24,7 @@ impl ComputedLengthPercentage {
         relative_type: Option<RelativeType>,
         style_ctx: &StyleContext,
         absolute_ctx: &AbsoluteContext,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, ResolveError> {
         match len_pct {
             LengthPercentage::Length(len) => Ok(Self::Px(len.to_px(relative_type, Some(style_ctx), absolute_ctx)?)),
             LengthPercentage::Percentage(pct) => Ok(Self::Percentage(pct.as_fraction())),
@@ -36,11 +37,35 @@ impl ComputedLengthPercentage {
                         Ok(Self::Px(px))
                     }
                     Ok(CalcKind::Percentage(p)) => Ok(Self::Percentage(p.as_fraction())),
-                    _ => Err("Unsupported calc expression for LengthPercentage".to_string()),
+                    _ => Err(ResolveError::UnsupportedCalcExpression("LengthPercentage")),
                 }
             }
         }
     }
+
+    /// The value in pixels, with a percentage taken of the given reference length.
+    #[must_use]
+    pub fn to_px(&self, reference: f64) -> f64 {
+        match self {
+            Self::Px(px) => *px,
+            Self::Percentage(frac) => frac * reference,
+        }
+    }
+}
+
+impl Default for ComputedLengthPercentage {
+    fn default() -> Self {
+        Self::Px(0.0)
+    }
+}
+
+impl From<ComputedLengthPercentage> for LengthPercentage {
+    fn from(value: ComputedLengthPercentage) -> Self {
+        match value {
+            ComputedLengthPercentage::Px(px) => Self::Length(Length::px(px)),
+            ComputedLengthPercentage::Percentage(frac) => Self::Percentage(Percentage::from_fraction(frac)),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
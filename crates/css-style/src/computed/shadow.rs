@@ -0,0 +1,87 @@
+use css_values::{
+    color::Color,
+    quantity::Length,
+    shadow::{BoxShadow, BoxShadowList},
+};
+
+use crate::{
+    AbsoluteContext, Color4f, RelativeType, StyleContext,
+    properties::{CSSProperty, PixelRepr},
+};
+
+/// A shadow of `box-shadow` with its lengths in pixels and its color resolved.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComputedBoxShadow {
+    pub inset: bool,
+    pub offset_x: f64,
+    pub offset_y: f64,
+    pub blur_radius: f64,
+    pub spread_radius: f64,
+    pub color: Color4f,
+}
+
+/// The computed `box-shadow` property, empty for `none`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ComputedBoxShadowList(pub Vec<ComputedBoxShadow>);
+
+impl From<ComputedBoxShadowList> for BoxShadowList {
+    fn from(computed: ComputedBoxShadowList) -> Self {
+        Self(
+            computed
+                .0
+                .into_iter()
+                .map(|shadow| BoxShadow {
+                    inset: shadow.inset,
+                    offset_x: Length::px(shadow.offset_x),
+                    offset_y: Length::px(shadow.offset_y),
+                    blur_radius: Length::px(shadow.blur_radius),
+                    spread_radius: Length::px(shadow.spread_radius),
+                    color: Some(shadow.color.into()),
+                })
+                .collect(),
+        )
+    }
+}
+
+impl ComputedBoxShadowList {
+    /// Whether the value is `none`.
+    #[must_use]
+    pub fn is_none(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Resolves the lengths of the shadows to pixels and their colors, a shadow without a color taking the `color` of
+    /// the element.
+    pub(crate) fn resolve(
+        box_shadow: &BoxShadowList,
+        text_color: &CSSProperty<Color>,
+        style_ctx: &StyleContext,
+        absolute_ctx: &AbsoluteContext,
+    ) -> Self {
+        let px = |length: Length| {
+            length
+                .to_px(Some(RelativeType::FontSize), Some(style_ctx), absolute_ctx)
+                .unwrap_or(0.0)
+        };
+
+        Self(
+            box_shadow
+                .0
+                .iter()
+                .map(|shadow| ComputedBoxShadow {
+                    inset: shadow.inset,
+                    offset_x: px(shadow.offset_x),
+                    offset_y: px(shadow.offset_y),
+                    blur_radius: px(shadow.blur_radius).max(0.0),
+                    spread_radius: px(shadow.spread_radius),
+                    color: Color4f::from_css_color(
+                        shadow.color.as_ref().unwrap_or(&Color::Current),
+                        text_color,
+                        style_ctx,
+                        absolute_ctx,
+                    ),
+                })
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,120 @@
+use css_values::{calc::CalcKind, text::LineHeight};
+
+use crate::{AbsoluteContext, StyleContext, errors::ResolveError, properties::PixelRepr};
+
+/// The computed value of `line-height`.
+///
+/// A number is kept as a factor, so that descendants inheriting it multiply it by their own font size, while lengths
+/// and percentages are resolved to pixels against the font size of the element and inherited as is.
+///
+/// <https://www.w3.org/TR/CSS2/visudet.html#propdef-line-height>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ComputedLineHeight {
+    /// A factor of the font size of the element, from `normal` or a unitless number.
+    Factor(f64),
+
+    /// A height in pixels, from a length or a percentage.
+    Px(f64),
+}
+
+impl Default for ComputedLineHeight {
+    fn default() -> Self {
+        Self::Factor(1.2)
+    }
+}
+
+impl ComputedLineHeight {
+    /// Resolves a specified `line-height`.
+    ///
+    /// # Arguments
+    /// * `line_height` - The specified value.
+    /// * `style_ctx` - The context of the element, with the computed font size of the element itself.
+    /// * `absolute_ctx` - The context the `normal` factor is read from.
+    ///
+    /// # Errors
+    /// Returns an error if a length cannot be resolved to pixels.
+    pub fn resolve(
+        line_height: LineHeight,
+        style_ctx: &StyleContext,
+        absolute_ctx: &AbsoluteContext,
+    ) -> Result<Self, ResolveError> {
+        let normal = Self::Factor(absolute_ctx.root_line_height_multiplier);
+
+        Ok(match line_height {
+            LineHeight::Normal => normal,
+            LineHeight::Number(factor) => Self::Factor(factor.max(0.0)),
+            LineHeight::Length(length) => Self::Px(length.to_px(None, Some(style_ctx), absolute_ctx)?.max(0.0)),
+            LineHeight::Percentage(pct) => Self::Px(style_ctx.font_size * pct.as_fraction().max(0.0)),
+            LineHeight::Calc(expr) => match expr.into_sum().kind() {
+                Ok(CalcKind::Length(length)) => Self::Px(length.to_px(None, Some(style_ctx), absolute_ctx)?.max(0.0)),
+                Ok(CalcKind::Percentage(pct)) => Self::Px(style_ctx.font_size * pct.as_fraction().max(0.0)),
+                Ok(CalcKind::Number(factor)) => Self::Factor(factor.max(0.0)),
+                _ => normal,
+            },
+        })
+    }
+
+    /// The height of a line box in pixels for text of the given font size.
+    #[must_use]
+    pub const fn to_px(self, font_size: f64) -> f64 {
+        match self {
+            Self::Factor(factor) => factor * font_size,
+            Self::Px(px) => px,
+        }
+    }
+}
+
+impl From<ComputedLineHeight> for LineHeight {
+    fn from(value: ComputedLineHeight) -> Self {
+        match value {
+            ComputedLineHeight::Factor(factor) => Self::Number(factor),
+            ComputedLineHeight::Px(px) => Self::px(px),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use css_values::{numeric::Percentage, quantity::Length};
+    use url::Url;
+
+    use crate::ComputedStyle;
+
+    use super::*;
+
+    fn resolve(line_height: LineHeight, font_size: f64) -> ComputedLineHeight {
+        let url = Url::parse("http://localhost").unwrap();
+        let absolute_ctx = AbsoluteContext::default_url(&url);
+        let parent = ComputedStyle::default();
+        let style_ctx = StyleContext {
+            parent_style: &parent,
+            font_size,
+        };
+
+        ComputedLineHeight::resolve(line_height, &style_ctx, &absolute_ctx).unwrap()
+    }
+
+    #[test]
+    fn test_number_is_kept_as_a_factor() {
+        let line_height = resolve(LineHeight::Number(2.0), 16.0);
+
+        assert_eq!(line_height, ComputedLineHeight::Factor(2.0));
+        assert!((line_height.to_px(16.0) - 32.0).abs() < 0.001);
+        assert!((line_height.to_px(10.0) - 20.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_percentage_uses_the_font_size_of_the_element() {
+        let line_height = resolve(LineHeight::Percentage(Percentage::unbounded(150.0)), 20.0);
+
+        assert_eq!(line_height, ComputedLineHeight::Px(30.0));
+        assert!((line_height.to_px(10.0) - 30.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_length_resolves_to_pixels() {
+        let line_height = resolve(LineHeight::Length(Length::px(24.0)), 16.0);
+
+        assert_eq!(line_height, ComputedLineHeight::Px(24.0));
+    }
+}
@@ -0,0 +1,309 @@
+//! Container queries, the `@container` rules whose style rules only apply to the elements inside a size container
+//! of a matching size.
+//!
+//! The sizes of the containers are only known after layout, so the document is first styled without any of these
+//! rules applying, laid out, and styled again against the measured containers. A rule is matched against the nearest
+//! ancestor container of an element and never against the element itself, so the styles a query selects cannot
+//! change the size of the container it queried.
+
+use std::collections::HashMap;
+
+use css_cssom::{ComponentValue, ComponentValueStream, CssTokenKind};
+use css_values::{
+    container::{ContainerName, ContainerType},
+    quantity::{Length, LengthUnit},
+};
+use html_dom::{DocumentRoot, DomNode, NodeId};
+
+use crate::{AbsoluteContext, properties::PixelRepr};
+
+/// A size feature of a container query, compared against the content box of the container.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContainerCondition {
+    MinWidth(f64),
+    MaxWidth(f64),
+    MinHeight(f64),
+    MaxHeight(f64),
+    AspectRatio(f64),
+    MinAspectRatio(f64),
+    MaxAspectRatio(f64),
+}
+
+impl ContainerCondition {
+    /// Parses a feature such as `min-width: 400px` from the contents of its parentheses.
+    fn parse(values: &[ComponentValue], absolute_ctx: &AbsoluteContext) -> Option<Self> {
+        let mut stream = ComponentValueStream::new(values);
+
+        let Some(ComponentValue::Token(token)) = stream.next_non_whitespace() else {
+            return None;
+        };
+        let CssTokenKind::Ident(feature) = &token.kind else {
+            return None;
+        };
+
+        match stream.next_non_whitespace() {
+            Some(ComponentValue::Token(token)) if token.kind == CssTokenKind::Colon => {}
+            _ => return None,
+        }
+
+        let condition = match feature.to_ascii_lowercase().as_str() {
+            "min-width" => Self::MinWidth(Self::parse_length(&mut stream, absolute_ctx)?),
+            "max-width" => Self::MaxWidth(Self::parse_length(&mut stream, absolute_ctx)?),
+            "min-height" => Self::MinHeight(Self::parse_length(&mut stream, absolute_ctx)?),
+            "max-height" => Self::MaxHeight(Self::parse_length(&mut stream, absolute_ctx)?),
+            "aspect-ratio" => Self::AspectRatio(Self::parse_ratio(&mut stream)?),
+            "min-aspect-ratio" => Self::MinAspectRatio(Self::parse_ratio(&mut stream)?),
+            "max-aspect-ratio" => Self::MaxAspectRatio(Self::parse_ratio(&mut stream)?),
+            _ => return None,
+        };
+
+        stream.next_non_whitespace().is_none().then_some(condition)
+    }
+
+    fn parse_length(stream: &mut ComponentValueStream, absolute_ctx: &AbsoluteContext) -> Option<f64> {
+        let Some(ComponentValue::Token(token)) = stream.next_non_whitespace() else {
+            return None;
+        };
+
+        match &token.kind {
+            CssTokenKind::Dimension { value, unit } => {
+                let unit = unit.parse::<LengthUnit>().ok()?;
+                Length::new(value.to_f64(), unit)
+                    .to_px(None, None, absolute_ctx)
+                    .ok()
+            }
+            CssTokenKind::Number(value) if value.to_f64() == 0.0 => Some(0.0),
+            _ => None,
+        }
+    }
+
+    /// Parses a ratio such as `16 / 9`, or a single number for a ratio to one.
+    fn parse_ratio(stream: &mut ComponentValueStream) -> Option<f64> {
+        let number = |cv: Option<&ComponentValue>| match cv {
+            Some(ComponentValue::Token(token)) => match &token.kind {
+                CssTokenKind::Number(value) => Some(value.to_f64()),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let numerator = number(stream.next_non_whitespace())?;
+
+        match stream.next_non_whitespace() {
+            None => Some(numerator),
+            Some(ComponentValue::Token(token)) if token.kind == CssTokenKind::Delim('/') => {
+                let denominator = number(stream.next_non_whitespace())?;
+                (denominator > 0.0).then_some(numerator / denominator)
+            }
+            Some(_) => None,
+        }
+    }
+
+    /// Whether the container satisfies the condition. Features of a dimension the type of the container does not
+    /// expose never match.
+    fn matches(self, container: &Container) -> bool {
+        let ratio = || (container.height > 0.0).then_some(container.width / container.height);
+
+        match self {
+            Self::MinWidth(px) => container.container_type.queries_width() && container.width >= px,
+            Self::MaxWidth(px) => container.container_type.queries_width() && container.width <= px,
+            Self::MinHeight(px) => container.container_type.queries_height() && container.height >= px,
+            Self::MaxHeight(px) => container.container_type.queries_height() && container.height <= px,
+            Self::AspectRatio(expected) => {
+                container.container_type.queries_height()
+                    && ratio().is_some_and(|ratio| (ratio - expected).abs() < 1e-6)
+            }
+            Self::MinAspectRatio(expected) => {
+                container.container_type.queries_height() && ratio().is_some_and(|ratio| ratio >= expected)
+            }
+            Self::MaxAspectRatio(expected) => {
+                container.container_type.queries_height() && ratio().is_some_and(|ratio| ratio <= expected)
+            }
+        }
+    }
+}
+
+/// The prelude of a `@container` rule, an optional container name followed by size features joined by `and`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerQuery {
+    /// The name the container must have, the nearest container of any name is queried otherwise.
+    pub name: Option<String>,
+    pub conditions: Vec<ContainerCondition>,
+}
+
+impl ContainerQuery {
+    /// Parses the prelude of a `@container` rule, returning `None` if it is invalid or uses syntax that is not
+    /// supported, such as `not` and `or`, in which case the rules inside never apply.
+    pub fn parse(prelude: &[ComponentValue], absolute_ctx: &AbsoluteContext) -> Option<Self> {
+        let mut stream = ComponentValueStream::new(prelude);
+        let mut name = None;
+        let mut conditions = Vec::new();
+        let mut expects_condition = true;
+
+        while let Some(cv) = stream.next_non_whitespace() {
+            match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident) if ident.eq_ignore_ascii_case("and") && !expects_condition => {
+                        expects_condition = true;
+                    }
+                    CssTokenKind::Ident(ident)
+                        if name.is_none()
+                            && conditions.is_empty()
+                            && !["and", "or", "not", "none"]
+                                .iter()
+                                .any(|reserved| ident.eq_ignore_ascii_case(reserved)) =>
+                    {
+                        name = Some(ident.clone());
+                    }
+                    _ => return None,
+                },
+                ComponentValue::SimpleBlock(block) if expects_condition => {
+                    conditions.push(ContainerCondition::parse(&block.value, absolute_ctx)?);
+                    expects_condition = false;
+                }
+                _ => return None,
+            }
+        }
+
+        (!conditions.is_empty() && !expects_condition).then_some(Self { name, conditions })
+    }
+}
+
+/// An element with a `container-type` other than `normal`, with the size of its content box.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Container {
+    pub names: ContainerName,
+    pub container_type: ContainerType,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// The size containers of a document, as measured by a layout.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContainerSizes(HashMap<NodeId, Container>);
+
+impl ContainerSizes {
+    pub fn insert(&mut self, node_id: NodeId, container: Container) {
+        self.0.insert(node_id, container);
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether the query matches the nearest ancestor container of the node with the name of the query. Without such
+    /// a container the query does not match.
+    pub(crate) fn matches(&self, query: &ContainerQuery, dom: &DocumentRoot, node: &DomNode) -> bool {
+        let mut ancestor = node.parent;
+
+        while let Some(node_id) = ancestor {
+            if let Some(container) = self.0.get(&node_id)
+                && query
+                    .name
+                    .as_ref()
+                    .is_none_or(|name| container.names.contains(name))
+            {
+                return query
+                    .conditions
+                    .iter()
+                    .all(|condition| condition.matches(container));
+            }
+
+            ancestor = dom[node_id].parent;
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use css_cssom::{CSSStyleSheet, CssParser};
+    use html_dom::{Element, HtmlTag, NodeData, Tag};
+    use url::Url;
+
+    use super::*;
+    use crate::{Color4f, StyleTree};
+
+    const CARD_CSS: &str = "section { container-type: inline-size; color: black } p { color: black } \
+        @container (min-width: 400px) { p { color: white } section { color: white } }";
+
+    fn element(tag: HtmlTag) -> NodeData {
+        NodeData::Element(Element::new(Tag::Html(tag), HashSet::new(), HashMap::new()))
+    }
+
+    fn container(width: f64) -> Container {
+        Container {
+            names: ContainerName::default(),
+            container_type: ContainerType::InlineSize,
+            width,
+            height: 100.0,
+        }
+    }
+
+    #[test]
+    fn test_parse_named_query() {
+        let url = Url::parse("http://localhost").unwrap();
+        let stylesheet =
+            CssParser::default().parse_css("@container sidebar (min-width: 20em) and (max-height: 300px) {}", false);
+        let stylesheet = CSSStyleSheet::from(stylesheet);
+        let css_cssom::CSSRule::AtRule(at_rule) = &stylesheet.css_rules()[0] else {
+            panic!("expected an at-rule");
+        };
+
+        let query = ContainerQuery::parse(at_rule.prelude_values(), &AbsoluteContext::default_url(&url)).unwrap();
+
+        assert_eq!(query.name.as_deref(), Some("sidebar"));
+        assert_eq!(
+            query.conditions,
+            vec![
+                ContainerCondition::MinWidth(320.0),
+                ContainerCondition::MaxHeight(300.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_query_matches_ancestor_container() {
+        let mut dom = DocumentRoot::new();
+        let section = dom.push_node(&element(HtmlTag::Section), None);
+        let paragraph = dom.push_node(&element(HtmlTag::P), Some(section));
+
+        let stylesheets = [CSSStyleSheet::from(
+            CssParser::default().parse_css(CARD_CSS, false),
+        )];
+        let url = Url::parse("http://localhost").unwrap();
+        let absolute_ctx = AbsoluteContext::default_url(&url);
+
+        let unmeasured = StyleTree::build(None, &absolute_ctx, &dom, &stylesheets);
+        assert!(unmeasured.has_container_queries());
+        assert_eq!(unmeasured[paragraph].color, Color4f::BLACK);
+
+        let mut narrow = ContainerSizes::default();
+        narrow.insert(section, container(300.0));
+        let styles = StyleTree::build_with_containers(None, &absolute_ctx, &dom, &stylesheets, narrow);
+        assert_eq!(styles[paragraph].color, Color4f::BLACK);
+
+        let mut wide = ContainerSizes::default();
+        wide.insert(section, container(500.0));
+        let styles = StyleTree::build_with_containers(None, &absolute_ctx, &dom, &stylesheets, wide);
+        assert_eq!(styles[paragraph].color, Color4f::WHITE);
+
+        // A container is never matched against its own size.
+        assert_eq!(styles[section].color, Color4f::BLACK);
+    }
+
+    #[test]
+    fn test_height_is_not_queryable_on_inline_size_container() {
+        let condition = ContainerCondition::MinHeight(50.0);
+
+        assert!(!condition.matches(&container(500.0)));
+        assert!(condition.matches(&Container {
+            container_type: ContainerType::Size,
+            ..container(500.0)
+        }));
+    }
+}
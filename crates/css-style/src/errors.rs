@@ -0,0 +1,27 @@
+use css_values::{global::Global, quantity::Dimension};
+use thiserror::Error;
+
+use crate::RelativeType;
+
+/// An error resolving a specified value to a computed value.
+#[derive(Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ResolveError {
+    #[error("cannot resolve global property: {0:?}")]
+    GlobalProperty(Global),
+
+    #[error("unsupported dimension type in calc(): {0:?}")]
+    UnsupportedCalcDimension(Dimension),
+
+    #[error("unsupported calc expression for {0}")]
+    UnsupportedCalcExpression(&'static str),
+
+    #[error("percentage relative to {relative_type:?} requires a style context")]
+    MissingStyleContext { relative_type: RelativeType },
+
+    #[error("parent {dimension} is not a fixed pixel value, cannot resolve percentage")]
+    UnresolvedParentSize { dimension: &'static str },
+
+    #[error("failed to resolve URL: {0}")]
+    InvalidUrl(#[from] url::ParseError),
+}
@@ -0,0 +1,287 @@
+//! This module defines the `FontFace` structure, which represents a font declared with an `@font-face` rule in a
+//! stylesheet. Font faces are collected from the stylesheets of a document and used during font selection, where the
+//! `unicode-range` of a face decides which characters it may be used for.
+
+use css_cssom::{
+    CSSAtRule, CSSRule, CSSStyleSheet, ComponentValue, ComponentValueStream, CssTokenKind, KnownProperty, Property,
+};
+use css_values::{
+    CSSParsable,
+    text::{FontDisplay, FontFamilyName},
+    unicode_range::UnicodeRange,
+};
+use tracing::debug;
+
+use crate::FontFamily;
+
+/// A source of the font data of a font face, as listed in the `src` descriptor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FontFaceSource {
+    /// A font installed on the system, e.g. `local("Open Sans")`.
+    Local(String),
+
+    /// A font file to download, e.g. `url("font.woff2")`.
+    Url(String),
+}
+
+/// A font declared with an `@font-face` rule.
+///
+/// <https://drafts.csswg.org/css-fonts/#font-face-rule>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FontFace {
+    /// The family name the face is referenced by in `font-family`.
+    pub family: String,
+
+    /// The sources of the font data, in order of preference.
+    pub sources: Vec<FontFaceSource>,
+
+    /// The code points the face may be used for, every code point if the descriptor is missing.
+    pub unicode_ranges: Vec<UnicodeRange>,
+
+    /// How text using the face is rendered while its font data is downloading.
+    pub display: FontDisplay,
+}
+
+impl FontFace {
+    /// Collects the font faces declared at the top level of the given stylesheets, in source order.
+    #[must_use]
+    pub fn collect(stylesheets: &[CSSStyleSheet]) -> Vec<Self> {
+        stylesheets
+            .iter()
+            .flat_map(CSSStyleSheet::css_rules)
+            .filter_map(|rule| match rule {
+                CSSRule::AtRule(at_rule) => Self::from_at_rule(at_rule),
+                CSSRule::Style(_) => None,
+            })
+            .collect()
+    }
+
+    /// Creates a font face from an `@font-face` rule.
+    ///
+    /// # Returns
+    /// `None` if the rule is not an `@font-face` rule or lacks a `font-family` descriptor.
+    #[must_use]
+    pub fn from_at_rule(at_rule: &CSSAtRule) -> Option<Self> {
+        if !at_rule.name().eq_ignore_ascii_case("font-face") {
+            return None;
+        }
+
+        let mut family = None;
+        let mut sources = Vec::new();
+        let mut unicode_ranges = vec![UnicodeRange::ALL];
+        let mut display = FontDisplay::default();
+
+        for declaration in at_rule.declarations() {
+            let mut stream = ComponentValueStream::new(&declaration.original_values);
+
+            match &declaration.property {
+                Property::Known(KnownProperty::FontFamily) => {
+                    family =
+                        FontFamily::parse(&mut stream)
+                            .ok()
+                            .and_then(|font_family| match font_family.names().first() {
+                                Some(FontFamilyName::Specific(name)) => Some(name.clone()),
+                                _ => None,
+                            });
+                }
+                Property::Custom(name) if name.eq_ignore_ascii_case("src") => {
+                    sources = Self::parse_sources(&declaration.original_values);
+                }
+                Property::Custom(name) if name.eq_ignore_ascii_case("unicode-range") => {
+                    match UnicodeRange::parse_list(&mut stream) {
+                        Ok(ranges) => unicode_ranges = ranges,
+                        Err(e) => debug!("Ignoring invalid unicode-range in @font-face: {e}"),
+                    }
+                }
+                Property::Custom(name) if name.eq_ignore_ascii_case("font-display") => {
+                    match FontDisplay::parse(&mut stream) {
+                        Ok(value) => display = value,
+                        Err(e) => debug!("Ignoring invalid font-display in @font-face: {e}"),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            family: family?,
+            sources,
+            unicode_ranges,
+            display,
+        })
+    }
+
+    /// Checks whether the face may be used for the given character.
+    #[must_use]
+    pub fn covers(&self, ch: char) -> bool {
+        self.unicode_ranges
+            .iter()
+            .any(|range| range.contains(u32::from(ch)))
+    }
+
+    /// The URLs of the font files listed as `url()` sources that the font database can read, in order of preference.
+    /// WOFF and WOFF2 files are compressed, which it does not decode.
+    pub fn urls(&self) -> impl Iterator<Item = &str> {
+        self.sources.iter().filter_map(|source| match source {
+            FontFaceSource::Url(url) if Self::is_supported_url(url) => Some(url.as_str()),
+            FontFaceSource::Url(_) | FontFaceSource::Local(_) => None,
+        })
+    }
+
+    /// Whether the URL of a font file does not name a WOFF or WOFF2 file by its extension or `data:` media type.
+    fn is_supported_url(url: &str) -> bool {
+        let url = url.to_ascii_lowercase();
+        let path = url.split(['?', '#']).next().unwrap_or_default();
+
+        !(path.ends_with(".woff")
+            || path.ends_with(".woff2")
+            || path.starts_with("data:font/woff")
+            || path.starts_with("data:application/font-woff")
+            || path.starts_with("data:application/x-font-woff"))
+    }
+
+    /// Whether a `format()` hint names a format the font database can read.
+    fn is_supported_format(format: &str) -> bool {
+        ["truetype", "opentype", "collection", "truetype-aat"]
+            .iter()
+            .any(|supported| format.eq_ignore_ascii_case(supported))
+    }
+
+    /// The names of the system fonts listed as `local()` sources.
+    pub fn local_names(&self) -> impl Iterator<Item = &str> {
+        self.sources.iter().filter_map(|source| match source {
+            FontFaceSource::Local(name) => Some(name.as_str()),
+            FontFaceSource::Url(_) => None,
+        })
+    }
+
+    fn parse_sources(values: &[ComponentValue]) -> Vec<FontFaceSource> {
+        let mut sources = Vec::new();
+
+        for value in values {
+            match value {
+                ComponentValue::Token(token) => {
+                    if let CssTokenKind::Url(url) = &token.kind {
+                        sources.push(FontFaceSource::Url(url.clone()));
+                    }
+                }
+                ComponentValue::Function(function) => {
+                    let argument = function.value.iter().find_map(|cv| match cv {
+                        ComponentValue::Token(token) => match &token.kind {
+                            CssTokenKind::String(s) | CssTokenKind::Ident(s) => Some(s.clone()),
+                            _ => None,
+                        },
+                        _ => None,
+                    });
+
+                    match argument {
+                        Some(name) if function.name.eq_ignore_ascii_case("local") => {
+                            sources.push(FontFaceSource::Local(name));
+                        }
+                        Some(url) if function.name.eq_ignore_ascii_case("url") => {
+                            sources.push(FontFaceSource::Url(url));
+                        }
+                        // The hint follows the source it describes, which is skipped when it can not be read.
+                        Some(format)
+                            if function.name.eq_ignore_ascii_case("format")
+                                && !Self::is_supported_format(&format)
+                                && matches!(sources.last(), Some(FontFaceSource::Url(_))) =>
+                        {
+                            sources.pop();
+                        }
+                        _ => {}
+                    }
+                }
+                ComponentValue::SimpleBlock(_) => {}
+            }
+        }
+
+        sources
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn font_faces(css: &str) -> Vec<FontFace> {
+        let mut parser = css_cssom::CssParser::default();
+        let stylesheet = CSSStyleSheet::from(parser.parse_css(css, false));
+
+        FontFace::collect(&[stylesheet])
+    }
+
+    #[test]
+    fn test_font_face_unicode_range() {
+        let faces = font_faces(
+            "@font-face { font-family: 'Latin'; src: local('Open Sans'), url('latin.woff2'); unicode-range: U+0000-00FF, U+0131; }",
+        );
+
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].family, "Latin");
+        assert_eq!(
+            faces[0].sources,
+            vec![
+                FontFaceSource::Local("Open Sans".to_string()),
+                FontFaceSource::Url("latin.woff2".to_string())
+            ]
+        );
+        assert!(faces[0].covers('a'));
+        assert!(faces[0].covers('\u{131}'));
+        assert!(!faces[0].covers('中'));
+    }
+
+    #[test]
+    fn test_font_face_cjk_unicode_range() {
+        let faces =
+            font_faces("@font-face { font-family: CJK; src: local(Noto); unicode-range: U+4E00-9FFF, U+2E80; }");
+
+        assert_eq!(faces.len(), 1);
+        assert!(faces[0].covers('中'));
+        assert!(faces[0].covers('\u{2E80}'));
+        assert!(!faces[0].covers('a'));
+    }
+
+    #[test]
+    fn test_font_face_without_unicode_range_covers_everything() {
+        let faces = font_faces("@font-face { font-family: Everything; src: local(Roboto); }");
+
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].local_names().collect::<Vec<_>>(), vec!["Roboto"]);
+        assert!(faces[0].covers('中'));
+    }
+
+    #[test]
+    fn test_font_face_display() {
+        let faces = font_faces(
+            "@font-face { font-family: A; src: url(a.woff2); font-display: swap; } \
+            @font-face { font-family: B; src: url(b.woff2); font-display: sometimes; }",
+        );
+
+        assert_eq!(faces.len(), 2);
+        assert_eq!(faces[0].display, FontDisplay::Swap);
+        assert_eq!(faces[1].display, FontDisplay::Auto);
+    }
+
+    #[test]
+    fn test_font_face_urls_skip_unsupported_formats() {
+        let faces = font_faces(
+            "@font-face { font-family: A; src: url(a.woff2) format('woff2'), url(a.woff), \
+            url(a.bin) format('woff'), url('a.ttf?v=2') format('truetype'), url(a.otf); }",
+        );
+
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].urls().collect::<Vec<_>>(), vec!["a.ttf?v=2", "a.otf"]);
+        assert!(
+            font_faces("@font-face { font-family: B; src: url(b.woff2); }")[0]
+                .urls()
+                .next()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_font_face_without_family_is_ignored() {
+        assert!(font_faces("@font-face { src: local(Roboto); }").is_empty());
+    }
+}
@@ -3,7 +3,7 @@ use css_values::{
     quantity::Dimension,
 };
 
-use crate::{AbsoluteContext, RelativeType, StyleContext, properties::PixelRepr};
+use crate::{AbsoluteContext, RelativeType, StyleContext, errors::ResolveError, properties::PixelRepr};
 
 impl PixelRepr for CalcValue {
     fn to_px(
@@ -11,12 +11,12 @@ impl PixelRepr for CalcValue {
         rel_type: Option<RelativeType>,
         style_ctx: Option<&StyleContext>,
         abs_ctx: &AbsoluteContext,
-    ) -> Result<f64, String> {
+    ) -> Result<f64, ResolveError> {
         match self {
             Self::Number(n) => Ok(n),
             Self::Dimension(dim) => match dim {
                 Dimension::Length(l) => l.to_px(rel_type, style_ctx, abs_ctx),
-                _ => Err(format!("Unsupported dimension type in calc(): {:?}", dim)),
+                _ => Err(ResolveError::UnsupportedCalcDimension(dim)),
             },
             Self::Keyword(k) => Ok(k.to_f64()),
             Self::NestedSum(sum) => sum.to_px(rel_type, style_ctx, abs_ctx),
@@ -56,7 +56,7 @@ impl PixelRepr for CalcProduct {
         rel_type: Option<RelativeType>,
         style_ctx: Option<&StyleContext>,
         abs_ctx: &AbsoluteContext,
-    ) -> Result<f64, String> {
+    ) -> Result<f64, ResolveError> {
         match self {
             Self::Value(v) => v.to_px(rel_type, style_ctx, abs_ctx),
             Self::Multiply(left, right) => {
@@ -80,7 +80,7 @@ impl PixelRepr for CalcSum {
         rel_type: Option<RelativeType>,
         style_ctx: Option<&StyleContext>,
         abs_ctx: &AbsoluteContext,
-    ) -> Result<f64, String> {
+    ) -> Result<f64, ResolveError> {
         match self {
             Self::Product(p) => p.to_px(rel_type, style_ctx, abs_ctx),
             Self::Add(left, right) => {
@@ -99,7 +99,7 @@ impl PixelRepr for CalcExpression {
         rel_type: Option<RelativeType>,
         style_ctx: Option<&StyleContext>,
         abs_ctx: &AbsoluteContext,
-    ) -> Result<f64, String> {
+    ) -> Result<f64, ResolveError> {
         self.into_sum().to_px(rel_type, style_ctx, abs_ctx)
     }
 }
@@ -112,7 +112,7 @@ mod tests {
 
     use super::*;
     use css_cssom::{ComponentValue, CssToken, CssTokenKind, Function, NumericValue};
-    use css_values::error::CssValueError;
+    use css_values::{error::CssValueError, numeric::Percentage};
     use url::Url;
 
     /// Helper function to create test contexts
@@ -1210,4 +1210,18 @@ mod tests {
         let result = expr.to_px(None, Some(&style_ctx), &abs_ctx);
         assert_eq!(result.unwrap(), 150.0);
     }
+
+    #[test]
+    fn test_percentage_without_style_context() {
+        let (_, abs_ctx) = create_test_contexts();
+        let result =
+            CalcValue::Percentage(Percentage::new(50.0)).to_px(Some(RelativeType::ParentWidth), None, &abs_ctx);
+
+        assert_eq!(
+            result,
+            Err(ResolveError::MissingStyleContext {
+                relative_type: RelativeType::ParentWidth
+            })
+        );
+    }
 }
@@ -0,0 +1,428 @@
+//! This module resolves the generated content of the `::before` and `::after` pseudo-elements, whose text is given by
+//! the `content` property and may include attributes of the element and the values of CSS counters.
+//!
+//! Counters are created by `counter-reset` and `counter-increment` and are visible to the element that created them,
+//! its following siblings and all their descendants, so the DOM is walked in document order while the counters in
+//! scope are tracked.
+//!
+//! The nesting level of quotes is kept across the whole document in the same walk, so an `open-quote` inserts the
+//! quotation mark of the level it opens and a `close-quote` that of the level it closes, as given by `quotes`.
+
+use std::collections::{HashMap, HashSet};
+
+use css_selectors::PseudoElement;
+use css_values::{
+    content::{Content, ContentItem},
+    display::{Float, OutsideDisplay},
+};
+use html_dom::{DomNode, NodeId};
+
+use crate::{ComputedStyle, Position, rules::CascadeContext, tree::PropertyRegistry};
+
+/// The box generated by a `::before` or `::after` pseudo-element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedContent {
+    /// The element the pseudo-element belongs to.
+    pub owner: NodeId,
+
+    /// The style of the pseudo-element, which inherits from its element and is always laid out as a static inline box.
+    pub style: ComputedStyle,
+
+    /// The text given by the `content` property, with attributes and counters substituted.
+    pub text: String,
+}
+
+/// A counter created by an element, visible until the parent of that element is closed.
+#[derive(Debug)]
+struct CounterInstance {
+    /// The parent of the element that created the counter, `None` for a counter created by a root element.
+    scope: Option<NodeId>,
+    value: i32,
+}
+
+/// The counters in scope while walking the DOM, each name with its nested instances, innermost last.
+#[derive(Debug, Default)]
+struct Counters(HashMap<String, Vec<CounterInstance>>);
+
+impl Counters {
+    /// Creates a counter, replacing a counter of the same name created by a previous sibling.
+    fn reset(&mut self, name: &str, value: i32, scope: Option<NodeId>) {
+        let instances = self.0.entry(name.to_string()).or_default();
+
+        if instances
+            .last()
+            .is_some_and(|instance| instance.scope == scope)
+        {
+            instances.pop();
+        }
+
+        instances.push(CounterInstance { scope, value });
+    }
+
+    /// Increments the innermost counter of the given name, creating it if no counter of that name is in scope.
+    fn increment(&mut self, name: &str, by: i32, scope: Option<NodeId>) {
+        match self
+            .0
+            .get_mut(name)
+            .and_then(|instances| instances.last_mut())
+        {
+            Some(instance) => instance.value = instance.value.saturating_add(by),
+            None => self.reset(name, by, scope),
+        }
+    }
+
+    /// The value of the innermost counter of the given name, zero if there is none.
+    fn value(&self, name: &str) -> i32 {
+        self.0
+            .get(name)
+            .and_then(|instances| instances.last())
+            .map_or(0, |instance| instance.value)
+    }
+
+    /// The values of every counter of the given name in scope, outermost first, joined by the separator, zero if there
+    /// is none.
+    fn values(&self, name: &str, separator: &str) -> String {
+        match self.0.get(name) {
+            Some(instances) if !instances.is_empty() => instances
+                .iter()
+                .map(|instance| instance.value.to_string())
+                .collect::<Vec<_>>()
+                .join(separator),
+            _ => String::from("0"),
+        }
+    }
+
+    /// Ends the scope of the counters created by the children of the given element.
+    fn close(&mut self, node_id: NodeId) {
+        for instances in self.0.values_mut() {
+            instances.retain(|instance| instance.scope != Some(node_id));
+        }
+    }
+
+    /// Applies the `counter-reset` and then the `counter-increment` of an element or pseudo-element.
+    fn apply(&mut self, style: &ComputedStyle, scope: Option<NodeId>) {
+        for (name, value) in &style.counter_reset.0 {
+            self.reset(name, *value, scope);
+        }
+
+        for (name, by) in &style.counter_increment.0 {
+            self.increment(name, *by, scope);
+        }
+    }
+}
+
+/// Walks the DOM in document order, computing the style and text of every `::before` and `::after` pseudo-element.
+pub(crate) struct ContentGenerator<'a> {
+    ctx: &'a CascadeContext<'a>,
+    property_registry: &'a mut PropertyRegistry,
+    styles: &'a [ComputedStyle],
+
+    /// The pseudo-elements styled by at least one rule, the others never generate content.
+    styled: HashSet<PseudoElement>,
+
+    /// The number of quotes opened and not yet closed so far in document order.
+    quote_depth: usize,
+}
+
+impl<'a> ContentGenerator<'a> {
+    pub fn new(
+        ctx: &'a CascadeContext<'a>,
+        property_registry: &'a mut PropertyRegistry,
+        styles: &'a [ComputedStyle],
+    ) -> Self {
+        let styled = ctx
+            .rules
+            .generated
+            .iter()
+            .filter_map(|rule| rule.pseudo_element)
+            .collect();
+
+        Self {
+            ctx,
+            property_registry,
+            styles,
+            styled,
+            quote_depth: 0,
+        }
+    }
+
+    /// Generates the content of the pseudo-elements of every element.
+    ///
+    /// # Returns
+    /// The generated content keyed by the element and pseudo-element, pseudo-elements whose `content` is `none` or
+    /// `normal` have no entry.
+    pub fn generate(mut self) -> HashMap<(NodeId, PseudoElement), GeneratedContent> {
+        let (dom, styles) = (self.ctx.dom, self.styles);
+        let mut generated = HashMap::new();
+        let mut counters = Counters::default();
+
+        // The open elements and whether they generate a box. Parents always precede their children in the DOM, so
+        // every element still open when a node is visited is an ancestor of it, and the elements closed before it have
+        // had all of their descendants visited.
+        let mut open_elements: Vec<(NodeId, bool)> = Vec::new();
+
+        for node in &dom.nodes {
+            if node.data.as_element().is_none() {
+                continue;
+            }
+
+            while let Some(&(open, rendered)) = open_elements.last()
+                && Some(open) != node.parent
+            {
+                open_elements.pop();
+                self.close_element(open, rendered, &mut counters, &mut generated);
+            }
+
+            // Elements without a box, including the descendants of a `display: none` element, neither affect counters
+            // nor generate content.
+            let rendered = !styles[*node.id].display.is_none() && open_elements.last().is_none_or(|&(_, r)| r);
+
+            if rendered {
+                counters.apply(&styles[*node.id], node.parent);
+
+                if let Some(content) = self.pseudo_element(node, PseudoElement::Before, &mut counters) {
+                    generated.insert((node.id, PseudoElement::Before), content);
+                }
+            }
+
+            open_elements.push((node.id, rendered));
+        }
+
+        while let Some((open, rendered)) = open_elements.pop() {
+            self.close_element(open, rendered, &mut counters, &mut generated);
+        }
+
+        generated
+    }
+
+    /// Generates the `::after` pseudo-element of an element once all of its descendants have been visited.
+    fn close_element(
+        &mut self,
+        node_id: NodeId,
+        rendered: bool,
+        counters: &mut Counters,
+        generated: &mut HashMap<(NodeId, PseudoElement), GeneratedContent>,
+    ) {
+        let dom = self.ctx.dom;
+
+        if rendered && let Some(content) = self.pseudo_element(&dom[node_id], PseudoElement::After, counters) {
+            generated.insert((node_id, PseudoElement::After), content);
+        }
+
+        counters.close(node_id);
+    }
+
+    fn pseudo_element(
+        &mut self,
+        node: &DomNode,
+        pseudo_element: PseudoElement,
+        counters: &mut Counters,
+    ) -> Option<GeneratedContent> {
+        if !self.styled.contains(&pseudo_element) {
+            return None;
+        }
+
+        let mut style =
+            ComputedStyle::from_node(self.ctx, node.id, Some(pseudo_element), self.property_registry, self.styles);
+
+        let Content::Items(items) = &style.content else {
+            return None;
+        };
+
+        if style.display.is_none() {
+            return None;
+        }
+
+        counters.apply(&style, Some(node.id));
+
+        let element = node.data.as_element()?;
+        let mut text = String::new();
+
+        for item in items {
+            match item {
+                ContentItem::String(string) => text.push_str(string),
+                ContentItem::Attr(name) => text.push_str(element.get_attribute(name).unwrap_or_default()),
+                ContentItem::Counter(name) => text.push_str(&counters.value(name).to_string()),
+                ContentItem::Counters { name, separator } => text.push_str(&counters.values(name, separator)),
+                ContentItem::OpenQuote => {
+                    text.push_str(style.quotes.open(self.quote_depth));
+                    self.quote_depth += 1;
+                }
+                // A close quote without an open quote to close inserts nothing.
+                ContentItem::CloseQuote if self.quote_depth > 0 => {
+                    self.quote_depth -= 1;
+                    text.push_str(style.quotes.close(self.quote_depth));
+                }
+                ContentItem::CloseQuote => {}
+                ContentItem::NoOpenQuote => self.quote_depth += 1,
+                ContentItem::NoCloseQuote => self.quote_depth = self.quote_depth.saturating_sub(1),
+            }
+        }
+
+        // Only inline generated content is supported, so the pseudo-element is neither a block, floated nor positioned.
+        style.display = OutsideDisplay::Inline.into();
+        style.float = Float::None;
+        style.position = Position::Static;
+
+        Some(GeneratedContent {
+            owner: node.id,
+            style,
+            text,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use css_cssom::{CSSStyleSheet, CssParser};
+    use html_dom::{DocumentRoot, Element, HtmlTag, NodeData, Tag};
+    use url::Url;
+
+    use super::*;
+    use crate::{AbsoluteContext, StyleTree};
+
+    fn element(tag: HtmlTag, attributes: &[(&str, &str)]) -> NodeData {
+        let attributes = attributes
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+
+        NodeData::Element(Element::new(Tag::Html(tag), HashSet::new(), attributes))
+    }
+
+    #[test]
+    fn test_list_counters_and_attributes() {
+        let mut dom = DocumentRoot::new();
+        let list = dom.push_node(&element(HtmlTag::Ol, &[]), None);
+        let first = dom.push_node(&element(HtmlTag::Li, &[("title", "first")]), Some(list));
+        let second = dom.push_node(&element(HtmlTag::Li, &[]), Some(list));
+
+        let css = "ol { counter-reset: item 4 } li { counter-increment: item } \
+            li::before { content: counter(item) \". \" } li:after { content: attr(title) }";
+        let stylesheets = [CSSStyleSheet::from(
+            CssParser::default().parse_css(css, false),
+        )];
+        let url = Url::parse("http://localhost").unwrap();
+        let tree = StyleTree::build(None, &AbsoluteContext::default_url(&url), &dom, &stylesheets);
+
+        let before = tree.pseudo_element(first, PseudoElement::Before).unwrap();
+        assert_eq!(before.owner, first);
+        assert_eq!(before.text, "5. ");
+        assert!(before.style.display.is_inline());
+        assert_eq!(
+            tree.pseudo_element(second, PseudoElement::Before)
+                .unwrap()
+                .text,
+            "6. "
+        );
+
+        assert_eq!(
+            tree.pseudo_element(first, PseudoElement::After)
+                .unwrap()
+                .text,
+            "first"
+        );
+        assert_eq!(
+            tree.pseudo_element(second, PseudoElement::After)
+                .unwrap()
+                .text,
+            ""
+        );
+        assert!(tree.pseudo_element(list, PseudoElement::Before).is_none());
+    }
+
+    #[test]
+    fn test_nested_lists_counters() {
+        let mut dom = DocumentRoot::new();
+        let outer = dom.push_node(&element(HtmlTag::Ol, &[]), None);
+        let first = dom.push_node(&element(HtmlTag::Li, &[]), Some(outer));
+        let inner = dom.push_node(&element(HtmlTag::Ol, &[]), Some(first));
+        let nested = dom.push_node(&element(HtmlTag::Li, &[]), Some(inner));
+        let second = dom.push_node(&element(HtmlTag::Li, &[]), Some(outer));
+
+        let css = "ol { counter-reset: section } li { counter-increment: section } \
+            li::before { content: counters(section, \".\") }";
+        let stylesheets = [CSSStyleSheet::from(
+            CssParser::default().parse_css(css, false),
+        )];
+        let url = Url::parse("http://localhost").unwrap();
+        let tree = StyleTree::build(None, &AbsoluteContext::default_url(&url), &dom, &stylesheets);
+
+        let text = |node_id| {
+            tree.pseudo_element(node_id, PseudoElement::Before)
+                .unwrap()
+                .text
+                .clone()
+        };
+
+        assert_eq!(text(first), "1");
+        assert_eq!(text(nested), "1.1");
+        assert_eq!(text(second), "2");
+    }
+
+    #[test]
+    fn test_nested_quotes() {
+        let mut dom = DocumentRoot::new();
+        let outer = dom.push_node(&element(HtmlTag::Q, &[]), None);
+        let inner = dom.push_node(&element(HtmlTag::Q, &[]), Some(outer));
+        let unquoted = dom.push_node(&element(HtmlTag::Span, &[]), Some(outer));
+
+        let css = "q { quotes: \"«\" \"»\" \"<\" \">\" } q::before { content: open-quote } \
+            q::after { content: close-quote } span::before { content: no-open-quote \"-\" }";
+        let stylesheets = [CSSStyleSheet::from(
+            CssParser::default().parse_css(css, false),
+        )];
+        let url = Url::parse("http://localhost").unwrap();
+        let tree = StyleTree::build(None, &AbsoluteContext::default_url(&url), &dom, &stylesheets);
+
+        let text = |node_id, pseudo_element| {
+            tree.pseudo_element(node_id, pseudo_element)
+                .unwrap()
+                .text
+                .clone()
+        };
+
+        assert_eq!(text(outer, PseudoElement::Before), "«");
+        assert_eq!(text(inner, PseudoElement::Before), "<");
+        assert_eq!(text(inner, PseudoElement::After), ">");
+        assert_eq!(text(unquoted, PseudoElement::Before), "-");
+
+        // The span left a quote open, so the outer quote closes the second level.
+        assert_eq!(text(outer, PseudoElement::After), ">");
+    }
+
+    #[test]
+    fn test_sibling_reset_replaces_counter() {
+        let mut counters = Counters::default();
+        counters.reset("item", 0, Some(NodeId(0)));
+        counters.increment("item", 1, Some(NodeId(0)));
+        counters.reset("item", 10, Some(NodeId(0)));
+
+        assert_eq!(counters.value("item"), 10);
+    }
+
+    #[test]
+    fn test_nested_counters_close_with_their_scope() {
+        let mut counters = Counters::default();
+        counters.reset("item", 0, Some(NodeId(0)));
+        counters.increment("item", 1, Some(NodeId(0)));
+        counters.reset("item", 5, Some(NodeId(2)));
+
+        assert_eq!(counters.value("item"), 5);
+        assert_eq!(counters.values("item", "."), "1.5");
+
+        counters.close(NodeId(2));
+        assert_eq!(counters.value("item"), 1);
+    }
+
+    #[test]
+    fn test_increment_without_counter_creates_one() {
+        let mut counters = Counters::default();
+        counters.increment("section", 2, None);
+
+        assert_eq!(counters.value("section"), 2);
+        assert_eq!(counters.value("missing"), 0);
+    }
+}
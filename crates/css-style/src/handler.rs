@@ -1,18 +1,19 @@
 use css_cssom::{ComponentValue, ComponentValueStream, CssTokenKind, HashType};
 use css_values::{
     CSSParsable, FlexBasis, FlexDirection, FlexWrap, Gap, OverflowBlock,
-    background::{Attachment, BgClip, RepeatStyle, Size, VisualBox, WidthHeightSize},
+    background::{Attachment, BgClip, MaskMode, RepeatStyle, Size, VisualBox, WidthHeightSize},
     border::{BorderStyle, BorderWidth},
     calc::CalcKind,
     color::{Color, base::ColorBase},
     combination::LengthPercentage,
+    dimension::ContainIntrinsicLength,
     error::CssValueError,
     global::Global,
     image::Image,
     numeric::{Flex, NumberOrCalc, Percentage},
     position::BgPosition,
     quantity::{Length, LengthUnit},
-    text::{FontSize, FontWeight, WritingMode},
+    text::{FontSize, WritingMode},
 };
 use tracing::trace;
 
@@ -22,7 +23,7 @@ use crate::{
         CSSProperty, PixelRepr,
         background::{
             BackgroundAttachment, BackgroundClip, BackgroundImage, BackgroundOrigin, BackgroundPosition,
-            BackgroundPositionX, BackgroundPositionY, BackgroundRepeat, BackgroundSize,
+            BackgroundPositionX, BackgroundPositionY, BackgroundRepeat, BackgroundSize, MaskOrigin,
         },
         offset::Margin,
     },
@@ -116,6 +117,40 @@ macro_rules! simple_property_handler {
     };
 }
 
+/// A macro to generate handlers for the `border-*-radius` longhands, which take a horizontal radius and an optional
+/// vertical one. Only circular corners are drawn, so the vertical radius is validated but dropped.
+macro_rules! border_radius_handler {
+    ($fn_name:ident, $field:ident, $prop_name:expr) => {
+        pub fn $fn_name(ctx: &mut PropertyUpdateContext, stream: &mut ComponentValueStream) {
+            if let Ok(global) = Global::parse(stream) {
+                if stream.has_remaining_tokens() {
+                    ctx.record_error_from_stream(
+                        $prop_name,
+                        stream,
+                        CssValueError::InvalidValue("Unexpected extra tokens after global value".into()),
+                    );
+                    return;
+                }
+
+                ctx.specified_style.$field = CSSProperty::Global(global);
+                return;
+            }
+
+            match parse_border_radii(stream) {
+                Ok(radii) if (1..=2).contains(&radii.len()) && !stream.has_remaining_tokens() => {
+                    ctx.specified_style.$field = CSSProperty::Value(radii[0].clone());
+                }
+                Ok(_) => ctx.record_error_from_stream(
+                    $prop_name,
+                    stream,
+                    CssValueError::InvalidValue("Expected one or two radii".to_string()),
+                ),
+                Err(e) => ctx.record_error_from_stream($prop_name, stream, e),
+            }
+        }
+    };
+}
+
 /// A macro to generate shorthand property handlers for 4-side offset properties (e.g. `margin`, `padding`).
 /// Parses the value once as either a `Global` or an `Offset`, then assigns to all four physical side fields.
 macro_rules! offset_shorthand_handler {
@@ -252,6 +287,7 @@ offset_shorthand_handler!(handle_padding, Offset, "padding", padding_top, paddin
 simple_property_handler!(handle_align_content, align_content, "align-content");
 simple_property_handler!(handle_align_items, align_items, "align-items");
 simple_property_handler!(handle_align_self, align_self, "align-self");
+simple_property_handler!(handle_backdrop_filter, backdrop_filter, "backdrop-filter");
 simple_property_handler!(handle_background_attachment, background_attachment, "background-attachment");
 simple_property_handler!(handle_background_blend_mode, background_blend_mode, "background-blend-mode");
 simple_property_handler!(handle_background_clip, background_clip, "background-clip");
@@ -262,6 +298,10 @@ simple_property_handler!(handle_background_position_x, background_position_x, "b
 simple_property_handler!(handle_background_position_y, background_position_y, "background-position-y");
 simple_property_handler!(handle_background_repeat, background_repeat, "background-repeat");
 simple_property_handler!(handle_background_size, background_size, "background-size");
+border_radius_handler!(handle_border_bottom_left_radius, border_radius_bottom_left, "border-bottom-left-radius");
+border_radius_handler!(handle_border_bottom_right_radius, border_radius_bottom_right, "border-bottom-right-radius");
+border_radius_handler!(handle_border_top_left_radius, border_radius_top_left, "border-top-left-radius");
+border_radius_handler!(handle_border_top_right_radius, border_radius_top_right, "border-top-right-radius");
 simple_property_handler!(handle_border_bottom_color, border_bottom_color, "border-bottom-color");
 simple_property_handler!(handle_border_bottom_style, border_bottom_style, "border-bottom-style");
 simple_property_handler!(handle_border_bottom_width, border_bottom_width, "border-bottom-width");
@@ -275,30 +315,60 @@ simple_property_handler!(handle_border_top_color, border_top_color, "border-top-
 simple_property_handler!(handle_border_top_style, border_top_style, "border-top-style");
 simple_property_handler!(handle_border_top_width, border_top_width, "border-top-width");
 simple_property_handler!(handle_bottom, bottom, "bottom");
+simple_property_handler!(handle_box_shadow, box_shadow, "box-shadow");
 simple_property_handler!(handle_clear, clear, "clear");
 simple_property_handler!(handle_color, color, "color");
 simple_property_handler!(handle_column_gap, column_gap, "column-gap");
+simple_property_handler!(handle_contain_intrinsic_height, contain_intrinsic_height, "contain-intrinsic-height");
+simple_property_handler!(handle_contain_intrinsic_width, contain_intrinsic_width, "contain-intrinsic-width");
+simple_property_handler!(handle_container_name, container_name, "container-name");
+simple_property_handler!(handle_container_type, container_type, "container-type");
+simple_property_handler!(handle_content, content, "content");
+simple_property_handler!(handle_content_visibility, content_visibility, "content-visibility");
+simple_property_handler!(handle_counter_increment, counter_increment, "counter-increment");
+simple_property_handler!(handle_counter_reset, counter_reset, "counter-reset");
 simple_property_handler!(handle_cursor, cursor, "cursor");
 simple_property_handler!(handle_display, display, "display");
 simple_property_handler!(handle_flex_basis, flex_basis, "flex-basis");
 simple_property_handler!(handle_flex_direction, flex_direction, "flex-direction");
 simple_property_handler!(handle_flex_grow, flex_grow, "flex-grow");
 simple_property_handler!(handle_flex_shrink, flex_shrink, "flex-shrink");
+simple_property_handler!(handle_filter, filter, "filter");
 simple_property_handler!(handle_flex_wrap, flex_wrap, "flex-wrap");
 simple_property_handler!(handle_float, float, "float");
 simple_property_handler!(handle_font_family, font_family, "font-family");
+simple_property_handler!(handle_font_kerning, font_kerning, "font-kerning");
+simple_property_handler!(handle_font_size_adjust, font_size_adjust, "font-size-adjust");
+simple_property_handler!(handle_font_variant_ligatures, font_variant_ligatures, "font-variant-ligatures");
+simple_property_handler!(handle_font_variant_numeric, font_variant_numeric, "font-variant-numeric");
+simple_property_handler!(handle_font_variant_caps, font_variant_caps, "font-variant-caps");
+simple_property_handler!(handle_font_feature_settings, font_feature_settings, "font-feature-settings");
+simple_property_handler!(handle_font_weight, font_weight, "font-weight");
+simple_property_handler!(handle_forced_color_adjust, forced_color_adjust, "forced-color-adjust");
 simple_property_handler!(handle_height, height, "height");
+simple_property_handler!(handle_hyphenate_character, hyphenate_character, "hyphenate-character");
+simple_property_handler!(handle_hyphens, hyphens, "hyphens");
+simple_property_handler!(handle_image_rendering, image_rendering, "image-rendering");
+simple_property_handler!(handle_isolation, isolation, "isolation");
 simple_property_handler!(handle_justify_content, justify_content, "justify-content");
 simple_property_handler!(handle_justify_items, justify_items, "justify-items");
 simple_property_handler!(handle_justify_self, justify_self, "justify-self");
 simple_property_handler!(handle_left, left, "left");
+simple_property_handler!(handle_letter_spacing, letter_spacing, "letter-spacing");
 simple_property_handler!(handle_line_height, line_height, "line-height");
 simple_property_handler!(handle_margin_bottom, margin_bottom, "margin-bottom");
 simple_property_handler!(handle_margin_left, margin_left, "margin-left");
 simple_property_handler!(handle_margin_right, margin_right, "margin-right");
 simple_property_handler!(handle_margin_top, margin_top, "margin-top");
+simple_property_handler!(handle_mask_image, mask_image, "mask-image");
+simple_property_handler!(handle_mask_mode, mask_mode, "mask-mode");
+simple_property_handler!(handle_mask_origin, mask_origin, "mask-origin");
+simple_property_handler!(handle_mask_repeat, mask_repeat, "mask-repeat");
+simple_property_handler!(handle_mask_size, mask_size, "mask-size");
 simple_property_handler!(handle_max_height, max_height, "max-height");
 simple_property_handler!(handle_max_width, max_width, "max-width");
+simple_property_handler!(handle_mix_blend_mode, mix_blend_mode, "mix-blend-mode");
+simple_property_handler!(handle_object_fit, object_fit, "object-fit");
 simple_property_handler!(handle_order, order, "order");
 simple_property_handler!(handle_overflow_anchor, overflow_anchor, "overflow-anchor");
 simple_property_handler!(handle_overflow_wrap, overflow_wrap, "overflow-wrap");
@@ -308,13 +378,21 @@ simple_property_handler!(handle_padding_bottom, padding_bottom, "padding-bottom"
 simple_property_handler!(handle_padding_left, padding_left, "padding-left");
 simple_property_handler!(handle_padding_right, padding_right, "padding-right");
 simple_property_handler!(handle_padding_top, padding_top, "padding-top");
+simple_property_handler!(handle_paint_order, paint_order, "paint-order");
 simple_property_handler!(handle_position, position, "position");
+simple_property_handler!(handle_quotes, quotes, "quotes");
+simple_property_handler!(handle_resize, resize, "resize");
 simple_property_handler!(handle_right, right, "right");
 simple_property_handler!(handle_row_gap, row_gap, "row-gap");
+simple_property_handler!(handle_tab_size, tab_size, "tab-size");
 simple_property_handler!(handle_text_align, text_align, "text-align");
+simple_property_handler!(handle_text_indent, text_indent, "text-indent");
 simple_property_handler!(handle_top, top, "top");
 simple_property_handler!(handle_whitespace, whitespace, "white-space");
 simple_property_handler!(handle_width, width, "width");
+simple_property_handler!(handle_will_change, will_change, "will-change");
+simple_property_handler!(handle_word_break, word_break, "word-break");
+simple_property_handler!(handle_word_spacing, word_spacing, "word-spacing");
 simple_property_handler!(handle_writing_mode, writing_mode, "writing-mode");
 logical_pair_handler!(
     handle_margin_block,
@@ -368,6 +446,63 @@ logical_edge_handler!(handle_margin_inline_start, "margin-inline-start", margin_
 logical_edge_handler!(handle_margin_inline_end, "margin-inline-end", margin_right, margin_bottom, margin_bottom);
 logical_edge_handler!(handle_padding_inline_start, "padding-inline-start", padding_left, padding_top, padding_top);
 logical_edge_handler!(handle_padding_inline_end, "padding-inline-end", padding_right, padding_bottom, padding_bottom);
+logical_edge_handler!(
+    handle_contain_intrinsic_block_size,
+    "contain-intrinsic-block-size",
+    contain_intrinsic_height,
+    contain_intrinsic_width,
+    contain_intrinsic_width
+);
+logical_edge_handler!(
+    handle_contain_intrinsic_inline_size,
+    "contain-intrinsic-inline-size",
+    contain_intrinsic_width,
+    contain_intrinsic_height,
+    contain_intrinsic_height
+);
+
+/// Handles the `contain-intrinsic-size` shorthand, whose first value sets `contain-intrinsic-width` and second value
+/// sets `contain-intrinsic-height`, defaulting to the first.
+pub fn handle_contain_intrinsic_size(ctx: &mut PropertyUpdateContext, stream: &mut ComponentValueStream) {
+    if let Ok(global) = Global::parse(stream) {
+        if stream.has_remaining_tokens() {
+            ctx.record_error_from_stream("contain-intrinsic-size", stream, CssValueError::UnexpectedRemainingInput);
+            return;
+        }
+
+        ctx.specified_style.contain_intrinsic_width = CSSProperty::Global(global);
+        ctx.specified_style.contain_intrinsic_height = CSSProperty::Global(global);
+        return;
+    }
+
+    let width = match ContainIntrinsicLength::parse(stream) {
+        Ok(width) => width,
+        Err(e) => {
+            ctx.record_error_from_stream("contain-intrinsic-size", stream, e);
+            return;
+        }
+    };
+
+    let height = if stream.has_remaining_tokens() {
+        match ContainIntrinsicLength::parse(stream) {
+            Ok(height) => height,
+            Err(e) => {
+                ctx.record_error_from_stream("contain-intrinsic-size", stream, e);
+                return;
+            }
+        }
+    } else {
+        width
+    };
+
+    if stream.has_remaining_tokens() {
+        ctx.record_error_from_stream("contain-intrinsic-size", stream, CssValueError::UnexpectedRemainingInput);
+        return;
+    }
+
+    ctx.specified_style.contain_intrinsic_width = width.into();
+    ctx.specified_style.contain_intrinsic_height = height.into();
+}
 
 pub fn handle_background_position(ctx: &mut PropertyUpdateContext, stream: &mut ComponentValueStream) {
     let checkpoint = stream.checkpoint();
@@ -411,6 +546,53 @@ pub fn handle_background_position(ctx: &mut PropertyUpdateContext, stream: &mut
     }
 }
 
+/// Try to parse a single `Size` value (1–2 tokens: cover | contain | auto | <length-percentage> ){1,2}
+/// directly from the stream. Returns `None` and restores on failure.
+fn try_parse_single_size(stream: &mut ComponentValueStream) -> Option<Size> {
+    let checkpoint = stream.checkpoint();
+
+    fn parse_width_height_token(kind: &CssTokenKind) -> Option<WidthHeightSize> {
+        match kind {
+            CssTokenKind::Ident(s) if s.eq_ignore_ascii_case("auto") => Some(WidthHeightSize::Auto),
+            CssTokenKind::Dimension { value, unit } => {
+                let len_unit = unit.parse::<LengthUnit>().ok()?;
+                Some(WidthHeightSize::Length(LengthPercentage::Length(Length::new(value.to_f64(), len_unit))))
+            }
+            CssTokenKind::Percentage(pct) => {
+                Some(WidthHeightSize::Length(LengthPercentage::Percentage(Percentage::new(pct.to_f64()))))
+            }
+            CssTokenKind::Number(n) if n.to_f64() == 0.0 => {
+                Some(WidthHeightSize::Length(LengthPercentage::Length(Length::new(0.0, LengthUnit::Px))))
+            }
+            _ => None,
+        }
+    }
+
+    if let Some(ComponentValue::Token(t)) = stream.next_non_whitespace() {
+        if let CssTokenKind::Ident(s) = &t.kind {
+            if s.eq_ignore_ascii_case("cover") {
+                return Some(Size::Cover);
+            }
+            if s.eq_ignore_ascii_case("contain") {
+                return Some(Size::Contain);
+            }
+        }
+        if let Some(w) = parse_width_height_token(&t.kind) {
+            let checkpoint2 = stream.checkpoint();
+            if let Some(ComponentValue::Token(t2)) = stream.next_non_whitespace()
+                && let Some(h) = parse_width_height_token(&t2.kind)
+            {
+                return Some(Size::WidthHeight(w, Some(h)));
+            }
+            stream.restore(checkpoint2);
+            return Some(Size::WidthHeight(w, None));
+        }
+    }
+
+    stream.restore(checkpoint);
+    None
+}
+
 /// Handles the `background` shorthand property.
 ///
 /// CSS grammar:
@@ -487,53 +669,6 @@ pub fn handle_background(ctx: &mut PropertyUpdateContext, stream: &mut Component
     let mut y_positions = Vec::new();
     let mut final_color = Color::Base(ColorBase::Transparent);
 
-    /// Try to parse a single `Size` value (1–2 tokens: cover | contain | auto | <length-percentage> ){1,2}
-    /// directly from the stream. Returns `None` and restores on failure.
-    fn try_parse_single_size(stream: &mut ComponentValueStream) -> Option<Size> {
-        let checkpoint = stream.checkpoint();
-
-        fn parse_width_height_token(kind: &CssTokenKind) -> Option<WidthHeightSize> {
-            match kind {
-                CssTokenKind::Ident(s) if s.eq_ignore_ascii_case("auto") => Some(WidthHeightSize::Auto),
-                CssTokenKind::Dimension { value, unit } => {
-                    let len_unit = unit.parse::<LengthUnit>().ok()?;
-                    Some(WidthHeightSize::Length(LengthPercentage::Length(Length::new(value.to_f64(), len_unit))))
-                }
-                CssTokenKind::Percentage(pct) => {
-                    Some(WidthHeightSize::Length(LengthPercentage::Percentage(Percentage::new(pct.to_f64()))))
-                }
-                CssTokenKind::Number(n) if n.to_f64() == 0.0 => {
-                    Some(WidthHeightSize::Length(LengthPercentage::Length(Length::new(0.0, LengthUnit::Px))))
-                }
-                _ => None,
-            }
-        }
-
-        if let Some(ComponentValue::Token(t)) = stream.next_non_whitespace() {
-            if let CssTokenKind::Ident(s) = &t.kind {
-                if s.eq_ignore_ascii_case("cover") {
-                    return Some(Size::Cover);
-                }
-                if s.eq_ignore_ascii_case("contain") {
-                    return Some(Size::Contain);
-                }
-            }
-            if let Some(w) = parse_width_height_token(&t.kind) {
-                let checkpoint2 = stream.checkpoint();
-                if let Some(ComponentValue::Token(t2)) = stream.next_non_whitespace()
-                    && let Some(h) = parse_width_height_token(&t2.kind)
-                {
-                    return Some(Size::WidthHeight(w, Some(h)));
-                }
-                stream.restore(checkpoint2);
-                return Some(Size::WidthHeight(w, None));
-            }
-        }
-
-        stream.restore(checkpoint);
-        None
-    }
-
     let mut done = false;
 
     while !done {
@@ -787,6 +922,219 @@ pub fn handle_background(ctx: &mut PropertyUpdateContext, stream: &mut Component
     }
 }
 
+/// Handles the `mask-position` shorthand property, which sets `mask-position-x` and `mask-position-y` the same way
+/// `background-position` sets the background positions.
+pub fn handle_mask_position(ctx: &mut PropertyUpdateContext, stream: &mut ComponentValueStream) {
+    let checkpoint = stream.checkpoint();
+
+    if let Ok(global) = Global::parse(stream) {
+        ctx.specified_style.mask_position_x = CSSProperty::Global(global);
+        ctx.specified_style.mask_position_y = CSSProperty::Global(global);
+        return;
+    }
+
+    stream.restore(checkpoint);
+
+    let writing_mode = ctx.resolve_writing_mode();
+
+    let mut x_pos = Vec::new();
+    let mut y_pos = Vec::new();
+
+    stream.skip_whitespace();
+    match BackgroundPosition::parse(stream) {
+        Ok(mask_position) => {
+            for position in mask_position.0 {
+                BackgroundPosition::resolve_bg_position_layer(position, writing_mode, &mut x_pos, &mut y_pos);
+            }
+        }
+
+        Err(e) => {
+            ctx.record_error_from_stream(
+                "mask-position",
+                stream,
+                CssValueError::InvalidValue(format!("Invalid value for mask-position: {e}")),
+            );
+        }
+    }
+
+    if !x_pos.is_empty() {
+        ctx.specified_style.mask_position_x = CSSProperty::Value(BackgroundPositionX(x_pos));
+    }
+
+    if !y_pos.is_empty() {
+        ctx.specified_style.mask_position_y = CSSProperty::Value(BackgroundPositionY(y_pos));
+    }
+}
+
+/// Handles the `mask` shorthand property.
+///
+/// CSS grammar:
+/// mask = <mask-layer>#
+///
+/// <mask-layer> =
+///   <mask-reference>              ||
+///   <position> [ / <bg-size> ]?   ||
+///   <repeat-style>                ||
+///   <visual-box>                  ||
+///   <masking-mode>
+///
+/// The clip box and `<compositing-operator>` of a layer are not supported, and since `mask-mode` holds a single value
+/// the mode of the first layer that sets one applies to all layers.
+pub fn handle_mask(ctx: &mut PropertyUpdateContext, stream: &mut ComponentValueStream) {
+    if let Ok(global) = Global::parse(stream) {
+        if stream.has_remaining_tokens() {
+            ctx.record_error_from_stream(
+                "mask",
+                stream,
+                CssValueError::InvalidValue("Unexpected extra tokens after global value".into()),
+            );
+            return;
+        }
+
+        ctx.specified_style.mask_image = CSSProperty::Global(global);
+        ctx.specified_style.mask_mode = CSSProperty::Global(global);
+        ctx.specified_style.mask_origin = CSSProperty::Global(global);
+        ctx.specified_style.mask_position_x = CSSProperty::Global(global);
+        ctx.specified_style.mask_position_y = CSSProperty::Global(global);
+        ctx.specified_style.mask_repeat = CSSProperty::Global(global);
+        ctx.specified_style.mask_size = CSSProperty::Global(global);
+        return;
+    }
+
+    let writing_mode = ctx.resolve_writing_mode();
+
+    let mut images = Vec::new();
+    let mut repeats = Vec::new();
+    let mut origins = Vec::new();
+    let mut sizes = Vec::new();
+    let mut x_positions = Vec::new();
+    let mut y_positions = Vec::new();
+    let mut mode: Option<MaskMode> = None;
+
+    let mut done = false;
+
+    while !done {
+        let mut layer_image: Option<Image> = None;
+        let mut layer_repeat: Option<(RepeatStyle, RepeatStyle)> = None;
+        let mut layer_origin: Option<VisualBox> = None;
+        let mut layer_size: Option<Size> = None;
+        let mut layer_position: Option<BgPosition> = None;
+
+        loop {
+            if !stream.has_remaining_tokens() {
+                done = true;
+                break;
+            }
+
+            if let Some(ComponentValue::Token(t)) = stream.peek()
+                && matches!(t.kind, CssTokenKind::Comma)
+            {
+                stream.next_cv();
+                break;
+            }
+
+            if layer_position.is_none() {
+                let checkpoint = stream.checkpoint();
+                if let Ok(pos) = BgPosition::parse(stream) {
+                    layer_position = Some(pos);
+
+                    let cp_slash = stream.checkpoint();
+                    stream.skip_whitespace();
+                    if let Some(ComponentValue::Token(t)) = stream.peek()
+                        && matches!(t.kind, CssTokenKind::Delim('/'))
+                    {
+                        stream.next_cv();
+                        if let Some(sz) = try_parse_single_size(stream) {
+                            layer_size = Some(sz);
+                        } else {
+                            stream.restore(cp_slash);
+                        }
+                    }
+                    continue;
+                }
+                stream.restore(checkpoint);
+            }
+
+            match stream.next_non_whitespace().cloned() {
+                Some(ComponentValue::Function(func)) => {
+                    if layer_image.is_none()
+                        && let Ok(img) = Image::try_from(&func)
+                    {
+                        layer_image = Some(img);
+                        continue;
+                    }
+                }
+                Some(ComponentValue::Token(t)) => match t.kind {
+                    CssTokenKind::Url(url) if layer_image.is_none() => layer_image = Some(Image::Url(url)),
+                    CssTokenKind::Ident(ident) => {
+                        if layer_image.is_none() && ident.eq_ignore_ascii_case("none") {
+                            layer_image = Some(Image::None);
+                        } else if layer_repeat.is_none() && ident.eq_ignore_ascii_case("repeat-x") {
+                            layer_repeat = Some((RepeatStyle::Repeat, RepeatStyle::NoRepeat));
+                        } else if layer_repeat.is_none() && ident.eq_ignore_ascii_case("repeat-y") {
+                            layer_repeat = Some((RepeatStyle::NoRepeat, RepeatStyle::Repeat));
+                        } else if let Ok(style) = ident.parse::<RepeatStyle>() {
+                            layer_repeat = match layer_repeat {
+                                None => Some((style, style)),
+                                Some((horizontal, _)) => Some((horizontal, style)),
+                            };
+                        } else if layer_origin.is_none() && ident.eq_ignore_ascii_case("content-box") {
+                            layer_origin = Some(VisualBox::Content);
+                        } else if layer_origin.is_none() && ident.eq_ignore_ascii_case("padding-box") {
+                            layer_origin = Some(VisualBox::Padding);
+                        } else if layer_origin.is_none() && ident.eq_ignore_ascii_case("border-box") {
+                            layer_origin = Some(VisualBox::Border);
+                        } else if let Ok(layer_mode) = ident.parse::<MaskMode>() {
+                            mode.get_or_insert(layer_mode);
+                        }
+                    }
+                    _ => {}
+                },
+                Some(_) => {}
+                None => {
+                    done = true;
+                    break;
+                }
+            }
+        }
+
+        images.push(layer_image.unwrap_or(Image::None));
+        repeats.push(layer_repeat.unwrap_or((RepeatStyle::Repeat, RepeatStyle::Repeat)));
+        origins.push(layer_origin.unwrap_or(VisualBox::Border));
+
+        if let Some(sz) = layer_size {
+            sizes.push(sz);
+        }
+
+        if let Some(pos) = layer_position {
+            BackgroundPosition::resolve_bg_position_layer(pos, writing_mode, &mut x_positions, &mut y_positions);
+        }
+    }
+
+    ctx.specified_style.mask_image = CSSProperty::Value(BackgroundImage(images));
+    ctx.specified_style.mask_mode = CSSProperty::Value(mode.unwrap_or_default());
+    ctx.specified_style.mask_repeat = CSSProperty::Value(BackgroundRepeat(repeats));
+    ctx.specified_style.mask_origin = CSSProperty::Value(MaskOrigin(origins));
+
+    if sizes.is_empty() {
+        ctx.specified_style.mask_size = CSSProperty::Value(BackgroundSize::default());
+    } else {
+        ctx.specified_style.mask_size = CSSProperty::Value(BackgroundSize(sizes));
+    }
+
+    if x_positions.is_empty() {
+        ctx.specified_style.mask_position_x = CSSProperty::Global(Global::Initial);
+    } else {
+        ctx.specified_style.mask_position_x = CSSProperty::Value(BackgroundPositionX(x_positions));
+    }
+
+    if y_positions.is_empty() {
+        ctx.specified_style.mask_position_y = CSSProperty::Global(Global::Initial);
+    } else {
+        ctx.specified_style.mask_position_y = CSSProperty::Value(BackgroundPositionY(y_positions));
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum BorderSide {
     All,
@@ -1794,6 +2142,107 @@ pub fn handle_border_width(ctx: &mut PropertyUpdateContext, stream: &mut Compone
     }
 }
 
+/// Parses the non-negative radii of `border-radius` or one of its longhands, up to a `/` or the end of the value.
+fn parse_border_radii(stream: &mut ComponentValueStream) -> Result<Vec<LengthPercentage>, CssValueError> {
+    let mut radii = Vec::new();
+
+    loop {
+        stream.skip_whitespace();
+
+        let radius = match stream.peek() {
+            None => break,
+            Some(ComponentValue::Token(t)) if matches!(t.kind, CssTokenKind::Delim('/')) => break,
+            Some(cv) => LengthPercentage::try_from(cv)?,
+        };
+
+        let negative = match &radius {
+            LengthPercentage::Length(len) => len.value() < 0.0,
+            LengthPercentage::Percentage(pct) => pct.value() < 0.0,
+            LengthPercentage::Calc(_) => false,
+        };
+        if negative {
+            return Err(CssValueError::InvalidValue("Negative border radius".to_string()));
+        }
+
+        radii.push(radius);
+        stream.next_cv();
+    }
+
+    Ok(radii)
+}
+
+/// Handles the `border-radius` shorthand property, one to four radii for the corners clockwise from the top left,
+/// optionally followed by a `/` and the vertical radii. Only circular corners are drawn, so the vertical radii are
+/// validated but dropped.
+pub fn handle_border_radius(ctx: &mut PropertyUpdateContext, stream: &mut ComponentValueStream) {
+    if let Ok(global) = Global::parse(stream) {
+        if stream.has_remaining_tokens() {
+            ctx.record_error_from_stream(
+                "border-radius",
+                stream,
+                CssValueError::InvalidValue("Unexpected extra tokens after global value".into()),
+            );
+            return;
+        }
+
+        ctx.specified_style.border_radius_top_left = CSSProperty::Global(global);
+        ctx.specified_style.border_radius_top_right = CSSProperty::Global(global);
+        ctx.specified_style.border_radius_bottom_right = CSSProperty::Global(global);
+        ctx.specified_style.border_radius_bottom_left = CSSProperty::Global(global);
+        return;
+    }
+
+    let horizontal = match parse_border_radii(stream) {
+        Ok(radii) => radii,
+        Err(e) => {
+            ctx.record_error_from_stream("border-radius", stream, e);
+            return;
+        }
+    };
+
+    if stream.next_cv().is_some() {
+        match parse_border_radii(stream) {
+            Ok(vertical) if (1..=4).contains(&vertical.len()) && !stream.has_remaining_tokens() => {}
+            Ok(_) => {
+                ctx.record_error_from_stream(
+                    "border-radius",
+                    stream,
+                    CssValueError::InvalidValue("Invalid number of vertical radii".to_string()),
+                );
+                return;
+            }
+            Err(e) => {
+                ctx.record_error_from_stream("border-radius", stream, e);
+                return;
+            }
+        }
+    }
+
+    let (top_left, top_right, bottom_right, bottom_left) = match horizontal.as_slice() {
+        [all] => (all, all, all, all),
+        [top_left_bottom_right, top_right_bottom_left] => {
+            (top_left_bottom_right, top_right_bottom_left, top_left_bottom_right, top_right_bottom_left)
+        }
+        [top_left, top_right_bottom_left, bottom_right] => {
+            (top_left, top_right_bottom_left, bottom_right, top_right_bottom_left)
+        }
+        [top_left, top_right, bottom_right, bottom_left] => (top_left, top_right, bottom_right, bottom_left),
+        _ => {
+            ctx.record_error_from_stream(
+                "border-radius",
+                stream,
+                CssValueError::InvalidValue("Invalid number of radii".to_string()),
+            );
+            return;
+        }
+    };
+
+    ctx.specified_style.border_radius_top_left = CSSProperty::Value(top_left.clone());
+    ctx.specified_style.border_radius_top_right = CSSProperty::Value(top_right.clone());
+    ctx.specified_style.border_radius_bottom_right = CSSProperty::Value(bottom_right.clone());
+    ctx.specified_style.border_radius_bottom_left = CSSProperty::Value(bottom_left.clone());
+}
+
 pub fn handle_flex(ctx: &mut PropertyUpdateContext, stream: &mut ComponentValueStream) {
     if let Ok(global) = Global::parse(stream) {
         if stream.has_remaining_tokens() {
@@ -2079,34 +2528,6 @@ pub fn handle_font_size(ctx: &mut PropertyUpdateContext, stream: &mut ComponentV
     }
 }
 
-/// Handles the `font-weight` property by parsing the provided component values and updating the specified style's font weight accordingly. The function checks for the presence
-/// of `lighter` and `bolder` keywords, which adjust the font weight relative to the parent's font weight.
-pub fn handle_font_weight(ctx: &mut PropertyUpdateContext, stream: &mut ComponentValueStream) {
-    let checkpoint = stream.checkpoint();
-
-    while let Some(cv) = stream.next_cv() {
-        if let ComponentValue::Token(token) = cv
-            && let CssTokenKind::Ident(ident) = &token.kind
-        {
-            if ident.eq_ignore_ascii_case("lighter") {
-                let lighter = ctx.style_ctx.parent_style.font_weight - 100;
-                ctx.specified_style.font_weight = CSSProperty::Value(FontWeight::from(lighter));
-                return;
-            } else if ident.eq_ignore_ascii_case("bolder") {
-                let bolder = ctx.style_ctx.parent_style.font_weight + 100;
-                ctx.specified_style.font_weight = CSSProperty::Value(FontWeight::from(bolder));
-                return;
-            }
-        }
-    }
-
-    stream.restore(checkpoint);
-
-    if let Err(e) = CSSProperty::update_property(&mut ctx.specified_style.font_weight, stream) {
-        ctx.record_error_from_stream("font-weight", stream, e);
-    }
-}
-
 pub fn handle_gap(ctx: &mut PropertyUpdateContext, stream: &mut ComponentValueStream) {
     let checkpoint = stream.checkpoint();
 
@@ -2184,7 +2605,6 @@ pub fn handle_overflow(ctx: &mut PropertyUpdateContext, stream: &mut ComponentVa
     let mut second_overflow = None;
 
     if let Ok(global) = Global::parse(stream) {
-        stream.next_cv();
         first_overflow = Some(CSSProperty::Global(global));
     } else {
         if let Ok(overflow_block) = OverflowBlock::parse(stream) {
@@ -2196,7 +2616,6 @@ pub fn handle_overflow(ctx: &mut PropertyUpdateContext, stream: &mut ComponentVa
         second_overflow = first_overflow;
     } else {
         if let Ok(global) = Global::parse(stream) {
-            stream.next_cv();
             second_overflow = Some(CSSProperty::Global(global));
         } else {
             if let Ok(overflow_block) = OverflowBlock::parse(stream) {
@@ -2251,12 +2670,18 @@ pub fn handle_overflow(ctx: &mut PropertyUpdateContext, stream: &mut ComponentVa
 mod tests {
     use std::net::Ipv4Addr;
 
-    use crate::ComputedStyle;
+    use crate::{Color4f, ComputedBoxShadowList, ComputedStyle};
 
     use super::*;
 
     use css_cssom::CSSStyleSheet;
-    use css_values::position::{PositionX, PositionY};
+    use css_values::{
+        background::{BlendMode, Isolation},
+        color::ForcedColorAdjust,
+        display::ContentVisibility,
+        image::ObjectFit,
+        position::{PositionX, PositionY},
+    };
     use url::Url;
 
     fn absoulte_ctx() -> AbsoluteContext<'static> {
@@ -2296,6 +2721,87 @@ mod tests {
         assert_eq!(specified, before);
     }
 
+    #[test]
+    fn test_border_radius() {
+        let abs = absoulte_ctx();
+        let style_ctx = style_ctx();
+        let mut specified = SpecifiedStyle::default();
+
+        let decls = CSSStyleSheet::from_inline("border-radius: 4px 50% / 2px;");
+        let values = decls[0].original_values.clone();
+        let mut stream = ComponentValueStream::from(&values);
+        let mut ctx = PropertyUpdateContext::new(&abs, &style_ctx, &mut specified);
+
+        handle_border_radius(&mut ctx, &mut stream);
+
+        assert!(ctx.errors.is_empty());
+
+        let px = CSSProperty::Value(LengthPercentage::Length(Length::px(4.0)));
+        let pct = CSSProperty::Value(LengthPercentage::Percentage(Percentage::new(50.0)));
+        assert_eq!(specified.border_radius_top_left, px);
+        assert_eq!(specified.border_radius_top_right, pct);
+        assert_eq!(specified.border_radius_bottom_right, px);
+        assert_eq!(specified.border_radius_bottom_left, pct);
+    }
+
+    #[test]
+    fn test_box_shadow() {
+        let abs = absoulte_ctx();
+        let style_ctx = style_ctx();
+        let mut specified = SpecifiedStyle::default();
+
+        let decls = CSSStyleSheet::from_inline("box-shadow: 2px 3px 4px red, inset 0 0 0 1px;");
+        let values = decls[0].original_values.clone();
+        let mut stream = ComponentValueStream::from(&values);
+        let mut ctx = PropertyUpdateContext::new(&abs, &style_ctx, &mut specified);
+
+        handle_box_shadow(&mut ctx, &mut stream);
+
+        assert!(ctx.errors.is_empty());
+
+        let CSSProperty::Value(box_shadow) = &specified.box_shadow else {
+            panic!("expected a box-shadow value");
+        };
+        let computed = ComputedBoxShadowList::resolve(box_shadow, &specified.color, &style_ctx, &abs);
+        let [outer, inset] = computed.0.as_slice() else {
+            panic!("expected two shadows");
+        };
+
+        assert!(!outer.inset);
+        assert!((outer.offset_y - 3.0).abs() < f64::EPSILON);
+        assert!((outer.blur_radius - 4.0).abs() < f64::EPSILON);
+        assert_eq!(outer.color, Color4f::rgba(1.0, 0.0, 0.0, 1.0));
+        assert!(inset.inset);
+        assert!((inset.spread_radius - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_border_radius_invalid() {
+        let abs = absoulte_ctx();
+        let style_ctx = style_ctx();
+
+        for value in [
+            "-1px",
+            "1px 2px 3px 4px 5px",
+            "1px /",
+            "1px / 2px 3px 4px 5px 6px",
+            "red",
+        ] {
+            let mut specified = SpecifiedStyle::default();
+            let before = specified.clone();
+
+            let decls = CSSStyleSheet::from_inline(&format!("border-radius: {value};"));
+            let values = decls[0].original_values.clone();
+            let mut stream = ComponentValueStream::from(&values);
+            let mut ctx = PropertyUpdateContext::new(&abs, &style_ctx, &mut specified);
+
+            handle_border_radius(&mut ctx, &mut stream);
+
+            assert_eq!(ctx.errors.len(), 1, "{value}");
+            assert_eq!(specified, before, "{value}");
+        }
+    }
+
     #[test]
     fn test_border_any_order() {
         let abs = absoulte_ctx();
@@ -2718,6 +3224,127 @@ mod tests {
         assert_eq!(specified.overflow_y, CSSProperty::Value(OverflowBlock::Auto));
     }
 
+    #[test]
+    fn test_content_visibility_and_contain_intrinsic_size() {
+        let abs = absoulte_ctx();
+        let style_ctx = style_ctx();
+        let mut specified = SpecifiedStyle::default();
+
+        let decls = CSSStyleSheet::from_inline("content-visibility: auto; contain-intrinsic-size: auto 300px 50px;");
+        let mut ctx = PropertyUpdateContext::new(&abs, &style_ctx, &mut specified);
+
+        let values = decls[0].original_values.clone();
+        handle_content_visibility(&mut ctx, &mut ComponentValueStream::from(&values));
+        let values = decls[1].original_values.clone();
+        handle_contain_intrinsic_size(&mut ctx, &mut ComponentValueStream::from(&values));
+
+        assert!(ctx.errors.is_empty());
+        assert_eq!(specified.content_visibility, CSSProperty::Value(ContentVisibility::Auto));
+        assert_eq!(
+            specified.contain_intrinsic_width,
+            CSSProperty::Value(ContainIntrinsicLength::AutoLength(Length::px(300.0)))
+        );
+        assert_eq!(
+            specified.contain_intrinsic_height,
+            CSSProperty::Value(ContainIntrinsicLength::Length(Length::px(50.0)))
+        );
+    }
+
+    #[test]
+    fn test_forced_color_adjust() {
+        let abs = absoulte_ctx();
+        let style_ctx = style_ctx();
+        let mut specified = SpecifiedStyle::default();
+
+        let decls = CSSStyleSheet::from_inline("forced-color-adjust: preserve-parent-color;");
+        let mut ctx = PropertyUpdateContext::new(&abs, &style_ctx, &mut specified);
+
+        let values = decls[0].original_values.clone();
+        handle_forced_color_adjust(&mut ctx, &mut ComponentValueStream::from(&values));
+
+        assert!(ctx.errors.is_empty());
+        assert_eq!(specified.forced_color_adjust, CSSProperty::Value(ForcedColorAdjust::PreserveParentColor));
+    }
+
+    #[test]
+    fn test_mix_blend_mode_and_isolation() {
+        let abs = absoulte_ctx();
+        let style_ctx = style_ctx();
+        let mut specified = SpecifiedStyle::default();
+
+        let decls = CSSStyleSheet::from_inline("mix-blend-mode: multiply; isolation: isolate;");
+        let mut ctx = PropertyUpdateContext::new(&abs, &style_ctx, &mut specified);
+
+        let values = decls[0].original_values.clone();
+        handle_mix_blend_mode(&mut ctx, &mut ComponentValueStream::from(&values));
+        let values = decls[1].original_values.clone();
+        handle_isolation(&mut ctx, &mut ComponentValueStream::from(&values));
+
+        assert!(ctx.errors.is_empty());
+        assert_eq!(specified.mix_blend_mode, CSSProperty::Value(BlendMode::Multiply));
+        assert_eq!(specified.isolation, CSSProperty::Value(Isolation::Isolate));
+    }
+
+    #[test]
+    fn test_mask() {
+        let abs = absoulte_ctx();
+        let style_ctx = style_ctx();
+        let mut specified = SpecifiedStyle::default();
+
+        let decls = CSSStyleSheet::from_inline("mask: url(star.svg) center / contain no-repeat luminance content-box;");
+        let values = decls[0].original_values.clone();
+        let mut ctx = PropertyUpdateContext::new(&abs, &style_ctx, &mut specified);
+
+        handle_mask(&mut ctx, &mut ComponentValueStream::from(&values));
+
+        assert!(ctx.errors.is_empty(), "Expected no errors, got: {:?}", ctx.errors);
+        assert_eq!(specified.mask_image, CSSProperty::Value(BackgroundImage(vec![Image::Url("star.svg".to_string())])));
+        assert_eq!(specified.mask_mode, CSSProperty::Value(MaskMode::Luminance));
+        assert_eq!(specified.mask_origin, CSSProperty::Value(MaskOrigin(vec![VisualBox::Content])));
+        assert_eq!(
+            specified.mask_repeat,
+            CSSProperty::Value(BackgroundRepeat(vec![(RepeatStyle::NoRepeat, RepeatStyle::NoRepeat)]))
+        );
+        assert_eq!(specified.mask_size, CSSProperty::Value(BackgroundSize(vec![Size::Contain])));
+        assert!(matches!(specified.mask_position_x, CSSProperty::Value(_)));
+    }
+
+    #[test]
+    fn test_mask_longhands() {
+        let abs = absoulte_ctx();
+        let style_ctx = style_ctx();
+        let mut specified = SpecifiedStyle::default();
+
+        let decls = CSSStyleSheet::from_inline("mask-mode: alpha; mask-origin: padding-box;");
+        let mut ctx = PropertyUpdateContext::new(&abs, &style_ctx, &mut specified);
+
+        let values = decls[0].original_values.clone();
+        handle_mask_mode(&mut ctx, &mut ComponentValueStream::from(&values));
+        let values = decls[1].original_values.clone();
+        handle_mask_origin(&mut ctx, &mut ComponentValueStream::from(&values));
+
+        assert!(ctx.errors.is_empty());
+        assert_eq!(specified.mask_mode, CSSProperty::Value(MaskMode::Alpha));
+        assert_eq!(specified.mask_origin, CSSProperty::Value(MaskOrigin(vec![VisualBox::Padding])));
+        assert_eq!(MaskOrigin::default(), MaskOrigin(vec![VisualBox::Border]));
+    }
+
+    #[test]
+    fn test_object_fit() {
+        let abs = absoulte_ctx();
+        let style_ctx = style_ctx();
+        let mut specified = SpecifiedStyle::default();
+
+        let decls = CSSStyleSheet::from_inline("object-fit: cover;");
+        let mut ctx = PropertyUpdateContext::new(&abs, &style_ctx, &mut specified);
+
+        let values = decls[0].original_values.clone();
+        handle_object_fit(&mut ctx, &mut ComponentValueStream::from(&values));
+
+        assert!(ctx.errors.is_empty());
+        assert_eq!(specified.object_fit, CSSProperty::Value(ObjectFit::Cover));
+    }
+
     #[test]
     fn test_overflow_double_value() {
         let abs = absoulte_ctx();
@@ -2751,4 +3378,57 @@ mod tests {
 
         assert!(!ctx.errors.is_empty());
     }
+
+    #[test]
+    fn test_color_global_keywords() {
+        let abs = absoulte_ctx();
+        let style_ctx = style_ctx();
+
+        for (keyword, parsed, resolved) in [
+            ("inherit", Global::Inherit, Global::Inherit),
+            ("initial", Global::Initial, Global::Initial),
+            ("unset", Global::Unset, Global::Inherit),
+            ("revert", Global::Revert, Global::Inherit),
+        ] {
+            let mut specified = SpecifiedStyle::default();
+
+            let decls = CSSStyleSheet::from_inline(&format!("color: {keyword};"));
+            let values = decls[0].original_values.clone();
+            let mut stream = ComponentValueStream::from(&values);
+            let mut ctx = PropertyUpdateContext::new(&abs, &style_ctx, &mut specified);
+
+            handle_color(&mut ctx, &mut stream);
+
+            assert!(ctx.errors.is_empty(), "{keyword}");
+            assert_eq!(specified.color, CSSProperty::Global(parsed));
+
+            specified.resolve_unset();
+            assert_eq!(specified.color, CSSProperty::Global(resolved));
+        }
+    }
+
+    #[test]
+    fn test_border_unset() {
+        let abs = absoulte_ctx();
+        let style_ctx = style_ctx();
+        let mut specified = SpecifiedStyle {
+            border_top_style: CSSProperty::Value(BorderStyle::Solid),
+            ..Default::default()
+        };
+
+        let decls = CSSStyleSheet::from_inline("border: unset;");
+        let values = decls[0].original_values.clone();
+        let mut stream = ComponentValueStream::from(&values);
+        let mut ctx = PropertyUpdateContext::new(&abs, &style_ctx, &mut specified);
+
+        handle_border(&mut ctx, &mut stream, BorderSide::All);
+
+        assert!(ctx.errors.is_empty());
+        assert_eq!(specified.border_top_style, CSSProperty::Global(Global::Unset));
+
+        specified.resolve_unset();
+        assert_eq!(specified.border_top_style, CSSProperty::Global(Global::Initial));
+        assert_eq!(specified.border_left_width, CSSProperty::Global(Global::Initial));
+        assert_eq!(specified.border_bottom_color, CSSProperty::Global(Global::Initial));
+    }
 }
@@ -0,0 +1,141 @@
+//! This module defines the invalidation sets used to incrementally update a page after a DOM mutation. The
+//! `StyleInvalidationSet` tracks the nodes whose attributes have changed, so that only their styles (and those of their
+//! descendants) are recomputed, while the `LayoutInvalidationSet` tracks which of the recomputed nodes need their
+//! geometry recomputed and which only need to be repainted.
+
+use std::collections::HashSet;
+
+use html_dom::NodeId;
+
+/// The nodes whose attributes have changed since their styles were last computed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StyleInvalidationSet {
+    nodes: HashSet<NodeId>,
+}
+
+impl StyleInvalidationSet {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks the style of a node as stale.
+    pub fn insert(&mut self, node_id: NodeId) {
+        self.nodes.insert(node_id);
+    }
+
+    #[must_use]
+    pub fn contains(&self, node_id: NodeId) -> bool {
+        self.nodes.contains(&node_id)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.nodes.iter().copied()
+    }
+}
+
+impl FromIterator<NodeId> for StyleInvalidationSet {
+    fn from_iter<T: IntoIterator<Item = NodeId>>(iter: T) -> Self {
+        Self {
+            nodes: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// The nodes whose computed style changed during a style recomputation.
+///
+/// A node is either marked for layout, when a property affecting its geometry changed, or for repaint, when only its
+/// paint properties (such as `color` or `background-color`) changed and its geometry can be kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayoutInvalidationSet {
+    layout: HashSet<NodeId>,
+    repaint: HashSet<NodeId>,
+}
+
+impl LayoutInvalidationSet {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks a node as needing its geometry recomputed, which also repaints it.
+    pub fn mark_layout(&mut self, node_id: NodeId) {
+        self.repaint.remove(&node_id);
+        self.layout.insert(node_id);
+    }
+
+    /// Marks a node as only needing to be repainted, unless it already needs layout.
+    pub fn mark_repaint(&mut self, node_id: NodeId) {
+        if !self.layout.contains(&node_id) {
+            self.repaint.insert(node_id);
+        }
+    }
+
+    #[must_use]
+    pub fn needs_layout(&self, node_id: NodeId) -> bool {
+        self.layout.contains(&node_id)
+    }
+
+    #[must_use]
+    pub fn needs_repaint(&self, node_id: NodeId) -> bool {
+        self.repaint.contains(&node_id)
+    }
+
+    /// The nodes whose geometry must be recomputed.
+    pub fn layout_nodes(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.layout.iter().copied()
+    }
+
+    /// The nodes whose paint properties changed while their geometry did not.
+    pub fn repaint_nodes(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.repaint.iter().copied()
+    }
+
+    /// Whether any node needs its geometry recomputed, if not the layout tree can be repainted as is.
+    #[must_use]
+    pub fn requires_layout(&self) -> bool {
+        !self.layout.is_empty()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.layout.is_empty() && self.repaint.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_takes_precedence_over_repaint() {
+        let mut set = LayoutInvalidationSet::new();
+        set.mark_repaint(NodeId(1));
+        set.mark_layout(NodeId(1));
+        set.mark_repaint(NodeId(1));
+
+        assert!(set.needs_layout(NodeId(1)));
+        assert!(!set.needs_repaint(NodeId(1)));
+        assert!(set.requires_layout());
+    }
+
+    #[test]
+    fn test_repaint_only() {
+        let mut set = LayoutInvalidationSet::new();
+        set.mark_repaint(NodeId(2));
+
+        assert!(set.needs_repaint(NodeId(2)));
+        assert!(!set.requires_layout());
+        assert!(!set.is_empty());
+    }
+}
@@ -6,9 +6,17 @@
 
 mod cascade;
 mod computed;
+mod container;
+pub mod errors;
+mod font_face;
 mod functions;
+mod generated;
 mod handler;
+mod invalidation;
+mod media;
+mod parts;
 mod properties;
+mod pseudo;
 mod rules;
 mod specified;
 mod tree;
@@ -16,12 +24,23 @@ mod tree;
 pub use computed::{
     ComputedStyle,
     color::Color4f,
-    dimension::{ComputedMaxSize, ComputedSize},
-    offset::{ComputedMargin, ComputedOffset},
+    dimension::{ComputedIntrinsicLength, ComputedMaxSize, ComputedSize},
+    filter::{ComputedDropShadow, ComputedFilter, ComputedFilterFunction},
+    offset::{ComputedMargin, ComputedOffset, ComputedTextIndent},
+    position::ComputedLengthPercentage,
+    shadow::{ComputedBoxShadow, ComputedBoxShadowList},
+    text::ComputedLineHeight,
 };
+pub use container::{Container, ContainerCondition, ContainerQuery, ContainerSizes};
+pub use css_selectors::{PseudoElement, PseudoElementKind};
+pub use font_face::{FontFace, FontFaceSource};
+pub use generated::GeneratedContent;
+pub use invalidation::{LayoutInvalidationSet, StyleInvalidationSet};
+pub use media::MediaQuery;
+pub use parts::PartStyles;
 pub use properties::display::*;
 pub use properties::font::*;
 pub use properties::offset::*;
 pub use properties::position::*;
-pub use properties::{AbsoluteContext, RelativeType, StyleContext};
+pub use properties::{AbsoluteContext, InputDevice, RelativeType, StyleContext, SystemPreferences};
 pub use tree::StyleTree;
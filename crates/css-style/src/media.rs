@@ -0,0 +1,664 @@
+//! Media queries, the preludes of the `@media` rules whose rules only apply while the viewport and the preferences of
+//! the user match them, such as `screen and (max-width: 600px)` or `(prefers-color-scheme: dark)`.
+
+use css_cssom::{AssociatedToken, ComponentValue, CssTokenKind};
+use css_values::{
+    media::{
+        ColorScheme, ContrastPreference, ForcedColorsState, Hover, MediaCondition, MediaFeature, MediaType,
+        Orientation, Pointer, RangeOperator, ReducedDataPreference, ReducedMotionPreference,
+    },
+    quantity::{Length, LengthUnit},
+};
+
+use crate::{AbsoluteContext, properties::PixelRepr};
+
+/// A media query, or a part of one combined with the others by `and`, `or` and `not`.
+///
+/// NOTE: Only the size of the viewport, the colors of the screen and the preferences of the user can be queried, any
+/// other known feature evaluates to false while a query using an unknown feature is invalid and never matches.
+///
+/// <https://drafts.csswg.org/mediaqueries/>
+#[derive(Debug, Clone, PartialEq)]
+pub enum MediaQuery {
+    /// A media type such as `screen` or `print`, of which only `all` and `screen` match.
+    Type(MediaType),
+
+    /// A dimension of the viewport compared against a length in pixels, like `(min-width: 600px)` or
+    /// `(width < 600px)`.
+    Size {
+        dimension: MediaCondition,
+        operator: RangeOperator,
+        px: f64,
+    },
+
+    /// The ratio of the width to the height of the viewport compared against a ratio, like
+    /// `(min-aspect-ratio: 16/9)`.
+    AspectRatio {
+        operator: RangeOperator,
+        ratio: f64,
+    },
+
+    /// Whether the viewport is taller than it is wide, like `(orientation: portrait)`.
+    Orientation(Orientation),
+
+    /// The number of bits per color component of the screen compared against a number, like `(color)` or
+    /// `(min-color: 8)`.
+    Color {
+        operator: RangeOperator,
+        bits: f64,
+    },
+
+    /// A preference of the user, like `(prefers-color-scheme: dark)`.
+    Preference(MediaFeature),
+
+    /// A feature of the device that is known but can't be queried, like `(monochrome)` or
+    /// `(min-resolution: 2dppx)`, which never matches.
+    Unsupported(MediaCondition),
+
+    Not(Box<Self>),
+    And(Vec<Self>),
+
+    /// Queries joined by `or`, or the queries of a comma-separated list.
+    Or(Vec<Self>),
+}
+
+impl MediaQuery {
+    /// The number of bits per color component of the screen, which is assumed to be a common 8-bit color screen.
+    const COLOR_BITS: f64 = 8.0;
+
+    /// Parses the prelude of a `@media` rule, a comma-separated list of queries that matches when any of them does.
+    ///
+    /// # Arguments
+    /// * `prelude` - The component values of the prelude.
+    /// * `absolute_ctx` - The context the lengths of the queries are resolved to pixels with.
+    ///
+    /// # Returns
+    /// The query of the rule. An empty prelude matches all media, and an invalid query of the list never matches
+    /// without affecting the others.
+    #[must_use]
+    pub fn parse(prelude: &[ComponentValue], absolute_ctx: &AbsoluteContext) -> Self {
+        let values = significant(prelude);
+        if values.is_empty() {
+            return Self::Type(MediaType::All);
+        }
+
+        let mut queries: Vec<Self> = values
+            .split(|cv| matches!(cv, ComponentValue::Token(token) if token.kind == CssTokenKind::Comma))
+            .map(|query| {
+                Self::parse_query(query, absolute_ctx)
+                    .unwrap_or_else(|| Self::Not(Box::new(Self::Type(MediaType::All))))
+            })
+            .collect();
+
+        if queries.len() == 1 {
+            queries.remove(0)
+        } else {
+            Self::Or(queries)
+        }
+    }
+
+    /// Whether the viewport and the preferences of the user match the query.
+    ///
+    /// # Arguments
+    /// * `absolute_ctx` - The context with the size of the viewport and the preferences of the user.
+    #[must_use]
+    pub fn matches(&self, absolute_ctx: &AbsoluteContext) -> bool {
+        match self {
+            Self::Type(media_type) => matches!(media_type, MediaType::All | MediaType::Screen),
+            Self::Size {
+                dimension,
+                operator,
+                px,
+            } => {
+                let value = match dimension {
+                    MediaCondition::Width | MediaCondition::DeviceWidth => absolute_ctx.viewport_width,
+                    MediaCondition::Height | MediaCondition::DeviceHeight => absolute_ctx.viewport_height,
+                    _ => return false,
+                };
+
+                compare(value, *operator, *px)
+            }
+            Self::AspectRatio { operator, ratio } => {
+                compare(absolute_ctx.viewport_width / absolute_ctx.viewport_height, *operator, *ratio)
+            }
+            Self::Orientation(orientation) => {
+                let is_portrait = absolute_ctx.viewport_height >= absolute_ctx.viewport_width;
+                is_portrait == (*orientation == Orientation::Portrait)
+            }
+            Self::Color { operator, bits } => compare(Self::COLOR_BITS, *operator, *bits),
+            Self::Unsupported(_) => false,
+            Self::Preference(feature) => matches_media_feature(feature, absolute_ctx),
+            Self::Not(query) => !query.matches(absolute_ctx),
+            Self::And(queries) => queries.iter().all(|query| query.matches(absolute_ctx)),
+            Self::Or(queries) => queries.iter().any(|query| query.matches(absolute_ctx)),
+        }
+    }
+
+    /// Parses a single query of a list, like `not screen and (color)` or `(min-width: 600px) or (hover: hover)`.
+    fn parse_query(values: &[&ComponentValue], absolute_ctx: &AbsoluteContext) -> Option<Self> {
+        let (negated, values) = match values.split_first() {
+            Some((first, rest)) if is_ident(first, "not") => (true, rest),
+            Some((first, rest)) if is_ident(first, "only") => (false, rest),
+            _ => (false, values),
+        };
+
+        let query = match values.split_first() {
+            Some((ComponentValue::Token(token), rest)) => {
+                let CssTokenKind::Ident(ident) = &token.kind else {
+                    return None;
+                };
+                let media_type = Self::Type(ident.parse::<MediaType>().ok()?);
+
+                match rest.split_first() {
+                    None => media_type,
+                    Some((and, condition)) if is_ident(and, "and") => Self::And(vec![
+                        media_type,
+                        Self::parse_condition(condition, false, absolute_ctx)?,
+                    ]),
+                    Some(_) => return None,
+                }
+            }
+            _ => Self::parse_condition(values, true, absolute_ctx)?,
+        };
+
+        Some(if negated {
+            Self::Not(Box::new(query))
+        } else {
+            query
+        })
+    }
+
+    /// Parses a condition, parenthesized features joined by either `and` or `or`, or a single one negated by `not`.
+    fn parse_condition(values: &[&ComponentValue], allows_or: bool, absolute_ctx: &AbsoluteContext) -> Option<Self> {
+        if let Some((first, rest)) = values.split_first()
+            && is_ident(first, "not")
+        {
+            let [operand] = rest else {
+                return None;
+            };
+            return Some(Self::Not(Box::new(Self::parse_in_parens(operand, absolute_ctx)?)));
+        }
+
+        if values.len() % 2 == 0 {
+            return None;
+        }
+
+        let mut operands = Vec::with_capacity(values.len() / 2 + 1);
+        let mut is_or = None;
+
+        for (index, cv) in values.iter().enumerate() {
+            if index % 2 == 0 {
+                operands.push(Self::parse_in_parens(cv, absolute_ctx)?);
+                continue;
+            }
+
+            let or = if is_ident(cv, "and") {
+                false
+            } else if allows_or && is_ident(cv, "or") {
+                true
+            } else {
+                return None;
+            };
+
+            if is_or.is_some_and(|is_or| is_or != or) {
+                return None;
+            }
+            is_or = Some(or);
+        }
+
+        Some(match is_or {
+            None => operands.remove(0),
+            Some(false) => Self::And(operands),
+            Some(true) => Self::Or(operands),
+        })
+    }
+
+    /// Parses a feature or a nested condition in parentheses.
+    fn parse_in_parens(cv: &ComponentValue, absolute_ctx: &AbsoluteContext) -> Option<Self> {
+        let ComponentValue::SimpleBlock(block) = cv else {
+            return None;
+        };
+        if block.associated_token != AssociatedToken::Parenthesis {
+            return None;
+        }
+
+        let values = significant(&block.value);
+        Self::parse_feature(&values, absolute_ctx).or_else(|| Self::parse_condition(&values, true, absolute_ctx))
+    }
+
+    /// Parses a feature such as `min-width: 600px`, `prefers-color-scheme: dark`, `width >= 600px`,
+    /// `400px <= width < 800px` or `(orientation)` from the contents of its parentheses.
+    fn parse_feature(values: &[&ComponentValue], absolute_ctx: &AbsoluteContext) -> Option<Self> {
+        if let Some(colon) = values
+            .iter()
+            .position(|cv| matches!(cv, ComponentValue::Token(token) if token.kind == CssTokenKind::Colon))
+        {
+            let [name] = &values[..colon] else {
+                return None;
+            };
+            return Self::parse_plain_feature(&ident(name)?.to_ascii_lowercase(), &values[colon + 1..], absolute_ctx);
+        }
+
+        if let [name] = values {
+            return Self::parse_boolean_feature(ident(name)?);
+        }
+
+        let position = values
+            .iter()
+            .position(|cv| ident(cv).is_some_and(|name| name.parse::<MediaCondition>().is_ok()))?;
+        let condition = ident(values[position])?.parse::<MediaCondition>().ok()?;
+        let (before, after) = (&values[..position], &values[position + 1..]);
+
+        if before.is_empty() {
+            let (operator, len) = parse_operator(after)?;
+            return Self::parse_range(condition, operator, &after[len..], absolute_ctx);
+        }
+
+        let (low_operator, len) = trailing_operator(before)?;
+        let low = Self::parse_range(condition, reversed(low_operator), &before[..before.len() - len], absolute_ctx)?;
+        if after.is_empty() {
+            return Some(low);
+        }
+
+        let (high_operator, len) = parse_operator(after)?;
+        let is_less = |operator| matches!(operator, RangeOperator::LessThan | RangeOperator::LessThanOrEqual);
+        if is_less(low_operator) != is_less(high_operator) || low_operator == RangeOperator::Equal {
+            return None;
+        }
+
+        Some(Self::And(vec![
+            low,
+            Self::parse_range(condition, high_operator, &after[len..], absolute_ctx)?,
+        ]))
+    }
+
+    /// Parses a feature written as `name: value`, where the name of a range feature may be prefixed with `min-` or
+    /// `max-`.
+    fn parse_plain_feature(name: &str, value: &[&ComponentValue], absolute_ctx: &AbsoluteContext) -> Option<Self> {
+        if name.parse::<MediaFeature>().is_ok() {
+            let [value] = value else {
+                return None;
+            };
+            return parse_preference(name, ident(value)?).map(Self::Preference);
+        }
+
+        let (operator, name) = if let Some(name) = name.strip_prefix("min-") {
+            (RangeOperator::GreaterThanOrEqual, name)
+        } else if let Some(name) = name.strip_prefix("max-") {
+            (RangeOperator::LessThanOrEqual, name)
+        } else {
+            (RangeOperator::Equal, name)
+        };
+
+        let condition = name.parse::<MediaCondition>().ok()?;
+        if condition.is_range_query() {
+            return Self::parse_range(condition, operator, value, absolute_ctx);
+        }
+        if operator != RangeOperator::Equal {
+            return None;
+        }
+
+        match (condition, value) {
+            (MediaCondition::Orientation, [value]) => Some(Self::Orientation(ident(value)?.parse().ok()?)),
+            (_, [_]) => Some(Self::Unsupported(condition)),
+            _ => None,
+        }
+    }
+
+    /// Parses a feature in a boolean context such as `(orientation)` or `(hover)`, which matches when the feature has
+    /// a value other than zero, `none` or `no-preference`.
+    fn parse_boolean_feature(name: &str) -> Option<Self> {
+        if let Ok(feature) = name.parse::<MediaFeature>() {
+            let none = match feature {
+                MediaFeature::ForcedColors(_) => MediaFeature::ForcedColors(ForcedColorsState::None),
+                MediaFeature::Hover(_) => MediaFeature::Hover(Hover::None),
+                MediaFeature::Pointer(_) => MediaFeature::Pointer(Pointer::None),
+                MediaFeature::PrefersColorScheme(_) => return Some(Self::Type(MediaType::All)),
+                MediaFeature::PrefersContrast(_) => MediaFeature::PrefersContrast(ContrastPreference::NoPreference),
+                MediaFeature::PrefersReducedData(_) => {
+                    MediaFeature::PrefersReducedData(ReducedDataPreference::NoPreference)
+                }
+                MediaFeature::PrefersReducedMotion(_) => {
+                    MediaFeature::PrefersReducedMotion(ReducedMotionPreference::NoPreference)
+                }
+            };
+            return Some(Self::Not(Box::new(Self::Preference(none))));
+        }
+
+        Some(match name.parse::<MediaCondition>().ok()? {
+            dimension @ (MediaCondition::Width
+            | MediaCondition::Height
+            | MediaCondition::DeviceWidth
+            | MediaCondition::DeviceHeight) => Self::Size {
+                dimension,
+                operator: RangeOperator::GreaterThan,
+                px: 0.0,
+            },
+            MediaCondition::AspectRatio | MediaCondition::DeviceAspectRatio | MediaCondition::Orientation => {
+                Self::Type(MediaType::All)
+            }
+            MediaCondition::Color => Self::Color {
+                operator: RangeOperator::GreaterThan,
+                bits: 0.0,
+            },
+            condition => Self::Unsupported(condition),
+        })
+    }
+
+    /// Parses the value a range feature such as `width` or `aspect-ratio` is compared against.
+    fn parse_range(
+        condition: MediaCondition,
+        operator: RangeOperator,
+        value: &[&ComponentValue],
+        absolute_ctx: &AbsoluteContext,
+    ) -> Option<Self> {
+        match condition {
+            MediaCondition::Width
+            | MediaCondition::Height
+            | MediaCondition::DeviceWidth
+            | MediaCondition::DeviceHeight => {
+                let [value] = value else {
+                    return None;
+                };
+                Some(Self::Size {
+                    dimension: condition,
+                    operator,
+                    px: length_px(value, absolute_ctx)?,
+                })
+            }
+            MediaCondition::AspectRatio | MediaCondition::DeviceAspectRatio => Some(Self::AspectRatio {
+                operator,
+                ratio: ratio(value)?,
+            }),
+            MediaCondition::Color => {
+                let [value] = value else {
+                    return None;
+                };
+                Some(Self::Color {
+                    operator,
+                    bits: integer(value)?,
+                })
+            }
+            _ if condition.is_range_query() && !value.is_empty() => Some(Self::Unsupported(condition)),
+            _ => None,
+        }
+    }
+}
+
+/// The component values that are not whitespace.
+fn significant(values: &[ComponentValue]) -> Vec<&ComponentValue> {
+    values.iter().filter(|cv| !cv.is_whitespace()).collect()
+}
+
+fn ident(cv: &ComponentValue) -> Option<&str> {
+    match cv {
+        ComponentValue::Token(token) => match &token.kind {
+            CssTokenKind::Ident(ident) => Some(ident),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_ident(cv: &ComponentValue, expected: &str) -> bool {
+    ident(cv).is_some_and(|ident| ident.eq_ignore_ascii_case(expected))
+}
+
+/// Resolves a length such as `600px` or `40em` to pixels, a unitless zero being a length too.
+fn length_px(cv: &ComponentValue, absolute_ctx: &AbsoluteContext) -> Option<f64> {
+    let ComponentValue::Token(token) = cv else {
+        return None;
+    };
+
+    match &token.kind {
+        CssTokenKind::Dimension { value, unit } => {
+            let unit = unit.parse::<LengthUnit>().ok()?;
+            Length::new(value.to_f64(), unit)
+                .to_px(None, None, absolute_ctx)
+                .ok()
+        }
+        CssTokenKind::Number(value) if value.to_f64() == 0.0 => Some(0.0),
+        _ => None,
+    }
+}
+
+/// Parses a comparison such as `<` or `>=`.
+///
+/// # Returns
+/// The operator and the number of component values it spans.
+fn parse_operator(values: &[&ComponentValue]) -> Option<(RangeOperator, usize)> {
+    let delim = |cv: Option<&&ComponentValue>| match cv {
+        Some(ComponentValue::Token(token)) => match token.kind {
+            CssTokenKind::Delim(delim) => Some(delim),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    let or_equal = delim(values.get(1)) == Some('=');
+    match (delim(values.first())?, or_equal) {
+        ('<', true) => Some((RangeOperator::LessThanOrEqual, 2)),
+        ('>', true) => Some((RangeOperator::GreaterThanOrEqual, 2)),
+        ('<', false) => Some((RangeOperator::LessThan, 1)),
+        ('>', false) => Some((RangeOperator::GreaterThan, 1)),
+        ('=', false) => Some((RangeOperator::Equal, 1)),
+        _ => None,
+    }
+}
+
+/// Parses a comparison such as `<` or `>=` at the end of the values, for a range such as `600px < width`.
+///
+/// # Returns
+/// The operator and the number of component values it spans.
+fn trailing_operator(values: &[&ComponentValue]) -> Option<(RangeOperator, usize)> {
+    (1..=2)
+        .rev()
+        .filter_map(|len| values.len().checked_sub(len))
+        .find_map(|start| parse_operator(&values[start..]).filter(|(_, len)| start + len == values.len()))
+}
+
+/// Parses a non-negative integer such as the number of bits of `(min-color: 8)`.
+fn integer(cv: &ComponentValue) -> Option<f64> {
+    match cv {
+        ComponentValue::Token(token) => match &token.kind {
+            CssTokenKind::Number(value) if value.to_f64() >= 0.0 && value.to_f64().fract() == 0.0 => {
+                Some(value.to_f64())
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Parses a ratio such as `16/9` or `2` to its value, where a ratio with a zero denominator is infinite.
+fn ratio(values: &[&ComponentValue]) -> Option<f64> {
+    let number = |cv: &ComponentValue| match cv {
+        ComponentValue::Token(token) => match &token.kind {
+            CssTokenKind::Number(value) if value.to_f64() >= 0.0 => Some(value.to_f64()),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    let (numerator, denominator) = match values {
+        [numerator] => (number(numerator)?, 1.0),
+        [numerator, ComponentValue::Token(slash), denominator] if slash.kind == CssTokenKind::Delim('/') => {
+            (number(numerator)?, number(denominator)?)
+        }
+        _ => return None,
+    };
+
+    let ratio = numerator / denominator;
+    (!ratio.is_nan()).then_some(ratio)
+}
+
+/// Compares the value of a feature against the value of a query.
+fn compare(value: f64, operator: RangeOperator, target: f64) -> bool {
+    match operator {
+        RangeOperator::LessThan => value < target,
+        RangeOperator::LessThanOrEqual => value <= target,
+        RangeOperator::GreaterThan => value > target,
+        RangeOperator::GreaterThanOrEqual => value >= target,
+        RangeOperator::Equal => (value - target).abs() < f64::EPSILON,
+    }
+}
+
+/// The operator comparing the operands the other way around, for a range such as `600px < width`.
+const fn reversed(operator: RangeOperator) -> RangeOperator {
+    match operator {
+        RangeOperator::LessThan => RangeOperator::GreaterThan,
+        RangeOperator::LessThanOrEqual => RangeOperator::GreaterThanOrEqual,
+        RangeOperator::GreaterThan => RangeOperator::LessThan,
+        RangeOperator::GreaterThanOrEqual => RangeOperator::LessThanOrEqual,
+        RangeOperator::Equal => RangeOperator::Equal,
+    }
+}
+
+/// Parses a preference of the user such as `prefers-color-scheme: dark`.
+fn parse_preference(name: &str, value: &str) -> Option<MediaFeature> {
+    Some(match name.parse::<MediaFeature>().ok()? {
+        MediaFeature::ForcedColors(_) => MediaFeature::ForcedColors(value.parse::<ForcedColorsState>().ok()?),
+        MediaFeature::Hover(_) => MediaFeature::Hover(value.parse::<Hover>().ok()?),
+        MediaFeature::Pointer(_) => MediaFeature::Pointer(value.parse::<Pointer>().ok()?),
+        MediaFeature::PrefersColorScheme(_) => MediaFeature::PrefersColorScheme(value.parse::<ColorScheme>().ok()?),
+        MediaFeature::PrefersContrast(_) => MediaFeature::PrefersContrast(value.parse::<ContrastPreference>().ok()?),
+        MediaFeature::PrefersReducedData(_) => {
+            MediaFeature::PrefersReducedData(value.parse::<ReducedDataPreference>().ok()?)
+        }
+        MediaFeature::PrefersReducedMotion(_) => {
+            MediaFeature::PrefersReducedMotion(value.parse::<ReducedMotionPreference>().ok()?)
+        }
+    })
+}
+
+fn matches_media_feature(media_feature: &MediaFeature, absolute_ctx: &AbsoluteContext) -> bool {
+    match media_feature {
+        MediaFeature::ForcedColors(state) => *state == absolute_ctx.system_preferences.forced_colors,
+        MediaFeature::Hover(hover) => *hover == absolute_ctx.system_preferences.input_device.hover(),
+        MediaFeature::Pointer(pointer) => *pointer == absolute_ctx.system_preferences.input_device.pointer(),
+        MediaFeature::PrefersColorScheme(color_scheme) => {
+            *color_scheme == absolute_ctx.system_preferences.prefers_color_scheme
+        }
+        MediaFeature::PrefersContrast(ContrastPreference::Custom) => {
+            absolute_ctx.system_preferences.forced_colors == ForcedColorsState::Active
+                && absolute_ctx.system_preferences.prefers_contrast == ContrastPreference::NoPreference
+        }
+        MediaFeature::PrefersContrast(preference) => *preference == absolute_ctx.system_preferences.prefers_contrast,
+        MediaFeature::PrefersReducedData(preference) => {
+            (*preference == ReducedDataPreference::Reduce) == absolute_ctx.system_preferences.prefers_reduced_data
+        }
+        MediaFeature::PrefersReducedMotion(preference) => {
+            (*preference == ReducedMotionPreference::Reduce) == absolute_ctx.system_preferences.prefers_reduced_motion
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use css_cssom::{CSSRule, CSSStyleSheet, CssParser};
+    use url::Url;
+
+    use super::*;
+    use crate::SystemPreferences;
+
+    fn matches(prelude: &str, viewport_width: f64, prefers_color_scheme: ColorScheme) -> bool {
+        let css = format!("@media {prelude} {{ p {{ color: red }} }}");
+        let stylesheet = CSSStyleSheet::from(CssParser::default().parse_css(&css, false));
+        let CSSRule::AtRule(at_rule) = &stylesheet.css_rules()[0] else {
+            panic!("expected an at-rule");
+        };
+
+        let url = Url::parse("http://localhost").unwrap();
+        let absolute_ctx = AbsoluteContext {
+            viewport_width,
+            viewport_height: 600.0,
+            system_preferences: SystemPreferences {
+                prefers_color_scheme,
+                ..Default::default()
+            },
+            ..AbsoluteContext::default_url(&url)
+        };
+
+        MediaQuery::parse(at_rule.prelude_values(), &absolute_ctx).matches(&absolute_ctx)
+    }
+
+    #[test]
+    fn test_media_types() {
+        assert!(matches("screen", 800.0, ColorScheme::Light));
+        assert!(matches("all", 800.0, ColorScheme::Light));
+        assert!(!matches("print", 800.0, ColorScheme::Light));
+        assert!(matches("not print", 800.0, ColorScheme::Light));
+        assert!(matches("print, screen", 800.0, ColorScheme::Light));
+        assert!(matches("only screen and (min-width: 600px)", 800.0, ColorScheme::Light));
+    }
+
+    #[test]
+    fn test_width_features() {
+        assert!(matches("(max-width: 600px)", 500.0, ColorScheme::Light));
+        assert!(!matches("(max-width: 600px)", 700.0, ColorScheme::Light));
+        assert!(matches("(width >= 600px)", 700.0, ColorScheme::Light));
+        assert!(matches("(400px <= width < 800px)", 700.0, ColorScheme::Light));
+        assert!(!matches("(400px <= width < 800px)", 800.0, ColorScheme::Light));
+        assert!(!matches("(400px < width > 800px)", 900.0, ColorScheme::Light));
+    }
+
+    #[test]
+    fn test_logical_combinations() {
+        assert!(matches("(max-width: 600px) or (prefers-color-scheme: dark)", 800.0, ColorScheme::Dark));
+        assert!(!matches("(max-width: 600px) and (prefers-color-scheme: dark)", 800.0, ColorScheme::Dark));
+        assert!(matches("not (prefers-color-scheme: dark)", 800.0, ColorScheme::Light));
+        assert!(matches("screen and ((max-width: 600px) or (min-width: 700px))", 800.0, ColorScheme::Light));
+        assert!(!matches("(max-width: 600px) and (min-width: 100px) or (hover: hover)", 500.0, ColorScheme::Light));
+        assert!(!matches("(unknown-feature: 1)", 800.0, ColorScheme::Light));
+        assert!(!matches("not (unknown-feature: 1)", 800.0, ColorScheme::Light));
+    }
+
+    #[test]
+    fn test_orientation_and_aspect_ratio() {
+        assert!(matches("(orientation: landscape)", 800.0, ColorScheme::Light));
+        assert!(matches("(orientation: portrait)", 500.0, ColorScheme::Light));
+        assert!(matches("(orientation: portrait)", 600.0, ColorScheme::Light));
+        assert!(!matches("(orientation: sideways)", 800.0, ColorScheme::Light));
+        assert!(matches("(orientation)", 800.0, ColorScheme::Light));
+
+        assert!(matches("(aspect-ratio: 4/3)", 800.0, ColorScheme::Light));
+        assert!(matches("(min-aspect-ratio: 16 / 9)", 1200.0, ColorScheme::Light));
+        assert!(!matches("(min-aspect-ratio: 16/9)", 800.0, ColorScheme::Light));
+        assert!(matches("(max-aspect-ratio: 1)", 500.0, ColorScheme::Light));
+        assert!(matches("(aspect-ratio > 1)", 800.0, ColorScheme::Light));
+        assert!(matches("(1/1 < aspect-ratio <= 16/9)", 800.0, ColorScheme::Light));
+        assert!(!matches("(aspect-ratio: 4/x)", 800.0, ColorScheme::Light));
+    }
+
+    #[test]
+    fn test_unsupported_features() {
+        assert!(!matches("(monochrome)", 800.0, ColorScheme::Light));
+        assert!(!matches("(min-resolution: 2dppx)", 800.0, ColorScheme::Light));
+        assert!(!matches("(resolution >= 2dppx)", 800.0, ColorScheme::Light));
+        assert!(matches("not all and (monochrome)", 800.0, ColorScheme::Light));
+        assert!(matches("not all and (min-resolution: 2dppx)", 800.0, ColorScheme::Light));
+        assert!(matches("screen, (min-resolution: 2dppx)", 800.0, ColorScheme::Light));
+    }
+
+    #[test]
+    fn test_color_feature() {
+        assert!(matches("(color)", 800.0, ColorScheme::Light));
+        assert!(matches("screen and (color)", 800.0, ColorScheme::Light));
+        assert!(!matches("not (color)", 800.0, ColorScheme::Light));
+        assert!(matches("(min-color: 8)", 800.0, ColorScheme::Light));
+        assert!(!matches("(min-color: 10)", 800.0, ColorScheme::Light));
+        assert!(matches("(color >= 1)", 800.0, ColorScheme::Light));
+        assert!(!matches("(color: 1.5)", 800.0, ColorScheme::Light));
+    }
+
+    #[test]
+    fn test_boolean_preferences() {
+        assert!(matches("(hover)", 800.0, ColorScheme::Light));
+        assert!(matches("(pointer)", 800.0, ColorScheme::Light));
+        assert!(!matches("(prefers-reduced-motion)", 800.0, ColorScheme::Light));
+        assert!(matches("not (prefers-reduced-motion)", 800.0, ColorScheme::Light));
+        assert!(!matches("(prefers-contrast)", 800.0, ColorScheme::Light));
+        assert!(!matches("(forced-colors)", 800.0, ColorScheme::Light));
+        assert!(matches("(prefers-color-scheme)", 800.0, ColorScheme::Dark));
+    }
+}
@@ -0,0 +1,184 @@
+//! This module resolves the styles of the `::part()` pseudo-elements of the elements the browser draws by itself, such
+//! as the track and the indicator of a `<progress>` bar or the thumb of a range slider. Parts generate no boxes of their own, their styles are only
+//! read when the element is painted.
+
+use std::collections::{HashMap, HashSet};
+
+use css_selectors::PseudoElement;
+use html_dom::{DomNode, HtmlTag, NodeId, RangeElement, Tag};
+
+use crate::{ComputedStyle, rules::CascadeContext, tree::PropertyRegistry};
+
+/// The styles of the parts of an element, a part is `None` when no rule styles it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PartStyles {
+    /// The style of `::part(indicator)`, the filled portion of the bar.
+    pub indicator: Option<ComputedStyle>,
+
+    /// The style of `::part(track)`, the groove the indicator fills.
+    pub track: Option<ComputedStyle>,
+
+    /// The style of `::part(thumb)`, the handle of a range slider.
+    pub thumb: Option<ComputedStyle>,
+}
+
+/// Computes the styles of the parts of the `<progress>`, `<meter>` and `<input type="range">` elements among the given
+/// nodes.
+///
+/// # Returns
+/// The styles keyed by the element, elements none of whose parts are styled have no entry.
+pub(crate) fn style_parts<'a>(
+    ctx: &CascadeContext,
+    nodes: impl IntoIterator<Item = &'a DomNode>,
+    property_registry: &mut PropertyRegistry,
+    styles: &[ComputedStyle],
+) -> HashMap<NodeId, PartStyles> {
+    let styled: HashSet<PseudoElement> = ctx
+        .rules
+        .generated
+        .iter()
+        .filter_map(|rule| rule.pseudo_element)
+        .filter(|pseudo_element| {
+            matches!(pseudo_element, PseudoElement::Track | PseudoElement::Indicator | PseudoElement::Thumb)
+        })
+        .collect();
+
+    let mut parts = HashMap::new();
+    if styled.is_empty() {
+        return parts;
+    }
+
+    for node in nodes {
+        let has_parts = node.data.as_element().is_some_and(|element| {
+            matches!(element.tag, Tag::Html(HtmlTag::Progress | HtmlTag::Meter))
+                || RangeElement::from_element(element).is_some()
+        });
+        if !has_parts || styles[*node.id].display.is_none() {
+            continue;
+        }
+
+        let mut style = |pseudo_element: PseudoElement| {
+            styled
+                .contains(&pseudo_element)
+                .then(|| ComputedStyle::from_node(ctx, node.id, Some(pseudo_element), property_registry, styles))
+        };
+
+        parts.insert(
+            node.id,
+            PartStyles {
+                indicator: style(PseudoElement::Indicator),
+                track: style(PseudoElement::Track),
+                thumb: style(PseudoElement::Thumb),
+            },
+        );
+    }
+
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use css_cssom::{CSSStyleSheet, CssParser};
+    use html_dom::{DocumentRoot, Element, NodeData};
+    use url::Url;
+
+    use super::*;
+    use crate::{AbsoluteContext, Color4f, StyleInvalidationSet, StyleTree};
+
+    #[test]
+    fn test_part_styles_of_progress() {
+        let mut dom = DocumentRoot::new();
+        let body = dom.push_node(
+            &NodeData::Element(Element::new(Tag::Html(HtmlTag::Body), HashSet::new(), HashMap::new())),
+            None,
+        );
+        let progress = dom.push_node(
+            &NodeData::Element(Element::new(Tag::Html(HtmlTag::Progress), HashSet::new(), HashMap::new())),
+            Some(body),
+        );
+
+        let css = "progress::part(indicator) { background-color: blue }";
+        let stylesheets = [CSSStyleSheet::from(
+            CssParser::default().parse_css(css, false),
+        )];
+        let url = Url::parse("http://localhost").unwrap();
+        let tree = StyleTree::build(None, &AbsoluteContext::default_url(&url), &dom, &stylesheets);
+
+        let parts = tree.parts(progress).unwrap();
+        assert_eq!(
+            parts.indicator.as_ref().map(|style| style.background_color),
+            Some(Color4f::rgba(0.0, 0.0, 1.0, 1.0))
+        );
+        assert!(parts.track.is_none());
+        assert!(tree.parts(body).is_none());
+    }
+
+    #[test]
+    fn test_part_styles_of_range_input() {
+        let mut dom = DocumentRoot::new();
+        let body = dom.push_node(
+            &NodeData::Element(Element::new(Tag::Html(HtmlTag::Body), HashSet::new(), HashMap::new())),
+            None,
+        );
+        let input = |input_type: &str| {
+            NodeData::Element(Element::new(
+                Tag::Html(HtmlTag::Input),
+                HashSet::new(),
+                HashMap::from([("type".to_string(), input_type.to_string())]),
+            ))
+        };
+        let range = dom.push_node(&input("range"), Some(body));
+        let text = dom.push_node(&input("text"), Some(body));
+
+        let css = "input::part(thumb) { background-color: red }";
+        let stylesheets = [CSSStyleSheet::from(
+            CssParser::default().parse_css(css, false),
+        )];
+        let url = Url::parse("http://localhost").unwrap();
+        let tree = StyleTree::build(None, &AbsoluteContext::default_url(&url), &dom, &stylesheets);
+
+        let parts = tree.parts(range).unwrap();
+        assert_eq!(parts.thumb.as_ref().map(|style| style.background_color), Some(Color4f::rgba(1.0, 0.0, 0.0, 1.0)));
+        assert!(tree.parts(text).is_none());
+    }
+
+    #[test]
+    fn test_part_styles_of_recomputed_nodes() {
+        let mut dom = DocumentRoot::new();
+        let body = dom.push_node(
+            &NodeData::Element(Element::new(Tag::Html(HtmlTag::Body), HashSet::new(), HashMap::new())),
+            None,
+        );
+        let progress = || NodeData::Element(Element::new(Tag::Html(HtmlTag::Progress), HashSet::new(), HashMap::new()));
+        let first = dom.push_node(&progress(), Some(body));
+        let second = dom.push_node(&progress(), Some(body));
+
+        let css = "progress::part(indicator) { background-color: blue } \
+                   progress[value]::part(indicator) { background-color: red }";
+        let stylesheets = [CSSStyleSheet::from(
+            CssParser::default().parse_css(css, false),
+        )];
+        let url = Url::parse("http://localhost").unwrap();
+        let absolute_ctx = AbsoluteContext::default_url(&url);
+        let mut tree = StyleTree::build(None, &absolute_ctx, &dom, &stylesheets);
+
+        dom.nodes[*first]
+            .data
+            .as_element_mut()
+            .unwrap()
+            .set_attribute("value", "0.5");
+        let invalidated =
+            tree.recompute(None, &absolute_ctx, &dom, &stylesheets, &StyleInvalidationSet::from_iter([first]));
+
+        let indicator = |node_id: NodeId| {
+            tree.parts(node_id)
+                .and_then(|parts| parts.indicator.as_ref())
+                .map(|style| style.background_color)
+        };
+        assert!(invalidated.needs_layout(first));
+        assert_eq!(indicator(first), Some(Color4f::rgba(1.0, 0.0, 0.0, 1.0)));
+        assert_eq!(indicator(second), Some(Color4f::rgba(0.0, 0.0, 1.0, 1.0)));
+    }
+}
@@ -1,27 +1,40 @@
 use std::fmt::Debug;
 
-use browser_preferences::theme::ThemeCategory;
 use css_cssom::ComponentValueStream;
 use css_values::{
     AlignContent, AlignItems, AlignSelf, CSSParsable, FlexBasis, FlexDirection, FlexWrap, Gap, JustifyContent,
-    JustifyItems, JustifySelf, OverflowAnchor, OverflowBlock, OverflowWrap,
+    JustifyItems, JustifySelf, OverflowAnchor, OverflowBlock, OverflowWrap, Resize,
+    background::{BlendMode, Isolation, MaskMode},
     border::{BorderStyle, BorderWidth},
-    color::Color,
+    color::{Color, ForcedColorAdjust},
+    combination::LengthPercentage,
+    container::{ContainerName, ContainerType},
+    content::{Content, CounterIncrement, CounterReset, QuoteList},
     cursor::Cursor,
-    dimension::{MarginValue, MaxSize, OffsetValue, Size},
-    display::{Clear, Float},
+    dimension::{ContainIntrinsicLength, MarginValue, MaxSize, OffsetValue, Size},
+    display::{Clear, ContentVisibility, Float, WillChange},
     error::CssValueError,
+    filter::Filter,
     global::Global,
+    image::{ImageRendering, ObjectFit},
+    media::{ColorScheme, ContrastPreference, ForcedColorsState, Hover, Pointer},
     numeric::{Flex, Order},
-    text::{FontSize, FontWeight, LineHeight, TextAlign, Whitespace, WritingMode},
+    shadow::BoxShadowList,
+    svg::PaintOrder,
+    text::{
+        FontFeatureSettings, FontKerning, FontSize, FontSizeAdjust, FontVariantCaps, FontVariantLigatures,
+        FontVariantNumeric, FontWeight, HyphenateCharacter, Hyphens, LineHeight, Spacing, TabSize, TextAlign,
+        TextIndent, Whitespace, WordBreak, WritingMode,
+    },
 };
 use url::Url;
 
 use crate::{
     ComputedStyle, Display, FontFamily, Position,
+    errors::ResolveError,
     properties::background::{
         BackgroundAttachment, BackgroundBlendMode, BackgroundClip, BackgroundImage, BackgroundOrigin,
-        BackgroundPositionX, BackgroundPositionY, BackgroundRepeat, BackgroundSize,
+        BackgroundPositionX, BackgroundPositionY, BackgroundRepeat, BackgroundSize, MaskOrigin,
     },
 };
 
@@ -50,7 +63,7 @@ pub trait PixelRepr: Sized {
         rel_type: Option<RelativeType>,
         style_ctx: Option<&StyleContext>,
         absolute_ctx: &AbsoluteContext,
-    ) -> Result<f64, String>;
+    ) -> Result<f64, ResolveError>;
 }
 
 /// Global CSS values that can be applied to any property, affecting how the property is resolved in relation to its initial value, inheritance, and user styles.
@@ -66,12 +79,80 @@ pub enum RelativeType {
     // TODO: flex container's inner main size
 }
 
+/// Preferences of the operating system that pages can query with media features.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SystemPreferences {
+    /// The number of device pixels per CSS pixel of the display, used to pick among the images of an `image-set()`.
+    pub device_pixel_ratio: f64,
+
+    /// The color scheme of the system, matched by `prefers-color-scheme`.
+    pub prefers_color_scheme: ColorScheme,
+
+    /// Whether the system asks to minimize motion, matched by `prefers-reduced-motion`.
+    pub prefers_reduced_motion: bool,
+
+    /// Whether the user asks to minimize the data transferred, matched by `prefers-reduced-data`.
+    pub prefers_reduced_data: bool,
+
+    /// The contrast asked for by the system, matched by `prefers-contrast`.
+    pub prefers_contrast: ContrastPreference,
+
+    /// Whether the system forces its own palette onto pages, matched by `forced-colors`. While active, the colors of
+    /// elements are replaced by system colors unless they opt out with `forced-color-adjust`.
+    pub forced_colors: ForcedColorsState,
+
+    /// The device the user last interacted with, matched by `pointer` and `hover`.
+    pub input_device: InputDevice,
+}
+
+impl Default for SystemPreferences {
+    fn default() -> Self {
+        Self {
+            device_pixel_ratio: 1.0,
+            prefers_color_scheme: ColorScheme::default(),
+            prefers_reduced_motion: false,
+            prefers_reduced_data: false,
+            prefers_contrast: ContrastPreference::default(),
+            forced_colors: ForcedColorsState::default(),
+            input_device: InputDevice::default(),
+        }
+    }
+}
+
+/// The kind of device the user points at the page with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InputDevice {
+    #[default]
+    Mouse,
+    Touch,
+}
+
+impl InputDevice {
+    /// The accuracy of the device, a mouse is a fine pointer while a finger is a coarse one.
+    #[must_use]
+    pub const fn pointer(self) -> Pointer {
+        match self {
+            Self::Mouse => Pointer::Fine,
+            Self::Touch => Pointer::Coarse,
+        }
+    }
+
+    /// Whether the device can hover over elements without activating them, which a touchscreen cannot.
+    #[must_use]
+    pub const fn hover(self) -> Hover {
+        match self {
+            Self::Mouse => Hover::Hover,
+            Self::Touch => Hover::None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct DocumentContext {
     pub root_font_size: f64,
     pub root_line_height_multiplier: f64,
     pub root_color: Color,
-    pub theme_category: ThemeCategory,
+    pub system_preferences: SystemPreferences,
 }
 
 /// Context for resolving absolute CSS properties.
@@ -82,7 +163,7 @@ pub struct AbsoluteContext<'page> {
     pub viewport_width: f64,
     pub viewport_height: f64,
     pub root_color: Color,
-    pub theme_category: ThemeCategory,
+    pub system_preferences: SystemPreferences,
     pub document_url: &'page Url,
 }
 
@@ -98,7 +179,7 @@ impl<'page> AbsoluteContext<'page> {
             root_font_size: document_ctx.root_font_size,
             root_line_height_multiplier: document_ctx.root_line_height_multiplier,
             root_color: document_ctx.root_color,
-            theme_category: document_ctx.theme_category,
+            system_preferences: document_ctx.system_preferences,
             viewport_width,
             viewport_height,
             document_url,
@@ -111,7 +192,15 @@ impl<'page> AbsoluteContext<'page> {
             root_font_size: 16.0,
             root_line_height_multiplier: 1.2,
             root_color: Color::BLACK,
-            theme_category: ThemeCategory::Light,
+            system_preferences: SystemPreferences {
+                device_pixel_ratio: 1.0,
+                prefers_color_scheme: ColorScheme::Light,
+                prefers_reduced_motion: false,
+                prefers_reduced_data: false,
+                prefers_contrast: ContrastPreference::NoPreference,
+                forced_colors: ForcedColorsState::None,
+                input_device: InputDevice::Mouse,
+            },
             viewport_width: 800.0,
             viewport_height: 600.0,
             document_url,
@@ -153,10 +242,10 @@ impl<T: CSSParsable> CSSProperty<T> {
     }
 
     /// Resolves the property to its specific value if it is set, or returns an error if it is a global value.
-    pub(crate) fn resolve(property: &Self) -> Result<&T, String> {
+    pub(crate) fn resolve(property: &Self) -> Result<&T, ResolveError> {
         match property {
             Self::Value(val) => Ok(val),
-            Self::Global(global) => Err(format!("Cannot resolve global property: {global:?}")),
+            Self::Global(global) => Err(ResolveError::GlobalProperty(*global)),
         }
     }
 
@@ -227,25 +316,39 @@ pub type BackgroundRepeatProperty = CSSProperty<BackgroundRepeat>;
 pub type BackgroundPositionXProperty = CSSProperty<BackgroundPositionX>;
 pub type BackgroundPositionYProperty = CSSProperty<BackgroundPositionY>;
 pub type BackgroundSizeProperty = CSSProperty<BackgroundSize>;
+pub type BlendModeProperty = CSSProperty<BlendMode>;
+pub type IsolationProperty = CSSProperty<Isolation>;
+
+// Masking
+pub type MaskModeProperty = CSSProperty<MaskMode>;
+pub type MaskOriginProperty = CSSProperty<MaskOrigin>;
 
 // Border
 pub type BorderWidthValueProperty = CSSProperty<BorderWidth>;
 pub type BorderStyleValueProperty = CSSProperty<BorderStyle>;
+pub type BorderRadiusProperty = CSSProperty<LengthPercentage>;
+pub type BoxShadowProperty = CSSProperty<BoxShadowList>;
 
 // Color
 pub type ColorProperty = CSSProperty<Color>;
+pub type ForcedColorAdjustProperty = CSSProperty<ForcedColorAdjust>;
 
 // Dimensions
 pub type SizeProperty = CSSProperty<Size>;
 pub type MaxSizeProperty = CSSProperty<MaxSize>;
+pub type ContainIntrinsicLengthProperty = CSSProperty<ContainIntrinsicLength>;
 
 // Display
 pub type ClearProperty = CSSProperty<Clear>;
+pub type ContentVisibilityProperty = CSSProperty<ContentVisibility>;
+pub type WillChangeProperty = CSSProperty<WillChange>;
 pub type DisplayProperty = CSSProperty<Display>;
+pub type FilterProperty = CSSProperty<Filter>;
 pub type FloatProperty = CSSProperty<Float>;
 pub type OverflowAnchorProperty = CSSProperty<OverflowAnchor>;
 pub type OverflowProperty = CSSProperty<OverflowBlock>;
 pub type OverflowWrapProperty = CSSProperty<OverflowWrap>;
+pub type ResizeProperty = CSSProperty<Resize>;
 
 // Flex & Grid
 pub type AlignContentProperty = CSSProperty<AlignContent>;
@@ -263,8 +366,18 @@ pub type OrderProperty = CSSProperty<Order>;
 
 // Font
 pub type FontWeightProperty = CSSProperty<FontWeight>;
+pub type FontKerningProperty = CSSProperty<FontKerning>;
 pub type FontFamilyProperty = CSSProperty<FontFamily>;
 pub type FontSizeProperty = CSSProperty<FontSize>;
+pub type FontSizeAdjustProperty = CSSProperty<FontSizeAdjust>;
+pub type FontVariantLigaturesProperty = CSSProperty<FontVariantLigatures>;
+pub type FontVariantNumericProperty = CSSProperty<FontVariantNumeric>;
+pub type FontVariantCapsProperty = CSSProperty<FontVariantCaps>;
+pub type FontFeatureSettingsProperty = CSSProperty<FontFeatureSettings>;
+
+// Image
+pub type ImageRenderingProperty = CSSProperty<ImageRendering>;
+pub type ObjectFitProperty = CSSProperty<ObjectFit>;
 
 // Margin & Padding
 pub type MarginProperty = CSSProperty<MarginValue>;
@@ -274,10 +387,29 @@ pub type OffsetProperty = CSSProperty<OffsetValue>;
 pub type PositionProperty = CSSProperty<Position>;
 
 // Text
+pub type HyphenateCharacterProperty = CSSProperty<HyphenateCharacter>;
+pub type HyphensProperty = CSSProperty<Hyphens>;
 pub type LineHeightProperty = CSSProperty<LineHeight>;
+pub type SpacingProperty = CSSProperty<Spacing>;
+pub type TabSizeProperty = CSSProperty<TabSize>;
 pub type TextAlignProperty = CSSProperty<TextAlign>;
+pub type TextIndentProperty = CSSProperty<TextIndent>;
 pub type WritingModeProperty = CSSProperty<WritingMode>;
 pub type WhitespaceProperty = CSSProperty<Whitespace>;
+pub type WordBreakProperty = CSSProperty<WordBreak>;
+
+// Container
+pub type ContainerNameProperty = CSSProperty<ContainerName>;
+pub type ContainerTypeProperty = CSSProperty<ContainerType>;
+
+// Generated content
+pub type ContentProperty = CSSProperty<Content>;
+pub type CounterIncrementProperty = CSSProperty<CounterIncrement>;
+pub type CounterResetProperty = CSSProperty<CounterReset>;
+pub type QuotesProperty = CSSProperty<QuoteList>;
+
+// SVG
+pub type PaintOrderProperty = CSSProperty<PaintOrder>;
 
 // Misc
 pub type CursorProperty = CSSProperty<Cursor>;
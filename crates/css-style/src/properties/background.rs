@@ -294,6 +294,23 @@ impl CSSParsable for BackgroundOrigin {
     }
 }
 
+/// The `mask-origin` property, which takes the same values as `background-origin` but positions mask images relative
+/// to the border box by default.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaskOrigin(pub Vec<VisualBox>);
+
+impl Default for MaskOrigin {
+    fn default() -> Self {
+        Self(vec![VisualBox::Border])
+    }
+}
+
+impl CSSParsable for MaskOrigin {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        BackgroundOrigin::parse(stream).map(|origin| Self(origin.0))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BackgroundPosition(pub Vec<BgPosition>);
 
@@ -5,6 +5,7 @@ use css_values::{border::BorderWidth, calc::CalcKind};
 
 use crate::{
     RelativeType,
+    errors::ResolveError,
     properties::{AbsoluteContext, PixelRepr, StyleContext},
 };
 
@@ -14,7 +15,7 @@ impl PixelRepr for BorderWidth {
         rel_type: Option<RelativeType>,
         style_ctx: Option<&StyleContext>,
         abs_ctx: &AbsoluteContext,
-    ) -> Result<f64, String> {
+    ) -> Result<f64, ResolveError> {
         Ok(match self {
             Self::Length(len) => len.to_px(rel_type, style_ctx, abs_ctx)?,
             Self::Calc(expr) => {
@@ -12,39 +12,41 @@ use css_values::{
 
 use crate::{
     RelativeType,
+    errors::ResolveError,
     properties::{AbsoluteContext, PixelRepr, StyleContext},
 };
 
+/// The font size of the `medium` keyword in pixels, the middle of the absolute size scale.
+const MEDIUM_FONT_SIZE: f64 = 16.0;
+
+/// The ratio between adjacent sizes of the absolute size scale.
+const FONT_SIZE_RATIO: f64 = 1.2;
+
 impl PixelRepr for AbsoluteSize {
     fn to_px(
         self,
         _rel_type: Option<RelativeType>,
         _style_ctx: Option<&StyleContext>,
         _abs_ctx: &AbsoluteContext,
-    ) -> Result<f64, String> {
-        Ok(match self {
-            Self::XxSmall => 9.0,
-            Self::XSmall => 10.0,
-            Self::Small => 13.0,
-            Self::Medium => 16.0,
-            Self::Large => 18.0,
-            Self::XLarge => 24.0,
-            Self::XxLarge => 32.0,
-            Self::XxxLarge => 48.0,
-        })
+    ) -> Result<f64, ResolveError> {
+        Ok(MEDIUM_FONT_SIZE * FONT_SIZE_RATIO.powi(self.step()))
     }
 }
 
+/// Steps one size along the absolute size scale from the font size of the parent. Since the scale is geometric, this also
+/// works for parent sizes between two keywords or outside of the scale.
 impl PixelRepr for RelativeSize {
     fn to_px(
         self,
         _rel_type: Option<RelativeType>,
         style_ctx: Option<&StyleContext>,
         abs_ctx: &AbsoluteContext,
-    ) -> Result<f64, String> {
+    ) -> Result<f64, ResolveError> {
+        let parent_font_size = style_ctx.map_or(abs_ctx.root_font_size, |ctx| ctx.parent_style.font_size);
+
         Ok(match self {
-            Self::Smaller => style_ctx.map_or(abs_ctx.root_font_size * 0.833, |ctx| ctx.parent_style.font_size * 0.833),
-            Self::Larger => style_ctx.map_or(abs_ctx.root_font_size * 1.2, |ctx| ctx.parent_style.font_size * 1.2),
+            Self::Smaller => parent_font_size / FONT_SIZE_RATIO,
+            Self::Larger => parent_font_size * FONT_SIZE_RATIO,
         })
     }
 }
@@ -141,7 +143,7 @@ impl PixelRepr for FontSize {
         rel_type: Option<RelativeType>,
         style_ctx: Option<&StyleContext>,
         abs_ctx: &AbsoluteContext,
-    ) -> Result<f64, String> {
+    ) -> Result<f64, ResolveError> {
         Ok(match self {
             Self::Absolute(abs) => abs.to_px(rel_type, style_ctx, abs_ctx)?,
             Self::Length(len) => len.to_px(rel_type, style_ctx, abs_ctx)?,
@@ -166,8 +168,14 @@ impl PixelRepr for FontSize {
 
 #[cfg(test)]
 mod tests {
-    use css_cssom::{CssToken, NumericValue};
+    use std::collections::{HashMap, HashSet};
+
+    use css_cssom::{CSSStyleSheet, CssParser, CssToken, NumericValue};
     use css_values::{CSSParsable, quantity::Length, text::FontWeight};
+    use html_dom::{DocumentRoot, Element, HtmlTag, NodeData, Tag};
+    use url::Url;
+
+    use crate::StyleTree;
 
     use super::*;
 
@@ -246,4 +254,49 @@ mod tests {
         let font_size = FontSize::parse(&mut input.as_slice().into()).unwrap();
         assert_eq!(font_size, FontSize::Length(Length::px(16.0)));
     }
+
+    #[test]
+    fn test_font_size_keywords() {
+        let div = || NodeData::Element(Element::new(Tag::Html(HtmlTag::Div), HashSet::new(), HashMap::new()));
+
+        let mut dom = DocumentRoot::new();
+        let large = dom.push_node(&div(), None);
+        let smaller = dom.push_node(&div(), Some(large));
+        let larger = dom.push_node(&div(), Some(smaller));
+        let xx_small = dom.push_node(&div(), Some(larger));
+
+        let css = "div { font-size: large } div div { font-size: smaller } div div div { font-size: larger } \
+                   div div div div { font-size: xx-small }";
+        let stylesheets = [CSSStyleSheet::from(
+            CssParser::default().parse_css(css, false),
+        )];
+        let url = Url::parse("http://localhost").unwrap();
+        let tree = StyleTree::build(None, &AbsoluteContext::default_url(&url), &dom, &stylesheets);
+
+        assert!((tree.get(large).unwrap().font_size - 19.2).abs() < 1e-9);
+        assert!((tree.get(smaller).unwrap().font_size - 16.0).abs() < 1e-9);
+        assert!((tree.get(larger).unwrap().font_size - 19.2).abs() < 1e-9);
+        assert!((tree.get(xx_small).unwrap().font_size - 16.0 / 1.728).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_relative_font_weight_uses_parent_weight() {
+        let div = || NodeData::Element(Element::new(Tag::Html(HtmlTag::Div), HashSet::new(), HashMap::new()));
+
+        let mut dom = DocumentRoot::new();
+        let parent = dom.push_node(&div(), None);
+        let bolder = dom.push_node(&div(), Some(parent));
+        let lighter = dom.push_node(&div(), Some(bolder));
+
+        let css = "div { font-weight: 400 } div div { font-weight: bolder } div div div { font-weight: lighter }";
+        let stylesheets = [CSSStyleSheet::from(
+            CssParser::default().parse_css(css, false),
+        )];
+        let url = Url::parse("http://localhost").unwrap();
+        let tree = StyleTree::build(None, &AbsoluteContext::default_url(&url), &dom, &stylesheets);
+
+        assert!((tree.get(parent).unwrap().font_weight - 400.0).abs() < f32::EPSILON);
+        assert!((tree.get(bolder).unwrap().font_weight - 700.0).abs() < f32::EPSILON);
+        assert!((tree.get(lighter).unwrap().font_weight - 400.0).abs() < f32::EPSILON);
+    }
 }
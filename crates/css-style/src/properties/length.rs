@@ -4,6 +4,7 @@ use css_values::quantity::{Length, LengthUnit};
 
 use crate::{
     RelativeType,
+    errors::ResolveError,
     properties::{AbsoluteContext, PixelRepr, StyleContext},
 };
 
@@ -13,7 +14,7 @@ impl PixelRepr for Length {
         rel_type: Option<RelativeType>,
         style_ctx: Option<&StyleContext>,
         abs_ctx: &AbsoluteContext,
-    ) -> Result<f64, String> {
+    ) -> Result<f64, ResolveError> {
         Ok(match self.unit() {
             LengthUnit::Px => self.value(),
             LengthUnit::Cm => self.value() * 96.0 / 2.54,
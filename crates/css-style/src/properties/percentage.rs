@@ -1,6 +1,6 @@
 use css_values::numeric::Percentage;
 
-use crate::{AbsoluteContext, ComputedSize, RelativeType, StyleContext, properties::PixelRepr};
+use crate::{AbsoluteContext, ComputedSize, RelativeType, StyleContext, errors::ResolveError, properties::PixelRepr};
 
 impl PixelRepr for Percentage {
     fn to_px(
@@ -8,29 +8,31 @@ impl PixelRepr for Percentage {
         rel_type: Option<RelativeType>,
         style_ctx: Option<&StyleContext>,
         abs_ctx: &AbsoluteContext,
-    ) -> Result<f64, String> {
+    ) -> Result<f64, ResolveError> {
         Ok(match rel_type {
             Some(val) => match val {
                 RelativeType::FontSize => style_ctx
                     .map_or(abs_ctx.root_font_size * self.as_fraction(), |ctx| ctx.font_size * self.as_fraction()),
                 RelativeType::ParentHeight => {
                     let Some(ctx) = style_ctx else {
-                        return Err("Percentage with ParentHeight relative type requires a RelativeContext".to_string());
+                        return Err(ResolveError::MissingStyleContext { relative_type: val });
                     };
 
                     match ctx.parent_style.height {
                         ComputedSize::Px(px) => px * self.as_fraction(),
-                        _ => Err("Parent height is not a fixed pixel value, cannot resolve percentage".to_string())?,
+                        _ => Err(ResolveError::UnresolvedParentSize {
+                            dimension: "height",
+                        })?,
                     }
                 }
                 RelativeType::ParentWidth => {
                     let Some(ctx) = style_ctx else {
-                        return Err("Percentage with ParentWidth relative type requires a RelativeContext".to_string());
+                        return Err(ResolveError::MissingStyleContext { relative_type: val });
                     };
 
                     match ctx.parent_style.width {
                         ComputedSize::Px(px) => px * self.as_fraction(),
-                        _ => Err("Parent width is not a fixed pixel value, cannot resolve percentage".to_string())?,
+                        _ => Err(ResolveError::UnresolvedParentSize { dimension: "width" })?,
                     }
                 }
                 RelativeType::RootFontSize => abs_ctx.root_font_size * self.as_fraction(),
@@ -38,23 +40,19 @@ impl PixelRepr for Percentage {
                 RelativeType::ViewportWidth => abs_ctx.viewport_width * self.as_fraction(),
                 RelativeType::BackgroundArea => {
                     let Some(ctx) = style_ctx else {
-                        return Err(
-                            "Percentage with BackgroundArea relative type requires a RelativeContext".to_string()
-                        );
+                        return Err(ResolveError::MissingStyleContext { relative_type: val });
                     };
 
                     let width = match ctx.parent_style.width {
                         ComputedSize::Px(px) => px,
-                        _ => Err("Parent width is not a fixed pixel value, cannot resolve background area percentage"
-                            .to_string())?,
+                        _ => Err(ResolveError::UnresolvedParentSize { dimension: "width" })?,
                     };
 
                     let height = match ctx.parent_style.height {
                         ComputedSize::Px(px) => px,
-                        _ => {
-                            Err("Parent height is not a fixed pixel value, cannot resolve background area percentage"
-                                .to_string())?
-                        }
+                        _ => Err(ResolveError::UnresolvedParentSize {
+                            dimension: "height",
+                        })?,
                     };
 
                     let bg_area = width * height;
@@ -0,0 +1,124 @@
+//! This module resolves the styles of the pseudo-elements that style a portion of their element instead of generating
+//! a box of their own: `::first-line` and `::first-letter` of block containers, and `::placeholder` of text inputs.
+//! Their styles inherit from their element and are read when its text is laid out.
+//!
+//! `::selection` is parsed but not cascaded, it applies to every element, and the selection is painted in the accent
+//! color of the browser instead.
+
+use std::collections::{HashMap, HashSet};
+
+use css_selectors::{PseudoElement, PseudoElementKind};
+use html_dom::{Element, HtmlTag, NodeId, Tag};
+
+use crate::{ComputedStyle, rules::CascadeContext, tree::PropertyRegistry};
+
+/// Whether a pseudo-element applies to an element with a style.
+fn applies_to(pseudo_element: PseudoElement, element: &Element, style: &ComputedStyle) -> bool {
+    match pseudo_element {
+        PseudoElement::FirstLine | PseudoElement::FirstLetter => style.display.is_block(),
+        PseudoElement::Placeholder => matches!(element.tag, Tag::Html(HtmlTag::Input | HtmlTag::Textarea)),
+        _ => false,
+    }
+}
+
+/// Computes the styles of the `::first-line`, `::first-letter` and `::placeholder` pseudo-elements of every rendered
+/// element they apply to.
+///
+/// # Returns
+/// The styles keyed by the element and pseudo-element, pseudo-elements no rule styles have no entry.
+pub(crate) fn style_pseudo_elements(
+    ctx: &CascadeContext,
+    property_registry: &mut PropertyRegistry,
+    styles: &[ComputedStyle],
+) -> HashMap<(NodeId, PseudoElement), ComputedStyle> {
+    let styled: HashSet<PseudoElement> = ctx
+        .rules
+        .generated
+        .iter()
+        .filter_map(|rule| rule.pseudo_element)
+        .filter(|pseudo_element| {
+            pseudo_element.kind() == PseudoElementKind::Typographic || *pseudo_element == PseudoElement::Placeholder
+        })
+        .collect();
+
+    let mut pseudo_styles = HashMap::new();
+    if styled.is_empty() {
+        return pseudo_styles;
+    }
+
+    for node in &ctx.dom.nodes {
+        let Some(element) = node.data.as_element() else {
+            continue;
+        };
+
+        let style = &styles[*node.id];
+        if style.display.is_none() {
+            continue;
+        }
+
+        for pseudo_element in &styled {
+            if !applies_to(*pseudo_element, element, style) {
+                continue;
+            }
+
+            let pseudo_style = ComputedStyle::from_node(ctx, node.id, Some(*pseudo_element), property_registry, styles);
+            pseudo_styles.insert((node.id, *pseudo_element), pseudo_style);
+        }
+    }
+
+    pseudo_styles
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use css_cssom::{CSSStyleSheet, CssParser};
+    use html_dom::{DocumentRoot, NodeData};
+    use url::Url;
+
+    use super::*;
+    use crate::{AbsoluteContext, Color4f, StyleTree};
+
+    #[test]
+    fn test_pseudo_element_styles() {
+        let element = |tag| NodeData::Element(Element::new(Tag::Html(tag), HashSet::new(), HashMap::new()));
+
+        let mut dom = DocumentRoot::new();
+        let body = dom.push_node(&element(HtmlTag::Body), None);
+        let paragraph = dom.push_node(&element(HtmlTag::P), Some(body));
+        let span = dom.push_node(&element(HtmlTag::Span), Some(paragraph));
+        let input = dom.push_node(&element(HtmlTag::Input), Some(body));
+
+        let css = "p { display: block; color: blue } p::first-line { font-size: 20px } \
+                   ::selection { background-color: red } input::placeholder { color: gray }";
+        let stylesheets = [CSSStyleSheet::from(
+            CssParser::default().parse_css(css, false),
+        )];
+        let url = Url::parse("http://localhost").unwrap();
+        let tree = StyleTree::build(None, &AbsoluteContext::default_url(&url), &dom, &stylesheets);
+
+        let first_line = tree
+            .pseudo_element_style(paragraph, PseudoElement::FirstLine)
+            .unwrap();
+        assert_eq!(first_line.color, Color4f::rgba(0.0, 0.0, 1.0, 1.0));
+        assert!((first_line.font_size - 20.0).abs() < f64::EPSILON);
+        assert!(
+            tree.pseudo_element_style(span, PseudoElement::FirstLine)
+                .is_none()
+        );
+
+        assert!(
+            tree.pseudo_element_style(span, PseudoElement::Selection)
+                .is_none()
+        );
+        assert!(
+            tree.pseudo_element_style(input, PseudoElement::Placeholder)
+                .is_some()
+        );
+        assert!(
+            tree.pseudo_element_style(paragraph, PseudoElement::Placeholder)
+                .is_none()
+        );
+    }
+}
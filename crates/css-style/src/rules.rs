@@ -1,45 +1,63 @@
-use std::collections::HashSet;
+use std::sync::Arc;
 
-use browser_preferences::theme::ThemeCategory;
+use browser_preferences::BrowserPreferences;
 use css_cssom::{
     CSSAtRule, CSSDeclaration, CSSRule, CSSStyleRule, CSSStyleSheet, ComponentValue, ComponentValueStream,
     CssTokenKind, Property, SimpleBlock, StylesheetOrigin,
 };
-use css_selectors::{CompoundSelectorSequence, SelectorSpecificity, generate_selector_list};
-use css_values::{
-    media::{MediaCondition, MediaFeature, MediaType, RangeOperator},
-    property::{PropertyDescriptor, PropertySyntax, SyntaxComponent},
-    quantity::{Length, LengthUnit},
+use css_selectors::{
+    CompoundSelectorSequence, PseudoElement, SelectorBloomFilter, SelectorSpecificity, parse_selector_list,
+    selector_pseudo_element,
 };
+use css_values::property::{PropertyDescriptor, PropertySyntax, SyntaxComponent};
+use html_dom::DocumentRoot;
 
 use crate::{
     AbsoluteContext,
     cascade::{CascadeSpecificity, CascadedDeclaration, RuleIndex},
-    properties::PixelRepr,
+    container::{ContainerQuery, ContainerSizes},
+    media::MediaQuery,
     specified::SpecifiedStyle,
     tree::PropertyRegistry,
 };
 
 #[derive(Debug)]
 pub struct Rules<'css> {
-    pub generated: &'css [GeneratedRule<'css>],
+    pub generated: &'css [GeneratedRule],
     pub index: &'css RuleIndex,
+    /// The containers the `@container` rules are matched against.
+    pub containers: &'css ContainerSizes,
+}
+
+/// What the styles of the nodes of a document are cascaded from, the same for every node of the document.
+#[derive(Debug, Clone, Copy)]
+pub struct CascadeContext<'a> {
+    pub preferences: Option<&'a BrowserPreferences>,
+    pub absolute_ctx: &'a AbsoluteContext<'a>,
+    pub dom: &'a DocumentRoot,
+    pub rules: &'a Rules<'a>,
 }
 
 /// A rule that has been generated from the stylesheets, containing the selector sequences, declarations, origin, and specificity for cascade resolution.
-#[derive(Debug)]
-pub struct GeneratedRule<'css> {
+#[derive(Debug, Clone)]
+pub struct GeneratedRule {
     pub selector_sequences: Vec<CompoundSelectorSequence>,
-    pub declarations: &'css [CSSDeclaration],
+    /// The IDs, classes and tag required by the subject of the selector, checked before the full match.
+    pub bloom_filter: SelectorBloomFilter,
+    pub declarations: Arc<[CSSDeclaration]>,
     pub origin: StylesheetOrigin,
     pub specificity: SelectorSpecificity,
+    /// The pseudo-element styled by the rule, `None` if it styles the matched element itself.
+    pub pseudo_element: Option<PseudoElement>,
+    /// The query of the `@container` rule the rule is nested in, which must match the container of the element.
+    pub container_query: Option<Arc<ContainerQuery>>,
 }
 
-impl<'css> GeneratedRule<'css> {
+impl GeneratedRule {
     /// Build a list of generated rules from the provided stylesheets, filtering out any rules that are not
     /// applicable based on the absolute context (e.g. media queries that don't match the current environment).
     pub fn build(
-        stylesheets: &'css [CSSStyleSheet],
+        stylesheets: &[CSSStyleSheet],
         property_registry: &mut PropertyRegistry,
         absolute_ctx: &AbsoluteContext,
     ) -> Vec<Self> {
@@ -49,16 +67,25 @@ impl<'css> GeneratedRule<'css> {
             for rule in stylesheet.css_rules() {
                 match rule {
                     CSSRule::Style(style) => {
-                        Self::push_rule(&mut generated_rules, stylesheet, style);
+                        Self::push_rule(&mut generated_rules, stylesheet, style, None);
                     }
                     CSSRule::AtRule(at_rule) => {
-                        if !Self::allows_at_rule(at_rule, property_registry, absolute_ctx) {
+                        let container_query = if at_rule.name().eq_ignore_ascii_case("container") {
+                            let Some(query) = ContainerQuery::parse(at_rule.prelude_values(), absolute_ctx) else {
+                                continue;
+                            };
+                            Some(Arc::new(query))
+                        } else if Self::allows_at_rule(at_rule, property_registry, absolute_ctx) {
+                            None
+                        } else {
                             continue;
-                        }
+                        };
 
                         for rule in &at_rule.rules {
                             match rule {
-                                CSSRule::Style(style) => Self::push_rule(&mut generated_rules, stylesheet, style),
+                                CSSRule::Style(style) => {
+                                    Self::push_rule(&mut generated_rules, stylesheet, style, container_query.as_ref());
+                                }
                                 CSSRule::AtRule(nested_at_rule) => {
                                     Self::handle_nested_at_rule(
                                         &mut generated_rules,
@@ -66,6 +93,7 @@ impl<'css> GeneratedRule<'css> {
                                         nested_at_rule,
                                         property_registry,
                                         absolute_ctx,
+                                        container_query.as_ref(),
                                     );
                                 }
                             }
@@ -79,26 +107,41 @@ impl<'css> GeneratedRule<'css> {
     }
 
     /// Recursively handle nested at-rules, filtering out any that are not applicable based on the absolute context.
+    ///
+    /// The rules inside carry the query of the innermost `@container` rule they are nested in.
     fn handle_nested_at_rule(
         generated_rules: &mut Vec<Self>,
         stylesheet: &CSSStyleSheet,
-        at_rule: &'css CSSAtRule,
+        at_rule: &CSSAtRule,
         property_registry: &mut PropertyRegistry,
         absolute_ctx: &AbsoluteContext,
+        container_query: Option<&Arc<ContainerQuery>>,
     ) {
-        if !at_rule.can_be_nested() || !Self::allows_at_rule(at_rule, property_registry, absolute_ctx) {
+        if !at_rule.can_be_nested() {
             return;
         }
 
+        let container_query = if at_rule.name().eq_ignore_ascii_case("container") {
+            let Some(query) = ContainerQuery::parse(at_rule.prelude_values(), absolute_ctx) else {
+                return;
+            };
+            Some(Arc::new(query))
+        } else if Self::allows_at_rule(at_rule, property_registry, absolute_ctx) {
+            container_query.cloned()
+        } else {
+            return;
+        };
+
         for rule in &at_rule.rules {
             match rule {
-                CSSRule::Style(style) => Self::push_rule(generated_rules, stylesheet, style),
+                CSSRule::Style(style) => Self::push_rule(generated_rules, stylesheet, style, container_query.as_ref()),
                 CSSRule::AtRule(nested_at_rule) => Self::handle_nested_at_rule(
                     generated_rules,
                     stylesheet,
                     nested_at_rule,
                     property_registry,
                     absolute_ctx,
+                    container_query.as_ref(),
                 ),
             }
         }
@@ -122,13 +165,7 @@ impl<'css> GeneratedRule<'css> {
         }
 
         if at_rule.name().eq_ignore_ascii_case("media") {
-            let stream = ComponentValueStream::new(at_rule.prelude_values());
-
-            Self::eval_or_logic(stream, |and_stream| {
-                Self::eval_and_logic(and_stream, HashSet::new, |query, media_types| {
-                    Self::handle_media_query(query, absolute_ctx, media_types)
-                })
-            })
+            MediaQuery::parse(at_rule.prelude_values(), absolute_ctx).matches(absolute_ctx)
         } else if at_rule.name().eq_ignore_ascii_case("supports") {
             let stream = ComponentValueStream::new(at_rule.prelude_values());
 
@@ -283,368 +320,6 @@ impl<'css> GeneratedRule<'css> {
         )
     }
 
-    fn handle_media_query(
-        mut stream: ComponentValueStream,
-        absolute_ctx: &AbsoluteContext,
-        media_types: &mut HashSet<MediaType>,
-    ) -> bool {
-        let mut is_not = false;
-
-        while let Some(cv) = stream.next_non_whitespace() {
-            match cv {
-                ComponentValue::Token(token) => {
-                    if let CssTokenKind::Ident(ident) = &token.kind {
-                        if ident.eq_ignore_ascii_case("not") {
-                            is_not = true;
-                            continue;
-                        }
-
-                        let Ok(media_type) = ident.parse::<MediaType>() else {
-                            return false;
-                        };
-
-                        if !matches!(media_type, MediaType::All | MediaType::Screen | MediaType::Print) {
-                            // <https://drafts.csswg.org/mediaqueries/#media-types>
-                            continue;
-                        }
-
-                        media_types.insert(media_type);
-                    }
-                }
-
-                ComponentValue::SimpleBlock(block) => {
-                    if media_types.contains(&MediaType::Print) && media_types.len() == 1 {
-                        // NOTE: We don't support print media queries or the print format.
-                        return false;
-                    }
-
-                    return Self::handle_media_block(block, absolute_ctx) ^ is_not;
-                }
-                ComponentValue::Function(_) => return false,
-            }
-        }
-
-        media_types
-            .iter()
-            .any(|m| matches!(m, MediaType::All | MediaType::Screen))
-            ^ is_not
-    }
-
-    fn handle_media_block(block: &SimpleBlock, absolute_ctx: &AbsoluteContext) -> bool {
-        let mut block_stream = ComponentValueStream::new(&block.value);
-
-        if let Some(ComponentValue::Token(token)) = block_stream.next_non_whitespace() {
-            match &token.kind {
-                CssTokenKind::Ident(ident) => {
-                    let bytes = ident.as_bytes();
-
-                    if bytes.len() < 4 {
-                        return false;
-                    }
-
-                    let mut buf = [0u8; 4];
-                    buf.copy_from_slice(bytes[..4].try_into().unwrap());
-                    buf.make_ascii_lowercase();
-
-                    match &buf {
-                        b"max-" => {
-                            let Ok(remaining) = str::from_utf8(&bytes[4..]) else {
-                                return false;
-                            };
-
-                            let Ok(media_condition) = remaining.parse::<MediaCondition>() else {
-                                return false;
-                            };
-
-                            return Self::handle_media_range_max(media_condition, &mut block_stream, absolute_ctx);
-                        }
-                        b"min-" => {
-                            let Ok(remaining) = str::from_utf8(&bytes[4..]) else {
-                                return false;
-                            };
-
-                            let Ok(media_condition) = remaining.parse::<MediaCondition>() else {
-                                return false;
-                            };
-
-                            return Self::handle_media_range_min(media_condition, &mut block_stream, absolute_ctx);
-                        }
-                        _ => {
-                            let Ok(media_feature) = ident.parse::<MediaFeature>() else {
-                                return false;
-                            };
-
-                            match media_feature {
-                                MediaFeature::PrefersColorScheme => {
-                                    block_stream.skip_whitespace();
-                                    block_stream.next_cv(); // Skip ':'
-                                    if let Some(ComponentValue::Token(value_token)) = block_stream.next_non_whitespace()
-                                        && let CssTokenKind::Ident(value_ident) = &value_token.kind
-                                    {
-                                        return match absolute_ctx.theme_category {
-                                            ThemeCategory::Dark => value_ident.eq_ignore_ascii_case("dark"),
-                                            ThemeCategory::Light => value_ident.eq_ignore_ascii_case("light"),
-                                        };
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                CssTokenKind::Dimension { value, unit } => {
-                    // Range: 100px <= width <= 500px OR 500px >= width >= 100px
-                    // Range Values (ident): aspect-ratio, color, color-index, device-aspect-ratio,
-                    //               device-height, device-width, height, resolution, width
-
-                    // TODO: Resolution
-                    let Ok(first_length_unit) = unit.parse::<LengthUnit>() else {
-                        return false;
-                    };
-
-                    let first_length = Length::new(value.to_f64(), first_length_unit);
-
-                    let first_comparison_token = match block_stream.next_non_whitespace() {
-                        Some(ComponentValue::Token(token)) => {
-                            if let CssTokenKind::Delim(delim) = token.kind {
-                                delim
-                            } else {
-                                return false;
-                            }
-                        }
-                        _ => return false,
-                    };
-
-                    let second_comparison_token = match block_stream.peek() {
-                        Some(ComponentValue::Token(token)) => {
-                            if let CssTokenKind::Delim(delim) = token.kind {
-                                block_stream.next_cv();
-                                Some(delim)
-                            } else {
-                                None
-                            }
-                        }
-                        _ => None,
-                    };
-
-                    let first_comparison = match (first_comparison_token, second_comparison_token) {
-                        ('<', Some('=')) => RangeOperator::LessThanOrEqual,
-                        ('>', Some('=')) => RangeOperator::GreaterThanOrEqual,
-                        ('<', None) => RangeOperator::LessThan,
-                        ('>', None) => RangeOperator::GreaterThan,
-                        _ => return false,
-                    };
-
-                    let value = match block_stream.next_non_whitespace() {
-                        Some(ComponentValue::Token(token)) => {
-                            if let CssTokenKind::Ident(ident) = &token.kind {
-                                match ident.parse::<MediaCondition>() {
-                                    Ok(cond) => cond,
-                                    Err(_) => return false,
-                                }
-                            } else {
-                                return false;
-                            }
-                        }
-                        _ => return false,
-                    };
-
-                    let first_comparison_token = match block_stream.next_non_whitespace() {
-                        Some(ComponentValue::Token(token)) => {
-                            if let CssTokenKind::Delim(delim) = token.kind {
-                                delim
-                            } else {
-                                return false;
-                            }
-                        }
-                        _ => return false,
-                    };
-
-                    let second_comparison_token = match block_stream.peek() {
-                        Some(ComponentValue::Token(token)) => {
-                            if let CssTokenKind::Delim(delim) = token.kind {
-                                block_stream.next_cv();
-                                Some(delim)
-                            } else {
-                                None
-                            }
-                        }
-                        _ => None,
-                    };
-
-                    let second_comparison = match (first_comparison_token, second_comparison_token) {
-                        ('<', Some('=')) => RangeOperator::LessThanOrEqual,
-                        ('>', Some('=')) => RangeOperator::GreaterThanOrEqual,
-                        ('<', None) => RangeOperator::LessThan,
-                        ('>', None) => RangeOperator::GreaterThan,
-                        _ => return false,
-                    };
-
-                    let second_length = match block_stream.next_non_whitespace() {
-                        Some(ComponentValue::Token(token)) => {
-                            if let CssTokenKind::Dimension { value, unit } = &token.kind {
-                                let Ok(length_unit) = unit.parse::<LengthUnit>() else {
-                                    return false;
-                                };
-
-                                Length::new(value.to_f64(), length_unit)
-                            } else {
-                                return false;
-                            }
-                        }
-                        _ => return false,
-                    };
-
-                    match value {
-                        MediaCondition::Width | MediaCondition::DeviceWidth => {
-                            let viewport_width = absolute_ctx.viewport_width;
-                            return Self::resolve_range(
-                                absolute_ctx,
-                                first_length,
-                                first_comparison,
-                                second_comparison,
-                                second_length,
-                                viewport_width,
-                            );
-                        }
-                        MediaCondition::Height | MediaCondition::DeviceHeight => {
-                            let viewport_height = absolute_ctx.viewport_height;
-                            return Self::resolve_range(
-                                absolute_ctx,
-                                first_length,
-                                first_comparison,
-                                second_comparison,
-                                second_length,
-                                viewport_height,
-                            );
-                        }
-                        _ => return false,
-                    }
-                }
-                _ => return false,
-            }
-        }
-
-        false
-    }
-
-    fn handle_media_range_max(
-        media_condition: MediaCondition,
-        block_stream: &mut ComponentValueStream,
-        absolute_ctx: &AbsoluteContext,
-    ) -> bool {
-        block_stream.skip_whitespace();
-        block_stream.next_cv(); // Skip ':'
-
-        if let Some(ComponentValue::Token(token)) = block_stream.next_non_whitespace() {
-            match &token.kind {
-                CssTokenKind::Dimension { value, unit } => {
-                    let Ok(length_unit) = unit.parse::<LengthUnit>() else {
-                        return false;
-                    };
-
-                    let length = Length::new(value.to_f64(), length_unit);
-                    let Ok(px) = length.to_px(None, None, absolute_ctx) else {
-                        return false;
-                    };
-
-                    match media_condition {
-                        MediaCondition::Width | MediaCondition::DeviceWidth => {
-                            let viewport_width = absolute_ctx.viewport_width;
-                            return viewport_width <= px;
-                        }
-                        MediaCondition::Height | MediaCondition::DeviceHeight => {
-                            let viewport_height = absolute_ctx.viewport_height;
-                            return viewport_height <= px;
-                        }
-                        _ => return false,
-                    }
-                }
-                _ => return false,
-            }
-        }
-
-        false
-    }
-
-    fn handle_media_range_min(
-        media_condition: MediaCondition,
-        block_stream: &mut ComponentValueStream,
-        absolute_ctx: &AbsoluteContext,
-    ) -> bool {
-        block_stream.skip_whitespace();
-        block_stream.next_cv(); // Skip ':'
-        block_stream.skip_whitespace();
-
-        if let Some(ComponentValue::Token(token)) = block_stream.next_non_whitespace() {
-            match &token.kind {
-                CssTokenKind::Dimension { value, unit } => {
-                    let Ok(length_unit) = unit.parse::<LengthUnit>() else {
-                        return false;
-                    };
-
-                    let length = Length::new(value.to_f64(), length_unit);
-                    let Ok(px) = length.to_px(None, None, absolute_ctx) else {
-                        return false;
-                    };
-
-                    match media_condition {
-                        MediaCondition::Width | MediaCondition::DeviceWidth => {
-                            let viewport_width = absolute_ctx.viewport_width;
-                            return viewport_width >= px;
-                        }
-                        MediaCondition::Height | MediaCondition::DeviceHeight => {
-                            let viewport_height = absolute_ctx.viewport_height;
-                            return viewport_height >= px;
-                        }
-                        _ => return false,
-                    }
-                }
-                _ => return false,
-            }
-        }
-
-        false
-    }
-
-    fn resolve_range(
-        absolute_ctx: &AbsoluteContext<'_>,
-        first_length: Length,
-        first_comparison: RangeOperator,
-        second_comparison: RangeOperator,
-        second_length: Length,
-        value: f64,
-    ) -> bool {
-        if matches!(first_comparison, RangeOperator::LessThan | RangeOperator::LessThanOrEqual)
-            && matches!(second_comparison, RangeOperator::GreaterThan | RangeOperator::GreaterThanOrEqual)
-        {
-            return false;
-        }
-
-        let Ok(first_px) = first_length.to_px(None, None, absolute_ctx) else {
-            return false;
-        };
-
-        let Ok(second_px) = second_length.to_px(None, None, absolute_ctx) else {
-            return false;
-        };
-
-        let first_condition = match first_comparison {
-            RangeOperator::LessThan => value > first_px,
-            RangeOperator::LessThanOrEqual => value >= first_px,
-            RangeOperator::GreaterThan => value < first_px,
-            RangeOperator::GreaterThanOrEqual => value <= first_px,
-            RangeOperator::Equal => return false,
-        };
-        let second_condition = match second_comparison {
-            RangeOperator::LessThan => value < second_px,
-            RangeOperator::LessThanOrEqual => value <= second_px,
-            RangeOperator::GreaterThan => value > second_px,
-            RangeOperator::GreaterThanOrEqual => value >= second_px,
-            RangeOperator::Equal => return false,
-        };
-        first_condition && second_condition
-    }
-
     fn handle_supports_condition(
         mut stream: ComponentValueStream,
         property_registry: &PropertyRegistry,
@@ -713,10 +388,15 @@ impl<'css> GeneratedRule<'css> {
     }
 
     /// Push a style rule into the generated rules list, extracting its selector sequences, declarations, origin, and specificity for cascade resolution.
-    fn push_rule(generated_rules: &mut Vec<Self>, stylesheet: &CSSStyleSheet, style_rule: &'css CSSStyleRule) {
-        let selector_list = generate_selector_list(&style_rule.prelude);
+    fn push_rule(
+        generated_rules: &mut Vec<Self>,
+        stylesheet: &CSSStyleSheet,
+        style_rule: &CSSStyleRule,
+        container_query: Option<&Arc<ContainerQuery>>,
+    ) {
+        let declarations: Arc<[CSSDeclaration]> = Arc::from(style_rule.declarations());
 
-        for selector_sequence in selector_list {
+        for selector_sequence in parse_selector_list(&style_rule.prelude) {
             let specificity = selector_sequence
                 .iter()
                 .map(css_selectors::SpecificityCalculable::specificity)
@@ -724,11 +404,171 @@ impl<'css> GeneratedRule<'css> {
                 .unwrap_or_default();
 
             generated_rules.push(GeneratedRule {
+                bloom_filter: SelectorBloomFilter::from_selector(&selector_sequence),
+                pseudo_element: selector_pseudo_element(&selector_sequence),
                 selector_sequences: selector_sequence,
-                declarations: style_rule.declarations(),
+                declarations: Arc::clone(&declarations),
                 origin: stylesheet.origin(),
                 specificity,
+                container_query: container_query.cloned(),
             });
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use css_cssom::CssParser;
+    use css_values::media::{ColorScheme, ContrastPreference, ForcedColorsState};
+    use url::Url;
+
+    use super::*;
+    use crate::{InputDevice, SystemPreferences};
+
+    const COLOR_SCHEME_CSS: &str = "p { color: black; } \
+        @media (prefers-color-scheme: dark) { p { color: white; } } \
+        @media (prefers-color-scheme: light) { p { color: navy; } }";
+
+    const REDUCED_MOTION_CSS: &str = "* { animation-duration: 1s; } \
+        @media (prefers-reduced-motion: reduce) { * { animation-duration: 0s; } }";
+
+    const REDUCED_DATA_CSS: &str = "div { background-image: url(hero.jpg); } \
+        @media (prefers-reduced-data: reduce) { div { background-image: none; } }";
+
+    const POINTER_CSS: &str = "button { padding: 2px; } \
+        @media (pointer: coarse) { button { padding: 12px; } }";
+
+    const HOVER_CSS: &str = "a { color: blue; } \
+        @media (hover: hover) { a { color: red; } }";
+
+    const CONTRAST_CSS: &str = "p { color: gray; } \
+        @media (prefers-contrast: more) { p { color: black; } } \
+        @media (prefers-contrast: less) { p { color: silver; } } \
+        @media (prefers-contrast: custom) { p { color: teal; } }";
+
+    const FORCED_COLORS_CSS: &str = "a { border-style: none; } \
+        @media (forced-colors: active) { a { border-style: solid; } }";
+
+    /// The value declared by the last applicable rule, which wins the cascade since all rules share a specificity.
+    fn winning_value(css: &str, system_preferences: SystemPreferences) -> String {
+        let stylesheets = [CSSStyleSheet::from(
+            CssParser::default().parse_css(css, false),
+        )];
+        let url = Url::parse("http://localhost").unwrap();
+        let absolute_ctx = AbsoluteContext {
+            system_preferences,
+            ..AbsoluteContext::default_url(&url)
+        };
+
+        let rules = GeneratedRule::build(&stylesheets, &mut PropertyRegistry::default(), &absolute_ctx);
+        let values = &rules.last().unwrap().declarations[0].original_values;
+
+        values
+            .iter()
+            .find_map(|cv| match cv {
+                ComponentValue::Token(token) if token.kind != CssTokenKind::Whitespace => Some(token.kind.to_string()),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_prefers_color_scheme_dark() {
+        let system_preferences = SystemPreferences {
+            prefers_color_scheme: ColorScheme::Dark,
+            ..Default::default()
+        };
+
+        assert_eq!(winning_value(COLOR_SCHEME_CSS, system_preferences), "white");
+    }
+
+    #[test]
+    fn test_prefers_color_scheme_light() {
+        let system_preferences = SystemPreferences {
+            prefers_color_scheme: ColorScheme::Light,
+            ..Default::default()
+        };
+
+        assert_eq!(winning_value(COLOR_SCHEME_CSS, system_preferences), "navy");
+    }
+
+    #[test]
+    fn test_prefers_reduced_motion_disables_animations() {
+        let system_preferences = SystemPreferences {
+            prefers_reduced_motion: true,
+            ..Default::default()
+        };
+
+        assert_eq!(winning_value(REDUCED_MOTION_CSS, system_preferences), "0s");
+    }
+
+    #[test]
+    fn test_no_motion_preference_keeps_animations() {
+        assert_eq!(winning_value(REDUCED_MOTION_CSS, SystemPreferences::default()), "1s");
+    }
+
+    #[test]
+    fn test_prefers_reduced_data_drops_background_images() {
+        let system_preferences = SystemPreferences {
+            prefers_reduced_data: true,
+            ..Default::default()
+        };
+
+        assert_eq!(winning_value(REDUCED_DATA_CSS, system_preferences), "none");
+        assert_ne!(winning_value(REDUCED_DATA_CSS, SystemPreferences::default()), "none");
+    }
+
+    #[test]
+    fn test_pointer_coarse_applies_on_touch() {
+        let system_preferences = SystemPreferences {
+            input_device: InputDevice::Touch,
+            ..Default::default()
+        };
+
+        assert_eq!(winning_value(POINTER_CSS, system_preferences), "12px");
+    }
+
+    #[test]
+    fn test_pointer_coarse_ignored_with_mouse() {
+        assert_eq!(winning_value(POINTER_CSS, SystemPreferences::default()), "2px");
+    }
+
+    #[test]
+    fn test_hover_matches_only_mouse() {
+        let touch = SystemPreferences {
+            input_device: InputDevice::Touch,
+            ..Default::default()
+        };
+
+        assert_eq!(winning_value(HOVER_CSS, SystemPreferences::default()), "red");
+        assert_eq!(winning_value(HOVER_CSS, touch), "blue");
+    }
+
+    #[test]
+    fn test_prefers_contrast() {
+        let more = SystemPreferences {
+            prefers_contrast: ContrastPreference::More,
+            ..Default::default()
+        };
+        let less = SystemPreferences {
+            prefers_contrast: ContrastPreference::Less,
+            ..Default::default()
+        };
+
+        assert_eq!(winning_value(CONTRAST_CSS, SystemPreferences::default()), "gray");
+        assert_eq!(winning_value(CONTRAST_CSS, more), "black");
+        assert_eq!(winning_value(CONTRAST_CSS, less), "silver");
+    }
+
+    #[test]
+    fn test_forced_colors_active() {
+        let forced = SystemPreferences {
+            forced_colors: ForcedColorsState::Active,
+            ..Default::default()
+        };
+
+        assert_eq!(winning_value(FORCED_COLORS_CSS, SystemPreferences::default()), "none");
+        assert_eq!(winning_value(FORCED_COLORS_CSS, forced), "solid");
+        assert_eq!(winning_value(CONTRAST_CSS, forced), "teal");
+    }
+}
@@ -1,17 +1,18 @@
 use std::sync::Arc;
 
 use css_cssom::{CSSStyleSheet, ComponentValue, ComponentValueStream, KnownProperty, Property};
+use css_selectors::PseudoElement;
 use css_values::global::Global;
-use html_dom::{DocumentRoot, NodeId};
+use html_dom::NodeId;
 use tracing::debug;
 
 use crate::{
     ComputedStyle, StyleContext,
-    cascade::{CascadedDeclaration, cascade, cascade_variables},
+    cascade::{CascadedDeclaration, CascadedStyle, cascade, cascade_variables},
     functions::variables::{ScopedVariables, resolve_css_variables},
     handler::*,
     properties::*,
-    rules::Rules,
+    rules::CascadeContext,
     tree::PropertyRegistry,
 };
 
@@ -21,6 +22,7 @@ pub struct SpecifiedStyle {
     pub align_content: AlignContentProperty,
     pub align_items: AlignItemsProperty,
     pub align_self: AlignSelfProperty,
+    pub backdrop_filter: FilterProperty,
     pub background_attachment: BackgroundAttachmentProperty,
     pub background_blend_mode: BackgroundBlendModeProperty,
     pub background_clip: BackgroundClipProperty,
@@ -37,6 +39,10 @@ pub struct SpecifiedStyle {
     pub border_left_color: ColorProperty,
     pub border_left_style: BorderStyleValueProperty,
     pub border_left_width: BorderWidthValueProperty,
+    pub border_radius_bottom_left: BorderRadiusProperty,
+    pub border_radius_bottom_right: BorderRadiusProperty,
+    pub border_radius_top_left: BorderRadiusProperty,
+    pub border_radius_top_right: BorderRadiusProperty,
     pub border_right_color: ColorProperty,
     pub border_right_style: BorderStyleValueProperty,
     pub border_right_width: BorderWidthValueProperty,
@@ -44,11 +50,21 @@ pub struct SpecifiedStyle {
     pub border_top_style: BorderStyleValueProperty,
     pub border_top_width: BorderWidthValueProperty,
     pub bottom: MarginProperty,
+    pub box_shadow: BoxShadowProperty,
     pub clear: ClearProperty,
     pub color: ColorProperty,
     pub column_gap: GapProperty,
+    pub contain_intrinsic_height: ContainIntrinsicLengthProperty,
+    pub contain_intrinsic_width: ContainIntrinsicLengthProperty,
+    pub container_name: ContainerNameProperty,
+    pub container_type: ContainerTypeProperty,
+    pub content: ContentProperty,
+    pub content_visibility: ContentVisibilityProperty,
+    pub counter_increment: CounterIncrementProperty,
+    pub counter_reset: CounterResetProperty,
     pub cursor: CursorProperty,
     pub display: DisplayProperty,
+    pub filter: FilterProperty,
     pub flex_basis: FlexBasisProperty,
     pub flex_direction: FlexDirectionProperty,
     pub flex_grow: FlexValueProperty,
@@ -56,20 +72,41 @@ pub struct SpecifiedStyle {
     pub flex_wrap: FlexWrapProperty,
     pub float: FloatProperty,
     pub font_family: FontFamilyProperty,
+    pub font_kerning: FontKerningProperty,
     pub font_size: FontSizeProperty,
+    pub font_size_adjust: FontSizeAdjustProperty,
+    pub font_variant_ligatures: FontVariantLigaturesProperty,
+    pub font_variant_numeric: FontVariantNumericProperty,
+    pub font_variant_caps: FontVariantCapsProperty,
+    pub font_feature_settings: FontFeatureSettingsProperty,
     pub font_weight: FontWeightProperty,
+    pub forced_color_adjust: ForcedColorAdjustProperty,
     pub height: SizeProperty,
+    pub hyphenate_character: HyphenateCharacterProperty,
+    pub hyphens: HyphensProperty,
+    pub image_rendering: ImageRenderingProperty,
+    pub isolation: IsolationProperty,
     pub justify_content: JustifyContentProperty,
     pub justify_items: JustifyItemsProperty,
     pub justify_self: JustifySelfProperty,
     pub left: MarginProperty,
+    pub letter_spacing: SpacingProperty,
     pub line_height: LineHeightProperty,
     pub margin_bottom: MarginProperty,
     pub margin_left: MarginProperty,
     pub margin_right: MarginProperty,
     pub margin_top: MarginProperty,
+    pub mask_image: BackgroundImageProperty,
+    pub mask_mode: MaskModeProperty,
+    pub mask_origin: MaskOriginProperty,
+    pub mask_position_x: BackgroundPositionXProperty,
+    pub mask_position_y: BackgroundPositionYProperty,
+    pub mask_repeat: BackgroundRepeatProperty,
+    pub mask_size: BackgroundSizeProperty,
     pub max_height: MaxSizeProperty,
     pub max_width: MaxSizeProperty,
+    pub mix_blend_mode: BlendModeProperty,
+    pub object_fit: ObjectFitProperty,
     pub order: OrderProperty,
     pub overflow_anchor: OverflowAnchorProperty,
     pub overflow_wrap: OverflowWrapProperty,
@@ -79,13 +116,21 @@ pub struct SpecifiedStyle {
     pub padding_left: OffsetProperty,
     pub padding_right: OffsetProperty,
     pub padding_top: OffsetProperty,
+    pub paint_order: PaintOrderProperty,
     pub position: PositionProperty,
+    pub quotes: QuotesProperty,
+    pub resize: ResizeProperty,
     pub right: MarginProperty,
     pub row_gap: GapProperty,
+    pub tab_size: TabSizeProperty,
     pub text_align: TextAlignProperty,
+    pub text_indent: TextIndentProperty,
     pub top: MarginProperty,
     pub whitespace: WhitespaceProperty,
     pub width: SizeProperty,
+    pub will_change: WillChangeProperty,
+    pub word_break: WordBreakProperty,
+    pub word_spacing: SpacingProperty,
     pub writing_mode: WritingModeProperty,
 
     // === Non-CSS properties ===
@@ -95,15 +140,23 @@ pub struct SpecifiedStyle {
 
 impl SpecifiedStyle {
     /// Computes the `ComputedStyle` for a given node in the DOM.
+    ///
+    /// With a `pseudo_element` the style of that pseudo-element of the node is computed instead, which does not take
+    /// the `style` attribute of the node into account.
     pub fn from_node(
-        absolute_ctx: &AbsoluteContext,
+        ctx: &CascadeContext,
         style_ctx: &StyleContext,
         parent_style: &ComputedStyle,
         node_id: NodeId,
-        dom: &DocumentRoot,
-        rules: &Rules,
+        pseudo_element: Option<PseudoElement>,
         property_registry: &PropertyRegistry,
     ) -> Self {
+        let CascadeContext {
+            absolute_ctx,
+            dom,
+            rules,
+            ..
+        } = *ctx;
         let mut specified_style = Self::default();
 
         let parent_variables = parent_style.variables.clone();
@@ -113,15 +166,23 @@ impl SpecifiedStyle {
         let inline_declarations = node
             .data
             .as_element()
+            .filter(|_| pseudo_element.is_none())
             .and_then(|e| e.get_attribute("style"))
             .map(CSSStyleSheet::from_inline)
             .unwrap_or_default();
 
-        let (declarations, variables) =
-            &mut CascadedDeclaration::collect(node, dom, rules.generated, rules.index, &inline_declarations);
+        let mut cascaded = CascadedStyle::collect(
+            node,
+            pseudo_element,
+            dom,
+            rules.generated,
+            rules.index,
+            rules.containers,
+            &inline_declarations,
+        );
 
-        let properties = cascade(declarations);
-        let new_vars = cascade_variables(variables);
+        let properties = cascade(&mut cascaded);
+        let new_vars = cascade_variables(&mut cascaded);
 
         if !new_vars.is_empty() {
             specified_style.variables = Some(Arc::new(ScopedVariables {
@@ -143,16 +204,156 @@ impl SpecifiedStyle {
 
         ctx.log_errors();
 
+        specified_style.resolve_unset();
+
         specified_style
     }
 
+    /// Replaces the `unset` keyword with `inherit` for inherited properties and `initial` for the others.
+    ///
+    /// A `revert` or `revert-layer` keyword left after the cascade was declared in the user-agent stylesheet, or had no
+    /// value from a lower origin to roll back to, in which case it behaves as `unset` as well.
+    pub(crate) fn resolve_unset(&mut self) {
+        macro_rules! resolve_unset {
+            ($global:expr; $($field:ident),* $(,)?) => {
+                $(
+                    if matches!(self.$field, CSSProperty::Global(Global::Unset | Global::Revert | Global::RevertLayer)) {
+                        self.$field = CSSProperty::Global($global);
+                    }
+                )*
+            };
+        }
+
+        resolve_unset!(
+            Global::Initial;
+            align_content,
+            align_items,
+            align_self,
+            backdrop_filter,
+            background_attachment,
+            background_blend_mode,
+            background_clip,
+            background_color,
+            background_image,
+            background_origin,
+            background_position_x,
+            background_position_y,
+            background_repeat,
+            background_size,
+            border_bottom_color,
+            border_bottom_style,
+            border_bottom_width,
+            border_left_color,
+            border_left_style,
+            border_left_width,
+            border_radius_bottom_left,
+            border_radius_bottom_right,
+            border_radius_top_left,
+            border_radius_top_right,
+            border_right_color,
+            border_right_style,
+            border_right_width,
+            border_top_color,
+            border_top_style,
+            border_top_width,
+            bottom,
+            box_shadow,
+            clear,
+            column_gap,
+            contain_intrinsic_height,
+            contain_intrinsic_width,
+            container_name,
+            container_type,
+            content,
+            content_visibility,
+            counter_increment,
+            counter_reset,
+            display,
+            filter,
+            flex_basis,
+            flex_direction,
+            flex_grow,
+            flex_shrink,
+            flex_wrap,
+            float,
+            height,
+            isolation,
+            justify_content,
+            justify_items,
+            justify_self,
+            left,
+            margin_bottom,
+            margin_left,
+            margin_right,
+            margin_top,
+            mask_image,
+            mask_mode,
+            mask_origin,
+            mask_position_x,
+            mask_position_y,
+            mask_repeat,
+            mask_size,
+            max_height,
+            max_width,
+            mix_blend_mode,
+            object_fit,
+            order,
+            overflow_anchor,
+            overflow_x,
+            overflow_y,
+            padding_bottom,
+            padding_left,
+            padding_right,
+            padding_top,
+            position,
+            resize,
+            right,
+            row_gap,
+            top,
+            width,
+            will_change,
+        );
+
+        resolve_unset!(
+            Global::Inherit;
+            color,
+            cursor,
+            font_family,
+            font_kerning,
+            font_size,
+            font_size_adjust,
+            font_variant_ligatures,
+            font_variant_numeric,
+            font_variant_caps,
+            font_feature_settings,
+            font_weight,
+            forced_color_adjust,
+            hyphenate_character,
+            hyphens,
+            image_rendering,
+            letter_spacing,
+            line_height,
+            overflow_wrap,
+            paint_order,
+            quotes,
+            tab_size,
+            text_align,
+            text_indent,
+            whitespace,
+            word_break,
+            word_spacing,
+            writing_mode,
+        );
+    }
+
     /// Checks if a given declaration is supported by the specified style system.
     pub fn supports(
         declaration: CascadedDeclaration,
         property_registry: &PropertyRegistry,
         absolute_ctx: &AbsoluteContext,
     ) -> bool {
-        let mut declarations = vec![declaration];
+        let mut cascaded = CascadedStyle::default();
+        cascaded.push(declaration);
 
         let mut default_style = Self::default();
         let default_computed_style = ComputedStyle::default();
@@ -160,7 +361,7 @@ impl SpecifiedStyle {
 
         let mut ctx = PropertyUpdateContext::new(absolute_ctx, &default_style_ctx, &mut default_style);
 
-        let properties = cascade(&mut declarations);
+        let properties = cascade(&mut cascaded);
 
         for (property, value) in properties {
             if Self::resolve_property(property, value, property_registry, &mut ctx) && !ctx.has_errors() {
@@ -198,6 +399,7 @@ impl SpecifiedStyle {
                 KnownProperty::AlignContent => handle_align_content(ctx, &mut stream),
                 KnownProperty::AlignItems => handle_align_items(ctx, &mut stream),
                 KnownProperty::AlignSelf => handle_align_self(ctx, &mut stream),
+                KnownProperty::BackdropFilter => handle_backdrop_filter(ctx, &mut stream),
                 KnownProperty::Background => handle_background(ctx, &mut stream),
                 KnownProperty::BackgroundAttachment => handle_background_attachment(ctx, &mut stream),
                 KnownProperty::BackgroundBlendMode => handle_background_blend_mode(ctx, &mut stream),
@@ -217,6 +419,8 @@ impl SpecifiedStyle {
                 KnownProperty::BorderBottom => handle_border(ctx, &mut stream, BorderSide::Bottom),
                 KnownProperty::BorderBottomColor => handle_border_bottom_color(ctx, &mut stream),
                 KnownProperty::BorderBottomStyle => handle_border_bottom_style(ctx, &mut stream),
+                KnownProperty::BorderBottomLeftRadius => handle_border_bottom_left_radius(ctx, &mut stream),
+                KnownProperty::BorderBottomRightRadius => handle_border_bottom_right_radius(ctx, &mut stream),
                 KnownProperty::BorderBottomWidth => handle_border_bottom_width(ctx, &mut stream),
                 KnownProperty::BorderColor => handle_border_color(ctx, &mut stream),
                 KnownProperty::BorderInline => handle_border(ctx, &mut stream, BorderSide::Inline),
@@ -226,6 +430,7 @@ impl SpecifiedStyle {
                 KnownProperty::BorderLeftColor => handle_border_left_color(ctx, &mut stream),
                 KnownProperty::BorderLeftStyle => handle_border_left_style(ctx, &mut stream),
                 KnownProperty::BorderLeftWidth => handle_border_left_width(ctx, &mut stream),
+                KnownProperty::BorderRadius => handle_border_radius(ctx, &mut stream),
                 KnownProperty::BorderRight => handle_border(ctx, &mut stream, BorderSide::Right),
                 KnownProperty::BorderRightColor => handle_border_right_color(ctx, &mut stream),
                 KnownProperty::BorderRightStyle => handle_border_right_style(ctx, &mut stream),
@@ -233,15 +438,30 @@ impl SpecifiedStyle {
                 KnownProperty::BorderStyle => handle_border_style(ctx, &mut stream),
                 KnownProperty::BorderTop => handle_border(ctx, &mut stream, BorderSide::Top),
                 KnownProperty::BorderTopColor => handle_border_top_color(ctx, &mut stream),
+                KnownProperty::BorderTopLeftRadius => handle_border_top_left_radius(ctx, &mut stream),
+                KnownProperty::BorderTopRightRadius => handle_border_top_right_radius(ctx, &mut stream),
                 KnownProperty::BorderTopStyle => handle_border_top_style(ctx, &mut stream),
                 KnownProperty::BorderTopWidth => handle_border_top_width(ctx, &mut stream),
                 KnownProperty::BorderWidth => handle_border_width(ctx, &mut stream),
                 KnownProperty::Bottom => handle_bottom(ctx, &mut stream),
+                KnownProperty::BoxShadow => handle_box_shadow(ctx, &mut stream),
                 KnownProperty::Clear => handle_clear(ctx, &mut stream),
                 KnownProperty::Color => handle_color(ctx, &mut stream),
                 KnownProperty::ColumnGap => handle_column_gap(ctx, &mut stream),
+                KnownProperty::ContainIntrinsicBlockSize => handle_contain_intrinsic_block_size(ctx, &mut stream),
+                KnownProperty::ContainIntrinsicHeight => handle_contain_intrinsic_height(ctx, &mut stream),
+                KnownProperty::ContainIntrinsicInlineSize => handle_contain_intrinsic_inline_size(ctx, &mut stream),
+                KnownProperty::ContainIntrinsicSize => handle_contain_intrinsic_size(ctx, &mut stream),
+                KnownProperty::ContainIntrinsicWidth => handle_contain_intrinsic_width(ctx, &mut stream),
+                KnownProperty::ContainerName => handle_container_name(ctx, &mut stream),
+                KnownProperty::ContainerType => handle_container_type(ctx, &mut stream),
+                KnownProperty::Content => handle_content(ctx, &mut stream),
+                KnownProperty::ContentVisibility => handle_content_visibility(ctx, &mut stream),
+                KnownProperty::CounterIncrement => handle_counter_increment(ctx, &mut stream),
+                KnownProperty::CounterReset => handle_counter_reset(ctx, &mut stream),
                 KnownProperty::Cursor => handle_cursor(ctx, &mut stream),
                 KnownProperty::Display => handle_display(ctx, &mut stream),
+                KnownProperty::Filter => handle_filter(ctx, &mut stream),
                 KnownProperty::Flex => handle_flex(ctx, &mut stream),
                 KnownProperty::FlexBasis => handle_flex_basis(ctx, &mut stream),
                 KnownProperty::FlexDirection => handle_flex_direction(ctx, &mut stream),
@@ -251,14 +471,26 @@ impl SpecifiedStyle {
                 KnownProperty::FlexWrap => handle_flex_wrap(ctx, &mut stream),
                 KnownProperty::Float => handle_float(ctx, &mut stream),
                 KnownProperty::FontFamily => handle_font_family(ctx, &mut stream),
+                KnownProperty::ForcedColorAdjust => handle_forced_color_adjust(ctx, &mut stream),
+                KnownProperty::FontKerning => handle_font_kerning(ctx, &mut stream),
                 KnownProperty::FontSize => handle_font_size(ctx, &mut stream),
+                KnownProperty::FontSizeAdjust => handle_font_size_adjust(ctx, &mut stream),
+                KnownProperty::FontVariantLigatures => handle_font_variant_ligatures(ctx, &mut stream),
+                KnownProperty::FontVariantNumeric => handle_font_variant_numeric(ctx, &mut stream),
+                KnownProperty::FontVariantCaps => handle_font_variant_caps(ctx, &mut stream),
+                KnownProperty::FontFeatureSettings => handle_font_feature_settings(ctx, &mut stream),
                 KnownProperty::FontWeight => handle_font_weight(ctx, &mut stream),
                 KnownProperty::Gap => handle_gap(ctx, &mut stream),
                 KnownProperty::Height => handle_height(ctx, &mut stream),
+                KnownProperty::HyphenateCharacter => handle_hyphenate_character(ctx, &mut stream),
+                KnownProperty::Hyphens => handle_hyphens(ctx, &mut stream),
+                KnownProperty::ImageRendering => handle_image_rendering(ctx, &mut stream),
+                KnownProperty::Isolation => handle_isolation(ctx, &mut stream),
                 KnownProperty::JustifyContent => handle_justify_content(ctx, &mut stream),
                 KnownProperty::JustifyItems => handle_justify_items(ctx, &mut stream),
                 KnownProperty::JustifySelf => handle_justify_self(ctx, &mut stream),
                 KnownProperty::Left => handle_left(ctx, &mut stream),
+                KnownProperty::LetterSpacing => handle_letter_spacing(ctx, &mut stream),
                 KnownProperty::LineHeight => handle_line_height(ctx, &mut stream),
                 KnownProperty::Margin => handle_margin(ctx, &mut stream),
                 KnownProperty::MarginBlock => handle_margin_block(ctx, &mut stream),
@@ -271,8 +503,17 @@ impl SpecifiedStyle {
                 KnownProperty::MarginLeft => handle_margin_left(ctx, &mut stream),
                 KnownProperty::MarginRight => handle_margin_right(ctx, &mut stream),
                 KnownProperty::MarginTop => handle_margin_top(ctx, &mut stream),
+                KnownProperty::Mask => handle_mask(ctx, &mut stream),
+                KnownProperty::MaskImage => handle_mask_image(ctx, &mut stream),
+                KnownProperty::MaskMode => handle_mask_mode(ctx, &mut stream),
+                KnownProperty::MaskOrigin => handle_mask_origin(ctx, &mut stream),
+                KnownProperty::MaskPosition => handle_mask_position(ctx, &mut stream),
+                KnownProperty::MaskRepeat => handle_mask_repeat(ctx, &mut stream),
+                KnownProperty::MaskSize => handle_mask_size(ctx, &mut stream),
                 KnownProperty::MaxHeight => handle_max_height(ctx, &mut stream),
                 KnownProperty::MaxWidth => handle_max_width(ctx, &mut stream),
+                KnownProperty::MixBlendMode => handle_mix_blend_mode(ctx, &mut stream),
+                KnownProperty::ObjectFit => handle_object_fit(ctx, &mut stream),
                 KnownProperty::Order => handle_order(ctx, &mut stream),
                 KnownProperty::Overflow => handle_overflow(ctx, &mut stream, OverflowSide::All),
                 KnownProperty::OverflowAnchor => handle_overflow_anchor(ctx, &mut stream),
@@ -292,13 +533,21 @@ impl SpecifiedStyle {
                 KnownProperty::PaddingLeft => handle_padding_left(ctx, &mut stream),
                 KnownProperty::PaddingRight => handle_padding_right(ctx, &mut stream),
                 KnownProperty::PaddingTop => handle_padding_top(ctx, &mut stream),
+                KnownProperty::PaintOrder => handle_paint_order(ctx, &mut stream),
                 KnownProperty::Position => handle_position(ctx, &mut stream),
+                KnownProperty::Quotes => handle_quotes(ctx, &mut stream),
+                KnownProperty::Resize => handle_resize(ctx, &mut stream),
                 KnownProperty::Right => handle_right(ctx, &mut stream),
                 KnownProperty::RowGap => handle_row_gap(ctx, &mut stream),
+                KnownProperty::TabSize => handle_tab_size(ctx, &mut stream),
                 KnownProperty::TextAlign => handle_text_align(ctx, &mut stream),
+                KnownProperty::TextIndent => handle_text_indent(ctx, &mut stream),
                 KnownProperty::Top => handle_top(ctx, &mut stream),
                 KnownProperty::WhiteSpace => handle_whitespace(ctx, &mut stream),
                 KnownProperty::Width => handle_width(ctx, &mut stream),
+                KnownProperty::WillChange => handle_will_change(ctx, &mut stream),
+                KnownProperty::WordBreak => handle_word_break(ctx, &mut stream),
+                KnownProperty::WordSpacing => handle_word_spacing(ctx, &mut stream),
                 KnownProperty::WritingMode => handle_writing_mode(ctx, &mut stream),
                 _ => {
                     return false;
@@ -321,6 +570,7 @@ impl Default for SpecifiedStyle {
             align_content: CSSProperty::Global(Global::Initial),
             align_items: CSSProperty::Global(Global::Initial),
             align_self: CSSProperty::Global(Global::Initial),
+            backdrop_filter: CSSProperty::Global(Global::Initial),
             background_attachment: CSSProperty::Global(Global::Initial),
             background_blend_mode: CSSProperty::Global(Global::Initial),
             background_clip: CSSProperty::Global(Global::Initial),
@@ -337,6 +587,10 @@ impl Default for SpecifiedStyle {
             border_left_color: CSSProperty::Global(Global::Initial),
             border_left_style: CSSProperty::Global(Global::Initial),
             border_left_width: CSSProperty::Global(Global::Initial),
+            border_radius_bottom_left: CSSProperty::Global(Global::Initial),
+            border_radius_bottom_right: CSSProperty::Global(Global::Initial),
+            border_radius_top_left: CSSProperty::Global(Global::Initial),
+            border_radius_top_right: CSSProperty::Global(Global::Initial),
             border_right_color: CSSProperty::Global(Global::Initial),
             border_right_style: CSSProperty::Global(Global::Initial),
             border_right_width: CSSProperty::Global(Global::Initial),
@@ -344,9 +598,19 @@ impl Default for SpecifiedStyle {
             border_top_style: CSSProperty::Global(Global::Initial),
             border_top_width: CSSProperty::Global(Global::Initial),
             bottom: CSSProperty::Global(Global::Initial),
+            box_shadow: CSSProperty::Global(Global::Initial),
             clear: CSSProperty::Global(Global::Initial),
             column_gap: CSSProperty::Global(Global::Initial),
+            contain_intrinsic_height: CSSProperty::Global(Global::Initial),
+            contain_intrinsic_width: CSSProperty::Global(Global::Initial),
+            container_name: CSSProperty::Global(Global::Initial),
+            container_type: CSSProperty::Global(Global::Initial),
+            content: CSSProperty::Global(Global::Initial),
+            content_visibility: CSSProperty::Global(Global::Initial),
+            counter_increment: CSSProperty::Global(Global::Initial),
+            counter_reset: CSSProperty::Global(Global::Initial),
             display: CSSProperty::Global(Global::Initial),
+            filter: CSSProperty::Global(Global::Initial),
             flex_basis: CSSProperty::Global(Global::Initial),
             flex_direction: CSSProperty::Global(Global::Initial),
             flex_grow: CSSProperty::Global(Global::Initial),
@@ -354,6 +618,7 @@ impl Default for SpecifiedStyle {
             flex_wrap: CSSProperty::Global(Global::Initial),
             float: CSSProperty::Global(Global::Initial),
             height: CSSProperty::Global(Global::Initial),
+            isolation: CSSProperty::Global(Global::Initial),
             justify_content: CSSProperty::Global(Global::Initial),
             justify_items: CSSProperty::Global(Global::Initial),
             justify_self: CSSProperty::Global(Global::Initial),
@@ -362,8 +627,17 @@ impl Default for SpecifiedStyle {
             margin_left: CSSProperty::Global(Global::Initial),
             margin_right: CSSProperty::Global(Global::Initial),
             margin_top: CSSProperty::Global(Global::Initial),
+            mask_image: CSSProperty::Global(Global::Initial),
+            mask_mode: CSSProperty::Global(Global::Initial),
+            mask_origin: CSSProperty::Global(Global::Initial),
+            mask_position_x: CSSProperty::Global(Global::Initial),
+            mask_position_y: CSSProperty::Global(Global::Initial),
+            mask_repeat: CSSProperty::Global(Global::Initial),
+            mask_size: CSSProperty::Global(Global::Initial),
             max_height: CSSProperty::Global(Global::Initial),
             max_width: CSSProperty::Global(Global::Initial),
+            mix_blend_mode: CSSProperty::Global(Global::Initial),
+            object_fit: CSSProperty::Global(Global::Initial),
             order: CSSProperty::Global(Global::Initial),
             overflow_anchor: CSSProperty::Global(Global::Initial),
             overflow_x: CSSProperty::Global(Global::Initial),
@@ -373,21 +647,40 @@ impl Default for SpecifiedStyle {
             padding_right: CSSProperty::Global(Global::Initial),
             padding_top: CSSProperty::Global(Global::Initial),
             position: CSSProperty::Global(Global::Initial),
+            resize: CSSProperty::Global(Global::Initial),
             right: CSSProperty::Global(Global::Initial),
             row_gap: CSSProperty::Global(Global::Initial),
             top: CSSProperty::Global(Global::Initial),
             width: CSSProperty::Global(Global::Initial),
+            will_change: CSSProperty::Global(Global::Initial),
 
             // Inherited properties
             color: CSSProperty::Global(Global::Inherit),
             cursor: CSSProperty::Global(Global::Inherit),
             font_family: CSSProperty::Global(Global::Inherit),
+            font_kerning: CSSProperty::Global(Global::Inherit),
             font_size: CSSProperty::Global(Global::Inherit),
+            font_size_adjust: CSSProperty::Global(Global::Inherit),
+            font_variant_ligatures: CSSProperty::Global(Global::Inherit),
+            font_variant_numeric: CSSProperty::Global(Global::Inherit),
+            font_variant_caps: CSSProperty::Global(Global::Inherit),
+            font_feature_settings: CSSProperty::Global(Global::Inherit),
             font_weight: CSSProperty::Global(Global::Inherit),
+            forced_color_adjust: CSSProperty::Global(Global::Inherit),
+            hyphenate_character: CSSProperty::Global(Global::Inherit),
+            hyphens: CSSProperty::Global(Global::Inherit),
+            image_rendering: CSSProperty::Global(Global::Inherit),
+            letter_spacing: CSSProperty::Global(Global::Inherit),
             line_height: CSSProperty::Global(Global::Inherit),
             overflow_wrap: CSSProperty::Global(Global::Inherit),
+            paint_order: CSSProperty::Global(Global::Inherit),
+            quotes: CSSProperty::Global(Global::Inherit),
+            tab_size: CSSProperty::Global(Global::Inherit),
             text_align: CSSProperty::Global(Global::Inherit),
+            text_indent: CSSProperty::Global(Global::Inherit),
             whitespace: CSSProperty::Global(Global::Inherit),
+            word_break: CSSProperty::Global(Global::Inherit),
+            word_spacing: CSSProperty::Global(Global::Inherit),
             writing_mode: CSSProperty::Global(Global::Inherit),
         }
     }
@@ -3,18 +3,24 @@
 //! The `build` method of `StyleTree` constructs the styled tree from the given absolute context, DOM tree, and stylesheets by computing the styles for each node
 //! based on the cascade rules and the provided stylesheets.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ops::Index;
 
 use browser_preferences::BrowserPreferences;
 use css_cssom::CSSStyleSheet;
+use css_selectors::PseudoElement;
 use css_values::property::PropertyDescriptor;
 use html_dom::{DocumentRoot, NodeId};
 
 use crate::ComputedStyle;
 use crate::cascade::RuleIndex;
-use crate::properties::AbsoluteContext;
-use crate::rules::{GeneratedRule, Rules};
+use crate::container::ContainerSizes;
+use crate::generated::{ContentGenerator, GeneratedContent};
+use crate::invalidation::{LayoutInvalidationSet, StyleInvalidationSet};
+use crate::parts::{PartStyles, style_parts};
+use crate::properties::{AbsoluteContext, SystemPreferences};
+use crate::pseudo::style_pseudo_elements;
+use crate::rules::{CascadeContext, GeneratedRule, Rules};
 
 /// Represents the property registry, for storing the descriptors of CSS properties, parsed via the @property rule in the stylesheets.
 #[derive(Debug, Default, Clone)]
@@ -22,6 +28,62 @@ pub struct PropertyRegistry {
     pub descriptors: HashMap<String, PropertyDescriptor>,
 }
 
+/// The parts of the absolute context that decide which conditional rules (`@media`, `@supports`) apply.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RuleEnvironment {
+    root_font_size: f64,
+    viewport_width: f64,
+    viewport_height: f64,
+    system_preferences: SystemPreferences,
+}
+
+impl From<&AbsoluteContext<'_>> for RuleEnvironment {
+    fn from(absolute_ctx: &AbsoluteContext) -> Self {
+        Self {
+            root_font_size: absolute_ctx.root_font_size,
+            viewport_width: absolute_ctx.viewport_width,
+            viewport_height: absolute_ctx.viewport_height,
+            system_preferences: absolute_ctx.system_preferences,
+        }
+    }
+}
+
+/// The rules generated from the stylesheets of the document and their index, kept by the style tree so that
+/// recomputing the styles of a few nodes does not generate them again.
+#[derive(Debug, Clone, Default)]
+struct RuleCache {
+    /// The stylesheets the rules were generated from.
+    stylesheets: Vec<CSSStyleSheet>,
+
+    /// The environment the conditional rules were evaluated in, `None` before any rules are generated.
+    environment: Option<RuleEnvironment>,
+    rules: Vec<GeneratedRule>,
+    index: RuleIndex,
+    property_registry: PropertyRegistry,
+}
+
+impl RuleCache {
+    fn new(stylesheets: &[CSSStyleSheet], absolute_ctx: &AbsoluteContext) -> Self {
+        let mut property_registry = PropertyRegistry::default();
+        let rules = GeneratedRule::build(stylesheets, &mut property_registry, absolute_ctx);
+        let index = RuleIndex::build(&rules);
+
+        Self {
+            stylesheets: stylesheets.to_vec(),
+            environment: Some(RuleEnvironment::from(absolute_ctx)),
+            rules,
+            index,
+            property_registry,
+        }
+    }
+
+    /// Whether the rules were generated from other stylesheets, or in an environment where other conditional rules
+    /// may apply.
+    fn is_stale(&self, stylesheets: &[CSSStyleSheet], absolute_ctx: &AbsoluteContext) -> bool {
+        self.environment != Some(RuleEnvironment::from(absolute_ctx)) || self.stylesheets != stylesheets
+    }
+}
+
 /// Represents the style tree,
 ///
 /// It is a hierarchical structure of styled nodes corresponding to the DOM tree. Each node in the style tree
@@ -31,6 +93,25 @@ pub struct PropertyRegistry {
 pub struct StyleTree {
     /// The styled nodes corresponding to the DOM nodes. Accessed via the `NodeId` as the index.
     nodes: Vec<ComputedStyle>,
+
+    /// The `::before` and `::after` pseudo-elements which generate a box, keyed by their element.
+    generated: HashMap<(NodeId, PseudoElement), GeneratedContent>,
+
+    /// The styles of the `::part()` pseudo-elements of `<progress>` and `<meter>` elements, keyed by their element.
+    parts: HashMap<NodeId, PartStyles>,
+
+    /// The styles of the `::first-line`, `::first-letter`, `::selection` and `::placeholder` pseudo-elements, keyed by
+    /// their element.
+    pseudo_styles: HashMap<(NodeId, PseudoElement), ComputedStyle>,
+
+    /// The containers the `@container` rules were matched against.
+    containers: ContainerSizes,
+
+    /// Whether the stylesheets contain `@container` rules.
+    container_queries: bool,
+
+    /// The rules the styles were computed from.
+    rule_cache: RuleCache,
 }
 
 impl StyleTree {
@@ -43,30 +124,239 @@ impl StyleTree {
         dom: &DocumentRoot,
         stylesheets: &[CSSStyleSheet],
     ) -> Self {
-        let mut property_registry = PropertyRegistry::default();
-        let rules = GeneratedRule::build(stylesheets, &mut property_registry, absolute_ctx);
-        let rule_index = RuleIndex::build(&rules);
+        Self::build_with_containers(preferences, absolute_ctx, dom, stylesheets, ContainerSizes::default())
+    }
+
+    /// Builds the style tree like [`StyleTree::build`], matching the `@container` rules against containers measured by
+    /// a layout of the document. Without containers none of these rules apply.
+    #[must_use]
+    pub fn build_with_containers(
+        preferences: Option<&BrowserPreferences>,
+        absolute_ctx: &AbsoluteContext,
+        dom: &DocumentRoot,
+        stylesheets: &[CSSStyleSheet],
+        containers: ContainerSizes,
+    ) -> Self {
+        Self::build_from_rules(preferences, absolute_ctx, dom, RuleCache::new(stylesheets, absolute_ctx), containers)
+    }
+
+    /// Builds the style tree from rules already generated from the stylesheets.
+    fn build_from_rules(
+        preferences: Option<&BrowserPreferences>,
+        absolute_ctx: &AbsoluteContext,
+        dom: &DocumentRoot,
+        mut rule_cache: RuleCache,
+        containers: ContainerSizes,
+    ) -> Self {
+        let rules = Rules {
+            generated: &rule_cache.rules,
+            index: &rule_cache.index,
+            containers: &containers,
+        };
+        let ctx = CascadeContext {
+            preferences,
+            absolute_ctx,
+            dom,
+            rules: &rules,
+        };
+        let property_registry = &mut rule_cache.property_registry;
 
         let mut styles = Vec::with_capacity(dom.nodes.len());
 
         for node in &dom.nodes {
-            let computed_style = ComputedStyle::from_node(
-                preferences,
-                absolute_ctx,
-                node.id,
-                dom,
-                &Rules {
-                    generated: &rules,
-                    index: &rule_index,
-                },
-                &mut property_registry,
-                &styles,
-            );
+            let computed_style = ComputedStyle::from_node(&ctx, node.id, None, property_registry, &styles);
 
             styles.push(computed_style);
         }
 
-        Self { nodes: styles }
+        let generated = ContentGenerator::new(&ctx, property_registry, &styles).generate();
+        let parts = style_parts(&ctx, &dom.nodes, property_registry, &styles);
+        let pseudo_styles = style_pseudo_elements(&ctx, property_registry, &styles);
+
+        Self {
+            nodes: styles,
+            generated,
+            parts,
+            pseudo_styles,
+            container_queries: rule_cache
+                .rules
+                .iter()
+                .any(|rule| rule.container_query.is_some()),
+            containers,
+            rule_cache,
+        }
+    }
+
+    /// Whether the stylesheets contain `@container` rules, which need the sizes of the containers from a layout of the
+    /// document to apply.
+    #[must_use]
+    pub const fn has_container_queries(&self) -> bool {
+        self.container_queries
+    }
+
+    /// The containers the `@container` rules were matched against.
+    #[must_use]
+    pub const fn containers(&self) -> &ContainerSizes {
+        &self.containers
+    }
+
+    /// The invalidated nodes together with the siblings after them, which `+` and `~` selectors can match through an
+    /// invalidated node.
+    fn with_later_siblings(dom: &DocumentRoot, dirty: &StyleInvalidationSet) -> HashSet<NodeId> {
+        let mut stale: HashSet<NodeId> = dirty.iter().collect();
+
+        for node_id in dirty.iter() {
+            let Some(node) = dom.nodes.get(*node_id) else {
+                continue;
+            };
+
+            let siblings = node
+                .parent
+                .map_or(&dom.root_nodes, |parent| &dom.nodes[*parent].children);
+            if let Some(position) = siblings.iter().position(|sibling| *sibling == node_id) {
+                stale.extend(&siblings[position + 1..]);
+            }
+        }
+
+        stale
+    }
+
+    /// Recomputes the styles of the invalidated nodes, the siblings after them and their descendants in place, keeping
+    /// the styles of every other node. Descendants are recomputed since they may inherit from, or be matched through, a
+    /// changed node, and later siblings since `+` and `~` selectors may match them through it.
+    ///
+    /// The rules generated from the stylesheets are kept between calls, and only generated again when the stylesheets,
+    /// the viewport or the preferences the conditional rules depend on have changed, in which case every node is
+    /// recomputed.
+    ///
+    /// # Arguments
+    /// * `preferences` - The browser preferences used when computing styles.
+    /// * `absolute_ctx` - The absolute context for resolving relative values.
+    /// * `dom` - The mutated DOM tree.
+    /// * `stylesheets` - The stylesheets of the document.
+    /// * `dirty` - The nodes whose attributes or state have changed.
+    ///
+    /// # Returns
+    /// The nodes whose computed style changed, split by whether they need layout or only repaint. If the number of
+    /// nodes in the DOM or the rules changed the whole tree is rebuilt and every node is marked for layout.
+    pub fn recompute(
+        &mut self,
+        preferences: Option<&BrowserPreferences>,
+        absolute_ctx: &AbsoluteContext,
+        dom: &DocumentRoot,
+        stylesheets: &[CSSStyleSheet],
+        dirty: &StyleInvalidationSet,
+    ) -> LayoutInvalidationSet {
+        let mut invalidated = LayoutInvalidationSet::new();
+
+        let rules_changed = self.rule_cache.is_stale(stylesheets, absolute_ctx);
+        if rules_changed || self.nodes.len() != dom.nodes.len() {
+            let containers = std::mem::take(&mut self.containers);
+            let rule_cache = if rules_changed {
+                RuleCache::new(stylesheets, absolute_ctx)
+            } else {
+                std::mem::take(&mut self.rule_cache)
+            };
+            *self = Self::build_from_rules(preferences, absolute_ctx, dom, rule_cache, containers);
+            for node in &dom.nodes {
+                invalidated.mark_layout(node.id);
+            }
+            return invalidated;
+        }
+
+        if dirty.is_empty() {
+            return invalidated;
+        }
+
+        let rules = Rules {
+            generated: &self.rule_cache.rules,
+            index: &self.rule_cache.index,
+            containers: &self.containers,
+        };
+        let ctx = CascadeContext {
+            preferences,
+            absolute_ctx,
+            dom,
+            rules: &rules,
+        };
+        let property_registry = &mut self.rule_cache.property_registry;
+        let dirty = Self::with_later_siblings(dom, dirty);
+        let mut recomputed = HashSet::new();
+
+        // Parents always precede their children in the DOM, so a parent is up to date before its children are visited.
+        for node in &dom.nodes {
+            let stale = dirty.contains(&node.id)
+                || node
+                    .parent
+                    .is_some_and(|parent| recomputed.contains(&parent));
+            if !stale {
+                continue;
+            }
+
+            let computed_style = ComputedStyle::from_node(&ctx, node.id, None, property_registry, &self.nodes);
+
+            recomputed.insert(node.id);
+
+            let old_style = &self.nodes[*node.id];
+            if *old_style == computed_style {
+                continue;
+            }
+
+            if old_style.requires_layout(&computed_style) {
+                invalidated.mark_layout(node.id);
+            } else {
+                invalidated.mark_repaint(node.id);
+            }
+
+            self.nodes[*node.id] = computed_style;
+        }
+
+        // Counters make the content of a pseudo-element depend on every element before it in the document, so it is
+        // generated again for the whole document.
+        let generated = ContentGenerator::new(&ctx, property_registry, &self.nodes).generate();
+
+        // The text of a pseudo-element is laid out as part of its element, so a repaint of the element alone would paint
+        // it with the colors of the element.
+        for key in self.generated.keys().chain(generated.keys()) {
+            if self.generated.get(key) != generated.get(key) || invalidated.needs_repaint(key.0) {
+                invalidated.mark_layout(key.0);
+            }
+        }
+
+        self.generated = generated;
+
+        let restyled = dom
+            .nodes
+            .iter()
+            .filter(|node| recomputed.contains(&node.id));
+        let mut parts = style_parts(&ctx, restyled, property_registry, &self.nodes);
+
+        // Only the parts of a recomputed node can change. The colors of the parts are resolved by the layout of their
+        // element.
+        for node_id in &recomputed {
+            let part_styles = parts.remove(node_id);
+            if self.parts.get(node_id) != part_styles.as_ref() {
+                invalidated.mark_layout(*node_id);
+            }
+
+            match part_styles {
+                Some(part_styles) => self.parts.insert(*node_id, part_styles),
+                None => self.parts.remove(node_id),
+            };
+        }
+
+        let pseudo_styles = style_pseudo_elements(&ctx, property_registry, &self.nodes);
+
+        // The pseudo-elements can change the font of the text they style.
+        for key in self.pseudo_styles.keys().chain(pseudo_styles.keys()) {
+            if self.pseudo_styles.get(key) != pseudo_styles.get(key) {
+                invalidated.mark_layout(key.0);
+            }
+        }
+
+        self.pseudo_styles = pseudo_styles;
+
+        invalidated
     }
 
     pub fn total_nodes(&self) -> usize {
@@ -76,11 +366,41 @@ impl StyleTree {
     pub fn get(&self, node_id: NodeId) -> Option<&ComputedStyle> {
         self.nodes.get(*node_id)
     }
+
+    /// The generated content of a `::before` or `::after` pseudo-element of a node.
+    ///
+    /// # Returns
+    /// The style and text of the pseudo-element, or `None` if its `content` does not generate a box.
+    #[must_use]
+    pub fn pseudo_element(&self, node_id: NodeId, pseudo_element: PseudoElement) -> Option<&GeneratedContent> {
+        self.generated.get(&(node_id, pseudo_element))
+    }
+
+    /// The style of a `::first-line`, `::first-letter` or `::placeholder` pseudo-element of a node.
+    ///
+    /// # Returns
+    /// The style of the pseudo-element, or `None` if no rule styles it or it does not apply to the node.
+    #[must_use]
+    pub fn pseudo_element_style(&self, node_id: NodeId, pseudo_element: PseudoElement) -> Option<&ComputedStyle> {
+        self.pseudo_styles.get(&(node_id, pseudo_element))
+    }
+
+    /// The styles of the `::part()` pseudo-elements of a `<progress>` or `<meter>` element.
+    ///
+    /// # Returns
+    /// The styles of the parts, or `None` if none of them is styled.
+    #[must_use]
+    pub fn parts(&self, node_id: NodeId) -> Option<&PartStyles> {
+        self.parts.get(&node_id)
+    }
 }
 
 impl From<Vec<ComputedStyle>> for StyleTree {
     fn from(nodes: Vec<ComputedStyle>) -> Self {
-        Self { nodes }
+        Self {
+            nodes,
+            ..Self::default()
+        }
     }
 }
 
@@ -99,3 +419,70 @@ impl Index<&NodeId> for StyleTree {
         &self.nodes[**index]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use css_cssom::CssParser;
+    use html_dom::{Element, HtmlTag, NodeData, Tag};
+    use url::Url;
+
+    use super::*;
+    use crate::Color4f;
+
+    fn element(tag: HtmlTag) -> NodeData {
+        NodeData::Element(Element::new(Tag::Html(tag), HashSet::new(), HashMap::new()))
+    }
+
+    #[test]
+    fn test_recompute_later_siblings() {
+        let mut dom = DocumentRoot::new();
+        let body = dom.push_node(&element(HtmlTag::Body), None);
+        let div = dom.push_node(&element(HtmlTag::Div), Some(body));
+        let first = dom.push_node(&element(HtmlTag::P), Some(body));
+        let second = dom.push_node(&element(HtmlTag::P), Some(body));
+
+        let css = ".on + p { color: red } .on ~ p { background-color: blue }";
+        let stylesheets = [CSSStyleSheet::from(
+            CssParser::default().parse_css(css, false),
+        )];
+        let url = Url::parse("http://localhost").unwrap();
+        let absolute_ctx = AbsoluteContext::default_url(&url);
+        let mut tree = StyleTree::build(None, &absolute_ctx, &dom, &stylesheets);
+
+        let NodeData::Element(div_element) = &mut dom.nodes[*div].data else {
+            unreachable!();
+        };
+        div_element.class_set = Some(HashSet::from(["on".to_string()]));
+
+        let dirty = StyleInvalidationSet::from_iter([div]);
+        let invalidated = tree.recompute(None, &absolute_ctx, &dom, &stylesheets, &dirty);
+
+        assert!(invalidated.needs_repaint(first));
+        assert!(invalidated.needs_repaint(second));
+        assert_eq!(tree[first].color, Color4f::rgba(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(tree[second].background_color, Color4f::rgba(0.0, 0.0, 1.0, 1.0));
+        assert_ne!(tree[second].color, Color4f::rgba(1.0, 0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_recompute_changed_stylesheets() {
+        let mut dom = DocumentRoot::new();
+        let body = dom.push_node(&element(HtmlTag::Body), None);
+        let paragraph = dom.push_node(&element(HtmlTag::P), Some(body));
+
+        let parse = |css: &str| {
+            [CSSStyleSheet::from(
+                CssParser::default().parse_css(css, false),
+            )]
+        };
+        let url = Url::parse("http://localhost").unwrap();
+        let absolute_ctx = AbsoluteContext::default_url(&url);
+        let mut tree = StyleTree::build(None, &absolute_ctx, &dom, &parse("p { color: red }"));
+
+        let invalidated =
+            tree.recompute(None, &absolute_ctx, &dom, &parse("p { color: blue }"), &StyleInvalidationSet::new());
+
+        assert!(invalidated.needs_layout(paragraph));
+        assert_eq!(tree[paragraph].color, Color4f::rgba(0.0, 0.0, 1.0, 1.0));
+    }
+}
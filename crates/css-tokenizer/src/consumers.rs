@@ -12,5 +12,8 @@ pub mod string;
 /// Main token consumers module §4.3.1 and §4.3.2
 pub mod token;
 
+/// Unicode-range token consumer, allowed only in the value of a `unicode-range` descriptor
+pub mod unicode_range;
+
 /// URL token consumer §4.3.6 and §4.3.14
 pub mod url;
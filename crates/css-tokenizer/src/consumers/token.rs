@@ -5,6 +5,7 @@ use crate::{
         ident::{consume_ident_like_token, consume_ident_sequence},
         numeric::consume_numeric_token,
         string::consume_string_token,
+        unicode_range::{consume_unicode_range_token, would_start_unicode_range},
     },
     tokenizer::CssTokenizer,
     tokens::{CssToken, CssTokenKind, HashType},
@@ -195,6 +196,9 @@ pub fn consume_token(tokenizer: &mut CssTokenizer) -> CssToken {
             tokenizer.stream.reconsume();
             consume_numeric_token(tokenizer)
         }
+        'u' | 'U' if tokenizer.unicode_ranges_allowed && would_start_unicode_range(tokenizer) => {
+            consume_unicode_range_token(tokenizer)
+        }
         c if is_ident_start_code_point(c) => {
             tokenizer.stream.reconsume();
             consume_ident_like_token(tokenizer)
@@ -0,0 +1,50 @@
+use crate::{
+    char::is_hex_digit,
+    tokenizer::CssTokenizer,
+    tokens::{CssToken, CssTokenKind},
+};
+
+/// Check if the code points after a just consumed `U` would start a unicode-range
+pub fn would_start_unicode_range(tokenizer: &CssTokenizer) -> bool {
+    tokenizer.stream.peek() == Some('+')
+        && tokenizer
+            .stream
+            .peek_at(1)
+            .is_some_and(|c| is_hex_digit(c) || c == '?')
+}
+
+/// Consume a unicode-range token
+///
+/// Unlike the specification every hex digit and `?` is consumed instead of at most six, so that the token keeps the
+/// range as it was written and a range with too many digits is rejected when it is parsed.
+pub fn consume_unicode_range_token(tokenizer: &mut CssTokenizer) -> CssToken {
+    tokenizer.stream.consume(); // +
+
+    let mut text = String::new();
+    while let Some(c) = tokenizer
+        .stream
+        .peek()
+        .filter(|c| is_hex_digit(*c) || *c == '?')
+    {
+        text.push(c);
+        tokenizer.stream.consume();
+    }
+
+    if !text.contains('?')
+        && tokenizer.stream.peek() == Some('-')
+        && tokenizer.stream.peek_at(1).is_some_and(is_hex_digit)
+    {
+        text.push('-');
+        tokenizer.stream.consume();
+
+        while let Some(c) = tokenizer.stream.peek().filter(|c| is_hex_digit(*c)) {
+            text.push(c);
+            tokenizer.stream.consume();
+        }
+    }
+
+    CssToken {
+        kind: CssTokenKind::UnicodeRange(text),
+        position: CssTokenizer::collect_positions(tokenizer),
+    }
+}
@@ -119,6 +119,12 @@ pub struct CssTokenizer {
 
     /// Whether to collect position information for tokens
     pub collect_positions: bool,
+
+    /// Whether the tokenizer is in the value of a `unicode-range` descriptor, where unicode-range tokens are allowed
+    pub(crate) unicode_ranges_allowed: bool,
+
+    /// Whether the last non-whitespace token was a `unicode-range` ident, which a colon makes a descriptor name
+    after_unicode_range_name: bool,
 }
 
 impl CssTokenizer {
@@ -134,6 +140,8 @@ impl CssTokenizer {
             stream: InputStream::new(&preprocessed_input),
             errors: Vec::new(),
             collect_positions,
+            unicode_ranges_allowed: false,
+            after_unicode_range_name: false,
         }
     }
 
@@ -174,6 +182,28 @@ impl CssTokenizer {
         }
     }
 
+    /// Tracks whether the next tokens are in the value of a `unicode-range` descriptor. The specification has the
+    /// parser allow unicode-range tokens while it consumes the value of the descriptor, since the tokenizer runs ahead
+    /// of the parser here the value is instead recognized by the `unicode-range` ident and colon before it, and ends
+    /// with the declaration.
+    fn update_unicode_ranges_allowed(&mut self, kind: &CssTokenKind) {
+        match kind {
+            CssTokenKind::Whitespace => {}
+            CssTokenKind::Colon if self.after_unicode_range_name => {
+                self.unicode_ranges_allowed = true;
+                self.after_unicode_range_name = false;
+            }
+            CssTokenKind::Ident(name) if !self.unicode_ranges_allowed => {
+                self.after_unicode_range_name = name.eq_ignore_ascii_case("unicode-range");
+            }
+            CssTokenKind::Semicolon | CssTokenKind::OpenCurly | CssTokenKind::CloseCurly => {
+                self.unicode_ranges_allowed = false;
+                self.after_unicode_range_name = false;
+            }
+            _ => self.after_unicode_range_name = false,
+        }
+    }
+
     /// Preprocess the input string according to the CSS specification (§3.3)
     ///
     /// # Arguments
@@ -221,6 +251,7 @@ impl Iterator for CssTokenizer {
 
     fn next(&mut self) -> Option<Self::Item> {
         let token = consume_token(self);
+        self.update_unicode_ranges_allowed(&token.kind);
 
         if matches!(token.kind, CssTokenKind::Eof) {
             None
@@ -441,6 +472,39 @@ mod tests {
         assert!(matches!(&tokens[0].kind, CssTokenKind::Ident(s) if s == "élément"));
     }
 
+    #[test]
+    fn test_unicode_range() {
+        let kinds = |css: &str| {
+            CssTokenizer::tokenize(css, false)
+                .into_iter()
+                .map(|token| token.kind)
+                .filter(|kind| *kind != CssTokenKind::Whitespace)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            kinds("unicode-range: U+4E00-9FFF, u+0??; color: U+1"),
+            vec![
+                CssTokenKind::Ident("unicode-range".into()),
+                CssTokenKind::Colon,
+                CssTokenKind::UnicodeRange("4E00-9FFF".into()),
+                CssTokenKind::Comma,
+                CssTokenKind::UnicodeRange("0??".into()),
+                CssTokenKind::Semicolon,
+                CssTokenKind::Ident("color".into()),
+                CssTokenKind::Colon,
+                CssTokenKind::Ident("U".into()),
+                CssTokenKind::Number(NumericValue::Integer(1)),
+            ]
+        );
+
+        assert_eq!(
+            kinds("u+a { unicode-range : U+0E00 }")[..2],
+            [CssTokenKind::Ident("u".into()), CssTokenKind::Delim('+')]
+        );
+        assert_eq!(kinds("unicode-range: U+0E00")[2], CssTokenKind::UnicodeRange("0E00".into()));
+    }
+
     #[test]
     fn test_identifier_with_escape() {
         let tokens = CssTokenizer::tokenize(r"\31 abc", true);
@@ -157,6 +157,10 @@ pub enum CssTokenKind {
 
     /// End of file marker (not emitted, used internally)
     Eof,
+
+    ///\<unicode-range-token\>: A range of code points after `U+` as it was written (e.g. `4E00-9FFF` or `4??`), only
+    /// emitted in the value of a `unicode-range` descriptor
+    UnicodeRange(String),
 }
 
 impl Display for CssTokenKind {
@@ -191,6 +195,7 @@ impl Display for CssTokenKind {
             Self::OpenCurly => write!(f, "{{"),
             Self::CloseCurly => write!(f, "}}"),
             Self::Eof => Ok(()),
+            Self::UnicodeRange(range) => write!(f, "U+{range}"),
         }
     }
 }
@@ -1,6 +1,7 @@
+use css_cssom::{ComponentValue, ComponentValueStream, CssTokenKind};
 use strum::EnumString;
 
-use crate::combination::LengthPercentage;
+use crate::{CSSParsable, combination::LengthPercentage, error::CssValueError};
 
 #[derive(Debug, Clone, Default, Copy, PartialEq, Eq)]
 pub enum Attachment {
@@ -18,10 +19,12 @@ pub enum Attachment {
 
 /// The `background-blend-mode` property specifies the blending mode for each background layer (color and image) of an element.
 ///
-/// It determines how the background layers are blended together and with the content of the element.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+/// It determines how the background layers are blended together and with the content of the element. The same modes
+/// are used by `mix-blend-mode`, which blends a whole element with the content behind it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumString)]
 #[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
 pub enum BlendMode {
+    #[default]
     Normal,
     Multiply,
     Screen,
@@ -40,6 +43,87 @@ pub enum BlendMode {
     Luminosity,
 }
 
+impl CSSParsable for BlendMode {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        stream
+            .next_non_whitespace()
+            .ok_or(CssValueError::UnexpectedEndOfInput)
+            .and_then(|cv| match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident) => ident
+                        .parse()
+                        .map_err(|_| CssValueError::InvalidValue(format!("Invalid blend mode: {ident}"))),
+                    _ => Err(CssValueError::InvalidToken(token.kind.clone())),
+                },
+                cvs => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            })
+    }
+}
+
+/// The `isolation` property specifies whether an element creates a new stacking context that isolates the blending of
+/// its descendants, so that an element with a `mix-blend-mode` only blends with the content of the group.
+///
+/// <https://drafts.fxtf.org/compositing/#isolation>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum Isolation {
+    #[default]
+    Auto,
+    Isolate,
+}
+
+impl CSSParsable for Isolation {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        stream
+            .next_non_whitespace()
+            .ok_or(CssValueError::UnexpectedEndOfInput)
+            .and_then(|cv| match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident) => ident
+                        .parse()
+                        .map_err(|_| CssValueError::InvalidValue(format!("Invalid isolation value: {ident}"))),
+                    _ => Err(CssValueError::InvalidToken(token.kind.clone())),
+                },
+                cvs => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            })
+    }
+}
+
+/// The `mask-mode` property specifies whether the alpha or the luminance of a mask image decides how much of the
+/// element is shown.
+///
+/// <https://drafts.fxtf.org/css-masking/#the-mask-mode>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum MaskMode {
+    /// The alpha channel of the mask is used.
+    Alpha,
+
+    /// The luminance of the mask, multiplied by its alpha, is used.
+    Luminance,
+
+    /// Luminance for SVG `<mask>` elements and alpha for every other image. This is the initial value.
+    #[default]
+    MatchSource,
+}
+
+impl CSSParsable for MaskMode {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        stream
+            .next_non_whitespace()
+            .ok_or(CssValueError::UnexpectedEndOfInput)
+            .and_then(|cv| match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident) => ident
+                        .parse()
+                        .map_err(|_| CssValueError::InvalidValue(format!("Invalid mask mode: {ident}"))),
+                    _ => Err(CssValueError::InvalidToken(token.kind.clone())),
+                },
+                cvs => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            })
+    }
+}
+
 /// The `background-origin` property specifies the background painting area for an element.
 ///
 /// It determines where the background image or color is applied in relation to the content, padding, and border of the element.
@@ -129,3 +213,48 @@ impl Default for Size {
         Self::WidthHeight(WidthHeightSize::Auto, Some(WidthHeightSize::Auto))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use css_cssom::CssToken;
+
+    use super::*;
+
+    fn ident(keyword: &str) -> Vec<ComponentValue> {
+        vec![ComponentValue::Token(CssToken {
+            kind: CssTokenKind::Ident(keyword.to_string()),
+            position: Default::default(),
+        })]
+    }
+
+    #[test]
+    fn test_parse_blend_mode() {
+        let input = ident("color-dodge");
+        assert_eq!(BlendMode::parse(&mut ComponentValueStream::new(&input)), Ok(BlendMode::ColorDodge));
+
+        let input = ident("Luminosity");
+        assert_eq!(BlendMode::parse(&mut ComponentValueStream::new(&input)), Ok(BlendMode::Luminosity));
+
+        let input = ident("dissolve");
+        assert!(BlendMode::parse(&mut ComponentValueStream::new(&input)).is_err());
+    }
+
+    #[test]
+    fn test_parse_isolation() {
+        let input = ident("isolate");
+        assert_eq!(Isolation::parse(&mut ComponentValueStream::new(&input)), Ok(Isolation::Isolate));
+        assert_eq!(Isolation::default(), Isolation::Auto);
+    }
+
+    #[test]
+    fn test_parse_mask_mode() {
+        let input = ident("LUMINANCE");
+        assert_eq!(MaskMode::parse(&mut ComponentValueStream::new(&input)), Ok(MaskMode::Luminance));
+
+        let input = ident("match-source");
+        assert_eq!(MaskMode::parse(&mut ComponentValueStream::new(&input)), Ok(MaskMode::MatchSource));
+
+        let input = ident("luminosity");
+        assert!(MaskMode::parse(&mut ComponentValueStream::new(&input)).is_err());
+    }
+}
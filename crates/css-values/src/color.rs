@@ -1,6 +1,7 @@
 use std::ops::RangeInclusive;
 
 use css_cssom::{ComponentValue, ComponentValueStream, CssTokenKind};
+use strum::EnumString;
 
 use crate::{
     CSSParsable,
@@ -329,3 +330,34 @@ impl CSSParsable for Color {
         }
     }
 }
+
+/// Whether the colors of an element are replaced by the system colors while forced colors are active.
+///
+/// <https://drafts.csswg.org/css-color-adjust/#forced-color-adjust-prop>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum ForcedColorAdjust {
+    #[default]
+    Auto,
+    None,
+
+    /// Like `none`, except that an inherited `color` keeps the forced color of the parent.
+    PreserveParentColor,
+}
+
+impl CSSParsable for ForcedColorAdjust {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        stream
+            .next_non_whitespace()
+            .ok_or(CssValueError::UnexpectedEndOfInput)
+            .and_then(|cv| match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident) => ident.parse().map_err(|_| {
+                        CssValueError::InvalidValue(format!("Invalid forced-color-adjust value: {ident}"))
+                    }),
+                    _ => Err(CssValueError::InvalidToken(token.kind.clone())),
+                },
+                cvs => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            })
+    }
+}
@@ -1,4 +1,5 @@
 use css_cssom::{ComponentValue, CssTokenKind, Function};
+use strum::EnumString;
 
 use crate::{
     calc::{CalcDomain, CalcExpression, is_math_function},
@@ -8,6 +9,32 @@ use crate::{
     quantity::Angle,
 };
 
+/// The predefined color spaces of the `color()` function.
+///
+/// <https://drafts.csswg.org/css-color-4/#predefined>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+#[strum(ascii_case_insensitive)]
+pub enum ColorSpace {
+    #[strum(serialize = "srgb")]
+    Srgb,
+    #[strum(serialize = "srgb-linear")]
+    SrgbLinear,
+    #[strum(serialize = "display-p3")]
+    DisplayP3,
+    #[strum(serialize = "a98-rgb")]
+    A98Rgb,
+    #[strum(serialize = "prophoto-rgb")]
+    ProphotoRgb,
+    #[strum(serialize = "rec2020")]
+    Rec2020,
+    #[strum(serialize = "xyz-d50")]
+    XyzD50,
+
+    /// `xyz-d65`, or `xyz` for short.
+    #[strum(serialize = "xyz-d65", serialize = "xyz")]
+    XyzD65,
+}
+
 /// Represents a color specified using functional notation, which can be in the form of `srgba()` functions (e.g., `rgb()`, `rgba()`, `hsl()`, `hsla()`, `hwb()`) or `color()` functions (e.g., `lab()`, `oklab()`).
 #[derive(Debug, Clone, PartialEq)]
 pub enum ColorFunction {
@@ -64,12 +91,17 @@ pub enum ColorFunction {
     /// * H: Hue angle in degrees
     /// * alpha: Opacity (0.0 to 1.0)
     Oklch(ColorValue, ColorValue, Hue, Alpha),
+
+    /// `color()` function with a color space and its three components and optional alpha
+    ///
+    /// * Components: 0 to 1 or 0% to 100%, values outside the range are kept
+    /// * alpha: Opacity (0.0 to 1.0)
+    Color(ColorSpace, ColorValue, ColorValue, ColorValue, Alpha),
     //
     // TODO: ictcp()
     //       jzazbz()
     //       jzczhz()
     //       alpha()
-    //       color()
     //       hdr-color()
 }
 
@@ -279,6 +311,29 @@ impl TryFrom<&Function> for ColorFunction {
                 [Some(l), Some(c), Some(h)] => Ok(Self::Oklch(l, c, Hue::try_from(h)?, raw.alpha)),
                 _ => Err(CssValueError::InvalidValue("Missing components in oklab() or oklch()".to_string())),
             }
+        } else if func.name.eq_ignore_ascii_case("color") {
+            let mut values = func
+                .value
+                .iter()
+                .skip_while(|cv| matches!(cv, ComponentValue::Token(token) if token.kind == CssTokenKind::Whitespace));
+
+            let space = match values.next() {
+                Some(ComponentValue::Token(token)) => match &token.kind {
+                    CssTokenKind::Ident(ident) => ident
+                        .parse::<ColorSpace>()
+                        .map_err(|_| CssValueError::InvalidValue(format!("Unsupported color space: {ident}")))?,
+                    kind => return Err(CssValueError::InvalidToken(kind.clone())),
+                },
+                Some(cv) => return Err(CssValueError::InvalidComponentValue(cv.clone())),
+                None => return Err(CssValueError::InvalidValue("Missing color space in color()".to_string())),
+            };
+
+            let raw = Self::parse_color_components(&values.cloned().collect::<Vec<_>>())?;
+
+            match raw.channels {
+                [Some(c1), Some(c2), Some(c3)] => Ok(Self::Color(space, c1, c2, c3, raw.alpha)),
+                _ => Err(CssValueError::InvalidValue("Missing components in color()".to_string())),
+            }
         } else {
             Err(CssValueError::InvalidFunction(func.name.clone()))
         }
@@ -530,6 +585,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_color_parsing() {
+        let mut color = css_color_fn!("color", 1, "50%", 0, 0.5);
+        let ComponentValue::Function(function) = &mut color[0] else {
+            unreachable!();
+        };
+        function.value.splice(
+            0..0,
+            [
+                ComponentValue::Token(CssToken {
+                    kind: CssTokenKind::Ident("display-p3".to_string()),
+                    position: None,
+                }),
+                ComponentValue::Token(CssToken {
+                    kind: CssTokenKind::Whitespace,
+                    position: None,
+                }),
+            ],
+        );
+
+        let parsed = Color::parse(&mut color.as_slice().into()).unwrap();
+        assert_eq!(
+            parsed,
+            Color::Base(ColorBase::Function(ColorFunction::Color(
+                ColorSpace::DisplayP3,
+                ColorValue::Number(1.0),
+                ColorValue::Percentage(Percentage::new(50.0)),
+                ColorValue::Number(0.0),
+                Alpha::new(0.5)
+            )))
+        );
+
+        assert_eq!("XYZ".parse::<ColorSpace>(), Ok(ColorSpace::XyzD65));
+        assert_eq!("rec2020".parse::<ColorSpace>(), Ok(ColorSpace::Rec2020));
+        assert!("cmyk".parse::<ColorSpace>().is_err());
+    }
+
     #[test]
     fn test_oklch_parsing() {
         let oklch = css_color_fn!("oklch", 0.5, 0.1, 120, "none");
@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use crate::media::ColorScheme;
+
 /// System colors defined in CSS specifications.
 ///
 /// These are colors that correspond to the user's operating system or browser theme settings,
@@ -105,6 +107,31 @@ impl SystemColor {
             Self::Deprecated(deprecated) => deprecated.to_hex(),
         }
     }
+
+    /// Converts the `SystemColor` to its hexadecimal string representation in the high contrast palette used while
+    /// forced colors are active, which like the high contrast themes of the operating systems has a light and a dark
+    /// variant. Colors without a high contrast variant keep their regular value.
+    #[must_use]
+    pub const fn to_forced_hex(self, color_scheme: ColorScheme) -> Option<&'static str> {
+        match color_scheme {
+            ColorScheme::Light => match self {
+                Self::ButtonFace | Self::Canvas | Self::Field | Self::HighlightText => Some("#FFFFFF"),
+                Self::ButtonBorder | Self::ButtonText | Self::CanvasText | Self::FieldText => Some("#000000"),
+                Self::ActiveText | Self::LinkText | Self::VisitedText => Some("#00009F"),
+                Self::GrayText => Some("#600000"),
+                Self::Highlight => Some("#37006E"),
+                _ => self.to_hex(),
+            },
+            ColorScheme::Dark => match self {
+                Self::ButtonFace | Self::Canvas | Self::Field | Self::HighlightText => Some("#000000"),
+                Self::ButtonBorder | Self::ButtonText | Self::CanvasText | Self::FieldText => Some("#FFFFFF"),
+                Self::ActiveText | Self::LinkText | Self::VisitedText => Some("#FFFF00"),
+                Self::GrayText => Some("#3FF23F"),
+                Self::Highlight => Some("#1AEBFF"),
+                _ => self.to_hex(),
+            },
+        }
+    }
 }
 
 /// Deprecated system colors that were defined in older versions of CSS but are no longer recommended for use.
@@ -256,4 +283,12 @@ mod tests {
         let color: SystemColor = "activeBorder".parse().unwrap();
         assert_eq!(color, SystemColor::Deprecated(DeprecatedColor::ActiveBorder));
     }
+
+    #[test]
+    fn forced_palette_follows_color_scheme() {
+        assert_eq!(SystemColor::Canvas.to_forced_hex(ColorScheme::Dark), Some("#000000"));
+        assert_eq!(SystemColor::CanvasText.to_forced_hex(ColorScheme::Dark), Some("#FFFFFF"));
+        assert_eq!(SystemColor::Canvas.to_forced_hex(ColorScheme::Light), Some("#FFFFFF"));
+        assert_eq!(SystemColor::Mark.to_forced_hex(ColorScheme::Dark), SystemColor::Mark.to_hex());
+    }
 }
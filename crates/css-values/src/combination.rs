@@ -26,6 +26,12 @@ impl From<Length> for LengthPercentage {
     }
 }
 
+impl Default for LengthPercentage {
+    fn default() -> Self {
+        Self::Length(Length::zero())
+    }
+}
+
 impl From<Percentage> for LengthPercentage {
     fn from(percentage: Percentage) -> Self {
         Self::Percentage(percentage)
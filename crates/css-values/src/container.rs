@@ -0,0 +1,141 @@
+//! This module contains the values of the `container-type` and `container-name` properties, which make an element a
+//! container that the `@container` rules of its descendants are matched against.
+
+use css_cssom::{ComponentValue, ComponentValueStream, CssTokenKind};
+use strum::EnumString;
+
+use crate::{CSSParsable, error::CssValueError};
+
+/// Represents the CSS `container-type` property, which dimensions of an element container queries can match.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/container-type>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, EnumString)]
+#[strum(serialize_all = "kebab_case", ascii_case_insensitive)]
+pub enum ContainerType {
+    /// The element is not a size container.
+    #[default]
+    Normal,
+
+    /// Queries can match both the width and the height of the element.
+    Size,
+
+    /// Queries can match the width of the element.
+    InlineSize,
+}
+
+impl ContainerType {
+    /// Whether queries can match the width of the container.
+    #[must_use]
+    pub const fn queries_width(self) -> bool {
+        matches!(self, Self::Size | Self::InlineSize)
+    }
+
+    /// Whether queries can match the height of the container.
+    #[must_use]
+    pub const fn queries_height(self) -> bool {
+        matches!(self, Self::Size)
+    }
+}
+
+impl CSSParsable for ContainerType {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        stream
+            .next_non_whitespace()
+            .map_or(Err(CssValueError::ExpectedComponentValue), |cv| match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident) => ident
+                        .parse()
+                        .map_err(|_| CssValueError::InvalidValue(format!("Invalid container-type value: {ident}"))),
+                    _ => Err(CssValueError::InvalidToken(token.kind.clone())),
+                },
+                cvs => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            })
+    }
+}
+
+/// Represents the CSS `container-name` property, the names a `@container` rule can select the container by, or `none`
+/// for an empty list.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/container-name>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContainerName(pub Vec<String>);
+
+impl ContainerName {
+    /// Whether the container has the given name.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.iter().any(|own| own == name)
+    }
+}
+
+impl CSSParsable for ContainerName {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        let mut names = Vec::new();
+
+        while let Some(cv) = stream.next_non_whitespace() {
+            let ComponentValue::Token(token) = cv else {
+                return Err(CssValueError::InvalidComponentValue(cv.clone()));
+            };
+
+            match &token.kind {
+                CssTokenKind::Ident(ident) if names.is_empty() && ident.eq_ignore_ascii_case("none") => {
+                    return Ok(Self::default());
+                }
+                CssTokenKind::Ident(ident)
+                    if ["none", "and", "or", "not"]
+                        .iter()
+                        .any(|reserved| ident.eq_ignore_ascii_case(reserved)) =>
+                {
+                    return Err(CssValueError::InvalidValue(format!("Invalid container name: {ident}")));
+                }
+                CssTokenKind::Ident(ident) => names.push(ident.clone()),
+                kind => return Err(CssValueError::InvalidToken(kind.clone())),
+            }
+        }
+
+        if names.is_empty() {
+            Err(CssValueError::UnexpectedEndOfInput)
+        } else {
+            Ok(Self(names))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use css_cssom::CssToken;
+
+    fn ident(value: &str) -> ComponentValue {
+        ComponentValue::Token(CssToken {
+            kind: CssTokenKind::Ident(value.to_string()),
+            position: Default::default(),
+        })
+    }
+
+    #[test]
+    fn test_parse_container_type() {
+        assert_eq!("inline-size".parse(), Ok(ContainerType::InlineSize));
+        assert_eq!("SIZE".parse(), Ok(ContainerType::Size));
+        assert!(ContainerType::InlineSize.queries_width());
+        assert!(!ContainerType::InlineSize.queries_height());
+    }
+
+    #[test]
+    fn test_parse_container_names() {
+        let input = vec![ident("sidebar"), ident("card")];
+        let mut stream = ComponentValueStream::new(&input);
+
+        let name = ContainerName::parse(&mut stream).unwrap();
+        assert_eq!(name, ContainerName(vec!["sidebar".to_string(), "card".to_string()]));
+        assert!(name.contains("card"));
+    }
+
+    #[test]
+    fn test_parse_container_name_rejects_keywords() {
+        let input = vec![ident("card"), ident("and")];
+        let mut stream = ComponentValueStream::new(&input);
+
+        assert!(ContainerName::parse(&mut stream).is_err());
+    }
+}
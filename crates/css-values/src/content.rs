@@ -0,0 +1,431 @@
+//! This module contains the values of the generated content properties, `content` for the `::before` and `::after`
+//! pseudo-elements, the `counter-reset` and `counter-increment` properties of the counters they can display and the
+//! `quotes` property of the quotation marks they can insert.
+
+use css_cssom::{ComponentValue, ComponentValueStream, CssTokenKind};
+
+use crate::{CSSParsable, error::CssValueError};
+
+/// A single part of the text generated by the `content` property.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentItem {
+    /// A quoted string, inserted as is.
+    String(String),
+
+    /// `attr(name)`, the value of an attribute of the element, or nothing if the attribute is missing.
+    Attr(String),
+
+    /// `counter(name)`, the value of a counter in decimal notation.
+    Counter(String),
+
+    /// `counters(name, separator)`, the values of every nested counter of that name, outermost first, joined by the
+    /// separator.
+    Counters { name: String, separator: String },
+
+    /// `open-quote`, the opening quotation mark of the current nesting level of quotes, which is then increased.
+    OpenQuote,
+
+    /// `close-quote`, decreases the nesting level of quotes and inserts the closing quotation mark of that level.
+    CloseQuote,
+
+    /// `no-open-quote`, increases the nesting level of quotes without inserting anything.
+    NoOpenQuote,
+
+    /// `no-close-quote`, decreases the nesting level of quotes without inserting anything.
+    NoCloseQuote,
+}
+
+/// Represents the CSS `content` property, which generates the text of the `::before` and `::after` pseudo-elements.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/content>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Content {
+    /// Computes to `none` on the `::before` and `::after` pseudo-elements.
+    #[default]
+    Normal,
+
+    /// No pseudo-element is generated.
+    None,
+
+    /// The generated text, concatenated from its parts.
+    Items(Vec<ContentItem>),
+}
+
+impl Content {
+    /// Whether a pseudo-element with this content generates a box.
+    #[must_use]
+    pub const fn generates_box(&self) -> bool {
+        matches!(self, Self::Items(_))
+    }
+}
+
+impl CSSParsable for Content {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        let mut items = Vec::new();
+
+        while let Some(cv) = stream.next_non_whitespace() {
+            let item = match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::String(text) => ContentItem::String(text.clone()),
+                    CssTokenKind::Ident(ident) if items.is_empty() && ident.eq_ignore_ascii_case("normal") => {
+                        return Ok(Self::Normal);
+                    }
+                    CssTokenKind::Ident(ident) if items.is_empty() && ident.eq_ignore_ascii_case("none") => {
+                        return Ok(Self::None);
+                    }
+                    // `initial-quote` is an old name of `open-quote`, still accepted for compatibility.
+                    CssTokenKind::Ident(ident)
+                        if ident.eq_ignore_ascii_case("open-quote") || ident.eq_ignore_ascii_case("initial-quote") =>
+                    {
+                        ContentItem::OpenQuote
+                    }
+                    CssTokenKind::Ident(ident) if ident.eq_ignore_ascii_case("close-quote") => ContentItem::CloseQuote,
+                    CssTokenKind::Ident(ident) if ident.eq_ignore_ascii_case("no-open-quote") => {
+                        ContentItem::NoOpenQuote
+                    }
+                    CssTokenKind::Ident(ident) if ident.eq_ignore_ascii_case("no-close-quote") => {
+                        ContentItem::NoCloseQuote
+                    }
+                    _ => return Err(CssValueError::InvalidToken(token.kind.clone())),
+                },
+                ComponentValue::Function(func) if func.name.eq_ignore_ascii_case("attr") => {
+                    ContentItem::Attr(function_ident(&func.value)?)
+                }
+                ComponentValue::Function(func) if func.name.eq_ignore_ascii_case("counter") => {
+                    ContentItem::Counter(function_ident(&func.value)?)
+                }
+                ComponentValue::Function(func) if func.name.eq_ignore_ascii_case("counters") => {
+                    counters_arguments(&func.value)?
+                }
+                ComponentValue::Function(func) => return Err(CssValueError::InvalidFunction(func.name.clone())),
+                cvs => return Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            };
+
+            items.push(item);
+        }
+
+        if items.is_empty() {
+            Err(CssValueError::UnexpectedEndOfInput)
+        } else {
+            Ok(Self::Items(items))
+        }
+    }
+}
+
+/// Reads the name given as the first argument of `attr()` or `counter()`, any further arguments such as the counter
+/// style are ignored.
+fn function_ident(arguments: &[ComponentValue]) -> Result<String, CssValueError> {
+    let mut stream = ComponentValueStream::new(arguments);
+
+    match stream.next_non_whitespace() {
+        Some(ComponentValue::Token(token)) => match &token.kind {
+            CssTokenKind::Ident(name) => Ok(name.clone()),
+            kind => Err(CssValueError::InvalidToken(kind.clone())),
+        },
+        Some(cvs) => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+        None => Err(CssValueError::UnexpectedEndOfInput),
+    }
+}
+
+/// Reads the name and separator given as the arguments of `counters()`, a following counter style is ignored.
+fn counters_arguments(arguments: &[ComponentValue]) -> Result<ContentItem, CssValueError> {
+    let name = function_ident(arguments)?;
+    let mut stream = ComponentValueStream::new(arguments);
+    stream.next_non_whitespace();
+
+    match stream.next_non_whitespace() {
+        Some(ComponentValue::Token(token)) if token.kind == CssTokenKind::Comma => {}
+        Some(cvs) => return Err(CssValueError::InvalidComponentValue(cvs.clone())),
+        None => return Err(CssValueError::UnexpectedEndOfInput),
+    }
+
+    match stream.next_non_whitespace() {
+        Some(ComponentValue::Token(token)) => match &token.kind {
+            CssTokenKind::String(separator) => Ok(ContentItem::Counters {
+                name,
+                separator: separator.clone(),
+            }),
+            kind => Err(CssValueError::InvalidToken(kind.clone())),
+        },
+        Some(cvs) => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+        None => Err(CssValueError::UnexpectedEndOfInput),
+    }
+}
+
+/// Parses a list of counter names, each optionally followed by an integer, or `none` for an empty list.
+///
+/// # Arguments
+/// * `stream` - The value of the property.
+/// * `default` - The integer of the counters given without one.
+fn parse_counter_list(stream: &mut ComponentValueStream, default: i32) -> Result<Vec<(String, i32)>, CssValueError> {
+    let mut counters: Vec<(String, i32)> = Vec::new();
+
+    while let Some(cv) = stream.next_non_whitespace() {
+        let ComponentValue::Token(token) = cv else {
+            return Err(CssValueError::InvalidComponentValue(cv.clone()));
+        };
+
+        match &token.kind {
+            CssTokenKind::Ident(ident) if counters.is_empty() && ident.eq_ignore_ascii_case("none") => {
+                return Ok(Vec::new());
+            }
+            CssTokenKind::Ident(name) => counters.push((name.clone(), default)),
+            CssTokenKind::Number(value) if value.is_integer() => {
+                let Some((_, integer)) = counters.last_mut() else {
+                    return Err(CssValueError::InvalidToken(token.kind.clone()));
+                };
+
+                *integer = value.to_f64() as i32;
+            }
+            kind => return Err(CssValueError::InvalidToken(kind.clone())),
+        }
+    }
+
+    if counters.is_empty() {
+        Err(CssValueError::UnexpectedEndOfInput)
+    } else {
+        Ok(counters)
+    }
+}
+
+/// Represents the CSS `counter-reset` property, the counters created by an element and their initial values.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/counter-reset>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CounterReset(pub Vec<(String, i32)>);
+
+impl CSSParsable for CounterReset {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        parse_counter_list(stream, 0).map(Self)
+    }
+}
+
+/// Represents the CSS `counter-increment` property, the counters incremented by an element and by how much.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/counter-increment>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CounterIncrement(pub Vec<(String, i32)>);
+
+impl CSSParsable for CounterIncrement {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        parse_counter_list(stream, 1).map(Self)
+    }
+}
+
+/// Represents the CSS `quotes` property, the opening and closing quotation marks of each nesting level of quotes,
+/// outermost first. Levels deeper than the list use the marks of the last pair.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/quotes>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuoteList(pub Vec<(String, String)>);
+
+impl QuoteList {
+    /// The opening quotation mark of the given nesting level, empty if there are no quotation marks.
+    #[must_use]
+    pub fn open(&self, depth: usize) -> &str {
+        self.pair(depth).map_or("", |(open, _)| open)
+    }
+
+    /// The closing quotation mark of the given nesting level, empty if there are no quotation marks.
+    #[must_use]
+    pub fn close(&self, depth: usize) -> &str {
+        self.pair(depth).map_or("", |(_, close)| close)
+    }
+
+    fn pair(&self, depth: usize) -> Option<&(String, String)> {
+        self.0.get(depth).or_else(|| self.0.last())
+    }
+}
+
+/// The English quotation marks, used for `auto`.
+impl Default for QuoteList {
+    fn default() -> Self {
+        Self(vec![
+            ("\u{201c}".to_string(), "\u{201d}".to_string()),
+            ("\u{2018}".to_string(), "\u{2019}".to_string()),
+        ])
+    }
+}
+
+impl CSSParsable for QuoteList {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        let mut strings = Vec::new();
+
+        while let Some(cv) = stream.next_non_whitespace() {
+            let ComponentValue::Token(token) = cv else {
+                return Err(CssValueError::InvalidComponentValue(cv.clone()));
+            };
+
+            match &token.kind {
+                CssTokenKind::Ident(ident) if strings.is_empty() && ident.eq_ignore_ascii_case("none") => {
+                    return Ok(Self(Vec::new()));
+                }
+                CssTokenKind::Ident(ident) if strings.is_empty() && ident.eq_ignore_ascii_case("auto") => {
+                    return Ok(Self::default());
+                }
+                CssTokenKind::String(text) => strings.push(text.clone()),
+                kind => return Err(CssValueError::InvalidToken(kind.clone())),
+            }
+        }
+
+        if strings.is_empty() || strings.len() % 2 != 0 {
+            return Err(CssValueError::InvalidValue(String::from("The quotes property requires pairs of strings")));
+        }
+
+        let mut strings = strings.into_iter();
+        let mut pairs = Vec::new();
+        while let (Some(open), Some(close)) = (strings.next(), strings.next()) {
+            pairs.push((open, close));
+        }
+
+        Ok(Self(pairs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use css_cssom::{CssToken, Function, NumericValue};
+
+    fn token(kind: CssTokenKind) -> ComponentValue {
+        ComponentValue::Token(CssToken {
+            kind,
+            position: Default::default(),
+        })
+    }
+
+    fn function(name: &str, argument: &str) -> ComponentValue {
+        let mut function = Function::new(name.to_string());
+        function.value = vec![token(CssTokenKind::Ident(argument.to_string()))];
+        ComponentValue::Function(function)
+    }
+
+    #[test]
+    fn test_parse_content_items() {
+        let input = vec![
+            function("counter", "item"),
+            token(CssTokenKind::Whitespace),
+            token(CssTokenKind::String(". ".to_string())),
+            function("attr", "title"),
+        ];
+        let mut stream = ComponentValueStream::new(&input);
+
+        assert_eq!(
+            Content::parse(&mut stream).unwrap(),
+            Content::Items(vec![
+                ContentItem::Counter("item".to_string()),
+                ContentItem::String(". ".to_string()),
+                ContentItem::Attr("title".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_counters_with_separator() {
+        let mut function = Function::new("counters".to_string());
+        function.value = vec![
+            token(CssTokenKind::Ident("section".to_string())),
+            token(CssTokenKind::Comma),
+            token(CssTokenKind::Whitespace),
+            token(CssTokenKind::String(".".to_string())),
+        ];
+        let input = vec![ComponentValue::Function(function)];
+        let mut stream = ComponentValueStream::new(&input);
+
+        assert_eq!(
+            Content::parse(&mut stream).unwrap(),
+            Content::Items(vec![ContentItem::Counters {
+                name: "section".to_string(),
+                separator: ".".to_string(),
+            }])
+        );
+    }
+
+    #[test]
+    fn test_parse_counters_requires_separator() {
+        let input = vec![function("counters", "section")];
+        let mut stream = ComponentValueStream::new(&input);
+
+        assert!(Content::parse(&mut stream).is_err());
+    }
+
+    #[test]
+    fn test_parse_content_none() {
+        let input = vec![token(CssTokenKind::Ident("none".to_string()))];
+        let mut stream = ComponentValueStream::new(&input);
+
+        assert_eq!(Content::parse(&mut stream).unwrap(), Content::None);
+        assert!(!Content::None.generates_box());
+    }
+
+    #[test]
+    fn test_parse_counter_increment() {
+        let input = vec![
+            token(CssTokenKind::Ident("chapter".to_string())),
+            token(CssTokenKind::Whitespace),
+            token(CssTokenKind::Ident("section".to_string())),
+            token(CssTokenKind::Whitespace),
+            token(CssTokenKind::Number(NumericValue::from(2))),
+        ];
+        let mut stream = ComponentValueStream::new(&input);
+
+        assert_eq!(
+            CounterIncrement::parse(&mut stream).unwrap(),
+            CounterIncrement(vec![("chapter".to_string(), 1), ("section".to_string(), 2)])
+        );
+    }
+
+    #[test]
+    fn test_parse_quote_keywords() {
+        let input = vec![
+            token(CssTokenKind::Ident("open-quote".to_string())),
+            token(CssTokenKind::Whitespace),
+            token(CssTokenKind::Ident("no-close-quote".to_string())),
+            token(CssTokenKind::Whitespace),
+            token(CssTokenKind::Ident("initial-quote".to_string())),
+        ];
+        let mut stream = ComponentValueStream::new(&input);
+
+        assert_eq!(
+            Content::parse(&mut stream).unwrap(),
+            Content::Items(vec![
+                ContentItem::OpenQuote,
+                ContentItem::NoCloseQuote,
+                ContentItem::OpenQuote
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_quote_list() {
+        let input = vec![
+            token(CssTokenKind::String("«".to_string())),
+            token(CssTokenKind::Whitespace),
+            token(CssTokenKind::String("»".to_string())),
+            token(CssTokenKind::Whitespace),
+            token(CssTokenKind::String("'".to_string())),
+            token(CssTokenKind::Whitespace),
+            token(CssTokenKind::String("'".to_string())),
+        ];
+        let quotes = QuoteList::parse(&mut ComponentValueStream::new(&input)).unwrap();
+
+        assert_eq!(quotes.open(0), "«");
+        assert_eq!(quotes.close(1), "'");
+        assert_eq!(quotes.open(5), "'");
+
+        let input = vec![token(CssTokenKind::String("«".to_string()))];
+        assert!(QuoteList::parse(&mut ComponentValueStream::new(&input)).is_err());
+
+        let input = vec![token(CssTokenKind::Ident("none".to_string()))];
+        let quotes = QuoteList::parse(&mut ComponentValueStream::new(&input)).unwrap();
+        assert_eq!(quotes.open(0), "");
+    }
+
+    #[test]
+    fn test_parse_counter_reset_none() {
+        let input = vec![token(CssTokenKind::Ident("none".to_string()))];
+        let mut stream = ComponentValueStream::new(&input);
+
+        assert_eq!(CounterReset::parse(&mut stream).unwrap(), CounterReset(Vec::new()));
+    }
+}
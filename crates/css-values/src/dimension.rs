@@ -176,6 +176,40 @@ impl CSSParsable for MaxSize {
     }
 }
 
+/// A `contain-intrinsic-width` or `contain-intrinsic-height` value, the size an element with skipped contents is laid
+/// out with instead of the size of its contents.
+///
+/// <https://drafts.csswg.org/css-sizing-4/#intrinsic-size-override>
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ContainIntrinsicLength {
+    #[default]
+    None,
+    Length(Length),
+
+    /// `auto <length>`, the last size the element was laid out with if it has one, and the length otherwise.
+    AutoLength(Length),
+}
+
+impl CSSParsable for ContainIntrinsicLength {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        let cv = stream
+            .next_non_whitespace()
+            .ok_or(CssValueError::UnexpectedEndOfInput)?;
+
+        match cv {
+            ComponentValue::Token(token) => match &token.kind {
+                CssTokenKind::Ident(ident) if ident.eq_ignore_ascii_case("none") => Ok(Self::None),
+                CssTokenKind::Ident(ident) if ident.eq_ignore_ascii_case("auto") => {
+                    Ok(Self::AutoLength(Length::parse(stream)?))
+                }
+                CssTokenKind::Number(num) if num.to_f64() == 0.0 => Ok(Self::Length(Length::zero())),
+                _ => Ok(Self::Length(Length::try_from(token)?)),
+            },
+            cvs => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+        }
+    }
+}
+
 /// Represents a CSS offset value, used for border & padding offsets. It can be a length, percentage, or calc expression.
 #[derive(Debug, Clone, PartialEq)]
 pub enum OffsetValue {
@@ -403,6 +437,35 @@ mod tests {
         assert_eq!(dim, Size::MaxContent);
     }
 
+    #[test]
+    fn test_parse_contain_intrinsic_length() {
+        let ident = |value: &str| {
+            ComponentValue::Token(CssToken {
+                kind: CssTokenKind::Ident(value.to_string()),
+                position: None,
+            })
+        };
+        let length = ComponentValue::Token(CssToken {
+            kind: CssTokenKind::Dimension {
+                value: NumericValue::from(500.0),
+                unit: "px".to_string(),
+            },
+            position: None,
+        });
+
+        let none = vec![ident("none")];
+        assert_eq!(ContainIntrinsicLength::parse(&mut none.as_slice().into()).unwrap(), ContainIntrinsicLength::None);
+
+        let auto = vec![ident("auto"), length];
+        assert_eq!(
+            ContainIntrinsicLength::parse(&mut auto.as_slice().into()).unwrap(),
+            ContainIntrinsicLength::AutoLength(Length::px(500.0))
+        );
+
+        let invalid = vec![ident("auto")];
+        assert!(ContainIntrinsicLength::parse(&mut invalid.as_slice().into()).is_err());
+    }
+
     #[test]
     fn test_parse_invalid_dimension() {
         let tokens = vec![ComponentValue::Token(CssToken {
@@ -225,3 +225,131 @@ impl CSSParsable for Clear {
         clear.ok_or(CssValueError::UnexpectedEndOfInput)
     }
 }
+
+/// The `content-visibility` property controls whether an element renders its contents at all, allowing the layout
+/// and rendering of off-screen contents to be skipped.
+///
+/// <https://drafts.csswg.org/css-contain/#content-visibility>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, EnumString)]
+#[strum(serialize_all = "kebab_case", ascii_case_insensitive)]
+pub enum ContentVisibility {
+    #[default]
+    Visible,
+
+    /// The contents are skipped while the element is not relevant to the user, e.g. while it is off-screen.
+    Auto,
+
+    /// The contents are always skipped, the element is sized as if it was empty.
+    Hidden,
+}
+
+impl CSSParsable for ContentVisibility {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        stream
+            .next_non_whitespace()
+            .ok_or(CssValueError::UnexpectedEndOfInput)
+            .and_then(|cv| match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident) => ident
+                        .parse()
+                        .map_err(|_| CssValueError::InvalidValue(format!("Invalid content-visibility: {ident}"))),
+                    _ => Err(CssValueError::InvalidToken(token.kind.clone())),
+                },
+                cvs => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            })
+    }
+}
+
+/// The `will-change` property hints which aspects of an element are expected to change, so the browser can prepare
+/// for the changes ahead of time, e.g. by rendering the element into a layer of its own.
+///
+/// <https://drafts.csswg.org/css-will-change/#will-change>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum WillChange {
+    #[default]
+    Auto,
+
+    /// The scroll position of the element is expected to change.
+    ScrollPosition,
+
+    /// The contents of the element are expected to change.
+    Contents,
+
+    /// The properties expected to change, in lowercase. In a list of several hints `scroll-position` and `contents`
+    /// are kept here along with the property names.
+    Properties(Vec<String>),
+}
+
+impl WillChange {
+    /// Whether the element is expected to move or fade, the changes that are cheaper to render when the element is
+    /// promoted to a compositing layer of its own.
+    #[must_use]
+    pub fn promotes_layer(&self) -> bool {
+        match self {
+            Self::Properties(properties) => properties
+                .iter()
+                .any(|property| property == "transform" || property == "opacity"),
+            _ => false,
+        }
+    }
+}
+
+impl CSSParsable for WillChange {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        let mut hints = Vec::new();
+
+        while let Some(cv) = stream.next_non_whitespace() {
+            let ComponentValue::Token(token) = cv else {
+                return Err(CssValueError::InvalidComponentValue(cv.clone()));
+            };
+
+            match &token.kind {
+                CssTokenKind::Ident(ident) => {
+                    let hint = ident.to_ascii_lowercase();
+                    if matches!(hint.as_str(), "auto" | "none" | "all" | "will-change") {
+                        if hint == "auto" && hints.is_empty() && !stream.has_remaining_tokens() {
+                            return Ok(Self::Auto);
+                        }
+                        return Err(CssValueError::InvalidValue(format!("Invalid will-change hint: {ident}")));
+                    }
+                    hints.push(hint);
+                }
+                CssTokenKind::Comma => {}
+                kind => return Err(CssValueError::InvalidToken(kind.clone())),
+            }
+        }
+
+        match hints.as_slice() {
+            [] => Err(CssValueError::ExpectedComponentValue),
+            [hint] if hint == "scroll-position" => Ok(Self::ScrollPosition),
+            [hint] if hint == "contents" => Ok(Self::Contents),
+            _ => Ok(Self::Properties(hints)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use css_cssom::CSSStyleSheet;
+
+    use super::*;
+
+    fn parse_will_change(value: &str) -> Result<WillChange, CssValueError> {
+        let decls = CSSStyleSheet::from_inline(&format!("will-change: {value};"));
+        WillChange::parse(&mut ComponentValueStream::new(&decls[0].original_values))
+    }
+
+    #[test]
+    fn test_will_change() {
+        assert_eq!(parse_will_change("auto"), Ok(WillChange::Auto));
+        assert_eq!(parse_will_change("scroll-position"), Ok(WillChange::ScrollPosition));
+        assert_eq!(
+            parse_will_change("Transform, contents"),
+            Ok(WillChange::Properties(vec!["transform".to_string(), "contents".to_string()]))
+        );
+        assert!(parse_will_change("opacity").unwrap().promotes_layer());
+        assert!(!parse_will_change("contents").unwrap().promotes_layer());
+        assert!(parse_will_change("transform, auto").is_err());
+        assert!(parse_will_change("all").is_err());
+    }
+}
@@ -0,0 +1,193 @@
+//! Values of the `filter` property, the graphical effects applied to an element before it is drawn.
+
+use css_cssom::{ComponentValue, ComponentValueStream, CssTokenKind, Function};
+
+use crate::{CSSParsable, color::Color, error::CssValueError, quantity::Length};
+
+/// The `drop-shadow()` filter function, a blurred and offset copy of the alpha of the element drawn behind it in a
+/// single color. Unlike `box-shadow` the shadow follows the visible shape of the element, including its transparent
+/// parts.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Values/filter-function/drop-shadow>
+#[derive(Debug, Clone, PartialEq)]
+pub struct DropShadow {
+    /// The color of the shadow, `None` for the `color` of the element.
+    pub color: Option<Color>,
+    pub offset_x: Length,
+    pub offset_y: Length,
+
+    /// The blur radius, twice the standard deviation of the Gaussian blur. Zero for a sharp shadow.
+    pub blur_radius: Length,
+}
+
+impl DropShadow {
+    /// Parses the arguments of `drop-shadow()`, a color and two or three lengths in either order.
+    fn parse(function: &Function) -> Result<Self, CssValueError> {
+        let mut color = None;
+        let mut lengths = Vec::with_capacity(3);
+
+        let mut stream = ComponentValueStream::new(&function.value);
+        while let Some(cv) = stream.next_non_whitespace() {
+            if let Ok(length) = Length::try_from(cv) {
+                lengths.push(length);
+                continue;
+            }
+
+            if color.is_some() {
+                return Err(CssValueError::InvalidComponentValue(cv.clone()));
+            }
+            color = Some(Color::parse(&mut ComponentValueStream::new(std::slice::from_ref(cv)))?);
+        }
+
+        let (offset_x, offset_y, blur_radius) = match lengths.as_slice() {
+            [x, y] => (*x, *y, Length::zero()),
+            [x, y, blur] if blur.value() >= 0.0 => (*x, *y, *blur),
+            [_, _, blur] => {
+                return Err(CssValueError::InvalidValue(format!("Negative drop-shadow blur radius: {}", blur.value())));
+            }
+            _ => {
+                return Err(CssValueError::InvalidValue("drop-shadow() takes two or three lengths".to_string()));
+            }
+        };
+
+        Ok(Self {
+            color,
+            offset_x,
+            offset_y,
+            blur_radius,
+        })
+    }
+}
+
+/// Parses the argument of `blur()`, a non-negative length that is zero when left out.
+fn parse_blur(function: &Function) -> Result<Length, CssValueError> {
+    let mut stream = ComponentValueStream::new(&function.value);
+    let Some(cv) = stream.next_non_whitespace() else {
+        return Ok(Length::zero());
+    };
+
+    let radius = Length::try_from(cv)?;
+    if let Some(cv) = stream.next_non_whitespace() {
+        return Err(CssValueError::InvalidComponentValue(cv.clone()));
+    }
+    if radius.value() < 0.0 {
+        return Err(CssValueError::InvalidValue(format!("Negative blur radius: {}", radius.value())));
+    }
+
+    Ok(radius)
+}
+
+/// A function of the `filter` and `backdrop-filter` properties.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterFunction {
+    /// The `blur()` function, a Gaussian blur whose standard deviation is the given length.
+    Blur(Length),
+    DropShadow(DropShadow),
+}
+
+/// The `filter` property applies graphical effects such as shadows to an element, in the order they are listed. The
+/// `backdrop-filter` property takes the same value, applied to what is behind the element instead.
+///
+/// NOTE: Only `blur()` and `drop-shadow()` are supported, a value with any other function is invalid.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/filter>
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Filter(pub Vec<FilterFunction>);
+
+impl Filter {
+    /// Whether the value is `none`.
+    #[must_use]
+    pub fn is_none(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl CSSParsable for Filter {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        let mut functions = Vec::new();
+
+        while let Some(cv) = stream.next_non_whitespace() {
+            match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident)
+                        if ident.eq_ignore_ascii_case("none")
+                            && functions.is_empty()
+                            && !stream.has_remaining_tokens() =>
+                    {
+                        return Ok(Self::default());
+                    }
+                    kind => return Err(CssValueError::InvalidToken(kind.clone())),
+                },
+                ComponentValue::Function(function) if function.name.eq_ignore_ascii_case("blur") => {
+                    functions.push(FilterFunction::Blur(parse_blur(function)?));
+                }
+                ComponentValue::Function(function) if function.name.eq_ignore_ascii_case("drop-shadow") => {
+                    functions.push(FilterFunction::DropShadow(DropShadow::parse(function)?));
+                }
+                ComponentValue::Function(function) => {
+                    return Err(CssValueError::InvalidValue(format!("Unsupported filter function: {}", function.name)));
+                }
+                cv => return Err(CssValueError::InvalidComponentValue(cv.clone())),
+            }
+        }
+
+        if functions.is_empty() {
+            return Err(CssValueError::ExpectedComponentValue);
+        }
+
+        Ok(Self(functions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use css_cssom::CSSStyleSheet;
+
+    use super::*;
+    use crate::quantity::LengthUnit;
+
+    fn parse(value: &str) -> Result<Filter, CssValueError> {
+        let decls = CSSStyleSheet::from_inline(&format!("filter: {value};"));
+        Filter::parse(&mut ComponentValueStream::new(&decls[0].original_values))
+    }
+
+    #[test]
+    fn test_parse_drop_shadow() {
+        let filter = parse("drop-shadow(2px 2px 4px rgba(0, 0, 0, 0.5))").unwrap();
+        let [FilterFunction::DropShadow(shadow)] = filter.0.as_slice() else {
+            panic!("expected a single drop-shadow");
+        };
+
+        assert_eq!(shadow.offset_x, Length::new(2.0, LengthUnit::Px));
+        assert_eq!(shadow.offset_y, Length::new(2.0, LengthUnit::Px));
+        assert_eq!(shadow.blur_radius, Length::new(4.0, LengthUnit::Px));
+        assert!(shadow.color.is_some());
+
+        let filter = parse("drop-shadow(red 1em -1px)").unwrap();
+        let [FilterFunction::DropShadow(shadow)] = filter.0.as_slice() else {
+            panic!("expected a single drop-shadow");
+        };
+        assert_eq!(shadow.offset_x, Length::new(1.0, LengthUnit::Em));
+        assert_eq!(shadow.blur_radius, Length::zero());
+    }
+
+    #[test]
+    fn test_parse_invalid_filters() {
+        assert_eq!(parse("none"), Ok(Filter::default()));
+        assert!(parse("drop-shadow(2px)").is_err());
+        assert!(parse("drop-shadow(2px 2px -4px)").is_err());
+        assert!(parse("drop-shadow(red 2px 2px blue)").is_err());
+        assert!(parse("blur(-4px)").is_err());
+        assert!(parse("blur(4px 2px)").is_err());
+        assert!(parse("brightness(50%)").is_err());
+    }
+
+    #[test]
+    fn test_parse_blur() {
+        assert_eq!(
+            parse("blur(10px) drop-shadow(1px 1px)").unwrap().0[0],
+            FilterFunction::Blur(Length::new(10.0, LengthUnit::Px))
+        );
+        assert_eq!(parse("blur()").unwrap().0, [FilterFunction::Blur(Length::zero())]);
+    }
+}
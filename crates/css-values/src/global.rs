@@ -14,7 +14,7 @@ pub enum Global {
     Initial,
 
     /// The property resets to the value established by the user-agent stylesheet (or by user styles, if any exist).
-    /// It behaves as initial if the property is inherited by default, and inherit if not.
+    /// It behaves as unset if no such value exists.
     Revert,
 
     /// The property resets to the value established by the user-agent stylesheet (or by user styles, if any exist). It behaves as either inherit or
@@ -34,6 +34,7 @@ impl CSSParsable for Global {
             && let CssTokenKind::Ident(ident) = &token.kind
             && let Ok(global) = ident.parse()
         {
+            stream.next_cv();
             return Ok(global);
         }
 
@@ -1,4 +1,5 @@
 use css_cssom::{ComponentValue, ComponentValueStream, CssToken, CssTokenKind, Function};
+use strum::EnumString;
 
 use crate::{
     CSSParsable,
@@ -9,6 +10,7 @@ use crate::{
         linear::LinearGradientSyntax,
         radial::RadialGradientSyntax,
     },
+    quantity::Resolution,
 };
 
 pub mod gradient;
@@ -230,6 +232,87 @@ impl CSSParsable for Gradient {
     }
 }
 
+/// A candidate image of an `image-set()`, along with the resolution it is meant to be displayed at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageSetCandidate {
+    /// The URL of the image, as written in the stylesheet.
+    pub url: String,
+
+    /// The resolution of the image, `1x` when none is given.
+    pub resolution: Resolution,
+}
+
+/// The `image-set()` function, a set of images of which the one best matching the resolution of the display is used.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Values/image/image-set>
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageSet {
+    pub candidates: Vec<ImageSetCandidate>,
+}
+
+impl ImageSet {
+    /// Parse the arguments of an `image-set()` function, a comma separated list of `url()` functions or strings,
+    /// each followed by an optional resolution.
+    pub fn parse_function(func: &Function) -> Result<Self, CssValueError> {
+        let candidates = Gradient::split_on_commas(&func.value)
+            .iter()
+            .map(|segment| Self::parse_candidate(Gradient::strip_whitespace(segment)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if candidates.is_empty() {
+            return Err(CssValueError::InvalidValue("Expected at least one image in image-set()".into()));
+        }
+
+        Ok(Self { candidates })
+    }
+
+    fn parse_candidate(segment: &[ComponentValue]) -> Result<ImageSetCandidate, CssValueError> {
+        let mut stream = ComponentValueStream::from(segment);
+
+        let url = match stream.next_non_whitespace() {
+            Some(ComponentValue::Token(token)) => match &token.kind {
+                CssTokenKind::Url(url) | CssTokenKind::String(url) => url.clone(),
+                _ => return Err(CssValueError::InvalidToken(token.kind.clone())),
+            },
+            Some(ComponentValue::Function(func)) if func.name.eq_ignore_ascii_case("url") => match func.value.first() {
+                Some(ComponentValue::Token(CssToken {
+                    kind: CssTokenKind::String(url),
+                    ..
+                })) => url.clone(),
+                _ => return Err(CssValueError::InvalidValue("Expected a string token in url() function".into())),
+            },
+            Some(cv) => return Err(CssValueError::InvalidComponentValue(cv.clone())),
+            None => return Err(CssValueError::UnexpectedEndOfInput),
+        };
+
+        let resolution = match stream.next_non_whitespace() {
+            Some(ComponentValue::Token(token)) => Resolution::try_from(token)?,
+            Some(cv) => return Err(CssValueError::InvalidComponentValue(cv.clone())),
+            None => Resolution::dppx(1.0),
+        };
+
+        Ok(ImageSetCandidate { url, resolution })
+    }
+
+    /// Selects the candidate to display on a screen with the given number of device pixels per CSS pixel, which is
+    /// the candidate with the lowest resolution that is still at least as high as that of the screen, or the one
+    /// with the highest resolution when all of them are lower.
+    #[must_use]
+    pub fn select(&self, device_pixel_ratio: f64) -> Option<&ImageSetCandidate> {
+        let dppx = |candidate: &&ImageSetCandidate| candidate.resolution.to_dppx();
+
+        self.candidates
+            .iter()
+            .filter(|candidate| dppx(candidate) >= device_pixel_ratio)
+            .min_by(|a, b| dppx(a).total_cmp(&dppx(b)))
+            .or_else(|| {
+                self.candidates
+                    .iter()
+                    .max_by(|a, b| dppx(a).total_cmp(&dppx(b)))
+            })
+    }
+}
+
 /// Represents an image in CSS,
 ///
 /// Including URLs, gradients, and more complex constructs like cross-fades and image sets.
@@ -239,10 +322,10 @@ pub enum Image {
     None,
     Url(String),
     Gradient(Box<Gradient>),
+    ImageSet(ImageSet),
     // TODO: Element()
     // TODO: Image()
     // TODO: CrossFade()
-    // TODO: ImageSet()
     // TODO: Paint()
 }
 
@@ -252,7 +335,12 @@ impl TryFrom<&Function> for Image {
     fn try_from(value: &Function) -> Result<Self, Self::Error> {
         Gradient::parse_function(value).map_or_else(
             |_| {
-                if value.name.eq_ignore_ascii_case("url") {
+                if value.name.eq_ignore_ascii_case("image-set") || value.name.eq_ignore_ascii_case("-webkit-image-set")
+                {
+                    ImageSet::parse_function(value)
+                        .map(Self::ImageSet)
+                        .map_err(|e| e.to_string())
+                } else if value.name.eq_ignore_ascii_case("url") {
                     if let Some(ComponentValue::Token(token)) = value.value.first() {
                         if let CssTokenKind::String(s) = &token.kind {
                             Ok(Self::Url(s.clone()))
@@ -288,3 +376,141 @@ impl CSSParsable for Image {
         }
     }
 }
+
+/// The `image-rendering` property sets the algorithm used to scale images.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/image-rendering>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, EnumString)]
+#[strum(serialize_all = "kebab_case", ascii_case_insensitive)]
+pub enum ImageRendering {
+    /// The image is scaled with an algorithm that smooths colors, such as bilinear filtering.
+    #[default]
+    Auto,
+
+    /// The image is scaled with an algorithm that preserves contrast and edges, without smoothing colors.
+    CrispEdges,
+
+    /// The image is scaled up with nearest-neighbor filtering, so that it appears to be made of large pixels.
+    Pixelated,
+}
+
+impl CSSParsable for ImageRendering {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        stream
+            .next_non_whitespace()
+            .map_or(Err(CssValueError::ExpectedComponentValue), |cv| match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident) => ident
+                        .parse()
+                        .map_err(|_| CssValueError::InvalidValue(format!("Invalid image-rendering value: {ident}"))),
+                    _ => Err(CssValueError::InvalidToken(token.kind.clone())),
+                },
+                cvs => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            })
+    }
+}
+
+/// How the content of a replaced element, such as an image, is fitted into the box of the element.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/object-fit>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, EnumString)]
+#[strum(serialize_all = "kebab_case", ascii_case_insensitive)]
+pub enum ObjectFit {
+    /// The content is stretched to fill the box, ignoring its aspect ratio.
+    #[default]
+    Fill,
+
+    /// The content is scaled to fit inside the box keeping its aspect ratio, leaving the rest of the box empty.
+    Contain,
+
+    /// The content is scaled to cover the whole box keeping its aspect ratio, cropping the parts that overflow it.
+    Cover,
+
+    /// The content keeps its intrinsic size and is cropped by the box.
+    None,
+
+    /// The content is sized as `none` or `contain`, whichever results in the smaller content.
+    ScaleDown,
+}
+
+impl CSSParsable for ObjectFit {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        stream
+            .next_non_whitespace()
+            .map_or(Err(CssValueError::ExpectedComponentValue), |cv| match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident) => ident
+                        .parse()
+                        .map_err(|_| CssValueError::InvalidValue(format!("Invalid object-fit value: {ident}"))),
+                    _ => Err(CssValueError::InvalidToken(token.kind.clone())),
+                },
+                cvs => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use css_cssom::CSSStyleSheet;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_image_rendering() {
+        assert_eq!("auto".parse(), Ok(ImageRendering::Auto));
+        assert_eq!("crisp-edges".parse(), Ok(ImageRendering::CrispEdges));
+        assert_eq!("PIXELATED".parse(), Ok(ImageRendering::Pixelated));
+        assert!("smooth".parse::<ImageRendering>().is_err());
+    }
+
+    fn image_set(css: &str) -> ImageSet {
+        let decls = CSSStyleSheet::from_inline(&format!("background-image: {css};"));
+        let mut stream = ComponentValueStream::new(&decls[0].original_values);
+
+        match Image::parse(&mut stream) {
+            Ok(Image::ImageSet(set)) => set,
+            other => panic!("Expected an image-set, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_image_set() {
+        let set = image_set(r#"image-set(url("img.png") 1x, "img@2x.png" 2x, url("img-print.png") 192dpi)"#);
+
+        assert_eq!(
+            set.candidates,
+            vec![
+                ImageSetCandidate {
+                    url: "img.png".into(),
+                    resolution: Resolution::dppx(1.0),
+                },
+                ImageSetCandidate {
+                    url: "img@2x.png".into(),
+                    resolution: Resolution::dppx(2.0),
+                },
+                ImageSetCandidate {
+                    url: "img-print.png".into(),
+                    resolution: Resolution::dpi(192.0),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_image_set_selects_candidate_for_device_pixel_ratio() {
+        let set = image_set(r#"image-set("img.png", "img@2x.png" 2x)"#);
+
+        assert_eq!(set.select(1.0).unwrap().url, "img.png");
+        assert_eq!(set.select(2.0).unwrap().url, "img@2x.png");
+        assert_eq!(set.select(1.5).unwrap().url, "img@2x.png");
+        assert_eq!(set.select(3.0).unwrap().url, "img@2x.png");
+    }
+
+    #[test]
+    fn test_parse_object_fit() {
+        assert_eq!("fill".parse(), Ok(ObjectFit::Fill));
+        assert_eq!("Cover".parse(), Ok(ObjectFit::Cover));
+        assert_eq!("scale-down".parse(), Ok(ObjectFit::ScaleDown));
+        assert!("stretch".parse::<ObjectFit>().is_err());
+    }
+}
@@ -81,3 +81,54 @@ impl CSSParsable for OverflowAnchor {
             })
     }
 }
+
+/// The `resize` property sets whether the user can resize an element by dragging the handle in its bottom-right corner,
+/// which only applies to elements whose overflow is not `visible` and to replaced elements such as `<textarea>`.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/resize>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum Resize {
+    #[default]
+    None,
+    Both,
+    Horizontal,
+    Vertical,
+
+    /// The block direction, vertical in horizontal writing modes.
+    Block,
+
+    /// The inline direction, horizontal in horizontal writing modes.
+    Inline,
+}
+
+impl Resize {
+    /// Whether the user can change the width of the element.
+    #[must_use]
+    pub const fn resizes_width(self) -> bool {
+        matches!(self, Self::Both | Self::Horizontal | Self::Inline)
+    }
+
+    /// Whether the user can change the height of the element.
+    #[must_use]
+    pub const fn resizes_height(self) -> bool {
+        matches!(self, Self::Both | Self::Vertical | Self::Block)
+    }
+}
+
+impl CSSParsable for Resize {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        stream
+            .next_non_whitespace()
+            .ok_or(CssValueError::UnexpectedEndOfInput)
+            .and_then(|cv| match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident) => ident
+                        .parse()
+                        .map_err(|_| CssValueError::InvalidValue(format!("Invalid resize value: {ident}"))),
+                    _ => Err(CssValueError::InvalidToken(token.kind.clone())),
+                },
+                cvs => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            })
+    }
+}
@@ -9,10 +9,13 @@ pub mod border;
 pub mod calc;
 pub mod color;
 pub mod combination;
+pub mod container;
+pub mod content;
 pub mod cursor;
 pub mod dimension;
 pub mod display;
 pub mod error;
+pub mod filter;
 pub mod global;
 pub mod image;
 mod layout;
@@ -21,7 +24,10 @@ pub mod numeric;
 pub mod position;
 pub mod property;
 pub mod quantity;
+pub mod shadow;
+pub mod svg;
 pub mod text;
+pub mod unicode_range;
 
 /// Trait for CSS value types that can be parsed from a `ComponentValueStream`.
 ///
@@ -20,10 +20,78 @@ pub enum MediaType {
     Speech,
 }
 
+/// A media feature matched against the preferences of the user, such as `(prefers-color-scheme: dark)`.
+///
+/// Parsing the name of a feature yields the feature with a default value, which is replaced by the value of the query.
 #[derive(Debug, Clone, PartialEq, Eq, EnumString)]
 #[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
 pub enum MediaFeature {
-    PrefersColorScheme,
+    ForcedColors(ForcedColorsState),
+    Hover(Hover),
+    Pointer(Pointer),
+    PrefersColorScheme(ColorScheme),
+    PrefersContrast(ContrastPreference),
+    PrefersReducedData(ReducedDataPreference),
+    PrefersReducedMotion(ReducedMotionPreference),
+}
+
+/// The color scheme preferred by the user.
+///
+/// <https://drafts.csswg.org/mediaqueries-5/#prefers-color-scheme>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, EnumString)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum ColorScheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+/// Whether the user asked to minimize the amount of motion, such as animations and transitions.
+///
+/// <https://drafts.csswg.org/mediaqueries-5/#prefers-reduced-motion>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, EnumString)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum ReducedMotionPreference {
+    #[default]
+    NoPreference,
+    Reduce,
+}
+
+/// Whether the user asked to minimize the amount of data transferred, such as on a metered connection.
+///
+/// <https://drafts.csswg.org/mediaqueries-5/#prefers-reduced-data>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, EnumString)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum ReducedDataPreference {
+    #[default]
+    NoPreference,
+    Reduce,
+}
+
+/// Whether the user asked for more or less contrast between colors than the page uses by default.
+///
+/// <https://drafts.csswg.org/mediaqueries-5/#prefers-contrast>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, EnumString)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum ContrastPreference {
+    #[default]
+    NoPreference,
+    More,
+    Less,
+
+    /// A palette that is neither more nor less contrasted, only matched while forced colors are active.
+    Custom,
+}
+
+/// Whether the system replaces the colors of pages with a limited palette of its own, like a high contrast theme.
+///
+/// <https://drafts.csswg.org/mediaqueries-5/#forced-colors>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, EnumString)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum ForcedColorsState {
+    #[default]
+    None,
+    Active,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, EnumString)]
@@ -35,18 +103,26 @@ pub enum LogicalOperator {
     Only,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, EnumString)]
+/// Whether the primary input device can hover over elements, such as a mouse, unlike a touchscreen.
+///
+/// <https://drafts.csswg.org/mediaqueries-4/#hover>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumString)]
 #[strum(serialize_all = "lowercase", ascii_case_insensitive)]
 pub enum Hover {
     None,
+    #[default]
     Hover,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, EnumString)]
+/// The accuracy of the primary pointing device, a finger on a touchscreen being coarse and a mouse being fine.
+///
+/// <https://drafts.csswg.org/mediaqueries-4/#pointer>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumString)]
 #[strum(serialize_all = "lowercase", ascii_case_insensitive)]
 pub enum Pointer {
     None,
     Coarse,
+    #[default]
     Fine,
 }
 
@@ -96,6 +96,12 @@ impl Percentage {
         Self(value.clamp(-100.0, 100.0))
     }
 
+    /// Create a new Percentage that may exceed 100%, for properties where it scales a length, such as `line-height`
+    #[must_use]
+    pub const fn unbounded(value: f64) -> Self {
+        Self(value)
+    }
+
     /// Create a Percentage from a fraction (-1.0 to 1.0)
     #[must_use]
     pub fn from_fraction(fraction: f64) -> Self {
@@ -635,6 +635,7 @@ impl CSSParsable for Frequency {
 pub enum ResolutionUnit {
     Dpi,
     Dpcm,
+    #[strum(serialize = "dppx", serialize = "x")]
     Dppx,
 }
 
@@ -0,0 +1,184 @@
+//! Values of the `box-shadow` property, the shadows cast by the box of an element.
+
+use css_cssom::{ComponentValue, ComponentValueStream, CssTokenKind};
+
+use crate::{CSSParsable, color::Color, error::CssValueError, quantity::Length};
+
+/// A single shadow of `box-shadow`, a copy of the border box of the element offset, grown by the spread radius,
+/// blurred and filled with a single color. An inset shadow is cast inside the padding box instead.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/box-shadow>
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxShadow {
+    /// Whether the shadow is cast inside the box instead of outside of it.
+    pub inset: bool,
+    pub offset_x: Length,
+    pub offset_y: Length,
+
+    /// The blur radius, twice the standard deviation of the Gaussian blur. Zero for a sharp shadow.
+    pub blur_radius: Length,
+
+    /// How much the shadow grows before it is blurred, negative to shrink it.
+    pub spread_radius: Length,
+
+    /// The color of the shadow, `None` for the `color` of the element.
+    pub color: Option<Color>,
+}
+
+impl BoxShadow {
+    /// Parses a single shadow, two to four lengths with an optional `inset` keyword and color on either side.
+    fn parse(values: &[ComponentValue]) -> Result<Self, CssValueError> {
+        let mut inset = false;
+        let mut color = None;
+        let mut lengths = Vec::with_capacity(4);
+
+        for cv in values.iter().filter(|cv| !cv.is_whitespace()) {
+            if let Some(length) = shadow_length(cv) {
+                lengths.push(length);
+                continue;
+            }
+
+            if let ComponentValue::Token(token) = cv
+                && let CssTokenKind::Ident(ident) = &token.kind
+                && ident.eq_ignore_ascii_case("inset")
+                && !inset
+            {
+                inset = true;
+                continue;
+            }
+
+            if color.is_some() {
+                return Err(CssValueError::InvalidComponentValue(cv.clone()));
+            }
+            color = Some(Color::parse(&mut ComponentValueStream::new(std::slice::from_ref(cv)))?);
+        }
+
+        let (offset_x, offset_y, blur_radius, spread_radius) = match lengths.as_slice() {
+            [x, y] => (*x, *y, Length::zero(), Length::zero()),
+            [x, y, blur] => (*x, *y, *blur, Length::zero()),
+            [x, y, blur, spread] => (*x, *y, *blur, *spread),
+            _ => {
+                return Err(CssValueError::InvalidValue("box-shadow takes two to four lengths".to_string()));
+            }
+        };
+
+        if blur_radius.value() < 0.0 {
+            return Err(CssValueError::InvalidValue(format!(
+                "Negative box-shadow blur radius: {}",
+                blur_radius.value()
+            )));
+        }
+
+        Ok(Self {
+            inset,
+            offset_x,
+            offset_y,
+            blur_radius,
+            spread_radius,
+            color,
+        })
+    }
+}
+
+/// A length of a shadow, where a unitless zero is also allowed.
+fn shadow_length(cv: &ComponentValue) -> Option<Length> {
+    if let ComponentValue::Token(token) = cv
+        && let CssTokenKind::Number(number) = &token.kind
+        && number.to_f64() == 0.0
+    {
+        return Some(Length::zero());
+    }
+
+    Length::try_from(cv).ok()
+}
+
+/// The `box-shadow` property, a comma separated list of shadows. The first shadow is drawn on top.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/box-shadow>
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BoxShadowList(pub Vec<BoxShadow>);
+
+impl BoxShadowList {
+    /// Whether the value is `none`.
+    #[must_use]
+    pub fn is_none(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl CSSParsable for BoxShadowList {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        let mut shadows = Vec::new();
+        let mut values = Vec::new();
+
+        while let Some(cv) = stream.next_non_whitespace() {
+            match cv {
+                ComponentValue::Token(token) if matches!(token.kind, CssTokenKind::Comma) => {
+                    shadows.push(BoxShadow::parse(&values)?);
+                    values.clear();
+                }
+                ComponentValue::Token(token)
+                    if matches!(&token.kind, CssTokenKind::Ident(ident) if ident.eq_ignore_ascii_case("none"))
+                        && shadows.is_empty()
+                        && values.is_empty()
+                        && !stream.has_remaining_tokens() =>
+                {
+                    return Ok(Self::default());
+                }
+                cv => values.push(cv.clone()),
+            }
+        }
+
+        if values.is_empty() {
+            return Err(CssValueError::ExpectedComponentValue);
+        }
+        shadows.push(BoxShadow::parse(&values)?);
+
+        Ok(Self(shadows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use css_cssom::CSSStyleSheet;
+
+    use super::*;
+    use crate::quantity::LengthUnit;
+
+    fn parse(value: &str) -> Result<BoxShadowList, CssValueError> {
+        let decls = CSSStyleSheet::from_inline(&format!("box-shadow: {value};"));
+        BoxShadowList::parse(&mut ComponentValueStream::new(&decls[0].original_values))
+    }
+
+    #[test]
+    fn test_parse_box_shadow() {
+        let shadows = parse("0 2px 4px rgba(0, 0, 0, 0.5), inset red 1em 1em 0 -2px").unwrap();
+        let [outer, inner] = shadows.0.as_slice() else {
+            panic!("expected two shadows");
+        };
+
+        assert!(!outer.inset);
+        assert_eq!(outer.offset_x, Length::zero());
+        assert_eq!(outer.offset_y, Length::new(2.0, LengthUnit::Px));
+        assert_eq!(outer.blur_radius, Length::new(4.0, LengthUnit::Px));
+        assert_eq!(outer.spread_radius, Length::zero());
+        assert!(outer.color.is_some());
+
+        assert!(inner.inset);
+        assert_eq!(inner.offset_x, Length::new(1.0, LengthUnit::Em));
+        assert_eq!(inner.spread_radius, Length::new(-2.0, LengthUnit::Px));
+        assert!(inner.color.is_some());
+    }
+
+    #[test]
+    fn test_parse_invalid_box_shadows() {
+        assert_eq!(parse("none"), Ok(BoxShadowList::default()));
+        assert!(parse("2px").is_err());
+        assert!(parse("1px 1px -2px").is_err());
+        assert!(parse("1px 1px 1px 1px 1px").is_err());
+        assert!(parse("red 1px 1px blue").is_err());
+        assert!(parse("inset inset 1px 1px").is_err());
+        assert!(parse("1px 1px,").is_err());
+        assert!(parse("none, 1px 1px").is_err());
+    }
+}
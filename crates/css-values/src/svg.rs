@@ -0,0 +1,176 @@
+//! Values of the properties that only apply to SVG content.
+
+use std::fmt::{self, Display};
+
+use css_cssom::{ComponentValue, ComponentValueStream, CssTokenKind};
+use strum::{Display as StrumDisplay, EnumString};
+
+use crate::{CSSParsable, error::CssValueError};
+
+/// One of the operations drawing an SVG shape or text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, StrumDisplay)]
+#[strum(serialize_all = "kebab_case", ascii_case_insensitive)]
+pub enum PaintOrderLayer {
+    /// The interior of the shape.
+    Fill,
+
+    /// The outline of the shape.
+    Stroke,
+
+    /// The markers drawn at the vertices of the shape.
+    Markers,
+}
+
+/// The `paint-order` property sets the order the fill, stroke and markers of SVG shapes and text are drawn in, later
+/// layers are drawn on top of earlier ones. Layers left out of the value are drawn after the listed ones, in their
+/// default order.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/paint-order>
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PaintOrder(Vec<PaintOrderLayer>);
+
+impl PaintOrder {
+    /// The default order, `fill stroke markers`.
+    const NORMAL: [PaintOrderLayer; 3] = [
+        PaintOrderLayer::Fill,
+        PaintOrderLayer::Stroke,
+        PaintOrderLayer::Markers,
+    ];
+
+    /// Creates an order from the layers listed first, followed by the layers left out in their default order.
+    #[must_use]
+    pub fn new(listed: &[PaintOrderLayer]) -> Self {
+        let mut layers = listed.to_vec();
+        layers.extend(Self::NORMAL.iter().filter(|layer| !listed.contains(layer)));
+
+        Self(layers)
+    }
+
+    /// The layers in the order they are drawn.
+    #[must_use]
+    pub fn layers(&self) -> &[PaintOrderLayer] {
+        &self.0
+    }
+
+    /// Whether the layers are drawn in the default order.
+    #[must_use]
+    pub fn is_normal(&self) -> bool {
+        self.0 == Self::NORMAL
+    }
+}
+
+impl Default for PaintOrder {
+    fn default() -> Self {
+        Self(Self::NORMAL.to_vec())
+    }
+}
+
+impl Display for PaintOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_normal() {
+            return write!(f, "normal");
+        }
+
+        for (index, layer) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{layer}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl CSSParsable for PaintOrder {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        let mut listed = Vec::new();
+
+        while let Some(cv) = stream.next_non_whitespace() {
+            let ident = match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident) => ident.clone(),
+                    kind => return Err(CssValueError::InvalidToken(kind.clone())),
+                },
+                cv => return Err(CssValueError::InvalidComponentValue(cv.clone())),
+            };
+
+            if ident.eq_ignore_ascii_case("normal") && listed.is_empty() && !stream.has_remaining_tokens() {
+                return Ok(Self::default());
+            }
+
+            let layer: PaintOrderLayer = ident
+                .parse()
+                .map_err(|_| CssValueError::InvalidValue(format!("Invalid paint-order value: {ident}")))?;
+
+            if listed.contains(&layer) {
+                return Err(CssValueError::InvalidValue(format!("Repeated paint-order layer: {ident}")));
+            }
+
+            listed.push(layer);
+        }
+
+        if listed.is_empty() {
+            return Err(CssValueError::ExpectedComponentValue);
+        }
+
+        Ok(Self::new(&listed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use css_cssom::CssToken;
+
+    use super::*;
+
+    fn parse(keywords: &[&str]) -> Result<PaintOrder, CssValueError> {
+        let input: Vec<ComponentValue> = keywords
+            .iter()
+            .flat_map(|keyword| {
+                [
+                    CssTokenKind::Ident((*keyword).to_string()),
+                    CssTokenKind::Whitespace,
+                ]
+            })
+            .map(|kind| {
+                ComponentValue::Token(CssToken {
+                    kind,
+                    position: Default::default(),
+                })
+            })
+            .collect();
+
+        PaintOrder::parse(&mut ComponentValueStream::new(&input))
+    }
+
+    #[test]
+    fn test_parse_paint_order() {
+        assert_eq!(parse(&["normal"]), Ok(PaintOrder::default()));
+        assert_eq!(
+            parse(&["stroke"]).unwrap().layers(),
+            [
+                PaintOrderLayer::Stroke,
+                PaintOrderLayer::Fill,
+                PaintOrderLayer::Markers
+            ]
+        );
+        assert_eq!(
+            parse(&["markers", "STROKE"]).unwrap().layers(),
+            [
+                PaintOrderLayer::Markers,
+                PaintOrderLayer::Stroke,
+                PaintOrderLayer::Fill
+            ]
+        );
+        assert!(parse(&["fill", "fill"]).is_err());
+        assert!(parse(&["normal", "fill"]).is_err());
+        assert!(parse(&["outline"]).is_err());
+    }
+
+    #[test]
+    fn test_paint_order_to_string() {
+        assert_eq!(PaintOrder::default().to_string(), "normal");
+        assert_eq!(PaintOrder::new(&[PaintOrderLayer::Stroke]).to_string(), "stroke fill markers");
+    }
+}
@@ -3,12 +3,13 @@
 //! The absolute font sizes are mapped to specific pixel values, while the relative font sizes adjust based on the parent element's font size.
 //! The generic font family names represent common categories of fonts that can be used in CSS.
 
-use css_cssom::{ComponentValue, ComponentValueStream, CssTokenKind};
+use css_cssom::{ComponentValue, ComponentValueStream, CssTokenKind, NumericValue};
 use strum::EnumString;
 
 use crate::{
     CSSParsable,
     calc::{CalcExpression, is_math_function},
+    dimension::OffsetValue,
     error::CssValueError,
     numeric::Percentage,
     quantity::{Length, LengthUnit},
@@ -17,35 +18,52 @@ use crate::{
 /// CSS defines several keywords for font sizes, which are mapped to specific pixel values.
 ///
 /// These keywords are used to specify the size of text in a way that is relative to the user's preferred font size (medium) or to other font sizes.
-/// The absolute size keywords are mapped to specific pixel values, while the relative size keywords adjust the font size based on the parent element's font size.
+/// The keywords form a scale where each size is 1.2 times the size before it, the relative size keywords step along the same scale from the parent element's font size.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, EnumString)]
 #[strum(serialize_all = "kebab_case", ascii_case_insensitive)]
 pub enum AbsoluteSize {
-    /// An absolute size 60% the size of medium. Mapped to the deprecated size="1".
+    /// An absolute size 58% the size of medium, three steps below it. Mapped to the deprecated size="1".
     XxSmall,
 
-    /// An absolute size 75% the size of medium.
+    /// An absolute size 69% the size of medium, two steps below it.
     XSmall,
 
-    /// An absolute size 89% the size of medium. Mapped to the deprecated size="2".
+    /// An absolute size 83% the size of medium, one step below it. Mapped to the deprecated size="2".
     Small,
 
     /// A user's preferred font size. This value is used as the reference middle value. Mapped to size="3".
     Medium,
 
-    /// An absolute size 20% larger than medium. Mapped to the deprecated size="4".
+    /// An absolute size 20% larger than medium, one step above it. Mapped to the deprecated size="4".
     Large,
 
-    /// An absolute size 50% larger than medium. Mapped to the deprecated size="5".
+    /// An absolute size 44% larger than medium, two steps above it. Mapped to the deprecated size="5".
     XLarge,
 
-    /// An absolute size twice the size of medium. Mapped to the deprecated size="6".
+    /// An absolute size 73% larger than medium, three steps above it. Mapped to the deprecated size="6".
     XxLarge,
 
-    /// An absolute size three times the size of medium. Mapped to the deprecated size="7".
+    /// An absolute size about twice the size of medium, four steps above it. Mapped to the deprecated size="7".
     XxxLarge,
 }
 
+impl AbsoluteSize {
+    /// The number of steps between this size and `medium` on the font size scale, negative for smaller sizes.
+    #[must_use]
+    pub const fn step(self) -> i32 {
+        match self {
+            Self::XxSmall => -3,
+            Self::XSmall => -2,
+            Self::Small => -1,
+            Self::Medium => 0,
+            Self::Large => 1,
+            Self::XLarge => 2,
+            Self::XxLarge => 3,
+            Self::XxxLarge => 4,
+        }
+    }
+}
+
 /// Represents a font family name, which can be either a generic family (serif, sans-serif, etc.) or a specific font name.
 ///
 /// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/font-family>
@@ -130,41 +148,597 @@ impl CSSParsable for FontSize {
     }
 }
 
-/// Represents the font weight property, which can be a keyword (normal, bold) or a numeric value (100-900).
+/// The `font-size-adjust` property keeps the x-height of the text the same when a fallback font is used instead of the
+/// first font of `font-family`, scaling the fallback font to make up for its smaller or larger lowercase letters.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/font-size-adjust>
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum FontSizeAdjust {
+    /// Fallback fonts are used at the font size.
+    #[default]
+    None,
+
+    /// The x-height of a fallback font is made this multiple of the font size.
+    Number(f64),
+
+    /// The x-height of a fallback font is made that of the first font of `font-family`.
+    FromFont,
+}
+
+impl CSSParsable for FontSizeAdjust {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        match stream.next_non_whitespace() {
+            Some(ComponentValue::Token(token)) => match &token.kind {
+                CssTokenKind::Ident(ident) if ident.eq_ignore_ascii_case("none") => Ok(Self::None),
+                CssTokenKind::Ident(ident) if ident.eq_ignore_ascii_case("from-font") => Ok(Self::FromFont),
+                CssTokenKind::Ident(ident) => {
+                    Err(CssValueError::InvalidValue(format!("Invalid font-size-adjust value: {ident}")))
+                }
+                CssTokenKind::Number(num) if num.to_f64() >= 0.0 => Ok(Self::Number(num.to_f64())),
+                _ => Err(CssValueError::InvalidToken(token.kind.clone())),
+            },
+            Some(cvs) => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            None => Err(CssValueError::ExpectedComponentValue),
+        }
+    }
+}
+
+/// The `font-kerning` property sets whether the kerning information stored in a font is used, adjusting the space
+/// between specific pairs of letters such as "AV".
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/font-kerning>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, EnumString)]
+#[strum(serialize_all = "kebab_case", ascii_case_insensitive)]
+pub enum FontKerning {
+    /// The browser decides whether kerning is applied, which it always is.
+    #[default]
+    Auto,
+
+    /// Kerning is applied.
+    Normal,
+
+    /// Kerning is disabled.
+    None,
+}
+
+impl CSSParsable for FontKerning {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        stream
+            .next_non_whitespace()
+            .map_or(Err(CssValueError::ExpectedComponentValue), |cv| match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident) => ident
+                        .parse()
+                        .map_err(|_| CssValueError::InvalidValue(format!("Invalid font-kerning value: {ident}"))),
+                    _ => Err(CssValueError::InvalidToken(token.kind.clone())),
+                },
+                cvs => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            })
+    }
+}
+
+/// The `font-display` descriptor of an `@font-face` rule decides how text using the font is rendered while the font is
+/// downloading: hidden for a short block period, then in a fallback font until the font arrives within the swap period.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/At-rules/@font-face/font-display>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, EnumString)]
+#[strum(serialize_all = "kebab_case", ascii_case_insensitive)]
+pub enum FontDisplay {
+    /// The browser decides, which is the same as `block`.
+    #[default]
+    Auto,
+
+    /// A long block period and an infinite swap period.
+    Block,
+
+    /// No block period and an infinite swap period.
+    Swap,
+
+    /// A very short block period and a short swap period.
+    Fallback,
+
+    /// A very short block period and no swap period, the font is only used if it is available right away.
+    Optional,
+}
+
+impl CSSParsable for FontDisplay {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        stream
+            .next_non_whitespace()
+            .map_or(Err(CssValueError::ExpectedComponentValue), |cv| match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident) => ident
+                        .parse()
+                        .map_err(|_| CssValueError::InvalidValue(format!("Invalid font-display value: {ident}"))),
+                    _ => Err(CssValueError::InvalidToken(token.kind.clone())),
+                },
+                cvs => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            })
+    }
+}
+
+/// The `font-variant-ligatures` property selects the ligatures and contextual forms of the font that are used. A group of
+/// ligatures that is not mentioned is left to the defaults of the font.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/font-variant-ligatures>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FontVariantLigatures {
+    /// Common ligatures such as "fi", the `liga` and `clig` features.
+    pub common: Option<bool>,
+
+    /// Decorative ligatures chosen by the type designer, the `dlig` feature.
+    pub discretionary: Option<bool>,
+
+    /// Ligatures used in old books, the `hlig` feature.
+    pub historical: Option<bool>,
+
+    /// Alternative glyphs adapted to their surrounding letters, the `calt` feature.
+    pub contextual: Option<bool>,
+}
+
+impl FontVariantLigatures {
+    /// The `none` keyword, disabling every ligature and contextual form.
+    pub const NONE: Self = Self {
+        common: Some(false),
+        discretionary: Some(false),
+        historical: Some(false),
+        contextual: Some(false),
+    };
+
+    /// The OpenType features set by the value, each with the value 1 to enable it or 0 to disable it.
+    #[must_use]
+    pub fn feature_settings(self) -> Vec<([u8; 4], u32)> {
+        let groups: [(Option<bool>, &[[u8; 4]]); 4] = [
+            (self.common, &[*b"liga", *b"clig"]),
+            (self.discretionary, &[*b"dlig"]),
+            (self.historical, &[*b"hlig"]),
+            (self.contextual, &[*b"calt"]),
+        ];
+
+        groups
+            .into_iter()
+            .filter_map(|(enabled, tags)| enabled.map(|enabled| (enabled, tags)))
+            .flat_map(|(enabled, tags)| tags.iter().map(move |tag| (*tag, u32::from(enabled))))
+            .collect()
+    }
+}
+
+impl CSSParsable for FontVariantLigatures {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        let mut ligatures = Self::default();
+        let mut keywords = 0;
+
+        while let Some(cv) = stream.next_non_whitespace() {
+            let ComponentValue::Token(token) = cv else {
+                return Err(CssValueError::InvalidComponentValue(cv.clone()));
+            };
+            let CssTokenKind::Ident(ident) = &token.kind else {
+                return Err(CssValueError::InvalidToken(token.kind.clone()));
+            };
+            let invalid = || CssValueError::InvalidValue(format!("Invalid font-variant-ligatures value: {ident}"));
+
+            let (group, enabled) = match ident.to_ascii_lowercase().as_str() {
+                keyword @ ("normal" | "none") if keywords == 0 => {
+                    if stream.next_non_whitespace().is_some() {
+                        return Err(invalid());
+                    }
+
+                    return Ok(if keyword == "none" {
+                        Self::NONE
+                    } else {
+                        Self::default()
+                    });
+                }
+                "common-ligatures" => (&mut ligatures.common, true),
+                "no-common-ligatures" => (&mut ligatures.common, false),
+                "discretionary-ligatures" => (&mut ligatures.discretionary, true),
+                "no-discretionary-ligatures" => (&mut ligatures.discretionary, false),
+                "historical-ligatures" => (&mut ligatures.historical, true),
+                "no-historical-ligatures" => (&mut ligatures.historical, false),
+                "contextual" => (&mut ligatures.contextual, true),
+                "no-contextual" => (&mut ligatures.contextual, false),
+                _ => return Err(invalid()),
+            };
+
+            if group.replace(enabled).is_some() {
+                return Err(invalid());
+            }
+
+            keywords += 1;
+        }
+
+        if keywords == 0 {
+            return Err(CssValueError::ExpectedComponentValue);
+        }
+
+        Ok(ligatures)
+    }
+}
+
+/// The style of the digits selected by `font-variant-numeric`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumericFigure {
+    /// Digits aligned on the baseline, the `lnum` feature.
+    Lining,
+
+    /// Digits with ascenders and descenders like lowercase letters, the `onum` feature.
+    Oldstyle,
+}
+
+/// The widths of the digits selected by `font-variant-numeric`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumericSpacing {
+    /// Digits of varying widths, the `pnum` feature.
+    Proportional,
+
+    /// Digits of the same width that line up in columns, the `tnum` feature.
+    Tabular,
+}
+
+/// The form of the fractions selected by `font-variant-numeric`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumericFraction {
+    /// Fractions with the numerator above and to the left of the denominator, the `frac` feature.
+    Diagonal,
+
+    /// Fractions with the numerator stacked on the denominator, the `afrc` feature.
+    Stacked,
+}
+
+/// The `font-variant-numeric` property selects alternative glyphs of the font for numbers, fractions and ordinals.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/font-variant-numeric>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FontVariantNumeric {
+    pub figure: Option<NumericFigure>,
+    pub spacing: Option<NumericSpacing>,
+    pub fraction: Option<NumericFraction>,
+
+    /// Ordinal markers such as the "st" of "1st" as superscripts, the `ordn` feature.
+    pub ordinal: bool,
+
+    /// A zero with a slash, the `zero` feature.
+    pub slashed_zero: bool,
+}
+
+impl FontVariantNumeric {
+    /// The OpenType features enabled by the value.
+    #[must_use]
+    pub fn feature_settings(self) -> Vec<([u8; 4], u32)> {
+        let figure = self.figure.map(|figure| match figure {
+            NumericFigure::Lining => *b"lnum",
+            NumericFigure::Oldstyle => *b"onum",
+        });
+        let spacing = self.spacing.map(|spacing| match spacing {
+            NumericSpacing::Proportional => *b"pnum",
+            NumericSpacing::Tabular => *b"tnum",
+        });
+        let fraction = self.fraction.map(|fraction| match fraction {
+            NumericFraction::Diagonal => *b"frac",
+            NumericFraction::Stacked => *b"afrc",
+        });
+        let ordinal = self.ordinal.then_some(*b"ordn");
+        let slashed_zero = self.slashed_zero.then_some(*b"zero");
+
+        [figure, spacing, fraction, ordinal, slashed_zero]
+            .into_iter()
+            .flatten()
+            .map(|tag| (tag, 1))
+            .collect()
+    }
+}
+
+impl CSSParsable for FontVariantNumeric {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        fn set<T>(slot: &mut Option<T>, value: T) -> bool {
+            slot.replace(value).is_none()
+        }
+
+        let mut numeric = Self::default();
+        let mut keywords = 0;
+
+        while let Some(cv) = stream.next_non_whitespace() {
+            let ComponentValue::Token(token) = cv else {
+                return Err(CssValueError::InvalidComponentValue(cv.clone()));
+            };
+            let CssTokenKind::Ident(ident) = &token.kind else {
+                return Err(CssValueError::InvalidToken(token.kind.clone()));
+            };
+            let invalid = || CssValueError::InvalidValue(format!("Invalid font-variant-numeric value: {ident}"));
+
+            let is_new = match ident.to_ascii_lowercase().as_str() {
+                "normal" if keywords == 0 => {
+                    if stream.next_non_whitespace().is_some() {
+                        return Err(invalid());
+                    }
+
+                    return Ok(Self::default());
+                }
+                "lining-nums" => set(&mut numeric.figure, NumericFigure::Lining),
+                "oldstyle-nums" => set(&mut numeric.figure, NumericFigure::Oldstyle),
+                "proportional-nums" => set(&mut numeric.spacing, NumericSpacing::Proportional),
+                "tabular-nums" => set(&mut numeric.spacing, NumericSpacing::Tabular),
+                "diagonal-fractions" => set(&mut numeric.fraction, NumericFraction::Diagonal),
+                "stacked-fractions" => set(&mut numeric.fraction, NumericFraction::Stacked),
+                "ordinal" => !std::mem::replace(&mut numeric.ordinal, true),
+                "slashed-zero" => !std::mem::replace(&mut numeric.slashed_zero, true),
+                _ => return Err(invalid()),
+            };
+
+            if !is_new {
+                return Err(invalid());
+            }
+
+            keywords += 1;
+        }
+
+        if keywords == 0 {
+            return Err(CssValueError::ExpectedComponentValue);
+        }
+
+        Ok(numeric)
+    }
+}
+
+/// The `font-variant-caps` property selects alternative glyphs of the font for capital letters, such as small capitals.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/font-variant-caps>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, EnumString)]
+#[strum(serialize_all = "kebab_case", ascii_case_insensitive)]
+pub enum FontVariantCaps {
+    #[default]
+    Normal,
+
+    /// Lowercase letters are shown as small capitals, the `smcp` feature.
+    SmallCaps,
+
+    /// Both lowercase and uppercase letters are shown as small capitals, the `c2sc` and `smcp` features.
+    AllSmallCaps,
+
+    /// Lowercase letters are shown as petite capitals, the `pcap` feature.
+    PetiteCaps,
+
+    /// Both lowercase and uppercase letters are shown as petite capitals, the `c2pc` and `pcap` features.
+    AllPetiteCaps,
+
+    /// Uppercase letters are shown as small capitals while lowercase letters are kept, the `unic` feature.
+    Unicase,
+
+    /// Capitals designed for titles in large font sizes, the `titl` feature.
+    TitlingCaps,
+}
+
+impl FontVariantCaps {
+    /// The OpenType features enabled by the value.
+    #[must_use]
+    pub fn feature_settings(self) -> Vec<([u8; 4], u32)> {
+        let tags: &[[u8; 4]] = match self {
+            Self::Normal => &[],
+            Self::SmallCaps => &[*b"smcp"],
+            Self::AllSmallCaps => &[*b"c2sc", *b"smcp"],
+            Self::PetiteCaps => &[*b"pcap"],
+            Self::AllPetiteCaps => &[*b"c2pc", *b"pcap"],
+            Self::Unicase => &[*b"unic"],
+            Self::TitlingCaps => &[*b"titl"],
+        };
+
+        tags.iter().map(|tag| (*tag, 1)).collect()
+    }
+
+    /// The feature turning lowercase letters into small or petite capitals, `None` for values that do not make
+    /// lowercase letters smaller capitals. Without it in the font, the capitals are synthesized from scaled uppercase
+    /// letters.
+    #[must_use]
+    pub const fn lowercase_feature(self) -> Option<[u8; 4]> {
+        match self {
+            Self::SmallCaps | Self::AllSmallCaps => Some(*b"smcp"),
+            Self::PetiteCaps | Self::AllPetiteCaps => Some(*b"pcap"),
+            Self::Normal | Self::Unicase | Self::TitlingCaps => None,
+        }
+    }
+
+    /// Whether uppercase letters are shown as small capitals as well.
+    #[must_use]
+    pub const fn includes_uppercase(self) -> bool {
+        matches!(self, Self::AllSmallCaps | Self::AllPetiteCaps)
+    }
+}
+
+impl CSSParsable for FontVariantCaps {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        stream
+            .next_non_whitespace()
+            .map_or(Err(CssValueError::ExpectedComponentValue), |cv| match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident) => ident
+                        .parse()
+                        .map_err(|_| CssValueError::InvalidValue(format!("Invalid font-variant-caps value: {ident}"))),
+                    _ => Err(CssValueError::InvalidToken(token.kind.clone())),
+                },
+                cvs => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            })
+    }
+}
+
+/// An OpenType feature and its value, such as `"smcp" 1`. A value of `0` disables the feature, values above `1` select
+/// one of the alternates of features such as `salt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FontFeatureSetting {
+    pub tag: [u8; 4],
+    pub value: u32,
+}
+
+/// The `font-feature-settings` property turns OpenType features of the font on and off by their four-letter tags, a
+/// low-level escape hatch for features without a `font-variant-*` property.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/font-feature-settings>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FontFeatureSettings(Vec<FontFeatureSetting>);
+
+impl FontFeatureSettings {
+    /// Creates the settings from a list of features, a feature listed more than once takes its last value.
+    #[must_use]
+    pub fn new(settings: &[FontFeatureSetting]) -> Self {
+        let mut features: Vec<FontFeatureSetting> = Vec::with_capacity(settings.len());
+
+        for setting in settings {
+            features.retain(|feature| feature.tag != setting.tag);
+            features.push(*setting);
+        }
+
+        Self(features)
+    }
+
+    /// The features set by the value, empty for `normal`.
+    #[must_use]
+    pub fn settings(&self) -> &[FontFeatureSetting] {
+        &self.0
+    }
+
+    /// Parses one `<string> [ <integer [0,∞]> | on | off ]?` entry of the list.
+    fn parse_setting(stream: &mut ComponentValueStream) -> Result<FontFeatureSetting, CssValueError> {
+        let tag = match stream.next_non_whitespace() {
+            Some(ComponentValue::Token(token)) => match &token.kind {
+                CssTokenKind::String(tag) => tag.clone(),
+                kind => return Err(CssValueError::InvalidToken(kind.clone())),
+            },
+            Some(cv) => return Err(CssValueError::InvalidComponentValue(cv.clone())),
+            None => return Err(CssValueError::ExpectedComponentValue),
+        };
+
+        let tag: [u8; 4] = tag
+            .as_bytes()
+            .try_into()
+            .ok()
+            .filter(|tag: &[u8; 4]| tag.iter().all(|byte| (0x20..=0x7e).contains(byte)))
+            .ok_or_else(|| CssValueError::InvalidValue(format!("Invalid OpenType feature tag: {tag}")))?;
+
+        let value = match stream.next_non_whitespace() {
+            None => 1,
+            Some(ComponentValue::Token(token)) => match &token.kind {
+                CssTokenKind::Ident(ident) if ident.eq_ignore_ascii_case("on") => 1,
+                CssTokenKind::Ident(ident) if ident.eq_ignore_ascii_case("off") => 0,
+                CssTokenKind::Number(NumericValue::Integer(value)) => u32::try_from(*value).map_err(|_| {
+                    CssValueError::InvalidValue(format!("Invalid font-feature-settings value: {value}"))
+                })?,
+                kind => return Err(CssValueError::InvalidToken(kind.clone())),
+            },
+            Some(cv) => return Err(CssValueError::InvalidComponentValue(cv.clone())),
+        };
+
+        if stream.next_non_whitespace().is_some() {
+            return Err(CssValueError::InvalidValue("Expected a comma between font features".to_string()));
+        }
+
+        Ok(FontFeatureSetting { tag, value })
+    }
+}
+
+impl CSSParsable for FontFeatureSettings {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        let values = stream.remaining();
+
+        let mut keyword = ComponentValueStream::new(values);
+        if let Some(ComponentValue::Token(token)) = keyword.next_non_whitespace()
+            && matches!(&token.kind, CssTokenKind::Ident(ident) if ident.eq_ignore_ascii_case("normal"))
+            && !keyword.has_remaining_tokens()
+        {
+            return Ok(Self::default());
+        }
+
+        let settings = values
+            .split(|cv| matches!(cv, ComponentValue::Token(token) if token.kind == CssTokenKind::Comma))
+            .map(|setting| Self::parse_setting(&mut ComponentValueStream::new(setting)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::new(&settings))
+    }
+}
+
+/// Represents the font weight property, which can be a keyword (normal, bold, bolder, lighter) or a numeric value
+/// between 1 and 1000.
 ///
 /// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/font-weight>
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum FontWeight {
-    Thin = 100,
-    ExtraLight = 200,
-    Light = 300,
+    /// The same as `400`.
     #[default]
-    Normal = 400,
-    Medium = 500,
-    SemiBold = 600,
-    Bold = 700,
-    ExtraBold = 800,
-    Black = 900,
-}
-
-impl From<u16> for FontWeight {
-    fn from(value: u16) -> Self {
-        match value {
-            100 => Self::Thin,
-            200 => Self::ExtraLight,
-            300 => Self::Light,
-            400 => Self::Normal,
-            500 => Self::Medium,
-            600 => Self::SemiBold,
-            700 => Self::Bold,
-            800 => Self::ExtraBold,
-            900 => Self::Black,
-            // TODO: Once we support variable font weights, we need to clamp between 1 and 1000 and drop the rounding logic.
-            _ => Self::from(((value.saturating_add(50)) / 100 * 100).clamp(100, 900)),
+    Normal,
+
+    /// The same as `700`.
+    Bold,
+
+    /// One weight bolder than the inherited weight.
+    Bolder,
+
+    /// One weight lighter than the inherited weight.
+    Lighter,
+
+    /// An exact weight, clamped between 1 and 1000.
+    Number(f32),
+}
+
+impl FontWeight {
+    /// The lightest weight a font can have.
+    pub const MIN: f32 = 1.0;
+
+    /// The boldest weight a font can have.
+    pub const MAX: f32 = 1000.0;
+
+    /// Create an exact weight, clamped between [`FontWeight::MIN`] and [`FontWeight::MAX`].
+    #[must_use]
+    pub const fn number(value: f32) -> Self {
+        Self::Number(value.clamp(Self::MIN, Self::MAX))
+    }
+
+    /// Resolve the weight to a number, `bolder` and `lighter` being relative to the inherited weight as defined by the
+    /// table in the specification.
+    ///
+    /// <https://www.w3.org/TR/css-fonts-4/#relative-weights>
+    ///
+    /// # Arguments
+    /// * `parent` - The computed weight of the parent element.
+    ///
+    /// # Returns
+    /// The numeric weight, between 1 and 1000.
+    #[must_use]
+    pub const fn resolve(self, parent: f32) -> f32 {
+        match self {
+            Self::Normal => 400.0,
+            Self::Bold => 700.0,
+            Self::Number(value) => value,
+            Self::Bolder => {
+                if parent < 350.0 {
+                    400.0
+                } else if parent < 550.0 {
+                    700.0
+                } else if parent < 900.0 {
+                    900.0
+                } else {
+                    parent
+                }
+            }
+            Self::Lighter => {
+                if parent < 100.0 {
+                    parent
+                } else if parent < 550.0 {
+                    100.0
+                } else if parent < 750.0 {
+                    400.0
+                } else {
+                    700.0
+                }
+            }
         }
     }
 }
 
+impl From<f32> for FontWeight {
+    fn from(value: f32) -> Self {
+        Self::number(value)
+    }
+}
+
 impl CSSParsable for FontWeight {
     fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
         stream
@@ -176,11 +750,15 @@ impl CSSParsable for FontWeight {
                             Ok(Self::Normal)
                         } else if ident.eq_ignore_ascii_case("bold") {
                             Ok(Self::Bold)
+                        } else if ident.eq_ignore_ascii_case("bolder") {
+                            Ok(Self::Bolder)
+                        } else if ident.eq_ignore_ascii_case("lighter") {
+                            Ok(Self::Lighter)
                         } else {
                             Err(CssValueError::InvalidValue(format!("Invalid font weight keyword: {ident}")))
                         }
                     }
-                    CssTokenKind::Number(num) => Ok(Self::from(num.to_f64() as u16)),
+                    CssTokenKind::Number(num) => Ok(Self::number(num.to_f64() as f32)),
                     _ => Err(CssValueError::InvalidToken(token.kind.clone())),
                 },
                 cvs => Err(CssValueError::InvalidComponentValue(cvs.clone())),
@@ -240,6 +818,78 @@ pub enum GenericName {
     FangSong,
 }
 
+/// The `hyphens` property specifies how words should be hyphenated when text wraps across multiple lines.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/hyphens>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, EnumString)]
+#[strum(serialize_all = "kebab_case", ascii_case_insensitive)]
+pub enum Hyphens {
+    /// Words are not broken at line breaks, even where soft hyphens (`&shy;`) suggest a break point.
+    None,
+
+    /// Words are only broken at soft hyphens (`&shy;`), where a visible hyphen is shown at the end of the line.
+    #[default]
+    Manual,
+
+    /// Words are broken at the hyphenation points chosen by the browser, in addition to soft hyphens.
+    Auto,
+}
+
+impl CSSParsable for Hyphens {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        stream
+            .next_non_whitespace()
+            .map_or(Err(CssValueError::ExpectedComponentValue), |cv| match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident) => ident
+                        .parse()
+                        .map_err(|_| CssValueError::InvalidValue(format!("Invalid hyphens value: {ident}"))),
+                    _ => Err(CssValueError::InvalidToken(token.kind.clone())),
+                },
+                cvs => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            })
+    }
+}
+
+/// The `hyphenate-character` property sets the string shown at the end of a line broken within a hyphenated word.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/hyphenate-character>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum HyphenateCharacter {
+    /// The hyphen of the typographic conventions of the language, a hyphen-minus (`-`) for every supported language.
+    #[default]
+    Auto,
+
+    /// The given string, e.g. `"="` for the double hyphen of Fraktur texts.
+    String(String),
+}
+
+impl HyphenateCharacter {
+    /// The string shown at the end of a hyphenated line.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Auto => "-",
+            Self::String(string) => string,
+        }
+    }
+}
+
+impl CSSParsable for HyphenateCharacter {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        stream
+            .next_non_whitespace()
+            .map_or(Err(CssValueError::ExpectedComponentValue), |cv| match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident) if ident.eq_ignore_ascii_case("auto") => Ok(Self::Auto),
+                    CssTokenKind::String(string) => Ok(Self::String(string.clone())),
+                    _ => Err(CssValueError::InvalidToken(token.kind.clone())),
+                },
+                cvs => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            })
+    }
+}
+
 /// The `line-height` property sets the height of a line box. It's commonly used to set the distance between lines of text.
 ///
 /// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/line-height>
@@ -276,7 +926,7 @@ impl CSSParsable for LineHeight {
                             .map_err(|_| CssValueError::InvalidUnit(unit.clone()))?;
                         Ok(Self::Length(Length::new(value.to_f64(), len_unit)))
                     }
-                    CssTokenKind::Percentage(pct) => Ok(Self::Percentage(Percentage::new(pct.to_f64()))),
+                    CssTokenKind::Percentage(pct) => Ok(Self::Percentage(Percentage::unbounded(pct.to_f64()))),
                     _ => Err(CssValueError::InvalidToken(token.kind.clone())),
                 },
                 cvs => Err(CssValueError::InvalidComponentValue(cvs.clone())),
@@ -298,6 +948,84 @@ pub enum RelativeSize {
     Larger,
 }
 
+/// The value of the `letter-spacing` and `word-spacing` properties, the space added between letters or words in
+/// addition to the spacing of the font.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/letter-spacing>
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Spacing {
+    /// The spacing of the font, without any space added.
+    #[default]
+    Normal,
+
+    /// A space added to the spacing of the font, which may be negative.
+    Length(Length),
+}
+
+impl Spacing {
+    #[must_use]
+    pub const fn px(value: f64) -> Self {
+        Self::Length(Length::px(value))
+    }
+}
+
+impl CSSParsable for Spacing {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        match stream.next_non_whitespace() {
+            Some(ComponentValue::Token(token)) => match &token.kind {
+                CssTokenKind::Ident(ident) if ident.eq_ignore_ascii_case("normal") => Ok(Self::Normal),
+                CssTokenKind::Number(num) if num.to_f64() == 0.0 => Ok(Self::px(0.0)),
+                CssTokenKind::Dimension { value, unit } => {
+                    let len_unit = unit
+                        .parse::<LengthUnit>()
+                        .map_err(|_| CssValueError::InvalidUnit(unit.clone()))?;
+                    Ok(Self::Length(Length::new(value.to_f64(), len_unit)))
+                }
+                _ => Err(CssValueError::InvalidToken(token.kind.clone())),
+            },
+            Some(cvs) => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            None => Err(CssValueError::ExpectedComponentValue),
+        }
+    }
+}
+
+/// The `tab-size` property sets the width of the tab characters kept by `white-space: pre` and `pre-wrap`.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/tab-size>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TabSize {
+    /// A multiple of the width of the space character of the font.
+    Number(f64),
+
+    /// A fixed width.
+    Length(Length),
+}
+
+impl Default for TabSize {
+    fn default() -> Self {
+        Self::Number(8.0)
+    }
+}
+
+impl CSSParsable for TabSize {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        match stream.next_non_whitespace() {
+            Some(ComponentValue::Token(token)) => match &token.kind {
+                CssTokenKind::Number(num) if num.to_f64() >= 0.0 => Ok(Self::Number(num.to_f64())),
+                CssTokenKind::Dimension { value, unit } if value.to_f64() >= 0.0 => {
+                    let len_unit = unit
+                        .parse::<LengthUnit>()
+                        .map_err(|_| CssValueError::InvalidUnit(unit.clone()))?;
+                    Ok(Self::Length(Length::new(value.to_f64(), len_unit)))
+                }
+                _ => Err(CssValueError::InvalidToken(token.kind.clone())),
+            },
+            Some(cvs) => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            None => Err(CssValueError::ExpectedComponentValue),
+        }
+    }
+}
+
 /// The `text-align` property describes how inline content of a block element is aligned. It has no effect on non-block elements or when `display: table-cell` is used.
 ///
 /// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/text-align>
@@ -343,6 +1071,59 @@ impl CSSParsable for TextAlign {
     }
 }
 
+/// The `text-indent` property sets the indentation of the first line of text in a block container.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/text-indent>
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextIndent {
+    /// The indentation, a percentage is relative to the width of the containing block.
+    pub length: OffsetValue,
+
+    /// `hanging`, indents every line except the first one instead.
+    pub hanging: bool,
+
+    /// `each-line`, also indents the lines after a forced line break.
+    pub each_line: bool,
+}
+
+impl CSSParsable for TextIndent {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        let mut length = None;
+        let mut hanging = false;
+        let mut each_line = false;
+
+        while let Some(cv) = stream.next_non_whitespace() {
+            match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident) if ident.eq_ignore_ascii_case("hanging") && !hanging => hanging = true,
+                    CssTokenKind::Ident(ident) if ident.eq_ignore_ascii_case("each-line") && !each_line => {
+                        each_line = true;
+                    }
+                    CssTokenKind::Ident(ident) => {
+                        return Err(CssValueError::InvalidValue(format!("Invalid text-indent value: {ident}")));
+                    }
+                    _ if length.is_none() => {
+                        length = Some(OffsetValue::parse(&mut ComponentValueStream::new(std::slice::from_ref(cv)))?);
+                    }
+                    _ => return Err(CssValueError::InvalidToken(token.kind.clone())),
+                },
+                ComponentValue::Function(_) if length.is_none() => {
+                    length = Some(OffsetValue::parse(&mut ComponentValueStream::new(std::slice::from_ref(cv)))?);
+                }
+                cvs => return Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            }
+        }
+
+        let length = length.ok_or(CssValueError::ExpectedComponentValue)?;
+
+        Ok(Self {
+            length,
+            hanging,
+            each_line,
+        })
+    }
+}
+
 /// The `white-space` property
 ///
 /// Describes how whitespace inside an element is handled. It can be used to control whether and how whitespace is collapsed,
@@ -392,6 +1173,42 @@ impl CSSParsable for Whitespace {
     }
 }
 
+/// The `word-break` property sets whether line breaks appear wherever the text would otherwise overflow its content box.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/Reference/Properties/word-break>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, EnumString)]
+#[strum(serialize_all = "kebab_case", ascii_case_insensitive)]
+pub enum WordBreak {
+    /// Lines are broken at the usual break opportunities, such as the spaces between words.
+    #[default]
+    Normal,
+
+    /// Lines may be broken between any two characters, commonly used for CJK text mixed with long words.
+    BreakAll,
+
+    /// Lines are not broken within CJK text, other text behaves as with `normal`.
+    KeepAll,
+
+    /// Deprecated, behaves as `normal` combined with `overflow-wrap: anywhere`.
+    BreakWord,
+}
+
+impl CSSParsable for WordBreak {
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        stream
+            .next_non_whitespace()
+            .map_or(Err(CssValueError::ExpectedComponentValue), |cv| match cv {
+                ComponentValue::Token(token) => match &token.kind {
+                    CssTokenKind::Ident(ident) => ident
+                        .parse()
+                        .map_err(|_| CssValueError::InvalidValue(format!("Invalid word-break value: {ident}"))),
+                    _ => Err(CssValueError::InvalidToken(token.kind.clone())),
+                },
+                cvs => Err(CssValueError::InvalidComponentValue(cvs.clone())),
+            })
+    }
+}
+
 /// The `writing-mode` property defines whether lines of text are laid out horizontally or vertically, and the direction in which blocks progress.
 ///
 /// It also affects the orientation of certain characters and the behavior of text alignment and justification.
@@ -449,6 +1266,145 @@ mod tests {
         assert_eq!("monospace".parse(), Ok(GenericName::Monospace));
     }
 
+    #[test]
+    fn test_parse_font_kerning() {
+        assert_eq!("auto".parse(), Ok(FontKerning::Auto));
+        assert_eq!("NONE".parse(), Ok(FontKerning::None));
+        assert!("off".parse::<FontKerning>().is_err());
+    }
+
+    fn idents(keywords: &[&str]) -> Vec<ComponentValue> {
+        use css_cssom::CssToken;
+
+        keywords
+            .iter()
+            .flat_map(|keyword| {
+                [
+                    CssTokenKind::Ident((*keyword).to_string()),
+                    CssTokenKind::Whitespace,
+                ]
+            })
+            .map(|kind| {
+                ComponentValue::Token(CssToken {
+                    kind,
+                    position: Default::default(),
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_font_display() {
+        let input = idents(&["fallback"]);
+        assert_eq!(FontDisplay::parse(&mut ComponentValueStream::new(&input)), Ok(FontDisplay::Fallback));
+        assert_eq!("SWAP".parse(), Ok(FontDisplay::Swap));
+        assert!("eventually".parse::<FontDisplay>().is_err());
+    }
+
+    #[test]
+    fn test_parse_font_size_adjust() {
+        use css_cssom::{CssToken, NumericValue};
+
+        let input = idents(&["from-font"]);
+        assert_eq!(FontSizeAdjust::parse(&mut ComponentValueStream::new(&input)), Ok(FontSizeAdjust::FromFont));
+
+        let input = [ComponentValue::Token(CssToken {
+            kind: CssTokenKind::Number(NumericValue::from(0.5)),
+            position: Default::default(),
+        })];
+        assert_eq!(FontSizeAdjust::parse(&mut ComponentValueStream::new(&input)), Ok(FontSizeAdjust::Number(0.5)));
+
+        let input = idents(&["auto"]);
+        assert!(FontSizeAdjust::parse(&mut ComponentValueStream::new(&input)).is_err());
+    }
+
+    #[test]
+    fn test_parse_font_variant_ligatures() {
+        let input = idents(&["none"]);
+        let ligatures = FontVariantLigatures::parse(&mut ComponentValueStream::new(&input)).unwrap();
+        assert_eq!(ligatures, FontVariantLigatures::NONE);
+        assert!(ligatures.feature_settings().contains(&(*b"liga", 0)));
+        assert!(ligatures.feature_settings().contains(&(*b"calt", 0)));
+
+        let input = idents(&["discretionary-ligatures", "no-contextual"]);
+        let ligatures = FontVariantLigatures::parse(&mut ComponentValueStream::new(&input)).unwrap();
+        assert_eq!(ligatures.feature_settings(), vec![(*b"dlig", 1), (*b"calt", 0)]);
+
+        let input = idents(&["contextual", "no-contextual"]);
+        assert!(FontVariantLigatures::parse(&mut ComponentValueStream::new(&input)).is_err());
+
+        let input = idents(&["normal", "contextual"]);
+        assert!(FontVariantLigatures::parse(&mut ComponentValueStream::new(&input)).is_err());
+    }
+
+    #[test]
+    fn test_parse_font_variant_numeric() {
+        let input = idents(&["tabular-nums", "slashed-zero"]);
+        let numeric = FontVariantNumeric::parse(&mut ComponentValueStream::new(&input)).unwrap();
+        assert_eq!(numeric.spacing, Some(NumericSpacing::Tabular));
+        assert_eq!(numeric.feature_settings(), vec![(*b"tnum", 1), (*b"zero", 1)]);
+
+        let input = idents(&["normal"]);
+        assert_eq!(
+            FontVariantNumeric::parse(&mut ComponentValueStream::new(&input)),
+            Ok(FontVariantNumeric::default())
+        );
+
+        let input = idents(&["tabular-nums", "proportional-nums"]);
+        assert!(FontVariantNumeric::parse(&mut ComponentValueStream::new(&input)).is_err());
+    }
+
+    #[test]
+    fn test_parse_font_weight() {
+        use css_cssom::{CssToken, NumericValue};
+
+        let input = idents(&["lighter"]);
+        assert_eq!(FontWeight::parse(&mut ComponentValueStream::new(&input)), Ok(FontWeight::Lighter));
+
+        let token = |value: i64| {
+            [ComponentValue::Token(CssToken {
+                kind: CssTokenKind::Number(NumericValue::from(value)),
+                position: Default::default(),
+            })]
+        };
+        assert_eq!(FontWeight::parse(&mut ComponentValueStream::new(&token(350))), Ok(FontWeight::Number(350.0)));
+        assert_eq!(FontWeight::parse(&mut ComponentValueStream::new(&token(1200))), Ok(FontWeight::Number(1000.0)));
+    }
+
+    #[test]
+    fn test_resolve_relative_font_weight() {
+        assert!((FontWeight::Bolder.resolve(400.0) - 700.0).abs() < f32::EPSILON);
+        assert!((FontWeight::Bolder.resolve(300.0) - 400.0).abs() < f32::EPSILON);
+        assert!((FontWeight::Bolder.resolve(950.0) - 950.0).abs() < f32::EPSILON);
+        assert!((FontWeight::Lighter.resolve(700.0) - 400.0).abs() < f32::EPSILON);
+        assert!((FontWeight::Lighter.resolve(400.0) - 100.0).abs() < f32::EPSILON);
+        assert!((FontWeight::Lighter.resolve(50.0) - 50.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_parse_spacing() {
+        use css_cssom::{CssToken, NumericValue};
+
+        let token = |kind| {
+            ComponentValue::Token(CssToken {
+                kind,
+                position: Default::default(),
+            })
+        };
+
+        let input = [token(CssTokenKind::Ident("normal".to_string()))];
+        assert_eq!(Spacing::parse(&mut ComponentValueStream::new(&input)), Ok(Spacing::Normal));
+
+        let input = [token(CssTokenKind::Dimension {
+            value: NumericValue::from(-1),
+            unit: "px".to_string(),
+        })];
+        assert_eq!(Spacing::parse(&mut ComponentValueStream::new(&input)), Ok(Spacing::px(-1.0)));
+
+        let input = [token(CssTokenKind::Number(NumericValue::from(3)))];
+        assert!(Spacing::parse(&mut ComponentValueStream::new(&input)).is_err());
+    }
+
     #[test]
     fn test_parse_text_align() {
         assert_eq!("start".parse(), Ok(TextAlign::Start));
@@ -460,4 +1416,169 @@ mod tests {
         assert_eq!("match-parent".parse(), Ok(TextAlign::MatchParent));
         assert!("unknown".parse::<TextAlign>().is_err());
     }
+
+    #[test]
+    fn test_parse_tab_size() {
+        use css_cssom::{CssToken, NumericValue};
+
+        let token = |kind| {
+            ComponentValue::Token(CssToken {
+                kind,
+                position: Default::default(),
+            })
+        };
+
+        let input = [token(CssTokenKind::Number(NumericValue::from(4)))];
+        assert_eq!(TabSize::parse(&mut ComponentValueStream::new(&input)), Ok(TabSize::Number(4.0)));
+
+        let input = [token(CssTokenKind::Dimension {
+            value: NumericValue::from(20),
+            unit: "px".to_string(),
+        })];
+        assert_eq!(TabSize::parse(&mut ComponentValueStream::new(&input)), Ok(TabSize::Length(Length::px(20.0))));
+
+        let input = [token(CssTokenKind::Number(NumericValue::from(-1)))];
+        assert!(TabSize::parse(&mut ComponentValueStream::new(&input)).is_err());
+    }
+
+    #[test]
+    fn test_parse_text_indent() {
+        use css_cssom::{CssToken, NumericValue};
+
+        let token = |kind| {
+            ComponentValue::Token(CssToken {
+                kind,
+                position: Default::default(),
+            })
+        };
+
+        let input = [token(CssTokenKind::Dimension {
+            value: NumericValue::from(2),
+            unit: "em".to_string(),
+        })];
+        assert_eq!(
+            TextIndent::parse(&mut ComponentValueStream::new(&input)),
+            Ok(TextIndent {
+                length: OffsetValue::Length(Length::new(2.0, LengthUnit::Em)),
+                hanging: false,
+                each_line: false,
+            })
+        );
+
+        let input = [
+            token(CssTokenKind::Ident("each-line".to_string())),
+            token(CssTokenKind::Whitespace),
+            token(CssTokenKind::Percentage(NumericValue::from(10))),
+            token(CssTokenKind::Whitespace),
+            token(CssTokenKind::Ident("hanging".to_string())),
+        ];
+        assert_eq!(
+            TextIndent::parse(&mut ComponentValueStream::new(&input)),
+            Ok(TextIndent {
+                length: OffsetValue::Percentage(Percentage::new(10.0)),
+                hanging: true,
+                each_line: true,
+            })
+        );
+
+        let input = [token(CssTokenKind::Ident("hanging".to_string()))];
+        assert!(TextIndent::parse(&mut ComponentValueStream::new(&input)).is_err());
+    }
+
+    #[test]
+    fn test_parse_word_break_and_hyphens() {
+        assert_eq!("break-all".parse(), Ok(WordBreak::BreakAll));
+        assert_eq!("KEEP-ALL".parse(), Ok(WordBreak::KeepAll));
+        assert_eq!("break-word".parse(), Ok(WordBreak::BreakWord));
+        assert_eq!("auto".parse(), Ok(Hyphens::Auto));
+        assert_eq!("none".parse(), Ok(Hyphens::None));
+        assert!("anywhere".parse::<WordBreak>().is_err());
+    }
+
+    #[test]
+    fn test_font_variant_caps() {
+        assert_eq!("all-small-caps".parse(), Ok(FontVariantCaps::AllSmallCaps));
+        assert_eq!("Titling-Caps".parse(), Ok(FontVariantCaps::TitlingCaps));
+        assert!("smallcaps".parse::<FontVariantCaps>().is_err());
+
+        assert_eq!(FontVariantCaps::AllSmallCaps.feature_settings(), [(*b"c2sc", 1), (*b"smcp", 1)]);
+        assert_eq!(FontVariantCaps::PetiteCaps.lowercase_feature(), Some(*b"pcap"));
+        assert_eq!(FontVariantCaps::Unicase.lowercase_feature(), None);
+    }
+
+    #[test]
+    fn test_parse_font_feature_settings() {
+        use css_cssom::CssToken;
+
+        let token = |kind| {
+            ComponentValue::Token(CssToken {
+                kind,
+                position: Default::default(),
+            })
+        };
+        let string = |tag: &str| token(CssTokenKind::String(tag.to_string()));
+        let ident = |ident: &str| token(CssTokenKind::Ident(ident.to_string()));
+        let parse = |values: &[ComponentValue]| FontFeatureSettings::parse(&mut ComponentValueStream::new(values));
+
+        let settings = parse(&[
+            string("smcp"),
+            token(CssTokenKind::Whitespace),
+            token(CssTokenKind::Number(NumericValue::Integer(1))),
+            token(CssTokenKind::Comma),
+            string("zero"),
+            token(CssTokenKind::Comma),
+            string("liga"),
+            ident("off"),
+            token(CssTokenKind::Comma),
+            string("smcp"),
+            ident("OFF"),
+        ])
+        .unwrap();
+        assert_eq!(
+            settings.settings(),
+            [
+                FontFeatureSetting {
+                    tag: *b"zero",
+                    value: 1
+                },
+                FontFeatureSetting {
+                    tag: *b"liga",
+                    value: 0
+                },
+                FontFeatureSetting {
+                    tag: *b"smcp",
+                    value: 0
+                },
+            ]
+        );
+
+        assert_eq!(parse(&[ident("normal")]), Ok(FontFeatureSettings::default()));
+        assert!(parse(&[string("toolong")]).is_err());
+        assert!(
+            parse(&[
+                string("smcp"),
+                token(CssTokenKind::Number(NumericValue::Integer(-1)))
+            ])
+            .is_err()
+        );
+        assert!(parse(&[string("smcp"), string("zero")]).is_err());
+    }
+
+    #[test]
+    fn test_parse_hyphenate_character() {
+        use css_cssom::CssToken;
+
+        let token = |kind| {
+            ComponentValue::Token(CssToken {
+                kind,
+                position: Default::default(),
+            })
+        };
+        let parse = |kind| HyphenateCharacter::parse(&mut ComponentValueStream::new(&[token(kind)]));
+
+        assert_eq!(parse(CssTokenKind::Ident("AUTO".to_string())), Ok(HyphenateCharacter::Auto));
+        assert_eq!(parse(CssTokenKind::String("=".to_string())), Ok(HyphenateCharacter::String("=".to_string())));
+        assert!(parse(CssTokenKind::Ident("none".to_string())).is_err());
+        assert_eq!(HyphenateCharacter::Auto.as_str(), "-");
+    }
 }
@@ -0,0 +1,170 @@
+use css_cssom::{ComponentValue, ComponentValueStream, CssTokenKind};
+
+use crate::{CSSParsable, error::CssValueError};
+
+/// The largest valid Unicode code point.
+const MAX_CODE_POINT: u32 = 0x0010_FFFF;
+
+/// A range of Unicode code points, as used by the `unicode-range` descriptor of `@font-face`.
+///
+/// <https://drafts.csswg.org/css-fonts/#unicode-range-desc>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnicodeRange {
+    /// A single code point, e.g. `U+0025`.
+    Single(u32),
+
+    /// An inclusive range of code points, e.g. `U+0025-0040` or `U+00??`.
+    Range(u32, u32),
+}
+
+impl UnicodeRange {
+    /// The range covering every code point, the initial value of `unicode-range`.
+    pub const ALL: Self = Self::Range(0, MAX_CODE_POINT);
+
+    /// Checks whether the range contains the given code point.
+    #[must_use]
+    pub const fn contains(&self, code_point: u32) -> bool {
+        match self {
+            Self::Single(single) => *single == code_point,
+            Self::Range(start, end) => *start <= code_point && code_point <= *end,
+        }
+    }
+
+    /// Parses a comma-separated list of ranges, e.g. `U+0000-00FF, U+0131, U+4??`.
+    ///
+    /// # Errors
+    /// Returns an error if any of the ranges is invalid, in which case the whole descriptor is ignored.
+    pub fn parse_list(stream: &mut ComponentValueStream) -> Result<Vec<Self>, CssValueError> {
+        let mut ranges = vec![Self::parse(stream)?];
+
+        while stream.has_remaining_tokens() {
+            match stream.next_cv() {
+                Some(ComponentValue::Token(token)) if token.kind == CssTokenKind::Comma => {
+                    ranges.push(Self::parse(stream)?);
+                }
+                Some(cv) => return Err(CssValueError::InvalidComponentValue(cv.clone())),
+                None => break,
+            }
+        }
+
+        Ok(ranges)
+    }
+
+    /// Parses the text of a range after the `U+` prefix, e.g. `0025-0040` or `00??`.
+    fn from_text(text: &str) -> Result<Self, CssValueError> {
+        let invalid = || CssValueError::InvalidValue(format!("Invalid unicode-range: U+{text}"));
+
+        let parse_hex = |digits: &str| {
+            if digits.is_empty() || digits.len() > 6 {
+                return Err(invalid());
+            }
+
+            u32::from_str_radix(digits, 16).map_err(|_| invalid())
+        };
+
+        let range = if let Some((start, end)) = text.split_once('-') {
+            Self::Range(parse_hex(start)?, parse_hex(end)?)
+        } else if text.contains('?') {
+            let digits = text.trim_end_matches('?');
+            if digits.contains('?') {
+                return Err(invalid());
+            }
+
+            let wildcards = text.len() - digits.len();
+            let prefix = text.replace('?', "0");
+            let start = parse_hex(&prefix)?;
+            let end = start | (16u32.pow(wildcards as u32) - 1);
+
+            Self::Range(start, end)
+        } else {
+            Self::Single(parse_hex(text)?)
+        };
+
+        match range {
+            Self::Single(code_point) if code_point <= MAX_CODE_POINT => Ok(range),
+            Self::Range(start, end) if start <= end && end <= MAX_CODE_POINT => Ok(range),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl CSSParsable for UnicodeRange {
+    /// Parses a single range from a unicode-range token, which keeps the text of the range as it was written.
+    fn parse(stream: &mut ComponentValueStream) -> Result<Self, CssValueError> {
+        match stream.next_non_whitespace() {
+            Some(ComponentValue::Token(token)) => match &token.kind {
+                CssTokenKind::UnicodeRange(text) => Self::from_text(text),
+                kind => Err(CssValueError::InvalidToken(kind.clone())),
+            },
+            Some(cv) => Err(CssValueError::InvalidComponentValue(cv.clone())),
+            None => Err(CssValueError::UnexpectedEndOfInput),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use css_cssom::CSSStyleSheet;
+
+    use super::*;
+
+    fn parse(css: &str) -> Result<Vec<UnicodeRange>, CssValueError> {
+        let decls = CSSStyleSheet::from_inline(&format!("unicode-range: {css};"));
+        let mut stream = ComponentValueStream::new(&decls[0].original_values);
+
+        UnicodeRange::parse_list(&mut stream)
+    }
+
+    #[test]
+    fn test_parse_single() {
+        assert_eq!(parse("U+0025"), Ok(vec![UnicodeRange::Single(0x25)]));
+        assert_eq!(parse("u+a0"), Ok(vec![UnicodeRange::Single(0xA0)]));
+        assert_eq!(parse("U+0E00"), Ok(vec![UnicodeRange::Single(0xE00)]));
+        assert_eq!(parse("U+2E80"), Ok(vec![UnicodeRange::Single(0x2E80)]));
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse("U+0025-0040"), Ok(vec![UnicodeRange::Range(0x25, 0x40)]));
+        assert_eq!(parse("U+00a0-00ff"), Ok(vec![UnicodeRange::Range(0xA0, 0xFF)]));
+        assert_eq!(parse("U+0400-04FF"), Ok(vec![UnicodeRange::Range(0x400, 0x4FF)]));
+        assert_eq!(parse("U+4E00-9FFF"), Ok(vec![UnicodeRange::Range(0x4E00, 0x9FFF)]));
+        assert_eq!(parse("U+2E80-2EFF"), Ok(vec![UnicodeRange::Range(0x2E80, 0x2EFF)]));
+    }
+
+    #[test]
+    fn test_parse_wildcard() {
+        assert_eq!(parse("U+00??"), Ok(vec![UnicodeRange::Range(0x00, 0xFF)]));
+        assert_eq!(parse("U+4??"), Ok(vec![UnicodeRange::Range(0x400, 0x4FF)]));
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(
+            parse("U+0000-00FF, U+0131, U+4??"),
+            Ok(vec![
+                UnicodeRange::Range(0x00, 0xFF),
+                UnicodeRange::Single(0x131),
+                UnicodeRange::Range(0x400, 0x4FF),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse("U+0040-0025").is_err());
+        assert!(parse("U+110000").is_err());
+        assert!(parse("U+0000025").is_err());
+        assert!(parse("U+0025-0000040").is_err());
+        assert!(parse("U+0?1").is_err());
+        assert!(parse("0025").is_err());
+    }
+
+    #[test]
+    fn test_contains() {
+        assert!(UnicodeRange::Range(0x25, 0x40).contains(0x30));
+        assert!(!UnicodeRange::Range(0x25, 0x40).contains(0x41));
+        assert!(UnicodeRange::Single(0x131).contains(0x131));
+        assert!(UnicodeRange::ALL.contains(0x4E2D));
+    }
+}
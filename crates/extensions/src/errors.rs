@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+/// Errors that make an extension manifest unusable.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ManifestError {
+    #[error("invalid manifest JSON: {0}")]
+    Json(String),
+
+    #[error("unsupported manifest version {0}, expected 2 or 3")]
+    UnsupportedVersion(u8),
+
+    #[error("the manifest has no name")]
+    MissingName,
+
+    #[error("a content script has no match patterns")]
+    MissingMatches,
+}
+
+impl From<serde_json::Error> for ManifestError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error.to_string())
+    }
+}
@@ -0,0 +1,12 @@
+//! Parsing of the `manifest.json` of browser extensions, describing the scripts an extension runs and the permissions
+//! it needs.
+//!
+//! <https://developer.mozilla.org/en-US/docs/Mozilla/Add-ons/WebExtensions/manifest.json>
+
+pub mod errors;
+mod manifest;
+
+pub use manifest::{
+    BackgroundScript, BrowserAction, ContentScript, ExtensionIcon, ExtensionManifest, MANIFEST_FILE_NAME, Permission,
+    RunAt,
+};
@@ -0,0 +1,270 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::errors::ManifestError;
+
+/// The name of the manifest file at the root of an extension directory.
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// An API or host an extension asks to access, from the `permissions` and `host_permissions` keys.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(from = "String")]
+pub enum Permission {
+    ActiveTab,
+    Cookies,
+    Downloads,
+    History,
+    Notifications,
+    Scripting,
+    Storage,
+    Tabs,
+    WebNavigation,
+    WebRequest,
+    WebRequestBlocking,
+
+    /// A match pattern of the hosts the extension can access, such as `https://*.example.com/*` or `<all_urls>`.
+    Host(String),
+
+    /// A permission that is not known to the browser, kept so that it can be reported.
+    Other(String),
+}
+
+impl From<String> for Permission {
+    fn from(permission: String) -> Self {
+        match permission.as_str() {
+            "activeTab" => Self::ActiveTab,
+            "cookies" => Self::Cookies,
+            "downloads" => Self::Downloads,
+            "history" => Self::History,
+            "notifications" => Self::Notifications,
+            "scripting" => Self::Scripting,
+            "storage" => Self::Storage,
+            "tabs" => Self::Tabs,
+            "webNavigation" => Self::WebNavigation,
+            "webRequest" => Self::WebRequest,
+            "webRequestBlocking" => Self::WebRequestBlocking,
+            _ if permission == "<all_urls>" || permission.contains("://") => Self::Host(permission),
+            _ => Self::Other(permission),
+        }
+    }
+}
+
+/// The scripts an extension runs in the background, either the `scripts` of a manifest version 2 background page or
+/// the `service_worker` of manifest version 3.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct BackgroundScript {
+    pub scripts: Vec<String>,
+    pub service_worker: Option<String>,
+
+    /// Whether the background page stays loaded, `None` when the manifest leaves it out.
+    pub persistent: Option<bool>,
+}
+
+/// When a content script is injected into a page.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunAt {
+    /// After the stylesheets are loaded, before the DOM is built.
+    DocumentStart,
+
+    /// Once the DOM is built, before its sub-resources are loaded.
+    DocumentEnd,
+
+    /// Once the page has loaded, or shortly after `DocumentEnd`.
+    #[default]
+    DocumentIdle,
+}
+
+/// Scripts and stylesheets injected into the pages whose URL matches one of the `matches` patterns.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ContentScript {
+    /// The match patterns of the pages to inject into, such as `https://*.example.com/*`.
+    pub matches: Vec<String>,
+
+    /// The match patterns of the pages to leave out, even when they match `matches`.
+    #[serde(default)]
+    pub exclude_matches: Vec<String>,
+
+    /// The scripts to run, relative to the extension directory.
+    #[serde(default)]
+    pub js: Vec<String>,
+
+    /// The stylesheets to inject, relative to the extension directory.
+    #[serde(default)]
+    pub css: Vec<String>,
+
+    #[serde(default)]
+    pub run_at: RunAt,
+
+    /// Whether the scripts are also injected into the frames of the page.
+    #[serde(default)]
+    pub all_frames: bool,
+}
+
+/// An icon of an extension, either a single image or images by their size in pixels.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum ExtensionIcon {
+    Path(String),
+    Sizes(BTreeMap<String, String>),
+}
+
+/// The button an extension adds to the toolbar, from `browser_action` in manifest version 2 or `action` in version 3.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct BrowserAction {
+    pub default_title: Option<String>,
+    pub default_icon: Option<ExtensionIcon>,
+
+    /// The HTML page shown in a popup when the button is clicked, relative to the extension directory.
+    pub default_popup: Option<String>,
+}
+
+/// The parsed `manifest.json` of an extension.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ExtensionManifest {
+    pub manifest_version: u8,
+    pub name: String,
+    pub version: String,
+
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The permissions of the extension, including the hosts of `host_permissions` in manifest version 3.
+    #[serde(default)]
+    pub permissions: Vec<Permission>,
+
+    #[serde(default)]
+    host_permissions: Vec<Permission>,
+
+    #[serde(default)]
+    pub background: Option<BackgroundScript>,
+
+    #[serde(default)]
+    pub content_scripts: Vec<ContentScript>,
+
+    #[serde(default, alias = "action")]
+    pub browser_action: Option<BrowserAction>,
+}
+
+impl ExtensionManifest {
+    /// Parses and validates the content of a `manifest.json`. Keys that are not known are ignored.
+    ///
+    /// # Errors
+    /// * If the JSON is invalid or a key has a value of the wrong type.
+    /// * If the `manifest_version` is not 2 or 3.
+    /// * If the name is empty, or a content script has no match patterns.
+    pub fn parse(json: &str) -> Result<Self, ManifestError> {
+        let mut manifest: Self = serde_json::from_str(json)?;
+
+        if !matches!(manifest.manifest_version, 2 | 3) {
+            return Err(ManifestError::UnsupportedVersion(manifest.manifest_version));
+        }
+
+        if manifest.name.trim().is_empty() {
+            return Err(ManifestError::MissingName);
+        }
+
+        if manifest
+            .content_scripts
+            .iter()
+            .any(|script| script.matches.is_empty())
+        {
+            return Err(ManifestError::MissingMatches);
+        }
+
+        let host_permissions = std::mem::take(&mut manifest.host_permissions);
+        manifest.permissions.extend(host_permissions);
+
+        Ok(manifest)
+    }
+
+    /// The match patterns of the pages any content script of the extension is injected into.
+    pub fn content_script_matches(&self) -> impl Iterator<Item = &str> {
+        self.content_scripts
+            .iter()
+            .flat_map(|script| script.matches.iter().map(String::as_str))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest_v2() {
+        let manifest = ExtensionManifest::parse(
+            r#"{
+                "manifest_version": 2,
+                "name": "Dark Reader",
+                "version": "4.9.0",
+                "permissions": ["storage", "tabs", "<all_urls>", "unlimitedStorage"],
+                "background": { "scripts": ["background.js"], "persistent": false },
+                "content_scripts": [{
+                    "matches": ["https://*/*", "http://*/*"],
+                    "js": ["inject.js"],
+                    "run_at": "document_start"
+                }],
+                "browser_action": { "default_title": "Dark Reader", "default_icon": "icon.png" }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.name, "Dark Reader");
+        assert_eq!(
+            manifest.permissions,
+            [
+                Permission::Storage,
+                Permission::Tabs,
+                Permission::Host("<all_urls>".to_string()),
+                Permission::Other("unlimitedStorage".to_string()),
+            ]
+        );
+        assert_eq!(manifest.background.unwrap().scripts, ["background.js"]);
+        assert_eq!(manifest.content_scripts[0].run_at, RunAt::DocumentStart);
+        assert_eq!(manifest.content_script_matches().collect::<Vec<_>>(), ["https://*/*", "http://*/*"]);
+        assert_eq!(manifest.browser_action.unwrap().default_icon, Some(ExtensionIcon::Path("icon.png".to_string())));
+    }
+
+    #[test]
+    fn test_parse_manifest_v3() {
+        let manifest = ExtensionManifest::parse(
+            r#"{
+                "manifest_version": 3,
+                "name": "Reader",
+                "version": "1.0",
+                "host_permissions": ["https://example.com/*"],
+                "background": { "service_worker": "worker.js" },
+                "action": { "default_popup": "popup.html", "default_icon": { "16": "icon16.png" } },
+                "unknown_key": true
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.permissions, [Permission::Host("https://example.com/*".to_string())]);
+        assert_eq!(manifest.background.unwrap().service_worker.as_deref(), Some("worker.js"));
+        assert_eq!(manifest.browser_action.unwrap().default_popup.as_deref(), Some("popup.html"));
+        assert!(manifest.content_scripts.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_manifests() {
+        assert_eq!(
+            ExtensionManifest::parse(r#"{ "manifest_version": 1, "name": "Old", "version": "1" }"#),
+            Err(ManifestError::UnsupportedVersion(1))
+        );
+        assert_eq!(
+            ExtensionManifest::parse(r#"{ "manifest_version": 3, "name": " ", "version": "1" }"#),
+            Err(ManifestError::MissingName)
+        );
+        assert_eq!(
+            ExtensionManifest::parse(
+                r#"{ "manifest_version": 3, "name": "A", "version": "1", "content_scripts": [{ "matches": [] }] }"#
+            ),
+            Err(ManifestError::MissingMatches)
+        );
+        assert!(matches!(ExtensionManifest::parse(r#"{ "name": "A" }"#), Err(ManifestError::Json(_))));
+    }
+}
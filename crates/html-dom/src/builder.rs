@@ -1,8 +1,8 @@
-use html_escape::decode_html_entities;
 use html_tokenizer::{Token, TokenKind};
 
 use crate::{
     collector::{Collector, TagInfo},
+    decode::{decode_attribute_value_lossy, decode_character_references_lossy},
     dom::{DocumentRoot, Element, NodeData, NodeId},
     tag::Tag,
 };
@@ -81,6 +81,24 @@ impl<C: Collector + Default> DomTreeBuilder<C> {
         }
     }
 
+    /// The DOM tree built so far, which scripts run while parsing is blocked may modify.
+    pub const fn dom_mut(&mut self) -> &mut DocumentRoot {
+        &mut self.dom_tree
+    }
+
+    /// Inserts text as a child of the last open element without decoding HTML entities, used for the markup of
+    /// foreign content such as an inline `<svg>` element that is kept as source.
+    ///
+    /// # Arguments
+    /// * `text` - The raw text to insert.
+    pub fn insert_raw_text(&mut self, text: String) {
+        if text.is_empty() || self.open_elements.is_empty() {
+            return;
+        }
+
+        self.insert_node(&NodeData::Text(text));
+    }
+
     /// Inserts a node reference into the DOM tree, either as a child of the last open element or as a root node.
     ///
     /// # Arguments
@@ -98,19 +116,16 @@ impl<C: Collector + Default> DomTreeBuilder<C> {
     /// # Arguments
     /// * `new_tag` - A reference to the `HtmlTag` representing the new tag being processed.
     fn handle_auto_close(&mut self, new_tag: &Tag) {
-        let should_pop = if let Some(last_id) = self.open_elements.last() {
-            let last_node = &self.dom_tree[last_id];
+        while let Some(last_id) = self.open_elements.last() {
+            let should_pop = self.dom_tree[last_id]
+                .data
+                .as_element()
+                .is_some_and(|elem| elem.tag.should_auto_close(new_tag));
 
-            if let NodeData::Element(elem) = &last_node.data {
-                elem.tag.should_auto_close(new_tag)
-            } else {
-                false
+            if !should_pop {
+                break;
             }
-        } else {
-            false
-        };
 
-        if should_pop {
             self.open_elements.pop();
         }
     }
@@ -121,7 +136,15 @@ impl<C: Collector + Default> DomTreeBuilder<C> {
     /// * `token` - A reference to the `Token` representing the start tag to be processed.
     fn handle_start_tag(&mut self, token: Token) {
         let tag = Tag::from_str_insensitive(&token.data);
-        let attributes = token.attributes;
+        let attributes = token.attributes.map(|attrs| {
+            attrs
+                .into_iter()
+                .map(|(name, value)| {
+                    let value = decode_attribute_value_lossy(&value).into_owned();
+                    (name, value)
+                })
+                .collect()
+        });
         let class_set = attributes
             .as_ref()
             .and_then(|attrs| {
@@ -169,6 +192,7 @@ impl<C: Collector + Default> DomTreeBuilder<C> {
     /// * `token` - A reference to the `Token` representing the end tag to be processed.
     fn handle_end_tag(&mut self, token: &Token) {
         let target_tag = Tag::from_str_insensitive(&token.data);
+        self.close_implied_end_tags(&target_tag);
 
         let should_close = if let Some(last_id) = self.open_elements.last() {
             let last_node = &self.dom_tree[last_id];
@@ -187,12 +211,45 @@ impl<C: Collector + Default> DomTreeBuilder<C> {
         }
     }
 
+    /// Pops the elements with an implied end tag, like `<option>`, that are still open inside the element closed by
+    /// an end tag, so that `</select>` closes its select even when the last option was not closed.
+    ///
+    /// # Arguments
+    /// * `target_tag` - The tag of the end tag being processed.
+    fn close_implied_end_tags(&mut self, target_tag: &Tag) {
+        let is_open = self.open_elements.iter().any(|id| {
+            self.dom_tree[id]
+                .data
+                .as_element()
+                .is_some_and(|elem| elem.tag == *target_tag)
+        });
+
+        if !is_open {
+            return;
+        }
+
+        while let Some(last_id) = self.open_elements.last() {
+            let implied = self.dom_tree[last_id]
+                .data
+                .as_element()
+                .is_some_and(|elem| {
+                    elem.tag != *target_tag && matches!(elem.tag, Tag::Html(html_tag) if html_tag.has_implied_end_tag())
+                });
+
+            if !implied {
+                break;
+            }
+
+            self.open_elements.pop();
+        }
+    }
+
     /// Handles text content tokens, normalizing whitespace and decoding HTML entities.
     ///
     /// # Arguments
     /// * `token` - A reference to the `Token` containing the text content to be processed.
     fn handle_text_content(&mut self, token: Token) {
-        let text_content = decode_html_entities(&token.data);
+        let text_content = decode_character_references_lossy(&token.data);
 
         if let Some(last_id) = self.open_elements.last() {
             let parent_node = &self.dom_tree[last_id];
@@ -0,0 +1,185 @@
+use crate::Element;
+
+/// The default width of a `<canvas>` element in CSS pixels.
+pub const DEFAULT_CANVAS_WIDTH: u32 = 300;
+
+/// The default height of a `<canvas>` element in CSS pixels.
+pub const DEFAULT_CANVAS_HEIGHT: u32 = 150;
+
+/// An RGBA color with 8 bits per channel, used for canvas fill and stroke styles.
+pub type CanvasColor = [u8; 4];
+
+/// A pixel buffer that can be drawn onto a canvas with `draw_image`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanvasImage {
+    pub width: u32,
+    pub height: u32,
+
+    /// Raw RGBA pixel data (4 bytes per pixel)
+    pub rgba: Vec<u8>,
+}
+
+/// A single drawing operation recorded by a `CanvasRenderingContext2d`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CanvasCommand {
+    FillRect {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    },
+    StrokeRect {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    },
+    FillText {
+        text: String,
+        x: f32,
+        y: f32,
+    },
+    DrawImage {
+        image: CanvasImage,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    },
+    BeginPath,
+    MoveTo {
+        x: f32,
+        y: f32,
+    },
+    LineTo {
+        x: f32,
+        y: f32,
+    },
+    ClosePath,
+    Stroke,
+    Fill,
+    SetFillStyle(CanvasColor),
+    SetStrokeStyle(CanvasColor),
+    SetLineWidth(f32),
+}
+
+/// The 2D drawing API of a `<canvas>` element.
+///
+/// Every method records a `CanvasCommand` in a buffer rather than drawing immediately, the buffer is
+/// later executed by the engine to produce the pixels of the canvas.
+#[derive(Debug, Clone, Default)]
+pub struct CanvasRenderingContext2d {
+    commands: Vec<CanvasCommand>,
+}
+
+impl CanvasRenderingContext2d {
+    /// Creates a context with an empty command buffer.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            commands: Vec::new(),
+        }
+    }
+
+    /// The commands recorded so far.
+    #[must_use]
+    pub fn commands(&self) -> &[CanvasCommand] {
+        &self.commands
+    }
+
+    /// Takes the recorded commands out of the context, leaving the buffer empty.
+    pub fn take_commands(&mut self) -> Vec<CanvasCommand> {
+        std::mem::take(&mut self.commands)
+    }
+
+    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        self.commands.push(CanvasCommand::FillRect {
+            x,
+            y,
+            width,
+            height,
+        });
+    }
+
+    pub fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        self.commands.push(CanvasCommand::StrokeRect {
+            x,
+            y,
+            width,
+            height,
+        });
+    }
+
+    pub fn fill_text(&mut self, text: impl Into<String>, x: f32, y: f32) {
+        self.commands.push(CanvasCommand::FillText {
+            text: text.into(),
+            x,
+            y,
+        });
+    }
+
+    pub fn draw_image(&mut self, image: CanvasImage, x: f32, y: f32, width: f32, height: f32) {
+        self.commands.push(CanvasCommand::DrawImage {
+            image,
+            x,
+            y,
+            width,
+            height,
+        });
+    }
+
+    pub fn begin_path(&mut self) {
+        self.commands.push(CanvasCommand::BeginPath);
+    }
+
+    pub fn move_to(&mut self, x: f32, y: f32) {
+        self.commands.push(CanvasCommand::MoveTo { x, y });
+    }
+
+    pub fn line_to(&mut self, x: f32, y: f32) {
+        self.commands.push(CanvasCommand::LineTo { x, y });
+    }
+
+    pub fn close_path(&mut self) {
+        self.commands.push(CanvasCommand::ClosePath);
+    }
+
+    pub fn stroke(&mut self) {
+        self.commands.push(CanvasCommand::Stroke);
+    }
+
+    pub fn fill(&mut self) {
+        self.commands.push(CanvasCommand::Fill);
+    }
+
+    pub fn set_fill_style(&mut self, color: CanvasColor) {
+        self.commands.push(CanvasCommand::SetFillStyle(color));
+    }
+
+    pub fn set_stroke_style(&mut self, color: CanvasColor) {
+        self.commands.push(CanvasCommand::SetStrokeStyle(color));
+    }
+
+    pub fn set_line_width(&mut self, width: f32) {
+        self.commands.push(CanvasCommand::SetLineWidth(width));
+    }
+}
+
+/// Resolves the size of the bitmap backing a `<canvas>` element from its `width` and `height` attributes.
+///
+/// # Arguments
+/// * `element` - The `<canvas>` element.
+///
+/// # Returns
+/// The width and height of the canvas in pixels, falling back to 300x150 for missing or invalid values.
+#[must_use]
+pub fn canvas_size(element: &Element) -> (u32, u32) {
+    let parse = |name: &str, default: u32| {
+        element
+            .get_attribute(name)
+            .and_then(|v| v.trim().parse::<u32>().ok())
+            .unwrap_or(default)
+    };
+
+    (parse("width", DEFAULT_CANVAS_WIDTH), parse("height", DEFAULT_CANVAS_HEIGHT))
+}
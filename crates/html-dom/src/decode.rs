@@ -0,0 +1,328 @@
+use std::borrow::Cow;
+
+use html_escape::decode_html_entities;
+use thiserror::Error;
+
+/// The named character references that are also decoded without their semicolon, like `&copy` in `&copy 2024`, as
+/// older documents relied on it. Every other name needs its semicolon.
+///
+/// <https://html.spec.whatwg.org/multipage/named-characters.html>
+const LEGACY_NAMES: [&str; 106] = [
+    "AElig", "AMP", "Aacute", "Acirc", "Agrave", "Aring", "Atilde", "Auml", "COPY", "Ccedil", "ETH", "Eacute", "Ecirc",
+    "Egrave", "Euml", "GT", "Iacute", "Icirc", "Igrave", "Iuml", "LT", "Ntilde", "Oacute", "Ocirc", "Ograve", "Oslash",
+    "Otilde", "Ouml", "QUOT", "REG", "THORN", "Uacute", "Ucirc", "Ugrave", "Uuml", "Yacute", "aacute", "acirc",
+    "acute", "aelig", "agrave", "amp", "aring", "atilde", "auml", "brvbar", "ccedil", "cedil", "cent", "copy",
+    "curren", "deg", "divide", "eacute", "ecirc", "egrave", "eth", "euml", "frac12", "frac14", "frac34", "gt",
+    "iacute", "icirc", "iexcl", "igrave", "iquest", "iuml", "laquo", "lt", "macr", "micro", "middot", "nbsp", "not",
+    "ntilde", "oacute", "ocirc", "ograve", "ordf", "ordm", "oslash", "otilde", "ouml", "para", "plusmn", "pound",
+    "quot", "raquo", "reg", "sect", "shy", "sup1", "sup2", "sup3", "szlig", "thorn", "times", "uacute", "ucirc",
+    "ugrave", "uml", "uuml", "yacute", "yen", "yuml",
+];
+
+/// The characters that numeric character references to the C1 control characters U+0080 to U+009F stand for, the
+/// characters of the same bytes in Windows-1252, which documents labelled as Latin-1 were often written in. `None` for
+/// the bytes Windows-1252 leaves undefined, which are kept as control characters.
+const C1_REPLACEMENTS: [Option<char>; 32] = [
+    Some('\u{20AC}'),
+    None,
+    Some('\u{201A}'),
+    Some('\u{0192}'),
+    Some('\u{201E}'),
+    Some('\u{2026}'),
+    Some('\u{2020}'),
+    Some('\u{2021}'),
+    Some('\u{02C6}'),
+    Some('\u{2030}'),
+    Some('\u{0160}'),
+    Some('\u{2039}'),
+    Some('\u{0152}'),
+    None,
+    Some('\u{017D}'),
+    None,
+    None,
+    Some('\u{2018}'),
+    Some('\u{2019}'),
+    Some('\u{201C}'),
+    Some('\u{201D}'),
+    Some('\u{2022}'),
+    Some('\u{2013}'),
+    Some('\u{2014}'),
+    Some('\u{02DC}'),
+    Some('\u{2122}'),
+    Some('\u{0161}'),
+    Some('\u{203A}'),
+    Some('\u{0153}'),
+    None,
+    Some('\u{017E}'),
+    Some('\u{0178}'),
+];
+
+/// A numeric character reference that does not stand for a character.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TokenizationError {
+    /// A reference without digits, like `&#;` or `&#xG;`.
+    #[error("character reference '{0}' has no digits")]
+    AbsenceOfDigits(String),
+
+    /// A reference to U+0000.
+    #[error("character reference to U+0000")]
+    NullCharacterReference,
+
+    /// A reference to a surrogate code point, which only encodes a character in pairs in UTF-16.
+    #[error("character reference to surrogate U+{0:04X}")]
+    SurrogateCharacterReference(u32),
+
+    /// A reference beyond U+10FFFF, the last code point of Unicode.
+    #[error("character reference '{0}' is outside the range of Unicode")]
+    CharacterReferenceOutsideUnicodeRange(String),
+}
+
+/// Decodes the character references of text, named ones like `&amp;` and numeric ones like `&#160;` and `&#xA0;`.
+///
+/// As in the HTML parser, a reference without its semicolon is decoded when it is numeric or one of the legacy named
+/// references like `&amp`, references to the C1 control characters are decoded as their Windows-1252 characters, and
+/// an ampersand not starting a known reference is kept as is.
+///
+/// # Arguments
+/// * `text` - The text to decode.
+///
+/// # Returns
+/// The decoded text, borrowed when it has no references.
+///
+/// # Errors
+/// If a numeric character reference has no digits, or is to U+0000, a surrogate or beyond U+10FFFF.
+pub fn decode_character_references(text: &str) -> Result<Cow<'_, str>, TokenizationError> {
+    decode(text, false, false)
+}
+
+/// Decodes the character references of text like `decode_character_references`, recovering from invalid numeric
+/// references as the HTML parser does. A reference without digits is kept as is, and one to a code point that is not
+/// a character is decoded as U+FFFD.
+///
+/// # Arguments
+/// * `text` - The text to decode.
+///
+/// # Returns
+/// The decoded text, borrowed when it has no references.
+#[must_use]
+pub fn decode_character_references_lossy(text: &str) -> Cow<'_, str> {
+    decode(text, true, false).unwrap_or(Cow::Borrowed(text))
+}
+
+/// Decodes the character references of an attribute value like `decode_character_references_lossy`, except that a
+/// legacy named reference without its semicolon is kept as is when followed by `=` or an alphanumeric character, so
+/// the query of `href="?a=1&copy=2"` is not turned into `?a=1©=2`.
+///
+/// # Arguments
+/// * `value` - The attribute value to decode.
+///
+/// # Returns
+/// The decoded value, borrowed when it has no references.
+#[must_use]
+pub fn decode_attribute_value_lossy(value: &str) -> Cow<'_, str> {
+    decode(value, true, true).unwrap_or(Cow::Borrowed(value))
+}
+
+fn decode(text: &str, lossy: bool, in_attribute: bool) -> Result<Cow<'_, str>, TokenizationError> {
+    if !text.contains('&') {
+        return Ok(Cow::Borrowed(text));
+    }
+
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        let reference = &rest[start + 1..];
+
+        let character = match reference.strip_prefix('#') {
+            Some(numeric) => match decode_numeric(numeric) {
+                Ok((character, len)) => Some((character.to_string(), len + 1)),
+                Err((TokenizationError::AbsenceOfDigits(_), _)) if lossy => None,
+                Err((_, len)) if lossy => Some((char::REPLACEMENT_CHARACTER.to_string(), len + 1)),
+                Err((error, _)) => return Err(error),
+            },
+            None => decode_named(reference, in_attribute),
+        };
+
+        match character {
+            Some((character, len)) => {
+                decoded.push_str(&character);
+                rest = &reference[len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = reference;
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    Ok(Cow::Owned(decoded))
+}
+
+/// Decodes a numeric character reference.
+///
+/// # Arguments
+/// * `reference` - The text after `&#`.
+///
+/// # Returns
+/// The character and the length of the reference in `reference`, including its semicolon.
+///
+/// # Errors
+/// The error and the length of the reference, the error being `AbsenceOfDigits` when there are no digits.
+fn decode_numeric(reference: &str) -> Result<(char, usize), (TokenizationError, usize)> {
+    let (radix, prefix_len) = match reference.as_bytes().first() {
+        Some(b'x' | b'X') => (16, 1),
+        _ => (10, 0),
+    };
+
+    let digits = &reference[prefix_len..];
+    let digits_len = digits
+        .bytes()
+        .take_while(|byte| char::from(*byte).is_digit(radix))
+        .count();
+    let digits = &digits[..digits_len];
+
+    if digits.is_empty() {
+        let error = TokenizationError::AbsenceOfDigits(format!("&#{}", &reference[..prefix_len]));
+        return Err((error, prefix_len));
+    }
+
+    let mut len = prefix_len + digits_len;
+    if reference[len..].starts_with(';') {
+        len += 1;
+    }
+
+    let code = u32::from_str_radix(digits, radix).unwrap_or(u32::MAX);
+    let character = match code {
+        0 => return Err((TokenizationError::NullCharacterReference, len)),
+        0xD800..=0xDFFF => return Err((TokenizationError::SurrogateCharacterReference(code), len)),
+        0x80..=0x9F => C1_REPLACEMENTS[(code - 0x80) as usize].or_else(|| char::from_u32(code)),
+        _ => char::from_u32(code),
+    };
+
+    match character {
+        Some(character) => Ok((character, len)),
+        None => {
+            Err((TokenizationError::CharacterReferenceOutsideUnicodeRange(format!("&#{}", &reference[..len])), len))
+        }
+    }
+}
+
+/// Decodes a named character reference, the longest legacy name it starts with when the name is not followed by a
+/// semicolon or is not known, so `&notit;` is decoded as `¬it;`.
+///
+/// # Arguments
+/// * `reference` - The text after `&`.
+/// * `in_attribute` - Whether the reference is in an attribute value, where a legacy name followed by `=` or an
+///   alphanumeric character is not a reference.
+///
+/// # Returns
+/// The characters and the length of the reference in `reference`, `None` when it is not a known reference.
+fn decode_named(reference: &str, in_attribute: bool) -> Option<(String, usize)> {
+    let name_len = reference
+        .bytes()
+        .take_while(u8::is_ascii_alphanumeric)
+        .count();
+    if name_len == 0 {
+        return None;
+    }
+
+    let name = &reference[..name_len];
+    if reference[name_len..].starts_with(';') {
+        let entity = format!("&{name};");
+        let decoded = decode_html_entities(&entity);
+        if decoded != entity {
+            return Some((decoded.into_owned(), name_len + 1));
+        }
+    }
+
+    let legacy = LEGACY_NAMES
+        .iter()
+        .filter(|legacy| name.starts_with(**legacy))
+        .max_by_key(|legacy| legacy.len())?;
+
+    if in_attribute
+        && reference[legacy.len()..]
+            .bytes()
+            .next()
+            .is_some_and(|next| next == b'=' || next.is_ascii_alphanumeric())
+    {
+        return None;
+    }
+
+    Some((decode_html_entities(&format!("&{legacy};")).into_owned(), legacy.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_references() {
+        assert_eq!(decode_character_references("a&#160;b").unwrap(), "a\u{A0}b");
+        assert_eq!(decode_character_references("a&#x00A0;b").unwrap(), "a\u{A0}b");
+        assert_eq!(decode_character_references("&#X41;&#65").unwrap(), "AA");
+    }
+
+    #[test]
+    fn test_c1_remap() {
+        assert_eq!(decode_character_references("&#128;").unwrap(), "\u{20AC}");
+        assert_eq!(decode_character_references("&#x99;").unwrap(), "\u{2122}");
+        assert_eq!(decode_character_references("&#x9F;").unwrap(), "\u{178}");
+        assert_eq!(decode_character_references("&#x81;").unwrap(), "\u{81}");
+        assert_eq!(decode_character_references("&#x9D;").unwrap(), "\u{9D}");
+    }
+
+    #[test]
+    fn test_legacy_names_without_semicolon() {
+        assert_eq!(decode_character_references("&copy 2024").unwrap(), "\u{A9} 2024");
+        assert_eq!(decode_character_references("a &amp b").unwrap(), "a & b");
+        assert_eq!(decode_character_references("&notit;").unwrap(), "\u{AC}it;");
+        assert_eq!(decode_character_references("&hellip").unwrap(), "&hellip");
+        assert_eq!(decode_character_references("&hellip;").unwrap(), "\u{2026}");
+    }
+
+    #[test]
+    fn test_attribute_value_exception() {
+        assert_eq!(decode_attribute_value_lossy("?a=1&copy=2"), "?a=1&copy=2");
+        assert_eq!(decode_attribute_value_lossy("?a=1&copyright"), "?a=1&copyright");
+        assert_eq!(decode_attribute_value_lossy("?a=1&copy;=2"), "?a=1\u{A9}=2");
+        assert_eq!(decode_attribute_value_lossy("&copy 2024"), "\u{A9} 2024");
+        assert_eq!(decode_character_references_lossy("?a=1&copy=2"), "?a=1\u{A9}=2");
+    }
+
+    #[test]
+    fn test_unknown_ampersand_kept() {
+        assert!(matches!(decode_character_references("no references"), Ok(Cow::Borrowed(_))));
+        assert_eq!(decode_character_references("a & b &unknown;").unwrap(), "a & b &unknown;");
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(decode_character_references("&#;"), Err(TokenizationError::AbsenceOfDigits("&#".to_string())));
+        assert_eq!(decode_character_references("&#xG;"), Err(TokenizationError::AbsenceOfDigits("&#x".to_string())));
+        assert_eq!(decode_character_references("&#0;"), Err(TokenizationError::NullCharacterReference));
+        assert_eq!(
+            decode_character_references("&#xD800;"),
+            Err(TokenizationError::SurrogateCharacterReference(0xD800))
+        );
+        assert_eq!(
+            decode_character_references("&#x110000;"),
+            Err(TokenizationError::CharacterReferenceOutsideUnicodeRange("&#x110000;".to_string()))
+        );
+        assert_eq!(
+            TokenizationError::SurrogateCharacterReference(0xD800).to_string(),
+            "character reference to surrogate U+D800"
+        );
+    }
+
+    #[test]
+    fn test_lossy_recovery() {
+        assert_eq!(decode_character_references_lossy("&#;"), "&#;");
+        assert_eq!(decode_character_references_lossy("a&#0;b"), "a\u{FFFD}b");
+        assert_eq!(decode_character_references_lossy("&#xD800;"), "\u{FFFD}");
+        assert_eq!(decode_character_references_lossy("&#99999999;"), "\u{FFFD}");
+    }
+}
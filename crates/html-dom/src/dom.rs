@@ -4,7 +4,7 @@ use std::{
     ops::{Deref, Index, IndexMut},
 };
 
-use crate::tag::Tag;
+use crate::{state::ElementState, tag::Tag};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct NodeId(pub usize);
@@ -134,6 +134,30 @@ impl Element {
             .and_then(|attrs| attrs.get(name).map(String::as_str))
     }
 
+    /// Set the value of an attribute, adding it if not present
+    ///
+    /// # Arguments
+    /// * `name` - The name of the attribute to set
+    /// * `value` - The new value of the attribute
+    pub fn set_attribute(&mut self, name: &str, value: impl Into<String>) {
+        self.attributes
+            .get_or_insert_with(HashMap::new)
+            .insert(name.to_string(), value.into());
+    }
+
+    /// Remove an attribute, doing nothing if it is not present
+    ///
+    /// # Arguments
+    /// * `name` - The name of the attribute to remove
+    ///
+    /// # Returns
+    /// bool indicating whether the attribute was present
+    pub fn remove_attribute(&mut self, name: &str) -> bool {
+        self.attributes
+            .as_mut()
+            .is_some_and(|attrs| attrs.remove(name).is_some())
+    }
+
     /// Get the tag name of this element as a string
     ///
     /// # Returns
@@ -142,6 +166,27 @@ impl Element {
     pub fn tag_name(&self) -> String {
         self.tag.to_string()
     }
+
+    fn estimated_heap_bytes(&self) -> usize {
+        let attributes = self.attributes.as_ref().map_or(0, |attributes| {
+            attributes
+                .iter()
+                .map(|(name, value)| size_of::<(String, String)>() + name.capacity() + value.capacity())
+                .sum()
+        });
+        let classes = self.class_set.as_ref().map_or(0, |classes| {
+            classes
+                .iter()
+                .map(|class| size_of::<String>() + class.capacity())
+                .sum()
+        });
+        let tag = match &self.tag {
+            Tag::Unknown(name) => name.capacity(),
+            _ => 0,
+        };
+
+        attributes + classes + tag
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -159,6 +204,14 @@ impl NodeData {
         }
     }
 
+    #[must_use]
+    pub const fn as_element_mut(&mut self) -> Option<&mut Element> {
+        match self {
+            Self::Element(elem) => Some(elem),
+            Self::Text(_) => None,
+        }
+    }
+
     #[must_use]
     pub const fn as_text(&self) -> Option<&String> {
         match self {
@@ -180,6 +233,9 @@ pub struct DomNode {
 pub struct DocumentRoot {
     pub nodes: Vec<DomNode>,
     pub root_nodes: Vec<NodeId>,
+
+    /// The hover and focus state of the nodes.
+    pub state: ElementState,
 }
 
 impl DocumentRoot {
@@ -193,6 +249,28 @@ impl DocumentRoot {
         self.nodes.get(node_id.0)
     }
 
+    /// A rough estimate of the memory used by the tree, counting the node storage, the text of the text nodes and the
+    /// attributes and classes of the elements. Hash table overhead is approximated by the size of the entries.
+    #[must_use]
+    pub fn estimated_size_bytes(&self) -> usize {
+        let heap_bytes: usize = self
+            .nodes
+            .iter()
+            .map(|node| {
+                node.children.capacity() * size_of::<NodeId>()
+                    + match &node.data {
+                        NodeData::Text(text) => text.capacity(),
+                        NodeData::Element(element) => element.estimated_heap_bytes(),
+                    }
+            })
+            .sum();
+
+        size_of::<Self>()
+            + self.nodes.capacity() * size_of::<DomNode>()
+            + self.root_nodes.capacity() * size_of::<NodeId>()
+            + heap_bytes
+    }
+
     /// Walk up the DOM tree from the given node, returning all ancestor nodes
     /// (parent, grandparent, etc.) in order from nearest to farthest.
     #[must_use]
@@ -210,6 +288,33 @@ impl DocumentRoot {
         result
     }
 
+    /// The language of a node, from the `lang` attribute of the node or its nearest ancestor that has one. An empty
+    /// `lang` attribute means the language is unknown.
+    ///
+    /// # Returns
+    /// The language tag, such as `en-US` or `de`, or `None` if the language is unknown.
+    #[must_use]
+    pub fn language(&self, node_id: NodeId) -> Option<&str> {
+        let mut current = Some(node_id);
+
+        while let Some(id) = current {
+            let node = self.get_node(&id)?;
+
+            if let Some(lang) = node
+                .data
+                .as_element()
+                .and_then(|element| element.get_attribute("lang"))
+            {
+                let lang = lang.trim();
+                return (!lang.is_empty()).then_some(lang);
+            }
+
+            current = node.parent;
+        }
+
+        None
+    }
+
     pub fn push_node(&mut self, data: &NodeData, parent: Option<NodeId>) -> NodeId {
         let node_id = NodeId(self.nodes.len());
         let new_node = DomNode {
@@ -231,6 +336,121 @@ impl DocumentRoot {
         node_id
     }
 
+    /// Creates a node that is not yet attached to the tree, like `document.createElement`.
+    ///
+    /// # Arguments
+    /// * `data` - The data of the new node.
+    ///
+    /// # Returns
+    /// The `NodeId` of the detached node.
+    pub fn create_node(&mut self, data: NodeData) -> NodeId {
+        let node_id = NodeId(self.nodes.len());
+        self.nodes.push(DomNode {
+            id: node_id,
+            parent: None,
+            children: Vec::new(),
+            data,
+        });
+
+        node_id
+    }
+
+    /// Appends a node as the last child of an element, detaching it from its previous parent first.
+    ///
+    /// # Arguments
+    /// * `parent` - The element to append to.
+    /// * `child` - The node to append.
+    ///
+    /// # Returns
+    /// `false` if either node does not exist, the parent is not an element, or the child is the parent or one of
+    /// its ancestors.
+    pub fn append_child(&mut self, parent: NodeId, child: NodeId) -> bool {
+        let (Some(parent_node), Some(_)) = (self.get_node(&parent), self.get_node(&child)) else {
+            return false;
+        };
+
+        if !matches!(parent_node.data, NodeData::Element(_))
+            || parent == child
+            || self
+                .ancestors(parent_node)
+                .iter()
+                .any(|node| node.id == child)
+        {
+            return false;
+        }
+
+        self.detach(child);
+        self[&child].parent = Some(parent);
+        self[&parent].children.push(child);
+
+        true
+    }
+
+    /// Removes a child from an element, leaving it detached from the tree.
+    ///
+    /// # Arguments
+    /// * `parent` - The element to remove the child from.
+    /// * `child` - The child to remove.
+    ///
+    /// # Returns
+    /// `false` if `child` is not a child of `parent`.
+    pub fn remove_child(&mut self, parent: NodeId, child: NodeId) -> bool {
+        if self
+            .get_node(&child)
+            .is_none_or(|node| node.parent != Some(parent))
+        {
+            return false;
+        }
+
+        self.detach(child);
+        true
+    }
+
+    /// Applies the changes made to another copy of the document, replacing the changed nodes and appending the nodes
+    /// created since the copies were last in sync.
+    ///
+    /// # Arguments
+    /// * `changes` - The nodes changed or created in the other copy, in any order.
+    ///
+    /// # Returns
+    /// `false`, applying nothing, if the created nodes do not directly follow the nodes of this copy, which means the
+    /// copies are out of sync.
+    pub fn apply_changes(&mut self, mut changes: Vec<DomNode>) -> bool {
+        changes.sort_by_key(|node| node.id.0);
+
+        let len = self.nodes.len();
+        if changes
+            .iter()
+            .filter(|node| node.id.0 >= len)
+            .enumerate()
+            .any(|(index, node)| node.id.0 != len + index)
+        {
+            return false;
+        }
+
+        for node in changes {
+            match self.nodes.get_mut(node.id.0) {
+                Some(existing) => *existing = node,
+                None => self.nodes.push(node),
+            }
+        }
+
+        // A root node may have been moved into an element.
+        let nodes = &self.nodes;
+        self.root_nodes
+            .retain(|node_id| nodes[node_id.0].parent.is_none());
+
+        true
+    }
+
+    /// Removes a node from the children of its parent, or from the root nodes.
+    fn detach(&mut self, node_id: NodeId) {
+        match self[&node_id].parent.take() {
+            Some(parent) => self[&parent].children.retain(|id| *id != node_id),
+            None => self.root_nodes.retain(|id| *id != node_id),
+        }
+    }
+
     #[must_use]
     pub const fn is_empty(&self) -> bool {
         self.nodes.is_empty()
@@ -315,3 +535,83 @@ impl Display for DocumentRoot {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tag::HtmlTag;
+
+    fn node(index: usize, data: NodeData) -> DomNode {
+        DomNode {
+            id: NodeId(index),
+            parent: None,
+            children: Vec::new(),
+            data,
+        }
+    }
+
+    #[test]
+    fn test_estimated_size_counts_text_and_attributes() {
+        let mut dom = DocumentRoot::new();
+        assert_eq!(dom.estimated_size_bytes(), size_of::<DocumentRoot>());
+
+        let element = Element::new(
+            Tag::Html(HtmlTag::Div),
+            HashSet::from(["a".to_string()]),
+            HashMap::from([("id".to_string(), "main".to_string())]),
+        );
+        dom.nodes = vec![
+            node(0, NodeData::Element(element)),
+            node(1, NodeData::Text("hello world".to_string())),
+        ];
+        dom.root_nodes = vec![NodeId(0), NodeId(1)];
+
+        let attributes = size_of::<(String, String)>() + "id".len() + "main".len();
+        let classes = size_of::<String>() + "a".len();
+        assert_eq!(
+            dom.estimated_size_bytes(),
+            size_of::<DocumentRoot>()
+                + 2 * size_of::<DomNode>()
+                + 2 * size_of::<NodeId>()
+                + attributes
+                + classes
+                + "hello world".len()
+        );
+    }
+
+    #[test]
+    fn test_apply_changes() {
+        let div = || NodeData::Element(Element::new(Tag::Html(HtmlTag::Div), HashSet::new(), HashMap::new()));
+
+        let mut dom = DocumentRoot::new();
+        let root = dom.push_node(&div(), None);
+        let text = dom.push_node(&NodeData::Text("hello".to_string()), Some(root));
+
+        let mut changed = dom.clone();
+        let span = changed.create_node(div());
+        changed.append_child(root, span);
+        changed.remove_child(root, text);
+
+        let changes = vec![
+            changed[span].clone(),
+            changed[text].clone(),
+            changed[root].clone(),
+        ];
+        assert!(dom.apply_changes(changes));
+
+        assert_eq!(dom.nodes.len(), 3);
+        assert_eq!(dom[root].children, vec![span]);
+        assert_eq!(dom[span].parent, Some(root));
+        assert_eq!(dom[text].parent, None);
+        assert_eq!(dom.root_nodes, vec![root]);
+    }
+
+    #[test]
+    fn test_apply_changes_out_of_sync() {
+        let mut dom = DocumentRoot::new();
+        dom.push_node(&NodeData::Text("hello".to_string()), None);
+
+        assert!(!dom.apply_changes(vec![node(2, NodeData::Text("skipped".to_string()))]));
+        assert_eq!(dom.nodes.len(), 1);
+    }
+}
@@ -0,0 +1,533 @@
+use std::fmt::{Display, Formatter};
+
+use regex::Regex;
+use url::Url;
+
+use crate::{DocumentRoot, DomNode, Element, HtmlTag, NodeData, NodeId, SelectElement, Tag, control_value};
+
+/// A bitmask describing which constraints a form control fails, mirroring the HTML `ValidityState` interface.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ValidityState(u16);
+
+impl ValidityState {
+    /// The control is `required` but has no value.
+    pub const VALUE_MISSING: Self = Self(1 << 0);
+
+    /// The value does not match the control's `type` (e.g. `email` or `url`).
+    pub const TYPE_MISMATCH: Self = Self(1 << 1);
+
+    /// The value does not match the control's `pattern` attribute.
+    pub const PATTERN_MISMATCH: Self = Self(1 << 2);
+
+    /// The value is longer than `maxlength`.
+    pub const TOO_LONG: Self = Self(1 << 3);
+
+    /// The value is shorter than `minlength`.
+    pub const TOO_SHORT: Self = Self(1 << 4);
+
+    /// The value is less than `min`.
+    pub const RANGE_UNDERFLOW: Self = Self(1 << 5);
+
+    /// The value is greater than `max`.
+    pub const RANGE_OVERFLOW: Self = Self(1 << 6);
+
+    /// The value is not allowed by `step`.
+    pub const STEP_MISMATCH: Self = Self(1 << 7);
+
+    /// The value could not be converted for the control's `type` (e.g. a non-numeric `number` input).
+    pub const BAD_INPUT: Self = Self(1 << 8);
+
+    /// Returns the raw bits of the bitmask.
+    #[must_use]
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Returns `true` if no constraint is violated.
+    #[must_use]
+    pub const fn is_valid(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if all flags in `other` are set.
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Sets all flags in `other`.
+    pub const fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+/// A single failed constraint check for a form control.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The DOM node of the invalid control.
+    pub node_id: NodeId,
+
+    /// Which constraints the control failed.
+    pub validity: ValidityState,
+
+    /// A human-readable message describing the first failed constraint.
+    pub message: String,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.node_id, self.message)
+    }
+}
+
+/// Performs HTML5 constraint validation on the controls of a `<form>`.
+pub struct FormValidator;
+
+impl FormValidator {
+    /// Input types that are never candidates for constraint validation.
+    const BARRED_INPUT_TYPES: [&str; 5] = ["hidden", "submit", "reset", "button", "image"];
+
+    /// The step of `number` and `range` inputs without a valid `step` attribute.
+    const DEFAULT_STEP: f64 = 1.0;
+
+    /// Validates every submittable control inside the given form.
+    ///
+    /// # Arguments
+    /// * `form_node` - The `<form>` element to validate.
+    /// * `tree` - The document the form belongs to.
+    ///
+    /// # Returns
+    /// A list of validation errors, in document order. An empty list means the form is valid or has `novalidate` set.
+    #[must_use]
+    pub fn validate_form(form_node: &DomNode, tree: &DocumentRoot) -> Vec<ValidationError> {
+        let Some(form) = form_node.data.as_element() else {
+            return Vec::new();
+        };
+
+        if form.tag != Tag::Html(HtmlTag::Form) || form.has_attribute("novalidate") {
+            return Vec::new();
+        }
+
+        let mut errors = Vec::new();
+        let mut stack: Vec<NodeId> = form_node.children.iter().rev().copied().collect();
+
+        while let Some(node_id) = stack.pop() {
+            let Some(node) = tree.get_node(&node_id) else {
+                continue;
+            };
+
+            if let Some(element) = node.data.as_element()
+                && let Some(error) = Self::validate_control(node, element, tree)
+            {
+                errors.push(error);
+            }
+
+            stack.extend(node.children.iter().rev());
+        }
+
+        errors
+    }
+
+    /// Validates a single control, returning an error if any constraint fails.
+    fn validate_control(node: &DomNode, element: &Element, tree: &DocumentRoot) -> Option<ValidationError> {
+        if element.has_attribute("disabled") || element.has_attribute("readonly") {
+            return None;
+        }
+
+        let (validity, message) = match &element.tag {
+            Tag::Html(HtmlTag::Input) => {
+                let input_type = element
+                    .get_attribute("type")
+                    .unwrap_or("text")
+                    .trim()
+                    .to_ascii_lowercase();
+
+                if Self::BARRED_INPUT_TYPES.contains(&input_type.as_str()) {
+                    return None;
+                }
+
+                let value = element.get_attribute("value").unwrap_or_default();
+                Self::check_input(element, &input_type, value)
+            }
+            Tag::Html(HtmlTag::Textarea) => {
+                let value = control_value(node, tree);
+                Self::check_text(element, &value)
+            }
+            Tag::Html(HtmlTag::Select) => {
+                let value = selected_value(node, tree);
+                if element.has_attribute("required") && value.is_empty() {
+                    (ValidityState::VALUE_MISSING, String::from("Please select an item in the list."))
+                } else {
+                    (ValidityState::default(), String::new())
+                }
+            }
+            _ => return None,
+        };
+
+        if validity.is_valid() {
+            return None;
+        }
+
+        Some(ValidationError {
+            node_id: node.id,
+            validity,
+            message,
+        })
+    }
+
+    /// Checks the constraints of an `<input>` element.
+    fn check_input(element: &Element, input_type: &str, value: &str) -> (ValidityState, String) {
+        let mut validity = ValidityState::default();
+        let mut message = None;
+
+        if matches!(input_type, "checkbox" | "radio") {
+            if element.has_attribute("required") && !element.has_attribute("checked") {
+                validity.insert(ValidityState::VALUE_MISSING);
+                message = Some(String::from("Please check this box if you want to proceed."));
+            }
+
+            return (validity, message.unwrap_or_default());
+        }
+
+        let (text_validity, text_message) = Self::check_text(element, value);
+        validity.insert(text_validity);
+        if !text_validity.is_valid() {
+            message = Some(text_message);
+        }
+
+        if value.is_empty() {
+            return (validity, message.unwrap_or_default());
+        }
+
+        match input_type {
+            "email" => {
+                let all_valid = if element.has_attribute("multiple") {
+                    value
+                        .split(',')
+                        .all(|email| Self::is_valid_email(email.trim()))
+                } else {
+                    Self::is_valid_email(value)
+                };
+
+                if !all_valid {
+                    validity.insert(ValidityState::TYPE_MISMATCH);
+                    message.get_or_insert_with(|| String::from("Please enter an email address."));
+                }
+            }
+            "url" => {
+                if Url::parse(value).is_err() {
+                    validity.insert(ValidityState::TYPE_MISMATCH);
+                    message.get_or_insert_with(|| String::from("Please enter a URL."));
+                }
+            }
+            "number" | "range" => match value.trim().parse::<f64>() {
+                Ok(number) if number.is_finite() => {
+                    if let Some(min) = element
+                        .get_attribute("min")
+                        .and_then(|m| m.trim().parse::<f64>().ok())
+                        && number < min
+                    {
+                        validity.insert(ValidityState::RANGE_UNDERFLOW);
+                        message.get_or_insert_with(|| format!("Value must be greater than or equal to {min}."));
+                    }
+
+                    if let Some(max) = element
+                        .get_attribute("max")
+                        .and_then(|m| m.trim().parse::<f64>().ok())
+                        && number > max
+                    {
+                        validity.insert(ValidityState::RANGE_OVERFLOW);
+                        message.get_or_insert_with(|| format!("Value must be less than or equal to {max}."));
+                    }
+
+                    let step = match element.get_attribute("step").map(str::trim) {
+                        Some(step) if step.eq_ignore_ascii_case("any") => None,
+                        step => Some(
+                            step.and_then(|s| s.parse::<f64>().ok())
+                                .filter(|s| s.is_finite() && *s > 0.0)
+                                .unwrap_or(Self::DEFAULT_STEP),
+                        ),
+                    };
+
+                    if let Some(step) = step {
+                        let base = element
+                            .get_attribute("min")
+                            .and_then(|m| m.trim().parse::<f64>().ok())
+                            .unwrap_or(0.0);
+                        let steps = (number - base) / step;
+
+                        if (steps - steps.round()).abs() > 1e-9 {
+                            validity.insert(ValidityState::STEP_MISMATCH);
+                            message.get_or_insert_with(|| String::from("Please enter a valid value."));
+                        }
+                    }
+                }
+                _ => {
+                    validity.insert(ValidityState::BAD_INPUT);
+                    message.get_or_insert_with(|| String::from("Please enter a number."));
+                }
+            },
+            _ => {}
+        }
+
+        (validity, message.unwrap_or_default())
+    }
+
+    /// Checks the constraints shared by text-like controls: `required`, `minlength`, `maxlength` and `pattern`.
+    fn check_text(element: &Element, value: &str) -> (ValidityState, String) {
+        let mut validity = ValidityState::default();
+        let mut message = None;
+
+        if value.is_empty() {
+            if element.has_attribute("required") {
+                validity.insert(ValidityState::VALUE_MISSING);
+                message = Some(String::from("Please fill out this field."));
+            }
+
+            return (validity, message.unwrap_or_default());
+        }
+
+        let length = value.chars().count();
+
+        if let Some(min_length) = element
+            .get_attribute("minlength")
+            .and_then(|l| l.trim().parse::<usize>().ok())
+            && length < min_length
+        {
+            validity.insert(ValidityState::TOO_SHORT);
+            message.get_or_insert_with(|| {
+                format!("Please lengthen this text to {min_length} characters or more (currently {length}).")
+            });
+        }
+
+        if let Some(max_length) = element
+            .get_attribute("maxlength")
+            .and_then(|l| l.trim().parse::<usize>().ok())
+            && length > max_length
+        {
+            validity.insert(ValidityState::TOO_LONG);
+            message.get_or_insert_with(|| {
+                format!("Please shorten this text to {max_length} characters or less (currently {length}).")
+            });
+        }
+
+        if let Some(pattern) = element.get_attribute("pattern")
+            && let Ok(regex) = Regex::new(&format!("^(?:{pattern})$"))
+            && !regex.is_match(value)
+        {
+            validity.insert(ValidityState::PATTERN_MISMATCH);
+            message.get_or_insert_with(|| {
+                element
+                    .get_attribute("title")
+                    .map_or_else(|| String::from("Please match the requested format."), str::to_string)
+            });
+        }
+
+        (validity, message.unwrap_or_default())
+    }
+
+    /// A simplified version of the "valid email address" production from the HTML specification.
+    fn is_valid_email(value: &str) -> bool {
+        let Some((local, domain)) = value.split_once('@') else {
+            return false;
+        };
+
+        let local_valid = !local.is_empty()
+            && local
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || ".!#$%&'*+/=?^_`{|}~-".contains(c));
+
+        let domain_valid = !domain.is_empty()
+            && domain.split('.').all(|label| {
+                !label.is_empty()
+                    && label.len() <= 63
+                    && !label.starts_with('-')
+                    && !label.ends_with('-')
+                    && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            });
+
+        local_valid && domain_valid
+    }
+}
+
+/// Constructs the entry list of a form, i.e. the `(name, value)` pairs that would be submitted.
+///
+/// # Arguments
+/// * `form_node` - The `<form>` element to collect entries from.
+/// * `tree` - The document the form belongs to.
+///
+/// # Returns
+/// The entries in document order. Disabled, unnamed, unchecked and button controls are skipped.
+#[must_use]
+pub fn form_entries(form_node: &DomNode, tree: &DocumentRoot) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+    let mut stack: Vec<NodeId> = form_node.children.iter().rev().copied().collect();
+
+    while let Some(node_id) = stack.pop() {
+        let node = &tree[node_id];
+
+        let Some(element) = node.data.as_element() else {
+            continue;
+        };
+
+        stack.extend(node.children.iter().rev());
+
+        let Some(name) = element.get_attribute("name").filter(|n| !n.is_empty()) else {
+            continue;
+        };
+
+        if element.has_attribute("disabled") {
+            continue;
+        }
+
+        let value = match &element.tag {
+            Tag::Html(HtmlTag::Input) => {
+                let input_type = element
+                    .get_attribute("type")
+                    .unwrap_or("text")
+                    .trim()
+                    .to_ascii_lowercase();
+
+                match input_type.as_str() {
+                    "submit" | "reset" | "button" | "image" | "file" => continue,
+                    "checkbox" | "radio" => {
+                        if !element.has_attribute("checked") {
+                            continue;
+                        }
+
+                        element.get_attribute("value").unwrap_or("on").to_string()
+                    }
+                    _ => element
+                        .get_attribute("value")
+                        .unwrap_or_default()
+                        .to_string(),
+                }
+            }
+            Tag::Html(HtmlTag::Textarea) => control_value(node, tree),
+            Tag::Html(HtmlTag::Select) => selected_value(node, tree),
+            _ => continue,
+        };
+
+        entries.push((name.to_string(), value));
+    }
+
+    entries
+}
+
+/// Concatenates all descendant text of a node, used for the default value of a `<textarea>`.
+pub(crate) fn text_content(node: &DomNode, tree: &DocumentRoot) -> String {
+    let mut content = String::new();
+
+    for child_id in &node.children {
+        let child = &tree[child_id];
+        match &child.data {
+            NodeData::Text(text) => content.push_str(text),
+            NodeData::Element(_) => content.push_str(&text_content(child, tree)),
+        }
+    }
+
+    content
+}
+
+/// Resolves the value of a `<select>`, which is the value of its selected (or first) `<option>`.
+fn selected_value(node: &DomNode, tree: &DocumentRoot) -> String {
+    SelectElement::from_node(node, tree)
+        .map(|select| select.value())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(attributes: &[(&str, &str)]) -> Element {
+        let mut element = Element {
+            tag: Tag::Html(HtmlTag::Input),
+            ..Element::default()
+        };
+        for (name, value) in attributes {
+            element.set_attribute(name, *value);
+        }
+        element
+    }
+
+    fn check(input_type: &str, attributes: &[(&str, &str)], value: &str) -> ValidityState {
+        FormValidator::check_input(&input(attributes), input_type, value).0
+    }
+
+    #[test]
+    fn test_step_defaults_to_one() {
+        assert!(check("number", &[], "3").is_valid());
+        assert_eq!(check("number", &[], "1.5"), ValidityState::STEP_MISMATCH);
+        assert_eq!(check("range", &[], "0.5"), ValidityState::STEP_MISMATCH);
+    }
+
+    #[test]
+    fn test_step_any_disables_step_check() {
+        assert!(check("number", &[("step", "any")], "1.2345").is_valid());
+        assert!(check("number", &[("step", " ANY ")], "1.2345").is_valid());
+    }
+
+    #[test]
+    fn test_invalid_step_uses_default() {
+        for step in ["0", "-2", "abc", "NaN", "inf"] {
+            assert!(check("number", &[("step", step)], "2").is_valid(), "step={step}");
+            assert_eq!(check("number", &[("step", step)], "2.5"), ValidityState::STEP_MISMATCH, "step={step}");
+        }
+    }
+
+    #[test]
+    fn test_fractional_step() {
+        assert!(check("number", &[("step", "0.1")], "0.3").is_valid());
+        assert!(check("number", &[("step", "0.01")], "19.99").is_valid());
+        assert_eq!(check("number", &[("step", "0.1")], "0.35"), ValidityState::STEP_MISMATCH);
+    }
+
+    #[test]
+    fn test_step_base_is_min() {
+        assert!(check("number", &[("min", "1"), ("step", "2")], "5").is_valid());
+        assert_eq!(check("number", &[("min", "1"), ("step", "2")], "4"), ValidityState::STEP_MISMATCH);
+        assert!(check("number", &[("min", "0.5")], "2.5").is_valid());
+    }
+
+    #[test]
+    fn test_step_with_range_errors() {
+        let validity = check("number", &[("min", "0"), ("max", "10"), ("step", "3")], "11");
+        assert!(validity.contains(ValidityState::RANGE_OVERFLOW));
+        assert!(validity.contains(ValidityState::STEP_MISMATCH));
+    }
+
+    #[test]
+    fn test_pattern_matches_whole_value() {
+        assert!(check("text", &[("pattern", "[a-z]+")], "abc").is_valid());
+        assert_eq!(check("text", &[("pattern", "b")], "abc"), ValidityState::PATTERN_MISMATCH);
+        assert_eq!(check("text", &[("pattern", "[a-z]+")], "abc1"), ValidityState::PATTERN_MISMATCH);
+    }
+
+    #[test]
+    fn test_pattern_alternation_is_anchored() {
+        assert!(check("text", &[("pattern", "cat|dog")], "dog").is_valid());
+        assert_eq!(check("text", &[("pattern", "cat|dog")], "catdog"), ValidityState::PATTERN_MISMATCH);
+        assert_eq!(check("text", &[("pattern", "cat|dog")], "hotdog"), ValidityState::PATTERN_MISMATCH);
+    }
+
+    #[test]
+    fn test_pattern_ignored_for_empty_value() {
+        assert!(check("text", &[("pattern", "[0-9]+")], "").is_valid());
+        assert_eq!(check("text", &[("pattern", "[0-9]+"), ("required", "")], ""), ValidityState::VALUE_MISSING);
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_ignored() {
+        assert!(check("text", &[("pattern", "[a-")], "anything").is_valid());
+        assert!(check("text", &[("pattern", "(?=a)a")], "b").is_valid());
+    }
+
+    #[test]
+    fn test_pattern_message_uses_title() {
+        let (validity, message) =
+            FormValidator::check_input(&input(&[("pattern", "[0-9]{5}"), ("title", "Five digits")]), "text", "123");
+        assert_eq!(validity, ValidityState::PATTERN_MISMATCH);
+        assert_eq!(message, "Five digits");
+    }
+}
@@ -0,0 +1,315 @@
+use crate::{Element, HtmlTag, Tag};
+
+/// Parses a floating-point number attribute, ignoring values that are not finite numbers.
+fn number_attribute(element: &Element, name: &str) -> Option<f64> {
+    element
+        .get_attribute(name)
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|value| value.is_finite())
+}
+
+/// The parsed attributes of a `<progress>` element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressElement {
+    /// How much of the task has been completed, clamped to `0.0..=max`, or `None` while the progress is indeterminate.
+    pub value: Option<f64>,
+
+    /// How much work the task requires in total, always positive.
+    pub max: f64,
+}
+
+impl ProgressElement {
+    /// Parses the attributes of a `<progress>` element.
+    ///
+    /// # Returns
+    /// The parsed progress element, or `None` if the element is not a `<progress>`.
+    #[must_use]
+    pub fn from_element(element: &Element) -> Option<Self> {
+        if element.tag != Tag::Html(HtmlTag::Progress) {
+            return None;
+        }
+
+        let max = number_attribute(element, "max")
+            .filter(|max| *max > 0.0)
+            .unwrap_or(1.0);
+        let value = number_attribute(element, "value").map(|value| value.clamp(0.0, max));
+
+        Some(Self { value, max })
+    }
+
+    /// The completed fraction of the task, or `None` while the progress is indeterminate.
+    #[must_use]
+    pub fn position(&self) -> Option<f64> {
+        self.value.map(|value| value / self.max)
+    }
+}
+
+/// The region of the range of a `<meter>` that its value lies in, relative to the optimum value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MeterRegion {
+    /// The value lies in the same region as the optimum value.
+    Optimum,
+
+    /// The value lies in a region next to the optimum region.
+    Suboptimum,
+
+    /// The value lies at the opposite end of the range from the optimum value.
+    EvenLessGood,
+}
+
+/// The parsed attributes of a `<meter>` element, with every value clamped as described by the HTML specification.
+///
+/// <https://html.spec.whatwg.org/multipage/form-elements.html#the-meter-element>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeterElement {
+    pub value: f64,
+    pub min: f64,
+    pub max: f64,
+    pub low: f64,
+    pub high: f64,
+    pub optimum: f64,
+}
+
+impl MeterElement {
+    /// Parses the attributes of a `<meter>` element.
+    ///
+    /// # Returns
+    /// The parsed meter element, or `None` if the element is not a `<meter>`.
+    #[must_use]
+    pub fn from_element(element: &Element) -> Option<Self> {
+        if element.tag != Tag::Html(HtmlTag::Meter) {
+            return None;
+        }
+
+        let min = number_attribute(element, "min").unwrap_or(0.0);
+        let max = number_attribute(element, "max").unwrap_or(1.0).max(min);
+        let value = number_attribute(element, "value")
+            .unwrap_or(0.0)
+            .clamp(min, max);
+        let low = number_attribute(element, "low")
+            .unwrap_or(min)
+            .clamp(min, max);
+        let high = number_attribute(element, "high")
+            .unwrap_or(max)
+            .clamp(low, max);
+        let optimum = number_attribute(element, "optimum")
+            .unwrap_or((min + max) / 2.0)
+            .clamp(min, max);
+
+        Some(Self {
+            value,
+            min,
+            max,
+            low,
+            high,
+            optimum,
+        })
+    }
+
+    /// The fraction of the range from the minimum to the maximum covered by the value.
+    #[must_use]
+    pub fn position(&self) -> f64 {
+        if self.max > self.min {
+            (self.value - self.min) / (self.max - self.min)
+        } else {
+            0.0
+        }
+    }
+
+    /// The region of the range the value lies in. The low and high boundaries split the range into three regions,
+    /// the one containing the optimum value is the optimum region.
+    #[must_use]
+    pub fn region(&self) -> MeterRegion {
+        if self.optimum < self.low {
+            if self.value <= self.low {
+                MeterRegion::Optimum
+            } else if self.value <= self.high {
+                MeterRegion::Suboptimum
+            } else {
+                MeterRegion::EvenLessGood
+            }
+        } else if self.optimum > self.high {
+            if self.value >= self.high {
+                MeterRegion::Optimum
+            } else if self.value >= self.low {
+                MeterRegion::Suboptimum
+            } else {
+                MeterRegion::EvenLessGood
+            }
+        } else if self.value >= self.low && self.value <= self.high {
+            MeterRegion::Optimum
+        } else {
+            MeterRegion::Suboptimum
+        }
+    }
+}
+
+/// The parsed attributes of an `<input type="range">`, with the value clamped to the range and snapped to the step as
+/// described by the HTML specification.
+///
+/// <https://html.spec.whatwg.org/multipage/input.html#range-state-(type=range)>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RangeElement {
+    pub value: f64,
+    pub min: f64,
+    pub max: f64,
+
+    /// The granularity of the value, or `None` for `step="any"`.
+    pub step: Option<f64>,
+}
+
+impl RangeElement {
+    /// Parses the attributes of an `<input type="range">`, with `min`, `max` and `step` defaulting to 0, 100 and 1.
+    ///
+    /// # Returns
+    /// The parsed range, or `None` if the element is not a range input.
+    #[must_use]
+    pub fn from_element(element: &Element) -> Option<Self> {
+        let is_range = element.tag == Tag::Html(HtmlTag::Input)
+            && element
+                .get_attribute("type")
+                .is_some_and(|input_type| input_type.trim().eq_ignore_ascii_case("range"));
+        if !is_range {
+            return None;
+        }
+
+        let min = number_attribute(element, "min").unwrap_or(0.0);
+        let max = number_attribute(element, "max").unwrap_or(100.0).max(min);
+        let step = if element
+            .get_attribute("step")
+            .is_some_and(|step| step.trim().eq_ignore_ascii_case("any"))
+        {
+            None
+        } else {
+            Some(
+                number_attribute(element, "step")
+                    .filter(|step| *step > 0.0)
+                    .unwrap_or(1.0),
+            )
+        };
+
+        let mut range = Self {
+            value: min,
+            min,
+            max,
+            step,
+        };
+        range.value = range.sanitize(number_attribute(element, "value").unwrap_or(min + (max - min) / 2.0));
+
+        Some(range)
+    }
+
+    /// Clamps a value to the range and rounds it to the nearest allowed step, counted from the minimum.
+    #[must_use]
+    pub fn sanitize(&self, value: f64) -> f64 {
+        let Some(step) = self.step else {
+            return value.clamp(self.min, self.max);
+        };
+
+        // The largest value reachable in whole steps, which is below the maximum when the range is not a multiple of
+        // the step.
+        let max = self.min + ((self.max - self.min) / step).floor() * step;
+        let steps = ((value.clamp(self.min, max) - self.min) / step).round();
+
+        // Rounds away the error of the multiplication, so that e.g. three steps of 0.1 are 0.3.
+        let value = self.min + steps * step;
+        let rounded = (value * 1e9).round() / 1e9;
+
+        rounded.clamp(self.min, max)
+    }
+
+    /// The fraction of the range from the minimum to the maximum covered by the value.
+    #[must_use]
+    pub fn position(&self) -> f64 {
+        if self.max > self.min {
+            (self.value - self.min) / (self.max - self.min)
+        } else {
+            0.0
+        }
+    }
+
+    /// The allowed value at a fraction of the range, e.g. where the thumb of the slider is dragged to.
+    #[must_use]
+    pub fn value_at(&self, position: f64) -> f64 {
+        self.sanitize(self.min + position.clamp(0.0, 1.0) * (self.max - self.min))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(tag: HtmlTag, attributes: &[(&str, &str)]) -> Element {
+        let mut element = Element {
+            tag: Tag::Html(tag),
+            ..Element::default()
+        };
+        for (name, value) in attributes {
+            element.set_attribute(name, *value);
+        }
+        element
+    }
+
+    fn meter(attributes: &[(&str, &str)]) -> MeterElement {
+        MeterElement::from_element(&element(HtmlTag::Meter, attributes)).unwrap()
+    }
+
+    #[test]
+    fn test_progress_attributes() {
+        let progress = ProgressElement::from_element(&element(HtmlTag::Progress, &[("value", "30"), ("max", "120")]));
+        assert_eq!(progress.and_then(|progress| progress.position()), Some(0.25));
+
+        let progress = ProgressElement::from_element(&element(HtmlTag::Progress, &[("value", "2"), ("max", "-1")]));
+        assert_eq!(
+            progress,
+            Some(ProgressElement {
+                value: Some(1.0),
+                max: 1.0
+            })
+        );
+
+        let progress = ProgressElement::from_element(&element(HtmlTag::Progress, &[("value", "NaN")]));
+        assert_eq!(progress.and_then(|progress| progress.position()), None);
+
+        assert_eq!(ProgressElement::from_element(&element(HtmlTag::Meter, &[])), None);
+    }
+
+    #[test]
+    fn test_meter_values_are_clamped() {
+        let meter = meter(&[
+            ("min", "10"),
+            ("max", "5"),
+            ("value", "20"),
+            ("low", "0"),
+            ("high", "30"),
+        ]);
+
+        assert_eq!(meter.max, 10.0);
+        assert_eq!(meter.value, 10.0);
+        assert_eq!(meter.low, 10.0);
+        assert_eq!(meter.high, 10.0);
+        assert_eq!(meter.position(), 0.0);
+        assert_eq!(MeterElement::from_element(&element(HtmlTag::Progress, &[])), None);
+    }
+
+    #[test]
+    fn test_meter_regions() {
+        let region = |value: &str, optimum: &str| {
+            meter(&[
+                ("max", "100"),
+                ("low", "30"),
+                ("high", "70"),
+                ("value", value),
+                ("optimum", optimum),
+            ])
+            .region()
+        };
+
+        assert_eq!(region("50", "50"), MeterRegion::Optimum);
+        assert_eq!(region("80", "50"), MeterRegion::Suboptimum);
+        assert_eq!(region("20", "10"), MeterRegion::Optimum);
+        assert_eq!(region("50", "10"), MeterRegion::Suboptimum);
+        assert_eq!(region("90", "10"), MeterRegion::EvenLessGood);
+        assert_eq!(region("10", "90"), MeterRegion::EvenLessGood);
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{DocumentRoot, DomNode, Element, HtmlTag, NodeId, Tag, form::text_content};
+
+/// The character shown in place of each character of a password.
+pub const PASSWORD_MASK: char = '•';
+
+/// The kind of an editable text control.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextControlKind {
+    /// A single-line `<input>`, e.g. `text`, `search`, `email`, `url` or `tel`.
+    Text,
+
+    /// An `<input type="password">`, whose characters are masked when displayed.
+    Password,
+
+    /// An `<input type="number">`, which only accepts numeric characters.
+    Number,
+
+    /// A multi-line `<textarea>`.
+    TextArea,
+}
+
+/// The kind of an `<input>` that is checked by clicking it rather than edited as text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CheckableKind {
+    /// An `<input type="checkbox">`, toggled on each click.
+    Checkbox,
+
+    /// An `<input type="radio">`, which unchecks the other radio buttons of its group when checked.
+    Radio,
+}
+
+impl CheckableKind {
+    /// Resolves the checkable kind of an element.
+    ///
+    /// # Arguments
+    /// * `element` - The `<input>` element.
+    ///
+    /// # Returns
+    /// The kind, or `None` if the element is not a checkbox or radio button.
+    #[must_use]
+    pub fn from_element(element: &Element) -> Option<Self> {
+        if element.tag != Tag::Html(HtmlTag::Input) {
+            return None;
+        }
+
+        let input_type = element.get_attribute("type")?.trim();
+        if input_type.eq_ignore_ascii_case("checkbox") {
+            Some(Self::Checkbox)
+        } else if input_type.eq_ignore_ascii_case("radio") {
+            Some(Self::Radio)
+        } else {
+            None
+        }
+    }
+}
+
+/// The editing constraints of an `<input>` or `<textarea>` element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextControl {
+    pub kind: TextControlKind,
+
+    /// The maximum number of characters of the value, from the `maxlength` attribute.
+    pub max_length: Option<usize>,
+
+    /// The hint shown while the value is empty, from the `placeholder` attribute.
+    pub placeholder: Option<String>,
+
+    /// Whether the value can be edited by the user, `false` for `readonly` and `disabled` controls.
+    pub editable: bool,
+
+    /// Whether the spelling of the value is checked, from the `spellcheck` attribute. Checking is on by default,
+    /// except for passwords and numbers, which are never checked.
+    pub spellcheck: bool,
+}
+
+impl TextControl {
+    /// Input types that are not edited as text.
+    const NON_TEXT_INPUT_TYPES: [&str; 13] = [
+        "hidden",
+        "submit",
+        "reset",
+        "button",
+        "image",
+        "file",
+        "checkbox",
+        "radio",
+        "range",
+        "color",
+        "date",
+        "datetime-local",
+        "time",
+    ];
+
+    /// Resolves the text control of an element.
+    ///
+    /// # Arguments
+    /// * `element` - The `<input>` or `<textarea>` element.
+    ///
+    /// # Returns
+    /// The text control, or `None` if the element is not edited as text.
+    #[must_use]
+    pub fn from_element(element: &Element) -> Option<Self> {
+        let kind = match element.tag {
+            Tag::Html(HtmlTag::Textarea) => TextControlKind::TextArea,
+            Tag::Html(HtmlTag::Input) => {
+                let input_type = element
+                    .get_attribute("type")
+                    .unwrap_or("text")
+                    .trim()
+                    .to_ascii_lowercase();
+
+                match input_type.as_str() {
+                    "password" => TextControlKind::Password,
+                    "number" => TextControlKind::Number,
+                    t if Self::NON_TEXT_INPUT_TYPES.contains(&t) => return None,
+                    _ => TextControlKind::Text,
+                }
+            }
+            _ => return None,
+        };
+
+        let spellcheck = matches!(kind, TextControlKind::Text | TextControlKind::TextArea)
+            && !element
+                .get_attribute("spellcheck")
+                .is_some_and(|value| value.trim().eq_ignore_ascii_case("false"));
+
+        Some(Self {
+            kind,
+            max_length: element
+                .get_attribute("maxlength")
+                .and_then(|l| l.trim().parse::<usize>().ok()),
+            placeholder: element
+                .get_attribute("placeholder")
+                .filter(|p| !p.is_empty())
+                .map(str::to_string),
+            editable: !element.has_attribute("readonly") && !element.has_attribute("disabled"),
+            spellcheck,
+        })
+    }
+
+    /// Whether the control accepts line breaks.
+    #[must_use]
+    pub const fn is_multiline(&self) -> bool {
+        matches!(self.kind, TextControlKind::TextArea)
+    }
+
+    /// Whether a single character may be entered into the control.
+    #[must_use]
+    pub fn accepts_char(&self, c: char) -> bool {
+        match self.kind {
+            TextControlKind::Number => c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'),
+            TextControlKind::TextArea => !c.is_control() || c == '\n' || c == '\t',
+            TextControlKind::Text | TextControlKind::Password => !c.is_control(),
+        }
+    }
+
+    /// Applies the constraints of the control to a value, removing characters it does not accept and
+    /// truncating it to `maxlength`.
+    #[must_use]
+    pub fn sanitize(&self, value: &str) -> String {
+        let filtered = value.chars().filter(|c| self.accepts_char(*c));
+
+        match self.max_length {
+            Some(max_length) => filtered.take(max_length).collect(),
+            None => filtered.collect(),
+        }
+    }
+
+    /// The text shown for a value, masking passwords.
+    #[must_use]
+    pub fn display_text(&self, value: &str) -> String {
+        match self.kind {
+            TextControlKind::Password => value.chars().map(|_| PASSWORD_MASK).collect(),
+            _ => value.to_string(),
+        }
+    }
+}
+
+/// Collects the radio buttons in the group of a radio button, which are the radio buttons with the same `name` in the
+/// same form, or outside of any form when the radio button has no form. A radio button without a name is the only
+/// member of its group.
+///
+/// # Arguments
+/// * `node` - The `<input type="radio">` node.
+/// * `tree` - The document the node belongs to.
+///
+/// # Returns
+/// The nodes of the group in tree order, including `node`.
+#[must_use]
+pub fn radio_group(node: &DomNode, tree: &DocumentRoot) -> Vec<NodeId> {
+    let name = node
+        .data
+        .as_element()
+        .and_then(|element| element.get_attribute("name"))
+        .filter(|name| !name.is_empty());
+
+    let Some(name) = name else {
+        return vec![node.id];
+    };
+
+    let form = form_owner(node, tree);
+
+    tree.nodes
+        .iter()
+        .filter(|candidate| {
+            candidate.data.as_element().is_some_and(|element| {
+                CheckableKind::from_element(element) == Some(CheckableKind::Radio)
+                    && element.get_attribute("name") == Some(name)
+            }) && form_owner(candidate, tree) == form
+        })
+        .map(|candidate| candidate.id)
+        .collect()
+}
+
+/// Finds the `<form>` ancestor of a node.
+fn form_owner(node: &DomNode, tree: &DocumentRoot) -> Option<NodeId> {
+    let mut parent = node.parent;
+
+    while let Some(parent_id) = parent {
+        let ancestor = &tree[parent_id];
+        if ancestor
+            .data
+            .as_element()
+            .is_some_and(|element| element.tag == Tag::Html(HtmlTag::Form))
+        {
+            return Some(parent_id);
+        }
+
+        parent = ancestor.parent;
+    }
+
+    None
+}
+
+/// Resolves the current value of an `<input>` or `<textarea>`.
+///
+/// The value of an `<input>` is its `value` attribute. A `<textarea>` uses its `value` attribute once it has been
+/// edited, and its text content before that.
+#[must_use]
+pub fn control_value(node: &DomNode, tree: &DocumentRoot) -> String {
+    let Some(element) = node.data.as_element() else {
+        return String::new();
+    };
+
+    match element.get_attribute("value") {
+        Some(value) => value.to_string(),
+        None if element.tag == Tag::Html(HtmlTag::Textarea) => text_content(node, tree),
+        None => String::new(),
+    }
+}
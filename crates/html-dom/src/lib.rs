@@ -5,19 +5,64 @@
 /// The DOM tree builder.
 mod builder;
 
+/// The 2D drawing API of `<canvas>` elements.
+mod canvas;
+
 /// The Collector for gathering information during parsing, like tags and attributes.
 ///
 /// This is optional and can be customized.
 mod collector;
 
+/// Decoding of the character references of text.
+mod decode;
+
 /// DOM based structures and utilities.
 mod dom;
 
+/// HTML5 constraint validation for forms.
+mod form;
+
+/// Gauges like `<progress>` and `<meter>`, and `<input type="range">` sliders.
+mod gauge;
+
+/// Editable text controls like `<input>` and `<textarea>`.
+mod input;
+
+/// Media elements like `<video>` and `<audio>`.
+mod media;
+
+/// Extraction of the metadata of a document, like its description and Open Graph tags.
+mod metadata;
+
+/// The options of `<select>` elements.
+mod select;
+
+/// The hover and focus state of the nodes of a document.
+mod state;
+
+/// Inline `<svg>` elements, which are drawn as images.
+mod svg;
+
 /// HTML tags and related utilities.
 mod tag;
 
 pub use builder::{BuildResult, DomTreeBuilder};
+pub use canvas::{
+    CanvasColor, CanvasCommand, CanvasImage, CanvasRenderingContext2d, DEFAULT_CANVAS_HEIGHT, DEFAULT_CANVAS_WIDTH,
+    canvas_size,
+};
 pub use collector::{Collector, DefaultCollector, TagInfo};
+pub use decode::{
+    TokenizationError, decode_attribute_value_lossy, decode_character_references, decode_character_references_lossy,
+};
 pub use dom::{DocumentRoot, DomNode, Element, NodeData, NodeId};
-pub use html_tokenizer::{HtmlTokenizer, Token, TokenState, TokenizerState};
-pub use tag::{HtmlTag, Tag};
+pub use form::{FormValidator, ValidationError, ValidityState, form_entries};
+pub use gauge::{MeterElement, MeterRegion, ProgressElement, RangeElement};
+pub use html_tokenizer::{HtmlTokenizer, Token, TokenKind, TokenState, TokenizerState};
+pub use input::{CheckableKind, PASSWORD_MASK, TextControl, TextControlKind, control_value, radio_group};
+pub use media::{MediaElement, MediaKind};
+pub use metadata::{DocumentMetadata, extract_metadata};
+pub use select::{SelectElement, SelectOption};
+pub use state::{ElementState, HoverChange};
+pub use svg::{DEFAULT_SVG_HEIGHT, DEFAULT_SVG_WIDTH, inline_svgs, svg_document};
+pub use tag::{HTML_NAMESPACE, HtmlTag, MATHML_NAMESPACE, SVG_NAMESPACE, SvgTag, Tag};
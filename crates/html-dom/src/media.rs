@@ -0,0 +1,91 @@
+use crate::{DomNode, HtmlTag, Tag};
+
+/// The kind of media element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MediaKind {
+    Video,
+    Audio,
+}
+
+/// The parsed attributes of a `<video>` or `<audio>` element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaElement {
+    pub kind: MediaKind,
+
+    /// Whether the element shows playback controls, an `<audio>` element without them is not rendered.
+    pub controls: bool,
+}
+
+impl MediaElement {
+    /// Parses the media attributes of a DOM node.
+    ///
+    /// # Arguments
+    /// * `node` - The `<video>` or `<audio>` node.
+    ///
+    /// # Returns
+    /// The parsed media element, or `None` if the node is not a media element.
+    #[must_use]
+    pub fn from_node(node: &DomNode) -> Option<Self> {
+        let element = node.data.as_element()?;
+
+        let kind = match element.tag {
+            Tag::Html(HtmlTag::Video) => MediaKind::Video,
+            Tag::Html(HtmlTag::Audio) => MediaKind::Audio,
+            _ => return None,
+        };
+
+        Some(Self {
+            kind,
+            controls: element.has_attribute("controls"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DocumentRoot, Element, NodeData};
+
+    use super::*;
+
+    fn media(tag: HtmlTag, attributes: &[(&str, &str)]) -> Option<MediaElement> {
+        let mut element = Element {
+            tag: Tag::Html(tag),
+            ..Element::default()
+        };
+        for (name, value) in attributes {
+            element.set_attribute(name, *value);
+        }
+
+        let mut dom = DocumentRoot::new();
+        let node_id = dom.push_node(&NodeData::Element(element), None);
+
+        MediaElement::from_node(&dom[node_id])
+    }
+
+    #[test]
+    fn test_video_with_controls() {
+        assert_eq!(
+            media(HtmlTag::Video, &[("controls", ""), ("src", "movie.mp4")]),
+            Some(MediaElement {
+                kind: MediaKind::Video,
+                controls: true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_audio_without_controls() {
+        assert_eq!(
+            media(HtmlTag::Audio, &[("src", "song.mp3"), ("autoplay", "")]),
+            Some(MediaElement {
+                kind: MediaKind::Audio,
+                controls: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_not_a_media_element() {
+        assert_eq!(media(HtmlTag::Img, &[("controls", "")]), None);
+    }
+}
@@ -0,0 +1,137 @@
+use url::Url;
+
+use crate::{
+    dom::{DocumentRoot, Element, NodeData},
+    tag::{HtmlTag, Tag},
+};
+
+/// Structured metadata of a document, read from its `<title>`, `<meta>` and `<link>` elements.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentMetadata {
+    /// The text of the first `<title>` element.
+    pub title: Option<String>,
+
+    /// The content of `<meta name="description">`.
+    pub description: Option<String>,
+
+    /// The Open Graph title, from `<meta property="og:title">`.
+    pub og_title: Option<String>,
+
+    /// The Open Graph image, from `<meta property="og:image">`.
+    pub og_image: Option<String>,
+
+    /// The Open Graph description, from `<meta property="og:description">`.
+    pub og_description: Option<String>,
+
+    /// The preferred URL of the document, from `<link rel="canonical">`.
+    pub canonical_url: Option<Url>,
+
+    /// The content of `<meta name="author">`.
+    pub author: Option<String>,
+
+    /// The publication date, from `<meta property="article:published_time">`, `<meta name="date">` or
+    /// `<meta itemprop="datePublished">`, as written in the document.
+    pub published_date: Option<String>,
+
+    /// The language of the document, from the `lang` attribute of `<html>` or
+    /// `<meta http-equiv="content-language">`.
+    pub language: Option<String>,
+}
+
+/// Extracts the metadata of a document. When a value is given more than once, the first one in document order is
+/// used.
+///
+/// # Arguments
+/// * `root` - The document to read the metadata from.
+/// * `base_url` - The URL relative canonical URLs are resolved against, if any.
+///
+/// # Returns
+/// The metadata found in the document, with missing or empty values left as `None`.
+#[must_use]
+pub fn extract_metadata(root: &DocumentRoot, base_url: Option<&Url>) -> DocumentMetadata {
+    fn set(field: &mut Option<String>, value: Option<&str>) {
+        if field.is_none()
+            && let Some(value) = value.map(str::trim).filter(|value| !value.is_empty())
+        {
+            *field = Some(value.to_string());
+        }
+    }
+
+    let mut metadata = DocumentMetadata::default();
+    let mut content_language = None;
+
+    for node in &root.nodes {
+        let NodeData::Element(element) = &node.data else {
+            continue;
+        };
+
+        match element.tag {
+            Tag::Html(HtmlTag::Html) => set(&mut metadata.language, element.get_attribute("lang")),
+            Tag::Html(HtmlTag::Title) if metadata.title.is_none() => {
+                let text = node
+                    .children
+                    .iter()
+                    .filter_map(|child| root[child].data.as_text())
+                    .flat_map(|text| text.split_whitespace())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                set(&mut metadata.title, Some(&text));
+            }
+            Tag::Html(HtmlTag::Meta) => {
+                let content = element.get_attribute("content");
+                let key = |name: &str| attribute_lowercase(element, name);
+
+                match key("name").as_deref() {
+                    Some("description") => set(&mut metadata.description, content),
+                    Some("author") => set(&mut metadata.author, content),
+                    Some("date") => set(&mut metadata.published_date, content),
+                    _ => {}
+                }
+
+                match key("property").as_deref() {
+                    Some("og:title") => set(&mut metadata.og_title, content),
+                    Some("og:image") => set(&mut metadata.og_image, content),
+                    Some("og:description") => set(&mut metadata.og_description, content),
+                    Some("article:published_time") => set(&mut metadata.published_date, content),
+                    _ => {}
+                }
+
+                if key("itemprop").as_deref() == Some("datepublished") {
+                    set(&mut metadata.published_date, content);
+                }
+
+                if key("http-equiv").as_deref() == Some("content-language") {
+                    set(&mut content_language, content);
+                }
+            }
+            Tag::Html(HtmlTag::Link) if metadata.canonical_url.is_none() => {
+                let is_canonical = element.get_attribute("rel").is_some_and(|rel| {
+                    rel.split_whitespace()
+                        .any(|token| token.eq_ignore_ascii_case("canonical"))
+                });
+
+                if is_canonical && let Some(href) = element.get_attribute("href").map(str::trim) {
+                    metadata.canonical_url = match base_url {
+                        Some(base_url) => base_url.join(href).ok(),
+                        None => Url::parse(href).ok(),
+                    };
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if metadata.language.is_none() {
+        metadata.language = content_language;
+    }
+
+    metadata
+}
+
+/// The value of an attribute in ASCII lowercase, for attributes whose values are matched case-insensitively.
+fn attribute_lowercase(element: &Element, name: &str) -> Option<String> {
+    element
+        .get_attribute(name)
+        .map(|value| value.trim().to_ascii_lowercase())
+}
@@ -0,0 +1,137 @@
+use crate::{DocumentRoot, DomNode, HtmlTag, NodeId, Tag, form::text_content};
+
+/// An `<option>` of a `<select>` element, in tree order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectOption {
+    /// The `<option>` node.
+    pub node_id: NodeId,
+
+    /// The text shown for the option, its `label` attribute or its text with collapsed whitespace.
+    pub label: String,
+
+    /// The value submitted for the option, its `value` attribute or its text.
+    pub value: String,
+
+    /// Whether the option or its `<optgroup>` is `disabled`.
+    pub disabled: bool,
+
+    /// Whether the option has the `selected` attribute.
+    pub selected: bool,
+}
+
+/// The parsed options of a `<select>` element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectElement {
+    pub options: Vec<SelectOption>,
+
+    /// Whether the `multiple` attribute is present, such a select shows a list box instead of a dropdown.
+    pub multiple: bool,
+
+    pub disabled: bool,
+}
+
+impl SelectElement {
+    /// Parses the options of a DOM node, including those nested in `<optgroup>` elements.
+    ///
+    /// # Arguments
+    /// * `node` - The `<select>` node.
+    /// * `tree` - The document the node belongs to, used to look up its options.
+    ///
+    /// # Returns
+    /// The parsed select element, or `None` if the node is not a `<select>`.
+    #[must_use]
+    pub fn from_node(node: &DomNode, tree: &DocumentRoot) -> Option<Self> {
+        let element = node.data.as_element()?;
+        if element.tag != Tag::Html(HtmlTag::Select) {
+            return None;
+        }
+
+        let mut options = Vec::new();
+        let mut stack: Vec<(NodeId, bool)> = node
+            .children
+            .iter()
+            .rev()
+            .map(|child_id| (*child_id, false))
+            .collect();
+
+        while let Some((node_id, group_disabled)) = stack.pop() {
+            let child = &tree[node_id];
+            let Some(element) = child.data.as_element() else {
+                continue;
+            };
+
+            match element.tag {
+                Tag::Html(HtmlTag::Option) => {
+                    let text = text_content(child, tree)
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" ");
+
+                    options.push(SelectOption {
+                        node_id,
+                        label: element
+                            .get_attribute("label")
+                            .filter(|label| !label.is_empty())
+                            .map_or_else(|| text.clone(), str::to_string),
+                        value: element.get_attribute("value").map_or(text, str::to_string),
+                        disabled: group_disabled || element.has_attribute("disabled"),
+                        selected: element.has_attribute("selected"),
+                    });
+                }
+                Tag::Html(HtmlTag::Optgroup) => {
+                    let disabled = group_disabled || element.has_attribute("disabled");
+                    stack.extend(
+                        child
+                            .children
+                            .iter()
+                            .rev()
+                            .map(|child_id| (*child_id, disabled)),
+                    );
+                }
+                _ => stack.extend(
+                    child
+                        .children
+                        .iter()
+                        .rev()
+                        .map(|child_id| (*child_id, group_disabled)),
+                ),
+            }
+        }
+
+        Some(Self {
+            options,
+            multiple: element.has_attribute("multiple"),
+            disabled: element.has_attribute("disabled"),
+        })
+    }
+
+    /// The index of the option that is selected, the first enabled option with the `selected` attribute, or for a
+    /// dropdown without one the first enabled option.
+    #[must_use]
+    pub fn selected_index(&self) -> Option<usize> {
+        self.options
+            .iter()
+            .position(|option| option.selected && !option.disabled)
+            .or_else(|| {
+                if self.multiple {
+                    None
+                } else {
+                    self.options.iter().position(|option| !option.disabled)
+                }
+            })
+    }
+
+    /// The option that is selected, see [`SelectElement::selected_index`].
+    #[must_use]
+    pub fn selected_option(&self) -> Option<&SelectOption> {
+        self.selected_index().map(|index| &self.options[index])
+    }
+
+    /// The value of the select, which is the value of its selected option.
+    #[must_use]
+    pub fn value(&self) -> String {
+        self.selected_option()
+            .map(|option| option.value.clone())
+            .unwrap_or_default()
+    }
+}
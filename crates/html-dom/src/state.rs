@@ -0,0 +1,97 @@
+use std::collections::HashSet;
+
+use crate::{DocumentRoot, NodeId};
+
+/// The user action state of the nodes of a document, which is matched by the `:hover` and `:focus` pseudo-classes.
+#[derive(Debug, Clone, Default)]
+pub struct ElementState {
+    /// The node under the pointer and all of its ancestors.
+    hovered: HashSet<NodeId>,
+
+    /// The node receiving keyboard input, if any.
+    focused: Option<NodeId>,
+}
+
+/// The nodes entering and leaving the hover state when the pointer moves, see [`DocumentRoot::hover_change`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HoverChange {
+    pub added: Vec<NodeId>,
+    pub removed: Vec<NodeId>,
+}
+
+impl HoverChange {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// All the nodes whose hover state changed.
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.added.iter().chain(&self.removed).copied()
+    }
+}
+
+impl DocumentRoot {
+    /// Whether the node is under the pointer or is an ancestor of the node under the pointer.
+    #[must_use]
+    pub fn is_hovered(&self, node_id: NodeId) -> bool {
+        self.state.hovered.contains(&node_id)
+    }
+
+    /// Whether the node receives keyboard input.
+    #[must_use]
+    pub fn is_focused(&self, node_id: NodeId) -> bool {
+        self.state.focused == Some(node_id)
+    }
+
+    /// The node receiving keyboard input, if any.
+    #[must_use]
+    pub const fn focused(&self) -> Option<NodeId> {
+        self.state.focused
+    }
+
+    /// Computes how the hover state changes when the pointer moves over another node, without applying it.
+    ///
+    /// # Arguments
+    /// * `target` - The node under the pointer, or `None` if the pointer is outside of the document.
+    ///
+    /// # Returns
+    /// The nodes entering and leaving the hover state, sorted by their `NodeId`.
+    #[must_use]
+    pub fn hover_change(&self, target: Option<NodeId>) -> HoverChange {
+        let mut hovered = HashSet::new();
+        if let Some(node) = target.and_then(|target| self.get_node(&target)) {
+            hovered.insert(node.id);
+            hovered.extend(self.ancestors(node).iter().map(|ancestor| ancestor.id));
+        }
+
+        let mut added: Vec<NodeId> = hovered.difference(&self.state.hovered).copied().collect();
+        let mut removed: Vec<NodeId> = self.state.hovered.difference(&hovered).copied().collect();
+        added.sort_unstable_by_key(|node_id| node_id.0);
+        removed.sort_unstable_by_key(|node_id| node_id.0);
+
+        HoverChange { added, removed }
+    }
+
+    /// Applies a change of the hover state computed by [`DocumentRoot::hover_change`].
+    pub fn apply_hover_change(&mut self, change: &HoverChange) {
+        for node_id in &change.removed {
+            self.state.hovered.remove(node_id);
+        }
+
+        self.state.hovered.extend(change.added.iter().copied());
+    }
+
+    /// Moves the keyboard focus to another node, or removes it when `None`.
+    ///
+    /// # Returns
+    /// The nodes whose focus state changed, the previously focused node and the newly focused one.
+    pub fn set_focused(&mut self, node_id: Option<NodeId>) -> Vec<NodeId> {
+        if self.state.focused == node_id {
+            return Vec::new();
+        }
+
+        let previous = std::mem::replace(&mut self.state.focused, node_id);
+        previous.into_iter().chain(node_id).collect()
+    }
+}
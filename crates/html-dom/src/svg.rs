@@ -0,0 +1,91 @@
+use std::fmt::Write;
+
+use crate::{
+    dom::{DocumentRoot, NodeData, NodeId},
+    tag::{SVG_NAMESPACE, SvgTag, Tag},
+};
+
+/// The width of an inline `<svg>` element without a `width` attribute.
+pub const DEFAULT_SVG_WIDTH: u32 = 300;
+
+/// The height of an inline `<svg>` element without a `height` attribute.
+pub const DEFAULT_SVG_HEIGHT: u32 = 150;
+
+/// Finds the inline `<svg>` elements of a document. The parser keeps the content of an `<svg>` element as markup,
+/// so nested `<svg>` elements are part of that markup and only the outermost ones are returned.
+///
+/// # Arguments
+/// * `dom` - The document to search.
+///
+/// # Returns
+/// The IDs of the `<svg>` elements in document order.
+#[must_use]
+pub fn inline_svgs(dom: &DocumentRoot) -> Vec<NodeId> {
+    dom.nodes
+        .iter()
+        .filter(|node| matches!(&node.data, NodeData::Element(element) if element.tag == Tag::Svg(SvgTag::Svg)))
+        .map(|node| node.id)
+        .collect()
+}
+
+/// Serializes an inline `<svg>` element into a standalone SVG document, from its attributes and the markup the
+/// parser kept as its text content.
+///
+/// # Arguments
+/// * `dom` - The document containing the element.
+/// * `node_id` - The ID of the `<svg>` element.
+/// * `style` - CSS declarations added to the `style` attribute of the root element, e.g. the inherited properties
+///   computed for the element from the stylesheets of the page, which the content of the SVG document inherits.
+///
+/// # Returns
+/// The SVG document, or `None` if the node is not an `<svg>` element.
+#[must_use]
+pub fn svg_document(dom: &DocumentRoot, node_id: NodeId, style: Option<&str>) -> Option<String> {
+    let node = dom.get_node(&node_id)?;
+    let NodeData::Element(element) = &node.data else {
+        return None;
+    };
+
+    if element.tag != Tag::Svg(SvgTag::Svg) {
+        return None;
+    }
+
+    let mut document = String::from("<svg");
+
+    if !element.has_attribute("xmlns") {
+        let _ = write!(document, " xmlns=\"{SVG_NAMESPACE}\"");
+    }
+
+    let escape = |value: &str| {
+        value
+            .replace('&', "&amp;")
+            .replace('"', "&quot;")
+            .replace('<', "&lt;")
+    };
+
+    for (name, value) in element.attributes.iter().flatten() {
+        if style.is_some() && name.eq_ignore_ascii_case("style") {
+            continue;
+        }
+
+        let _ = write!(document, " {name}=\"{}\"", escape(value));
+    }
+
+    if let Some(style) = style {
+        // Declarations of the element's own `style` attribute come last, so they still take precedence.
+        let own_style = element.get_attribute("style").unwrap_or_default();
+        let _ = write!(document, " style=\"{}\"", escape(&format!("{style};{own_style}")));
+    }
+
+    document.push('>');
+
+    for child in &node.children {
+        if let NodeData::Text(markup) = &dom[child].data {
+            document.push_str(markup);
+        }
+    }
+
+    document.push_str("</svg>");
+
+    Some(document)
+}
@@ -2,6 +2,15 @@ use std::fmt::Display;
 
 use strum::AsRefStr;
 
+/// The namespace of HTML elements.
+pub const HTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
+
+/// The namespace of SVG elements.
+pub const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+/// The namespace of MathML elements.
+pub const MATHML_NAMESPACE: &str = "http://www.w3.org/1998/Math/MathML";
+
 /// Represents an HTML tag, which can be either a known tag or an unknown tag.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Tag {
@@ -46,6 +55,16 @@ impl Tag {
         }
     }
 
+    /// The namespace URI of the element, unknown tags are treated as HTML elements.
+    #[must_use]
+    pub const fn namespace(&self) -> &'static str {
+        match self {
+            Self::Html(_) | Self::Unknown(_) => HTML_NAMESPACE,
+            Self::Svg(_) => SVG_NAMESPACE,
+            Self::MathML(_) => MATHML_NAMESPACE,
+        }
+    }
+
     #[must_use]
     pub fn as_str(&self) -> &str {
         match self {
@@ -544,11 +563,19 @@ impl HtmlTag {
                 | (Self::Dd, Self::Dd)
                 | (Self::Dt, Self::Dt)
                 | (Self::Option, Self::Option | Self::Optgroup)
+                | (Self::Optgroup, Self::Optgroup)
                 | (Self::Tr, Self::Tr)
                 | (Self::Td | Self::Th, Self::Td | Self::Th | Self::Tr)
                 | (Self::Head, Self::Body)
         )
     }
+
+    /// Determines if the end tag of the element may be omitted when an ancestor is closed, like an `<option>` that is
+    /// closed by the `</select>` around it.
+    #[must_use]
+    pub const fn has_implied_end_tag(self) -> bool {
+        matches!(self, Self::Option | Self::Optgroup)
+    }
 }
 
 impl Display for HtmlTag {
@@ -85,6 +85,9 @@ fn criterion_benchmark(c: &mut Criterion) {
 
                                 info!("Extracted SVG Content: {}", svg);
                             }
+                            BlockedReason::ResourceHint { kind, href, .. } => {
+                                info!("Parser found resource hint: kind={:?}, href={}", kind, href);
+                            }
                             _ => {
                                 error!("Parser blocked for unhandled reason: {:?}", reason);
                                 break None;
@@ -0,0 +1,282 @@
+use std::borrow::Cow;
+
+use encoding_rs::{Encoding, UTF_8, WINDOWS_1252};
+
+use crate::errors::HtmlParseError;
+
+/// The number of bytes scanned for a `<meta>` tag declaring the character encoding.
+const PRESCAN_LENGTH: usize = 1024;
+
+/// An HTML document decoded to UTF-8, ready to be given to the [`HtmlStreamParser`](crate::HtmlStreamParser).
+#[derive(Debug)]
+pub struct DecodedDocument<'a> {
+    /// The text of the document.
+    pub text: Cow<'a, str>,
+
+    /// The name of the encoding the document was decoded from, e.g. `Shift_JIS`.
+    pub encoding: &'static str,
+
+    /// Set if some bytes were invalid in the encoding and were replaced with U+FFFD. The document is still usable.
+    pub error: Option<HtmlParseError>,
+}
+
+/// Decodes the bytes of an HTML document to UTF-8.
+///
+/// The encoding is taken from, in order of preference, a byte order mark, a `<meta charset>` or
+/// `<meta http-equiv="Content-Type">` tag in the first 1024 bytes, the `charset` parameter of the `Content-Type`
+/// header, and finally defaults to UTF-8.
+///
+/// # Arguments
+/// * `bytes` - The body of the response.
+/// * `content_type` - The value of the `Content-Type` header of the response, if any.
+#[must_use]
+pub fn decode_document<'a>(bytes: &'a [u8], content_type: Option<&str>) -> DecodedDocument<'a> {
+    let (encoding, bytes) = match Encoding::for_bom(bytes) {
+        Some((encoding, bom_length)) => (encoding, &bytes[bom_length..]),
+        None => {
+            let encoding = prescan(bytes)
+                .or_else(|| {
+                    content_type
+                        .and_then(charset_parameter)
+                        .and_then(encoding_for_label)
+                })
+                .unwrap_or(UTF_8);
+
+            (encoding, bytes)
+        }
+    };
+
+    let (text, had_errors) = encoding.decode_without_bom_handling(bytes);
+
+    DecodedDocument {
+        text,
+        encoding: encoding.name(),
+        error: had_errors
+            .then(|| HtmlParseError::CharsetDecodeError(format!("invalid byte sequences for {}", encoding.name()))),
+    }
+}
+
+/// Looks up an encoding by a label such as `utf-8` or `latin1`.
+///
+/// UTF-16 labels are read as UTF-8 since the `<meta>` tag could not have been read otherwise, and `x-user-defined`
+/// is read as windows-1252, as required by the HTML specification.
+fn encoding_for_label(label: &str) -> Option<&'static Encoding> {
+    let encoding = Encoding::for_label(label.trim().as_bytes())?;
+
+    if encoding.name().starts_with("UTF-16") {
+        Some(UTF_8)
+    } else if encoding.name() == "x-user-defined" {
+        Some(WINDOWS_1252)
+    } else {
+        Some(encoding)
+    }
+}
+
+/// Extracts the `charset` parameter of a `Content-Type` value, e.g. `text/html; charset="Shift_JIS"`.
+fn charset_parameter(content_type: &str) -> Option<&str> {
+    content_type.split(';').skip(1).find_map(|parameter| {
+        let (name, value) = parameter.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| value.trim().trim_matches(['"', '\'']))
+    })
+}
+
+/// Scans the start of the document for a `<meta>` tag declaring the encoding.
+///
+/// <https://html.spec.whatwg.org/multipage/parsing.html#prescan-a-byte-stream-to-determine-its-encoding>
+fn prescan(bytes: &[u8]) -> Option<&'static Encoding> {
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(PRESCAN_LENGTH)]).to_ascii_lowercase();
+    let mut rest = head.as_str();
+
+    while let Some(start) = rest.find("<meta") {
+        rest = &rest[start + "<meta".len()..];
+
+        if !rest.starts_with(|c: char| c.is_ascii_whitespace() || c == '/') {
+            continue;
+        }
+
+        let end = rest.find('>').unwrap_or(rest.len());
+        let attributes = parse_attributes(&rest[..end]);
+        let attribute = |name: &str| {
+            attributes
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| *value)
+        };
+
+        let label = match attribute("charset") {
+            Some(charset) => Some(charset),
+            None if attribute("http-equiv") == Some("content-type") => {
+                attribute("content").and_then(meta_content_charset)
+            }
+            None => None,
+        };
+
+        if let Some(encoding) = label.and_then(|label| encoding_for_label(label.trim_matches(['"', '\'', ';', ' ']))) {
+            return Some(encoding);
+        }
+    }
+
+    None
+}
+
+/// Extracts the encoding label from the `content` attribute of a `<meta http-equiv="Content-Type">` tag, e.g.
+/// `text/html; charset=Shift_JIS`. Whitespace is allowed around the `=`, and the label ends at a `;` or whitespace
+/// unless it is quoted.
+///
+/// <https://html.spec.whatwg.org/multipage/urls-and-fetching.html#algorithm-for-extracting-a-character-encoding-from-a-meta-element>
+fn meta_content_charset(content: &str) -> Option<&str> {
+    let mut rest = content;
+
+    loop {
+        let start = rest.find("charset")?;
+        rest = rest[start + "charset".len()..].trim_start_matches(|c: char| c.is_ascii_whitespace());
+
+        let Some(value) = rest.strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start_matches(|c: char| c.is_ascii_whitespace());
+
+        return match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].find(quote).map(|end| &value[1..=end]),
+            Some(_) => Some(
+                &value[..value
+                    .find(|c: char| c == ';' || c.is_ascii_whitespace())
+                    .unwrap_or(value.len())],
+            ),
+            None => None,
+        };
+    }
+}
+
+/// Splits the inside of a tag into `name=value` pairs, values may be quoted with `"` or `'` or left unquoted.
+fn parse_attributes(mut input: &str) -> Vec<(&str, &str)> {
+    let mut attributes = Vec::new();
+
+    loop {
+        input = input.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == '/');
+        if input.is_empty() {
+            break;
+        }
+
+        let name_end = input
+            .find(|c: char| c.is_ascii_whitespace() || c == '=' || c == '/')
+            .unwrap_or(input.len());
+        let name = &input[..name_end];
+        input = input[name_end..].trim_start();
+
+        let Some(after_equals) = input.strip_prefix('=') else {
+            attributes.push((name, ""));
+            continue;
+        };
+
+        input = after_equals.trim_start();
+
+        let value = match input.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let value_end = input[1..].find(quote).map_or(input.len(), |end| end + 1);
+                let value = &input[1..value_end];
+                input = input.get(value_end + 1..).unwrap_or("");
+                value
+            }
+            _ => {
+                let value_end = input
+                    .find(|c: char| c.is_ascii_whitespace())
+                    .unwrap_or(input.len());
+                let value = &input[..value_end];
+                input = &input[value_end..];
+                value
+            }
+        };
+
+        attributes.push((name, value));
+    }
+
+    attributes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bom_overrides_meta_and_header() {
+        let bytes = b"\xEF\xBB\xBF<meta charset=\"windows-1252\"><p>caf\xC3\xA9</p>";
+        let document = decode_document(bytes, Some("text/html; charset=Shift_JIS"));
+
+        assert_eq!(document.encoding, "UTF-8");
+        assert_eq!(document.text, "<meta charset=\"windows-1252\"><p>caf\u{E9}</p>");
+        assert!(document.error.is_none());
+    }
+
+    #[test]
+    fn test_utf16_bom() {
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(
+            "<meta charset=\"windows-1252\"><p>hi</p>"
+                .bytes()
+                .flat_map(|byte| [byte, 0]),
+        );
+
+        let document = decode_document(&bytes, None);
+        assert_eq!(document.encoding, "UTF-16LE");
+        assert_eq!(document.text, "<meta charset=\"windows-1252\"><p>hi</p>");
+    }
+
+    #[test]
+    fn test_meta_charset() {
+        let document = decode_document(b"<meta charset=\"windows-1252\"><p>caf\xE9</p>", None);
+        assert_eq!(document.encoding, "windows-1252");
+        assert_eq!(document.text, "<meta charset=\"windows-1252\"><p>caf\u{E9}</p>");
+    }
+
+    #[test]
+    fn test_meta_overrides_header() {
+        let document = decode_document(b"<meta charset=utf-8>", Some("text/html; charset=\"Shift_JIS\""));
+        assert_eq!(document.encoding, "UTF-8");
+
+        let document = decode_document(b"<p>no meta</p>", Some("text/html; charset=\"Shift_JIS\""));
+        assert_eq!(document.encoding, "Shift_JIS");
+    }
+
+    #[test]
+    fn test_prescan_limit() {
+        let meta = "<meta charset=\"windows-1252\">";
+
+        let inside = format!("<!--{}-->{meta}", " ".repeat(PRESCAN_LENGTH - meta.len() - 7));
+        assert_eq!(inside.len(), PRESCAN_LENGTH);
+        assert_eq!(decode_document(inside.as_bytes(), None).encoding, "windows-1252");
+
+        let outside = format!("<!--{}-->{meta}", " ".repeat(PRESCAN_LENGTH));
+        assert_eq!(decode_document(outside.as_bytes(), None).encoding, "UTF-8");
+    }
+
+    #[test]
+    fn test_http_equiv_content() {
+        let encoding = |content: &str| {
+            let html = format!("<meta http-equiv=\"Content-Type\" content=\"{content}\">");
+            decode_document(html.as_bytes(), None).encoding
+        };
+
+        assert_eq!(encoding("text/html; charset=Shift_JIS"), "Shift_JIS");
+        assert_eq!(encoding("text/html; charset=windows-1252; foo=bar"), "windows-1252");
+        assert_eq!(encoding("text/html; charset = 'ISO-8859-2'"), "ISO-8859-2");
+        assert_eq!(encoding("text/html;charset=iso-8859-2 "), "ISO-8859-2");
+        assert_eq!(encoding("text/html; charset='ISO-8859-2"), "UTF-8");
+        assert_eq!(encoding("text/html"), "UTF-8");
+    }
+
+    #[test]
+    fn test_http_equiv_requires_content_type() {
+        let document = decode_document(b"<meta http-equiv=\"refresh\" content=\"charset=windows-1252\">", None);
+        assert_eq!(document.encoding, "UTF-8");
+    }
+
+    #[test]
+    fn test_label_overrides() {
+        assert_eq!(decode_document(b"<meta charset=\"utf-16\">", None).encoding, "UTF-8");
+        assert_eq!(decode_document(b"<meta charset=\"x-user-defined\">", None).encoding, "windows-1252");
+        assert_eq!(decode_document(b"<meta charset=\"latin1\">", None).encoding, "windows-1252");
+    }
+}
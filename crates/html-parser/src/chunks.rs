@@ -0,0 +1,98 @@
+use std::{
+    collections::VecDeque,
+    io::{BufRead, Error, ErrorKind, Read, Result},
+};
+
+/// A reader over the chunks of a document that arrive separately, like the body of a response read with chunked
+/// transfer encoding. The chunks are read in order as one stream, so a character or tag split across two chunks is
+/// read as a whole.
+///
+/// A reader created with `streaming` accepts chunks as they arrive. Until it is closed, reading it once every chunk
+/// has been read fails with `ErrorKind::WouldBlock` instead of ending the stream.
+#[derive(Debug, Default)]
+pub struct ChunkReader {
+    /// The chunks that have not been fully read yet.
+    chunks: VecDeque<Vec<u8>>,
+
+    /// How much of the first chunk has been read.
+    position: usize,
+
+    /// Whether every chunk has been pushed, so that running out of chunks is the end of the stream.
+    closed: bool,
+}
+
+impl ChunkReader {
+    /// Creates a reader over every chunk of a document.
+    pub fn new(chunks: impl IntoIterator<Item = Vec<u8>>) -> Self {
+        Self {
+            chunks: chunks.into_iter().collect(),
+            position: 0,
+            closed: true,
+        }
+    }
+
+    /// Creates a reader that chunks are pushed to as they arrive, until it is closed with `close`.
+    #[must_use]
+    pub fn streaming() -> Self {
+        Self::default()
+    }
+
+    /// Appends a chunk after the ones not read yet.
+    pub fn push(&mut self, chunk: Vec<u8>) {
+        self.chunks.push_back(chunk);
+    }
+
+    /// Marks every chunk as pushed, the stream then ends once they have been read.
+    pub const fn close(&mut self) {
+        self.closed = true;
+    }
+
+    #[must_use]
+    pub const fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Whether every chunk pushed so far has been read.
+    #[must_use]
+    pub fn is_drained(&self) -> bool {
+        self.chunks.iter().map(Vec::len).sum::<usize>() <= self.position
+    }
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let available = self.fill_buf()?;
+        let len = available.len().min(buf.len());
+
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+
+        Ok(len)
+    }
+}
+
+impl BufRead for ChunkReader {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        while self
+            .chunks
+            .front()
+            .is_some_and(|chunk| self.position >= chunk.len())
+        {
+            self.chunks.pop_front();
+            self.position = 0;
+        }
+
+        if self.chunks.is_empty() && !self.closed {
+            return Err(Error::from(ErrorKind::WouldBlock));
+        }
+
+        Ok(self
+            .chunks
+            .front()
+            .map_or(&[], |chunk| &chunk[self.position..]))
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.position += amount;
+    }
+}
@@ -1,7 +1,8 @@
 use thiserror::Error;
 
 #[derive(Error, Debug, Clone, PartialEq, Eq, PartialOrd)]
-pub enum HtmlParsingError {
+#[non_exhaustive]
+pub enum HtmlParseError {
     #[error("unable to read from stream: {0}")]
     UnableToReadStream(String),
 
@@ -10,4 +11,7 @@ pub enum HtmlParsingError {
 
     #[error("unexpected UTF-8 error: {0}")]
     UnexpectedUtf8Error(String),
+
+    #[error("unable to decode document: {0}")]
+    CharsetDecodeError(String),
 }
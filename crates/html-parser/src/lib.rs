@@ -13,6 +13,12 @@
 //! 5. Once the content is available, the parser resumes processing.
 //! 6. The parser continues until the entire HTML content is processed.
 
+/// Detection of the character encoding of documents.
+mod charset;
+
+/// A reader over a document that arrives in chunks.
+mod chunks;
+
 /// The error types for HTML parsing.
 pub mod errors;
 
@@ -22,5 +28,7 @@ mod parser;
 /// The state management for the HTML parser.
 mod state;
 
-pub use parser::HtmlStreamParser;
-pub use state::{BlockedReason, ParserState, ResourceType, Script};
+pub use charset::{DecodedDocument, decode_document};
+pub use chunks::ChunkReader;
+pub use parser::{HtmlStreamParser, ParserOptions};
+pub use state::{BlockedReason, ParserState, ResourceHintKind, ResourceType, Script};
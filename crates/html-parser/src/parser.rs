@@ -1,11 +1,14 @@
-use std::io::BufRead;
+use std::io::{BufRead, ErrorKind};
 use std::mem;
 
 use crate::{
-    errors::HtmlParsingError,
+    chunks::ChunkReader,
+    errors::HtmlParseError,
     state::{BlockingCause, ResourceMetadata, Script},
 };
-use html_dom::{Collector, DefaultCollector, DomTreeBuilder, HtmlTokenizer, Token, TokenState, TokenizerState};
+use html_dom::{
+    Collector, DefaultCollector, DocumentRoot, DomTreeBuilder, HtmlTag, HtmlTokenizer, Token, TokenKind, TokenState,
+};
 use tracing::trace;
 
 use crate::{
@@ -13,6 +16,14 @@ use crate::{
     state::{BlockedReason, ParserState},
 };
 
+/// Options changing how a document is parsed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParserOptions {
+    /// Whether scripts are enabled for the document. The content of `<noscript>` elements is only parsed into the DOM
+    /// when they are not, and discarded otherwise.
+    pub js_enabled: bool,
+}
+
 /// A streaming HTML parser that reads HTML content in chunks and builds the DOM tree incrementally.
 pub struct HtmlStreamParser<R: BufRead, C: Collector + Default> {
     /// The buffered reader from which HTML content is read.
@@ -24,8 +35,9 @@ pub struct HtmlStreamParser<R: BufRead, C: Collector + Default> {
     /// The internal byte buffer for handling incomplete UTF-8 sequences.
     byte_buffer: Vec<u8>,
 
-    /// The state of the HTML tokenizer.
-    tokenizer_state: TokenizerState,
+    /// The HTML tokenizer, fed one character at a time so that the parser can block right after the token that
+    /// caused it.
+    tokenizer: HtmlTokenizer,
 
     /// The DOM tree builder that constructs the DOM from tokens.
     builder: Option<DomTreeBuilder<C>>,
@@ -38,6 +50,24 @@ pub struct HtmlStreamParser<R: BufRead, C: Collector + Default> {
 
     /// The buffer used for reading bytes from the input stream.
     read_buffer: Vec<u8>,
+
+    /// The options the document is parsed with.
+    options: ParserOptions,
+
+    /// The raw text content the parser is waiting for more chunks of before it can continue.
+    pending: Option<PendingContent>,
+}
+
+/// Content that could not be read yet because its chunks have not all arrived.
+enum PendingContent {
+    /// The content of an element the parser blocks for, like an inline `<script>`.
+    Block {
+        cause: BlockingCause,
+        last_token: Option<Token>,
+    },
+
+    /// The content of a `<noscript>` element being discarded.
+    Noscript,
 }
 
 impl<R: BufRead, C: Collector + Default> HtmlStreamParser<R, C> {
@@ -49,11 +79,13 @@ impl<R: BufRead, C: Collector + Default> HtmlStreamParser<R, C> {
             reader,
             buffer: String::with_capacity(Self::DEFAULT_BUFFER_SIZE),
             byte_buffer: Vec::new(),
-            tokenizer_state: TokenizerState::default(),
+            tokenizer: HtmlTokenizer::new(),
             builder: Some(DomTreeBuilder::new(None)),
             state: ParserState::default(),
             previous_token_state: TokenState::Data,
             read_buffer: vec![0u8; Self::DEFAULT_BUFFER_SIZE],
+            options: ParserOptions::default(),
+            pending: None,
         }
     }
 
@@ -62,24 +94,47 @@ impl<R: BufRead, C: Collector + Default> HtmlStreamParser<R, C> {
         self
     }
 
+    #[must_use]
+    pub const fn with_options(mut self, options: ParserOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// The DOM tree built so far, or `None` once parsing has completed and the tree was handed over in
+    /// `ParserState::Completed`.
+    pub fn dom_mut(&mut self) -> Option<&mut DocumentRoot> {
+        self.builder.as_mut().map(DomTreeBuilder::dom_mut)
+    }
+
     /// Processes the next chunk of HTML content from the input stream.
     ///
     /// # Returns
     /// A `Result` containing the current parser state after processing the chunk, or an error message if an error occurs while reading from the stream.
     ///
     /// # Errors
-    /// * `HtmlParsingError::UnableToReadStream` - If an error occurs while reading from the input stream.
-    /// * `HtmlParsingError::UnexpectedUtf8Error` - If an unexpected UTF-8 decoding error occurs while processing the input stream.
-    pub fn step(&mut self) -> Result<ParserState<C>, HtmlParsingError> {
+    /// * `HtmlParseError::UnableToReadStream` - If an error occurs while reading from the input stream.
+    /// * `HtmlParseError::UnexpectedUtf8Error` - If an unexpected UTF-8 decoding error occurs while processing the input stream.
+    pub fn step(&mut self) -> Result<ParserState<C>, HtmlParseError> {
         match &self.state {
             ParserState::Blocked(_) | ParserState::Completed(_) => return Ok(std::mem::take(&mut self.state)),
             ParserState::Running => {}
         }
 
+        if let Some(pending) = self.pending.take() {
+            match pending {
+                PendingContent::Block { cause, last_token } => self.block(cause, last_token),
+                PendingContent::Noscript => self.resume_noscript(),
+            }
+
+            if self.pending.is_some() || !matches!(self.state, ParserState::Running) {
+                return Ok(std::mem::take(&mut self.state));
+            }
+        }
+
         if !self.buffer.is_empty() {
             let full_chunk = mem::take(&mut self.buffer);
             self.process_chunk(&full_chunk);
-            if !matches!(self.state, ParserState::Running) {
+            if self.pending.is_some() || !matches!(self.state, ParserState::Running) {
                 return Ok(std::mem::take(&mut self.state));
             }
         }
@@ -107,8 +162,9 @@ impl<R: BufRead, C: Collector + Default> HtmlStreamParser<R, C> {
                 }
 
                 if !matches!(self.state, ParserState::Blocked(_))
-                    && let Some(builder) = self.builder.take()
+                    && let Some(mut builder) = self.builder.take()
                 {
+                    builder.build_from_tokens(self.tokenizer.flush());
                     self.state = ParserState::Completed(builder.finalize());
                 }
                 Ok(std::mem::take(&mut self.state))
@@ -136,7 +192,8 @@ impl<R: BufRead, C: Collector + Default> HtmlStreamParser<R, C> {
 
                 Ok(std::mem::take(&mut self.state))
             }
-            Err(e) => Err(HtmlParsingError::UnableToReadStream(e.to_string())),
+            Err(e) if e.kind() == ErrorKind::WouldBlock => Ok(std::mem::take(&mut self.state)),
+            Err(e) => Err(HtmlParseError::UnableToReadStream(e.to_string())),
         }
     }
 
@@ -147,11 +204,12 @@ impl<R: BufRead, C: Collector + Default> HtmlStreamParser<R, C> {
     ///
     /// # Returns
     /// A `Result` containing the extracted content if successful, or an error message if the end tag is not found before the end of the stream or if reading from the stream fails.
+    /// `None` when the stream is waiting for more chunks, the content read so far is then kept in the buffer.
     ///
     /// # Errors
-    /// * `HtmlParsingError::MalformedDocument` - If the specified end tag is not found before the end of the stream.
-    /// * `HtmlParsingError::UnableToReadStream` - If an error occurs while reading from the stream.
-    fn extract_content_until_end_tag(&mut self, tag: &str) -> Result<String, HtmlParsingError> {
+    /// * `HtmlParseError::MalformedDocument` - If the specified end tag is not found before the end of the stream.
+    /// * `HtmlParseError::UnableToReadStream` - If an error occurs while reading from the stream.
+    fn extract_content_until_end_tag(&mut self, tag: &str) -> Option<Result<String, HtmlParseError>> {
         let mut content = String::new();
         let tag_lower = tag.to_ascii_lowercase();
         let tail_len = tag_lower.len().saturating_sub(1);
@@ -163,7 +221,7 @@ impl<R: BufRead, C: Collector + Default> HtmlStreamParser<R, C> {
                 if let Some(idx) = buffer_lower.find(&tag_lower) {
                     content.push_str(&self.buffer[..idx]);
                     self.buffer = self.buffer[idx + tag.len()..].to_string();
-                    return Ok(content);
+                    return Some(Ok(content));
                 }
 
                 if self.buffer.len() > tail_len {
@@ -179,23 +237,31 @@ impl<R: BufRead, C: Collector + Default> HtmlStreamParser<R, C> {
 
             match self.reader.read(&mut self.read_buffer) {
                 Ok(0) => {
-                    return Err(HtmlParsingError::MalformedDocument(format!(
+                    return Some(Err(HtmlParseError::MalformedDocument(format!(
                         "End tag '{tag}' not found before end of stream"
-                    )));
+                    ))));
                 }
                 Ok(bytes_read) => {
                     let mut combined_bytes = self.byte_buffer.clone();
                     combined_bytes.extend_from_slice(&self.read_buffer[..bytes_read]);
 
-                    let (chunk, remaining_bytes) = Self::try_decode_utf8(&combined_bytes)?;
+                    let (chunk, remaining_bytes) = match Self::try_decode_utf8(&combined_bytes) {
+                        Ok(decoded) => decoded,
+                        Err(error) => return Some(Err(error)),
+                    };
                     self.byte_buffer = remaining_bytes;
 
                     if !chunk.is_empty() {
                         self.buffer.push_str(&chunk);
                     }
                 }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                    content.push_str(&self.buffer);
+                    self.buffer = content;
+                    return None;
+                }
                 Err(e) => {
-                    return Err(HtmlParsingError::UnableToReadStream(e.to_string()));
+                    return Some(Err(HtmlParseError::UnableToReadStream(e.to_string())));
                 }
             }
         }
@@ -207,15 +273,27 @@ impl<R: BufRead, C: Collector + Default> HtmlStreamParser<R, C> {
     /// * `chunk` - A string slice containing the HTML content to process.
     fn process_chunk(&mut self, chunk: &str) {
         let mut tokens: Vec<Token> = Vec::new();
+        let mut encoded = [0; 4];
 
         for (idx, ch) in chunk.char_indices() {
-            HtmlTokenizer::process_char(&mut self.tokenizer_state, ch, &mut tokens);
-            let current_state = self.tokenizer_state.state;
+            tokens.extend(self.tokenizer.feed(ch.encode_utf8(&mut encoded)));
+            let current_state = self.tokenizer.state().state;
             if current_state == self.previous_token_state {
                 continue;
             }
             self.previous_token_state = current_state;
 
+            if self.options.js_enabled
+                && current_state == TokenState::Data
+                && tokens.last().is_some_and(|token| {
+                    token.kind == TokenKind::StartTag
+                        && HtmlTag::from_str_insensitive(&token.data) == Some(HtmlTag::Noscript)
+                })
+            {
+                self.skip_noscript_content(&chunk[idx + ch.len_utf8()..], mem::take(&mut tokens));
+                return;
+            }
+
             let Some(cause) = BlockingCause::classify_cause(current_state, tokens.last()) else {
                 continue;
             };
@@ -231,98 +309,164 @@ impl<R: BufRead, C: Collector + Default> HtmlStreamParser<R, C> {
                 self.buffer.push_str(&chunk[next_idx..]);
             }
 
-            let reason = match cause {
-                BlockingCause::Script => {
-                    trace!("Blocking parser for script content at token: {:?}", last_token);
-
-                    let attributes = last_token.and_then(|t| t.attributes);
-
-                    let src = attributes
-                        .as_ref()
-                        .and_then(|attrs| attrs.get("src").cloned());
-
-                    let script = match src {
-                        Some(src) => {
-                            let is_async = attributes
-                                .as_ref()
-                                .and_then(|attrs| attrs.get("async").cloned())
-                                .is_some_and(|value| {
-                                    value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("")
-                                });
-
-                            let is_deferred = attributes
-                                .as_ref()
-                                .and_then(|attrs| attrs.get("defer").cloned())
-                                .is_some_and(|value| {
-                                    value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("")
-                                });
-
-                            Script::External {
-                                src,
-                                is_async,
-                                is_deferred,
-                            }
-                        }
-                        None => {
-                            let data = self.extract_content_until_end_tag("</script>");
+            self.block(cause, last_token);
+            return;
+        }
 
-                            let type_attr = attributes
-                                .as_ref()
-                                .and_then(|attrs| attrs.get("type").cloned())
-                                .unwrap_or_else(|| "text/javascript".to_string());
+        self.builder.as_mut().unwrap().build_from_tokens(tokens);
+    }
+
+    /// Blocks the parser for the content or resource of the element that caused it to block.
+    ///
+    /// # Arguments
+    /// * `cause` - Why the parser blocks.
+    /// * `last_token` - The start tag of the element.
+    fn block(&mut self, cause: BlockingCause, last_token: Option<Token>) {
+        match self.blocked_reason(&cause, last_token.as_ref()) {
+            Some(reason) => self.state = ParserState::Blocked(reason),
+            None => self.pending = Some(PendingContent::Block { cause, last_token }),
+        }
+    }
 
-                            Script::Inline { data, type_attr }
+    /// Builds the reason the parser blocks for, reading the raw text content of the element when it has one.
+    ///
+    /// # Arguments
+    /// * `cause` - Why the parser blocks.
+    /// * `last_token` - The start tag of the element.
+    ///
+    /// # Returns
+    /// The reason, or `None` when the chunks of the content have not all arrived yet.
+    fn blocked_reason(&mut self, cause: &BlockingCause, last_token: Option<&Token>) -> Option<BlockedReason> {
+        let reason = match cause {
+            BlockingCause::Script => {
+                trace!("Blocking parser for script content at token: {:?}", last_token);
+
+                let attributes = last_token.and_then(|t| t.attributes.clone());
+
+                let src = attributes
+                    .as_ref()
+                    .and_then(|attrs| attrs.get("src").cloned());
+
+                let script = match src {
+                    Some(src) => {
+                        let is_async = attributes
+                            .as_ref()
+                            .and_then(|attrs| attrs.get("async").cloned())
+                            .is_some_and(|value| value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case(""));
+
+                        let is_deferred = attributes
+                            .as_ref()
+                            .and_then(|attrs| attrs.get("defer").cloned())
+                            .is_some_and(|value| value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case(""));
+
+                        Script::External {
+                            src,
+                            is_async,
+                            is_deferred,
                         }
-                    };
+                    }
+                    None => {
+                        let data = self.extract_content_until_end_tag("</script>")?;
 
-                    BlockedReason::WaitingForScript { script }
-                }
-                BlockingCause::Style => {
-                    trace!("Blocking parser for style content at token: {:?}", last_token);
+                        let type_attr = attributes
+                            .as_ref()
+                            .and_then(|attrs| attrs.get("type").cloned())
+                            .unwrap_or_else(|| "text/javascript".to_string());
 
-                    let data = self.extract_content_until_end_tag("</style>");
-                    let attributes = last_token.and_then(|t| t.attributes);
-                    BlockedReason::WaitingForStyle { data, attributes }
-                }
-                BlockingCause::Svg => {
-                    trace!("Blocking parser for SVG content at token: {:?}", last_token);
+                        Script::Inline { data, type_attr }
+                    }
+                };
 
-                    let data = self.extract_content_until_end_tag("</svg>");
-                    BlockedReason::SVGContent { data }
-                }
-                BlockingCause::Math => {
-                    trace!("Blocking parser for Math content at token: {:?}", last_token);
-                    let data = self.extract_content_until_end_tag("</math>");
-                    BlockedReason::MathML { data }
-                }
-                BlockingCause::Stylesheet { href } => {
-                    trace!("Blocking parser for stylesheet resource at token: {:?}", last_token);
+                BlockedReason::WaitingForScript { script }
+            }
+            BlockingCause::Style => {
+                trace!("Blocking parser for style content at token: {:?}", last_token);
 
-                    BlockedReason::WaitingForResource(ResourceType::Style, href, ResourceMetadata::default())
+                let data = self.extract_content_until_end_tag("</style>")?;
+                let attributes = last_token.and_then(|t| t.attributes.clone());
+                BlockedReason::WaitingForStyle { data, attributes }
+            }
+            BlockingCause::Svg => {
+                trace!("Blocking parser for SVG content at token: {:?}", last_token);
+
+                let data = self.extract_content_until_end_tag("</svg>")?;
+                if let Ok(markup) = &data {
+                    self.builder
+                        .as_mut()
+                        .unwrap()
+                        .insert_raw_text(markup.clone());
                 }
-                BlockingCause::Favicon {
-                    href,
-                    content_type,
-                    sizes,
-                } => {
-                    trace!("Blocking parser for favicon resource at token: {:?}", last_token);
-
-                    BlockedReason::WaitingForResource(
-                        ResourceType::Favicon,
-                        href,
-                        ResourceMetadata {
-                            content_type,
-                            sizes,
-                        },
-                    )
+
+                BlockedReason::SVGContent { data }
+            }
+            BlockingCause::Math => {
+                trace!("Blocking parser for Math content at token: {:?}", last_token);
+                let data = self.extract_content_until_end_tag("</math>")?;
+                BlockedReason::MathML { data }
+            }
+            BlockingCause::Stylesheet { href } => {
+                trace!("Blocking parser for stylesheet resource at token: {:?}", last_token);
+
+                BlockedReason::WaitingForResource(ResourceType::Style, href.clone(), ResourceMetadata::default())
+            }
+            BlockingCause::Favicon {
+                href,
+                content_type,
+                sizes,
+            } => {
+                trace!("Blocking parser for favicon resource at token: {:?}", last_token);
+
+                BlockedReason::WaitingForResource(
+                    ResourceType::Favicon,
+                    href.clone(),
+                    ResourceMetadata {
+                        content_type: content_type.clone(),
+                        sizes: *sizes,
+                    },
+                )
+            }
+            BlockingCause::ResourceHint {
+                kind,
+                href,
+                as_type,
+            } => {
+                trace!("Found resource hint {:?} at token: {:?}", kind, last_token);
+
+                BlockedReason::ResourceHint {
+                    kind: *kind,
+                    href: href.clone(),
+                    as_type: *as_type,
                 }
-            };
+            }
+        };
 
-            self.state = ParserState::Blocked(reason);
-            return;
-        }
+        Some(reason)
+    }
 
+    /// Discards the content of a `<noscript>` element, which is fallback content for when scripts are disabled, and
+    /// continues parsing after it. The end tag is kept so that the element is closed in the DOM.
+    ///
+    /// # Arguments
+    /// * `rest` - The rest of the chunk after the start tag of the element.
+    /// * `tokens` - The tokens of the chunk up to and including the start tag.
+    fn skip_noscript_content(&mut self, rest: &str, tokens: Vec<Token>) {
+        trace!("Skipping <noscript> content with scripts enabled");
         self.builder.as_mut().unwrap().build_from_tokens(tokens);
+        self.buffer.insert_str(0, rest);
+        self.resume_noscript();
+    }
+
+    /// Skips to the end tag of the `<noscript>` element being discarded, waiting for more chunks when it has not
+    /// arrived yet.
+    fn resume_noscript(&mut self) {
+        match self.extract_content_until_end_tag("</noscript>") {
+            Some(Ok(_)) => {
+                let remaining = format!("</noscript>{}", mem::take(&mut self.buffer));
+                self.process_chunk(&remaining);
+            }
+            Some(Err(error)) => trace!(%error, "Unclosed <noscript> element"),
+            None => self.pending = Some(PendingContent::Noscript),
+        }
     }
 
     /// Attempts to decode a byte slice as UTF-8, handling incomplete sequences and invalid bytes.
@@ -333,7 +477,7 @@ impl<R: BufRead, C: Collector + Default> HtmlStreamParser<R, C> {
     /// # Returns
     /// A `Result` containing a tuple of the decoded string and any remaining bytes that could not be decoded,
     /// or an error message if decoding fails.
-    fn try_decode_utf8(bytes: &[u8]) -> Result<(String, Vec<u8>), HtmlParsingError> {
+    fn try_decode_utf8(bytes: &[u8]) -> Result<(String, Vec<u8>), HtmlParseError> {
         match str::from_utf8(bytes) {
             Ok(text) => Ok((text.to_string(), Vec::new())),
             Err(error) => {
@@ -348,7 +492,7 @@ impl<R: BufRead, C: Collector + Default> HtmlStreamParser<R, C> {
 
                 let valid_text = match valid_text {
                     Ok(text) => text,
-                    Err(e) => return Err(HtmlParsingError::UnexpectedUtf8Error(e)),
+                    Err(e) => return Err(HtmlParseError::UnexpectedUtf8Error(e)),
                 };
 
                 let remaining_bytes = &bytes[valid_up_to..];
@@ -400,8 +544,204 @@ impl<R: BufRead, C: Collector + Default> HtmlStreamParser<R, C> {
     }
 }
 
+impl<C: Collector + Default> HtmlStreamParser<ChunkReader, C> {
+    /// Creates a parser of a document whose chunks have all arrived, such as the chunks of a response body. A
+    /// character, tag or character reference split across two chunks is parsed as one.
+    pub fn from_chunks(chunks: impl IntoIterator<Item = Vec<u8>>) -> Self {
+        Self::new(ChunkReader::new(chunks))
+    }
+
+    /// Creates a parser of a document whose chunks are pushed with `push_chunk` as they arrive, until `finish`.
+    #[must_use]
+    pub fn streaming() -> Self {
+        Self::new(ChunkReader::streaming())
+    }
+
+    /// Appends the next chunk of the document, parsed by the following calls to `step`.
+    pub fn push_chunk(&mut self, chunk: Vec<u8>) {
+        self.reader.push(chunk);
+    }
+
+    /// Ends the document, `step` completes the parse once the chunks pushed so far are parsed.
+    pub const fn finish(&mut self) {
+        self.reader.close();
+    }
+
+    /// Whether the parser has parsed every chunk pushed so far and needs the next one to make progress.
+    #[must_use]
+    pub fn awaiting_chunk(&self) -> bool {
+        !self.reader.is_closed()
+            && self.reader.is_drained()
+            && matches!(self.state, ParserState::Running)
+            && (self.buffer.is_empty() || self.pending.is_some())
+    }
+}
+
 impl<R: BufRead> HtmlStreamParser<R, DefaultCollector> {
     pub fn simple(reader: R) -> Self {
         Self::new(reader)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use html_dom::{NodeData, NodeId, Tag};
+
+    use super::*;
+
+    /// Parses a document pushing each chunk only once the parser is waiting for it, returning the DOM and the
+    /// reasons the parser blocked for.
+    fn parse_streaming(chunks: &[&[u8]]) -> (DocumentRoot, Vec<BlockedReason>) {
+        parse_streaming_with(ParserOptions::default(), chunks)
+    }
+
+    fn parse_streaming_with(options: ParserOptions, chunks: &[&[u8]]) -> (DocumentRoot, Vec<BlockedReason>) {
+        let mut parser = HtmlStreamParser::<ChunkReader, DefaultCollector>::streaming().with_options(options);
+        let mut chunks = chunks.iter();
+        let mut blocked = Vec::new();
+
+        loop {
+            if parser.awaiting_chunk() {
+                match chunks.next() {
+                    Some(chunk) => parser.push_chunk(chunk.to_vec()),
+                    None => parser.finish(),
+                }
+            }
+
+            match parser.step().unwrap() {
+                ParserState::Running => {}
+                ParserState::Blocked(reason) => blocked.push(reason),
+                ParserState::Completed(result) => return (result.dom_tree, blocked),
+            }
+        }
+    }
+
+    fn texts(dom: &DocumentRoot) -> Vec<&str> {
+        dom.nodes
+            .iter()
+            .filter_map(|node| match &node.data {
+                NodeData::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn element(dom: &DocumentRoot, tag: HtmlTag) -> Option<NodeId> {
+        dom.nodes
+            .iter()
+            .find(|node| {
+                node.data
+                    .as_element()
+                    .is_some_and(|element| element.tag == Tag::Html(tag))
+            })
+            .map(|node| node.id)
+    }
+
+    /// Whether the text node with the given content is inside a `<noscript>`.
+    fn in_noscript(dom: &DocumentRoot, content: &str) -> bool {
+        let node = dom
+            .nodes
+            .iter()
+            .find(|node| node.data.as_text().is_some_and(|text| text == content))
+            .unwrap();
+
+        dom.ancestors(node).iter().any(|ancestor| {
+            ancestor
+                .data
+                .as_element()
+                .is_some_and(|element| element.tag == Tag::Html(HtmlTag::Noscript))
+        })
+    }
+
+    const JS_ENABLED: ParserOptions = ParserOptions { js_enabled: true };
+
+    fn attribute<'a>(dom: &'a DocumentRoot, name: &str) -> Option<&'a str> {
+        dom.nodes
+            .iter()
+            .find_map(|node| node.data.as_element()?.get_attribute(name))
+    }
+
+    #[test]
+    fn test_utf8_split_across_chunks() {
+        let (dom, _) = parse_streaming(&[b"<p>caf\xC3", b"\xA9 \xF0\x9F", b"\x98", b"\x80</p>"]);
+        assert_eq!(texts(&dom), ["café 😀"]);
+    }
+
+    #[test]
+    fn test_character_reference_split_across_chunks() {
+        let (dom, _) = parse_streaming(&[b"<p>fish &am", b"p; chips &#x2", b"0AC;</p>"]);
+        assert_eq!(texts(&dom), ["fish & chips €"]);
+    }
+
+    #[test]
+    fn test_attributes_split_across_chunks() {
+        let (dom, _) = parse_streaming(&[b"<a hr", b"ef=\"/pa", b"th\" cla", b"ss=\"x\">link</a>"]);
+        assert_eq!(attribute(&dom, "href"), Some("/path"));
+        assert_eq!(attribute(&dom, "class"), Some("x"));
+        assert_eq!(texts(&dom), ["link"]);
+    }
+
+    #[test]
+    fn test_raw_text_split_across_chunks() {
+        let (dom, blocked) = parse_streaming(&[b"<style>p { col", b"or: red }</st", b"yle><p>after</p>"]);
+
+        assert!(matches!(
+            blocked.as_slice(),
+            [BlockedReason::WaitingForStyle { data: Ok(data), .. }] if data == "p { color: red }"
+        ));
+        assert_eq!(texts(&dom), ["after"]);
+    }
+
+    #[test]
+    fn test_noscript_content_is_discarded_with_scripts_enabled() {
+        let html: &[u8] = b"<p>before</p><noscript><p>fallback</p></noscript><p>after</p>";
+
+        let (dom, _) = parse_streaming_with(JS_ENABLED, &[html]);
+        assert_eq!(texts(&dom), ["before", "after"]);
+
+        let noscript = element(&dom, HtmlTag::Noscript).unwrap();
+        assert!(dom[noscript].children.is_empty());
+        assert!(!in_noscript(&dom, "after"));
+
+        let (dom, _) = parse_streaming(&[html]);
+        assert_eq!(texts(&dom), ["before", "fallback", "after"]);
+        assert!(in_noscript(&dom, "fallback"));
+    }
+
+    #[test]
+    fn test_noscript_end_tag_split_across_chunks() {
+        let (dom, _) = parse_streaming_with(
+            JS_ENABLED,
+            &[
+                b"<noscript><p>fall",
+                b"back</nos",
+                b"cript><p>af",
+                b"ter</p>",
+            ],
+        );
+
+        assert_eq!(texts(&dom), ["after"]);
+        assert!(!in_noscript(&dom, "after"));
+    }
+
+    #[test]
+    fn test_text_at_end_of_document() {
+        let (dom, _) = parse_streaming(&[b"<p>first</p>sec", b"ond"]);
+        assert_eq!(texts(&dom), ["first", "second"]);
+    }
+
+    #[test]
+    fn test_awaiting_chunk() {
+        let mut parser = HtmlStreamParser::<ChunkReader, DefaultCollector>::streaming();
+        assert!(parser.awaiting_chunk());
+
+        parser.push_chunk(b"<p>text".to_vec());
+        assert!(!parser.awaiting_chunk());
+        assert!(matches!(parser.step(), Ok(ParserState::Running)));
+        assert!(parser.awaiting_chunk());
+
+        parser.finish();
+        assert!(!parser.awaiting_chunk());
+        assert!(matches!(parser.step(), Ok(ParserState::Completed(_))));
+    }
+}
@@ -2,13 +2,73 @@ use std::collections::HashMap;
 
 use html_dom::{BuildResult, Token, TokenState};
 
-use crate::errors::HtmlParsingError;
+use crate::errors::HtmlParseError;
 
 /// Represents the type of resource that the parser is waiting for.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ResourceType {
     Style,
     Favicon,
+    Script,
+    Font,
+    Image,
+    Document,
+    Fetch,
+}
+
+impl ResourceType {
+    /// Maps the value of a `<link as="...">` attribute to a resource type.
+    pub fn from_as_attribute(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "style" => Some(Self::Style),
+            "script" => Some(Self::Script),
+            "font" => Some(Self::Font),
+            "image" => Some(Self::Image),
+            "document" => Some(Self::Document),
+            "fetch" => Some(Self::Fetch),
+            _ => None,
+        }
+    }
+}
+
+/// The kind of resource hint declared by a `<link rel="...">` tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceHintKind {
+    /// `rel="dns-prefetch"`, resolve the host name ahead of time.
+    DnsPrefetch,
+
+    /// `rel="preconnect"`, establish a connection to the origin ahead of time.
+    Preconnect,
+
+    /// `rel="preload"`, fetch a resource needed by the current page.
+    Preload,
+
+    /// `rel="modulepreload"`, fetch a module script needed by the current page.
+    ModulePreload,
+
+    /// `rel="prefetch"`, fetch a resource likely needed by a future navigation.
+    Prefetch,
+}
+
+impl ResourceHintKind {
+    /// Maps the value of a `<link rel="...">` attribute to a resource hint kind.
+    pub fn from_rel(rel: &str) -> Option<Self> {
+        rel.split_ascii_whitespace().find_map(|token| {
+            if token.eq_ignore_ascii_case("dns-prefetch") {
+                Some(Self::DnsPrefetch)
+            } else if token.eq_ignore_ascii_case("preconnect") {
+                Some(Self::Preconnect)
+            } else if token.eq_ignore_ascii_case("preload") {
+                Some(Self::Preload)
+            } else if token.eq_ignore_ascii_case("modulepreload") {
+                Some(Self::ModulePreload)
+            } else if token.eq_ignore_ascii_case("prefetch") {
+                Some(Self::Prefetch)
+            } else {
+                None
+            }
+        })
+    }
 }
 
 /// Metadata about a resource that the parser is waiting for.
@@ -32,6 +92,11 @@ pub(crate) enum BlockingCause {
         content_type: Option<String>,
         sizes: Option<(u32, u32)>,
     },
+    ResourceHint {
+        kind: ResourceHintKind,
+        href: String,
+        as_type: Option<ResourceType>,
+    },
 }
 
 impl BlockingCause {
@@ -60,6 +125,21 @@ impl BlockingCause {
                         content_type,
                         sizes,
                     })
+                } else if let Some(kind) = ResourceHintKind::from_rel(rel)
+                    && !href.is_empty()
+                {
+                    let as_type = match kind {
+                        ResourceHintKind::ModulePreload => Some(ResourceType::Script),
+                        _ => attr
+                            .get("as")
+                            .and_then(|a| ResourceType::from_as_attribute(a)),
+                    };
+
+                    Some(BlockingCause::ResourceHint {
+                        kind,
+                        href,
+                        as_type,
+                    })
                 } else {
                     None
                 }
@@ -73,7 +153,7 @@ impl BlockingCause {
 #[derive(Debug, Clone)]
 pub enum Script {
     Inline {
-        data: Result<String, HtmlParsingError>,
+        data: Result<String, HtmlParseError>,
         type_attr: String,
     },
 
@@ -94,21 +174,29 @@ pub enum BlockedReason {
     /// The parser is waiting for a style resource to load.
     /// The associated `HashMap` contains attributes of the style element.
     WaitingForStyle {
-        data: Result<String, HtmlParsingError>,
+        data: Result<String, HtmlParseError>,
         attributes: Option<HashMap<String, String>>,
     },
 
     /// The parser is waiting for a generic resource to load, from <link> tags.
     WaitingForResource(ResourceType, String, ResourceMetadata),
 
+    /// The parser found a resource hint (`dns-prefetch`, `preconnect`, `preload`, `modulepreload`
+    /// or `prefetch`) in a <link> tag. The hint does not need to complete before parsing resumes.
+    ResourceHint {
+        kind: ResourceHintKind,
+        href: String,
+        as_type: Option<ResourceType>,
+    },
+
     /// The parser is waiting for SVG parsing to complete.
     SVGContent {
-        data: Result<String, HtmlParsingError>,
+        data: Result<String, HtmlParseError>,
     },
 
     /// The parser is waiting for MathML parsing to complete.
     MathML {
-        data: Result<String, HtmlParsingError>,
+        data: Result<String, HtmlParseError>,
     },
 }
 
@@ -125,3 +213,32 @@ pub enum ParserState<C> {
     /// The parser has completed processing.
     Completed(BuildResult<C>),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_hint_from_rel() {
+        assert_eq!(ResourceHintKind::from_rel("preload"), Some(ResourceHintKind::Preload));
+        assert_eq!(ResourceHintKind::from_rel("DNS-Prefetch"), Some(ResourceHintKind::DnsPrefetch));
+        assert_eq!(ResourceHintKind::from_rel("modulepreload"), Some(ResourceHintKind::ModulePreload));
+        assert_eq!(ResourceHintKind::from_rel("  prefetch  "), Some(ResourceHintKind::Prefetch));
+        assert_eq!(ResourceHintKind::from_rel("stylesheet preconnect"), Some(ResourceHintKind::Preconnect));
+        assert_eq!(ResourceHintKind::from_rel("stylesheet"), None);
+        assert_eq!(ResourceHintKind::from_rel("preloading"), None);
+        assert_eq!(ResourceHintKind::from_rel(""), None);
+    }
+
+    #[test]
+    fn test_resource_type_from_as_attribute() {
+        assert_eq!(ResourceType::from_as_attribute("style"), Some(ResourceType::Style));
+        assert_eq!(ResourceType::from_as_attribute(" Script "), Some(ResourceType::Script));
+        assert_eq!(ResourceType::from_as_attribute("FONT"), Some(ResourceType::Font));
+        assert_eq!(ResourceType::from_as_attribute("image"), Some(ResourceType::Image));
+        assert_eq!(ResourceType::from_as_attribute("document"), Some(ResourceType::Document));
+        assert_eq!(ResourceType::from_as_attribute("fetch"), Some(ResourceType::Fetch));
+        assert_eq!(ResourceType::from_as_attribute("audio"), None);
+        assert_eq!(ResourceType::from_as_attribute(""), None);
+    }
+}
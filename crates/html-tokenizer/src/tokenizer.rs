@@ -42,9 +42,68 @@ pub struct TokenizerState {
 
 /// A tokenizer for HTML content that processes chunks of HTML and emits tokens.
 /// This tokenizer handles various HTML states, including text, tags, attributes, comments, and declarations.
-pub struct HtmlTokenizer;
+///
+/// Chunks are fed to it as they arrive with `feed`, a token split across chunks, like a tag whose attributes arrive in
+/// the next chunk, is emitted once it is complete. `flush` ends the input.
+#[derive(Debug, Default)]
+pub struct HtmlTokenizer {
+    /// The state of the tokenizer between chunks.
+    state: TokenizerState,
+}
 
 impl HtmlTokenizer {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The state of the tokenizer after the chunks fed so far.
+    #[must_use]
+    pub const fn state(&self) -> &TokenizerState {
+        &self.state
+    }
+
+    /// Tokenizes the next chunk of the input.
+    ///
+    /// # Arguments
+    /// * `chunk` - The HTML content following the previous chunk.
+    ///
+    /// # Returns
+    /// The tokens completed by the chunk. Text is only emitted once the tag following it starts, so that a character
+    /// reference split across chunks is decoded as one.
+    pub fn feed(&mut self, chunk: &str) -> Vec<Token> {
+        let mut tokens = Vec::new();
+
+        for ch in chunk.chars() {
+            Self::process_char(&mut self.state, ch, &mut tokens);
+        }
+
+        tokens
+    }
+
+    /// Ends the input, emitting the text at the end of it. A tag or comment left unterminated is discarded, and the
+    /// tokenizer is reset so that it can be fed another document.
+    ///
+    /// # Returns
+    /// The remaining tokens.
+    pub fn flush(&mut self) -> Vec<Token> {
+        let mut tokens = Vec::new();
+
+        let state = std::mem::take(&mut self.state);
+        if state.state == TokenState::Data && !state.temporary_buffer.is_empty() {
+            Self::emit_token(
+                &mut tokens,
+                Token {
+                    kind: TokenKind::Text,
+                    attributes: None,
+                    data: state.temporary_buffer,
+                },
+            );
+        }
+
+        tokens
+    }
+
     /// Processes a single character based on the current parser state and updates the tokenizer state accordingly.
     ///
     /// # Arguments
@@ -158,3 +217,98 @@ impl HtmlTokenizer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed_all(chunks: &[&str]) -> Vec<Token> {
+        let mut tokenizer = HtmlTokenizer::new();
+        let mut tokens: Vec<Token> = chunks
+            .iter()
+            .flat_map(|chunk| tokenizer.feed(chunk))
+            .collect();
+        tokens.extend(tokenizer.flush());
+        tokens
+    }
+
+    fn kinds_and_data(tokens: &[Token]) -> Vec<(TokenKind, &str)> {
+        tokens
+            .iter()
+            .map(|token| (token.kind.clone(), token.data.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_tag_split_across_chunks() {
+        let tokens = feed_all(&["<a hr", "ef=\"/pa", "th\" cla", "ss=x>link</", "a>"]);
+
+        assert_eq!(
+            kinds_and_data(&tokens),
+            [
+                (TokenKind::StartTag, "a"),
+                (TokenKind::Text, "link"),
+                (TokenKind::EndTag, "a")
+            ]
+        );
+
+        let attributes = tokens[0].attributes.as_ref().unwrap();
+        assert_eq!(attributes.get("href").map(String::as_str), Some("/path"));
+        assert_eq!(attributes.get("class").map(String::as_str), Some("x"));
+    }
+
+    #[test]
+    fn test_character_reference_split_across_chunks() {
+        let mut tokenizer = HtmlTokenizer::new();
+
+        assert_eq!(kinds_and_data(&tokenizer.feed("<p>fish &am")), [(TokenKind::StartTag, "p")]);
+        assert!(tokenizer.feed("p; chips &#x2").is_empty());
+        assert_eq!(
+            kinds_and_data(&tokenizer.feed("0AC;</p>")),
+            [
+                (TokenKind::Text, "fish &amp; chips &#x20AC;"),
+                (TokenKind::EndTag, "p")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multi_byte_characters_across_chunks() {
+        let tokens = feed_all(&["<p>caf", "é ", "😀</p>"]);
+        assert_eq!(
+            kinds_and_data(&tokens),
+            [
+                (TokenKind::StartTag, "p"),
+                (TokenKind::Text, "café 😀"),
+                (TokenKind::EndTag, "p")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flush_emits_trailing_text() {
+        let mut tokenizer = HtmlTokenizer::new();
+
+        assert_eq!(kinds_and_data(&tokenizer.feed("<p>trailing")), [(TokenKind::StartTag, "p")]);
+        assert_eq!(kinds_and_data(&tokenizer.flush()), [(TokenKind::Text, "trailing")]);
+        assert!(tokenizer.flush().is_empty());
+    }
+
+    #[test]
+    fn test_flush_discards_unterminated_tag_and_resets() {
+        let mut tokenizer = HtmlTokenizer::new();
+
+        assert_eq!(kinds_and_data(&tokenizer.feed("text<div cla")), [(TokenKind::Text, "text")]);
+        assert!(tokenizer.flush().is_empty());
+        assert_eq!(tokenizer.state().state, TokenState::Data);
+
+        assert_eq!(
+            kinds_and_data(&tokenizer.feed("<span>next</span>")),
+            [
+                (TokenKind::StartTag, "span"),
+                (TokenKind::Text, "next"),
+                (TokenKind::EndTag, "span")
+            ]
+        );
+    }
+}
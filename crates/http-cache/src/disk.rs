@@ -22,7 +22,7 @@ use crate::{
     errors::CacheError,
     header::{CacheControlResponse, CacheHeader},
     http::HttpCache,
-    index::{Index, IndexDatabase, IndexEntry, IndexTable},
+    index::{Index, IndexDatabase, IndexEntry, IndexTable, PartitionEntry, PartitionTable},
     large::LargeFile,
 };
 
@@ -261,6 +261,57 @@ impl DiskCache {
         Self::delete_with_connection(paths, key, &vary, &connection)
     }
 
+    /// Records the storage partition a cache entry was stored in and the origin of the cached resource, so that it is
+    /// removed when the data of either origin is cleared.
+    pub fn record_partition(&self, key: [u8; 32], partition: String, origin: String) -> Result<(), CacheError> {
+        let Ok(connection) = self.database.connection.lock() else {
+            return Err(CacheError::DatabaseLock);
+        };
+
+        PartitionTable::insert(
+            &connection,
+            &PartitionEntry {
+                key,
+                partition,
+                origin,
+            },
+        )
+        .map_err(CacheError::Database)
+    }
+
+    /// Removes every cache entry of an origin, whatever its vary headers, deleting both the index entries and the
+    /// associated data. These are the entries stored in the partition of the origin and the resources of the origin
+    /// stored in other partitions.
+    ///
+    /// # Returns
+    /// The number of removed entries.
+    pub fn delete_origin(&self, paths: &AppPaths, origin: &str) -> Result<usize, CacheError> {
+        let Ok(connection) = self.database.connection.lock() else {
+            return Err(CacheError::DatabaseLock);
+        };
+
+        let keys = PartitionTable::keys_by_origin(&connection, origin).map_err(CacheError::Database)?;
+
+        keys.iter()
+            .try_fold(0, |removed, key| Ok(removed + Self::delete_all_with_connection(paths, *key, &connection)?))
+    }
+
+    /// Removes the entries stored without a storage partition by a version that keyed entries by their URL alone,
+    /// which can no longer be looked up.
+    ///
+    /// # Returns
+    /// The number of removed entries.
+    pub fn purge_unpartitioned(&self, paths: &AppPaths) -> Result<usize, CacheError> {
+        let Ok(connection) = self.database.connection.lock() else {
+            return Err(CacheError::DatabaseLock);
+        };
+
+        let keys = IndexTable::keys_without_partition(&connection).map_err(CacheError::Database)?;
+
+        keys.iter()
+            .try_fold(0, |removed, key| Ok(removed + Self::delete_all_with_connection(paths, *key, &connection)?))
+    }
+
     /// Revalidates a cache entry in the index table, updating the `expires_at` and `fetched_at` fields.
     pub fn revalidate(&self, key: [u8; 32], headers: &HeaderMap) -> Result<(), CacheError> {
         let Ok(connection) = self.database.connection.lock() else {
@@ -316,6 +367,7 @@ impl DiskCache {
                 }
 
                 IndexTable::delete_by_key(connection, &key).map_err(CacheError::Database)?;
+                PartitionTable::delete_by_key(connection, &key).map_err(CacheError::Database)?;
 
                 return Ok(true);
             }
@@ -324,6 +376,36 @@ impl DiskCache {
         Ok(false)
     }
 
+    /// Removes every entry of a key whatever its vary headers, using the provided connection, deleting the index
+    /// entries, their data and the partition of the key.
+    ///
+    /// # Returns
+    /// The number of removed entries.
+    fn delete_all_with_connection(
+        paths: &AppPaths,
+        key: [u8; 32],
+        connection: &Connection,
+    ) -> Result<usize, CacheError> {
+        let entries = IndexTable::get_by_key(connection, &key).map_err(CacheError::Database)?;
+
+        for entry in &entries {
+            match entry.entry {
+                IndexEntry::Large => LargeFile::delete(paths, key)?,
+                IndexEntry::Block => {
+                    let offset = entry.offset.ok_or(CacheError::CorruptedIndex)?;
+                    let header_size = entry.header_size.ok_or(CacheError::CorruptedIndex)?;
+
+                    BlockFile::delete(paths, entry.file_id, offset, header_size)?;
+                }
+            }
+        }
+
+        IndexTable::delete_by_key(connection, &key).map_err(CacheError::Database)?;
+        PartitionTable::delete_by_key(connection, &key).map_err(CacheError::Database)?;
+
+        Ok(entries.len())
+    }
+
     /// Compacts block files on a per-file basis.
     ///
     /// For each `.bin` file in the blocks directory that is nearly full (>= 80% of
@@ -9,7 +9,7 @@ use postcard::to_stdvec;
 use sha2::{Digest, Sha256};
 use tracing::debug;
 
-use http_types::response::CompleteResponse;
+use http_types::{partition::StoragePartitionKey, response::CompleteResponse};
 use io::paths::AppPaths;
 
 use crate::{
@@ -47,13 +47,19 @@ impl HttpCache {
         }
     }
 
-    /// Gets the cache entry for a given key, if it exists.
+    /// Gets the cache entry for a given URL in a storage partition, if it exists.
     ///
     /// # Errors
     /// * If the cache lock is poisoned.
     /// * If there is an error reading from disk or deserializing the cached value.
-    pub fn get(&self, paths: &AppPaths, key: &str, request_headers: &HeaderMap) -> Result<CacheEntry, CacheError> {
-        let sha = Self::hash_url(key);
+    pub fn get(
+        &self,
+        paths: &AppPaths,
+        partition: &StoragePartitionKey,
+        url: &str,
+        request_headers: &HeaderMap,
+    ) -> Result<CacheEntry, CacheError> {
+        let sha = Self::hash_key(partition, url);
 
         let Some(entry) = self.inner.get(paths, sha, request_headers)? else {
             return Ok(CacheEntry::Miss);
@@ -92,7 +98,7 @@ impl HttpCache {
         Ok(CacheEntry::Hit(deserialized))
     }
 
-    /// Stores a successfully loaded value in the cache for a given key.
+    /// Stores a successfully loaded value in the cache for a given URL in a storage partition.
     ///
     /// # Errors
     /// * If there is already an entry for the key in the cache.
@@ -100,11 +106,12 @@ impl HttpCache {
     pub fn store(
         &self,
         paths: &AppPaths,
-        key: String,
+        partition: &StoragePartitionKey,
+        url: String,
         response: CompleteResponse,
         request_headers: &HeaderMap,
     ) -> Result<(), CacheError> {
-        if let Err(error) = self.store_on_disk(paths, &key, &response, request_headers) {
+        if let Err(error) = self.store_on_disk(paths, partition, &url, &response, request_headers) {
             debug!(%error, "failed to store on disk");
             return Err(error);
         }
@@ -121,11 +128,12 @@ impl HttpCache {
     fn store_on_disk(
         &self,
         paths: &AppPaths,
-        key: &str,
+        partition: &StoragePartitionKey,
+        url: &str,
         response: &CompleteResponse,
         request_headers: &HeaderMap,
     ) -> Result<(), CacheError> {
-        let sha = Self::hash_url(key);
+        let sha = Self::hash_key(partition, url);
 
         let serialized = to_stdvec(response).map_err(CacheError::Serialization)?;
 
@@ -152,29 +160,73 @@ impl HttpCache {
             request_headers,
             &cache_control,
             cache_headers,
-        )
+        )?;
+
+        let origin = url::Url::parse(url)
+            .map(|url| StoragePartitionKey::from_url(&url).to_string())
+            .unwrap_or_default();
+
+        self.inner
+            .record_partition(sha, partition.to_string(), origin)
     }
 
-    pub fn revalidate(&self, key: &str, request_headers: &HeaderMap) -> Result<(), CacheError> {
-        let sha = Self::hash_url(key);
+    pub fn revalidate(
+        &self,
+        partition: &StoragePartitionKey,
+        url: &str,
+        request_headers: &HeaderMap,
+    ) -> Result<(), CacheError> {
+        let sha = Self::hash_key(partition, url);
 
         self.inner.revalidate(sha, request_headers)
     }
 
-    /// Evicts a cache entry for a given key, removing it from both memory and disk.
+    /// Evicts a cache entry for a given URL in a storage partition, removing it from both memory and disk.
     ///
     /// # Errors
     /// * If there is an error removing the entry from disk.
-    pub fn evict(&mut self, paths: &AppPaths, key: &str, request_headers: &HeaderMap) -> Result<bool, CacheError> {
-        let sha = Self::hash_url(key);
+    pub fn evict(
+        &mut self,
+        paths: &AppPaths,
+        partition: &StoragePartitionKey,
+        url: &str,
+        request_headers: &HeaderMap,
+    ) -> Result<bool, CacheError> {
+        let sha = Self::hash_key(partition, url);
 
         let removed_disk = self.inner.delete(paths, sha, request_headers)?;
         Ok(removed_disk)
     }
 
-    /// Hashes a URL to produce a unique key for caching.
-    fn hash_url(url: &str) -> [u8; 32] {
+    /// Removes every cache entry of an origin: the resources loaded by pages of the origin, stored in its partition,
+    /// and the resources of the origin loaded by pages of other origins.
+    ///
+    /// # Returns
+    /// The number of removed entries.
+    ///
+    /// # Errors
+    /// * If there is an error removing the entries from disk.
+    pub fn clear_origin(&self, paths: &AppPaths, origin: &StoragePartitionKey) -> Result<usize, CacheError> {
+        self.inner.delete_origin(paths, &origin.to_string())
+    }
+
+    /// Removes the entries stored by a version that keyed entries by their URL alone, which can no longer be looked
+    /// up since entries are keyed by their storage partition as well.
+    ///
+    /// # Returns
+    /// The number of removed entries.
+    ///
+    /// # Errors
+    /// * If there is an error removing the entries from disk.
+    pub fn purge_unpartitioned(&self, paths: &AppPaths) -> Result<usize, CacheError> {
+        self.inner.purge_unpartitioned(paths)
+    }
+
+    /// Hashes a URL together with the storage partition it is loaded in to produce a unique key for caching.
+    fn hash_key(partition: &StoragePartitionKey, url: &str) -> [u8; 32] {
         let mut hasher = Sha256::new();
+        hasher.update(partition.to_string().as_bytes());
+        hasher.update(b" ");
         hasher.update(url.as_bytes());
         hasher.finalize().into()
     }
@@ -223,12 +275,19 @@ mod tests {
 
     use super::*;
 
+    fn partition(url: &str) -> StoragePartitionKey {
+        StoragePartitionKey::from_url(&url::Url::parse(url).unwrap())
+    }
+
     #[test]
-    fn test_hash_url() {
+    fn test_hash_key() {
         let url = "https://example.com/resource";
-        let hash1 = HttpCache::hash_url(url);
-        let hash2 = HttpCache::hash_url(url);
+        let hash1 = HttpCache::hash_key(&partition("https://example.com/"), url);
+        let hash2 = HttpCache::hash_key(&partition("https://example.com/"), url);
         assert_eq!(hash1, hash2);
+
+        let other = HttpCache::hash_key(&partition("https://example.org/"), url);
+        assert_ne!(hash1, other);
     }
 
     #[test]
@@ -272,16 +331,17 @@ mod tests {
         response_headers.insert(VARY, "Accept-Encoding".parse().unwrap());
         response_headers.insert("Accept-Encoding", "gzip".parse().unwrap());
         let key = "https://example.com/resource".to_string();
+        let partition = partition("https://example.com/");
         let response = CompleteResponse::new(StatusCode::OK, response_headers, "cached_data".into());
 
         let database = IndexDatabase::open(AppPaths::try_new().unwrap()).expect("Couldn't open database");
         let cache = HttpCache::new(database);
         let dirs = AppPaths::try_new().unwrap();
 
-        let result = cache.store(&dirs, key.clone(), response, &request_header);
+        let result = cache.store(&dirs, &partition, key.clone(), response, &request_header);
         assert!(result.is_ok());
 
-        let retrieved = cache.get(&dirs, &key, &request_header);
+        let retrieved = cache.get(&dirs, &partition, &key, &request_header);
 
         assert!(retrieved.is_ok());
         assert!(matches!(retrieved.unwrap(), CacheEntry::Hit(_)));
@@ -297,22 +357,111 @@ mod tests {
         response_headers.insert(VARY, "Accept-Encoding".parse().unwrap());
         response_headers.insert("Accept-Encoding", "gzip".parse().unwrap());
         let key = "https://example.com/resource".to_string();
+        let partition = partition("https://example.com/");
         let response = CompleteResponse::new(StatusCode::OK, response_headers, "cached_data".into());
 
         let database = IndexDatabase::open(AppPaths::try_new().unwrap()).expect("Couldn't open database");
         let cache = HttpCache::new(database);
         let dirs = AppPaths::try_new().unwrap();
 
-        let result = cache.store(&dirs, key.clone(), response, &request_header);
+        let result = cache.store(&dirs, &partition, key.clone(), response, &request_header);
         assert!(result.is_ok());
 
         // Attempt to retrieve with a different Accept-Encoding value
         let mut different_request_header = HeaderMap::new();
         different_request_header.insert("Accept-Encoding", "deflate".parse().unwrap());
 
-        let retrieved = cache.get(&dirs, &key, &different_request_header);
+        let retrieved = cache.get(&dirs, &partition, &key, &different_request_header);
 
         assert!(retrieved.is_ok());
         assert!(matches!(retrieved.unwrap(), CacheEntry::Miss));
     }
+
+    #[test]
+    #[serial]
+    fn test_partitions_are_isolated() {
+        let request_header = HeaderMap::new();
+        let key = "https://cdn.example.net/library.js".to_string();
+        let first = partition("https://example.com/");
+        let second = partition("https://example.org/");
+        let response = CompleteResponse::new(StatusCode::OK, HeaderMap::new(), "shared_data".into());
+
+        let database = IndexDatabase::open(AppPaths::try_new().unwrap()).expect("Couldn't open database");
+        let cache = HttpCache::new(database);
+        let dirs = AppPaths::try_new().unwrap();
+
+        cache
+            .store(&dirs, &first, key.clone(), response, &request_header)
+            .unwrap();
+
+        assert!(matches!(cache.get(&dirs, &first, &key, &request_header).unwrap(), CacheEntry::Hit(_)));
+        assert!(matches!(cache.get(&dirs, &second, &key, &request_header).unwrap(), CacheEntry::Miss));
+
+        assert_eq!(cache.clear_origin(&dirs, &first).unwrap(), 1);
+        assert!(matches!(cache.get(&dirs, &first, &key, &request_header).unwrap(), CacheEntry::Miss));
+    }
+
+    #[test]
+    #[serial]
+    fn test_clear_origin_removes_its_resources_in_other_partitions() {
+        let request_header = HeaderMap::new();
+        let key = "https://cdn.example.net/font.ttf".to_string();
+        let first = partition("https://example.com/");
+        let second = partition("https://example.org/");
+        let response = CompleteResponse::new(StatusCode::OK, HeaderMap::new(), "font_data".into());
+
+        let database = IndexDatabase::open(AppPaths::try_new().unwrap()).expect("Couldn't open database");
+        let cache = HttpCache::new(database);
+        let dirs = AppPaths::try_new().unwrap();
+
+        for partition in [&first, &second] {
+            cache
+                .store(&dirs, partition, key.clone(), response.clone(), &request_header)
+                .unwrap();
+        }
+
+        assert_eq!(
+            cache
+                .clear_origin(&dirs, &partition("https://cdn.example.net/"))
+                .unwrap(),
+            2
+        );
+        assert!(matches!(cache.get(&dirs, &first, &key, &request_header).unwrap(), CacheEntry::Miss));
+        assert!(matches!(cache.get(&dirs, &second, &key, &request_header).unwrap(), CacheEntry::Miss));
+    }
+
+    #[test]
+    #[serial]
+    fn test_purge_unpartitioned_entries() {
+        let request_header = HeaderMap::new();
+        let response = CompleteResponse::new(StatusCode::OK, HeaderMap::new(), "legacy_data".into());
+        let serialized = to_stdvec(&response).unwrap();
+        let legacy_key: [u8; 32] = Sha256::digest(b"https://example.com/legacy").into();
+
+        let database = IndexDatabase::open(AppPaths::try_new().unwrap()).expect("Couldn't open database");
+        let cache = HttpCache::new(database);
+        let dirs = AppPaths::try_new().unwrap();
+
+        cache
+            .inner
+            .put(
+                &dirs,
+                legacy_key,
+                &serialized,
+                &HeaderMap::new(),
+                &request_header,
+                &CacheControlResponse::from(""),
+                CacheHeader::new(&serialized, legacy_key),
+            )
+            .unwrap();
+
+        assert!(cache.purge_unpartitioned(&dirs).unwrap() >= 1);
+        assert!(
+            cache
+                .inner
+                .get(&dirs, legacy_key, &request_header)
+                .unwrap()
+                .is_none()
+        );
+    }
 }
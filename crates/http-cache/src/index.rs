@@ -183,6 +183,7 @@ impl Database for IndexDatabase {
         conn.execute_batch("PRAGMA journal_mode = WAL;")?;
 
         IndexTable::create_table(&conn)?;
+        PartitionTable::create_table(&conn)?;
 
         Ok(Self {
             connection: Arc::new(Mutex::new(conn)),
@@ -312,6 +313,26 @@ impl IndexTable {
         }
     }
 
+    /// Retrieves the keys of the entries that have no storage partition, which were stored by a version that keyed
+    /// entries by their URL alone and can no longer be looked up.
+    pub fn keys_without_partition(conn: &Connection) -> Result<Vec<[u8; 32]>> {
+        let mut stmt =
+            conn.prepare("SELECT DISTINCT key FROM cache_index WHERE key NOT IN (SELECT key FROM cache_partition)")?;
+        let mut rows = stmt.query([])?;
+
+        let mut keys = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let key: Vec<u8> = row.get(0)?;
+
+            if let Ok(key) = <[u8; 32]>::try_from(key.as_slice()) {
+                keys.push(key);
+            }
+        }
+
+        Ok(keys)
+    }
+
     /// Deletes an index entry by its key, used when an entry is found to be expired or corrupted.
     pub fn delete_by_key(conn: &Connection, key: &[u8; 32]) -> Result<()> {
         conn.execute("DELETE FROM cache_index WHERE key = ?1", params![key])?;
@@ -431,3 +452,89 @@ impl Table for IndexTable {
         Ok(())
     }
 }
+
+/// The storage partition a cache entry was stored in, used to find every entry of an origin when its data is cleared.
+#[derive(Debug)]
+pub struct PartitionEntry {
+    /// The key of the cached resource in the index.
+    pub key: [u8; 32],
+
+    /// The serialized `StoragePartitionKey` of the page that loaded the resource.
+    pub partition: String,
+
+    /// The serialized `StoragePartitionKey` of the origin of the resource itself.
+    pub origin: String,
+}
+
+/// Table interface mapping cache keys to the storage partition they were stored in.
+pub struct PartitionTable;
+
+impl PartitionTable {
+    /// Retrieves the keys of every entry of an origin, those stored in its partition and the resources of the origin
+    /// stored in the partitions of other origins.
+    pub fn keys_by_origin(conn: &Connection, origin: &str) -> Result<Vec<[u8; 32]>> {
+        let mut stmt = conn.prepare("SELECT key FROM cache_partition WHERE partition = ?1 OR origin = ?1")?;
+        let mut rows = stmt.query(params![origin])?;
+
+        let mut keys = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let key: Vec<u8> = row.get(0)?;
+
+            if let Ok(key) = <[u8; 32]>::try_from(key.as_slice()) {
+                keys.push(key);
+            }
+        }
+
+        Ok(keys)
+    }
+
+    /// Deletes the partition of a key, used once its entries are removed from the index.
+    pub fn delete_by_key(conn: &Connection, key: &[u8; 32]) -> Result<()> {
+        conn.execute("DELETE FROM cache_partition WHERE key = ?1", params![key])?;
+        Ok(())
+    }
+
+    /// Adds the columns introduced after the table was first created to a table created by an older version.
+    fn add_missing_columns(conn: &Connection) -> Result<()> {
+        let mut stmt = conn.prepare("PRAGMA table_info(cache_partition)")?;
+        let columns = stmt
+            .query_map([], |row| row.get::<usize, String>(1))?
+            .collect::<Result<Vec<_>>>()?;
+
+        if !columns.iter().any(|column| column == "origin") {
+            conn.execute("ALTER TABLE cache_partition ADD COLUMN origin TEXT NOT NULL DEFAULT ''", [])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Table for PartitionTable {
+    type Record = PartitionEntry;
+
+    fn create_table(conn: &Connection) -> Result<()> {
+        conn.execute_batch(
+            "BEGIN TRANSACTION;
+            CREATE TABLE IF NOT EXISTS cache_partition (
+                key BLOB PRIMARY KEY,
+                partition TEXT NOT NULL,
+                origin TEXT NOT NULL DEFAULT ''
+            );
+            CREATE INDEX IF NOT EXISTS partition_idx ON cache_partition (partition);
+            COMMIT;",
+        )?;
+
+        Self::add_missing_columns(conn)?;
+        conn.execute_batch("CREATE INDEX IF NOT EXISTS origin_idx ON cache_partition (origin);")
+    }
+
+    fn insert(conn: &Connection, data: &Self::Record) -> Result<()> {
+        conn.execute(
+            "INSERT OR REPLACE INTO cache_partition (key, partition, origin) VALUES (?1, ?2, ?3)",
+            params![&data.key, data.partition, data.origin],
+        )?;
+
+        Ok(())
+    }
+}
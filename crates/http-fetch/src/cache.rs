@@ -8,6 +8,7 @@ use http_cache::{
     http::{CacheEntry, HttpCache},
 };
 use http_types::{
+    partition::StoragePartitionKey,
     request::{Request, RequestContext},
     response::CompleteResponse,
 };
@@ -22,10 +23,11 @@ use crate::{
 
 pub(crate) fn cache_lookup(
     paths: &AppPaths,
+    partition: &StoragePartitionKey,
     request_context: &RequestContext,
     http_cache: &HttpCache,
 ) -> Result<CacheEntry, CacheError> {
-    http_cache.get(paths, request_context.url.as_str(), &request_context.headers)
+    http_cache.get(paths, partition, request_context.url.as_str(), &request_context.headers)
 }
 
 pub(crate) async fn make_revalidation_request(
@@ -33,6 +35,7 @@ pub(crate) async fn make_revalidation_request(
     client: &dyn HttpClient,
     paths: &AppPaths,
     http_cache: &HttpCache,
+    partition: StoragePartitionKey,
     stale_data: CompleteResponse,
     revalidation_headers: HeaderMap,
 ) -> Result<Box<dyn ResponseHandle>, NetworkError> {
@@ -49,7 +52,7 @@ pub(crate) async fn make_revalidation_request(
     let status = network_request.head().status_code;
 
     if status == StatusCode::NOT_MODIFIED {
-        match http_cache.revalidate(&url, &network_request.head().headers) {
+        match http_cache.revalidate(&partition, &url, &network_request.head().headers) {
             Ok(()) => {
                 return Ok(LocalHandle::new(stale_data).into());
             }
@@ -60,7 +63,14 @@ pub(crate) async fn make_revalidation_request(
 
         return Ok(network_request);
     } else if status == StatusCode::OK {
-        return Ok(CacheHandle::wrap_handle(paths.clone(), http_cache, url, network_request, request_headers));
+        return Ok(CacheHandle::wrap_handle(
+            paths.clone(),
+            http_cache,
+            partition,
+            url,
+            network_request,
+            request_headers,
+        ));
     }
 
     Ok(network_request)
@@ -1,25 +1,59 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 
-use http::Method;
+use http::{Method, Version};
 use http_types::{body::HttpBody, request::RequestContext, response::HeaderResponse};
 use reqwest::redirect::Policy;
 
-use crate::{client::HttpClient, errors::NetworkError, handle::ResponseHandle, handles::ReqwestHandle};
+use crate::{
+    client::HttpClient,
+    connections::ConnectionTracker,
+    dns::DohResolver,
+    errors::NetworkError,
+    handle::ResponseHandle,
+    handles::ReqwestHandle,
+    network_log::{NetworkEntry, NetworkLog},
+    robots::{CrawlRateLimiter, RobotsTxtCache},
+};
+
+/// The maximum number of idle connections kept open per host. With HTTP/2 a single connection multiplexes every
+/// request to a host, so this mostly limits the HTTP/1.1 connections kept alive.
+const MAX_IDLE_CONNECTIONS_PER_HOST: usize = 6;
+
+/// How long an idle connection is kept open for reuse.
+const IDLE_CONNECTION_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// The interval of the TCP keep-alive probes, which keep idle connections from being dropped by middleboxes.
+const TCP_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(60);
 
 /// An HTTP client implementation using the `reqwest` library.
-#[derive(Debug, Default)]
+///
+/// A single client is shared by the whole browser, every clone shares its connection pool, so the navigations and
+/// sub-resource loads of every tab reuse the same keep-alive connections.
+///
+/// Requests over HTTP/1.1 are never pipelined, as `hyper` does not support it, so a connection carries one request at
+/// a time and the pool opens a connection for each concurrent request to a host.
+#[derive(Debug)]
 pub struct ReqwestClient {
     /// The underlying reqwest client.
     client: reqwest::Client,
     /// The preflight client used for OPTIONS requests.
     preflight_client: reqwest::Client,
+    /// The log of the requests sent by this client and its clones.
+    network_log: NetworkLog,
+    /// The connections held open by the pool of the client and its clones.
+    connections: ConnectionTracker,
+    /// Spaces out the requests to each domain by its crawl delay, only set for automated browsing.
+    rate_limiter: Option<CrawlRateLimiter>,
 }
 
 impl ReqwestClient {
     /// Creates a new instance of `ReqwestClient` with default settings.
     ///
+    /// HTTP/2 is negotiated through ALPN for `https://` URLs, falling back to HTTP/1.1 for servers that do not support
+    /// it. Connections are pooled per host, so requests to the same host share a single multiplexed HTTP/2 connection.
+    ///
     /// # Returns
     /// A new `ReqwestClient` instance ready to send HTTP requests.
     ///
@@ -27,26 +61,80 @@ impl ReqwestClient {
     /// Panics if the reqwest client fails to build, which is unlikely under normal circumstances.
     #[must_use]
     pub fn new() -> Self {
-        Self {
-            client: reqwest::Client::builder()
-                .no_brotli()
-                .no_deflate()
-                .no_gzip()
-                .no_zstd()
-                .http2_max_header_list_size(u16::MAX as u32)
-                .build()
-                .unwrap(),
-            preflight_client: reqwest::Client::builder()
-                .redirect(Policy::none())
+        Self::build(None)
+    }
+
+    /// Creates a new instance of `ReqwestClient` resolving hostnames with the given DNS-over-HTTPS resolver instead of
+    /// the resolver of the operating system.
+    ///
+    /// # Panics
+    /// Panics if the reqwest client fails to build, which is unlikely under normal circumstances.
+    #[must_use]
+    pub fn with_dns_resolver(resolver: DohResolver) -> Self {
+        Self::build(Some(Arc::new(resolver)))
+    }
+
+    fn build(resolver: Option<Arc<DohResolver>>) -> Self {
+        let builder = || {
+            let builder = reqwest::Client::builder()
                 .no_brotli()
                 .no_deflate()
                 .no_gzip()
                 .no_zstd()
                 .http2_max_header_list_size(u16::MAX as u32)
-                .build()
-                .unwrap(),
+                .http2_adaptive_window(true)
+                .pool_max_idle_per_host(MAX_IDLE_CONNECTIONS_PER_HOST)
+                .pool_idle_timeout(IDLE_CONNECTION_TIMEOUT)
+                .tcp_keepalive(TCP_KEEPALIVE_INTERVAL)
+                // Only logs the traffic of each connection when tracing is enabled for `reqwest::connect::verbose`.
+                .connection_verbose(true);
+
+            match &resolver {
+                Some(resolver) => builder.dns_resolver(Arc::clone(resolver)),
+                None => builder,
+            }
+        };
+
+        Self {
+            client: builder().build().unwrap(),
+            preflight_client: builder().redirect(Policy::none()).build().unwrap(),
+            network_log: NetworkLog::new(),
+            connections: ConnectionTracker::new(MAX_IDLE_CONNECTIONS_PER_HOST, IDLE_CONNECTION_TIMEOUT),
+            rate_limiter: None,
         }
     }
+
+    /// Makes the client wait for the crawl delay of the `robots.txt` of a domain between its navigations to it, used
+    /// when the browser is automated instead of controlled by a user. The files are fetched through this client, but
+    /// are not rate limited themselves.
+    ///
+    /// # Arguments
+    /// * `user_agent` - The product token the groups of the `robots.txt` files are matched against.
+    #[must_use]
+    pub fn with_crawl_delays(mut self, user_agent: impl Into<String>) -> Self {
+        let robots = RobotsTxtCache::new(user_agent, self.box_clone());
+
+        self.rate_limiter = Some(CrawlRateLimiter::new(robots));
+        self
+    }
+
+    /// The log of the requests sent by this client, shared with its clones.
+    #[must_use]
+    pub fn network_log(&self) -> NetworkLog {
+        self.network_log.clone()
+    }
+
+    /// The tracker of the connections held open by this client, shared with its clones.
+    #[must_use]
+    pub fn connections(&self) -> ConnectionTracker {
+        self.connections.clone()
+    }
+}
+
+impl Default for ReqwestClient {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[async_trait]
@@ -56,6 +144,10 @@ impl HttpClient for ReqwestClient {
         context: Arc<RequestContext>,
         body: HttpBody,
     ) -> Result<Box<dyn ResponseHandle>, NetworkError> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.wait_for_domain_slot(&context).await;
+        }
+
         let mut request = if context.method == Method::OPTIONS {
             self.preflight_client
                 .request(context.method.clone(), context.url.clone())
@@ -87,6 +179,16 @@ impl HttpClient for ReqwestClient {
         };
 
         let status_code = response.status();
+        let connection = self
+            .connections
+            .acquire(response.url(), response.version() == Version::HTTP_2);
+
+        self.network_log.record(NetworkEntry {
+            method: context.method.clone(),
+            url: context.url.clone(),
+            status_code,
+            protocol: response.version().into(),
+        });
 
         let headers = response
             .headers()
@@ -99,13 +201,16 @@ impl HttpClient for ReqwestClient {
             headers,
         };
 
-        Ok(Box::new(ReqwestHandle::new(response, metadata)))
+        Ok(Box::new(ReqwestHandle::new(response, metadata, connection)))
     }
 
     fn box_clone(&self) -> Box<dyn HttpClient> {
         Box::new(Self {
             client: self.client.clone(),
             preflight_client: self.preflight_client.clone(),
+            network_log: self.network_log.clone(),
+            connections: self.connections.clone(),
+            rate_limiter: self.rate_limiter.clone(),
         })
     }
 }
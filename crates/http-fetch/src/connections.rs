@@ -0,0 +1,252 @@
+//! An estimate of the connections held open by the pool of an HTTP client. `reqwest` does not expose its pool, so
+//! the connections are counted from the responses received through the client, by the origin of the final URL after
+//! redirects. Every response being read over HTTP/1.1 holds a connection of its own, while responses over HTTP/2 share
+//! a single connection per origin. Once a response is read, its connection is kept idle until it is reused or the idle
+//! timeout of the pool expires. Requests that fail never count, as no connection is known to be kept for them.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use url::Url;
+
+/// The connections open in the pool of an HTTP client.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConnectionStats {
+    /// The connections carrying a request.
+    pub active: usize,
+
+    /// The connections kept alive for reuse.
+    pub idle: usize,
+}
+
+/// The connections to a single origin.
+#[derive(Debug, Clone, Copy)]
+struct OriginConnections {
+    /// The requests in flight.
+    requests: usize,
+    /// The idle connections.
+    idle: usize,
+    /// Whether the origin speaks HTTP/2, multiplexing its requests over a single connection.
+    multiplexed: bool,
+    /// When the last request to the origin completed.
+    last_used: Instant,
+}
+
+impl OriginConnections {
+    const fn active(&self) -> usize {
+        if self.multiplexed && self.requests > 0 {
+            1
+        } else {
+            self.requests
+        }
+    }
+}
+
+/// Tracks the connections of an HTTP client by origin, shared between all clones of the client.
+#[derive(Debug, Clone)]
+pub struct ConnectionTracker {
+    origins: Arc<Mutex<HashMap<String, OriginConnections>>>,
+    max_idle_per_origin: usize,
+    idle_timeout: Duration,
+}
+
+impl ConnectionTracker {
+    /// Creates a tracker for a pool with the given limits.
+    ///
+    /// # Arguments
+    /// * `max_idle_per_origin` - The maximum number of idle connections kept per origin.
+    /// * `idle_timeout` - How long an idle connection is kept before it is closed.
+    #[must_use]
+    pub fn new(max_idle_per_origin: usize, idle_timeout: Duration) -> Self {
+        Self {
+            origins: Arc::default(),
+            max_idle_per_origin,
+            idle_timeout,
+        }
+    }
+
+    /// Records a response being received, reusing an idle connection to its origin if there is one. Origins left
+    /// without connections are forgotten, so that only the origins with open connections are tracked.
+    ///
+    /// # Arguments
+    /// * `url` - The final URL of the response, after redirects.
+    /// * `multiplexed` - Whether the origin answered over HTTP/2, in which case its responses share a single
+    ///   connection.
+    ///
+    /// # Returns
+    /// A guard holding the connection until it is dropped, once the response body has been read.
+    ///
+    /// # Panics
+    /// * If the lock of the tracker is poisoned.
+    #[must_use]
+    pub fn acquire(&self, url: &Url, multiplexed: bool) -> ConnectionGuard {
+        let origin = url.origin().ascii_serialization();
+        let now = Instant::now();
+
+        let mut origins = self.origins.lock().unwrap();
+        origins.retain(|_, connections| {
+            connections.requests > 0
+                || (connections.idle > 0 && now.duration_since(connections.last_used) < self.idle_timeout)
+        });
+
+        let connections = origins.entry(origin.clone()).or_insert(OriginConnections {
+            requests: 0,
+            idle: 0,
+            multiplexed,
+            last_used: now,
+        });
+
+        if now.duration_since(connections.last_used) >= self.idle_timeout {
+            connections.idle = 0;
+        }
+
+        connections.multiplexed = multiplexed;
+        if connections.multiplexed {
+            connections.idle = 0;
+        } else {
+            connections.idle = connections.idle.saturating_sub(1);
+        }
+        connections.requests += 1;
+
+        ConnectionGuard {
+            tracker: self.clone(),
+            origin,
+        }
+    }
+
+    /// The connections currently open, leaving out the idle connections whose timeout has expired.
+    ///
+    /// # Panics
+    /// * If the lock of the tracker is poisoned.
+    #[must_use]
+    pub fn stats(&self) -> ConnectionStats {
+        let now = Instant::now();
+        let origins = self.origins.lock().unwrap();
+
+        origins
+            .values()
+            .fold(ConnectionStats::default(), |stats, connections| {
+                let idle = if now.duration_since(connections.last_used) < self.idle_timeout {
+                    connections.idle
+                } else {
+                    0
+                };
+
+                ConnectionStats {
+                    active: stats.active + connections.active(),
+                    idle: stats.idle + idle,
+                }
+            })
+    }
+
+    fn release(&self, origin: &str) {
+        let mut origins = self.origins.lock().unwrap();
+        let Some(connections) = origins.get_mut(origin) else {
+            return;
+        };
+
+        let now = Instant::now();
+        if now.duration_since(connections.last_used) >= self.idle_timeout {
+            connections.idle = 0;
+        }
+
+        connections.requests = connections.requests.saturating_sub(1);
+        connections.last_used = now;
+        connections.idle = if connections.multiplexed {
+            usize::from(connections.requests == 0)
+        } else {
+            (connections.idle + 1).min(self.max_idle_per_origin)
+        };
+    }
+}
+
+/// A connection held by a response being read, released back to the pool when dropped.
+#[derive(Debug)]
+pub struct ConnectionGuard {
+    tracker: ConnectionTracker,
+    origin: String,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.tracker.release(&self.origin);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_http1_requests_hold_a_connection_each() {
+        let tracker = ConnectionTracker::new(6, Duration::from_secs(90));
+
+        let a = tracker.acquire(&url("http://example.com/a"), false);
+        let b = tracker.acquire(&url("http://example.com/b"), false);
+        assert_eq!(tracker.stats(), ConnectionStats { active: 2, idle: 0 });
+
+        drop(a);
+        assert_eq!(tracker.stats(), ConnectionStats { active: 1, idle: 1 });
+
+        let c = tracker.acquire(&url("http://example.com/c"), false);
+        assert_eq!(tracker.stats(), ConnectionStats { active: 2, idle: 0 });
+
+        drop(b);
+        drop(c);
+        assert_eq!(tracker.stats(), ConnectionStats { active: 0, idle: 2 });
+    }
+
+    #[test]
+    fn test_http2_requests_share_a_connection() {
+        let tracker = ConnectionTracker::new(6, Duration::from_secs(90));
+
+        let guards: Vec<_> = (0..4)
+            .map(|i| tracker.acquire(&url(&format!("https://example.com/{i}")), true))
+            .collect();
+        assert_eq!(tracker.stats(), ConnectionStats { active: 1, idle: 0 });
+
+        drop(guards);
+        assert_eq!(tracker.stats(), ConnectionStats { active: 0, idle: 1 });
+    }
+
+    #[test]
+    fn test_idle_connections_are_capped_and_expire() {
+        let tracker = ConnectionTracker::new(1, Duration::ZERO);
+
+        let a = tracker.acquire(&url("http://example.com/a"), false);
+        let b = tracker.acquire(&url("http://example.com/b"), false);
+        drop(a);
+        drop(b);
+
+        assert_eq!(tracker.stats(), ConnectionStats { active: 0, idle: 0 });
+
+        let tracker = ConnectionTracker::new(1, Duration::from_secs(90));
+        let a = tracker.acquire(&url("http://example.com/a"), false);
+        let b = tracker.acquire(&url("http://example.com/b"), false);
+        drop(a);
+        drop(b);
+
+        assert_eq!(tracker.stats(), ConnectionStats { active: 0, idle: 1 });
+    }
+
+    #[test]
+    fn test_origins_without_connections_are_forgotten() {
+        let tracker = ConnectionTracker::new(6, Duration::ZERO);
+
+        for i in 0..100 {
+            drop(tracker.acquire(&url(&format!("http://host{i}.example.com/")), false));
+        }
+        let held = tracker.acquire(&url("http://example.com/"), false);
+
+        assert_eq!(tracker.origins.lock().unwrap().len(), 1);
+        assert_eq!(tracker.stats(), ConnectionStats { active: 1, idle: 0 });
+        drop(held);
+    }
+}
@@ -0,0 +1,200 @@
+//! Parsing of the `Content-Disposition` response header, which tells whether a response is shown in the browser or
+//! saved as a file, and the name the file is saved under.
+//!
+//! <https://www.rfc-editor.org/rfc/rfc6266>
+
+use http::{HeaderMap, header::CONTENT_DISPOSITION};
+
+/// The parsed value of a `Content-Disposition` header.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ContentDisposition {
+    /// Whether the response is to be downloaded instead of rendered.
+    pub attachment: bool,
+
+    /// The file name suggested by the server, from `filename*` when it can be decoded, otherwise from `filename`.
+    /// Any directory components are stripped, so it is safe to join onto a download directory.
+    pub filename: Option<String>,
+}
+
+impl ContentDisposition {
+    /// Parses the `Content-Disposition` header of a response.
+    ///
+    /// # Returns
+    /// The parsed header, or `None` if the response has no valid `Content-Disposition` header.
+    #[must_use]
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        headers
+            .get(CONTENT_DISPOSITION)
+            .and_then(|value| value.to_str().ok())
+            .map(Self::parse)
+    }
+
+    /// Parses the value of a `Content-Disposition` header. Unknown disposition types are treated as `attachment`, as
+    /// required by RFC 6266.
+    #[must_use]
+    pub fn parse(value: &str) -> Self {
+        let mut parts = split_parameters(value).into_iter();
+        let disposition = parts.next().unwrap_or_default();
+
+        let mut filename = None;
+        let mut extended_filename = None;
+
+        for parameter in parts {
+            let Some((name, value)) = parameter.split_once('=') else {
+                continue;
+            };
+
+            match name.trim().to_ascii_lowercase().as_str() {
+                "filename" => filename = Some(unquote(value.trim())),
+                "filename*" => extended_filename = decode_extended_value(value.trim()),
+                _ => {}
+            }
+        }
+
+        Self {
+            attachment: !disposition.trim().eq_ignore_ascii_case("inline"),
+            filename: extended_filename
+                .or(filename)
+                .and_then(|filename| sanitize_filename(&filename)),
+        }
+    }
+}
+
+/// Splits a header value at the semicolons that are not inside a quoted string.
+fn split_parameters(value: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut escaped = false;
+
+    for c in value.chars() {
+        match c {
+            _ if escaped => {
+                current.push(c);
+                escaped = false;
+            }
+            '\\' if quoted => {
+                current.push(c);
+                escaped = true;
+            }
+            '"' => {
+                current.push(c);
+                quoted = !quoted;
+            }
+            ';' if !quoted => parts.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+
+    parts.push(current);
+    parts
+}
+
+/// Removes the quotes and backslash escapes of a quoted string, returning other values as is.
+fn unquote(value: &str) -> String {
+    let Some(inner) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    else {
+        return value.to_string();
+    };
+
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            unquoted.extend(chars.next());
+        } else {
+            unquoted.push(c);
+        }
+    }
+
+    unquoted
+}
+
+/// Decodes an RFC 5987 extended value, `charset'language'percent-encoded-value`. Only the `UTF-8` and `ISO-8859-1`
+/// charsets are supported.
+///
+/// <https://www.rfc-editor.org/rfc/rfc5987#section-3.2>
+fn decode_extended_value(value: &str) -> Option<String> {
+    let mut parts = value.splitn(3, '\'');
+    let charset = parts.next()?;
+    let _language = parts.next()?;
+    let encoded = parts.next()?;
+
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut iter = encoded.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            bytes.push(u8::from_str_radix(str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+
+    if charset.eq_ignore_ascii_case("utf-8") {
+        String::from_utf8(bytes).ok()
+    } else if charset.eq_ignore_ascii_case("iso-8859-1") {
+        Some(bytes.into_iter().map(char::from).collect())
+    } else {
+        None
+    }
+}
+
+/// Keeps only the last path component of a suggested file name and drops control characters, so a server can not
+/// make a download escape the directory it is saved in.
+fn sanitize_filename(filename: &str) -> Option<String> {
+    let name: String = filename
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect();
+    let name = name.trim();
+
+    (!name.is_empty() && name != "." && name != "..").then(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attachment_with_quoted_filename() {
+        let disposition = ContentDisposition::parse(r#"attachment; filename="report; 2024.pdf""#);
+
+        assert!(disposition.attachment);
+        assert_eq!(disposition.filename.as_deref(), Some("report; 2024.pdf"));
+    }
+
+    #[test]
+    fn test_inline_is_not_an_attachment() {
+        let disposition = ContentDisposition::parse("INLINE");
+
+        assert!(!disposition.attachment);
+        assert_eq!(disposition.filename, None);
+    }
+
+    #[test]
+    fn test_extended_filename_takes_precedence() {
+        let disposition = ContentDisposition::parse(
+            "attachment; filename=\"EURO rates.txt\"; filename*=UTF-8''%e2%82%ac%20rates.txt",
+        );
+
+        assert_eq!(disposition.filename.as_deref(), Some("\u{20ac} rates.txt"));
+
+        let latin1 = ContentDisposition::parse("attachment; filename*=iso-8859-1'en'%A3%20rates.txt");
+        assert_eq!(latin1.filename.as_deref(), Some("\u{a3} rates.txt"));
+    }
+
+    #[test]
+    fn test_filename_directories_are_stripped() {
+        let disposition = ContentDisposition::parse(r#"attachment; filename="../../etc\\passwd""#);
+        assert_eq!(disposition.filename.as_deref(), Some("passwd"));
+
+        let disposition = ContentDisposition::parse(r#"attachment; filename="..""#);
+        assert_eq!(disposition.filename, None);
+    }
+}
@@ -0,0 +1,458 @@
+//! DNS-over-HTTPS resolution as described in RFC 8484, used in place of the resolver of the operating system so that
+//! hostname lookups are not visible to the local network.
+//!
+//! <https://www.rfc-editor.org/rfc/rfc8484>
+
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use http::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use tracing::{debug, trace};
+use url::Url;
+
+use crate::errors::DnsError;
+
+/// The DNS-over-HTTPS endpoint of Cloudflare.
+pub const CLOUDFLARE_ENDPOINT: &str = "https://1.1.1.1/dns-query";
+
+/// The DNS-over-HTTPS endpoint of Google.
+pub const GOOGLE_ENDPOINT: &str = "https://8.8.8.8/dns-query";
+
+/// The media type of a DNS message in wire format.
+const DNS_MESSAGE: &str = "application/dns-message";
+
+/// The longest time a lookup is cached, regardless of the TTL of its records.
+const MAX_CACHE_TTL: u32 = 24 * 60 * 60;
+
+const RECORD_TYPE_A: u16 = 1;
+const RECORD_TYPE_AAAA: u16 = 28;
+const CLASS_IN: u16 = 1;
+
+/// Sends a DNS query to a DNS-over-HTTPS endpoint and returns the response message.
+#[async_trait]
+pub trait DnsTransport: Send + Sync + Debug {
+    /// Exchanges a DNS message in wire format with the endpoint.
+    ///
+    /// # Arguments
+    /// * `endpoint` - The URL of the DNS-over-HTTPS endpoint.
+    /// * `query` - The DNS query message.
+    ///
+    /// # Returns
+    /// The DNS response message, or an error if the request failed.
+    async fn exchange(&self, endpoint: &Url, query: Vec<u8>) -> Result<Vec<u8>, DnsError>;
+}
+
+/// A transport that posts DNS queries to the endpoint using `reqwest`.
+///
+/// The endpoint itself is resolved by the operating system, so endpoints with an IP address as host, such as
+/// `https://1.1.1.1/dns-query`, avoid any lookup outside of DNS-over-HTTPS.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestDnsTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestDnsTransport {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl DnsTransport for ReqwestDnsTransport {
+    async fn exchange(&self, endpoint: &Url, query: Vec<u8>) -> Result<Vec<u8>, DnsError> {
+        let response = self
+            .client
+            .post(endpoint.clone())
+            .header(CONTENT_TYPE, DNS_MESSAGE)
+            .header(ACCEPT, DNS_MESSAGE)
+            .body(query)
+            .send()
+            .await
+            .map_err(|error| DnsError::Transport(error.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(DnsError::Transport(format!("status code {}", response.status().as_u16())));
+        }
+
+        let body = response
+            .bytes()
+            .await
+            .map_err(|error| DnsError::Transport(error.to_string()))?;
+
+        Ok(body.to_vec())
+    }
+}
+
+/// The addresses of a hostname and when they stop being valid.
+#[derive(Debug, Clone)]
+struct CachedLookup {
+    addresses: Vec<IpAddr>,
+    expires_at: Instant,
+}
+
+/// The addresses of a single record type in a DNS response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DnsAnswer {
+    addresses: Vec<IpAddr>,
+
+    /// The lowest TTL of the answer records, in seconds.
+    ttl: u32,
+}
+
+/// Resolves hostnames by sending DNS queries over HTTPS to a configurable endpoint, caching the addresses for the TTL
+/// of the answer.
+///
+/// The resolver implements the DNS resolver interface of `reqwest`, so it can be plugged into a `reqwest::Client`.
+#[derive(Debug, Clone)]
+pub struct DohResolver {
+    endpoint: Url,
+    transport: Arc<dyn DnsTransport>,
+    cache: Arc<Mutex<HashMap<String, CachedLookup>>>,
+}
+
+impl DohResolver {
+    /// Creates a resolver sending its queries to the given endpoint.
+    #[must_use]
+    pub fn new(endpoint: Url) -> Self {
+        Self::with_transport(endpoint, Arc::new(ReqwestDnsTransport::new()))
+    }
+
+    /// Creates a resolver sending its queries to the given endpoint through a custom transport.
+    #[must_use]
+    pub fn with_transport(endpoint: Url, transport: Arc<dyn DnsTransport>) -> Self {
+        Self {
+            endpoint,
+            transport,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    #[must_use]
+    pub const fn endpoint(&self) -> &Url {
+        &self.endpoint
+    }
+
+    /// Resolves the IPv4 and IPv6 addresses of a hostname, from the cache if a previous lookup is still valid.
+    ///
+    /// # Arguments
+    /// * `hostname` - The hostname to resolve.
+    ///
+    /// # Returns
+    /// The addresses of the hostname, or an error if no address could be resolved.
+    pub async fn lookup(&self, hostname: &str) -> Result<Vec<IpAddr>, DnsError> {
+        let hostname = hostname.trim_end_matches('.').to_ascii_lowercase();
+
+        if let Some(addresses) = self.cached(&hostname) {
+            trace!("Resolved {} from the DNS cache", hostname);
+            return Ok(addresses);
+        }
+
+        let (ipv4, ipv6) = tokio::join!(self.query(&hostname, RECORD_TYPE_A), self.query(&hostname, RECORD_TYPE_AAAA));
+
+        let mut addresses = Vec::new();
+        let mut ttl = MAX_CACHE_TTL;
+        let mut error = None;
+
+        for answer in [ipv4, ipv6] {
+            match answer {
+                Ok(answer) if !answer.addresses.is_empty() => {
+                    addresses.extend(answer.addresses);
+                    ttl = ttl.min(answer.ttl);
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    debug!(%err, "DNS-over-HTTPS query for {} failed", hostname);
+                    error = Some(err);
+                }
+            }
+        }
+
+        if addresses.is_empty() {
+            return Err(error.unwrap_or(DnsError::NoAddresses(hostname)));
+        }
+
+        if ttl > 0
+            && let Ok(mut cache) = self.cache.lock()
+        {
+            cache.insert(
+                hostname,
+                CachedLookup {
+                    addresses: addresses.clone(),
+                    expires_at: Instant::now() + Duration::from_secs(u64::from(ttl)),
+                },
+            );
+        }
+
+        Ok(addresses)
+    }
+
+    /// Returns the cached addresses of a hostname, evicting them if their TTL has expired.
+    fn cached(&self, hostname: &str) -> Option<Vec<IpAddr>> {
+        let mut cache = self.cache.lock().ok()?;
+        let lookup = cache.get(hostname)?;
+
+        if lookup.expires_at <= Instant::now() {
+            cache.remove(hostname);
+            return None;
+        }
+
+        Some(lookup.addresses.clone())
+    }
+
+    async fn query(&self, hostname: &str, record_type: u16) -> Result<DnsAnswer, DnsError> {
+        let query = encode_query(hostname, record_type)?;
+        let response = self.transport.exchange(&self.endpoint, query).await?;
+
+        decode_response(&response, record_type)
+    }
+}
+
+impl Resolve for DohResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let resolver = self.clone();
+
+        Box::pin(async move {
+            let addresses = resolver.lookup(name.as_str()).await?;
+            let addrs: Addrs = Box::new(
+                addresses
+                    .into_iter()
+                    .map(|address| SocketAddr::new(address, 0)),
+            );
+
+            Ok(addrs)
+        })
+    }
+}
+
+/// Encodes a recursive query for a single record type of a hostname.
+///
+/// The message ID is always 0, as recommended for DNS-over-HTTPS to improve cache friendliness.
+fn encode_query(hostname: &str, record_type: u16) -> Result<Vec<u8>, DnsError> {
+    if hostname.is_empty() || hostname.len() > 253 {
+        return Err(DnsError::InvalidHostname(hostname.to_string()));
+    }
+
+    let mut message = Vec::with_capacity(18 + hostname.len());
+
+    // ID, flags (recursion desired), QDCOUNT, ANCOUNT, NSCOUNT, ARCOUNT
+    message.extend_from_slice(&[0, 0, 0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0]);
+
+    for label in hostname.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(DnsError::InvalidHostname(hostname.to_string()));
+        }
+
+        message.push(label.len() as u8);
+        message.extend_from_slice(label.as_bytes());
+    }
+
+    message.push(0);
+    message.extend_from_slice(&record_type.to_be_bytes());
+    message.extend_from_slice(&CLASS_IN.to_be_bytes());
+
+    Ok(message)
+}
+
+/// Decodes the addresses of a record type from a DNS response, ignoring records of other types such as `CNAME`.
+fn decode_response(message: &[u8], record_type: u16) -> Result<DnsAnswer, DnsError> {
+    let header = message
+        .get(..12)
+        .ok_or(DnsError::Malformed("truncated header"))?;
+
+    let flags = read_u16(header, 2);
+    if flags & 0x8000 == 0 {
+        return Err(DnsError::Malformed("message is not a response"));
+    }
+
+    let response_code = (flags & 0x000F) as u8;
+    if response_code != 0 {
+        return Err(DnsError::ResponseCode(response_code));
+    }
+
+    let question_count = read_u16(header, 4);
+    let answer_count = read_u16(header, 6);
+
+    let mut offset = 12;
+    for _ in 0..question_count {
+        offset = skip_name(message, offset)? + 4;
+    }
+
+    let mut addresses = Vec::new();
+    let mut ttl = u32::MAX;
+
+    for _ in 0..answer_count {
+        offset = skip_name(message, offset)?;
+
+        let record = message
+            .get(offset..offset + 10)
+            .ok_or(DnsError::Malformed("truncated record"))?;
+        let answer_type = read_u16(record, 0);
+        let answer_ttl = u32::from_be_bytes([record[4], record[5], record[6], record[7]]);
+        let data_length = read_u16(record, 8) as usize;
+        offset += 10;
+
+        let data = message
+            .get(offset..offset + data_length)
+            .ok_or(DnsError::Malformed("truncated record data"))?;
+        offset += data_length;
+
+        if answer_type != record_type {
+            continue;
+        }
+
+        let address = match (answer_type, data.len()) {
+            (RECORD_TYPE_A, 4) => IpAddr::V4(Ipv4Addr::new(data[0], data[1], data[2], data[3])),
+            (RECORD_TYPE_AAAA, 16) => {
+                let mut octets = [0; 16];
+                octets.copy_from_slice(data);
+                IpAddr::V6(Ipv6Addr::from(octets))
+            }
+            _ => return Err(DnsError::Malformed("invalid address length")),
+        };
+
+        addresses.push(address);
+        ttl = ttl.min(answer_ttl);
+    }
+
+    Ok(DnsAnswer {
+        ttl: if addresses.is_empty() { 0 } else { ttl },
+        addresses,
+    })
+}
+
+/// Returns the offset following a possibly compressed domain name.
+fn skip_name(message: &[u8], mut offset: usize) -> Result<usize, DnsError> {
+    loop {
+        let length = *message
+            .get(offset)
+            .ok_or(DnsError::Malformed("truncated name"))?;
+
+        match length {
+            0 => return Ok(offset + 1),
+            pointer if pointer & 0xC0 == 0xC0 => {
+                if offset + 1 >= message.len() {
+                    return Err(DnsError::Malformed("truncated name pointer"));
+                }
+
+                return Ok(offset + 2);
+            }
+            label => offset += 1 + label as usize,
+        }
+    }
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_be_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// A DoH server answering every `A` query with a fixed address and TTL, and every other query with no records.
+    #[derive(Debug)]
+    struct MockDohServer {
+        address: Ipv4Addr,
+        ttl: u32,
+        requests: AtomicUsize,
+    }
+
+    impl MockDohServer {
+        fn new(address: Ipv4Addr, ttl: u32) -> Arc<Self> {
+            Arc::new(Self {
+                address,
+                ttl,
+                requests: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl DnsTransport for MockDohServer {
+        async fn exchange(&self, _endpoint: &Url, query: Vec<u8>) -> Result<Vec<u8>, DnsError> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+
+            let record_type = read_u16(&query, query.len() - 4);
+            let mut response = query;
+            response[2] = 0x81;
+            response[3] = 0x80;
+
+            if record_type == RECORD_TYPE_A {
+                response[7] = 1;
+                response.extend_from_slice(&[0xC0, 0x0C]);
+                response.extend_from_slice(&RECORD_TYPE_A.to_be_bytes());
+                response.extend_from_slice(&CLASS_IN.to_be_bytes());
+                response.extend_from_slice(&self.ttl.to_be_bytes());
+                response.extend_from_slice(&4u16.to_be_bytes());
+                response.extend_from_slice(&self.address.octets());
+            }
+
+            Ok(response)
+        }
+    }
+
+    fn resolver(server: &Arc<MockDohServer>) -> DohResolver {
+        let transport: Arc<dyn DnsTransport> = server.clone();
+        DohResolver::with_transport(Url::parse(CLOUDFLARE_ENDPOINT).unwrap(), transport)
+    }
+
+    #[tokio::test]
+    async fn test_lookup() {
+        let server = MockDohServer::new(Ipv4Addr::new(93, 184, 216, 34), 300);
+        let resolver = resolver(&server);
+
+        let addresses = resolver.lookup("example.com").await.unwrap();
+
+        assert_eq!(addresses, vec![IpAddr::V4(Ipv4Addr::new(93, 184, 216, 34))]);
+        assert_eq!(server.requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_lookup_within_ttl_is_cached() {
+        let server = MockDohServer::new(Ipv4Addr::new(10, 0, 0, 1), 300);
+        let resolver = resolver(&server);
+
+        let first = resolver.lookup("example.com").await.unwrap();
+        let requests = server.requests.load(Ordering::SeqCst);
+        let second = resolver.lookup("EXAMPLE.com.").await.unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(server.requests.load(Ordering::SeqCst), requests);
+    }
+
+    #[tokio::test]
+    async fn test_zero_ttl_is_not_cached() {
+        let server = MockDohServer::new(Ipv4Addr::new(10, 0, 0, 1), 0);
+        let resolver = resolver(&server);
+
+        resolver.lookup("example.com").await.unwrap();
+        resolver.lookup("example.com").await.unwrap();
+
+        assert_eq!(server.requests.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_error_response_code() {
+        let mut response = encode_query("missing.example", RECORD_TYPE_A).unwrap();
+        response[2] = 0x81;
+        response[3] = 0x83;
+
+        assert!(matches!(decode_response(&response, RECORD_TYPE_A), Err(DnsError::ResponseCode(3))));
+    }
+
+    #[test]
+    fn test_invalid_hostname() {
+        assert!(encode_query("example..com", RECORD_TYPE_A).is_err());
+        assert!(encode_query(&"a".repeat(64), RECORD_TYPE_A).is_err());
+    }
+}
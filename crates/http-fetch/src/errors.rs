@@ -6,6 +6,7 @@ use http_types::errors::RequestError;
 
 /// Errors related to network operations, preventing successful completion of a network request.
 #[derive(Error, Debug, Clone)]
+#[non_exhaustive]
 pub enum NetworkError {
     #[error("Connection timed out")]
     Timeout,
@@ -50,3 +51,22 @@ pub enum FetchError {
     #[error("Preflight request failed")]
     PreflightFailed,
 }
+
+/// Errors that can occur while resolving a hostname over DNS-over-HTTPS.
+#[derive(Error, Debug, Clone)]
+pub enum DnsError {
+    #[error("DNS-over-HTTPS request failed: {0}")]
+    Transport(String),
+
+    #[error("Malformed DNS message: {0}")]
+    Malformed(&'static str),
+
+    #[error("Invalid hostname: {0}")]
+    InvalidHostname(String),
+
+    #[error("DNS server responded with error code {0}")]
+    ResponseCode(u8),
+
+    #[error("No addresses found for {0}")]
+    NoAddresses(String),
+}
@@ -1,6 +1,9 @@
 use async_trait::async_trait;
 
-use http_types::response::{HeaderResponse, Response};
+use http_types::{
+    properties::Protocol,
+    response::{HeaderResponse, Response},
+};
 
 use crate::errors::NetworkError;
 
@@ -10,6 +13,13 @@ pub trait ResponseHandle: Send + Sync {
     /// Returns the head of the response, without consuming the body.
     fn head(&self) -> &HeaderResponse;
 
+    /// Returns the HTTP version the response was received over.
+    ///
+    /// Responses not received from the network, such as local files, report HTTP/1.1.
+    fn protocol(&self) -> Protocol {
+        Protocol::default()
+    }
+
     /// Consumes and returns the full response, buffering if necessary.
     async fn response(self: Box<Self>) -> Result<Response, NetworkError>;
 }
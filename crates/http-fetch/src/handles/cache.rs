@@ -6,6 +6,8 @@ use tracing::debug;
 use http_cache::{block::MAX_BLOCK_SIZE, http::HttpCache};
 use http_types::{
     body::{CompleteHttpBody, HttpBody, TeeStream},
+    partition::StoragePartitionKey,
+    properties::Protocol,
     response::{CompleteResponse, HeaderResponse, Response},
 };
 use io::paths::AppPaths;
@@ -17,6 +19,7 @@ pub struct CacheHandle {
     paths: AppPaths,
     inner: Box<dyn ResponseHandle>,
     cache: HttpCache,
+    partition: StoragePartitionKey,
     cache_key: String,
     request_headers: HeaderMap,
 }
@@ -26,6 +29,7 @@ impl CacheHandle {
         paths: AppPaths,
         inner: Box<dyn ResponseHandle>,
         cache: HttpCache,
+        partition: StoragePartitionKey,
         cache_key: String,
         request_headers: HeaderMap,
     ) -> Self {
@@ -33,6 +37,7 @@ impl CacheHandle {
             paths,
             inner,
             cache,
+            partition,
             cache_key,
             request_headers,
         }
@@ -41,11 +46,12 @@ impl CacheHandle {
     pub fn wrap_handle(
         paths: AppPaths,
         http_cache: &HttpCache,
+        partition: StoragePartitionKey,
         cache_key: String,
         handle: Box<dyn ResponseHandle>,
         request_headers: HeaderMap,
     ) -> Box<dyn ResponseHandle> {
-        Box::new(CacheHandle::new(paths, handle, http_cache.clone(), cache_key, request_headers))
+        Box::new(CacheHandle::new(paths, handle, http_cache.clone(), partition, cache_key, request_headers))
     }
 }
 
@@ -55,11 +61,16 @@ impl ResponseHandle for CacheHandle {
         self.inner.head()
     }
 
+    fn protocol(&self) -> Protocol {
+        self.inner.protocol()
+    }
+
     async fn response(self: Box<Self>) -> Result<Response, NetworkError> {
         let CacheHandle {
             paths,
             inner,
             cache,
+            partition,
             cache_key,
             request_headers,
         } = *self;
@@ -73,7 +84,7 @@ impl ResponseHandle for CacheHandle {
         match response.body {
             HttpBody::Empty | HttpBody::Buffered(_) => {
                 if let Some(complete) = response.to_cacheable(MAX_BLOCK_SIZE as usize)
-                    && let Err(err) = cache.store(&paths, cache_key, complete, &request_headers)
+                    && let Err(err) = cache.store(&paths, &partition, cache_key, complete, &request_headers)
                 {
                     debug!(%err);
                 }
@@ -98,7 +109,7 @@ impl ResponseHandle for CacheHandle {
                             body: CompleteHttpBody(bytes),
                         };
 
-                        if let Err(err) = cache.store(&paths, cache_key, cached, &request_headers) {
+                        if let Err(err) = cache.store(&paths, &partition, cache_key, cached, &request_headers) {
                             debug!(%err);
                         }
                     }
@@ -2,6 +2,7 @@ use async_trait::async_trait;
 
 use http_types::{
     body::HttpBody,
+    properties::Protocol,
     response::{HeaderResponse, Response},
 };
 
@@ -27,6 +28,10 @@ impl ResponseHandle for DecodeHandle {
         self.inner.head()
     }
 
+    fn protocol(&self) -> Protocol {
+        self.inner.protocol()
+    }
+
     async fn response(self: Box<Self>) -> Result<Response, NetworkError> {
         let mut response = self.inner.response().await?;
         let encoding_order = get_encoding_order(&response.head.headers)?;
@@ -1,20 +1,31 @@
 use async_trait::async_trait;
 use http_types::{
     body::HttpBody,
+    properties::Protocol,
     response::{HeaderResponse, Response},
 };
 
-use crate::{errors::NetworkError, handle::ResponseHandle};
+use crate::{connections::ConnectionGuard, errors::NetworkError, handle::ResponseHandle};
 
 #[derive(Debug)]
 pub struct ReqwestHandle {
     inner: reqwest::Response,
     head: HeaderResponse,
+    protocol: Protocol,
+    /// Holds the connection of the response until its body has been read.
+    _connection: ConnectionGuard,
 }
 
 impl ReqwestHandle {
-    pub fn new(inner: reqwest::Response, head: HeaderResponse) -> Self {
-        Self { inner, head }
+    pub fn new(inner: reqwest::Response, head: HeaderResponse, connection: ConnectionGuard) -> Self {
+        let protocol = Protocol::from(inner.version());
+
+        Self {
+            inner,
+            head,
+            protocol,
+            _connection: connection,
+        }
     }
 }
 
@@ -24,6 +35,10 @@ impl ResponseHandle for ReqwestHandle {
         &self.head
     }
 
+    fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+
     async fn response(self: Box<Self>) -> Result<Response, NetworkError> {
         let status_code = self.head.status_code;
         let headers = self.head.headers;
@@ -0,0 +1,226 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug},
+    sync::Mutex,
+};
+
+use tokio::sync::oneshot::{self, Sender};
+use tracing::trace;
+use url::Url;
+
+/// Deduplicates concurrent loads of the same resource.
+///
+/// The first caller to load a URL performs the request, while any caller asking for the same URL before it completes
+/// is added to a waiting list instead of starting another request. Once the request completes, its result is broadcast
+/// to every waiting caller.
+pub struct InFlightRequests<T> {
+    in_flight: Mutex<HashMap<Url, Vec<Sender<T>>>>,
+}
+
+impl<T: Clone> InFlightRequests<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Loads a resource, sharing the result of a load already in flight for the same URL.
+    ///
+    /// # Arguments
+    /// * `url` - The URL of the resource, used to identify loads of the same resource.
+    /// * `request` - The future performing the load, which is only awaited if no load of the URL is in flight.
+    ///
+    /// # Returns
+    /// The result of the load, either from `request` or from the load already in flight.
+    ///
+    /// # Panics
+    /// * If the lock of the in-flight map is poisoned.
+    pub async fn load<F>(&self, url: &Url, request: F) -> T
+    where
+        F: Future<Output = T>,
+    {
+        let receiver = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            match in_flight.get_mut(url) {
+                Some(waiting) => {
+                    let (sender, receiver) = oneshot::channel();
+                    waiting.push(sender);
+                    Some(receiver)
+                }
+                None => {
+                    in_flight.insert(url.clone(), Vec::new());
+                    None
+                }
+            }
+        };
+
+        if let Some(receiver) = receiver {
+            trace!("Waiting for in-flight request to {}", url);
+
+            return match receiver.await {
+                Ok(result) => result,
+                Err(_) => {
+                    trace!("In-flight request to {} was cancelled, requesting it again", url);
+                    request.await
+                }
+            };
+        }
+
+        let guard = InFlightGuard {
+            in_flight: &self.in_flight,
+            url,
+            finished: false,
+        };
+
+        let result = request.await;
+
+        for sender in guard.finish() {
+            let _ = sender.send(result.clone());
+        }
+
+        result
+    }
+
+    /// The number of URLs currently being loaded.
+    ///
+    /// # Panics
+    /// * If the lock of the in-flight map is poisoned.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.in_flight.lock().unwrap().len()
+    }
+
+    /// Whether no URL is currently being loaded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: Clone> Default for InFlightRequests<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for InFlightRequests<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let in_flight = self
+            .in_flight
+            .lock()
+            .map(|in_flight| in_flight.len())
+            .unwrap_or_default();
+        f.debug_struct("InFlightRequests")
+            .field("in_flight", &in_flight)
+            .finish()
+    }
+}
+
+/// Removes the entry of the loading caller from the in-flight map, even if its load is cancelled. Dropping the waiting
+/// senders of a cancelled load wakes the waiting callers, which then load the resource themselves.
+struct InFlightGuard<'a, T> {
+    in_flight: &'a Mutex<HashMap<Url, Vec<Sender<T>>>>,
+    url: &'a Url,
+    finished: bool,
+}
+
+impl<T> InFlightGuard<'_, T> {
+    fn finish(mut self) -> Vec<Sender<T>> {
+        self.finished = true;
+        self.remove()
+    }
+
+    fn remove(&self) -> Vec<Sender<T>> {
+        self.in_flight
+            .lock()
+            .map(|mut in_flight| in_flight.remove(self.url).unwrap_or_default())
+            .unwrap_or_default()
+    }
+}
+
+impl<T> Drop for InFlightGuard<'_, T> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.remove();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use super::*;
+
+    async fn request(counter: &AtomicUsize, body: &str) -> Result<String, String> {
+        counter.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        Ok(body.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_are_deduplicated() {
+        let requests = InFlightRequests::new();
+        let counter = AtomicUsize::new(0);
+        let url = Url::parse("https://example.com/style.css").unwrap();
+
+        let (a, b, c) = tokio::join!(
+            requests.load(&url, request(&counter, "body")),
+            requests.load(&url, request(&counter, "body")),
+            requests.load(&url, request(&counter, "body")),
+        );
+
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+        assert_eq!(a, Ok("body".to_string()));
+        assert_eq!(b, a);
+        assert_eq!(c, a);
+        assert!(requests.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_different_urls_are_not_deduplicated() {
+        let requests = InFlightRequests::new();
+        let counter = AtomicUsize::new(0);
+        let first = Url::parse("https://example.com/a.png").unwrap();
+        let second = Url::parse("https://example.com/b.png").unwrap();
+
+        let (a, b) = tokio::join!(
+            requests.load(&first, request(&counter, "a")),
+            requests.load(&second, request(&counter, "b")),
+        );
+
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+        assert_eq!(a, Ok("a".to_string()));
+        assert_eq!(b, Ok("b".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_request_is_retried_by_waiters() {
+        let requests = InFlightRequests::new();
+        let counter = AtomicUsize::new(0);
+        let url = Url::parse("https://example.com/image.png").unwrap();
+
+        let cancelled = tokio::time::timeout(
+            Duration::from_millis(1),
+            requests.load(&url, async {
+                counter.fetch_add(1, Ordering::SeqCst);
+                std::future::pending::<Result<String, String>>().await
+            }),
+        );
+        let waiting = async {
+            tokio::task::yield_now().await;
+            requests.load(&url, request(&counter, "retried")).await
+        };
+
+        let (cancelled, waiting) = tokio::join!(cancelled, waiting);
+
+        assert!(cancelled.is_err());
+        assert_eq!(waiting, Ok("retried".to_string()));
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+        assert!(requests.is_empty());
+    }
+}
@@ -1,8 +1,14 @@
 pub mod cache;
 pub mod client;
 pub mod clients;
+pub mod connections;
+pub mod disposition;
+pub mod dns;
 pub mod errors;
 pub mod handle;
 pub mod handles;
+pub mod inflight;
 pub(crate) mod middleware;
+pub mod network_log;
 pub mod request;
+pub mod robots;
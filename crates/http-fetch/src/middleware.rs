@@ -2,6 +2,6 @@ mod cookies;
 mod decoding;
 mod headers;
 
-pub use cookies::{apply_cookies, handle_response_cookie};
+pub use cookies::{apply_cookies, handle_response_cookie, request_site};
 pub use decoding::{decode, decode_stream, get_encoding_order};
 pub use headers::add_forbidden_headers;
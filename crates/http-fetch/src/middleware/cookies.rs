@@ -2,8 +2,13 @@ use http::{HeaderValue, header::COOKIE};
 use tracing::{debug, trace, trace_span, warn};
 use url::Url;
 
-use cookies::{Cookie, CookieJar};
-use http_types::request::Request;
+use cookies::{Cookie, CookieJar, RequestSite};
+use http_types::{
+    properties::{Destination, RequestMode},
+    request::{Request, RequestContext},
+};
+
+use super::headers::is_same_site;
 
 const REQUEST_COOKIE: &str = "request_cookie";
 const RESPONSE_COOKIE: &str = "response_cookie";
@@ -49,6 +54,24 @@ pub fn apply_cookies(request: &mut Request, cookies: &[Cookie]) {
     }
 }
 
+/// How a request relates to the page that made it, which decides the `SameSite` cookies it is sent with.
+///
+/// # Arguments
+/// * `current_url` - The URL of the page making the request, `None` when it was not made from a page.
+/// * `context` - The request.
+#[must_use]
+pub fn request_site(current_url: Option<&Url>, context: &RequestContext) -> RequestSite {
+    if current_url.is_none_or(|current_url| is_same_site(current_url, &context.url)) {
+        return RequestSite::SameSite;
+    }
+
+    RequestSite::CrossSite {
+        top_level_navigation: context.request_mode == RequestMode::Navigate
+            && matches!(context.destination, Destination::Document)
+            && context.method.is_safe(),
+    }
+}
+
 pub fn handle_response_cookie(cookie_jar: &CookieJar, request_url: &Url, header_value: &HeaderValue) {
     let Some(host) = request_url.host() else {
         debug!("Request URL does not have a valid domain host");
@@ -74,3 +97,66 @@ pub fn handle_response_cookie(cookie_jar: &CookieJar, request_url: &Url, header_
 
     cookie_jar.add_cookie(cookie, host.to_owned());
 }
+
+#[cfg(test)]
+mod tests {
+    use http::Method;
+
+    use super::*;
+
+    fn site_of(current_url: Option<&str>, request: Request) -> RequestSite {
+        let current_url = current_url.map(|url| Url::parse(url).unwrap());
+        request_site(current_url.as_ref(), &request.context)
+    }
+
+    #[test]
+    fn test_request_site_without_a_page() {
+        let request = Request::builder("https://example.com/").build();
+
+        assert_eq!(site_of(None, request), RequestSite::SameSite);
+    }
+
+    #[test]
+    fn test_request_site_of_same_site_requests() {
+        let subresource = Request::builder("https://static.example.co.uk/logo.png")
+            .destination(Destination::Image)
+            .request_mode(RequestMode::NoCors)
+            .build();
+        let post = Request::builder("https://example.co.uk/login")
+            .method(Method::POST)
+            .build();
+
+        assert_eq!(site_of(Some("https://www.example.co.uk/"), subresource), RequestSite::SameSite);
+        assert_eq!(site_of(Some("https://www.example.co.uk/"), post), RequestSite::SameSite);
+    }
+
+    #[test]
+    fn test_request_site_of_cross_site_requests() {
+        let page = Some("https://example.co.uk/");
+        let cross_site = |top_level_navigation| RequestSite::CrossSite {
+            top_level_navigation,
+        };
+
+        let navigation = Request::builder("https://other.co.uk/").build();
+        assert_eq!(site_of(page, navigation), cross_site(true));
+
+        let post_navigation = Request::builder("https://other.co.uk/login")
+            .method(Method::POST)
+            .build();
+        assert_eq!(site_of(page, post_navigation), cross_site(false));
+
+        let frame = Request::builder("https://other.co.uk/embed")
+            .destination(Destination::Iframe)
+            .build();
+        assert_eq!(site_of(page, frame), cross_site(false));
+
+        let subresource = Request::builder("https://other.co.uk/logo.png")
+            .destination(Destination::Image)
+            .request_mode(RequestMode::NoCors)
+            .build();
+        assert_eq!(site_of(page, subresource), cross_site(false));
+
+        let insecure = Request::builder("http://example.co.uk/").build();
+        assert_eq!(site_of(page, insecure), cross_site(true));
+    }
+}
@@ -1,8 +1,8 @@
 use http::{HeaderMap, HeaderName, HeaderValue};
 use rand::RngExt;
-use url::Url;
+use url::{Host, Url};
 
-use http_types::request::Request;
+use http_types::{properties::RequestMode, request::Request};
 use manifest::{APP_MAJOR_VERSION, APP_NAME};
 
 pub fn add_forbidden_headers(request: &mut Request, current_url: Option<&Url>) {
@@ -45,39 +45,66 @@ pub fn add_forbidden_headers(request: &mut Request, current_url: Option<&Url>) {
     //headers.insert(HeaderName::from_bytes(b"sec-ch-ua-wow64").unwrap(), HeaderValue::from_str("?0").unwrap());
 }
 
+/// Adds the `Sec-Fetch-*` metadata headers, which tell the server what the response is used for and where the request
+/// came from.
+///
+/// <https://w3c.github.io/webappsec-fetch-metadata/>
 fn add_forbidden_fetch_headers(request: &mut Request, current_url: Option<&Url>) {
-    request.context.headers.insert(
+    let context = &mut request.context;
+
+    context.headers.insert(
         HeaderName::from_bytes(b"sec-fetch-dest").unwrap(),
-        HeaderValue::from_str(request.context.destination.as_ref()).unwrap(),
+        HeaderValue::from_str(context.destination.as_ref()).unwrap(),
     );
-    request.context.headers.insert(
+    context.headers.insert(
         HeaderName::from_bytes(b"sec-fetch-mode").unwrap(),
-        HeaderValue::from_str(request.context.request_mode.as_ref()).unwrap(),
+        HeaderValue::from_str(context.request_mode.as_ref()).unwrap(),
+    );
+    context.headers.insert(
+        HeaderName::from_bytes(b"sec-fetch-site").unwrap(),
+        HeaderValue::from_static(fetch_site(current_url, &context.url)),
     );
 
-    // TODO: Have a more detailed way to check if the user activated the search and probably wait until it is stable.
-    // if is_user {
-    //    headers.insert(HeaderName::from_bytes(b"sec-fetch-user").unwrap(), HeaderValue::from_str("?1").unwrap());
-    // }
+    if context.user_activation && context.request_mode == RequestMode::Navigate {
+        context
+            .headers
+            .insert(HeaderName::from_bytes(b"sec-fetch-user").unwrap(), HeaderValue::from_static("?1"));
+    }
+}
 
-    if let Some(url) = current_url {
-        let fetch_site = if url.origin() == request.context.url.origin() {
-            "same-origin"
-        } else if url.domain() == request.context.url.domain() {
-            "same-site"
-        } else {
-            "cross-site"
-        };
+/// The value of `Sec-Fetch-Site` for a request, `none` when it was not made from a page, like a URL typed into the
+/// address bar.
+pub(super) fn fetch_site(current_url: Option<&Url>, request_url: &Url) -> &'static str {
+    let Some(current_url) = current_url else {
+        return "none";
+    };
 
-        request
-            .context
-            .headers
-            .insert(HeaderName::from_bytes(b"sec-fetch-site").unwrap(), HeaderValue::from_str(fetch_site).unwrap());
+    if current_url.origin() == request_url.origin() {
+        "same-origin"
+    } else if is_same_site(current_url, request_url) {
+        "same-site"
     } else {
-        request
-            .context
-            .headers
-            .insert(HeaderName::from_bytes(b"sec-fetch-site").unwrap(), HeaderValue::from_str("none").unwrap());
+        "cross-site"
+    }
+}
+
+/// Whether two URLs have the same scheme and the same site.
+///
+/// <https://html.spec.whatwg.org/multipage/browsers.html#same-site>
+pub(super) fn is_same_site(a: &Url, b: &Url) -> bool {
+    a.scheme() == b.scheme() && site(a).is_some_and(|site_a| site(b).is_some_and(|site_b| site_a == site_b))
+}
+
+/// The site of a URL: the registrable domain of its host by the public suffix list, the host itself when it has none,
+/// like a public suffix or `localhost`, or the address of an IP host.
+fn site(url: &Url) -> Option<String> {
+    match url.host()? {
+        Host::Domain(domain) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+
+            Some(psl::domain_str(&domain).map_or_else(|| domain.clone(), str::to_string))
+        }
+        host => Some(host.to_string()),
     }
 }
 
@@ -109,3 +136,80 @@ fn generate_not_a_brand() -> String {
         _ => "Not-A Brand".to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use http_types::properties::Destination;
+
+    use super::*;
+
+    fn headers(current_url: Option<&str>, mut request: Request) -> HeaderMap {
+        let current_url = current_url.map(|url| Url::parse(url).unwrap());
+        add_forbidden_fetch_headers(&mut request, current_url.as_ref());
+        request.context.headers
+    }
+
+    #[test]
+    fn test_fetch_site() {
+        let url = Url::parse("https://www.example.com/page").unwrap();
+        let same_site = Url::parse("https://static.example.com/style.css").unwrap();
+        let insecure = Url::parse("http://static.example.com/style.css").unwrap();
+        let cross_site = Url::parse("https://cdn.example.org/style.css").unwrap();
+
+        assert_eq!(fetch_site(None, &url), "none");
+        assert_eq!(fetch_site(Some(&url), &url), "same-origin");
+        assert_eq!(fetch_site(Some(&url), &same_site), "same-site");
+        assert_eq!(fetch_site(Some(&url), &insecure), "cross-site");
+        assert_eq!(fetch_site(Some(&url), &cross_site), "cross-site");
+    }
+
+    #[test]
+    fn test_fetch_site_uses_public_suffixes() {
+        let url = |url: &str| Url::parse(url).unwrap();
+
+        assert_eq!(
+            fetch_site(Some(&url("https://www.example.co.uk/")), &url("https://static.example.co.uk/")),
+            "same-site"
+        );
+        assert_eq!(fetch_site(Some(&url("https://example.co.uk/")), &url("https://other.co.uk/")), "cross-site");
+        assert_eq!(fetch_site(Some(&url("https://alice.github.io/")), &url("https://bob.github.io/")), "cross-site");
+        assert_eq!(fetch_site(Some(&url("http://127.0.0.1:8080/")), &url("http://127.0.0.1:9090/")), "same-site");
+    }
+
+    #[test]
+    fn test_user_navigation_headers() {
+        let request = Request::builder("https://example.com/")
+            .user_activation(true)
+            .build();
+        let headers = headers(None, request);
+
+        assert_eq!(headers["sec-fetch-dest"], "document");
+        assert_eq!(headers["sec-fetch-mode"], "navigate");
+        assert_eq!(headers["sec-fetch-site"], "none");
+        assert_eq!(headers["sec-fetch-user"], "?1");
+    }
+
+    #[test]
+    fn test_navigation_without_user_activation_headers() {
+        let request = Request::builder("https://example.com/").build();
+        let headers = headers(Some("https://example.com/"), request);
+
+        assert_eq!(headers["sec-fetch-mode"], "navigate");
+        assert!(!headers.contains_key("sec-fetch-user"));
+    }
+
+    #[test]
+    fn test_subresource_headers() {
+        let request = Request::builder("https://example.com/logo.png")
+            .destination(Destination::Image)
+            .request_mode(RequestMode::NoCors)
+            .user_activation(true)
+            .build();
+        let headers = headers(Some("https://example.com/"), request);
+
+        assert_eq!(headers["sec-fetch-dest"], "image");
+        assert_eq!(headers["sec-fetch-mode"], "no-cors");
+        assert_eq!(headers["sec-fetch-site"], "same-origin");
+        assert!(!headers.contains_key("sec-fetch-user"));
+    }
+}
@@ -0,0 +1,123 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use http::{Method, StatusCode};
+use http_types::properties::Protocol;
+use url::Url;
+
+/// A request sent over the network, as shown in the network panel of the devtools.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkEntry {
+    pub method: Method,
+    pub url: Url,
+    pub status_code: StatusCode,
+    pub protocol: Protocol,
+}
+
+/// A log of the most recent requests sent by an HTTP client, shared between all clones of the client.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkLog {
+    entries: Arc<Mutex<VecDeque<NetworkEntry>>>,
+}
+
+impl NetworkLog {
+    /// The maximum number of entries kept, older entries are dropped first.
+    pub const MAX_ENTRIES: usize = 500;
+
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a completed request, dropping the oldest entry if the log is full.
+    ///
+    /// # Panics
+    /// * If the lock of the log is poisoned.
+    pub fn record(&self, entry: NetworkEntry) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= Self::MAX_ENTRIES {
+            entries.pop_front();
+        }
+
+        entries.push_back(entry);
+    }
+
+    /// The recorded requests, oldest first.
+    ///
+    /// # Panics
+    /// * If the lock of the log is poisoned.
+    #[must_use]
+    pub fn entries(&self) -> Vec<NetworkEntry> {
+        self.entries.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// A rough estimate of the memory used by the recorded requests.
+    ///
+    /// # Panics
+    /// * If the lock of the log is poisoned.
+    #[must_use]
+    pub fn estimated_size_bytes(&self) -> usize {
+        let entries = self.entries.lock().unwrap();
+
+        entries.capacity() * size_of::<NetworkEntry>()
+            + entries
+                .iter()
+                .map(|entry| entry.url.as_str().len())
+                .sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(url: &str) -> NetworkEntry {
+        NetworkEntry {
+            method: Method::GET,
+            url: Url::parse(url).unwrap(),
+            status_code: StatusCode::OK,
+            protocol: Protocol::Http1_1,
+        }
+    }
+
+    #[test]
+    fn test_oldest_entries_are_evicted() {
+        let log = NetworkLog::new();
+        for index in 0..NetworkLog::MAX_ENTRIES + 2 {
+            log.record(entry(&format!("https://example.com/{index}")));
+        }
+
+        let entries = log.entries();
+        assert_eq!(entries.len(), NetworkLog::MAX_ENTRIES);
+        assert_eq!(entries[0].url.as_str(), "https://example.com/2");
+        assert_eq!(
+            entries[NetworkLog::MAX_ENTRIES - 1].url.as_str(),
+            format!("https://example.com/{}", NetworkLog::MAX_ENTRIES + 1)
+        );
+    }
+
+    #[test]
+    fn test_clones_share_the_log() {
+        let log = NetworkLog::new();
+        log.clone().record(entry("https://example.com/"));
+
+        assert_eq!(log.entries(), vec![entry("https://example.com/")]);
+    }
+
+    #[test]
+    fn test_estimated_size_counts_entries_and_urls() {
+        let log = NetworkLog::new();
+        assert_eq!(log.estimated_size_bytes(), 0);
+
+        log.record(entry("https://example.com/"));
+        log.record(entry("https://example.com/style.css"));
+
+        let capacity = log.entries.lock().unwrap().capacity();
+        assert_eq!(
+            log.estimated_size_bytes(),
+            capacity * size_of::<NetworkEntry>() + "https://example.com/".len() + "https://example.com/style.css".len()
+        );
+    }
+}
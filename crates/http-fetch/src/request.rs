@@ -12,6 +12,7 @@ use http_policy::{
     referrer::apply_referrer,
 };
 use http_types::{
+    partition::StoragePartitionKey,
     properties::Credentials,
     request::{Request, RequestContext},
 };
@@ -23,7 +24,7 @@ use crate::{
     errors::FetchError,
     handle::ResponseHandle,
     handles::{CacheHandle, DecodeHandle, LocalHandle},
-    middleware::{add_forbidden_headers, apply_cookies, handle_response_cookie},
+    middleware::{add_forbidden_headers, apply_cookies, handle_response_cookie, request_site},
 };
 
 const STATUS_CODE: &str = "status_code";
@@ -44,7 +45,9 @@ pub async fn fetch(
 
     add_headers(current_url, &mut request, browser_headers);
 
-    match cache_lookup(paths, &request.context, http_cache) {
+    let partition = StoragePartitionKey::for_request(current_url, &request.context);
+
+    match cache_lookup(paths, &partition, &request.context, http_cache) {
         Ok(entry) => match entry {
             CacheEntry::Hit(data) => {
                 debug!({ STATUS_CODE } = data.head.status_code.as_u16(), { CACHE } = "hit");
@@ -55,9 +58,17 @@ pub async fn fetch(
                 revalidation_headers,
             } => {
                 trace!("Cache requires revalidation for {}", request.context.url);
-                return make_revalidation_request(request, client, paths, http_cache, stale_data, revalidation_headers)
-                    .await
-                    .map_err(FetchError::Network);
+                return make_revalidation_request(
+                    request,
+                    client,
+                    paths,
+                    http_cache,
+                    partition,
+                    stale_data,
+                    revalidation_headers,
+                )
+                .await
+                .map_err(FetchError::Network);
             }
             CacheEntry::Miss => {
                 trace!("Cache Miss");
@@ -76,7 +87,8 @@ pub async fn fetch(
         && let Some(host) = &request.context.url.host()
     {
         let is_secure = request.context.url.scheme().eq_ignore_ascii_case("https");
-        let cookies = cookie_jar.get_cookies(host, request.context.url.path(), is_secure);
+        let site = request_site(current_url, &request.context);
+        let cookies = cookie_jar.get_cookies(host, request.context.url.path(), is_secure, site);
 
         apply_cookies(&mut request, &cookies);
     }
@@ -104,7 +116,14 @@ pub async fn fetch(
 
     let cache_key = request_context.url.to_string();
     let final_handle = if response_head.status_code.is_success() {
-        CacheHandle::wrap_handle(paths.clone(), http_cache, cache_key, response_handle, request_context.headers.clone())
+        CacheHandle::wrap_handle(
+            paths.clone(),
+            http_cache,
+            partition,
+            cache_key,
+            response_handle,
+            request_context.headers.clone(),
+        )
     } else {
         response_handle
     };
@@ -0,0 +1,464 @@
+//! Crawl delays from `robots.txt`, so that automated browsing visiting many pages of a site waits between requests
+//! as long as the site asks.
+//!
+//! <https://www.rfc-editor.org/rfc/rfc9309>
+
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use http_types::{
+    properties::{Credentials, Destination, RequestMode},
+    request::{Request, RequestContext},
+};
+use tracing::{debug, trace};
+use url::Url;
+
+use crate::{client::HttpClient, inflight::InFlightRequests};
+
+/// How long a fetched `robots.txt` is cached.
+const ROBOTS_TXT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The longest crawl delay that is respected, so a misconfigured site cannot stall the browser indefinitely.
+const MAX_CRAWL_DELAY: Duration = Duration::from_secs(60);
+
+/// The largest `robots.txt` that is parsed, larger files are treated as if there was none.
+const MAX_ROBOTS_TXT_SIZE: usize = 512 * 1024;
+
+/// How long fetching a `robots.txt` may take before the site is treated as if it had none, so a slow site does not
+/// hold up the crawl.
+const ROBOTS_TXT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The directives of a `robots.txt` that apply to the browser.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RobotsTxt {
+    /// The time to wait between two requests to the site.
+    pub crawl_delay: Option<Duration>,
+}
+
+impl RobotsTxt {
+    /// Parses the `Crawl-delay` of a `robots.txt` for a user agent. The delay of the group naming the user agent is
+    /// used if there is one, otherwise the delay of the `*` group.
+    ///
+    /// # Arguments
+    /// * `text` - The content of the `robots.txt`.
+    /// * `user_agent` - The product token of the user agent, e.g. `Mozilla`.
+    #[must_use]
+    pub fn parse(text: &str, user_agent: &str) -> Self {
+        let mut specific = None;
+        let mut wildcard = None;
+
+        let mut group_agents: Vec<String> = Vec::new();
+        let mut in_agent_lines = false;
+
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+
+            if key.trim().eq_ignore_ascii_case("user-agent") {
+                if !in_agent_lines {
+                    group_agents.clear();
+                }
+                group_agents.push(value.to_ascii_lowercase());
+                in_agent_lines = true;
+                continue;
+            }
+
+            in_agent_lines = false;
+
+            if !key.trim().eq_ignore_ascii_case("crawl-delay") {
+                continue;
+            }
+
+            let Some(delay) = value
+                .parse::<f64>()
+                .ok()
+                .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+                .map(|seconds| Duration::from_secs_f64(seconds).min(MAX_CRAWL_DELAY))
+            else {
+                continue;
+            };
+
+            for agent in &group_agents {
+                if agent == "*" {
+                    wildcard.get_or_insert(delay);
+                } else if !user_agent.is_empty() && agent.eq_ignore_ascii_case(user_agent) {
+                    specific.get_or_insert(delay);
+                }
+            }
+        }
+
+        Self {
+            crawl_delay: specific.or(wildcard),
+        }
+    }
+}
+
+/// Fetches the `robots.txt` of a site.
+#[async_trait]
+pub trait RobotsTxtTransport: Send + Sync + Debug {
+    /// Fetches the text of a `robots.txt`.
+    ///
+    /// # Arguments
+    /// * `url` - The URL of the `robots.txt`.
+    ///
+    /// # Returns
+    /// The text of the file, or `None` if the site has none or it could not be fetched.
+    async fn fetch(&self, url: &Url) -> Option<String>;
+}
+
+/// A transport fetching `robots.txt` files through the HTTP client of the browser, sharing its connection pool and
+/// DNS resolver. The files are fetched without credentials, and give up after `ROBOTS_TXT_TIMEOUT`.
+#[derive(Debug, Clone)]
+pub struct HttpRobotsTxtTransport {
+    client: Box<dyn HttpClient>,
+}
+
+impl HttpRobotsTxtTransport {
+    /// Creates a transport sending its requests with a client, which should not be rate limited itself.
+    #[must_use]
+    pub fn new(client: Box<dyn HttpClient>) -> Self {
+        Self { client }
+    }
+
+    async fn fetch_text(&self, url: &Url) -> Option<String> {
+        let Request { context, body } = Request::builder_url(url.clone())
+            .credentials(Credentials::Omit)
+            .destination(Destination::Empty)
+            .request_mode(RequestMode::NoCors)
+            .build();
+
+        let handle = self.client.send(Arc::new(context), body).await.ok()?;
+
+        let head = handle.head();
+        if !head.status_code.is_success() {
+            return None;
+        }
+
+        let response = handle
+            .response()
+            .await
+            .ok()?
+            .into_complete(MAX_ROBOTS_TXT_SIZE)
+            .await?;
+
+        String::from_utf8(response.body.0.to_vec()).ok()
+    }
+}
+
+#[async_trait]
+impl RobotsTxtTransport for HttpRobotsTxtTransport {
+    async fn fetch(&self, url: &Url) -> Option<String> {
+        let text = tokio::time::timeout(ROBOTS_TXT_TIMEOUT, self.fetch_text(url)).await;
+
+        if text.is_err() {
+            debug!("Fetching {} timed out", url);
+        }
+
+        text.ok().flatten()
+    }
+}
+
+/// A fetched `robots.txt` and when it was fetched.
+#[derive(Debug, Clone)]
+struct CachedRobotsTxt {
+    robots: RobotsTxt,
+    fetched_at: Instant,
+}
+
+/// Caches the `robots.txt` of every origin for 24 hours, fetching it on the first request to the origin. Requests to
+/// an origin made while its file is being fetched wait for that fetch instead of starting another.
+#[derive(Debug, Clone)]
+pub struct RobotsTxtCache {
+    user_agent: String,
+    transport: Arc<dyn RobotsTxtTransport>,
+    cache: Arc<Mutex<HashMap<String, CachedRobotsTxt>>>,
+    in_flight: Arc<InFlightRequests<RobotsTxt>>,
+}
+
+impl RobotsTxtCache {
+    /// Creates a cache fetching the files through the HTTP client of the browser.
+    ///
+    /// # Arguments
+    /// * `user_agent` - The product token the groups of the files are matched against.
+    /// * `client` - The client the files are fetched with, which should not be rate limited itself.
+    #[must_use]
+    pub fn new(user_agent: impl Into<String>, client: Box<dyn HttpClient>) -> Self {
+        Self::with_transport(user_agent, Arc::new(HttpRobotsTxtTransport::new(client)))
+    }
+
+    /// Creates a cache fetching the files through a custom transport.
+    #[must_use]
+    pub fn with_transport(user_agent: impl Into<String>, transport: Arc<dyn RobotsTxtTransport>) -> Self {
+        Self {
+            user_agent: user_agent.into(),
+            transport,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+            in_flight: Arc::new(InFlightRequests::new()),
+        }
+    }
+
+    /// The directives of the `robots.txt` of the origin of a URL, fetched if it is not cached or has expired. A site
+    /// without a `robots.txt` has no directives.
+    pub async fn get(&self, url: &Url) -> RobotsTxt {
+        let origin = url.origin().ascii_serialization();
+
+        if let Some(robots) = self.cached(&origin) {
+            return robots;
+        }
+
+        let Ok(robots_url) = url.join("/robots.txt") else {
+            return RobotsTxt::default();
+        };
+
+        self.in_flight
+            .load(&robots_url, async {
+                // A fetch of the file may have finished since the cache was checked.
+                if let Some(robots) = self.cached(&origin) {
+                    return robots;
+                }
+
+                trace!("Fetching {}", robots_url);
+
+                let robots = self
+                    .transport
+                    .fetch(&robots_url)
+                    .await
+                    .map(|text| RobotsTxt::parse(&text, &self.user_agent))
+                    .unwrap_or_default();
+
+                self.cache.lock().unwrap().insert(
+                    origin,
+                    CachedRobotsTxt {
+                        robots: robots.clone(),
+                        fetched_at: Instant::now(),
+                    },
+                );
+
+                robots
+            })
+            .await
+    }
+
+    /// The cached directives of an origin, unless they have expired.
+    fn cached(&self, origin: &str) -> Option<RobotsTxt> {
+        self.cache
+            .lock()
+            .unwrap()
+            .get(origin)
+            .filter(|cached| cached.fetched_at.elapsed() < ROBOTS_TXT_TTL)
+            .map(|cached| cached.robots.clone())
+    }
+}
+
+/// Spaces out the requests to every domain by the crawl delay of its `robots.txt`.
+///
+/// Only automated browsing is rate limited, when a user browses they control the pace of the requests themselves.
+/// The delay spaces out the pages that are crawled, so only top-level navigations wait for it, not the subresources
+/// each page loads.
+#[derive(Debug, Clone)]
+pub struct CrawlRateLimiter {
+    robots: RobotsTxtCache,
+
+    /// The time of the last request to each domain, or of the next one if it is already waiting for its slot.
+    last_request_time: Arc<Mutex<HashMap<String, Instant>>>,
+}
+
+impl CrawlRateLimiter {
+    /// Creates a rate limiter reading the crawl delays from the given cache.
+    #[must_use]
+    pub fn new(robots: RobotsTxtCache) -> Self {
+        Self {
+            robots,
+            last_request_time: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Waits until a request to the host of a URL is allowed by its crawl delay, and reserves the slot for the
+    /// request. Requests other than top-level navigations, and URLs without a host, are never delayed.
+    ///
+    /// # Arguments
+    /// * `context` - The request about to be sent.
+    pub async fn wait_for_domain_slot(&self, context: &RequestContext) {
+        if !matches!(context.destination, Destination::Document) || context.request_mode != RequestMode::Navigate {
+            return;
+        }
+
+        let url = &context.url;
+        let Some(host) = url.host_str() else {
+            return;
+        };
+
+        let Some(delay) = self.robots.get(url).await.crawl_delay else {
+            return;
+        };
+
+        let slot = {
+            let mut last_request_time = self.last_request_time.lock().unwrap();
+            let now = Instant::now();
+            let slot = last_request_time
+                .get(host)
+                .map_or(now, |last| (*last + delay).max(now));
+
+            last_request_time.insert(host.to_string(), slot);
+            slot
+        };
+
+        let wait = slot.saturating_duration_since(Instant::now());
+        if !wait.is_zero() {
+            debug!("Waiting {:?} for the crawl delay of {}", wait, host);
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// A site serving the same `robots.txt` for every origin.
+    #[derive(Debug)]
+    struct MockSite {
+        robots_txt: Option<&'static str>,
+        requests: AtomicUsize,
+    }
+
+    impl MockSite {
+        fn new(robots_txt: Option<&'static str>) -> Arc<Self> {
+            Arc::new(Self {
+                robots_txt,
+                requests: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl RobotsTxtTransport for MockSite {
+        async fn fetch(&self, url: &Url) -> Option<String> {
+            assert_eq!(url.path(), "/robots.txt");
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            self.robots_txt.map(str::to_string)
+        }
+    }
+
+    fn navigation(url: &str) -> RequestContext {
+        Request::builder(url).build().context
+    }
+
+    #[test]
+    fn test_parse_crawl_delay() {
+        let text = "User-agent: *\nDisallow: /private\nCrawl-delay: 2\n";
+        assert_eq!(RobotsTxt::parse(text, "Mozilla").crawl_delay, Some(Duration::from_secs(2)));
+
+        let text = "user-agent: *\ncrawl-delay: 0.5 # half a second\n";
+        assert_eq!(RobotsTxt::parse(text, "Mozilla").crawl_delay, Some(Duration::from_millis(500)));
+
+        assert_eq!(RobotsTxt::parse("User-agent: *\nDisallow: /\n", "Mozilla").crawl_delay, None);
+        assert_eq!(RobotsTxt::parse("User-agent: *\nCrawl-delay: soon\n", "Mozilla").crawl_delay, None);
+        assert_eq!(
+            RobotsTxt::parse("User-agent: *\nCrawl-delay: 86400\n", "Mozilla").crawl_delay,
+            Some(MAX_CRAWL_DELAY)
+        );
+    }
+
+    #[test]
+    fn test_parse_prefers_specific_user_agent() {
+        let text = "User-agent: *\nCrawl-delay: 10\n\nUser-agent: Googlebot\nUser-agent: mozilla\nCrawl-delay: 1\n";
+        assert_eq!(RobotsTxt::parse(text, "Mozilla").crawl_delay, Some(Duration::from_secs(1)));
+        assert_eq!(RobotsTxt::parse(text, "Bingbot").crawl_delay, Some(Duration::from_secs(10)));
+
+        let text = "User-agent: Googlebot\nCrawl-delay: 5\n";
+        assert_eq!(RobotsTxt::parse(text, "Mozilla").crawl_delay, None);
+    }
+
+    #[tokio::test]
+    async fn test_robots_txt_is_cached_per_origin() {
+        let site = MockSite::new(Some("User-agent: *\nCrawl-delay: 3\n"));
+        let cache = RobotsTxtCache::with_transport("Mozilla", site.clone());
+
+        let url = Url::parse("https://example.com/a").unwrap();
+        assert_eq!(cache.get(&url).await.crawl_delay, Some(Duration::from_secs(3)));
+        cache
+            .get(&Url::parse("https://example.com/b?c").unwrap())
+            .await;
+        assert_eq!(site.requests.load(Ordering::SeqCst), 1);
+
+        cache
+            .get(&Url::parse("https://other.example/").unwrap())
+            .await;
+        assert_eq!(site.requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_fetch_robots_txt_once() {
+        let site = MockSite::new(Some("User-agent: *\nCrawl-delay: 3\n"));
+        let cache = RobotsTxtCache::with_transport("Mozilla", site.clone());
+
+        let url = Url::parse("https://example.com/").unwrap();
+        let (a, b, c) = tokio::join!(cache.get(&url), cache.get(&url), cache.get(&url));
+
+        assert_eq!(site.requests.load(Ordering::SeqCst), 1);
+        for robots in [a, b, c] {
+            assert_eq!(robots.crawl_delay, Some(Duration::from_secs(3)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_waits_for_crawl_delay() {
+        let site = MockSite::new(Some("User-agent: *\nCrawl-delay: 0.05\n"));
+        let limiter = CrawlRateLimiter::new(RobotsTxtCache::with_transport("Mozilla", site));
+        let request = navigation("https://example.com/");
+
+        let start = Instant::now();
+        limiter.wait_for_domain_slot(&request).await;
+        limiter.wait_for_domain_slot(&request).await;
+        limiter.wait_for_domain_slot(&request).await;
+
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_only_delays_navigations() {
+        let site = MockSite::new(Some("User-agent: *\nCrawl-delay: 30\n"));
+        let limiter = CrawlRateLimiter::new(RobotsTxtCache::with_transport("Mozilla", site.clone()));
+
+        let start = Instant::now();
+        limiter
+            .wait_for_domain_slot(&navigation("https://example.com/"))
+            .await;
+
+        for _ in 0..5 {
+            let image = Request::builder("https://example.com/image.png")
+                .destination(Destination::Image)
+                .request_mode(RequestMode::NoCors)
+                .build();
+            limiter.wait_for_domain_slot(&image.context).await;
+        }
+
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(site.requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_without_robots_txt() {
+        let limiter = CrawlRateLimiter::new(RobotsTxtCache::with_transport("Mozilla", MockSite::new(None)));
+        let request = navigation("https://example.com/");
+
+        let start = Instant::now();
+        for _ in 0..5 {
+            limiter.wait_for_domain_slot(&request).await;
+        }
+
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -1,5 +1,6 @@
 pub mod body;
 pub mod errors;
+pub mod partition;
 pub mod properties;
 pub mod request;
 pub mod response;
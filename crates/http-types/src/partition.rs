@@ -0,0 +1,59 @@
+//! Keys that partition per-site data, so that what one origin stores is kept apart from what another origin stores.
+
+use std::fmt::{Display, Formatter};
+
+use url::Url;
+
+use crate::{properties::RequestMode, request::RequestContext};
+
+/// The origin a piece of per-site data belongs to, made of the scheme, host and port of a URL.
+///
+/// Resources loaded by a page are stored under the partition of the page that loaded them, so a resource shared by
+/// two sites is cached twice and one site can not tell whether the other has loaded it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StoragePartitionKey {
+    /// The scheme of the origin, such as `https`.
+    pub scheme: String,
+
+    /// The host of the origin, empty for URLs without one such as `file:` URLs.
+    pub host: String,
+
+    /// The port of the origin, the default port of the scheme if the URL has none, or `0` if the scheme has no
+    /// default port.
+    pub port: u16,
+}
+
+impl StoragePartitionKey {
+    /// Creates the partition key of the origin of a URL.
+    ///
+    /// # Arguments
+    /// * `url` - The URL whose origin the key is created for.
+    #[must_use]
+    pub fn from_url(url: &Url) -> Self {
+        Self {
+            scheme: url.scheme().to_ascii_lowercase(),
+            host: url.host_str().unwrap_or_default().to_ascii_lowercase(),
+            port: url.port_or_known_default().unwrap_or_default(),
+        }
+    }
+
+    /// Creates the partition key a request is made in, which is the origin of the page making it, or the origin of
+    /// the requested URL itself for navigations and requests not made by a page.
+    ///
+    /// # Arguments
+    /// * `current_url` - The URL of the page making the request, if any.
+    /// * `context` - The context of the request.
+    #[must_use]
+    pub fn for_request(current_url: Option<&Url>, context: &RequestContext) -> Self {
+        match current_url {
+            Some(url) if context.request_mode != RequestMode::Navigate => Self::from_url(url),
+            _ => Self::from_url(&context.url),
+        }
+    }
+}
+
+impl Display for StoragePartitionKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}://{}:{}", self.scheme, self.host, self.port)
+    }
+}
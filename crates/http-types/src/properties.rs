@@ -110,3 +110,53 @@ pub enum Referrer {
     /// * Warning: This policy will leak potentially-private information from HTTPS resource URLs to insecure origins. Carefully consider the impact of this setting.
     UnsafeUrl,
 }
+
+/// The HTTP version a response was received over.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/HTTP/Guides/Evolution_of_HTTP>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, AsRefStr)]
+pub enum Protocol {
+    /// HTTP/1.1, also used for the older HTTP/1.0 and HTTP/0.9. (default)
+    #[default]
+    #[strum(serialize = "HTTP/1.1")]
+    Http1_1,
+
+    /// HTTP/2, which multiplexes requests to the same host over a single connection.
+    #[strum(serialize = "HTTP/2")]
+    Http2,
+
+    /// HTTP/3, running over QUIC.
+    #[strum(serialize = "HTTP/3")]
+    Http3,
+}
+
+impl From<http::Version> for Protocol {
+    fn from(version: http::Version) -> Self {
+        match version {
+            http::Version::HTTP_2 => Self::Http2,
+            http::Version::HTTP_3 => Self::Http3,
+            _ => Self::Http1_1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protocol_from_version() {
+        assert_eq!(Protocol::from(http::Version::HTTP_09), Protocol::Http1_1);
+        assert_eq!(Protocol::from(http::Version::HTTP_10), Protocol::Http1_1);
+        assert_eq!(Protocol::from(http::Version::HTTP_11), Protocol::Http1_1);
+        assert_eq!(Protocol::from(http::Version::HTTP_2), Protocol::Http2);
+        assert_eq!(Protocol::from(http::Version::HTTP_3), Protocol::Http3);
+    }
+
+    #[test]
+    fn test_protocol_names() {
+        assert_eq!(Protocol::Http1_1.as_ref(), "HTTP/1.1");
+        assert_eq!(Protocol::Http2.as_ref(), "HTTP/2");
+        assert_eq!(Protocol::Http3.as_ref(), "HTTP/3");
+    }
+}
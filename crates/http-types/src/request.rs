@@ -18,6 +18,10 @@ pub struct RequestContext {
     pub destination: Destination,
     pub referrer: Referrer,
     pub request_mode: RequestMode,
+
+    /// Whether the request is a navigation the user started, by following a link, submitting a form or entering a URL.
+    /// Sent to the server as `Sec-Fetch-User`.
+    pub user_activation: bool,
 }
 
 /// Represents an HTTP request.
@@ -104,6 +108,7 @@ impl RequestBuilder {
             destination: Destination::Document,
             referrer: Referrer::default(),
             request_mode: RequestMode::Navigate,
+            user_activation: false,
         };
 
         Ok(Self {
@@ -211,6 +216,17 @@ impl RequestBuilder {
         self
     }
 
+    /// Marks the request as a navigation started by the user, which is only reported to the server for requests with
+    /// the `Navigate` mode.
+    ///
+    /// # Arguments
+    /// * `user_activation` - Whether the user started the navigation.
+    #[must_use]
+    pub const fn user_activation(mut self, user_activation: bool) -> Self {
+        self.context.user_activation = user_activation;
+        self
+    }
+
     /// Finalizes and builds the Request object.
     ///
     /// # Panics
@@ -261,6 +277,7 @@ impl From<Url> for RequestBuilder {
                 destination: Destination::Document,
                 referrer: Referrer::default(),
                 request_mode: RequestMode::Navigate,
+                user_activation: false,
             },
             body: HttpBody::Empty,
         }
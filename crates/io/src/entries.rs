@@ -9,3 +9,8 @@ pub const PROFILE_PREFERENCES: AppFile = AppFile(Entry::config("preferences.toml
 
 pub const GLOBAL_THEMES_DIRECTORY: AppDirectory = AppDirectory(Entry::user_data("themes/", true));
 pub const PROFILE_THEMES_DIRECTORY: AppDirectory = AppDirectory(Entry::user_data("themes/", false));
+
+pub const GLOBAL_SPELLING_DICTIONARY: AppFile = AppFile(Entry::user_data("dictionaries/en.txt", true));
+
+pub const PROFILE_SESSION: AppFile = AppFile(Entry::user_data("session/tabs.bin", false));
+pub const PROFILE_SESSION_LOCK: AppFile = AppFile(Entry::user_data("session/running.lock", false));
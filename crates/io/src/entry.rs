@@ -193,13 +193,13 @@ impl<'path> AppDirectory<'path> {
 
 pub struct AppFile<'path>(pub Entry<'path>);
 
-impl Readable for AppFile<'_> {
-    type Output = Bytes;
-
-    fn read(self, paths: &AppPaths, max_file_size: Option<u64>) -> Result<Self::Output, ResourceError> {
+impl AppFile<'_> {
+    /// Resolves the full path of the file against the directories of the application.
+    #[must_use]
+    pub fn path(&self, paths: &AppPaths) -> PathBuf {
         let entry = self.0;
 
-        let path = if entry.is_global() {
+        if entry.is_global() {
             match entry.file_path() {
                 EntryCategory::Absolute => PathBuf::from(entry.path()),
                 EntryCategory::Cache => paths.global_cache.join(entry.path()),
@@ -215,7 +215,15 @@ impl Readable for AppFile<'_> {
                 EntryCategory::UserData => paths.profile_data.join(entry.path()),
                 EntryCategory::Temporary => paths.temp.join(entry.path()),
             }
-        };
+        }
+    }
+}
+
+impl Readable for AppFile<'_> {
+    type Output = Bytes;
+
+    fn read(self, paths: &AppPaths, max_file_size: Option<u64>) -> Result<Self::Output, ResourceError> {
+        let path = self.path(paths);
 
         let mut file = File::open(&path).map_err(|e| ResourceError::Io(e.to_string()))?;
         let metadata = file
@@ -246,24 +254,7 @@ impl Readable for AppFile<'_> {
 impl Writable for AppFile<'_> {
     fn write<C: AsRef<[u8]>>(self, data: C, paths: &AppPaths) -> Result<(), ResourceError> {
         let entry = self.0;
-
-        let path = if entry.is_global() {
-            match entry.file_path() {
-                EntryCategory::Absolute => PathBuf::from(entry.path()),
-                EntryCategory::Cache => paths.global_cache.join(entry.path()),
-                EntryCategory::Config => paths.global_config.join(entry.path()),
-                EntryCategory::UserData => paths.global_data.join(entry.path()),
-                EntryCategory::Temporary => paths.temp.join(entry.path()),
-            }
-        } else {
-            match entry.file_path() {
-                EntryCategory::Absolute => PathBuf::from(entry.path()),
-                EntryCategory::Cache => paths.profile_cache.join(entry.path()),
-                EntryCategory::Config => paths.profile_config.join(entry.path()),
-                EntryCategory::UserData => paths.profile_data.join(entry.path()),
-                EntryCategory::Temporary => paths.temp.join(entry.path()),
-            }
-        };
+        let path = self.path(paths);
 
         if !is_relative_path(entry.path()) {
             return Err(ResourceError::InvalidPath(entry.path().to_string()));
@@ -5,6 +5,7 @@ pub mod entry;
 pub mod errors;
 pub mod http;
 pub mod paths;
+pub mod resource;
 mod traits;
 
 pub use entry::Entry;
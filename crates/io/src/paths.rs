@@ -122,6 +122,17 @@ pub fn get_data_path(subdirs: Vec<String>) -> Option<PathBuf> {
     }
 }
 
+/// Get the path to the directory downloaded files are saved in, the download directory of the user, or a `Downloads`
+/// directory in the data directory of the application if the user has none.
+///
+/// # Returns
+/// * `Some(PathBuf)` - The path to the download directory if it can be determined
+/// * `None` - If neither directory can be determined
+#[must_use]
+pub fn get_download_path() -> Option<PathBuf> {
+    dirs::download_dir().or_else(|| get_data_path(vec!["Downloads".to_string()]))
+}
+
 /// Get the path to the temporary directory for the application.
 ///
 /// # Returns
@@ -0,0 +1,175 @@
+use std::collections::VecDeque;
+
+/// The priority of a sub-resource load, from the most to the least urgent.
+///
+/// Render-blocking resources are `Critical`, since the page cannot be painted before they are loaded, while
+/// speculative loads are `Idle` and only use the network when nothing more urgent is waiting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Stylesheets and render-blocking scripts.
+    Critical,
+
+    /// Fonts referenced by `@font-face` and other preloaded resources.
+    High,
+
+    /// Images.
+    Normal,
+
+    /// Resources that are not needed to display the page, such as the favicon.
+    Low,
+
+    /// Prefetch hints for future navigations.
+    Idle,
+}
+
+impl Priority {
+    /// Every priority, from the most to the least urgent.
+    pub const ALL: [Self; 5] = [
+        Self::Critical,
+        Self::High,
+        Self::Normal,
+        Self::Low,
+        Self::Idle,
+    ];
+
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// A request for a sub-resource waiting for a free slot in its priority tier.
+#[derive(Debug)]
+pub struct ResourceRequest<T> {
+    pub priority: Priority,
+    pub payload: T,
+}
+
+/// Orders the sub-resource loads of the pages, limiting how many loads of each priority run at the same time.
+///
+/// Requests are queued per priority and started most urgent first, in the order they were queued within a priority.
+/// A request is started once its tier has fewer than the maximum number of loads in flight, so a burst of images never
+/// delays a stylesheet.
+#[derive(Debug)]
+pub struct ResourceManager<T> {
+    queues: [VecDeque<T>; Priority::ALL.len()],
+    in_flight: [usize; Priority::ALL.len()],
+    max_concurrent: usize,
+}
+
+impl<T> ResourceManager<T> {
+    /// The default number of loads of a single priority running at the same time.
+    pub const DEFAULT_MAX_CONCURRENT: usize = 6;
+
+    /// Creates an empty manager.
+    ///
+    /// # Arguments
+    /// * `max_concurrent` - The maximum number of loads in flight per priority, at least one.
+    #[must_use]
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            queues: Default::default(),
+            in_flight: [0; Priority::ALL.len()],
+            max_concurrent: max_concurrent.max(1),
+        }
+    }
+
+    /// Queues a request until a slot of its priority is free.
+    pub fn push(&mut self, request: ResourceRequest<T>) {
+        self.queues[request.priority.index()].push_back(request.payload);
+    }
+
+    /// Takes the most urgent queued request whose priority has a free slot, marking it as in flight.
+    ///
+    /// # Returns
+    /// The request to start, or `None` if every queued request is waiting for a slot.
+    pub fn pop(&mut self) -> Option<ResourceRequest<T>> {
+        Priority::ALL.into_iter().find_map(|priority| {
+            let index = priority.index();
+            if self.in_flight[index] >= self.max_concurrent {
+                return None;
+            }
+
+            let payload = self.queues[index].pop_front()?;
+            self.in_flight[index] += 1;
+
+            Some(ResourceRequest { priority, payload })
+        })
+    }
+
+    /// Marks a load of the given priority as finished, freeing its slot.
+    pub fn complete(&mut self, priority: Priority) {
+        let in_flight = &mut self.in_flight[priority.index()];
+        *in_flight = in_flight.saturating_sub(1);
+    }
+
+    /// The number of loads of the given priority that are queued or in flight.
+    #[must_use]
+    pub fn pending(&self, priority: Priority) -> usize {
+        self.queues[priority.index()].len() + self.in_flight[priority.index()]
+    }
+
+    /// Whether every critical resource has been loaded, in which case the page can be painted even if images and
+    /// other less urgent resources are still loading.
+    #[must_use]
+    pub fn is_first_paint_ready(&self) -> bool {
+        self.pending(Priority::Critical) == 0
+    }
+}
+
+impl<T> Default for ResourceManager<T> {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX_CONCURRENT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(priority: Priority, payload: &'static str) -> ResourceRequest<&'static str> {
+        ResourceRequest { priority, payload }
+    }
+
+    #[test]
+    fn test_most_urgent_first() {
+        let mut manager = ResourceManager::default();
+        manager.push(request(Priority::Normal, "image.png"));
+        manager.push(request(Priority::Idle, "next.html"));
+        manager.push(request(Priority::Critical, "style.css"));
+
+        assert_eq!(manager.pop().unwrap().payload, "style.css");
+        assert_eq!(manager.pop().unwrap().payload, "image.png");
+        assert_eq!(manager.pop().unwrap().payload, "next.html");
+        assert!(manager.pop().is_none());
+    }
+
+    #[test]
+    fn test_concurrency_limit_per_priority() {
+        let mut manager = ResourceManager::new(1);
+        manager.push(request(Priority::Normal, "a.png"));
+        manager.push(request(Priority::Normal, "b.png"));
+        manager.push(request(Priority::Critical, "style.css"));
+
+        assert_eq!(manager.pop().unwrap().payload, "style.css");
+        assert_eq!(manager.pop().unwrap().payload, "a.png");
+        assert!(manager.pop().is_none());
+
+        manager.complete(Priority::Normal);
+        assert_eq!(manager.pop().unwrap().payload, "b.png");
+    }
+
+    #[test]
+    fn test_first_paint_ready_with_pending_images() {
+        let mut manager = ResourceManager::default();
+        manager.push(request(Priority::Critical, "style.css"));
+        manager.push(request(Priority::Normal, "image.png"));
+        assert!(!manager.is_first_paint_ready());
+
+        manager.pop();
+        manager.pop();
+        manager.complete(Priority::Critical);
+
+        assert!(manager.is_first_paint_ready());
+        assert_eq!(manager.pending(Priority::Normal), 1);
+    }
+}
@@ -10,13 +10,12 @@ use std::{
     time::Duration,
 };
 
-use browser_preferences::theme::ThemeCategory;
 use browser_ui::load_fallback_fonts;
 use cosmic_text::FontSystem;
 use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
 use css_cssom::{CSSStyleSheet, StylesheetOrigin};
 use css_display::BoxTree;
-use css_style::{AbsoluteContext, StyleTree};
+use css_style::{AbsoluteContext, StyleTree, SystemPreferences};
 use css_values::color::Color;
 use html_dom::DocumentRoot;
 use html_parser::{BlockedReason, HtmlStreamParser, ParserState, Script};
@@ -51,7 +50,7 @@ fn absolute_context(viewport: Rect) -> AbsoluteContext<'static> {
         root_font_size: 16.0,
         root_line_height_multiplier: 1.2,
         document_url: document_url(),
-        theme_category: ThemeCategory::Light,
+        system_preferences: SystemPreferences::default(),
         root_color: Color::BLACK,
     }
 }
@@ -130,7 +129,7 @@ fn parse_html_and_collect_styles(
                     Script::External { .. } => {}
                     Script::Inline { .. } => {}
                 },
-                BlockedReason::WaitingForResource(_, _, _) => {}
+                BlockedReason::WaitingForResource(_, _, _) | BlockedReason::ResourceHint { .. } => {}
                 BlockedReason::SVGContent { data } => {
                     data.expect("failed to extract SVG content");
                 }
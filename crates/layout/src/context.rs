@@ -1,4 +1,5 @@
 mod float;
+mod font_loading;
 mod formatting;
 mod geometry;
 mod image;
@@ -7,9 +8,10 @@ mod position;
 mod text;
 
 pub use float::FloatContext;
+pub use font_loading::{FontLoadState, FontLoader, FontRendering, PENDING_FONT_METADATA};
 pub(crate) use formatting::FormattingContext;
 pub(crate) use geometry::{BoxModel, Geometry};
-pub use image::{ImageContext, ImageData, LayoutImage};
+pub use image::{ImageContext, ImageData, LayoutImage, PartColors, ReplacedKind};
 pub use layout::LayoutContext;
 pub use position::PositionContext;
-pub use text::{TextContext, TextDescription, TextFragment};
+pub use text::{SOFT_HYPHEN, TextContext, TextDescription, TextFragment, TextSpacing};
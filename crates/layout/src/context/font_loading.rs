@@ -0,0 +1,329 @@
+//! The loading of web fonts declared with `@font-face`, following their `font-display` descriptor. While a font is
+//! downloading, text using it is laid out with the next font of its `font-family`, so that the layout keeps the same
+//! shape when the font arrives, and is hidden during the block period of the font.
+//!
+//! <https://drafts.csswg.org/css-fonts/#font-display-timeline>
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use css_values::text::FontDisplay;
+
+/// The block period of `auto` and `block`.
+const LONG_BLOCK_PERIOD: Duration = Duration::from_secs(3);
+
+/// The block period of `fallback` and `optional`.
+const SHORT_BLOCK_PERIOD: Duration = Duration::from_millis(100);
+
+/// The swap period of `fallback`.
+const SHORT_SWAP_PERIOD: Duration = Duration::from_secs(3);
+
+/// The glyph metadata of text laid out with a fallback font during the block period of a web font, which the
+/// renderer does not draw.
+pub const PENDING_FONT_METADATA: usize = 1;
+
+/// The periods of a `font-display` value.
+const fn periods(display: FontDisplay) -> (Duration, Option<Duration>) {
+    match display {
+        FontDisplay::Auto | FontDisplay::Block => (LONG_BLOCK_PERIOD, None),
+        FontDisplay::Swap => (Duration::ZERO, None),
+        FontDisplay::Fallback => (SHORT_BLOCK_PERIOD, Some(SHORT_SWAP_PERIOD)),
+        FontDisplay::Optional => (SHORT_BLOCK_PERIOD, Some(Duration::ZERO)),
+    }
+}
+
+/// The state of a web font on the `font-display` timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontLoadState {
+    /// The font is declared but its download has not started yet, text using it is shown with the fallback font.
+    Loading,
+
+    /// The download started at the given instant, text using the font is hidden until the block period ends.
+    BlockPeriod(Instant),
+
+    /// The download started at the given instant, text using the font is shown with the fallback font and swapped to
+    /// the font if it arrives before the swap period ends.
+    SwapPeriod(Instant),
+
+    /// The font arrived in time and is used, or the swap period ended and the fallback font is kept.
+    Done,
+}
+
+/// How text using a web font is rendered, see [`FontLoader::rendering`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontRendering {
+    /// Laid out with the fallback font, but not drawn.
+    Hidden,
+
+    /// Laid out and drawn with the fallback font.
+    Fallback,
+
+    /// Laid out and drawn with the web font.
+    Font,
+}
+
+/// A web font on the `font-display` timeline.
+#[derive(Debug, Clone, Copy)]
+struct FontLoad {
+    display: FontDisplay,
+    state: FontLoadState,
+    loaded: bool,
+}
+
+impl FontLoad {
+    /// Moves the font along the timeline up to `now`.
+    ///
+    /// # Returns
+    /// Whether the state changed.
+    fn advance(&mut self, now: Instant) -> bool {
+        let (block, swap) = periods(self.display);
+
+        let next = match self.state {
+            FontLoadState::BlockPeriod(start) if now.duration_since(start) >= block => {
+                if swap.is_some_and(|swap| now.duration_since(start) >= block + swap) {
+                    FontLoadState::Done
+                } else {
+                    FontLoadState::SwapPeriod(start)
+                }
+            }
+            FontLoadState::SwapPeriod(start) if swap.is_some_and(|swap| now.duration_since(start) >= block + swap) => {
+                FontLoadState::Done
+            }
+            state => state,
+        };
+
+        let changed = next != self.state;
+        self.state = next;
+        changed
+    }
+
+    /// The next instant at which the font moves along the timeline by itself.
+    fn deadline(&self) -> Option<Instant> {
+        let (block, swap) = periods(self.display);
+
+        match self.state {
+            FontLoadState::BlockPeriod(start) => Some(start + block),
+            FontLoadState::SwapPeriod(start) => swap.map(|swap| start + block + swap),
+            FontLoadState::Loading | FontLoadState::Done => None,
+        }
+    }
+}
+
+/// Tracks the web fonts of the documents being laid out by family name.
+#[derive(Debug, Clone, Default)]
+pub struct FontLoader {
+    loads: HashMap<String, FontLoad>,
+}
+
+impl FontLoader {
+    /// Declares a web font whose download is about to start, keeping the state of a family that is already known.
+    ///
+    /// # Arguments
+    /// * `family` - The family name of the `@font-face` rule.
+    /// * `display` - The `font-display` descriptor of the rule.
+    pub fn declare(&mut self, family: &str, display: FontDisplay) {
+        self.loads
+            .entry(family.to_ascii_lowercase())
+            .or_insert(FontLoad {
+                display,
+                state: FontLoadState::Loading,
+                loaded: false,
+            });
+    }
+
+    /// Starts the block period of a declared font whose download started at `now`.
+    ///
+    /// # Returns
+    /// Whether the state changed, `false` if the font is not declared or already started.
+    pub fn start(&mut self, family: &str, now: Instant) -> bool {
+        let Some(load) = self.loads.get_mut(&family.to_ascii_lowercase()) else {
+            return false;
+        };
+
+        if load.state != FontLoadState::Loading {
+            return false;
+        }
+
+        load.state = FontLoadState::BlockPeriod(now);
+        load.advance(now);
+        true
+    }
+
+    /// Records the arrival of the data of a font at `now`.
+    ///
+    /// # Returns
+    /// Whether the font is to be used, which is the case when it arrived before the end of its swap period. The text
+    /// using the font then needs to be laid out and rendered again.
+    pub fn finish(&mut self, family: &str, now: Instant) -> bool {
+        let Some(load) = self.loads.get_mut(&family.to_ascii_lowercase()) else {
+            return false;
+        };
+
+        load.advance(now);
+        if !matches!(load.state, FontLoadState::BlockPeriod(_) | FontLoadState::SwapPeriod(_)) {
+            return false;
+        }
+
+        load.state = FontLoadState::Done;
+        load.loaded = true;
+        true
+    }
+
+    /// Records that the data of a font could not be loaded from any of its sources, ending its block and swap periods
+    /// so that text using it is shown with the fallback font.
+    ///
+    /// # Returns
+    /// Whether the state changed, in which case the text using the font needs to be laid out and rendered again.
+    pub fn fail(&mut self, family: &str) -> bool {
+        let Some(load) = self.loads.get_mut(&family.to_ascii_lowercase()) else {
+            return false;
+        };
+
+        if load.state == FontLoadState::Done {
+            return false;
+        }
+
+        load.state = FontLoadState::Done;
+        true
+    }
+
+    /// Forgets every font, when the document being laid out is replaced by one that declares its own.
+    pub fn clear(&mut self) {
+        self.loads.clear();
+    }
+
+    /// Moves every font along its timeline up to `now`.
+    ///
+    /// # Returns
+    /// Whether the state of any font changed, in which case the text using it needs to be laid out and rendered again.
+    pub fn advance(&mut self, now: Instant) -> bool {
+        self.loads
+            .values_mut()
+            .fold(false, |changed, load| load.advance(now) || changed)
+    }
+
+    /// The next instant at which a font moves along its timeline by itself, when [`FontLoader::advance`] is to be
+    /// called.
+    #[must_use]
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.loads.values().filter_map(FontLoad::deadline).min()
+    }
+
+    /// The state of a font, `None` if the family is not a web font.
+    #[must_use]
+    pub fn state(&self, family: &str) -> Option<FontLoadState> {
+        self.loads
+            .get(&family.to_ascii_lowercase())
+            .map(|load| load.state)
+    }
+
+    /// How text using a font is rendered, `None` if the family is not a web font.
+    #[must_use]
+    pub fn rendering(&self, family: &str) -> Option<FontRendering> {
+        let load = self.loads.get(&family.to_ascii_lowercase())?;
+
+        Some(match load.state {
+            FontLoadState::BlockPeriod(_) => FontRendering::Hidden,
+            FontLoadState::Loading | FontLoadState::SwapPeriod(_) => FontRendering::Fallback,
+            FontLoadState::Done if load.loaded => FontRendering::Font,
+            FontLoadState::Done => FontRendering::Fallback,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn started(display: FontDisplay, now: Instant) -> FontLoader {
+        let mut loader = FontLoader::default();
+        loader.declare("Web", display);
+        loader.start("Web", now);
+        loader
+    }
+
+    #[test]
+    fn test_block_hides_text_then_shows_fallback() {
+        let now = Instant::now();
+        let mut loader = started(FontDisplay::Block, now);
+
+        assert_eq!(loader.rendering("web"), Some(FontRendering::Hidden));
+        assert_eq!(loader.next_deadline(), Some(now + LONG_BLOCK_PERIOD));
+
+        assert!(loader.advance(now + LONG_BLOCK_PERIOD));
+        assert_eq!(loader.state("Web"), Some(FontLoadState::SwapPeriod(now)));
+        assert_eq!(loader.rendering("Web"), Some(FontRendering::Fallback));
+        assert_eq!(loader.next_deadline(), None);
+
+        assert!(loader.finish("Web", now + Duration::from_secs(60)));
+        assert_eq!(loader.rendering("Web"), Some(FontRendering::Font));
+    }
+
+    #[test]
+    fn test_swap_shows_fallback_immediately() {
+        let now = Instant::now();
+        let loader = started(FontDisplay::Swap, now);
+
+        assert_eq!(loader.rendering("Web"), Some(FontRendering::Fallback));
+    }
+
+    #[test]
+    fn test_fallback_gives_up_after_swap_period() {
+        let now = Instant::now();
+        let mut loader = started(FontDisplay::Fallback, now);
+
+        assert!(loader.advance(now + SHORT_BLOCK_PERIOD));
+        assert_eq!(loader.rendering("Web"), Some(FontRendering::Fallback));
+
+        assert!(loader.advance(now + SHORT_BLOCK_PERIOD + SHORT_SWAP_PERIOD));
+        assert_eq!(loader.state("Web"), Some(FontLoadState::Done));
+        assert!(!loader.finish("Web", now + SHORT_BLOCK_PERIOD + SHORT_SWAP_PERIOD));
+        assert_eq!(loader.rendering("Web"), Some(FontRendering::Fallback));
+    }
+
+    #[test]
+    fn test_optional_only_uses_font_within_block_period() {
+        let now = Instant::now();
+
+        let mut fast = started(FontDisplay::Optional, now);
+        assert!(fast.finish("Web", now + Duration::from_millis(50)));
+        assert_eq!(fast.rendering("Web"), Some(FontRendering::Font));
+
+        let mut slow = started(FontDisplay::Optional, now);
+        assert!(!slow.finish("Web", now + Duration::from_millis(150)));
+        assert_eq!(slow.rendering("Web"), Some(FontRendering::Fallback));
+    }
+
+    #[test]
+    fn test_failure_ends_block_period() {
+        let now = Instant::now();
+        let mut loader = started(FontDisplay::Block, now);
+
+        assert!(loader.fail("Web"));
+        assert_eq!(loader.state("Web"), Some(FontLoadState::Done));
+        assert_eq!(loader.rendering("Web"), Some(FontRendering::Fallback));
+        assert_eq!(loader.next_deadline(), None);
+        assert!(!loader.fail("Web"));
+    }
+
+    #[test]
+    fn test_clear_forgets_fonts() {
+        let mut loader = started(FontDisplay::Block, Instant::now());
+
+        loader.clear();
+        assert_eq!(loader.rendering("Web"), None);
+        assert_eq!(loader.next_deadline(), None);
+    }
+
+    #[test]
+    fn test_unknown_family_is_not_a_web_font() {
+        let mut loader = FontLoader::default();
+        assert_eq!(loader.rendering("Web"), None);
+
+        loader.declare("Web", FontDisplay::Block);
+        assert_eq!(loader.rendering("Web"), Some(FontRendering::Fallback));
+        assert_eq!(loader.next_deadline(), None);
+    }
+}
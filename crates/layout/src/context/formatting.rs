@@ -2,7 +2,7 @@ use css_display::BoxNode;
 use css_style::{ComputedStyle, Display, Position};
 use css_values::{
     OverflowBlock,
-    display::{Float, InsideDisplay, InternalDisplay, OutsideDisplay},
+    display::{ContentVisibility, Float, InsideDisplay, InternalDisplay, OutsideDisplay},
 };
 use html_dom::{DocumentRoot, HtmlTag};
 
@@ -91,6 +91,11 @@ impl FormattingContext {
             return true;
         }
 
+        // * Elements with content-visibility: auto or hidden, which apply layout containment.
+        if style.content_visibility != ContentVisibility::Visible {
+            return true;
+        }
+
         // TODO: Elements with contain: layout, content, or paint.
         // TODO: Query containers (elements where container-type isn't normal).
         // TODO: Multicol containers (elements where column-count or column-width isn't auto, including elements with column-count: 1).
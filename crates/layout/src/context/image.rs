@@ -1,14 +1,94 @@
 use std::{collections::HashMap, sync::Arc};
 
+use css_style::{Color4f, ComputedStyle, PartStyles};
+use css_values::image::{ImageRendering, ObjectFit};
 use html_dom::NodeId;
 
+/// The kind of replaced element an image node was created for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReplacedKind {
+    /// An `<img>` element.
+    #[default]
+    Image,
+
+    /// A `<video>` element, rendered as its poster image or a placeholder.
+    Video,
+
+    /// An `<audio>` element with `controls`, rendered as a player placeholder.
+    Audio,
+
+    /// A `<canvas>` element, rendered as its bitmap once its drawing commands have been executed.
+    Canvas,
+
+    /// A single-line `<input>` text control.
+    TextInput,
+
+    /// A multi-line `<textarea>` text control.
+    TextArea,
+
+    /// A `<select>` dropdown, rendered as a button with the label of its selected option.
+    Select,
+
+    /// An `<input type="checkbox">`, rendered as a box with a checkmark while checked.
+    Checkbox,
+
+    /// An `<input type="radio">`, rendered as a circle with a dot while checked.
+    Radio,
+
+    /// A `<progress>` element, rendered as a track filled up to the completed fraction of the task.
+    Progress,
+
+    /// A `<meter>` element, rendered as a track filled up to its value in the color of the region of the value.
+    Meter,
+
+    /// An `<input type="range">`, rendered as a track with a thumb that is dragged to pick the value.
+    Range,
+}
+
+/// The background colors of the `::part(track)`, `::part(indicator)` and `::part(thumb)` pseudo-elements of a
+/// `<progress>`, `<meter>` or range slider, a part without a background color is drawn in the colors of the browser.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PartColors {
+    pub indicator: Option<Color4f>,
+    pub track: Option<Color4f>,
+    pub thumb: Option<Color4f>,
+}
+
+impl From<&PartStyles> for PartColors {
+    fn from(parts: &PartStyles) -> Self {
+        let background = |style: Option<&ComputedStyle>| {
+            style
+                .map(|style| style.background_color)
+                .filter(|color| color.a > 0.0)
+        };
+
+        Self {
+            indicator: background(parts.indicator.as_ref()),
+            track: background(parts.track.as_ref()),
+            thumb: background(parts.thumb.as_ref()),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ImageData {
     pub node_id: NodeId,
 
+    /// The kind of element this node was created for.
+    pub kind: ReplacedKind,
+
     /// Whether this image node is using placeholder dimensions and should be
     /// updated to the intrinsic image size once the image has been decoded.
     pub image_needs_intrinsic_size: bool,
+
+    /// How the image is filtered when it is drawn at a size other than its intrinsic size.
+    pub image_rendering: ImageRendering,
+
+    /// How the image is fitted into the content box of the node.
+    pub object_fit: ObjectFit,
+
+    /// The colors of the parts of a `<progress>`, `<meter>` or range slider.
+    pub part_colors: PartColors,
 }
 
 #[derive(Debug, Clone)]
@@ -19,6 +99,8 @@ pub struct LayoutImage {
     pub height: u32,
     /// Raw RGBA pixel data (4 bytes per pixel)
     pub rgba: Vec<u8>,
+    /// The source of an SVG image, so it can be rasterized again at the size it is drawn at.
+    pub svg_source: Option<Arc<[u8]>>,
 }
 
 #[derive(Debug, Clone, Default)]
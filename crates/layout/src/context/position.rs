@@ -33,6 +33,11 @@ impl PositionContext {
         }
     }
 
+    /// The initial containing block, the size of the viewport.
+    pub const fn viewport(&self) -> Rect {
+        self.viewport
+    }
+
     pub fn push_parent(&mut self, parent_id: &LayoutNodeId, rect: Rect) {
         self.parent_id = *parent_id;
         self.positioned.push(rect);
@@ -76,7 +81,13 @@ impl PositionContext {
                 margin_state: pending.margin_state,
             };
 
-            let mut state = LayoutState::new(&mut layout_tree.nodes, input, &mut new_position_ctx, &mut float_ctx);
+            let mut state = LayoutState::new(
+                &mut layout_tree.nodes,
+                input,
+                &mut new_position_ctx,
+                &mut float_ctx,
+                &mut layout_tree.remembered_sizes,
+            );
             let root_style = ComputedStyle::default();
 
             if let Some((node, size)) = BlockLayout::layout(&pending.layout_id, &root_style, &mut flow, &mut state) {
@@ -1,20 +1,56 @@
-use std::sync::Arc;
+use std::{borrow::Cow, collections::HashMap, sync::Arc, time::Instant};
 
-use cosmic_text::{Align, Attrs, Buffer, Family, FontSystem, Metrics, Shaping, Stretch, Weight, Wrap};
-use css_style::FontFamily;
-use css_values::text::{FontFamilyName, GenericName, TextAlign, Whitespace, WritingMode};
+use cosmic_text::{
+    Align, Attrs, Buffer, Family, FeatureTag, FontFeatures, FontSystem, LayoutRun, Metrics, Shaping, Stretch, Style,
+    Weight, Wrap, fontdb,
+};
+use css_style::{FontFace, FontFamily};
+use css_values::{
+    OverflowWrap,
+    text::{
+        FontFamilyName, FontFeatureSettings, FontKerning, FontSizeAdjust, FontVariantCaps, FontVariantLigatures,
+        FontVariantNumeric, GenericName, Hyphens, TextAlign, Whitespace, WordBreak, WritingMode,
+    },
+};
 
-use crate::Rect;
+use crate::{
+    Rect,
+    context::font_loading::{FontLoadState, FontLoader, FontRendering, PENDING_FONT_METADATA},
+};
+
+/// The invisible `&shy;` character, marking where a word may be hyphenated.
+pub const SOFT_HYPHEN: char = '\u{AD}';
 
 #[derive(Debug, Clone)]
 pub struct TextFragment {
     pub size: Rect,
     pub buffers: Vec<Arc<Buffer>>,
 
+    /// The spacing the glyphs of the buffers are drawn with, which cosmic-text does not apply when shaping.
+    pub spacing: TextSpacing,
+
     #[cfg(debug_assertions)]
     pub debug_content: String,
 }
 
+impl TextFragment {
+    /// A rough estimate of the memory used by the shaped buffers of the fragment, approximated by their text.
+    #[must_use]
+    pub fn estimated_size_bytes(&self) -> usize {
+        self.buffers
+            .iter()
+            .map(|buffer| {
+                size_of::<Buffer>()
+                    + buffer
+                        .lines
+                        .iter()
+                        .map(|line| line.text().len())
+                        .sum::<usize>()
+            })
+            .sum()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Text {
     pub width: f64,
@@ -24,13 +60,162 @@ pub struct Text {
     pub buffer: Buffer,
 }
 
+/// The space added by `letter-spacing` after every glyph but the last of a line and by `word-spacing` after every
+/// space, in pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TextSpacing {
+    pub letter: f64,
+    pub word: f64,
+}
+
+impl TextSpacing {
+    #[must_use]
+    pub const fn is_none(self) -> bool {
+        self.letter == 0.0 && self.word == 0.0
+    }
+
+    /// The space added to a shaped line, on top of the width measured by cosmic-text.
+    fn line_extra(self, run: &LayoutRun) -> f64 {
+        if self.is_none() {
+            return 0.0;
+        }
+
+        let spaces = run
+            .glyphs
+            .iter()
+            .filter(|glyph| run.text.get(glyph.start..glyph.end) == Some(" "))
+            .count();
+
+        self.letter * run.glyphs.len().saturating_sub(1) as f64 + self.word * spaces as f64
+    }
+}
+
 #[derive(Debug)]
 pub struct TextDescription<'text> {
     pub whitespace: &'text Whitespace,
-    pub line_height: f64,
+    /// The used `line-height` in pixels.
+    pub line_height_px: f64,
     pub font_family: &'text FontFamily,
-    pub font_weight: u16,
+    pub font_weight: f32,
     pub font_size_px: f64,
+    pub font_size_adjust: FontSizeAdjust,
+    pub word_break: WordBreak,
+    pub overflow_wrap: OverflowWrap,
+    pub hyphens: Hyphens,
+    /// The text shown at the end of a line broken at a hyphenation opportunity, from `hyphenate-character`.
+    pub hyphenate_character: &'text str,
+    pub spacing: TextSpacing,
+    pub font_kerning: FontKerning,
+    pub font_variant_ligatures: FontVariantLigatures,
+    pub font_variant_numeric: FontVariantNumeric,
+    pub font_variant_caps: FontVariantCaps,
+    pub font_feature_settings: &'text FontFeatureSettings,
+}
+
+impl TextDescription<'_> {
+    /// Where lines may be broken within the text, from `white-space`, `word-break` and `overflow-wrap`.
+    ///
+    /// NOTE: `word-break: keep-all` is treated as `normal`, CJK text is still broken between characters.
+    const fn wrap(&self) -> Wrap {
+        match (self.whitespace, self.word_break, self.overflow_wrap) {
+            (Whitespace::Pre, _, _) => Wrap::None,
+            (_, WordBreak::BreakAll, _) => Wrap::Glyph,
+            (_, WordBreak::BreakWord, _) | (_, _, OverflowWrap::BreakWord | OverflowWrap::Anywhere) => {
+                Wrap::WordOrGlyph
+            }
+            _ => Wrap::Word,
+        }
+    }
+
+    /// The OpenType features the text is shaped with, from `font-kerning`, `font-feature-settings` and the
+    /// `font-variant-*` properties. Features that are not set are left to the defaults of the shaper.
+    fn font_features(&self, small_caps: SmallCapsSynthesis) -> FontFeatures {
+        let mut features = FontFeatures::new();
+
+        if self.font_kerning == FontKerning::None {
+            features.disable(FeatureTag::KERNING);
+        }
+
+        for (tag, value) in self.feature_settings(small_caps) {
+            features.set(FeatureTag::new(&tag), value);
+        }
+
+        features
+    }
+
+    /// The value of every OpenType feature set by the style, one per tag. The features of `font-feature-settings` are
+    /// overridden by those of the `font-variant-*` properties when both set the same feature. The features of
+    /// `font-variant-caps` are left out when its capitals are synthesized, so that no letter is made smaller twice.
+    fn feature_settings(&self, small_caps: SmallCapsSynthesis) -> Vec<([u8; 4], u32)> {
+        let caps = match small_caps {
+            SmallCapsSynthesis::Scale => Vec::new(),
+            SmallCapsSynthesis::Feature | SmallCapsSynthesis::None => self.font_variant_caps.feature_settings(),
+        };
+
+        let mut settings: Vec<([u8; 4], u32)> = self
+            .font_feature_settings
+            .settings()
+            .iter()
+            .map(|setting| (setting.tag, setting.value))
+            .collect();
+
+        for (tag, value) in self
+            .font_variant_ligatures
+            .feature_settings()
+            .into_iter()
+            .chain(self.font_variant_numeric.feature_settings())
+            .chain(caps)
+        {
+            settings.retain(|(set, _)| *set != tag);
+            settings.push((tag, value));
+        }
+
+        settings
+    }
+}
+
+/// The size of the uppercase letters standing in for small capitals, relative to the font size, when the font has no
+/// small capitals of its own.
+const SMALL_CAPS_SCALE: f32 = 0.75;
+
+/// How the small or petite capitals asked for by `font-variant-caps` are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmallCapsSynthesis {
+    /// The font has glyphs for the capitals, selected with its OpenType feature.
+    Feature,
+
+    /// The font has no glyphs for the capitals, the letters are shown as uppercase letters scaled down to
+    /// [`SMALL_CAPS_SCALE`] of the font size.
+    Scale,
+
+    /// The text has no small or petite capitals.
+    None,
+}
+
+/// A font of the fallback list of a `font-family`, see [`TextContext::fallback_fonts`].
+struct FallbackFont<'a> {
+    family: Family<'a>,
+    /// The `@font-face` rule the font was declared with, restricting the characters it is used for.
+    face: Option<&'a FontFace>,
+    /// Whether the font stands in for a web font in its block period, the text is then laid out but not drawn.
+    hidden: bool,
+}
+
+impl<'a> FallbackFont<'a> {
+    fn covers(&self, ch: char) -> bool {
+        self.face.is_none_or(|face| face.covers(ch))
+    }
+
+    /// The attributes of the text rendered with the font, marking the glyphs of hidden text for the renderer.
+    fn attrs(&self, attrs: &Attrs<'a>) -> Attrs<'a> {
+        let attrs = attrs.clone().family(self.family);
+
+        if self.hidden {
+            attrs.metadata(PENDING_FONT_METADATA)
+        } else {
+            attrs
+        }
+    }
 }
 
 /// `TextContext` provides functionality to measure and render text.
@@ -38,6 +223,14 @@ pub struct TextDescription<'text> {
 pub struct TextContext {
     /// The font system used for text rendering.
     font_system: FontSystem,
+    /// The `@font-face` rules of the document being laid out, consulted when selecting the font of each character.
+    font_faces: Vec<FontFace>,
+    /// The `font-display` timeline of the web fonts declared by `font_faces`.
+    font_loader: FontLoader,
+    /// The x-height of every font queried for `font-size-adjust`, relative to its em size.
+    x_height_aspects: HashMap<fontdb::ID, Option<f64>>,
+    /// The OpenType features in the `GSUB` table of every font queried for `font-variant-caps`.
+    substitution_features: HashMap<fontdb::ID, Vec<[u8; 4]>>,
     pub last_text_align: TextAlign,
     pub last_writing_mode: WritingMode,
 }
@@ -46,6 +239,10 @@ impl Default for TextContext {
     fn default() -> Self {
         Self {
             font_system: FontSystem::new(),
+            font_faces: Vec::new(),
+            font_loader: FontLoader::default(),
+            x_height_aspects: HashMap::new(),
+            substitution_features: HashMap::new(),
             last_text_align: TextAlign::default(),
             last_writing_mode: WritingMode::default(),
         }
@@ -56,6 +253,10 @@ impl TextContext {
     pub fn new(font_system: FontSystem) -> Self {
         Self {
             font_system,
+            font_faces: Vec::new(),
+            font_loader: FontLoader::default(),
+            x_height_aspects: HashMap::new(),
+            substitution_features: HashMap::new(),
             last_text_align: TextAlign::default(),
             last_writing_mode: WritingMode::default(),
         }
@@ -66,34 +267,120 @@ impl TextContext {
         &mut self.font_system
     }
 
+    /// Sets the `@font-face` rules of the document about to be laid out, replacing those of the previous document. The
+    /// rules with a `url()` source are declared to the font loader, which keeps the timeline of fonts already known.
+    pub fn set_font_faces(&mut self, font_faces: Vec<FontFace>) {
+        for face in &font_faces {
+            if face.urls().next().is_some() {
+                self.font_loader.declare(&face.family, face.display);
+            }
+        }
+
+        self.font_faces = font_faces;
+    }
+
+    /// The `font-display` timeline of the web fonts.
+    pub const fn font_loader(&self) -> &FontLoader {
+        &self.font_loader
+    }
+
+    /// Get a mutable reference to the `font-display` timeline of the web fonts, to start their downloads and advance
+    /// their periods.
+    pub const fn font_loader_mut(&mut self) -> &mut FontLoader {
+        &mut self.font_loader
+    }
+
+    /// Registers the downloaded data of a web font under the family name of its `@font-face` rule, if it arrived before
+    /// the end of its swap period or another face of the family already did. Data the font database can not read
+    /// ends the block period of the font like a failed download.
+    ///
+    /// # Arguments
+    /// * `family` - The family name of the `@font-face` rule.
+    /// * `data` - The font file, in a format the font database reads (TrueType or OpenType).
+    /// * `now` - The instant the data arrived.
+    ///
+    /// # Returns
+    /// Whether the rendering of the text using the font changed, in which case it needs to be laid out and rendered
+    /// again.
+    pub fn load_web_font(&mut self, family: &str, data: Vec<u8>, now: Instant) -> bool {
+        self.font_loader.advance(now);
+        let pending = matches!(
+            self.font_loader.state(family),
+            Some(FontLoadState::BlockPeriod(_) | FontLoadState::SwapPeriod(_))
+        );
+        if !pending && self.font_loader.rendering(family) != Some(FontRendering::Font) {
+            return false;
+        }
+
+        let db = self.font_system.db_mut();
+        let ids = db.load_font_source(fontdb::Source::Binary(Arc::new(data)));
+        if ids.is_empty() {
+            return self.font_loader.fail(family);
+        }
+
+        // The family name inside the font file need not match the one of the rule, which is the one text refers to.
+        for id in ids {
+            if let Some(mut info) = db.face(id).cloned() {
+                db.remove_face(id);
+                info.families = vec![(family.to_string(), fontdb::Language::English_UnitedStates)];
+                db.push_face_info(info);
+            }
+        }
+
+        self.font_loader.finish(family, now);
+        true
+    }
+
     pub fn measure_text_that_fits<'text>(
         &mut self,
         text: &'text str,
         text_description: &TextDescription,
         max_width: f64,
     ) -> (Text, Option<&'text str>) {
+        const MAX_SPACING_PASSES: usize = 4;
+
         // NOTE: CSS allows line-height: 0, but cosmic-text requires a positive line height.
-        let line_height_px = text_description.line_height.max(0.1) * text_description.font_size_px;
+        let line_height_px = text_description.line_height_px.max(0.1);
 
-        let metrics = Metrics::new(text_description.font_size_px as f32, line_height_px as f32);
+        let font_size_px = self.adjusted_font_size(text_description);
+
+        let metrics = Metrics::new(font_size_px as f32, line_height_px as f32);
         let family = Self::resolve_font_family(text_description.font_family);
         let weight = Self::resolve_font_weight(text_description.font_weight);
+        let small_caps = self.small_caps_synthesis(text_description);
         let attrs = Attrs::new()
             .family(family)
             .weight(weight)
-            .stretch(Stretch::Normal);
+            .stretch(Stretch::Normal)
+            .font_features(text_description.font_features(small_caps));
 
-        let wrap_mode = match text_description.whitespace {
-            Whitespace::Pre => Wrap::None,
-            _ => Wrap::Word,
-        };
+        let wrap_mode = text_description.wrap();
+        let spacing = text_description.spacing;
 
         let mut buffer = Buffer::new(&mut self.font_system, metrics);
         buffer.set_size(&mut self.font_system, Some(max_width as f32), None);
         buffer.set_wrap(&mut self.font_system, wrap_mode);
-        buffer.set_text(&mut self.font_system, text, &attrs, Shaping::Advanced, Some(Align::Left));
+        self.set_buffer_text(&mut buffer, text, &attrs, text_description, small_caps);
         buffer.shape_until_scroll(&mut self.font_system, false);
 
+        // cosmic-text breaks the lines without the letter and word spacing, so a first line that no longer fits once
+        // spaced is broken again with the wrap width narrowed by its overflow.
+        let mut wrap_width = max_width;
+        for _ in 0..MAX_SPACING_PASSES {
+            let overflow = buffer
+                .layout_runs()
+                .next()
+                .map_or(0.0, |run| f64::from(run.line_w) + spacing.line_extra(&run) - max_width);
+
+            if overflow <= 0.0 || wrap_width - overflow <= 0.0 {
+                break;
+            }
+
+            wrap_width -= overflow;
+            buffer.set_size(&mut self.font_system, Some(wrap_width as f32), None);
+            buffer.shape_until_scroll(&mut self.font_system, false);
+        }
+
         let mut run_count = 0;
         let mut first_run_end = text.len();
         for run in buffer.layout_runs() {
@@ -111,10 +398,18 @@ impl TextContext {
             let fitted_text = &text[..split_index];
             let remaining_text = &text[split_index..];
 
-            buffer.set_text(&mut self.font_system, fitted_text, &attrs, Shaping::Advanced, Some(Align::Left));
+            // A line broken at a soft hyphen ends with a visible hyphen.
+            let fitted_text = match fitted_text.strip_suffix(SOFT_HYPHEN) {
+                Some(hyphenated) if text_description.hyphens != Hyphens::None => {
+                    Cow::Owned(format!("{hyphenated}{}", text_description.hyphenate_character))
+                }
+                _ => Cow::Borrowed(fitted_text),
+            };
+
+            self.set_buffer_text(&mut buffer, &fitted_text, &attrs, text_description, small_caps);
             buffer.shape_until_scroll(&mut self.font_system, false);
 
-            let measured = TextContext::extract_text_metrics(&buffer, text_description, fitted_text);
+            let measured = TextContext::extract_text_metrics(&buffer, text_description, &fitted_text);
             return (Text { buffer, ..measured }, Some(remaining_text));
         }
 
@@ -124,7 +419,7 @@ impl TextContext {
 
     /// Extract text metrics from an already-shaped buffer.
     fn extract_text_metrics(buffer: &Buffer, text_description: &TextDescription, text: &str) -> Text {
-        let line_height_px = text_description.line_height.max(0.1) * text_description.font_size_px;
+        let line_height_px = text_description.line_height_px.max(0.1);
         let preserve_whitespace = matches!(text_description.whitespace, Whitespace::Pre | Whitespace::PreWrap);
         let is_whitespace_only = text.trim().is_empty() && !preserve_whitespace;
 
@@ -137,7 +432,7 @@ impl TextContext {
                 run.glyphs.last().map_or(0.0, |g| g.x + g.w)
             } else {
                 run.line_w
-            });
+            }) + text_description.spacing.line_extra(&run);
             max_width = max_width.max(w);
             last_line_width = w;
             line_count += 1;
@@ -145,7 +440,7 @@ impl TextContext {
 
         if is_whitespace_only && max_width == 0.0 {
             let space_count = text.chars().filter(|c| *c == ' ').count().max(1);
-            max_width = space_count as f64 * text_description.font_size_px * 0.25;
+            max_width = space_count as f64 * (text_description.font_size_px * 0.25 + text_description.spacing.word);
             last_line_width = max_width;
             if line_count == 0 {
                 line_count = 1;
@@ -168,12 +463,253 @@ impl TextContext {
         }
     }
 
-    fn resolve_font_family(font_family: &FontFamily) -> Family<'_> {
+    /// Sets the text of a buffer, selecting the font of each character. Without any `@font-face` rule the whole text
+    /// uses the family of `attrs`, otherwise the text is split into spans of characters sharing the same font. With
+    /// synthesized small capitals, the letters shown as small capitals get spans of their own at a smaller size.
+    fn set_buffer_text(
+        &mut self,
+        buffer: &mut Buffer,
+        text: &str,
+        attrs: &Attrs,
+        text_description: &TextDescription,
+        small_caps: SmallCapsSynthesis,
+    ) {
+        let (text, scaled) = if small_caps == SmallCapsSynthesis::Scale {
+            let (shown, scaled) = synthesize_small_caps(text, text_description.font_variant_caps);
+            (Cow::Owned(shown), scaled)
+        } else {
+            (Cow::Borrowed(text), Vec::new())
+        };
+
+        if self.font_faces.is_empty() && scaled.is_empty() {
+            buffer.set_text(&mut self.font_system, &text, attrs, Shaping::Advanced, Some(Align::Left));
+            return;
+        }
+
+        let font_faces = std::mem::take(&mut self.font_faces);
+        let fallbacks = self.fallback_fonts(&font_faces, text_description.font_family);
+
+        let metrics = buffer.metrics();
+        let small_metrics = Metrics::new(metrics.font_size * SMALL_CAPS_SCALE, metrics.line_height);
+        let span_attrs = |(font, small): (usize, bool)| {
+            let attrs = fallbacks[font].attrs(attrs);
+            if small {
+                attrs.metrics(small_metrics)
+            } else {
+                attrs
+            }
+        };
+
+        let mut spans = Vec::new();
+        let mut span_start = 0;
+        let mut span_key = None;
+
+        for (char_idx, (idx, ch)) in text.char_indices().enumerate() {
+            let font = fallbacks
+                .iter()
+                .position(|fallback| fallback.covers(ch))
+                .unwrap_or(fallbacks.len() - 1);
+            let key = (font, scaled.get(char_idx).copied().unwrap_or(false));
+
+            if let Some(current) = span_key
+                && current != key
+            {
+                spans.push((&text[span_start..idx], span_attrs(current)));
+                span_start = idx;
+            }
+
+            span_key = Some(key);
+        }
+
+        if let Some(current) = span_key {
+            spans.push((&text[span_start..], span_attrs(current)));
+        }
+
+        buffer.set_rich_text(&mut self.font_system, spans, attrs, Shaping::Advanced, Some(Align::Left));
+
+        self.font_faces = font_faces;
+    }
+
+    /// Whether the small or petite capitals of `font-variant-caps` come from the font or are synthesized, decided by
+    /// the `GSUB` table of the font the text is shaped with.
+    fn small_caps_synthesis(&mut self, text_description: &TextDescription) -> SmallCapsSynthesis {
+        let Some(feature) = text_description.font_variant_caps.lowercase_feature() else {
+            return SmallCapsSynthesis::None;
+        };
+
+        let family = Self::resolve_font_family(text_description.font_family);
+        let weight = Self::resolve_font_weight(text_description.font_weight);
+        let has_feature = self
+            .query_font(family, weight)
+            .is_some_and(|id| self.substitution_features(id).contains(&feature));
+
+        if has_feature {
+            SmallCapsSynthesis::Feature
+        } else {
+            SmallCapsSynthesis::Scale
+        }
+    }
+
+    /// Builds the fallback list of a `font-family`, in which every family declared with `@font-face` is replaced by
+    /// its faces whose `local()` source is installed or whose `url()` source has loaded. A character is rendered with
+    /// the first font of the list whose `unicode-range` covers it, the last font of the list covering every character.
+    ///
+    /// A face whose font is still downloading is left out, except during its block period, where it stands in as the
+    /// next font of the list with its text hidden.
+    fn fallback_fonts<'a>(&self, font_faces: &'a [FontFace], font_family: &'a FontFamily) -> Vec<FallbackFont<'a>> {
+        let mut fallbacks = Vec::new();
+
+        for name in font_family.names() {
+            if let FontFamilyName::Specific(specific) = name {
+                let declared: Vec<&FontFace> = font_faces
+                    .iter()
+                    .filter(|face| face.family.eq_ignore_ascii_case(specific))
+                    .collect();
+
+                if !declared.is_empty() {
+                    for face in declared {
+                        if let Some(local) = face.local_names().find(|local| self.has_font(local)) {
+                            fallbacks.push(FallbackFont {
+                                family: Family::Name(local),
+                                face: Some(face),
+                                hidden: false,
+                            });
+                            continue;
+                        }
+
+                        match self.font_loader.rendering(&face.family) {
+                            Some(FontRendering::Font) => fallbacks.push(FallbackFont {
+                                family: Family::Name(&face.family),
+                                face: Some(face),
+                                hidden: false,
+                            }),
+                            Some(FontRendering::Hidden) => fallbacks.push(FallbackFont {
+                                family: Family::SansSerif,
+                                face: Some(face),
+                                hidden: true,
+                            }),
+                            Some(FontRendering::Fallback) | None => {}
+                        }
+                    }
+
+                    continue;
+                }
+            }
+
+            fallbacks.push(FallbackFont {
+                family: Self::resolve_font_family_name(font_family, name),
+                face: None,
+                hidden: false,
+            });
+
+            return Self::resolve_hidden_fallbacks(fallbacks);
+        }
+
+        fallbacks.push(FallbackFont {
+            family: Family::SansSerif,
+            face: None,
+            hidden: false,
+        });
+
+        Self::resolve_hidden_fallbacks(fallbacks)
+    }
+
+    /// Lays out the text of every web font in its block period with the font following it in the list, so that the
+    /// layout keeps its shape when the text is shown. The last font of the list is never hidden.
+    fn resolve_hidden_fallbacks(mut fallbacks: Vec<FallbackFont<'_>>) -> Vec<FallbackFont<'_>> {
+        for idx in (0..fallbacks.len().saturating_sub(1)).rev() {
+            if fallbacks[idx].hidden {
+                fallbacks[idx].family = fallbacks[idx + 1].family;
+            }
+        }
+
+        fallbacks
+    }
+
+    /// The font size the text is shaped with. With `font-size-adjust`, a fallback font used instead of the first font of
+    /// `font-family` is scaled so that its x-height is the one asked for.
+    fn adjusted_font_size(&mut self, text_description: &TextDescription) -> f64 {
+        if text_description.font_size_adjust == FontSizeAdjust::None {
+            return text_description.font_size_px;
+        }
+
+        let weight = Self::resolve_font_weight(text_description.font_weight);
+        let primary_family = match &text_description.font_family.names()[0] {
+            FontFamilyName::Specific(name) => Family::Name(name),
+            name => Self::resolve_font_family_name(text_description.font_family, name),
+        };
+        let used_family = Self::resolve_font_family(text_description.font_family);
+
+        let primary = self.query_font(primary_family, weight);
+        let used = self.query_font(used_family, weight);
+
+        let Some(used) = used.filter(|used| primary != Some(*used)) else {
+            return text_description.font_size_px;
+        };
+
+        let primary_aspect = primary.and_then(|primary| self.x_height_aspect(primary));
+        let used_aspect = self.x_height_aspect(used);
+
+        adjust_font_size(text_description.font_size_px, text_description.font_size_adjust, primary_aspect, used_aspect)
+    }
+
+    /// The installed font matching a family and weight best.
+    fn query_font(&self, family: Family, weight: Weight) -> Option<fontdb::ID> {
+        self.font_system.db().query(&fontdb::Query {
+            families: &[family],
+            weight,
+            stretch: Stretch::Normal,
+            style: Style::Normal,
+        })
+    }
+
+    fn x_height_aspect(&mut self, id: fontdb::ID) -> Option<f64> {
+        if let Some(aspect) = self.x_height_aspects.get(&id) {
+            return *aspect;
+        }
+
+        let aspect = self
+            .font_system
+            .db()
+            .with_face_data(id, read_x_height_aspect)
+            .flatten();
+        self.x_height_aspects.insert(id, aspect);
+
+        aspect
+    }
+
+    fn substitution_features(&mut self, id: fontdb::ID) -> &[[u8; 4]] {
+        if !self.substitution_features.contains_key(&id) {
+            let features = self
+                .font_system
+                .db()
+                .with_face_data(id, read_substitution_features)
+                .unwrap_or_default();
+            self.substitution_features.insert(id, features);
+        }
+
+        &self.substitution_features[&id]
+    }
+
+    /// Checks whether a font family is installed in the font system.
+    fn has_font(&self, family: &str) -> bool {
+        self.font_system.db().faces().any(|face| {
+            face.families
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case(family))
+        })
+    }
+
+    fn resolve_font_family(font_family: &FontFamily) -> Family<'static> {
+        Self::resolve_font_family_name(font_family, &font_family.names()[0])
+    }
+
+    fn resolve_font_family_name(font_family: &FontFamily, name: &FontFamilyName) -> Family<'static> {
         if font_family.is_monospace() {
             return Family::Monospace;
         }
 
-        match &font_family.names()[0] {
+        match name {
             FontFamilyName::Generic(generic) => match generic {
                 GenericName::Serif => Family::Serif,
                 GenericName::Monospace => Family::Monospace,
@@ -186,19 +722,94 @@ impl TextContext {
         }
     }
 
-    const fn resolve_font_weight(font_weight: u16) -> Weight {
-        match font_weight {
-            100 => Weight::THIN,
-            200 => Weight::EXTRA_LIGHT,
-            300 => Weight::LIGHT,
-            500 => Weight::MEDIUM,
-            600 => Weight::SEMIBOLD,
-            700 => Weight::BOLD,
-            800 => Weight::EXTRA_BOLD,
-            900 => Weight::BLACK,
-            _ => Weight::NORMAL,
+    /// The exact weight is kept rather than rounded to a hundred, cosmic-text sets it as the `wght` axis of a variable
+    /// font when shaping and rasterizing its glyphs, while a static font is matched to the face of the closest weight.
+    fn resolve_font_weight(font_weight: f32) -> Weight {
+        Weight(font_weight.round().clamp(1.0, 1000.0) as u16)
+    }
+}
+
+/// Scales a font size so that a fallback font with the x-height `used_aspect` has the x-height asked for by
+/// `font-size-adjust`, the font size being kept when either x-height is unknown.
+fn adjust_font_size(
+    font_size: f64,
+    font_size_adjust: FontSizeAdjust,
+    primary_aspect: Option<f64>,
+    used_aspect: Option<f64>,
+) -> f64 {
+    let target = match font_size_adjust {
+        FontSizeAdjust::None => None,
+        FontSizeAdjust::Number(aspect) => Some(aspect),
+        FontSizeAdjust::FromFont => primary_aspect,
+    };
+
+    match (target, used_aspect) {
+        (Some(target), Some(used)) if used > 0.0 => font_size * target / used,
+        _ => font_size,
+    }
+}
+
+/// The text shown for synthesized small capitals of `font-variant-caps`, in which the lowercase letters, and with
+/// `all-small-caps` or `all-petite-caps` the uppercase letters as well, are shown as scaled uppercase letters.
+///
+/// # Returns
+/// The text with the lowercase letters uppercased, along with whether each of its characters is scaled down. A letter
+/// whose uppercase form is not a single character of the same length, such as `ß`, is kept as it is so that the text
+/// keeps the byte offsets of the original.
+fn synthesize_small_caps(text: &str, caps: FontVariantCaps) -> (String, Vec<bool>) {
+    let mut shown = String::with_capacity(text.len());
+    let mut scaled = Vec::with_capacity(text.len());
+
+    for ch in text.chars() {
+        let mut uppercase = ch.to_uppercase();
+        let single = match (uppercase.next(), uppercase.next()) {
+            (Some(upper), None) if upper.len_utf8() == ch.len_utf8() => Some(upper),
+            _ => None,
+        };
+
+        match single {
+            Some(upper) if ch.is_lowercase() => {
+                shown.push(upper);
+                scaled.push(true);
+            }
+            _ => {
+                shown.push(ch);
+                scaled.push(caps.includes_uppercase() && ch.is_uppercase());
+            }
         }
     }
+
+    (shown, scaled)
+}
+
+/// Reads the tags of the features in the `GSUB` table of a font, the glyph substitutions such as `smcp` the font
+/// supports. A font without the table has none.
+fn read_substitution_features(data: &[u8], index: u32) -> Vec<[u8; 4]> {
+    let Some(gsub) = ttf_parser::Face::parse(data, index)
+        .ok()
+        .and_then(|face| face.tables().gsub)
+    else {
+        return Vec::new();
+    };
+
+    let mut tags: Vec<[u8; 4]> = gsub
+        .features
+        .into_iter()
+        .map(|feature| feature.tag.to_bytes())
+        .collect();
+    tags.dedup();
+
+    tags
+}
+
+/// Reads the x-height of a font relative to its em size, `None` for fonts that do not record their x-height. The
+/// `index` selects the font of a font collection.
+fn read_x_height_aspect(data: &[u8], index: u32) -> Option<f64> {
+    let face = ttf_parser::Face::parse(data, index).ok()?;
+    let units_per_em = face.units_per_em();
+    let x_height = face.x_height()?;
+
+    (units_per_em > 0 && x_height > 0).then(|| f64::from(x_height) / f64::from(units_per_em))
 }
 
 #[cfg(test)]
@@ -210,10 +821,21 @@ mod tests {
         let mut text_ctx = TextContext::default();
         let text_desc = TextDescription {
             whitespace: &Whitespace::Normal,
-            line_height: 1.2,
+            line_height_px: 19.2,
             font_family: &FontFamily::default(),
-            font_weight: 400,
+            font_weight: 400.0,
             font_size_px: 16.0,
+            font_size_adjust: FontSizeAdjust::None,
+            word_break: WordBreak::Normal,
+            overflow_wrap: OverflowWrap::Normal,
+            hyphens: Hyphens::Manual,
+            hyphenate_character: "-",
+            spacing: TextSpacing::default(),
+            font_kerning: FontKerning::Auto,
+            font_variant_ligatures: FontVariantLigatures::default(),
+            font_variant_numeric: FontVariantNumeric::default(),
+            font_variant_caps: FontVariantCaps::Normal,
+            font_feature_settings: &FontFeatureSettings::default(),
         };
 
         let (measured, remaining) = text_ctx.measure_text_that_fits(
@@ -225,4 +847,349 @@ mod tests {
         assert!(measured.height > 0.0);
         assert_eq!(remaining, Some(" This is a test of the text measurement system."));
     }
+
+    #[test]
+    fn test_wrap_mode() {
+        let mut text_desc = TextDescription {
+            whitespace: &Whitespace::Normal,
+            line_height_px: 19.2,
+            font_family: &FontFamily::default(),
+            font_weight: 400.0,
+            font_size_px: 16.0,
+            font_size_adjust: FontSizeAdjust::None,
+            word_break: WordBreak::Normal,
+            overflow_wrap: OverflowWrap::Normal,
+            hyphens: Hyphens::Manual,
+            hyphenate_character: "-",
+            spacing: TextSpacing::default(),
+            font_kerning: FontKerning::Auto,
+            font_variant_ligatures: FontVariantLigatures::default(),
+            font_variant_numeric: FontVariantNumeric::default(),
+            font_variant_caps: FontVariantCaps::Normal,
+            font_feature_settings: &FontFeatureSettings::default(),
+        };
+        assert_eq!(text_desc.wrap(), Wrap::Word);
+
+        text_desc.overflow_wrap = OverflowWrap::BreakWord;
+        assert_eq!(text_desc.wrap(), Wrap::WordOrGlyph);
+
+        text_desc.word_break = WordBreak::BreakAll;
+        assert_eq!(text_desc.wrap(), Wrap::Glyph);
+
+        text_desc.whitespace = &Whitespace::Pre;
+        assert_eq!(text_desc.wrap(), Wrap::None);
+    }
+
+    #[test]
+    fn test_font_size_adjust_keeps_x_height_of_primary_font() {
+        let primary_aspect = 0.52;
+        let fallback_aspect = 0.45;
+
+        let font_size = adjust_font_size(16.0, FontSizeAdjust::FromFont, Some(primary_aspect), Some(fallback_aspect));
+        assert!((font_size * fallback_aspect - 16.0 * primary_aspect).abs() < 1e-9);
+
+        let font_size = adjust_font_size(16.0, FontSizeAdjust::Number(0.5), None, Some(fallback_aspect));
+        assert!((font_size * fallback_aspect - 8.0).abs() < 1e-9);
+
+        assert_eq!(adjust_font_size(16.0, FontSizeAdjust::FromFont, None, Some(fallback_aspect)), 16.0);
+        assert_eq!(adjust_font_size(16.0, FontSizeAdjust::None, Some(primary_aspect), Some(fallback_aspect)), 16.0);
+    }
+
+    #[test]
+    fn test_resolve_font_weight_keeps_exact_weight() {
+        assert_eq!(TextContext::resolve_font_weight(350.0), Weight(350));
+        assert_eq!(TextContext::resolve_font_weight(700.0), Weight::BOLD);
+        assert_eq!(TextContext::resolve_font_weight(1200.0), Weight(1000));
+    }
+
+    #[test]
+    fn test_read_x_height_aspect() {
+        use io::embedded::OPEN_SANS_REGULAR;
+
+        assert_eq!(read_x_height_aspect(&OPEN_SANS_REGULAR.load(), 0), Some(1096.0 / 2048.0));
+        assert_eq!(read_x_height_aspect(b"not a font", 0), None);
+    }
+
+    #[test]
+    fn test_letter_spacing_widens_measured_text() {
+        let mut text_ctx = TextContext::default();
+        let mut text_desc = TextDescription {
+            whitespace: &Whitespace::Normal,
+            line_height_px: 19.2,
+            font_family: &FontFamily::default(),
+            font_weight: 400.0,
+            font_size_px: 16.0,
+            font_size_adjust: FontSizeAdjust::None,
+            word_break: WordBreak::Normal,
+            overflow_wrap: OverflowWrap::Normal,
+            hyphens: Hyphens::Manual,
+            hyphenate_character: "-",
+            spacing: TextSpacing::default(),
+            font_kerning: FontKerning::Auto,
+            font_variant_ligatures: FontVariantLigatures::default(),
+            font_variant_numeric: FontVariantNumeric::default(),
+            font_variant_caps: FontVariantCaps::Normal,
+            font_feature_settings: &FontFeatureSettings::default(),
+        };
+
+        let (normal, _) = text_ctx.measure_text_that_fits("AV", &text_desc, 1000.0);
+
+        text_desc.spacing.letter = 2.0;
+        let (spaced, _) = text_ctx.measure_text_that_fits("AV", &text_desc, 1000.0);
+
+        assert!((spaced.width - normal.width - 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_spacing_is_included_when_fitting_lines() {
+        let mut text_ctx = TextContext::default();
+        let mut text_desc = TextDescription {
+            whitespace: &Whitespace::Normal,
+            line_height_px: 19.2,
+            font_family: &FontFamily::default(),
+            font_weight: 400.0,
+            font_size_px: 16.0,
+            font_size_adjust: FontSizeAdjust::None,
+            word_break: WordBreak::Normal,
+            overflow_wrap: OverflowWrap::Normal,
+            hyphens: Hyphens::Manual,
+            hyphenate_character: "-",
+            spacing: TextSpacing::default(),
+            font_kerning: FontKerning::Auto,
+            font_variant_ligatures: FontVariantLigatures::default(),
+            font_variant_numeric: FontVariantNumeric::default(),
+            font_variant_caps: FontVariantCaps::Normal,
+            font_feature_settings: &FontFeatureSettings::default(),
+        };
+
+        let (normal, remaining) = text_ctx.measure_text_that_fits("one two three", &text_desc, 1000.0);
+        assert_eq!(remaining, None);
+
+        text_desc.spacing.word = 10.0;
+        let (spaced, remaining) = text_ctx.measure_text_that_fits("one two three", &text_desc, normal.width + 5.0);
+
+        assert!(remaining.is_some());
+        assert!(spaced.width <= normal.width + 5.0);
+    }
+
+    #[test]
+    fn test_font_variant_overrides_font_feature_settings() {
+        use css_values::text::{FontFeatureSetting, NumericFigure};
+
+        let font_feature_settings = FontFeatureSettings::new(&[
+            FontFeatureSetting {
+                tag: *b"smcp",
+                value: 1,
+            },
+            FontFeatureSetting {
+                tag: *b"lnum",
+                value: 0,
+            },
+        ]);
+        let text_desc = TextDescription {
+            whitespace: &Whitespace::Normal,
+            line_height_px: 19.2,
+            font_family: &FontFamily::default(),
+            font_weight: 400.0,
+            font_size_px: 16.0,
+            font_size_adjust: FontSizeAdjust::None,
+            word_break: WordBreak::Normal,
+            overflow_wrap: OverflowWrap::Normal,
+            hyphens: Hyphens::Manual,
+            hyphenate_character: "-",
+            spacing: TextSpacing::default(),
+            font_kerning: FontKerning::Auto,
+            font_variant_ligatures: FontVariantLigatures::default(),
+            font_variant_numeric: FontVariantNumeric {
+                figure: Some(NumericFigure::Lining),
+                ..FontVariantNumeric::default()
+            },
+            font_variant_caps: FontVariantCaps::Normal,
+            font_feature_settings: &font_feature_settings,
+        };
+
+        assert_eq!(text_desc.feature_settings(SmallCapsSynthesis::None), [(*b"smcp", 1), (*b"lnum", 1)]);
+    }
+
+    #[test]
+    fn test_smcp_selects_small_cap_glyphs() {
+        use css_values::text::FontFeatureSetting;
+        use io::embedded::ROBOTO_MONO_REGULAR;
+
+        let mut text_ctx = TextContext::default();
+        let db = text_ctx.font_system_mut().db_mut();
+        db.load_font_source(fontdb::Source::Binary(Arc::new(ROBOTO_MONO_REGULAR.load())));
+        db.set_monospace_family("Roboto Mono");
+
+        let font_family = FontFamily::new(vec![FontFamilyName::Generic(GenericName::Monospace)]);
+        let small_caps = FontFeatureSettings::new(&[FontFeatureSetting {
+            tag: *b"smcp",
+            value: 1,
+        }]);
+        let mut text_desc = TextDescription {
+            whitespace: &Whitespace::Normal,
+            line_height_px: 19.2,
+            font_family: &font_family,
+            font_weight: 400.0,
+            font_size_px: 16.0,
+            font_size_adjust: FontSizeAdjust::None,
+            word_break: WordBreak::Normal,
+            overflow_wrap: OverflowWrap::Normal,
+            hyphens: Hyphens::Manual,
+            hyphenate_character: "-",
+            spacing: TextSpacing::default(),
+            font_kerning: FontKerning::Auto,
+            font_variant_ligatures: FontVariantLigatures::default(),
+            font_variant_numeric: FontVariantNumeric::default(),
+            font_variant_caps: FontVariantCaps::Normal,
+            font_feature_settings: &FontFeatureSettings::default(),
+        };
+        let glyph_ids = |text: &Text| -> Vec<u16> {
+            text.buffer
+                .layout_runs()
+                .flat_map(|run| run.glyphs.iter().map(|glyph| glyph.glyph_id))
+                .collect()
+        };
+
+        let (lowercase, _) = text_ctx.measure_text_that_fits("abc", &text_desc, 1000.0);
+
+        text_desc.font_feature_settings = &small_caps;
+        let (small_capitals, _) = text_ctx.measure_text_that_fits("abc", &text_desc, 1000.0);
+
+        assert_eq!(glyph_ids(&small_capitals).len(), 3);
+        assert!(
+            glyph_ids(&lowercase)
+                .iter()
+                .zip(glyph_ids(&small_capitals))
+                .all(|(lowercase, small_capital)| *lowercase != small_capital)
+        );
+    }
+
+    #[test]
+    fn test_synthesize_small_caps() {
+        assert_eq!(
+            synthesize_small_caps("Straße 1", FontVariantCaps::SmallCaps),
+            ("STRAßE 1".to_string(), vec![false, true, true, true, false, true, false, false])
+        );
+        assert_eq!(synthesize_small_caps("Ab", FontVariantCaps::AllSmallCaps), ("AB".to_string(), vec![true, true]));
+    }
+
+    #[test]
+    fn test_small_caps_are_synthesized_without_smcp() {
+        use io::embedded::{OPEN_SANS_REGULAR, ROBOTO_MONO_REGULAR};
+
+        let mut text_ctx = TextContext::default();
+        let db = text_ctx.font_system_mut().db_mut();
+        db.load_font_source(fontdb::Source::Binary(Arc::new(ROBOTO_MONO_REGULAR.load())));
+        db.load_font_source(fontdb::Source::Binary(Arc::new(OPEN_SANS_REGULAR.load())));
+        db.set_monospace_family("Roboto Mono");
+        db.set_serif_family("Open Sans");
+
+        assert!(read_substitution_features(&ROBOTO_MONO_REGULAR.load(), 0).contains(b"smcp"));
+        assert!(!read_substitution_features(&OPEN_SANS_REGULAR.load(), 0).contains(b"smcp"));
+
+        let monospace = FontFamily::new(vec![FontFamilyName::Generic(GenericName::Monospace)]);
+        let serif = FontFamily::new(vec![FontFamilyName::Generic(GenericName::Serif)]);
+        let mut text_desc = TextDescription {
+            whitespace: &Whitespace::Normal,
+            line_height_px: 19.2,
+            font_family: &monospace,
+            font_weight: 400.0,
+            font_size_px: 16.0,
+            font_size_adjust: FontSizeAdjust::None,
+            word_break: WordBreak::Normal,
+            overflow_wrap: OverflowWrap::Normal,
+            hyphens: Hyphens::Manual,
+            hyphenate_character: "-",
+            spacing: TextSpacing::default(),
+            font_kerning: FontKerning::Auto,
+            font_variant_ligatures: FontVariantLigatures::default(),
+            font_variant_numeric: FontVariantNumeric::default(),
+            font_variant_caps: FontVariantCaps::Normal,
+            font_feature_settings: &FontFeatureSettings::default(),
+        };
+        assert_eq!(text_ctx.small_caps_synthesis(&text_desc), SmallCapsSynthesis::None);
+
+        text_desc.font_variant_caps = FontVariantCaps::SmallCaps;
+        assert_eq!(text_ctx.small_caps_synthesis(&text_desc), SmallCapsSynthesis::Feature);
+
+        text_desc.font_family = &serif;
+        assert_eq!(text_ctx.small_caps_synthesis(&text_desc), SmallCapsSynthesis::Scale);
+        assert!(
+            text_desc
+                .feature_settings(SmallCapsSynthesis::Scale)
+                .is_empty()
+        );
+
+        // The scaled uppercase letters are narrower than the uppercase letters of the font.
+        let (small_capitals, _) = text_ctx.measure_text_that_fits("small", &text_desc, 1000.0);
+        text_desc.font_variant_caps = FontVariantCaps::Normal;
+        let (capitals, _) = text_ctx.measure_text_that_fits("SMALL", &text_desc, 1000.0);
+        assert!(small_capitals.width < capitals.width);
+    }
+
+    #[test]
+    fn test_web_font_text_is_hidden_during_block_period() {
+        use std::time::Duration;
+
+        use css_style::FontFaceSource;
+        use css_values::{text::FontDisplay, unicode_range::UnicodeRange};
+
+        let mut text_ctx = TextContext::default();
+        text_ctx.set_font_faces(vec![FontFace {
+            family: "Web".to_string(),
+            sources: vec![FontFaceSource::Url("web.woff2".to_string())],
+            unicode_ranges: vec![UnicodeRange::ALL],
+            display: FontDisplay::Block,
+        }]);
+
+        let now = Instant::now();
+        text_ctx.font_loader_mut().start("Web", now);
+
+        let font_family = FontFamily::new(vec![
+            FontFamilyName::Specific("Web".to_string()),
+            FontFamilyName::Generic(GenericName::SansSerif),
+        ]);
+        let text_desc = TextDescription {
+            whitespace: &Whitespace::Normal,
+            line_height_px: 19.2,
+            font_family: &font_family,
+            font_weight: 400.0,
+            font_size_px: 16.0,
+            font_size_adjust: FontSizeAdjust::None,
+            word_break: WordBreak::Normal,
+            overflow_wrap: OverflowWrap::Normal,
+            hyphens: Hyphens::Manual,
+            hyphenate_character: "-",
+            spacing: TextSpacing::default(),
+            font_kerning: FontKerning::Auto,
+            font_variant_ligatures: FontVariantLigatures::default(),
+            font_variant_numeric: FontVariantNumeric::default(),
+            font_variant_caps: FontVariantCaps::Normal,
+            font_feature_settings: &FontFeatureSettings::default(),
+        };
+        let glyph_metadata = |text: &Text| {
+            text.buffer
+                .layout_runs()
+                .flat_map(|run| run.glyphs.iter().map(|glyph| glyph.metadata))
+                .collect::<Vec<_>>()
+        };
+
+        let (hidden, _) = text_ctx.measure_text_that_fits("Hello", &text_desc, 500.0);
+        assert!(!glyph_metadata(&hidden).is_empty());
+        assert!(
+            glyph_metadata(&hidden)
+                .iter()
+                .all(|metadata| *metadata == PENDING_FONT_METADATA)
+        );
+
+        assert!(
+            text_ctx
+                .font_loader_mut()
+                .advance(now + Duration::from_secs(3))
+        );
+        let (shown, _) = text_ctx.measure_text_that_fits("Hello", &text_desc, 500.0);
+        assert!(glyph_metadata(&shown).iter().all(|metadata| *metadata == 0));
+        assert!((shown.width - hidden.width).abs() < 0.001);
+    }
 }
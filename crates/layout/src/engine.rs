@@ -1,16 +1,19 @@
+use std::collections::HashMap;
+
 use crate::{
-    LayoutNode, LayoutTree,
+    CornerRadii, LayoutColors, LayoutNode, LayoutTree, ReplacedKind,
     context::{FloatContext, ImageContext, LayoutContext, PositionContext, TextContext},
     mode::{
         LayoutMode,
         block::{BlockFlowState, BlockLayout},
-        inline::{InlineContext, InlineLayout},
+        inline::{InlineContext, InlineLayout, replaced_colors},
     },
-    primitives::Rect,
+    primitives::{Rect, Size},
 };
 use css_display::{BoxTree, LayoutNodeId};
-use css_style::ComputedStyle;
-use html_dom::{DocumentRoot, NodeId};
+use css_style::{ComputedStyle, Container, ContainerSizes, LayoutInvalidationSet, StyleTree};
+use css_values::container::ContainerType;
+use html_dom::{DocumentRoot, NodeData, NodeId, TextControl, control_value};
 
 use tracing::{trace, warn};
 
@@ -28,6 +31,12 @@ pub(crate) struct LayoutState<'a, 'input> {
     pub input: &'a mut LayoutInput<'input>,
     pub position_ctx: &'a mut PositionContext,
     pub float_ctx: &'a mut FloatContext,
+
+    /// The area of the page that is shown, `content-visibility: auto` elements outside of it skip their contents.
+    pub visible_area: Rect,
+
+    /// The last size of the contents of the `content-visibility: auto` elements that were laid out with their contents.
+    pub remembered_sizes: &'a mut HashMap<NodeId, Size>,
 }
 
 impl<'a, 'input> LayoutState<'a, 'input> {
@@ -36,12 +45,17 @@ impl<'a, 'input> LayoutState<'a, 'input> {
         input: &'a mut LayoutInput<'input>,
         position_ctx: &'a mut PositionContext,
         float_ctx: &'a mut FloatContext,
+        remembered_sizes: &'a mut HashMap<NodeId, Size>,
     ) -> Self {
+        let visible_area = position_ctx.viewport();
+
         Self {
             nodes,
             input,
             position_ctx,
             float_ctx,
+            visible_area,
+            remembered_sizes,
         }
     }
 }
@@ -59,6 +73,22 @@ impl LayoutTree {
     /// [`LayoutTree`] where the image and all of its siblings / ancestors have
     /// been repositioned correctly.
     pub fn compute_layout(input: &mut LayoutInput, viewport: Rect) -> Self {
+        Self::compute_layout_with_remembered_sizes(input, viewport, HashMap::new())
+    }
+
+    /// Compute layout for an entire style tree like [`LayoutTree::compute_layout`], keeping the remembered sizes of
+    /// a previous layout of the same document. `content-visibility: auto` elements with a
+    /// `contain-intrinsic-size: auto` that were shown before keep the size of their contents while they are skipped.
+    ///
+    /// # Arguments
+    /// * `input` - The trees to lay out.
+    /// * `viewport` - The viewport to lay out in.
+    /// * `remembered_sizes` - The [`LayoutTree::remembered_sizes`] of the previous layout.
+    pub fn compute_layout_with_remembered_sizes(
+        input: &mut LayoutInput,
+        viewport: Rect,
+        mut remembered_sizes: HashMap<NodeId, Size>,
+    ) -> Self {
         let mut position_ctx = PositionContext::new(viewport);
         let mut float_ctx = FloatContext::new();
 
@@ -69,7 +99,7 @@ impl LayoutTree {
         let mut root_nodes = Vec::with_capacity(root_layout_ids.len());
         let mut nodes = vec![None; input.box_tree.nodes.len()];
 
-        let mut state = LayoutState::new(&mut nodes, input, &mut position_ctx, &mut float_ctx);
+        let mut state = LayoutState::new(&mut nodes, input, &mut position_ctx, &mut float_ctx, &mut remembered_sizes);
         let root_style = ComputedStyle::default();
 
         for layout_id in &root_layout_ids {
@@ -90,6 +120,7 @@ impl LayoutTree {
             nodes,
             content_height,
             content_width: max_width,
+            remembered_sizes,
         };
 
         trace!("Initial layout complete, resolving deferred positions...");
@@ -102,6 +133,7 @@ impl LayoutTree {
                 .is_sorted_by(|a, b| a.layout_id.index() < b.layout_id.index())
         );
 
+        tree.update_scrollable_overflow();
         tree
     }
 
@@ -114,6 +146,78 @@ impl LayoutTree {
         viewport: Rect,
         layout_tree: &mut LayoutTree,
         input: &mut LayoutInput<'css>,
+    ) {
+        Self::relayout_node_in_place(node_id, viewport, viewport, layout_tree, input);
+        layout_tree.update_scrollable_overflow();
+    }
+
+    /// Whether any `content-visibility: auto` element whose contents were skipped is now inside the visible area.
+    ///
+    /// # Arguments
+    /// * `visible_area` - The area of the page that is shown, i.e. the viewport moved by the scroll offset.
+    #[must_use]
+    pub fn has_skipped_content(&self, visible_area: Rect) -> bool {
+        !self.skipped_nodes(visible_area).is_empty()
+    }
+
+    /// Lays out the contents of every element that were skipped and that are now inside the visible area, moving the
+    /// content after them when their size changes.
+    ///
+    /// # Arguments
+    /// * `visible_area` - The area of the page that is shown, i.e. the viewport moved by the scroll offset.
+    /// * `viewport` - The viewport the layout tree was computed with.
+    /// * `layout_tree` - The layout tree to update in place.
+    /// * `input` - The trees the layout tree was built from.
+    ///
+    /// # Returns
+    /// The DOM nodes whose contents were laid out.
+    pub fn reveal_skipped<'css>(
+        visible_area: Rect,
+        viewport: Rect,
+        layout_tree: &mut LayoutTree,
+        input: &mut LayoutInput<'css>,
+    ) -> Vec<NodeId> {
+        let node_ids = layout_tree.skipped_nodes(visible_area);
+
+        for node_id in &node_ids {
+            // The element was found inside the visible area, so it is shown regardless of where it ends up.
+            if let Some(layout_id) = input.box_tree.dom_to_layout[node_id.index()]
+                && let Some(node) = &mut layout_tree.nodes[layout_id.index()]
+            {
+                node.skipped = false;
+            }
+
+            Self::relayout_node_in_place(node_id, viewport, visible_area, layout_tree, input);
+        }
+
+        if !node_ids.is_empty() {
+            layout_tree.update_scrollable_overflow();
+        }
+
+        node_ids
+    }
+
+    /// The DOM nodes of the `content-visibility: auto` elements whose contents were skipped and that are inside the
+    /// visible area.
+    ///
+    /// # Arguments
+    /// * `visible_area` - The area of the page that is shown, i.e. the viewport moved by the scroll offset.
+    #[must_use]
+    pub fn skipped_nodes(&self, visible_area: Rect) -> Vec<NodeId> {
+        self.nodes
+            .iter()
+            .flatten()
+            .filter(|node| node.skipped && BlockLayout::is_in_visible_area(node.border_box(), visible_area))
+            .filter_map(|node| node.node_id)
+            .collect()
+    }
+
+    fn relayout_node_in_place<'css>(
+        node_id: &NodeId,
+        viewport: Rect,
+        visible_area: Rect,
+        layout_tree: &mut LayoutTree,
+        input: &mut LayoutInput<'css>,
     ) {
         let Some(layout_id) = input.box_tree.dom_to_layout[node_id.index()] else {
             warn!("Layout ID not found for node_id: {:?}", node_id);
@@ -162,14 +266,28 @@ impl LayoutTree {
                 let inline_items =
                     InlineLayout::collect_inline_items_from_node(viewport, input, style, &box_node.layout_id);
 
-                let inline_ctx = InlineContext::new(containing_block);
+                let inline_ctx = InlineContext::new(containing_block, style.writing_mode, style.text_indent);
 
-                let mut state = LayoutState::new(&mut layout_tree.nodes, input, &mut position_ctx, &mut float_ctx);
+                let mut state = LayoutState::new(
+                    &mut layout_tree.nodes,
+                    input,
+                    &mut position_ctx,
+                    &mut float_ctx,
+                    &mut layout_tree.remembered_sizes,
+                );
+                state.visible_area = visible_area;
                 InlineLayout::layout(&mut state, &inline_items, inline_ctx)
             }
             _ => {
                 // FIXME: Should retain the old margin collapse state for the node being relayouted, so that it doesn't lose track of deferred positioned children.
-                let mut state = LayoutState::new(&mut layout_tree.nodes, input, &mut position_ctx, &mut float_ctx);
+                let mut state = LayoutState::new(
+                    &mut layout_tree.nodes,
+                    input,
+                    &mut position_ctx,
+                    &mut float_ctx,
+                    &mut layout_tree.remembered_sizes,
+                );
+                state.visible_area = visible_area;
                 let root_style = ComputedStyle::default();
 
                 let Some((node, size)) = BlockLayout::layout(&box_node.layout_id, &root_style, &mut flow, &mut state)
@@ -222,6 +340,86 @@ impl LayoutTree {
         layout_tree.content_height += delta;
     }
 
+    /// Applies paint-only style changes to the layout tree in place, updating the colors, corner radii and cursor of the
+    /// nodes marked for repaint without recomputing any geometry.
+    ///
+    /// # Arguments
+    /// * `dom` - The DOM tree the layout tree was built from.
+    /// * `style_tree` - The recomputed style tree.
+    /// * `dirty` - The invalidation set produced by the style recomputation.
+    pub fn repaint(&mut self, dom: &DocumentRoot, style_tree: &StyleTree, dirty: &LayoutInvalidationSet) {
+        for node in self.nodes.iter_mut().flatten() {
+            let Some(node_id) = node.node_id else {
+                continue;
+            };
+
+            if !dirty.needs_repaint(node_id) {
+                continue;
+            }
+
+            let (Some(style), Some(dom_node)) = (style_tree.get(node_id), dom.get_node(&node_id)) else {
+                continue;
+            };
+
+            node.colors = match &dom_node.data {
+                NodeData::Text(_) => LayoutColors::text_only(style.color),
+                NodeData::Element(element) => {
+                    let is_text_control = node
+                        .image_data
+                        .as_ref()
+                        .is_some_and(|data| matches!(data.kind, ReplacedKind::TextInput | ReplacedKind::TextArea));
+                    let is_placeholder = is_text_control
+                        && control_value(dom_node, dom).is_empty()
+                        && TextControl::from_element(element).is_some_and(|control| control.placeholder.is_some());
+
+                    replaced_colors(style, is_placeholder)
+                }
+            };
+            if matches!(dom_node.data, NodeData::Element(_)) {
+                let border_box = node.border_box();
+                node.border_radius = CornerRadii::resolve(style, border_box.width, border_box.height);
+                node.box_shadow.clone_from(&style.box_shadow.0);
+                node.drop_shadow = style.filter.drop_shadows().copied().collect();
+            }
+            node.cursor = style.cursor;
+        }
+    }
+
+    /// Measures the content box of every element with a `container-type` other than `normal`, which the `@container`
+    /// rules of the document are matched against when it is styled again.
+    ///
+    /// # Arguments
+    /// * `style_tree` - The style tree the layout tree was built from.
+    #[must_use]
+    pub fn container_sizes(&self, style_tree: &StyleTree) -> ContainerSizes {
+        let mut containers = ContainerSizes::default();
+
+        for node in self.nodes.iter().flatten() {
+            let Some(node_id) = node.node_id else {
+                continue;
+            };
+
+            let Some(style) = style_tree
+                .get(node_id)
+                .filter(|style| style.container_type != ContainerType::Normal)
+            else {
+                continue;
+            };
+
+            containers.insert(
+                node_id,
+                Container {
+                    names: style.container_name.clone(),
+                    container_type: style.container_type,
+                    width: node.dimensions.width,
+                    height: node.dimensions.height,
+                },
+            );
+        }
+
+        containers
+    }
+
     fn shift_y_recursively(nodes: &mut Vec<Option<LayoutNode>>, id: &LayoutNodeId, delta: f64) {
         let Some(mut node) = std::mem::take(&mut nodes[id.index()]) else {
             panic!("Node not found in layout tree for layout_id: {:?}", id);
@@ -0,0 +1,16 @@
+use html_dom::NodeId;
+use thiserror::Error;
+
+/// An error laying out a page or looking up its layout.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LayoutError {
+    #[error("no page to lay out")]
+    NoDocument,
+
+    #[error("unable to lay out nodes {nodes:?} of a page that has not been laid out yet")]
+    NotLaidOut { nodes: Vec<NodeId> },
+
+    #[error("node {0} is not present in the layout tree, it may not render, e.g. display: none")]
+    NotRendered(NodeId),
+}
@@ -6,16 +6,26 @@
 
 mod context;
 mod engine;
+pub mod errors;
 mod mode;
 mod node;
 mod primitives;
+mod selection;
 mod tree;
 
-pub use context::{ImageContext, ImageData, LayoutImage, TextContext};
+pub use context::{
+    FontLoadState, FontLoader, FontRendering, ImageContext, ImageData, LayoutImage, PENDING_FONT_METADATA, PartColors,
+    ReplacedKind, TextContext,
+};
 pub use css_style::{Color4f, Position};
+pub use css_values::{
+    Resize,
+    background::{BlendMode, Isolation},
+    image::{ImageRendering, ObjectFit},
+};
 pub use engine::LayoutInput;
 pub(crate) use engine::LayoutState;
 pub use html_dom::NodeId;
 pub use node::LayoutNode;
-pub use primitives::{LayoutColors, Margin, Rect};
+pub use primitives::{CornerRadii, LayoutColors, Margin, Rect, Size};
 pub use tree::LayoutTree;
@@ -1,10 +1,13 @@
 use css_display::LayoutNodeId;
 use css_style::{ComputedSize, ComputedStyle, Position};
-use css_values::display::Float;
+use css_values::{
+    OverflowBlock, Resize,
+    display::{ContentVisibility, Float},
+};
 use tracing::{Level, enabled, trace};
 
 use crate::{
-    LayoutColors, LayoutNode, LayoutState, Rect,
+    CornerRadii, LayoutColors, LayoutNode, LayoutState, Rect, Size,
     context::{BoxModel, FormattingContext, Geometry, LayoutContext},
     mode::{
         LayoutMode,
@@ -123,7 +126,38 @@ impl BlockLayout {
             margin_state: flow.margin_state,
         };
 
-        let child_layout_result = Self::layout_children(&box_node.children, style, &mut child_flow, state);
+        let remembered_size = box_node
+            .node_id
+            .and_then(|node_id| state.remembered_sizes.get(&node_id))
+            .copied();
+        let intrinsic_width = style
+            .contain_intrinsic_width
+            .to_px_with_remembered(remembered_size.map(|size| size.width))
+            .unwrap_or(0.0);
+        let intrinsic_height = style
+            .contain_intrinsic_height
+            .to_px_with_remembered(remembered_size.map(|size| size.height))
+            .unwrap_or(0.0);
+
+        let border_box = Rect::new(
+            x,
+            child_start_y,
+            width + box_model.padding.horizontal() + box_model.border.horizontal(),
+            intrinsic_height + box_model.padding.vertical() + box_model.border.vertical(),
+        );
+        let skips_contents = Self::skips_contents(layout_id, style, border_box, state);
+
+        let child_layout_result = if skips_contents {
+            // Size containment, the element is laid out as if it were empty with its `contain-intrinsic-size`.
+            let content_box = child_flow.layout_ctx.containing_block();
+
+            ChildLayoutResult {
+                node_container: Rect::new(content_box.x, content_box.y, intrinsic_width, intrinsic_height),
+                ..Default::default()
+            }
+        } else {
+            Self::layout_children(&box_node.children, style, &mut child_flow, state)
+        };
 
         if enabled!(Level::TRACE)
             && let Some(node_id) = box_node.node_id
@@ -166,6 +200,15 @@ impl BlockLayout {
         }
         .max(0.0);
 
+        if !skips_contents
+            && style.content_visibility == ContentVisibility::Auto
+            && let Some(node_id) = box_node.node_id
+        {
+            state
+                .remembered_sizes
+                .insert(node_id, Size::new(width, raw_height));
+        }
+
         if matches!(style.float, Float::None) {
             flow.layout_ctx.cursor().y += raw_height;
         }
@@ -183,14 +226,25 @@ impl BlockLayout {
         let node = LayoutNode::builder(*layout_id)
             .block_formatting_context(establishes_bfc)
             .border(box_model.border)
+            .border_radius(CornerRadii::resolve(
+                style,
+                width + box_model.padding.horizontal(),
+                raw_height + box_model.padding.vertical(),
+            ))
+            .box_shadow(style.box_shadow.0.clone())
             .children(child_layout_result.node_ids)
             .colors(colors)
             .cursor(style.cursor)
             .dimensions(node_dimensions)
+            .drop_shadow(style.filter.drop_shadows().copied().collect())
+            .isolation(style.isolation)
             .margin(box_model.margin)
             .maybe_node_id(box_node.node_id)
+            .mix_blend_mode(style.mix_blend_mode)
             .padding(box_model.padding)
             .position(style.position)
+            .resize(Self::resize(style))
+            .skipped(skips_contents && style.content_visibility == ContentVisibility::Auto)
             .build();
 
         state.nodes[layout_id.index()] = Some(node);
@@ -234,7 +288,11 @@ impl BlockLayout {
                     children,
                 );
 
-                let inline_ctx = InlineContext::new(child_flow.layout_ctx.containing_block());
+                let inline_ctx = InlineContext::new(
+                    child_flow.layout_ctx.containing_block(),
+                    parent_style.writing_mode,
+                    parent_style.text_indent,
+                );
 
                 let (ids, nodes_size, container) = InlineLayout::layout(state, &inline_items, inline_ctx);
 
@@ -351,6 +409,39 @@ impl BlockLayout {
         }
     }
 
+    /// Whether the contents of a block are skipped, which is always the case for `content-visibility: hidden`. With
+    /// `content-visibility: auto` they are skipped while the block is outside the visible area, unless they were
+    /// already laid out, so that content that has been shown keeps its size when it is scrolled away from.
+    ///
+    /// <https://drafts.csswg.org/css-contain/#content-visibility>
+    fn skips_contents(layout_id: &LayoutNodeId, style: &ComputedStyle, border_box: Rect, state: &LayoutState) -> bool {
+        match style.content_visibility {
+            ContentVisibility::Visible => false,
+            ContentVisibility::Hidden => true,
+            ContentVisibility::Auto => {
+                let was_shown = state.nodes[layout_id.index()]
+                    .as_ref()
+                    .is_some_and(|node| !node.skipped);
+
+                !was_shown && !Self::is_in_visible_area(border_box, state.visible_area)
+            }
+        }
+    }
+
+    /// Whether a border box is inside the visible area, an empty box counts when its origin is.
+    pub(crate) fn is_in_visible_area(border_box: Rect, visible_area: Rect) -> bool {
+        border_box.intersects(&visible_area) || visible_area.contains_point(border_box.x, border_box.y)
+    }
+
+    /// The `resize` of a block, which only applies when its overflow is not `visible`.
+    fn resize(style: &ComputedStyle) -> Resize {
+        if style.overflow_x == OverflowBlock::Visible && style.overflow_y == OverflowBlock::Visible {
+            Resize::None
+        } else {
+            style.resize
+        }
+    }
+
     pub(crate) fn calculate_height(
         style: &ComputedStyle,
         box_model: &BoxModel,
@@ -382,13 +473,15 @@ mod tests {
     use std::collections::{HashMap, HashSet};
 
     use crate::{
-        LayoutInput, Margin, TextContext,
+        LayoutInput, LayoutTree, Margin, TextContext,
         context::{FloatContext, ImageContext, PositionContext},
         primitives::SideOffset,
     };
+    use css_cssom::{CSSStyleSheet, CssParser};
     use css_display::{BoxNode, BoxTree};
-    use css_style::{ComputedMargin, ComputedSize, ComputedStyle};
+    use css_style::{AbsoluteContext, ComputedMargin, ComputedSize, ComputedStyle, StyleTree};
     use html_dom::{DocumentRoot, DomNode, Element, HtmlTag, NodeData, NodeId, Tag};
+    use url::Url;
 
     use super::*;
 
@@ -616,6 +709,7 @@ mod tests {
                 parent: None,
             }],
             root_nodes: vec![NodeId(0)],
+            ..Default::default()
         };
         let style = ComputedStyle::default();
         let box_node = BoxNode::new(None, LayoutNodeId::new(0), &NodeId(0), &style, vec![]);
@@ -633,8 +727,10 @@ mod tests {
             image: &img_ctx,
         };
         let mut nodes = vec![None; input.box_tree.nodes.len()];
+        let mut remembered_sizes = HashMap::new();
         let layout_node = {
-            let mut state = LayoutState::new(&mut nodes, &mut input, &mut position_ctx, &mut float_ctx);
+            let mut state =
+                LayoutState::new(&mut nodes, &mut input, &mut position_ctx, &mut float_ctx, &mut remembered_sizes);
             BlockLayout::layout(&LayoutNodeId::new(0), &ComputedStyle::default(), &mut flow, &mut state)
         };
 
@@ -649,4 +745,79 @@ mod tests {
         assert_eq!(layout_node.dimensions.height, 0.0);
         assert_eq!(layout_node.children.len(), 0);
     }
+
+    #[test]
+    fn test_content_visibility_skips_contents_outside_visible_area() {
+        let element = |tag| NodeData::Element(Element::new(Tag::Html(tag), HashSet::new(), HashMap::new()));
+
+        let mut dom = DocumentRoot::new();
+        let body = dom.push_node(&element(HtmlTag::Body), None);
+        dom.push_node(&element(HtmlTag::Div), Some(body));
+        let section = dom.push_node(&element(HtmlTag::Section), Some(body));
+        let paragraph = dom.push_node(&element(HtmlTag::P), Some(section));
+        let article = dom.push_node(&element(HtmlTag::Article), Some(body));
+        dom.push_node(&element(HtmlTag::P), Some(article));
+
+        let css = "body, div, section, p, article { display: block; } div { height: 1000px; } p { height: 50px; } \
+                   section { content-visibility: auto; contain-intrinsic-size: auto 200px; } \
+                   article { content-visibility: hidden; contain-intrinsic-size: 20px; }";
+        let stylesheets = [CSSStyleSheet::from(
+            CssParser::default().parse_css(css, false),
+        )];
+        let url = Url::parse("http://localhost").unwrap();
+        let styles = StyleTree::build(None, &AbsoluteContext::default_url(&url), &dom, &stylesheets);
+        let box_tree = BoxTree::new(&dom, &styles);
+
+        let mut text_ctx = TextContext::default();
+        let image_ctx = ImageContext::new();
+        let mut input = LayoutInput {
+            dom: &dom,
+            box_tree: &box_tree,
+            text: &mut text_ctx,
+            image: &image_ctx,
+        };
+        let mut tree = LayoutTree::compute_layout(&mut input, viewport());
+        let node = |tree: &LayoutTree, node_id| {
+            tree.nodes
+                .iter()
+                .flatten()
+                .find(|node| node.node_id == Some(node_id))
+                .cloned()
+                .unwrap()
+        };
+
+        let skipped = node(&tree, section);
+        assert!(skipped.skipped);
+        assert!(skipped.children.is_empty());
+        assert_eq!(skipped.dimensions.height, 200.0);
+        assert_eq!(node(&tree, article).dimensions.y, 1200.0);
+
+        assert!(!tree.has_skipped_content(viewport()));
+        let visible_area = Rect::new(0.0, 900.0, 800.0, 600.0);
+        assert!(tree.has_skipped_content(visible_area));
+
+        let revealed = LayoutTree::reveal_skipped(visible_area, viewport(), &mut tree, &mut input);
+        assert_eq!(revealed, vec![section]);
+
+        let shown = node(&tree, section);
+        assert!(!shown.skipped);
+        assert_eq!(shown.dimensions.height, 50.0);
+        assert_eq!(node(&tree, paragraph).dimensions.y, 1000.0);
+        assert_eq!(node(&tree, article).dimensions.y, 1050.0);
+
+        let hidden = node(&tree, article);
+        assert!(!hidden.skipped);
+        assert!(hidden.children.is_empty());
+        assert_eq!(hidden.dimensions.height, 20.0);
+
+        assert_eq!(tree.remembered_sizes[&section].height, 50.0);
+        assert!(!tree.remembered_sizes.contains_key(&article));
+
+        let tree =
+            LayoutTree::compute_layout_with_remembered_sizes(&mut input, viewport(), tree.remembered_sizes.clone());
+        let remembered = node(&tree, section);
+        assert!(remembered.skipped);
+        assert_eq!(remembered.dimensions.height, 50.0);
+        assert_eq!(node(&tree, article).dimensions.y, 1050.0);
+    }
 }
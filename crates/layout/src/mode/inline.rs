@@ -1,5 +1,6 @@
 use css_display::LayoutNodeId;
-use css_style::{ComputedSize, ComputedStyle};
+use css_style::{ComputedSize, ComputedStyle, ComputedTextIndent};
+use css_values::text::WritingMode;
 use html_dom::NodeId;
 use tracing::{Level, enabled, trace};
 
@@ -13,6 +14,7 @@ use crate::{
             image::layout_image,
             line::LineBoxBuilder,
             text::layout_text,
+            vertical::{can_layout_vertical, layout_vertical},
             whitespace::canonicalize_whitespace,
         },
     },
@@ -23,8 +25,11 @@ mod collection;
 mod image;
 mod line;
 mod text;
+mod vertical;
 mod whitespace;
 
+pub(crate) use image::replaced_colors;
+
 /// Tracks an inline box decoration (background, border, padding) that needs to
 /// be emitted as a `LayoutNode` once the line is finished and final positions
 /// are known.
@@ -59,16 +64,27 @@ pub struct InlineLayoutContext<'node> {
     pub available_width: f64,
     pub ids: Vec<LayoutNodeId>,
     pub inline_box_stack: Vec<ActiveInlineBox<'node>>,
+    pub text_indent: ComputedTextIndent,
 }
 
 #[derive(Debug, Clone, Copy)]
 pub struct InlineContext {
     containing_block: Rect,
+
+    /// The writing mode of the block container, in the vertical modes the lines are laid out as columns.
+    writing_mode: WritingMode,
+
+    /// The `text-indent` of the block container.
+    text_indent: ComputedTextIndent,
 }
 
 impl InlineContext {
-    pub const fn new(containing_block: Rect) -> Self {
-        Self { containing_block }
+    pub const fn new(containing_block: Rect, writing_mode: WritingMode, text_indent: ComputedTextIndent) -> Self {
+        Self {
+            containing_block,
+            writing_mode,
+            text_indent,
+        }
     }
 }
 
@@ -125,6 +141,12 @@ impl InlineLayout {
         items: &[InlineItem<'input>],
         inline_ctx: InlineContext,
     ) -> (Vec<LayoutNodeId>, Vec<Rect>, Rect) {
+        if matches!(inline_ctx.writing_mode, WritingMode::VerticalRl | WritingMode::VerticalLr)
+            && can_layout_vertical(items)
+        {
+            return layout_vertical(state, items, inline_ctx);
+        }
+
         let mut node_container = inline_ctx.containing_block;
         let mut node_dimensions = Vec::with_capacity(items.len());
         let mut line = LineBoxBuilder::new(
@@ -139,8 +161,15 @@ impl InlineLayout {
             start_x: inline_ctx.containing_block.x,
             ids: Vec::new(),
             inline_box_stack: Vec::new(),
+            text_indent: inline_ctx.text_indent,
         };
 
+        line.line_box.indent(
+            inline_ctx
+                .text_indent
+                .line_offset(inline_ctx.containing_block.width, true),
+        );
+
         for item in items {
             if enabled!(Level::TRACE) {
                 trace!(%item)
@@ -209,7 +238,7 @@ impl InlineLayout {
                         state.input.text.last_text_align = *alignment;
                         state.input.text.last_writing_mode = *writing_mode;
 
-                        if line.line_box.width + total_width > available_line_width && line.line_box.width > 0.0 {
+                        if line.line_box.width + total_width > available_line_width && line.line_box.has_content() {
                             line.finish_line_with_decorations(
                                 state.nodes,
                                 &mut inline_layout_ctx,
@@ -321,8 +350,110 @@ impl InlineLayout {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use css_cssom::{CSSStyleSheet, CssParser};
+    use css_display::BoxTree;
+    use css_style::{AbsoluteContext, StyleTree};
+    use html_dom::{DocumentRoot, Element, HtmlTag, NodeData, Tag};
+    use url::Url;
+
+    use crate::{ImageContext, LayoutTree, TextContext};
+
     use super::*;
 
+    /// The position of every line of a wrapped paragraph, relative to the text node, with the given declarations.
+    fn line_fragments(declarations: &str) -> Vec<Rect> {
+        let mut dom = DocumentRoot::new();
+        let div = dom
+            .push_node(&NodeData::Element(Element::new(Tag::Html(HtmlTag::Div), HashSet::new(), HashMap::new())), None);
+        let text = dom.push_node(&NodeData::Text("lorem ipsum dolor sit amet ".repeat(8)), Some(div));
+
+        let css = format!("div {{ display: block; width: 400px; font-size: 16px; {declarations} }}");
+        let stylesheets = [CSSStyleSheet::from(
+            CssParser::default().parse_css(&css, false),
+        )];
+        let url = Url::parse("http://localhost").unwrap();
+        let styles = StyleTree::build(None, &AbsoluteContext::default_url(&url), &dom, &stylesheets);
+        let box_tree = BoxTree::new(&dom, &styles);
+
+        let mut text_ctx = TextContext::default();
+        let image_ctx = ImageContext::new();
+        let mut input = LayoutInput {
+            dom: &dom,
+            box_tree: &box_tree,
+            text: &mut text_ctx,
+            image: &image_ctx,
+        };
+        let tree = LayoutTree::compute_layout(&mut input, Rect::new(0.0, 0.0, 800.0, 600.0));
+
+        let node = tree
+            .nodes
+            .iter()
+            .flatten()
+            .find(|node| node.node_id == Some(text))
+            .unwrap();
+
+        node.text_fragments
+            .iter()
+            .map(|fragment| {
+                Rect::new(
+                    node.dimensions.x + fragment.size.x,
+                    node.dimensions.y + fragment.size.y,
+                    fragment.size.width,
+                    fragment.size.height,
+                )
+            })
+            .collect()
+    }
+
+    /// The horizontal position of every line of a wrapped paragraph with the given `text-indent`.
+    fn line_starts(text_indent: &str) -> Vec<f64> {
+        line_fragments(&format!("text-indent: {text_indent}"))
+            .iter()
+            .map(|fragment| fragment.x)
+            .collect()
+    }
+
+    #[test]
+    fn unitless_line_height_multiplies_the_font_size() {
+        let lines = line_fragments("line-height: 2");
+
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| (line.height - 32.0).abs() < 0.001));
+        assert!(
+            lines
+                .windows(2)
+                .all(|pair| (pair[1].y - pair[0].y - 32.0).abs() < 0.001)
+        );
+    }
+
+    #[test]
+    fn percentage_line_height_multiplies_the_font_size() {
+        let lines = line_fragments("line-height: 150%");
+
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| (line.height - 24.0).abs() < 0.001));
+    }
+
+    #[test]
+    fn text_indent_offsets_only_the_first_line() {
+        let starts = line_starts("10%");
+
+        assert!(starts.len() > 1);
+        assert!((starts[0] - 40.0).abs() < 0.001);
+        assert!(starts[1..].iter().all(|x| x.abs() < 0.001));
+    }
+
+    #[test]
+    fn hanging_text_indent_offsets_the_following_lines() {
+        let starts = line_starts("40px hanging");
+
+        assert!(starts.len() > 1);
+        assert!(starts[0].abs() < 0.001);
+        assert!(starts[1..].iter().all(|x| (x - 40.0).abs() < 0.001));
+    }
+
     #[test]
     fn auto_inline_flow_root_width_uses_descendant_extent() {
         let nested_text = LayoutNode::builder(LayoutNodeId::new(2))
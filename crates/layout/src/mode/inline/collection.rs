@@ -1,9 +1,12 @@
 use css_display::LayoutNodeId;
-use css_style::{ComputedMaxSize, ComputedSize, ComputedStyle, Display};
+use css_style::{ComputedMaxSize, ComputedSize, ComputedStyle, Display, PartStyles};
 use css_values::display::{InsideDisplay, OutsideDisplay};
-use html_dom::{HtmlTag, NodeData, NodeId, Tag};
+use html_dom::{
+    CheckableKind, DEFAULT_SVG_HEIGHT, DEFAULT_SVG_WIDTH, HtmlTag, MediaElement, NodeData, NodeId, RangeElement,
+    SelectElement, SvgTag, Tag, TextControl, canvas_size, control_value,
+};
 
-use crate::{LayoutInput, Rect};
+use crate::{LayoutInput, Rect, ReplacedKind};
 
 #[derive(Debug, Clone)]
 pub struct TextRun<'node> {
@@ -11,6 +14,37 @@ pub struct TextRun<'node> {
     pub node_id: &'node NodeId,
     pub content: String,
     pub style: &'node ComputedStyle,
+
+    /// The language of the text from the nearest `lang` attribute, `None` if it is not known.
+    pub lang: Option<&'node str>,
+}
+
+/// The space between the edge of a text control and its text.
+pub const TEXT_CONTROL_PADDING: f64 = 4.0;
+
+/// The space reserved at the end of a `<select>` dropdown for its arrow.
+pub const SELECT_ARROW_WIDTH: f64 = 16.0;
+
+/// The size of a checkbox or radio button whose `width` and `height` are `auto`.
+const CHECKABLE_SIZE: f64 = 13.0;
+
+/// The size of a `<progress>` bar whose `width` and `height` are `auto`.
+const PROGRESS_SIZE: (f64, f64) = (160.0, 16.0);
+
+/// The size of a `<meter>` bar whose `width` and `height` are `auto`.
+const METER_SIZE: (f64, f64) = (80.0, 16.0);
+
+/// The size of an `<input type="range">` slider whose `width` and `height` are `auto`.
+const RANGE_SIZE: (f64, f64) = (129.0, 16.0);
+
+/// The text shown inside an editable text control.
+#[derive(Debug, Clone)]
+pub struct ControlText {
+    /// The displayed text, with passwords already masked.
+    pub content: String,
+
+    /// Whether the control is empty and `content` is its placeholder.
+    pub is_placeholder: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -22,6 +56,11 @@ pub struct ImageItem<'node> {
     pub has_explicit_width: bool,
     pub has_explicit_height: bool,
     pub needs_intrinsic_size: bool,
+    pub kind: ReplacedKind,
+    pub control_text: Option<ControlText>,
+
+    /// The styles of the `::part()` pseudo-elements of a `<progress>`, `<meter>` or range slider.
+    pub parts: Option<&'node PartStyles>,
     pub style: &'node ComputedStyle,
 }
 
@@ -51,7 +90,7 @@ pub enum InlineItem<'node> {
         style: &'node ComputedStyle,
     },
 
-    /// An `<img>` element with an optional source URL and explicit dimensions.
+    /// A replaced element (`<img>`, `<video>`, `<audio>`, `<canvas>` or a text control) with an optional source URL and explicit dimensions.
     Image(ImageItem<'node>),
 
     /// A line break, <br>
@@ -81,10 +120,29 @@ pub fn collect<'dom>(
     items: &mut Vec<InlineItem<'dom>>,
 ) -> Result<(), ()> {
     let box_node = &input.box_tree[layout_id];
+
+    // The text of a `::before` or `::after` pseudo-element, attributed to its element since it has no node of its own.
+    if let Some(generated) = box_node.generated_content {
+        items.push(InlineItem::TextRun(TextRun {
+            layout_id,
+            node_id: &generated.owner,
+            content: generated.text.clone(),
+            style: &generated.style,
+            lang: input.dom.language(generated.owner),
+        }));
+
+        return Ok(());
+    }
+
     let Some(node_id) = &box_node.node_id else {
         for child in &box_node.children {
             let bn = &input.box_tree[child];
 
+            if bn.generated_content.is_some() {
+                collect(containing_rect, input, parent_style, child, items)?;
+                continue;
+            }
+
             let Some(text_node_id) = &bn.node_id else {
                 continue;
             };
@@ -96,6 +154,7 @@ pub fn collect<'dom>(
                 node_id: text_node_id,
                 content: text.clone(),
                 style: parent_style,
+                lang: input.dom.language(*text_node_id),
             }));
         }
 
@@ -112,26 +171,55 @@ pub fn collect<'dom>(
                 node_id,
                 content: content.clone(),
                 style: parent_style,
+                lang: input.dom.language(*node_id),
             }));
         }
         NodeData::Element(element) => match element.tag {
             Tag::Html(HtmlTag::Br) => {
                 items.push(InlineItem::Break {
-                    line_height_px: style.line_height,
+                    line_height_px: style.line_height.to_px(style.font_size),
                 });
             }
-            Tag::Html(HtmlTag::Img) => {
+            Tag::Html(HtmlTag::Img | HtmlTag::Video | HtmlTag::Audio | HtmlTag::Canvas) | Tag::Svg(SvgTag::Svg) => {
                 const DEFAULT_IMAGE_WIDTH: f64 = 300.0;
                 const DEFAULT_IMAGE_HEIGHT: f64 = 150.0;
+                const DEFAULT_AUDIO_HEIGHT: f64 = 54.0;
+
+                let is_svg = element.tag == Tag::Svg(SvgTag::Svg);
+                let (kind, default_width, default_height) = match &element.tag {
+                    Tag::Html(HtmlTag::Video) => (ReplacedKind::Video, DEFAULT_IMAGE_WIDTH, DEFAULT_IMAGE_HEIGHT),
+                    Tag::Html(HtmlTag::Audio) => (ReplacedKind::Audio, DEFAULT_IMAGE_WIDTH, DEFAULT_AUDIO_HEIGHT),
+                    Tag::Html(HtmlTag::Canvas) => {
+                        let (width, height) = canvas_size(element);
+                        (ReplacedKind::Canvas, f64::from(width), f64::from(height))
+                    }
+                    // An inline `<svg>` element is drawn as an image rasterized from its markup.
+                    Tag::Svg(_) => (ReplacedKind::Image, f64::from(DEFAULT_SVG_WIDTH), f64::from(DEFAULT_SVG_HEIGHT)),
+                    _ => (ReplacedKind::Image, DEFAULT_IMAGE_WIDTH, DEFAULT_IMAGE_HEIGHT),
+                };
 
-                let Some(attrs) = element.attributes.as_ref() else {
+                // An `<img>` without attributes has no source, and an `<audio>` element without
+                // `controls` has no visual representation.
+                if (kind == ReplacedKind::Image && !is_svg && element.attributes.is_none())
+                    || (kind == ReplacedKind::Audio
+                        && !MediaElement::from_node(node).is_some_and(|media| media.controls))
+                {
                     return Ok(());
-                };
+                }
 
-                let known = input.image.get(&box_node.node_id.unwrap());
+                // Audio elements never have an intrinsic image, video elements use their poster and canvas
+                // elements are sized by their bitmap, which is already known from the attributes.
+                let known = match kind {
+                    ReplacedKind::Audio | ReplacedKind::Canvas => None,
+                    _ => input.image.get(&box_node.node_id.unwrap()),
+                };
 
-                let attr_width = attrs.get("width").and_then(|v| v.parse::<f64>().ok());
-                let attr_height = attrs.get("height").and_then(|v| v.parse::<f64>().ok());
+                let attr_width = element
+                    .get_attribute("width")
+                    .and_then(|v| v.parse::<f64>().ok());
+                let attr_height = element
+                    .get_attribute("height")
+                    .and_then(|v| v.parse::<f64>().ok());
 
                 let css_width = !matches!(style.width, ComputedSize::Auto);
                 let css_height = !matches!(style.height, ComputedSize::Auto);
@@ -143,32 +231,24 @@ pub fn collect<'dom>(
                         match style.width {
                             ComputedSize::Px(px) => px,
                             ComputedSize::Percentage(frac) => frac * containing_rect.width,
-                            _ => known
-                                .as_ref()
-                                .map_or(DEFAULT_IMAGE_WIDTH, |m| m.width as f64), // TODO: Handle other types of computed size
+                            _ => known.as_ref().map_or(default_width, |m| m.width as f64), // TODO: Handle other types of computed size
                         }
                     } else if let Some(attr_w) = attr_width {
                         attr_w
                     } else {
-                        known
-                            .as_ref()
-                            .map_or(DEFAULT_IMAGE_WIDTH, |m| m.width as f64)
+                        known.as_ref().map_or(default_width, |m| m.width as f64)
                     };
 
                     let h = if css_height {
                         match style.height {
                             ComputedSize::Px(px) => px,
                             ComputedSize::Percentage(frac) => frac * containing_rect.height,
-                            _ => known
-                                .as_ref()
-                                .map_or(DEFAULT_IMAGE_HEIGHT, |m| m.height as f64), // TODO: Handle other types of computed size
+                            _ => known.as_ref().map_or(default_height, |m| m.height as f64), // TODO: Handle other types of computed size
                         }
                     } else if let Some(attr_h) = attr_height {
                         attr_h
                     } else {
-                        known
-                            .as_ref()
-                            .map_or(DEFAULT_IMAGE_HEIGHT, |m| m.height as f64)
+                        known.as_ref().map_or(default_height, |m| m.height as f64)
                     };
 
                     let max_width = match style.max_width {
@@ -189,7 +269,11 @@ pub fn collect<'dom>(
                         } else {
                             h
                         },
-                        attr_width.is_none() && attr_height.is_none() && !css_width && !css_height,
+                        !matches!(kind, ReplacedKind::Audio | ReplacedKind::Canvas)
+                            && attr_width.is_none()
+                            && attr_height.is_none()
+                            && !css_width
+                            && !css_height,
                     )
                 };
 
@@ -201,6 +285,196 @@ pub fn collect<'dom>(
                     has_explicit_width,
                     has_explicit_height,
                     needs_intrinsic_size,
+                    kind,
+                    control_text: None,
+                    parts: None,
+                    style,
+                }));
+            }
+            Tag::Html(HtmlTag::Input | HtmlTag::Textarea) if TextControl::from_element(element).is_some() => {
+                const DEFAULT_INPUT_SIZE: f64 = 20.0;
+                const DEFAULT_TEXTAREA_ROWS: f64 = 2.0;
+
+                let Some(control) = TextControl::from_element(element) else {
+                    return Ok(());
+                };
+
+                // NOTE: Approximates the average character width as half the font size.
+                let char_width = style.font_size * 0.5;
+                let line_height_px = style.line_height.to_px(style.font_size).max(0.1);
+                let parse_attr = |name: &str| {
+                    element
+                        .get_attribute(name)
+                        .and_then(|v| v.trim().parse::<f64>().ok())
+                        .filter(|v| *v > 0.0)
+                };
+
+                let (kind, default_width, default_height) = if control.is_multiline() {
+                    let cols = parse_attr("cols").unwrap_or(DEFAULT_INPUT_SIZE);
+                    let rows = parse_attr("rows").unwrap_or(DEFAULT_TEXTAREA_ROWS);
+
+                    (
+                        ReplacedKind::TextArea,
+                        cols * char_width + TEXT_CONTROL_PADDING * 2.0,
+                        rows * line_height_px + TEXT_CONTROL_PADDING * 2.0,
+                    )
+                } else {
+                    let size = parse_attr("size").unwrap_or(DEFAULT_INPUT_SIZE);
+
+                    (
+                        ReplacedKind::TextInput,
+                        size * char_width + TEXT_CONTROL_PADDING * 2.0,
+                        line_height_px + TEXT_CONTROL_PADDING * 2.0,
+                    )
+                };
+
+                let width = match style.width {
+                    ComputedSize::Px(px) => px,
+                    ComputedSize::Percentage(frac) => frac * containing_rect.width,
+                    _ => default_width,
+                };
+                let height = match style.height {
+                    ComputedSize::Px(px) => px,
+                    ComputedSize::Percentage(frac) => frac * containing_rect.height,
+                    _ => default_height,
+                };
+
+                let value = control_value(node, input.dom);
+                let control_text = if value.is_empty() {
+                    control.placeholder.map(|placeholder| ControlText {
+                        content: placeholder,
+                        is_placeholder: true,
+                    })
+                } else {
+                    Some(ControlText {
+                        content: control.display_text(&value),
+                        is_placeholder: false,
+                    })
+                };
+
+                items.push(InlineItem::Image(ImageItem {
+                    layout_id,
+                    node_id,
+                    width,
+                    height,
+                    has_explicit_width: true,
+                    has_explicit_height: true,
+                    needs_intrinsic_size: false,
+                    kind,
+                    control_text,
+                    parts: None,
+                    style,
+                }));
+            }
+            Tag::Html(HtmlTag::Input) if CheckableKind::from_element(element).is_some() => {
+                let Some(checkable) = CheckableKind::from_element(element) else {
+                    return Ok(());
+                };
+
+                let width = match style.width {
+                    ComputedSize::Px(px) => px,
+                    ComputedSize::Percentage(frac) => frac * containing_rect.width,
+                    _ => CHECKABLE_SIZE,
+                };
+                let height = match style.height {
+                    ComputedSize::Px(px) => px,
+                    ComputedSize::Percentage(frac) => frac * containing_rect.height,
+                    _ => CHECKABLE_SIZE,
+                };
+
+                items.push(InlineItem::Image(ImageItem {
+                    layout_id,
+                    node_id,
+                    width,
+                    height,
+                    has_explicit_width: true,
+                    has_explicit_height: true,
+                    needs_intrinsic_size: false,
+                    kind: match checkable {
+                        CheckableKind::Checkbox => ReplacedKind::Checkbox,
+                        CheckableKind::Radio => ReplacedKind::Radio,
+                    },
+                    control_text: None,
+                    parts: None,
+                    style,
+                }));
+            }
+            Tag::Html(HtmlTag::Progress | HtmlTag::Meter | HtmlTag::Input)
+                if element.tag != Tag::Html(HtmlTag::Input) || RangeElement::from_element(element).is_some() =>
+            {
+                let (kind, (default_width, default_height)) = match element.tag {
+                    Tag::Html(HtmlTag::Progress) => (ReplacedKind::Progress, PROGRESS_SIZE),
+                    Tag::Html(HtmlTag::Meter) => (ReplacedKind::Meter, METER_SIZE),
+                    _ => (ReplacedKind::Range, RANGE_SIZE),
+                };
+
+                let width = match style.width {
+                    ComputedSize::Px(px) => px,
+                    ComputedSize::Percentage(frac) => frac * containing_rect.width,
+                    _ => default_width,
+                };
+                let height = match style.height {
+                    ComputedSize::Px(px) => px,
+                    ComputedSize::Percentage(frac) => frac * containing_rect.height,
+                    _ => default_height,
+                };
+
+                items.push(InlineItem::Image(ImageItem {
+                    layout_id,
+                    node_id,
+                    width,
+                    height,
+                    has_explicit_width: true,
+                    has_explicit_height: true,
+                    needs_intrinsic_size: false,
+                    kind,
+                    control_text: None,
+                    parts: box_node.parts,
+                    style,
+                }));
+            }
+            Tag::Html(HtmlTag::Select) if !element.has_attribute("multiple") => {
+                let Some(select) = SelectElement::from_node(node, input.dom) else {
+                    return Ok(());
+                };
+
+                // NOTE: Approximates the average character width as half the font size, like text controls.
+                let char_width = style.font_size * 0.5;
+                let line_height_px = style.line_height.to_px(style.font_size).max(0.1);
+                let widest_label = select
+                    .options
+                    .iter()
+                    .map(|option| option.label.chars().count())
+                    .max()
+                    .unwrap_or(0);
+
+                let width = match style.width {
+                    ComputedSize::Px(px) => px,
+                    ComputedSize::Percentage(frac) => frac * containing_rect.width,
+                    _ => widest_label as f64 * char_width + TEXT_CONTROL_PADDING * 2.0 + SELECT_ARROW_WIDTH,
+                };
+                let height = match style.height {
+                    ComputedSize::Px(px) => px,
+                    ComputedSize::Percentage(frac) => frac * containing_rect.height,
+                    _ => line_height_px + TEXT_CONTROL_PADDING * 2.0,
+                };
+
+                let control_text = select.selected_option().map(|option| ControlText {
+                    content: option.label.clone(),
+                    is_placeholder: false,
+                });
+
+                items.push(InlineItem::Image(ImageItem {
+                    layout_id,
+                    node_id,
+                    width,
+                    height,
+                    has_explicit_width: true,
+                    has_explicit_height: true,
+                    needs_intrinsic_size: false,
+                    kind: ReplacedKind::Select,
+                    control_text,
+                    parts: None,
                     style,
                 }));
             }
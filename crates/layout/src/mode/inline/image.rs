@@ -1,11 +1,31 @@
+use std::sync::Arc;
+
 use css_style::{ComputedMaxSize, ComputedSize, ComputedStyle};
+use css_values::text::Whitespace;
 
 use crate::{
-    ImageData, LayoutColors, LayoutInput, LayoutNode, Rect,
-    context::FloatContext,
-    mode::inline::{InlineLayoutContext, collection::ImageItem, line::LineBoxBuilder},
+    ImageData, LayoutColors, LayoutInput, LayoutNode, PartColors, Rect, TextContext,
+    context::{FloatContext, TextDescription, TextFragment, TextSpacing},
+    mode::inline::{
+        InlineLayoutContext,
+        collection::{ImageItem, TEXT_CONTROL_PADDING},
+        line::LineBoxBuilder,
+    },
 };
 
+/// The opacity applied to the text color of a placeholder.
+const PLACEHOLDER_OPACITY: f64 = 0.5;
+
+/// Resolves the colors of a replaced element, dimming the text color while a text control shows its placeholder.
+pub(crate) fn replaced_colors(style: &ComputedStyle, is_placeholder: bool) -> LayoutColors {
+    let mut colors = LayoutColors::from(style);
+    if is_placeholder {
+        colors.color.a *= PLACEHOLDER_OPACITY;
+    }
+
+    colors
+}
+
 pub fn layout_image<'node>(
     nodes: &mut [Option<LayoutNode>],
     ctx: &mut InlineLayoutContext<'node>,
@@ -33,26 +53,100 @@ pub fn layout_image<'node>(
         image.map(|i| (i.width, i.height)),
     );
 
-    if line.line_box.width + img_width > ctx.available_width && line.line_box.width > 0.0 {
+    if line.line_box.width + img_width > ctx.available_width && line.line_box.has_content() {
         line.finish_line_with_decorations(nodes, ctx, input.text, float_ctx, None);
     }
 
-    let node = LayoutNode::builder(*img.layout_id)
+    let colors = replaced_colors(
+        img.style,
+        img.control_text
+            .as_ref()
+            .is_some_and(|text| text.is_placeholder),
+    );
+
+    let mut node = LayoutNode::builder(*img.layout_id)
+        .box_shadow(img.style.box_shadow.0.clone())
         .dimensions(Rect::new(line.line_box.x, line.line_box.y, img_width, img_height))
-        .colors(LayoutColors::from(img.style))
+        .drop_shadow(img.style.filter.drop_shadows().copied().collect())
+        .colors(colors)
+        .isolation(img.style.isolation)
+        .mix_blend_mode(img.style.mix_blend_mode)
         .node_id(*img.node_id)
         .image_data(ImageData {
             node_id: *img.node_id,
+            kind: img.kind,
             image_needs_intrinsic_size: img.needs_intrinsic_size && !has_intrinsic_size,
+            image_rendering: img.style.image_rendering,
+            object_fit: img.style.object_fit,
+            part_colors: img.parts.map(PartColors::from).unwrap_or_default(),
         })
+        .resize(img.style.resize)
         .build();
 
-    let ascent = img_height;
-    line.line_box.add_ascent(ascent);
+    if let Some(control_text) = &img.control_text {
+        node.text_fragments = layout_control_text(input.text, img.style, &control_text.content);
+    }
+
+    // The line is at least as tall as the strut of the image, the content area of its font plus the half-leading
+    // `(line-height - content area) / 2` above and below it, which together make up the used line height.
+    let strut = img.style.line_height.to_px(img.style.font_size);
+    line.line_box.add_ascent(img_height.max(strut));
     nodes[img.layout_id.index()] = Some(node);
     ctx.ids.push(*img.layout_id);
 }
 
+/// Shapes the text of a text control into one fragment per line, inset by the padding of the control.
+///
+/// Text inside a control never wraps, lines that are too long overflow the control instead.
+fn layout_control_text(text_ctx: &mut TextContext, style: &ComputedStyle, content: &str) -> Vec<TextFragment> {
+    const MAX_LINE_WIDTH: f64 = 1.0e6;
+
+    let text_desc = TextDescription {
+        whitespace: &Whitespace::Pre,
+        line_height_px: style.line_height.to_px(style.font_size),
+        font_family: &style.font_family,
+        font_weight: style.font_weight,
+        font_size_px: style.font_size,
+        font_size_adjust: style.font_size_adjust,
+        word_break: style.word_break,
+        overflow_wrap: style.overflow_wrap,
+        hyphens: style.hyphens,
+        hyphenate_character: style.hyphenate_character.as_str(),
+        spacing: TextSpacing {
+            letter: style.letter_spacing,
+            word: style.word_spacing,
+        },
+        font_kerning: style.font_kerning,
+        font_variant_ligatures: style.font_variant_ligatures,
+        font_variant_numeric: style.font_variant_numeric,
+        font_variant_caps: style.font_variant_caps,
+        font_feature_settings: &style.font_feature_settings,
+    };
+    let line_height_px = text_desc.line_height_px.max(0.1);
+
+    content
+        .split('\n')
+        .enumerate()
+        .map(|(index, line)| {
+            let (measured, _) = text_ctx.measure_text_that_fits(line, &text_desc, MAX_LINE_WIDTH);
+
+            TextFragment {
+                size: Rect::new(
+                    TEXT_CONTROL_PADDING,
+                    TEXT_CONTROL_PADDING + index as f64 * line_height_px,
+                    measured.width,
+                    line_height_px,
+                ),
+                buffers: vec![Arc::new(measured.buffer)],
+                spacing: text_desc.spacing,
+
+                #[cfg(debug_assertions)]
+                debug_content: line.to_string(),
+            }
+        })
+        .collect()
+}
+
 fn resolve_image_size(
     width: f64,
     height: f64,
@@ -6,7 +6,7 @@ use css_values::text::{TextAlign, WritingMode};
 use html_dom::NodeId;
 
 use crate::{
-    LayoutColors, LayoutNode, Rect, TextContext,
+    CornerRadii, LayoutColors, LayoutNode, Rect, TextContext,
     context::{FloatContext, Geometry},
     mode::inline::{ActiveInlineBox, InlineDecoration, InlineLayoutContext},
 };
@@ -29,6 +29,9 @@ pub struct LineBox<'node> {
     pub x: f64,
     pub y: f64,
     pub decorations: Vec<InlineDecoration<'node>>,
+
+    /// The `text-indent` the line starts at, negative for an outdent.
+    pub indent: f64,
 }
 
 impl LineBox<'_> {
@@ -42,9 +45,21 @@ impl LineBox<'_> {
             x,
             y,
             decorations: Vec::with_capacity(4),
+            indent: 0.0,
         }
     }
 
+    /// Indent the start of the line, the content added after it is placed from the indentation on.
+    pub fn indent(&mut self, amount: f64) {
+        self.indent = amount;
+        self.width += amount;
+    }
+
+    /// Whether anything has been placed on the line beyond its indentation.
+    pub fn has_content(&self) -> bool {
+        self.width > self.indent
+    }
+
     /// Get the available width for this line, accounting for floats
     pub fn available_width(&self, float_ctx: &FloatContext, container_width: f64) -> f64 {
         let (left_edge, right_edge) = float_ctx.available_width_at(self.y, container_width);
@@ -150,8 +165,17 @@ impl LineBox<'_> {
                 .dimensions(node_dimension)
                 .padding(dec.padding)
                 .border(dec.border)
+                .border_radius(CornerRadii::resolve(
+                    dec.style,
+                    dec_width + dec.padding.horizontal(),
+                    dec_height + dec.padding.vertical(),
+                ))
+                .box_shadow(dec.style.box_shadow.0.clone())
                 .colors(LayoutColors::from(dec.style))
+                .drop_shadow(dec.style.filter.drop_shadows().copied().collect())
+                .isolation(dec.style.isolation)
                 .maybe_node_id(dec.node_id)
+                .mix_blend_mode(dec.style.mix_blend_mode)
                 .build();
 
             Rect::<f64>::union_rect(&mut container, node_dimension);
@@ -302,6 +326,12 @@ impl<'node> LineBoxBuilder<'node> {
         ctx.current_y += min_line_height.map_or(line_result.line_height, |min_h| line_result.line_height.max(min_h));
         self.line_box = LineBox::new(ctx.start_x, ctx.current_y);
 
+        // Only forced line breaks give the finished line a minimum height, `each-line` indents the line after them
+        // like the first line.
+        let first_line = ctx.text_indent.each_line && min_line_height.is_some();
+        self.line_box
+            .indent(ctx.text_indent.line_offset(ctx.available_width, first_line));
+
         for con_box in continuing_boxes {
             ctx.inline_box_stack.push(con_box);
         }
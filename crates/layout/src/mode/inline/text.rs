@@ -1,14 +1,15 @@
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 use cosmic_text::Buffer;
 use css_display::LayoutNodeId;
 use css_style::ComputedStyle;
-use css_values::text::Whitespace;
+use css_values::text::{Hyphens, TabSize, Whitespace};
 use html_dom::NodeId;
+use hypher::Lang;
 
 use crate::{
     LayoutColors, LayoutNode, Rect, TextContext,
-    context::{FloatContext, TextDescription, TextFragment},
+    context::{FloatContext, SOFT_HYPHEN, TextDescription, TextFragment, TextSpacing},
     mode::inline::{InlineLayoutContext, collection::TextRun, line::LineBoxBuilder},
 };
 
@@ -34,7 +35,7 @@ pub fn layout_text<'node>(
     let font_size_px = text.style.font_size;
     let whitespace = &text.style.whitespace;
     let text_align = text.style.text_align;
-    let line_height = text.style.line_height;
+    let line_height_px = text.style.line_height.to_px(font_size_px);
     let font_family = &text.style.font_family;
     let font_weight = text.style.font_weight;
     let writing_mode = &text.style.writing_mode;
@@ -46,14 +47,35 @@ pub fn layout_text<'node>(
 
     let text_desc = TextDescription {
         whitespace,
-        line_height,
+        line_height_px,
         font_family,
         font_weight,
         font_size_px,
+        font_size_adjust: text.style.font_size_adjust,
+        word_break: text.style.word_break,
+        overflow_wrap: text.style.overflow_wrap,
+        hyphens: text.style.hyphens,
+        hyphenate_character: text.style.hyphenate_character.as_str(),
+        spacing: TextSpacing {
+            letter: text.style.letter_spacing,
+            word: text.style.word_spacing,
+        },
+        font_kerning: text.style.font_kerning,
+        font_variant_ligatures: text.style.font_variant_ligatures,
+        font_variant_numeric: text.style.font_variant_numeric,
+        font_variant_caps: text.style.font_variant_caps,
+        font_feature_settings: &text.style.font_feature_settings,
     };
 
-    if preserves_newlines && text.content.contains('\n') {
-        let segments: Vec<&str> = text.content.split('\n').collect();
+    let mut content = hyphenate(&text.content, text.style.hyphens, text.lang);
+
+    if matches!(whitespace, Whitespace::Pre | Whitespace::PreWrap) && content.contains('\t') {
+        let tab_size = tab_size_in_spaces(text_ctx, &text_desc, text.style.tab_size);
+        content = Cow::Owned(expand_tabs(&content, tab_size));
+    }
+
+    if preserves_newlines && content.contains('\n') {
+        let segments: Vec<&str> = content.split('\n').collect();
 
         for (seg_idx, segment) in segments.iter().enumerate() {
             let input = TextInput {
@@ -71,7 +93,7 @@ pub fn layout_text<'node>(
             }
 
             if seg_idx < segments.len() - 1 {
-                line.finish_line_with_decorations(nodes, ctx, text_ctx, float_ctx, Some(line_height));
+                line.finish_line_with_decorations(nodes, ctx, text_ctx, float_ctx, Some(line_height_px));
             }
         }
 
@@ -83,7 +105,7 @@ pub fn layout_text<'node>(
             text_ctx,
             float_ctx,
             &TextInput {
-                content: &text.content,
+                content: &content,
                 layout_id: *text.layout_id,
                 node_id: *text.node_id,
                 style: text.style,
@@ -96,6 +118,144 @@ pub fn layout_text<'node>(
     }
 }
 
+/// Marks the points where the words of the text may be broken with a hyphen, according to `hyphens`.
+///
+/// With `auto` every word is split into syllables by soft hyphens, using the hyphenation patterns of the language of
+/// the text. Text of an unknown language is hyphenated as English, text of a language without patterns is left as it
+/// is. With `none` the soft hyphens of the text are removed so that no word is broken.
+fn hyphenate<'a>(content: &'a str, hyphens: Hyphens, lang: Option<&str>) -> Cow<'a, str> {
+    // Words shorter than this are never hyphenated.
+    const MIN_WORD_LENGTH: usize = 5;
+
+    match hyphens {
+        Hyphens::Manual => Cow::Borrowed(content),
+        Hyphens::None if content.contains(SOFT_HYPHEN) => Cow::Owned(content.replace(SOFT_HYPHEN, "")),
+        Hyphens::None => Cow::Borrowed(content),
+        Hyphens::Auto => {
+            let Some(lang) = lang.map_or(Some(Lang::English), hyphenation_language) else {
+                return Cow::Borrowed(content);
+            };
+
+            let mut hyphenated = String::with_capacity(content.len());
+
+            for piece in content.split_inclusive(|c: char| !c.is_alphabetic()) {
+                let word = piece.trim_end_matches(|c: char| !c.is_alphabetic());
+                if word.chars().count() < MIN_WORD_LENGTH {
+                    hyphenated.push_str(piece);
+                    continue;
+                }
+
+                // The syllables are found on the lowercase word and applied to the original by their number of
+                // characters. Lowercasing can change the length of a character in bytes, like the Kelvin sign, but
+                // every character is lowercased to exactly one so that the counts match.
+                let lowercase: String = word.chars().map(lowercase_char).collect();
+
+                let mut rest = word;
+                for (index, syllable) in hypher::hyphenate(&lowercase, lang).enumerate() {
+                    if index > 0 {
+                        hyphenated.push(SOFT_HYPHEN);
+                    }
+
+                    let end = rest
+                        .char_indices()
+                        .nth(syllable.chars().count())
+                        .map_or(rest.len(), |(end, _)| end);
+                    hyphenated.push_str(&rest[..end]);
+                    rest = &rest[end..];
+                }
+
+                hyphenated.push_str(rest);
+                hyphenated.push_str(&piece[word.len()..]);
+            }
+
+            Cow::Owned(hyphenated)
+        }
+    }
+}
+
+/// The lowercase form of a character, or the character itself when it lowercases to more than one character.
+fn lowercase_char(c: char) -> char {
+    let mut lowercase = c.to_lowercase();
+
+    match (lowercase.next(), lowercase.next()) {
+        (Some(lower), None) => lower,
+        _ => c,
+    }
+}
+
+/// The hyphenation patterns for a language tag such as `de-CH`, chosen by its primary language subtag.
+fn hyphenation_language(tag: &str) -> Option<Lang> {
+    let primary = tag.split(['-', '_']).next()?.to_ascii_lowercase();
+    let code: [u8; 2] = primary.as_bytes().try_into().ok()?;
+
+    Lang::from_iso(code)
+}
+
+/// The widest tab stop in spaces. Every tab is expanded into spaces before shaping, so a huge `tab-size` would make a
+/// single tab allocate and shape that many spaces.
+const MAX_TAB_SIZE: usize = 100;
+
+/// The number of spaces a tab stop is wide, a `tab-size` length is rounded to the nearest number of spaces of the font.
+fn tab_size_in_spaces(text_ctx: &mut TextContext, text_desc: &TextDescription, tab_size: TabSize) -> usize {
+    const MAX_LINE_WIDTH: f64 = 1.0e6;
+
+    let spaces = match tab_size {
+        TabSize::Number(spaces) => spaces,
+        TabSize::Length(length) => {
+            let (space, _) = text_ctx.measure_text_that_fits(" ", text_desc, MAX_LINE_WIDTH);
+
+            if space.width > 0.0 {
+                length.value() / space.width
+            } else {
+                0.0
+            }
+        }
+    };
+
+    tab_stop_columns(spaces)
+}
+
+/// Rounds a tab size to whole columns, at most `MAX_TAB_SIZE`.
+fn tab_stop_columns(spaces: f64) -> usize {
+    if spaces.is_nan() {
+        return 0;
+    }
+
+    spaces.round().clamp(0.0, MAX_TAB_SIZE as f64) as usize
+}
+
+/// Replaces every tab character with the spaces up to the next tab stop, the tab stops being `tab_size` columns
+/// apart. Columns are counted from the start of the text run or its last newline.
+fn expand_tabs(content: &str, tab_size: usize) -> String {
+    let mut expanded = String::with_capacity(content.len());
+    let mut column = 0;
+
+    for c in content.chars() {
+        match c {
+            '\t' => {
+                let spaces = if tab_size == 0 {
+                    0
+                } else {
+                    tab_size - column % tab_size
+                };
+
+                expanded.extend(std::iter::repeat_n(' ', spaces));
+                column += spaces;
+            }
+            '\n' => {
+                expanded.push(c);
+                column = 0;
+            }
+            _ => {
+                expanded.push(c);
+                column += 1;
+            }
+        }
+    }
+
+    expanded
+}
+
 /// Measure a single-line text segment (no embedded newlines) and add it to
 /// the current [`LineBox`], word-wrapping across multiple lines when the
 /// text exceeds `available_width`.
@@ -119,7 +279,7 @@ fn layout_text_segment<'node>(
             - CHARACTER_WIDTH;
         let remaining_line_space = (available_width - line.line_box.width).max(0.0);
 
-        if remaining_line_space < 1.0 && line.line_box.width > 0.0 {
+        if remaining_line_space < 1.0 && line.line_box.has_content() {
             flush_fragment(nodes, line, text, &mut current_fragment_buffers, current_fragment_w, current_fragment_h);
             current_fragment_w = 0.0;
             current_fragment_h = 0.0;
@@ -185,6 +345,7 @@ fn flush_fragment<'node>(
     let fragment = TextFragment {
         size: Rect::new(0.0, 0.0, width, height),
         buffers: std::mem::take(buffers),
+        spacing: text.desc.spacing,
 
         #[cfg(debug_assertions)]
         debug_content: text.content.to_string(),
@@ -199,7 +360,7 @@ fn flush_fragment<'node>(
 }
 
 fn flush_newline_marker<'node>(nodes: &mut [Option<LayoutNode>], line: &mut LineBoxBuilder<'node>, text: &TextInput) {
-    let line_height = text.style.line_height * text.style.font_size;
+    let line_height = text.style.line_height.to_px(text.style.font_size);
     let node = nodes[text.layout_id.index()].get_or_insert_with(|| {
         LayoutNode::builder(text.layout_id)
             .colors(LayoutColors::text_only(text.style.color))
@@ -211,6 +372,7 @@ fn flush_newline_marker<'node>(nodes: &mut [Option<LayoutNode>], line: &mut Line
     let fragment = TextFragment {
         size: Rect::new(0.0, 0.0, 0.0, line_height),
         buffers: Vec::new(),
+        spacing: TextSpacing::default(),
 
         #[cfg(debug_assertions)]
         debug_content: String::new(),
@@ -221,3 +383,73 @@ fn flush_newline_marker<'node>(nodes: &mut [Option<LayoutNode>], line: &mut Line
     line.line_box
         .add_fragment(text.layout_id, idx, text.style, &mut node.text_fragments[idx].size, line_height, 0.0);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hyphenate_auto() {
+        let hyphenated = hyphenate("An extensive Hyphenation.", Hyphens::Auto, None);
+
+        assert_eq!(hyphenated.replace(SOFT_HYPHEN, ""), "An extensive Hyphenation.");
+        assert!(hyphenated.starts_with("An ex\u{AD}ten\u{AD}sive Hy"));
+    }
+
+    #[test]
+    fn test_hyphenate_by_language() {
+        let word = "Rindfleischetikettierungsüberwachungsaufgabenübertragungsgesetz";
+        let hyphenated = hyphenate(word, Hyphens::Auto, Some("de-DE"));
+
+        assert_eq!(hyphenated.replace(SOFT_HYPHEN, ""), word);
+
+        // The German patterns break the compound between its parts, and never within `sch`.
+        assert!(hyphenated.starts_with("Rind\u{AD}fleisch"));
+        assert!(hyphenated.ends_with("\u{AD}ge\u{AD}setz"));
+        assert_eq!(hyphenated.matches("\u{AD}über\u{AD}").count(), 2);
+        assert!(hyphenated.contains("\u{AD}auf\u{AD}"));
+        assert!(!hyphenated.contains("s\u{AD}ch") && !hyphenated.contains("sc\u{AD}h"));
+
+        // Without patterns for the language the text is not hyphenated.
+        assert_eq!(hyphenate("extensive", Hyphens::Auto, Some("x-klingon")), "extensive");
+    }
+
+    #[test]
+    fn test_hyphenate_keeps_characters_whose_lowercase_has_another_length() {
+        // The Kelvin sign lowercases to a one byte `k`, and `Ⱥ` to a three byte `ⱥ`.
+        for word in [
+            "\u{212A}ILOMETERS",
+            "Ⱥnticonstitutional",
+            "ȺȺȺȺȺȺȺȺ",
+            "İnternational",
+        ] {
+            let hyphenated = hyphenate(word, Hyphens::Auto, None);
+
+            assert_eq!(hyphenated.replace(SOFT_HYPHEN, ""), word);
+        }
+
+        assert!(hyphenate("Ⱥnticonstitutional", Hyphens::Auto, None).contains(SOFT_HYPHEN));
+    }
+
+    #[test]
+    fn test_expand_tabs_to_next_tab_stop() {
+        assert_eq!(expand_tabs("abc\td", 4), "abc d");
+        assert_eq!(expand_tabs("\tx\n1234\ty", 4), "    x\n1234    y");
+        assert_eq!(expand_tabs("a\tb", 0), "ab");
+    }
+
+    #[test]
+    fn test_tab_stop_columns_are_clamped() {
+        assert_eq!(tab_stop_columns(4.4), 4);
+        assert_eq!(tab_stop_columns(-3.0), 0);
+        assert_eq!(tab_stop_columns(1.0e12), MAX_TAB_SIZE);
+        assert_eq!(tab_stop_columns(f64::INFINITY), MAX_TAB_SIZE);
+        assert_eq!(tab_stop_columns(f64::NAN), 0);
+    }
+
+    #[test]
+    fn test_hyphenate_none_removes_soft_hyphens() {
+        assert_eq!(hyphenate("ex\u{AD}ten\u{AD}sive", Hyphens::None, None), "extensive");
+        assert_eq!(hyphenate("ex\u{AD}ten\u{AD}sive", Hyphens::Manual, None), "ex\u{AD}ten\u{AD}sive");
+    }
+}
@@ -0,0 +1,337 @@
+//! Inline layout in the vertical writing modes, where the lines are columns of text read from top to bottom that
+//! progress from right to left (`vertical-rl`) or from left to right (`vertical-lr`).
+//!
+//! Every character is set upright and stacked below the previous one, as with `text-orientation: upright`, so Latin
+//! text is not turned sideways. Only text and line breaks are laid out in columns, content with inline boxes, replaced
+//! elements or inline blocks is laid out horizontally instead, so that none of it is lost.
+
+use std::sync::Arc;
+
+use css_display::LayoutNodeId;
+use css_values::{
+    OverflowWrap,
+    text::{Hyphens, Whitespace, WordBreak, WritingMode},
+};
+
+use crate::{
+    LayoutColors, LayoutNode, LayoutState, Rect,
+    context::{TextDescription, TextFragment, TextSpacing},
+    mode::inline::{
+        InlineContext, InlineLayout,
+        collection::{InlineItem, TextRun},
+    },
+};
+
+/// The characters of a text run placed in the same column.
+struct ColumnPiece<'item, 'input> {
+    run: &'item TextRun<'input>,
+    text: String,
+    column: usize,
+
+    /// The offset of the first character from the top of the column.
+    offset: f64,
+}
+
+/// Places the characters of the text runs in columns of a fixed height, starting a new column when the current one is
+/// full or at a line break.
+struct ColumnFlow<'item, 'input> {
+    height: f64,
+    widths: Vec<f64>,
+    cursor: f64,
+    extent: f64,
+    pieces: Vec<ColumnPiece<'item, 'input>>,
+}
+
+impl<'item, 'input> ColumnFlow<'item, 'input> {
+    fn new(height: f64) -> Self {
+        Self {
+            height,
+            widths: vec![0.0],
+            cursor: 0.0,
+            extent: 0.0,
+            pieces: Vec::new(),
+        }
+    }
+
+    const fn column(&self) -> usize {
+        self.widths.len() - 1
+    }
+
+    fn break_column(&mut self) {
+        self.widths.push(0.0);
+        self.cursor = 0.0;
+    }
+
+    fn place_run(&mut self, run: &'item TextRun<'input>) {
+        let advance = run.style.font_size;
+        let column_width = run.style.line_height.to_px(run.style.font_size);
+        let mut piece = ColumnPiece {
+            run,
+            text: String::new(),
+            column: self.column(),
+            offset: self.cursor,
+        };
+
+        for c in run.content.chars() {
+            let overflows = self.cursor > 0.0 && self.cursor + advance > self.height;
+
+            if c == '\n' || overflows {
+                self.push(piece);
+                self.break_column();
+
+                piece = ColumnPiece {
+                    run,
+                    text: String::new(),
+                    column: self.column(),
+                    offset: 0.0,
+                };
+
+                // The line break itself and a space at the start of a column take no room.
+                if c == '\n' || c == ' ' {
+                    continue;
+                }
+            }
+
+            piece.text.push(c);
+            self.cursor += advance;
+            self.extent = self.extent.max(self.cursor);
+
+            let column = self.column();
+            self.widths[column] = self.widths[column].max(column_width);
+        }
+
+        self.push(piece);
+    }
+
+    fn push(&mut self, piece: ColumnPiece<'item, 'input>) {
+        if !piece.text.is_empty() {
+            self.pieces.push(piece);
+        }
+    }
+
+    /// The left edge of every column, the first column being at the right edge of the containing block for
+    /// `vertical-rl` and at its left edge for `vertical-lr`.
+    fn column_positions(&self, containing_block: Rect, writing_mode: WritingMode) -> Vec<f64> {
+        let mut positions = Vec::with_capacity(self.widths.len());
+        let mut progressed = 0.0;
+
+        for width in &self.widths {
+            positions.push(match writing_mode {
+                WritingMode::VerticalRl => containing_block.x + containing_block.width - progressed - width,
+                _ => containing_block.x + progressed,
+            });
+            progressed += width;
+        }
+
+        positions
+    }
+}
+
+/// Whether the inline items can be laid out in columns, which is the case when they are only text and line breaks.
+pub fn can_layout_vertical(items: &[InlineItem<'_>]) -> bool {
+    items
+        .iter()
+        .all(|item| matches!(item, InlineItem::TextRun(_) | InlineItem::Break { .. }))
+}
+
+/// Lays out the inline items in columns, see the module documentation. The items must be accepted by
+/// `can_layout_vertical`.
+///
+/// The columns are as tall as the containing block, or as the viewport when its height is not known.
+pub fn layout_vertical<'input>(
+    state: &mut LayoutState<'_, 'input>,
+    items: &[InlineItem<'input>],
+    inline_ctx: InlineContext,
+) -> (Vec<LayoutNodeId>, Vec<Rect>, Rect) {
+    const MAX_COLUMN_WIDTH: f64 = 1.0e6;
+
+    let containing_block = inline_ctx.containing_block;
+    let column_height = if containing_block.height.is_finite() && containing_block.height > 0.0 {
+        containing_block.height
+    } else {
+        state.position_ctx.viewport().height
+    };
+
+    let mut flow = ColumnFlow::new(column_height);
+
+    for item in items {
+        match item {
+            InlineItem::TextRun(run) => flow.place_run(run),
+            InlineItem::Break { .. } => flow.break_column(),
+            _ => unreachable!("{item} cannot be laid out in columns"),
+        }
+    }
+
+    let positions = flow.column_positions(containing_block, inline_ctx.writing_mode);
+    let mut ids: Vec<LayoutNodeId> = Vec::new();
+
+    for piece in &flow.pieces {
+        let style = piece.run.style;
+        let text_desc = TextDescription {
+            whitespace: &Whitespace::Pre,
+            line_height_px: style.font_size,
+            font_family: &style.font_family,
+            font_weight: style.font_weight,
+            font_size_px: style.font_size,
+            font_size_adjust: style.font_size_adjust,
+            word_break: WordBreak::Normal,
+            overflow_wrap: OverflowWrap::Normal,
+            hyphens: Hyphens::Manual,
+            hyphenate_character: style.hyphenate_character.as_str(),
+            spacing: TextSpacing::default(),
+            font_kerning: style.font_kerning,
+            font_variant_ligatures: style.font_variant_ligatures,
+            font_variant_numeric: style.font_variant_numeric,
+            font_variant_caps: style.font_variant_caps,
+            font_feature_settings: &style.font_feature_settings,
+        };
+
+        // Every character on a line of its own, one em apart.
+        let stacked = piece
+            .text
+            .chars()
+            .map(String::from)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let (measured, _) = state
+            .input
+            .text
+            .measure_text_that_fits(&stacked, &text_desc, MAX_COLUMN_WIDTH);
+
+        let column_width = flow.widths[piece.column];
+        let x = positions[piece.column] + (column_width - measured.width).max(0.0) / 2.0;
+        let y = containing_block.y + piece.offset;
+
+        let layout_id = *piece.run.layout_id;
+        let node = state.nodes[layout_id.index()].get_or_insert_with(|| {
+            LayoutNode::builder(layout_id)
+                .colors(LayoutColors::text_only(style.color))
+                .cursor(style.cursor)
+                .node_id(*piece.run.node_id)
+                .build()
+        });
+
+        node.text_fragments.push(TextFragment {
+            size: Rect::new(x, y, measured.width, measured.height),
+            buffers: vec![Arc::new(measured.buffer)],
+            spacing: TextSpacing::default(),
+
+            #[cfg(debug_assertions)]
+            debug_content: piece.text.clone(),
+        });
+
+        if !ids.contains(&layout_id) {
+            ids.push(layout_id);
+        }
+    }
+
+    let mut node_dimensions = Vec::with_capacity(ids.len());
+
+    for id in &ids {
+        let Some(node) = state.nodes[id.index()].as_mut() else {
+            continue;
+        };
+
+        let mut node_bbox: Option<Rect> = None;
+        for fragment in &node.text_fragments {
+            Rect::<f64>::union_rect(&mut node_bbox, fragment.size);
+        }
+
+        node.dimensions = node_bbox.unwrap_or_default();
+        node_dimensions.push(node.dimensions);
+    }
+
+    InlineLayout::finalize_fragment_positions(state.nodes, &ids);
+
+    let container = Rect::new(containing_block.x, containing_block.y, containing_block.width, flow.extent);
+
+    (ids, node_dimensions, container)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use css_cssom::{CSSStyleSheet, CssParser};
+    use css_display::BoxTree;
+    use css_style::{AbsoluteContext, StyleTree};
+    use html_dom::{DocumentRoot, Element, HtmlTag, NodeData, Tag};
+    use url::Url;
+
+    use crate::{ImageContext, LayoutInput, LayoutTree, TextContext};
+
+    use super::*;
+
+    const VERTICAL_CSS: &str =
+        "div { display: block; writing-mode: vertical-rl; height: 100px; font-size: 20px; line-height: 1.5 }";
+
+    fn element(tag: HtmlTag) -> NodeData {
+        NodeData::Element(Element::new(Tag::Html(tag), HashSet::new(), HashMap::new()))
+    }
+
+    fn layout(dom: &DocumentRoot, css: &str) -> LayoutTree {
+        let stylesheets = [CSSStyleSheet::from(
+            CssParser::default().parse_css(css, false),
+        )];
+        let url = Url::parse("http://localhost").unwrap();
+        let styles = StyleTree::build(None, &AbsoluteContext::default_url(&url), dom, &stylesheets);
+        let box_tree = BoxTree::new(dom, &styles);
+
+        let mut text_ctx = TextContext::default();
+        let image_ctx = ImageContext::new();
+        let mut input = LayoutInput {
+            dom,
+            box_tree: &box_tree,
+            text: &mut text_ctx,
+            image: &image_ctx,
+        };
+        LayoutTree::compute_layout(&mut input, Rect::new(0.0, 0.0, 800.0, 600.0))
+    }
+
+    #[test]
+    fn test_vertical_rl_columns_progress_leftwards() {
+        let mut dom = DocumentRoot::new();
+        let div = dom.push_node(&element(HtmlTag::Div), None);
+        let text = dom.push_node(&NodeData::Text("日本語の縦書きです".to_string()), Some(div));
+
+        let tree = layout(&dom, VERTICAL_CSS);
+
+        let node = tree
+            .nodes
+            .iter()
+            .flatten()
+            .find(|node| node.node_id == Some(text))
+            .unwrap();
+        assert_eq!(node.text_fragments.len(), 2);
+
+        let first = node.text_fragments[0].size;
+        let second = node.text_fragments[1].size;
+
+        // Five characters fit in a column of 100px, the rest continue in the next column to the left.
+        assert!(node.dimensions.x + second.x < node.dimensions.x + first.x);
+        assert_eq!(first.y, second.y);
+        assert!(first.height > second.height);
+        assert!(node.dimensions.x + first.x + first.width <= 800.0);
+    }
+
+    #[test]
+    fn test_inline_boxes_fall_back_to_horizontal_layout() {
+        let mut dom = DocumentRoot::new();
+        let div = dom.push_node(&element(HtmlTag::Div), None);
+        dom.push_node(&NodeData::Text("日本語の".to_string()), Some(div));
+        let span = dom.push_node(&element(HtmlTag::Span), Some(div));
+        let text = dom.push_node(&NodeData::Text("縦書きです".to_string()), Some(span));
+
+        let css = format!("{VERTICAL_CSS} span {{ display: inline; border: 1px solid black }}");
+        let tree = layout(&dom, &css);
+
+        let find = |node_id| {
+            tree.nodes
+                .iter()
+                .flatten()
+                .find(|node| node.node_id == Some(node_id))
+        };
+        assert!(find(span).is_some());
+        assert!(find(text).is_some_and(|node| !node.text_fragments.is_empty()));
+    }
+}
@@ -159,6 +159,7 @@ mod tests {
                 node_id: &NodeId(1),
                 content: "A ".to_string(),
                 style: &style,
+                lang: None,
             }),
             InlineItem::InlineBoxStart {
                 layout_id: &id2,
@@ -170,6 +171,7 @@ mod tests {
                 node_id: &NodeId(3),
                 content: " ".to_string(),
                 style: &style,
+                lang: None,
             }),
             InlineItem::InlineBoxEnd { layout_id: &id2 },
             InlineItem::TextRun(TextRun {
@@ -177,6 +179,7 @@ mod tests {
                 node_id: &NodeId(4),
                 content: "B".to_string(),
                 style: &style,
+                lang: None,
             }),
         ];
 
@@ -208,6 +211,7 @@ mod tests {
                 node_id: &NodeId(1),
                 content: "A ".to_string(),
                 style: &style,
+                lang: None,
             }),
             InlineItem::InlineFlowRoot {
                 layout_id: &node.layout_id,
@@ -218,6 +222,7 @@ mod tests {
                 node_id: &NodeId(3),
                 content: " B".to_string(),
                 style: &style,
+                lang: None,
             }),
         ];
 
@@ -1,25 +1,56 @@
 use css_display::LayoutNodeId;
-use css_style::Position;
-use css_values::cursor::Cursor;
+use css_style::{ComputedBoxShadow, ComputedDropShadow, Position};
+use css_values::{
+    Resize,
+    background::{BlendMode, Isolation},
+    cursor::Cursor,
+};
 use html_dom::NodeId;
 
-use crate::{ImageData, LayoutColors, Margin, Rect, context::TextFragment, primitives::SideOffset};
+use crate::{CornerRadii, ImageData, LayoutColors, Margin, Rect, context::TextFragment, primitives::SideOffset};
 
 /// A node in the layout tree representing a rendered element
 #[derive(Debug, Clone)]
 pub struct LayoutNode {
     pub block_formatting_context: bool,
     pub border: SideOffset,
+
+    /// The radii of the corners of the border box, zero for square corners.
+    pub border_radius: CornerRadii,
+
+    /// The shadows of `box-shadow`, the first drawn on top.
+    pub box_shadow: Vec<ComputedBoxShadow>,
     pub children: Vec<LayoutNodeId>,
     pub colors: LayoutColors,
     pub cursor: Cursor,
     pub dimensions: Rect,
+
+    /// The shadows of the `drop-shadow()` functions of the `filter` of the element, in the order they are applied.
+    pub drop_shadow: Vec<ComputedDropShadow>,
     pub image_data: Option<ImageData>,
+
+    /// Whether the element isolates the blending of its descendants from the content behind it.
+    pub isolation: Isolation,
     pub layout_id: LayoutNodeId,
     pub margin: Margin,
+
+    /// How the element, with its descendants, is blended with the content behind it.
+    pub mix_blend_mode: BlendMode,
     pub node_id: Option<NodeId>,
     pub padding: SideOffset,
     pub position: Position,
+
+    /// The directions the user can resize the element in, `none` for elements that cannot be resized.
+    pub resize: Resize,
+
+    /// The area covered by the padding box of the element and everything it contains, including content overflowing
+    /// it, see [`LayoutTree::update_scrollable_overflow`](crate::LayoutTree::update_scrollable_overflow).
+    pub scrollable_overflow: Rect,
+
+    /// Whether the contents of a `content-visibility: auto` element were skipped because it was outside the visible
+    /// area, in which case it has no children until [`LayoutTree::reveal_skipped`](crate::LayoutTree::reveal_skipped)
+    /// lays them out.
+    pub skipped: bool,
     pub text_fragments: Vec<TextFragment>,
 }
 
@@ -32,6 +63,62 @@ impl LayoutNode {
     pub fn insert_child(&mut self, child_id: LayoutNodeId) {
         self.children.push(child_id);
     }
+
+    /// The border box of the element, its dimensions extended by its padding.
+    #[must_use]
+    pub fn border_box(&self) -> Rect {
+        Rect::new(
+            self.dimensions.x,
+            self.dimensions.y,
+            self.dimensions.width + self.padding.horizontal(),
+            self.dimensions.height + self.padding.vertical(),
+        )
+    }
+
+    /// The padding box of the element, its border box without its borders.
+    #[must_use]
+    pub fn padding_box(&self) -> Rect {
+        let border_box = self.border_box();
+
+        Rect::new(
+            border_box.x + self.border.left,
+            border_box.y + self.border.top,
+            (border_box.width - self.border.horizontal()).max(0.0),
+            (border_box.height - self.border.vertical()).max(0.0),
+        )
+    }
+
+    /// The inner width of the element, like `clientWidth`, which is the width of its padding box. Scrollbars are not
+    /// laid out, so none is subtracted.
+    #[must_use]
+    pub fn client_width(&self) -> f64 {
+        self.padding_box().width
+    }
+
+    /// The inner height of the element, like `clientHeight`, which is the height of its padding box.
+    #[must_use]
+    pub fn client_height(&self) -> f64 {
+        self.padding_box().height
+    }
+
+    /// The width of the content of the element including overflow, like `scrollWidth`. Content overflowing the start
+    /// of the padding box can not be scrolled to and is not counted.
+    #[must_use]
+    pub fn scroll_width(&self) -> f64 {
+        let padding_box = self.padding_box();
+        let overflow = self.scrollable_overflow;
+
+        (overflow.x + overflow.width - padding_box.x).max(padding_box.width)
+    }
+
+    /// The height of the content of the element including overflow, like `scrollHeight`.
+    #[must_use]
+    pub fn scroll_height(&self) -> f64 {
+        let padding_box = self.padding_box();
+        let overflow = self.scrollable_overflow;
+
+        (overflow.y + overflow.height - padding_box.y).max(padding_box.height)
+    }
 }
 
 /// Builder pattern for constructing a `LayoutNode`.
@@ -46,16 +133,24 @@ impl NodeBuilder {
             layout_node: LayoutNode {
                 block_formatting_context: false,
                 border: SideOffset::default(),
+                border_radius: CornerRadii::default(),
+                box_shadow: Vec::new(),
                 children: Vec::new(),
                 colors: LayoutColors::default(),
                 cursor: Cursor::default(),
                 dimensions: Rect::default(),
+                drop_shadow: Vec::new(),
                 image_data: None,
+                isolation: Isolation::Auto,
                 layout_id,
                 margin: Margin::default(),
+                mix_blend_mode: BlendMode::Normal,
                 node_id: None,
                 padding: SideOffset::default(),
                 position: Position::Static,
+                resize: Resize::None,
+                scrollable_overflow: Rect::default(),
+                skipped: false,
                 text_fragments: vec![],
             },
         }
@@ -71,6 +166,16 @@ impl NodeBuilder {
         self
     }
 
+    pub const fn border_radius(mut self, border_radius: CornerRadii) -> Self {
+        self.layout_node.border_radius = border_radius;
+        self
+    }
+
+    pub fn box_shadow(mut self, box_shadow: Vec<ComputedBoxShadow>) -> Self {
+        self.layout_node.box_shadow = box_shadow;
+        self
+    }
+
     pub fn children(mut self, children: Vec<LayoutNodeId>) -> Self {
         self.layout_node.children = children;
         self
@@ -91,16 +196,31 @@ impl NodeBuilder {
         self
     }
 
+    pub fn drop_shadow(mut self, drop_shadow: Vec<ComputedDropShadow>) -> Self {
+        self.layout_node.drop_shadow = drop_shadow;
+        self
+    }
+
     pub fn image_data(mut self, image_data: ImageData) -> Self {
         self.layout_node.image_data = Some(image_data);
         self
     }
 
+    pub const fn isolation(mut self, isolation: Isolation) -> Self {
+        self.layout_node.isolation = isolation;
+        self
+    }
+
     pub const fn margin(mut self, margin: Margin) -> Self {
         self.layout_node.margin = margin;
         self
     }
 
+    pub const fn mix_blend_mode(mut self, mix_blend_mode: BlendMode) -> Self {
+        self.layout_node.mix_blend_mode = mix_blend_mode;
+        self
+    }
+
     pub const fn node_id(mut self, node_id: NodeId) -> Self {
         self.layout_node.node_id = Some(node_id);
         self
@@ -121,6 +241,16 @@ impl NodeBuilder {
         self
     }
 
+    pub const fn resize(mut self, resize: Resize) -> Self {
+        self.layout_node.resize = resize;
+        self
+    }
+
+    pub const fn skipped(mut self, skipped: bool) -> Self {
+        self.layout_node.skipped = skipped;
+        self
+    }
+
     pub fn text_fragments(mut self, text_fragments: Vec<TextFragment>) -> Self {
         self.layout_node.text_fragments = text_fragments;
         self
@@ -4,4 +4,4 @@ mod offset;
 
 pub use colors::LayoutColors;
 pub use dimension::{Rect, Size};
-pub use offset::{Margin, MarginValue, SideOffset};
+pub use offset::{CornerRadii, Margin, MarginValue, SideOffset};
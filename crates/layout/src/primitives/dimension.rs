@@ -25,6 +25,15 @@ impl<T: Add<Output = T> + PartialOrd + Copy> Rect<T> {
         px >= self.x && px <= self.x + self.width && py >= self.y && py <= self.y + self.height
     }
 
+    /// Whether the rectangle overlaps `other`, rectangles that only touch at an edge do not overlap.
+    #[must_use]
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.x < other.x + other.width
+            && other.x < self.x + self.width
+            && self.y < other.y + other.height
+            && other.y < self.y + self.height
+    }
+
     pub fn max(self, other: Self) -> Self {
         let new_x = if self.x >= other.x { self.x } else { other.x };
         let new_y = if self.y >= other.y { self.y } else { other.y };
@@ -1,4 +1,4 @@
-use css_style::ComputedMargin;
+use css_style::{ComputedMargin, ComputedStyle};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MarginValue {
@@ -108,3 +108,113 @@ impl SideOffset {
         Self::default()
     }
 }
+
+/// Resolved radii of the four corners of a border box in pixels, from `border-radius`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CornerRadii {
+    pub top_left: f64,
+    pub top_right: f64,
+    pub bottom_right: f64,
+    pub bottom_left: f64,
+}
+
+impl CornerRadii {
+    /// Resolves the corner radii of an element with a border box of the given size. Percentages are taken of the width,
+    /// and all radii are scaled down together when two adjacent ones are larger than the side between them.
+    #[must_use]
+    pub fn resolve(style: &ComputedStyle, width: f64, height: f64) -> Self {
+        let radii = Self {
+            top_left: style.border_radius_top_left.to_px(width),
+            top_right: style.border_radius_top_right.to_px(width),
+            bottom_right: style.border_radius_bottom_right.to_px(width),
+            bottom_left: style.border_radius_bottom_left.to_px(width),
+        };
+
+        let scale = [
+            width / (radii.top_left + radii.top_right),
+            width / (radii.bottom_left + radii.bottom_right),
+            height / (radii.top_left + radii.bottom_left),
+            height / (radii.top_right + radii.bottom_right),
+        ]
+        .into_iter()
+        .filter(|factor| factor.is_finite())
+        .fold(1.0, f64::min);
+
+        Self {
+            top_left: radii.top_left * scale,
+            top_right: radii.top_right * scale,
+            bottom_right: radii.bottom_right * scale,
+            bottom_left: radii.bottom_left * scale,
+        }
+    }
+
+    /// Whether all corners are square.
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.top_left <= 0.0 && self.top_right <= 0.0 && self.bottom_right <= 0.0 && self.bottom_left <= 0.0
+    }
+
+    /// The radii of the inner edge of a border with the given widths, such as the corners of the padding box.
+    #[must_use]
+    pub fn inset(&self, border: SideOffset) -> Self {
+        Self {
+            top_left: (self.top_left - border.top.max(border.left)).max(0.0),
+            top_right: (self.top_right - border.top.max(border.right)).max(0.0),
+            bottom_right: (self.bottom_right - border.bottom.max(border.right)).max(0.0),
+            bottom_left: (self.bottom_left - border.bottom.max(border.left)).max(0.0),
+        }
+    }
+
+    /// The radii of a box grown by the given distance on every side, such as the spread of a shadow. Rounded corners
+    /// grow with it while square corners stay square.
+    #[must_use]
+    pub fn outset(&self, distance: f64) -> Self {
+        let grow = |radius: f64| {
+            if radius > 0.0 {
+                (radius + distance).max(0.0)
+            } else {
+                0.0
+            }
+        };
+
+        Self {
+            top_left: grow(self.top_left),
+            top_right: grow(self.top_right),
+            bottom_right: grow(self.bottom_right),
+            bottom_left: grow(self.bottom_left),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use css_style::ComputedLengthPercentage;
+
+    use super::*;
+
+    #[test]
+    fn test_corner_radii_resolve() {
+        let style = ComputedStyle {
+            border_radius_top_left: ComputedLengthPercentage::Percentage(0.5),
+            border_radius_top_right: ComputedLengthPercentage::Px(10.0),
+            ..Default::default()
+        };
+
+        let radii = CornerRadii::resolve(&style, 40.0, 100.0);
+        assert!((radii.top_left - 20.0).abs() < f64::EPSILON);
+        assert!((radii.top_right - 10.0).abs() < f64::EPSILON);
+        assert!(radii.bottom_left.abs() < f64::EPSILON);
+
+        let radii = CornerRadii::resolve(&style, 20.0, 100.0);
+        assert!((radii.top_left + radii.top_right - 20.0).abs() < 1e-9);
+
+        let inner = radii.inset(SideOffset::all(4.0));
+        assert!(inner.bottom_right.abs() < f64::EPSILON);
+        assert!(!inner.is_zero());
+        assert!(CornerRadii::default().is_zero());
+
+        let outer = inner.outset(6.0);
+        assert!(outer.bottom_right.abs() < f64::EPSILON);
+        assert!((outer.top_left - (inner.top_left + 6.0)).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,328 @@
+//! The geometry of text selections. A position in the text of the page is a text node and a byte offset into the text
+//! laid out for it, which is the content of the node after white space processing, counted across all of its lines.
+
+use css_display::LayoutNodeId;
+use html_dom::NodeId;
+
+use crate::{LayoutTree, Rect, context::SOFT_HYPHEN};
+
+/// A shaped glyph of a text node, with its position in the text of the node and its box on the page.
+#[derive(Debug, Clone, Copy)]
+struct TextGlyph<'tree> {
+    node_id: NodeId,
+    offset: usize,
+    rect: Rect,
+    text: &'tree str,
+}
+
+impl TextGlyph<'_> {
+    /// The position just after the glyph.
+    const fn end(&self) -> usize {
+        self.offset + self.text.len()
+    }
+}
+
+impl LayoutTree {
+    /// The boxes of the glyphs between two positions in the text of the page, the positions may be given in either
+    /// order.
+    ///
+    /// # Arguments
+    /// * `start_node` - The text node of the first position.
+    /// * `start_offset` - The byte offset into the laid out text of `start_node`.
+    /// * `end_node` - The text node of the second position.
+    /// * `end_offset` - The byte offset into the laid out text of `end_node`.
+    ///
+    /// # Returns
+    /// The box and first character of every glyph in the range in tree order, or nothing if either node has no text
+    /// laid out.
+    #[must_use]
+    pub fn glyphs_in_range(
+        &self,
+        start_node: NodeId,
+        start_offset: usize,
+        end_node: NodeId,
+        end_offset: usize,
+    ) -> Vec<(Rect, char)> {
+        let glyphs = self.text_glyphs(&self.root_nodes);
+
+        Self::glyph_range(&glyphs, (start_node, start_offset), (end_node, end_offset))
+            .iter()
+            .filter_map(|glyph| Some((glyph.rect, glyph.text.chars().next()?)))
+            .collect()
+    }
+
+    /// The text between two positions in the text of the page, with a line break between glyphs on different lines.
+    ///
+    /// # Arguments
+    /// * `start` - The text node and byte offset of the first position.
+    /// * `end` - The text node and byte offset of the second position, which may come before `start`.
+    #[must_use]
+    pub fn text_in_range(&self, start: (NodeId, usize), end: (NodeId, usize)) -> String {
+        let glyphs = self.text_glyphs(&self.root_nodes);
+        let mut text = String::new();
+        let mut previous: Option<Rect> = None;
+
+        for glyph in Self::glyph_range(&glyphs, start, end) {
+            if previous.is_some_and(|rect| glyph.rect.y >= rect.y + rect.height) {
+                text.push('\n');
+            }
+
+            text.extend(glyph.text.chars().filter(|&c| c != SOFT_HYPHEN));
+            previous = Some(glyph.rect);
+        }
+
+        text
+    }
+
+    /// Resolves the position in the text of the page closest to the given point, on the line of text under it.
+    ///
+    /// # Returns
+    /// The text node and the byte offset of the glyph boundary nearest to `x`, or `None` if there is no text at the
+    /// height of `y`.
+    #[must_use]
+    pub fn text_position_at(&self, x: f64, y: f64) -> Option<(NodeId, usize)> {
+        let glyphs = self.text_glyphs(&self.root_nodes);
+
+        let glyph = glyphs
+            .iter()
+            .filter(|glyph| y >= glyph.rect.y && y < glyph.rect.y + glyph.rect.height)
+            .min_by(|a, b| {
+                let distance = |glyph: &TextGlyph| {
+                    (glyph.rect.x - x)
+                        .max(x - (glyph.rect.x + glyph.rect.width))
+                        .max(0.0)
+                };
+                distance(a).total_cmp(&distance(b))
+            })?;
+
+        let offset = if x < glyph.rect.x + glyph.rect.width / 2.0 {
+            glyph.offset
+        } else {
+            glyph.end()
+        };
+
+        Some((glyph.node_id, offset))
+    }
+
+    /// The range covering all text of the block formatting context around a position, or of the whole page when there
+    /// is no position.
+    ///
+    /// # Returns
+    /// The positions before the first glyph and after the last glyph of the block, or `None` if it has no text.
+    #[must_use]
+    pub fn block_text_range(&self, position: Option<NodeId>) -> Option<((NodeId, usize), (NodeId, usize))> {
+        let block = position
+            .and_then(|node_id| self.enclosing_block(node_id))
+            .map_or_else(|| self.root_nodes.clone(), |block| vec![block]);
+        let glyphs = self.text_glyphs(&block);
+
+        let first = glyphs.first()?;
+        let last = glyphs.last()?;
+        Some(((first.node_id, first.offset), (last.node_id, last.end())))
+    }
+
+    /// The innermost node establishing a block formatting context that contains the text of a node.
+    fn enclosing_block(&self, node_id: NodeId) -> Option<LayoutNodeId> {
+        let text = self
+            .nodes
+            .iter()
+            .flatten()
+            .find(|node| node.node_id == Some(node_id))?;
+        let path = self.find_path(text.layout_id)?;
+
+        (1..=path.len())
+            .rev()
+            .filter_map(|depth| self.node_at(&path[..depth]))
+            .find(|id| {
+                self.nodes[id.index()]
+                    .as_ref()
+                    .is_some_and(|node| node.block_formatting_context)
+            })
+            .copied()
+    }
+
+    /// The glyphs between two positions, given in either order, of a list of glyphs in tree order.
+    fn glyph_range<'glyphs, 'tree>(
+        glyphs: &'glyphs [TextGlyph<'tree>],
+        start: (NodeId, usize),
+        end: (NodeId, usize),
+    ) -> &'glyphs [TextGlyph<'tree>] {
+        let (Some(start), Some(end)) = (Self::glyph_index(glyphs, start), Self::glyph_index(glyphs, end)) else {
+            return &[];
+        };
+
+        &glyphs[start.min(end)..start.max(end)]
+    }
+
+    /// The index of the first glyph at or after a position, one past the last glyph of the node when the position is
+    /// at the end of its text, or `None` if the node has no glyphs.
+    fn glyph_index(glyphs: &[TextGlyph], (node_id, offset): (NodeId, usize)) -> Option<usize> {
+        let first = glyphs.iter().position(|glyph| glyph.node_id == node_id)?;
+        let count = glyphs[first..]
+            .iter()
+            .take_while(|glyph| glyph.node_id == node_id)
+            .count();
+
+        Some(
+            glyphs[first..first + count]
+                .iter()
+                .position(|glyph| glyph.offset >= offset)
+                .map_or(first + count, |index| first + index),
+        )
+    }
+
+    /// The glyphs of the text nodes below the given nodes, in tree order. The text drawn inside of form controls is not
+    /// part of the page and is left out.
+    fn text_glyphs(&self, roots: &[LayoutNodeId]) -> Vec<TextGlyph<'_>> {
+        let mut glyphs = Vec::new();
+        for root in roots {
+            self.collect_text_glyphs(*root, &mut glyphs);
+        }
+
+        glyphs
+    }
+
+    fn collect_text_glyphs<'tree>(&'tree self, layout_id: LayoutNodeId, glyphs: &mut Vec<TextGlyph<'tree>>) {
+        let Some(Some(node)) = self.nodes.get(layout_id.index()) else {
+            return;
+        };
+
+        if let (Some(node_id), None) = (node.node_id, &node.image_data) {
+            let mut base = 0;
+
+            for fragment in &node.text_fragments {
+                for buffer in &fragment.buffers {
+                    for run in buffer.layout_runs() {
+                        // Glyphs are moved right by the spacing the renderer draws them with.
+                        let mut spacing = 0.0;
+
+                        for glyph in run.glyphs {
+                            let text = run.text.get(glyph.start..glyph.end).unwrap_or_default();
+
+                            glyphs.push(TextGlyph {
+                                node_id,
+                                offset: base + glyph.start,
+                                rect: Rect::new(
+                                    node.dimensions.x + fragment.size.x + f64::from(glyph.x) + spacing,
+                                    node.dimensions.y + fragment.size.y + f64::from(run.line_top),
+                                    f64::from(glyph.w),
+                                    f64::from(run.line_height),
+                                ),
+                                text,
+                            });
+
+                            spacing += fragment.spacing.letter;
+                            if text == " " {
+                                spacing += fragment.spacing.word;
+                            }
+                        }
+                    }
+
+                    base += buffer
+                        .lines
+                        .iter()
+                        .map(|line| line.text().len())
+                        .sum::<usize>();
+                }
+            }
+        }
+
+        for child in &node.children {
+            self.collect_text_glyphs(*child, glyphs);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use css_cssom::{CSSStyleSheet, CssParser};
+    use css_display::BoxTree;
+    use css_style::{AbsoluteContext, StyleTree};
+    use html_dom::{DocumentRoot, Element, HtmlTag, NodeData, Tag};
+    use url::Url;
+
+    use crate::{ImageContext, LayoutInput, TextContext};
+
+    use super::*;
+
+    fn layout(paragraphs: &[&str]) -> (LayoutTree, Vec<NodeId>) {
+        let mut dom = DocumentRoot::new();
+        let body = dom.push_node(
+            &NodeData::Element(Element::new(Tag::Html(HtmlTag::Body), HashSet::new(), HashMap::new())),
+            None,
+        );
+        let texts = paragraphs
+            .iter()
+            .map(|content| {
+                let p = dom.push_node(
+                    &NodeData::Element(Element::new(Tag::Html(HtmlTag::P), HashSet::new(), HashMap::new())),
+                    Some(body),
+                );
+                dom.push_node(&NodeData::Text((*content).to_string()), Some(p))
+            })
+            .collect();
+
+        let css = "body, p { display: block } p { font-size: 16px }";
+        let stylesheets = [CSSStyleSheet::from(
+            CssParser::default().parse_css(css, false),
+        )];
+        let url = Url::parse("http://localhost").unwrap();
+        let styles = StyleTree::build(None, &AbsoluteContext::default_url(&url), &dom, &stylesheets);
+        let box_tree = BoxTree::new(&dom, &styles);
+
+        let mut text_ctx = TextContext::default();
+        let image_ctx = ImageContext::new();
+        let mut input = LayoutInput {
+            dom: &dom,
+            box_tree: &box_tree,
+            text: &mut text_ctx,
+            image: &image_ctx,
+        };
+
+        (LayoutTree::compute_layout(&mut input, Rect::new(0.0, 0.0, 800.0, 600.0)), texts)
+    }
+
+    #[test]
+    fn test_glyphs_in_range_within_node() {
+        let (tree, texts) = layout(&["Hello world"]);
+
+        let glyphs = tree.glyphs_in_range(texts[0], 6, texts[0], 11);
+        let chars: String = glyphs.iter().map(|(_, c)| c).collect();
+        assert_eq!(chars, "world");
+        assert!(glyphs.windows(2).all(|pair| pair[0].0.x < pair[1].0.x));
+
+        let reversed = tree.glyphs_in_range(texts[0], 11, texts[0], 6);
+        assert_eq!(reversed.len(), glyphs.len());
+    }
+
+    #[test]
+    fn test_text_in_range_spans_nodes() {
+        let (tree, texts) = layout(&["First", "Second"]);
+
+        assert_eq!(tree.text_in_range((texts[0], 2), (texts[1], 3)), "rst\nSec");
+        assert_eq!(tree.text_in_range((texts[1], 3), (texts[0], 2)), "rst\nSec");
+    }
+
+    #[test]
+    fn test_text_position_at_nearest_boundary() {
+        let (tree, texts) = layout(&["Hello"]);
+
+        let glyphs = tree.glyphs_in_range(texts[0], 0, texts[0], 5);
+        let (first, _) = glyphs[0];
+        let (last, _) = glyphs[4];
+        let y = first.y + first.height / 2.0;
+
+        assert_eq!(tree.text_position_at(first.x + 0.1, y), Some((texts[0], 0)));
+        assert_eq!(tree.text_position_at(last.x + last.width + 50.0, y), Some((texts[0], 5)));
+        assert_eq!(tree.text_position_at(first.x, first.y - 100.0), None);
+    }
+
+    #[test]
+    fn test_block_text_range_without_position_covers_page() {
+        let (tree, texts) = layout(&["First", "Second"]);
+
+        assert_eq!(tree.block_text_range(None), Some(((texts[0], 0), (texts[1], 6))));
+    }
+}
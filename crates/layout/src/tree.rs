@@ -1,6 +1,9 @@
+use std::collections::HashMap;
+
 use css_display::LayoutNodeId;
+use html_dom::NodeId;
 
-use crate::LayoutNode;
+use crate::{LayoutNode, Rect, Size, context::TextFragment};
 
 /// The root of the layout tree containing all layout nodes
 #[derive(Debug, Clone, Default)]
@@ -15,9 +18,85 @@ pub struct LayoutTree {
 
     /// The total content width of the layout tree
     pub content_width: f64,
+
+    /// The last size of the contents of every `content-visibility: auto` element that was laid out with its contents,
+    /// which it keeps while its contents are skipped when it has a `contain-intrinsic-size: auto`.
+    ///
+    /// <https://drafts.csswg.org/css-sizing-4/#last-remembered>
+    pub remembered_sizes: HashMap<NodeId, Size>,
 }
 
 impl LayoutTree {
+    /// A rough estimate of the memory used by the tree, counting the node storage and the shaped text buffers.
+    ///
+    /// Text buffers shared between fragments are counted once per fragment.
+    #[must_use]
+    pub fn estimated_size_bytes(&self) -> usize {
+        let heap_bytes: usize = self
+            .nodes
+            .iter()
+            .flatten()
+            .map(|node| {
+                node.children.capacity() * size_of::<LayoutNodeId>()
+                    + node.text_fragments.capacity() * size_of::<TextFragment>()
+                    + node
+                        .text_fragments
+                        .iter()
+                        .map(TextFragment::estimated_size_bytes)
+                        .sum::<usize>()
+            })
+            .sum();
+
+        size_of::<Self>()
+            + self.nodes.capacity() * size_of::<Option<LayoutNode>>()
+            + self.root_nodes.capacity() * size_of::<LayoutNodeId>()
+            + heap_bytes
+    }
+
+    /// Computes the scrollable overflow of every node, the union of its padding box with the border boxes, text and
+    /// overflow of its descendants. Must be called again after the geometry of the tree changed.
+    pub fn update_scrollable_overflow(&mut self) {
+        for root in self.root_nodes.clone() {
+            self.update_node_overflow(root);
+        }
+    }
+
+    /// Computes the scrollable overflow of a node and its descendants.
+    ///
+    /// # Returns
+    /// The area the node covers in the scrollable overflow of its parent, its border box and its own overflow.
+    fn update_node_overflow(&mut self, layout_id: LayoutNodeId) -> Option<Rect> {
+        let node = self.nodes.get(layout_id.index())?.as_ref()?;
+        let children = node.children.clone();
+
+        let mut overflow = Some(node.padding_box());
+        for fragment in &node.text_fragments {
+            Rect::union_rect(
+                &mut overflow,
+                Rect::new(
+                    node.dimensions.x + fragment.size.x,
+                    node.dimensions.y + fragment.size.y,
+                    fragment.size.width,
+                    fragment.size.height,
+                ),
+            );
+        }
+
+        for child in children {
+            if let Some(child_area) = self.update_node_overflow(child) {
+                Rect::union_rect(&mut overflow, child_area);
+            }
+        }
+
+        let node = self.nodes[layout_id.index()].as_mut()?;
+        let overflow = overflow.unwrap_or_default();
+        node.scrollable_overflow = overflow;
+
+        let mut area = Some(node.border_box());
+        Rect::union_rect(&mut area, overflow);
+        area
+    }
+
     /// Resolves the layout node at the given (x, y) coordinates
     #[must_use]
     pub fn resolve(&self, x: f64, y: f64) -> Vec<&LayoutNode> {
@@ -101,3 +180,65 @@ impl LayoutTree {
         Some(current)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::primitives::SideOffset;
+
+    use super::*;
+
+    fn tree_with_overflowing_child(child: Rect) -> LayoutTree {
+        let parent_id = LayoutNodeId::new(0);
+        let child_id = LayoutNodeId::new(1);
+
+        let parent = LayoutNode::builder(parent_id)
+            .dimensions(Rect::new(10.0, 10.0, 100.0, 50.0))
+            .padding(SideOffset::all(5.0))
+            .border(SideOffset::all(2.0))
+            .children(vec![child_id])
+            .build();
+        let child = LayoutNode::builder(child_id).dimensions(child).build();
+
+        let mut tree = LayoutTree {
+            root_nodes: vec![parent_id],
+            nodes: vec![Some(parent), Some(child)],
+            content_height: 60.0,
+            content_width: 110.0,
+            ..Default::default()
+        };
+        tree.update_scrollable_overflow();
+        tree
+    }
+
+    #[test]
+    fn test_metrics_without_overflow() {
+        let tree = tree_with_overflowing_child(Rect::new(20.0, 20.0, 50.0, 20.0));
+        let parent = tree.nodes[0].as_ref().unwrap();
+
+        assert_eq!(parent.client_width(), 106.0);
+        assert_eq!(parent.client_height(), 56.0);
+        assert_eq!(parent.scroll_width(), 106.0);
+        assert_eq!(parent.scroll_height(), 56.0);
+    }
+
+    #[test]
+    fn test_estimated_size_counts_nodes() {
+        assert_eq!(LayoutTree::default().estimated_size_bytes(), size_of::<LayoutTree>());
+
+        let tree = tree_with_overflowing_child(Rect::new(20.0, 20.0, 50.0, 20.0));
+        assert_eq!(
+            tree.estimated_size_bytes(),
+            size_of::<LayoutTree>() + 2 * size_of::<Option<LayoutNode>>() + 2 * size_of::<LayoutNodeId>()
+        );
+    }
+
+    #[test]
+    fn test_metrics_with_overflow() {
+        let tree = tree_with_overflowing_child(Rect::new(20.0, 20.0, 300.0, 200.0));
+        let parent = tree.nodes[0].as_ref().unwrap();
+
+        assert_eq!(parent.client_width(), 106.0);
+        assert_eq!(parent.scroll_width(), 308.0);
+        assert_eq!(parent.scroll_height(), 208.0);
+    }
+}
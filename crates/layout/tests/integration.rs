@@ -8,12 +8,11 @@ mod tests {
     use std::net::Ipv4Addr;
     use url::Url;
 
-    use browser_preferences::theme::ThemeCategory;
     use browser_ui::load_fallback_fonts;
     use cosmic_text::FontSystem;
     use css_cssom::{CSSStyleSheet, StylesheetOrigin};
     use css_display::BoxTree;
-    use css_style::{AbsoluteContext, StyleTree};
+    use css_style::{AbsoluteContext, StyleTree, SystemPreferences};
     use css_values::color::Color;
     use html_parser::{BlockedReason, HtmlStreamParser, ParserState};
     use io::embedded::DEFAULT_CSS;
@@ -99,7 +98,7 @@ mod tests {
                 root_font_size: 16.0,
                 root_line_height_multiplier: 1.2,
                 document_url: &url,
-                theme_category: ThemeCategory::Light,
+                system_preferences: SystemPreferences::default(),
                 root_color: Color::BLACK,
             };
 
@@ -474,6 +473,7 @@ mod tests {
                 width: 640,
                 height: 480,
                 rgba: vec![],
+                svg_source: None,
             }
             .into(),
         );
@@ -584,6 +584,7 @@ mod tests {
                 width: 640,
                 height: 480,
                 rgba: vec![],
+                svg_source: None,
             }
             .into(),
         );
@@ -640,6 +641,7 @@ mod tests {
                 width: 640,
                 height: 480,
                 rgba: vec![],
+                svg_source: None,
             }
             .into(),
         );
@@ -2,7 +2,7 @@ use std::sync::Arc;
 use std::{collections::HashMap, fmt::Debug};
 
 use cosmic_text::{Buffer, CacheKey, FontSystem, SwashCache, SwashContent, SwashImage};
-use layout::{Color4f, Rect};
+use layout::{Color4f, PENDING_FONT_METADATA, Rect};
 
 /// A region in the atlas where a glyph is stored
 #[derive(Debug, Clone, Copy)]
@@ -215,6 +215,12 @@ impl GlyphAtlas {
         (self.width, self.height)
     }
 
+    /// The number of bytes used by the atlas texture and the glyph lookup table.
+    #[must_use]
+    pub fn estimated_size_bytes(&self) -> usize {
+        self.width as usize * self.height as usize + self.glyph_cache.capacity() * size_of::<(CacheKey, GlyphRegion)>()
+    }
+
     /// Upload glyph image data to the atlas texture
     fn upload_glyph_data(&self, queue: &wgpu::Queue, image: &SwashImage, atlas_x: u32, atlas_y: u32) {
         let data = match image.content {
@@ -323,15 +329,36 @@ pub struct TextBlockInfo {
 }
 
 impl TextBlockInfo {
-    /// Extract glyph render info from a cosmic-text Buffer
-    fn from_buffer(buffer: &Buffer, base_x: f32, base_y: f32, text_color: Color4f) -> Self {
+    /// Extract glyph render info from a cosmic-text Buffer, moving every glyph after the first of a line right by
+    /// `letter_spacing` and every glyph after a space by `word_spacing`.
+    ///
+    /// Glyphs laid out with a fallback font while their web font is in its block period are left out, they keep their
+    /// room in the layout but are not drawn.
+    fn from_buffer(
+        buffer: &Buffer,
+        base_x: f32,
+        base_y: f32,
+        text_color: Color4f,
+        letter_spacing: f32,
+        word_spacing: f32,
+    ) -> Self {
         let mut info = Self::default();
 
         for run in buffer.layout_runs() {
             let line_y = run.line_y;
+            let mut spacing = 0.0;
 
             for glyph in run.glyphs {
-                let physical = glyph.physical((base_x, base_y), 1.0);
+                let physical = glyph.physical((base_x + spacing, base_y), 1.0);
+
+                spacing += letter_spacing;
+                if run.text.get(glyph.start..glyph.end) == Some(" ") {
+                    spacing += word_spacing;
+                }
+
+                if glyph.metadata == PENDING_FONT_METADATA {
+                    continue;
+                }
 
                 info.glyphs.push(GlyphRenderInfo {
                     cache_key: physical.cache_key,
@@ -347,7 +374,14 @@ impl TextBlockInfo {
 
     /// Extract from an Arc<Buffer>
     #[must_use]
-    pub fn from_arc_buffer(buffer: &Arc<Buffer>, base_x: f32, base_y: f32, text_color: Color4f) -> Self {
-        Self::from_buffer(buffer.as_ref(), base_x, base_y, text_color)
+    pub fn from_arc_buffer(
+        buffer: &Arc<Buffer>,
+        base_x: f32,
+        base_y: f32,
+        text_color: Color4f,
+        letter_spacing: f32,
+        word_spacing: f32,
+    ) -> Self {
+        Self::from_buffer(buffer.as_ref(), base_x, base_y, text_color, letter_spacing, word_spacing)
     }
 }
@@ -0,0 +1,315 @@
+//! The blend modes of the compositing specification, which the colors of an element with a `mix-blend-mode` are
+//! blended with against the colors painted behind it.
+//!
+//! <https://drafts.fxtf.org/compositing/#blending>
+
+use layout::{Color4f, LayoutImage};
+
+/// The blend modes of the compositing specification.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BlendMode {
+    #[default]
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+}
+
+impl BlendMode {
+    /// Blends a source color with a backdrop color.
+    ///
+    /// # Arguments
+    /// * `backdrop` - The color below the source, `Cb` in the specification.
+    /// * `source` - The color being blended, `Cs` in the specification.
+    ///
+    /// # Returns
+    /// The blended color, before it is composited with the backdrop.
+    #[must_use]
+    pub fn blend(self, backdrop: [f32; 3], source: [f32; 3]) -> [f32; 3] {
+        let separable = |f: fn(f32, f32) -> f32| {
+            [
+                f(backdrop[0], source[0]),
+                f(backdrop[1], source[1]),
+                f(backdrop[2], source[2]),
+            ]
+        };
+
+        match self {
+            Self::Normal => source,
+            Self::Multiply => separable(|cb, cs| cb * cs),
+            Self::Screen => separable(screen),
+            Self::Overlay => separable(|cb, cs| hard_light(cs, cb)),
+            Self::Darken => separable(f32::min),
+            Self::Lighten => separable(f32::max),
+            Self::ColorDodge => separable(color_dodge),
+            Self::ColorBurn => separable(color_burn),
+            Self::HardLight => separable(hard_light),
+            Self::SoftLight => separable(soft_light),
+            Self::Difference => separable(|cb, cs| (cb - cs).abs()),
+            Self::Exclusion => separable(|cb, cs| cb + cs - 2.0 * cb * cs),
+            Self::Hue => set_lum(set_sat(source, sat(backdrop)), lum(backdrop)),
+            Self::Saturation => set_lum(set_sat(backdrop, sat(source)), lum(backdrop)),
+            Self::Color => set_lum(source, lum(backdrop)),
+            Self::Luminosity => set_lum(backdrop, lum(source)),
+        }
+    }
+
+    /// Blends a source color with a backdrop color and composites the result over the backdrop.
+    ///
+    /// # Returns
+    /// The color seen where the source is drawn over the backdrop, in straight alpha.
+    #[must_use]
+    pub fn composite(self, backdrop: Color4f, source: Color4f) -> Color4f {
+        let alpha = source.a + backdrop.a * (1.0 - source.a);
+        if alpha <= 0.0 {
+            return Color4f::TRANSPARENT;
+        }
+
+        let cb = [backdrop.r as f32, backdrop.g as f32, backdrop.b as f32];
+        let cs = [source.r as f32, source.g as f32, source.b as f32];
+        let blended = self.blend(cb, cs);
+
+        let channel = |index: usize| {
+            let mixed = f64::from(cs[index] + (blended[index].clamp(0.0, 1.0) - cs[index]) * backdrop.a as f32);
+            (mixed * source.a + f64::from(cb[index]) * backdrop.a * (1.0 - source.a)) / alpha
+        };
+
+        Color4f::rgba(channel(0), channel(1), channel(2), alpha)
+    }
+
+    /// Blends every pixel of an image with a backdrop color and composites it over the backdrop, like `composite`
+    /// does for a single color. Fully transparent pixels are left transparent.
+    ///
+    /// # Returns
+    /// The blended image, with no SVG source since it cannot be rasterized again from it.
+    #[must_use]
+    pub fn composite_image(self, backdrop: Color4f, image: &LayoutImage) -> LayoutImage {
+        let rgba = image
+            .rgba
+            .chunks_exact(4)
+            .flat_map(|pixel| {
+                if pixel[3] == 0 {
+                    return [0; 4];
+                }
+
+                let [r, g, b, a] = [pixel[0], pixel[1], pixel[2], pixel[3]].map(|channel| f64::from(channel) / 255.0);
+                let blended = self.composite(backdrop, Color4f::rgba(r, g, b, a));
+
+                [blended.r, blended.g, blended.b, blended.a]
+                    .map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8)
+            })
+            .collect();
+
+        LayoutImage {
+            width: image.width,
+            height: image.height,
+            rgba,
+            svg_source: None,
+        }
+    }
+}
+
+impl From<layout::BlendMode> for BlendMode {
+    fn from(mode: layout::BlendMode) -> Self {
+        match mode {
+            layout::BlendMode::Normal => Self::Normal,
+            layout::BlendMode::Multiply => Self::Multiply,
+            layout::BlendMode::Screen => Self::Screen,
+            layout::BlendMode::Overlay => Self::Overlay,
+            layout::BlendMode::Darken => Self::Darken,
+            layout::BlendMode::Lighten => Self::Lighten,
+            layout::BlendMode::ColorDodge => Self::ColorDodge,
+            layout::BlendMode::ColorBurn => Self::ColorBurn,
+            layout::BlendMode::HardLight => Self::HardLight,
+            layout::BlendMode::SoftLight => Self::SoftLight,
+            layout::BlendMode::Difference => Self::Difference,
+            layout::BlendMode::Exclusion => Self::Exclusion,
+            layout::BlendMode::Hue => Self::Hue,
+            layout::BlendMode::Saturation => Self::Saturation,
+            layout::BlendMode::Color => Self::Color,
+            layout::BlendMode::Luminosity => Self::Luminosity,
+        }
+    }
+}
+
+fn screen(cb: f32, cs: f32) -> f32 {
+    cb + cs - cb * cs
+}
+
+fn hard_light(cb: f32, cs: f32) -> f32 {
+    if cs <= 0.5 {
+        cb * 2.0 * cs
+    } else {
+        screen(cb, 2.0 * cs - 1.0)
+    }
+}
+
+fn color_dodge(cb: f32, cs: f32) -> f32 {
+    if cb <= 0.0 {
+        0.0
+    } else if cs >= 1.0 {
+        1.0
+    } else {
+        (cb / (1.0 - cs)).min(1.0)
+    }
+}
+
+fn color_burn(cb: f32, cs: f32) -> f32 {
+    if cb >= 1.0 {
+        1.0
+    } else if cs <= 0.0 {
+        0.0
+    } else {
+        1.0 - ((1.0 - cb) / cs).min(1.0)
+    }
+}
+
+fn soft_light(cb: f32, cs: f32) -> f32 {
+    if cs <= 0.5 {
+        return cb - (1.0 - 2.0 * cs) * cb * (1.0 - cb);
+    }
+
+    let d = if cb <= 0.25 {
+        ((16.0 * cb - 12.0) * cb + 4.0) * cb
+    } else {
+        cb.sqrt()
+    };
+    cb + (2.0 * cs - 1.0) * (d - cb)
+}
+
+fn lum(c: [f32; 3]) -> f32 {
+    0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+}
+
+fn clip_color(c: [f32; 3]) -> [f32; 3] {
+    let l = lum(c);
+    let n = c[0].min(c[1]).min(c[2]);
+    let x = c[0].max(c[1]).max(c[2]);
+
+    let mut result = c;
+    if n < 0.0 {
+        result = result.map(|channel| l + (channel - l) * l / (l - n));
+    }
+    if x > 1.0 {
+        result = result.map(|channel| l + (channel - l) * (1.0 - l) / (x - l));
+    }
+    result
+}
+
+fn set_lum(c: [f32; 3], l: f32) -> [f32; 3] {
+    let d = l - lum(c);
+    clip_color(c.map(|channel| channel + d))
+}
+
+fn sat(c: [f32; 3]) -> f32 {
+    c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+}
+
+fn set_sat(c: [f32; 3], s: f32) -> [f32; 3] {
+    let min = c[0].min(c[1]).min(c[2]);
+    let max = c[0].max(c[1]).max(c[2]);
+
+    if max <= min {
+        return [0.0; 3];
+    }
+    c.map(|channel| (channel - min) * s / (max - min))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_color_eq(actual: [f32; 3], expected: [f32; 3]) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-4, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn test_separable_modes() {
+        let backdrop = [0.5, 0.2, 1.0];
+        let source = [0.5, 0.8, 0.0];
+
+        assert_color_eq(BlendMode::Normal.blend(backdrop, source), source);
+        assert_color_eq(BlendMode::Multiply.blend(backdrop, source), [0.25, 0.16, 0.0]);
+        assert_color_eq(BlendMode::Screen.blend(backdrop, source), [0.75, 0.84, 1.0]);
+        assert_color_eq(BlendMode::Darken.blend(backdrop, source), [0.5, 0.2, 0.0]);
+        assert_color_eq(BlendMode::Lighten.blend(backdrop, source), [0.5, 0.8, 1.0]);
+        assert_color_eq(BlendMode::Difference.blend(backdrop, source), [0.0, 0.6, 1.0]);
+        assert_color_eq(BlendMode::Exclusion.blend(backdrop, source), [0.5, 0.68, 1.0]);
+        assert_color_eq(BlendMode::Overlay.blend(backdrop, source), [0.5, 0.32, 1.0]);
+        assert_color_eq(BlendMode::HardLight.blend(backdrop, source), [0.5, 0.68, 0.0]);
+    }
+
+    #[test]
+    fn test_dodge_and_burn_edges() {
+        assert_color_eq(BlendMode::ColorDodge.blend([0.0, 0.5, 0.5], [1.0, 1.0, 0.5]), [0.0, 1.0, 1.0]);
+        assert_color_eq(BlendMode::ColorBurn.blend([1.0, 0.5, 0.5], [0.0, 0.0, 1.0]), [1.0, 0.0, 0.5]);
+    }
+
+    #[test]
+    fn test_soft_light_keeps_backdrop_for_mid_gray() {
+        let backdrop = [0.1, 0.4, 0.9];
+
+        assert_color_eq(BlendMode::SoftLight.blend(backdrop, [0.5; 3]), backdrop);
+    }
+
+    #[test]
+    fn test_non_separable_modes() {
+        let red = [1.0, 0.0, 0.0];
+        let gray = [0.5; 3];
+
+        // A gray backdrop has no saturation, so taking the hue of red keeps it gray.
+        assert_color_eq(BlendMode::Hue.blend(gray, red), gray);
+        assert_color_eq(BlendMode::Saturation.blend(gray, red), gray);
+
+        let colored = BlendMode::Color.blend(gray, red);
+        assert!((lum(colored) - 0.5).abs() < 1e-4);
+        assert!(colored[0] > colored[1] && colored[1] == colored[2]);
+
+        assert_color_eq(BlendMode::Luminosity.blend(red, gray), [1.0, 0.285_714, 0.285_714]);
+    }
+
+    #[test]
+    fn test_composite_image() {
+        let image = LayoutImage {
+            width: 2,
+            height: 1,
+            rgba: vec![255, 0, 0, 255, 255, 0, 0, 0],
+            svg_source: None,
+        };
+
+        let white = Color4f::rgba(1.0, 1.0, 1.0, 1.0);
+        assert_eq!(BlendMode::Multiply.composite_image(white, &image).rgba, [255, 0, 0, 255, 0, 0, 0, 0]);
+
+        let blue = Color4f::rgba(0.0, 0.0, 1.0, 1.0);
+        assert_eq!(BlendMode::Multiply.composite_image(blue, &image).rgba, [0, 0, 0, 255, 0, 0, 0, 0]);
+        assert_eq!(BlendMode::Screen.composite_image(blue, &image).rgba, [255, 0, 255, 255, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_composite() {
+        let white = Color4f::rgba(1.0, 1.0, 1.0, 1.0);
+        let red = Color4f::rgba(1.0, 0.0, 0.0, 1.0);
+        let blue = Color4f::rgba(0.0, 0.0, 1.0, 1.0);
+
+        assert_eq!(BlendMode::Multiply.composite(white, red), red);
+        assert_eq!(BlendMode::Screen.composite(red, blue), Color4f::rgba(1.0, 0.0, 1.0, 1.0));
+
+        // Without a backdrop there is nothing to blend with, the source is drawn as is.
+        assert_eq!(BlendMode::Difference.composite(Color4f::TRANSPARENT, red), red);
+        assert_eq!(BlendMode::Normal.composite(Color4f::TRANSPARENT, Color4f::TRANSPARENT), Color4f::TRANSPARENT);
+    }
+}
@@ -0,0 +1,25 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SvgError {
+    #[error("failed to parse SVG: {0}")]
+    Parse(String),
+
+    #[error("invalid SVG raster size {width}x{height}")]
+    InvalidSize { width: u32, height: u32 },
+}
+
+/// An error decoding an image so it can be rendered.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RenderError {
+    #[error("failed to decode image {url}: {message}")]
+    ImageDecode { url: String, message: String },
+
+    #[error("failed to decode SVG image {url}")]
+    Svg {
+        url: String,
+        #[source]
+        source: SvgError,
+    },
+}
@@ -7,9 +7,15 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use layout::{LayoutImage, NodeId, Rect};
+use layout::{Color4f, ImageRendering, LayoutImage, NodeId, ObjectFit, Rect};
+use tracing::warn;
 use wgpu;
 
+use crate::{
+    BlendMode,
+    svg::{MAX_SVG_SIZE, SvgRenderer},
+};
+
 /// Information needed to render a single image on screen.
 ///
 /// Carried from the render data collection phase (main thread) through
@@ -22,11 +28,97 @@ pub struct ImageRenderInfo {
     pub screen_rect: Rect,
     /// Decoded RGBA image data (width, height, pixels)
     pub data: Arc<LayoutImage>,
+    /// The `image-rendering` of the element, which selects the filter used when scaling
+    pub image_rendering: ImageRendering,
+    /// The `object-fit` of the element, which sizes the image within `screen_rect`
+    pub object_fit: ObjectFit,
+    /// The `mix-blend-mode` the image is blended with and the color painted behind it, `None` if it is not blended
+    pub blend: Option<(BlendMode, Color4f)>,
+}
+
+impl ImageRenderInfo {
+    /// The filter used to sample the texture of the image, `Nearest` keeps the pixels of
+    /// `pixelated` and `crisp-edges` images sharp when they are scaled up.
+    #[must_use]
+    pub const fn filter_mode(&self) -> wgpu::FilterMode {
+        match self.image_rendering {
+            ImageRendering::Auto => wgpu::FilterMode::Linear,
+            ImageRendering::Pixelated | ImageRendering::CrispEdges => wgpu::FilterMode::Nearest,
+        }
+    }
+
+    /// The rectangle the whole image is scaled to per its `object-fit`, centered in `screen_rect`. With `cover` and
+    /// `none` it may extend past `screen_rect`, with `contain` and `scale-down` it may leave parts of it empty.
+    #[must_use]
+    pub fn object_rect(&self) -> Rect {
+        let rect = self.screen_rect;
+        let (width, height) = (f64::from(self.data.width), f64::from(self.data.height));
+
+        if self.object_fit == ObjectFit::Fill || width <= 0.0 || height <= 0.0 {
+            return rect;
+        }
+
+        let contain = (rect.width / width).min(rect.height / height);
+        let scale = match self.object_fit {
+            ObjectFit::Fill | ObjectFit::Contain => contain,
+            ObjectFit::Cover => (rect.width / width).max(rect.height / height),
+            ObjectFit::None => 1.0,
+            ObjectFit::ScaleDown => contain.min(1.0),
+        };
+        let (width, height) = (width * scale, height * scale);
+
+        Rect::new(rect.x + (rect.width - width) / 2.0, rect.y + (rect.height - height) / 2.0, width, height)
+    }
+
+    /// Resolves where the image is drawn and which part of its texture is sampled, cropping the parts of the
+    /// [`object rectangle`](Self::object_rect) outside of `screen_rect`.
+    ///
+    /// # Returns
+    /// The rectangle to draw the image in and the matching texture coordinates, normalized to `0.0..=1.0`.
+    #[must_use]
+    pub fn placement(&self) -> (Rect, Rect) {
+        let object = self.object_rect();
+        let rect = self.screen_rect;
+
+        let x = object.x.max(rect.x);
+        let y = object.y.max(rect.y);
+        let right = (object.x + object.width).min(rect.x + rect.width);
+        let bottom = (object.y + object.height).min(rect.y + rect.height);
+        let dest = Rect::new(x, y, (right - x).max(0.0), (bottom - y).max(0.0));
+
+        if object.width <= 0.0 || object.height <= 0.0 {
+            return (dest, Rect::new(0.0, 0.0, 1.0, 1.0));
+        }
+
+        let uv = Rect::new(
+            (dest.x - object.x) / object.width,
+            (dest.y - object.y) / object.height,
+            dest.width / object.width,
+            dest.height / object.height,
+        );
+
+        (dest, uv)
+    }
 }
 
 /// A single GPU-resident image with its bind group.
 struct GpuImage {
     bind_group: wgpu::BindGroup,
+
+    /// The size of the RGBA texture in bytes.
+    bytes: usize,
+
+    /// The filter of the sampler in the bind group.
+    filter_mode: wgpu::FilterMode,
+
+    /// The size of the texture in pixels.
+    size: (u32, u32),
+
+    /// The blend mode and backdrop the pixels of the texture were blended with.
+    blend: Option<(BlendMode, Color4f)>,
+
+    /// The parsed document of an SVG image, kept to rasterize it again when it is drawn at another size.
+    svg: Option<SvgRenderer>,
 }
 
 /// Cache of GPU textures for images, keyed by source URL.
@@ -37,12 +129,13 @@ struct GpuImage {
 pub struct GpuImageCache {
     cache: HashMap<NodeId, GpuImage>,
     bind_group_layout: wgpu::BindGroupLayout,
-    sampler: wgpu::Sampler,
+    linear_sampler: wgpu::Sampler,
+    nearest_sampler: wgpu::Sampler,
 }
 
 impl GpuImageCache {
     /// Creates a new GPU image cache, including the shared bind group layout
-    /// and sampler that will be used for all image textures.
+    /// and the samplers that will be used for all image textures.
     #[must_use]
     pub fn new(device: &wgpu::Device) -> Self {
         let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
@@ -67,22 +160,26 @@ impl GpuImageCache {
             ],
         });
 
-        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("Image Sampler"),
+        Self {
+            cache: HashMap::new(),
+            bind_group_layout,
+            linear_sampler: Self::create_sampler(device, wgpu::FilterMode::Linear),
+            nearest_sampler: Self::create_sampler(device, wgpu::FilterMode::Nearest),
+        }
+    }
+
+    /// Creates a sampler that magnifies and minifies image textures with the given filter.
+    fn create_sampler(device: &wgpu::Device, filter_mode: wgpu::FilterMode) -> wgpu::Sampler {
+        device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some(&format!("Image Sampler: {filter_mode:?}")),
             address_mode_u: wgpu::AddressMode::ClampToEdge,
             address_mode_v: wgpu::AddressMode::ClampToEdge,
             address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
+            mag_filter: filter_mode,
+            min_filter: filter_mode,
             mipmap_filter: wgpu::FilterMode::Nearest,
             ..Default::default()
-        });
-
-        Self {
-            cache: HashMap::new(),
-            bind_group_layout,
-            sampler,
-        }
+        })
     }
 
     /// Returns the bind group layout used for image textures.
@@ -94,75 +191,134 @@ impl GpuImageCache {
 
     /// Ensures the given image is uploaded to the GPU and returns its bind group.
     ///
-    /// If the image is already cached, returns the existing bind group.
-    /// Otherwise, creates a new GPU texture, uploads the RGBA data, and
-    /// creates a bind group for it.
+    /// If the image is already cached with the same filter and size, returns the existing bind group. Otherwise,
+    /// creates a new GPU texture, uploads the RGBA data, and creates a bind group for it that samples with
+    /// `filter_mode`. An SVG image is rasterized again at `target_size`, the size in pixels it is drawn at, so it
+    /// stays sharp when it is scaled. A blended image is blended again when its blend mode or backdrop changes.
     pub fn ensure_uploaded(
         &mut self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
-        node_id: &NodeId,
-        data: &LayoutImage,
+        image: &ImageRenderInfo,
+        target_size: (u32, u32),
     ) -> &wgpu::BindGroup {
-        if !self.cache.contains_key(node_id) {
-            let texture = device.create_texture(&wgpu::TextureDescriptor {
-                label: Some(&format!("Image Texture: {node_id}")),
-                size: wgpu::Extent3d {
-                    width: data.width,
-                    height: data.height,
-                    depth_or_array_layers: 1,
-                },
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::Rgba8Unorm,
-                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
-                view_formats: &[],
-            });
-
-            queue.write_texture(
-                wgpu::TexelCopyTextureInfo {
-                    texture: &texture,
-                    mip_level: 0,
-                    origin: wgpu::Origin3d::ZERO,
-                    aspect: wgpu::TextureAspect::All,
-                },
-                &data.rgba,
-                wgpu::TexelCopyBufferLayout {
-                    offset: 0,
-                    bytes_per_row: Some(4 * data.width),
-                    rows_per_image: Some(data.height),
-                },
-                wgpu::Extent3d {
-                    width: data.width,
-                    height: data.height,
-                    depth_or_array_layers: 1,
-                },
-            );
+        let node_id = &image.node_id;
+        let data: &LayoutImage = &image.data;
+        let filter_mode = image.filter_mode();
 
-            let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let size = if data.svg_source.is_some() {
+            (target_size.0.clamp(1, MAX_SVG_SIZE), target_size.1.clamp(1, MAX_SVG_SIZE))
+        } else {
+            (data.width, data.height)
+        };
 
-            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some(&format!("Image Bind Group: {node_id}")),
-                layout: &self.bind_group_layout,
-                entries: &[
-                    wgpu::BindGroupEntry {
-                        binding: 0,
-                        resource: wgpu::BindingResource::TextureView(&texture_view),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 1,
-                        resource: wgpu::BindingResource::Sampler(&self.sampler),
-                    },
-                ],
-            });
+        if self.cache.get(node_id).is_none_or(|cached| {
+            cached.filter_mode != filter_mode || cached.size != size || cached.blend != image.blend
+        }) {
+            let mut svg = self.cache.remove(node_id).and_then(|image| image.svg);
+
+            let rasterized = match &data.svg_source {
+                Some(source) if size != (data.width, data.height) => {
+                    if svg.is_none() {
+                        svg = SvgRenderer::parse(source)
+                            .inspect_err(|error| warn!(%error, "Failed to parse SVG image {node_id}"))
+                            .ok();
+                    }
 
-            self.cache.insert(*node_id, GpuImage { bind_group });
+                    svg.as_ref().and_then(|svg| svg.render(size.0, size.1).ok())
+                }
+                _ => None,
+            };
+
+            let pixels = rasterized.as_ref().unwrap_or(data);
+            let blended = image
+                .blend
+                .map(|(mode, backdrop)| mode.composite_image(backdrop, pixels));
+            let pixels = blended.as_ref().unwrap_or(pixels);
+            let bind_group = self.upload(device, queue, node_id, pixels, filter_mode);
+
+            self.cache.insert(
+                *node_id,
+                GpuImage {
+                    bind_group,
+                    bytes: pixels.rgba.len(),
+                    filter_mode,
+                    size,
+                    blend: image.blend,
+                    svg,
+                },
+            );
         }
 
         &self.cache[node_id].bind_group
     }
 
+    /// Creates a texture holding the RGBA pixels of an image and a bind group sampling it with `filter_mode`.
+    fn upload(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        node_id: &NodeId,
+        data: &LayoutImage,
+        filter_mode: wgpu::FilterMode,
+    ) -> wgpu::BindGroup {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(&format!("Image Texture: {node_id}")),
+            size: wgpu::Extent3d {
+                width: data.width,
+                height: data.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            &data.rgba,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * data.width),
+                rows_per_image: Some(data.height),
+            },
+            wgpu::Extent3d {
+                width: data.width,
+                height: data.height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = match filter_mode {
+            wgpu::FilterMode::Linear => &self.linear_sampler,
+            wgpu::FilterMode::Nearest => &self.nearest_sampler,
+        };
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&format!("Image Bind Group: {node_id}")),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&texture_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+            ],
+        })
+    }
+
     /// Returns the bind group for a cached image, if it exists.
     #[must_use]
     pub fn get_bind_group(&self, node_id: &NodeId) -> Option<&wgpu::BindGroup> {
@@ -175,8 +331,62 @@ impl GpuImageCache {
         self.cache.contains_key(node_id)
     }
 
+    /// The number of bytes of GPU memory used by the cached textures.
+    #[must_use]
+    pub fn estimated_size_bytes(&self) -> usize {
+        self.cache.values().map(|image| image.bytes).sum()
+    }
+
     /// Clears all cached GPU textures and bind groups.
     pub fn clear(&mut self) {
         self.cache.clear();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image_info(width: u32, height: u32, screen_rect: Rect, object_fit: ObjectFit) -> ImageRenderInfo {
+        ImageRenderInfo {
+            node_id: NodeId(0),
+            screen_rect,
+            data: Arc::new(LayoutImage {
+                width,
+                height,
+                rgba: vec![0; (width * height * 4) as usize],
+                svg_source: None,
+            }),
+            image_rendering: ImageRendering::Auto,
+            object_fit,
+            blend: None,
+        }
+    }
+
+    #[test]
+    fn test_cover_crops_top_and_bottom_of_portrait_image() {
+        let info = image_info(100, 200, Rect::new(10.0, 20.0, 100.0, 100.0), ObjectFit::Cover);
+
+        let (dest, uv) = info.placement();
+        assert_eq!(dest, Rect::new(10.0, 20.0, 100.0, 100.0));
+        assert_eq!(uv, Rect::new(0.0, 0.25, 1.0, 0.5));
+    }
+
+    #[test]
+    fn test_contain_letterboxes_portrait_image() {
+        let info = image_info(100, 200, Rect::new(0.0, 0.0, 100.0, 100.0), ObjectFit::Contain);
+
+        let (dest, uv) = info.placement();
+        assert_eq!(dest, Rect::new(25.0, 0.0, 50.0, 100.0));
+        assert_eq!(uv, Rect::new(0.0, 0.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_fill_and_scale_down() {
+        let fill = image_info(100, 200, Rect::new(0.0, 0.0, 100.0, 100.0), ObjectFit::Fill);
+        assert_eq!(fill.placement(), (Rect::new(0.0, 0.0, 100.0, 100.0), Rect::new(0.0, 0.0, 1.0, 1.0)));
+
+        let scale_down = image_info(20, 10, Rect::new(0.0, 0.0, 100.0, 100.0), ObjectFit::ScaleDown);
+        assert_eq!(scale_down.object_rect(), Rect::new(40.0, 45.0, 20.0, 10.0));
+    }
+}
@@ -3,6 +3,12 @@
 /// The atlas module handles glyph atlases for text rendering
 mod atlas;
 
+/// The blend module defines the blend modes of `mix-blend-mode`
+mod blend;
+
+/// The errors module defines the error types of the renderer
+pub mod errors;
+
 /// The globals module manages global uniform buffers and bind groups
 mod globals;
 
@@ -15,10 +21,18 @@ mod rect;
 /// The texture module defines rendering pipelines for textured quads and text
 mod texture;
 
+/// The svg module rasterizes SVG documents into images
+mod svg;
+
 /// The vertex module defines vertex structures and layouts
 mod vertex;
 
 pub use atlas::{GlyphAtlas, TextBlockInfo};
+pub use blend::BlendMode;
 pub use image::{GpuImageCache, ImageRenderInfo};
-pub use rect::{RectPipeline, RenderRect, RenderTri};
+pub use rect::{
+    CORNER_SEGMENTS, RectPipeline, RenderRect, RenderShape, RenderTri, rounded_rect_outline,
+    rounded_rect_outline_with_segments,
+};
+pub use svg::{MAX_SVG_SIZE, SvgRenderer};
 pub use texture::TexturePipeline;
@@ -1,6 +1,6 @@
 use bytemuck::{Pod, Zeroable};
 use io::embedded::SOLID_SHADER;
-use layout::{Color4f, Rect};
+use layout::{Color4f, CornerRadii, Rect};
 use tracing::debug;
 use wgpu::{Device, Queue, RenderPipeline, TextureFormat};
 
@@ -17,11 +17,59 @@ pub struct SolidVertex {
     color: [f32; 4],
 }
 
+/// The number of straight segments a rounded corner is approximated with.
+pub const CORNER_SEGMENTS: usize = 8;
+
 /// Data for rectangles to be rendered
 #[derive(Debug, Clone)]
 pub struct RenderRect {
     pub rect: Rect,
     pub background: Color4f,
+
+    /// The radii of the corners of the rectangle, zero for square corners.
+    pub radii: CornerRadii,
+}
+
+/// The outline of a rectangle with rounded corners, clockwise from the left end of the top-left corner.
+///
+/// Every corner has `CORNER_SEGMENTS + 1` points, also when it is square, so that the outlines of two rectangles can be
+/// joined point by point, such as the outer and inner edge of a border.
+#[must_use]
+pub fn rounded_rect_outline(rect: Rect<f32>, radii: CornerRadii) -> Vec<[f32; 2]> {
+    rounded_rect_outline_with_segments(rect, radii, CORNER_SEGMENTS)
+}
+
+/// The outline of a rectangle with rounded corners like `rounded_rect_outline`, with every corner approximated by the
+/// given number of segments instead, such as fewer for shapes that are drawn many times over.
+#[must_use]
+pub fn rounded_rect_outline_with_segments(rect: Rect<f32>, radii: CornerRadii, segments: usize) -> Vec<[f32; 2]> {
+    let segments = segments.max(1);
+    let right = rect.x + rect.width;
+    let bottom = rect.y + rect.height;
+    let top_left = radii.top_left as f32;
+    let top_right = radii.top_right as f32;
+    let bottom_right = radii.bottom_right as f32;
+    let bottom_left = radii.bottom_left as f32;
+
+    let corners = [
+        (rect.x + top_left, rect.y + top_left, top_left, std::f32::consts::PI),
+        (right - top_right, rect.y + top_right, top_right, std::f32::consts::PI * 1.5),
+        (right - bottom_right, bottom - bottom_right, bottom_right, 0.0),
+        (rect.x + bottom_left, bottom - bottom_left, bottom_left, std::f32::consts::FRAC_PI_2),
+    ];
+
+    let mut points = Vec::with_capacity(corners.len() * (segments + 1));
+    for (center_x, center_y, radius, start_angle) in corners {
+        for step in 0..=segments {
+            let angle = start_angle + std::f32::consts::FRAC_PI_2 * step as f32 / segments as f32;
+            points.push([
+                center_x + radius * angle.cos(),
+                center_y + radius * angle.sin(),
+            ]);
+        }
+    }
+
+    points
 }
 
 /// Data for triangles to be rendered
@@ -33,6 +81,26 @@ pub struct RenderTri {
     pub color: Color4f,
 }
 
+/// A solid shape to be rendered. Rectangles and triangles are kept in one list, in paint order, so that a shape
+/// painted later is drawn over the ones painted before it whatever their kind.
+#[derive(Debug, Clone)]
+pub enum RenderShape {
+    Rect(RenderRect),
+    Tri(RenderTri),
+}
+
+impl From<RenderRect> for RenderShape {
+    fn from(rect: RenderRect) -> Self {
+        Self::Rect(rect)
+    }
+}
+
+impl From<RenderTri> for RenderShape {
+    fn from(tri: RenderTri) -> Self {
+        Self::Tri(tri)
+    }
+}
+
 /// A GPU pipeline for rendering solid-colored rectangles using position and color attributes
 pub struct RectPipeline {
     pipeline: RenderPipeline,
@@ -183,6 +251,21 @@ impl RectPipeline {
         self.vertices.extend_from_slice(&quad_vertices);
     }
 
+    /// Pushes a solid-colored rectangle with rounded corners to be rendered, as a fan of triangles from its center
+    pub fn push_rounded_quad(&mut self, rect: Rect<f32>, radii: CornerRadii, background: Color4f) {
+        if radii.is_zero() {
+            self.push_quad(rect, background);
+            return;
+        }
+
+        let center = [rect.x + rect.width / 2.0, rect.y + rect.height / 2.0];
+        let outline = rounded_rect_outline(rect, radii);
+        for (index, point) in outline.iter().enumerate() {
+            let next = outline[(index + 1) % outline.len()];
+            self.push_triangle(center, *point, next, background);
+        }
+    }
+
     /// Pushes a solid-colored triangle to be rendered
     pub fn push_triangle(&mut self, p0: [f32; 2], p1: [f32; 2], p2: [f32; 2], color: Color4f) {
         if self.vertices.len() + 3 > self.max_vertices {
@@ -261,3 +344,50 @@ impl RectPipeline {
         self.vertex_count
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rounded_rect_outline() {
+        let rect = Rect::new(10.0, 20.0, 100.0, 50.0);
+        let radii = CornerRadii {
+            top_left: 10.0,
+            ..CornerRadii::default()
+        };
+
+        let outline = rounded_rect_outline(rect, radii);
+        assert_eq!(outline.len(), 4 * (CORNER_SEGMENTS + 1));
+
+        let close = |a: [f32; 2], b: [f32; 2]| (a[0] - b[0]).abs() < 1e-4 && (a[1] - b[1]).abs() < 1e-4;
+        assert!(close(outline[0], [10.0, 30.0]));
+        assert!(close(outline[CORNER_SEGMENTS], [20.0, 20.0]));
+        assert!(close(outline[CORNER_SEGMENTS + 1], [110.0, 20.0]));
+        assert!(
+            outline
+                .iter()
+                .all(|p| p[0] >= 10.0 - 1e-4 && p[0] <= 110.0 + 1e-4 && p[1] >= 20.0 - 1e-4 && p[1] <= 70.0 + 1e-4)
+        );
+    }
+
+    #[test]
+    fn test_rounded_rect_outline_with_segments() {
+        let rect = Rect::new(0.0, 0.0, 40.0, 40.0);
+        let radii = CornerRadii {
+            top_left: 10.0,
+            top_right: 10.0,
+            bottom_right: 10.0,
+            bottom_left: 10.0,
+        };
+
+        let outline = rounded_rect_outline_with_segments(rect, radii, 2);
+        assert_eq!(outline.len(), 4 * 3);
+
+        let close = |a: [f32; 2], b: [f32; 2]| (a[0] - b[0]).abs() < 1e-4 && (a[1] - b[1]).abs() < 1e-4;
+        assert!(close(outline[0], [0.0, 10.0]));
+        assert!(close(outline[2], [10.0, 0.0]));
+        assert!(close(outline[3], [30.0, 0.0]));
+        assert_eq!(rounded_rect_outline_with_segments(rect, radii, 0).len(), 4 * 2);
+    }
+}
@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use layout::LayoutImage;
+use resvg::{tiny_skia, usvg};
+
+use crate::errors::SvgError;
+
+/// The largest width or height, in pixels, an SVG image is rasterized at.
+pub const MAX_SVG_SIZE: u32 = 8192;
+
+/// A parsed SVG document that can be rasterized at any size.
+///
+/// The document is parsed once with `usvg` and drawn on the CPU with `tiny-skia`, so a vector image can be
+/// rasterized again whenever it is displayed at a new size instead of scaling a bitmap.
+pub struct SvgRenderer {
+    /// The source of the document, kept so the rasterized images can be rendered again later.
+    source: Arc<[u8]>,
+
+    /// The parsed document.
+    tree: usvg::Tree,
+}
+
+impl SvgRenderer {
+    /// Parses an SVG document.
+    ///
+    /// # Arguments
+    /// * `data` - The bytes of the SVG document.
+    ///
+    /// # Errors
+    /// Returns `SvgError::Parse` if the document is not a valid SVG image.
+    pub fn parse(data: &[u8]) -> Result<Self, SvgError> {
+        let tree =
+            usvg::Tree::from_data(data, &usvg::Options::default()).map_err(|err| SvgError::Parse(err.to_string()))?;
+
+        Ok(Self {
+            source: Arc::from(data),
+            tree,
+        })
+    }
+
+    /// The intrinsic size of the image in CSS pixels, taken from the `width` and `height` of the root element or
+    /// its `viewBox`.
+    #[must_use]
+    pub fn size(&self) -> (u32, u32) {
+        let size = self.tree.size().to_int_size();
+        (size.width(), size.height())
+    }
+
+    /// Rasterizes the image at its intrinsic size.
+    ///
+    /// # Errors
+    /// Returns `SvgError::InvalidSize` if the intrinsic size is larger than `MAX_SVG_SIZE`.
+    pub fn render_intrinsic(&self) -> Result<LayoutImage, SvgError> {
+        let (width, height) = self.size();
+        self.render(width, height)
+    }
+
+    /// Rasterizes the image scaled to the given size.
+    ///
+    /// # Arguments
+    /// * `width` - The width of the raster in pixels.
+    /// * `height` - The height of the raster in pixels.
+    ///
+    /// # Returns
+    /// An image with straight-alpha RGBA pixels, which keeps the SVG source so it can be rendered again at
+    /// another size.
+    ///
+    /// # Errors
+    /// Returns `SvgError::InvalidSize` if either dimension is zero or larger than `MAX_SVG_SIZE`.
+    pub fn render(&self, width: u32, height: u32) -> Result<LayoutImage, SvgError> {
+        if width > MAX_SVG_SIZE || height > MAX_SVG_SIZE {
+            return Err(SvgError::InvalidSize { width, height });
+        }
+
+        let mut pixmap = tiny_skia::Pixmap::new(width, height).ok_or(SvgError::InvalidSize { width, height })?;
+
+        let size = self.tree.size();
+        let transform = tiny_skia::Transform::from_scale(width as f32 / size.width(), height as f32 / size.height());
+        resvg::render(&self.tree, transform, &mut pixmap.as_mut());
+
+        // tiny-skia stores premultiplied alpha, while image textures are uploaded with straight alpha.
+        let rgba = pixmap
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let color = pixel.demultiply();
+                [color.red(), color.green(), color.blue(), color.alpha()]
+            })
+            .collect();
+
+        Ok(LayoutImage {
+            width,
+            height,
+            rgba,
+            svg_source: Some(Arc::clone(&self.source)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED_CIRCLE: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20" viewBox="0 0 20 20">
+        <circle cx="10" cy="10" r="8" fill="red"/>
+    </svg>"#;
+
+    fn pixel(image: &LayoutImage, x: u32, y: u32) -> [u8; 4] {
+        let offset = ((y * image.width + x) * 4) as usize;
+        image.rgba[offset..offset + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn test_render_red_circle() {
+        let svg = SvgRenderer::parse(RED_CIRCLE.as_bytes()).unwrap();
+        assert_eq!(svg.size(), (20, 20));
+
+        let image = svg.render_intrinsic().unwrap();
+        assert_eq!((image.width, image.height), (20, 20));
+        assert_eq!(image.rgba.len(), 20 * 20 * 4);
+        assert_eq!(pixel(&image, 10, 10), [255, 0, 0, 255]);
+        assert_eq!(pixel(&image, 0, 0)[3], 0);
+        assert_eq!(pixel(&image, 19, 19)[3], 0);
+    }
+
+    #[test]
+    fn test_render_at_target_size() {
+        let svg = SvgRenderer::parse(RED_CIRCLE.as_bytes()).unwrap();
+        let image = svg.render(100, 50).unwrap();
+
+        assert_eq!((image.width, image.height), (100, 50));
+        assert_eq!(pixel(&image, 50, 25), [255, 0, 0, 255]);
+        assert_eq!(pixel(&image, 0, 0)[3], 0);
+        assert!(image.svg_source.is_some());
+    }
+
+    #[test]
+    fn test_invalid_svg() {
+        assert!(matches!(SvgRenderer::parse(b"not an svg"), Err(SvgError::Parse(_))));
+
+        let svg = SvgRenderer::parse(RED_CIRCLE.as_bytes()).unwrap();
+        assert_eq!(
+            svg.render(0, 10).unwrap_err(),
+            SvgError::InvalidSize {
+                width: 0,
+                height: 10
+            }
+        );
+    }
+}
@@ -21,6 +21,7 @@ fuzz_target!(|data: &[u8]| {
                     BlockedReason::SVGContent { .. } => {}
                     BlockedReason::MathML { .. } => {}
                     BlockedReason::WaitingForResource(_, _, _) => {}
+                    BlockedReason::ResourceHint { .. } => {}
                 },
                 ParserState::Completed(_) => {
                     break;